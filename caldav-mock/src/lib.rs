@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mock `CalDAV` server for the integration tests of AIM.
+//!
+//! The server runs on a local port with [`wiremock`], itself built on hyper, and serves a
+//! [`Scenario`]: the calendars and objects of a user, the credentials it requires and the ways it
+//! misbehaves, such as flaky 503s, redirects, truncated listings or a missing REPORT. Scenarios
+//! are plain TOML, so the `caldav` client tests and the sync tests of `core` can share the ones in
+//! the `scenarios` directory of this crate.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use aimcal_caldav_mock::{MockCalDav, Scenario};
+//!
+//! let server = MockCalDav::start(Scenario::bundled("basic")?).await;
+//! let base_url = server.uri();
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(
+    trivial_casts,
+    trivial_numeric_casts,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+    clippy::dbg_macro,
+    clippy::indexing_slicing,
+    clippy::pedantic
+)]
+
+mod scenario;
+mod server;
+mod xml;
+
+pub use crate::scenario::{
+    AuthSpec, CalendarSpec, FaultSpec, RedirectSpec, ResourceSpec, Scenario, ScenarioError,
+    ServerSpec,
+};
+pub use crate::server::{MockCalDav, MockResource};
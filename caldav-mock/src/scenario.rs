@@ -0,0 +1,294 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative description of the server, its calendars and its misbehaviors.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+
+/// What the mock server serves, usually read from a TOML file.
+///
+/// ```toml
+/// [server]
+/// calendar_home = "/dav/calendars/alice/"
+///
+/// [auth]
+/// username = "alice"
+/// password = "secret"
+///
+/// [[calendar]]
+/// href = "/dav/calendars/alice/work/"
+/// display_name = "Work"
+///
+/// [[calendar.resource]]
+/// name = "standup.ics"
+/// data = "BEGIN:VCALENDAR\r\n...END:VCALENDAR\r\n"
+///
+/// [[fault]]
+/// method = "REPORT"
+/// status = 503
+/// times = 1
+/// ```
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Discovery and capabilities of the server.
+    #[serde(default)]
+    pub server: ServerSpec,
+    /// Credentials the server requires, if any.
+    #[serde(default)]
+    pub auth: Option<AuthSpec>,
+    /// Calendar collections under the calendar home.
+    #[serde(default, rename = "calendar")]
+    pub calendars: Vec<CalendarSpec>,
+    /// Paths the server redirects elsewhere.
+    #[serde(default, rename = "redirect")]
+    pub redirects: Vec<RedirectSpec>,
+    /// Canned responses sent instead of the regular ones, checked in order.
+    #[serde(default, rename = "fault")]
+    pub faults: Vec<FaultSpec>,
+}
+
+impl Scenario {
+    /// Parses a scenario from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid or does not describe a scenario.
+    pub fn from_toml(text: &str) -> Result<Self, ScenarioError> {
+        toml::from_str(text).map_err(|e| ScenarioError::Parse(e.to_string()))
+    }
+
+    /// Reads a scenario from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not describe a scenario.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScenarioError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ScenarioError::Io(path.into(), e))?;
+        Self::from_toml(&text)
+    }
+
+    /// Reads one of the scenarios shipped in the `scenarios` directory of this crate, such as
+    /// `basic` or `no-report`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no such scenario or it is invalid.
+    pub fn bundled(name: &str) -> Result<Self, ScenarioError> {
+        Self::load(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("scenarios")
+                .join(format!("{name}.toml")),
+        )
+    }
+
+    /// Looks up a calendar by its href.
+    #[must_use]
+    pub fn calendar(&self, href: &str) -> Option<&CalendarSpec> {
+        self.calendars.iter().find(|c| c.href == href)
+    }
+}
+
+/// Discovery and capabilities of the server.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSpec {
+    /// `DAV` header of OPTIONS responses.
+    pub dav: String,
+    /// `Allow` header of OPTIONS responses, leave REPORT out to simulate a server lacking it.
+    pub allow: String,
+    /// Principal of the authenticated user.
+    pub principal: String,
+    /// Calendar home set of the principal.
+    pub calendar_home: String,
+}
+
+impl Default for ServerSpec {
+    fn default() -> Self {
+        Self {
+            dav: "1, 2, 3, calendar-access".to_string(),
+            allow: "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT, MKCALENDAR".to_string(),
+            principal: "/dav/principals/user/".to_string(),
+            calendar_home: "/dav/calendars/user/".to_string(),
+        }
+    }
+}
+
+/// Credentials the server requires, challenging requests without them with 401.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged)]
+pub enum AuthSpec {
+    /// HTTP basic authentication.
+    Basic {
+        /// Expected username.
+        username: String,
+        /// Expected password.
+        password: String,
+    },
+    /// Bearer token authentication.
+    Bearer {
+        /// Expected token.
+        token: String,
+    },
+}
+
+/// A calendar collection and the calendar objects it holds.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CalendarSpec {
+    /// Href of the collection, ending with a slash.
+    pub href: String,
+    /// Display name of the collection.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Components the collection accepts.
+    #[serde(default = "default_components")]
+    pub components: Vec<String>,
+    /// Owner principal, for calendars shared by another user.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Privileges of the current user, such as `read`, `write`, `bind` and `unbind`.
+    #[serde(default = "default_privileges")]
+    pub privileges: Vec<String>,
+    /// Number of members listed before a Depth 1 PROPFIND or a sync-collection REPORT is
+    /// truncated with a 507 status, as servers do on large collections.
+    #[serde(default)]
+    pub truncate_after: Option<usize>,
+    /// Calendar objects in the collection.
+    #[serde(default, rename = "resource")]
+    pub resources: Vec<ResourceSpec>,
+}
+
+fn default_components() -> Vec<String> {
+    vec!["VEVENT".to_string(), "VTODO".to_string()]
+}
+
+fn default_privileges() -> Vec<String> {
+    vec!["all".to_string()]
+}
+
+/// A calendar object.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceSpec {
+    /// Name of the object in its collection, such as `standup.ics`.
+    pub name: String,
+    /// `ETag` of the object with its quotes, generated if left out.
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// The iCalendar data.
+    pub data: String,
+}
+
+/// Redirects every path under `from` to the same path under `to`.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedirectSpec {
+    /// Path prefix to redirect.
+    pub from: String,
+    /// Path prefix to redirect to.
+    pub to: String,
+    /// Redirect status, 308 by default to keep the method and body.
+    #[serde(default = "default_redirect_status")]
+    pub status: u16,
+}
+
+const fn default_redirect_status() -> u16 {
+    308
+}
+
+/// A canned response sent instead of the regular one, such as a flaky 503.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FaultSpec {
+    /// Method to match, any method if left out.
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Path to match, any path if left out.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Status of the response.
+    pub status: u16,
+    /// Headers of the response.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Body of the response.
+    #[serde(default)]
+    pub body: String,
+    /// How many matching requests fail, every one if left out.
+    #[serde(default)]
+    pub times: Option<usize>,
+}
+
+/// Errors reading a scenario.
+#[derive(Debug)]
+pub enum ScenarioError {
+    /// The scenario file cannot be read.
+    Io(PathBuf, io::Error),
+    /// The TOML is invalid or does not describe a scenario.
+    Parse(String),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "Failed to read scenario {}: {e}", path.display()),
+            Self::Parse(e) => write!(f, "Invalid scenario: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenario_parses_with_defaults() {
+        let scenario = Scenario::from_toml(
+            r#"
+[[calendar]]
+href = "/dav/calendars/user/work/"
+
+[[calendar.resource]]
+name = "a.ics"
+data = "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n"
+"#,
+        )
+        .unwrap();
+        assert_eq!(scenario.server.calendar_home, "/dav/calendars/user/");
+        assert!(scenario.auth.is_none());
+        let calendar = scenario.calendar("/dav/calendars/user/work/").unwrap();
+        assert_eq!(calendar.components, ["VEVENT", "VTODO"]);
+        assert_eq!(calendar.privileges, ["all"]);
+        assert_eq!(calendar.resources.len(), 1);
+    }
+
+    #[test]
+    fn scenario_parses_auth_kinds() {
+        let scenario = Scenario::from_toml("[auth]\ntoken = \"abc\"").unwrap();
+        assert!(matches!(scenario.auth, Some(AuthSpec::Bearer { .. })));
+        let scenario =
+            Scenario::from_toml("[auth]\nusername = \"alice\"\npassword = \"secret\"").unwrap();
+        assert!(matches!(scenario.auth, Some(AuthSpec::Basic { .. })));
+    }
+
+    #[test]
+    fn scenario_rejects_unknown_fields() {
+        assert!(Scenario::from_toml("[server]\nport = 8080").is_err());
+    }
+
+    #[test]
+    fn scenario_loads_bundled() {
+        for name in ["basic", "no-report", "flaky", "truncated"] {
+            let scenario = Scenario::bundled(name).unwrap_or_else(|e| panic!("{name}: {e}"));
+            assert!(!scenario.calendars.is_empty(), "{name}");
+        }
+        assert!(Scenario::bundled("missing").is_err());
+    }
+}
@@ -0,0 +1,766 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! The mock server, answering requests from the state a scenario describes.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use tokio::io::copy_bidirectional;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+use crate::scenario::{AuthSpec, FaultSpec, RedirectSpec, Scenario, ServerSpec};
+use crate::xml;
+
+const XML_MIME: &str = "application/xml; charset=utf-8";
+const SYNC_TOKEN_PREFIX: &str = "urn:aimcal-mock:sync:";
+
+/// A mock `CalDAV` server on a local port, serving a [`Scenario`].
+///
+/// Calendar objects live in memory: PUT and DELETE change them with the `ETag` preconditions of
+/// RFC 4918, and every change advances the sync-token of its calendar. PROPFIND reports every
+/// property the server knows whatever the request asked for, which the `CalDAV` client ignores
+/// where it does not need them.
+///
+/// Clients connect through a forwarder counting their connections, see
+/// [`MockCalDav::connections`].
+#[derive(Debug)]
+pub struct MockCalDav {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+    address: SocketAddr,
+    connections: Arc<AtomicUsize>,
+    forwarder: JoinHandle<()>,
+}
+
+impl MockCalDav {
+    /// Starts a server serving `scenario`.
+    ///
+    /// Each server listens on its own port, so that the capabilities the `CalDAV` client caches
+    /// per server do not leak between tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port is free to listen on.
+    pub async fn start(scenario: Scenario) -> Self {
+        let server = MockServer::builder().start().await;
+        let state = Arc::new(Mutex::new(State::new(scenario)));
+        Mock::given(any())
+            .respond_with(Handler(state.clone()))
+            .mount(&server)
+            .await;
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to listen on a local port");
+        let address = listener
+            .local_addr()
+            .expect("failed to get the local address");
+        let connections = Arc::new(AtomicUsize::new(0));
+        let forwarder = tokio::spawn(forward(listener, *server.address(), connections.clone()));
+        Self {
+            server,
+            state,
+            address,
+            connections,
+            forwarder,
+        }
+    }
+
+    /// Base URL of the server, such as `http://127.0.0.1:41234`.
+    #[must_use]
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Number of connections clients opened to the server so far, to check that they reuse them.
+    #[must_use]
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// Calendar home set of the principal.
+    #[must_use]
+    pub fn calendar_home(&self) -> String {
+        self.state().server.calendar_home.clone()
+    }
+
+    /// Gets a calendar object as the server holds it.
+    #[must_use]
+    pub fn resource(&self, href: &str) -> Option<MockResource> {
+        let state = self.state();
+        let (_, calendar) = state.calendar_of(href)?;
+        calendar.resources.get(href).map(|r| MockResource {
+            etag: r.etag.clone(),
+            data: r.data.clone(),
+        })
+    }
+
+    /// Hrefs of the calendar objects in a calendar, in order.
+    #[must_use]
+    pub fn hrefs(&self, calendar_href: &str) -> Vec<String> {
+        self.state()
+            .calendar_at(calendar_href)
+            .map(|(_, c)| c.resources.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Stores a calendar object as another client would, returning its new `ETag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no calendar of the scenario holds `href`.
+    #[must_use]
+    pub fn put_resource(&self, href: &str, data: &str) -> String {
+        let mut state = self.state();
+        let etag = state.next_etag();
+        let calendar_href = state.calendar_of(href).map_or_else(
+            || panic!("no calendar holds {href}"),
+            |(calendar_href, _)| calendar_href.to_string(),
+        );
+        if let Some(calendar) = state.calendars.get_mut(&calendar_href) {
+            calendar.store(href, etag.clone(), data.to_string());
+        }
+        etag
+    }
+
+    /// Deletes a calendar object as another client would, returning whether it existed.
+    #[must_use]
+    pub fn delete_resource(&self, href: &str) -> bool {
+        let mut state = self.state();
+        let Some((calendar_href, _)) = state.calendar_of(href) else {
+            return false;
+        };
+        let calendar_href = calendar_href.to_string();
+        state
+            .calendars
+            .get_mut(&calendar_href)
+            .is_some_and(|calendar| calendar.remove(href))
+    }
+
+    /// Current sync-token of a calendar.
+    #[must_use]
+    pub fn sync_token(&self, calendar_href: &str) -> Option<String> {
+        self.state()
+            .calendar_at(calendar_href)
+            .map(|(_, c)| c.sync_token())
+    }
+
+    /// Methods and paths of the requests received so far, in order.
+    pub async fn requests(&self) -> Vec<(String, String)> {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| (r.method.to_string(), r.url.path().to_string()))
+            .collect()
+    }
+
+    /// Number of requests received so far with `method` on `path`.
+    pub async fn count_requests(&self, method: &str, path: &str) -> usize {
+        self.requests()
+            .await
+            .iter()
+            .filter(|(m, p)| m.eq_ignore_ascii_case(method) && p == path)
+            .count()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockCalDav {
+    fn drop(&mut self) {
+        self.forwarder.abort();
+    }
+}
+
+/// Forwards every connection accepted by `listener` to the server at `target`, counting them,
+/// as the requests wiremock records do not tell which connection they came on.
+async fn forward(listener: TcpListener, target: SocketAddr, connections: Arc<AtomicUsize>) {
+    while let Ok((mut client, _)) = listener.accept().await {
+        connections.fetch_add(1, Ordering::SeqCst);
+        tokio::spawn(async move {
+            if let Ok(mut server) = TcpStream::connect(target).await {
+                // Either side closes the connection when it is done with it
+                _ = copy_bidirectional(&mut client, &mut server).await;
+            }
+        });
+    }
+}
+
+/// A calendar object held by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResource {
+    /// `ETag` of the object with its quotes.
+    pub etag: String,
+    /// The iCalendar data.
+    pub data: String,
+}
+
+struct Handler(Arc<Mutex<State>>);
+
+impl Respond for Handler {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .handle(request)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    server: ServerSpec,
+    auth: Option<AuthSpec>,
+    redirects: Vec<RedirectSpec>,
+    /// Faults along with how many requests they failed.
+    faults: Vec<(FaultSpec, usize)>,
+    calendars: BTreeMap<String, Calendar>,
+    etag_counter: u64,
+}
+
+#[derive(Debug, Default)]
+struct Calendar {
+    display_name: Option<String>,
+    components: Vec<String>,
+    owner: Option<String>,
+    privileges: Vec<String>,
+    truncate_after: Option<usize>,
+    resources: BTreeMap<String, Resource>,
+    /// Hrefs of deleted objects along with the sync revision that deleted them.
+    tombstones: BTreeMap<String, u64>,
+    revision: u64,
+}
+
+#[derive(Debug)]
+struct Resource {
+    etag: String,
+    data: String,
+    /// Sync revision that last changed the object.
+    revision: u64,
+}
+
+impl State {
+    fn new(scenario: Scenario) -> Self {
+        let mut state = Self {
+            server: scenario.server,
+            auth: scenario.auth,
+            redirects: scenario.redirects,
+            faults: scenario.faults.into_iter().map(|f| (f, 0)).collect(),
+            calendars: BTreeMap::new(),
+            etag_counter: 0,
+        };
+        for spec in scenario.calendars {
+            let mut calendar = Calendar {
+                display_name: spec.display_name,
+                components: spec.components,
+                owner: spec.owner,
+                privileges: spec.privileges,
+                truncate_after: spec.truncate_after,
+                ..Calendar::default()
+            };
+            let prefix = collection_path(&spec.href);
+            for resource in spec.resources {
+                let etag = resource.etag.unwrap_or_else(|| state.next_etag());
+                calendar.store(&format!("{prefix}{}", resource.name), etag, resource.data);
+            }
+            state.calendars.insert(spec.href, calendar);
+        }
+        state
+    }
+
+    fn next_etag(&mut self) -> String {
+        self.etag_counter += 1;
+        format!("\"mock-{}\"", self.etag_counter)
+    }
+
+    /// The calendar whose collection directly holds `href`.
+    fn calendar_of(&self, href: &str) -> Option<(&str, &Calendar)> {
+        let (parent, name) = href.rsplit_once('/')?;
+        if name.is_empty() {
+            return None;
+        }
+        self.calendars
+            .iter()
+            .find(|(calendar_href, _)| calendar_href.trim_end_matches('/') == parent)
+            .map(|(calendar_href, calendar)| (calendar_href.as_str(), calendar))
+    }
+
+    fn calendar_at(&self, path: &str) -> Option<(&str, &Calendar)> {
+        self.calendars
+            .iter()
+            .find(|(href, _)| same_collection(href, path))
+            .map(|(href, calendar)| (href.as_str(), calendar))
+    }
+
+    fn handle(&mut self, request: &Request) -> ResponseTemplate {
+        let method = request.method.as_str().to_ascii_uppercase();
+        let path = request.url.path().to_string();
+        if let Some(response) = self.fault(&method, &path) {
+            return response;
+        }
+        if let Some(response) = self.redirect(&path) {
+            return response;
+        }
+        if let Some(response) = self.challenge(request) {
+            return response;
+        }
+
+        let body = String::from_utf8_lossy(&request.body);
+        let header = |name: &str| {
+            request
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        match method.as_str() {
+            "OPTIONS" => ResponseTemplate::new(200)
+                .insert_header("DAV", self.server.dav.as_str())
+                .insert_header("Allow", self.server.allow.as_str()),
+            "PROPFIND" => self.propfind(&path, header("Depth").as_deref().unwrap_or("0")),
+            "REPORT" if !self.allows("REPORT") => ResponseTemplate::new(405),
+            "REPORT" => self.report(&path, &body),
+            "GET" | "HEAD" => self.get(&path),
+            "PUT" => self.put(
+                &path,
+                header("If-Match").as_deref(),
+                header("If-None-Match").as_deref(),
+                &body,
+            ),
+            "DELETE" => self.delete(&path, header("If-Match").as_deref()),
+            "MKCALENDAR" => self.mkcalendar(&path),
+            _ => ResponseTemplate::new(405),
+        }
+    }
+
+    fn allows(&self, method: &str) -> bool {
+        self.server
+            .allow
+            .split(',')
+            .any(|m| m.trim().eq_ignore_ascii_case(method))
+    }
+
+    fn fault(&mut self, method: &str, path: &str) -> Option<ResponseTemplate> {
+        let (fault, hits) = self.faults.iter_mut().find(|(fault, hits)| {
+            fault
+                .method
+                .as_ref()
+                .is_none_or(|m| m.eq_ignore_ascii_case(method))
+                && fault.path.as_ref().is_none_or(|p| same_collection(p, path))
+                && fault.times.is_none_or(|times| *hits < times)
+        })?;
+        *hits += 1;
+        let mut response = ResponseTemplate::new(fault.status);
+        for (name, value) in &fault.headers {
+            response = response.insert_header(name.as_str(), value.as_str());
+        }
+        if !fault.body.is_empty() {
+            response = response.set_body_raw(fault.body.clone(), XML_MIME);
+        }
+        Some(response)
+    }
+
+    fn redirect(&self, path: &str) -> Option<ResponseTemplate> {
+        self.redirects.iter().find_map(|redirect| {
+            let rest = path.strip_prefix(&redirect.from)?;
+            let location = format!("{}{}", redirect.to, rest.trim_start_matches('/'));
+            Some(ResponseTemplate::new(redirect.status).insert_header("Location", location))
+        })
+    }
+
+    /// Challenges requests without the expected credentials.
+    fn challenge(&self, request: &Request) -> Option<ResponseTemplate> {
+        let auth = self.auth.as_ref()?;
+        let (expected, challenge) = match auth {
+            AuthSpec::Basic { username, password } => (
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{username}:{password}"))
+                ),
+                "Basic realm=\"aimcal-mock\"",
+            ),
+            AuthSpec::Bearer { token } => {
+                (format!("Bearer {token}"), "Bearer realm=\"aimcal-mock\"")
+            }
+        };
+        let given = request
+            .headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok());
+        if given == Some(expected.as_str()) {
+            return None;
+        }
+        Some(ResponseTemplate::new(401).insert_header("WWW-Authenticate", challenge))
+    }
+
+    fn propfind(&self, path: &str, depth: &str) -> ResponseTemplate {
+        let mut responses = Vec::new();
+        let home = &self.server.calendar_home;
+        if same_collection(path, home) || same_collection(path, &self.server.principal) {
+            responses.push(xml::prop_response(path, &self.principal_props()));
+            if depth != "0" && same_collection(path, home) {
+                for (href, calendar) in &self.calendars {
+                    responses.push(xml::prop_response(href, &calendar.props()));
+                }
+            }
+        } else if let Some((href, calendar)) = self.calendar_at(path) {
+            responses.push(xml::prop_response(href, &calendar.props()));
+            if depth != "0" {
+                let members: Vec<_> = calendar.resources.iter().collect();
+                let (listed, truncated) = calendar.truncate(&members);
+                for (member, resource) in listed {
+                    responses.push(xml::prop_response(member, &resource.props(false)));
+                }
+                if truncated {
+                    responses.push(xml::status_response(href, "507 Insufficient Storage"));
+                }
+            }
+        } else if let Some((_, calendar)) = self.calendar_of(path)
+            && let Some(resource) = calendar.resources.get(path)
+        {
+            responses.push(xml::prop_response(path, &resource.props(false)));
+        } else {
+            return ResponseTemplate::new(404);
+        }
+        multistatus(&responses, None)
+    }
+
+    fn principal_props(&self) -> String {
+        format!(
+            "<D:resourcetype><D:collection/></D:resourcetype>\
+<D:current-user-principal><D:href>{}</D:href></D:current-user-principal>\
+<C:calendar-home-set><D:href>{}</D:href></C:calendar-home-set>",
+            xml::escape(&self.server.principal),
+            xml::escape(&self.server.calendar_home)
+        )
+    }
+
+    fn report(&self, path: &str, body: &str) -> ResponseTemplate {
+        if body.contains("calendar-multiget") {
+            return self.multiget(body);
+        }
+        let Some((href, calendar)) = self.calendar_at(path) else {
+            return ResponseTemplate::new(404);
+        };
+        if body.contains("sync-collection") {
+            calendar.sync_collection(href, body)
+        } else if body.contains("calendar-query") {
+            calendar.query(body)
+        } else {
+            ResponseTemplate::new(403).set_body_raw(xml::error("supported-report"), XML_MIME)
+        }
+    }
+
+    fn multiget(&self, body: &str) -> ResponseTemplate {
+        let responses: Vec<_> = xml::element_texts(body, "href")
+            .into_iter()
+            .map(|href| {
+                self.calendar_of(href)
+                    .and_then(|(_, calendar)| calendar.resources.get(href))
+                    .map_or_else(
+                        || xml::status_response(href, "404 Not Found"),
+                        |resource| xml::prop_response(href, &resource.props(true)),
+                    )
+            })
+            .collect();
+        multistatus(&responses, None)
+    }
+
+    fn get(&self, path: &str) -> ResponseTemplate {
+        match self
+            .calendar_of(path)
+            .and_then(|(_, calendar)| calendar.resources.get(path))
+        {
+            Some(resource) => ResponseTemplate::new(200)
+                .insert_header("ETag", resource.etag.as_str())
+                .set_body_raw(resource.data.clone(), "text/calendar; charset=utf-8"),
+            None => ResponseTemplate::new(404),
+        }
+    }
+
+    fn put(
+        &mut self,
+        path: &str,
+        if_match: Option<&str>,
+        if_none_match: Option<&str>,
+        body: &str,
+    ) -> ResponseTemplate {
+        let Some((calendar_href, calendar)) = self.calendar_of(path) else {
+            return ResponseTemplate::new(409);
+        };
+        let existing = calendar.resources.get(path);
+        let privilege = if existing.is_some() {
+            "write-content"
+        } else {
+            "bind"
+        };
+        if !calendar.allows(privilege) {
+            return ResponseTemplate::new(403)
+                .set_body_raw(xml::error("need-privileges"), XML_MIME);
+        }
+        if let Some(response) = precondition(existing, if_match, if_none_match) {
+            return response;
+        }
+
+        let created = existing.is_none();
+        let calendar_href = calendar_href.to_string();
+        let etag = self.next_etag();
+        if let Some(calendar) = self.calendars.get_mut(&calendar_href) {
+            calendar.store(path, etag.clone(), body.to_string());
+        }
+        ResponseTemplate::new(if created { 201 } else { 204 }).insert_header("ETag", etag.as_str())
+    }
+
+    fn delete(&mut self, path: &str, if_match: Option<&str>) -> ResponseTemplate {
+        let Some((calendar_href, calendar)) = self.calendar_of(path) else {
+            return ResponseTemplate::new(404);
+        };
+        let Some(existing) = calendar.resources.get(path) else {
+            return ResponseTemplate::new(404);
+        };
+        if !calendar.allows("unbind") {
+            return ResponseTemplate::new(403)
+                .set_body_raw(xml::error("need-privileges"), XML_MIME);
+        }
+        if let Some(response) = precondition(Some(existing), if_match, None) {
+            return response;
+        }
+
+        let calendar_href = calendar_href.to_string();
+        if let Some(calendar) = self.calendars.get_mut(&calendar_href) {
+            calendar.remove(path);
+        }
+        ResponseTemplate::new(204)
+    }
+
+    fn mkcalendar(&mut self, path: &str) -> ResponseTemplate {
+        if self.calendar_at(path).is_some() {
+            return ResponseTemplate::new(405);
+        }
+        let calendar = Calendar {
+            components: vec!["VEVENT".to_string(), "VTODO".to_string()],
+            privileges: vec!["all".to_string()],
+            ..Calendar::default()
+        };
+        self.calendars.insert(collection_path(path), calendar);
+        ResponseTemplate::new(201)
+    }
+}
+
+impl Calendar {
+    fn sync_token(&self) -> String {
+        format!("{SYNC_TOKEN_PREFIX}{}", self.revision)
+    }
+
+    fn store(&mut self, href: &str, etag: String, data: String) {
+        self.revision += 1;
+        self.tombstones.remove(href);
+        let resource = Resource {
+            etag,
+            data,
+            revision: self.revision,
+        };
+        self.resources.insert(href.to_string(), resource);
+    }
+
+    fn remove(&mut self, href: &str) -> bool {
+        if self.resources.remove(href).is_none() {
+            return false;
+        }
+        self.revision += 1;
+        self.tombstones.insert(href.to_string(), self.revision);
+        true
+    }
+
+    fn allows(&self, privilege: &str) -> bool {
+        self.privileges.iter().any(|p| {
+            p == "all"
+                || p == privilege
+                || (p == "write" && matches!(privilege, "write-content" | "bind" | "unbind"))
+        })
+    }
+
+    /// Splits off the members listed before the listing is truncated, if it is.
+    fn truncate<'a, T>(&self, members: &'a [T]) -> (&'a [T], bool) {
+        match self.truncate_after {
+            Some(limit) if members.len() > limit => (members.get(..limit).unwrap_or(members), true),
+            _ => (members, false),
+        }
+    }
+
+    fn props(&self) -> String {
+        let mut props =
+            String::from("<D:resourcetype><D:collection/><C:calendar/></D:resourcetype>");
+        if let Some(name) = &self.display_name {
+            let _ = write!(
+                props,
+                "<D:displayname>{}</D:displayname>",
+                xml::escape(name)
+            );
+        }
+        props.push_str("<C:supported-calendar-component-set>");
+        for component in &self.components {
+            let _ = write!(props, "<C:comp name=\"{}\"/>", xml::escape(component));
+        }
+        props.push_str("</C:supported-calendar-component-set>");
+        if let Some(owner) = &self.owner {
+            let _ = write!(
+                props,
+                "<D:owner><D:href>{}</D:href></D:owner>",
+                xml::escape(owner)
+            );
+        }
+        props.push_str("<D:current-user-privilege-set>");
+        for privilege in &self.privileges {
+            let _ = write!(props, "<D:privilege><D:{privilege}/></D:privilege>");
+        }
+        props.push_str("</D:current-user-privilege-set>");
+        let _ = write!(props, "<D:sync-token>{}</D:sync-token>", self.sync_token());
+        props
+    }
+
+    /// Answers a calendar-query with the objects holding the components it filters on.
+    fn query(&self, body: &str) -> ResponseTemplate {
+        let components: Vec<_> = xml::comp_filter_names(body)
+            .into_iter()
+            .filter(|name| name != "VCALENDAR")
+            .collect();
+        let responses: Vec<_> = self
+            .resources
+            .iter()
+            .filter(|(_, resource)| {
+                components.is_empty()
+                    || components
+                        .iter()
+                        .any(|c| resource.data.contains(&format!("BEGIN:{c}")))
+            })
+            .map(|(href, resource)| xml::prop_response(href, &resource.props(true)))
+            .collect();
+        multistatus(&responses, None)
+    }
+
+    /// Answers a sync-collection REPORT (RFC 6578) with the members changed since its token, or
+    /// every member without one.
+    fn sync_collection(&self, href: &str, body: &str) -> ResponseTemplate {
+        let token = xml::element_texts(body, "sync-token")
+            .first()
+            .copied()
+            .unwrap_or_default();
+        let since = if token.is_empty() {
+            0
+        } else {
+            match token
+                .strip_prefix(SYNC_TOKEN_PREFIX)
+                .and_then(|r| r.parse::<u64>().ok())
+                .filter(|&r| r <= self.revision)
+            {
+                Some(revision) => revision,
+                None => {
+                    return ResponseTemplate::new(403)
+                        .set_body_raw(xml::error("valid-sync-token"), XML_MIME);
+                }
+            }
+        };
+
+        // Changes in the order they happened, so a truncated listing resumes where it stopped
+        let mut changes: Vec<(u64, &str, Option<&Resource>)> = self
+            .resources
+            .iter()
+            .filter(|(_, r)| r.revision > since)
+            .map(|(href, r)| (r.revision, href.as_str(), Some(r)))
+            .collect();
+        if since > 0 {
+            changes.extend(
+                self.tombstones
+                    .iter()
+                    .filter(|&(_, &revision)| revision > since)
+                    .map(|(href, &revision)| (revision, href.as_str(), None)),
+            );
+        }
+        changes.sort_by_key(|&(revision, _, _)| revision);
+
+        let (listed, truncated) = self.truncate(&changes);
+        let mut responses: Vec<_> = listed
+            .iter()
+            .map(|&(_, member, resource)| match resource {
+                Some(resource) => xml::prop_response(member, &resource.props(false)),
+                None => xml::status_response(member, "404 Not Found"),
+            })
+            .collect();
+        let revision = if truncated {
+            responses.push(xml::status_response(href, "507 Insufficient Storage"));
+            listed.last().map_or(since, |&(revision, _, _)| revision)
+        } else {
+            self.revision
+        };
+        multistatus(&responses, Some(&format!("{SYNC_TOKEN_PREFIX}{revision}")))
+    }
+}
+
+impl Resource {
+    fn props(&self, with_data: bool) -> String {
+        let mut props = format!(
+            "<D:resourcetype/><D:getetag>{}</D:getetag>",
+            xml::escape(&self.etag)
+        );
+        if with_data {
+            let _ = write!(
+                props,
+                "<C:calendar-data>{}</C:calendar-data>",
+                xml::escape(&self.data)
+            );
+        }
+        props
+    }
+}
+
+/// Checks the `If-Match` and `If-None-Match` preconditions of a write, answering 412 with the
+/// current `ETag` if they fail.
+fn precondition(
+    existing: Option<&Resource>,
+    if_match: Option<&str>,
+    if_none_match: Option<&str>,
+) -> Option<ResponseTemplate> {
+    let etag = existing.map(|r| r.etag.as_str());
+    let failed = match (if_match, if_none_match) {
+        (Some("*"), _) => etag.is_none(),
+        (Some(expected), _) => etag != Some(expected),
+        (None, Some("*")) => etag.is_some(),
+        (None, Some(unexpected)) => etag == Some(unexpected),
+        (None, None) => false,
+    };
+    if !failed {
+        return None;
+    }
+    let mut response = ResponseTemplate::new(412);
+    if let Some(etag) = etag {
+        response = response.insert_header("ETag", etag);
+    }
+    Some(response)
+}
+
+fn multistatus(responses: &[String], sync_token: Option<&str>) -> ResponseTemplate {
+    ResponseTemplate::new(207).set_body_raw(xml::multistatus(responses, sync_token), XML_MIME)
+}
+
+/// Whether two paths name the same collection, with or without the trailing slash.
+fn same_collection(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+fn collection_path(href: &str) -> String {
+    format!("{}/", href.trim_end_matches('/'))
+}
@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rendering of the `WebDAV` multistatus bodies the server responds with.
+
+use std::fmt::Write;
+
+/// Wraps `responses` in a `<D:multistatus>`, with a sync-token for sync-collection REPORTs.
+pub fn multistatus(responses: &[String], sync_token: Option<&str>) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n\
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\">\n",
+    );
+    for response in responses {
+        xml.push_str(response);
+    }
+    if let Some(token) = sync_token {
+        let _ = writeln!(xml, "  <D:sync-token>{}</D:sync-token>", escape(token));
+    }
+    xml.push_str("</D:multistatus>\n");
+    xml
+}
+
+/// A `<D:response>` holding `props`, which must already be XML.
+pub fn prop_response(href: &str, props: &str) -> String {
+    format!(
+        "  <D:response>
+    <D:href>{}</D:href>
+    <D:propstat>
+      <D:prop>{props}</D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>\n",
+        escape(href)
+    )
+}
+
+/// A `<D:response>` carrying only a status, such as 404 for members gone since a sync-token or
+/// 507 for a truncated listing.
+pub fn status_response(href: &str, status: &str) -> String {
+    format!(
+        "  <D:response>
+    <D:href>{}</D:href>
+    <D:status>HTTP/1.1 {status}</D:status>
+  </D:response>\n",
+        escape(href)
+    )
+}
+
+/// A `<D:error>` body with a precondition element, such as `valid-sync-token`.
+pub fn error(precondition: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n\
+<D:error xmlns:D=\"DAV:\"><D:{precondition}/></D:error>\n"
+    )
+}
+
+/// Escapes text for XML element content, leaving quotes as they are the way servers send `ETag`s.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Text of every element named `local_name` in `xml`, whatever its namespace prefix.
+pub fn element_texts<'a>(xml: &'a str, local_name: &str) -> Vec<&'a str> {
+    let mut texts = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = find_open_tag(rest, local_name) {
+        let Some(after) = rest.get(start..) else {
+            break;
+        };
+        let Some(open_end) = after.find('>') else {
+            break;
+        };
+        let content = after.get(open_end + 1..).unwrap_or_default();
+        // Self-closing elements have no text
+        if after.get(..open_end).is_some_and(|tag| tag.ends_with('/')) {
+            texts.push("");
+            rest = content;
+            continue;
+        }
+        let Some(close) = content.find("</") else {
+            break;
+        };
+        texts.push(content.get(..close).unwrap_or_default().trim());
+        rest = content.get(close..).unwrap_or_default();
+    }
+    texts
+}
+
+/// Byte offset of the next opening tag named `local_name`, with or without a prefix.
+fn find_open_tag(xml: &str, local_name: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(lt) = xml.get(offset..)?.find('<') {
+        let start = offset + lt;
+        let tag = xml.get(start + 1..)?;
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(tag.len());
+        let name = tag.get(..name_end)?;
+        let local = name.rsplit_once(':').map_or(name, |(_, local)| local);
+        if local == local_name && !name.starts_with('/') {
+            return Some(start);
+        }
+        offset = start + 1;
+    }
+    None
+}
+
+/// Values of the `name` attribute of the `<C:comp-filter>` elements in `xml`.
+pub fn comp_filter_names(xml: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = find_open_tag(rest, "comp-filter") {
+        let after = rest.get(start..).unwrap_or_default();
+        let tag_end = after.find('>').unwrap_or(after.len());
+        let tag = after.get(..tag_end).unwrap_or_default();
+        if let Some((_, value)) = tag.split_once("name=\"")
+            && let Some((name, _)) = value.split_once('"')
+        {
+            names.push(name.to_string());
+        }
+        rest = after.get(tag_end..).unwrap_or_default();
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_escapes_markup() {
+        assert_eq!(escape("a<b>&\"c'"), "a&lt;b&gt;&amp;\"c'");
+    }
+
+    #[test]
+    fn xml_finds_element_texts_with_any_prefix() {
+        let xml = "<D:sync-collection xmlns:D=\"DAV:\"><D:sync-token>tok-1</D:sync-token>\
+<href>/a.ics</href><D:href>/b.ics</D:href><D:sync-level>1</D:sync-level>\
+</D:sync-collection>";
+        assert_eq!(element_texts(xml, "sync-token"), ["tok-1"]);
+        assert_eq!(element_texts(xml, "href"), ["/a.ics", "/b.ics"]);
+        assert_eq!(element_texts("<D:sync-token/>", "sync-token"), [""]);
+    }
+
+    #[test]
+    fn xml_finds_comp_filter_names() {
+        let xml = "<C:filter><C:comp-filter name=\"VCALENDAR\">\
+<C:comp-filter name=\"VTODO\"/></C:comp-filter></C:filter>";
+        assert_eq!(comp_filter_names(xml), ["VCALENDAR", "VTODO"]);
+    }
+}
@@ -0,0 +1,240 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests of the mock server itself, with raw HTTP requests.
+
+use aimcal_caldav_mock::{MockCalDav, Scenario};
+use reqwest::{Client, Method, StatusCode};
+
+const WORK: &str = "/dav/calendars/alice/work/";
+
+async fn basic() -> (MockCalDav, Client) {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    (server, Client::new())
+}
+
+fn method(name: &str) -> Method {
+    Method::from_bytes(name.as_bytes()).unwrap()
+}
+
+fn sync_collection(token: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\" ?>\
+<D:sync-collection xmlns:D=\"DAV:\"><D:sync-token>{token}</D:sync-token>\
+<D:sync-level>1</D:sync-level><D:prop><D:getetag/></D:prop></D:sync-collection>"
+    )
+}
+
+#[tokio::test]
+async fn server_challenges_requests_without_credentials() {
+    let (server, client) = basic().await;
+    let url = format!("{}{WORK}", server.uri());
+
+    let resp = client
+        .request(method("PROPFIND"), &url)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert!(resp.headers().contains_key("WWW-Authenticate"));
+
+    let resp = client
+        .request(method("PROPFIND"), &url)
+        .basic_auth("alice", Some("wrong"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = client
+        .request(method("PROPFIND"), &url)
+        .basic_auth("alice", Some("secret"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::MULTI_STATUS);
+}
+
+#[tokio::test]
+async fn server_redirects_well_known_path() {
+    let (server, _) = basic().await;
+    let client = Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let resp = client
+        .get(format!("{}/.well-known/caldav", server.uri()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        resp.headers().get("Location").unwrap(),
+        "/dav/calendars/alice/"
+    );
+}
+
+#[tokio::test]
+async fn server_put_checks_etag_preconditions() {
+    let (server, client) = basic().await;
+    let href = format!("{WORK}standup.ics");
+    let url = format!("{}{href}", server.uri());
+    let data = server.resource(&href).unwrap().data;
+
+    let resp = client
+        .put(&url)
+        .basic_auth("alice", Some("secret"))
+        .header("If-Match", "\"stale\"")
+        .body(data.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(resp.headers().get("ETag").unwrap(), "\"standup-1\"");
+
+    let resp = client
+        .put(&url)
+        .basic_auth("alice", Some("secret"))
+        .header("If-None-Match", "*")
+        .body(data.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    let resp = client
+        .put(&url)
+        .basic_auth("alice", Some("secret"))
+        .header("If-Match", "\"standup-1\"")
+        .body(data)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let etag = resp.headers().get("ETag").unwrap().to_str().unwrap();
+    assert_eq!(server.resource(&href).unwrap().etag, etag);
+}
+
+#[tokio::test]
+async fn server_refuses_writes_without_privileges() {
+    let (server, client) = basic().await;
+    let resp = client
+        .put(format!("{}/dav/calendars/bob/team/new.ics", server.uri()))
+        .basic_auth("alice", Some("secret"))
+        .body("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn server_sync_collection_reports_changes_since_token() {
+    let (server, client) = basic().await;
+    let url = format!("{}{WORK}", server.uri());
+    let token = server.sync_token(WORK).unwrap();
+
+    let review = format!("{WORK}review.ics");
+    let data = server.resource(&review).unwrap().data;
+    assert_ne!(server.put_resource(&review, &data), "\"review-1\"");
+    assert!(server.delete_resource(&format!("{WORK}report.ics")));
+    assert_ne!(server.sync_token(WORK).unwrap(), token);
+
+    let body = client
+        .request(method("REPORT"), &url)
+        .basic_auth("alice", Some("secret"))
+        .body(sync_collection(&token))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("review.ics"), "{body}");
+    assert!(body.contains("report.ics</D:href>\n    <D:status>HTTP/1.1 404"));
+    assert!(!body.contains("standup.ics"), "{body}");
+    assert!(body.contains(&server.sync_token(WORK).unwrap()));
+
+    let resp = client
+        .request(method("REPORT"), &url)
+        .basic_auth("alice", Some("secret"))
+        .body(sync_collection("urn:unknown"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert!(resp.text().await.unwrap().contains("valid-sync-token"));
+}
+
+#[tokio::test]
+async fn server_truncates_listings_with_507() {
+    let server = MockCalDav::start(Scenario::bundled("truncated").unwrap()).await;
+    let client = Client::new();
+    let url = format!("{}/dav/calendars/user/default/", server.uri());
+
+    let body = client
+        .request(method("REPORT"), &url)
+        .body(sync_collection(""))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("todo-2.ics"), "{body}");
+    assert!(!body.contains("todo-3.ics"), "{body}");
+    assert!(body.contains("507 Insufficient Storage"), "{body}");
+
+    // The token resumes the listing where it was truncated
+    let token = body
+        .split("<D:sync-token>")
+        .nth(1)
+        .and_then(|rest| rest.split('<').next())
+        .unwrap();
+    let body = client
+        .request(method("REPORT"), &url)
+        .body(sync_collection(token))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("todo-3.ics"), "{body}");
+    assert!(!body.contains("507"), "{body}");
+}
+
+#[tokio::test]
+async fn server_fails_with_fault_the_configured_times() {
+    let server = MockCalDav::start(Scenario::bundled("flaky").unwrap()).await;
+    let client = Client::new();
+    let url = format!("{}/dav/calendars/user/default/todo-1.ics", server.uri());
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(resp.headers().get("Retry-After").unwrap(), "1");
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        server
+            .count_requests("GET", "/dav/calendars/user/default/todo-1.ics")
+            .await,
+        2
+    );
+}
+
+#[tokio::test]
+async fn server_rejects_report_when_not_allowed() {
+    let server = MockCalDav::start(Scenario::bundled("no-report").unwrap()).await;
+    let resp = Client::new()
+        .request(
+            method("REPORT"),
+            format!("{}/dav/calendars/user/default/", server.uri()),
+        )
+        .body("<C:calendar-query xmlns:C=\"urn:ietf:params:xml:ns:caldav\"/>")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
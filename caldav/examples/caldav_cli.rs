@@ -140,6 +140,7 @@ impl Cli {
             auth,
             timeout_secs: self.timeout,
             user_agent: "aimcal-caldav-cli/0.1.0".to_string(),
+            ..Default::default()
         })
     }
 }
@@ -242,7 +243,11 @@ async fn cmd_list_events(
     for resource in events {
         let extracted = extract_events(&resource.data);
         if let Some(event) = extracted.first() {
-            let start_str = format_datetime(&event.dt_start.value);
+            let start_str = event
+                .dt_start
+                .as_ref()
+                .map(|dt_start| format_datetime(&dt_start.value))
+                .unwrap_or_default();
             let summary = event
                 .summary
                 .as_ref()
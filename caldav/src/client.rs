@@ -4,26 +4,30 @@
 
 //! `CalDAV` client for calendar operations.
 
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
-use std::sync::{Arc, PoisonError};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
 
-use aimcal_ical::{ICalendar, TodoStatusValue, fmt, parse};
+use aimcal_ical::{ICalendar, MethodValue, TodoStatusValue, fmt, parse};
 use jiff::Zoned;
 use jiff::civil::DateTime;
 use quick_xml::Writer;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use reqwest::Method;
+use reqwest::{Method, Response, StatusCode};
 
 use crate::config::CalDavConfig;
 use crate::error::CalDavError;
 use crate::http::HttpClient;
 use crate::request::{
-    CalendarMultiGetRequest, CalendarQueryRequest, FreeBusyQueryRequest, Prop, PropFindRequest,
+    CalendarMultiGetRequest, CalendarQueryRequest, Depth, FreeBusyQueryRequest, Prop,
+    PropFindRequest,
 };
 use crate::response::MultiStatusResponse;
 use crate::todo_helper::{get_todo_status, is_completed_todo, is_pending_todo};
 use crate::todo_overlap::todo_overlaps_time_range;
-use crate::types::{CalendarCollection, CalendarResource, ETag, Href, ServerCapabilities};
+use crate::types::{
+    CalendarCollection, CalendarResource, ETag, Href, ScheduleTag, ServerCapabilities,
+};
 use crate::xml::ns;
 
 /// `CalDAV` client for accessing and managing calendars on `CalDAV` servers.
@@ -49,28 +53,43 @@ use crate::xml::ns;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// The client owns a pool of connections to the server, which its clones share. Clone the client
+/// for each collection, rather than creating one, so that the requests reuse the connections.
+/// The pool is only set up on the first request, so a client that is never used costs nothing.
 #[derive(Debug, Clone)]
 pub struct CalDavClient {
-    http: Arc<HttpClient>,
+    http: Arc<OnceLock<HttpClient>>,
     config: CalDavConfig,
     capabilities: Arc<std::sync::RwLock<ServerCapabilities>>,
 }
 
 impl CalDavClient {
-    /// Creates a new `CalDAV` client.
+    /// Creates a new `CalDAV` client, without connecting to the server or setting up the HTTP
+    /// client yet.
     ///
     /// # Errors
     ///
-    /// Returns an error if HTTP client initialization fails.
+    /// Returns an error if the base URL is invalid.
     pub fn new(config: CalDavConfig) -> Result<Self, CalDavError> {
-        let http = HttpClient::new(config.clone())?;
+        reqwest::Url::parse(&config.base_url)
+            .map_err(|e| CalDavError::Config(format!("Invalid base URL: {e}")))?;
         Ok(Self {
-            http: Arc::new(http),
+            http: Arc::default(),
             config,
             capabilities: Arc::new(std::sync::RwLock::new(ServerCapabilities::new())),
         })
     }
 
+    /// The HTTP client, set up on the first request.
+    fn http(&self) -> Result<&HttpClient, CalDavError> {
+        if let Some(http) = self.http.get() {
+            return Ok(http);
+        }
+        let http = HttpClient::new(self.config.clone())?;
+        Ok(self.http.get_or_init(|| http))
+    }
+
     /// Returns the current server capabilities.
     ///
     /// Capabilities are discovered via [`discover()`] and updated when
@@ -102,46 +121,20 @@ impl CalDavClient {
     /// Returns an error if the server doesn't support `CalDAV` or discovery fails.
     pub async fn discover(&self) -> Result<DiscoverResult, CalDavError> {
         // Check for CalDAV support
-        let url = self.full_url(&self.config.calendar_home);
-        let resp = self
-            .http
-            .execute(self.http.build_request(Method::OPTIONS, &url))
-            .await?;
-
-        let dav_header = resp
-            .headers()
-            .get("DAV")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-
-        // Parse and store capabilities
-        let capabilities = ServerCapabilities::from_dav_header(dav_header);
-        self.set_capabilities(capabilities);
+        let capabilities = self.options().await?;
+        self.record_capabilities(capabilities);
 
         let supports_calendars = capabilities.supports_calendars;
 
         // Find calendar home set
-        let mut propfind = PropFindRequest::new();
-        propfind.add_property(Prop::CalendarHomeSet);
-
-        let xml_body = propfind.build()?;
-        let resp = self
-            .http
-            .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"PROPFIND")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(xml_body),
+        let multistatus = self
+            .propfind(
+                &self.config.calendar_home,
+                &[Prop::CalendarHomeSet, Prop::CurrentUserPrincipal],
+                Depth::Zero,
             )
             .await?;
 
-        let xml = resp.text().await?;
-        let multistatus = MultiStatusResponse::from_xml(&xml)?;
-
         let calendar_home = multistatus
             .responses
             .iter()
@@ -152,13 +145,112 @@ impl CalDavClient {
                     .and_then(|p| p.props.calendar_home_set.clone())
             })
             .unwrap_or_else(|| Href::new(self.config.calendar_home.clone()));
+        let current_user_principal = multistatus.responses.iter().find_map(|r| {
+            r.prop_stats
+                .iter()
+                .find(|p| p.status.contains("200"))
+                .and_then(|p| p.props.current_user_principal.clone())
+        });
 
         Ok(DiscoverResult {
             supports_calendars,
             calendar_home,
+            current_user_principal,
         })
     }
 
+    /// Detects the server capabilities, sending OPTIONS only for the first client of a server.
+    ///
+    /// Capabilities are cached per base URL for the lifetime of the process, including a
+    /// REPORT rejected later by [`query()`], so that every calendar of a server lacking
+    /// REPORT falls back without probing it again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the OPTIONS request fails.
+    pub async fn detect_capabilities(&self) -> Result<ServerCapabilities, CalDavError> {
+        let cached = detected_capabilities()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&self.config.base_url)
+            .copied();
+        if let Some(caps) = cached {
+            self.set_capabilities(caps);
+            return Ok(caps);
+        }
+
+        let caps = self.options().await?;
+        self.record_capabilities(caps);
+        Ok(caps)
+    }
+
+    /// Reads the capabilities from the DAV and Allow headers of an OPTIONS response.
+    async fn options(&self) -> Result<ServerCapabilities, CalDavError> {
+        let url = self.full_url(&self.config.calendar_home);
+        let http = self.http()?;
+        let resp = http
+            .execute(http.build_request(Method::OPTIONS, &url))
+            .await?;
+
+        let header = |name| resp.headers().get(name).and_then(|v| v.to_str().ok());
+        Ok(ServerCapabilities::from_headers(
+            header("DAV").unwrap_or(""),
+            header("Allow"),
+        ))
+    }
+
+    /// Sets the capabilities and caches them for the server, warning the first time the
+    /// server turns out to lack REPORT.
+    fn record_capabilities(&self, caps: ServerCapabilities) {
+        self.set_capabilities(caps);
+        let previous = detected_capabilities()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(self.config.base_url.clone(), caps);
+        if !caps.can_query() && previous.is_none_or(|p| p.can_query()) {
+            tracing::warn!(
+                server = %self.config.base_url,
+                "server does not seem to support CalDAV REPORT, syncing with PROPFIND and GET \
+                 instead, which is slower"
+            );
+        }
+    }
+
+    /// Sends a REPORT request, recording that the server lacks REPORT if it rejects the
+    /// method with 405 or 501.
+    async fn report(&self, url: &str, xml_body: String) -> Result<Response, CalDavError> {
+        let resp = self.send_report(url, xml_body).await?;
+        HttpClient::check(resp).await
+    }
+
+    /// Sends a REPORT request like [`report()`](Self::report), leaving the other statuses to
+    /// the caller.
+    async fn send_report(&self, url: &str, xml_body: String) -> Result<Response, CalDavError> {
+        let http = self.http()?;
+        let resp = http
+            .build_request(
+                Method::from_bytes(b"REPORT")
+                    .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
+                url,
+            )
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(xml_body)
+            .send()
+            .await?;
+
+        if matches!(
+            resp.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            self.record_capabilities(ServerCapabilities {
+                supports_report: false,
+                ..self.capabilities()
+            });
+            return Err(CalDavError::UnsupportedCapability("REPORT".to_string()));
+        }
+        Ok(resp)
+    }
+
     /// Creates a new calendar collection.
     ///
     /// # Errors
@@ -217,17 +309,16 @@ impl CalDavClient {
         let body = String::from_utf8(writer.into_inner().into_inner())
             .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
 
-        let _ = self
-            .http
+        let http = self.http()?;
+        let _ = http
             .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"MKCALENDAR")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(body),
+                http.build_request(
+                    Method::from_bytes(b"MKCALENDAR")
+                        .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
+                    &url,
+                )
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(body),
             )
             .await?;
 
@@ -240,13 +331,32 @@ impl CalDavClient {
     ///
     /// Returns an error if the object doesn't exist or parsing fails.
     pub async fn get_event(&self, href: &Href) -> Result<CalendarResource, CalDavError> {
+        self.fetch(href, None).await
+    }
+
+    /// Gets a calendar object by href, keeping `etag` if the server sends none.
+    pub(crate) async fn get_resource(
+        &self,
+        href: &Href,
+        etag: ETag,
+    ) -> Result<CalendarResource, CalDavError> {
+        self.fetch(href, Some(etag)).await
+    }
+
+    async fn fetch(
+        &self,
+        href: &Href,
+        fallback_etag: Option<ETag>,
+    ) -> Result<CalendarResource, CalDavError> {
         let url = self.full_url(href.as_str());
-        let resp = self
-            .http
-            .execute(self.http.build_request(Method::GET, &url))
-            .await?;
+        let http = self.http()?;
+        let resp = http.execute(http.build_request(Method::GET, &url)).await?;
 
-        let etag = HttpClient::extract_etag(&resp)?;
+        let etag = match HttpClient::extract_etag(&resp) {
+            Ok(etag) => etag,
+            Err(e) => fallback_etag.ok_or(e)?,
+        };
+        let schedule_tag = HttpClient::extract_schedule_tag(&resp);
         let ical_data = resp.text().await?;
 
         let calendars = parse(&ical_data).map_err(|e| CalDavError::Ical(format!("{e:?}")))?;
@@ -256,7 +366,9 @@ impl CalDavClient {
             .next()
             .ok_or_else(|| CalDavError::InvalidResponse("No calendar data found".to_string()))?;
 
-        Ok(CalendarResource::new(href.clone(), etag, data.to_owned()))
+        let mut resource = CalendarResource::new(href.clone(), etag, data.to_owned());
+        resource.schedule_tag = schedule_tag;
+        Ok(resource)
     }
 
     /// Gets a single todo by href.
@@ -270,11 +382,12 @@ impl CalDavClient {
         self.get_event(href).await
     }
 
-    /// Creates a new calendar object.
+    /// Creates a new calendar object, unless one exists at `href`.
     ///
     /// # Errors
     ///
-    /// Returns an error if creation fails.
+    /// Returns an error if creation fails, or
+    /// [`PreconditionFailed`](CalDavError::PreconditionFailed) if `href` is taken.
     pub async fn create_event(
         &self,
         href: &Href,
@@ -284,14 +397,13 @@ impl CalDavClient {
         let ical_data = fmt::format(calendar)
             .map_err(|e| CalDavError::Ical(format!("Formatter error: {e}")))?;
 
-        let resp = self
-            .http
-            .execute(
-                self.http
-                    .build_request(Method::PUT, &url)
+        let http = self.http()?;
+        let resp = http
+            .execute(HttpClient::if_none_match_any(
+                http.build_request(Method::PUT, &url)
                     .header("Content-Type", "text/calendar; charset=utf-8")
                     .body(ical_data),
-            )
+            ))
             .await?;
 
         HttpClient::extract_etag(&resp)
@@ -312,35 +424,129 @@ impl CalDavClient {
         self.create_event(href, calendar).await
     }
 
-    /// Updates an existing calendar object.
+    /// Updates an existing calendar object, if it still has the `ETag` `etag`, see
+    /// [`if_match`](Self::if_match).
     ///
     /// # Errors
     ///
-    /// Returns an error if update fails or `ETag` mismatch.
+    /// Returns an error if update fails or `ETag` mismatch, or `etag` is missing.
     pub async fn update_event(
         &self,
         href: &Href,
         etag: &ETag,
         calendar: &ICalendar<String>,
     ) -> Result<ETag, CalDavError> {
+        let condition = self.if_match(href, etag).await?;
         let url = self.full_url(href.as_str());
         let ical_data = fmt::format(calendar)
             .map_err(|e| CalDavError::Ical(format!("Formatter error: {e}")))?;
 
-        let resp = self
-            .http
+        let http = self.http()?;
+        let resp = http
             .execute(HttpClient::if_match(
-                self.http
-                    .build_request(Method::PUT, &url)
+                http.build_request(Method::PUT, &url)
                     .header("Content-Type", "text/calendar; charset=utf-8")
                     .body(ical_data),
-                etag,
+                &condition,
             ))
             .await?;
 
         HttpClient::extract_etag(&resp)
     }
 
+    /// Replaces a calendar object whatever it holds, losing the changes made on the server
+    /// since it was read. Only for callers that opted in to overwriting, prefer
+    /// [`update_event`](Self::update_event).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub async fn overwrite_event(
+        &self,
+        href: &Href,
+        calendar: &ICalendar<String>,
+    ) -> Result<ETag, CalDavError> {
+        let url = self.full_url(href.as_str());
+        let ical_data = fmt::format(calendar)
+            .map_err(|e| CalDavError::Ical(format!("Formatter error: {e}")))?;
+
+        let http = self.http()?;
+        let resp = http
+            .execute(
+                http.build_request(Method::PUT, &url)
+                    .header("Content-Type", "text/calendar; charset=utf-8")
+                    .body(ical_data),
+            )
+            .await?;
+
+        HttpClient::extract_etag(&resp)
+    }
+
+    /// The `If-Match` value conditioning a write of `href` on it still having the `ETag`
+    /// `etag`, which is sent back as the server gave it.
+    ///
+    /// `If-Match` compares strongly, so a weak tag never matches (RFC 7232 section 3.1). For
+    /// servers that only send weak tags, `etag` is compared weakly with the tag of a fresh GET
+    /// instead, and the write is conditioned on the resource existing with `*`, leaving the
+    /// time between the two requests unguarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PreconditionFailed`](CalDavError::PreconditionFailed) if `etag` is missing,
+    /// or is weak and no longer matches.
+    async fn if_match(&self, href: &Href, etag: &ETag) -> Result<String, CalDavError> {
+        if etag.is_missing() {
+            return Err(CalDavError::PreconditionFailed(format!(
+                "no ETag known for {href}, refusing to change it"
+            )));
+        }
+        if !etag.is_weak() {
+            return Ok(etag.header_value());
+        }
+
+        let current = self.get_event(href).await?.etag;
+        if current.weak_eq(etag) {
+            Ok("*".to_string())
+        } else {
+            Err(CalDavError::PreconditionFailed(current.to_string()))
+        }
+    }
+
+    /// Updates the copy of a scheduling object the current user was invited to, conditioned
+    /// on its `Schedule-Tag` rather than its `ETag` (RFC 6638).
+    ///
+    /// The server merges the update with replies of other attendees it stored in between,
+    /// and only rejects it if the organizer changed the object since `schedule_tag`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if update fails or the schedule tag no longer matches.
+    pub async fn update_as_attendee(
+        &self,
+        href: &Href,
+        schedule_tag: &ScheduleTag,
+        calendar: &ICalendar<String>,
+    ) -> Result<PutResponse, CalDavError> {
+        let url = self.full_url(href.as_str());
+        let ical_data = fmt::format(calendar)
+            .map_err(|e| CalDavError::Ical(format!("Formatter error: {e}")))?;
+
+        let http = self.http()?;
+        let resp = http
+            .execute(HttpClient::if_schedule_tag_match(
+                http.build_request(Method::PUT, &url)
+                    .header("Content-Type", "text/calendar; charset=utf-8")
+                    .body(ical_data),
+                schedule_tag,
+            ))
+            .await?;
+
+        Ok(PutResponse {
+            etag: HttpClient::extract_etag(&resp).ok(),
+            schedule_tag: HttpClient::extract_schedule_tag(&resp),
+        })
+    }
+
     /// Updates an existing todo.
     ///
     /// This is a convenience alias for [`update_event`] that works with VTODO components.
@@ -357,20 +563,22 @@ impl CalDavClient {
         self.update_event(href, etag, calendar).await
     }
 
-    /// Deletes a calendar object.
+    /// Deletes a calendar object, if it still has the `ETag` `etag`, see
+    /// [`if_match`](Self::if_match).
     ///
     /// # Errors
     ///
-    /// Returns an error if deletion fails.
+    /// Returns an error if deletion fails or `ETag` mismatch, or `etag` is missing.
     pub async fn delete_event(&self, href: &Href, etag: &ETag) -> Result<(), CalDavError> {
         let url = self.full_url(href.as_str());
+        let condition = self.if_match(href, etag).await?;
 
-        self.http
-            .execute(HttpClient::if_match(
-                self.http.build_request(Method::DELETE, &url),
-                etag,
-            ))
-            .await?;
+        let http = self.http()?;
+        http.execute(HttpClient::if_match(
+            http.build_request(Method::DELETE, &url),
+            &condition,
+        ))
+        .await?;
 
         Ok(())
     }
@@ -388,9 +596,18 @@ impl CalDavClient {
 
     /// Queries calendar objects with filters.
     ///
+    /// Servers rejecting REPORT can be synced with [`sync_calendar`](crate::sync_calendar)
+    /// instead.
+    ///
+    /// Results the server truncated, such as to the [`limit`](CalendarQueryRequest::limit) of
+    /// the request, are paged through by querying each half of the time range in turn.
+    ///
     /// # Errors
     ///
-    /// Returns an error if query fails or the server doesn't support calendar-query.
+    /// Returns an error if query fails or the server doesn't support calendar-query, including
+    /// [`CalDavError::UnsupportedCapability`] when it rejects REPORT with 405 or 501, and
+    /// [`CalDavError::InvalidResponse`] if the server truncated results that have no time range
+    /// to page through.
     pub async fn query(
         &self,
         calendar_href: &Href,
@@ -405,25 +622,55 @@ impl CalDavClient {
         }
 
         let url = self.full_url(calendar_href.as_str());
-        let xml_body = request.build()?;
-
-        let resp = self
-            .http
-            .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"REPORT")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(xml_body),
-            )
-            .await?;
+        let mut resources = Vec::new();
+        let mut seen = HashSet::new();
+        let mut pending = vec![request.clone()];
+        while let Some(request) = pending.pop() {
+            let multistatus = self.query_page(&url, &request).await?;
+            if multistatus.is_truncated() {
+                let Some((first, second)) = request.split_time_range() else {
+                    return Err(CalDavError::InvalidResponse(format!(
+                        "Server truncated the results of the query of {calendar_href}, query a \
+                         shorter time range"
+                    )));
+                };
+                tracing::debug!(%url, "server truncated the results, querying each half");
+                pending.push(second);
+                pending.push(first);
+                continue;
+            }
+
+            // Resources overlapping both halves of a time range come twice
+            let page = multistatus.into_resources()?;
+            let hrefs: Vec<Href> = page.iter().map(|r| r.href.clone()).collect();
+            resources.extend(page.into_iter().filter(|r| !seen.contains(&r.href)));
+            seen.extend(hrefs);
+        }
+        Ok(resources)
+    }
 
-        let xml = resp.text().await?;
-        let multistatus = MultiStatusResponse::from_xml(&xml)?;
-        multistatus.into_resources()
+    /// Sends a calendar-query, again without its limit if the server rejects it.
+    async fn query_page(
+        &self,
+        url: &str,
+        request: &CalendarQueryRequest,
+    ) -> Result<MultiStatusResponse, CalDavError> {
+        let resp = self.send_report(url, request.build()?).await?;
+        let resp = if request.nresults().is_some()
+            && matches!(
+                resp.status(),
+                StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::INSUFFICIENT_STORAGE
+            ) {
+            tracing::debug!(
+                url,
+                status = resp.status().as_u16(),
+                "server rejected the limit of the query, sending it without"
+            );
+            self.report(url, request.without_limit().build()?).await?
+        } else {
+            HttpClient::check(resp).await?
+        };
+        self.http()?.read_multistatus(resp).await
     }
 
     /// Retrieves multiple calendar objects by href.
@@ -453,22 +700,9 @@ impl CalDavClient {
 
         let xml_body = multiget.build()?;
 
-        let resp = self
-            .http
-            .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"REPORT")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(xml_body),
-            )
-            .await?;
+        let resp = self.report(&url, xml_body).await?;
 
-        let xml = resp.text().await?;
-        let multistatus = MultiStatusResponse::from_xml(&xml)?;
+        let multistatus = self.http()?.read_multistatus(resp).await?;
         multistatus.into_resources()
     }
 
@@ -614,19 +848,7 @@ impl CalDavClient {
         let request = FreeBusyQueryRequest::new(start.to_string(), end.to_string());
         let xml_body = request.build()?;
 
-        let resp = self
-            .http
-            .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"REPORT")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(xml_body),
-            )
-            .await?;
+        let resp = self.report(&url, xml_body).await?;
 
         // Parse free-busy response
         let xml = resp.text().await?;
@@ -644,33 +866,294 @@ impl CalDavClient {
     ///
     /// Returns an error if PROPFIND fails.
     pub async fn list_calendars(&self) -> Result<Vec<CalendarCollection>, CalDavError> {
-        let url = self.full_url(&self.config.calendar_home);
+        self.list_calendars_in(&self.config.calendar_home).await
+    }
+
+    /// Gets the metadata of a single calendar collection, including its owner and
+    /// whether the current user may modify it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if PROPFIND fails or `href` is not a calendar collection.
+    pub async fn get_calendar(&self, href: &Href) -> Result<CalendarCollection, CalDavError> {
+        let multistatus = self
+            .propfind(href.as_str(), Self::COLLECTION_PROPS, Depth::Zero)
+            .await?;
+        multistatus
+            .into_collections()
+            .into_iter()
+            .next()
+            .ok_or_else(|| CalDavError::NotFound(href.clone()))
+    }
+
+    /// Finds the principals whose calendars the user `principal` can access as a delegate.
+    ///
+    /// Delegation is read from the `CalendarServer` `calendar-proxy-read-for` and
+    /// `calendar-proxy-write-for` properties and from the standard `group-membership`.
+    /// Proxy groups listed as group memberships are resolved to the principal they
+    /// grant access to. A principal reachable both ways is reported writable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if PROPFIND fails.
+    pub async fn delegations(&self, principal: &Href) -> Result<Vec<Delegation>, CalDavError> {
+        let multistatus = self
+            .propfind(
+                principal.as_str(),
+                &[
+                    Prop::GroupMembership,
+                    Prop::CalendarProxyReadFor,
+                    Prop::CalendarProxyWriteFor,
+                ],
+                Depth::Zero,
+            )
+            .await?;
+
+        let mut delegations: Vec<Delegation> = Vec::new();
+        let mut add = |principal: Href, read_only: bool| match delegations
+            .iter_mut()
+            .find(|d| d.principal == principal)
+        {
+            Some(existing) => existing.read_only &= read_only,
+            None => delegations.push(Delegation {
+                principal,
+                read_only,
+            }),
+        };
+        for props in multistatus
+            .responses
+            .iter()
+            .flat_map(|r| &r.prop_stats)
+            .filter(|p| p.status.contains("200"))
+            .map(|p| &p.props)
+        {
+            for href in &props.calendar_proxy_read_for {
+                add(href.clone(), true);
+            }
+            for href in &props.calendar_proxy_write_for {
+                add(href.clone(), false);
+            }
+            for href in &props.group_membership {
+                match proxy_group_target(href) {
+                    Some((target, read_only)) => add(target, read_only),
+                    None => add(href.clone(), false),
+                }
+            }
+        }
+        Ok(delegations)
+    }
+
+    /// Gets the calendar collections shared with the current user by other principals.
+    ///
+    /// Each collection is marked with its owner, falling back to the delegating principal
+    /// when the server does not report one, and is read-only if either the delegation or
+    /// the privileges on the collection do not allow writing. Principals without a
+    /// calendar home are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not report the current user principal or a
+    /// PROPFIND fails.
+    pub async fn list_delegated_calendars(&self) -> Result<Vec<CalendarCollection>, CalDavError> {
+        let principal = self.current_user_principal().await?;
+        let mut collections: Vec<CalendarCollection> = Vec::new();
+        for delegation in self.delegations(&principal).await? {
+            let home = self
+                .propfind(
+                    delegation.principal.as_str(),
+                    &[Prop::CalendarHomeSet],
+                    Depth::Zero,
+                )
+                .await?
+                .responses
+                .into_iter()
+                .flat_map(|r| r.prop_stats)
+                .find_map(|p| p.props.calendar_home_set);
+            let Some(home) = home else {
+                continue;
+            };
+
+            for mut collection in self.list_calendars_in(home.as_str()).await? {
+                if collections.iter().any(|c| c.href == collection.href) {
+                    continue;
+                }
+                collection
+                    .owner
+                    .get_or_insert_with(|| delegation.principal.clone());
+                if delegation.read_only {
+                    collection.privileges = collection.privileges.without_writes();
+                }
+                collections.push(collection);
+            }
+        }
+        Ok(collections)
+    }
+
+    /// Finds the scheduling inbox and outbox of the current user (RFC 6638).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not report the current user principal or a
+    /// PROPFIND fails.
+    pub async fn discover_scheduling(&self) -> Result<SchedulingCollections, CalDavError> {
+        let principal = self.current_user_principal().await?;
+        let multistatus = self
+            .propfind(
+                principal.as_str(),
+                &[Prop::ScheduleInboxUrl, Prop::ScheduleOutboxUrl],
+                Depth::Zero,
+            )
+            .await?;
+
+        let mut collections = SchedulingCollections::default();
+        for props in multistatus
+            .responses
+            .into_iter()
+            .flat_map(|r| r.prop_stats)
+            .filter(|p| p.status.contains("200"))
+            .map(|p| p.props)
+        {
+            collections.inbox = collections.inbox.or(props.schedule_inbox_url);
+            collections.outbox = collections.outbox.or(props.schedule_outbox_url);
+        }
+        Ok(collections)
+    }
+
+    /// Gets the scheduling messages waiting in the inbox of the current user, such as the
+    /// iTIP REQUEST of an invitation, the CANCEL of a meeting or the REPLY of an attendee.
+    ///
+    /// Messages stay in the inbox until they are deleted, so they are pending until acted on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalDavError::UnsupportedCapability`] if the server does not report a
+    /// scheduling inbox, or an error if a request fails.
+    pub async fn fetch_inbox(&self) -> Result<Vec<SchedulingMessage>, CalDavError> {
+        let inbox =
+            self.discover_scheduling().await?.inbox.ok_or_else(|| {
+                CalDavError::UnsupportedCapability("schedule-inbox-URL".to_string())
+            })?;
+
+        let mut messages = Vec::new();
+        for (href, etag) in self.list_etags(&inbox).await? {
+            let resource = self.get_resource(&href, etag).await?;
+            let method = resource.data.method.as_ref().map(|m| m.value);
+            messages.push(SchedulingMessage { resource, method });
+        }
+        Ok(messages)
+    }
+
+    /// Finds the principal of the authenticated user.
+    async fn current_user_principal(&self) -> Result<Href, CalDavError> {
+        self.propfind(
+            &self.config.calendar_home,
+            &[Prop::CurrentUserPrincipal],
+            Depth::Zero,
+        )
+        .await?
+        .responses
+        .into_iter()
+        .flat_map(|r| r.prop_stats)
+        .find_map(|p| p.props.current_user_principal)
+        .ok_or_else(|| {
+            CalDavError::InvalidResponse("Server did not report current-user-principal".into())
+        })
+    }
+
+    /// Lists the calendar objects in a collection with their `ETag`s, using a Depth 1 PROPFIND.
+    ///
+    /// Unlike [`query()`], this works on servers that lack REPORT.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if PROPFIND fails, or if the server truncated the listing with a 507
+    /// status, as the members left out cannot be told from deleted ones.
+    pub async fn list_etags(&self, calendar_href: &Href) -> Result<Vec<(Href, ETag)>, CalDavError> {
+        let multistatus = self
+            .propfind(
+                calendar_href.as_str(),
+                &[Prop::ResourceType, Prop::GetETag],
+                Depth::One,
+            )
+            .await?;
+        if multistatus.is_truncated() {
+            return Err(CalDavError::InvalidResponse(format!(
+                "Server truncated the listing of {calendar_href}"
+            )));
+        }
+
+        let mut etags = Vec::new();
+        for response in multistatus.responses {
+            let Some(props) = response
+                .prop_stats
+                .iter()
+                .find(|p| p.status.contains("200"))
+                .map(|p| &p.props)
+            else {
+                continue;
+            };
+            // Skip the collection itself and any nested collections
+            if props.is_collection || response.href == *calendar_href {
+                continue;
+            }
+            if let Some(etag) = props.get_etag.clone() {
+                etags.push((response.href, etag));
+            } else {
+                tracing::warn!(href = %response.href, "skipping resource without ETag");
+            }
+        }
+        Ok(etags)
+    }
+
+    /// Properties describing a calendar collection.
+    const COLLECTION_PROPS: &[Prop] = &[
+        Prop::DisplayName,
+        Prop::ResourceType,
+        Prop::CalendarDescription,
+        Prop::SupportedCalendarComponents,
+        Prop::Owner,
+        Prop::CurrentUserPrivilegeSet,
+    ];
+
+    /// Lists the calendar collections directly under `home`.
+    async fn list_calendars_in(&self, home: &str) -> Result<Vec<CalendarCollection>, CalDavError> {
+        let multistatus = self
+            .propfind(home, Self::COLLECTION_PROPS, Depth::One)
+            .await?;
+        Ok(multistatus.into_collections())
+    }
+
+    /// Sends a PROPFIND for `props` on `href` with the given depth.
+    async fn propfind(
+        &self,
+        href: &str,
+        props: &[Prop],
+        depth: Depth,
+    ) -> Result<MultiStatusResponse, CalDavError> {
+        let url = self.full_url(href);
 
         let mut propfind = PropFindRequest::new();
-        propfind.add_property(Prop::DisplayName);
-        propfind.add_property(Prop::ResourceType);
-        propfind.add_property(Prop::CalendarDescription);
-        propfind.add_property(Prop::SupportedCalendarComponents);
+        for &prop in props {
+            propfind.add_property(prop);
+        }
+        propfind.set_depth(depth);
 
         let xml_body = propfind.build()?;
-        let resp = self
-            .http
+        let http = self.http()?;
+        let resp = http
             .execute(
-                self.http
-                    .build_request(
-                        Method::from_bytes(b"PROPFIND")
-                            .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
-                        &url,
-                    )
-                    .header("Content-Type", "application/xml; charset=utf-8")
-                    .body(xml_body)
-                    .header("Depth", "1"),
+                http.build_request(
+                    Method::from_bytes(b"PROPFIND")
+                        .map_err(|e| CalDavError::Http(format!("Invalid method: {e}")))?,
+                    &url,
+                )
+                .header("Content-Type", "application/xml; charset=utf-8")
+                .body(xml_body)
+                .header("Depth", propfind.depth().header_value()),
             )
             .await?;
 
-        let xml = resp.text().await?;
-        let multistatus = MultiStatusResponse::from_xml(&xml)?;
-        Ok(multistatus.into_collections())
+        http.read_multistatus(resp).await
     }
 
     /// Builds full URL from href.
@@ -692,6 +1175,12 @@ impl CalDavClient {
     }
 }
 
+/// Capabilities detected per server, keyed by base URL.
+fn detected_capabilities() -> &'static Mutex<HashMap<String, ServerCapabilities>> {
+    static DETECTED: OnceLock<Mutex<HashMap<String, ServerCapabilities>>> = OnceLock::new();
+    DETECTED.get_or_init(Mutex::default)
+}
+
 /// Result of `CalDAV` server discovery.
 #[derive(Debug, Clone)]
 pub struct DiscoverResult {
@@ -699,6 +1188,57 @@ pub struct DiscoverResult {
     pub supports_calendars: bool,
     /// The calendar home set href.
     pub calendar_home: Href,
+    /// The principal of the authenticated user, if reported by the server.
+    pub current_user_principal: Option<Href>,
+}
+
+/// Access to another principal's calendars granted to the current user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delegation {
+    /// The principal whose calendars are shared.
+    pub principal: Href,
+    /// Whether the access is limited to reading.
+    pub read_only: bool,
+}
+
+/// Resolves a `CalendarServer` proxy group such as `/principals/alice/calendar-proxy-read/`
+/// to the principal it grants access to, and whether that access is read-only.
+fn proxy_group_target(group: &Href) -> Option<(Href, bool)> {
+    let trimmed = group.trim_end_matches('/');
+    let (parent, name) = trimmed.rsplit_once('/')?;
+    let read_only = match name {
+        "calendar-proxy-read" => true,
+        "calendar-proxy-write" => false,
+        _ => return None,
+    };
+    Some((Href::new(format!("{parent}/")), read_only))
+}
+
+/// The scheduling collections of a principal (RFC 6638).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchedulingCollections {
+    /// The collection receiving scheduling messages, such as invitations.
+    pub inbox: Option<Href>,
+    /// The collection to submit scheduling messages to.
+    pub outbox: Option<Href>,
+}
+
+/// A scheduling message in the inbox of the current user.
+#[derive(Debug, Clone)]
+pub struct SchedulingMessage {
+    /// The message resource in the inbox.
+    pub resource: CalendarResource,
+    /// The iTIP method of the message, such as REQUEST, CANCEL or REPLY.
+    pub method: Option<MethodValue>,
+}
+
+/// Tags the server sent back for a stored calendar object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PutResponse {
+    /// The new `ETag`, which servers may leave out when they changed the object on storing it.
+    pub etag: Option<ETag>,
+    /// The new `Schedule-Tag`, for scheduling objects.
+    pub schedule_tag: Option<ScheduleTag>,
 }
 
 /// Free/busy data.
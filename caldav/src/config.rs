@@ -42,12 +42,33 @@ pub struct CalDavConfig {
     /// User agent string.
     #[serde(default = "default_user_agent")]
     pub user_agent: String,
+    /// Maximum number of idle connections kept open to the server for reuse.
+    #[serde(default = "default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle connection is kept open for reuse before it is closed.
+    #[serde(default = "default_pool_idle_timeout")]
+    pub pool_idle_timeout_secs: u64,
+    /// Maximum size in bytes of a multistatus response, larger ones are refused as they arrive.
+    #[serde(default = "default_max_response_bytes")]
+    pub max_response_bytes: u64,
 }
 
 const fn default_timeout() -> u64 {
     30
 }
 
+const fn default_pool_max_idle_per_host() -> usize {
+    8
+}
+
+const fn default_pool_idle_timeout() -> u64 {
+    90
+}
+
+const fn default_max_response_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
 fn default_user_agent() -> String {
     concat!("aimcal-caldav/", env!("CARGO_PKG_VERSION")).to_string()
 }
@@ -60,6 +81,9 @@ impl Default for CalDavConfig {
             auth: AuthMethod::default(),
             timeout_secs: default_timeout(),
             user_agent: default_user_agent(),
+            pool_max_idle_per_host: default_pool_max_idle_per_host(),
+            pool_idle_timeout_secs: default_pool_idle_timeout(),
+            max_response_bytes: default_max_response_bytes(),
         }
     }
 }
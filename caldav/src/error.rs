@@ -39,6 +39,9 @@ pub enum CalDavError {
 
     /// Server doesn't support required capability.
     UnsupportedCapability(String),
+
+    /// Response body larger than the limit of the configuration, in bytes.
+    ResponseTooLarge(u64),
 }
 
 impl fmt::Display for CalDavError {
@@ -56,6 +59,11 @@ impl fmt::Display for CalDavError {
             Self::UnsupportedCapability(cap) => {
                 write!(f, "Server doesn't support required capability: {cap}")
             }
+            Self::ResponseTooLarge(limit) => write!(
+                f,
+                "Response larger than {limit} bytes, query a shorter time range or raise \
+                 max_response_bytes"
+            ),
         }
     }
 }
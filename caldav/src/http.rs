@@ -4,11 +4,19 @@
 
 //! HTTP client wrapper with authentication and `ETag` handling.
 
+use std::io::{self, BufRead, Cursor, Read};
+use std::time::{Duration, Instant};
+
 use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use tokio::sync::mpsc;
 
 use crate::config::{AuthMethod, CalDavConfig};
 use crate::error::CalDavError;
-use crate::types::ETag;
+use crate::response::MultiStatusResponse;
+use crate::types::{ETag, ScheduleTag};
+
+/// Chunks of a response body read ahead of the multistatus parser.
+const CHUNKS_AHEAD: usize = 16;
 
 /// HTTP client for `CalDAV` operations.
 #[derive(Debug)]
@@ -24,9 +32,13 @@ impl HttpClient {
     ///
     /// Returns an error if HTTP client creation fails.
     pub fn new(config: CalDavConfig) -> Result<Self, CalDavError> {
+        // Connections are kept alive in a pool, and HTTP/2 is negotiated where the server
+        // supports it, so that the requests of a sync reuse a few connections
         let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(config.timeout_secs))
+            .timeout(Duration::from_secs(config.timeout_secs))
             .user_agent(&config.user_agent)
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
             .build()?;
         Ok(Self { client, config })
     }
@@ -52,8 +64,26 @@ impl HttpClient {
     ///
     /// Returns an error if the request fails or returns an error status code.
     pub async fn execute(&self, req: RequestBuilder) -> Result<Response, CalDavError> {
-        let resp = req.send().await?;
+        let req = req.build()?;
+        let (method, url) = (req.method().clone(), req.url().clone());
+        let start = Instant::now();
+        let resp = self.client.execute(req).await?;
+        tracing::debug!(
+            %method,
+            %url,
+            status = resp.status().as_u16(),
+            duration = ?start.elapsed(),
+            "sent request"
+        );
+        Self::check(resp).await
+    }
 
+    /// Checks a response for HTTP errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response has an error status code.
+    pub async fn check(resp: Response) -> Result<Response, CalDavError> {
         match resp.status() {
             StatusCode::OK
             | StatusCode::CREATED
@@ -76,27 +106,127 @@ impl HttpClient {
         }
     }
 
-    /// Adds If-Match header for conditional updates.
-    pub fn if_match(req: RequestBuilder, etag: &ETag) -> RequestBuilder {
-        req.header("If-Match", etag.as_str())
+    /// Reads the multistatus of a response, parsing the body as it arrives rather than once it
+    /// is whole.
+    ///
+    /// The parser runs on a blocking thread fed with the chunks of the body, and stops when the
+    /// read is dropped, such as when the sync is cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalDavError::ResponseTooLarge`] once the body exceeds `max_response_bytes`, or
+    /// an error if reading or parsing the body fails.
+    pub async fn read_multistatus(
+        &self,
+        mut resp: Response,
+    ) -> Result<MultiStatusResponse, CalDavError> {
+        let limit = self.config.max_response_bytes;
+        if resp.content_length().is_some_and(|len| len > limit) {
+            return Err(CalDavError::ResponseTooLarge(limit));
+        }
+
+        let (tx, rx) = mpsc::channel(CHUNKS_AHEAD);
+        let parser = tokio::task::spawn_blocking(move || {
+            MultiStatusResponse::from_reader(ChunkReader::new(rx))
+        });
+
+        let mut read = 0;
+        while let Some(chunk) = resp.chunk().await? {
+            read += chunk.len() as u64;
+            if read > limit {
+                // Dropping the sender ends the parser
+                return Err(CalDavError::ResponseTooLarge(limit));
+            }
+            if tx.send(chunk.into()).await.is_err() {
+                // The parser stopped on an error, which it reports below
+                break;
+            }
+        }
+        drop(tx);
+
+        parser
+            .await
+            .map_err(|e| CalDavError::Xml(format!("Multistatus parser failed: {e}")))?
+    }
+
+    /// Adds If-Match header for conditional updates, with a tag or `*`, see
+    /// [`ETag::header_value`].
+    pub fn if_match(req: RequestBuilder, value: &str) -> RequestBuilder {
+        req.header("If-Match", value)
+    }
+
+    /// Adds If-Schedule-Tag-Match header for attendee updates (RFC 6638), so the server
+    /// merges them with the replies of other attendees instead of rejecting them.
+    pub fn if_schedule_tag_match(req: RequestBuilder, tag: &ScheduleTag) -> RequestBuilder {
+        req.header("If-Schedule-Tag-Match", tag.as_str())
     }
 
-    /// Adds If-None-Match header for conditional creation.
-    #[expect(dead_code)]
-    pub fn if_none_match(req: RequestBuilder, etag: &ETag) -> RequestBuilder {
-        req.header("If-None-Match", etag.as_str())
+    /// Adds `If-None-Match: *` for conditional creation, so that an existing resource is not
+    /// replaced.
+    pub fn if_none_match_any(req: RequestBuilder) -> RequestBuilder {
+        req.header("If-None-Match", "*")
     }
 
     /// Extracts `ETag` from response headers.
     ///
     /// # Errors
     ///
-    /// Returns an error if the `ETag` header is missing.
+    /// Returns an error if the `ETag` header is missing or empty.
     pub fn extract_etag(resp: &Response) -> Result<ETag, CalDavError> {
         resp.headers()
             .get("ETag")
             .and_then(|v| v.to_str().ok())
             .map(|s| ETag::new(s.to_string()))
+            .filter(|etag| !etag.is_missing())
             .ok_or_else(|| CalDavError::Http("Missing ETag header".to_string()))
     }
+
+    /// Extracts the `Schedule-Tag` from response headers, sent only for scheduling objects.
+    pub fn extract_schedule_tag(resp: &Response) -> Option<ScheduleTag> {
+        resp.headers()
+            .get("Schedule-Tag")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| ScheduleTag::new(s.to_string()))
+    }
+}
+
+/// A reader of the chunks of a response body, received from the task reading it.
+struct ChunkReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    chunk: Cursor<Vec<u8>>,
+}
+
+impl ChunkReader {
+    fn new(rx: mpsc::Receiver<Vec<u8>>) -> Self {
+        Self {
+            rx,
+            chunk: Cursor::default(),
+        }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut available = self.fill_buf()?;
+        let len = available.read(buf)?;
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for ChunkReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // An empty buffer is the end of the body, so empty chunks are skipped
+        while self.chunk.position() >= self.chunk.get_ref().len() as u64 {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = Cursor::new(chunk),
+                None => break,
+            }
+        }
+        self.chunk.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.chunk.consume(amount);
+    }
 }
@@ -31,19 +31,25 @@ mod todo_overlap;
 mod types;
 mod xml;
 
-pub use crate::client::{CalDavClient, DiscoverResult, FreeBusyData};
+pub use crate::client::{
+    CalDavClient, Delegation, DiscoverResult, FreeBusyData, PutResponse, SchedulingCollections,
+    SchedulingMessage,
+};
 pub use crate::config::{AuthMethod, CalDavConfig};
 pub use crate::error::CalDavError;
 pub use crate::request::{
-    CalendarMultiGetRequest, CalendarQueryRequest, FreeBusyQueryRequest, Prop, PropFindRequest,
-    TextMatch, TimeRange,
+    CalendarMultiGetRequest, CalendarQueryRequest, Depth, FreeBusyQueryRequest, Prop,
+    PropFindRequest, TextMatch, TimeRange,
 };
-pub use crate::response::MultiStatusResponse;
+pub use crate::response::{MultiStatusReader, MultiStatusResponse};
+pub use crate::sync::{SyncChanges, SyncState, sync_calendar};
 pub use crate::todo_helper::{
     extract_first_todo, get_todo_status, is_completed_todo, is_pending_todo,
 };
 pub use crate::todo_overlap::todo_overlaps_time_range;
-pub use crate::types::{CalendarCollection, CalendarResource, ETag, Href, ServerCapabilities};
+pub use crate::types::{
+    CalendarCollection, CalendarResource, ETag, Href, Privileges, ScheduleTag, ServerCapabilities,
+};
 
 // Re-export TodoStatusValue for convenience
 pub use aimcal_ical::semantic::TodoStatusValue;
@@ -6,16 +6,47 @@
 
 use std::io::Cursor;
 
+use jiff::civil::DateTime;
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Timestamp};
 use quick_xml::Writer;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 
 use crate::error::CalDavError;
 use crate::xml::ns;
 
+/// Format of the UTC times of a time range.
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
 /// PROPFIND request builder.
 #[derive(Debug)]
 pub struct PropFindRequest {
     props: Vec<Prop>,
+    depth: Depth,
+}
+
+/// Depth of a PROPFIND, sent in the `Depth` header.
+///
+/// There is no `infinity`, as a server honoring it may send the whole tree below a collection
+/// in one response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Depth {
+    /// The resource alone.
+    #[default]
+    Zero,
+    /// The resource and its direct members.
+    One,
+}
+
+impl Depth {
+    /// The value of the `Depth` header.
+    #[must_use]
+    pub const fn header_value(self) -> &'static str {
+        match self {
+            Self::Zero => "0",
+            Self::One => "1",
+        }
+    }
 }
 
 /// Properties to request in PROPFIND.
@@ -37,6 +68,24 @@ pub enum Prop {
     CalendarDescription,
     /// Calendar timezone.
     CalendarTimezone,
+    /// Principal of the authenticated user.
+    CurrentUserPrincipal,
+    /// Principal owning the resource.
+    Owner,
+    /// Privileges the authenticated user has on the resource.
+    CurrentUserPrivilegeSet,
+    /// Groups the principal is a member of.
+    GroupMembership,
+    /// Principals the user has read-only proxy access to (`CalendarServer` extension).
+    CalendarProxyReadFor,
+    /// Principals the user has read-write proxy access to (`CalendarServer` extension).
+    CalendarProxyWriteFor,
+    /// Collection receiving the scheduling messages of the principal (RFC 6638).
+    ScheduleInboxUrl,
+    /// Collection to submit scheduling messages of the principal to (RFC 6638).
+    ScheduleOutboxUrl,
+    /// Schedule tag of a scheduling object resource (RFC 6638).
+    ScheduleTag,
 }
 
 impl Prop {
@@ -50,17 +99,45 @@ impl Prop {
             Self::SupportedCalendarComponents => "supported-calendar-component-set",
             Self::CalendarDescription => "calendar-description",
             Self::CalendarTimezone => "calendar-timezone",
+            Self::CurrentUserPrincipal => "current-user-principal",
+            Self::Owner => "owner",
+            Self::CurrentUserPrivilegeSet => "current-user-privilege-set",
+            Self::GroupMembership => "group-membership",
+            Self::CalendarProxyReadFor => "calendar-proxy-read-for",
+            Self::CalendarProxyWriteFor => "calendar-proxy-write-for",
+            Self::ScheduleInboxUrl => "schedule-inbox-URL",
+            Self::ScheduleOutboxUrl => "schedule-outbox-URL",
+            Self::ScheduleTag => "schedule-tag",
         }
     }
 
     const fn namespace(self) -> Option<&'static str> {
         match self {
-            Self::DisplayName | Self::ResourceType | Self::GetETag => None,
+            Self::DisplayName
+            | Self::ResourceType
+            | Self::GetETag
+            | Self::CurrentUserPrincipal
+            | Self::Owner
+            | Self::CurrentUserPrivilegeSet
+            | Self::GroupMembership => None,
             Self::CalendarData
             | Self::CalendarHomeSet
             | Self::SupportedCalendarComponents
             | Self::CalendarDescription
-            | Self::CalendarTimezone => Some(ns::CALDAV),
+            | Self::CalendarTimezone
+            | Self::ScheduleInboxUrl
+            | Self::ScheduleOutboxUrl
+            | Self::ScheduleTag => Some(ns::CALDAV),
+            Self::CalendarProxyReadFor | Self::CalendarProxyWriteFor => Some(ns::CALENDARSERVER),
+        }
+    }
+
+    /// Prefix of the property element, as declared on the PROPFIND root.
+    const fn prefix(self) -> &'static str {
+        match self {
+            Self::CalendarProxyReadFor | Self::CalendarProxyWriteFor => "CS",
+            _ if self.namespace().is_some() => "C",
+            _ => "D",
         }
     }
 }
@@ -69,7 +146,10 @@ impl PropFindRequest {
     /// Creates a new PROPFIND request.
     #[must_use]
     pub fn new() -> Self {
-        Self { props: Vec::new() }
+        Self {
+            props: Vec::new(),
+            depth: Depth::default(),
+        }
     }
 
     /// Adds a property to the request.
@@ -78,6 +158,18 @@ impl PropFindRequest {
         self
     }
 
+    /// Sets the depth of the request, the resource alone by default.
+    pub fn set_depth(&mut self, depth: Depth) -> &mut Self {
+        self.depth = depth;
+        self
+    }
+
+    /// The depth of the request.
+    #[must_use]
+    pub const fn depth(&self) -> Depth {
+        self.depth
+    }
+
     /// Builds the XML body for the PROPFIND request.
     ///
     /// # Errors
@@ -89,9 +181,12 @@ impl PropFindRequest {
         // <D:propfind xmlns:D="DAV:">
         let mut propfind = BytesStart::new("D:propfind");
         propfind.push_attribute(("xmlns:D", ns::DAV));
-        if self.props.iter().any(|p| p.namespace().is_some()) {
+        if self.props.iter().any(|p| p.prefix() == "C") {
             propfind.push_attribute(("xmlns:C", ns::CALDAV));
         }
+        if self.props.iter().any(|p| p.prefix() == "CS") {
+            propfind.push_attribute(("xmlns:CS", ns::CALENDARSERVER));
+        }
         writer.write_event(Event::Start(propfind))?;
 
         // <D:prop>
@@ -99,17 +194,10 @@ impl PropFindRequest {
 
         // Properties
         for prop in &self.props {
-            let name = prop.name();
-
-            if prop.namespace().is_some() {
-                let elem = BytesStart::new(format!("C:{name}"));
-                // Namespace already declared on propfind
-                writer.write_event(Event::Start(elem))?;
-                writer.write_event(Event::End(BytesEnd::new(format!("C:{name}"))))?;
-            } else {
-                writer.write_event(Event::Start(BytesStart::new(format!("D:{name}"))))?;
-                writer.write_event(Event::End(BytesEnd::new(format!("D:{name}"))))?;
-            }
+            // Namespace already declared on propfind
+            let elem = format!("{}:{}", prop.prefix(), prop.name());
+            writer.write_event(Event::Start(BytesStart::new(elem.as_str())))?;
+            writer.write_event(Event::End(BytesEnd::new(elem)))?;
         }
 
         // </D:prop>
@@ -130,12 +218,13 @@ impl Default for PropFindRequest {
 }
 
 /// Calendar query request builder.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CalendarQueryRequest {
     time_range: Option<TimeRange>,
     #[expect(dead_code)]
     text_match: Option<TextMatch>,
     component: Option<String>,
+    limit: Option<u32>,
 }
 
 /// Time range filter for calendar queries.
@@ -166,6 +255,7 @@ impl CalendarQueryRequest {
             time_range: None,
             text_match: None,
             component: None,
+            limit: None,
         }
     }
 
@@ -183,6 +273,65 @@ impl CalendarQueryRequest {
         self
     }
 
+    /// Asks the server for at most `nresults` results per response, with the `DAV:limit` of
+    /// RFC 5323 that some servers accept.
+    ///
+    /// [`CalDavClient::query`](crate::CalDavClient::query) pages through the results of a
+    /// server honoring it by halving the time range, and sends the query again without the
+    /// limit to a server rejecting it.
+    #[must_use]
+    pub fn limit(mut self, nresults: u32) -> Self {
+        self.limit = Some(nresults);
+        self
+    }
+
+    /// The number of results asked for per response, if limited.
+    #[must_use]
+    pub const fn nresults(&self) -> Option<u32> {
+        self.limit
+    }
+
+    /// The same query without the limit on the number of results.
+    pub(crate) fn without_limit(&self) -> Self {
+        Self {
+            limit: None,
+            ..self.clone()
+        }
+    }
+
+    /// Splits the query into the two halves of its time range, to page through results the
+    /// server truncated, or `None` without a bounded time range or once it is a second long.
+    pub(crate) fn split_time_range(&self) -> Option<(Self, Self)> {
+        let range = self.time_range.as_ref()?;
+        let start = parse_utc(&range.start)?;
+        let end = parse_utc(range.end.as_deref()?)?;
+        let half = end.duration_since(start) / 2;
+        if half < SignedDuration::from_secs(1) {
+            return None;
+        }
+        let middle = start
+            .checked_add(half)
+            .ok()?
+            .strftime(UTC_FORMAT)
+            .to_string();
+
+        let first = Self {
+            time_range: Some(TimeRange {
+                start: range.start.clone(),
+                end: Some(middle.clone()),
+            }),
+            ..self.clone()
+        };
+        let second = Self {
+            time_range: Some(TimeRange {
+                start: middle,
+                end: range.end.clone(),
+            }),
+            ..self.clone()
+        };
+        Some((first, second))
+    }
+
     /// Builds the XML body for the calendar query request.
     ///
     /// # Errors
@@ -238,6 +387,15 @@ impl CalendarQueryRequest {
         // </C:filter>
         writer.write_event(Event::End(BytesEnd::new("C:filter")))?;
 
+        // <D:limit><D:nresults>...</D:nresults></D:limit>
+        if let Some(nresults) = self.limit {
+            writer.write_event(Event::Start(BytesStart::new("D:limit")))?;
+            writer.write_event(Event::Start(BytesStart::new("D:nresults")))?;
+            writer.write_event(Event::Text(BytesText::new(&nresults.to_string())))?;
+            writer.write_event(Event::End(BytesEnd::new("D:nresults")))?;
+            writer.write_event(Event::End(BytesEnd::new("D:limit")))?;
+        }
+
         // </C:calendar-query>
         writer.write_event(Event::End(BytesEnd::new("C:calendar-query")))?;
 
@@ -355,3 +513,9 @@ impl FreeBusyQueryRequest {
         String::from_utf8(bytes).map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))
     }
 }
+
+/// Parses a UTC time of a time range, such as `20260101T000000Z`.
+fn parse_utc(s: &str) -> Option<Timestamp> {
+    let datetime = DateTime::strptime(UTC_FORMAT, s).ok()?;
+    TimeZone::UTC.to_timestamp(datetime).ok()
+}
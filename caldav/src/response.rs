@@ -4,11 +4,13 @@
 
 //! Response parsers for WebDAV/CalDAV operations.
 
+use std::io::BufRead;
+
 use aimcal_ical::parse;
 use quick_xml::events::Event;
 
 use crate::error::CalDavError;
-use crate::types::{CalendarCollection, CalendarResource, ETag, Href};
+use crate::types::{CalendarCollection, CalendarResource, ETag, Href, Privileges, ScheduleTag};
 
 /// `WebDAV` multistatus response.
 #[derive(Debug, Clone)]
@@ -43,6 +45,15 @@ pub struct Properties {
     pub supported_calendar_components: Option<Vec<String>>,
     pub calendar_description: Option<String>,
     pub calendar_timezone: Option<String>,
+    pub current_user_principal: Option<Href>,
+    pub owner: Option<Href>,
+    pub current_user_privileges: Option<Vec<String>>,
+    pub group_membership: Vec<Href>,
+    pub calendar_proxy_read_for: Vec<Href>,
+    pub calendar_proxy_write_for: Vec<Href>,
+    pub schedule_inbox_url: Option<Href>,
+    pub schedule_outbox_url: Option<Href>,
+    pub schedule_tag: Option<ScheduleTag>,
     pub is_calendar: bool,
     pub is_collection: bool,
 }
@@ -53,51 +64,193 @@ impl MultiStatusResponse {
     /// # Errors
     ///
     /// Returns an error if XML parsing fails.
-    #[expect(clippy::too_many_lines)]
     pub fn from_xml(xml: &str) -> Result<Self, CalDavError> {
-        let mut reader = quick_xml::Reader::from_str(xml);
+        Self::from_reader(xml.as_bytes())
+    }
+
+    /// Parses a multistatus response as it is read from `reader`, see [`MultiStatusReader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or XML parsing fails.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, CalDavError> {
+        let responses = MultiStatusReader::new(reader).collect::<Result<_, _>>()?;
+        Ok(Self { responses })
+    }
+
+    /// Whether the server truncated the multistatus, reporting 507 for the request URI in place
+    /// of the members left out.
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        self.responses
+            .iter()
+            .any(|r| r.status.as_ref().is_some_and(|s| s.contains("507")))
+    }
+
+    /// Converts multistatus response to calendar resources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if conversion fails.
+    pub fn into_resources(self) -> Result<Vec<CalendarResource>, CalDavError> {
+        let mut resources = Vec::new();
+
+        for response in self.responses {
+            // Find successful propstat (status starts with "HTTP/1.1 200" or "HTTP/1.1 207")
+            for prop_stat in &response.prop_stats {
+                if (prop_stat.status.contains("200") || prop_stat.status.contains("207") )
+                    // Check if we have calendar data
+                    && let Some(data) = &prop_stat.props.calendar_data
+                {
+                    // Parse iCalendar data
+                    let calendars = parse(data)
+                        .map_err(|e| CalDavError::Ical(format!("Parse error: {e:?}")))?;
+
+                    for calendar in calendars {
+                        let owned = calendar.to_owned();
+                        let mut resource = CalendarResource::new(
+                            response.href.clone(),
+                            prop_stat
+                                .props
+                                .get_etag
+                                .clone()
+                                .unwrap_or_else(|| ETag::new(String::new())),
+                            owned,
+                        );
+                        resource
+                            .schedule_tag
+                            .clone_from(&prop_stat.props.schedule_tag);
+                        resources.push(resource);
+                    }
+                }
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// Converts multistatus response to calendar collections.
+    #[must_use]
+    pub fn into_collections(self) -> Vec<CalendarCollection> {
+        let mut collections = Vec::new();
+
+        for response in self.responses {
+            for prop_stat in &response.prop_stats {
+                if (prop_stat.status.contains("200") || prop_stat.status.contains("207")) &&
+                    // Only include if it's a calendar collection
+                    prop_stat.props.is_calendar && prop_stat.props.is_collection
+                {
+                    let mut collection = CalendarCollection::new(response.href.clone());
+                    collection
+                        .display_name
+                        .clone_from(&prop_stat.props.display_name);
+                    collection
+                        .description
+                        .clone_from(&prop_stat.props.calendar_description);
+                    collection.supported_components = prop_stat
+                        .props
+                        .supported_calendar_components
+                        .clone()
+                        .unwrap_or_default();
+                    collection.ctag.clone_from(&prop_stat.props.get_etag);
+                    collection.owner.clone_from(&prop_stat.props.owner);
+                    collection.privileges =
+                        if let Some(names) = &prop_stat.props.current_user_privileges {
+                            Privileges::from_names(names)
+                        } else {
+                            tracing::debug!(
+                                href = response.href.as_str(),
+                                "no current-user-privilege-set reported, assuming full access"
+                            );
+                            Privileges::ALL
+                        };
+                    collections.push(collection);
+                }
+            }
+        }
+
+        collections
+    }
+}
+
+/// Reads the responses of a multistatus one at a time, so that a large body is parsed as it
+/// arrives instead of being held whole.
+///
+/// Each item is a `response` element, yielded once its end tag is read. The reader stops at the
+/// first error.
+#[derive(Debug)]
+pub struct MultiStatusReader<R> {
+    reader: quick_xml::Reader<R>,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl<R: BufRead> MultiStatusReader<R> {
+    /// Creates a reader of the multistatus read from `reader`.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        let mut reader = quick_xml::Reader::from_reader(reader);
         // Configure reader to trim text and check namespaces
         reader.config_mut().trim_text(true);
         reader.config_mut().check_end_names = true;
+        Self {
+            reader,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Reads up to the next response, or `None` at the end of the multistatus.
+    fn next_response(&mut self) -> Result<Option<ResponseItem>, CalDavError> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_event_into(&mut self.buf)? {
+                Event::End(ref e) if e.name().local_name().into_inner() == b"multistatus" => {
+                    return Ok(None);
+                }
+                Event::Eof => return Ok(None),
+                Event::Start(ref e) if e.name().local_name().into_inner() == b"response" => {
+                    return self.read_response().map(Some);
+                }
+                _ => {}
+            }
+        }
+    }
 
-        let mut responses = Vec::new();
-        let mut current_response: Option<ResponseItem> = None;
-        let mut current_prop_stats: Vec<PropStat> = Vec::new();
+    /// Reads a response up to its end tag, once its start tag is read.
+    #[expect(clippy::too_many_lines)]
+    fn read_response(&mut self) -> Result<ResponseItem, CalDavError> {
+        let mut response = ResponseItem {
+            href: Href::new(String::new()),
+            prop_stats: Vec::new(),
+            status: None,
+        };
         let mut current_props: Properties = Properties::default();
         let mut in_prop = false;
-        let mut in_response = false;
         let mut in_propstat = false;
 
         let mut buf = Vec::new();
 
         loop {
-            match reader.read_event_into(&mut buf)? {
-                Event::End(ref e) if e.name().local_name().into_inner() == b"multistatus" => break,
-                Event::Eof => break,
+            match self.reader.read_event_into(&mut buf)? {
+                Event::End(ref e) if e.name().local_name().into_inner() == b"response" => {
+                    return Ok(response);
+                }
+                Event::Eof => return Err(CalDavError::Xml("Unexpected EOF".to_string())),
 
                 Event::Start(ref e) => {
                     match e.name().local_name().into_inner() {
-                        b"response" => {
-                            in_response = true;
-                            current_response = Some(ResponseItem {
-                                href: Href::new(String::new()),
-                                prop_stats: Vec::new(),
-                                status: None,
-                            });
-                        }
-                        b"href" if in_response => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                        b"href" => {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 let href = quick_xml::escape::unescape(text_str)
                                     .map_err(|e| CalDavError::Xml(format!("Escape error: {e}")))?
                                     .to_string();
-                                if let Some(ref mut resp) = current_response {
-                                    resp.href = Href::new(href);
-                                }
+                                response.href = Href::new(href);
                             }
                         }
-                        b"propstat" if in_response => {
+                        b"propstat" => {
                             in_propstat = true;
                             current_props = Properties::default();
                         }
@@ -106,7 +259,7 @@ impl MultiStatusResponse {
                         b"prop" => in_prop = true,
 
                         b"displayname" if in_prop => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 current_props.display_name = Some(
@@ -122,7 +275,7 @@ impl MultiStatusResponse {
                             current_props.is_collection = true;
                             // Look for calendar or collection
                             loop {
-                                match reader.read_event_into(&mut buf)? {
+                                match self.reader.read_event_into(&mut buf)? {
                                     Event::End(ref e)
                                         if e.name().local_name().into_inner()
                                             == b"resourcetype" =>
@@ -142,7 +295,7 @@ impl MultiStatusResponse {
                             }
                         }
                         b"getetag" if in_prop => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 current_props.get_etag = Some(ETag::new(
@@ -155,7 +308,7 @@ impl MultiStatusResponse {
                             }
                         }
                         b"calendar-data" if in_prop => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 current_props.calendar_data = Some(
@@ -170,7 +323,7 @@ impl MultiStatusResponse {
                         b"calendar-home-set" if in_prop => {
                             // Read href content
                             loop {
-                                match reader.read_event_into(&mut buf)? {
+                                match self.reader.read_event_into(&mut buf)? {
                                     Event::End(ref e)
                                         if e.name().local_name().into_inner()
                                             == b"calendar-home-set" =>
@@ -181,7 +334,7 @@ impl MultiStatusResponse {
                                         if e.name().local_name().into_inner() == b"href" =>
                                     {
                                         if let Event::Text(text) =
-                                            reader.read_event_into(&mut buf)?
+                                            self.reader.read_event_into(&mut buf)?
                                         {
                                             let text_str = str::from_utf8(&text).map_err(|e| {
                                                 CalDavError::Xml(format!("UTF-8 error: {e}"))
@@ -207,7 +360,7 @@ impl MultiStatusResponse {
                         b"supported-calendar-component-set" if in_prop => {
                             let mut components = Vec::new();
                             loop {
-                                match reader.read_event_into(&mut buf)? {
+                                match self.reader.read_event_into(&mut buf)? {
                                     Event::End(ref e)
                                         if e.name().local_name().into_inner()
                                             == b"supported-calendar-component-set" =>
@@ -235,7 +388,7 @@ impl MultiStatusResponse {
                             current_props.supported_calendar_components = Some(components);
                         }
                         b"calendar-description" if in_prop => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 current_props.calendar_description = Some(
@@ -248,7 +401,7 @@ impl MultiStatusResponse {
                             }
                         }
                         b"calendar-timezone" if in_prop => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 current_props.calendar_timezone = Some(
@@ -260,31 +413,110 @@ impl MultiStatusResponse {
                                 );
                             }
                         }
+                        b"current-user-principal" if in_prop => {
+                            current_props.current_user_principal =
+                                read_hrefs(&mut self.reader, b"current-user-principal")?
+                                    .into_iter()
+                                    .next();
+                        }
+                        b"owner" if in_prop => {
+                            current_props.owner =
+                                read_hrefs(&mut self.reader, b"owner")?.into_iter().next();
+                        }
+                        b"group-membership" if in_prop => {
+                            current_props.group_membership =
+                                read_hrefs(&mut self.reader, b"group-membership")?;
+                        }
+                        b"calendar-proxy-read-for" if in_prop => {
+                            current_props.calendar_proxy_read_for =
+                                read_hrefs(&mut self.reader, b"calendar-proxy-read-for")?;
+                        }
+                        b"calendar-proxy-write-for" if in_prop => {
+                            current_props.calendar_proxy_write_for =
+                                read_hrefs(&mut self.reader, b"calendar-proxy-write-for")?;
+                        }
+                        b"schedule-inbox-URL" if in_prop => {
+                            current_props.schedule_inbox_url =
+                                read_hrefs(&mut self.reader, b"schedule-inbox-URL")?
+                                    .into_iter()
+                                    .next();
+                        }
+                        b"schedule-outbox-URL" if in_prop => {
+                            current_props.schedule_outbox_url =
+                                read_hrefs(&mut self.reader, b"schedule-outbox-URL")?
+                                    .into_iter()
+                                    .next();
+                        }
+                        b"schedule-tag" if in_prop => {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
+                                let text_str = str::from_utf8(&text)
+                                    .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
+                                current_props.schedule_tag = Some(ScheduleTag::new(
+                                    quick_xml::escape::unescape(text_str)
+                                        .map_err(|e| {
+                                            CalDavError::Xml(format!("Escape error: {e}"))
+                                        })?
+                                        .to_string(),
+                                ));
+                            }
+                        }
+                        b"current-user-privilege-set" if in_prop => {
+                            let mut privileges = Vec::new();
+                            loop {
+                                match self.reader.read_event_into(&mut buf)? {
+                                    Event::End(ref e)
+                                        if e.name().local_name().into_inner()
+                                            == b"current-user-privilege-set" =>
+                                    {
+                                        break;
+                                    }
+                                    Event::Start(ref e) | Event::Empty(ref e)
+                                        if e.name().local_name().into_inner() != b"privilege" =>
+                                    {
+                                        let name =
+                                            str::from_utf8(e.name().local_name().into_inner())
+                                                .map_err(|e| {
+                                                    CalDavError::Xml(format!("UTF-8 error: {e}"))
+                                                })?
+                                                .to_string();
+                                        privileges.push(name);
+                                    }
+                                    Event::Eof => {
+                                        return Err(CalDavError::Xml("Unexpected EOF".to_string()));
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            current_props.current_user_privileges = Some(privileges);
+                        }
                         b"status" if in_propstat => {
-                            if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
                                 let text_str = str::from_utf8(&text)
                                     .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
                                 let status = quick_xml::escape::unescape(text_str)
                                     .map_err(|e| CalDavError::Xml(format!("Escape error: {e}")))?
                                     .to_string();
-                                current_prop_stats.push(PropStat {
+                                response.prop_stats.push(PropStat {
                                     props: current_props.clone(),
                                     status,
                                 });
                             }
                         }
+                        // Status of the whole response, such as 507 for a truncated listing
+                        b"status" => {
+                            if let Event::Text(text) = self.reader.read_event_into(&mut buf)? {
+                                let text_str = str::from_utf8(&text)
+                                    .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
+                                let status = quick_xml::escape::unescape(text_str)
+                                    .map_err(|e| CalDavError::Xml(format!("Escape error: {e}")))?
+                                    .to_string();
+                                response.status = Some(status);
+                            }
+                        }
                         _ => {}
                     }
                 }
                 Event::End(ref e) => match e.name().local_name().into_inner() {
-                    b"response" if in_response => {
-                        in_response = false;
-                        if let Some(mut resp) = current_response.take() {
-                            resp.prop_stats.clone_from(&current_prop_stats);
-                            current_prop_stats.clear();
-                            responses.push(resp);
-                        }
-                    }
                     b"propstat" if in_propstat => {
                         in_propstat = false;
                     }
@@ -297,77 +529,45 @@ impl MultiStatusResponse {
             }
             buf.clear();
         }
-
-        Ok(Self { responses })
     }
+}
 
-    /// Converts multistatus response to calendar resources.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if conversion fails.
-    pub fn into_resources(self) -> Result<Vec<CalendarResource>, CalDavError> {
-        let mut resources = Vec::new();
-
-        for response in self.responses {
-            // Find successful propstat (status starts with "HTTP/1.1 200" or "HTTP/1.1 207")
-            for prop_stat in &response.prop_stats {
-                if (prop_stat.status.contains("200") || prop_stat.status.contains("207") )
-                    // Check if we have calendar data
-                    && let Some(data) = &prop_stat.props.calendar_data
-                {
-                    // Parse iCalendar data
-                    let calendars = parse(data)
-                        .map_err(|e| CalDavError::Ical(format!("Parse error: {e:?}")))?;
+impl<R: BufRead> Iterator for MultiStatusReader<R> {
+    type Item = Result<ResponseItem, CalDavError>;
 
-                    for calendar in calendars {
-                        let owned = calendar.to_owned();
-                        resources.push(CalendarResource::new(
-                            response.href.clone(),
-                            prop_stat
-                                .props
-                                .get_etag
-                                .clone()
-                                .unwrap_or_else(|| ETag::new(String::new())),
-                            owned,
-                        ));
-                    }
-                }
-            }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
         }
-
-        Ok(resources)
+        let next = self.next_response().transpose();
+        self.done = !matches!(next, Some(Ok(_)));
+        next
     }
+}
 
-    /// Converts multistatus response to calendar collections.
-    #[must_use]
-    pub fn into_collections(self) -> Vec<CalendarCollection> {
-        let mut collections = Vec::new();
-
-        for response in self.responses {
-            for prop_stat in &response.prop_stats {
-                if (prop_stat.status.contains("200") || prop_stat.status.contains("207")) &&
-                    // Only include if it's a calendar collection
-                    prop_stat.props.is_calendar && prop_stat.props.is_collection
-                {
-                    let mut collection = CalendarCollection::new(response.href.clone());
-                    collection
-                        .display_name
-                        .clone_from(&prop_stat.props.display_name);
-                    collection
-                        .description
-                        .clone_from(&prop_stat.props.calendar_description);
-                    collection.supported_components = prop_stat
-                        .props
-                        .supported_calendar_components
-                        .clone()
-                        .unwrap_or_default();
-                    collection.ctag.clone_from(&prop_stat.props.get_etag);
-                    collections.push(collection);
+/// Reads the `href` children of the element named `end`, consuming its end tag.
+fn read_hrefs<R: BufRead>(
+    reader: &mut quick_xml::Reader<R>,
+    end: &[u8],
+) -> Result<Vec<Href>, CalDavError> {
+    let mut hrefs = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::End(ref e) if e.name().local_name().into_inner() == end => break,
+            Event::Start(ref e) if e.name().local_name().into_inner() == b"href" => {
+                if let Event::Text(text) = reader.read_event_into(&mut buf)? {
+                    let text_str = str::from_utf8(&text)
+                        .map_err(|e| CalDavError::Xml(format!("UTF-8 error: {e}")))?;
+                    let href = quick_xml::escape::unescape(text_str)
+                        .map_err(|e| CalDavError::Xml(format!("Escape error: {e}")))?;
+                    hrefs.push(Href::new(href.to_string()));
                 }
             }
+            Event::Eof => return Err(CalDavError::Xml("Unexpected EOF".to_string())),
+            _ => {}
         }
-
-        collections
+        buf.clear();
     }
+    Ok(hrefs)
 }
@@ -43,7 +43,7 @@ pub fn is_pending_todo(calendar: &ICalendar<String>) -> bool {
 
         // Check status
         // No completed property and no status means pending
-        matches!(
+        !matches!(
             todo.status.as_ref().map(|s| s.value),
             Some(TodoStatusValue::Completed | TodoStatusValue::Cancelled)
         )
@@ -64,6 +64,14 @@ impl From<&str> for Href {
 ///
 /// An `ETag` represents an entity tag returned by the `CalDAV` server,
 /// used for optimistic concurrency control and change detection.
+///
+/// Servers write the same tag in different forms, weak (`W/"abc"`) or strong (`"abc"`), and
+/// some leave the quotes out of `getetag` in PROPFIND while keeping them in the `ETag` header
+/// of a GET. The tag is kept as the server sent it and compared by its opaque part (RFC 7232
+/// section 2.3.2), with [`ETag::weak_eq`] to tell whether a resource changed and
+/// [`ETag::strong_eq`] for conditional writes. `==` compares the tags as sent.
+///
+/// An empty tag stands for one the server did not send, and matches no tag.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ETag(String);
 
@@ -79,6 +87,56 @@ impl ETag {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Whether the tag is weak, marked with `W/`.
+    #[must_use]
+    pub fn is_weak(&self) -> bool {
+        self.0.trim_start().starts_with("W/")
+    }
+
+    /// The opaque part of the tag, without the weakness mark and the quotes.
+    #[must_use]
+    pub fn opaque(&self) -> &str {
+        let tag = self.0.trim();
+        let tag = tag.strip_prefix("W/").unwrap_or(tag);
+        let tag = tag.strip_prefix('"').unwrap_or(tag);
+        tag.strip_suffix('"').unwrap_or(tag)
+    }
+
+    /// Whether the server sent no tag.
+    #[must_use]
+    pub fn is_missing(&self) -> bool {
+        self.opaque().is_empty()
+    }
+
+    /// Weak comparison, whether both tags have the same opaque part whether or not they are
+    /// weak, which tells that a resource is unchanged. A missing tag matches none.
+    #[must_use]
+    pub fn weak_eq(&self, other: &Self) -> bool {
+        !self.is_missing() && self.opaque() == other.opaque()
+    }
+
+    /// Strong comparison, whether both tags are strong and have the same opaque part, which
+    /// `If-Match` requires. A missing tag matches none.
+    #[must_use]
+    pub fn strong_eq(&self, other: &Self) -> bool {
+        !self.is_weak() && !other.is_weak() && self.weak_eq(other)
+    }
+
+    /// The tag as written in `If-Match` and `If-None-Match`: as the server sent it if it is
+    /// quoted, so that the opaque part goes back byte for byte, or quoted otherwise.
+    #[must_use]
+    pub fn header_value(&self) -> String {
+        let tag = self.0.trim();
+        let quoted = tag.strip_prefix("W/").unwrap_or(tag);
+        if quoted.len() >= 2 && quoted.starts_with('"') && quoted.ends_with('"') {
+            tag.to_string()
+        } else if self.is_weak() {
+            format!("W/\"{}\"", self.opaque())
+        } else {
+            format!("\"{}\"", self.opaque())
+        }
+    }
 }
 
 impl Deref for ETag {
@@ -113,6 +171,54 @@ impl From<&str> for ETag {
     }
 }
 
+/// Schedule tag of a scheduling object resource (RFC 6638).
+///
+/// Unlike the `ETag`, a `Schedule-Tag` only changes when the organizer changes the
+/// scheduling object, not when the server merges an attendee reply, so attendees
+/// condition their updates on it to avoid clobbering each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleTag(String);
+
+impl ScheduleTag {
+    /// Creates a new `ScheduleTag` from a string.
+    #[must_use]
+    pub const fn new(tag: String) -> Self {
+        Self(tag)
+    }
+
+    /// Returns the inner string value.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for ScheduleTag {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for ScheduleTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<String> for ScheduleTag {
+    fn from(tag: String) -> Self {
+        Self(tag)
+    }
+}
+
+impl From<&str> for ScheduleTag {
+    fn from(tag: &str) -> Self {
+        Self(tag.to_string())
+    }
+}
+
 /// A calendar object resource.
 ///
 /// Represents a calendar object (event, todo, etc.) stored on a `CalDAV` server,
@@ -123,6 +229,8 @@ pub struct CalendarResource {
     pub href: Href,
     /// The entity tag of the resource.
     pub etag: ETag,
+    /// The schedule tag, if the resource is a scheduling object (RFC 6638).
+    pub schedule_tag: Option<ScheduleTag>,
     /// The iCalendar data.
     pub data: ICalendar<String>,
 }
@@ -131,7 +239,12 @@ impl CalendarResource {
     /// Creates a new `CalendarResource`.
     #[must_use]
     pub const fn new(href: Href, etag: ETag, data: ICalendar<String>) -> Self {
-        Self { href, etag, data }
+        Self {
+            href,
+            etag,
+            schedule_tag: None,
+            data,
+        }
     }
 }
 
@@ -151,6 +264,10 @@ pub struct CalendarCollection {
     pub supported_components: Vec<String>,
     /// The collection tag (`CTag`) for change detection.
     pub ctag: Option<ETag>,
+    /// The principal owning the calendar, if reported by the server.
+    pub owner: Option<Href>,
+    /// What the current user may do with the calendar.
+    pub privileges: Privileges,
 }
 
 impl CalendarCollection {
@@ -163,10 +280,94 @@ impl CalendarCollection {
             description: None,
             supported_components: Vec::new(),
             ctag: None,
+            owner: None,
+            privileges: Privileges::ALL,
+        }
+    }
+}
+
+/// What the current user may do with a calendar collection, from its
+/// `current-user-privilege-set` (RFC 3744 section 5.4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Privileges {
+    /// Whether the user may read the calendar.
+    pub read: bool,
+    /// Whether the user may modify existing items.
+    pub write_content: bool,
+    /// Whether the user may create items.
+    pub bind: bool,
+    /// Whether the user may delete items.
+    pub unbind: bool,
+}
+
+impl Privileges {
+    /// Every privilege, assumed when the server does not report them.
+    pub const ALL: Self = Self {
+        read: true,
+        write_content: true,
+        bind: true,
+        unbind: true,
+    };
+
+    /// Only reading.
+    pub const READ_ONLY: Self = Self {
+        read: true,
+        write_content: false,
+        bind: false,
+        unbind: false,
+    };
+
+    /// Maps the names of the privileges in a `current-user-privilege-set`, where `all` and
+    /// `write` aggregate the others.
+    #[must_use]
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Self {
+        let mut privileges = Self {
+            read: false,
+            write_content: false,
+            bind: false,
+            unbind: false,
+        };
+        for name in names {
+            match name.as_ref() {
+                "all" => privileges = Self::ALL,
+                "read" => privileges.read = true,
+                "write" => {
+                    privileges.write_content = true;
+                    privileges.bind = true;
+                    privileges.unbind = true;
+                }
+                "write-content" => privileges.write_content = true,
+                "bind" => privileges.bind = true,
+                "unbind" => privileges.unbind = true,
+                _ => {}
+            }
+        }
+        privileges
+    }
+
+    /// Whether the user may not change the calendar in any way.
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        !self.write_content && !self.bind && !self.unbind
+    }
+
+    /// Drops the privileges to change the calendar.
+    #[must_use]
+    pub const fn without_writes(self) -> Self {
+        Self {
+            read: self.read,
+            ..Self::READ_ONLY
         }
     }
 }
 
+impl Default for Privileges {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
 /// Server capabilities discovered from the `CalDAV` server.
 ///
 /// Represents the features and operations supported by the server,
@@ -184,6 +385,8 @@ pub struct ServerCapabilities {
     pub supports_calendar_multiget: bool,
     /// Whether the server supports free-busy-query REPORT.
     pub supports_free_busy: bool,
+    /// Whether the server accepts the REPORT method at all.
+    pub supports_report: bool,
 }
 
 impl ServerCapabilities {
@@ -196,6 +399,7 @@ impl ServerCapabilities {
             supports_calendar_query: false,
             supports_calendar_multiget: false,
             supports_free_busy: false,
+            supports_report: false,
         }
     }
 
@@ -219,26 +423,42 @@ impl ServerCapabilities {
         caps.supports_calendar_query = caps.supports_calendars;
         caps.supports_calendar_multiget = caps.supports_calendars;
         caps.supports_free_busy = caps.supports_calendars;
+        caps.supports_report = caps.supports_calendars;
+
+        caps
+    }
 
+    /// Creates capabilities from the DAV and Allow header values of an OPTIONS response.
+    ///
+    /// Some servers advertise `calendar-access` but reject REPORT, which shows in the
+    /// Allow header (e.g., "OPTIONS, GET, PUT, DELETE, PROPFIND") when they send one.
+    #[must_use]
+    pub fn from_headers(dav_header: &str, allow_header: Option<&str>) -> Self {
+        let mut caps = Self::from_dav_header(dav_header);
+        if let Some(allow) = allow_header {
+            caps.supports_report = allow
+                .split(',')
+                .any(|method| method.trim().eq_ignore_ascii_case("REPORT"));
+        }
         caps
     }
 
     /// Checks if the server supports calendar-query REPORT.
     #[must_use]
     pub const fn can_query(&self) -> bool {
-        self.supports_calendars && self.supports_calendar_query
+        self.supports_calendars && self.supports_report && self.supports_calendar_query
     }
 
     /// Checks if the server supports calendar-multiget REPORT.
     #[must_use]
     pub const fn can_multiget(&self) -> bool {
-        self.supports_calendars && self.supports_calendar_multiget
+        self.supports_calendars && self.supports_report && self.supports_calendar_multiget
     }
 
     /// Checks if the server supports free-busy-query REPORT.
     #[must_use]
     pub const fn can_free_busy(&self) -> bool {
-        self.supports_calendars && self.supports_free_busy
+        self.supports_calendars && self.supports_report && self.supports_free_busy
     }
 
     /// Checks if the server supports MKCALENDAR.
@@ -15,6 +15,9 @@ pub mod ns {
 
     /// `CalDAV` namespace.
     pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+
+    /// Apple `CalendarServer` extensions namespace.
+    pub const CALENDARSERVER: &str = "http://calendarserver.org/ns/";
 }
 
 /// Reads text content of an XML element.
@@ -5,10 +5,11 @@
 //! Client integration tests with wiremock.
 
 use aimcal_caldav::{
-    AuthMethod, CalDavClient, CalDavConfig, CalendarQueryRequest, Href, ServerCapabilities,
+    AuthMethod, CalDavClient, CalDavConfig, CalDavError, CalendarQueryRequest, ETag, Href,
+    ScheduleTag, ServerCapabilities, SyncState, sync_calendar,
 };
-use aimcal_ical::{ICalendar, ProductId, ValueText, Version, fmt};
-use wiremock::matchers::{header, method, path};
+use aimcal_ical::{ICalendar, MethodValue, ProductId, ValueText, Version, fmt};
+use wiremock::matchers::{body_string_contains, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -282,9 +283,10 @@ async fn client_query_events() {
 async fn client_create_event() {
     let mock_server = MockServer::start().await;
 
-    // Mock PUT request
+    // Mock PUT request, which must not replace an existing resource
     Mock::given(method("PUT"))
         .and(path("/calendars/user/new-event.ics"))
+        .and(header("if-none-match", "*"))
         .respond_with(
             ResponseTemplate::new(201)
                 .insert_header("ETag", "\"new-etag\"")
@@ -860,3 +862,740 @@ async fn test_server_capabilities_from_dav_header() {
     assert!(caps.supports_calendars);
     assert!(caps.can_query());
 }
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn test_server_capabilities_from_headers() {
+    // No Allow header, REPORT is implied by calendar-access
+    let caps = ServerCapabilities::from_headers("1, 2, calendar-access", None);
+    assert!(caps.supports_report);
+    assert!(caps.can_query());
+
+    // Allow header listing REPORT
+    let caps = ServerCapabilities::from_headers(
+        "1, 2, calendar-access",
+        Some("OPTIONS, GET, PUT, DELETE, PROPFIND, report"),
+    );
+    assert!(caps.can_query());
+    assert!(caps.can_multiget());
+
+    // Allow header without REPORT
+    let caps = ServerCapabilities::from_headers(
+        "1, 2, calendar-access",
+        Some("OPTIONS, GET, PUT, DELETE, PROPFIND"),
+    );
+    assert!(caps.supports_calendars);
+    assert!(!caps.supports_report);
+    assert!(!caps.can_query());
+    assert!(!caps.can_multiget());
+    assert!(!caps.can_free_busy());
+    assert!(caps.can_mkcalendar());
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_query_rejected_report_returns_unsupported_capability() {
+    // Not pooled, as capabilities are cached per server address
+    let mock_server = MockServer::builder().start().await;
+
+    Mock::given(method("OPTIONS"))
+        .and(path("/dav/calendars/user/"))
+        .respond_with(ResponseTemplate::new(200).insert_header("DAV", "1, 2, calendar-access"))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("REPORT"))
+        .respond_with(ResponseTemplate::new(405))
+        .mount(&mock_server)
+        .await;
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/dav/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+
+    let client = CalDavClient::new(config.clone()).expect("Failed to create client");
+    assert!(client.detect_capabilities().await.unwrap().can_query());
+
+    let request = CalendarQueryRequest::new().component("VEVENT".to_string());
+    let err = client
+        .query(&Href::new("/dav/calendars/user/".to_string()), &request)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CalDavError::UnsupportedCapability(_)));
+    assert!(!client.capabilities().can_query());
+
+    // Other clients of the server reuse the capabilities without sending OPTIONS again
+    let other = CalDavClient::new(config).expect("Failed to create client");
+    assert!(!other.detect_capabilities().await.unwrap().can_query());
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|r| r.method.as_str() == "OPTIONS")
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_sync_calendar_diffs_etags() {
+    let mock_server = MockServer::builder().start().await;
+
+    let member = |name: &str, etag: &str| {
+        format!(
+            r#"<D:response>
+    <D:href>/dav/calendars/user/{name}</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype/>
+        <D:getetag>"{etag}"</D:getetag>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>"#
+        )
+    };
+    Mock::given(method("PROPFIND"))
+        .and(path("/dav/calendars/user/"))
+        .and(header("Depth", "1"))
+        .respond_with(ResponseTemplate::new(207).set_body_raw(
+            format!(
+                r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/dav/calendars/user/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+  {}
+  {}
+  {}
+</D:multistatus>"#,
+                member("new.ics", "1"),
+                member("changed.ics", "2"),
+                member("same.ics", "3"),
+            ),
+            "application/xml",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    for (name, uid) in [("new.ics", "new"), ("changed.ics", "changed")] {
+        // The server sends no ETag on GET, so the one from PROPFIND is kept
+        Mock::given(method("GET"))
+            .and(path(format!("/dav/calendars/user/{name}")))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                format!(
+                    "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\n\
+BEGIN:VTODO\r\nUID:{uid}\r\nDTSTAMP:20250101T000000Z\r\nSUMMARY:Todo\r\nEND:VTODO\r\n\
+END:VCALENDAR\r\n"
+                ),
+                "text/calendar",
+            ))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/dav/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+    let client = CalDavClient::new(config).expect("Failed to create client");
+
+    let mut state = SyncState::default();
+    for (name, etag) in [("changed.ics", "1"), ("same.ics", "3"), ("gone.ics", "4")] {
+        state.resource_etags.insert(
+            Href::new(format!("/dav/calendars/user/{name}")),
+            ETag::new(format!("\"{etag}\"")),
+        );
+    }
+
+    let changes = sync_calendar(&client, &Href::new("/dav/calendars/user/".into()), &state)
+        .await
+        .expect("Failed to sync calendar");
+
+    let hrefs = |resources: &[aimcal_caldav::CalendarResource]| -> Vec<String> {
+        resources.iter().map(|r| r.href.to_string()).collect()
+    };
+    assert_eq!(hrefs(&changes.added), ["/dav/calendars/user/new.ics"]);
+    assert_eq!(
+        hrefs(&changes.modified),
+        ["/dav/calendars/user/changed.ics"]
+    );
+    assert_eq!(changes.modified[0].etag.as_str(), "\"2\"");
+    assert_eq!(
+        changes.deleted,
+        [Href::new("/dav/calendars/user/gone.ics".into())]
+    );
+
+    // The unchanged resource is not fetched
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .all(|r| r.url.path() != "/dav/calendars/user/same.ics")
+    );
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_list_delegated_calendars() {
+    let mock_server = MockServer::start().await;
+
+    let multistatus = |href: &str, props: &str| {
+        ResponseTemplate::new(207).set_body_raw(
+            format!(
+                "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\" \
+xmlns:CS=\"http://calendarserver.org/ns/\">
+  <D:response>
+    <D:href>{href}</D:href>
+    <D:propstat>
+      <D:prop>{props}</D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>"
+            ),
+            "application/xml",
+        )
+    };
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/dav/calendars/bob/"))
+        .respond_with(multistatus(
+            "/dav/calendars/bob/",
+            "<D:current-user-principal><D:href>/principals/bob/</D:href></D:current-user-principal>",
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PROPFIND"))
+        .and(path("/principals/bob/"))
+        .respond_with(multistatus(
+            "/principals/bob/",
+            "<D:group-membership>\
+               <D:href>/principals/carol/calendar-proxy-write/</D:href>\
+             </D:group-membership>\
+             <CS:calendar-proxy-read-for><D:href>/principals/alice/</D:href></CS:calendar-proxy-read-for>",
+        ))
+        .mount(&mock_server)
+        .await;
+    for user in ["alice", "carol"] {
+        Mock::given(method("PROPFIND"))
+            .and(path(format!("/principals/{user}/")))
+            .respond_with(multistatus(
+                &format!("/principals/{user}/"),
+                &format!(
+                    "<C:calendar-home-set><D:href>/dav/calendars/{user}/</D:href></C:calendar-home-set>"
+                ),
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PROPFIND"))
+            .and(path(format!("/dav/calendars/{user}/")))
+            .and(header("Depth", "1"))
+            .respond_with(multistatus(
+                &format!("/dav/calendars/{user}/work/"),
+                "<D:resourcetype><D:collection/><C:calendar/></D:resourcetype>\
+                 <D:current-user-privilege-set>\
+                   <D:privilege><D:read/></D:privilege><D:privilege><D:write/></D:privilege>\
+                 </D:current-user-privilege-set>",
+            ))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/dav/calendars/bob/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+
+    let client = CalDavClient::new(config).expect("Failed to create client");
+    let calendars = client
+        .list_delegated_calendars()
+        .await
+        .expect("Failed to list delegated calendars");
+
+    let summary: Vec<_> = calendars
+        .iter()
+        .map(|c| {
+            (
+                c.href.as_str(),
+                c.owner.as_ref().map(Href::as_str),
+                c.privileges.is_read_only(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                "/dav/calendars/alice/work/",
+                Some("/principals/alice/"),
+                true
+            ),
+            (
+                "/dav/calendars/carol/work/",
+                Some("/principals/carol/"),
+                false
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_fetch_inbox() {
+    let mock_server = MockServer::start().await;
+
+    let multistatus = |body: &str| {
+        ResponseTemplate::new(207).set_body_raw(
+            format!(
+                "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\">{body}</D:multistatus>"
+            ),
+            "application/xml",
+        )
+    };
+    let response = |href: &str, props: &str| {
+        format!(
+            "<D:response><D:href>{href}</D:href><D:propstat><D:prop>{props}</D:prop>\
+             <D:status>HTTP/1.1 200 OK</D:status></D:propstat></D:response>"
+        )
+    };
+
+    Mock::given(method("PROPFIND"))
+        .and(path("/dav/calendars/bob/"))
+        .respond_with(multistatus(&response(
+            "/dav/calendars/bob/",
+            "<D:current-user-principal><D:href>/principals/bob/</D:href></D:current-user-principal>",
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PROPFIND"))
+        .and(path("/principals/bob/"))
+        .respond_with(multistatus(&response(
+            "/principals/bob/",
+            "<C:schedule-inbox-URL><D:href>/dav/inbox/bob/</D:href></C:schedule-inbox-URL>\
+             <C:schedule-outbox-URL><D:href>/dav/outbox/bob/</D:href></C:schedule-outbox-URL>",
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PROPFIND"))
+        .and(path("/dav/inbox/bob/"))
+        .and(header("Depth", "1"))
+        .respond_with(multistatus(&format!(
+            "{}{}",
+            response(
+                "/dav/inbox/bob/",
+                "<D:resourcetype><D:collection/><C:schedule-inbox/></D:resourcetype>"
+            ),
+            response(
+                "/dav/inbox/bob/invite.ics",
+                "<D:resourcetype/><D:getetag>\"i1\"</D:getetag>"
+            ),
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/dav/inbox/bob/invite.ics"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"i1\"")
+                .set_body_string(
+                    "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//Server//EN\r\n\
+METHOD:REQUEST\r\n\
+BEGIN:VEVENT\r\n\
+UID:meeting@example.com\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+DTSTART:20250110T090000Z\r\n\
+SUMMARY:Planning\r\n\
+ORGANIZER:mailto:alice@example.com\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n",
+                ),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/dav/calendars/bob/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+
+    let client = CalDavClient::new(config).expect("Failed to create client");
+    let scheduling = client
+        .discover_scheduling()
+        .await
+        .expect("Failed to discover scheduling");
+    assert_eq!(scheduling.outbox, Some(Href::from("/dav/outbox/bob/")));
+
+    let messages = client.fetch_inbox().await.expect("Failed to fetch inbox");
+    assert_eq!(messages.len(), 1);
+    let message = &messages[0];
+    assert_eq!(message.resource.href.as_str(), "/dav/inbox/bob/invite.ics");
+    assert_eq!(message.method, Some(MethodValue::Request));
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_update_as_attendee_with_schedule_tag() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/calendars/user/event1.ics"))
+        .and(header("if-schedule-tag-match", "\"s-1\""))
+        .respond_with(
+            ResponseTemplate::new(204)
+                .insert_header("Schedule-Tag", "\"s-1\"")
+                .set_body_string(""),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+
+    let client = CalDavClient::new(config).expect("Failed to create client");
+    let mut ical = ICalendar::new();
+    ical.version = Version::default();
+    ical.prod_id = ProductId {
+        value: ValueText::new("-//Test//CalDAV Client//EN".to_string()),
+        x_parameters: Vec::new(),
+        retained_parameters: Vec::new(),
+        span: (),
+    };
+
+    let response = client
+        .update_as_attendee(
+            &Href::new("/calendars/user/event1.ics".to_string()),
+            &ScheduleTag::new("\"s-1\"".to_string()),
+            &ical,
+        )
+        .await
+        .expect("Failed to update event");
+
+    // The server merged the update, so it sends no ETag
+    assert_eq!(response.etag, None);
+    assert_eq!(response.schedule_tag, Some(ScheduleTag::from("\"s-1\"")));
+}
+
+/// A minimal calendar to upload.
+fn test_calendar() -> ICalendar<String> {
+    let mut ical = ICalendar::new();
+    ical.version = Version::default();
+    ical.prod_id = ProductId {
+        value: ValueText::new("-//Test//CalDAV Client//EN".to_string()),
+        x_parameters: Vec::new(),
+        retained_parameters: Vec::new(),
+        span: (),
+    };
+    ical
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_update_event_with_weak_etag_checks_it_first() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/calendars/user/event1.ics"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "W/\"v1\"")
+                .set_body_string(fmt::format(&test_calendar()).unwrap()),
+        )
+        .mount(&mock_server)
+        .await;
+    // A weak tag never matches If-Match, so the write is conditioned on the resource existing
+    Mock::given(method("PUT"))
+        .and(path("/calendars/user/event1.ics"))
+        .and(header("if-match", "*"))
+        .respond_with(ResponseTemplate::new(204).insert_header("ETag", "W/\"v2\""))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+    let client = CalDavClient::new(config).expect("Failed to create client");
+    let href = Href::new("/calendars/user/event1.ics".to_string());
+
+    let etag = client
+        .update_event(&href, &ETag::new("W/\"v1\"".to_string()), &test_calendar())
+        .await
+        .expect("Failed to update event");
+    assert_eq!(etag.as_str(), "W/\"v2\"");
+
+    // Changed on the server since
+    let err = client
+        .update_event(&href, &ETag::new("W/\"v0\"".to_string()), &test_calendar())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CalDavError::PreconditionFailed(_)), "{err}");
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_update_event_without_etag_is_refused() {
+    let mock_server = MockServer::start().await;
+
+    let config = CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..Default::default()
+    };
+    let client = CalDavClient::new(config).expect("Failed to create client");
+    let href = Href::new("/calendars/user/event1.ics".to_string());
+
+    let err = client
+        .update_event(&href, &ETag::new(String::new()), &test_calendar())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CalDavError::PreconditionFailed(_)), "{err}");
+    let err = client
+        .delete_event(&href, &ETag::new(String::new()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CalDavError::PreconditionFailed(_)), "{err}");
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+/// A calendar-query multistatus holding a todo per UID, truncated with a 507 if `truncated`.
+fn query_multistatus(uids: &[&str], truncated: bool) -> String {
+    let responses: String = uids
+        .iter()
+        .map(|uid| {
+            format!(
+                "<D:response>
+  <D:href>/dav/calendars/user/{uid}.ics</D:href>
+  <D:propstat>
+    <D:prop>
+      <D:getetag>\"{uid}\"</D:getetag>
+      <C:calendar-data>BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\n\
+BEGIN:VTODO\r\nUID:{uid}\r\nDTSTAMP:20260101T000000Z\r\nSUMMARY:Todo\r\nEND:VTODO\r\n\
+END:VCALENDAR\r\n</C:calendar-data>
+    </D:prop>
+    <D:status>HTTP/1.1 200 OK</D:status>
+  </D:propstat>
+</D:response>
+"
+            )
+        })
+        .collect();
+    let truncation = if truncated {
+        "<D:response>
+  <D:href>/dav/calendars/user/</D:href>
+  <D:status>HTTP/1.1 507 Insufficient Storage</D:status>
+</D:response>
+"
+    } else {
+        ""
+    };
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\">
+{responses}{truncation}</D:multistatus>"
+    )
+}
+
+/// A client of `mock_server` that detected calendar-access.
+async fn query_client(mock_server: &MockServer, config: CalDavConfig) -> CalDavClient {
+    Mock::given(method("OPTIONS"))
+        .respond_with(ResponseTemplate::new(200).insert_header("DAV", "1, 2, calendar-access"))
+        .mount(mock_server)
+        .await;
+    let client = CalDavClient::new(CalDavConfig {
+        base_url: mock_server.uri(),
+        calendar_home: "/dav/calendars/user/".to_string(),
+        auth: AuthMethod::None,
+        ..config
+    })
+    .expect("Failed to create client");
+    assert!(client.detect_capabilities().await.unwrap().can_query());
+    client
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_query_pages_through_truncated_results() {
+    // Not pooled, as capabilities are cached per server address
+    let mock_server = MockServer::builder().start().await;
+    let client = query_client(&mock_server, CalDavConfig::default()).await;
+
+    let pages = [
+        (("20260101T000000Z", "20260201T000000Z"), vec!["a"], true),
+        (
+            ("20260101T000000Z", "20260116T120000Z"),
+            vec!["a", "b"],
+            false,
+        ),
+        (
+            ("20260116T120000Z", "20260201T000000Z"),
+            vec!["b", "c"],
+            false,
+        ),
+    ];
+    for ((start, end), uids, truncated) in pages {
+        Mock::given(method("REPORT"))
+            .and(body_string_contains(format!("start=\"{start}\"")))
+            .and(body_string_contains(format!("end=\"{end}\"")))
+            .and(body_string_contains("<D:nresults>2</D:nresults>"))
+            .respond_with(
+                ResponseTemplate::new(207)
+                    .set_body_raw(query_multistatus(&uids, truncated), "application/xml"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let request = CalendarQueryRequest::new()
+        .component("VTODO".to_string())
+        .time_range(
+            "20260101T000000Z".to_string(),
+            Some("20260201T000000Z".to_string()),
+        )
+        .limit(2);
+    let resources = client
+        .query(&Href::new("/dav/calendars/user/".to_string()), &request)
+        .await
+        .expect("Failed to query todos");
+
+    // Those of the truncated page are queried again, and those of both halves kept once
+    let hrefs: Vec<&str> = resources.iter().map(|r| r.href.as_str()).collect();
+    assert_eq!(
+        hrefs,
+        [
+            "/dav/calendars/user/a.ics",
+            "/dav/calendars/user/b.ics",
+            "/dav/calendars/user/c.ics"
+        ]
+    );
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_query_truncated_without_time_range_fails() {
+    let mock_server = MockServer::builder().start().await;
+    let client = query_client(&mock_server, CalDavConfig::default()).await;
+
+    Mock::given(method("REPORT"))
+        .respond_with(
+            ResponseTemplate::new(207)
+                .set_body_raw(query_multistatus(&["a"], true), "application/xml"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let request = CalendarQueryRequest::new()
+        .component("VTODO".to_string())
+        .limit(1);
+    let err = client
+        .query(&Href::new("/dav/calendars/user/".to_string()), &request)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CalDavError::InvalidResponse(_)), "{err}");
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_query_sends_again_without_rejected_limit() {
+    let mock_server = MockServer::builder().start().await;
+    let client = query_client(&mock_server, CalDavConfig::default()).await;
+
+    Mock::given(method("REPORT"))
+        .and(body_string_contains("nresults"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("REPORT"))
+        .respond_with(
+            ResponseTemplate::new(207)
+                .set_body_raw(query_multistatus(&["a", "b"], false), "application/xml"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let request = CalendarQueryRequest::new()
+        .component("VTODO".to_string())
+        .limit(100);
+    let resources = client
+        .query(&Href::new("/dav/calendars/user/".to_string()), &request)
+        .await
+        .expect("Failed to query todos");
+    assert_eq!(resources.len(), 2);
+    // A rejected limit is not a lack of REPORT
+    assert!(client.capabilities().can_query());
+}
+
+#[tokio::test]
+#[ignore = "require network"]
+async fn client_refuses_responses_over_the_size_limit() {
+    let mock_server = MockServer::builder().start().await;
+    let uids: Vec<String> = (0..1000).map(|i| format!("todo-{i}")).collect();
+    let uids: Vec<&str> = uids.iter().map(String::as_str).collect();
+    let body = query_multistatus(&uids, false);
+
+    // Listings are never asked for with Depth: infinity
+    Mock::given(method("PROPFIND"))
+        .and(header("Depth", "1"))
+        .respond_with(ResponseTemplate::new(207).set_body_raw(body.clone(), "application/xml"))
+        .mount(&mock_server)
+        .await;
+
+    let config = CalDavConfig {
+        max_response_bytes: 64 * 1024,
+        ..Default::default()
+    };
+    let client = query_client(&mock_server, config).await;
+    let href = Href::new("/dav/calendars/user/".to_string());
+    let err = client.list_etags(&href).await.unwrap_err();
+    assert!(
+        matches!(err, CalDavError::ResponseTooLarge(65_536)),
+        "{err}"
+    );
+    assert!(err.to_string().contains("time range"));
+
+    Mock::given(method("REPORT"))
+        .respond_with(ResponseTemplate::new(207).set_body_raw(body, "application/xml"))
+        .mount(&mock_server)
+        .await;
+    let request = CalendarQueryRequest::new().component("VTODO".to_string());
+    let err = client.query(&href, &request).await.unwrap_err();
+    assert!(matches!(err, CalDavError::ResponseTooLarge(_)), "{err}");
+
+    // The same responses are read whole under the default limit
+    let client = query_client(&mock_server, CalDavConfig::default()).await;
+    assert_eq!(client.query(&href, &request).await.unwrap().len(), 1000);
+}
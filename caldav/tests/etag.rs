@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Entity tag comparison tests, with the forms servers are seen to send.
+
+use aimcal_caldav::ETag;
+
+fn etag(s: &str) -> ETag {
+    ETag::new(s.to_string())
+}
+
+#[test]
+fn etag_nextcloud_strong_quoted() {
+    // Nextcloud sends the same quoted tag in the ETag header and in getetag
+    let get = etag("\"5f1c6d1e2b3a4\"");
+    let propfind = etag("\"5f1c6d1e2b3a4\"");
+    assert!(!get.is_weak());
+    assert_eq!(get.opaque(), "5f1c6d1e2b3a4");
+    assert!(get.strong_eq(&propfind));
+    assert_eq!(get.header_value(), "\"5f1c6d1e2b3a4\"");
+}
+
+#[test]
+fn etag_icloud_unquoted_in_propfind() {
+    // iCloud leaves the quotes out of getetag, but sends them in the ETag header
+    let get = etag("\"C=1234@U=5678\"");
+    let propfind = etag("C=1234@U=5678");
+    assert!(get.weak_eq(&propfind));
+    assert!(get.strong_eq(&propfind));
+    assert_eq!(propfind.header_value(), "\"C=1234@U=5678\"");
+}
+
+#[test]
+fn etag_radicale_strong_hash() {
+    let get = etag("\"0f3e0c4bbd0d1ac0c44bc6d4b2b1e2a5\"");
+    let changed = etag("\"b3a1e2a5\"");
+    assert!(!get.weak_eq(&changed));
+    assert!(!get.strong_eq(&changed));
+    assert_eq!(get.header_value(), get.as_str());
+}
+
+#[test]
+fn etag_weak_matches_only_weakly() {
+    // Weak tags, e.g. from a proxy compressing responses, are sent back as they came
+    let weak = etag("W/\"abc\"");
+    let strong = etag("\"abc\"");
+    assert!(weak.is_weak());
+    assert_eq!(weak.opaque(), "abc");
+    assert!(weak.weak_eq(&strong));
+    assert!(!weak.strong_eq(&strong));
+    assert!(!weak.strong_eq(&weak));
+    assert_eq!(weak.header_value(), "W/\"abc\"");
+    assert_eq!(etag("W/abc").header_value(), "W/\"abc\"");
+}
+
+#[test]
+fn etag_missing_matches_nothing() {
+    let missing = etag("");
+    assert!(missing.is_missing());
+    assert!(!missing.weak_eq(&missing));
+    assert!(!missing.strong_eq(&missing));
+    assert!(etag("\"\"").is_missing());
+    assert!(!etag("\"\"").weak_eq(&etag("")));
+}
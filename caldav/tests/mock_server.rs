@@ -0,0 +1,346 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Client integration tests against the scenarios of the mock `CalDAV` server.
+
+use aimcal_caldav::{
+    AuthMethod, CalDavClient, CalDavConfig, CalDavError, CalendarQueryRequest, ETag, Href,
+    SyncState, sync_calendar,
+};
+use aimcal_caldav_mock::{CalendarSpec, FaultSpec, MockCalDav, ResourceSpec, Scenario};
+
+const WORK: &str = "/dav/calendars/alice/work/";
+
+fn client(server: &MockCalDav, calendar_home: &str, password: &str) -> CalDavClient {
+    let config = CalDavConfig {
+        base_url: server.uri(),
+        calendar_home: calendar_home.to_string(),
+        auth: AuthMethod::Basic {
+            username: "alice".to_string(),
+            password: password.to_string(),
+        },
+        ..Default::default()
+    };
+    CalDavClient::new(config).expect("Failed to create client")
+}
+
+async fn basic() -> (MockCalDav, CalDavClient) {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let client = client(&server, "/dav/calendars/alice/", "secret");
+    (server, client)
+}
+
+fn state_of(resources: &[aimcal_caldav::CalendarResource]) -> SyncState {
+    let mut state = SyncState::default();
+    for resource in resources {
+        state
+            .resource_etags
+            .insert(resource.href.clone(), resource.etag.clone());
+    }
+    state
+}
+
+fn hrefs(resources: &[aimcal_caldav::CalendarResource]) -> Vec<&str> {
+    let mut hrefs: Vec<_> = resources.iter().map(|r| r.href.as_str()).collect();
+    hrefs.sort_unstable();
+    hrefs
+}
+
+#[tokio::test]
+async fn mock_discovers_calendars_through_well_known_redirect() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let client = client(&server, "/.well-known/caldav", "secret");
+
+    let result = client.discover().await.expect("Failed to discover");
+    assert!(result.supports_calendars);
+    assert_eq!(result.calendar_home.as_str(), "/dav/calendars/alice/");
+    assert_eq!(
+        result.current_user_principal.as_ref().map(Href::as_str),
+        Some("/dav/principals/alice/")
+    );
+
+    let mut calendars = client.list_calendars().await.expect("Failed to list");
+    calendars.sort_by(|a, b| a.href.as_str().cmp(b.href.as_str()));
+    let names: Vec<_> = calendars
+        .iter()
+        .map(|c| c.display_name.as_deref())
+        .collect();
+    assert_eq!(names, [Some("Work"), Some("Team")]);
+    let team = calendars.last().unwrap();
+    assert_eq!(
+        team.owner.as_ref().map(Href::as_str),
+        Some("/dav/principals/bob/")
+    );
+    assert!(team.privileges.read);
+    assert!(!team.privileges.bind);
+    let work = calendars.first().unwrap();
+    assert!(work.privileges.bind);
+    assert_eq!(work.supported_components, ["VEVENT", "VTODO"]);
+}
+
+#[tokio::test]
+async fn mock_rejects_wrong_credentials() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let client = client(&server, "/dav/calendars/alice/", "wrong");
+
+    let err = client.discover().await.unwrap_err();
+    assert!(err.to_string().contains("401"), "{err}");
+}
+
+#[tokio::test]
+async fn mock_full_sync_queries_each_component() {
+    let (_server, client) = basic().await;
+    let caps = client.detect_capabilities().await.unwrap();
+    assert!(caps.can_query());
+
+    let calendar = Href::from(WORK);
+    let events = client
+        .query(
+            &calendar,
+            &CalendarQueryRequest::new().component("VEVENT".into()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        hrefs(&events),
+        [
+            "/dav/calendars/alice/work/review.ics",
+            "/dav/calendars/alice/work/standup.ics"
+        ]
+    );
+    let todos = client.get_pending_todos(&calendar).await.unwrap();
+    assert_eq!(hrefs(&todos), ["/dav/calendars/alice/work/report.ics"]);
+    assert_eq!(todos.first().unwrap().etag.as_str(), "\"report-1\"");
+}
+
+#[tokio::test]
+async fn mock_incremental_sync_fetches_only_changes() {
+    let (server, client) = basic().await;
+    let calendar = Href::from(WORK);
+
+    let changes = sync_calendar(&client, &calendar, &SyncState::default())
+        .await
+        .unwrap();
+    assert_eq!(changes.added.len(), 3);
+    assert!(changes.modified.is_empty() && changes.deleted.is_empty());
+    let state = state_of(&changes.added);
+
+    // Another client edits one object, deletes one and adds one
+    let review = format!("{WORK}review.ics");
+    let data = server.resource(&review).unwrap().data;
+    let review_etag = server.put_resource(
+        &review,
+        &data.replace("Design review", "Design review (moved)"),
+    );
+    assert!(server.delete_resource(&format!("{WORK}report.ics")));
+    let added = format!("{WORK}retro.ics");
+    let added_etag = server.put_resource(
+        &added,
+        &data
+            .replace("UID:review", "UID:retro")
+            .replace("Design review", "Retro"),
+    );
+
+    let changes = sync_calendar(&client, &calendar, &state).await.unwrap();
+    assert_eq!(hrefs(&changes.added), [added.as_str()]);
+    assert_eq!(hrefs(&changes.modified), [review.as_str()]);
+    assert_eq!(changes.added.first().unwrap().etag.as_str(), added_etag);
+    assert_eq!(changes.modified.first().unwrap().etag.as_str(), review_etag);
+    let deleted: Vec<_> = changes.deleted.iter().map(Href::as_str).collect();
+    assert_eq!(deleted, ["/dav/calendars/alice/work/report.ics"]);
+
+    // The unchanged object was fetched once, by the first sync
+    let standup = format!("{WORK}standup.ics");
+    assert_eq!(server.count_requests("GET", &standup).await, 1);
+    assert_eq!(server.count_requests("GET", &review).await, 2);
+}
+
+#[tokio::test]
+async fn mock_update_with_stale_etag_conflicts() {
+    let (server, client) = basic().await;
+    let href = Href::from(format!("{WORK}standup.ics"));
+    let resource = client.get_event(&href).await.unwrap();
+
+    // Another client changes the object first
+    let data = server.resource(&href).unwrap().data;
+    let current = server.put_resource(&href, &data);
+
+    let err = client
+        .update_event(&href, &resource.etag, &resource.data)
+        .await
+        .unwrap_err();
+    match err {
+        CalDavError::PreconditionFailed(etag) => assert_eq!(etag, current),
+        err => panic!("Expected a conflict, got {err}"),
+    }
+
+    let etag = client
+        .update_event(&href, &ETag::new(current), &resource.data)
+        .await
+        .unwrap();
+    assert_eq!(server.resource(&href).unwrap().etag, etag.as_str());
+
+    let stale = ETag::new("\"standup-1\"".to_string());
+    let err = client.delete_event(&href, &stale).await.unwrap_err();
+    assert!(matches!(err, CalDavError::PreconditionFailed(_)), "{err}");
+    client.delete_event(&href, &etag).await.unwrap();
+    assert!(server.resource(&href).is_none());
+}
+
+#[tokio::test]
+async fn mock_syncs_without_report() {
+    let server = MockCalDav::start(Scenario::bundled("no-report").unwrap()).await;
+    let client = client(&server, "/dav/calendars/user/", "unused");
+    let calendar = Href::from("/dav/calendars/user/default/");
+
+    let caps = client.detect_capabilities().await.unwrap();
+    assert!(!caps.can_query());
+    let err = client
+        .query(&calendar, &CalendarQueryRequest::new())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, CalDavError::UnsupportedCapability(_)),
+        "{err}"
+    );
+
+    let changes = sync_calendar(&client, &calendar, &SyncState::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        hrefs(&changes.added),
+        [
+            "/dav/calendars/user/default/event-1.ics",
+            "/dav/calendars/user/default/todo-1.ics"
+        ]
+    );
+    assert_eq!(server.count_requests("REPORT", calendar.as_str()).await, 0);
+}
+
+#[tokio::test]
+async fn mock_falls_back_when_report_is_rejected() {
+    // Advertises REPORT, but rejects it
+    let mut scenario = Scenario::bundled("basic").unwrap();
+    scenario.faults.push(FaultSpec {
+        method: Some("REPORT".to_string()),
+        path: None,
+        status: 501,
+        headers: Default::default(),
+        body: String::new(),
+        times: None,
+    });
+    let server = MockCalDav::start(scenario).await;
+    let client = client(&server, "/dav/calendars/alice/", "secret");
+    let calendar = Href::from(WORK);
+
+    assert!(client.detect_capabilities().await.unwrap().can_query());
+    let err = client
+        .query(&calendar, &CalendarQueryRequest::new())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, CalDavError::UnsupportedCapability(_)),
+        "{err}"
+    );
+    assert!(!client.capabilities().can_query());
+
+    let changes = sync_calendar(&client, &calendar, &SyncState::default())
+        .await
+        .unwrap();
+    assert_eq!(changes.added.len(), 3);
+}
+
+#[tokio::test]
+async fn mock_retries_flaky_server() {
+    let server = MockCalDav::start(Scenario::bundled("flaky").unwrap()).await;
+    let client = client(&server, "/dav/calendars/user/", "unused");
+    let href = Href::from("/dav/calendars/user/default/todo-1.ics");
+
+    let err = client.get_todo(&href).await.unwrap_err();
+    assert!(err.to_string().contains("503"), "{err}");
+    let resource = client.get_todo(&href).await.unwrap();
+    assert_eq!(resource.etag.as_str(), "\"todo-1\"");
+}
+
+#[tokio::test]
+async fn mock_truncated_listing_reports_no_deletions() {
+    let server = MockCalDav::start(Scenario::bundled("truncated").unwrap()).await;
+    let client = client(&server, "/dav/calendars/user/", "unused");
+    let calendar = Href::from("/dav/calendars/user/default/");
+
+    let err = client.list_etags(&calendar).await.unwrap_err();
+    assert!(matches!(err, CalDavError::InvalidResponse(_)), "{err}");
+
+    // Objects left out of the listing must not be taken for deleted ones
+    let mut state = SyncState::default();
+    state.resource_etags.insert(
+        Href::from("/dav/calendars/user/default/todo-3.ics"),
+        ETag::new("\"mock-3\"".to_string()),
+    );
+    assert!(sync_calendar(&client, &calendar, &state).await.is_err());
+}
+
+#[tokio::test]
+async fn mock_full_sync_reuses_connections() {
+    // Three calendars holding 50 events in all
+    let mut scenario = Scenario::bundled("basic").unwrap();
+    let data = scenario
+        .calendar(WORK)
+        .and_then(|c| c.resources.first())
+        .map(|r| r.data.clone())
+        .unwrap();
+    scenario.calendars = (0..3)
+        .map(|c| CalendarSpec {
+            href: format!("/dav/calendars/alice/calendar-{c}/"),
+            display_name: Some(format!("Calendar {c}")),
+            components: vec!["VEVENT".to_string()],
+            owner: None,
+            privileges: vec!["all".to_string()],
+            truncate_after: None,
+            resources: (0..50)
+                .filter(|i| i % 3 == c)
+                .map(|i| ResourceSpec {
+                    name: format!("event-{i}.ics"),
+                    etag: None,
+                    data: data.replace("UID:standup", &format!("UID:event-{i}")),
+                })
+                .collect(),
+        })
+        .collect();
+    let server = MockCalDav::start(scenario).await;
+    let client = client(&server, "/dav/calendars/alice/", "secret");
+
+    client.discover().await.unwrap();
+    let calendars = client.list_calendars().await.unwrap();
+    assert_eq!(calendars.len(), 3);
+
+    // The collections are fetched at once, with clones of the client
+    let sync = |href: Href| {
+        let client = client.clone();
+        async move {
+            let query = client.query(&href, &CalendarQueryRequest::new()).await;
+            let changes = sync_calendar(&client, &href, &SyncState::default()).await;
+            (query.unwrap().len(), changes.unwrap().added.len())
+        }
+    };
+    let mut hrefs = calendars.into_iter().map(|c| c.href);
+    let (a, b, c) = tokio::join!(
+        sync(hrefs.next().unwrap()),
+        sync(hrefs.next().unwrap()),
+        sync(hrefs.next().unwrap()),
+    );
+    assert_eq!(a.0 + b.0 + c.0, 50);
+    assert_eq!(a.1 + b.1 + c.1, 50);
+
+    // Every GET of a resource is a request of its own, but not a connection of its own: about
+    // one connection per concurrent sync is opened, with some slack for a connection that is
+    // not back in the pool yet when the next request starts
+    let requests = server.requests().await.len();
+    assert!(requests > 50, "{requests} requests");
+    let connections = server.connections();
+    assert!(
+        connections <= 6,
+        "{connections} connections for {requests} requests"
+    );
+}
@@ -4,7 +4,7 @@
 
 //! Request building tests.
 
-use aimcal_caldav::{CalendarMultiGetRequest, CalendarQueryRequest, Prop, PropFindRequest};
+use aimcal_caldav::{CalendarMultiGetRequest, CalendarQueryRequest, Depth, Prop, PropFindRequest};
 
 #[test]
 fn request_propfind_builds_xml() {
@@ -80,6 +80,36 @@ fn request_calendar_query_without_component_builds_xml() {
     assert!(!xml.contains("<C:comp-filter name=\"VEVENT\">"));
 }
 
+#[test]
+fn request_calendar_query_with_limit_builds_xml() {
+    let request = CalendarQueryRequest::new()
+        .component("VTODO".to_string())
+        .limit(500);
+    assert_eq!(request.nresults(), Some(500));
+
+    let xml = request.build().expect("Failed to build calendar-query XML");
+
+    assert!(xml.contains("<D:limit>"));
+    assert!(xml.contains("<D:nresults>500</D:nresults>"));
+    assert!(xml.find("</C:filter>") < xml.find("<D:limit>"));
+    assert!(
+        !CalendarQueryRequest::new()
+            .build()
+            .unwrap()
+            .contains("limit")
+    );
+}
+
+#[test]
+fn request_propfind_depth_defaults_to_zero() {
+    let mut request = PropFindRequest::new();
+    assert_eq!(request.depth(), Depth::Zero);
+    assert_eq!(request.depth().header_value(), "0");
+
+    request.set_depth(Depth::One);
+    assert_eq!(request.depth().header_value(), "1");
+}
+
 #[test]
 fn request_calendar_multiget_builds_xml() {
     let mut request = CalendarMultiGetRequest::new();
@@ -131,3 +161,19 @@ fn request_free_busy_query_builds_xml() {
     assert!(xml.contains("end=\"20250131T235959Z\""));
     assert!(xml.contains("</C:free-busy-query>"));
 }
+
+#[test]
+fn request_propfind_delegation_properties_declare_calendarserver_namespace() {
+    let mut request = PropFindRequest::new();
+    request.add_property(Prop::GroupMembership);
+    request.add_property(Prop::CalendarProxyReadFor);
+    request.add_property(Prop::CalendarProxyWriteFor);
+
+    let xml = request.build().expect("Failed to build PROPFIND XML");
+
+    assert!(xml.contains("xmlns:CS=\"http://calendarserver.org/ns/\""));
+    assert!(!xml.contains("xmlns:C="));
+    assert!(xml.contains("<D:group-membership>"));
+    assert!(xml.contains("<CS:calendar-proxy-read-for>"));
+    assert!(xml.contains("</CS:calendar-proxy-write-for>"));
+}
@@ -4,7 +4,11 @@
 
 //! Response parsing tests.
 
-use aimcal_caldav::MultiStatusResponse;
+use std::cell::Cell;
+use std::io::{self, BufReader, Cursor, Read};
+use std::rc::Rc;
+
+use aimcal_caldav::{Href, MultiStatusReader, MultiStatusResponse, Privileges};
 
 #[test]
 fn response_parse_simple_namespace_test() {
@@ -26,6 +30,37 @@ fn response_parse_simple_namespace_test() {
     );
 }
 
+#[test]
+fn response_parse_response_level_status() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\">
+  <D:response>
+    <D:href>/calendars/user/event1.ics</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:getetag>\"12345\"</D:getetag>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/user/</D:href>
+    <D:status>HTTP/1.1 507 Insufficient Storage</D:status>
+  </D:response>
+</D:multistatus>";
+
+    let response = MultiStatusResponse::from_xml(xml).expect("Failed to parse multistatus");
+
+    assert_eq!(response.responses.len(), 2);
+    assert_eq!(response.responses[0].status, None);
+    assert_eq!(
+        response.responses[1].status.as_deref(),
+        Some("HTTP/1.1 507 Insufficient Storage")
+    );
+    assert!(response.responses[1].prop_stats.is_empty());
+}
+
 #[test]
 fn response_parse_multistatus_basic() {
     let xml = "\
@@ -368,3 +403,287 @@ fn response_parse_resourcetype_with_calendar() {
     assert!(response.responses[0].prop_stats[0].props.is_calendar);
     assert!(response.responses[0].prop_stats[0].props.is_collection);
 }
+
+#[test]
+fn response_parse_delegation_properties() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:CS=\"http://calendarserver.org/ns/\">
+  <D:response>
+    <D:href>/principals/users/bob/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:group-membership>
+          <D:href>/principals/users/carol/calendar-proxy-write/</D:href>
+          <D:href>/principals/groups/team/</D:href>
+        </D:group-membership>
+        <CS:calendar-proxy-read-for>
+          <D:href>/principals/users/alice/</D:href>
+        </CS:calendar-proxy-read-for>
+        <CS:calendar-proxy-write-for/>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>";
+
+    let response = MultiStatusResponse::from_xml(xml).expect("Failed to parse multistatus");
+    let props = &response.responses[0].prop_stats[0].props;
+
+    let hrefs = |hrefs: &[Href]| -> Vec<String> { hrefs.iter().map(ToString::to_string).collect() };
+    assert_eq!(
+        hrefs(&props.group_membership),
+        [
+            "/principals/users/carol/calendar-proxy-write/",
+            "/principals/groups/team/"
+        ]
+    );
+    assert_eq!(
+        hrefs(&props.calendar_proxy_read_for),
+        ["/principals/users/alice/"]
+    );
+    assert!(props.calendar_proxy_write_for.is_empty());
+}
+
+#[test]
+fn response_into_collections_reads_owner_and_privileges() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\">
+  <D:response>
+    <D:href>/calendars/alice/shared/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+        <D:owner><D:href>/principals/users/alice/</D:href></D:owner>
+        <D:current-user-privilege-set>
+          <D:privilege><D:read/></D:privilege>
+          <D:privilege><D:read-current-user-privilege-set/></D:privilege>
+        </D:current-user-privilege-set>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/alice/team/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+        <D:current-user-privilege-set>
+          <D:privilege><D:read/></D:privilege>
+          <D:privilege><D:write-content/></D:privilege>
+        </D:current-user-privilege-set>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/alice/unknown/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>";
+
+    let response = MultiStatusResponse::from_xml(xml).expect("Failed to parse multistatus");
+    let collections = response.into_collections();
+
+    assert_eq!(collections.len(), 3);
+    assert_eq!(
+        collections[0].owner.as_ref().unwrap().as_str(),
+        "/principals/users/alice/"
+    );
+    assert_eq!(collections[0].privileges, Privileges::READ_ONLY);
+    assert!(collections[1].owner.is_none());
+    assert_eq!(
+        collections[1].privileges,
+        Privileges {
+            read: true,
+            write_content: true,
+            bind: false,
+            unbind: false,
+        }
+    );
+    assert!(!collections[1].privileges.is_read_only());
+    // Without a privilege set, the collection is assumed writable
+    assert_eq!(collections[2].privileges, Privileges::ALL);
+}
+
+#[test]
+fn response_parse_scheduling_properties() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\" xmlns:C=\"urn:ietf:params:xml:ns:caldav\">
+  <D:response>
+    <D:href>/principals/bob/</D:href>
+    <D:propstat>
+      <D:prop>
+        <C:schedule-inbox-URL><D:href>/dav/calendars/bob/inbox/</D:href></C:schedule-inbox-URL>
+        <C:schedule-outbox-URL><D:href>/dav/calendars/bob/outbox/</D:href></C:schedule-outbox-URL>
+        <C:schedule-tag>\"s-1\"</C:schedule-tag>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>";
+
+    let response = MultiStatusResponse::from_xml(xml).expect("Failed to parse multistatus");
+    let props = &response.responses[0].prop_stats[0].props;
+    assert_eq!(
+        props.schedule_inbox_url,
+        Some(Href::new("/dav/calendars/bob/inbox/".to_string()))
+    );
+    assert_eq!(
+        props.schedule_outbox_url,
+        Some(Href::new("/dav/calendars/bob/outbox/".to_string()))
+    );
+    assert_eq!(
+        props.schedule_tag.as_ref().map(|t| t.as_str()),
+        Some("\"s-1\"")
+    );
+}
+
+#[test]
+fn privileges_from_names_expand_aggregates() {
+    assert_eq!(Privileges::from_names(&["all"]), Privileges::ALL);
+    assert_eq!(Privileges::from_names(&["read", "write"]), Privileges::ALL);
+    assert_eq!(
+        Privileges::from_names(&["read", "read-acl"]),
+        Privileges::READ_ONLY
+    );
+    let privileges = Privileges::from_names(&["read", "bind", "unbind"]);
+    assert!(!privileges.write_content);
+    assert!(privileges.bind && privileges.unbind);
+    assert!(!privileges.is_read_only());
+    assert!(Privileges::from_names::<&str>(&[]).is_read_only());
+    assert!(!Privileges::from_names::<&str>(&[]).read);
+}
+
+/// A multistatus of `entries` responses, generated as it is read.
+struct GeneratedMultiStatus {
+    entries: usize,
+    generated: Rc<Cell<usize>>,
+    chunk: Cursor<Vec<u8>>,
+    finished: bool,
+}
+
+impl GeneratedMultiStatus {
+    fn new(entries: usize, generated: Rc<Cell<usize>>) -> Self {
+        Self {
+            entries,
+            generated,
+            chunk: Cursor::new(Vec::new()),
+            finished: false,
+        }
+    }
+
+    fn next_chunk(&mut self) -> String {
+        let i = self.generated.get();
+        if i == self.entries {
+            self.finished = true;
+            return "</D:multistatus>\n".to_string();
+        }
+        self.generated.set(i + 1);
+        let head = if i == 0 {
+            "<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n<D:multistatus xmlns:D=\"DAV:\">\n"
+        } else {
+            ""
+        };
+        format!(
+            "{head}  <D:response>
+    <D:href>/calendars/user/{i}.ics</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:getetag>\"{i}\"</D:getetag>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+"
+        )
+    }
+}
+
+impl Read for GeneratedMultiStatus {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let len = self.chunk.read(buf)?;
+            if len > 0 || self.finished {
+                return Ok(len);
+            }
+            self.chunk = Cursor::new(self.next_chunk().into_bytes());
+        }
+    }
+}
+
+#[test]
+fn response_reader_streams_large_multistatus() {
+    const ENTRIES: usize = 10_000;
+    let generated = Rc::new(Cell::new(0));
+    let body = BufReader::new(GeneratedMultiStatus::new(ENTRIES, generated.clone()));
+    let mut reader = MultiStatusReader::new(body);
+
+    // The first response is read before most of the body is generated
+    let first = reader.next().unwrap().expect("Failed to read response");
+    assert_eq!(first.href.as_str(), "/calendars/user/0.ics");
+    assert!(
+        generated.get() < 100,
+        "read {} responses ahead",
+        generated.get()
+    );
+
+    let mut count = 1;
+    for (i, response) in (1..).zip(reader) {
+        let response = response.expect("Failed to read response");
+        assert_eq!(response.href.as_str(), format!("/calendars/user/{i}.ics"));
+        let etag = response.prop_stats[0].props.get_etag.as_ref().unwrap();
+        assert_eq!(etag.as_str(), format!("\"{i}\""));
+        count += 1;
+    }
+    assert_eq!(count, ENTRIES);
+    assert_eq!(generated.get(), ENTRIES);
+}
+
+#[test]
+fn response_reader_stops_at_truncated_body() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\">
+  <D:response>
+    <D:href>/calendars/user/event1.ics</D:href>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/user/event2.ics</D:href>";
+
+    let mut reader = MultiStatusReader::new(xml.as_bytes());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+    assert!(MultiStatusResponse::from_xml(xml).is_err());
+}
+
+#[test]
+fn response_is_truncated_with_507_for_the_collection() {
+    let xml = "\
+<?xml version=\"1.0\" encoding=\"utf-8\" ?>
+<D:multistatus xmlns:D=\"DAV:\">
+  <D:response>
+    <D:href>/calendars/user/event1.ics</D:href>
+  </D:response>
+  <D:response>
+    <D:href>/calendars/user/</D:href>
+    <D:status>HTTP/1.1 507 Insufficient Storage</D:status>
+  </D:response>
+</D:multistatus>";
+
+    assert!(MultiStatusResponse::from_xml(xml).unwrap().is_truncated());
+    let complete = xml.replace("507 Insufficient Storage", "200 OK");
+    assert!(
+        !MultiStatusResponse::from_xml(&complete)
+            .unwrap()
+            .is_truncated()
+    );
+}
@@ -4,10 +4,16 @@
 
 use std::sync::OnceLock;
 
-use aimcal_core::{DateTimeAnchor, EventStatus, Id, Kind, Priority, TodoStatus};
-use clap::{Arg, ArgMatches, ValueEnum, arg, value_parser};
+use aimcal_core::{
+    AlarmTrigger, DateRangeAnchor, DateTimeAnchor, EventStatus, Id, Kind, Priority, TodoStatus,
+    parse_signed_duration,
+};
+use clap::{Arg, ArgAction, ArgMatches, ValueEnum, arg, value_parser};
 use clap_num::number_range;
+use jiff::SignedDuration;
 
+use crate::cmd_crypt::THREAT_MODEL;
+use crate::porcelain::{Porcelain, PorcelainVersion};
 use crate::util::OutputFormat;
 
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +32,43 @@ impl CommonArgs {
             .copied()
             .unwrap_or(OutputFormat::Table)
     }
+
+    /// The `--no-interactive` flag of commands that prompt for missing fields.
+    pub fn no_interactive() -> Arg {
+        arg!(--"no-interactive" "Never prompt for missing fields, failing instead")
+    }
+
+    pub fn get_no_interactive(matches: &ArgMatches) -> bool {
+        matches.get_flag("no-interactive")
+    }
+
+    /// The `--porcelain[=VERSION]` and `-z` flags of listing commands.
+    pub fn porcelain() -> [Arg; 2] {
+        [
+            arg!(--porcelain [VERSION] "Print one record per line in a stable format for scripts")
+                .long_help(
+                    "Print one record per line in a stable format for scripts: kind, short id, \
+                     uid, status, priority, date and summary, separated by TABs. A version never \
+                     changes once released, so pin it with `--porcelain=v1`.",
+                )
+                .value_parser(value_parser!(PorcelainVersion))
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("v1")
+                .conflicts_with("output-format"),
+            arg!(z: -z "End porcelain records with NUL instead of LF, leaving summaries unescaped")
+                .requires("porcelain"),
+        ]
+    }
+
+    pub fn get_porcelain(matches: &ArgMatches) -> Option<Porcelain> {
+        matches
+            .get_one::<PorcelainVersion>("porcelain")
+            .map(|&version| Porcelain {
+                version,
+                nul_terminated: matches.get_flag("z"),
+            })
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -87,8 +130,36 @@ impl EventOrTodoArgs {
             .collect()
     }
 
+    pub fn alarm(self) -> Arg {
+        arg!(--alarm <DURATION>)
+            .help(format!(
+                "Reminder before the {} (30m, \"1 day\", -PT15M...)",
+                self.kind_name()
+            ))
+            .value_parser(value_parser!(AlarmTrigger))
+    }
+
+    pub fn no_alarm(self) -> Arg {
+        arg!(--"no-alarm")
+            .help(format!("Without a reminder for the {}", self.kind_name()))
+            .conflicts_with("alarm")
+    }
+
+    /// Gets the reminder, `Some(None)` if disabled with `--no-alarm`.
+    #[expect(clippy::option_option)]
+    pub fn get_alarm(matches: &ArgMatches) -> Option<Option<AlarmTrigger>> {
+        if matches.get_flag("no-alarm") {
+            Some(None)
+        } else {
+            matches.get_one("alarm").copied().map(Some)
+        }
+    }
+
     pub fn description(self) -> Arg {
-        arg!(--description <DESCRIPTION>).help(format!("Description of the {}", self.kind_name()))
+        arg!(--description <DESCRIPTION>).help(format!(
+            "Description of the {}, or - to read it from stdin",
+            self.kind_name()
+        ))
     }
 
     pub fn get_description(matches: &ArgMatches) -> Option<String> {
@@ -113,7 +184,10 @@ impl EventOrTodoArgs {
         } else {
             arg!(summary: -s --summary <SUMMARY>)
         };
-        arg.help(format!("Summary of the {}", self.kind_name()))
+        arg.help(format!(
+            "Summary of the {}, or - to read it from stdin",
+            self.kind_name()
+        ))
     }
 
     pub fn get_summary(matches: &ArgMatches) -> Option<String> {
@@ -133,6 +207,24 @@ impl EventOrTodoArgs {
         matches.get_one("time").cloned()
     }
 
+    /// Filter on custom metadata, which may be repeated to require all of them.
+    pub fn meta(self) -> Arg {
+        arg!(--meta <"KEY=VALUE">)
+            .help(format!(
+                "Only list {}s with this custom metadata, may be repeated",
+                self.kind_name()
+            ))
+            .value_parser(key_value)
+            .action(ArgAction::Append)
+    }
+
+    pub fn get_meta(matches: &ArgMatches) -> Vec<(String, String)> {
+        matches
+            .get_many::<(String, String)>("meta")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default()
+    }
+
     fn kind_name(self) -> &'static str {
         match self.kind {
             Some(Kind::Event) => "event",
@@ -142,6 +234,32 @@ impl EventOrTodoArgs {
     }
 }
 
+/// Arguments of the commands expanding todo templates.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateArgs;
+
+impl TemplateArgs {
+    /// The value of a variable of the template, which may be repeated.
+    pub fn var() -> Arg {
+        arg!(--var <"NAME=VALUE"> "Value of a variable of the template, may be repeated")
+            .value_parser(key_value)
+            .action(ArgAction::Append)
+    }
+
+    pub fn get_vars(matches: &ArgMatches) -> Vec<(String, String)> {
+        matches
+            .get_many::<(String, String)>("var")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn key_value(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got {s:?}"))
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct EventArgs {
     monopolize: bool,
@@ -168,6 +286,17 @@ impl EventArgs {
         matches.get_one("end").cloned()
     }
 
+    pub fn duration(self) -> Arg {
+        arg!(--duration <DURATION>)
+            .help(self.monopolize(&"Duration of the event instead of an end (30m, \"1h 30m\"...)"))
+            .value_parser(parse_signed_duration)
+            .allow_hyphen_values(true)
+    }
+
+    pub fn get_duration(matches: &ArgMatches) -> Option<SignedDuration> {
+        matches.get_one("duration").copied()
+    }
+
     pub fn status(self) -> Arg {
         arg!(--status <STATUS>)
             .help(self.monopolize(&"Status of the event"))
@@ -205,6 +334,26 @@ impl TodoArgs {
         matches.get_one("due").cloned()
     }
 
+    pub fn business_days(self) -> Arg {
+        arg!(--"business-days").help(
+            self.monopolize(&"Count the days of a relative due, such as 3d, in business days"),
+        )
+    }
+
+    pub fn get_business_days(matches: &ArgMatches) -> bool {
+        matches.get_flag("business-days")
+    }
+
+    pub fn estimate(self) -> Arg {
+        arg!(--estimate <DURATION>)
+            .help(self.monopolize(&"How long the todo is estimated to take (90m, \"1h 30m\"...)"))
+            .value_parser(parse_signed_duration)
+    }
+
+    pub fn get_estimate(matches: &ArgMatches) -> Option<SignedDuration> {
+        matches.get_one("estimate").copied()
+    }
+
     pub fn percent_complete(self) -> Arg {
         pub fn from_0_to_100(s: &str) -> Result<u8, String> {
             number_range(s, 0, 100)
@@ -239,6 +388,61 @@ impl TodoArgs {
         matches.get_one("status").copied()
     }
 
+    pub fn encrypt_description(self) -> Arg {
+        let help = self.monopolize(&"Encrypt the description with the configured key");
+        arg!(--"encrypt-description").help(&help).long_help(format!(
+            "{help}\n\n\
+             Needs `key` in the [core.encryption] section, see `aim crypt generate-key`. \
+             Only this todo is encrypted, existing todos are left as they are.\n\n\
+             {THREAT_MODEL}"
+        ))
+    }
+
+    pub fn get_encrypt_description(matches: &ArgMatches) -> bool {
+        matches.get_flag("encrypt-description")
+    }
+
+    pub fn recur_from_completion(self) -> Arg {
+        let help = self.monopolize(&"Advance the todo from the day it is completed if it recurs");
+        arg!(--"from-completion").help(&help).long_help(format!(
+            "{help}\n\n\
+             A weekly todo completed three days late is next due a week after it was completed, \
+             rather than as scheduled. Overrides `recur_from` of the config for this todo."
+        ))
+    }
+
+    pub fn get_recur_from_completion(matches: &ArgMatches) -> bool {
+        matches.get_flag("from-completion")
+    }
+
+    pub fn category(self) -> Arg {
+        arg!(--category <CATEGORY>)
+            .help(self.monopolize(&"Category of the todo, may be repeated"))
+            .long_help(
+                "\
+Category of the todo, may be repeated. The todo takes the defaults of the [core.categories] \
+section of its categories for the priority, reminder and calendar not given, the first category \
+setting one winning.",
+            )
+            .action(ArgAction::Append)
+    }
+
+    pub fn get_categories(matches: &ArgMatches) -> Vec<String> {
+        matches
+            .get_many::<String>("category")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn no_defaults(self) -> Arg {
+        arg!(--"no-defaults")
+            .help(self.monopolize(&"Leave out the defaults of the categories of the todo"))
+    }
+
+    pub fn get_no_defaults(matches: &ArgMatches) -> bool {
+        matches.get_flag("no-defaults")
+    }
+
     fn monopolize(self, help: &impl ToString) -> String {
         if self.monopolize {
             help.to_string()
@@ -248,6 +452,82 @@ impl TodoArgs {
     }
 }
 
+/// Shorthands for a range of days, such as `--week`, or `--due-week` for the due of todos.
+#[derive(Debug, Clone, Copy)]
+pub struct RangeArgs {
+    due: bool,
+}
+
+impl RangeArgs {
+    const GROUP: &str = "range";
+
+    pub const fn new(due: bool) -> Self {
+        Self { due }
+    }
+
+    pub fn args(self) -> [Arg; 5] {
+        let [week, next_week, month, next_month, days] = self.names();
+        let what = if self.due { "Due" } else { "In" };
+        [
+            Arg::new(week)
+                .long(week)
+                .help(format!("{what} this week, from the configured week start"))
+                .action(ArgAction::SetTrue)
+                .group(Self::GROUP),
+            Arg::new(next_week)
+                .long(next_week)
+                .help(format!("{what} next week"))
+                .action(ArgAction::SetTrue)
+                .group(Self::GROUP),
+            Arg::new(month)
+                .long(month)
+                .help(format!("{what} this calendar month"))
+                .action(ArgAction::SetTrue)
+                .group(Self::GROUP),
+            Arg::new(next_month)
+                .long(next_month)
+                .help(format!("{what} next calendar month"))
+                .action(ArgAction::SetTrue)
+                .group(Self::GROUP),
+            Arg::new(days)
+                .long(days)
+                .value_name("N")
+                .help(format!("{what} the N days starting today"))
+                .value_parser(value_parser!(i64).range(1..))
+                .group(Self::GROUP),
+        ]
+    }
+
+    pub fn get_range(self, matches: &ArgMatches) -> Option<DateRangeAnchor> {
+        let [week, next_week, month, next_month, days] = self.names();
+        if matches.get_flag(week) {
+            Some(DateRangeAnchor::this_week())
+        } else if matches.get_flag(next_week) {
+            Some(DateRangeAnchor::next_week())
+        } else if matches.get_flag(month) {
+            Some(DateRangeAnchor::this_month())
+        } else if matches.get_flag(next_month) {
+            Some(DateRangeAnchor::next_month())
+        } else {
+            matches.get_one(days).copied().map(DateRangeAnchor::Days)
+        }
+    }
+
+    const fn names(self) -> [&'static str; 5] {
+        if self.due {
+            [
+                "due-week",
+                "due-next-week",
+                "due-month",
+                "due-next-month",
+                "due-days",
+            ]
+        } else {
+            ["week", "next-week", "month", "next-month", "days"]
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct CalendarArgs {
     monopolize: bool,
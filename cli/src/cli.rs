@@ -2,62 +2,172 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{error::Error, ffi::OsString, path::PathBuf};
+use std::{error::Error, ffi::OsString, path::PathBuf, process::ExitCode, time::Instant};
 
-use aimcal_core::{APP_NAME, Aim};
-use clap::{ArgMatches, Command, ValueHint, arg, builder::styling, crate_version, value_parser};
+use aimcal_core::{APP_NAME, Aim, AimError};
+use clap::{
+    ArgAction, ArgMatches, Command, ValueHint, arg, builder::styling, crate_version, value_parser,
+};
 use colored::Colorize;
 use futures::{FutureExt, future::BoxFuture};
-use tracing::level_filters::LevelFilter;
-use tracing_subscriber::{EnvFilter, Registry, prelude::*};
+use tracing::Instrument;
 
-use crate::cmd_calendar::{CmdCalendarList, CmdCalendarShow};
+use crate::arg::CommonArgs;
+use crate::cmd_alias::{CmdAliasList, config_arg, expand_aliases, profile_arg};
+use crate::cmd_backup::{CmdBackupCreate, CmdBackupRestore, run_scheduled_backup};
+use crate::cmd_calendar::{
+    CmdCalendarDisable, CmdCalendarEnable, CmdCalendarList, CmdCalendarSetDefault, CmdCalendarShow,
+};
+use crate::cmd_checkin::CmdCheckin;
+use crate::cmd_config::CmdConfigCheck;
+use crate::cmd_crypt::{CmdCryptGenerateKey, CmdCryptRekey};
 use crate::cmd_event::{
-    CmdEventDelay, CmdEventEdit, CmdEventList, CmdEventNew, CmdEventReschedule,
+    CmdEventCheckin, CmdEventDelay, CmdEventEdit, CmdEventExDateAdd, CmdEventExDateRemove,
+    CmdEventExport, CmdEventList, CmdEventNew, CmdEventRDateAdd, CmdEventRDateRemove,
+    CmdEventReschedule, CmdEventShow,
 };
 use crate::cmd_generate_completion::CmdGenerateCompletion;
+use crate::cmd_get::{CmdGet, FieldUnset};
+use crate::cmd_housekeeping::{CmdCacheStats, CmdHousekeepingRun};
+use crate::cmd_list::CmdList;
+use crate::cmd_md::CmdMdSync;
+use crate::cmd_open::CmdOpen;
+use crate::cmd_plan::CmdPlan;
+use crate::cmd_profile::{CmdProfileCreate, CmdProfileList, CmdProfileRemove};
+use crate::cmd_review::CmdReview;
+use crate::cmd_rules::{CmdRulesApply, CmdRulesTest};
+use crate::cmd_template::{CmdTemplateList, CmdTemplateShow};
 use crate::cmd_todo::{
-    CmdTodoCancel, CmdTodoDelay, CmdTodoDone, CmdTodoEdit, CmdTodoList, CmdTodoNew,
-    CmdTodoReschedule, CmdTodoUndo,
+    CmdTodoCancel, CmdTodoCapture, CmdTodoDefer, CmdTodoDelay, CmdTodoDone, CmdTodoEdit,
+    CmdTodoExport, CmdTodoGetMeta, CmdTodoList, CmdTodoNew, CmdTodoProgress, CmdTodoReschedule,
+    CmdTodoSetMeta, CmdTodoShow, CmdTodoSnooze, CmdTodoUndo, CmdTodoUnsetMeta, CmdTodoUnwait,
+    CmdTodoWait,
+};
+use crate::cmd_toplevel::{
+    CmdDashboard, CmdDelay, CmdExport, CmdFlush, CmdImport, CmdInbox, CmdReschedule, CmdSync,
 };
-use crate::cmd_toplevel::{CmdDashboard, CmdDelay, CmdFlush, CmdReschedule};
 use crate::cmd_tui::{CmdEdit, CmdNew};
-use crate::config::parse_config;
+use crate::cmd_tz::{CmdTzAudit, CmdTzRebase};
+use crate::config::{Config, ConfigLocation, load_aliases, parse_config};
+use crate::logging::init_tracing;
+use crate::prompt::set_assume_yes;
+use crate::table;
 
 /// Run the AIM command-line interface.
 ///
+/// Errors of the command are printed, and reflected in the returned exit code, see
+/// [`exit_code`].
+///
 /// # Errors
-/// If an error occurs while running the CLI
-pub async fn run() -> Result<(), Box<dyn Error>> {
-    init_tracing()?;
+/// If the CLI cannot be set up
+pub async fn run() -> Result<ExitCode, Box<dyn Error>> {
+    // Parsed first for the verbosity, so the logs of loading the aliases are not seen
+    let parsed = Cli::parse();
+    match &parsed {
+        Ok(cli) => init_tracing(cli.verbose, cli.log_file.as_deref())?,
+        Err(_) => init_tracing(0, None)?,
+    }
 
     // Prevent Ctrl+C from killing the process during interactive prompts (cliclack).
     // Without this handler, the `console` crate's internal `raise(SIGINT)` terminates
     // the process before cliclack can restore the cursor. See fadeevab/cliclack#84.
     ctrlc::set_handler(move || {})?;
 
-    let err = match Cli::parse() {
-        Ok(cli) => match cli.run().await {
-            Ok(()) => return Ok(()),
-            Err(e) => e,
-        },
-        Err(e) => e,
+    let (err, code) = match parsed {
+        Ok(cli) => {
+            let quiet = cli.command.is_quiet();
+            match cli.run().await {
+                Ok(()) => return Ok(ExitCode::SUCCESS),
+                // Scripts read stdout, so errors go to stderr, and unset fields only set the code
+                Err(e) if quiet => {
+                    if !e.is::<FieldUnset>() {
+                        eprintln!("Error: {e}");
+                    }
+                    return Ok(ExitCode::from(exit_code(e.as_ref())));
+                }
+                Err(e) => {
+                    let code = exit_code(e.as_ref());
+                    (e, code)
+                }
+            }
+        }
+        // Errors in the aliases are config errors, the others come from clap
+        Err(e) => {
+            let code = match e.downcast_ref::<AimError>() {
+                Some(_) => exit_code(e.as_ref()),
+                None => EXIT_USAGE,
+            };
+            (e, code)
+        }
     };
     println!("{} {}", "Error:".red(), err);
-    Ok(())
+    if let Some(hint) = err.downcast_ref::<AimError>().and_then(hint) {
+        println!("{} {}", "Hint:".yellow(), hint);
+    }
+    Ok(ExitCode::from(code))
 }
 
-pub fn init_tracing() -> Result<(), Box<dyn Error>> {
-    let stdout_log = tracing_subscriber::fmt::layer().pretty();
-
-    let filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::ERROR.into())
-        .from_env_lossy();
-
-    let subscriber = Registry::default().with(filter).with(stdout_log);
+/// Exit code for errors without a more specific code.
+pub const EXIT_FAILURE: u8 = 1;
+/// Exit code for invalid command-line arguments.
+pub const EXIT_USAGE: u8 = 2;
+/// Exit code when the event, todo or calendar does not exist.
+pub const EXIT_NOT_FOUND: u8 = 3;
+/// Exit code when a short ID matches more than one item.
+pub const EXIT_AMBIGUOUS_ID: u8 = 4;
+/// Exit code for invalid input values.
+pub const EXIT_INVALID_INPUT: u8 = 5;
+/// Exit code when the item was modified elsewhere.
+pub const EXIT_CONFLICT: u8 = 6;
+/// Exit code when a calendar file cannot be parsed.
+pub const EXIT_PARSE: u8 = 7;
+/// Exit code when the calendar store is unreachable or fails.
+pub const EXIT_BACKEND: u8 = 8;
+/// Exit code for an invalid configuration.
+pub const EXIT_CONFIG: u8 = 9;
+
+/// Maps an error returned by a command to the exit code of the process.
+#[must_use]
+pub fn exit_code(err: &(dyn Error + 'static)) -> u8 {
+    match err.downcast_ref::<AimError>() {
+        Some(AimError::NotFound { .. }) => EXIT_NOT_FOUND,
+        Some(AimError::AmbiguousShortId { .. }) => EXIT_AMBIGUOUS_ID,
+        Some(AimError::InvalidInput { .. }) => EXIT_INVALID_INPUT,
+        Some(AimError::Conflict { .. }) => EXIT_CONFLICT,
+        Some(AimError::Parse { .. }) => EXIT_PARSE,
+        Some(AimError::Backend(_)) => EXIT_BACKEND,
+        Some(AimError::Config(_)) => EXIT_CONFIG,
+        _ => EXIT_FAILURE,
+    }
+}
 
-    tracing::subscriber::set_global_default(subscriber)?;
-    Ok(())
+fn hint(err: &AimError) -> Option<&'static str> {
+    match err {
+        AimError::NotFound { .. } => Some("Run `aim dashboard` to see the current short IDs."),
+        AimError::AmbiguousShortId { .. } => {
+            Some("Pass one of the candidate UIDs, or a longer UID prefix, instead.")
+        }
+        AimError::Conflict { .. } => {
+            Some("Run `aim sync` to fetch the latest version, then retry.")
+        }
+        AimError::Parse { .. } => Some("Fix or remove the file, then run the command again."),
+        AimError::Backend(_) if err.is_network() => {
+            Some("Check your network connection and store configuration.")
+        }
+        AimError::Backend(_) => {
+            Some("Check that the calendar directories exist and that aim may write to them.")
+        }
+        AimError::Database { .. } => {
+            Some("Check that the state directory exists and that aim may write to it.")
+        }
+        AimError::SchemaTooNew { .. } => {
+            Some("Pass --read-only-compat to list and show items with this aim anyway.")
+        }
+        AimError::Locked { .. } => {
+            Some("Close the other aim, or remove the lock file if it is no longer running.")
+        }
+        _ => None,
+    }
 }
 
 /// Command-line interface
@@ -66,6 +176,24 @@ pub struct Cli {
     /// Path to the configuration file
     pub config: Option<PathBuf>,
 
+    /// Profile to use, see `aim profile`
+    pub profile: Option<String>,
+
+    /// Never prompt, failing instead where a choice is needed
+    pub yes: bool,
+
+    /// Open a database written by a newer aim for listing, see `--read-only-compat`
+    pub read_only_compat: bool,
+
+    /// How many times `-v` is given, raising the verbosity of the logs of aim
+    pub verbose: u8,
+
+    /// File to append the logs to as JSON lines
+    pub log_file: Option<PathBuf>,
+
+    /// Name of the command, such as `todo new`, for the logs
+    pub command_name: String,
+
     /// The command to execute
     pub command: Commands,
 }
@@ -73,6 +201,7 @@ pub struct Cli {
 impl Cli {
     /// Create the command-line interface
     #[must_use]
+    #[expect(clippy::too_many_lines)]
     pub fn command() -> Command {
         const STYLES: styling::Styles = styling::Styles::styled()
             .header(styling::AnsiColor::Green.on_default().bold())
@@ -85,31 +214,87 @@ impl Cli {
             .author("Zexin Yuan <aim@yzx9.xyz>")
             .version(crate_version!())
             .styles(STYLES)
-            .subcommand_required(false) // allow default to dashboard
+            .subcommand_required(false) // allow default to dashboard or `default_command`
             .arg_required_else_help(false)
             .arg(
                 arg!(-c --config [CONFIG] "Path to the configuration file")
                     .long_help(
                         "\
 Path to the configuration file. Can be specified via AIM_CONFIG environment variable. \
-Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
-%LOCALAPPDATA%/aim/config.toml on Windows.",
+Defaults to the config of the profile in use, $XDG_CONFIG_HOME/aim/config.toml on Linux and \
+MacOS, %LOCALAPPDATA%/aim/config.toml on Windows for the default profile.",
                     )
                     .value_parser(value_parser!(PathBuf))
                     .value_hint(ValueHint::FilePath),
             )
+            .arg(
+                arg!(--profile <PROFILE> "Profile to use, with its own config and state")
+                    .long_help(
+                        "\
+Profile to use, with its own config, database and calendars. Can be specified via AIM_PROFILE \
+environment variable, or with `default_profile` in the config of the default profile. The config \
+of a profile is at profiles/<PROFILE>/config.toml in the config directory.",
+                    )
+                    .conflicts_with("config"),
+            )
+            .arg(
+                arg!(-y --yes "Never prompt, failing instead where a choice is needed")
+                    .long_help(
+                        "\
+Never prompt, failing instead where a choice is needed, such as an id matching more than one \
+item. Prompts are also skipped when the output is not a terminal.",
+                    )
+                    .global(true),
+            )
+            .arg(
+                arg!(-v --verbose "Log more of what aim does, -vv and -vvv for even more")
+                    .long_help(
+                        "\
+Log more of what aim does to stderr: info with -v, debug with -vv and trace with -vvv. Only the \
+logs of aim itself are raised, those of its dependencies stay at warn. Overrides RUST_LOG.",
+                    )
+                    .action(ArgAction::Count)
+                    .global(true),
+            )
+            .arg(
+                arg!(--"log-file" <PATH> "Append the logs to a file as JSON lines")
+                    .long_help(
+                        "\
+Append the logs to a file as JSON lines, each event with its fields and the spans it happened in, \
+such as the command, the calendar synced or the file ingested. The file gets the level of -v, and \
+at least info.",
+                    )
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath)
+                    .global(true),
+            )
+            .arg(
+                arg!(--"read-only-compat" "List items from a database written by a newer aim")
+                    .long_help(
+                        "\
+List items from a database written by a newer aim, instead of refusing it. The database is read \
+as is from an in-memory copy, without syncing the calendars, and nothing is saved to it. Only \
+works with commands that list or show items, and as long as the database still has the columns \
+this aim reads.",
+                    )
+                    .global(true),
+            )
             .subcommand(CmdDashboard::command())
             .subcommand(CmdNew::command())
+            .subcommand(CmdTodoCapture::command())
             .subcommand(CmdEdit::command())
             .subcommand(CmdDelay::command())
             .subcommand(CmdReschedule::command())
             .subcommand(
                 Command::new("calendar")
-                    .about("Manage calendars")
-                    .arg_required_else_help(true)
-                    .subcommand_required(true)
+                    .visible_alias("calendars")
+                    .about("Manage calendars, listing them without a subcommand")
+                    .arg(CommonArgs::output_format())
                     .subcommand(CmdCalendarList::command())
-                    .subcommand(CmdCalendarShow::command()),
+                    .subcommand(CmdCalendarShow::command())
+                    .subcommand(CmdCalendarSetDefault::command())
+                    .subcommand(CmdCalendarEnable::command())
+                    .subcommand(CmdCalendarDisable::command()),
             )
             .subcommand(
                 Command::new("event")
@@ -121,6 +306,25 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
                     .subcommand(CmdEventEdit::command())
                     .subcommand(CmdEventDelay::command())
                     .subcommand(CmdEventReschedule::command())
+                    .subcommand(CmdEventCheckin::command())
+                    .subcommand(
+                        Command::new("exdate")
+                            .about("Manage exception dates of a recurring event")
+                            .arg_required_else_help(true)
+                            .subcommand_required(true)
+                            .subcommand(CmdEventExDateAdd::command())
+                            .subcommand(CmdEventExDateRemove::command()),
+                    )
+                    .subcommand(
+                        Command::new("rdate")
+                            .about("Manage extra occurrence dates of a recurring event")
+                            .arg_required_else_help(true)
+                            .subcommand_required(true)
+                            .subcommand(CmdEventRDateAdd::command())
+                            .subcommand(CmdEventRDateRemove::command()),
+                    )
+                    .subcommand(CmdEventShow::command())
+                    .subcommand(CmdEventExport::command())
                     .subcommand(CmdEventList::command()),
             )
             .subcommand(
@@ -136,20 +340,140 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
                     .subcommand(CmdTodoCancel::command())
                     .subcommand(CmdTodoDelay::command())
                     .subcommand(CmdTodoReschedule::command())
+                    .subcommand(CmdTodoSnooze::command())
+                    .subcommand(CmdTodoDefer::command())
+                    .subcommand(CmdTodoWait::command())
+                    .subcommand(CmdTodoUnwait::command())
+                    .subcommand(CmdTodoProgress::command())
+                    .subcommand(CmdTodoShow::command())
+                    .subcommand(CmdTodoSetMeta::command())
+                    .subcommand(CmdTodoGetMeta::command())
+                    .subcommand(CmdTodoUnsetMeta::command())
+                    .subcommand(CmdTodoExport::command())
                     .subcommand(CmdTodoList::command()),
             )
             .subcommand(CmdTodoDone::command())
+            .subcommand(CmdTodoSnooze::command())
+            .subcommand(CmdTodoDefer::command())
+            .subcommand(CmdTodoWait::command())
+            .subcommand(CmdTodoUnwait::command())
+            .subcommand(CmdTodoProgress::command())
+            .subcommand(CmdGet::command())
+            .subcommand(CmdOpen::command())
+            .subcommand(CmdPlan::command())
+            .subcommand(CmdReview::command())
+            .subcommand(CmdList::command())
+            .subcommand(CmdCheckin::command())
+            .subcommand(CmdSync::command())
+            .subcommand(CmdImport::command())
+            .subcommand(CmdExport::command())
+            .subcommand(CmdInbox::command())
             .subcommand(CmdFlush::command())
+            .subcommand(
+                Command::new("backup")
+                    .about("Back up and restore the database, config and local calendars")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdBackupCreate::command())
+                    .subcommand(CmdBackupRestore::command()),
+            )
+            .subcommand(
+                Command::new("housekeeping")
+                    .about("Archive old items out of the cache, per the retention policy")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdHousekeepingRun::command()),
+            )
+            .subcommand(
+                Command::new("rules")
+                    .about("Apply and test the categorization rules of the config")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdRulesApply::command())
+                    .subcommand(CmdRulesTest::command()),
+            )
+            .subcommand(
+                Command::new("crypt")
+                    .about("Manage the keys of encrypted todo descriptions")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdCryptRekey::command())
+                    .subcommand(CmdCryptGenerateKey::command()),
+            )
+            .subcommand(
+                Command::new("cache")
+                    .about("Inspect the local cache of calendar items")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdCacheStats::command()),
+            )
+            .subcommand(
+                Command::new("templates")
+                    .alias("template")
+                    .about("List and preview the todo templates of the config")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdTemplateList::command())
+                    .subcommand(CmdTemplateShow::command()),
+            )
+            .subcommand(
+                Command::new("tz")
+                    .about("Audit and rebase the times of items after the home time zone changed")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdTzAudit::command())
+                    .subcommand(CmdTzRebase::command()),
+            )
+            .subcommand(
+                Command::new("md")
+                    .about("Mirror todos with Markdown task lists")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdMdSync::command()),
+            )
+            .subcommand(
+                Command::new("config")
+                    .about("Check the config")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdConfigCheck::command()),
+            )
+            .subcommand(
+                Command::new("alias")
+                    .about("Show the command aliases of the config")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdAliasList::command()),
+            )
+            .subcommand(
+                Command::new("profile")
+                    .about("Manage the profiles, each with its own config and state")
+                    .arg_required_else_help(true)
+                    .subcommand_required(true)
+                    .subcommand(CmdProfileList::command())
+                    .subcommand(CmdProfileCreate::command())
+                    .subcommand(CmdProfileRemove::command()),
+            )
             .subcommand(CmdGenerateCompletion::command())
     }
 
-    /// Parse the command-line arguments
+    /// Parse the command-line arguments, expanding the aliases of the config
     ///
     /// # Errors
-    /// If an error occurs while parsing the arguments
+    /// If the aliases are invalid or an error occurs while parsing the arguments
     pub fn parse() -> Result<Self, Box<dyn Error>> {
+        let args: Vec<OsString> = std::env::args_os().collect();
+        let location = ConfigLocation {
+            path: config_arg(&args),
+            profile: profile_arg(&args),
+            read_only_compat: false,
+            read_only: false,
+        };
+        let aliases = load_aliases(location).map_err(|e| AimError::Config(e.to_string()))?;
+        let args = expand_aliases(args, &aliases)?;
+
         let commands = Self::command();
-        let matches = commands.get_matches();
+        let matches = commands.get_matches_from(args);
         Self::from(&matches)
     }
 
@@ -171,19 +495,105 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
     ///
     /// # Errors
     /// If an error occurs while parsing the arguments
+    #[expect(clippy::too_many_lines)]
     pub fn from(matches: &ArgMatches) -> Result<Self, Box<dyn Error>> {
         use Commands::{
-            CalendarList, CalendarShow, Dashboard, Delay, Edit, EventDelay, EventEdit, EventList,
-            EventNew, EventReschedule, Flush, GenerateCompletion, New, Reschedule, TodoCancel,
-            TodoDelay, TodoDone, TodoEdit, TodoList, TodoNew, TodoReschedule, TodoUndo,
+            AliasList, BackupCreate, BackupRestore, CacheStats, CalendarDisable, CalendarEnable,
+            CalendarList, CalendarSetDefault, CalendarShow, Capture, Checkin, ConfigCheck,
+            CryptGenerateKey, CryptRekey, Dashboard, Delay, Edit, EventCheckin, EventDelay,
+            EventEdit, EventExDateAdd, EventExDateRemove, EventExport, EventList, EventNew,
+            EventRDateAdd, EventRDateRemove, EventReschedule, EventShow, Export, Flush,
+            GenerateCompletion, Get, HousekeepingRun, Import, Inbox, List, MdSync, New, Open, Plan,
+            ProfileCreate, ProfileList, ProfileRemove, Reschedule, Review, RulesApply, RulesTest,
+            Sync, TemplateList, TemplateShow, TodoCancel, TodoDefer, TodoDelay, TodoDone, TodoEdit,
+            TodoExport, TodoGetMeta, TodoList, TodoNew, TodoProgress, TodoReschedule, TodoSetMeta,
+            TodoShow, TodoSnooze, TodoUndo, TodoUnsetMeta, TodoUnwait, TodoWait, TzAudit, TzRebase,
         };
         let command = match matches.subcommand() {
             Some((CmdDashboard::NAME, matches)) => Dashboard(CmdDashboard::from(matches)),
             Some((CmdNew::NAME, matches)) => New(CmdNew::from(matches)),
+            Some((CmdTodoCapture::NAME, matches)) => Capture(CmdTodoCapture::from(matches)),
             Some((CmdEdit::NAME, matches)) => Edit(CmdEdit::from(matches)),
             Some((CmdDelay::NAME, matches)) => Delay(CmdDelay::from(matches)),
             Some((CmdReschedule::NAME, matches)) => Reschedule(CmdReschedule::from(matches)),
+            Some((CmdGet::NAME, matches)) => Get(CmdGet::from(matches)),
+            Some((CmdOpen::NAME, matches)) => Open(CmdOpen::from(matches)),
+            Some((CmdPlan::NAME, matches)) => Plan(CmdPlan::from(matches)),
+            Some((CmdReview::NAME, matches)) => Review(CmdReview::from(matches)),
+            Some((CmdList::NAME, matches)) => List(CmdList::from(matches)),
+            Some((CmdCheckin::NAME, matches)) => Checkin(CmdCheckin::from(matches)),
+            Some((CmdSync::NAME, matches)) => Sync(CmdSync::from(matches)),
+            Some((CmdImport::NAME, matches)) => Import(CmdImport::from(matches)),
+            Some((CmdExport::NAME, matches)) => Export(CmdExport::from(matches)),
+            Some((CmdInbox::NAME, matches)) => Inbox(CmdInbox::from(matches)),
             Some((CmdFlush::NAME, matches)) => Flush(CmdFlush::from(matches)),
+            Some(("backup", matches)) => match matches.subcommand() {
+                Some((CmdBackupCreate::NAME, matches)) => {
+                    BackupCreate(CmdBackupCreate::from(matches))
+                }
+                Some((CmdBackupRestore::NAME, matches)) => {
+                    BackupRestore(CmdBackupRestore::from(matches))
+                }
+                _ => unreachable!(),
+            },
+            Some(("housekeeping", matches)) => match matches.subcommand() {
+                Some((CmdHousekeepingRun::NAME, matches)) => {
+                    HousekeepingRun(CmdHousekeepingRun::from(matches))
+                }
+                _ => unreachable!(),
+            },
+            Some(("rules", matches)) => match matches.subcommand() {
+                Some((CmdRulesApply::NAME, matches)) => RulesApply(CmdRulesApply::from(matches)),
+                Some((CmdRulesTest::NAME, matches)) => RulesTest(CmdRulesTest::from(matches)),
+                _ => unreachable!(),
+            },
+            Some(("crypt", matches)) => match matches.subcommand() {
+                Some((CmdCryptRekey::NAME, matches)) => CryptRekey(CmdCryptRekey::from(matches)),
+                Some((CmdCryptGenerateKey::NAME, matches)) => {
+                    CryptGenerateKey(CmdCryptGenerateKey::from(matches))
+                }
+                _ => unreachable!(),
+            },
+            Some(("cache", matches)) => match matches.subcommand() {
+                Some((CmdCacheStats::NAME, matches)) => CacheStats(CmdCacheStats::from(matches)),
+                _ => unreachable!(),
+            },
+            Some(("templates", matches)) => match matches.subcommand() {
+                Some((CmdTemplateList::NAME, matches)) => {
+                    TemplateList(CmdTemplateList::from(matches))
+                }
+                Some((CmdTemplateShow::NAME, matches)) => {
+                    TemplateShow(CmdTemplateShow::from(matches))
+                }
+                _ => unreachable!(),
+            },
+            Some(("tz", matches)) => match matches.subcommand() {
+                Some((CmdTzAudit::NAME, matches)) => TzAudit(CmdTzAudit::from(matches)),
+                Some((CmdTzRebase::NAME, matches)) => TzRebase(CmdTzRebase::from(matches)),
+                _ => unreachable!(),
+            },
+            Some(("config", matches)) => match matches.subcommand() {
+                Some((CmdConfigCheck::NAME, matches)) => ConfigCheck(CmdConfigCheck::from(matches)),
+                _ => unreachable!(),
+            },
+            Some(("alias", matches)) => match matches.subcommand() {
+                Some((CmdAliasList::NAME, matches)) => AliasList(CmdAliasList::from(matches)),
+                _ => unreachable!(),
+            },
+            Some(("profile", matches)) => match matches.subcommand() {
+                Some((CmdProfileList::NAME, matches)) => ProfileList(CmdProfileList::from(matches)),
+                Some((CmdProfileCreate::NAME, matches)) => {
+                    ProfileCreate(CmdProfileCreate::from(matches))
+                }
+                Some((CmdProfileRemove::NAME, matches)) => {
+                    ProfileRemove(CmdProfileRemove::from(matches))
+                }
+                _ => unreachable!(),
+            },
+            Some(("md", matches)) => match matches.subcommand() {
+                Some((CmdMdSync::NAME, matches)) => MdSync(CmdMdSync::from(matches)),
+                _ => unreachable!(),
+            },
             Some(("calendar", matches)) => match matches.subcommand() {
                 Some((CmdCalendarList::NAME, matches)) => {
                     CalendarList(CmdCalendarList::from(matches))
@@ -191,6 +601,16 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
                 Some((CmdCalendarShow::NAME, matches)) => {
                     CalendarShow(CmdCalendarShow::from(matches))
                 }
+                Some((CmdCalendarSetDefault::NAME, matches)) => {
+                    CalendarSetDefault(CmdCalendarSetDefault::from(matches))
+                }
+                Some((CmdCalendarEnable::NAME, matches)) => {
+                    CalendarEnable(CmdCalendarEnable::from(matches))
+                }
+                Some((CmdCalendarDisable::NAME, matches)) => {
+                    CalendarDisable(CmdCalendarDisable::from(matches))
+                }
+                None => CalendarList(CmdCalendarList::from(matches)),
                 _ => unreachable!(),
             },
             Some(("event", matches)) => match matches.subcommand() {
@@ -200,6 +620,29 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
                 Some((CmdEventReschedule::NAME, matches)) => {
                     EventReschedule(CmdEventReschedule::from(matches))
                 }
+                Some((CmdEventCheckin::NAME, matches)) => {
+                    EventCheckin(CmdEventCheckin::from(matches))
+                }
+                Some(("exdate", matches)) => match matches.subcommand() {
+                    Some((CmdEventExDateAdd::NAME, matches)) => {
+                        EventExDateAdd(CmdEventExDateAdd::from(matches))
+                    }
+                    Some((CmdEventExDateRemove::NAME, matches)) => {
+                        EventExDateRemove(CmdEventExDateRemove::from(matches))
+                    }
+                    _ => unreachable!(),
+                },
+                Some(("rdate", matches)) => match matches.subcommand() {
+                    Some((CmdEventRDateAdd::NAME, matches)) => {
+                        EventRDateAdd(CmdEventRDateAdd::from(matches))
+                    }
+                    Some((CmdEventRDateRemove::NAME, matches)) => {
+                        EventRDateRemove(CmdEventRDateRemove::from(matches))
+                    }
+                    _ => unreachable!(),
+                },
+                Some((CmdEventShow::NAME, matches)) => EventShow(CmdEventShow::from(matches)),
+                Some((CmdEventExport::NAME, matches)) => EventExport(CmdEventExport::from(matches)),
                 Some((CmdEventList::NAME, matches)) => EventList(CmdEventList::from(matches)),
                 _ => unreachable!(),
             },
@@ -213,19 +656,55 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
                 Some((CmdTodoReschedule::NAME, matches)) => {
                     TodoReschedule(CmdTodoReschedule::from(matches))
                 }
+                Some((CmdTodoSnooze::NAME, matches)) => TodoSnooze(CmdTodoSnooze::from(matches)),
+                Some((CmdTodoDefer::NAME, matches)) => TodoDefer(CmdTodoDefer::from(matches)),
+                Some((CmdTodoWait::NAME, matches)) => TodoWait(CmdTodoWait::from(matches)),
+                Some((CmdTodoUnwait::NAME, matches)) => TodoUnwait(CmdTodoUnwait::from(matches)),
+                Some((CmdTodoProgress::NAME, matches)) => {
+                    TodoProgress(CmdTodoProgress::from(matches))
+                }
+                Some((CmdTodoShow::NAME, matches)) => TodoShow(CmdTodoShow::from(matches)),
+                Some((CmdTodoSetMeta::NAME, matches)) => TodoSetMeta(CmdTodoSetMeta::from(matches)),
+                Some((CmdTodoGetMeta::NAME, matches)) => TodoGetMeta(CmdTodoGetMeta::from(matches)),
+                Some((CmdTodoUnsetMeta::NAME, matches)) => {
+                    TodoUnsetMeta(CmdTodoUnsetMeta::from(matches))
+                }
+                Some((CmdTodoExport::NAME, matches)) => TodoExport(CmdTodoExport::from(matches)),
                 Some((CmdTodoList::NAME, matches)) => TodoList(CmdTodoList::from(matches)),
                 _ => unreachable!(),
             },
             Some((CmdTodoDone::NAME, matches)) => TodoDone(CmdTodoDone::from(matches)),
+            Some((CmdTodoSnooze::NAME, matches)) => TodoSnooze(CmdTodoSnooze::from(matches)),
+            Some((CmdTodoDefer::NAME, matches)) => TodoDefer(CmdTodoDefer::from(matches)),
+            Some((CmdTodoWait::NAME, matches)) => TodoWait(CmdTodoWait::from(matches)),
+            Some((CmdTodoUnwait::NAME, matches)) => TodoUnwait(CmdTodoUnwait::from(matches)),
+            Some((CmdTodoProgress::NAME, matches)) => TodoProgress(CmdTodoProgress::from(matches)),
             Some((CmdGenerateCompletion::NAME, matches)) => {
                 GenerateCompletion(CmdGenerateCompletion::from(matches))
             }
-            None => Dashboard(CmdDashboard),
+            None => Dashboard(CmdDashboard::default()),
             _ => unreachable!(),
         };
 
         let config = matches.get_one("config").cloned();
-        Ok(Cli { config, command })
+        let profile = matches.get_one("profile").cloned();
+        let yes = matches.get_flag("yes");
+        let read_only_compat = matches.get_flag("read-only-compat");
+        if read_only_compat && !command.is_read_only() {
+            return Err(
+                "--read-only-compat only works with commands that list or show items".into(),
+            );
+        }
+        Ok(Cli {
+            config,
+            profile,
+            yes,
+            read_only_compat,
+            verbose: matches.get_count("verbose"),
+            log_file: matches.get_one("log-file").cloned(),
+            command_name: command_name(matches),
+            command,
+        })
     }
 
     /// Run the command
@@ -233,25 +712,117 @@ Defaults to $XDG_CONFIG_HOME/aim/config.toml on Linux and MacOS, \
     /// # Errors
     /// If an error occurs while running the command
     pub async fn run(self) -> Result<(), Box<dyn Error>> {
-        self.command.run(self.config).await
+        set_assume_yes(self.yes);
+        let location = ConfigLocation {
+            path: self.config,
+            profile: self.profile,
+            read_only_compat: self.read_only_compat,
+            read_only: self.command.is_read_only(),
+        };
+
+        let span = tracing::info_span!("command", name = %self.command_name);
+        let start = Instant::now();
+        let result = self.command.run(location).instrument(span.clone()).await;
+        span.in_scope(|| {
+            tracing::info!(duration = ?start.elapsed(), ok = result.is_ok(), "command finished");
+        });
+        result
+    }
+}
+
+/// The names of the subcommands given, such as `todo new`, or `dashboard` if none.
+fn command_name(matches: &ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut matches = matches;
+    while let Some((name, sub)) = matches.subcommand() {
+        names.push(name);
+        matches = sub;
+    }
+    if names.is_empty() {
+        CmdDashboard::NAME.to_string()
+    } else {
+        names.join(" ")
     }
 }
 
 /// The commands available in the CLI
 #[derive(Debug, Clone)]
 pub enum Commands {
+    /// List the command aliases
+    AliasList(CmdAliasList),
+
+    /// Check the config
+    ConfigCheck(CmdConfigCheck),
+
+    /// List the profiles
+    ProfileList(CmdProfileList),
+
+    /// Create a profile
+    ProfileCreate(CmdProfileCreate),
+
+    /// Remove a profile
+    ProfileRemove(CmdProfileRemove),
+
+    /// Create a backup archive
+    BackupCreate(CmdBackupCreate),
+
+    /// Restore a backup archive
+    BackupRestore(CmdBackupRestore),
+
+    /// Archive old items out of the cache
+    HousekeepingRun(CmdHousekeepingRun),
+
+    /// Show how many items are cached and archived
+    CacheStats(CmdCacheStats),
+
+    /// List the todo templates
+    TemplateList(CmdTemplateList),
+
+    /// Preview the todos a template expands into
+    TemplateShow(CmdTemplateShow),
+
+    /// List the times read differently since the home time zone changed
+    TzAudit(CmdTzAudit),
+
+    /// Rewrite the times written in an old home time zone
+    TzRebase(CmdTzRebase),
+
+    /// Apply the categorization rules again
+    RulesApply(CmdRulesApply),
+
+    /// Show which categorization rules fire for an item
+    RulesTest(CmdRulesTest),
+
+    /// Encrypt the descriptions sealed with a previous key with the current one
+    CryptRekey(CmdCryptRekey),
+
+    /// Print a new random encryption key
+    CryptGenerateKey(CmdCryptGenerateKey),
+
     /// List calendars
     CalendarList(CmdCalendarList),
 
     /// Show detailed calendar information
     CalendarShow(CmdCalendarShow),
 
+    /// Use a calendar for new items
+    CalendarSetDefault(CmdCalendarSetDefault),
+
+    /// Enable a calendar
+    CalendarEnable(CmdCalendarEnable),
+
+    /// Disable a calendar
+    CalendarDisable(CmdCalendarDisable),
+
     /// Show the dashboard
     Dashboard(CmdDashboard),
 
     /// New a event or todo
     New(CmdNew),
 
+    /// Capture a todo into the inbox
+    Capture(CmdTodoCapture),
+
     /// Edit a event or todo
     Edit(CmdEdit),
 
@@ -261,9 +832,42 @@ pub enum Commands {
     /// Reschedule an event or todo based on current time
     Reschedule(CmdReschedule),
 
+    /// Print a single field of an event or todo
+    Get(CmdGet),
+
+    /// Open the link of an event or todo in the browser
+    Open(CmdOpen),
+
+    /// Plan todos into blocks of working time in the calendar
+    Plan(CmdPlan),
+
+    /// Review the open todos one at a time
+    Review(CmdReview),
+
+    /// Show the smart lists, or the items of one
+    List(CmdList),
+
+    /// Check in yesterday's events one at a time
+    Checkin(CmdCheckin),
+
+    /// Synchronize calendars with their stores
+    Sync(CmdSync),
+
+    /// Import the events and todos of an iCalendar file
+    Import(CmdImport),
+
+    /// Export the occurrences of events between two dates as an iCalendar file
+    Export(CmdExport),
+
+    /// List the invitations waiting on the calendar servers
+    Inbox(CmdInbox),
+
     /// Flush the short IDs
     Flush(CmdFlush),
 
+    /// Mirror todos with the tasks of Markdown notes
+    MdSync(CmdMdSync),
+
     /// Add a new event
     EventNew(CmdEventNew),
 
@@ -276,6 +880,27 @@ pub enum Commands {
     /// Reschedule an event based on current time
     EventReschedule(CmdEventReschedule),
 
+    /// Record whether an event was attended
+    EventCheckin(CmdEventCheckin),
+
+    /// Add exception dates to an event
+    EventExDateAdd(CmdEventExDateAdd),
+
+    /// Remove exception dates from an event
+    EventExDateRemove(CmdEventExDateRemove),
+
+    /// Add extra occurrence dates to an event
+    EventRDateAdd(CmdEventRDateAdd),
+
+    /// Remove extra occurrence dates from an event
+    EventRDateRemove(CmdEventRDateRemove),
+
+    /// Show all properties of an event
+    EventShow(CmdEventShow),
+
+    /// Export an event as a standalone iCalendar file
+    EventExport(CmdEventExport),
+
     /// List events
     EventList(CmdEventList),
 
@@ -300,6 +925,36 @@ pub enum Commands {
     /// Reschedule a todo based on current time
     TodoReschedule(CmdTodoReschedule),
 
+    /// Snooze a todo based on original due
+    TodoSnooze(CmdTodoSnooze),
+
+    /// Defer a todo based on current time
+    TodoDefer(CmdTodoDefer),
+
+    /// Mark a todo as waiting on someone
+    TodoWait(CmdTodoWait),
+
+    /// Stop a todo waiting on someone
+    TodoUnwait(CmdTodoUnwait),
+
+    /// Set the percent complete of a todo
+    TodoProgress(CmdTodoProgress),
+
+    /// Show all properties of a todo
+    TodoShow(CmdTodoShow),
+
+    /// Set custom metadata of a todo
+    TodoSetMeta(CmdTodoSetMeta),
+
+    /// Print custom metadata of a todo
+    TodoGetMeta(CmdTodoGetMeta),
+
+    /// Remove custom metadata of a todo
+    TodoUnsetMeta(CmdTodoUnsetMeta),
+
+    /// Export a todo as a standalone iCalendar file
+    TodoExport(CmdTodoExport),
+
     /// List todos
     TodoList(CmdTodoList),
 
@@ -308,34 +963,110 @@ pub enum Commands {
 }
 
 impl Commands {
+    /// Whether the output of the command is meant for scripts, which only it may write to stdout.
+    #[must_use]
+    pub fn is_quiet(&self) -> bool {
+        matches!(self, Commands::Get(_))
+    }
+
+    /// Whether the command only lists or shows items, so it can run with `--read-only-compat`.
+    #[must_use]
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Commands::Dashboard(_)
+                | Commands::CalendarList(_)
+                | Commands::CalendarShow(_)
+                | Commands::CacheStats(_)
+                | Commands::TemplateList(_)
+                | Commands::TemplateShow(_)
+                | Commands::TzAudit(_)
+                | Commands::List(_)
+                | Commands::Get(_)
+                | Commands::Open(_)
+                | Commands::EventShow(_)
+                | Commands::EventExport(_)
+                | Commands::Export(_)
+                | Commands::EventList(_)
+                | Commands::TodoShow(_)
+                | Commands::TodoGetMeta(_)
+                | Commands::TodoExport(_)
+                | Commands::TodoList(_)
+        )
+    }
+
     /// Run the command with the given configuration
     ///
     /// # Errors
     /// If an error occurs while running the command
     #[rustfmt::skip]
     #[tracing::instrument(skip_all, fields(trace_id = %uuid::Uuid::new_v4()))]
-    pub async fn run(self, config: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    pub async fn run(self, config: ConfigLocation) -> Result<(), Box<dyn Error>> {
         use Commands::{
-            CalendarList, CalendarShow, Dashboard, Delay, Edit, EventDelay, EventEdit,
-            EventList, EventNew, EventReschedule, Flush, GenerateCompletion, New, Reschedule,
-            TodoCancel, TodoDelay, TodoDone, TodoEdit, TodoList, TodoNew, TodoReschedule,
-            TodoUndo,
+            AliasList, BackupCreate, BackupRestore, CacheStats, CalendarDisable, CalendarEnable, CalendarList,
+            CalendarSetDefault, CalendarShow, Capture, Checkin, ConfigCheck, CryptGenerateKey, CryptRekey, Dashboard, Delay, Edit,
+            EventCheckin, EventDelay, EventEdit, EventExDateAdd, EventExDateRemove, EventExport, EventList,
+            EventNew, EventRDateAdd, EventRDateRemove, EventReschedule, EventShow, Export, Flush,
+            GenerateCompletion, Get, HousekeepingRun, Import, Inbox, List, MdSync, New, Open, Plan, ProfileCreate, ProfileList, ProfileRemove, Reschedule, Review, RulesApply, RulesTest, Sync, TodoCancel, TodoDefer, TodoDelay,
+            TodoDone, TodoEdit, TodoExport, TodoGetMeta, TodoList, TodoNew, TodoProgress, TodoReschedule,
+            TemplateList, TemplateShow, TodoSetMeta, TodoShow, TodoSnooze, TodoUndo, TodoUnsetMeta, TodoUnwait, TodoWait,
+            TzAudit, TzRebase,
         };
         tracing::info!(?self, "running command");
         match self {
+            AliasList(a)       => a.run(config).await,
+            ConfigCheck(a)     => a.run(config).await,
+            ProfileList(a)     => a.run(config),
+            ProfileCreate(a)   => a.run(),
+            ProfileRemove(a)   => a.run(),
+            BackupCreate(a)    => Self::run_with_config(config, |x, c| a.run(x, c).boxed()).await,
+            BackupRestore(a)   => a.run(config).await,
+            HousekeepingRun(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CacheStats(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TemplateList(a)    => Self::run_with(config, |x| { a.run(x); async { Ok(()) }.boxed() }).await,
+            TemplateShow(a)    => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TzAudit(a)         => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TzRebase(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
+            RulesApply(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            RulesTest(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CryptRekey(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CryptGenerateKey(a) => { a.run(); Ok(()) }
             CalendarList(a)    => Self::run_with(config, |x| a.run(x).boxed()).await,
             CalendarShow(a)    => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CalendarSetDefault(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CalendarEnable(a)  => Self::run_with(config, |x| a.run(x).boxed()).await,
+            CalendarDisable(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
             Dashboard(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
             New(a)             => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Capture(a)         => Self::run_capturing(config, |x| a.run(x).boxed()).await,
             Edit(a)            => Self::run_with(config, |x| a.run(x).boxed()).await,
             Delay(a)           => Self::run_with(config, |x| a.run(x).boxed()).await,
             Reschedule(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Get(a)             => Self::run_quietly(config, |x| a.run(x).boxed()).await,
+            Open(a)            => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Plan(a)            => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Review(a)          => Self::run_with(config, |x| a.run(x).boxed()).await,
+            List(a)            => Self::run_with_config_impl(config, RunMode::Backup, |x, c| a.run(x, c).boxed()).await,
+            Checkin(a)         => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Sync(a) if a.previews() => Self::run_capturing(config, |x| a.run(x).boxed()).await,
+            Sync(a)            => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Import(a)          => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Export(a)          => Self::run_with(config, |x| a.run(x).boxed()).await,
+            Inbox(a)           => Self::run_with(config, |x| a.run(x).boxed()).await,
             Flush(a)           => Self::run_with(config, |x| a.run(x).boxed()).await,
+            MdSync(a)          => Self::run_with(config, |x| a.run(x).boxed()).await,
             EventNew(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
             EventEdit(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
             EventDelay(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
             EventReschedule(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
-            EventList(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventCheckin(a)    => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventExDateAdd(a)  => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventExDateRemove(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventRDateAdd(a)   => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventRDateRemove(a) => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventShow(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventExport(a)     => Self::run_with(config, |x| a.run(x).boxed()).await,
+            EventList(a)       => Self::run_with_config_impl(config, RunMode::Backup, |x, c| a.run(x, c).boxed()).await,
             TodoNew(a)         => Self::run_with(config, |x| a.run(x).boxed()).await,
             TodoEdit(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
             TodoUndo(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
@@ -343,26 +1074,94 @@ impl Commands {
             TodoCancel(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
             TodoDelay(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
             TodoReschedule(a)  => Self::run_with(config, |x| a.run(x).boxed()).await,
-            TodoList(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoSnooze(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoDefer(a)       => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoWait(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoUnwait(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoProgress(a)    => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoShow(a)        => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoSetMeta(a)     => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoGetMeta(a)     => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoUnsetMeta(a)   => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoExport(a)      => Self::run_with(config, |x| a.run(x).boxed()).await,
+            TodoList(a)        => Self::run_with_config_impl(config, RunMode::Backup, |x, c| a.run(x, c).boxed()).await,
             GenerateCompletion(a) => { a.run(); Ok(()) }
         }
     }
 
-    async fn run_with<F>(config: Option<PathBuf>, f: F) -> Result<(), Box<dyn Error>>
+    /// Runs the command, then takes a scheduled backup if one is due.
+    async fn run_with<F>(config: ConfigLocation, f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: for<'a> FnOnce(&'a mut Aim) -> BoxFuture<'a, Result<(), Box<dyn Error>>>,
+    {
+        Self::run_with_config_impl(config, RunMode::Backup, |x, _| f(x)).await
+    }
+
+    /// Runs a command whose output is meant for scripts, so only the command writes to stdout.
+    async fn run_quietly<F>(config: ConfigLocation, f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: for<'a> FnOnce(&'a mut Aim) -> BoxFuture<'a, Result<(), Box<dyn Error>>>,
+    {
+        Self::run_with_config_impl(config, RunMode::Quiet, |x, _| f(x)).await
+    }
+
+    /// Runs a command without synchronizing the stores first, as it only writes to the local cache
+    /// or syncs them itself.
+    async fn run_capturing<F>(config: ConfigLocation, f: F) -> Result<(), Box<dyn Error>>
     where
         F: for<'a> FnOnce(&'a mut Aim) -> BoxFuture<'a, Result<(), Box<dyn Error>>>,
+    {
+        Self::run_with_config_impl(config, RunMode::Capture, |x, _| f(x)).await
+    }
+
+    async fn run_with_config<F>(config: ConfigLocation, f: F) -> Result<(), Box<dyn Error>>
+    where
+        F: for<'a> FnOnce(&'a mut Aim, &'a Config) -> BoxFuture<'a, Result<(), Box<dyn Error>>>,
+    {
+        Self::run_with_config_impl(config, RunMode::Plain, f).await
+    }
+
+    async fn run_with_config_impl<F>(
+        config: ConfigLocation,
+        mode: RunMode,
+        f: F,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        F: for<'a> FnOnce(&'a mut Aim, &'a Config) -> BoxFuture<'a, Result<(), Box<dyn Error>>>,
     {
         tracing::debug!("parsing configuration...");
-        let (core_config, _config) = parse_config(config).await?;
+        let read_only = config.read_only;
+        let (core_config, config) = parse_config(config)
+            .await
+            .map_err(|e| AimError::Config(e.to_string()))?;
+
+        table::set_hyperlinks(config.hyperlinks.enabled());
 
         tracing::debug!("instantiating...");
-        let mut aim = Aim::new(core_config).await?;
+        let mut aim = match mode {
+            RunMode::Capture => Aim::builder(core_config).skip_sync().build().await?,
+            _ if read_only => Aim::builder(core_config).read_only().build().await?,
+            _ => Aim::new(core_config).await?,
+        };
         for notice in aim.startup_notices() {
-            println!("Note: {notice}");
+            match mode {
+                RunMode::Quiet => eprintln!("Note: {notice}"),
+                RunMode::Plain | RunMode::Backup | RunMode::Capture => println!("Note: {notice}"),
+            }
         }
 
         tracing::debug!("running command...");
-        f(&mut aim).await?;
+        f(&mut aim, &config).await?;
+
+        // A database read with `--read-only-compat` is an in-memory copy, not worth a backup
+        if mode == RunMode::Backup && !aim.read_only_compat() {
+            match run_scheduled_backup(&aim, &config).await {
+                Ok(Some(path)) => println!("Note: backup written to {}", path.display()),
+                Ok(None) => {}
+                // The command itself succeeded, so don't fail it
+                Err(e) => println!("{} scheduled backup failed: {e}", "Warning:".yellow()),
+            }
+        }
 
         tracing::debug!("closing...");
         aim.close().await?;
@@ -370,11 +1169,28 @@ impl Commands {
     }
 }
 
+/// What is done around a command besides running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunMode {
+    /// Nothing else.
+    Plain,
+    /// Taking a scheduled backup afterwards if one is due.
+    Backup,
+    /// Writing notes to stderr, leaving stdout to the output of the command.
+    Quiet,
+    /// Skipping the sync of the stores before, as the command only writes to the local cache or
+    /// syncs them itself.
+    Capture,
+}
+
 #[cfg(test)]
 mod tests {
-    use aimcal_core::Id;
+    use aimcal_core::{DateRangeAnchor, DateTimeAnchor, Id, ThisAndFuture, TodoStatus};
 
-    use crate::{cmd_generate_completion::Shell, util::OutputFormat};
+    use crate::event_formatter::EventColumn;
+    use crate::porcelain::{Porcelain, PorcelainVersion};
+    use crate::todo_formatter::TodoColumn;
+    use crate::{cmd_generate_completion::Shell, cmd_todo::Progress, util::OutputFormat};
 
     use super::*;
 
@@ -394,38 +1210,356 @@ mod tests {
     }
 
     #[test]
-    fn parses_dashboard_command() {
-        let args = ["test", "dashboard"];
+    fn parses_verbosity_and_log_file() {
+        let args = ["test", "-vv", "--log-file", "/tmp/aim.jsonl", "todo", "new"];
         let cli = Cli::try_parse_from(args).unwrap();
-        assert!(matches!(cli.command, Commands::Dashboard(_)));
+        assert_eq!(cli.verbose, 2);
+        assert_eq!(cli.log_file, Some(PathBuf::from("/tmp/aim.jsonl")));
+        assert_eq!(cli.command_name, "todo new");
+
+        // Given after the command, as the flag is global
+        let cli = Cli::try_parse_from(["test", "todo", "new", "-vvv"]).unwrap();
+        assert_eq!(cli.verbose, 3);
+
+        let cli = Cli::try_parse_from(["test"]).unwrap();
+        assert_eq!(cli.verbose, 0);
+        assert_eq!(cli.log_file, None);
+        assert_eq!(cli.command_name, "dashboard");
     }
 
     #[test]
-    fn parses_new_command() {
-        let args = ["test", "new"];
+    fn parses_alias_list_command() {
+        let args = ["test", "alias", "list", "--output-format", "json"];
         let cli = Cli::try_parse_from(args).unwrap();
-        assert!(matches!(cli.command, Commands::New(_)));
+        assert!(matches!(
+            cli.command,
+            Commands::AliasList(CmdAliasList {
+                output_format: OutputFormat::Json
+            })
+        ));
     }
 
     #[test]
-    fn parses_add_command() {
-        let args = ["test", "add"];
-        let cli = Cli::try_parse_from(args).unwrap();
-        assert!(matches!(cli.command, Commands::New(_)));
-    }
+    fn parses_list_command() {
+        let cli = Cli::try_parse_from(["test", "list"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::List(CmdList { name: None, .. })
+        ));
+        assert!(cli.command.is_read_only());
 
-    #[test]
-    fn parses_edit_command() {
-        let args = ["test", "edit", "id1"];
+        let args = [
+            "test",
+            "list",
+            "today",
+            "--category",
+            "work",
+            "--category",
+            "urgent",
+            "--calendar",
+            "personal",
+        ];
         let cli = Cli::try_parse_from(args).unwrap();
-        assert!(matches!(cli.command, Commands::Edit(_)));
+        let Commands::List(cmd) = cli.command else {
+            panic!("Expected List command");
+        };
+        assert_eq!(cmd.name.as_deref(), Some("today"));
+        assert_eq!(cmd.categories, ["work", "urgent"]);
+        assert_eq!(cmd.calendar_id.as_deref(), Some("personal"));
     }
 
     #[test]
-    fn parses_flush_command() {
-        let args = ["test", "flush"];
+    fn parses_profile_commands() {
+        let cli = Cli::try_parse_from(["test", "profile", "list"]).unwrap();
+        assert!(matches!(cli.command, Commands::ProfileList(_)));
+
+        let cli = Cli::try_parse_from(["test", "profile", "create", "work"]).unwrap();
+        assert!(matches!(cli.command, Commands::ProfileCreate(a) if a.profile.name() == "work"));
+
+        let args = ["test", "profile", "remove", "work", "--purge"];
         let cli = Cli::try_parse_from(args).unwrap();
-        assert!(matches!(cli.command, Commands::Flush(_)));
+        assert!(matches!(cli.command, Commands::ProfileRemove(a) if a.purge));
+
+        assert!(Cli::try_parse_from(["test", "profile", "create", "all"]).is_err());
+    }
+
+    #[test]
+    fn parses_profile_option() {
+        let cli = Cli::try_parse_from(["test", "--profile", "work", "todo", "list"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert!(matches!(cli.command, Commands::TodoList(_)));
+
+        let args = [
+            "test",
+            "--profile",
+            "work",
+            "--config",
+            "a.toml",
+            "todo",
+            "list",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_read_only_compat_for_listing_commands_only() {
+        let cli = Cli::try_parse_from(["test", "todo", "list"]).unwrap();
+        assert!(!cli.read_only_compat);
+
+        let cli = Cli::try_parse_from(["test", "--read-only-compat", "todo", "list"]).unwrap();
+        assert!(cli.read_only_compat);
+
+        let cli = Cli::try_parse_from(["test", "event", "show", "abc", "--read-only-compat"]);
+        assert!(cli.unwrap().read_only_compat);
+
+        let args = ["test", "--read-only-compat", "done", "abc123"];
+        let err = Cli::try_parse_from(args).unwrap_err();
+        assert!(err.to_string().contains("--read-only-compat"), "{err}");
+    }
+
+    #[test]
+    fn parses_dashboard_command() {
+        let args = ["test", "dashboard"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::Dashboard(_)));
+    }
+
+    #[test]
+    fn parses_new_command() {
+        let args = ["test", "new"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::New(_)));
+    }
+
+    #[test]
+    fn parses_add_command() {
+        let args = ["test", "add"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::New(_)));
+    }
+
+    #[test]
+    fn parses_edit_command() {
+        let args = ["test", "edit", "id1"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::Edit(_)));
+    }
+
+    #[test]
+    fn parses_flush_command() {
+        let args = ["test", "flush"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::Flush(_)));
+    }
+
+    #[test]
+    fn parses_sync_command() {
+        let args = ["test", "sync", "--subscriptions"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Sync(CmdSync {
+                subscriptions: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_inbox_command() {
+        let args = ["test", "inbox"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::Inbox(_)));
+    }
+
+    #[test]
+    fn parses_import_command() {
+        let cli = Cli::try_parse_from(["test", "import", "meeting.ics"]).unwrap();
+        match cli.command {
+            Commands::Import(cmd) => {
+                assert_eq!(cmd.file, PathBuf::from("meeting.ics"));
+                assert_eq!(cmd.calendar_id, None);
+            }
+            _ => panic!("Expected Import command"),
+        }
+    }
+
+    #[test]
+    fn parses_export_command() {
+        let args = [
+            "test",
+            "export",
+            "--expand",
+            "--from",
+            "2025-01-01",
+            "--to",
+            "2025-06-30",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Export(cmd) => {
+                assert_eq!(cmd.limit, aimcal_core::DEFAULT_EXPAND_LIMIT);
+                assert_eq!(cmd.output, None);
+            }
+            _ => panic!("Expected Export command"),
+        }
+
+        let args = [
+            "test",
+            "export",
+            "--from",
+            "2025-01-01",
+            "--to",
+            "2025-06-30",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_backup_create_command() {
+        let cli = Cli::try_parse_from(["test", "backup", "create"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::BackupCreate(CmdBackupCreate { to: None })
+        ));
+
+        let args = ["test", "backup", "create", "--to", "/tmp/aim.tar.zst"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::BackupCreate(cmd) => {
+                assert_eq!(cmd.to, Some(PathBuf::from("/tmp/aim.tar.zst")));
+            }
+            _ => panic!("Expected BackupCreate command"),
+        }
+    }
+
+    #[test]
+    fn parses_housekeeping_and_cache_commands() {
+        let cli = Cli::try_parse_from(["test", "housekeeping", "run"]).unwrap();
+        assert!(matches!(cli.command, Commands::HousekeepingRun(_)));
+
+        let cli = Cli::try_parse_from(["test", "cache", "stats"]).unwrap();
+        assert!(matches!(cli.command, Commands::CacheStats(_)));
+
+        assert!(Cli::try_parse_from(["test", "housekeeping"]).is_err());
+    }
+
+    #[test]
+    fn parses_tz_commands() {
+        let cli = Cli::try_parse_from(["test", "tz", "audit"]).unwrap();
+        let Commands::TzAudit(cmd) = &cli.command else {
+            panic!("Expected TzAudit command");
+        };
+        assert_eq!((cmd.from.as_deref(), cmd.to.as_deref()), (None, None));
+        assert!(cli.command.is_read_only());
+
+        let args = [
+            "test",
+            "tz",
+            "rebase",
+            "--from",
+            "Europe/Berlin",
+            "--to",
+            "Europe/Lisbon",
+            "--floating-only",
+            "--dry-run",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        let Commands::TzRebase(cmd) = &cli.command else {
+            panic!("Expected TzRebase command");
+        };
+        assert_eq!(
+            (cmd.from.as_str(), cmd.to.as_str()),
+            ("Europe/Berlin", "Europe/Lisbon")
+        );
+        assert!(cmd.floating_only && cmd.dry_run);
+        assert!(!cli.command.is_read_only());
+
+        assert!(Cli::try_parse_from(["test", "tz", "rebase", "--to", "Europe/Lisbon"]).is_err());
+        assert!(Cli::try_parse_from(["test", "tz", "audit", "--from", "Mars/Olympus"]).is_err());
+    }
+
+    #[test]
+    fn parses_templates_commands() {
+        let cli = Cli::try_parse_from(["test", "templates", "list"]).unwrap();
+        assert!(matches!(cli.command, Commands::TemplateList(_)));
+        assert!(cli.command.is_read_only());
+
+        let args = [
+            "test",
+            "template",
+            "show",
+            "release",
+            "--var",
+            "version=1.4",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        let Commands::TemplateShow(cmd) = &cli.command else {
+            panic!("Expected TemplateShow command");
+        };
+        assert_eq!(cmd.name, "release");
+        assert_eq!(cmd.vars, [("version".to_string(), "1.4".to_string())]);
+        assert!(cli.command.is_read_only());
+
+        assert!(Cli::try_parse_from(["test", "templates", "show"]).is_err());
+        assert!(
+            Cli::try_parse_from(["test", "templates", "show", "release", "--var", "version"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_rules_commands() {
+        let cli = Cli::try_parse_from(["test", "rules", "apply", "--all"]).unwrap();
+        match cli.command {
+            Commands::RulesApply(cmd) => assert!(cmd.ids.is_empty()),
+            _ => panic!("Expected RulesApply command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "rules", "apply", "1", "abc"]).unwrap();
+        match cli.command {
+            Commands::RulesApply(cmd) => assert_eq!(
+                cmd.ids,
+                vec![
+                    Id::ShortIdOrUid("1".to_string()),
+                    Id::ShortIdOrUid("abc".to_string())
+                ]
+            ),
+            _ => panic!("Expected RulesApply command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "rules", "test", "1"]).unwrap();
+        match cli.command {
+            Commands::RulesTest(cmd) => assert_eq!(cmd.id, Id::ShortIdOrUid("1".to_string())),
+            _ => panic!("Expected RulesTest command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "rules", "apply"]).is_err());
+        assert!(Cli::try_parse_from(["test", "rules", "apply", "1", "--all"]).is_err());
+    }
+
+    #[test]
+    fn parses_md_sync_command() {
+        let cli = Cli::try_parse_from(["test", "md", "sync", "notes"]).unwrap();
+        match cli.command {
+            Commands::MdSync(cmd) => assert_eq!(cmd.dir, PathBuf::from("notes")),
+            _ => panic!("Expected MdSync command"),
+        }
+        assert!(Cli::try_parse_from(["test", "md", "sync"]).is_err());
+    }
+
+    #[test]
+    fn parses_backup_restore_command() {
+        let args = ["test", "backup", "restore", "/tmp/aim.tar.zst"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::BackupRestore(cmd) => {
+                assert_eq!(cmd.file, PathBuf::from("/tmp/aim.tar.zst"));
+            }
+            _ => panic!("Expected BackupRestore command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "backup", "restore"]).is_err());
     }
 
     #[test]
@@ -475,6 +1609,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_event_edit_this_and_future_command() {
+        let args = [
+            "test",
+            "event",
+            "edit",
+            "some_id",
+            "--occurrence",
+            "2025-06-02",
+            "--this-and-future",
+            "--start",
+            "10:00",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::EventEdit(cmd) => {
+                assert!(cmd.occurrence.is_some());
+                assert_eq!(cmd.range, ThisAndFuture::Split);
+                assert_eq!(cmd.start, Some("10:00".to_string()));
+            }
+            _ => panic!("Expected EventEdit command"),
+        }
+
+        let mut args = args.to_vec();
+        args.push("--range-override");
+        let cli = Cli::try_parse_from(&args).unwrap();
+        match cli.command {
+            Commands::EventEdit(cmd) => assert_eq!(cmd.range, ThisAndFuture::Override),
+            _ => panic!("Expected EventEdit command"),
+        }
+
+        // Editing a single occurrence is not supported
+        let args = ["test", "event", "edit", "id", "--occurrence", "2025-06-02"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
     #[test]
     fn parses_event_delay_command() {
         let args = ["test", "event", "delay", "id1", "id2"];
@@ -507,6 +1677,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_event_and_todo_show_commands() {
+        let cli = Cli::try_parse_from(["test", "event", "show", "abc"]).unwrap();
+        match cli.command {
+            Commands::EventShow(cmd) => {
+                assert_eq!(cmd.id, Id::ShortIdOrUid("abc".to_string()));
+                assert!(!cmd.raw);
+            }
+            _ => panic!("Expected EventShow command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "show", "abc", "--raw"]).unwrap();
+        match cli.command {
+            Commands::TodoShow(cmd) => assert!(cmd.raw && !cmd.no_color),
+            _ => panic!("Expected TodoShow command"),
+        }
+
+        let args = ["test", "event", "show", "abc", "--raw", "--no-color"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::EventShow(cmd) => assert!(cmd.raw && cmd.no_color),
+            _ => panic!("Expected EventShow command"),
+        }
+        assert!(Cli::try_parse_from(["test", "event", "show", "abc", "--no-color"]).is_err());
+    }
+
+    #[test]
+    fn parses_event_and_todo_export_commands() {
+        let cli = Cli::try_parse_from([
+            "test",
+            "event",
+            "export",
+            "abc",
+            "--occurrence",
+            "2026-04-06",
+            "-o",
+            "meeting.ics",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::EventExport(cmd) => {
+                assert_eq!(cmd.id, Id::ShortIdOrUid("abc".to_string()));
+                assert!(cmd.occurrence.is_some());
+                assert_eq!(cmd.output, Some(PathBuf::from("meeting.ics")));
+            }
+            _ => panic!("Expected EventExport command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "export", "abc"]).unwrap();
+        match cli.command {
+            Commands::TodoExport(cmd) => {
+                assert!(cmd.occurrence.is_none());
+                assert!(cmd.output.is_none());
+            }
+            _ => panic!("Expected TodoExport command"),
+        }
+    }
+
     #[test]
     fn parses_event_list_command() {
         let args = ["test", "event", "list", "--output-format", "json"];
@@ -517,6 +1745,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_event_list_range_shorthands() {
+        for (flag, range) in [
+            ("--week", DateRangeAnchor::this_week()),
+            ("--next-week", DateRangeAnchor::next_week()),
+            ("--month", DateRangeAnchor::this_month()),
+            ("--next-month", DateRangeAnchor::next_month()),
+        ] {
+            let cli = Cli::try_parse_from(["test", "event", "list", flag]).unwrap();
+            match cli.command {
+                Commands::EventList(cmd) => assert_eq!(cmd.range, Some(range)),
+                _ => panic!("Expected EventList command"),
+            }
+        }
+
+        let cli = Cli::try_parse_from(["test", "event", "list", "--days", "10"]).unwrap();
+        match cli.command {
+            Commands::EventList(cmd) => assert_eq!(cmd.range, Some(DateRangeAnchor::Days(10))),
+            _ => panic!("Expected EventList command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "event", "list", "--week", "--month"]).is_err());
+        assert!(Cli::try_parse_from(["test", "event", "list", "--days", "0"]).is_err());
+    }
+
+    #[test]
+    fn parses_event_exdate_and_rdate_commands() {
+        let args = ["test", "event", "exdate", "add", "id1", "2025-04-18"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::EventExDateAdd(cmd) => {
+                assert_eq!(cmd.id, Id::ShortIdOrUid("id1".to_string()));
+                assert_eq!(cmd.dates, ["2025-04-18".parse::<DateTimeAnchor>().unwrap()]);
+            }
+            _ => panic!("Expected EventExDateAdd command"),
+        }
+
+        let args = ["test", "event", "exdate", "remove", "id1", "2025-04-18"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::EventExDateRemove(_)));
+
+        let args = ["test", "event", "rdate", "add", "id1", "2025-04-18"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::EventRDateAdd(_)));
+
+        let args = ["test", "event", "rdate", "remove", "id1", "2025-04-18"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(matches!(cli.command, Commands::EventRDateRemove(_)));
+    }
+
     #[test]
     fn parses_todo_new_command() {
         let args = ["test", "todo", "new", "a new todo"];
@@ -635,6 +1913,245 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_list_columns() {
+        let args = [
+            "test",
+            "todo",
+            "list",
+            "--columns",
+            "id,summary,due,categories",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(
+                cmd.columns,
+                Some(vec![
+                    TodoColumn::Id,
+                    TodoColumn::Summary,
+                    TodoColumn::Due,
+                    TodoColumn::Categories
+                ])
+            ),
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let args = ["test", "event", "list", "--columns", "time,summary"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::EventList(cmd) => assert_eq!(
+                cmd.columns,
+                Some(vec![EventColumn::TimeSpan, EventColumn::Summary])
+            ),
+            _ => panic!("Expected EventList command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "list"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(cmd.columns, None),
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let args = ["test", "todo", "list", "--columns", "id,nope"];
+        let err = Cli::try_parse_from(args).unwrap_err().to_string();
+        assert!(err.contains("'nope'"), "{err}");
+        assert!(err.contains("categories"), "{err}");
+    }
+
+    #[test]
+    fn parses_list_porcelain() {
+        let cli = Cli::try_parse_from(["test", "todo", "list", "--porcelain"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(cmd.porcelain, Some(Porcelain::default())),
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let args = ["test", "event", "list", "--porcelain=v1", "-z"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::EventList(cmd) => assert_eq!(
+                cmd.porcelain,
+                Some(Porcelain {
+                    version: PorcelainVersion::V1,
+                    nul_terminated: true,
+                })
+            ),
+            _ => panic!("Expected EventList command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "list"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(cmd.porcelain, None),
+            _ => panic!("Expected TodoList command"),
+        }
+    }
+
+    #[test]
+    fn parses_list_porcelain_rejects_invalid_usage() {
+        for args in [
+            ["test", "todo", "list", "--porcelain=v0"].as_slice(),
+            &["test", "todo", "list", "-z"],
+            &[
+                "test",
+                "todo",
+                "list",
+                "--porcelain",
+                "--output-format",
+                "json",
+            ],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn parses_todo_list_include_archived() {
+        let cli = Cli::try_parse_from(["test", "todo", "list"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => {
+                assert!(!cmd.conds.include_archived);
+                assert_eq!(cmd.conds.status, Some(TodoStatus::NeedsAction));
+            }
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "list", "--include-archived"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => {
+                assert!(cmd.conds.include_archived);
+                assert_eq!(cmd.conds.status, None);
+            }
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "event", "list", "--include-archived"]).unwrap();
+        match cli.command {
+            Commands::EventList(cmd) => assert!(cmd.conds.include_archived),
+            _ => panic!("Expected EventList command"),
+        }
+    }
+
+    #[test]
+    fn parses_todo_list_inbox() {
+        let cli = Cli::try_parse_from(["test", "todo", "list", "--inbox"]).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert!(cmd.conds.inbox),
+            _ => panic!("Expected TodoList command"),
+        }
+    }
+
+    #[test]
+    fn parses_capture_command() {
+        let cli = Cli::try_parse_from(["test", "in", "call", "the", "bank"]).unwrap();
+        match cli.command {
+            Commands::Capture(cmd) => assert_eq!(cmd.summary, "call the bank"),
+            _ => panic!("Expected Capture command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "capture", "call the bank"]).unwrap();
+        match cli.command {
+            Commands::Capture(cmd) => assert_eq!(cmd.summary, "call the bank"),
+            _ => panic!("Expected Capture command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "in"]).is_err());
+    }
+
+    #[test]
+    fn parses_yes_flag_anywhere() {
+        let cli = Cli::try_parse_from(["test", "done", "abc123"]).unwrap();
+        assert!(!cli.yes);
+
+        let cli = Cli::try_parse_from(["test", "--yes", "done", "abc123"]).unwrap();
+        assert!(cli.yes);
+
+        let cli = Cli::try_parse_from(["test", "todo", "done", "abc123", "-y"]).unwrap();
+        assert!(cli.yes);
+        assert!(matches!(cli.command, Commands::TodoDone(_)));
+    }
+
+    #[test]
+    fn parses_list_meta_filters() {
+        let args = [
+            "test",
+            "todo",
+            "list",
+            "--meta",
+            "ticket=JIRA-123",
+            "--meta",
+            "note=a=b",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(
+                cmd.conds.meta,
+                [
+                    ("ticket".to_string(), "JIRA-123".to_string()),
+                    ("note".to_string(), "a=b".to_string()),
+                ]
+            ),
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "event", "list", "--meta", "room=4F"]).unwrap();
+        match cli.command {
+            Commands::EventList(cmd) => {
+                assert_eq!(cmd.conds.meta, [("room".to_string(), "4F".to_string())]);
+            }
+            _ => panic!("Expected EventList command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "todo", "list", "--meta", "ticket"]).is_err());
+    }
+
+    #[test]
+    fn parses_todo_meta_commands() {
+        let args = ["test", "todo", "set-meta", "abc", "ticket", "JIRA-123"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoSetMeta(cmd) => {
+                assert_eq!(cmd.id, Id::ShortIdOrUid("abc".to_string()));
+                assert_eq!(cmd.key, "ticket");
+                assert_eq!(cmd.value, "JIRA-123");
+            }
+            _ => panic!("Expected TodoSetMeta command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "get-meta", "abc"]).unwrap();
+        match cli.command {
+            Commands::TodoGetMeta(cmd) => assert_eq!(cmd.key, None),
+            _ => panic!("Expected TodoGetMeta command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "unset-meta", "abc", "ticket"]).unwrap();
+        match cli.command {
+            Commands::TodoUnsetMeta(cmd) => assert_eq!(cmd.key, "ticket"),
+            _ => panic!("Expected TodoUnsetMeta command"),
+        }
+        assert!(Cli::try_parse_from(["test", "todo", "unset-meta", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parses_todo_list_due_range_shorthands() {
+        let args = ["test", "todo", "list", "--due-next-week"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => {
+                assert_eq!(cmd.due_range, Some(DateRangeAnchor::next_week()));
+            }
+            _ => panic!("Expected TodoList command"),
+        }
+
+        let args = ["test", "todo", "list", "--due-days", "3"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoList(cmd) => assert_eq!(cmd.due_range, Some(DateRangeAnchor::Days(3))),
+            _ => panic!("Expected TodoList command"),
+        }
+
+        assert!(Cli::try_parse_from(["test", "todo", "list", "--week"]).is_err());
+    }
+
     #[test]
     fn parses_calendar_list_command() {
         let args = ["test", "calendar", "list", "--output-format", "json"];
@@ -642,6 +2159,26 @@ mod tests {
         assert!(matches!(cli.command, Commands::CalendarList(_)));
     }
 
+    #[test]
+    fn parses_calendars_alias_without_subcommand_as_list() {
+        let args = ["test", "calendars", "--output-format", "json"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::CalendarList(cmd) => assert_eq!(cmd.output_format, OutputFormat::Json),
+            _ => panic!("Expected CalendarList command"),
+        }
+    }
+
+    #[test]
+    fn parses_calendar_management_commands() {
+        let cli = Cli::try_parse_from(["test", "calendars", "set-default", "work"]).unwrap();
+        assert!(matches!(cli.command, Commands::CalendarSetDefault(cmd) if cmd.id == "work"));
+        let cli = Cli::try_parse_from(["test", "calendar", "enable", "work"]).unwrap();
+        assert!(matches!(cli.command, Commands::CalendarEnable(cmd) if cmd.id == "work"));
+        let cli = Cli::try_parse_from(["test", "calendar", "disable", "work"]).unwrap();
+        assert!(matches!(cli.command, Commands::CalendarDisable(cmd) if cmd.id == "work"));
+    }
+
     #[test]
     fn parses_calendar_show_command() {
         let args = [
@@ -678,6 +2215,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_snooze_command() {
+        let args = ["test", "snooze", "3", "7", "9", "1w"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoSnooze(cmd) => {
+                let expected_ids = [
+                    Id::ShortIdOrUid("3".to_string()),
+                    Id::ShortIdOrUid("7".to_string()),
+                    Id::ShortIdOrUid("9".to_string()),
+                ];
+                assert_eq!(cmd.ids, expected_ids);
+                assert_eq!(cmd.time, DateTimeAnchor::InDays(7));
+            }
+            _ => panic!("Expected TodoSnooze command"),
+        }
+    }
+
+    #[test]
+    fn parses_wait_and_unwait_commands() {
+        let args = ["test", "wait", "id1", "--for", "Alice", "--until", "friday"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoWait(cmd) => {
+                assert_eq!(cmd.ids, [Id::ShortIdOrUid("id1".to_string())]);
+                assert_eq!(cmd.on, "Alice");
+            }
+            _ => panic!("Expected TodoWait command"),
+        }
+
+        let cli = Cli::try_parse_from(["test", "todo", "unwait", "id1"]).unwrap();
+        match cli.command {
+            Commands::TodoUnwait(cmd) => assert_eq!(cmd.ids, [Id::ShortIdOrUid("id1".to_string())]),
+            _ => panic!("Expected TodoUnwait command"),
+        }
+    }
+
+    #[test]
+    fn parses_defer_command() {
+        let args = ["test", "defer", "id1", "tomorrow"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoDefer(cmd) => {
+                assert_eq!(cmd.ids, [Id::ShortIdOrUid("id1".to_string())]);
+                assert_eq!(cmd.time, DateTimeAnchor::tomorrow());
+            }
+            _ => panic!("Expected TodoDefer command"),
+        }
+    }
+
+    #[test]
+    fn parses_progress_command() {
+        let args = ["test", "progress", "id1", "-10"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::TodoProgress(cmd) => {
+                assert_eq!(cmd.id, Id::ShortIdOrUid("id1".to_string()));
+                assert_eq!(cmd.progress, Progress::Change(-10));
+            }
+            _ => panic!("Expected TodoProgress command"),
+        }
+    }
+
     #[test]
     fn parses_generate_completion_command() {
         let args = ["test", "generate-completion", "zsh"];
@@ -687,4 +2287,28 @@ mod tests {
             _ => panic!("Expected GenerateCompletion command"),
         }
     }
+
+    #[test]
+    fn maps_errors_to_exit_codes() {
+        let err: Box<dyn Error> = Box::new(AimError::NotFound {
+            kind: "Todo",
+            id: "42".to_string(),
+        });
+        assert_eq!(exit_code(err.as_ref()), EXIT_NOT_FOUND);
+
+        let err: Box<dyn Error> = Box::new(AimError::Config("missing calendar".to_string()));
+        assert_eq!(exit_code(err.as_ref()), EXIT_CONFIG);
+
+        let err: Box<dyn Error> = "something went wrong".into();
+        assert_eq!(exit_code(err.as_ref()), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn hints_local_store_errors_at_the_directories() {
+        let err = AimError::from(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert_eq!(
+            hint(&err),
+            Some("Check that the calendar directories exist and that aim may write to them.")
+        );
+    }
 }
@@ -0,0 +1,367 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use aimcal_core::AimError;
+use clap::{ArgMatches, Command};
+
+use crate::arg::CommonArgs;
+use crate::config::{Aliases, ConfigLocation, parse_config};
+use crate::util::OutputFormat;
+
+/// Maximum number of aliases expanded one into another, to stop aliases that refer to each other.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdAliasList {
+    pub output_format: OutputFormat,
+}
+
+impl CmdAliasList {
+    pub const NAME: &str = "list";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("List the aliases defined in the config")
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    /// Lists the aliases without opening [`Aim`](aimcal_core::Aim), which they don't need.
+    pub async fn run(self, config: ConfigLocation) -> Result<(), Box<dyn Error>> {
+        let (_, config) = parse_config(config)
+            .await
+            .map_err(|e| AimError::Config(e.to_string()))?;
+
+        match self.output_format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&config.aliases)?),
+            OutputFormat::Table => println!("{}", format_aliases(&config.aliases)),
+        }
+        Ok(())
+    }
+}
+
+fn format_aliases(aliases: &Aliases) -> String {
+    let width = aliases.commands.keys().map(String::len).max().unwrap_or(0);
+    let mut lines: Vec<_> = aliases
+        .commands
+        .iter()
+        .map(|(name, args)| format!("{name:width$}  {}", join_args(args)))
+        .collect();
+    if lines.is_empty() {
+        lines.push("No aliases defined.".to_string());
+    }
+    if let Some(args) = &aliases.default_command {
+        lines.push(String::new());
+        lines.push(format!("Default command: {}", join_args(args)));
+    }
+    lines.join("\n")
+}
+
+/// Joins arguments into a line, quoting the ones that would not be read back as one word.
+fn join_args(args: &[String]) -> String {
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || "'\"\\".contains(c)) {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            } else {
+                arg.clone()
+            }
+        })
+        .collect();
+    args.join(" ")
+}
+
+/// Expands the command of the arguments if it is an alias, keeping the arguments after it.
+///
+/// Without a command, the default command is used if there is one. Arguments that start with an
+/// option other than `--config` and `--profile` are left as is, since they are not a command.
+///
+/// # Errors
+/// If aliases expand into each other more than [`MAX_ALIAS_DEPTH`] times.
+pub fn expand_aliases(
+    mut args: Vec<OsString>,
+    aliases: &Aliases,
+) -> Result<Vec<OsString>, Box<dyn Error>> {
+    let Some(pos) = command_position(&args) else {
+        return Ok(args);
+    };
+    if pos >= args.len() {
+        match &aliases.default_command {
+            Some(default) => args.extend(default.iter().map(OsString::from)),
+            None => return Ok(args),
+        }
+    }
+
+    let mut chain: Vec<&str> = Vec::new();
+    while let Some(expansion) = args
+        .get(pos)
+        .and_then(|arg| arg.to_str())
+        .and_then(|name| aliases.commands.get_key_value(name))
+    {
+        let (name, expansion) = expansion;
+        chain.push(name);
+        if chain.len() > MAX_ALIAS_DEPTH {
+            return Err(AimError::Config(format!(
+                "alias `{}` expands more than {MAX_ALIAS_DEPTH} times, it may refer to itself: {} -> ...",
+                chain.first().copied().unwrap_or_default(),
+                chain.join(" -> "),
+            ))
+            .into());
+        }
+        args.splice(pos..=pos, expansion.iter().map(OsString::from));
+    }
+    Ok(args)
+}
+
+/// Index of the command in the arguments, skipping the program name, `--config`, `--profile`,
+/// `-v` and `--log-file`.
+///
+/// Returns `None` if an option other than those comes first.
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let mut pos = 1;
+    while let Some(arg) = args.get(pos) {
+        let arg = arg.to_str()?;
+        match arg {
+            "-c" | "--config" => {
+                // The value of `--config` is optional
+                let has_value = args
+                    .get(pos + 1)
+                    .and_then(|a| a.to_str())
+                    .is_some_and(|a| !a.starts_with('-'));
+                pos += if has_value { 2 } else { 1 };
+            }
+            "--profile" | "--log-file" => pos += 2,
+            _ if arg.starts_with("--config=")
+                || arg.starts_with("-c")
+                || arg.starts_with("--profile=")
+                || arg.starts_with("--log-file=")
+                || arg == "--verbose"
+                || is_verbose_flags(arg) =>
+            {
+                pos += 1;
+            }
+            _ if arg.starts_with('-') => return None,
+            _ => return Some(pos),
+        }
+    }
+    Some(pos)
+}
+
+/// Whether `arg` is `-v` given once or more, such as `-vvv`.
+fn is_verbose_flags(arg: &str) -> bool {
+    arg.strip_prefix('-')
+        .is_some_and(|flags| !flags.is_empty() && flags.bytes().all(|b| b == b'v'))
+}
+
+/// The value of `--config` given in the arguments, if any.
+pub fn config_arg(args: &[OsString]) -> Option<PathBuf> {
+    let end = command_position(args).unwrap_or(args.len());
+    let mut iter = args.iter().take(end).skip(1);
+    while let Some(arg) = iter.next() {
+        let text = arg.to_str()?;
+        match text {
+            "-c" | "--config" => {
+                return iter
+                    .next()
+                    .filter(|a| a.to_str().is_some_and(|a| !a.starts_with('-')))
+                    .map(PathBuf::from);
+            }
+            _ => {
+                if let Some(path) = text
+                    .strip_prefix("--config=")
+                    .or_else(|| text.strip_prefix("-c"))
+                {
+                    return Some(PathBuf::from(path.strip_prefix('=').unwrap_or(path)));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The value of `--profile` given in the arguments, if any.
+pub fn profile_arg(args: &[OsString]) -> Option<String> {
+    let end = command_position(args).unwrap_or(args.len());
+    let mut iter = args.iter().take(end).skip(1);
+    while let Some(arg) = iter.next() {
+        let text = arg.to_str()?;
+        if text == "--profile" {
+            return iter.next()?.to_str().map(ToString::to_string);
+        }
+        if let Some(name) = text.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn aliases(commands: &[(&str, &[&str])], default_command: Option<&[&str]>) -> Aliases {
+        let to_args = |args: &[&str]| args.iter().map(ToString::to_string).collect();
+        Aliases {
+            commands: commands
+                .iter()
+                .map(|(name, args)| ((*name).to_string(), to_args(args)))
+                .collect::<BTreeMap<_, _>>(),
+            default_command: default_command.map(to_args),
+        }
+    }
+
+    fn expand(args: &[&str], aliases: &Aliases) -> Result<Vec<String>, Box<dyn Error>> {
+        let args = args.iter().map(OsString::from).collect();
+        let expanded = expand_aliases(args, aliases)?;
+        Ok(expanded
+            .into_iter()
+            .map(|a| a.into_string().unwrap())
+            .collect())
+    }
+
+    #[test]
+    fn expands_alias_and_appends_args() {
+        let aliases = aliases(&[("tl", &["todo", "list", "--status", "all"])], None);
+        assert_eq!(
+            expand(&["aim", "tl", "+work"], &aliases).unwrap(),
+            ["aim", "todo", "list", "--status", "all", "+work"]
+        );
+        assert_eq!(
+            expand(&["aim", "-c", "config.toml", "tl"], &aliases).unwrap(),
+            [
+                "aim",
+                "-c",
+                "config.toml",
+                "todo",
+                "list",
+                "--status",
+                "all"
+            ]
+        );
+        assert_eq!(
+            expand(&["aim", "-vv", "--log-file", "aim.jsonl", "tl"], &aliases).unwrap(),
+            [
+                "aim",
+                "-vv",
+                "--log-file",
+                "aim.jsonl",
+                "todo",
+                "list",
+                "--status",
+                "all"
+            ]
+        );
+        assert_eq!(
+            expand(&["aim", "todo", "tl"], &aliases).unwrap(),
+            ["aim", "todo", "tl"]
+        );
+        assert_eq!(
+            expand(&["aim", "--help", "tl"], &aliases).unwrap(),
+            ["aim", "--help", "tl"]
+        );
+    }
+
+    #[test]
+    fn expands_nested_aliases_and_default_command() {
+        let aliases = aliases(
+            &[("tl", &["todo", "list"]), ("w", &["tl", "+work"])],
+            Some(&["w"]),
+        );
+        assert_eq!(
+            expand(&["aim", "w", "-v"], &aliases).unwrap(),
+            ["aim", "todo", "list", "+work", "-v"]
+        );
+        assert_eq!(
+            expand(&["aim"], &aliases).unwrap(),
+            ["aim", "todo", "list", "+work"]
+        );
+        assert_eq!(
+            expand(&["aim", "--config=config.toml"], &aliases).unwrap(),
+            ["aim", "--config=config.toml", "todo", "list", "+work"]
+        );
+
+        let aliases = self::aliases(&[], None);
+        assert_eq!(expand(&["aim"], &aliases).unwrap(), ["aim"]);
+    }
+
+    #[test]
+    fn rejects_recursive_aliases() {
+        let aliases = aliases(&[("a", &["b"]), ("b", &["a", "x"])], None);
+        let err = expand(&["aim", "a"], &aliases).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("alias `a` expands more than 16 times")
+        );
+        assert!(err.to_string().contains("a -> b -> a"));
+    }
+
+    #[test]
+    fn finds_config_arg() {
+        let args = |args: &[&str]| -> Vec<OsString> { args.iter().map(OsString::from).collect() };
+        assert_eq!(
+            config_arg(&args(&["aim", "-c", "a.toml", "tl"])),
+            Some(PathBuf::from("a.toml"))
+        );
+        assert_eq!(
+            config_arg(&args(&["aim", "--config=a.toml"])),
+            Some(PathBuf::from("a.toml"))
+        );
+        assert_eq!(
+            config_arg(&args(&["aim", "-ca.toml"])),
+            Some(PathBuf::from("a.toml"))
+        );
+        assert_eq!(config_arg(&args(&["aim", "tl", "-c", "a.toml"])), None);
+    }
+
+    #[test]
+    fn finds_profile_arg_and_skips_it_for_the_command() {
+        let args = |args: &[&str]| -> Vec<OsString> { args.iter().map(OsString::from).collect() };
+        assert_eq!(
+            profile_arg(&args(&["aim", "--profile", "work", "tl"])),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            profile_arg(&args(&["aim", "-c", "a.toml", "--profile=home"])),
+            Some("home".to_string())
+        );
+        assert_eq!(
+            profile_arg(&args(&["aim", "tl", "--profile", "work"])),
+            None
+        );
+
+        let aliases = aliases(&[("tl", &["todo", "list"])], None);
+        assert_eq!(
+            expand(&["aim", "--profile", "work", "tl"], &aliases).unwrap(),
+            ["aim", "--profile", "work", "todo", "list"]
+        );
+    }
+
+    #[test]
+    fn formats_alias_list() {
+        let aliases = aliases(
+            &[("tl", &["todo", "list"]), ("work", &["tl", "+work ops"])],
+            Some(&["tl"]),
+        );
+        assert_eq!(
+            format_aliases(&aliases),
+            "\
+tl    todo list
+work  tl '+work ops'
+
+Default command: tl"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use aimcal_core::{Aim, AimError};
+use clap::{ArgMatches, Command, ValueHint, arg, value_parser};
+use jiff::Timestamp;
+
+use crate::config::{Config, ConfigLocation, parse_config};
+
+const ARCHIVE_PREFIX: &str = "aim-";
+const ARCHIVE_SUFFIX: &str = ".tar.zst";
+
+#[derive(Debug, Clone)]
+pub struct CmdBackupCreate {
+    pub to: Option<PathBuf>,
+}
+
+impl CmdBackupCreate {
+    pub const NAME: &str = "create";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Create a backup archive of the database, config and local calendars")
+            .arg(
+                arg!(--to <FILE> "Write the archive to this file instead of the backup directory")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            to: matches.get_one::<PathBuf>("to").cloned(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim, config: &Config) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "creating backup...");
+        let path = match self.to {
+            Some(path) => {
+                aim.create_backup(&path, Some(&config.path)).await?;
+                path
+            }
+            None => create_rotated(aim, config).await?,
+        };
+        println!("Backup written to {}", path.display());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdBackupRestore {
+    pub file: PathBuf,
+}
+
+impl CmdBackupRestore {
+    pub const NAME: &str = "restore";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Restore the database, config and local calendars from a backup archive")
+            .arg(
+                arg!(file: <FILE> "Backup archive to restore")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            file: matches
+                .get_one::<PathBuf>("file")
+                .expect("file is required")
+                .clone(),
+        }
+    }
+
+    /// Restores without opening [`Aim`], since the state is replaced underneath it.
+    pub async fn run(self, config: ConfigLocation) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "restoring backup...");
+        let (core_config, config) = parse_config(config)
+            .await
+            .map_err(|e| AimError::Config(e.to_string()))?;
+
+        let manifest = Aim::restore_backup(core_config, &self.file, Some(&config.path)).await?;
+        println!(
+            "Restored backup from {} (created {} by aim {})",
+            self.file.display(),
+            manifest.created_at,
+            manifest.aim_version
+        );
+        Ok(())
+    }
+}
+
+/// Takes a backup into the backup directory if the configured schedule says one is due.
+///
+/// # Errors
+/// If the backup directory cannot be read or the backup fails.
+pub async fn run_scheduled_backup(
+    aim: &Aim,
+    config: &Config,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let Some(interval) = config.backup.schedule.interval() else {
+        return Ok(None);
+    };
+
+    let dir = backup_dir(aim, config)?;
+    if let Some(latest) = list_archives(&dir)?.last() {
+        let modified = latest.metadata()?.modified()?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age < interval {
+            return Ok(None);
+        }
+    }
+
+    tracing::info!(dir = %dir.display(), "taking scheduled backup");
+    create_rotated(aim, config).await.map(Some)
+}
+
+/// Creates a timestamped archive in the backup directory and deletes the oldest ones.
+async fn create_rotated(aim: &Aim, config: &Config) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = backup_dir(aim, config)?;
+    let name = Timestamp::now().strftime("%Y%m%dT%H%M%SZ");
+    let path = dir.join(format!("{ARCHIVE_PREFIX}{name}{ARCHIVE_SUFFIX}"));
+    aim.create_backup(&path, Some(&config.path)).await?;
+
+    let archives = list_archives(&dir)?;
+    let stale = archives.len().saturating_sub(config.backup.keep.max(1));
+    for archive in archives.iter().take(stale) {
+        tracing::debug!(path = %archive.display(), "removing old backup");
+        std::fs::remove_file(archive)?;
+    }
+    Ok(path)
+}
+
+fn backup_dir(aim: &Aim, config: &Config) -> Result<PathBuf, Box<dyn Error>> {
+    match (&config.backup.dir, aim.state_dir()) {
+        (Some(dir), _) => Ok(dir.clone()),
+        (None, Some(state_dir)) => Ok(state_dir.join("backups")),
+        (None, None) => Err(AimError::Config(
+            "No backup directory, set `dir` in the [backup] section".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Archives created by [`create_rotated`], oldest first.
+fn list_archives(dir: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut archives = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let is_archive = path
+            .file_name()
+            .and_then(|a| a.to_str())
+            .is_some_and(|a| a.starts_with(ARCHIVE_PREFIX) && a.ends_with(ARCHIVE_SUFFIX));
+        if is_archive && path.is_file() {
+            archives.push(path);
+        }
+    }
+    archives.sort(); // timestamped names sort chronologically
+    Ok(archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_archives_ignores_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "aim-20260102T000000Z.tar.zst",
+            "aim-20260101T000000Z.tar.zst",
+            "manual.tar.zst",
+            "aim-notes.txt",
+        ] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let archives = list_archives(dir.path()).unwrap();
+        let names: Vec<_> = archives
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "aim-20260101T000000Z.tar.zst",
+                "aim-20260102T000000Z.tar.zst"
+            ]
+        );
+        assert!(
+            list_archives(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
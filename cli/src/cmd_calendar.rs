@@ -5,8 +5,10 @@
 use std::error::Error;
 use std::{borrow::Cow, fmt};
 
-use aimcal_core::{Aim, CalendarDetails, CalendarRecord, CalendarStoreDetails};
+use aimcal_core::{Aim, CalendarDetails, CalendarPrivileges, CalendarStoreDetails};
 use clap::{ArgMatches, Command, arg};
+use colored::Colorize;
+use jiff::{Timestamp, tz::TimeZone};
 
 use crate::arg::CommonArgs;
 use crate::table::{PaddingDirection, Table, TableColumn, TableStyleBasic, TableStyleJson};
@@ -22,7 +24,7 @@ impl CmdCalendarList {
 
     pub fn command() -> Command {
         Command::new(Self::NAME)
-            .about("List all calendars")
+            .about("List all calendars, with item counts and the outcome of their last sync")
             .arg(CommonArgs::output_format())
     }
 
@@ -33,7 +35,7 @@ impl CmdCalendarList {
     }
 
     pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
-        let calendars = aim.list_calendars().await?;
+        let calendars = aim.list_calendar_details().await?;
         print_calendars(&calendars, self.output_format);
         Ok(())
     }
@@ -57,10 +59,7 @@ impl CmdCalendarShow {
 
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
-            id: matches
-                .get_one::<String>("id")
-                .expect("id is required")
-                .clone(),
+            id: get_id(matches),
             output_format: CommonArgs::get_output_format(matches),
         }
     }
@@ -72,7 +71,98 @@ impl CmdCalendarShow {
     }
 }
 
-fn print_calendars(calendars: &[CalendarRecord], output_format: OutputFormat) {
+#[derive(Debug, Clone)]
+pub struct CmdCalendarSetDefault {
+    pub id: String,
+}
+
+impl CmdCalendarSetDefault {
+    pub const NAME: &str = "set-default";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Use a calendar for new items, in place of default_calendar of the config")
+            .arg(arg!(id: <ID> "Calendar identifier or name"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: get_id(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "setting default calendar...");
+        let id = aim.set_default_calendar(&self.id).await?;
+        println!("Default calendar: {}", id.bold());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdCalendarEnable {
+    pub id: String,
+}
+
+impl CmdCalendarEnable {
+    pub const NAME: &str = "enable";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Enable a calendar, overriding the config")
+            .arg(arg!(id: <ID> "Calendar identifier or name"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: get_id(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "enabling calendar...");
+        let id = aim.set_calendar_enabled(&self.id, true).await?;
+        println!("Enabled calendar: {}", id.bold());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdCalendarDisable {
+    pub id: String,
+}
+
+impl CmdCalendarDisable {
+    pub const NAME: &str = "disable";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Disable a calendar, so it is not synced and its items are not listed")
+            .arg(arg!(id: <ID> "Calendar identifier or name"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: get_id(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "disabling calendar...");
+        let id = aim.set_calendar_enabled(&self.id, false).await?;
+        println!("Disabled calendar: {}", id.bold());
+        Ok(())
+    }
+}
+
+fn get_id(matches: &ArgMatches) -> String {
+    matches
+        .get_one::<String>("id")
+        .expect("id is required")
+        .clone()
+}
+
+fn print_calendars(calendars: &[CalendarDetails], output_format: OutputFormat) {
     let formatter = CalendarFormatter::new(output_format);
     println!("{}", formatter.format(calendars));
 }
@@ -98,7 +188,7 @@ impl CalendarFormatter {
         Self { format }
     }
 
-    fn format<'a>(&'a self, calendars: &'a [CalendarRecord]) -> CalendarDisplay<'a> {
+    fn format<'a>(&'a self, calendars: &'a [CalendarDetails]) -> CalendarDisplay<'a> {
         CalendarDisplay {
             calendars,
             formatter: self,
@@ -108,7 +198,7 @@ impl CalendarFormatter {
 
 #[derive(Debug, Clone, Copy)]
 struct CalendarDisplay<'a> {
-    calendars: &'a [CalendarRecord],
+    calendars: &'a [CalendarDetails],
     formatter: &'a CalendarFormatter,
 }
 
@@ -118,8 +208,16 @@ impl fmt::Display for CalendarDisplay<'_> {
             CalendarColumn::Id,
             CalendarColumn::Name,
             CalendarColumn::Kind,
+            CalendarColumn::Location,
+            CalendarColumn::Owner,
+            CalendarColumn::Color,
             CalendarColumn::Priority,
             CalendarColumn::Enabled,
+            CalendarColumn::Default,
+            CalendarColumn::Events,
+            CalendarColumn::Todos,
+            CalendarColumn::LastSync,
+            CalendarColumn::LastError,
         ];
         let metas: Vec<_> = columns.iter().map(ColumnMeta).collect();
 
@@ -148,10 +246,30 @@ impl fmt::Display for CalendarDetailsDisplay<'_> {
             ("Priority", calendar.priority.to_string().into()),
             ("Enabled", yes_no(calendar.enabled).into()),
             ("Default", yes_no(calendar.is_default).into()),
+            ("Access", access(calendar.privileges).into()),
+            ("Events", calendar.events.to_string().into()),
+            ("Todos", calendar.todos.to_string().into()),
+            (
+                "Last Sync",
+                calendar
+                    .last_synced_at
+                    .as_deref()
+                    .map_or("never".into(), format_sync_time),
+            ),
             ("Created At", calendar.created_at.as_str().into()),
             ("Updated At", calendar.updated_at.as_str().into()),
         ];
 
+        if let Some(error) = &calendar.last_sync_error {
+            rows.push(("Last Error", error.as_str().into()));
+        }
+        if let Some(owner) = &calendar.owner {
+            rows.push(("Owner", owner.as_str().into()));
+        }
+        if let Some(color) = &calendar.color {
+            rows.push(("Color", color.as_str().into()));
+        }
+
         let mut backend_rows: Vec<(&str, Cow<'_, str>)> = Vec::new();
         if let Some(backend) = &calendar.store {
             match backend {
@@ -181,6 +299,23 @@ impl fmt::Display for CalendarDetailsDisplay<'_> {
                     backend_rows.push(("Timeout Seconds", timeout_secs.to_string().into()));
                     backend_rows.push(("User Agent", user_agent.as_str().into()));
                 }
+                CalendarStoreDetails::Subscription {
+                    url,
+                    refresh_interval_secs,
+                    timeout_secs,
+                    user_agent,
+                } => {
+                    backend_rows.push(("Store", "subscription".into()));
+                    backend_rows.push(("URL", url.as_str().into()));
+                    backend_rows.push((
+                        "Refresh Interval Seconds",
+                        refresh_interval_secs
+                            .map_or_else(|| "from feed".to_string(), |secs| secs.to_string())
+                            .into(),
+                    ));
+                    backend_rows.push(("Timeout Seconds", timeout_secs.to_string().into()));
+                    backend_rows.push(("User Agent", user_agent.as_str().into()));
+                }
             }
         }
 
@@ -202,43 +337,123 @@ const fn yes_no(value: bool) -> &'static str {
     if value { "Yes" } else { "No" }
 }
 
+/// Marks the calendars the user cannot change anything in.
+const LOCK: &str = "🔒";
+
+/// Lists what the user may do with the items of a calendar.
+fn access(privileges: CalendarPrivileges) -> String {
+    if privileges.is_read_only() {
+        return "read-only".to_string();
+    }
+    [
+        (privileges.read, "read"),
+        (privileges.write_content, "edit"),
+        (privileges.bind, "create"),
+        (privileges.unbind, "delete"),
+    ]
+    .into_iter()
+    .filter_map(|(allowed, name)| allowed.then_some(name))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Formats a sync time in the local time zone, to the minute.
+fn format_sync_time(time: &str) -> Cow<'_, str> {
+    match time.parse::<Timestamp>() {
+        Ok(ts) => ts
+            .to_zoned(TimeZone::system())
+            .strftime("%Y-%m-%d %H:%M")
+            .to_string()
+            .into(),
+        Err(_) => time.into(),
+    }
+}
+
+/// Path of a local calendar, or URL of a remote one.
+fn location(calendar: &CalendarDetails) -> &str {
+    match &calendar.store {
+        Some(CalendarStoreDetails::Local { calendar_path }) => {
+            calendar_path.as_deref().unwrap_or_default()
+        }
+        Some(CalendarStoreDetails::Caldav { calendar_href, .. }) => calendar_href,
+        Some(CalendarStoreDetails::Subscription { url, .. }) => url,
+        None => "",
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum CalendarColumn {
     Id,
     Name,
     Kind,
+    Location,
+    Owner,
+    Color,
     Priority,
     Enabled,
+    Default,
+    Events,
+    Todos,
+    LastSync,
+    LastError,
 }
 
 #[derive(Debug, Clone, Copy)]
 struct ColumnMeta<'a>(&'a CalendarColumn);
 
-impl TableColumn<CalendarRecord> for ColumnMeta<'_> {
+impl TableColumn<CalendarDetails> for ColumnMeta<'_> {
     fn name(&self) -> Cow<'_, str> {
         match self.0 {
             CalendarColumn::Id => "ID",
             CalendarColumn::Name => "Name",
             CalendarColumn::Kind => "Kind",
+            CalendarColumn::Location => "Location",
+            CalendarColumn::Owner => "Owner",
+            CalendarColumn::Color => "Color",
             CalendarColumn::Priority => "Priority",
             CalendarColumn::Enabled => "Enabled",
+            CalendarColumn::Default => "Default",
+            CalendarColumn::Events => "Events",
+            CalendarColumn::Todos => "Todos",
+            CalendarColumn::LastSync => "Last Sync",
+            CalendarColumn::LastError => "Last Error",
         }
         .into()
     }
 
-    fn format<'a>(&self, calendar: &'a CalendarRecord) -> Cow<'a, str> {
+    fn format<'a>(&self, calendar: &'a CalendarDetails) -> Cow<'a, str> {
         match self.0 {
             CalendarColumn::Id => calendar.id.as_str().into(),
+            CalendarColumn::Name if calendar.privileges.is_read_only() => {
+                format!("{LOCK} {}", calendar.name).into()
+            }
             CalendarColumn::Name => calendar.name.as_str().into(),
             CalendarColumn::Kind => calendar.kind.as_str().into(),
+            CalendarColumn::Location => location(calendar).into(),
+            CalendarColumn::Owner => calendar.owner.as_deref().unwrap_or_default().into(),
+            CalendarColumn::Color => calendar.color.as_deref().unwrap_or_default().into(),
             CalendarColumn::Priority => calendar.priority.to_string().into(),
             CalendarColumn::Enabled => yes_no(calendar.enabled).into(),
+            CalendarColumn::Default => yes_no(calendar.is_default).into(),
+            CalendarColumn::Events => calendar.events.to_string().into(),
+            CalendarColumn::Todos => calendar.todos.to_string().into(),
+            CalendarColumn::LastSync => calendar
+                .last_synced_at
+                .as_deref()
+                .map_or("never".into(), format_sync_time),
+            CalendarColumn::LastError => calendar
+                .last_sync_error
+                .as_deref()
+                .unwrap_or_default()
+                .into(),
         }
     }
 
     fn padding_direction(&self) -> PaddingDirection {
         match self.0 {
-            CalendarColumn::Priority => PaddingDirection::Right,
+            CalendarColumn::Priority | CalendarColumn::Events | CalendarColumn::Todos => {
+                PaddingDirection::Right
+            }
             _ => PaddingDirection::Left,
         }
     }
@@ -257,6 +472,18 @@ mod tests {
             priority: 1,
             enabled: true,
             is_default: false,
+            owner: Some("/principals/alice/".to_string()),
+            privileges: CalendarPrivileges {
+                read: true,
+                write_content: true,
+                bind: false,
+                unbind: false,
+            },
+            color: Some("#1e90ff".to_string()),
+            events: 3,
+            todos: 2,
+            last_synced_at: Some("2026-03-19T10:30:00+08:00".to_string()),
+            last_sync_error: Some("connection refused".to_string()),
             created_at: "2026-03-19T10:00:00+08:00".to_string(),
             updated_at: "2026-03-19T10:30:00+08:00".to_string(),
             store: Some(CalendarStoreDetails::Caldav {
@@ -273,5 +500,81 @@ mod tests {
         assert!(rendered.contains("Default"));
         assert!(rendered.contains("Base URL"));
         assert!(rendered.contains("Auth Method"));
+        assert!(rendered.contains("/principals/alice/"));
+        assert!(rendered.contains("connection refused"));
+        assert!(rendered.contains("#1e90ff"));
+        assert!(rendered.contains("read, edit"));
+    }
+
+    #[test]
+    fn calendars_table_marks_read_only_calendars_with_a_lock() {
+        let calendar = |id: &str, privileges| CalendarDetails {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: "caldav".to_string(),
+            priority: 0,
+            enabled: true,
+            is_default: false,
+            owner: None,
+            privileges,
+            color: None,
+            events: 0,
+            todos: 0,
+            last_synced_at: None,
+            last_sync_error: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            store: None,
+        };
+        let read_only = CalendarPrivileges {
+            read: true,
+            write_content: false,
+            bind: false,
+            unbind: false,
+        };
+        let calendars = [
+            calendar("work", CalendarPrivileges::ALL),
+            calendar("holidays", read_only),
+        ];
+
+        let name = ColumnMeta(&CalendarColumn::Name);
+        let names: Vec<_> = calendars.iter().map(|c| name.format(c)).collect();
+        assert_eq!(names, ["work", "🔒 holidays"]);
+        assert_eq!(access(read_only), "read-only");
+        assert_eq!(
+            access(CalendarPrivileges::ALL),
+            "read, edit, create, delete"
+        );
+    }
+
+    #[test]
+    fn formats_sync_time_in_local_time_zone() {
+        let time = "2026-03-19T10:30:00.123+08:00";
+        let expected = time
+            .parse::<Timestamp>()
+            .unwrap()
+            .to_zoned(TimeZone::system())
+            .strftime("%Y-%m-%d %H:%M")
+            .to_string();
+        assert_eq!(format_sync_time(time), expected);
+        assert_eq!(format_sync_time("garbage"), "garbage");
+    }
+
+    #[test]
+    fn parses_calendar_set_default_command() {
+        let matches = CmdCalendarSetDefault::command()
+            .try_get_matches_from(["set-default", "Work"])
+            .unwrap();
+        assert_eq!(CmdCalendarSetDefault::from(&matches).id, "Work");
+
+        let matches = CmdCalendarDisable::command()
+            .try_get_matches_from(["disable", "work"])
+            .unwrap();
+        assert_eq!(CmdCalendarDisable::from(&matches).id, "work");
+        assert!(
+            CmdCalendarEnable::command()
+                .try_get_matches_from(["enable"])
+                .is_err()
+        );
     }
 }
@@ -0,0 +1,229 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! End-of-day check-in of yesterday's events, recording what happened at one event at a time.
+
+use std::borrow::Cow;
+use std::error::Error;
+
+use aimcal_core::{Aim, Attendance, DateTimeAnchor, Event, EventConditions, Id, Pager};
+use clap::{ArgMatches, Command, arg};
+use colored::Colorize;
+
+use crate::cmd_event::format_check_in;
+use crate::cmd_review::{ask, read_key};
+use crate::details_formatter::DetailRows;
+use crate::util::format_datetime;
+
+const KEYS_HELP: &str = "[a]ttended  [s]kipped  [n]ote  [l]ater  [q]uit";
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdCheckin {
+    /// Go through the events checked in already as well, to check them in again.
+    pub all: bool,
+}
+
+impl CmdCheckin {
+    pub const NAME: &str = "checkin";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Check in yesterday's events one at a time")
+            .long_about(
+                "Check in yesterday's events one at a time, recording with a single key what happened:
+a marks it attended, s skipped, n adds a note to record with it, l leaves it for later and q
+quits. The check-ins are kept in the events, see `aim event checkin`.",
+            )
+            .arg(arg!(--all "Go through the events checked in already as well"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            all: matches.get_flag("all"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "checking in events...");
+        let conds = EventConditions {
+            startable: Some(DateTimeAnchor::yesterday()),
+            cutoff: Some(DateTimeAnchor::yesterday()),
+            unchecked: !self.all,
+            ..Default::default()
+        };
+        let pager: Pager = (i64::MAX, 0).into();
+        let events = aim.list_events(&conds, &pager).await?;
+        let mut events = aim.dedupe_events(events, aim.dedupe_mode()).await?;
+        // Unscheduled events are listed in any range, but didn't take place yesterday
+        events.retain(|event| event.start().is_some());
+        if events.is_empty() {
+            println!("{}", "No events to check in".italic());
+            return Ok(());
+        }
+
+        let mut tally = Tally::default();
+        for (index, event) in events.iter().enumerate() {
+            let progress = format!("{} of {}", index + 1, events.len());
+            if !check_in(aim, event, &progress, &mut tally).await? {
+                break;
+            }
+        }
+        println!();
+        println!("{}", tally.summary(events.len()));
+        Ok(())
+    }
+}
+
+/// Checks in a single event, returning whether to go on with the next one.
+async fn check_in(
+    aim: &Aim,
+    event: &impl Event,
+    progress: &str,
+    tally: &mut Tally,
+) -> Result<bool, Box<dyn Error>> {
+    let mut note: Option<String> = None;
+    loop {
+        println!();
+        println!("{}", progress.bold());
+        println!("{}", event_rows(event, note.as_deref()));
+        println!("{}", KEYS_HELP.dimmed());
+        let Some(key) = read_key()? else {
+            return Ok(false);
+        };
+        let attendance = match Key::from_char(key) {
+            Some(Key::Attended) => Attendance::Attended,
+            Some(Key::Skipped) => Attendance::Skipped,
+            Some(Key::Note) => {
+                let input = ask("Note:", note.as_deref())?;
+                let input = input.trim();
+                note = (!input.is_empty()).then(|| input.to_string());
+                continue;
+            }
+            Some(Key::Later) => {
+                tally.later += 1;
+                return Ok(true);
+            }
+            Some(Key::Quit) => return Ok(false),
+            None => {
+                println!("Unknown key '{key}'");
+                continue;
+            }
+        };
+
+        let id = Id::Uid(event.uid().into_owned());
+        aim.check_in_event(&id, attendance, note.as_deref()).await?;
+        tally.record(attendance);
+        let verb = match attendance {
+            Attendance::Attended => "Attended",
+            Attendance::Skipped => "Skipped",
+        };
+        println!("{} {}", verb.green(), event.summary());
+        return Ok(true);
+    }
+}
+
+fn event_rows<'a>(event: &'a impl Event, note: Option<&'a str>) -> DetailRows<'a> {
+    let mut rows = DetailRows::new();
+    rows.push_opt("ID", event.short_id().map(|id| id.to_string()));
+    rows.push("Summary", event.summary());
+    rows.push_opt("Start", event.start().map(format_datetime));
+    rows.push_opt("End", event.end().map(format_datetime));
+    rows.push_opt("Location", event.location());
+    rows.push_opt("Check-in", event.check_in().as_ref().map(format_check_in));
+    rows.push_opt("Note", note.map(Cow::Borrowed));
+    rows
+}
+
+/// A key pressed while checking in an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Attended,
+    Skipped,
+    Note,
+    Later,
+    Quit,
+}
+
+impl Key {
+    fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'a' => Some(Key::Attended),
+            's' => Some(Key::Skipped),
+            'n' => Some(Key::Note),
+            'l' => Some(Key::Later),
+            'q' => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// How many events were checked in so far, and how.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Tally {
+    attended: usize,
+    skipped: usize,
+    later: usize,
+}
+
+impl Tally {
+    fn record(&mut self, attendance: Attendance) {
+        match attendance {
+            Attendance::Attended => self.attended += 1,
+            Attendance::Skipped => self.skipped += 1,
+        }
+    }
+
+    fn summary(&self, total: usize) -> String {
+        format!(
+            "Checked in {} of {total} events: {} attended, {} skipped, {} left for later",
+            self.attended + self.skipped,
+            self.attended,
+            self.skipped,
+            self.later,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    fn parse(args: &[&str]) -> CmdCheckin {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Checkin(cmd) => cmd,
+            _ => panic!("Expected Checkin command"),
+        }
+    }
+
+    #[test]
+    fn parses_checkin_command() {
+        assert!(!parse(&["test", "checkin"]).all);
+        assert!(parse(&["test", "checkin", "--all"]).all);
+    }
+
+    #[test]
+    fn maps_keys_to_responses() {
+        assert_eq!(Key::from_char('a'), Some(Key::Attended));
+        assert_eq!(Key::from_char('S'), Some(Key::Skipped));
+        assert_eq!(Key::from_char('n'), Some(Key::Note));
+        assert_eq!(Key::from_char('l'), Some(Key::Later));
+        assert_eq!(Key::from_char('q'), Some(Key::Quit));
+        assert_eq!(Key::from_char('k'), None);
+    }
+
+    #[test]
+    fn summarizes_check_ins() {
+        let mut tally = Tally::default();
+        tally.record(Attendance::Attended);
+        tally.record(Attendance::Skipped);
+        tally.record(Attendance::Attended);
+        tally.later += 1;
+        assert_eq!(
+            tally.summary(5),
+            "Checked in 3 of 5 events: 2 attended, 1 skipped, 1 left for later"
+        );
+    }
+}
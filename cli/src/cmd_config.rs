@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use aimcal_core::{AimError, Config as CoreConfig};
+use clap::{ArgMatches, Command};
+use colored::Colorize;
+use ratatui::style::Color;
+
+use crate::config::{ConfigLocation, parse_config};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdConfigCheck;
+
+impl CmdConfigCheck {
+    pub const NAME: &str = "check";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Check the config, such as the calendars and colors of the categories")
+            .long_about(
+                "\
+Check the config beyond what is checked when it is loaded: the calendars the [core.categories] \
+sections refer to are configured, and their colors are names such as `blue` or hex colors such \
+as `#1e90ff`.",
+            )
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    /// Checks the config without opening [`Aim`](aimcal_core::Aim), which it does not need.
+    pub async fn run(self, config: ConfigLocation) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "checking config...");
+        let (core, config) = parse_config(config)
+            .await
+            .map_err(|e| AimError::Config(e.to_string()))?;
+
+        let problems = check_categories(&core);
+        if problems.is_empty() {
+            println!("{} {}", "Config OK:".green(), config.path.display());
+            return Ok(());
+        }
+        for problem in &problems {
+            println!("{} {problem}", "Problem:".red());
+        }
+        Err(format!(
+            "Found {} problems in {}",
+            problems.len(),
+            config.path.display()
+        )
+        .into())
+    }
+}
+
+/// Checks that the calendars and colors of the category defaults exist.
+fn check_categories(config: &CoreConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    for category in config.categories.categories() {
+        let name = &category.name;
+        if let Some(calendar) = &category.calendar
+            && config.find_calendar_id(calendar).is_none()
+        {
+            problems.push(format!(
+                "Category '{name}': calendar '{calendar}' is not configured"
+            ));
+        }
+        if let Some(color) = &category.color
+            && color.parse::<Color>().is_err()
+        {
+            problems.push(format!("Category '{name}': unknown color '{color}'"));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_calendars_and_colors_of_categories() {
+        let toml = r##"
+default_calendar = "personal"
+
+[[calendars]]
+id = "work"
+name = "Work"
+store = "local"
+
+[categories.work]
+calendar = "Work"
+color = "#1e90ff"
+
+[categories.home]
+calendar = "personal"
+color = "blue"
+
+[categories.gym]
+calendar = "Fitness"
+color = "sporty"
+"##;
+        let config: CoreConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            check_categories(&config),
+            [
+                "Category 'gym': calendar 'Fitness' is not configured",
+                "Category 'gym': unknown color 'sporty'",
+            ]
+        );
+    }
+}
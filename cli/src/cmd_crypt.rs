@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use aimcal_core::{Aim, RekeyReport, generate_key};
+use clap::{ArgMatches, Command};
+use colored::Colorize;
+
+/// What encrypted descriptions protect against, shown in the help of the commands using them.
+pub const THREAT_MODEL: &str = "\
+Encrypted descriptions are sealed with the key in the [core.encryption] section before they \
+leave this machine, so the operators of the calendar servers only see the other fields of the \
+todo. They are no protection against local attackers: anyone who can read the config, the secrets \
+files or the environment of aim has the key, and can open every description it sealed.";
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdCryptRekey;
+
+impl CmdCryptRekey {
+    pub const NAME: &str = "rekey";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Encrypt the descriptions sealed with a previous key with the current one")
+            .long_about(format!(
+                "\
+Encrypt the descriptions sealed with one of `previous_keys` with `key` again, so the previous \
+keys can be removed from the config. Descriptions no configured key opens are left as they are.
+
+{THREAT_MODEL}"
+            ))
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "rekeying encrypted descriptions...");
+        let report = aim.rekey_descriptions().await?;
+        for (uid, reason) in &report.skipped {
+            println!("{} skipped {uid}: {reason}", "Warning:".yellow());
+        }
+        println!("{}", describe(&report));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdCryptGenerateKey;
+
+impl CmdCryptGenerateKey {
+    pub const NAME: &str = "generate-key";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Print a new random key for the [core.encryption] section")
+            .long_about(format!(
+                "\
+Print a new random key for the [core.encryption] section. Keep it in a secrets file or the \
+environment and refer to it with `${{ENV:VAR_NAME}}`, losing it makes the descriptions sealed \
+with it unreadable.
+
+{THREAT_MODEL}"
+            ))
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub fn run(self) {
+        tracing::debug!(?self, "generating encryption key...");
+        println!("{}", generate_key());
+    }
+}
+
+fn describe(report: &RekeyReport) -> String {
+    format!(
+        "Rekeyed {} todos, {} already up to date, {} skipped",
+        report.rekeyed,
+        report.up_to_date,
+        report.skipped.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_report() {
+        let report = RekeyReport {
+            rekeyed: 2,
+            up_to_date: 5,
+            skipped: vec![("todo-1".to_string(), "no key opens it".to_string())],
+        };
+        assert_eq!(
+            describe(&report),
+            "Rekeyed 2 todos, 5 already up to date, 1 skipped"
+        );
+    }
+}
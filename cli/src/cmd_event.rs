@@ -3,29 +3,56 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
 
 use aimcal_core::{
-    Aim, DateTimeAnchor, Event, EventConditions, EventDraft, EventPatch, EventStatus, Id, Kind,
-    LooseDateTime, Pager,
+    Aim, AimError, AlarmTrigger, Attendance, CheckIn, DateRangeAnchor, DateTimeAnchor, DedupeMode,
+    Event, EventConditions, EventDraft, EventPatch, EventStatus, Id, Kind, LooseDateTime, Pager,
+    RecurrenceDateEdit, Repeat, RepeatEnd, ThisAndFuture,
 };
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgGroup, ArgMatches, Command, ValueHint, arg, value_parser};
 use colored::Colorize;
+use jiff::SignedDuration;
 
 use crate::arg::CalendarArgs;
-use crate::arg::{CommonArgs, EventArgs, EventOrTodoArgs};
-use crate::event_formatter::{EventColumn, EventFormatter};
-use crate::prompt::{DuplicateChoice, is_terminal, prompt_duplicate_choice, prompt_time};
+use crate::arg::{CommonArgs, EventArgs, EventOrTodoArgs, RangeArgs};
+use crate::column::{column_parser, parse_columns};
+use crate::config::Config;
+use crate::details_formatter::DetailRows;
+use crate::event_formatter::{EventColumn, EventFormatter, any_mirrored};
+use crate::porcelain::Porcelain;
+use crate::prompt::{
+    DuplicateChoice, can_prompt, is_terminal, missing_summary, prompt_duplicate_choice,
+    prompt_event_draft, prompt_time,
+};
+use crate::raw_formatter::print_raw;
+use crate::resolve::resolve_id;
 use crate::tui;
-use crate::util::{OutputFormat, parse_datetime, parse_datetime_range};
+use crate::util::{
+    OutputFormat, StdinArgs, format_datetime, input_source, parse_anchor, parse_datetime,
+    parse_datetime_range, write_export,
+};
 
+#[expect(clippy::option_option)]
 #[derive(Debug, Clone)]
 pub struct CmdEventNew {
     pub calendar_id: Option<String>,
+    pub alarm: Option<Option<AlarmTrigger>>,
     pub description: Option<String>,
     pub end: Option<String>,
+    pub duration: Option<SignedDuration>,
     pub start: Option<String>,
     pub status: Option<EventStatus>,
     pub summary: Option<String>,
+    /// Start from the event of this iCalendar file, or of stdin with `-`.
+    pub from_ics: Option<PathBuf>,
+    /// How the event repeats, with the day of the month and the end given apart.
+    pub repeat: Option<Repeat>,
+    pub on_day: Option<i8>,
+    pub until: Option<DateTimeAnchor>,
+    pub times: Option<u32>,
+    pub no_interactive: bool,
 
     pub output_format: OutputFormat,
 }
@@ -42,53 +69,96 @@ impl CmdEventNew {
             .arg(CalendarArgs::new(true).calendar())
             .arg(event_args.start())
             .arg(event_args.end())
+            .arg(event_args.duration())
             .arg(args.description())
+            .arg(args.alarm())
+            .arg(args.no_alarm())
             .arg(event_args.status())
+            .arg(
+                arg!(--"from-ics" <FILE> "Start from the event of an iCalendar file, or - for stdin")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+            .arg(
+                arg!(--repeat <RULE> "How the event repeats (weekly, \"every 2 weeks on mon,wed\"...)")
+                    .value_parser(value_parser!(Repeat)),
+            )
+            .arg(
+                arg!(--"on-day" <DAY> "Day of the month it repeats on, negative from the end")
+                    .value_parser(value_parser!(i8).range(-31..=31))
+                    .allow_negative_numbers(true)
+                    .requires("repeat"),
+            )
+            .arg(
+                arg!(--until <DATE> "Last day it repeats on (2025-12-31, friday, 30d...)")
+                    .value_parser(value_parser!(DateTimeAnchor))
+                    .requires("repeat"),
+            )
+            .arg(
+                arg!(--times <N> "Number of times it occurs")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .requires("repeat")
+                    .conflicts_with("until"),
+            )
+            .arg(CommonArgs::no_interactive())
             .arg(CommonArgs::output_format())
     }
 
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
             calendar_id: CalendarArgs::get_calendar(matches),
+            alarm: EventOrTodoArgs::get_alarm(matches),
             description: EventOrTodoArgs::get_description(matches),
             start: EventArgs::get_start(matches),
             end: EventArgs::get_end(matches),
+            duration: EventArgs::get_duration(matches),
             status: EventArgs::get_status(matches),
             summary: EventOrTodoArgs::get_summary(matches),
+            from_ics: matches.get_one::<PathBuf>("from-ics").cloned(),
+            repeat: matches.get_one("repeat").cloned(),
+            on_day: matches.get_one("on-day").copied(),
+            until: matches.get_one("until").cloned(),
+            times: matches.get_one("times").copied(),
+            no_interactive: CommonArgs::get_no_interactive(matches),
 
             output_format: CommonArgs::get_output_format(matches),
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "adding new event...");
 
+        let mut stdin = StdinArgs::default();
+        stdin.resolve("summary", &mut self.summary)?;
+        stdin.resolve("description", &mut self.description)?;
+        let interactive = !self.no_interactive && can_prompt();
+        if self.summary.is_none() && self.from_ics.is_none() && !interactive {
+            return Err(missing_summary());
+        }
+
+        let prompts = self.prompts();
         let tui = self.tui();
         let now = aim.now();
 
-        // Prepare a draft with the provided arguments
-        let mut draft = aim.default_event_draft();
-
-        match (self.start, self.end) {
-            (Some(start), Some(end)) => {
-                (draft.start, draft.end) = parse_datetime_range(&now, &start, &end)?;
-            }
-            (Some(start), None) => {
-                draft.start = parse_datetime(&now, &start)?;
-                draft.end = None;
+        // Prepare a draft with the provided arguments, on top of the given event if any
+        let mut draft = match &self.from_ics {
+            Some(path) => {
+                let content = stdin.read_file("event", path).await?;
+                aim.event_draft_from_ics(input_source(path), &content)?
             }
-            (None, Some(end)) => {
-                draft.start = None;
-                draft.end = parse_datetime(&now, &end)?;
-            }
-            (None, None) => {}
-        }
+            None => aim.default_event_draft(),
+        };
+
+        let start = parse_anchor(self.start.as_deref().unwrap_or_default())?;
+        let end = parse_anchor(self.end.as_deref().unwrap_or_default())?;
+        draft.set_times(&now, start, end, self.duration)?;
 
         if let Some(description) = self.description {
             draft.description = Some(description);
         }
 
         draft.calendar_id = self.calendar_id;
+        draft.alarm = self.alarm;
 
         if let Some(status) = self.status {
             draft.status = status;
@@ -98,8 +168,15 @@ impl CmdEventNew {
             draft.summary = summary;
         }
 
-        // If TUI is needed, launch the TUI to edit the draft
-        if tui {
+        // Without any argument, ask for the fields one by one; if only some are missing, launch
+        // the TUI to edit the draft
+        if prompts {
+            let Some(draft_prompted) = prompt_event_draft(&now, draft)? else {
+                tracing::info!("user cancel the event creation");
+                return Ok(());
+            };
+            draft = draft_prompted;
+        } else if tui {
             let Some(draft_tui) = tui::draft_event(aim, draft)? else {
                 tracing::info!("user cancel the event creation");
                 return Ok(());
@@ -107,6 +184,25 @@ impl CmdEventNew {
             draft = draft_tui;
         }
 
+        if let Some(mut repeat) = self.repeat {
+            if let Some(day) = self.on_day {
+                repeat.month_day = Some(day);
+            }
+            if let Some(until) = self.until {
+                let until =
+                    until
+                        .resolve_since_zoned(&now)
+                        .map_err(|reason| AimError::InvalidInput {
+                            field: "until",
+                            reason,
+                        })?;
+                repeat.end = Some(RepeatEnd::Until(until));
+            } else if let Some(times) = self.times {
+                repeat.end = Some(RepeatEnd::Times(times));
+            }
+            draft.repeat = Some(repeat);
+        }
+
         // Create the event
         Self::new_event(aim, draft, self.output_format).await
     }
@@ -144,7 +240,7 @@ impl CmdEventNew {
     }
 
     pub(crate) fn tui(&self) -> bool {
-        Self::need_tui(&self.summary, &self.start)
+        self.from_ics.is_none() && Self::need_tui(&self.summary, &self.start)
     }
 
     /// Determine whether TUI is needed based on the provided arguments.
@@ -152,16 +248,35 @@ impl CmdEventNew {
     pub(crate) fn need_tui(summary: &Option<String>, start: &Option<String>) -> bool {
         summary.is_none() || start.is_none()
     }
+
+    /// Whether to ask for the fields one by one, which is when none is given.
+    pub(crate) fn prompts(&self) -> bool {
+        self.calendar_id.is_none()
+            && self.alarm.is_none()
+            && self.description.is_none()
+            && self.end.is_none()
+            && self.duration.is_none()
+            && self.start.is_none()
+            && self.status.is_none()
+            && self.summary.is_none()
+            && self.from_ics.is_none()
+            && self.repeat.is_none()
+    }
 }
 
+#[expect(clippy::option_option)]
 #[derive(Debug, Clone)]
 pub struct CmdEventEdit {
     pub id: Id,
+    pub alarm: Option<Option<AlarmTrigger>>,
     pub description: Option<String>,
     pub end: Option<String>,
     pub start: Option<String>,
     pub status: Option<EventStatus>,
     pub summary: Option<String>,
+    /// Edit the occurrence on this date and the ones after it, instead of the whole event.
+    pub occurrence: Option<DateTimeAnchor>,
+    pub range: ThisAndFuture,
 
     pub output_format: OutputFormat,
 }
@@ -178,18 +293,40 @@ impl CmdEventEdit {
             .arg(event_args.start())
             .arg(event_args.end())
             .arg(args.description())
+            .arg(args.alarm())
+            .arg(args.no_alarm())
             .arg(event_args.status())
+            .arg(
+                arg!(--occurrence <DATE> "Date of the occurrence of a recurring event to edit from")
+                    .value_parser(value_parser!(DateTimeAnchor))
+                    .requires("this-and-future"),
+            )
+            .arg(
+                arg!(--"this-and-future" "Edit the occurrence and the ones after it, splitting the event in two")
+                    .requires("occurrence"),
+            )
+            .arg(
+                arg!(--"range-override" "Write an override with RANGE=THISANDFUTURE instead of splitting the event")
+                    .requires("this-and-future"),
+            )
             .arg(CommonArgs::output_format())
     }
 
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
             id: EventOrTodoArgs::get_id(matches),
+            alarm: EventOrTodoArgs::get_alarm(matches),
             description: EventOrTodoArgs::get_description(matches),
             start: EventArgs::get_start(matches),
             end: EventArgs::get_end(matches),
             status: EventArgs::get_status(matches),
             summary: EventOrTodoArgs::get_summary(matches),
+            occurrence: matches.get_one::<DateTimeAnchor>("occurrence").cloned(),
+            range: if matches.get_flag("range-override") {
+                ThisAndFuture::Override
+            } else {
+                ThisAndFuture::Split
+            },
 
             output_format: CommonArgs::get_output_format(matches),
         }
@@ -198,41 +335,64 @@ impl CmdEventEdit {
     pub fn new_tui(id: Id, output_format: OutputFormat) -> Self {
         Self {
             id,
+            alarm: None,
             description: None,
             end: None,
             start: None,
             status: None,
             summary: None,
+            occurrence: None,
+            range: ThisAndFuture::Split,
 
             output_format,
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "editing event...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Event)).await?;
+        let mut stdin = StdinArgs::default();
+        stdin.resolve("summary", &mut self.summary)?;
+        stdin.resolve("description", &mut self.description)?;
         let tui = self.tui();
 
+        // Times without a date are on the occurrence edited from, if any
+        let occurrence = match self.occurrence {
+            Some(anchor) => Some(anchor.resolve_since_zoned(&aim.now())?.date()),
+            None => None,
+        };
+        let now = match occurrence {
+            Some(date) => aim.now().with().date(date).build()?,
+            None => aim.now(),
+        };
+
         // Prepare the patch with the provided arguments
         let (start, end) = match (self.start, self.end) {
             (Some(start), Some(end)) => {
-                let (a, b) = parse_datetime_range(&aim.now(), &start, &end)?;
+                let (a, b) = parse_datetime_range(&now, &start, &end)?;
                 (Some(a), Some(b))
             }
-            (Some(start), None) => (Some(parse_datetime(&aim.now(), &start)?), None),
-            (None, Some(end)) => (None, Some(parse_datetime(&aim.now(), &end)?)),
+            (Some(start), None) => (Some(parse_datetime(&now, &start)?), None),
+            (None, Some(end)) => (None, Some(parse_datetime(&now, &end)?)),
             (None, None) => (None, None),
         };
         let mut patch = EventPatch {
+            alarm: self.alarm,
             description: self.description.map(|d| (!d.is_empty()).then_some(d)),
             end,
             start,
             status: self.status,
             summary: self.summary,
+            ex_dates: Vec::new(),
+            rdates: Vec::new(),
+            until: None,
+            meta: Vec::new(),
+            check_in: None,
         };
 
         // If TUI is needed, launch the TUI to edit the event
         if tui {
-            let event = aim.get_event(&self.id).await?;
+            let event = aim.get_event(&id).await?;
             patch = if let Some(data) = tui::patch_event(aim, &event, patch)? {
                 data
             } else {
@@ -242,14 +402,20 @@ impl CmdEventEdit {
         }
 
         // Update the event
-        let event = aim.update_event(&self.id, patch).await?;
-        print_events(aim, &[event], self.output_format);
+        if let Some(date) = occurrence {
+            let event = aim.update_event_from(&id, date, patch, self.range).await?;
+            print_events(aim, &[event], self.output_format);
+        } else {
+            let event = aim.update_event(&id, patch).await?;
+            print_events(aim, &[event], self.output_format);
+        }
         Ok(())
     }
 
     /// Determine whether TUI is needed based on the provided arguments.
     pub(crate) fn tui(&self) -> bool {
-        self.description.is_none()
+        self.alarm.is_none()
+            && self.description.is_none()
             && self.end.is_none()
             && self.start.is_none()
             && self.status.is_none()
@@ -295,9 +461,10 @@ impl CmdEventDelay {
 
         // Calculate new start and end based on original start and end if exists, otherwise based on now
         // TODO: move these logics to core crate, same for reschedule command
-        let mut events = Vec::with_capacity(self.ids.len());
+        let mut patches = Vec::with_capacity(self.ids.len());
         for id in &self.ids {
-            let event = aim.get_event(id).await?;
+            let id = resolve_id(aim, id, Some(Kind::Event)).await?;
+            let event = aim.get_event(&id).await?;
             let (start, end) = match (event.start(), event.end()) {
                 (Some(start), end) => {
                     let s = time.clone().resolve_at(&start);
@@ -319,15 +486,19 @@ impl CmdEventDelay {
                 }
             };
 
-            // Update the event
             let patch = EventPatch {
                 start: Some(start),
                 end: Some(end),
                 ..Default::default()
             };
+            patches.push((id, patch));
+        }
 
-            let event = aim.update_event(id, patch).await?;
-            events.push(event);
+        // Update the events
+        let ids = update_events(aim, patches).await?;
+        let mut events = Vec::with_capacity(ids.len());
+        for id in &ids {
+            events.push(aim.get_event(id).await?);
         }
         print_events(aim, &events, self.output_format);
         Ok(())
@@ -371,9 +542,10 @@ impl CmdEventReschedule {
         };
 
         // Calculate new start and end based on original start and end if exists, otherwise based on now
-        let mut events = Vec::with_capacity(self.ids.len());
+        let mut patches = Vec::with_capacity(self.ids.len());
         for id in &self.ids {
-            let event = aim.get_event(id).await?;
+            let id = resolve_id(aim, id, Some(Kind::Event)).await?;
+            let event = aim.get_event(&id).await?;
             let (start, end) = match (event.start(), event.end()) {
                 (Some(start), Some(end)) => {
                     use LooseDateTime::{DateOnly, Floating, Local};
@@ -411,24 +583,295 @@ impl CmdEventReschedule {
                 }
             };
 
-            // Update the event
             let patch = EventPatch {
                 start: Some(start),
                 end: Some(end),
                 ..Default::default()
             };
-            let event = aim.update_event(id, patch).await?;
-            events.push(event);
+            patches.push((id, patch));
+        }
+
+        // Update the events
+        let ids = update_events(aim, patches).await?;
+        let mut events = Vec::with_capacity(ids.len());
+        for id in &ids {
+            events.push(aim.get_event(id).await?);
         }
         print_events(aim, &events, self.output_format);
         Ok(())
     }
 }
 
+macro_rules! cmd_recurrence_date {
+    ($cmd: ident, $field: ident, $edit: path, $name: expr, $about: expr) => {
+        #[derive(Debug, Clone)]
+        pub struct $cmd {
+            pub id: Id,
+            pub dates: Vec<DateTimeAnchor>,
+            pub output_format: OutputFormat,
+        }
+
+        impl $cmd {
+            pub const NAME: &str = $name;
+
+            pub fn command() -> Command {
+                let (args, _event_args) = args();
+                Command::new(Self::NAME)
+                    .about($about)
+                    .arg(args.id())
+                    .arg(
+                        arg!(dates: <DATE> ...)
+                            .help("Dates of the occurrences (2025-04-18, \"2025-04-18 9:00\"...)")
+                            .value_parser(value_parser!(DateTimeAnchor)),
+                    )
+                    .arg(CommonArgs::output_format())
+            }
+
+            pub fn from(matches: &ArgMatches) -> Self {
+                Self {
+                    id: EventOrTodoArgs::get_id(matches),
+                    dates: matches
+                        .get_many::<DateTimeAnchor>("dates")
+                        .map(|dates| dates.cloned().collect())
+                        .unwrap_or_default(),
+                    output_format: CommonArgs::get_output_format(matches),
+                }
+            }
+
+            pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+                tracing::debug!(?self, concat!("running ", stringify!($field), " ", $name, "..."));
+                let id = resolve_id(aim, &self.id, Some(Kind::Event)).await?;
+
+                let mut edits = Vec::with_capacity(self.dates.len());
+                for date in self.dates {
+                    let date = date
+                        .resolve_since_zoned(&aim.now())
+                        .map_err(|e| format!("Failed to resolve since zoned: {e}"))?;
+                    edits.push($edit(date));
+                }
+
+                let patch = EventPatch {
+                    $field: edits,
+                    ..Default::default()
+                };
+                let event = aim.update_event(&id, patch).await?;
+                print_events(aim, &[event], self.output_format);
+                Ok(())
+            }
+        }
+    };
+}
+
+cmd_recurrence_date!(
+    CmdEventExDateAdd,
+    ex_dates,
+    RecurrenceDateEdit::Add,
+    "add",
+    "Exclude occurrences of a recurring event"
+);
+cmd_recurrence_date!(
+    CmdEventExDateRemove,
+    ex_dates,
+    RecurrenceDateEdit::Remove,
+    "remove",
+    "Restore excluded occurrences of a recurring event"
+);
+cmd_recurrence_date!(
+    CmdEventRDateAdd,
+    rdates,
+    RecurrenceDateEdit::Add,
+    "add",
+    "Add extra occurrences to a recurring event"
+);
+cmd_recurrence_date!(
+    CmdEventRDateRemove,
+    rdates,
+    RecurrenceDateEdit::Remove,
+    "remove",
+    "Remove extra occurrences from a recurring event"
+);
+
+#[derive(Debug, Clone)]
+pub struct CmdEventCheckin {
+    pub id: Id,
+    pub attendance: Attendance,
+    pub note: Option<String>,
+    pub output_format: OutputFormat,
+}
+
+impl CmdEventCheckin {
+    pub const NAME: &str = "checkin";
+
+    pub fn command() -> Command {
+        let (args, _event_args) = args();
+        Command::new(Self::NAME)
+            .about("Record whether an event was attended, with a note on how it went")
+            .long_about(
+                "\
+Record whether an event was attended or skipped, with a note on how it went, replacing the \
+check-in it had. The check-in is kept in the event, so it is synced along with it, while its \
+status is left as it is. Events awaiting a check-in are listed with `aim event list --unchecked`.",
+            )
+            .arg(args.id())
+            .arg(arg!(--attended "The event was attended"))
+            .arg(arg!(--skipped "The event was skipped"))
+            .group(
+                ArgGroup::new("attendance")
+                    .args(["attended", "skipped"])
+                    .required(true),
+            )
+            .arg(arg!(--note <NOTE> "A note on how it went, such as that it ran over"))
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            attendance: if matches.get_flag("skipped") {
+                Attendance::Skipped
+            } else {
+                Attendance::Attended
+            },
+            note: matches.get_one::<String>("note").cloned(),
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "checking in event...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Event)).await?;
+        let event = aim
+            .check_in_event(&id, self.attendance, self.note.as_deref())
+            .await?;
+        print_events(aim, &[event], self.output_format);
+        Ok(())
+    }
+}
+
+/// Describes a check-in, such as `attended: ran 20 min over`.
+pub fn format_check_in(check_in: &CheckIn) -> String {
+    let attendance = match check_in.attendance {
+        Attendance::Attended => "attended",
+        Attendance::Skipped => "skipped",
+    };
+    match &check_in.note {
+        Some(note) => format!("{attendance}: {note}"),
+        None => attendance.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdEventShow {
+    pub id: Id,
+    pub raw: bool,
+    pub no_color: bool,
+}
+
+impl CmdEventShow {
+    pub const NAME: &str = "show";
+
+    pub fn command() -> Command {
+        let (args, _event_args) = args();
+        Command::new(Self::NAME)
+            .about("Show all properties of an event")
+            .arg(args.id())
+            .arg(arg!(--raw "Print the event as serialized in its calendar"))
+            .arg(arg!(--"no-color" "Print the raw event without highlighting").requires("raw"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            raw: matches.get_flag("raw"),
+            no_color: matches.get_flag("no-color"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "showing event...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Event)).await?;
+        let details = aim.get_event_details(&id).await?;
+        if self.raw {
+            let raw = details
+                .raw
+                .ok_or("The event could not be read from its calendar")?;
+            return print_raw(&raw, self.no_color);
+        }
+
+        let event = aim.get_event(&id).await?;
+        let mut rows = DetailRows::new();
+        rows.push_opt("ID", event.short_id().map(|id| id.to_string()));
+        rows.push("UID", event.uid());
+        rows.push("Summary", event.summary());
+        rows.push_opt("Start", event.start().map(format_datetime));
+        rows.push_opt("End", event.end().map(format_datetime));
+        rows.push_opt("Status", event.status().map(|s| s.to_string()));
+        rows.push_opt("Check-in", event.check_in().as_ref().map(format_check_in));
+        rows.push_opt("Description", event.description());
+        rows.push_item_details(&details);
+        rows.push_item_location(&details);
+        println!("{rows}");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdEventExport {
+    pub id: Id,
+    pub occurrence: Option<DateTimeAnchor>,
+    pub output: Option<PathBuf>,
+}
+
+impl CmdEventExport {
+    pub const NAME: &str = "export";
+
+    pub fn command() -> Command {
+        let (args, _event_args) = args();
+        Command::new(Self::NAME)
+            .about("Export a event as a standalone iCalendar file")
+            .arg(args.id())
+            .arg(
+                arg!(--occurrence <DATE> "Export only the occurrence of a recurring event on this date")
+                    .value_parser(value_parser!(DateTimeAnchor)),
+            )
+            .arg(
+                arg!(-o --output <FILE> "Write to this file instead of stdout")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            occurrence: matches.get_one::<DateTimeAnchor>("occurrence").cloned(),
+            output: matches.get_one::<PathBuf>("output").cloned(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "exporting event...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Event)).await?;
+        let occurrence = match self.occurrence {
+            Some(anchor) => Some(anchor.resolve_since_zoned(&aim.now())?.date()),
+            None => None,
+        };
+        let export = aim.export_event(&id, occurrence).await?;
+        write_export(self.output.as_deref(), export)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CmdEventList {
     pub conds: EventConditions,
+    pub range: Option<DateRangeAnchor>,
+    /// List every copy of events kept in several calendars.
+    pub no_dedupe: bool,
+    /// Columns of the table, overriding the `[events]` config.
+    pub columns: Option<Vec<EventColumn>>,
     pub output_format: OutputFormat,
+    /// Print every event as a porcelain record instead, see [`Porcelain`].
+    pub porcelain: Option<Porcelain>,
 }
 
 impl CmdEventList {
@@ -438,31 +881,90 @@ impl CmdEventList {
         Command::new(Self::NAME)
             .about("List events")
             .arg(CalendarArgs::new(true).calendar())
+            .args(RangeArgs::new(false).args())
+            .arg(arg!(--yesterday "In yesterday, such as to check them in").conflicts_with("range"))
+            .arg(arg!(--unchecked "Only the events not checked in yet"))
+            .arg(arg!(--"include-archived" "Include the past events archived by housekeeping"))
+            .arg(args().0.meta())
+            .arg(no_dedupe_arg())
+            .arg(
+                arg!(--columns <COLUMNS> "Columns of the table output, separated by commas")
+                    .value_delimiter(',')
+                    .value_parser(column_parser::<EventColumn>()),
+            )
             .arg(CommonArgs::output_format())
+            .args(CommonArgs::porcelain())
     }
 
     pub fn from(matches: &ArgMatches) -> Self {
+        let yesterday = matches.get_flag("yesterday");
         Self {
             conds: EventConditions {
-                startable: Some(DateTimeAnchor::today()),
+                startable: Some(if yesterday {
+                    DateTimeAnchor::yesterday()
+                } else {
+                    DateTimeAnchor::today()
+                }),
+                cutoff: yesterday.then(DateTimeAnchor::yesterday),
                 calendar_id: CalendarArgs::get_calendar(matches),
-                ..Default::default()
+                include_archived: matches.get_flag("include-archived"),
+                meta: EventOrTodoArgs::get_meta(matches),
+                related_to: None,
+                unchecked: matches.get_flag("unchecked"),
+                categories: Vec::new(),
             },
+            range: RangeArgs::new(false).get_range(matches),
+            no_dedupe: matches.get_flag("no-dedupe"),
+            columns: matches
+                .get_many::<String>("columns")
+                .map(|names| parse_columns(&names.collect::<Vec<_>>()))
+                .transpose()
+                .expect("column names are checked by the parser"),
             output_format: CommonArgs::get_output_format(matches),
+            porcelain: CommonArgs::get_porcelain(matches),
         }
     }
 
-    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &Aim, config: &Config) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "listing events...");
-        Self::list(aim, &self.conds, self.output_format).await
+        if let Some(range) = self.range {
+            let (start, cutoff) = range.resolve_anchors(aim.now().date(), aim.week_start())?;
+            self.conds.startable = Some(start);
+            self.conds.cutoff = Some(cutoff);
+        }
+        let dedupe = dedupe_mode(aim, self.no_dedupe);
+        if let Some(porcelain) = self.porcelain {
+            return Self::list_porcelain(aim, &self.conds, dedupe, porcelain).await;
+        }
+        let columns = self.columns.as_ref().or(config.events.columns.as_ref());
+        let columns = columns.map(Vec::as_slice);
+        Self::list(aim, &self.conds, dedupe, self.output_format, columns).await
     }
 
-    /// List events with the given conditions and output format.
+    /// List all events with the given conditions as porcelain records, without any prompts.
+    async fn list_porcelain(
+        aim: &Aim,
+        conds: &EventConditions,
+        dedupe: DedupeMode,
+        porcelain: Porcelain,
+    ) -> Result<(), Box<dyn Error>> {
+        let total = aim.count_events(conds).await?;
+        let events = aim.list_events(conds, &(total, 0).into()).await?;
+        let events = aim.dedupe_events(events, dedupe).await?;
+        let mut stdout = io::stdout().lock();
+        porcelain.write_events(&mut stdout, &events)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// List events with the given conditions and output format, collapsing copies with `dedupe`.
     #[expect(clippy::cast_possible_truncation)]
     pub async fn list(
         aim: &Aim,
         conds: &EventConditions,
+        dedupe: DedupeMode,
         output_format: OutputFormat,
+        columns: Option<&[EventColumn]>,
     ) -> Result<(), Box<dyn Error>> {
         const LIMIT: i64 = 128;
 
@@ -479,11 +981,33 @@ impl CmdEventList {
             return Ok(());
         }
 
-        print_events(aim, &events, output_format);
+        let events = aim.dedupe_events(events, dedupe).await?;
+        match columns {
+            // Chosen columns apply to tables only, JSON keeps its keys for scripts
+            Some(columns) if output_format == OutputFormat::Table => {
+                let formatter = EventFormatter::new(aim.now(), columns.to_vec(), output_format);
+                println!("{}", formatter.format(&events));
+            }
+            _ => print_events(aim, &events, output_format),
+        }
         Ok(())
     }
 }
 
+/// The `--no-dedupe` flag of event listings.
+pub fn no_dedupe_arg() -> Arg {
+    arg!(--"no-dedupe" "List every copy of events kept in several calendars")
+}
+
+/// How a listing collapses copies of events, by the config unless `no_dedupe`.
+pub fn dedupe_mode(aim: &Aim, no_dedupe: bool) -> DedupeMode {
+    if no_dedupe {
+        DedupeMode::Off
+    } else {
+        aim.dedupe_mode()
+    }
+}
+
 const fn args() -> (EventOrTodoArgs, EventArgs) {
     (
         EventOrTodoArgs::new(Some(Kind::Event)),
@@ -491,12 +1015,30 @@ const fn args() -> (EventOrTodoArgs, EventArgs) {
     )
 }
 
+/// Applies the patches to their events all at once, so either all of them are updated or none,
+/// returning the IDs of the events by UID.
+async fn update_events(aim: &Aim, patches: Vec<(Id, EventPatch)>) -> Result<Vec<Id>, AimError> {
+    aim.transaction(|txn| {
+        Box::pin(async move {
+            let mut ids = Vec::with_capacity(patches.len());
+            for (id, patch) in patches {
+                ids.push(Id::Uid(txn.update_event(&id, patch).await?));
+            }
+            Ok(ids)
+        })
+    })
+    .await
+}
+
 fn print_events(aim: &Aim, events: &[impl Event], output_format: OutputFormat) {
-    use EventColumn::{DateTimeSpan, Id, ShortId, Summary, Uid};
-    let columns = match output_format {
+    use EventColumn::{Calendars, DateTimeSpan, Id, ShortId, Summary, Uid};
+    let mut columns = match output_format {
         OutputFormat::Table => vec![Id, DateTimeSpan, Summary],
         OutputFormat::Json => vec![Uid, ShortId, DateTimeSpan, Summary],
     };
+    if any_mirrored(events) {
+        columns.push(Calendars);
+    }
     let formatter = EventFormatter::new(aim.now(), columns, output_format);
     println!("{}", formatter.format(events));
 }
@@ -544,6 +1086,86 @@ mod tests {
         let parsed = CmdEventNew::from(&matches);
 
         assert!(parsed.tui());
+        assert!(parsed.prompts());
+        assert!(!parsed.no_interactive);
+    }
+
+    #[test]
+    fn parses_event_new_command_prompting_only_without_arguments() {
+        let args = ["new", "--start", "tomorrow 9:00", "--no-interactive"];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+
+        assert!(parsed.tui());
+        assert!(!parsed.prompts());
+        assert!(parsed.no_interactive);
+    }
+
+    #[test]
+    fn parses_event_new_command_with_duration() {
+        let args = [
+            "new",
+            "sync",
+            "--start",
+            "tomorrow 14:00",
+            "--duration",
+            "30m",
+        ];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+        assert_eq!(parsed.duration, Some(SignedDuration::from_mins(30)));
+        assert_eq!(parsed.end, None);
+
+        // Negative durations reach the draft, which rejects them
+        let args = ["new", "sync", "--duration", "-30m"];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+        assert_eq!(parsed.duration, Some(SignedDuration::from_mins(-30)));
+
+        let args = ["new", "sync", "--duration", "soon"];
+        assert!(CmdEventNew::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_event_new_command_with_repeat() {
+        let args = [
+            "new", "rent", "--repeat", "monthly", "--on-day", "-1", "--times", "12",
+        ];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+        assert_eq!(parsed.repeat, Some("monthly".parse().unwrap()));
+        assert_eq!(parsed.on_day, Some(-1));
+        assert_eq!(parsed.times, Some(12));
+        assert_eq!(parsed.until, None);
+
+        let args = ["new", "sync", "--repeat", "every 2 weeks on mon,wed"];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+        assert_eq!(parsed.repeat.unwrap().interval, 2);
+
+        let invalid: [&[&str]; 4] = [
+            &["new", "sync", "--repeat", "now and then"],
+            &["new", "sync", "--times", "3"],
+            &["new", "sync", "--repeat", "daily", "--times", "0"],
+            &[
+                "new", "sync", "--repeat", "daily", "--times", "3", "--until", "friday",
+            ],
+        ];
+        for args in invalid {
+            assert!(CmdEventNew::command().try_get_matches_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn parses_event_new_command_from_ics() {
+        let args = ["new", "--from-ics", "-", "--description", "Forwarded"];
+        let matches = CmdEventNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdEventNew::from(&matches);
+
+        assert_eq!(parsed.from_ics, Some(PathBuf::from("-")));
+        assert_eq!(parsed.description, Some("Forwarded".to_string()));
+        assert_eq!(parsed.summary, None);
+        assert!(!parsed.tui());
     }
 
     #[test]
@@ -578,6 +1200,30 @@ mod tests {
         assert_eq!(parsed.output_format, OutputFormat::Json);
     }
 
+    #[test]
+    fn parses_event_alarm_options() {
+        let matches = CmdEventEdit::command()
+            .try_get_matches_from(["edit", "test_id", "--alarm", "1 day"])
+            .unwrap();
+        let parsed = CmdEventEdit::from(&matches);
+        assert_eq!(parsed.alarm, Some(Some("-P1D".parse().unwrap())));
+        assert!(!parsed.tui());
+
+        let matches = CmdEventNew::command()
+            .try_get_matches_from(["new", "--no-alarm"])
+            .unwrap();
+        assert_eq!(CmdEventNew::from(&matches).alarm, Some(None));
+
+        let matches = CmdEventNew::command()
+            .try_get_matches_from(["new"])
+            .unwrap();
+        assert_eq!(CmdEventNew::from(&matches).alarm, None);
+
+        let result =
+            CmdEventNew::command().try_get_matches_from(["new", "--alarm", "30m", "--no-alarm"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn parses_event_edit_command_with_tui_mode() {
         let cmd = CmdEventEdit::command();
@@ -648,5 +1294,103 @@ mod tests {
 
         assert_eq!(parsed.conds.calendar_id, Some("work".to_string()));
         assert_eq!(parsed.output_format, OutputFormat::Json);
+        assert!(!parsed.no_dedupe);
+
+        let matches = CmdEventList::command()
+            .try_get_matches_from(["list", "--no-dedupe"])
+            .unwrap();
+        assert!(CmdEventList::from(&matches).no_dedupe);
+    }
+
+    #[test]
+    fn parses_event_list_command_for_yesterday_unchecked() {
+        let matches = CmdEventList::command()
+            .try_get_matches_from(["list", "--yesterday", "--unchecked"])
+            .unwrap();
+        let parsed = CmdEventList::from(&matches);
+
+        assert_eq!(parsed.conds.startable, Some(DateTimeAnchor::yesterday()));
+        assert_eq!(parsed.conds.cutoff, Some(DateTimeAnchor::yesterday()));
+        assert!(parsed.conds.unchecked);
+
+        let args = ["list", "--yesterday", "--week"];
+        assert!(CmdEventList::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_event_checkin_command() {
+        let args = ["checkin", "a", "--skipped", "--note", "Overslept"];
+        let matches = CmdEventCheckin::command()
+            .try_get_matches_from(args)
+            .unwrap();
+        let parsed = CmdEventCheckin::from(&matches);
+
+        assert_eq!(parsed.id, Id::ShortIdOrUid("a".to_string()));
+        assert_eq!(parsed.attendance, Attendance::Skipped);
+        assert_eq!(parsed.note.as_deref(), Some("Overslept"));
+
+        let matches = CmdEventCheckin::command()
+            .try_get_matches_from(["checkin", "a", "--attended"])
+            .unwrap();
+        assert_eq!(
+            CmdEventCheckin::from(&matches).attendance,
+            Attendance::Attended
+        );
+
+        for args in [
+            &["checkin", "a"][..],
+            &["checkin", "a", "--attended", "--skipped"],
+        ] {
+            assert!(
+                CmdEventCheckin::command()
+                    .try_get_matches_from(args)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn formats_check_in() {
+        let check_in = CheckIn {
+            attendance: Attendance::Attended,
+            note: Some("ran 20 min over".to_string()),
+        };
+        assert_eq!(format_check_in(&check_in), "attended: ran 20 min over");
+
+        let check_in = CheckIn {
+            attendance: Attendance::Skipped,
+            note: None,
+        };
+        assert_eq!(format_check_in(&check_in), "skipped");
+    }
+
+    #[test]
+    fn parses_event_exdate_add_command() {
+        let args = [
+            "add",
+            "a",
+            "2025-04-18",
+            "2025-04-25 9:00",
+            "--output-format",
+            "json",
+        ];
+        let matches = CmdEventExDateAdd::command()
+            .try_get_matches_from(args)
+            .unwrap();
+        let parsed = CmdEventExDateAdd::from(&matches);
+
+        assert_eq!(parsed.id, Id::ShortIdOrUid("a".to_string()));
+        assert_eq!(parsed.dates.len(), 2);
+        assert_eq!(parsed.output_format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn parses_event_rdate_remove_command_requires_date() {
+        let args = ["remove", "a"];
+        assert!(
+            CmdEventRDateRemove::command()
+                .try_get_matches_from(args)
+                .is_err()
+        );
     }
 }
@@ -0,0 +1,387 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Single values of events and todos for shell scripts, such as `$(aim get todo 1 due)`.
+
+use std::{error::Error, fmt};
+
+use aimcal_core::{Aim, AimError, Event, Id, LooseDateTime, Todo};
+use clap::{ArgMatches, Command, ValueEnum, arg, value_parser};
+use jiff::Zoned;
+
+use crate::arg::EventOrTodoArgs;
+
+#[derive(Debug, Clone)]
+pub struct CmdGet {
+    pub target: GetTarget,
+    pub format: TimeFormat,
+}
+
+/// The item to read a field of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GetTarget {
+    Todo(Id, TodoField),
+    Event(Id, EventField),
+    NextEvent(EventField),
+}
+
+impl CmdGet {
+    pub const NAME: &str = "get";
+
+    pub fn command() -> Command {
+        let field = |help| arg!(field: <FIELD>).help(help);
+        let id = |kind| arg!(id: <ID>).help(format!("The short id or uid of the {kind}"));
+        Command::new(Self::NAME)
+            .about("Print a single field of an event or todo, for scripts")
+            .long_about(
+                "\
+Print a single field of an event or todo, and nothing else, for scripts.
+
+Exits with 1 and prints nothing if the field is not set, and with 3 if the item does not exist.",
+            )
+            .arg_required_else_help(true)
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("todo")
+                    .about("Print a field of a todo")
+                    .arg(id("todo"))
+                    .arg(field("The field to print").value_parser(value_parser!(TodoField)))
+                    .arg(time_format()),
+            )
+            .subcommand(
+                Command::new("event")
+                    .about("Print a field of an event")
+                    .arg(id("event"))
+                    .arg(field("The field to print").value_parser(value_parser!(EventField)))
+                    .arg(time_format()),
+            )
+            .subcommand(
+                Command::new("next-event")
+                    .about("Print a field of the next event to start")
+                    .arg(field("The field to print").value_parser(value_parser!(EventField)))
+                    .arg(time_format()),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        let (target, matches) = match matches.subcommand() {
+            Some(("todo", matches)) => (
+                GetTarget::Todo(EventOrTodoArgs::get_id(matches), get_field(matches)),
+                matches,
+            ),
+            Some(("event", matches)) => (
+                GetTarget::Event(EventOrTodoArgs::get_id(matches), get_field(matches)),
+                matches,
+            ),
+            Some(("next-event", matches)) => (GetTarget::NextEvent(get_field(matches)), matches),
+            _ => unreachable!(),
+        };
+        Self {
+            target,
+            format: matches.get_one("format").copied().unwrap_or_default(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "getting field...");
+        let value = match &self.target {
+            GetTarget::Todo(id, field) => todo_field(&aim.get_todo(id).await?, *field),
+            GetTarget::Event(id, field) => event_field(&aim.get_event(id).await?, *field),
+            GetTarget::NextEvent(field) => {
+                let Some(event) = aim.next_event().await? else {
+                    let id = "next-event".to_string();
+                    return Err(AimError::NotFound { kind: "Event", id }.into());
+                };
+                event_field(&event, *field)
+            }
+        };
+        let value = value.ok_or(FieldUnset)?;
+        println!("{}", value.format(self.format, &aim.now()));
+        Ok(())
+    }
+}
+
+/// Error of a field that is not set on the item, which is reported by the exit code alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldUnset;
+
+impl fmt::Display for FieldUnset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the field is not set")
+    }
+}
+
+impl Error for FieldUnset {}
+
+/// Fields of a todo that `aim get todo` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TodoField {
+    /// Short ID, or the UID if it has none
+    Id,
+    /// Unique identifier
+    Uid,
+    /// Summary
+    Summary,
+    /// Description
+    Description,
+    /// Status, such as NEEDS-ACTION
+    Status,
+    /// Priority, from 1 (highest) to 9 (lowest)
+    Priority,
+    /// Percent complete, from 0 to 100
+    PercentComplete,
+    /// Start date and time
+    Start,
+    /// Due date and time
+    Due,
+    /// Date and time of completion
+    Completed,
+    /// Categories, separated by commas
+    Categories,
+}
+
+/// Fields of an event that `aim get event` and `aim get next-event` print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EventField {
+    /// Short ID, or the UID if it has none
+    Id,
+    /// Unique identifier
+    Uid,
+    /// Summary
+    Summary,
+    /// Description
+    Description,
+    /// Status, such as CONFIRMED
+    Status,
+    /// Start date and time
+    Start,
+    /// End date and time
+    End,
+}
+
+/// How dates and times are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeFormat {
+    /// RFC 3339, such as 2025-01-15T10:00:00+08:00, or 2025-01-15 for dates
+    #[default]
+    Rfc3339,
+    /// Seconds since the Unix epoch, taking dates at the start of the day
+    Unix,
+    /// Relative to now, such as "in 1d 2h" or "30m ago"
+    Relative,
+}
+
+fn time_format() -> clap::Arg {
+    arg!(--format <FORMAT> "Format of dates and times")
+        .value_parser(value_parser!(TimeFormat))
+        .default_value("rfc3339")
+}
+
+fn get_field<T: Clone + Send + Sync + 'static>(matches: &ArgMatches) -> T {
+    matches
+        .get_one::<T>("field")
+        .expect("field is required")
+        .clone()
+}
+
+/// A field value, with times kept apart to be formatted as asked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Text(String),
+    Time(LooseDateTime),
+}
+
+impl Value {
+    fn format(self, format: TimeFormat, now: &Zoned) -> String {
+        match self {
+            Value::Text(text) => text,
+            Value::Time(t) => format_time(t, format, now),
+        }
+    }
+}
+
+fn todo_field(todo: &impl Todo, field: TodoField) -> Option<Value> {
+    let text = |s: String| Some(Value::Text(s));
+    match field {
+        TodoField::Id => text(format_id(todo.short_id(), &todo.uid())),
+        TodoField::Uid => text(todo.uid().into_owned()),
+        TodoField::Summary => text(todo.summary().into_owned()),
+        TodoField::Description => todo.description().and_then(|d| text(d.into_owned())),
+        TodoField::Status => text(todo.status().to_string()),
+        TodoField::Priority => match u8::from(todo.priority()) {
+            0 => None,
+            p => text(p.to_string()),
+        },
+        TodoField::PercentComplete => todo.percent_complete().and_then(|p| text(p.to_string())),
+        TodoField::Start => todo.start().map(Value::Time),
+        TodoField::Due => todo.due().map(Value::Time),
+        TodoField::Completed => todo
+            .completed()
+            .map(|t| Value::Time(LooseDateTime::Local(t))),
+        TodoField::Categories => match todo.categories() {
+            categories if categories.is_empty() => None,
+            categories => text(categories.join(",")),
+        },
+    }
+}
+
+fn event_field(event: &impl Event, field: EventField) -> Option<Value> {
+    let text = |s: String| Some(Value::Text(s));
+    match field {
+        EventField::Id => text(format_id(event.short_id(), &event.uid())),
+        EventField::Uid => text(event.uid().into_owned()),
+        EventField::Summary => text(event.summary().into_owned()),
+        EventField::Description => event.description().and_then(|d| text(d.into_owned())),
+        EventField::Status => event.status().and_then(|s| text(s.to_string())),
+        EventField::Start => event.start().map(Value::Time),
+        EventField::End => event.end().map(Value::Time),
+    }
+}
+
+fn format_id(short_id: Option<std::num::NonZeroU32>, uid: &str) -> String {
+    short_id.map_or_else(|| uid.to_string(), |id| id.to_string())
+}
+
+/// Formats a time, taking floating times in the time zone of `now`.
+fn format_time(t: LooseDateTime, format: TimeFormat, now: &Zoned) -> String {
+    let date = t.date();
+    let zoned = match t {
+        LooseDateTime::DateOnly(d) => match format {
+            TimeFormat::Rfc3339 => return d.to_string(),
+            TimeFormat::Relative => return format_relative_days((d - now.date()).get_days()),
+            TimeFormat::Unix => d.to_zoned(now.time_zone().clone()),
+        },
+        LooseDateTime::Floating(dt) => dt.to_zoned(now.time_zone().clone()),
+        LooseDateTime::Local(zoned) => Ok(zoned),
+    };
+    // Only fails for times out of the range of jiff, fall back to the civil time then
+    let Ok(zoned) = zoned else {
+        return date.to_string();
+    };
+
+    match format {
+        TimeFormat::Rfc3339 => zoned.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+        TimeFormat::Unix => zoned.timestamp().as_second().to_string(),
+        TimeFormat::Relative => {
+            let seconds = zoned.timestamp().as_second() - now.timestamp().as_second();
+            format_relative_minutes(seconds / 60)
+        }
+    }
+}
+
+fn format_relative_days(days: i32) -> String {
+    match days {
+        0 => "today".to_string(),
+        d if d > 0 => format!("in {d}d"),
+        d => format!("{}d ago", d.unsigned_abs()),
+    }
+}
+
+fn format_relative_minutes(minutes: i64) -> String {
+    let abs = minutes.unsigned_abs();
+    let parts: Vec<String> = [(abs / 1440, "d"), (abs % 1440 / 60, "h"), (abs % 60, "m")]
+        .into_iter()
+        .filter(|&(n, _)| n > 0)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect();
+    match (parts.is_empty(), minutes > 0) {
+        (true, _) => "now".to_string(),
+        (false, true) => format!("in {}", parts.join(" ")),
+        (false, false) => format!("{} ago", parts.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    fn now() -> Zoned {
+        date(2025, 1, 15)
+            .at(9, 0, 0, 0)
+            .to_zoned(TimeZone::fixed(jiff::tz::offset(8)))
+            .unwrap()
+    }
+
+    fn parse(args: &[&str]) -> CmdGet {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Get(cmd) => cmd,
+            _ => panic!("Expected Get command"),
+        }
+    }
+
+    #[test]
+    fn parses_get_commands() {
+        let cmd = parse(&["test", "get", "todo", "1", "due"]);
+        assert_eq!(
+            cmd.target,
+            GetTarget::Todo(Id::ShortIdOrUid("1".to_string()), TodoField::Due)
+        );
+        assert_eq!(cmd.format, TimeFormat::Rfc3339);
+
+        let cmd = parse(&["test", "get", "event", "abc", "summary", "--format", "unix"]);
+        assert_eq!(
+            cmd.target,
+            GetTarget::Event(Id::ShortIdOrUid("abc".to_string()), EventField::Summary)
+        );
+        assert_eq!(cmd.format, TimeFormat::Unix);
+
+        let cmd = parse(&["test", "get", "next-event", "start", "--format", "relative"]);
+        assert_eq!(cmd.target, GetTarget::NextEvent(EventField::Start));
+        assert_eq!(cmd.format, TimeFormat::Relative);
+    }
+
+    #[test]
+    fn rejects_unknown_fields_listing_the_valid_ones() {
+        let err = Cli::try_parse_from(["test", "get", "event", "1", "due"]).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains("summary"), "{err}");
+        assert!(err.contains("start"), "{err}");
+    }
+
+    #[test]
+    fn formats_times_in_each_format() {
+        let local = LooseDateTime::Local(now().checked_add(jiff::Span::new().hours(26)).unwrap());
+        assert_eq!(
+            format_time(local.clone(), TimeFormat::Rfc3339, &now()),
+            "2025-01-16T11:00:00+08:00"
+        );
+        assert_eq!(
+            format_time(local.clone(), TimeFormat::Unix, &now()),
+            "1736996400"
+        );
+        assert_eq!(format_time(local, TimeFormat::Relative, &now()), "in 1d 2h");
+
+        let floating = LooseDateTime::Floating(date(2025, 1, 15).at(8, 30, 0, 0));
+        assert_eq!(
+            format_time(floating.clone(), TimeFormat::Rfc3339, &now()),
+            "2025-01-15T08:30:00+08:00"
+        );
+        assert_eq!(
+            format_time(floating, TimeFormat::Relative, &now()),
+            "30m ago"
+        );
+    }
+
+    #[test]
+    fn formats_dates_in_each_format() {
+        let d = LooseDateTime::DateOnly(date(2025, 1, 17));
+        assert_eq!(
+            format_time(d.clone(), TimeFormat::Rfc3339, &now()),
+            "2025-01-17"
+        );
+        assert_eq!(
+            format_time(d.clone(), TimeFormat::Unix, &now()),
+            "1737043200"
+        );
+        assert_eq!(format_time(d, TimeFormat::Relative, &now()), "in 2d");
+
+        let today = LooseDateTime::DateOnly(date(2025, 1, 15));
+        assert_eq!(format_time(today, TimeFormat::Relative, &now()), "today");
+    }
+}
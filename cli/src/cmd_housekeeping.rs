@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use aimcal_core::{Aim, HousekeepingReport, LooseDateTime};
+use clap::{ArgMatches, Command};
+use colored::Colorize;
+
+use crate::util::format_datetime;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdHousekeepingRun;
+
+impl CmdHousekeepingRun {
+    pub const NAME: &str = "run";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Archive old completed todos and past events out of the cache, per the retention policy")
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "running housekeeping...");
+        if !aim.housekeeping().is_enabled() {
+            println!(
+                "No retention policy, set `archive_completed_after` or `archive_events_after` in the [core.housekeeping] section."
+            );
+            return Ok(());
+        }
+        let report = aim.run_housekeeping().await?;
+        println!("{}", describe(report));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdCacheStats;
+
+impl CmdCacheStats {
+    pub const NAME: &str = "stats";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME).about("Show how many items are cached and archived")
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "reading cache stats...");
+        let stats = aim.cache_stats().await?;
+        println!(
+            "Events: {} cached, {} archived",
+            stats.events, stats.archived_events
+        );
+        println!(
+            "Todos: {} cached, {} archived",
+            stats.todos, stats.archived_todos
+        );
+        let last = stats.last_housekeeping.map_or_else(
+            || "never".to_string(),
+            |a| format_datetime(LooseDateTime::Local(a)),
+        );
+        println!("Last housekeeping: {last}");
+        Ok(())
+    }
+}
+
+/// Runs housekeeping after a sync if a retention policy is configured and it is due, noting
+/// what was archived.
+///
+/// The sync itself succeeded, so a failure is only reported as a warning.
+pub async fn run_scheduled_housekeeping(aim: &Aim) {
+    match aim.run_housekeeping_if_due().await {
+        Ok(Some(report)) if report != HousekeepingReport::default() => {
+            println!("Note: {}", describe(report));
+        }
+        Ok(_) => {}
+        Err(e) => println!("{} housekeeping failed: {e}", "Warning:".yellow()),
+    }
+}
+
+fn describe(report: HousekeepingReport) -> String {
+    format!(
+        "Archived {} events and {} todos",
+        report.events, report.todos
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_report() {
+        let report = HousekeepingReport {
+            events: 3,
+            todos: 12,
+        };
+        assert_eq!(describe(report), "Archived 3 events and 12 todos");
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use aimcal_core::{Aim, SmartList, SmartListQuery};
+use clap::{ArgAction, ArgMatches, Command, arg};
+use colored::Colorize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::arg::{CalendarArgs, CommonArgs, EventOrTodoArgs};
+use crate::cmd_event::{CmdEventList, dedupe_mode};
+use crate::cmd_todo::{TodoColumns, print_todos_with};
+use crate::config::Config;
+use crate::util::OutputFormat;
+
+/// Most todos listed of a list without a limit of its own, as by `aim todo list`.
+const LIMIT: i64 = 128;
+
+#[derive(Debug, Clone)]
+pub struct CmdList {
+    /// The list to show the items of, or all lists with their counts if none.
+    pub name: Option<String>,
+    /// Only items with all of these categories, on top of the conditions of the list.
+    pub categories: Vec<String>,
+    /// Only items in this calendar, instead of the one of the list.
+    pub calendar_id: Option<String>,
+    /// Only items with all of these custom metadata, on top of those of the list.
+    pub meta: Vec<(String, String)>,
+    pub output_format: OutputFormat,
+}
+
+impl CmdList {
+    pub const NAME: &str = "list";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Show the smart lists with their counts, or the items of one")
+            .arg(arg!(name: [NAME] "Name of the list, such as today, upcoming, someday or next"))
+            .arg(
+                arg!(--category <CATEGORY> "Only items with this category as well, may be repeated")
+                    .action(ArgAction::Append),
+            )
+            .arg(CalendarArgs::new(true).calendar())
+            .arg(EventOrTodoArgs::new(None).meta())
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            name: matches.get_one("name").cloned(),
+            categories: matches
+                .get_many::<String>("category")
+                .map(|values| values.cloned().collect())
+                .unwrap_or_default(),
+            calendar_id: CalendarArgs::get_calendar(matches),
+            meta: EventOrTodoArgs::get_meta(matches),
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim, config: &Config) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "listing smart lists...");
+        let Some(name) = &self.name else {
+            return self.print_counts(aim).await;
+        };
+        let Some(list) = aim.smart_lists().get(name) else {
+            return Err(format!("No list named '{name}', see `aim list` for the lists").into());
+        };
+
+        match self.narrow(aim, list) {
+            SmartListQuery::Todos { conds, sort, limit } => {
+                let pager = (limit.unwrap_or(LIMIT), 0).into();
+                let mut todos = aim.list_todos(&conds, &sort, &pager).await?;
+                if limit.is_none() && todos.len() >= usize::try_from(LIMIT)? {
+                    let total = aim.count_todos(&conds).await?;
+                    if total > LIMIT {
+                        let prompt = format!("Displaying the {LIMIT}/{total} todos");
+                        println!("{}", prompt.italic());
+                    }
+                } else if todos.is_empty() && self.output_format == OutputFormat::Table {
+                    println!("{}", "No todos found".italic());
+                    return Ok(());
+                }
+
+                // Reversed like `aim todo list`, so the most urgent todo is printed last
+                todos.reverse();
+                let columns = TodoColumns {
+                    chosen: config.todos.columns.clone(),
+                    show_progress: false,
+                };
+                print_todos_with(aim, &todos, self.output_format, &columns, &HashMap::new());
+            }
+            SmartListQuery::Events(conds) => {
+                let dedupe = dedupe_mode(aim, false);
+                let columns = config.events.columns.as_deref();
+                CmdEventList::list(aim, &conds, dedupe, self.output_format, columns).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints every list with the number of items in it, counted by the database.
+    async fn print_counts(&self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        let mut entries = Vec::new();
+        for list in aim.smart_lists().lists() {
+            let (kind, count) = match self.narrow(aim, list) {
+                SmartListQuery::Todos { conds, limit, .. } => {
+                    let count = aim.count_todos(&conds).await?;
+                    ("todos", limit.map_or(count, |limit| count.min(limit)))
+                }
+                SmartListQuery::Events(conds) => ("events", aim.count_events(&conds).await?),
+            };
+            entries.push(ListEntry {
+                name: list.name.clone(),
+                icon: list.icon.clone(),
+                kind,
+                count,
+            });
+        }
+
+        match self.output_format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+            OutputFormat::Table => println!("{}", format_lists(&entries)),
+        }
+        Ok(())
+    }
+
+    /// The query of the list, narrowed down by the options, leaving out the todos starting later
+    /// or waiting on someone like `aim todo list` does.
+    fn narrow(&self, aim: &Aim, list: &SmartList) -> SmartListQuery {
+        let mut query = list.query.clone();
+        match &mut query {
+            SmartListQuery::Todos { conds, .. } => {
+                conds.hide_unstarted = aim.hide_unstarted() && conds.start_after.is_none();
+                conds.hide_waiting = !conds.waiting && conds.waiting_for.is_none();
+                conds.categories.extend_from_slice(&self.categories);
+                conds.meta.extend_from_slice(&self.meta);
+                if let Some(calendar_id) = &self.calendar_id {
+                    conds.calendar_id = Some(calendar_id.clone());
+                }
+            }
+            SmartListQuery::Events(conds) => {
+                conds.categories.extend_from_slice(&self.categories);
+                conds.meta.extend_from_slice(&self.meta);
+                if let Some(calendar_id) = &self.calendar_id {
+                    conds.calendar_id = Some(calendar_id.clone());
+                }
+            }
+        }
+        query
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ListEntry {
+    name: String,
+    icon: Option<String>,
+    kind: &'static str,
+    count: i64,
+}
+
+fn format_lists(entries: &[ListEntry]) -> String {
+    let icon_width = entries
+        .iter()
+        .filter_map(|e| e.icon.as_deref().map(UnicodeWidthStr::width))
+        .max();
+    let width = entries.iter().map(|e| e.name.width()).max().unwrap_or(0);
+    entries
+        .iter()
+        .map(|e| {
+            let name = format!("{}{}", e.name, " ".repeat(width - e.name.width()));
+            let line = format!("{name}  {:>4} {}", e.count, e.kind);
+            match icon_width {
+                Some(icon_width) => {
+                    let icon = e.icon.as_deref().unwrap_or_default();
+                    let pad = " ".repeat(icon_width - icon.width());
+                    format!("{icon}{pad} {line}")
+                }
+                None => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, icon: Option<&str>, count: i64) -> ListEntry {
+        ListEntry {
+            name: name.to_string(),
+            icon: icon.map(ToString::to_string),
+            kind: "todos",
+            count,
+        }
+    }
+
+    #[test]
+    fn formats_lists_with_aligned_icons_and_counts() {
+        let entries = [entry("today", None, 3), entry("upcoming", Some("📅"), 12)];
+        assert_eq!(
+            format_lists(&entries),
+            "   today        3 todos\n📅 upcoming    12 todos"
+        );
+
+        let entries = [entry("next", None, 10)];
+        assert_eq!(format_lists(&entries), "next    10 todos");
+    }
+}
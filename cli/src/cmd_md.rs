@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use aimcal_core::Aim;
+use clap::{ArgMatches, Command, ValueHint, arg, value_parser};
+use colored::Colorize;
+
+#[derive(Debug, Clone)]
+pub struct CmdMdSync {
+    pub dir: PathBuf,
+}
+
+impl CmdMdSync {
+    pub const NAME: &str = "sync";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Mirror the tasks of the Markdown notes in a directory with todos")
+            .arg(
+                arg!(dir: <DIR> "Directory of the Markdown notes")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::DirPath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            dir: matches
+                .get_one::<PathBuf>("dir")
+                .expect("dir is required")
+                .clone(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "syncing markdown notes...");
+        let report = aim.sync_markdown(&self.dir).await?;
+        println!(
+            "Synced {} files: {} todos created, {} completed, {} reopened, {} boxes checked, {} unchecked",
+            report.files,
+            report.created,
+            report.completed,
+            report.reopened,
+            report.checked,
+            report.unchecked
+        );
+        for conflict in &report.conflicts {
+            println!("{} {conflict}", "Conflict:".yellow());
+        }
+        Ok(())
+    }
+}
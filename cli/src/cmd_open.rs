@@ -0,0 +1,219 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opening the links of events and todos, such as the meeting of an event, in the browser.
+
+use std::error::Error;
+use std::process::{Command as Process, Stdio};
+
+use aimcal_core::{Aim, Event, Id, ItemDetails, Kind, Todo};
+use clap::{ArgMatches, Command, arg, value_parser};
+
+use crate::arg::EventOrTodoArgs;
+use crate::resolve::resolve_candidate;
+
+#[derive(Debug, Clone)]
+pub struct CmdOpen {
+    pub id: Id,
+    pub which: Which,
+}
+
+/// Which of the links of the item to open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Which {
+    /// The first one, conferences first, then the URL, then the description.
+    Preferred,
+    /// None, listing them instead.
+    List,
+    /// The one at the 1-based index of the listing.
+    Index(u32),
+}
+
+impl CmdOpen {
+    pub const NAME: &str = "open";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Open the link of an event or todo, such as its meeting, in the browser")
+            .long_about(
+                "\
+Open the link of an event or todo in the browser, preferring its conferences, then its URL, then
+the first link in its description.
+
+Exits with 1 if the item has no link.",
+            )
+            .arg(arg!(id: <ID>).help("The short id or uid of the event or todo to open"))
+            .arg(
+                arg!(--which [INDEX] "List the links of the item, or open the one at the index")
+                    .value_parser(value_parser!(u32).range(1..))
+                    .num_args(0..=1),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        let which = match matches.get_one::<u32>("which") {
+            Some(&index) => Which::Index(index),
+            None if matches.contains_id("which") => Which::List,
+            None => Which::Preferred,
+        };
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            which,
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "opening item...");
+        let candidate = resolve_candidate(aim, &self.id, None).await?;
+        let id = Id::Uid(candidate.uid);
+        let (kind, links) = match candidate.kind {
+            Kind::Event => {
+                let event = aim.get_event(&id).await?;
+                let details = aim.get_event_details(&id).await?;
+                let url = event.url();
+                (
+                    "event",
+                    links(&details, url.as_deref(), event.description().as_deref()),
+                )
+            }
+            Kind::Todo => {
+                let todo = aim.get_todo(&id).await?;
+                let details = aim.get_todo_details(&id).await?;
+                let url = todo.url();
+                (
+                    "todo",
+                    links(&details, url.as_deref(), todo.description().as_deref()),
+                )
+            }
+        };
+        let Some(preferred) = links.first() else {
+            return Err(format!("The {kind} has no URL, conference or link to open").into());
+        };
+
+        let link = match self.which {
+            Which::Preferred => preferred,
+            Which::List => {
+                for (i, link) in links.iter().enumerate() {
+                    println!("{}. {link}", i + 1);
+                }
+                return Ok(());
+            }
+            Which::Index(index) => links
+                .get(index as usize - 1)
+                .ok_or_else(|| format!("The {kind} has only {} links", links.len()))?,
+        };
+        launch(link)
+    }
+}
+
+/// The links of an item in the order they are opened: the conferences, then the URL, read from
+/// the store or else the cache, then the first link in the description.
+fn links(details: &ItemDetails, url: Option<&str>, description: Option<&str>) -> Vec<String> {
+    let mut links = details.conferences.clone();
+    links.extend(details.url.as_deref().or(url).map(str::to_string));
+    links.extend(description.and_then(first_link).map(str::to_string));
+    links.dedup();
+    links
+}
+
+/// Finds the first `http` or `https` link in the text, leaving out the punctuation around it.
+fn first_link(text: &str) -> Option<&str> {
+    let start = text
+        .find("https://")
+        .into_iter()
+        .chain(text.find("http://"))
+        .min()?;
+    let link = text[start..]
+        .split(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\'' | '`'))
+        .next()?;
+    let link = link.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']']);
+    Some(link)
+}
+
+/// Opens the link with the opener of the platform, without waiting for it to exit.
+fn launch(link: &str) -> Result<(), Box<dyn Error>> {
+    let mut opener = if cfg!(target_os = "macos") {
+        Process::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Process::new("cmd");
+        // The first quoted argument of `start` is the title of the window
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Process::new("xdg-open")
+    };
+    opener
+        .arg(link)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to open {link}: {e}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    fn parse(args: &[&str]) -> CmdOpen {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Open(cmd) => cmd,
+            _ => panic!("Expected Open command"),
+        }
+    }
+
+    #[test]
+    fn parses_open_command() {
+        let cmd = parse(&["test", "open", "1"]);
+        assert_eq!(cmd.id, Id::ShortIdOrUid("1".to_string()));
+        assert_eq!(cmd.which, Which::Preferred);
+
+        assert_eq!(parse(&["test", "open", "1", "--which"]).which, Which::List);
+        assert_eq!(
+            parse(&["test", "open", "1", "--which", "2"]).which,
+            Which::Index(2)
+        );
+        assert!(Cli::try_parse_from(["test", "open", "1", "--which", "0"]).is_err());
+    }
+
+    #[test]
+    fn orders_conferences_before_url_before_description() {
+        let details = ItemDetails {
+            conferences: vec!["https://meet.example.com/abc".to_string()],
+            ..ItemDetails::default()
+        };
+        let description = "Agenda at https://docs.example.com/agenda.";
+        assert_eq!(
+            links(
+                &details,
+                Some("https://example.com/ticket"),
+                Some(description)
+            ),
+            [
+                "https://meet.example.com/abc",
+                "https://example.com/ticket",
+                "https://docs.example.com/agenda",
+            ]
+        );
+
+        let details = ItemDetails::default();
+        assert!(links(&details, None, Some("No links here")).is_empty());
+    }
+
+    #[test]
+    fn finds_the_first_link_in_text() {
+        assert_eq!(
+            first_link("Join (http://a.example.com/x) or https://b.example.com"),
+            Some("http://a.example.com/x")
+        );
+        assert_eq!(
+            first_link("<https://example.com/a?b=c>"),
+            Some("https://example.com/a?b=c")
+        );
+        assert_eq!(first_link("mailto:alice@example.com"), None);
+    }
+}
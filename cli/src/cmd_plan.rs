@@ -0,0 +1,293 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Planning todos into blocks of working time in the calendar.
+
+use std::error::Error;
+
+use aimcal_core::{
+    Aim, DateRangeAnchor, DateTimeAnchor, Id, Kind, Pager, Plan, PlanOptions, Todo, TodoConditions,
+    TodoStatus, UnplannedReason,
+};
+use clap::{ArgMatches, Command, arg, value_parser};
+use colored::Colorize;
+use jiff::Zoned;
+
+use crate::arg::{EventOrTodoArgs, RangeArgs};
+use crate::prompt::{can_choose, can_prompt, prompt_block_release, prompt_todos_to_plan};
+use crate::resolve::resolve_id;
+use crate::util::format_datetime;
+
+#[derive(Debug, Clone)]
+pub struct CmdPlan {
+    pub ids: Vec<Id>,
+    pub due_range: Option<DateRangeAnchor>,
+    pub meta: Vec<(String, String)>,
+    pub until: DateTimeAnchor,
+    pub calendar_id: Option<String>,
+    pub dry_run: bool,
+}
+
+impl CmdPlan {
+    pub const NAME: &str = "plan";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Plan todos into blocks of working time in the calendar")
+            .long_about(
+                "\
+Plan a block of working time in the calendar for each of the todos, as long as its estimate set \
+with `aim todo edit --estimate`, or the `default_estimate` of the config. The blocks take the \
+first working time from now on that no event takes, the todos due sooner first, then the ones of \
+higher priority, so planning the same todos again gives the same blocks. The blocks are events \
+related to their todo, with their summary prefixed by [Plan]. Todos done or with blocks planned \
+ahead are left out.
+
+Without ids or filters, the open todos to plan are chosen from a list.",
+            )
+            .arg(
+                arg!(id: [ID])
+                    .help("The short ids or uids of the todos to plan")
+                    .num_args(0..),
+            )
+            .args(RangeArgs::new(true).args())
+            .arg(EventOrTodoArgs::new(Some(Kind::Todo)).meta())
+            .arg(
+                arg!(--until <TIME> "End of the planning horizon (friday, 2025-01-01, 3d...)")
+                    .value_parser(value_parser!(DateTimeAnchor))
+                    .default_value("7d"),
+            )
+            .arg(
+                arg!(--calendar <ID>)
+                    .help("Calendar to create the blocks in, the default one if not given"),
+            )
+            .arg(arg!(--"dry-run" "Print the proposed blocks without creating them"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            ids: matches
+                .get_many::<String>("id")
+                .unwrap_or_default()
+                .map(|id| Id::ShortIdOrUid(id.clone()))
+                .collect(),
+            due_range: RangeArgs::new(true).get_range(matches),
+            meta: EventOrTodoArgs::get_meta(matches),
+            until: matches
+                .get_one("until")
+                .cloned()
+                .unwrap_or(DateTimeAnchor::InDays(7)),
+            calendar_id: matches.get_one("calendar").cloned(),
+            dry_run: matches.get_flag("dry-run"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "planning todos...");
+        let mut ids = Vec::with_capacity(self.ids.len());
+        for id in &self.ids {
+            ids.push(resolve_id(aim, id, Some(Kind::Todo)).await?);
+        }
+
+        let pager: Pager = (i64::MAX, 0).into();
+        if self.due_range.is_some() || !self.meta.is_empty() {
+            let conds = self.open_conditions(aim)?;
+            let todos = aim.list_todos(&conds, &[], &pager).await?;
+            ids.extend(todos.iter().map(|todo| Id::Uid(todo.uid().into_owned())));
+        } else if ids.is_empty() {
+            if !can_prompt() {
+                return Err("Give the todos to plan or a filter, or run interactively".into());
+            }
+            let conds = self.open_conditions(aim)?;
+            let todos = aim.list_todos(&conds, &[], &pager).await?;
+            if todos.is_empty() {
+                println!("{}", "No open todos to plan".italic());
+                return Ok(());
+            }
+            let items: Vec<_> = todos
+                .iter()
+                .map(|todo| {
+                    let id = todo
+                        .short_id()
+                        .map_or_else(|| todo.uid().into_owned(), |id| id.get().to_string());
+                    let due = todo
+                        .due()
+                        .map(|due| format!("due {}", format_datetime(due)));
+                    (format!("#{id} {}", todo.summary()), due.unwrap_or_default())
+                })
+                .collect();
+            let Some(chosen) = prompt_todos_to_plan(&items)? else {
+                tracing::info!("user cancel the planning");
+                return Ok(());
+            };
+            ids.extend(
+                chosen
+                    .into_iter()
+                    .filter_map(|i| todos.get(i))
+                    .map(|todo| Id::Uid(todo.uid().into_owned())),
+            );
+        }
+        if ids.is_empty() {
+            println!("{}", "No todos to plan".italic());
+            return Ok(());
+        }
+
+        let options = PlanOptions {
+            until: self.until,
+            calendar_id: self.calendar_id,
+            dry_run: self.dry_run,
+        };
+        let plan = aim.plan_todos(&ids, &options).await?;
+        print_plan(&plan, self.dry_run);
+        Ok(())
+    }
+
+    /// The conditions of the open todos matching the filters, all of them without any.
+    fn open_conditions(&self, aim: &Aim) -> Result<TodoConditions, Box<dyn Error>> {
+        // Todos are due by the end of the range, so overdue ones are planned as well
+        let due = self
+            .due_range
+            .map(|range| range.resolve_anchors(aim.now().date(), aim.week_start()))
+            .transpose()?
+            .map(|(_, cutoff)| cutoff);
+        Ok(TodoConditions {
+            status: Some(TodoStatus::NeedsAction),
+            due,
+            start_after: None,
+            hide_unstarted: aim.hide_unstarted(),
+            calendar_id: None,
+            include_archived: false,
+            meta: self.meta.clone(),
+            inbox: false,
+            hide_waiting: true,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
+        })
+    }
+}
+
+/// Offers to release the blocks planned ahead for the todos once completed, see
+/// [`Aim::release_planned_blocks`]. The blocks are kept where no one can be asked.
+pub async fn offer_block_release(
+    aim: &Aim,
+    ids: &[Id],
+    status: TodoStatus,
+) -> Result<(), Box<dyn Error>> {
+    if status != TodoStatus::Completed || !can_choose() {
+        return Ok(());
+    }
+    for id in ids {
+        let blocks = aim.planned_blocks(id).await?;
+        if blocks.is_empty() {
+            continue;
+        }
+        let summary = aim.get_todo(id).await?.summary().into_owned();
+        let release = prompt_block_release(&summary, blocks.len())?;
+        if let Some(release) = release {
+            aim.release_planned_blocks(id, release).await?;
+        }
+    }
+    Ok(())
+}
+
+fn print_plan(plan: &Plan, dry_run: bool) {
+    if plan.blocks.is_empty() {
+        println!("{}", "No blocks planned".italic());
+    } else if dry_run {
+        println!("{}", "Proposed blocks, nothing was created:".bold());
+    } else {
+        println!("{}", "Planned blocks:".bold());
+    }
+    for block in &plan.blocks {
+        println!(
+            "  {}  {}",
+            format_span(&block.start, &block.end),
+            block.summary
+        );
+    }
+    for todo in &plan.unplanned {
+        let reason = match todo.reason {
+            UnplannedReason::Done => "it is done".to_string(),
+            UnplannedReason::AlreadyPlanned => "it has blocks planned ahead".to_string(),
+            UnplannedReason::NoFreeSlot(estimate) => {
+                format!("no free working time of {estimate:#} before the horizon")
+            }
+        };
+        println!("{} {}: {reason}", "Not planned".yellow(), todo.summary);
+    }
+}
+
+/// Formats a block as its day and times, such as `Mon 2025-01-06 09:00-10:30`.
+fn format_span(start: &Zoned, end: &Zoned) -> String {
+    format!(
+        "{}-{}",
+        start.strftime("%a %Y-%m-%d %H:%M"),
+        end.strftime("%H:%M")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    fn parse(args: &[&str]) -> CmdPlan {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Plan(cmd) => cmd,
+            _ => panic!("Expected Plan command"),
+        }
+    }
+
+    #[test]
+    fn parses_plan_command() {
+        let cmd = parse(&["test", "plan"]);
+        assert!(cmd.ids.is_empty());
+        assert_eq!(cmd.due_range, None);
+        assert_eq!(cmd.until, DateTimeAnchor::InDays(7));
+        assert!(!cmd.dry_run);
+
+        let cmd = parse(&[
+            "test",
+            "plan",
+            "1",
+            "2",
+            "--until",
+            "3d",
+            "--due-week",
+            "--calendar",
+            "work",
+            "--dry-run",
+        ]);
+        assert_eq!(
+            cmd.ids,
+            [
+                Id::ShortIdOrUid("1".to_string()),
+                Id::ShortIdOrUid("2".to_string())
+            ]
+        );
+        assert_eq!(cmd.due_range, Some(DateRangeAnchor::this_week()));
+        assert_eq!(cmd.until, DateTimeAnchor::InDays(3));
+        assert_eq!(cmd.calendar_id.as_deref(), Some("work"));
+        assert!(cmd.dry_run);
+    }
+
+    #[test]
+    fn formats_spans_of_blocks() {
+        let at = |h, m| {
+            date(2025, 1, 6)
+                .to_datetime(time(h, m, 0, 0))
+                .in_tz("UTC")
+                .unwrap()
+        };
+        assert_eq!(
+            format_span(&at(9, 0), &at(10, 30)),
+            "Mon 2025-01-06 09:00-10:30"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use aimcal_core::{AimError, Profile};
+use clap::{ArgMatches, Command, arg};
+
+use crate::arg::CommonArgs;
+use crate::config::{ConfigLocation, active_profile, profile_dirs};
+use crate::util::OutputFormat;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdProfileList {
+    pub output_format: OutputFormat,
+}
+
+impl CmdProfileList {
+    pub const NAME: &str = "list";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("List the profiles, marking the one in use")
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    /// Lists the profiles without opening [`Aim`](aimcal_core::Aim), which they don't need.
+    pub fn run(self, location: ConfigLocation) -> Result<(), Box<dyn Error>> {
+        let dirs = profile_dirs()?;
+        let active = active_profile(location);
+        let profiles: Vec<_> = dirs
+            .list()?
+            .into_iter()
+            .map(|profile| ProfileEntry {
+                active: active.as_ref() == Some(&profile),
+                config: dirs.config_file(&profile),
+                state_dir: dirs.state_dir(&profile),
+                name: profile.to_string(),
+            })
+            .collect();
+
+        match self.output_format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&profiles)?),
+            OutputFormat::Table => println!("{}", format_profiles(&profiles)),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ProfileEntry {
+    name: String,
+    active: bool,
+    config: PathBuf,
+    state_dir: PathBuf,
+}
+
+fn format_profiles(profiles: &[ProfileEntry]) -> String {
+    if profiles.is_empty() {
+        return "No profiles found.".to_string();
+    }
+    let width = profiles.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    profiles
+        .iter()
+        .map(|p| {
+            let marker = if p.active { '*' } else { ' ' };
+            format!("{marker} {:width$}  {}", p.name, p.config.display())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdProfileCreate {
+    pub profile: Profile,
+}
+
+impl CmdProfileCreate {
+    pub const NAME: &str = "create";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Create a profile with its own config and state")
+            .arg(arg!(name: <NAME> "Name of the profile").value_parser(parse_profile))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            profile: matches
+                .get_one::<Profile>("name")
+                .expect("name is required")
+                .clone(),
+        }
+    }
+
+    pub fn run(self) -> Result<(), Box<dyn Error>> {
+        let path = profile_dirs()?.create(&self.profile)?;
+        println!(
+            "Created profile '{}', its config is at {}",
+            self.profile,
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdProfileRemove {
+    pub profile: Profile,
+    pub purge: bool,
+}
+
+impl CmdProfileRemove {
+    pub const NAME: &str = "remove";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Remove a profile, keeping its database and calendars unless purged")
+            .arg(arg!(name: <NAME> "Name of the profile").value_parser(parse_profile))
+            .arg(arg!(--purge "Also delete the database and local calendars of the profile"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            profile: matches
+                .get_one::<Profile>("name")
+                .expect("name is required")
+                .clone(),
+            purge: matches.get_flag("purge"),
+        }
+    }
+
+    pub fn run(self) -> Result<(), Box<dyn Error>> {
+        let dirs = profile_dirs()?;
+        dirs.remove(&self.profile, self.purge)?;
+        if self.purge {
+            println!("Removed profile '{}' and its data", self.profile);
+        } else {
+            println!(
+                "Removed profile '{}', its data is kept at {}",
+                self.profile,
+                dirs.state_dir(&self.profile).display()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn parse_profile(s: &str) -> Result<Profile, String> {
+    s.parse().map_err(|e: AimError| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_profiles_marking_the_active_one() {
+        let entry = |name: &str, active| ProfileEntry {
+            name: name.to_string(),
+            active,
+            config: PathBuf::from(format!("/c/{name}.toml")),
+            state_dir: PathBuf::from(format!("/s/{name}")),
+        };
+        let profiles = [entry("default", false), entry("work", true)];
+        assert_eq!(
+            format_profiles(&profiles),
+            "  default  /c/default.toml\n* work     /c/work.toml"
+        );
+        assert_eq!(format_profiles(&[]), "No profiles found.");
+    }
+}
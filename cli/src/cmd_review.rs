@@ -0,0 +1,839 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Weekly review of the open todos, deciding what to do with one todo at a time.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aimcal_core::{
+    Aim, AimError, DateTimeAnchor, Id, LooseDateTime, OverdueConfig, Pager, Priority, Todo,
+    TodoConditions, TodoPatch, TodoStatus, overdue_age,
+};
+use clap::{ArgMatches, Command, ValueEnum, arg, value_parser};
+use colored::Colorize;
+use jiff::Zoned;
+use jiff::civil::{Date, DateTime};
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::terminal;
+
+use crate::details_formatter::DetailRows;
+use crate::prompt::is_terminal;
+use crate::util::{format_datetime, write_json_atomic};
+
+/// Name of the file keeping the review in progress, in the state directory.
+pub const SESSION_FILE: &str = "review-session.json";
+
+/// Version of the session schema, bumped whenever a saved session can no longer be read as is.
+const SESSION_VERSION: u32 = 1;
+
+const KEYS_HELP: &str = "[k]eep  [d]one  [x] drop  [s]nooze  [e]dit  [u]ndo  [q]uit";
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdReview {
+    pub order: ReviewOrder,
+    pub resume: bool,
+}
+
+impl CmdReview {
+    pub const NAME: &str = "review";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Review the open todos one at a time")
+            .long_about(
+                "Review the open todos one at a time, deciding with a single key what to do with each:
+k keeps it, d marks it done, x drops it as cancelled, s snoozes it, e edits its summary,
+u undoes the last action and q quits. A review left with q is picked up by --continue.",
+            )
+            .arg(
+                arg!(--order <ORDER> "Order to review the todos in")
+                    .value_parser(value_parser!(ReviewOrder))
+                    .default_value("oldest"),
+            )
+            .arg(arg!(--continue "Continue the review left last time").conflicts_with("order"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            order: matches
+                .get_one::<ReviewOrder>("order")
+                .copied()
+                .unwrap_or_default(),
+            resume: matches.get_flag("continue"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "reviewing todos...");
+        let path = aim.state_dir().map(|dir| dir.join(SESSION_FILE));
+        let session = if self.resume {
+            path.as_deref()
+                .and_then(ReviewSession::load)
+                .ok_or("No review to continue, start one with `aim review`")?
+        } else {
+            ReviewSession::new(self.order, open_todos(aim, self.order).await?)
+        };
+        if session.queue.is_empty() {
+            println!("{}", "No open todos to review".italic());
+            return Ok(());
+        }
+
+        let mut review = Review { aim, session, path };
+        let finished = review.run().await?;
+        println!();
+        println!("{}", review.session.summary());
+        if finished {
+            review.discard();
+        } else if review.path.is_some() {
+            println!("Review paused, run `aim review --continue` to pick it up again");
+        }
+        Ok(())
+    }
+}
+
+/// Order to review the todos in.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewOrder {
+    /// The todos aim has known the longest first, by short ID.
+    #[default]
+    Oldest,
+    /// Overdue and soonest due first, then by priority, with undated todos last. If overdue
+    /// todos escalate, they come first by their escalated priority instead.
+    Urgency,
+}
+
+/// Lists the UIDs of the todos still to act on, in the order to review them.
+async fn open_todos(aim: &Aim, order: ReviewOrder) -> Result<Vec<String>, Box<dyn Error>> {
+    let conds = TodoConditions {
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        calendar_id: None,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager: Pager = (i64::MAX, 0).into();
+    let mut todos: Vec<_> = aim
+        .list_todos(&conds, &[], &pager)
+        .await?
+        .into_iter()
+        .filter(|todo| is_open(todo.status()))
+        .collect();
+    sort_todos(&mut todos, order, &aim.now(), aim.overdue());
+    Ok(todos.iter().map(|todo| todo.uid().into_owned()).collect())
+}
+
+fn is_open(status: TodoStatus) -> bool {
+    matches!(status, TodoStatus::NeedsAction | TodoStatus::InProcess)
+}
+
+fn sort_todos(todos: &mut [impl Todo], order: ReviewOrder, now: &Zoned, overdue: &OverdueConfig) {
+    match order {
+        ReviewOrder::Oldest => {
+            todos.sort_by_key(|todo| (todo.short_id().is_none(), todo.short_id()));
+        }
+        ReviewOrder::Urgency => todos.sort_by_key(|todo| {
+            let escalated = todo
+                .due()
+                .and_then(|due| overdue_age(&due, now))
+                .filter(|_| overdue.escalate)
+                .map(|age| priority_rank(overdue.effective_priority(todo.priority(), Some(age))));
+            let due = todo.due().map(|due| match due {
+                LooseDateTime::Local(zoned) => {
+                    zoned.with_time_zone(now.time_zone().clone()).datetime()
+                }
+                due => due.with_end_of_day(),
+            });
+            (
+                escalated.is_none(),
+                escalated,
+                due.is_none(),
+                due,
+                priority_rank(todo.priority()),
+            )
+        }),
+    }
+}
+
+/// Ranks the priorities from the highest, with no priority last.
+fn priority_rank(priority: Priority) -> u8 {
+    match u8::from(priority) {
+        0 => 10,
+        n => n,
+    }
+}
+
+/// A key pressed while reviewing a todo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Keep,
+    Done,
+    Drop,
+    Snooze,
+    Edit,
+    Undo,
+    Quit,
+}
+
+impl Key {
+    fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_lowercase() {
+            'k' => Some(Key::Keep),
+            'd' => Some(Key::Done),
+            'x' => Some(Key::Drop),
+            's' => Some(Key::Snooze),
+            'e' => Some(Key::Edit),
+            'u' => Some(Key::Undo),
+            'q' => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// A review in progress, saved after every action so that it survives quitting.
+#[derive(Debug)]
+struct Review<'a> {
+    aim: &'a Aim,
+    session: ReviewSession,
+    path: Option<PathBuf>,
+}
+
+impl Review<'_> {
+    /// Reviews the remaining todos, returning whether the review reached the end.
+    async fn run(&mut self) -> Result<bool, Box<dyn Error>> {
+        while let Some(uid) = self.session.current().map(str::to_string) {
+            let todo = match self.aim.get_todo(&Id::Uid(uid.clone())).await {
+                Ok(todo) if is_open(todo.status()) => todo,
+                // Finished or removed elsewhere since the review started
+                Ok(_) | Err(AimError::NotFound { .. }) => {
+                    self.session.position += 1;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            println!();
+            println!("{}", self.session.progress().bold());
+            println!("{}", todo_rows(&todo));
+            println!("{}", KEYS_HELP.dimmed());
+            let Some(key) = read_key()? else {
+                self.save();
+                return Ok(false);
+            };
+            let Some(key) = Key::from_char(key) else {
+                println!("Unknown key '{key}'");
+                continue;
+            };
+
+            let action = match key {
+                Key::Keep => Some(ReviewAction::Kept),
+                Key::Done => self.set_status(&uid, &todo, TodoStatus::Completed).await?,
+                Key::Drop => self.set_status(&uid, &todo, TodoStatus::Cancelled).await?,
+                Key::Snooze => self.snooze(&uid, &todo).await?,
+                Key::Edit => self.edit(&uid, &todo).await?,
+                Key::Undo => {
+                    self.undo().await?;
+                    None
+                }
+                Key::Quit => {
+                    self.save();
+                    return Ok(false);
+                }
+            };
+            if let Some(action) = action {
+                println!("{} {}", action.verb().green(), todo.summary());
+                self.session
+                    .record(uid, todo.summary().into_owned(), action);
+            }
+            self.save();
+        }
+        Ok(true)
+    }
+
+    async fn set_status(
+        &self,
+        uid: &str,
+        todo: &impl Todo,
+        status: TodoStatus,
+    ) -> Result<Option<ReviewAction>, Box<dyn Error>> {
+        let patch = TodoPatch {
+            status: Some(status),
+            ..Default::default()
+        };
+        self.aim
+            .update_todo(&Id::Uid(uid.to_string()), patch)
+            .await?;
+        let previous = PreviousStatus {
+            status: todo.status().to_string(),
+            percent_complete: todo.percent_complete(),
+        };
+        Ok(Some(match status {
+            TodoStatus::Cancelled => ReviewAction::Dropped(previous),
+            _ => ReviewAction::Done(previous),
+        }))
+    }
+
+    async fn snooze(
+        &self,
+        uid: &str,
+        todo: &impl Todo,
+    ) -> Result<Option<ReviewAction>, Box<dyn Error>> {
+        let input = ask("Snooze by (e.g., 1d, 2h, tomorrow):", None)?;
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+        let delta = DateTimeAnchor::from_str(input.trim())?;
+        self.aim
+            .snooze_todo(&Id::Uid(uid.to_string()), delta)
+            .await?;
+        Ok(Some(ReviewAction::Snoozed {
+            due: todo.due().map(Into::into),
+        }))
+    }
+
+    async fn edit(
+        &self,
+        uid: &str,
+        todo: &impl Todo,
+    ) -> Result<Option<ReviewAction>, Box<dyn Error>> {
+        let summary = todo.summary();
+        let input = ask("Summary:", Some(&summary))?;
+        let input = input.trim();
+        if input.is_empty() || input == summary {
+            return Ok(None);
+        }
+        let patch = TodoPatch {
+            summary: Some(input.to_string()),
+            ..Default::default()
+        };
+        self.aim
+            .update_todo(&Id::Uid(uid.to_string()), patch)
+            .await?;
+        Ok(Some(ReviewAction::Edited {
+            summary: summary.into_owned(),
+        }))
+    }
+
+    /// Reverts the last action and goes back to its todo.
+    async fn undo(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(entry) = self.session.log.last() else {
+            println!("Nothing to undo");
+            return Ok(());
+        };
+        if let Some(patch) = entry.action.undo_patch() {
+            self.aim
+                .update_todo(&Id::Uid(entry.uid.clone()), patch)
+                .await?;
+        }
+        println!("{} {}", "Undid".yellow(), entry.summary);
+        self.session.undo();
+        Ok(())
+    }
+
+    /// Saves the session, logging rather than failing so that a review can go on without it.
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = write_json_atomic(path, &self.session) {
+            tracing::warn!(path = %path.display(), error = %e, "failed to save review session");
+        }
+    }
+
+    /// Removes the saved session once the review is over.
+    fn discard(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        match fs::remove_file(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "failed to remove review session");
+            }
+        }
+    }
+}
+
+fn todo_rows(todo: &impl Todo) -> DetailRows<'static> {
+    let mut rows = DetailRows::new();
+    rows.push_opt("ID", todo.short_id().map(|id| id.to_string()));
+    rows.push("Summary", todo.summary().into_owned());
+    rows.push_opt("Start", todo.start().map(format_datetime));
+    rows.push_opt("Due", todo.due().map(format_datetime));
+    rows.push("Status", todo.status().to_string());
+    if todo.priority() != Priority::None {
+        rows.push("Priority", u8::from(todo.priority()).to_string());
+    }
+    rows.push_opt(
+        "Percent Complete",
+        todo.percent_complete().map(|p| format!("{p}%")),
+    );
+    rows.push_opt("Description", todo.description().map(Cow::into_owned));
+    rows
+}
+
+/// Reads a single key press, or the first character of a line when stdin is not a terminal.
+///
+/// Returns none at the end of input, on Escape or on Ctrl-C.
+pub(crate) fn read_key() -> io::Result<Option<char>> {
+    if !is_terminal() {
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        return Ok(line.trim().chars().next().or(Some(' ')));
+    }
+
+    terminal::enable_raw_mode()?;
+    let key = loop {
+        let key = match event::read() {
+            Ok(TermEvent::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Ok(None),
+            KeyCode::Char(c) => break Ok(Some(c)),
+            KeyCode::Esc => break Ok(None),
+            _ => {}
+        }
+    };
+    terminal::disable_raw_mode()?;
+    key
+}
+
+/// Asks for a line of text, starting from `initial` when stdin is a terminal.
+pub(crate) fn ask(prompt: &str, initial: Option<&str>) -> io::Result<String> {
+    if is_terminal() {
+        let mut input = cliclack::input(prompt).required(false);
+        if let Some(initial) = initial {
+            input = input.default_input(initial);
+        }
+        return input.interact();
+    }
+
+    print!("{prompt} ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line)
+}
+
+/// The review state kept between runs, to pick up a review left with `q`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ReviewSession {
+    version: u32,
+    order: ReviewOrder,
+    /// UIDs of the todos to review, in order.
+    queue: Vec<String>,
+    /// Index in `queue` of the todo under review.
+    position: usize,
+    /// Actions taken so far, to undo and summarize them.
+    #[serde(default)]
+    log: Vec<ReviewEntry>,
+}
+
+impl ReviewSession {
+    fn new(order: ReviewOrder, queue: Vec<String>) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            order,
+            queue,
+            position: 0,
+            log: Vec::new(),
+        }
+    }
+
+    /// Loads the session saved at `path`, if there is a readable one of this schema version.
+    fn load(path: &Path) -> Option<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "failed to read review session");
+                return None;
+            }
+        };
+        match serde_json::from_str::<Self>(&content) {
+            Ok(session) if session.version == SESSION_VERSION => Some(session),
+            Ok(session) => {
+                tracing::info!(
+                    version = session.version,
+                    "discarding review session of another version"
+                );
+                None
+            }
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "discarding corrupt review session");
+                None
+            }
+        }
+    }
+
+    /// UID of the todo under review, none once the review is over.
+    fn current(&self) -> Option<&str> {
+        self.queue.get(self.position).map(String::as_str)
+    }
+
+    fn progress(&self) -> String {
+        format!("{} of {}", self.position + 1, self.queue.len())
+    }
+
+    /// Records the action taken on the todo under review and moves on to the next one.
+    fn record(&mut self, uid: String, summary: String, action: ReviewAction) {
+        self.log.push(ReviewEntry {
+            index: self.position,
+            uid,
+            summary,
+            action,
+        });
+        self.position += 1;
+    }
+
+    /// Forgets the last action and goes back to its todo.
+    fn undo(&mut self) -> Option<ReviewEntry> {
+        let entry = self.log.pop()?;
+        self.position = entry.index;
+        Some(entry)
+    }
+
+    fn summary(&self) -> String {
+        let count = |f: fn(&ReviewAction) -> bool| self.log.iter().filter(|e| f(&e.action)).count();
+        format!(
+            "Reviewed {} of {} todos: {} kept, {} done, {} dropped, {} snoozed, {} edited",
+            self.position.min(self.queue.len()),
+            self.queue.len(),
+            count(|a| matches!(a, ReviewAction::Kept)),
+            count(|a| matches!(a, ReviewAction::Done(_))),
+            count(|a| matches!(a, ReviewAction::Dropped(_))),
+            count(|a| matches!(a, ReviewAction::Snoozed { .. })),
+            count(|a| matches!(a, ReviewAction::Edited { .. })),
+        )
+    }
+}
+
+/// An action taken during the review, with what it replaced to undo it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct ReviewEntry {
+    /// Index of the todo in the queue.
+    index: usize,
+    uid: String,
+    summary: String,
+    action: ReviewAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ReviewAction {
+    Kept,
+    Done(PreviousStatus),
+    Dropped(PreviousStatus),
+    Snoozed { due: Option<StoredDateTime> },
+    Edited { summary: String },
+}
+
+impl ReviewAction {
+    fn verb(&self) -> &'static str {
+        match self {
+            ReviewAction::Kept => "Kept",
+            ReviewAction::Done(_) => "Done",
+            ReviewAction::Dropped(_) => "Dropped",
+            ReviewAction::Snoozed { .. } => "Snoozed",
+            ReviewAction::Edited { .. } => "Edited",
+        }
+    }
+
+    /// The patch restoring the todo as it was before the action.
+    fn undo_patch(&self) -> Option<TodoPatch> {
+        let patch = match self {
+            ReviewAction::Kept => return None,
+            ReviewAction::Done(previous) | ReviewAction::Dropped(previous) => TodoPatch {
+                status: Some(previous.status.parse().unwrap_or_default()),
+                percent_complete: Some(previous.percent_complete),
+                ..Default::default()
+            },
+            ReviewAction::Snoozed { due } => TodoPatch {
+                due: Some(due.clone().map(Into::into)),
+                ..Default::default()
+            },
+            ReviewAction::Edited { summary } => TodoPatch {
+                summary: Some(summary.clone()),
+                ..Default::default()
+            },
+        };
+        Some(patch)
+    }
+}
+
+/// Status of a todo before it was marked done or dropped.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct PreviousStatus {
+    status: String,
+    percent_complete: Option<u8>,
+}
+
+/// [`LooseDateTime`] as written to the session file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum StoredDateTime {
+    Date(Date),
+    Floating(DateTime),
+    Local(Zoned),
+}
+
+impl From<LooseDateTime> for StoredDateTime {
+    fn from(dt: LooseDateTime) -> Self {
+        match dt {
+            LooseDateTime::DateOnly(date) => StoredDateTime::Date(date),
+            LooseDateTime::Floating(dt) => StoredDateTime::Floating(dt),
+            LooseDateTime::Local(zoned) => StoredDateTime::Local(zoned),
+        }
+    }
+}
+
+impl From<StoredDateTime> for LooseDateTime {
+    fn from(dt: StoredDateTime) -> Self {
+        match dt {
+            StoredDateTime::Date(date) => LooseDateTime::DateOnly(date),
+            StoredDateTime::Floating(dt) => LooseDateTime::Floating(dt),
+            StoredDateTime::Local(zoned) => LooseDateTime::Local(zoned),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+    use crate::cli::{Cli, Commands};
+
+    #[derive(Debug, Clone)]
+    struct TestTodo {
+        uid: &'static str,
+        due: Option<Date>,
+        priority: Priority,
+    }
+
+    impl Todo for TestTodo {
+        fn uid(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn completed(&self) -> Option<Zoned> {
+            None
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn start(&self) -> Option<LooseDateTime> {
+            None
+        }
+
+        fn due(&self) -> Option<LooseDateTime> {
+            self.due.map(LooseDateTime::DateOnly)
+        }
+
+        fn percent_complete(&self) -> Option<u8> {
+            None
+        }
+
+        fn priority(&self) -> Priority {
+            self.priority
+        }
+
+        fn status(&self) -> TodoStatus {
+            TodoStatus::NeedsAction
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+    }
+
+    fn uids(todos: &[TestTodo]) -> Vec<&str> {
+        todos.iter().map(|todo| todo.uid).collect()
+    }
+
+    fn parse(args: &[&str]) -> CmdReview {
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Review(cmd) => cmd,
+            _ => panic!("Expected Review command"),
+        }
+    }
+
+    #[test]
+    fn escalates_long_overdue_todos_by_urgency() {
+        let now = date(2026, 10, 14).at(10, 0, 0, 0).in_tz("UTC").unwrap();
+        let todo = |uid, due, priority| TestTodo { uid, due, priority };
+        let mut todos = vec![
+            todo("undated", None, Priority::P1),
+            todo("tomorrow", Some(date(2026, 10, 15)), Priority::P1),
+            todo("old low", Some(date(2026, 9, 1)), Priority::P9),
+            todo("recent high", Some(date(2026, 10, 12)), Priority::P2),
+        ];
+        let mut overdue = OverdueConfig::default();
+
+        sort_todos(&mut todos, ReviewOrder::Urgency, &now, &overdue);
+        assert_eq!(
+            uids(&todos),
+            ["old low", "recent high", "tomorrow", "undated"]
+        );
+
+        // Escalated by 2 and 1, old low ranks as P7 and recent high as P1
+        overdue.escalate = true;
+        todos.reverse();
+        sort_todos(&mut todos, ReviewOrder::Urgency, &now, &overdue);
+        assert_eq!(
+            uids(&todos),
+            ["recent high", "old low", "tomorrow", "undated"]
+        );
+    }
+
+    #[test]
+    fn parses_review_command() {
+        let cmd = parse(&["test", "review"]);
+        assert_eq!(cmd.order, ReviewOrder::Oldest);
+        assert!(!cmd.resume);
+
+        let cmd = parse(&["test", "review", "--order", "urgency"]);
+        assert_eq!(cmd.order, ReviewOrder::Urgency);
+
+        let cmd = parse(&["test", "review", "--continue"]);
+        assert!(cmd.resume);
+
+        assert!(
+            Cli::try_parse_from(["test", "review", "--continue", "--order", "oldest"]).is_err()
+        );
+    }
+
+    #[test]
+    fn maps_keys_to_actions() {
+        assert_eq!(Key::from_char('k'), Some(Key::Keep));
+        assert_eq!(Key::from_char('D'), Some(Key::Done));
+        assert_eq!(Key::from_char('x'), Some(Key::Drop));
+        assert_eq!(Key::from_char('s'), Some(Key::Snooze));
+        assert_eq!(Key::from_char('e'), Some(Key::Edit));
+        assert_eq!(Key::from_char('u'), Some(Key::Undo));
+        assert_eq!(Key::from_char('q'), Some(Key::Quit));
+        assert_eq!(Key::from_char('z'), None);
+    }
+
+    #[test]
+    fn ranks_no_priority_last() {
+        let mut priorities = [Priority::None, Priority::P5, Priority::P1];
+        priorities.sort_by_key(|p| priority_rank(*p));
+        assert_eq!(priorities, [Priority::P1, Priority::P5, Priority::None]);
+    }
+
+    #[test]
+    fn undo_goes_back_to_the_todo_of_the_last_action() {
+        let queue = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut session = ReviewSession::new(ReviewOrder::Oldest, queue);
+        session.record("a".to_string(), "A".to_string(), ReviewAction::Kept);
+        // "b" was finished elsewhere and skipped
+        session.position += 1;
+        assert_eq!(session.current(), Some("c"));
+
+        let entry = session.undo().unwrap();
+        assert_eq!(entry.uid, "a");
+        assert_eq!(session.current(), Some("a"));
+        assert_eq!(session.progress(), "1 of 3");
+        assert!(session.undo().is_none());
+    }
+
+    #[test]
+    fn summarizes_actions() {
+        let queue = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut session = ReviewSession::new(ReviewOrder::Urgency, queue);
+        let previous = PreviousStatus {
+            status: TodoStatus::NeedsAction.to_string(),
+            percent_complete: None,
+        };
+        session.record("a".to_string(), "A".to_string(), ReviewAction::Kept);
+        session.record(
+            "b".to_string(),
+            "B".to_string(),
+            ReviewAction::Done(previous),
+        );
+        assert_eq!(
+            session.summary(),
+            "Reviewed 2 of 3 todos: 1 kept, 1 done, 0 dropped, 0 snoozed, 0 edited"
+        );
+    }
+
+    #[test]
+    fn undo_patch_restores_previous_values() {
+        let done = ReviewAction::Done(PreviousStatus {
+            status: TodoStatus::InProcess.to_string(),
+            percent_complete: Some(40),
+        });
+        let patch = done.undo_patch().unwrap();
+        assert_eq!(patch.status, Some(TodoStatus::InProcess));
+        assert_eq!(patch.percent_complete, Some(Some(40)));
+        assert!(patch.validate().is_ok());
+
+        let due = LooseDateTime::DateOnly(date(2025, 1, 15));
+        let snoozed = ReviewAction::Snoozed {
+            due: Some(due.clone().into()),
+        };
+        assert_eq!(snoozed.undo_patch().unwrap().due, Some(Some(due)));
+        assert!(ReviewAction::Kept.undo_patch().is_none());
+    }
+
+    #[test]
+    fn restores_saved_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SESSION_FILE);
+        assert_eq!(ReviewSession::load(&path), None);
+
+        let mut session = ReviewSession::new(ReviewOrder::Urgency, vec!["a".to_string()]);
+        let due = date(2025, 1, 15)
+            .at(9, 0, 0, 0)
+            .to_zoned(TimeZone::get("Asia/Shanghai").unwrap())
+            .unwrap();
+        let action = ReviewAction::Snoozed {
+            due: Some(LooseDateTime::Local(due).into()),
+        };
+        session.record("a".to_string(), "A".to_string(), action);
+        write_json_atomic(&path, &session).unwrap();
+        assert_eq!(ReviewSession::load(&path), Some(session));
+    }
+
+    #[test]
+    fn ignores_corrupt_or_outdated_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SESSION_FILE);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(ReviewSession::load(&path), None);
+
+        fs::write(
+            &path,
+            r#"{ "version": 999, "order": "oldest", "queue": [], "position": 0 }"#,
+        )
+        .unwrap();
+        assert_eq!(ReviewSession::load(&path), None);
+    }
+}
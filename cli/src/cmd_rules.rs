@@ -0,0 +1,199 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::fmt::Write;
+
+use aimcal_core::{Aim, Id, Kind, Priority, RuleTest, RuleVerdict, RulesReport};
+use clap::{ArgGroup, ArgMatches, Command, arg};
+
+use crate::resolve::resolve_id;
+
+#[derive(Debug, Clone)]
+pub struct CmdRulesApply {
+    /// The items to apply the rules to, all of them if empty.
+    pub ids: Vec<Id>,
+}
+
+impl CmdRulesApply {
+    pub const NAME: &str = "apply";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Apply the categorization rules again, such as after changing them")
+            .long_about(
+                "\
+Apply the [[core.rules]] of the config again to cached items. Items are categorized as they are \
+cached, so this is only needed after changing the rules. What the rules add is kept in the cache, \
+the calendars are left untouched, and what a removed rule added goes away.",
+            )
+            .arg(
+                arg!(id: [ID])
+                    .help("The short id or uid of the event or todo")
+                    .num_args(1..),
+            )
+            .arg(arg!(--all "Apply to all events and todos, archived ones included"))
+            .group(ArgGroup::new("target").args(["id", "all"]).required(true))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        let ids = matches
+            .get_many::<String>("id")
+            .map(|ids| ids.map(|a| Id::ShortIdOrUid(a.clone())).collect())
+            .unwrap_or_default();
+        Self { ids }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "applying categorization rules...");
+        let mut ids = Vec::with_capacity(self.ids.len());
+        for id in &self.ids {
+            ids.push(resolve_id(aim, id, None).await?);
+        }
+        let report = aim.apply_rules(&ids).await?;
+        println!("{}", describe(report));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdRulesTest {
+    pub id: Id,
+}
+
+impl CmdRulesTest {
+    pub const NAME: &str = "test";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Show which categorization rules fire for an item, without applying them")
+            .arg(arg!(id: <ID>).help("The short id or uid of the event or todo"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        let id = matches
+            .get_one::<String>("id")
+            .expect("id is required")
+            .clone();
+        Self {
+            id: Id::ShortIdOrUid(id),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "testing categorization rules...");
+        let id = resolve_id(aim, &self.id, None).await?;
+        let test = aim.test_rules(&id).await?;
+        print!("{}", format_test(&test));
+        Ok(())
+    }
+}
+
+fn describe(report: RulesReport) -> String {
+    format!(
+        "Recategorized {} events and {} todos",
+        report.events, report.todos
+    )
+}
+
+fn format_test(test: &RuleTest) -> String {
+    let mut out = format!("{} ({})\n", test.summary, test.uid);
+    if test.traces.is_empty() {
+        out += "No rules, add [[core.rules]] sections to the config\n";
+        return out;
+    }
+
+    let width = test.traces.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    for trace in &test.traces {
+        let verdict = match trace.verdict {
+            RuleVerdict::Fired => "fires",
+            RuleVerdict::NotMatched => "does not match",
+            RuleVerdict::Unreached => "not reached",
+        };
+        let _ = writeln!(out, "  {:width$}  {verdict}", trace.name);
+    }
+
+    let outcome = &test.outcome;
+    if !outcome.categories.is_empty() {
+        let _ = writeln!(out, "Categories: {}", outcome.categories.join(", "));
+    }
+    if let Some(color) = &outcome.color {
+        let _ = writeln!(out, "Color: {color}");
+    }
+    match test.kind {
+        Kind::Todo if outcome.priority != Priority::None => {
+            let _ = writeln!(out, "Priority: at least {}", u8::from(outcome.priority));
+        }
+        Kind::Event if outcome.transparent => out += "Transparent: yes\n",
+        _ => {}
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::{RuleOutcome, RuleTrace};
+
+    use super::*;
+
+    #[test]
+    fn describes_report() {
+        let report = RulesReport {
+            events: 4,
+            todos: 1,
+        };
+        assert_eq!(describe(report), "Recategorized 4 events and 1 todos");
+    }
+
+    #[test]
+    fn formats_test() {
+        let trace = |name: &str, verdict| RuleTrace {
+            name: name.to_string(),
+            verdict,
+        };
+        let test = RuleTest {
+            uid: "todo-1".to_string(),
+            kind: Kind::Todo,
+            summary: "Review PR".to_string(),
+            traces: vec![
+                trace("work", RuleVerdict::Fired),
+                trace("urgent", RuleVerdict::NotMatched),
+                trace("rest", RuleVerdict::Unreached),
+            ],
+            outcome: RuleOutcome {
+                categories: vec!["Work".to_string(), "Code".to_string()],
+                color: Some("#1e90ff".to_string()),
+                priority: Priority::P2,
+                transparent: true,
+            },
+        };
+        assert_eq!(
+            format_test(&test),
+            "\
+Review PR (todo-1)
+  work    fires
+  urgent  does not match
+  rest    not reached
+Categories: Work, Code
+Color: #1e90ff
+Priority: at least 2
+"
+        );
+    }
+
+    #[test]
+    fn formats_test_without_rules() {
+        let test = RuleTest {
+            uid: "event-1".to_string(),
+            kind: Kind::Event,
+            summary: "Standup".to_string(),
+            traces: Vec::new(),
+            outcome: RuleOutcome::default(),
+        };
+        assert_eq!(
+            format_test(&test),
+            "Standup (event-1)\nNo rules, add [[core.rules]] sections to the config\n"
+        );
+    }
+}
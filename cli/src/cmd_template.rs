@@ -0,0 +1,190 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use aimcal_core::{Aim, ExpandedTodo, Template};
+use clap::{ArgMatches, Command, arg};
+use colored::Colorize;
+
+use crate::arg::{CalendarArgs, TemplateArgs, TodoArgs};
+use crate::cmd_todo::resolve_due;
+use crate::util::format_datetime;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdTemplateList;
+
+impl CmdTemplateList {
+    pub const NAME: &str = "list";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME).about("List the todo templates of the config")
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub fn run(self, aim: &Aim) {
+        tracing::debug!(?self, "listing templates...");
+        let templates = aim.templates().templates();
+        if templates.is_empty() {
+            println!("{}", "No templates in the config".italic());
+            return;
+        }
+        for template in templates {
+            println!("{}", format_template(template));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTemplateShow {
+    pub name: String,
+    /// Values of the variables, the others shown as their placeholders.
+    pub vars: Vec<(String, String)>,
+    pub due: Option<String>,
+    pub business_days: bool,
+    pub calendar_id: Option<String>,
+}
+
+impl CmdTemplateShow {
+    pub const NAME: &str = "show";
+
+    pub fn command() -> Command {
+        let todo_args = TodoArgs::new(true);
+        Command::new(Self::NAME)
+            .about("Preview the todos a template expands into, without creating them")
+            .arg(arg!(name: <NAME> "Name of the template"))
+            .arg(TemplateArgs::var())
+            .arg(todo_args.due())
+            .arg(todo_args.business_days())
+            .arg(CalendarArgs::new(true).calendar())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            name: matches
+                .get_one::<String>("name")
+                .expect("name is required")
+                .clone(),
+            vars: TemplateArgs::get_vars(matches),
+            due: TodoArgs::get_due(matches),
+            business_days: TodoArgs::get_business_days(matches),
+            calendar_id: CalendarArgs::get_calendar(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "showing template...");
+        let Some(template) = aim.templates().get(&self.name) else {
+            return Err(format!(
+                "No template named '{}', see `aim templates list` for the templates",
+                self.name
+            )
+            .into());
+        };
+
+        // Variables without a value keep their placeholder
+        let mut vars = self.vars.clone();
+        for name in &template.variables {
+            if !vars.iter().any(|(given, _)| given == name) {
+                vars.push((name.clone(), format!("{{{name}}}")));
+            }
+        }
+        let calendar_id = self.calendar_id.as_deref();
+        let due = match &self.due {
+            Some(due) => {
+                resolve_due(
+                    aim,
+                    due,
+                    self.business_days,
+                    calendar_id,
+                    &template.categories,
+                )
+                .await?
+            }
+            None => None,
+        };
+        let todos = aim
+            .expand_template(&self.name, &vars, due, calendar_id)
+            .await?;
+
+        println!("{}", format_template(template).bold());
+        for line in format_expansion(&todos) {
+            println!("  {line}");
+        }
+        Ok(())
+    }
+}
+
+/// Formats a template with its variables, such as `release  4 todos  version`.
+fn format_template(template: &Template) -> String {
+    let todos = template.items.len() + 1;
+    let line = format!("{}  {todos} todos", template.name);
+    if template.variables.is_empty() {
+        line
+    } else {
+        format!("{line}  {}", template.variables.join(", "))
+    }
+}
+
+/// Formats the todos of an expansion below their parents, with their dues.
+fn format_expansion(todos: &[ExpandedTodo]) -> Vec<String> {
+    let mut depths: Vec<usize> = Vec::with_capacity(todos.len());
+    todos
+        .iter()
+        .map(|todo| {
+            let depth = todo
+                .parent
+                .and_then(|parent| depths.get(parent))
+                .map_or(0, |depth| depth + 1);
+            depths.push(depth);
+            let line = format!("{}- {}", "  ".repeat(depth), todo.summary);
+            match &todo.due {
+                Some(due) => format!("{line}  due {}", format_datetime(due.clone())),
+                None => line,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::LooseDateTime;
+    use jiff::civil::date;
+
+    use super::*;
+
+    fn todo(parent: Option<usize>, summary: &str, due: Option<LooseDateTime>) -> ExpandedTodo {
+        ExpandedTodo {
+            parent,
+            summary: summary.to_string(),
+            description: None,
+            priority: None,
+            categories: Vec::new(),
+            due,
+        }
+    }
+
+    #[test]
+    fn formats_expansion_below_parents() {
+        let due = LooseDateTime::DateOnly(date(2026, 3, 9));
+        let todos = [
+            todo(None, "Release 1.4", Some(due)),
+            todo(Some(0), "Tag v1.4", None),
+            todo(Some(1), "Announce 1.4", None),
+            todo(Some(0), "Update the changelog", None),
+        ];
+        assert_eq!(
+            format_expansion(&todos),
+            [
+                "- Release 1.4  due 2026-03-09",
+                "  - Tag v1.4",
+                "    - Announce 1.4",
+                "  - Update the changelog",
+            ]
+        );
+    }
+}
@@ -2,36 +2,85 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 
 use aimcal_core::{
-    Aim, DateTimeAnchor, Id, Kind, Priority, SortOrder, Todo, TodoConditions, TodoDraft, TodoPatch,
-    TodoSort, TodoStatus,
+    Aim, AimError, AlarmTrigger, DateRangeAnchor, DateTimeAnchor, Id, Kind, LooseDateTime,
+    Priority, RecurFrom, SortOrder, SubtaskProgress, Todo, TodoConditions, TodoDraft, TodoPatch,
+    TodoSort, TodoStatus, normalize_meta_key, parse_signed_duration,
 };
-use clap::{ArgMatches, Command};
+use clap::{ArgMatches, Command, ValueHint, arg, value_parser};
 use colored::Colorize;
-
-use crate::arg::{CalendarArgs, CommonArgs, EventOrTodoArgs, TodoArgs};
+use jiff::SignedDuration;
+
+use crate::arg::{CalendarArgs, CommonArgs, EventOrTodoArgs, RangeArgs, TemplateArgs, TodoArgs};
+use crate::cmd_plan::offer_block_release;
+use crate::column::{ColumnData, any_needs, column_parser, parse_columns};
+use crate::config::Config;
+use crate::details_formatter::DetailRows;
+use crate::porcelain::Porcelain;
 use crate::prompt::{
-    DuplicateChoice, is_terminal, prompt_duplicate_choice, prompt_time, prompt_time_opt,
+    DuplicateChoice, can_prompt, is_terminal, missing_summary, prompt_duplicate_choice,
+    prompt_time, prompt_time_opt, prompt_todo_draft,
 };
+use crate::raw_formatter::print_raw;
+use crate::resolve::resolve_id;
 use crate::todo_formatter::{TodoColumn, TodoFormatter};
+use crate::todo_group::{TodoGroupBy, group_todos};
+use crate::todo_tree::{BoxedTodo, DEFAULT_MAX_DEPTH, fetch_ancestors, nest_todos};
 use crate::tui;
-use crate::util::{OutputFormat, parse_datetime};
+use crate::util::{
+    OutputFormat, StdinArgs, format_datetime, parse_anchor, parse_past_datetime, with_due_time,
+    write_export,
+};
 
+#[expect(clippy::option_option, clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub struct CmdTodoNew {
     pub calendar_id: Option<String>,
+    pub alarm: Option<Option<AlarmTrigger>>,
+    pub categories: Vec<String>,
     pub description: Option<String>,
     pub due: Option<String>,
+    /// Count the days of a relative due in business days.
+    pub business_days: bool,
+    pub estimate: Option<SignedDuration>,
     pub percent_complete: Option<u8>,
     pub priority: Option<Priority>,
+    /// Leave out the defaults of the categories, see [`aimcal_core::CategoryDefaults`].
+    pub no_defaults: bool,
     pub status: Option<TodoStatus>,
     pub summary: Option<String>,
+    pub encrypt_description: bool,
+    /// Advance the todo from the day it is completed if it recurs, see [`RecurFrom`].
+    pub from_completion: bool,
+    /// The template to create the todo and its subtasks from, see `aim templates`.
+    pub from_template: Option<String>,
+    /// Values of the variables of the template.
+    pub vars: Vec<(String, String)>,
+    pub no_interactive: bool,
 
     pub output_format: OutputFormat,
 }
 
+/// The fields of a new todo that a template fills in instead.
+const FIELDS_OF_NEW: [&str; 10] = [
+    "summary",
+    "description",
+    "estimate",
+    "percent",
+    "priority",
+    "status",
+    "category",
+    "no-defaults",
+    "encrypt-description",
+    "from-completion",
+];
+
 impl CmdTodoNew {
     pub const NAME: &str = "new";
 
@@ -44,30 +93,78 @@ impl CmdTodoNew {
             .arg(args.summary(true))
             .arg(CalendarArgs::new(true).calendar())
             .arg(todo_args.due())
+            .arg(todo_args.business_days())
+            .arg(todo_args.estimate())
             .arg(args.description())
+            .arg(args.alarm())
+            .arg(args.no_alarm())
             .arg(todo_args.percent_complete())
             .arg(todo_args.priority())
             .arg(todo_args.status())
+            .arg(todo_args.category())
+            .arg(todo_args.no_defaults())
+            .arg(todo_args.encrypt_description())
+            .arg(todo_args.recur_from_completion())
+            .arg(
+                arg!(--"from-template" <NAME> "Create the todo and its subtasks from a template")
+                    .long_help(
+                        "\
+Create the todo and its subtasks from a template of the config, see `aim templates list`. The \
+subtasks are linked to their parents and due at their offsets from the due of the todo, which is \
+the one of --due or else of the template. All of them are created at once, or none.",
+                    )
+                    .conflicts_with_all(FIELDS_OF_NEW),
+            )
+            // Clap skips the requirement of --from-template once a field conflicting with it is
+            // given, so the variables conflict with the fields as well
+            .arg(
+                TemplateArgs::var()
+                    .requires("from-template")
+                    .conflicts_with_all(FIELDS_OF_NEW),
+            )
             // options
+            .arg(CommonArgs::no_interactive())
             .arg(CommonArgs::output_format())
     }
 
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
             calendar_id: CalendarArgs::get_calendar(matches),
+            alarm: EventOrTodoArgs::get_alarm(matches),
+            categories: TodoArgs::get_categories(matches),
             description: EventOrTodoArgs::get_description(matches),
             due: TodoArgs::get_due(matches),
+            business_days: TodoArgs::get_business_days(matches),
+            estimate: TodoArgs::get_estimate(matches),
             percent_complete: TodoArgs::get_percent_complete(matches),
             priority: TodoArgs::get_priority(matches),
+            no_defaults: TodoArgs::get_no_defaults(matches),
             status: TodoArgs::get_status(matches),
             summary: EventOrTodoArgs::get_summary(matches),
+            encrypt_description: TodoArgs::get_encrypt_description(matches),
+            from_completion: TodoArgs::get_recur_from_completion(matches),
+            from_template: matches.get_one("from-template").cloned(),
+            vars: TemplateArgs::get_vars(matches),
+            no_interactive: CommonArgs::get_no_interactive(matches),
 
             output_format: CommonArgs::get_output_format(matches),
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "adding new todo...");
+        if let Some(name) = &self.from_template {
+            return self.new_from_template(aim, name).await;
+        }
+
+        let mut stdin = StdinArgs::default();
+        stdin.resolve("summary", &mut self.summary)?;
+        stdin.resolve("description", &mut self.description)?;
+        let interactive = !self.no_interactive && can_prompt();
+        if self.summary.is_none() && !interactive {
+            return Err(missing_summary());
+        }
+        let prompts = self.prompts();
         let tui = self.tui();
         let now = aim.now();
 
@@ -81,9 +178,27 @@ impl CmdTodoNew {
         }
 
         draft.calendar_id = self.calendar_id;
+        draft.alarm = self.alarm;
+        draft.categories = self.categories;
+        draft.no_defaults = self.no_defaults;
+        draft.encrypt_description = self.encrypt_description;
+        if self.from_completion {
+            draft.recur_from = Some(RecurFrom::Completion);
+        }
 
         if let Some(due) = &self.due {
-            draft.due = parse_datetime(&now, due)?;
+            let calendar_id = draft.calendar_id.as_deref();
+            // Without their defaults, the categories neither count days as business days
+            let categories: &[String] = if draft.no_defaults {
+                &[]
+            } else {
+                &draft.categories
+            };
+            draft.due = resolve_due(aim, due, self.business_days, calendar_id, categories).await?;
+        }
+
+        if let Some(estimate) = self.estimate {
+            draft.estimate = Some(estimate);
         }
 
         if let Some(percent) = self.percent_complete {
@@ -102,14 +217,22 @@ impl CmdTodoNew {
             draft.summary.clone_from(summary);
         }
 
-        // If TUI is needed, launch the TUI editor to let user edit the draft
-        if tui {
+        // Without any argument, ask for the fields one by one; if only some are missing, launch
+        // the TUI editor to let user edit the draft
+        if prompts {
+            let Some(draft_prompted) = prompt_todo_draft(&now, aim.default_due_time(), draft)?
+            else {
+                tracing::info!("user cancel the todo creation");
+                return Ok(());
+            };
+            draft = draft_prompted;
+        } else if tui {
             let Some(draft_tui) = tui::draft_todo(aim, draft)? else {
                 tracing::info!("user cancel the todo editing");
                 return Ok(());
             };
             draft = draft_tui;
-        } else if draft.due.is_none() {
+        } else if draft.due.is_none() && interactive {
             // Prompt for due time when not provided via CLI args
             let due = prompt_time_opt()?;
             if let Some(due_anchor) = due {
@@ -126,8 +249,9 @@ impl CmdTodoNew {
         draft: TodoDraft,
         output_format: OutputFormat,
     ) -> Result<(), Box<dyn Error>> {
-        // Duplicate detection: check for existing todos with same summary
-        if !draft.summary.is_empty() && is_terminal() {
+        // Duplicate detection: check for existing todos with same summary. Skipped when encrypting,
+        // as updating an existing todo would keep its description as it is stored.
+        if !draft.summary.is_empty() && !draft.encrypt_description && is_terminal() {
             let uid = match aim.find_latest_todo_by_summary(&draft.summary).await? {
                 Some(existing) => {
                     let existing_id = existing
@@ -153,6 +277,29 @@ impl CmdTodoNew {
         Ok(())
     }
 
+    /// Creates the todo and its subtasks from the template `name`, all at once.
+    async fn new_from_template(&self, aim: &Aim, name: &str) -> Result<(), Box<dyn Error>> {
+        let calendar_id = self.calendar_id.as_deref();
+        let due = match &self.due {
+            Some(due) => {
+                let categories = aim
+                    .templates()
+                    .get(name)
+                    .map(|template| template.categories.clone())
+                    .unwrap_or_default();
+                resolve_due(aim, due, self.business_days, calendar_id, &categories).await?
+            }
+            None => None,
+        };
+        let uids = aim
+            .new_todos_from_template(name, &self.vars, due, calendar_id)
+            .await?;
+        let ids: Vec<_> = uids.into_iter().map(Id::Uid).collect();
+        let todos = get_todos(aim, &ids).await?;
+        print_todos(aim, &todos, self.output_format);
+        Ok(())
+    }
+
     pub(crate) fn tui(&self) -> bool {
         Self::need_tui(&self.summary)
     }
@@ -162,13 +309,85 @@ impl CmdTodoNew {
     pub(crate) fn need_tui(summary: &Option<String>) -> bool {
         summary.is_none()
     }
+
+    /// Whether to ask for the fields one by one, which is when none is given.
+    pub(crate) fn prompts(&self) -> bool {
+        self.calendar_id.is_none()
+            && self.alarm.is_none()
+            && self.description.is_none()
+            && self.due.is_none()
+            && self.estimate.is_none()
+            && self.percent_complete.is_none()
+            && self.priority.is_none()
+            && self.status.is_none()
+            && self.summary.is_none()
+            && self.categories.is_empty()
+            && !self.no_defaults
+            && !self.encrypt_description
+            && !self.from_completion
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoCapture {
+    pub summary: String,
+
+    pub output_format: OutputFormat,
+}
+
+impl CmdTodoCapture {
+    pub const NAME: &str = "in";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .alias("capture")
+            .about("Capture a todo into the inbox, to triage later")
+            .long_about(
+                "\
+Capture a todo with nothing but a summary into the inbox, to triage later with `aim todo list \
+--inbox`. Nothing is prompted for, and the todo is written to the local cache only, to be pushed \
+to the default calendar by the next sync, so capturing is instant even if the calendar server is \
+slow. Giving the todo a due, a priority or a category takes it out of the inbox.",
+            )
+            .arg(
+                arg!(<SUMMARY> "Summary of the todo, the words are joined with spaces")
+                    .num_args(1..)
+                    .trailing_var_arg(true),
+            )
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            summary: matches
+                .get_many::<String>("SUMMARY")
+                .unwrap_or_default()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "capturing todo...");
+        let todo = aim.capture_todo(&self.summary).await?;
+        print_todos(aim, &[todo], self.output_format);
+        Ok(())
+    }
 }
 
+#[expect(clippy::option_option)]
 #[derive(Debug, Clone)]
 pub struct CmdTodoEdit {
     pub id: Id,
+    pub alarm: Option<Option<AlarmTrigger>>,
     pub description: Option<String>,
     pub due: Option<String>,
+    /// Count the days of a relative due in business days.
+    pub business_days: bool,
+    pub estimate: Option<SignedDuration>,
     pub percent_complete: Option<u8>,
     pub priority: Option<Priority>,
     pub status: Option<TodoStatus>,
@@ -187,7 +406,11 @@ impl CmdTodoEdit {
             .arg(args.id())
             .arg(args.summary(false))
             .arg(todo_args.due())
+            .arg(todo_args.business_days())
+            .arg(todo_args.estimate())
             .arg(args.description())
+            .arg(args.alarm())
+            .arg(args.no_alarm())
             .arg(todo_args.percent_complete())
             .arg(todo_args.priority())
             .arg(todo_args.status())
@@ -197,8 +420,11 @@ impl CmdTodoEdit {
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
             id: EventOrTodoArgs::get_id(matches),
+            alarm: EventOrTodoArgs::get_alarm(matches),
             description: EventOrTodoArgs::get_description(matches),
             due: TodoArgs::get_due(matches),
+            business_days: TodoArgs::get_business_days(matches),
+            estimate: TodoArgs::get_estimate(matches),
             percent_complete: TodoArgs::get_percent_complete(matches),
             priority: TodoArgs::get_priority(matches),
             status: TodoArgs::get_status(matches),
@@ -211,8 +437,11 @@ impl CmdTodoEdit {
     pub fn new_tui(id: Id, output_format: OutputFormat) -> Self {
         Self {
             id,
+            alarm: None,
             description: None,
             due: None,
+            business_days: false,
+            estimate: None,
             percent_complete: None,
             priority: None,
             status: None,
@@ -222,27 +451,54 @@ impl CmdTodoEdit {
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "editing todo...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let mut stdin = StdinArgs::default();
+        stdin.resolve("summary", &mut self.summary)?;
+        stdin.resolve("description", &mut self.description)?;
         let tui = self.tui();
 
+        // Days of a relative due may count as business days in the calendar of the todo
+        let due = match &self.due {
+            Some(due) => {
+                let todo = aim.get_todo(&id).await?;
+                let calendar_id = todo.calendar_id();
+                let categories = todo.categories();
+                let due = resolve_due(
+                    aim,
+                    due,
+                    self.business_days,
+                    calendar_id.as_deref(),
+                    &categories,
+                )
+                .await?;
+                Some(due)
+            }
+            None => None,
+        };
+
         // Prepare a patch with the provided arguments
         let mut patch = TodoPatch {
+            alarm: self.alarm,
             description: self.description.map(|d| (!d.is_empty()).then_some(d)),
-            due: self
-                .due
-                .as_ref()
-                .map(|a| parse_datetime(&aim.now(), a))
-                .transpose()?,
+            due,
             priority: self.priority,
+            estimate: self.estimate.map(Some),
             percent_complete: None,
             status: self.status,
             summary: self.summary,
+            snoozed_from: None,
+            meta: Vec::new(),
+            encrypted_description: None,
+            waiting: None,
+            recur_from: None,
+            completed_at: None,
         };
 
         // If TUI is needed, launch the TUI editor to let user edit the patch
         if tui {
-            let todo = aim.get_todo(&self.id).await?;
+            let todo = aim.get_todo(&id).await?;
             patch = if let Some(data) = tui::patch_todo(aim, &todo, patch)? {
                 data
             } else {
@@ -252,15 +508,17 @@ impl CmdTodoEdit {
         }
 
         // If no fields to edit, do nothing
-        let todo = aim.update_todo(&self.id, patch).await?;
+        let todo = aim.update_todo(&id, patch).await?;
         print_todos(aim, &[todo], self.output_format);
         Ok(())
     }
 
     /// Determine whether to use TUI mode, which is true if no fields to edit are provided
     pub(crate) fn tui(&self) -> bool {
-        self.description.is_none()
+        self.alarm.is_none()
+            && self.description.is_none()
             && self.due.is_none()
+            && self.estimate.is_none()
             && self.percent_complete.is_none()
             && self.priority.is_none()
             && self.status.is_none()
@@ -268,6 +526,25 @@ impl CmdTodoEdit {
     }
 }
 
+/// Resolves the due of a todo in `calendar_id` with `categories`, see [`Aim::resolve_due`], giving
+/// dates without a time the default due time.
+pub(crate) async fn resolve_due(
+    aim: &Aim,
+    due: &str,
+    business_days: bool,
+    calendar_id: Option<&str>,
+    categories: &[String],
+) -> Result<Option<LooseDateTime>, Box<dyn Error>> {
+    let Some(mut anchor) = parse_anchor(due)? else {
+        return Ok(None);
+    };
+    if business_days {
+        anchor = anchor.in_business_days();
+    }
+    let due = aim.resolve_due(anchor, calendar_id, categories).await?;
+    Ok(Some(with_due_time(&aim.now(), aim.default_due_time(), due)))
+}
+
 macro_rules! cmd_status {
     ($cmd: ident, $status:ident, $name: expr, $desc: expr) => {
         #[derive(Debug, Clone)]
@@ -296,26 +573,83 @@ macro_rules! cmd_status {
 
             pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
                 tracing::debug!(?self, concat!("marking todos as ", $desc));
-                let mut todos = vec![];
+                let mut patches = Vec::with_capacity(self.ids.len());
                 for id in self.ids {
+                    let id = resolve_id(aim, &id, Some(Kind::Todo)).await?;
                     let patch = TodoPatch {
                         status: Some(TodoStatus::$status),
                         ..Default::default()
                     };
-                    let todo = aim.update_todo(&id, patch).await?;
-                    todos.push(todo);
+                    patches.push((id, patch));
                 }
+                let ids = update_todos(aim, patches).await?;
+                let todos = get_todos(aim, &ids).await?;
                 print_todos(aim, &todos, self.output_format);
-                Ok(())
+                offer_block_release(aim, &ids, TodoStatus::$status).await
             }
         }
     };
 }
 
 cmd_status!(CmdTodoUndo, NeedsAction, "undo", "needs-action");
-cmd_status!(CmdTodoDone, Completed, "done", "completed");
 cmd_status!(CmdTodoCancel, Cancelled, "cancel", "canceled");
 
+#[derive(Debug, Clone)]
+pub struct CmdTodoDone {
+    pub ids: Vec<Id>,
+    /// When the todos were completed, if not now, see [`parse_past_datetime`].
+    pub at: Option<String>,
+    pub output_format: OutputFormat,
+}
+
+impl CmdTodoDone {
+    pub const NAME: &str = "done";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Mark a todo as completed")
+            .arg(args.ids())
+            .arg(arg!(--at <TIME> "When it was completed, if not now (yesterday 18:00, 2025-01-01 09:00...)"))
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            ids: EventOrTodoArgs::get_ids(matches),
+            at: matches.get_one::<String>("at").cloned(),
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "marking todos as completed");
+        let completed_at = match &self.at {
+            Some(at) => Some(parse_past_datetime(&aim.now(), at).map_err(|reason| {
+                AimError::InvalidInput {
+                    field: "completed at",
+                    reason,
+                }
+            })?),
+            None => None,
+        };
+        let mut patches = Vec::with_capacity(self.ids.len());
+        for id in self.ids {
+            let id = resolve_id(aim, &id, Some(Kind::Todo)).await?;
+            let patch = TodoPatch {
+                status: Some(TodoStatus::Completed),
+                completed_at: completed_at.clone(),
+                ..Default::default()
+            };
+            patches.push((id, patch));
+        }
+        let ids = update_todos(aim, patches).await?;
+        let todos = get_todos(aim, &ids).await?;
+        print_todos(aim, &todos, self.output_format);
+        offer_block_release(aim, &ids, TodoStatus::Completed).await
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CmdTodoDelay {
     pub ids: Vec<Id>,
@@ -352,10 +686,11 @@ impl CmdTodoDelay {
             None => prompt_time()?,
         };
 
-        let mut todos = vec![];
+        let mut patches = Vec::with_capacity(self.ids.len());
         for id in &self.ids {
+            let id = resolve_id(aim, id, Some(Kind::Todo)).await?;
             // Calculate new due based on original due if exists, otherwise based on now
-            let todo = aim.get_todo(id).await?;
+            let todo = aim.get_todo(&id).await?;
             let new_due = Some(match todo.due() {
                 Some(due) => time.clone().resolve_at(&due),
                 None => time
@@ -364,14 +699,16 @@ impl CmdTodoDelay {
                     .map_err(|e| format!("Failed to resolve since zoned: {e}"))?,
             });
 
-            // Update the todo
             let patch = TodoPatch {
                 due: Some(new_due),
                 ..Default::default()
             };
-            let todo = aim.update_todo(id, patch).await?;
-            todos.push(todo);
+            patches.push((id, patch));
         }
+
+        // Update the todos
+        let ids = update_todos(aim, patches).await?;
+        let todos = get_todos(aim, &ids).await?;
         print_todos(aim, &todos, self.output_format);
         Ok(())
     }
@@ -413,8 +750,9 @@ impl CmdTodoReschedule {
             None => prompt_time_opt()?,
         };
 
-        let mut todos = vec![];
+        let mut patches = Vec::with_capacity(self.ids.len());
         for id in &self.ids {
+            let id = resolve_id(aim, id, Some(Kind::Todo)).await?;
             // Calculate new due based on now
             let new_due = match time.as_ref() {
                 Some(a) => Some(
@@ -425,135 +763,944 @@ impl CmdTodoReschedule {
                 None => None,
             };
 
-            // Update the todo
             let patch = TodoPatch {
                 due: Some(new_due),
                 ..Default::default()
             };
-            let todo = aim.update_todo(id, patch).await?;
-            todos.push(todo);
+            patches.push((id, patch));
         }
+
+        // Update the todos
+        let ids = update_todos(aim, patches).await?;
+        let todos = get_todos(aim, &ids).await?;
         print_todos(aim, &todos, self.output_format);
         Ok(())
     }
 }
 
+macro_rules! cmd_postpone {
+    ($cmd: ident, $method: ident, $name: expr, $about: expr, $help: expr) => {
+        #[derive(Debug, Clone)]
+        pub struct $cmd {
+            pub ids: Vec<Id>,
+            pub time: DateTimeAnchor,
+            pub output_format: OutputFormat,
+        }
+
+        impl $cmd {
+            pub const NAME: &str = $name;
+
+            pub fn command() -> Command {
+                let (args, _todo_args) = args();
+                Command::new(Self::NAME)
+                    .about($about)
+                    .arg(args.ids())
+                    .arg(
+                        arg!(time: <TIME>)
+                            .help($help)
+                            .value_parser(value_parser!(DateTimeAnchor)),
+                    )
+                    .arg(CommonArgs::output_format())
+            }
+
+            pub fn from(matches: &ArgMatches) -> Self {
+                Self {
+                    ids: EventOrTodoArgs::get_ids(matches),
+                    time: matches
+                        .get_one::<DateTimeAnchor>("time")
+                        .cloned()
+                        .expect("time is required"),
+                    output_format: CommonArgs::get_output_format(matches),
+                }
+            }
+
+            pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+                tracing::debug!(?self, concat!("running ", $name, "..."));
+
+                let mut ids = Vec::with_capacity(self.ids.len());
+                for id in &self.ids {
+                    ids.push(resolve_id(aim, id, Some(Kind::Todo)).await?);
+                }
+                let time = self.time;
+                let ids = aim
+                    .transaction(|txn| {
+                        Box::pin(async move {
+                            let mut uids = Vec::with_capacity(ids.len());
+                            for id in &ids {
+                                uids.push(Id::Uid(txn.$method(id, time.clone()).await?));
+                            }
+                            Ok(uids)
+                        })
+                    })
+                    .await?;
+                let todos = get_todos(aim, &ids).await?;
+                print_todos(aim, &todos, self.output_format);
+                Ok(())
+            }
+        }
+    };
+}
+
+cmd_postpone!(
+    CmdTodoSnooze,
+    snooze_todo,
+    "snooze",
+    "Snooze todos, pushing their due by a delta from the current due",
+    "Delta to push the due by (3d, 1w, 2h...)"
+);
+cmd_postpone!(
+    CmdTodoDefer,
+    defer_todo,
+    "defer",
+    "Defer todos, setting their due to a time based on now",
+    "Time to defer to (2025-01-01 [9:00], 14:00, tomorrow...)"
+);
+
 #[derive(Debug, Clone)]
-pub struct CmdTodoList {
-    pub conds: TodoConditions,
+pub struct CmdTodoWait {
+    pub ids: Vec<Id>,
+    pub on: String,
+    pub until: Option<DateTimeAnchor>,
+    pub notify: bool,
     pub output_format: OutputFormat,
 }
 
-impl CmdTodoList {
-    pub const NAME: &str = "list";
+impl CmdTodoWait {
+    pub const NAME: &str = "wait";
 
     pub fn command() -> Command {
+        let (args, _todo_args) = args();
         Command::new(Self::NAME)
-            .about("List todos")
-            .arg(CalendarArgs::new(true).calendar())
+            .about("Mark todos as waiting on someone, hiding them until a follow-up date")
+            .long_about(
+                "\
+Mark todos handed off as waiting on someone. Waiting todos are left out of `aim todo list` and \
+the dashboard until their follow-up date passes, or until `aim unwait` without one, and are listed \
+with `aim todo list --waiting`. Their status is kept as it is, so other clients see them as usual.",
+            )
+            .arg(args.ids())
+            .arg(arg!(--for <WHO> "Who the todos are waiting on").required(true))
+            .arg(
+                arg!(--until <TIME> "When to follow up (friday, 2025-01-01, 3d...)")
+                    .value_parser(value_parser!(DateTimeAnchor)),
+            )
+            .arg(arg!(--notify "Add a reminder going off at the follow-up").requires("until"))
             .arg(CommonArgs::output_format())
     }
 
     pub fn from(matches: &ArgMatches) -> Self {
         Self {
-            conds: TodoConditions {
-                status: Some(TodoStatus::NeedsAction),
-                due: None,
-                calendar_id: CalendarArgs::get_calendar(matches),
-            },
+            ids: EventOrTodoArgs::get_ids(matches),
+            on: matches
+                .get_one::<String>("for")
+                .cloned()
+                .expect("for is required"),
+            until: matches.get_one("until").cloned(),
+            notify: matches.get_flag("notify"),
             output_format: CommonArgs::get_output_format(matches),
         }
     }
 
     pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
-        tracing::debug!(?self, "listing todos...");
-        Self::list(aim, &self.conds, self.output_format).await?;
+        tracing::debug!(?self, "marking todos as waiting...");
+        let mut todos = vec![];
+        for id in &self.ids {
+            let id = resolve_id(aim, id, Some(Kind::Todo)).await?;
+            let todo = aim
+                .wait_todo(&id, &self.on, self.until.clone(), self.notify)
+                .await?;
+            todos.push(todo);
+        }
+        print_todos(aim, &todos, self.output_format);
         Ok(())
     }
+}
 
-    #[expect(clippy::cast_possible_truncation)]
-    pub async fn list(
-        aim: &Aim,
-        conds: &TodoConditions,
-        output_format: OutputFormat,
-    ) -> Result<(), Box<dyn Error>> {
-        const LIMIT: i64 = 128;
+#[derive(Debug, Clone)]
+pub struct CmdTodoUnwait {
+    pub ids: Vec<Id>,
+    pub output_format: OutputFormat,
+}
 
-        let pager = (LIMIT, 0).into();
-        let sort = vec![
-            TodoSort::Priority {
-                order: SortOrder::Asc,
-                none_first: None,
-            },
-            TodoSort::Due(SortOrder::Asc),
-        ];
-        let mut todos = aim.list_todos(conds, &sort, &pager).await?;
-        todos.reverse();
-        if todos.len() >= LIMIT as usize {
-            let total = aim.count_todos(conds).await?;
-            if total > LIMIT {
-                let prompt = format!("Displaying the {LIMIT}/{total} todos");
-                println!("{}", prompt.italic());
-            }
-        } else if todos.is_empty() && output_format == OutputFormat::Table {
-            println!("{}", "No todos found".italic());
+impl CmdTodoUnwait {
+    pub const NAME: &str = "unwait";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Stop todos waiting on someone, listing them again")
+            .arg(args.ids())
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            ids: EventOrTodoArgs::get_ids(matches),
+            output_format: CommonArgs::get_output_format(matches),
         }
+    }
 
-        print_todos(aim, &todos, output_format);
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "clearing waiting todos...");
+        let mut todos = vec![];
+        for id in &self.ids {
+            let id = resolve_id(aim, id, Some(Kind::Todo)).await?;
+            todos.push(aim.unwait_todo(&id).await?);
+        }
+        print_todos(aim, &todos, self.output_format);
         Ok(())
     }
 }
 
-const fn args() -> (EventOrTodoArgs, TodoArgs) {
-    (EventOrTodoArgs::new(Some(Kind::Todo)), TodoArgs::new(true))
+#[derive(Debug, Clone)]
+pub struct CmdTodoProgress {
+    pub id: Id,
+    pub progress: Progress,
+    pub output_format: OutputFormat,
 }
 
-fn print_todos(aim: &Aim, todos: &[impl Todo], output_format: OutputFormat) {
-    use TodoColumn::{Due, Id, Priority, ShortId, Status, Summary, Uid};
-    let columns = match output_format {
-        OutputFormat::Table => vec![Status, Id, Priority, Due, Summary],
-        OutputFormat::Json => vec![Uid, ShortId, Status, Priority, Due, Summary],
-    };
-    let formatter = TodoFormatter::new(aim.now(), columns, output_format);
-    println!("{}", formatter.format(todos));
-}
+impl CmdTodoProgress {
+    pub const NAME: &str = "progress";
 
-#[cfg(test)]
-mod tests {
-    use aimcal_core::Priority;
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Set the percent complete of a todo, updating its status to match")
+            .arg(args.id())
+            .arg(
+                arg!(progress: <PERCENT>)
+                    .help("Percent complete (60), or a change of it (+10, -10)")
+                    .value_parser(value_parser!(Progress))
+                    .allow_hyphen_values(true),
+            )
+            .arg(CommonArgs::output_format())
+    }
 
-    use super::*;
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            progress: *matches
+                .get_one::<Progress>("progress")
+                .expect("progress is required"),
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
 
-    #[test]
-    fn parses_todo_new_command() {
-        let args = [
-            "new",
-            "Another summary",
-            "--calendar",
-            "work",
-            "--description",
-            "A description",
-            "--due",
-            "2025-01-01 12:00:00",
-            "--percent",
-            "66",
-            "--priority",
-            "1",
-            "--status",
-            "completed",
-            "--output-format",
-            "json",
-        ];
-        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
-        let parsed = CmdTodoNew::from(&matches);
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "setting todo progress...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let current = match self.progress {
+            Progress::Set(_) => 0,
+            Progress::Change(_) => aim
+                .get_todo(&id)
+                .await?
+                .percent_complete()
+                .unwrap_or_default(),
+        };
+        let patch = TodoPatch {
+            percent_complete: Some(Some(self.progress.apply(current))),
+            ..Default::default()
+        };
+        let todo = aim.update_todo(&id, patch).await?;
+        print_todos(aim, &[todo], self.output_format);
+        Ok(())
+    }
+}
 
-        assert_eq!(parsed.description, Some("A description".to_string()));
-        assert_eq!(parsed.calendar_id, Some("work".to_string()));
-        assert_eq!(parsed.due, Some("2025-01-01 12:00:00".to_string()));
-        assert_eq!(parsed.percent_complete, Some(66));
-        assert_eq!(parsed.priority, Some(Priority::P1));
-        assert_eq!(parsed.status, Some(TodoStatus::Completed));
-        assert_eq!(parsed.summary, Some("Another summary".to_string()));
+/// A percent complete, or a change of the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    Set(u8),
+    Change(i16),
+}
+
+impl Progress {
+    /// The percent complete after applying to `current`, clamped to 0-100.
+    pub fn apply(self, current: u8) -> u8 {
+        let percent = match self {
+            Progress::Set(percent) => i16::from(percent),
+            Progress::Change(delta) => i16::from(current).saturating_add(delta),
+        };
+        u8::try_from(percent.clamp(0, 100)).unwrap_or_default()
+    }
+}
+
+impl FromStr for Progress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches('%');
+        let invalid = |_| format!("invalid percent complete: {s}");
+        if s.starts_with(['+', '-']) {
+            s.parse().map(Progress::Change).map_err(invalid)
+        } else {
+            let percent: u16 = s.parse().map_err(invalid)?;
+            Ok(Progress::Set(u8::try_from(percent.min(100)).unwrap_or(100)))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoShow {
+    pub id: Id,
+    pub raw: bool,
+    pub no_color: bool,
+}
+
+impl CmdTodoShow {
+    pub const NAME: &str = "show";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Show all properties of a todo")
+            .arg(args.id())
+            .arg(arg!(--raw "Print the todo as serialized in its calendar"))
+            .arg(arg!(--"no-color" "Print the raw todo without highlighting").requires("raw"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            raw: matches.get_flag("raw"),
+            no_color: matches.get_flag("no-color"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "showing todo...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let details = aim.get_todo_details(&id).await?;
+        if self.raw {
+            let raw = details
+                .raw
+                .ok_or("The todo could not be read from its calendar")?;
+            return print_raw(&raw, self.no_color);
+        }
+
+        let todo = aim.get_todo(&id).await?;
+        let mut rows = DetailRows::new();
+        rows.push_opt("ID", todo.short_id().map(|id| id.to_string()));
+        rows.push("UID", todo.uid());
+        rows.push("Summary", todo.summary());
+        rows.push_opt("Start", todo.start().map(format_datetime));
+        rows.push_opt("Due", todo.due().map(format_datetime));
+        rows.push("Status", todo.status().to_string());
+        if todo.priority() != Priority::None {
+            rows.push("Priority", u8::from(todo.priority()).to_string());
+        }
+        rows.push_opt("Estimate", todo.estimate().map(|e| format!("{e:#}")));
+        rows.push_opt(
+            "Percent Complete",
+            todo.percent_complete().map(|p| format!("{p}%")),
+        );
+        rows.push_opt("Description", todo.description());
+        rows.push_item_details(&details);
+        rows.push_datetime("Completed", todo.completed());
+        rows.push_item_location(&details);
+        println!("{rows}");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoSetMeta {
+    pub id: Id,
+    pub key: String,
+    pub value: String,
+}
+
+impl CmdTodoSetMeta {
+    pub const NAME: &str = "set-meta";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Set custom metadata of a todo, such as a ticket number")
+            .arg(args.id())
+            .arg(arg!(<KEY> "Key of the metadata, case-insensitive"))
+            .arg(arg!(<VALUE> "Value of the metadata, as plain text"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            key: matches
+                .get_one::<String>("KEY")
+                .cloned()
+                .unwrap_or_default(),
+            value: matches
+                .get_one::<String>("VALUE")
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "setting todo metadata...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let meta = aim.set_meta(&id, &self.key, &self.value).await?;
+        print_meta(&meta);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoGetMeta {
+    pub id: Id,
+    pub key: Option<String>,
+}
+
+impl CmdTodoGetMeta {
+    pub const NAME: &str = "get-meta";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Print custom metadata of a todo")
+            .arg(args.id())
+            .arg(arg!([KEY] "Print only the value of this key"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            key: matches.get_one::<String>("KEY").cloned(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "getting todo metadata...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let meta = aim.get_meta(&id).await?;
+        match self.key {
+            Some(key) => {
+                let key = normalize_meta_key(&key)?;
+                let (_, value) = meta
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .ok_or_else(|| format!("Metadata {key:?} is not set"))?;
+                println!("{value}");
+            }
+            None => print_meta(&meta),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoUnsetMeta {
+    pub id: Id,
+    pub key: String,
+}
+
+impl CmdTodoUnsetMeta {
+    pub const NAME: &str = "unset-meta";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Remove custom metadata of a todo")
+            .arg(args.id())
+            .arg(arg!(<KEY> "Key of the metadata, case-insensitive"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            key: matches
+                .get_one::<String>("KEY")
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "removing todo metadata...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let meta = aim.unset_meta(&id, &self.key).await?;
+        print_meta(&meta);
+        Ok(())
+    }
+}
+
+/// Prints metadata as `key: value` lines.
+fn print_meta(meta: &[(String, String)]) {
+    if meta.is_empty() {
+        println!("{}", "No metadata".italic());
+    }
+    for (key, value) in meta {
+        println!("{key}: {value}");
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoExport {
+    pub id: Id,
+    pub occurrence: Option<DateTimeAnchor>,
+    pub output: Option<PathBuf>,
+}
+
+impl CmdTodoExport {
+    pub const NAME: &str = "export";
+
+    pub fn command() -> Command {
+        let (args, _todo_args) = args();
+        Command::new(Self::NAME)
+            .about("Export a todo as a standalone iCalendar file")
+            .arg(args.id())
+            .arg(
+                arg!(--occurrence <DATE> "Export only the occurrence of a recurring todo on this date")
+                    .value_parser(value_parser!(DateTimeAnchor)),
+            )
+            .arg(
+                arg!(-o --output <FILE> "Write to this file instead of stdout")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            id: EventOrTodoArgs::get_id(matches),
+            occurrence: matches.get_one::<DateTimeAnchor>("occurrence").cloned(),
+            output: matches.get_one::<PathBuf>("output").cloned(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "exporting todo...");
+        let id = resolve_id(aim, &self.id, Some(Kind::Todo)).await?;
+        let occurrence = match self.occurrence {
+            Some(anchor) => Some(anchor.resolve_since_zoned(&aim.now())?.date()),
+            None => None,
+        };
+        let export = aim.export_todo(&id, occurrence).await?;
+        write_export(self.output.as_deref(), export)
+    }
+}
+
+/// The key to sort listed todos by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TodoSortBy {
+    /// Priority, then due date
+    #[default]
+    Priority,
+    /// Due date, then priority
+    Due,
+    /// Summary, ignoring case and accents unless the config asks for binary collation
+    Summary,
+}
+
+impl TodoSortBy {
+    fn sort(self) -> Vec<TodoSort> {
+        let priority = TodoSort::Priority {
+            order: SortOrder::Asc,
+            none_first: None,
+        };
+        match self {
+            TodoSortBy::Priority => vec![priority, TodoSort::Due(SortOrder::Asc)],
+            TodoSortBy::Due => vec![TodoSort::Due(SortOrder::Asc), priority],
+            TodoSortBy::Summary => vec![TodoSort::Summary(SortOrder::Asc)],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTodoList {
+    pub conds: TodoConditions,
+    pub due_range: Option<DateRangeAnchor>,
+    /// Only todos overdue by at least this long.
+    pub min_overdue: Option<SignedDuration>,
+    pub sort_by: TodoSortBy,
+    pub group_by: Option<TodoGroupBy>,
+    /// How many levels deep to indent subtasks below their parent, if they are.
+    pub subtasks: Option<usize>,
+    pub show_progress: bool,
+    /// Columns of the table, overriding the `[todos]` config.
+    pub columns: Option<Vec<TodoColumn>>,
+    pub all: bool,
+    pub output_format: OutputFormat,
+    /// Print every todo as a porcelain record instead, see [`Porcelain`].
+    pub porcelain: Option<Porcelain>,
+}
+
+impl CmdTodoList {
+    pub const NAME: &str = "list";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("List todos")
+            .arg(CalendarArgs::new(true).calendar())
+            .args(RangeArgs::new(true).args())
+            .arg(
+                arg!(--"min-overdue" <DURATION> "Only list todos overdue by at least this long (12h, 7d...)")
+                    .value_parser(parse_signed_duration)
+                    .conflicts_with("range"),
+            )
+            .arg(
+                arg!(--"sort-by" <KEY> "Sort todos by this key")
+                    .value_parser(value_parser!(TodoSortBy))
+                    .default_value("priority"),
+            )
+            .arg(
+                arg!(--"group-by" <KEY> "Group todos in table output, with a count per group")
+                    .value_parser(value_parser!(TodoGroupBy)),
+            )
+            .arg(
+                arg!(--subtasks "Indent subtasks below their parent in table output, with the parents of matching subtasks dimmed")
+                    .conflicts_with("group-by"),
+            )
+            .arg(
+                arg!(--"max-depth" <N> "Indent subtasks at most this many levels deep, flattening deeper ones")
+                    .value_parser(value_parser!(usize))
+                    .requires("subtasks"),
+            )
+            .arg(arg!(--"show-progress" "Show the percent complete of todos as a progress bar"))
+            .arg(
+                arg!(--columns <COLUMNS> "Columns of the table output, separated by commas")
+                    .value_delimiter(',')
+                    .value_parser(column_parser::<TodoColumn>()),
+            )
+            .arg(
+                arg!(--"start-after" <TIME> "Only list todos scheduled to start after this time (2025-01-01, monday...)")
+                    .value_parser(value_parser!(DateTimeAnchor)),
+            )
+            .arg(
+                arg!(--scheduled "Only list todos scheduled to start later")
+                    .conflicts_with("start-after"),
+            )
+            .arg(arg!(--all "List todos scheduled to start later as well, despite `hide_unstarted`"))
+            .arg(arg!(--inbox "Only list todos captured with `aim in` and not triaged yet"))
+            .arg(arg!(--waiting "Only list todos waiting on someone, see `aim wait`"))
+            .arg(
+                arg!(--"waiting-for" <WHO> "Only list todos waiting on someone whose name contains this text")
+                    .conflicts_with("waiting"),
+            )
+            .arg(arg!(--"include-archived" "List completed todos as well, including those archived by housekeeping"))
+            .arg(args().0.meta())
+            .arg(CommonArgs::output_format())
+            .args(CommonArgs::porcelain())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        let include_archived = matches.get_flag("include-archived");
+        Self {
+            conds: TodoConditions {
+                // Archived todos are completed, so they would be filtered out again
+                status: (!include_archived).then_some(TodoStatus::NeedsAction),
+                due: None,
+                calendar_id: CalendarArgs::get_calendar(matches),
+                start_after: matches
+                    .get_one("start-after")
+                    .cloned()
+                    .or_else(|| matches.get_flag("scheduled").then(DateTimeAnchor::now)),
+                hide_unstarted: false,
+                include_archived,
+                meta: EventOrTodoArgs::get_meta(matches),
+                inbox: matches.get_flag("inbox"),
+                hide_waiting: false,
+                waiting: matches.get_flag("waiting"),
+                waiting_for: matches.get_one("waiting-for").cloned(),
+                categories: Vec::new(),
+                undated: false,
+            },
+            due_range: RangeArgs::new(true).get_range(matches),
+            min_overdue: matches.get_one("min-overdue").copied(),
+            sort_by: matches.get_one("sort-by").copied().unwrap_or_default(),
+            group_by: matches.get_one("group-by").copied(),
+            subtasks: matches.get_flag("subtasks").then(|| {
+                matches
+                    .get_one("max-depth")
+                    .copied()
+                    .unwrap_or(DEFAULT_MAX_DEPTH)
+            }),
+            show_progress: matches.get_flag("show-progress"),
+            columns: matches
+                .get_many::<String>("columns")
+                .map(|names| parse_columns(&names.collect::<Vec<_>>()))
+                .transpose()
+                .expect("column names are checked by the parser"),
+            all: matches.get_flag("all"),
+            output_format: CommonArgs::get_output_format(matches),
+            porcelain: CommonArgs::get_porcelain(matches),
+        }
+    }
+
+    pub async fn run(mut self, aim: &Aim, config: &Config) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "listing todos...");
+        // Asking for todos starting later lists them whatever the config
+        self.conds.hide_unstarted =
+            aim.hide_unstarted() && !self.all && self.conds.start_after.is_none();
+        // Waiting todos are listed again once their follow-up passes, unless asked for
+        self.conds.hide_waiting = !self.conds.waiting && self.conds.waiting_for.is_none();
+        // Todos are due by the end of the range, so overdue ones are listed as well
+        if let Some(range) = self.due_range {
+            let (_, cutoff) = range.resolve_anchors(aim.now().date(), aim.week_start())?;
+            self.conds.due = Some(cutoff);
+        }
+        // Todos due on a date are due by the end of it, as their ages are
+        if let Some(age) = self.min_overdue {
+            self.conds.due = Some(DateTimeAnchor::Relative(-age.as_secs()));
+        }
+        if let Some(porcelain) = self.porcelain {
+            return Self::list_porcelain(aim, &self.conds, self.sort_by, porcelain).await;
+        }
+        Self::list(
+            aim,
+            &self.conds,
+            self.sort_by,
+            self.group_by,
+            self.subtasks,
+            &TodoColumns {
+                chosen: self.columns.or_else(|| config.todos.columns.clone()),
+                show_progress: self.show_progress,
+            },
+            self.output_format,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// List all todos with the given conditions as porcelain records, most urgent first.
+    async fn list_porcelain(
+        aim: &Aim,
+        conds: &TodoConditions,
+        sort_by: TodoSortBy,
+        porcelain: Porcelain,
+    ) -> Result<(), Box<dyn Error>> {
+        let total = aim.count_todos(conds).await?;
+        let todos = aim
+            .list_todos(conds, &sort_by.sort(), &(total, 0).into())
+            .await?;
+        let mut stdout = io::stdout().lock();
+        porcelain.write_todos(&mut stdout, &todos)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    pub async fn list(
+        aim: &Aim,
+        conds: &TodoConditions,
+        sort_by: TodoSortBy,
+        group_by: Option<TodoGroupBy>,
+        subtasks: Option<usize>,
+        columns: &TodoColumns,
+        output_format: OutputFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        const LIMIT: i64 = 128;
+
+        let pager = (LIMIT, 0).into();
+        let mut todos = aim.list_todos(conds, &sort_by.sort(), &pager).await?;
+        if todos.len() >= LIMIT as usize {
+            let total = aim.count_todos(conds).await?;
+            if total > LIMIT {
+                let prompt = format!("Displaying the {LIMIT}/{total} todos");
+                println!("{}", prompt.italic());
+            }
+        } else if todos.is_empty() && output_format == OutputFormat::Table {
+            println!("{}", "No todos found".italic());
+        }
+
+        if let Some(max_depth) = subtasks
+            && output_format == OutputFormat::Table
+        {
+            let todos: Vec<BoxedTodo> = todos
+                .into_iter()
+                .map(|t| -> BoxedTodo { Box::new(t) })
+                .collect();
+            let ancestors = fetch_ancestors(aim, &todos).await?;
+            let uids: Vec<_> = todos.iter().chain(&ancestors).map(|t| t.uid()).collect();
+            let uids: Vec<_> = uids.iter().map(AsRef::as_ref).collect();
+            let progress = aim.count_subtasks(&uids).await?;
+
+            // Reversed like the plain list, keeping parents above their subtasks
+            let mut trees = nest_todos(todos, ancestors, &progress, max_depth);
+            trees.reverse();
+            let todos: Vec<_> = trees.into_iter().flatten().collect();
+            let dimmed = todos
+                .iter()
+                .filter(|t| t.context)
+                .map(|t| t.uid().into_owned());
+            let columns = columns.resolve(output_format, any_waiting(&todos));
+            let formatter = TodoFormatter::new(aim.now(), columns, output_format)
+                .with_dimmed(dimmed)
+                .with_subtasks(progress)
+                .with_overdue(aim.overdue().clone());
+            println!("{}", formatter.format(&todos));
+            return Ok(());
+        }
+
+        let progress = if output_format == OutputFormat::Table
+            && columns
+                .chosen
+                .as_ref()
+                .is_some_and(|c| any_needs(c, ColumnData::Subtasks))
+        {
+            let uids: Vec<_> = todos.iter().map(|t| t.uid()).collect();
+            let uids: Vec<_> = uids.iter().map(AsRef::as_ref).collect();
+            aim.count_subtasks(&uids).await?
+        } else {
+            HashMap::new()
+        };
+
+        match group_by {
+            Some(by) if output_format == OutputFormat::Table => {
+                // Reversed like the plain list, so the most urgent group is printed last
+                let mut groups =
+                    group_todos(todos, by, &aim.now(), aim.week_start(), aim.collation())?;
+                groups.reverse();
+                for (i, mut group) in groups.into_iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    let count = format!("({})", group.todos.len());
+                    println!("{} {}", group.label.bold(), count.italic());
+                    group.todos.reverse();
+                    print_todos_with(aim, &group.todos, output_format, columns, &progress);
+                }
+            }
+            _ => {
+                todos.reverse();
+                print_todos_with(aim, &todos, output_format, columns, &progress);
+            }
+        }
+        Ok(())
+    }
+}
+
+const fn args() -> (EventOrTodoArgs, TodoArgs) {
+    (EventOrTodoArgs::new(Some(Kind::Todo)), TodoArgs::new(true))
+}
+
+/// Applies the patches to their todos all at once, so either all of them are updated or none,
+/// returning the IDs of the todos by UID.
+async fn update_todos(aim: &Aim, patches: Vec<(Id, TodoPatch)>) -> Result<Vec<Id>, AimError> {
+    aim.transaction(|txn| {
+        Box::pin(async move {
+            let mut ids = Vec::with_capacity(patches.len());
+            for (id, patch) in patches {
+                ids.push(Id::Uid(txn.update_todo(&id, patch).await?));
+            }
+            Ok(ids)
+        })
+    })
+    .await
+}
+
+/// Gets the todos with the IDs, such as once a transaction changed them.
+async fn get_todos(aim: &Aim, ids: &[Id]) -> Result<Vec<impl Todo + 'static>, AimError> {
+    let mut todos = Vec::with_capacity(ids.len());
+    for id in ids {
+        todos.push(aim.get_todo(id).await?);
+    }
+    Ok(todos)
+}
+
+fn print_todos(aim: &Aim, todos: &[impl Todo], output_format: OutputFormat) {
+    let progress = HashMap::new();
+    print_todos_with(
+        aim,
+        todos,
+        output_format,
+        &TodoColumns::default(),
+        &progress,
+    );
+}
+
+pub fn print_todos_with(
+    aim: &Aim,
+    todos: &[impl Todo],
+    output_format: OutputFormat,
+    columns: &TodoColumns,
+    progress: &HashMap<String, SubtaskProgress>,
+) {
+    let columns = columns.resolve(output_format, any_waiting(todos));
+    let formatter = TodoFormatter::new(aim.now(), columns, output_format)
+        .with_subtasks(progress.clone())
+        .with_overdue(aim.overdue().clone());
+    println!("{}", formatter.format(todos));
+}
+
+/// The columns to list todos with, the built-in ones unless chosen.
+#[derive(Debug, Clone, Default)]
+pub struct TodoColumns {
+    /// Columns of the table, from `--columns` or the `[todos]` config.
+    pub chosen: Option<Vec<TodoColumn>>,
+    /// Whether to show the percent complete as well, from `--show-progress`.
+    pub show_progress: bool,
+}
+
+impl TodoColumns {
+    /// The columns to print todos with, with who they wait on in tables only if any of them
+    /// waits. Chosen columns apply to tables only, JSON keeps its keys for scripts.
+    fn resolve(&self, output_format: OutputFormat, waiting: bool) -> Vec<TodoColumn> {
+        use TodoColumn::{
+            Due, FollowUp, Id, Priority, Progress, ShortId, Status, Summary, Uid, WaitingOn,
+        };
+        if let (Some(chosen), OutputFormat::Table) = (&self.chosen, output_format) {
+            let mut columns = chosen.clone();
+            if self.show_progress && !columns.contains(&Progress) {
+                columns.push(Progress);
+            }
+            return columns;
+        }
+
+        let mut columns = match output_format {
+            OutputFormat::Table => vec![Status, Id, Priority, Due, Summary],
+            OutputFormat::Json => vec![Uid, ShortId, Status, Priority, Due, Summary],
+        };
+        if self.show_progress {
+            columns.insert(columns.len() - 1, Progress);
+        }
+        if waiting || output_format == OutputFormat::Json {
+            columns.extend([WaitingOn, FollowUp]);
+        }
+        columns
+    }
+}
+
+fn any_waiting(todos: &[impl Todo]) -> bool {
+    todos.iter().any(|t| t.waiting_on().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::Priority;
+
+    use super::*;
+
+    #[test]
+    fn parses_todo_new_command() {
+        let args = [
+            "new",
+            "Another summary",
+            "--calendar",
+            "work",
+            "--description",
+            "A description",
+            "--due",
+            "2025-01-01 12:00:00",
+            "--percent",
+            "66",
+            "--priority",
+            "1",
+            "--status",
+            "completed",
+            "--output-format",
+            "json",
+        ];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+
+        assert_eq!(parsed.description, Some("A description".to_string()));
+        assert_eq!(parsed.calendar_id, Some("work".to_string()));
+        assert_eq!(parsed.due, Some("2025-01-01 12:00:00".to_string()));
+        assert_eq!(parsed.percent_complete, Some(66));
+        assert_eq!(parsed.priority, Some(Priority::P1));
+        assert_eq!(parsed.status, Some(TodoStatus::Completed));
+        assert_eq!(parsed.summary, Some("Another summary".to_string()));
 
         assert!(!parsed.tui());
         assert_eq!(parsed.output_format, OutputFormat::Json);
@@ -566,9 +1713,94 @@ mod tests {
         let parsed = CmdTodoNew::from(&matches);
 
         assert!(parsed.tui());
+        assert!(parsed.prompts());
+        assert!(!parsed.no_interactive);
         assert_eq!(parsed.output_format, OutputFormat::Json);
     }
 
+    #[test]
+    fn parses_todo_new_command_prompting_only_without_arguments() {
+        let args = ["new", "--due", "tomorrow", "--no-interactive"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+
+        assert!(parsed.tui());
+        assert!(!parsed.prompts());
+        assert!(parsed.no_interactive);
+    }
+
+    #[test]
+    fn parses_todo_new_command_with_encrypt_description() {
+        let args = ["new", "Summary", "--encrypt-description"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert!(parsed.encrypt_description);
+
+        let args = ["new", "Summary"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert!(!parsed.encrypt_description);
+    }
+
+    #[test]
+    fn parses_todo_new_command_with_from_completion() {
+        let args = ["new", "Water plants", "--from-completion"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert!(parsed.from_completion);
+        assert!(!parsed.prompts());
+    }
+
+    #[test]
+    fn parses_todo_new_command_with_from_template() {
+        let args = ["new", "--from-template", "release", "--var", "version=1.4"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert_eq!(parsed.from_template, Some("release".to_string()));
+        assert_eq!(parsed.vars, [("version".to_string(), "1.4".to_string())]);
+
+        let args = ["new", "Release", "--from-template", "release"];
+        assert!(CmdTodoNew::command().try_get_matches_from(args).is_err());
+        let args = ["new", "Release", "--var", "version=1.4"];
+        assert!(CmdTodoNew::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_new_command_with_categories() {
+        let args = [
+            "new",
+            "Review",
+            "--category",
+            "work",
+            "--category",
+            "urgent",
+        ];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert_eq!(parsed.categories, ["work", "urgent"]);
+        assert!(!parsed.no_defaults);
+        assert!(!parsed.prompts());
+
+        let args = ["new", "Review", "--category", "work", "--no-defaults"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        assert!(CmdTodoNew::from(&matches).no_defaults);
+    }
+
+    #[test]
+    fn parses_todo_new_command_with_business_days() {
+        let args = ["new", "Send report", "--due", "3d", "--business-days"];
+        let matches = CmdTodoNew::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoNew::from(&matches);
+        assert_eq!(parsed.due, Some("3d".to_string()));
+        assert!(parsed.business_days);
+
+        let args = ["edit", "test_id", "--due", "3bd"];
+        let matches = CmdTodoEdit::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoEdit::from(&matches);
+        assert_eq!(parsed.due, Some("3bd".to_string()));
+        assert!(!parsed.business_days);
+    }
+
     #[test]
     fn parses_todo_edit_command() {
         let args = [
@@ -627,6 +1859,16 @@ mod tests {
         assert_eq!(parsed.output_format, OutputFormat::Json);
     }
 
+    #[test]
+    fn parses_todo_done_command_with_completion_time() {
+        let args = ["done", "abc", "--at", "yesterday 18:00"];
+        let matches = CmdTodoDone::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoDone::from(&matches);
+
+        assert_eq!(parsed.ids, vec![Id::ShortIdOrUid("abc".to_string())]);
+        assert_eq!(parsed.at.as_deref(), Some("yesterday 18:00"));
+    }
+
     #[test]
     fn parses_todo_done_command_with_multiple_ids() {
         let args = ["done", "a", "b", "c", "--output-format", "json"];
@@ -724,6 +1966,90 @@ mod tests {
         assert_eq!(parsed.output_format, OutputFormat::Json);
     }
 
+    #[test]
+    fn parses_todo_snooze_command() {
+        let args = ["snooze", "a", "b", "3d", "--output-format", "json"];
+        let matches = CmdTodoSnooze::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoSnooze::from(&matches);
+
+        let expected_ids = vec![
+            Id::ShortIdOrUid("a".to_string()),
+            Id::ShortIdOrUid("b".to_string()),
+        ];
+        assert_eq!(parsed.ids, expected_ids);
+        assert_eq!(parsed.time, DateTimeAnchor::InDays(3));
+        assert_eq!(parsed.output_format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn parses_todo_snooze_command_requires_time() {
+        let args = ["snooze", "a"];
+        assert!(CmdTodoSnooze::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_wait_command() {
+        let args = [
+            "wait", "a", "b", "--for", "Alice", "--until", "friday", "--notify",
+        ];
+        let matches = CmdTodoWait::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoWait::from(&matches);
+
+        let expected_ids = vec![
+            Id::ShortIdOrUid("a".to_string()),
+            Id::ShortIdOrUid("b".to_string()),
+        ];
+        assert_eq!(parsed.ids, expected_ids);
+        assert_eq!(parsed.on, "Alice");
+        assert_eq!(parsed.until, Some("friday".parse().unwrap()));
+        assert!(parsed.notify);
+    }
+
+    #[test]
+    fn parses_todo_wait_command_requires_who() {
+        let args = ["wait", "a", "--until", "friday"];
+        assert!(CmdTodoWait::command().try_get_matches_from(args).is_err());
+        let args = ["wait", "a", "--for", "Alice", "--notify"];
+        assert!(CmdTodoWait::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_list_waiting() {
+        let args = ["list", "--waiting-for", "alice"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.conds.waiting_for.as_deref(), Some("alice"));
+        assert!(!parsed.conds.waiting);
+
+        let args = ["list", "--waiting"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        assert!(CmdTodoList::from(&matches).conds.waiting);
+    }
+
+    #[test]
+    fn parses_todo_list_min_overdue() {
+        let args = ["list", "--min-overdue", "7d"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.min_overdue, Some(SignedDuration::from_hours(7 * 24)));
+
+        let args = ["list", "--min-overdue", "7d", "--due-week"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+        let args = ["list", "--min-overdue", "soon"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_defer_command() {
+        let args = ["defer", "a", "tomorrow"];
+        let matches = CmdTodoDefer::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoDefer::from(&matches);
+
+        assert_eq!(parsed.ids, vec![Id::ShortIdOrUid("a".to_string())]);
+        assert_eq!(parsed.time, DateTimeAnchor::tomorrow());
+        assert_eq!(parsed.output_format, OutputFormat::Table);
+    }
+
     #[test]
     fn parses_todo_list_command() {
         let args = ["list", "--calendar", "work", "--output-format", "json"];
@@ -731,6 +2057,108 @@ mod tests {
         let parsed = CmdTodoList::from(&matches);
 
         assert_eq!(parsed.conds.calendar_id, Some("work".to_string()));
+        assert_eq!(parsed.group_by, None);
         assert_eq!(parsed.output_format, OutputFormat::Json);
     }
+
+    #[test]
+    fn parses_todo_list_command_with_group_by() {
+        let args = ["list", "--group-by", "due-date"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.group_by, Some(TodoGroupBy::DueDate));
+
+        let args = ["list", "--group-by", "category"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.group_by, Some(TodoGroupBy::Category));
+
+        let args = ["list", "--group-by", "tag"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_list_command_with_subtasks() {
+        let args = ["list"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        assert_eq!(CmdTodoList::from(&matches).subtasks, None);
+
+        let args = ["list", "--subtasks"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.subtasks, Some(DEFAULT_MAX_DEPTH));
+
+        let args = ["list", "--subtasks", "--max-depth", "1"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        assert_eq!(CmdTodoList::from(&matches).subtasks, Some(1));
+
+        let args = ["list", "--max-depth", "1"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+        let args = ["list", "--subtasks", "--group-by", "status"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_list_command_with_sort_by() {
+        let matches = CmdTodoList::command()
+            .try_get_matches_from(["list"])
+            .unwrap();
+        assert_eq!(CmdTodoList::from(&matches).sort_by, TodoSortBy::Priority);
+
+        let args = ["list", "--sort-by", "summary"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        assert_eq!(CmdTodoList::from(&matches).sort_by, TodoSortBy::Summary);
+    }
+
+    #[test]
+    fn parses_todo_list_command_with_start_filters() {
+        let matches = CmdTodoList::command()
+            .try_get_matches_from(["list"])
+            .unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.conds.start_after, None);
+        assert!(!parsed.all);
+
+        let args = ["list", "--scheduled", "--all"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.conds.start_after, Some(DateTimeAnchor::now()));
+        assert!(parsed.all);
+
+        let args = ["list", "--start-after", "tomorrow"];
+        let matches = CmdTodoList::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdTodoList::from(&matches);
+        assert_eq!(parsed.conds.start_after, Some(DateTimeAnchor::tomorrow()));
+
+        let args = ["list", "--scheduled", "--start-after", "tomorrow"];
+        assert!(CmdTodoList::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_todo_progress_command() {
+        let args = ["progress", "abc", "-10"];
+        let matches = CmdTodoProgress::command()
+            .try_get_matches_from(args)
+            .unwrap();
+        let parsed = CmdTodoProgress::from(&matches);
+        assert_eq!(parsed.id, Id::ShortIdOrUid("abc".to_string()));
+        assert_eq!(parsed.progress, Progress::Change(-10));
+    }
+
+    #[test]
+    fn parses_and_applies_progress() {
+        let parse = |s: &str| s.parse::<Progress>().unwrap();
+        assert_eq!(parse("60"), Progress::Set(60));
+        assert_eq!(parse("60%"), Progress::Set(60));
+        assert_eq!(parse("250"), Progress::Set(100));
+        assert_eq!(parse("+10"), Progress::Change(10));
+        assert_eq!(parse("-10"), Progress::Change(-10));
+        assert!("ten".parse::<Progress>().is_err());
+        assert!("-".parse::<Progress>().is_err());
+
+        assert_eq!(Progress::Set(60).apply(90), 60);
+        assert_eq!(Progress::Change(10).apply(95), 100);
+        assert_eq!(Progress::Change(-30).apply(20), 0);
+        assert_eq!(Progress::Change(15).apply(40), 55);
+    }
 }
@@ -2,23 +2,40 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
+use std::path::{Path, PathBuf};
 
 use aimcal_core::{
-    Aim, DateTimeAnchor, EventConditions, Id, Kind, Pager, TodoConditions, TodoStatus,
+    Aim, AllDayPolicy, DEFAULT_EXPAND_LIMIT, DateRangeAnchor, DateTimeAnchor, DayLoad, DedupeMode,
+    Event, EventConditions, ForeignFormat, ForeignTodo, Id, Invitation, Kind, LoadLevel,
+    LooseDateTime, Pager, Priority, SyncChange, SyncDirection, SyncPlan, SyncResult,
+    TodoConditions, TodoStatus, describe_recurrence,
 };
-use clap::{ArgMatches, Command};
+use clap::{ArgMatches, Command, ValueHint, arg, value_parser};
 use colored::Colorize;
+use jiff::SignedDuration;
+use ratatui::crossterm::terminal;
 
-use crate::arg::EventOrTodoArgs;
-use crate::cmd_event::{CmdEventDelay, CmdEventReschedule};
-use crate::cmd_todo::{CmdTodoDelay, CmdTodoList, CmdTodoReschedule};
-use crate::event_formatter::{EventColumn, EventFormatter};
-use crate::prompt::prompt_time;
-use crate::util::OutputFormat;
+use crate::arg::{CalendarArgs, EventOrTodoArgs};
+use crate::cmd_event::{CmdEventDelay, CmdEventReschedule, dedupe_mode, no_dedupe_arg};
+use crate::cmd_housekeeping::run_scheduled_housekeeping;
+use crate::cmd_todo::{CmdTodoDelay, CmdTodoList, CmdTodoReschedule, TodoColumns, TodoSortBy};
+use crate::event_formatter::{EventColumn, EventFormatter, any_mirrored};
+use crate::prompt::{prompt_sync_plan, prompt_time};
+use crate::resolve::resolve_candidate;
+use crate::table::{Table, TableColumn, TableStyleBasic};
+use crate::timeline::{TimelineEvent, render_timeline, to_ansi};
+use crate::util::{
+    OutputFormat, STDIN_ARG, StdinArgs, format_datetime, input_source, write_export,
+};
 
 #[derive(Debug, Default, Clone, Copy)]
-pub struct CmdDashboard;
+pub struct CmdDashboard {
+    /// List every copy of events kept in several calendars.
+    pub no_dedupe: bool,
+}
 
 impl CmdDashboard {
     pub const NAME: &str = "dashboard";
@@ -26,25 +43,34 @@ impl CmdDashboard {
     pub fn command() -> Command {
         Command::new(Self::NAME)
             .about("Show the dashboard, which includes upcoming events and todos")
+            .arg(no_dedupe_arg())
     }
 
-    pub fn from(_matches: &ArgMatches) -> Self {
-        Self
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            no_dedupe: matches.get_flag("no-dedupe"),
+        }
     }
 
     /// Show the dashboard with events and todos.
     pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "generating dashboard...");
 
-        Self::list_events(aim).await?;
+        let dedupe = dedupe_mode(aim, self.no_dedupe);
+        Self::list_events(aim, dedupe).await?;
         println!();
 
+        if Self::show_timeline(aim, dedupe).await? {
+            Self::show_load(aim).await?;
+            println!();
+        }
+
         Self::list_todos(aim).await?;
         Ok(())
     }
 
     #[expect(clippy::cast_possible_truncation)]
-    async fn list_events(aim: &Aim) -> Result<(), Box<dyn Error>> {
+    async fn list_events(aim: &Aim, dedupe: DedupeMode) -> Result<(), Box<dyn Error>> {
         const MAX: i64 = 128;
 
         let pager: Pager = (MAX, 0).into();
@@ -60,6 +86,11 @@ impl CmdDashboard {
                 startable: Some(anchor.clone()),
                 cutoff: Some(anchor.clone()),
                 calendar_id: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             };
             let events = aim.list_events(&conds, &pager).await?;
             if !events.is_empty() {
@@ -80,12 +111,14 @@ impl CmdDashboard {
                     .resolve_at_start_of_day(&aim.now())
                     .map_err(|e| format!("Failed to resolve start of day: {e}"))?
                     .date();
-                let columns = vec![
-                    EventColumn::Id,
-                    EventColumn::TimeSpan { date },
-                    EventColumn::Summary,
-                ];
-                let formatter = EventFormatter::new(aim.now(), columns, OutputFormat::Table);
+                let events = aim.dedupe_events(events, dedupe).await?;
+                let mut columns =
+                    vec![EventColumn::Id, EventColumn::TimeSpan, EventColumn::Summary];
+                if any_mirrored(&events) {
+                    columns.push(EventColumn::Calendars);
+                }
+                let formatter =
+                    EventFormatter::new(aim.now(), columns, OutputFormat::Table).on(date);
                 println!("{}", formatter.format(&events));
             }
         }
@@ -96,21 +129,133 @@ impl CmdDashboard {
         Ok(())
     }
 
-    async fn list_todos(aim: &Aim) -> Result<(), Box<dyn Error>> {
+    /// Show the working hours of today as a timeline, returning whether there are any.
+    async fn show_timeline(aim: &Aim, dedupe: DedupeMode) -> Result<bool, Box<dyn Error>> {
         let now = aim.now();
-        let days_from_monday = now.weekday().to_monday_zero_offset();
-        let (days, label) = match days_from_monday {
-            0..5 => (6 - days_from_monday, "this week"),
-            _ => (3, "next 3 days"),
+        let Some(window) = aim.working_hours().await?.day_window(&now) else {
+            return Ok(false);
+        };
+
+        let colors: HashMap<_, _> = aim
+            .list_calendar_details()
+            .await?
+            .into_iter()
+            .filter_map(|c| Some((c.id, c.color?.parse().ok()?)))
+            .collect();
+        let conds = EventConditions {
+            startable: Some(DateTimeAnchor::today()),
+            cutoff: Some(DateTimeAnchor::today()),
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
+        };
+        let tz = now.time_zone();
+        let events = aim.list_events(&conds, &(i64::MAX, 0).into()).await?;
+        let events: Vec<_> = aim
+            .dedupe_events(events, dedupe)
+            .await?
+            .iter()
+            .filter_map(|event| {
+                // Transparent events take no time of the working day, nor do all-day ones unless
+                // they are configured to
+                if event.transparent() {
+                    return None;
+                }
+                let (start, end) = if event.is_all_day() {
+                    if aim.all_day_policy() == AllDayPolicy::Ignore {
+                        return None;
+                    }
+                    (window.0.timestamp(), window.1.timestamp())
+                } else {
+                    let start = event.start()?.to_timestamp_in(tz)?;
+                    let end = event.end().and_then(|e| e.to_timestamp_in(tz));
+                    (start, end.unwrap_or(start))
+                };
+                Some(TimelineEvent {
+                    summary: event.summary().into_owned(),
+                    start,
+                    end,
+                    // The color tag of the rules wins over the one of the calendar
+                    color: event.color().and_then(|c| c.parse().ok()).or_else(|| {
+                        event
+                            .calendar_id()
+                            .and_then(|id| colors.get(id.as_ref()).copied())
+                    }),
+                })
+            })
+            .collect();
+
+        let width = terminal::size().map_or(80, |(width, _)| width);
+        let styled = colored::control::SHOULD_COLORIZE.should_colorize();
+        let lines = render_timeline(&events, &window, &now, width, styled);
+        println!("🕒 {}", "Today".bold());
+        if styled {
+            println!("{}", to_ansi(&lines));
+        } else {
+            for line in lines {
+                println!("{line}");
+            }
+        }
+        Ok(true)
+    }
+
+    /// Show the time the todos due today take against the free working time left, if any.
+    async fn show_load(aim: &Aim) -> Result<(), Box<dyn Error>> {
+        let load = aim.day_load().await?;
+        if load.load.is_zero() {
+            return Ok(());
+        }
+        let line = describe_load(&load);
+        let line = match load.level() {
+            LoadLevel::Comfortable => line.green(),
+            LoadLevel::Tight => line.yellow(),
+            LoadLevel::Overcommitted => line.red(),
+        };
+        println!("{line}");
+        Ok(())
+    }
+
+    async fn list_todos(aim: &Aim) -> Result<(), Box<dyn Error>> {
+        let today = aim.now().date();
+        let week = DateRangeAnchor::this_week();
+        let (_, end_of_week) = week.resolve_dates(today, aim.week_start())?;
+        // On the last two days of the week, look a few days ahead instead
+        let (range, label) = if today.until(end_of_week)?.get_days() >= 2 {
+            (week, "this week")
+        } else {
+            (DateRangeAnchor::Days(4), "next 3 days")
         };
 
         println!("✅ {} {}", "To-Dos: within".bold(), label.bold());
+        let (_, due) = range.resolve_anchors(today, aim.week_start())?;
         let conds = TodoConditions {
             status: Some(TodoStatus::NeedsAction),
-            due: Some(DateTimeAnchor::InDays(i64::from(days))),
+            due: Some(due),
             calendar_id: None,
+            start_after: None,
+            hide_unstarted: aim.hide_unstarted(),
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: true,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         };
-        CmdTodoList::list(aim, &conds, OutputFormat::Table).await?;
+        CmdTodoList::list(
+            aim,
+            &conds,
+            TodoSortBy::default(),
+            None,
+            None,
+            &TodoColumns::default(),
+            OutputFormat::Table,
+        )
+        .await?;
         Ok(())
     }
 }
@@ -312,11 +457,524 @@ This will clear all short ID mappings, requiring them to be regenerated as neede
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct CmdSync {
+    pub subscriptions: bool,
+    pub dry_run: bool,
+    pub confirm: bool,
+}
+
+impl CmdSync {
+    pub const NAME: &str = "sync";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Synchronize calendars with their stores")
+            .long_about(
+                "\
+Synchronize calendars with their stores, pushing the writes staged for them first, such as the \
+todos captured, then pulling what changed in the stores into the local cache. With --dry-run, \
+the changes are listed instead of made, each with its direction, the calendar it goes to or \
+comes from and the properties that differ; --confirm lists the same and asks before syncing.
+
+Only staged writes are pushed, as other edits and deletions reach the stores when they are made.",
+            )
+            .arg(arg!(--subscriptions "Refresh subscribed feeds, ignoring their refresh interval"))
+            .arg(
+                arg!(--"dry-run" "List the changes to push and pull, without making any of them")
+                    .conflicts_with_all(["subscriptions", "confirm"]),
+            )
+            .arg(
+                arg!(--confirm "List the changes to push and pull and ask before making them")
+                    .conflicts_with("subscriptions"),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            subscriptions: matches.get_flag("subscriptions"),
+            dry_run: matches.get_flag("dry-run"),
+            confirm: matches.get_flag("confirm"),
+        }
+    }
+
+    /// Whether the writes to push are listed first, so the stores must not be synced before.
+    pub fn previews(self) -> bool {
+        self.dry_run || self.confirm
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "synchronizing...");
+        if self.previews() {
+            let plan = aim.plan_sync().await?;
+            print_sync_plan(&plan);
+            if self.dry_run {
+                return Ok(());
+            }
+            // Nothing listed is nothing to confirm, the stores are synced right away
+            if !plan.is_empty() && !prompt_sync_plan(plan.pushes().count(), plan.pulls().count())? {
+                return Ok(());
+            }
+            let result = aim.apply_sync(plan).await?;
+            print_sync_result(result);
+            run_scheduled_housekeeping(aim).await;
+            return Ok(());
+        }
+
+        if !self.subscriptions {
+            let result = aim.sync().await?;
+            print_sync_result(result);
+            run_scheduled_housekeeping(aim).await;
+            return Ok(());
+        }
+
+        let results = aim.sync_subscriptions().await;
+        if results.is_empty() {
+            println!("No subscriptions configured.");
+        }
+        for (calendar_id, result) in results {
+            match result {
+                Ok(r) => println!(
+                    "{calendar_id}: {} created, {} updated, {} deleted",
+                    r.created, r.updated, r.deleted
+                ),
+                Err(e) => println!("{calendar_id}: {} {e}", "Warning:".yellow()),
+            }
+        }
+        run_scheduled_housekeeping(aim).await;
+        Ok(())
+    }
+}
+
+fn print_sync_result(result: SyncResult) {
+    println!(
+        "Synced: {} created, {} updated, {} deleted",
+        result.created, result.updated, result.deleted
+    );
+}
+
+/// Prints the changes a sync makes, one per line, such as
+/// `push create todo "Call the bank" -> work: DTSTAMP, SUMMARY, UID` or
+/// `pull update event "Standup" <- work: DTSTART`.
+fn print_sync_plan(plan: &SyncPlan) {
+    if plan.changes.is_empty() {
+        println!("{}", "Nothing to push or pull".italic());
+    }
+    for change in &plan.changes {
+        println!("{}", format_sync_change(change));
+    }
+    for (uid, reason) in &plan.skipped {
+        println!("{} '{uid}' stays staged: {reason}", "Warning:".yellow());
+    }
+}
+
+fn format_sync_change(change: &SyncChange) -> String {
+    let kind = match change.kind {
+        Kind::Event => "event",
+        Kind::Todo => "todo",
+    };
+    let name = match &change.summary {
+        Some(summary) => format!("{summary:?}"),
+        None => change.uid.clone(),
+    };
+    let arrow = match change.direction {
+        SyncDirection::Pull => "<-",
+        SyncDirection::Push => "->",
+    };
+    let mut line = format!(
+        "{} {} {kind} {name} {arrow} {}",
+        change.direction.as_str(),
+        change.operation.as_str(),
+        change.calendar_id,
+    );
+    if !change.fields.is_empty() {
+        line.push_str(": ");
+        line.push_str(&change.fields.join(", "));
+    }
+    line
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CmdInbox;
+
+impl CmdInbox {
+    pub const NAME: &str = "inbox";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("List the invitations waiting on the calendar servers")
+            .long_about(
+                "\
+List the scheduling messages waiting in the inboxes of CalDAV servers, such as meeting \
+invitations, cancellations and replies, with who sent them, what they are about and when.",
+            )
+    }
+
+    pub fn from(_matches: &ArgMatches) -> Self {
+        Self
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "listing invitations...");
+        let tz = aim.now().time_zone().clone();
+        let invitations: Vec<_> = aim
+            .pending_invitations()
+            .await
+            .into_iter()
+            .map(|mut invitation| {
+                invitation.sent = invitation.sent.with_time_zone(tz.clone());
+                invitation
+            })
+            .collect();
+        if invitations.is_empty() {
+            println!("{}", "No pending invitations".italic());
+            return Ok(());
+        }
+
+        let columns = [
+            InboxColumn::Method,
+            InboxColumn::From,
+            InboxColumn::Summary,
+            InboxColumn::Start,
+            InboxColumn::Sent,
+        ];
+        let table = Table::new(TableStyleBasic::new(), &columns, &invitations);
+        println!("{table}");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum InboxColumn {
+    Method,
+    From,
+    Summary,
+    Start,
+    Sent,
+}
+
+impl TableColumn<Invitation> for InboxColumn {
+    fn name(&self) -> Cow<'_, str> {
+        match self {
+            InboxColumn::Method => "Method",
+            InboxColumn::From => "From",
+            InboxColumn::Summary => "Summary",
+            InboxColumn::Start => "Start",
+            InboxColumn::Sent => "Sent",
+        }
+        .into()
+    }
+
+    fn format<'a>(&self, invitation: &'a Invitation) -> Cow<'a, str> {
+        match self {
+            InboxColumn::Method => invitation.method.as_deref().unwrap_or_default().into(),
+            InboxColumn::From => invitation.organizer.as_deref().unwrap_or_default().into(),
+            InboxColumn::Summary => invitation.summary.as_str().into(),
+            InboxColumn::Start => invitation
+                .start
+                .clone()
+                .map(format_datetime)
+                .unwrap_or_default()
+                .into(),
+            InboxColumn::Sent => {
+                format_datetime(LooseDateTime::Local(invitation.sent.clone())).into()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdImport {
+    pub file: PathBuf,
+    pub calendar_id: Option<String>,
+    pub format: ImportFormat,
+    pub include_completed: bool,
+    pub dry_run: bool,
+}
+
+/// Format of the file to import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// An iCalendar file
+    Ics,
+    /// The JSON export of Todoist, or a zip archive holding it
+    Todoist,
+    #[value(name = "ticktick", help = "The CSV backup of TickTick")]
+    TickTick,
+}
+
+impl CmdImport {
+    pub const NAME: &str = "import";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Import the events and todos of an iCalendar file")
+            .long_about(
+                "\
+Import the events and todos of an iCalendar file, keeping their UIDs. \
+Items whose UID already exists are skipped rather than overwritten.
+
+With --format, the todos of a Todoist or TickTick export are imported instead. Projects, \
+lists, labels and tags become categories, and what cannot be mapped is reported at the end.",
+            )
+            .arg(
+                arg!(file: <FILE> "File to import, or - to read it from stdin")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+            .arg(CalendarArgs::new(true).calendar())
+            .arg(
+                arg!(--format <FORMAT> "Format of the file")
+                    .value_parser(value_parser!(ImportFormat))
+                    .default_value("ics"),
+            )
+            .arg(arg!(--"include-completed" "Also import completed todos of an export"))
+            .arg(arg!(--"dry-run" "Show the todos of an export without importing them"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            file: matches
+                .get_one::<PathBuf>("file")
+                .expect("file is required")
+                .clone(),
+            calendar_id: CalendarArgs::get_calendar(matches),
+            format: matches
+                .get_one::<ImportFormat>("format")
+                .copied()
+                .unwrap_or(ImportFormat::Ics),
+            include_completed: matches.get_flag("include-completed"),
+            dry_run: matches.get_flag("dry-run"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        let foreign = match self.format {
+            ImportFormat::Ics => None,
+            ImportFormat::Todoist => Some(ForeignFormat::Todoist),
+            ImportFormat::TickTick => Some(ForeignFormat::TickTick),
+        };
+        if let Some(format) = foreign {
+            return self.run_foreign(aim, format).await;
+        }
+        if self.include_completed || self.dry_run {
+            return Err("--include-completed and --dry-run need a --format other than ics".into());
+        }
+
+        tracing::debug!(?self, "importing calendar...");
+        let content = StdinArgs::default()
+            .read_file("calendar", &self.file)
+            .await?;
+        let report = aim
+            .import_ics(
+                input_source(&self.file),
+                &content,
+                self.calendar_id.as_deref(),
+            )
+            .await?;
+
+        println!(
+            "Imported: {} events, {} todos, {} overrides",
+            report.events, report.todos, report.overrides
+        );
+        if let Some(dates) = &report.expanded {
+            println!(
+                "{} the file is an expanded export of aim ({dates}), its occurrences were imported as events of their own rather than as recurring events",
+                "Warning:".yellow(),
+            );
+        }
+        if !report.skipped.is_empty() {
+            println!(
+                "{} skipped {} existing: {}",
+                "Warning:".yellow(),
+                report.skipped.len(),
+                report.skipped.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    async fn run_foreign(self, aim: &Aim, format: ForeignFormat) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "importing export...");
+        let content = if self.file == Path::new(STDIN_ARG) {
+            StdinArgs::default()
+                .read_file("export", &self.file)
+                .await?
+                .into_bytes()
+        } else {
+            tokio::fs::read(&self.file)
+                .await
+                .map_err(|e| format!("Failed to read {}: {e}", self.file.display()))?
+        };
+        let import = format.read(input_source(&self.file), &content, self.include_completed)?;
+
+        if self.dry_run {
+            let today = aim.now().date();
+            for todo in &import.todos {
+                println!("{}", describe_foreign_todo(todo, today));
+            }
+            println!("Would import {} todos", import.todos.len());
+        } else {
+            let report = aim
+                .import_todos(import.todos, self.calendar_id.as_deref())
+                .await?;
+            println!("Imported: {} todos", report.todos);
+            if !report.skipped.is_empty() {
+                println!(
+                    "{} skipped {} existing: {}",
+                    "Warning:".yellow(),
+                    report.skipped.len(),
+                    report.skipped.join(", ")
+                );
+            }
+        }
+
+        if !import.warnings.is_empty() {
+            println!(
+                "{} {} constructs could not be mapped:",
+                "Warning:".yellow(),
+                import.warnings.len()
+            );
+            for warning in &import.warnings {
+                println!("  {warning}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdExport {
+    pub from: DateTimeAnchor,
+    pub to: DateTimeAnchor,
+    pub limit: usize,
+    pub output: Option<PathBuf>,
+}
+
+impl CmdExport {
+    pub const NAME: &str = "export";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Export the occurrences of events between two dates as an iCalendar file")
+            .long_about(
+                "\
+Export the events of all enabled calendars between two dates as an iCalendar file, with every \
+occurrence of a recurring event expanded into an event of its own, for tools that ignore \
+RRULE.
+
+Overrides of occurrences take their place and excluded or cancelled occurrences are left out. \
+An occurrence gets the UID of its series followed by the time it recurs at. The file is \
+marked with X-AIM-EXPANDED, so importing it back into aim warns about it. To export a single \
+event as it is, use `aim event export`.",
+            )
+            .arg(
+                arg!(--expand "Expand recurring events into one event per occurrence")
+                    .required(true),
+            )
+            .arg(
+                arg!(--from <DATE> "First day to export occurrences of")
+                    .value_parser(value_parser!(DateTimeAnchor))
+                    .required(true),
+            )
+            .arg(
+                arg!(--to <DATE> "Last day to export occurrences of")
+                    .value_parser(value_parser!(DateTimeAnchor))
+                    .required(true),
+            )
+            .arg(
+                arg!(--limit <N> "Fail if there are more occurrences than this [default: 10000]")
+                    .value_parser(value_parser!(usize)),
+            )
+            .arg(
+                arg!(-o --output <FILE> "Write to this file instead of stdout")
+                    .value_parser(value_parser!(PathBuf))
+                    .value_hint(ValueHint::FilePath),
+            )
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            from: matches
+                .get_one::<DateTimeAnchor>("from")
+                .expect("from is required")
+                .clone(),
+            to: matches
+                .get_one::<DateTimeAnchor>("to")
+                .expect("to is required")
+                .clone(),
+            limit: matches
+                .get_one::<usize>("limit")
+                .copied()
+                .unwrap_or(DEFAULT_EXPAND_LIMIT),
+            output: matches.get_one::<PathBuf>("output").cloned(),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "exporting expanded events...");
+        let now = aim.now();
+        let from = self.from.resolve_since_zoned(&now)?.date();
+        let to = self.to.resolve_since_zoned(&now)?.date();
+        let export = aim.export_expanded(from, to, self.limit).await?;
+        if self.output.is_some() {
+            println!("Exported {} occurrences from {from} to {to}", export.len());
+        }
+        write_export(self.output.as_deref(), export)
+    }
+}
+
+/// One line about a todo of an export, such as `Water plants (due 2025-01-10, every 2 weeks)`.
+fn describe_foreign_todo(todo: &ForeignTodo, today: jiff::civil::Date) -> String {
+    let mut details = Vec::new();
+    if let Some(due) = &todo.due {
+        details.push(format!("due {}", format_datetime(due.clone())));
+    }
+    if let Some(rule) = &todo.rrule {
+        details.push(describe_recurrence(rule, today));
+    }
+    if todo.priority != Priority::None {
+        details.push(format!("priority {}", u8::from(todo.priority)));
+    }
+    if !todo.categories.is_empty() {
+        details.push(todo.categories.join(", "));
+    }
+    if let Some(completed) = &todo.completed {
+        let completed = LooseDateTime::Local(completed.clone());
+        details.push(format!("completed {}", format_datetime(completed)));
+    }
+
+    if details.is_empty() {
+        todo.summary.clone()
+    } else {
+        format!("{} ({})", todo.summary, details.join("; "))
+    }
+}
+
+/// Describes the load of the day, such as `⚠ 5h 30m of tasks, 3h 10m free`, warning when it takes
+/// most of the free time.
+fn describe_load(load: &DayLoad) -> String {
+    // Whole minutes, the time passing makes the seconds noise
+    let minutes = |d: SignedDuration| SignedDuration::from_mins(d.as_secs() / 60);
+    let warning = if load.level() == LoadLevel::Comfortable {
+        ""
+    } else {
+        "⚠ "
+    };
+    format!(
+        "{warning}{:#} of tasks, {:#} free",
+        minutes(load.load),
+        minutes(load.free)
+    )
+}
+
 async fn separate_ids(aim: &Aim, ids: Vec<Id>) -> Result<(Vec<Id>, Vec<Id>), Box<dyn Error>> {
     let mut event_ids = vec![];
     let mut todo_ids = vec![];
     for id in ids {
-        match aim.get_kind(&id).await? {
+        let candidate = resolve_candidate(aim, &id, None).await?;
+        let id = Id::Uid(candidate.uid);
+        match candidate.kind {
             Kind::Event => event_ids.push(id),
             Kind::Todo => todo_ids.push(id),
         }
@@ -332,7 +990,79 @@ mod tests {
     fn parses_dashboard_command() {
         let args = ["dashboard"];
         let matches = CmdDashboard::command().try_get_matches_from(args).unwrap();
-        let _ = CmdDashboard::from(&matches);
+        assert!(!CmdDashboard::from(&matches).no_dedupe);
+
+        let args = ["dashboard", "--no-dedupe"];
+        let matches = CmdDashboard::command().try_get_matches_from(args).unwrap();
+        assert!(CmdDashboard::from(&matches).no_dedupe);
+    }
+
+    #[test]
+    fn describes_the_load_of_the_day() {
+        let load = DayLoad {
+            load: SignedDuration::from_mins(330),
+            free: SignedDuration::from_mins(190) + SignedDuration::from_secs(42),
+            unestimated: 0,
+        };
+        assert_eq!(describe_load(&load), "⚠ 5h 30m of tasks, 3h 10m free");
+
+        let load = DayLoad {
+            load: SignedDuration::from_mins(45),
+            free: SignedDuration::from_hours(4),
+            unestimated: 1,
+        };
+        assert_eq!(describe_load(&load), "45m of tasks, 4h free");
+    }
+
+    #[test]
+    fn parses_import_command() {
+        let args = ["import", "-", "--calendar", "work"];
+        let matches = CmdImport::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdImport::from(&matches);
+
+        assert_eq!(parsed.file, PathBuf::from("-"));
+        assert_eq!(parsed.calendar_id, Some("work".to_string()));
+        assert_eq!(parsed.format, ImportFormat::Ics);
+        assert!(!parsed.dry_run);
+    }
+
+    #[test]
+    fn parses_import_command_of_an_export() {
+        let args = [
+            "import",
+            "backup.zip",
+            "--format",
+            "todoist",
+            "--include-completed",
+            "--dry-run",
+        ];
+        let matches = CmdImport::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdImport::from(&matches);
+
+        assert_eq!(parsed.format, ImportFormat::Todoist);
+        assert!(parsed.include_completed);
+        assert!(parsed.dry_run);
+
+        let args = ["import", "ticktick.csv", "--format", "ticktick"];
+        let matches = CmdImport::command().try_get_matches_from(args).unwrap();
+        assert_eq!(CmdImport::from(&matches).format, ImportFormat::TickTick);
+    }
+
+    #[test]
+    fn describes_todos_of_an_export() {
+        let todo = ForeignTodo {
+            uid: "todoist-1".to_string(),
+            summary: "Water plants".to_string(),
+            description: None,
+            due: Some(LooseDateTime::DateOnly(jiff::civil::date(2025, 1, 10))),
+            priority: Priority::P2,
+            categories: vec!["Home".to_string(), "garden".to_string()],
+            rrule: "FREQ=WEEKLY;INTERVAL=2".parse().ok(),
+            completed: None,
+        };
+        let line = describe_foreign_todo(&todo, jiff::civil::date(2025, 1, 1));
+        assert!(line.starts_with("Water plants (due "), "{line}");
+        assert!(line.contains("priority 2; Home, garden"), "{line}");
     }
 
     #[test]
@@ -371,4 +1101,33 @@ mod tests {
         let matches = CmdFlush::command().try_get_matches_from(args).unwrap();
         let _ = CmdFlush::from(&matches);
     }
+
+    #[test]
+    fn parses_sync_command() {
+        let args = ["sync"];
+        let matches = CmdSync::command().try_get_matches_from(args).unwrap();
+        assert!(!CmdSync::from(&matches).subscriptions);
+
+        let args = ["sync", "--subscriptions"];
+        let matches = CmdSync::command().try_get_matches_from(args).unwrap();
+        assert!(CmdSync::from(&matches).subscriptions);
+
+        let args = ["sync", "--dry-run"];
+        let matches = CmdSync::command().try_get_matches_from(args).unwrap();
+        let parsed = CmdSync::from(&matches);
+        assert!(parsed.dry_run && !parsed.confirm && parsed.previews());
+
+        let args = ["sync", "--confirm"];
+        let matches = CmdSync::command().try_get_matches_from(args).unwrap();
+        assert!(CmdSync::from(&matches).previews());
+        assert!(!CmdSync::from(&CmdSync::command().get_matches_from(["sync"])).previews());
+
+        for args in [
+            ["sync", "--dry-run", "--confirm"],
+            ["sync", "--dry-run", "--subscriptions"],
+            ["sync", "--confirm", "--subscriptions"],
+        ] {
+            assert!(CmdSync::command().try_get_matches_from(args).is_err());
+        }
+    }
 }
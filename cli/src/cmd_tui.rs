@@ -7,14 +7,18 @@
 
 use std::error::Error;
 
-use aimcal_core::{Aim, EventDraft, EventStatus, Id, Kind, Priority, TodoDraft, TodoStatus};
+use aimcal_core::{
+    Aim, EventDraft, EventStatus, Id, Kind, Priority, ThisAndFuture, TodoDraft, TodoStatus,
+};
 use clap::{ArgMatches, Command};
+use jiff::SignedDuration;
 
 use crate::arg::{CommonArgs, EventArgs, EventOrTodoArgs, EventOrTodoStatus, TodoArgs};
 use crate::cmd_event::{CmdEventEdit, CmdEventNew};
 use crate::cmd_todo::{CmdTodoEdit, CmdTodoNew};
+use crate::resolve::resolve_candidate;
 use crate::tui::{EventOrTodoDraft, draft_event_or_todo};
-use crate::util::{OutputFormat, parse_datetime, parse_datetime_range};
+use crate::util::{OutputFormat, StdinArgs, parse_anchor, parse_due};
 
 #[derive(Debug, Clone)]
 pub struct CmdNew {
@@ -26,6 +30,7 @@ pub struct CmdNew {
 
     // fields (event specific)
     pub end: Option<String>,
+    pub duration: Option<SignedDuration>,
     pub start: Option<String>,
 
     // fields (todo specific)
@@ -52,6 +57,7 @@ impl CmdNew {
             // fields (event specific)
             .arg(event_args.start())
             .arg(event_args.end())
+            .arg(event_args.duration())
             // fields (todo specific)
             .arg(todo_args.due())
             .arg(todo_args.percent_complete())
@@ -69,6 +75,7 @@ impl CmdNew {
             summary: EventOrTodoArgs::get_summary(matches),
 
             end: EventArgs::get_end(matches),
+            duration: EventArgs::get_duration(matches),
             start: EventArgs::get_start(matches),
 
             due: TodoArgs::get_due(matches),
@@ -79,9 +86,12 @@ impl CmdNew {
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         // TODO: check is it a event / todo
         tracing::debug!(?self, "adding new item using TUI...");
+        let mut stdin = StdinArgs::default();
+        stdin.resolve("summary", &mut self.summary)?;
+        stdin.resolve("description", &mut self.description)?;
 
         // infer kind
         let inferred_kind = self.infer_kind();
@@ -102,7 +112,7 @@ impl CmdNew {
                 }
             }
             Some(Kind::Todo) => {
-                if self.start.is_some() || self.end.is_some() {
+                if self.start.is_some() || self.end.is_some() || self.duration.is_some() {
                     return Err("Cannot provide event-specific fields for a todo".into());
                 } else if let Some(EventOrTodoStatus::Event(_)) = self.status {
                     return Err("Cannot provide event-specific status for a todo".into());
@@ -153,21 +163,14 @@ impl CmdNew {
 
         // fields (event specific)
         //
-        match (self.start, self.end) {
-            (Some(start), Some(end)) => {
-                let (s, e) = parse_datetime_range(&now, &start, &end)?;
-                event_draft.start = s;
-                event_draft.end = e;
-            }
-            (Some(start), None) => event_draft.start = parse_datetime(&aim.now(), &start)?,
-            (None, Some(end)) => event_draft.end = parse_datetime(&aim.now(), &end)?,
-            (None, None) => { /* do nothing */ }
-        }
+        let start = parse_anchor(self.start.as_deref().unwrap_or_default())?;
+        let end = parse_anchor(self.end.as_deref().unwrap_or_default())?;
+        event_draft.set_times(&now, start, end, self.duration)?;
 
         // fields (todo specific)
         //
         if let Some(due) = self.due {
-            todo_draft.due = parse_datetime(&now, &due)?;
+            todo_draft.due = parse_due(&now, aim.default_due_time(), &due)?;
         }
 
         if let Some(pc) = self.percent_complete {
@@ -207,7 +210,7 @@ impl CmdNew {
                 EventOrTodoStatus::Todo(_) => Some(Kind::Todo),
             }
         } else {
-            let maybe_event = self.start.is_some() || self.end.is_some();
+            let maybe_event = self.start.is_some() || self.end.is_some() || self.duration.is_some();
             let maybe_todo =
                 self.due.is_some() || self.percent_complete.is_some() || self.priority.is_some();
 
@@ -245,16 +248,9 @@ impl CmdNew {
 
         // fields (event specific)
         //
-        match (&self.start, &self.end) {
-            (Some(start), Some(end)) => {
-                let (s, e) = parse_datetime_range(&now, start, end)?;
-                draft.start = s;
-                draft.end = e;
-            }
-            (Some(start), None) => draft.start = parse_datetime(&aim.now(), start)?,
-            (None, Some(end)) => draft.end = parse_datetime(&aim.now(), end)?,
-            (None, None) => { /* do nothing */ }
-        }
+        let start = parse_anchor(self.start.as_deref().unwrap_or_default())?;
+        let end = parse_anchor(self.end.as_deref().unwrap_or_default())?;
+        draft.set_times(&now, start, end, self.duration)?;
 
         Ok(draft)
     }
@@ -282,7 +278,7 @@ impl CmdNew {
         // fields (todo specific)
         //
         if let Some(due) = &self.due {
-            draft.due = parse_datetime(&now, due)?;
+            draft.due = parse_due(&now, aim.default_due_time(), due)?;
         }
 
         if let Some(pc) = self.percent_complete {
@@ -361,15 +357,17 @@ impl CmdEdit {
         }
     }
 
-    pub async fn run(self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
+    pub async fn run(mut self, aim: &mut Aim) -> Result<(), Box<dyn Error>> {
         tracing::debug!(?self, "editing item using TUI...");
 
-        let kind = aim.get_kind(&self.id).await?;
+        let candidate = resolve_candidate(aim, &self.id, None).await?;
+        let kind = candidate.kind;
+        self.id = Id::Uid(candidate.uid);
 
         // Check if any fields are provided to determine if we should use TUI mode
         let use_tui = match kind {
             Kind::Event => {
-                tracing::debug!("item with id {} is an event", self.id.as_uid());
+                tracing::debug!(uid = %self.id.as_uid(), kind = "event", "resolved item kind");
                 if self.due.is_some() || self.percent_complete.is_some() || self.priority.is_some()
                 {
                     return Err("Cannot provide todo-specific fields for an event".into());
@@ -383,7 +381,7 @@ impl CmdEdit {
                     && self.summary.is_none()
             }
             Kind::Todo => {
-                tracing::debug!("item with id {} is a todo", self.id.as_uid());
+                tracing::debug!(uid = %self.id.as_uid(), kind = "todo", "resolved item kind");
                 if self.start.is_some() || self.end.is_some() {
                     return Err("Cannot provide event-specific fields for a todo".into());
                 } else if let Some(EventOrTodoStatus::Event(_)) = self.status {
@@ -430,6 +428,7 @@ impl CmdEdit {
                 tracing::info!("editing event with provided fields");
                 CmdEventEdit {
                     id: self.id,
+                    alarm: None,
                     description: self.description,
                     end: self.end,
                     start: self.start,
@@ -438,6 +437,8 @@ impl CmdEdit {
                         EventOrTodoStatus::Todo(_) => EventStatus::default(),
                     }),
                     summary: self.summary,
+                    occurrence: None,
+                    range: ThisAndFuture::Split,
 
                     output_format: self.output_format,
                 }
@@ -448,8 +449,11 @@ impl CmdEdit {
                 tracing::info!("editing todo with provided fields");
                 CmdTodoEdit {
                     id: self.id,
+                    alarm: None,
                     description: self.description,
                     due: self.due,
+                    business_days: false,
+                    estimate: None,
                     percent_complete: self.percent_complete,
                     priority: self.priority,
                     status: self.status.map(|s| match s {
@@ -0,0 +1,280 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use aimcal_core::{Aim, Kind, ZoneRebase, ZoneShift};
+use clap::{ArgMatches, Command, arg};
+use colored::Colorize;
+use jiff::tz::TimeZone;
+
+use crate::arg::CommonArgs;
+use crate::util::{OutputFormat, format_datetime};
+
+#[derive(Debug, Clone)]
+pub struct CmdTzAudit {
+    /// The home time zone the times were written in, the previous one recorded if none.
+    pub from: Option<String>,
+    /// The home time zone the times are read in now, the current one recorded if none.
+    pub to: Option<String>,
+    pub floating_only: bool,
+    pub output_format: OutputFormat,
+}
+
+impl CmdTzAudit {
+    pub const NAME: &str = "audit";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("List the items whose times read differently since the home time zone changed")
+            .arg(
+                arg!(--from <ZONE> "Home time zone the times were written in, by default the previous one")
+                    .value_parser(parse_zone),
+            )
+            .arg(
+                arg!(--to <ZONE> "Home time zone the times are read in, the current one by default")
+                    .value_parser(parse_zone),
+            )
+            .arg(arg!(--"floating-only" "Only list floating times"))
+            .arg(CommonArgs::output_format())
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            from: matches.get_one("from").cloned(),
+            to: matches.get_one("to").cloned(),
+            floating_only: matches.get_flag("floating-only"),
+            output_format: CommonArgs::get_output_format(matches),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "auditing times against the home time zone...");
+        let (from, to) = self.zones(aim).await?;
+        let (from_zone, to_zone) = (zone(&from)?, zone(&to)?);
+        let shifts = aim
+            .audit_time_zone(&from_zone, &to_zone, self.floating_only)
+            .await?;
+
+        match self.output_format {
+            OutputFormat::Json => {
+                let entries: Vec<_> = shifts.iter().map(ShiftEntry::from).collect();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            }
+            OutputFormat::Table if shifts.is_empty() => {
+                let prompt = format!("No times read differently in {to} than in {from}");
+                println!("{}", prompt.italic());
+            }
+            OutputFormat::Table => {
+                println!(
+                    "{}",
+                    format!("Read in {from} before, and in {to} now:").bold()
+                );
+                for shift in &shifts {
+                    println!("  {}", format_shift(shift));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The zones given, or else the previous and current home time zones recorded.
+    async fn zones(&self, aim: &Aim) -> Result<(String, String), Box<dyn Error>> {
+        let recorded: Vec<_> = aim
+            .home_time_zones()
+            .await?
+            .into_iter()
+            .map(|(zone, _)| zone)
+            .collect();
+        let from = match &self.from {
+            Some(from) => from.clone(),
+            None => recorded
+                .iter()
+                .rev()
+                .nth(1)
+                .cloned()
+                .ok_or("No earlier home time zone is recorded, give one with --from")?,
+        };
+        let to = match &self.to {
+            Some(to) => to.clone(),
+            None => recorded
+                .last()
+                .cloned()
+                .ok_or("No home time zone is recorded, give one with --to")?,
+        };
+        Ok((from, to))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CmdTzRebase {
+    pub from: String,
+    pub to: String,
+    pub floating_only: bool,
+    pub dry_run: bool,
+}
+
+impl CmdTzRebase {
+    pub const NAME: &str = "rebase";
+
+    pub fn command() -> Command {
+        Command::new(Self::NAME)
+            .about("Rewrite the times written in an old home time zone so they keep their instant")
+            .long_about(
+                "\
+Rewrite the times listed by `aim tz audit` so they happen at the instant they had in the old home \
+time zone. Floating times stay floating, with the wall clock of the new zone, and times in the old \
+zone are written in the new one. The items are updated in their calendars like any other edit.",
+            )
+            .arg(
+                arg!(--from <ZONE> "Home time zone the times were written in")
+                    .value_parser(parse_zone)
+                    .required(true),
+            )
+            .arg(
+                arg!(--to <ZONE> "Home time zone to rewrite the times into")
+                    .value_parser(parse_zone)
+                    .required(true),
+            )
+            .arg(arg!(--"floating-only" "Only rewrite floating times"))
+            .arg(arg!(--"dry-run" "Print the times to rewrite without changing them"))
+    }
+
+    pub fn from(matches: &ArgMatches) -> Self {
+        Self {
+            from: matches
+                .get_one::<String>("from")
+                .expect("from is required")
+                .clone(),
+            to: matches
+                .get_one::<String>("to")
+                .expect("to is required")
+                .clone(),
+            floating_only: matches.get_flag("floating-only"),
+            dry_run: matches.get_flag("dry-run"),
+        }
+    }
+
+    pub async fn run(self, aim: &Aim) -> Result<(), Box<dyn Error>> {
+        tracing::debug!(?self, "rebasing times onto the home time zone...");
+        let (from, to) = (zone(&self.from)?, zone(&self.to)?);
+        let report = aim
+            .rebase_time_zone(&from, &to, self.floating_only, self.dry_run)
+            .await?;
+        print_rebase(&report, self.dry_run);
+        Ok(())
+    }
+}
+
+fn parse_zone(s: &str) -> Result<String, String> {
+    TimeZone::get(s)
+        .map(|_| s.to_string())
+        .map_err(|e| format!("Invalid time zone '{s}': {e}"))
+}
+
+fn zone(name: &str) -> Result<TimeZone, Box<dyn Error>> {
+    TimeZone::get(name).map_err(|e| format!("Invalid time zone '{name}': {e}").into())
+}
+
+fn print_rebase(report: &ZoneRebase, dry_run: bool) {
+    if report.rebased.is_empty() {
+        println!("{}", "No times to rebase".italic());
+    } else if dry_run {
+        println!("{}", "Times to rebase, nothing was changed:".bold());
+    } else {
+        println!("{}", "Rebased times:".bold());
+    }
+    for shift in &report.rebased {
+        println!(
+            "  {:<5}  {} -> {}  {}",
+            shift.field,
+            format_datetime(shift.time.clone()),
+            format_datetime(shift.rebased()),
+            shift.summary
+        );
+    }
+    for (uid, reason) in &report.skipped {
+        println!("{} {uid}: {reason}", "Not rebased".yellow());
+    }
+}
+
+/// Formats a time as it was read and as it is read now, such as
+/// `start  floating  2026-03-02 09:00 CET -> 2026-03-02 09:00 WET  Standup`.
+fn format_shift(shift: &ZoneShift) -> String {
+    const FORMAT: &str = "%Y-%m-%d %H:%M %Z";
+    let kind = if shift.is_floating() {
+        "floating"
+    } else {
+        "zoned"
+    };
+    format!(
+        "{:<5}  {kind:<8}  {} -> {}  {}",
+        shift.field,
+        shift.was.strftime(FORMAT),
+        shift.now.strftime(FORMAT),
+        shift.summary
+    )
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ShiftEntry {
+    kind: &'static str,
+    uid: String,
+    summary: String,
+    field: &'static str,
+    floating: bool,
+    was: String,
+    now: String,
+}
+
+impl From<&ZoneShift> for ShiftEntry {
+    fn from(shift: &ZoneShift) -> Self {
+        Self {
+            kind: match shift.kind {
+                Kind::Event => "event",
+                Kind::Todo => "todo",
+            },
+            uid: shift.uid.clone(),
+            summary: shift.summary.clone(),
+            field: shift.field,
+            floating: shift.is_floating(),
+            was: shift.was.to_string(),
+            now: shift.now.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::LooseDateTime;
+    use jiff::civil::datetime;
+
+    use super::*;
+
+    #[test]
+    fn formats_shift() {
+        let berlin = TimeZone::get("Europe/Berlin").unwrap();
+        let lisbon = TimeZone::get("Europe/Lisbon").unwrap();
+        let dt = datetime(2026, 3, 2, 9, 0, 0, 0);
+        let shift = ZoneShift {
+            kind: Kind::Event,
+            uid: "standup".to_string(),
+            summary: "Standup".to_string(),
+            field: "start",
+            time: LooseDateTime::Floating(dt),
+            was: dt.to_zoned(berlin).unwrap(),
+            now: dt.to_zoned(lisbon).unwrap(),
+        };
+        assert_eq!(
+            format_shift(&shift),
+            "start  floating  2026-03-02 09:00 CET -> 2026-03-02 09:00 WET  Standup"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_zones() {
+        assert_eq!(parse_zone("Europe/Lisbon").unwrap(), "Europe/Lisbon");
+        assert!(parse_zone("Europe/Nowhere").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::builder::PossibleValuesParser;
+
+use crate::table::{ColumnPriority, ColumnWidth, PaddingDirection};
+
+/// The definition of a column of a listing, which can be chosen by its name.
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnDef<C> {
+    /// Name of the column in `--columns` and the `columns` of the config.
+    pub name: &'static str,
+    /// Header of the column, the key of its cells in JSON output.
+    pub header: &'static str,
+    /// The column, whose formatter extracts the cells from the items.
+    pub column: C,
+    /// Side the cells are aligned to.
+    pub alignment: PaddingDirection,
+    /// How the column takes room when the terminal is narrow.
+    pub width: ColumnWidth,
+    /// When the column drops out when the terminal is narrow.
+    pub priority: ColumnPriority,
+    /// Data the column needs loaded besides the listed items.
+    pub needs: ColumnData,
+}
+
+impl<C> ColumnDef<C> {
+    pub const fn new(
+        name: &'static str,
+        header: &'static str,
+        column: C,
+        alignment: PaddingDirection,
+        width: ColumnWidth,
+        priority: ColumnPriority,
+        needs: ColumnData,
+    ) -> Self {
+        Self {
+            name,
+            header,
+            column,
+            alignment,
+            width,
+            priority,
+            needs,
+        }
+    }
+}
+
+/// Data beyond the listed items that a column needs loaded to be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnData {
+    /// Nothing, the items have it all.
+    None,
+    /// The number of done and total subtasks of each todo.
+    Subtasks,
+}
+
+/// A column of a listing, with a registry of the ones that can be chosen.
+pub trait Column: Copy + PartialEq + 'static {
+    /// Kind of items listed, as in `[todos]` of the config.
+    const KIND: &'static str;
+
+    /// Every column, in the order they are listed in help and errors.
+    const DEFS: &'static [ColumnDef<Self>];
+
+    /// The definition of the column.
+    fn def(self) -> &'static ColumnDef<Self> {
+        Self::DEFS
+            .iter()
+            .find(|def| def.column == self)
+            .expect("every column is in the registry")
+    }
+}
+
+/// The names of the columns that can be chosen.
+pub fn column_names<C: Column>() -> Vec<&'static str> {
+    C::DEFS.iter().map(|def| def.name).collect()
+}
+
+/// Parser of a column name for `--columns`, which lists the names on an unknown one.
+pub fn column_parser<C: Column>() -> PossibleValuesParser {
+    PossibleValuesParser::new(column_names::<C>())
+}
+
+/// Resolves column names, failing with the valid ones on an unknown name.
+pub fn parse_columns<C: Column>(names: &[impl AsRef<str>]) -> Result<Vec<C>, String> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.as_ref().trim();
+            C::DEFS
+                .iter()
+                .find(|def| def.name.eq_ignore_ascii_case(name))
+                .map(|def| def.column)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown {} column `{name}`, expected one of: {}",
+                        C::KIND,
+                        column_names::<C>().join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Whether any of the columns needs `data` loaded.
+pub fn any_needs<C: Column>(columns: &[C], data: ColumnData) -> bool {
+    columns.iter().any(|column| column.def().needs == data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_formatter::EventColumn;
+    use crate::todo_formatter::TodoColumn;
+
+    fn assert_registry<C: Column + std::fmt::Debug>() {
+        for (i, def) in C::DEFS.iter().enumerate() {
+            assert_eq!(
+                def.column.def().name,
+                def.name,
+                "{:?} is listed twice",
+                def.column
+            );
+            assert!(
+                C::DEFS
+                    .iter()
+                    .skip(i + 1)
+                    .all(|other| other.name != def.name),
+                "`{}` names two columns",
+                def.name
+            );
+        }
+    }
+
+    #[test]
+    fn registries_name_each_column_once() {
+        assert_registry::<TodoColumn>();
+        assert_registry::<EventColumn>();
+    }
+
+    #[test]
+    fn parses_column_names() {
+        let columns = parse_columns::<TodoColumn>(&["id", " Summary ", "subtasks"]).unwrap();
+        assert_eq!(
+            columns,
+            [TodoColumn::Id, TodoColumn::Summary, TodoColumn::Subtasks]
+        );
+        assert!(any_needs(&columns, ColumnData::Subtasks));
+        assert!(!any_needs(
+            &[TodoColumn::Id, TodoColumn::Summary],
+            ColumnData::Subtasks
+        ));
+
+        let err = parse_columns::<EventColumn>(&["time", "due"]).unwrap_err();
+        assert_eq!(
+            err,
+            "unknown event column `due`, expected one of: id, short-id, uid, datetime, time, \
+             summary, description, location, categories, calendars, url"
+        );
+    }
+}
@@ -3,63 +3,217 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
+    collections::BTreeMap,
     error::Error,
     io::{IsTerminal, stdin, stdout},
     path::PathBuf,
     str::FromStr,
+    sync::OnceLock,
+    time::Duration,
 };
 
 use tokio::fs;
+use toml::Spanned;
 
-use aimcal_core::{APP_NAME, Config as CoreConfig};
+use aimcal_core::{Config as CoreConfig, Profile, ProfileDirs};
 
+use crate::cli::Cli;
+use crate::column::{Column, parse_columns};
+use crate::event_formatter::EventColumn;
 use crate::prompt::{DevModeChoice, prompt_dev_mode_choice};
+use crate::table::terminal_supports_hyperlinks;
+use crate::todo_formatter::TodoColumn;
 
 const AIM_CONFIG_ENV: &str = "AIM_CONFIG";
+const AIM_PROFILE_ENV: &str = "AIM_PROFILE";
 const AIM_DEV_ENV: &str = "AIM_DEV";
 
 const AIM_DEV_VALID_TRUE: &[&str] = &["1", "true", "yes"];
 const AIM_DEV_VALID_FALSE: &[&str] = &["0", "false", "no"];
 
+/// The answer to the dev mode prompt, so that it is asked once per run.
+static DEV_MODE_CHOICE: OnceLock<DevModeChoice> = OnceLock::new();
+
+/// Where the config is read from, the file given with `--config` or the profile given with
+/// `--profile`, falling back to the environment and then to the default profile, and how the
+/// state it points to is opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigLocation {
+    /// Path of the config file, from `--config`.
+    pub path: Option<PathBuf>,
+
+    /// Name of the profile, from `--profile`.
+    pub profile: Option<String>,
+
+    /// Whether a database written by a newer aim is opened for listing, from
+    /// `--read-only-compat`.
+    pub read_only_compat: bool,
+
+    /// Whether the command only reads, so that calendars synced a moment ago are not synced
+    /// again, see [`Commands::is_read_only`](crate::Commands::is_read_only).
+    pub read_only: bool,
+}
+
+impl From<Option<PathBuf>> for ConfigLocation {
+    fn from(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            profile: None,
+            read_only_compat: false,
+            read_only: false,
+        }
+    }
+}
+
 #[tracing::instrument]
-pub async fn parse_config(path: Option<PathBuf>) -> Result<(CoreConfig, Config), Box<dyn Error>> {
+pub async fn parse_config(
+    location: impl Into<ConfigLocation> + std::fmt::Debug,
+) -> Result<(CoreConfig, Config), Box<dyn Error>> {
+    let location = location.into();
+    let read_only_compat = location.read_only_compat;
+    let (path, profile) = resolve_config_path(location)?;
+    fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read config file at {}: {}", path.display(), e))?
+        .parse::<ConfigRaw>()
+        .map(|mut a| {
+            a.core.config_dir = path.parent().map(PathBuf::from);
+            a.core.dev_mode = is_dev_mode().unwrap_or(false);
+            a.core.profile = profile.clone();
+            a.core.read_only_compat = read_only_compat;
+            if let (Some(dir), Some(parent)) = (&mut a.backup.dir, path.parent()) {
+                *dir = parent.join(&*dir);
+            }
+            let config = Config {
+                path,
+                profile,
+                backup: a.backup,
+                aliases: a.aliases,
+                hyperlinks: a.hyperlinks,
+                todos: a.todos,
+                events: a.events,
+            };
+            (a.core, config)
+        })
+}
+
+/// Reads the aliases only, for expanding the arguments before they are parsed.
+///
+/// A missing or unreadable config file has no aliases, the command reports it if it needs the
+/// config. Other parts of the file are left to the command as well.
+///
+/// # Errors
+/// If the aliases are invalid.
+pub fn load_aliases(location: ConfigLocation) -> Result<Aliases, Box<dyn Error>> {
+    let Ok((path, _)) = resolve_config_path(location) else {
+        return Ok(Aliases::default());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => parse_aliases(&content),
+        Err(e) => {
+            tracing::debug!(path = %path.display(), error = %e, "no aliases loaded");
+            Ok(Aliases::default())
+        }
+    }
+}
+
+/// Resolves the config file to read and the profile it belongs to.
+///
+/// The file given with `--config` comes first, then the profile given with `--profile`, the file
+/// in `AIM_CONFIG`, the profile in `AIM_PROFILE`, and at last the `default_profile` of the config
+/// of the default profile, or that config itself.
+fn resolve_config_path(location: ConfigLocation) -> Result<(PathBuf, Profile), Box<dyn Error>> {
     let dev_mode_strategy = resolve_dev_mode_strategy()?;
+    let profile = location.profile.as_deref().map(str::parse).transpose()?;
 
-    let path = if let Some(path) = path {
+    let path = if let Some(path) = location.path {
         path
+    } else if let Some(profile) = profile {
+        return Ok((profile_config_path(dev_mode_strategy, &profile)?, profile));
     } else if should_use_aim_config_env(dev_mode_strategy) {
         PathBuf::from(std::env::var(AIM_CONFIG_ENV)?)
     } else if matches!(dev_mode_strategy, DevModeStrategy::ForcedNormal) {
-        let config = get_config_dir()?.join(format!("{APP_NAME}/config.toml"));
-        if !config.exists() {
-            return Err(format!("No config found at: {}", config.display()).into());
-        }
-        config
+        return resolve_profile(dev_mode_strategy);
     } else if let Ok(env_path) = std::env::var(AIM_CONFIG_ENV) {
         PathBuf::from(env_path)
     } else {
-        if matches!(effective_dev_mode(dev_mode_strategy), Some(true)) {
-            return Err(format!(
-                "Development environment detected ({AIM_DEV_ENV} is set): config must be explicitly specified via --config or {AIM_CONFIG_ENV} environment variable",
-            ).into());
+        return resolve_profile(dev_mode_strategy);
+    };
+    Ok((path, Profile::default()))
+}
+
+/// The profile the config would be read from, if its config is found.
+pub fn active_profile(location: ConfigLocation) -> Option<Profile> {
+    resolve_config_path(location)
+        .ok()
+        .map(|(_, profile)| profile)
+}
+
+/// Resolves the profile from `AIM_PROFILE` or the `default_profile` of the default config.
+fn resolve_profile(strategy: DevModeStrategy) -> Result<(PathBuf, Profile), Box<dyn Error>> {
+    if let Ok(name) = std::env::var(AIM_PROFILE_ENV)
+        && !name.is_empty()
+    {
+        let profile: Profile = name
+            .parse()
+            .map_err(|e| format!("{AIM_PROFILE_ENV}: {e}"))?;
+        return Ok((profile_config_path(strategy, &profile)?, profile));
+    }
+
+    let path = profile_config_path(strategy, &Profile::default())?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read config file at {}: {}", path.display(), e))?;
+    match parse_default_profile(&content)? {
+        Some(profile) if !profile.is_default() => {
+            Ok((profile_config_path(strategy, &profile)?, profile))
         }
-        // TODO: search config in multiple locations
-        let config = get_config_dir()?.join(format!("{APP_NAME}/config.toml"));
-        if !config.exists() {
+        _ => Ok((path, Profile::default())),
+    }
+}
+
+/// Path of the config file of the profile, which must exist.
+fn profile_config_path(
+    strategy: DevModeStrategy,
+    profile: &Profile,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if matches!(effective_dev_mode(strategy), Some(true)) {
+        return Err(format!(
+            "Development environment detected ({AIM_DEV_ENV} is set): config must be explicitly specified via --config or {AIM_CONFIG_ENV} environment variable",
+        ).into());
+    }
+    let config = profile_dirs()?.config_file(profile);
+    if !config.exists() {
+        if profile.is_default() {
             return Err(format!("No config found at: {}", config.display()).into());
         }
-        config
-    };
+        return Err(format!(
+            "No config found for profile '{profile}' at: {}, create it with `aim profile create {profile}`",
+            config.display()
+        )
+        .into());
+    }
+    Ok(config)
+}
 
-    fs::read_to_string(&path)
-        .await
-        .map_err(|e| format!("Failed to read config file at {}: {}", path.display(), e))?
-        .parse::<ConfigRaw>()
-        .map(|mut a| {
-            a.core.config_dir = path.parent().map(PathBuf::from);
-            a.core.dev_mode = is_dev_mode().unwrap_or(false);
-            (a.core, Config {})
-        })
+/// The directories of the profiles, under the config and state directories of the user.
+pub fn profile_dirs() -> Result<ProfileDirs, Box<dyn Error>> {
+    Ok(ProfileDirs::from_env()?)
+}
+
+/// The `default_profile` key of a config file, other keys are ignored.
+fn parse_default_profile(s: &str) -> Result<Option<Profile>, Box<dyn Error>> {
+    #[derive(Debug, serde::Deserialize)]
+    struct DefaultProfileRaw {
+        #[serde(default)]
+        default_profile: Option<String>,
+    }
+
+    let raw: DefaultProfileRaw = toml::from_str(s)?;
+    let profile = raw
+        .default_profile
+        .map(|name| name.parse().map_err(|e| format!("`default_profile`: {e}")))
+        .transpose()?;
+    Ok(profile)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -70,28 +224,305 @@ enum DevModeStrategy {
 }
 
 /// Configuration for the Aim application.
-#[derive(Debug, Clone, Copy, serde::Deserialize)]
-pub struct Config;
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Path of the configuration file.
+    pub path: PathBuf,
+
+    /// Profile the configuration belongs to.
+    pub profile: Profile,
+
+    /// Backup settings, from the `[backup]` section.
+    pub backup: BackupConfig,
+
+    /// Command aliases, from the `[aliases]` section and the `default_command` key.
+    pub aliases: Aliases,
+
+    /// When to show links in the output as terminal hyperlinks, from the `hyperlinks` key.
+    pub hyperlinks: Hyperlinks,
+
+    /// Settings for listing todos, from the `[todos]` section.
+    pub todos: ListingConfig<TodoColumn>,
+
+    /// Settings for listing events, from the `[events]` section.
+    pub events: ListingConfig<EventColumn>,
+}
+
+/// Settings for listing items in a table.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(try_from = "ListingRaw", bound = "C: Column")]
+pub struct ListingConfig<C> {
+    /// Columns of the table, unless `--columns` is given, the built-in ones if unset.
+    pub columns: Option<Vec<C>>,
+}
+
+impl<C> Default for ListingConfig<C> {
+    fn default() -> Self {
+        Self { columns: None }
+    }
+}
+
+impl<C: Column> TryFrom<ListingRaw> for ListingConfig<C> {
+    type Error = String;
+
+    fn try_from(raw: ListingRaw) -> Result<Self, Self::Error> {
+        let columns = raw
+            .columns
+            .map(|names| parse_columns(&names).map_err(|e| format!("`columns`: {e}")))
+            .transpose()?;
+        Ok(Self { columns })
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListingRaw {
+    columns: Option<Vec<String>>,
+}
+
+/// When links in the output, such as URLs and item IDs, are terminal hyperlinks (OSC 8).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Hyperlinks {
+    /// If stdout is a terminal known to support them.
+    #[default]
+    Auto,
+    /// Always, even when the output is piped.
+    Always,
+    /// Never.
+    Never,
+}
+
+impl Hyperlinks {
+    /// Whether to emit hyperlinks, checking the terminal for [`Hyperlinks::Auto`].
+    #[must_use]
+    pub fn enabled(self) -> bool {
+        match self {
+            Hyperlinks::Auto => terminal_supports_hyperlinks(),
+            Hyperlinks::Always => true,
+            Hyperlinks::Never => false,
+        }
+    }
+}
+
+/// Command aliases, expanded before the arguments are parsed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Aliases {
+    /// Arguments each alias stands for, by alias name.
+    pub commands: BTreeMap<String, Vec<String>>,
+
+    /// Arguments used when `aim` is run without a command, the dashboard if unset.
+    pub default_command: Option<Vec<String>>,
+}
+
+/// Settings for backup archives.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Directory for backup archives, relative to the config file.
+    ///
+    /// Defaults to `backups` in the state directory.
+    pub dir: Option<PathBuf>,
+
+    /// How often to take a backup automatically.
+    pub schedule: BackupSchedule,
+
+    /// Number of archives to keep in the backup directory, older ones are deleted.
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            schedule: BackupSchedule::Off,
+            keep: 7,
+        }
+    }
+}
+
+/// How often a backup is taken automatically after a command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupSchedule {
+    /// Only back up on `aim backup create`.
+    #[default]
+    Off,
+    /// At most once a day.
+    Daily,
+    /// At most once a week.
+    Weekly,
+}
+
+impl BackupSchedule {
+    /// Minimum age of the latest archive before another one is taken.
+    #[must_use]
+    pub fn interval(self) -> Option<Duration> {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            BackupSchedule::Off => None,
+            BackupSchedule::Daily => Some(Duration::from_secs(DAY)),
+            BackupSchedule::Weekly => Some(Duration::from_secs(7 * DAY)),
+        }
+    }
+}
 
 #[derive(Debug, serde::Deserialize)]
 struct ConfigRaw {
     core: CoreConfig,
+
+    #[serde(default)]
+    backup: BackupConfig,
+
+    #[serde(default)]
+    hyperlinks: Hyperlinks,
+
+    #[serde(default)]
+    todos: ListingConfig<TodoColumn>,
+
+    #[serde(default)]
+    events: ListingConfig<EventColumn>,
+
+    /// Parsed on its own by [`parse_aliases`], which needs the source to point at errors.
+    #[serde(skip)]
+    aliases: Aliases,
 }
 
 impl FromStr for ConfigRaw {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(toml::from_str(s)?)
+        let mut raw: Self = toml::from_str(s)?;
+        raw.aliases = parse_aliases(s)?;
+        Ok(raw)
     }
 }
 
-fn get_config_dir() -> Result<PathBuf, Box<dyn Error>> {
-    #[cfg(unix)]
-    let config_dir = xdg::BaseDirectories::new().get_config_home();
-    #[cfg(windows)]
-    let config_dir = dirs::config_dir();
-    config_dir.ok_or_else(|| "User-specific home directory not found".into())
+/// The alias part of the config file, other sections are ignored.
+#[derive(Debug, serde::Deserialize)]
+struct AliasesRaw {
+    #[serde(default)]
+    aliases: BTreeMap<Spanned<String>, Spanned<ArgsRaw>>,
+
+    #[serde(default)]
+    default_command: Option<Spanned<ArgsRaw>>,
+}
+
+/// Arguments given either as a list or as a single string split into words.
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum ArgsRaw {
+    List(Vec<String>),
+    Line(String),
+}
+
+fn parse_aliases(s: &str) -> Result<Aliases, Box<dyn Error>> {
+    let raw: AliasesRaw = toml::from_str(s)?;
+    let builtins = builtin_commands();
+
+    let mut commands = BTreeMap::new();
+    for (name, args) in raw.aliases {
+        let line = line_of(s, name.span().start);
+        let name = name.into_inner();
+        if builtins.contains(&name) {
+            return Err(format!(
+                "alias `{name}` shadows the built-in command of the same name, at line {line}: {}",
+                source_line(s, line),
+            )
+            .into());
+        }
+        let args = split_alias_args(s, args).map_err(|e| format!("alias `{name}` {e}"))?;
+        commands.insert(name, args);
+    }
+
+    let default_command = raw
+        .default_command
+        .map(|args| split_alias_args(s, args).map_err(|e| format!("`default_command` {e}")))
+        .transpose()?;
+
+    Ok(Aliases {
+        commands,
+        default_command,
+    })
+}
+
+/// Names and aliases of the top-level commands, which an alias must not take.
+fn builtin_commands() -> Vec<String> {
+    let command = Cli::command();
+    let mut names = vec!["help".to_string()];
+    for sub in command.get_subcommands() {
+        names.push(sub.get_name().to_string());
+        names.extend(sub.get_all_aliases().map(ToString::to_string));
+    }
+    names
+}
+
+fn split_alias_args(s: &str, args: Spanned<ArgsRaw>) -> Result<Vec<String>, String> {
+    let line = line_of(s, args.span().start);
+    let args = match args.into_inner() {
+        ArgsRaw::List(args) => args,
+        ArgsRaw::Line(line_args) => {
+            split_args(&line_args).map_err(|e| format!("{e}, at line {line}"))?
+        }
+    };
+    if args.is_empty() {
+        return Err(format!("has no arguments, at line {line}"));
+    }
+    Ok(args)
+}
+
+/// Splits a command line into words at whitespace, without any shell expansion.
+///
+/// Single quotes keep their content as is, double quotes allow `\"` and `\\` inside.
+fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(current.take()),
+            '\'' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("has an unterminated quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("has an unterminated quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("has an unterminated quote".to_string()),
+                    }
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Line number, starting at 1, of the byte offset in the source.
+fn line_of(s: &str, offset: usize) -> usize {
+    s.get(..offset).map_or(0, |s| s.matches('\n').count()) + 1
+}
+
+fn source_line(s: &str, line: usize) -> &str {
+    s.lines().nth(line - 1).unwrap_or_default().trim()
 }
 
 fn is_dev_mode() -> Option<bool> {
@@ -103,14 +534,11 @@ fn is_dev_mode() -> Option<bool> {
             Some(false)
         } else {
             tracing::warn!(
-                "Unrecognized value for {}: '{}'. Expected one of: {}. Treating as unset.",
-                AIM_DEV_ENV,
-                val,
-                format!(
-                    "true: {}, false: {}",
-                    AIM_DEV_VALID_TRUE.join(", "),
-                    AIM_DEV_VALID_FALSE.join(", ")
-                )
+                env = AIM_DEV_ENV,
+                value = %val,
+                expected_true = ?AIM_DEV_VALID_TRUE,
+                expected_false = ?AIM_DEV_VALID_FALSE,
+                "unrecognized value of the dev mode variable, treating as unset"
             );
             None
         }
@@ -128,7 +556,13 @@ fn resolve_dev_mode_strategy() -> Result<DevModeStrategy, Box<dyn Error>> {
         return Ok(DevModeStrategy::Environment);
     }
 
-    match prompt_dev_mode_choice()? {
+    let choice = if let Some(choice) = DEV_MODE_CHOICE.get() {
+        *choice
+    } else {
+        let choice = prompt_dev_mode_choice()?;
+        *DEV_MODE_CHOICE.get_or_init(|| choice)
+    };
+    match choice {
         DevModeChoice::Exit => {
             Err("Aborted because AIM_DEV was detected in the environment".into())
         }
@@ -313,6 +747,162 @@ calendar_path = "{}"
         }
     }
 
+    #[test]
+    fn parses_backup_section() {
+        let raw: ConfigRaw = r#"
+[core]
+calendar_path = "calendar"
+
+[backup]
+dir = "backups"
+schedule = "weekly"
+keep = 3
+"#
+        .parse()
+        .unwrap();
+        assert_eq!(raw.backup.dir, Some(PathBuf::from("backups")));
+        assert_eq!(raw.backup.schedule, BackupSchedule::Weekly);
+        assert_eq!(raw.backup.keep, 3);
+
+        let raw: ConfigRaw = "[core]\n".parse().unwrap();
+        assert_eq!(raw.backup, BackupConfig::default());
+        assert_eq!(raw.backup.schedule.interval(), None);
+    }
+
+    #[test]
+    fn parses_hyperlinks() {
+        let raw: ConfigRaw = "hyperlinks = \"never\"\n[core]\n".parse().unwrap();
+        assert_eq!(raw.hyperlinks, Hyperlinks::Never);
+        assert!(!raw.hyperlinks.enabled());
+
+        let raw: ConfigRaw = "hyperlinks = \"always\"\n[core]\n".parse().unwrap();
+        assert!(raw.hyperlinks.enabled());
+
+        let raw: ConfigRaw = "[core]\n".parse().unwrap();
+        assert_eq!(raw.hyperlinks, Hyperlinks::Auto);
+    }
+
+    #[test]
+    fn parses_listing_columns() {
+        let raw: ConfigRaw = r#"
+[core]
+
+[todos]
+columns = ["id", "summary", "due", "categories"]
+
+[events]
+columns = ["time", "summary", "location"]
+"#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            raw.todos.columns,
+            Some(vec![
+                TodoColumn::Id,
+                TodoColumn::Summary,
+                TodoColumn::Due,
+                TodoColumn::Categories
+            ])
+        );
+        assert_eq!(
+            raw.events.columns,
+            Some(vec![
+                EventColumn::TimeSpan,
+                EventColumn::Summary,
+                EventColumn::Location
+            ])
+        );
+
+        let raw: ConfigRaw = "[core]
+"
+        .parse()
+        .unwrap();
+        assert_eq!(raw.todos, ListingConfig::default());
+        assert_eq!(raw.events, ListingConfig::default());
+
+        let err = "[core]
+[todos]
+columns = [\"id\", \"nope\"]
+"
+        .parse::<ConfigRaw>()
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("unknown todo column `nope`"), "{err}");
+        assert!(
+            err.contains("expected one of: status, id, short-id"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parses_aliases() {
+        let raw: ConfigRaw = r#"
+default_command = "w"
+
+[core]
+calendar_path = "calendar"
+
+[aliases]
+tl = ["todo", "list", "--status", "all"]
+w = "tl '+work ops' --verbose"
+"#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            raw.aliases.commands.get("tl").unwrap(),
+            &["todo", "list", "--status", "all"]
+        );
+        assert_eq!(
+            raw.aliases.commands.get("w").unwrap(),
+            &["tl", "+work ops", "--verbose"]
+        );
+        assert_eq!(raw.aliases.default_command, Some(vec!["w".to_string()]));
+
+        let raw: ConfigRaw = "[core]\n".parse().unwrap();
+        assert_eq!(raw.aliases, Aliases::default());
+    }
+
+    #[test]
+    fn rejects_aliases_shadowing_builtin_commands() {
+        let err = "[core]\n\n[aliases]\ntl = \"todo list\"\ntodo = \"todo list\"\n"
+            .parse::<ConfigRaw>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "alias `todo` shadows the built-in command of the same name, at line 5: todo = \"todo list\""
+        );
+
+        let err = "[core]\n[aliases]\nt = [\"todo\", \"list\"]\n"
+            .parse::<ConfigRaw>()
+            .unwrap_err();
+        assert!(err.to_string().contains("at line 3"));
+    }
+
+    #[test]
+    fn rejects_invalid_alias_args() {
+        let err = "[core]\n[aliases]\ntl = []\n"
+            .parse::<ConfigRaw>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "alias `tl` has no arguments, at line 3");
+
+        let err = "[core]\n[aliases]\ntl = \"todo 'list\"\n"
+            .parse::<ConfigRaw>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "alias `tl` has an unterminated quote, at line 3"
+        );
+    }
+
+    #[test]
+    fn splits_args_without_shell_expansion() {
+        assert_eq!(
+            split_args(r#"todo  list "a \"b\"" 'c d' $HOME e\f"#).unwrap(),
+            ["todo", "list", r#"a "b""#, "c d", "$HOME", r"e\f"]
+        );
+        assert_eq!(split_args(" ").unwrap(), Vec::<String>::new());
+    }
+
     // TODO: Re-enable on Windows once get_config_dir() supports environment variables
     #[cfg(unix)]
     #[tokio::test]
@@ -352,6 +942,69 @@ calendar_path = "{}"
         }
     }
 
+    // TODO: Re-enable on Windows once get_config_dir() supports environment variables
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn profiles_keep_separate_configs_and_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_home = temp_dir.path().join("config");
+        let state_home = temp_dir.path().join("state");
+        let dirs = ProfileDirs::new(config_home.join("aim"), state_home.join("aim"));
+        let work: Profile = "work".parse().unwrap();
+        dirs.create(&Profile::default()).unwrap();
+        dirs.create(&work).unwrap();
+        fs::write(
+            dirs.config_file(&work),
+            "[core]\ndefault_priority = \"high\"\n",
+        )
+        .unwrap();
+
+        let _guard = env_lock().lock().await;
+        unsafe {
+            std::env::remove_var(AIM_CONFIG_ENV);
+            std::env::remove_var(AIM_PROFILE_ENV);
+            std::env::remove_var(AIM_DEV_ENV);
+            std::env::set_var("XDG_CONFIG_HOME", &config_home);
+            std::env::set_var("XDG_STATE_HOME", &state_home);
+        }
+
+        let parse = async |location: ConfigLocation| {
+            let (mut core, config) = parse_config(location).await.unwrap();
+            core.normalize().unwrap();
+            (core, config)
+        };
+        let with_profile = |name: &str| ConfigLocation {
+            profile: Some(name.to_string()),
+            ..Default::default()
+        };
+
+        let (default_core, default_config) = parse(ConfigLocation::default()).await;
+        let (work_core, work_config) = parse(with_profile("work")).await;
+        assert!(default_config.profile.is_default());
+        assert_eq!(work_config.profile, work);
+        assert_eq!(work_config.path, dirs.config_file(&work));
+        assert_ne!(default_core.default_priority, work_core.default_priority);
+        assert_eq!(default_core.state_dir, Some(state_home.join("aim")));
+        assert_eq!(work_core.state_dir, Some(dirs.state_dir(&work)));
+
+        unsafe { std::env::set_var(AIM_PROFILE_ENV, "work") };
+        assert_eq!(parse(ConfigLocation::default()).await.1.profile, work);
+        unsafe { std::env::remove_var(AIM_PROFILE_ENV) };
+
+        fs::write(
+            dirs.config_file(&Profile::default()),
+            "default_profile = \"work\"\n\n[core]\n",
+        )
+        .unwrap();
+        assert_eq!(parse(ConfigLocation::default()).await.1.profile, work);
+        assert!(parse_config(with_profile("home")).await.is_err());
+
+        unsafe {
+            std::env::remove_var("XDG_CONFIG_HOME");
+            std::env::remove_var("XDG_STATE_HOME");
+        }
+    }
+
     #[tokio::test]
     async fn returns_error_when_no_config_found() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,166 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::fmt;
+
+use aimcal_core::{ItemDetails, LooseDateTime};
+use jiff::Zoned;
+
+use crate::table::{hyperlink, hyperlinks_enabled};
+use crate::util::format_datetime;
+
+/// Labeled rows of a detail view, printed with the values aligned.
+#[derive(Debug, Clone, Default)]
+pub struct DetailRows<'a> {
+    rows: Vec<(&'static str, Cow<'a, str>)>,
+    hyperlinks: bool,
+}
+
+impl<'a> DetailRows<'a> {
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            hyperlinks: hyperlinks_enabled(),
+        }
+    }
+
+    pub fn push(&mut self, key: &'static str, value: impl Into<Cow<'a, str>>) {
+        self.rows.push((key, value.into()));
+    }
+
+    /// Pushes a row only if there is a value.
+    pub fn push_opt(&mut self, key: &'static str, value: Option<impl Into<Cow<'a, str>>>) {
+        if let Some(value) = value {
+            self.push(key, value);
+        }
+    }
+
+    /// Pushes one row per value, labeling the first one only.
+    pub fn push_all(&mut self, key: &'static str, values: &'a [String]) {
+        for (i, value) in values.iter().enumerate() {
+            self.push(if i == 0 { key } else { "" }, value.as_str());
+        }
+    }
+
+    /// Pushes one row per URL, as hyperlinks if enabled, labeling the first one only.
+    ///
+    /// Values without a scheme, such as a `CalDAV` href, are not links on their own.
+    pub fn push_links(&mut self, key: &'static str, urls: &'a [String]) {
+        for (i, url) in urls.iter().enumerate() {
+            let key = if i == 0 { key } else { "" };
+            if self.hyperlinks && url.contains("://") {
+                self.push(key, hyperlink(url, url));
+            } else {
+                self.push(key, url.as_str());
+            }
+        }
+    }
+
+    pub fn push_datetime(&mut self, key: &'static str, value: Option<Zoned>) {
+        self.push_opt(key, value.map(|t| format_datetime(LooseDateTime::Local(t))));
+    }
+
+    /// Pushes the properties read from the store, leaving out the ones that are unset.
+    pub fn push_item_details(&mut self, details: &'a ItemDetails) {
+        if !details.categories.is_empty() {
+            self.push("Categories", details.categories.join(", "));
+        }
+        self.push_opt("Location", details.location.as_deref());
+        self.push_links("URL", details.url.as_slice());
+        self.push_opt("Organizer", details.organizer.as_deref());
+        self.push_opt("Recurrence", details.recurrence.as_deref());
+        self.push_opt("Split From", details.split_from.as_deref());
+        self.push_all("Alarms", &details.alarms);
+        self.push_all("Attendees", &details.attendees);
+        self.push_links("Attachments", &details.attachments);
+        self.push_links("Conferences", &details.conferences);
+        for (i, (key, value)) in details.meta.iter().enumerate() {
+            self.push(
+                if i == 0 { "Metadata" } else { "" },
+                format!("{key}: {value}"),
+            );
+        }
+        self.push_datetime("Created", details.created.clone());
+        self.push_datetime("Last Modified", details.last_modified.clone());
+    }
+
+    /// Pushes where the item is stored.
+    pub fn push_item_location(&mut self, details: &'a ItemDetails) {
+        self.push("Calendar", details.calendar_id.as_str());
+        self.push_links("Resource", details.resource_id.as_slice());
+    }
+}
+
+impl fmt::Display for DetailRows<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .rows
+            .iter()
+            .map(|(key, _)| key.len())
+            .max()
+            .unwrap_or(0);
+        for (idx, (key, value)) in self.rows.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            if key.is_empty() {
+                write!(f, "{key:width$}  {value}")?;
+            } else {
+                write!(f, "{key:width$}: {value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligns_rows_and_continues_lists() {
+        let alarms = vec!["display at start".to_string(), "email at end".to_string()];
+        let mut rows = DetailRows::new();
+        rows.push("ID", "1");
+        rows.push_opt("Location", None::<&str>);
+        rows.push_all("Alarms", &alarms);
+        rows.push("Last Modified", "2026-01-01 09:00");
+
+        assert_eq!(
+            rows.to_string(),
+            "\
+ID           : 1
+Alarms       : display at start
+               email at end
+Last Modified: 2026-01-01 09:00"
+        );
+    }
+
+    #[test]
+    fn links_urls_with_a_scheme() {
+        let urls = vec![
+            "https://example.com/agenda.pdf".to_string(),
+            "/dav/calendars/work/1.ics".to_string(),
+        ];
+        let mut rows = DetailRows::new();
+        rows.hyperlinks = true;
+        rows.push_links("Attachments", &urls);
+        assert_eq!(
+            rows.to_string(),
+            format!(
+                "Attachments: {}\n             /dav/calendars/work/1.ics",
+                hyperlink(
+                    "https://example.com/agenda.pdf",
+                    "https://example.com/agenda.pdf"
+                )
+            )
+        );
+
+        let mut rows = DetailRows::new();
+        rows.hyperlinks = false;
+        rows.push_links("URL", urls.split_at(1).0);
+        assert_eq!(rows.to_string(), "URL: https://example.com/agenda.pdf");
+    }
+}
@@ -4,29 +4,44 @@
 
 use std::{borrow::Cow, fmt};
 
-use aimcal_core::{Event, LooseDateTime, RangePosition};
+use aimcal_core::{Event, Kind, LooseDateTime, RangePosition};
 use colored::Color;
 use jiff::{Zoned, civil::Date};
 
-use crate::table::{PaddingDirection, Table, TableColumn, TableStyleBasic, TableStyleJson};
-use crate::util::{OutputFormat, format_datetime};
+use crate::column::{Column, ColumnData, ColumnDef};
+use crate::table::ColumnPriority::{Essential, Optional};
+use crate::table::ColumnWidth::{Fixed, Flexible};
+use crate::table::PaddingDirection::{Left, Right};
+use crate::table::{
+    ColumnPriority, ColumnWidth, PaddingDirection, Table, TableColumn, TableStyleBasic,
+    TableStyleJson,
+};
+use crate::util::{OutputFormat, format_datetime, item_url};
 
 #[derive(Debug, Clone)]
 pub struct EventFormatter {
     now: Zoned,
     columns: Vec<EventColumn>,
     format: OutputFormat,
+    date: Date,
 }
 
 impl EventFormatter {
     pub fn new(now: Zoned, columns: Vec<EventColumn>, format: OutputFormat) -> Self {
         Self {
+            date: now.date(),
             now,
             columns,
             format,
         }
     }
 
+    /// Shows the times of events relative to `date` in [`EventColumn::TimeSpan`], today if unset.
+    pub fn on(mut self, date: Date) -> Self {
+        self.date = date;
+        self
+    }
+
     pub fn format<'a, E: Event>(&'a self, events: &'a [E]) -> Display<'a, E> {
         Display {
             events,
@@ -50,6 +65,7 @@ impl<E: Event> fmt::Display for Display<'_, E> {
             .map(|column| ColumnMeta {
                 column,
                 now: self.formatter.now.clone(),
+                date: self.formatter.date,
             })
             .collect();
 
@@ -66,57 +82,104 @@ impl<E: Event> fmt::Display for Display<'_, E> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventColumn {
+    Calendars,
+    Categories,
     DateTimeSpan,
+    Description,
     Id,
+    Location,
     ShortId,
     Summary,
-    TimeSpan { date: Date },
+    TimeSpan,
     Uid,
+    Url,
+}
+
+impl Column for EventColumn {
+    const KIND: &'static str = "event";
+
+    // In a narrow terminal, the optional columns drop out in the order of their rank: the
+    // description, categories, UID, URL, location, calendars and short ID, see `plan_layout`
+    #[rustfmt::skip]
+    const DEFS: &'static [ColumnDef<Self>] = &[
+        ColumnDef::new("id",          "ID",          Self::Id,           Right, Fixed,            Essential,   ColumnData::None),
+        ColumnDef::new("short-id",    "Short ID",    Self::ShortId,      Left,  Fixed,            Optional(6), ColumnData::None),
+        ColumnDef::new("uid",         "UID",         Self::Uid,          Right, Fixed,            Optional(2), ColumnData::None),
+        ColumnDef::new("datetime",    "Date Time",   Self::DateTimeSpan, Left,  Fixed,            Essential,   ColumnData::None),
+        ColumnDef::new("time",        "Time",        Self::TimeSpan,     Left,  Fixed,            Essential,   ColumnData::None),
+        ColumnDef::new("summary",     "Summary",     Self::Summary,      Left,  Flexible(20, 40), Essential,   ColumnData::None),
+        ColumnDef::new("description", "Description", Self::Description,  Left,  Flexible(10, 30), Optional(0), ColumnData::None),
+        ColumnDef::new("location",    "Location",    Self::Location,     Left,  Flexible(10, 20), Optional(4), ColumnData::None),
+        ColumnDef::new("categories",  "Categories",  Self::Categories,   Left,  Flexible(10, 20), Optional(1), ColumnData::None),
+        ColumnDef::new("calendars",   "Calendars",   Self::Calendars,    Left,  Fixed,            Optional(5), ColumnData::None),
+        ColumnDef::new("url",         "URL",         Self::Url,          Left,  Flexible(10, 30), Optional(3), ColumnData::None),
+    ];
 }
 
 #[derive(Debug, Clone)]
 struct ColumnMeta<'a> {
     column: &'a EventColumn,
     now: Zoned,
+    date: Date,
 }
 
 impl<E: Event> TableColumn<E> for ColumnMeta<'_> {
     fn name(&self) -> Cow<'_, str> {
-        match self.column {
-            EventColumn::DateTimeSpan => "Date Time",
-            EventColumn::Id => "ID",
-            EventColumn::ShortId => "Short ID",
-            EventColumn::Summary => "Summary",
-            EventColumn::TimeSpan { date: _ } => "Time",
-            EventColumn::Uid => "UID",
-        }
-        .into()
+        self.column.def().header.into()
     }
 
     fn format<'b>(&self, data: &'b E) -> Cow<'b, str> {
         match self.column {
+            EventColumn::Calendars => format_calendars(data),
+            EventColumn::Categories => data.categories().join(", ").into(),
             EventColumn::DateTimeSpan => format_datetime_span(data),
+            EventColumn::Description => format_description(data),
             EventColumn::Id => format_id(data),
+            EventColumn::Location => data.location().unwrap_or_default(),
             EventColumn::ShortId => format_short_id(data),
             EventColumn::Summary => format_summary(data),
-            EventColumn::TimeSpan { date } => format_time_span(data, *date),
+            EventColumn::TimeSpan => format_time_span(data, self.date),
             EventColumn::Uid => format_uid(data),
+            EventColumn::Url => data.url().unwrap_or_default(),
         }
     }
 
     fn padding_direction(&self) -> PaddingDirection {
+        self.column.def().alignment
+    }
+
+    fn width(&self) -> ColumnWidth {
+        self.column.def().width
+    }
+
+    fn priority(&self) -> ColumnPriority {
+        self.column.def().priority
+    }
+
+    fn compact<'b>(&self, data: &'b E) -> Option<Cow<'b, str>> {
         match self.column {
-            EventColumn::Id | EventColumn::Uid => PaddingDirection::Right,
-            _ => PaddingDirection::Left,
+            // Relative to the day listed, as the time column
+            EventColumn::DateTimeSpan => Some(format_time_span(data, self.date)),
+            _ => None,
         }
     }
 
     fn get_color(&self, data: &E) -> Option<Color> {
         match &self.column {
             EventColumn::DateTimeSpan => get_color_datetime_span(data, &self.now),
-            EventColumn::TimeSpan { date: _ } => get_color_time_span(data, &self.now),
+            EventColumn::TimeSpan => get_color_time_span(data, &self.now),
+            _ => None,
+        }
+    }
+
+    fn get_link(&self, data: &E) -> Option<String> {
+        match self.column {
+            EventColumn::Id | EventColumn::ShortId | EventColumn::Uid => {
+                Some(item_url(Kind::Event, &data.uid()))
+            }
+            EventColumn::Url => data.url().map(Cow::into_owned),
             _ => None,
         }
     }
@@ -143,11 +206,47 @@ fn format_short_id(event: &impl Event) -> Cow<'_, str> {
         .into()
 }
 
+fn format_calendars(event: &impl Event) -> Cow<'_, str> {
+    event
+        .calendar_id()
+        .into_iter()
+        .chain(event.mirrored_in())
+        .map(|id| format!("[{id}]"))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+/// Whether any of `events` has copies in other calendars, worth a [`EventColumn::Calendars`].
+pub fn any_mirrored(events: &[impl Event]) -> bool {
+    events.iter().any(|event| !event.mirrored_in().is_empty())
+}
+
 fn format_summary(event: &impl Event) -> Cow<'_, str> {
     event.summary().replace('\n', "↵").into()
 }
 
+fn format_description(event: &impl Event) -> Cow<'_, str> {
+    event
+        .description()
+        .map(|a| a.replace('\n', "↵").into())
+        .unwrap_or_default()
+}
+
 fn format_datetime_span(event: &impl Event) -> Cow<'_, str> {
+    if let Some((first, last)) = all_day_span(event) {
+        return if first == last {
+            format!("{} All day", first.strftime("%Y-%m-%d"))
+        } else {
+            format!(
+                "{}~{}",
+                first.strftime("%Y-%m-%d"),
+                last.strftime("%Y-%m-%d")
+            )
+        }
+        .into();
+    }
+
     match (event.start(), event.end()) {
         (Some(start), Some(end)) => {
             if start.date() == end.date() {
@@ -177,7 +276,8 @@ fn format_datetime_span(event: &impl Event) -> Cow<'_, str> {
         .into(),
         (Some(start), None) => format_datetime(start).into(),
         (None, Some(end)) => format!("~{}", format_datetime(end)).into(),
-        (None, None) => String::new().into(),
+        // An event without a start is not on the calendar, but still listed
+        (None, None) => "unscheduled".into(),
     }
 }
 
@@ -187,10 +287,17 @@ fn get_color_datetime_span(event: &impl Event, now: &Zoned) -> Option<Color> {
     const COLOR_PAST: Option<Color> = Some(Color::BrightBlack);
 
     let start = event.start()?;
+    let end = match all_day_span(event) {
+        Some((first, last)) if first <= now.date() && now.date() <= last => {
+            return COLOR_CURRENT;
+        }
+        Some(_) => return None,
+        None => event.end(),
+    };
     if start.date() != now.date() {
         return None;
     }
-    match LooseDateTime::position_in_range(&now.datetime(), &Some(start), &event.end()) {
+    match LooseDateTime::position_in_range(&now.datetime(), &Some(start), &end) {
         RangePosition::Before => COLOR_TODAY_LATE,
         RangePosition::InRange => COLOR_CURRENT,
         RangePosition::After => COLOR_PAST,
@@ -206,6 +313,21 @@ fn format_time_span(event: &impl Event, date: Date) -> Cow<'_, str> {
         d.strftime("%Y-%m-%d").to_string()
     }
 
+    if let Some((first, last)) = all_day_span(event) {
+        return if last < date {
+            format!("⇥{}", format_date(last))
+        } else if first > date {
+            format!("{}↦", format_date(first))
+        } else if first == last {
+            "All day".to_string()
+        } else if first.year() == date.year() && last.year() == date.year() {
+            format!("{}~{}", first.strftime("%m-%d"), last.strftime("%m-%d"))
+        } else {
+            format!("⇸{}", format_date(last))
+        }
+        .into();
+    }
+
     match (event.start(), event.end()) {
         (Some(start), Some(end)) => {
             let sdate = start.date();
@@ -248,8 +370,16 @@ fn format_time_span(event: &impl Event, date: Date) -> Cow<'_, str> {
         .into(),
         (Some(start), None) => format_datetime(start).into(),
         (None, Some(end)) => format!("↦{}", format_datetime(end)).into(),
-        (None, None) => String::new().into(),
+        (None, None) => "unscheduled".into(),
+    }
+}
+
+/// The first and last day of an all-day event, its end date being exclusive.
+fn all_day_span(event: &impl Event) -> Option<(Date, Date)> {
+    if !event.is_all_day() {
+        return None;
     }
+    Some((event.start()?.date(), event.last_day()?))
 }
 
 fn get_color_time_span(event: &impl Event, now: &Zoned) -> Option<Color> {
@@ -259,3 +389,95 @@ fn get_color_time_span(event: &impl Event, now: &Zoned) -> Option<Color> {
 fn format_uid(event: &impl Event) -> Cow<'_, str> {
     event.uid()
 }
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::EventStatus;
+    use jiff::civil::date;
+
+    use super::*;
+
+    struct AllDay(Date, Date);
+
+    impl Event for AllDay {
+        fn uid(&self) -> Cow<'_, str> {
+            "holiday".into()
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn start(&self) -> Option<LooseDateTime> {
+            Some(LooseDateTime::DateOnly(self.0))
+        }
+
+        fn end(&self) -> Option<LooseDateTime> {
+            Some(LooseDateTime::DateOnly(self.1))
+        }
+
+        fn status(&self) -> Option<EventStatus> {
+            None
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            "Holiday".into()
+        }
+    }
+
+    #[test]
+    fn formats_all_day_events_with_an_exclusive_end() {
+        let one_day = AllDay(date(2025, 6, 10), date(2025, 6, 11));
+        let three_days = AllDay(date(2025, 6, 10), date(2025, 6, 13));
+
+        assert_eq!(format_datetime_span(&one_day), "2025-06-10 All day");
+        assert_eq!(format_datetime_span(&three_days), "2025-06-10~2025-06-12");
+
+        assert_eq!(format_time_span(&one_day, date(2025, 6, 10)), "All day");
+        assert_eq!(format_time_span(&one_day, date(2025, 6, 11)), "⇥2025-06-10");
+        assert_eq!(
+            format_time_span(&three_days, date(2025, 6, 12)),
+            "06-10~06-12"
+        );
+        assert_eq!(
+            format_time_span(&three_days, date(2025, 6, 9)),
+            "2025-06-10↦"
+        );
+    }
+    #[test]
+    fn formats_events_without_a_start_as_unscheduled() {
+        struct Unscheduled;
+
+        impl Event for Unscheduled {
+            fn uid(&self) -> Cow<'_, str> {
+                "unscheduled".into()
+            }
+
+            fn description(&self) -> Option<Cow<'_, str>> {
+                None
+            }
+
+            fn start(&self) -> Option<LooseDateTime> {
+                None
+            }
+
+            fn end(&self) -> Option<LooseDateTime> {
+                None
+            }
+
+            fn status(&self) -> Option<EventStatus> {
+                None
+            }
+
+            fn summary(&self) -> Cow<'_, str> {
+                "Someday".into()
+            }
+        }
+
+        assert_eq!(format_datetime_span(&Unscheduled), "unscheduled");
+        assert_eq!(
+            format_time_span(&Unscheduled, date(2025, 6, 10)),
+            "unscheduled"
+        );
+    }
+}
@@ -21,19 +21,47 @@
 
 mod arg;
 mod cli;
+mod cmd_alias;
+mod cmd_backup;
 mod cmd_calendar;
+mod cmd_checkin;
+mod cmd_config;
+mod cmd_crypt;
 mod cmd_event;
 mod cmd_generate_completion;
+mod cmd_get;
+mod cmd_housekeeping;
+mod cmd_list;
+mod cmd_md;
+mod cmd_open;
+mod cmd_plan;
+mod cmd_profile;
+mod cmd_review;
+mod cmd_rules;
+mod cmd_template;
 mod cmd_todo;
 mod cmd_toplevel;
 mod cmd_tui;
+mod cmd_tz;
+mod column;
 mod config;
+mod details_formatter;
 mod event_formatter;
+mod logging;
+mod porcelain;
 mod prompt;
+mod raw_formatter;
+mod resolve;
 mod table;
+mod timeline;
 mod todo_formatter;
+mod todo_group;
+mod todo_tree;
 mod tui;
 mod util;
 
-pub use crate::cli::{Cli, Commands, run};
-pub use crate::config::Config;
+pub use crate::cli::{
+    Cli, Commands, EXIT_AMBIGUOUS_ID, EXIT_BACKEND, EXIT_CONFIG, EXIT_CONFLICT, EXIT_FAILURE,
+    EXIT_INVALID_INPUT, EXIT_NOT_FOUND, EXIT_PARSE, EXIT_USAGE, exit_code, run,
+};
+pub use crate::config::{Aliases, BackupConfig, BackupSchedule, Config};
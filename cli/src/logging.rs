@@ -0,0 +1,249 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Logs of the CLI, to stderr and optionally to a file of JSON lines.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use jiff::Timestamp;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::{LookupSpan, Scope};
+use tracing_subscriber::{EnvFilter, Layer, Registry, prelude::*};
+
+/// Targets of the aim crates, whose verbosity `-v` raises.
+const AIM_TARGETS: [&str; 5] = [
+    "aim",
+    "aimcal_cli",
+    "aimcal_core",
+    "aimcal_caldav",
+    "aimcal_ical",
+];
+
+/// Sets up the logs, with the verbosity of `-v` given `verbose` times and the JSON lines log
+/// file of `--log-file`, and logs panics with the spans they happen in.
+///
+/// Without `-v`, stderr gets the errors, or what `RUST_LOG` asks for. The log file gets the aim
+/// crates at the level of `-v`, at least info, and the dependencies at warn.
+///
+/// # Errors
+/// If the log file cannot be opened, or the logs are already set up.
+pub fn init_tracing(verbose: u8, log_file: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    // Logs go to stderr, keeping stdout to the output of the commands
+    let stderr_filter = match verbose_directives(verbose) {
+        Some(directives) => EnvFilter::new(directives),
+        None => EnvFilter::builder()
+            .with_default_directive(LevelFilter::ERROR.into())
+            .from_env_lossy(),
+    };
+    let stderr_log = tracing_subscriber::fmt::layer()
+        .pretty()
+        .with_writer(std::io::stderr)
+        .with_filter(stderr_filter);
+
+    let file_log = match log_file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Failed to open log file {}: {e}", path.display()))?;
+            let directives = verbose_directives(verbose.max(1)).unwrap_or_default();
+            Some(JsonLines::new(file).with_filter(EnvFilter::new(directives)))
+        }
+        None => None,
+    };
+
+    let subscriber = Registry::default().with(stderr_log).with(file_log);
+    tracing::subscriber::set_global_default(subscriber)?;
+    set_panic_hook();
+    Ok(())
+}
+
+/// The filter directives for `-v` given `verbose` times: info, debug and then trace for the aim
+/// crates, leaving the dependencies at warn.
+fn verbose_directives(verbose: u8) -> Option<String> {
+    let level = match verbose {
+        0 => return None,
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let mut directives = vec!["warn".to_string()];
+    directives.extend(AIM_TARGETS.iter().map(|target| format!("{target}={level}")));
+    Some(directives.join(","))
+}
+
+/// Logs a panic as an error before the default hook reports it, so the log has the spans the
+/// panic happened in.
+fn set_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(panic = %info, "aim panicked");
+        default_hook(info);
+    }));
+}
+
+/// A layer writing each event as a line of JSON, with the fields of the spans it happened in,
+/// outermost first.
+struct JsonLines {
+    file: Mutex<File>,
+}
+
+impl JsonLines {
+    fn new(file: File) -> Self {
+        Self {
+            file: Mutex::new(file),
+        }
+    }
+}
+
+impl<S> Layer<S> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(fields) = span.extensions_mut().get_mut::<Fields>()
+        {
+            values.record(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let spans: Vec<Value> = ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(Scope::from_root)
+            .map(|span| {
+                let mut entry = Map::new();
+                entry.insert("name".to_string(), span.name().into());
+                if let Some(fields) = span.extensions().get::<Fields>() {
+                    entry.extend(fields.0.clone());
+                }
+                Value::Object(entry)
+            })
+            .collect();
+
+        let metadata = event.metadata();
+        let line = serde_json::json!({
+            "timestamp": Timestamp::now().to_string(),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "fields": Value::Object(fields.0),
+            "spans": spans,
+        });
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        // Failing to log must not fail the command
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// The fields of a span or an event, as JSON values.
+#[derive(Debug, Default)]
+struct Fields(Map<String, Value>);
+
+impl Fields {
+    fn insert(&mut self, field: &Field, value: impl Into<Value>) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value);
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        self.insert(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raises_the_aim_crates_only() {
+        assert_eq!(verbose_directives(0), None);
+        assert_eq!(
+            verbose_directives(1).unwrap(),
+            "warn,aim=info,aimcal_cli=info,aimcal_core=info,aimcal_caldav=info,aimcal_ical=info"
+        );
+        assert!(verbose_directives(2).unwrap().contains("aimcal_core=debug"));
+        assert!(
+            verbose_directives(3)
+                .unwrap()
+                .contains("aimcal_caldav=trace")
+        );
+        assert!(
+            verbose_directives(9)
+                .unwrap()
+                .contains("aimcal_caldav=trace")
+        );
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn records_fields_as_json() {
+        let path = std::env::temp_dir().join(format!("aim-log-{}.jsonl", std::process::id()));
+        let file = File::create(&path).unwrap();
+        let subscriber = Registry::default().with(JsonLines::new(file));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("sync", calendar_id = "work");
+            let _guard = span.enter();
+            tracing::warn!(uid = "abc", count = 3, ok = true, "synced");
+        });
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let line: Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["fields"]["message"], "synced");
+        assert_eq!(line["fields"]["uid"], "abc");
+        assert_eq!(line["fields"]["count"], 3);
+        assert_eq!(line["fields"]["ok"], true);
+        assert_eq!(line["spans"][0]["name"], "sync");
+        assert_eq!(line["spans"][0]["calendar_id"], "work");
+    }
+}
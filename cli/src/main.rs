@@ -3,10 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::error::Error;
+use std::process::ExitCode;
 
 use aimcal_cli::run;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> Result<ExitCode, Box<dyn Error>> {
     run().await
 }
@@ -0,0 +1,334 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Line-oriented output for scripts, printed by `--porcelain`.
+//!
+//! Unlike the table and JSON output, a version of this format never changes once released: a
+//! change in fields or encoding becomes a new version, so scripts asking for `--porcelain=v1`
+//! keep working. Nothing here is shared with the human table, which is free to change its
+//! truncation, styling and columns.
+//!
+//! # Version 1
+//!
+//! One record per item, its fields separated by a TAB in this order:
+//!
+//! 1. kind, `event` or `todo`
+//! 2. short id, empty if there is none
+//! 3. uid
+//! 4. status, the iCalendar keyword such as `NEEDS-ACTION` or `CONFIRMED`, empty if unset
+//! 5. priority, `0` (none) to `9` for todos, empty for events
+//! 6. date, the start of events and the due of todos, empty if unset, as `2025-01-02`,
+//!    `2025-01-02T15:04:05` (floating) or `2025-01-02T15:04:05+08:00`
+//! 7. summary
+//!
+//! Records end with a LF, and a backslash, TAB, LF or CR in a field is written as `\\`, `\t`,
+//! `\n` or `\r`. With `-z`, records end with a NUL instead and the summary, being the last field,
+//! is written verbatim, so it may contain any of these. Other fields are escaped either way.
+
+use std::io::{self, Write};
+
+use aimcal_core::{Event, LooseDateTime, Todo};
+
+/// The version of the porcelain format, see the module documentation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PorcelainVersion {
+    /// Kind, short id, uid, status, priority, date and summary.
+    #[default]
+    V1,
+}
+
+/// How to print records with `--porcelain`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Porcelain {
+    pub version: PorcelainVersion,
+    /// Whether records end with a NUL instead of a LF, see `-z`.
+    pub nul_terminated: bool,
+}
+
+impl Porcelain {
+    /// Writes the records of events.
+    pub fn write_events(self, w: &mut impl Write, events: &[impl Event]) -> io::Result<()> {
+        for event in events {
+            let record = Record {
+                kind: "event",
+                short_id: event.short_id().map(|id| id.to_string()),
+                uid: &event.uid(),
+                status: event.status().map(|s| s.to_string()),
+                priority: None,
+                date: event.start(),
+                summary: &event.summary(),
+            };
+            self.write_record(w, &record)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the records of todos.
+    pub fn write_todos(self, w: &mut impl Write, todos: &[impl Todo]) -> io::Result<()> {
+        for todo in todos {
+            let record = Record {
+                kind: "todo",
+                short_id: todo.short_id().map(|id| id.to_string()),
+                uid: &todo.uid(),
+                status: Some(todo.status().to_string()),
+                priority: Some(todo.priority().into()),
+                date: todo.due(),
+                summary: &todo.summary(),
+            };
+            self.write_record(w, &record)?;
+        }
+        Ok(())
+    }
+
+    fn write_record(self, w: &mut impl Write, record: &Record<'_>) -> io::Result<()> {
+        match self.version {
+            PorcelainVersion::V1 => {
+                let fields = [
+                    record.kind,
+                    record.short_id.as_deref().unwrap_or_default(),
+                    record.uid,
+                    record.status.as_deref().unwrap_or_default(),
+                    &record.priority.map(|p| p.to_string()).unwrap_or_default(),
+                    &record.date.as_ref().map(format_date).unwrap_or_default(),
+                ];
+                for field in fields {
+                    write_escaped(w, field)?;
+                    w.write_all(b"\t")?;
+                }
+                if self.nul_terminated {
+                    w.write_all(record.summary.as_bytes())?;
+                    w.write_all(b"\0")
+                } else {
+                    write_escaped(w, record.summary)?;
+                    w.write_all(b"\n")
+                }
+            }
+        }
+    }
+}
+
+/// The fields of a record, in the order of version 1.
+#[derive(Debug)]
+struct Record<'a> {
+    kind: &'static str,
+    short_id: Option<String>,
+    uid: &'a str,
+    status: Option<String>,
+    priority: Option<u8>,
+    date: Option<LooseDateTime>,
+    summary: &'a str,
+}
+
+fn format_date(date: &LooseDateTime) -> String {
+    match date {
+        LooseDateTime::DateOnly(d) => d.strftime("%Y-%m-%d").to_string(),
+        LooseDateTime::Floating(dt) => dt.strftime("%Y-%m-%dT%H:%M:%S").to_string(),
+        LooseDateTime::Local(zoned) => zoned.strftime("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+    }
+}
+
+fn write_escaped(w: &mut impl Write, field: &str) -> io::Result<()> {
+    let mut rest = field;
+    while let Some(i) = rest.find(['\\', '\t', '\n', '\r']) {
+        let (plain, special) = rest.split_at(i);
+        w.write_all(plain.as_bytes())?;
+        let escaped: &[u8] = match special.as_bytes().first() {
+            Some(b'\\') => b"\\\\",
+            Some(b'\t') => b"\\t",
+            Some(b'\n') => b"\\n",
+            _ => b"\\r",
+        };
+        w.write_all(escaped)?;
+        rest = special.get(1..).unwrap_or_default();
+    }
+    w.write_all(rest.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::num::NonZeroU32;
+
+    use aimcal_core::{EventStatus, Priority, TodoStatus};
+    use jiff::Zoned;
+    use jiff::civil::date;
+
+    use super::*;
+
+    struct TestEvent {
+        short_id: Option<u32>,
+        uid: &'static str,
+        status: Option<EventStatus>,
+        start: Option<LooseDateTime>,
+        summary: &'static str,
+    }
+
+    impl Event for TestEvent {
+        fn short_id(&self) -> Option<NonZeroU32> {
+            self.short_id.and_then(NonZeroU32::new)
+        }
+
+        fn uid(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn start(&self) -> Option<LooseDateTime> {
+            self.start.clone()
+        }
+
+        fn end(&self) -> Option<LooseDateTime> {
+            None
+        }
+
+        fn status(&self) -> Option<EventStatus> {
+            self.status
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.summary.into()
+        }
+    }
+
+    struct TestTodo {
+        short_id: Option<u32>,
+        uid: &'static str,
+        status: TodoStatus,
+        priority: Priority,
+        due: Option<LooseDateTime>,
+        summary: &'static str,
+    }
+
+    impl Todo for TestTodo {
+        fn short_id(&self) -> Option<NonZeroU32> {
+            self.short_id.and_then(NonZeroU32::new)
+        }
+
+        fn uid(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn completed(&self) -> Option<Zoned> {
+            None
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn due(&self) -> Option<LooseDateTime> {
+            self.due.clone()
+        }
+
+        fn percent_complete(&self) -> Option<u8> {
+            None
+        }
+
+        fn priority(&self) -> Priority {
+            self.priority
+        }
+
+        fn status(&self) -> TodoStatus {
+            self.status
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.summary.into()
+        }
+    }
+
+    fn fixture_events() -> Vec<TestEvent> {
+        let start = date(2025, 1, 2).at(15, 4, 5, 0);
+        vec![
+            TestEvent {
+                short_id: Some(1),
+                uid: "event-1@example.com",
+                status: Some(EventStatus::Confirmed),
+                start: Some(LooseDateTime::Local(start.in_tz("Asia/Shanghai").unwrap())),
+                summary: "Standup",
+            },
+            TestEvent {
+                short_id: None,
+                uid: "event\t2",
+                status: None,
+                start: Some(LooseDateTime::DateOnly(date(2025, 1, 3))),
+                summary: "Offsite\tday 1\nday 2",
+            },
+        ]
+    }
+
+    fn fixture_todos() -> Vec<TestTodo> {
+        vec![
+            TestTodo {
+                short_id: Some(3),
+                uid: "todo-3@example.com",
+                status: TodoStatus::NeedsAction,
+                priority: Priority::P2,
+                due: Some(LooseDateTime::Floating(date(2025, 1, 4).at(9, 0, 0, 0))),
+                summary: r"Back up C:\Users",
+            },
+            TestTodo {
+                short_id: Some(4),
+                uid: "todo-4@example.com",
+                status: TodoStatus::Completed,
+                priority: Priority::None,
+                due: None,
+                summary: "Line 1\r\nLine 2",
+            },
+        ]
+    }
+
+    fn output(porcelain: Porcelain) -> Vec<u8> {
+        let mut out = Vec::new();
+        porcelain.write_events(&mut out, &fixture_events()).unwrap();
+        porcelain.write_todos(&mut out, &fixture_todos()).unwrap();
+        out
+    }
+
+    // These lock the bytes of version 1, which must never change: add a new version instead.
+    #[test]
+    fn porcelain_v1_output_is_stable() {
+        let porcelain = Porcelain {
+            version: PorcelainVersion::V1,
+            nul_terminated: false,
+        };
+        let expected: &[u8] = b"event\t1\tevent-1@example.com\tCONFIRMED\t\t2025-01-02T15:04:05+08:00\tStandup\n\
+            event\t\tevent\\t2\t\t\t2025-01-03\tOffsite\\tday 1\\nday 2\n\
+            todo\t3\ttodo-3@example.com\tNEEDS-ACTION\t2\t2025-01-04T09:00:00\tBack up C:\\\\Users\n\
+            todo\t4\ttodo-4@example.com\tCOMPLETED\t0\t\tLine 1\\r\\nLine 2\n";
+        assert_eq!(output(porcelain), expected);
+    }
+
+    #[test]
+    fn porcelain_v1_nul_terminated_output_is_stable() {
+        let porcelain = Porcelain {
+            version: PorcelainVersion::V1,
+            nul_terminated: true,
+        };
+        let expected: &[u8] =
+            b"event\t1\tevent-1@example.com\tCONFIRMED\t\t2025-01-02T15:04:05+08:00\tStandup\0\
+            event\t\tevent\\t2\t\t\t2025-01-03\tOffsite\tday 1\nday 2\0\
+            todo\t3\ttodo-3@example.com\tNEEDS-ACTION\t2\t2025-01-04T09:00:00\tBack up C:\\Users\0\
+            todo\t4\ttodo-4@example.com\tCOMPLETED\t0\t\tLine 1\r\nLine 2\0";
+        assert_eq!(output(porcelain), expected);
+    }
+
+    #[test]
+    fn porcelain_v1_records_split_into_seven_fields() {
+        let porcelain = Porcelain {
+            version: PorcelainVersion::V1,
+            nul_terminated: true,
+        };
+        let out = output(porcelain);
+        let records: Vec<_> = out.split(|&b| b == 0).filter(|r| !r.is_empty()).collect();
+        assert_eq!(records.len(), 4);
+        for record in records {
+            let fields: Vec<_> = record.splitn(7, |&b| b == b'\t').collect();
+            assert_eq!(fields.len(), 7);
+        }
+    }
+}
@@ -4,9 +4,15 @@
 
 use std::io::IsTerminal;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use aimcal_core::DateTimeAnchor;
-use cliclack::{input, intro, note, outro, select};
+use aimcal_core::{BlockRelease, DateTimeAnchor, EventDraft, IdCandidate, Priority, TodoDraft};
+use clap::ValueEnum;
+use cliclack::{confirm, input, intro, multiselect, note, outro, outro_cancel, select};
+use jiff::Zoned;
+use jiff::civil::Time;
+
+use crate::util::{format_datetime, parse_anchor, parse_due};
 
 const TIME_NOTE: &str = "\
 • Relative time: 10s, 10m, 2h, 3d
@@ -104,11 +110,34 @@ pub enum DuplicateChoice {
     CreateNew,
 }
 
+/// Whether to never prompt, failing instead where a choice is needed, see [`set_assume_yes`].
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
 /// Check if stdout is a terminal (interactive mode).
 pub fn is_terminal() -> bool {
     std::io::stdout().is_terminal()
 }
 
+/// Disables the prompts asking to choose, decided once from the command line.
+pub fn set_assume_yes(enabled: bool) {
+    ASSUME_YES.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the user can be asked to choose, rather than failing.
+pub fn can_choose() -> bool {
+    is_terminal() && !ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Whether the user can be asked to type answers, which needs a terminal on stdin as well.
+pub fn can_prompt() -> bool {
+    std::io::stdin().is_terminal() && can_choose()
+}
+
+/// Error of a new item without a summary when it cannot be asked for, such as in scripts.
+pub fn missing_summary() -> Box<dyn std::error::Error> {
+    "A summary is required, give it as an argument or run interactively".into()
+}
+
 pub fn prompt_duplicate_choice(
     item_kind: &str,
     existing_id: &str,
@@ -141,3 +170,314 @@ pub fn prompt_duplicate_choice(
 
     Ok(choice)
 }
+
+/// Asks which of the items an ambiguous id refers to, returning its index in `candidates`.
+pub fn prompt_id_candidate(
+    id: &str,
+    candidates: &[IdCandidate],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    intro("Ambiguous id")?;
+
+    let mut prompt = select(format!("Which item does {id} refer to?"));
+    for (i, candidate) in candidates.iter().enumerate() {
+        let label = format!("#{} {}", candidate.short_id, candidate.summary);
+        prompt = prompt.item(i, label, &candidate.uid);
+    }
+    let choice = prompt.interact()?;
+
+    outro("Item selected")?;
+    Ok(choice)
+}
+
+/// Asks which of the todos to plan, given by their label and hint, returning the indices of the
+/// chosen ones.
+///
+/// Returns `None` if the user aborts with Ctrl-C, so nothing is planned.
+pub fn prompt_todos_to_plan(
+    todos: &[(String, String)],
+) -> Result<Option<Vec<usize>>, Box<dyn std::error::Error>> {
+    aborted(choose_todos_to_plan(todos))
+}
+
+fn choose_todos_to_plan(
+    todos: &[(String, String)],
+) -> Result<Option<Vec<usize>>, Box<dyn std::error::Error>> {
+    intro("Plan todos")?;
+    let mut prompt = multiselect("Which todos to plan? (space to choose)");
+    for (i, (label, hint)) in todos.iter().enumerate() {
+        prompt = prompt.item(i, label, hint);
+    }
+    let chosen = prompt.interact()?;
+    outro(format!("Planning {} todos", chosen.len()))?;
+    Ok(Some(chosen))
+}
+
+/// Asks what to do with the blocks planned ahead for a todo just completed, see
+/// [`BlockRelease`].
+///
+/// Returns `None` to keep them, which is also what aborting with Ctrl-C does.
+pub fn prompt_block_release(
+    summary: &str,
+    blocks: usize,
+) -> Result<Option<BlockRelease>, Box<dyn std::error::Error>> {
+    intro("Planned blocks ahead")?;
+    let choice = select(format!(
+        "{summary} is done, what about its {blocks} blocks planned ahead?"
+    ))
+    .item(None, "Keep", "Leave the blocks in the calendar")
+    .item(
+        Some(BlockRelease::Shorten),
+        "Shorten",
+        "End the block in progress now and delete the later ones",
+    )
+    .item(
+        Some(BlockRelease::Delete),
+        "Delete",
+        "Delete the blocks that have not ended",
+    )
+    .initial_value(Some(BlockRelease::Shorten))
+    .interact();
+
+    let choice = match choice {
+        Ok(choice) => choice,
+        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => None,
+        Err(e) => return Err(e.into()),
+    };
+    match choice {
+        None => outro("Keeping the blocks")?,
+        Some(BlockRelease::Shorten) => outro("Shortening the blocks")?,
+        Some(BlockRelease::Delete) => outro("Deleting the blocks")?,
+    }
+    Ok(choice)
+}
+
+/// Asks once whether to go on with a sync whose changes were just listed, counting those pushed
+/// and pulled.
+///
+/// Returns `false` if the user declines or aborts with Ctrl-C, so nothing is pushed.
+pub fn prompt_sync_plan(pushes: usize, pulls: usize) -> Result<bool, Box<dyn std::error::Error>> {
+    intro("Sync")?;
+    let question = match (pushes, pulls) {
+        (0, 0) => "Sync without pushing or pulling anything?".to_string(),
+        (0, n) => format!("Pull these {n} changes into the cache?"),
+        (n, 0) => format!("Push these {n} writes to the stores and sync?"),
+        (n, m) => format!("Push these {n} writes to the stores and pull these {m} changes?"),
+    };
+    let push = match confirm(question).initial_value(false).interact() {
+        Ok(push) => push,
+        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => false,
+        Err(e) => return Err(e.into()),
+    };
+    if push {
+        outro("Syncing")?;
+    } else {
+        outro_cancel("Canceled, nothing was pushed")?;
+    }
+    Ok(push)
+}
+
+/// Asks for the fields of a new todo one by one, then confirms it with a preview.
+///
+/// Returns `None` if the user declines or aborts with Ctrl-C, so nothing is created.
+pub fn prompt_todo_draft(
+    now: &Zoned,
+    default_due_time: Option<Time>,
+    draft: TodoDraft,
+) -> Result<Option<TodoDraft>, Box<dyn std::error::Error>> {
+    aborted(prompt_todo_fields(now, default_due_time, draft))
+}
+
+fn prompt_todo_fields(
+    now: &Zoned,
+    default_due_time: Option<Time>,
+    mut draft: TodoDraft,
+) -> Result<Option<TodoDraft>, Box<dyn std::error::Error>> {
+    intro("New todo")?;
+    draft.summary = prompt_summary()?;
+
+    let due_now = now.clone();
+    let due: String = input("Due")
+        .placeholder("e.g., tomorrow, 14:30, 2025-01-15 (empty to skip)")
+        .required(false)
+        .validate(move |s: &String| {
+            parse_due(&due_now, default_due_time, s.trim())
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        })
+        .interact()?;
+    if let Some(due) = parse_due(now, default_due_time, due.trim())? {
+        draft.due = Some(due);
+    }
+
+    let priority: String = input("Priority")
+        .placeholder("0-9, high, medium or low (empty to skip)")
+        .required(false)
+        .validate(|s: &String| parse_priority(s).map(|_| ()))
+        .interact()?;
+    if let Some(priority) = parse_priority(&priority)? {
+        draft.priority = Some(priority);
+    }
+
+    let categories: String = input("Categories")
+        .placeholder("comma separated (empty to skip)")
+        .required(false)
+        .interact()?;
+    draft.categories = categories
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(ToString::to_string)
+        .collect();
+
+    draft.description = prompt_description()?;
+
+    let mut preview = vec![format!("Summary:     {}", draft.summary)];
+    if let Some(due) = &draft.due {
+        preview.push(format!("Due:         {}", format_datetime(due.clone())));
+    }
+    if let Some(priority) = draft.priority.filter(|&p| p != Priority::None) {
+        preview.push(format!("Priority:    {}", u8::from(priority)));
+    }
+    if !draft.categories.is_empty() {
+        preview.push(format!("Categories:  {}", draft.categories.join(", ")));
+    }
+    if let Some(description) = &draft.description {
+        preview.push(format!("Description: {description}"));
+    }
+    Ok(confirm_draft("todo", &preview)?.then_some(draft))
+}
+
+/// Asks for the fields of a new event one by one, then confirms it with a preview.
+///
+/// Returns `None` if the user declines or aborts with Ctrl-C, so nothing is created.
+pub fn prompt_event_draft(
+    now: &Zoned,
+    draft: EventDraft,
+) -> Result<Option<EventDraft>, Box<dyn std::error::Error>> {
+    aborted(prompt_event_fields(now, draft))
+}
+
+fn prompt_event_fields(
+    now: &Zoned,
+    mut draft: EventDraft,
+) -> Result<Option<EventDraft>, Box<dyn std::error::Error>> {
+    intro("New event")?;
+    draft.summary = prompt_summary()?;
+
+    let start_now = now.clone();
+    let start: String = input("Start")
+        .placeholder("e.g., tomorrow 9:00, 14:30, 2025-01-15")
+        .validate(move |s: &String| resolve_anchor(&start_now, s))
+        .interact()?;
+    let end_now = now.clone();
+    let end: String = input("End")
+        .placeholder("e.g., 2h, 17:00 (empty for the default duration)")
+        .required(false)
+        .validate(move |s: &String| resolve_anchor(&end_now, s))
+        .interact()?;
+    draft.set_times(
+        now,
+        parse_anchor(start.trim())?,
+        parse_anchor(end.trim())?,
+        None,
+    )?;
+
+    draft.description = prompt_description()?;
+
+    let mut preview = vec![format!("Summary:     {}", draft.summary)];
+    if let Some(start) = &draft.start {
+        preview.push(format!("Start:       {}", format_datetime(start.clone())));
+    }
+    if let Some(end) = &draft.end {
+        preview.push(format!("End:         {}", format_datetime(end.clone())));
+    }
+    if let Some(description) = &draft.description {
+        preview.push(format!("Description: {description}"));
+    }
+    Ok(confirm_draft("event", &preview)?.then_some(draft))
+}
+
+/// Asks for the summary of a new item, again until it is not empty.
+fn prompt_summary() -> std::io::Result<String> {
+    let summary: String = input("Summary")
+        .validate(|s: &String| {
+            if s.trim().is_empty() {
+                Err("Summary is required")
+            } else {
+                Ok(())
+            }
+        })
+        .interact()?;
+    Ok(summary.trim().to_string())
+}
+
+/// Asks for a description line by line, until a lone `.`, or none if the first line is empty.
+fn prompt_description() -> std::io::Result<Option<String>> {
+    let mut lines: Vec<String> = Vec::new();
+    loop {
+        let prompt = if lines.is_empty() {
+            "Description"
+        } else {
+            "Description, continued"
+        };
+        let line: String = input(prompt)
+            .placeholder("a lone . ends it (empty to skip)")
+            .required(false)
+            .interact()?;
+        if line.trim() == "." || (lines.is_empty() && line.trim().is_empty()) {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok((!lines.is_empty()).then(|| lines.join("\n")))
+}
+
+/// Shows the preview of a new item and asks to create it.
+fn confirm_draft(kind: &str, preview: &[String]) -> std::io::Result<bool> {
+    note("Preview", preview.join("\n"))?;
+    let create = confirm(format!("Create this {kind}?"))
+        .initial_value(true)
+        .interact()?;
+    if create {
+        outro(format!("Creating the {kind}"))?;
+    } else {
+        outro_cancel(format!("Canceled, no {kind} was created"))?;
+    }
+    Ok(create)
+}
+
+/// Turns a prompt aborted with Ctrl-C or Esc into `None`, so the command ends without error.
+fn aborted<T>(
+    result: Result<Option<T>, Box<dyn std::error::Error>>,
+) -> Result<Option<T>, Box<dyn std::error::Error>> {
+    match result {
+        Err(e)
+            if e.downcast_ref::<std::io::Error>()
+                .is_some_and(|e| e.kind() == std::io::ErrorKind::Interrupted) =>
+        {
+            outro_cancel("Canceled, nothing was created")?;
+            Ok(None)
+        }
+        result => result,
+    }
+}
+
+/// Parses a priority typed in a prompt, as given to `--priority` or `medium`, where empty means
+/// none.
+fn parse_priority(value: &str) -> Result<Option<Priority>, String> {
+    match value.trim() {
+        "" => Ok(None),
+        "medium" => Ok(Some(Priority::P5)),
+        value => <Priority as ValueEnum>::from_str(value, true)
+            .map(Some)
+            .map_err(|_| format!("Invalid priority '{value}', use 0-9, high, medium or low")),
+    }
+}
+
+/// Checks a date and time typed in a prompt resolves, where empty means none.
+fn resolve_anchor(now: &Zoned, value: &str) -> Result<(), String> {
+    match parse_anchor(value.trim())? {
+        Some(anchor) => anchor.resolve_since_zoned(now).map(|_| ()),
+        None => Ok(()),
+    }
+}
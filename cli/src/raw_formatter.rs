@@ -0,0 +1,298 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::process::{Command, Stdio};
+
+use aimcal_ical::parse_with_diagnostics;
+use aimcal_ical::syntax::{Token, tokenize};
+use colored::Colorize;
+use ratatui::crossterm::terminal;
+
+use crate::prompt::is_terminal;
+
+/// Wraps a raw component into a calendar, so it can be validated on its own.
+const CALENDAR_PREFIX: &str = "BEGIN:VCALENDAR\nVERSION:2.0\nPRODID:-//aim//raw//EN\n";
+
+/// The pager used when `$PAGER` is not set.
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Prints the raw iCalendar content of an item, as serialized in its calendar.
+///
+/// On a terminal, the content is highlighted unless `no_color`, and piped through `$PAGER` if it
+/// is taller than the terminal. Otherwise, it is printed unchanged so it can be redirected.
+pub fn print_raw(raw: &str, no_color: bool) -> Result<(), Box<dyn Error>> {
+    if !is_terminal() {
+        println!("{raw}");
+        return Ok(());
+    }
+
+    let output = if !no_color && colored::control::SHOULD_COLORIZE.should_colorize() {
+        highlight(raw)
+    } else {
+        raw.to_string()
+    };
+    let height = terminal::size().map_or(usize::MAX, |(_, height)| usize::from(height));
+    if output.lines().count() < height {
+        println!("{output}");
+        Ok(())
+    } else {
+        page(&output)
+    }
+}
+
+/// Highlights the raw content of a component, underlining the spans with parse problems.
+fn highlight(raw: &str) -> String {
+    let problems = problems(raw);
+    let mut output = String::with_capacity(raw.len() * 2);
+    for (role, range) in classify(raw) {
+        let mut start = range.start;
+        while start < range.end {
+            // Split the piece where a problem starts or ends, to underline only its span
+            let end = problems
+                .iter()
+                .flat_map(|p| [p.start, p.end])
+                .filter(|&i| start < i && i < range.end)
+                .min()
+                .unwrap_or(range.end);
+            let problem = problems.iter().any(|p| p.start <= start && end <= p.end);
+            let text = raw.get(start..end).unwrap_or_default();
+            output.push_str(&paint(text, role, problem));
+            start = end;
+        }
+    }
+    output
+}
+
+/// Role of a piece of a content line, styled differently when highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    /// The name of a property, or of a component in `BEGIN` and `END`.
+    Name,
+    /// The name of a parameter.
+    Parameter,
+    /// The value of a parameter.
+    ParameterValue,
+    /// The value of a property.
+    Value,
+    /// The `;`, `=`, `,` and `:` between the parts of a line.
+    Delimiter,
+    /// The line break and whitespace continuing a folded line.
+    Fold,
+    /// The line ending.
+    Newline,
+}
+
+fn paint(text: &str, role: Role, problem: bool) -> String {
+    let styled = match role {
+        Role::Name => text.blue().bold(),
+        Role::Parameter => text.cyan(),
+        Role::ParameterValue => text.magenta(),
+        Role::Value => text.normal(),
+        Role::Delimiter => text.dimmed(),
+        Role::Fold => {
+            // Mark the continuation in place of its leading whitespace
+            let line_break = text.trim_end_matches([' ', '\t']);
+            return format!("{line_break}{}", "↳".dimmed());
+        }
+        Role::Newline => return text.to_string(),
+    };
+    if problem {
+        styled.underline().to_string()
+    } else {
+        styled.to_string()
+    }
+}
+
+/// Where the lexer is in a content line, deciding the role of the next token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Name,
+    Parameter,
+    ParameterValue { quoted: bool },
+    Value,
+}
+
+/// Splits the content into pieces by role, following the tokens of the iCalendar lexer so the
+/// highlighting never disagrees with the parser.
+///
+/// The lexer skips folds, so the gaps between the tokens are the folded line breaks.
+fn classify(src: &str) -> Vec<(Role, Range<usize>)> {
+    let mut pieces = Vec::new();
+    let mut state = State::Name;
+    let mut last = 0;
+    for token in tokenize(src) {
+        let span = token.1.into_range();
+        if last < span.start {
+            pieces.push((Role::Fold, last..span.start));
+        }
+        last = span.end;
+
+        let role = match (state, token.0) {
+            (_, Token::Newline(_)) => {
+                state = State::Name;
+                Role::Newline
+            }
+            (State::Value, _) => Role::Value,
+            (State::ParameterValue { quoted: true }, Token::DQuote) => {
+                state = State::ParameterValue { quoted: false };
+                Role::ParameterValue
+            }
+            (State::ParameterValue { quoted: true }, _) => Role::ParameterValue,
+            (State::ParameterValue { quoted: false }, Token::DQuote) => {
+                state = State::ParameterValue { quoted: true };
+                Role::ParameterValue
+            }
+            (_, Token::Colon) => {
+                state = State::Value;
+                Role::Delimiter
+            }
+            (_, Token::Semicolon) => {
+                state = State::Parameter;
+                Role::Delimiter
+            }
+            (State::Parameter, Token::Equal) => {
+                state = State::ParameterValue { quoted: false };
+                Role::Delimiter
+            }
+            (State::ParameterValue { .. }, Token::Comma) => Role::Delimiter,
+            (State::Name, _) => Role::Name,
+            (State::Parameter, _) => Role::Parameter,
+            (State::ParameterValue { .. }, _) => Role::ParameterValue,
+        };
+        pieces.push((role, span));
+    }
+    if last < src.len() {
+        pieces.push((Role::Fold, last..src.len()));
+    }
+    pieces
+}
+
+/// Finds the spans of the raw content of a component with parse errors or warnings.
+fn problems(raw: &str) -> Vec<Range<usize>> {
+    let src = format!("{CALENDAR_PREFIX}{raw}\nEND:VCALENDAR\n");
+    let (_, diagnostics) = parse_with_diagnostics(&src);
+    diagnostics
+        .into_iter()
+        .filter_map(|d| {
+            let offset = CALENDAR_PREFIX.len();
+            let start = d.span.start.checked_sub(offset)?;
+            let end = d.span.end.saturating_sub(offset).min(raw.len());
+            (start < end).then_some(start..end)
+        })
+        .collect()
+}
+
+/// Pipes the output through the pager of `$PAGER`, or prints it if the pager cannot start.
+fn page(output: &str) -> Result<(), Box<dyn Error>> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut args = pager.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_PAGER);
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!(%pager, error = %e, "failed to start the pager");
+            println!("{output}");
+            return Ok(());
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may quit before reading everything
+        match writeln!(stdin, "{output}") {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+            Ok(()) | Err(_) => {}
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(src: &str) -> Vec<(Role, &str)> {
+        classify(src)
+            .into_iter()
+            .map(|(role, range)| (role, src.get(range).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_names_parameters_and_values() {
+        let src = "DTSTART;TZID=\"Asia/Shanghai;x\":20250101T090000\nSUMMARY:a;b:c";
+        assert_eq!(
+            roles(src),
+            vec![
+                (Role::Name, "DTSTART"),
+                (Role::Delimiter, ";"),
+                (Role::Parameter, "TZID"),
+                (Role::Delimiter, "="),
+                (Role::ParameterValue, "\""),
+                (Role::ParameterValue, "Asia"),
+                (Role::ParameterValue, "/"),
+                (Role::ParameterValue, "Shanghai"),
+                (Role::ParameterValue, ";"),
+                (Role::ParameterValue, "x"),
+                (Role::ParameterValue, "\""),
+                (Role::Delimiter, ":"),
+                (Role::Value, "20250101T090000"),
+                (Role::Newline, "\n"),
+                (Role::Name, "SUMMARY"),
+                (Role::Delimiter, ":"),
+                (Role::Value, "a"),
+                (Role::Value, ";"),
+                (Role::Value, "b"),
+                (Role::Value, ":"),
+                (Role::Value, "c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_folds_between_tokens() {
+        let src = "DESCRIPTION:ab\n cd";
+        assert_eq!(
+            roles(src),
+            vec![
+                (Role::Name, "DESCRIPTION"),
+                (Role::Delimiter, ":"),
+                (Role::Value, "ab"),
+                (Role::Fold, "\n "),
+                (Role::Value, "cd"),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_problems_in_the_raw_component() {
+        let raw = "\
+BEGIN:VTODO
+UID:1
+DTSTAMP:20250101T000000Z
+SUMMARY:First
+SUMMARY:Second
+END:VTODO";
+        let spans = problems(raw);
+        assert_eq!(spans.len(), 1);
+        let problem = spans.first().unwrap();
+        assert!(
+            raw.get(problem.start..)
+                .unwrap()
+                .starts_with("SUMMARY:Second")
+        );
+
+        let raw = "BEGIN:VTODO\nUID:1\nDTSTAMP:20250101T000000Z\nEND:VTODO";
+        assert!(problems(raw).is_empty());
+    }
+}
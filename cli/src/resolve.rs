@@ -0,0 +1,140 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::error::Error;
+
+use aimcal_core::{Aim, AimError, Id, IdCandidate, Kind};
+
+use crate::prompt::{can_choose, prompt_id_candidate};
+use crate::table::{PaddingDirection, Table, TableColumn, TableStyleBasic};
+use crate::util::format_datetime;
+
+/// Resolves an id typed by the user to the UID of the item it refers to, see
+/// [`resolve_candidate`].
+pub async fn resolve_id(aim: &Aim, id: &Id, kind_hint: Option<Kind>) -> Result<Id, Box<dyn Error>> {
+    let candidate = resolve_candidate(aim, id, kind_hint).await?;
+    Ok(Id::Uid(candidate.uid))
+}
+
+/// Resolves an id typed by the user to the item it refers to.
+///
+/// If the id matches more than one item, they are listed, then the user is asked to choose in
+/// interactive terminals, and the resolution fails otherwise or with `--yes`, rather than guess.
+pub async fn resolve_candidate(
+    aim: &Aim,
+    id: &Id,
+    kind_hint: Option<Kind>,
+) -> Result<IdCandidate, Box<dyn Error>> {
+    let mut candidates = aim.resolve_id(id, kind_hint).await?;
+    if candidates.len() == 1
+        && let Some(candidate) = candidates.pop()
+    {
+        return Ok(candidate);
+    }
+
+    eprintln!("{} matches {} items:", id.as_uid(), candidates.len());
+    eprintln!("{}", format_candidates(&candidates));
+    if !can_choose() {
+        return Err(AimError::AmbiguousShortId {
+            id: id.as_uid().to_string(),
+            candidates: candidates.into_iter().map(|c| c.uid).collect(),
+        }
+        .into());
+    }
+
+    let index = prompt_id_candidate(id.as_uid(), &candidates)?;
+    Ok(candidates.swap_remove(index))
+}
+
+fn format_candidates(candidates: &[IdCandidate]) -> String {
+    let columns = [
+        CandidateColumn::ShortId,
+        CandidateColumn::Kind,
+        CandidateColumn::Summary,
+        CandidateColumn::Date,
+        CandidateColumn::Uid,
+    ];
+    Table::new(TableStyleBasic::new(), &columns, candidates).to_string()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CandidateColumn {
+    ShortId,
+    Kind,
+    Summary,
+    Date,
+    Uid,
+}
+
+impl TableColumn<IdCandidate> for CandidateColumn {
+    fn name(&self) -> Cow<'_, str> {
+        match self {
+            CandidateColumn::ShortId => "ID",
+            CandidateColumn::Kind => "Kind",
+            CandidateColumn::Summary => "Summary",
+            CandidateColumn::Date => "Date",
+            CandidateColumn::Uid => "UID",
+        }
+        .into()
+    }
+
+    fn format<'a>(&self, candidate: &'a IdCandidate) -> Cow<'a, str> {
+        match self {
+            CandidateColumn::ShortId => format!("#{}", candidate.short_id).into(),
+            CandidateColumn::Kind => match candidate.kind {
+                Kind::Event => "event",
+                Kind::Todo => "todo",
+            }
+            .into(),
+            CandidateColumn::Summary => candidate.summary.as_str().into(),
+            CandidateColumn::Date => candidate
+                .date
+                .clone()
+                .map(format_datetime)
+                .unwrap_or_default()
+                .into(),
+            CandidateColumn::Uid => candidate.uid.as_str().into(),
+        }
+    }
+
+    fn padding_direction(&self) -> PaddingDirection {
+        match self {
+            CandidateColumn::ShortId => PaddingDirection::Right,
+            _ => PaddingDirection::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use aimcal_core::{IdMatch, LooseDateTime};
+    use jiff::civil::date;
+
+    use super::*;
+
+    #[test]
+    fn formats_candidates_as_a_table() {
+        let candidate = |short_id, kind, summary: &str, uid: &str| IdCandidate {
+            uid: uid.to_string(),
+            short_id: NonZeroU32::new(short_id).unwrap(),
+            kind,
+            summary: summary.to_string(),
+            date: Some(LooseDateTime::DateOnly(date(2025, 1, 15))),
+            matched: IdMatch::UidPrefix,
+        };
+        let candidates = [
+            candidate(3, Kind::Todo, "Write report", "report-1"),
+            candidate(12, Kind::Event, "Review", "report-2"),
+        ];
+
+        assert_eq!(
+            format_candidates(&candidates),
+            " #3 todo  Write report 2025-01-15 report-1\n\
+             #12 event Review       2025-01-15 report-2"
+        );
+    }
+}
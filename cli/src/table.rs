@@ -4,9 +4,13 @@
 
 use std::borrow::Cow;
 use std::fmt::{self, Display};
+use std::io::IsTerminal;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use colored::{Color, Colorize};
+use ratatui::crossterm::terminal;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 #[derive(Debug)]
@@ -34,15 +38,20 @@ impl<'a, T, C: TableColumn<T>, S: TableStyle<'a, T, C>> Display for Table<'a, T,
             .map(|data| self.columns.iter().map(|col| col.format(data)).collect())
             .collect::<Vec<_>>();
 
-        let columns = self.style.build(self.columns, &table);
+        let columns = self.style.build(self.columns, self.data, &table);
 
         write!(f, "{}", self.style.table_starting(&columns))?;
         for (i, (cells, data)) in table.into_iter().zip(self.data).enumerate() {
             write!(f, "{}", self.style.row_starting(data))?;
-            for (j, (col, cell)) in columns.iter().zip(cells).enumerate() {
+            let mut cells = columns
+                .iter()
+                .zip(cells)
+                .filter(|(col, _)| self.style.is_shown(col))
+                .peekable();
+            while let Some((col, cell)) = cells.next() {
                 write!(f, "{}", self.style.cell_stylize(data, col, cell))?;
 
-                if j < columns.len() - 1 {
+                if cells.peek().is_some() {
                     write!(f, "{}", self.style.cell_separator())?;
                 }
             }
@@ -60,7 +69,17 @@ impl<'a, T, C: TableColumn<T>, S: TableStyle<'a, T, C>> Display for Table<'a, T,
 pub trait TableStyle<'a, T, C: TableColumn<T>> {
     type ColumnMeta;
 
-    fn build<'b>(&self, columns: &'a [C], table: &'b [Vec<Cow<'a, str>>]) -> Vec<Self::ColumnMeta>;
+    fn build<'b>(
+        &self,
+        columns: &'a [C],
+        data: &'a [T],
+        table: &'b [Vec<Cow<'a, str>>],
+    ) -> Vec<Self::ColumnMeta>;
+
+    /// Whether the cells of the column are shown, rather than left out for lack of room.
+    fn is_shown(&self, _column: &Self::ColumnMeta) -> bool {
+        true
+    }
 
     #[expect(clippy::unnecessary_literal_bound)]
     fn table_starting(&self, _columns: &[Self::ColumnMeta]) -> &str {
@@ -114,10 +133,37 @@ pub trait TableColumn<T> {
         PaddingDirection::Left
     }
 
+    /// Determine how the column takes room when the table is wider than the terminal.
+    fn width(&self) -> ColumnWidth {
+        ColumnWidth::Fixed
+    }
+
+    /// Determine when the column drops out of a table wider than the terminal, see
+    /// [`plan_layout`].
+    fn priority(&self) -> ColumnPriority {
+        ColumnPriority::Essential
+    }
+
+    /// Format the data in the shorter form used when the table is wider than the terminal, if
+    /// the column has one, such as a glyph for a status.
+    fn compact<'a>(&self, _data: &'a T) -> Option<Cow<'a, str>> {
+        None
+    }
+
     /// Get the color for the column based on the data.
     fn get_color(&self, _data: &T) -> Option<Color> {
         None
     }
+
+    /// Whether the cell is shown in bold, to stand out more than its color does.
+    fn is_bold(&self, _data: &T) -> bool {
+        false
+    }
+
+    /// Get the URL the cell links to, shown as a hyperlink if the terminal supports it.
+    fn get_link(&self, _data: &T) -> Option<String> {
+        None
+    }
 }
 
 impl<T, C: TableColumn<T> + ?Sized> TableColumn<T> for Box<C> {
@@ -133,19 +179,46 @@ impl<T, C: TableColumn<T> + ?Sized> TableColumn<T> for Box<C> {
         self.as_ref().padding_direction()
     }
 
+    fn width(&self) -> ColumnWidth {
+        self.as_ref().width()
+    }
+
+    fn priority(&self) -> ColumnPriority {
+        self.as_ref().priority()
+    }
+
+    fn compact<'a>(&self, data: &'a T) -> Option<Cow<'a, str>> {
+        self.as_ref().compact(data)
+    }
+
     fn get_color(&self, data: &T) -> Option<Color> {
         self.as_ref().get_color(data)
     }
+
+    fn is_bold(&self, data: &T) -> bool {
+        self.as_ref().is_bold(data)
+    }
+
+    fn get_link(&self, data: &T) -> Option<String> {
+        self.as_ref().get_link(data)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct TableStyleBasic {
     padding: bool,
+    hyperlinks: bool,
+    /// Width of the terminal, which flexible columns are shrunk to fit in
+    max_width: Option<usize>,
 }
 
 impl TableStyleBasic {
     pub fn new() -> Self {
-        Self { padding: true }
+        Self {
+            padding: true,
+            hyperlinks: hyperlinks_enabled(),
+            max_width: terminal_width(),
+        }
     }
 }
 
@@ -155,28 +228,46 @@ impl<'a, T, C: 'a + TableColumn<T>> TableStyle<'a, T, C> for TableStyleBasic {
     fn build<'b>(
         &self,
         columns: &'a [C],
+        data: &'a [T],
         table: &'b [Vec<Cow<'a, str>>],
     ) -> Vec<TodoColumnBasicMeta<'a, T, C>> {
-        let max_lengths = self.padding.then(|| get_column_max_width(table));
+        let specs = ColumnSpec::measure(columns, data, table);
+        let plans = match self.max_width {
+            Some(max_width) => plan_layout(&specs, max_width),
+            None => specs.iter().map(ColumnSpec::natural_plan).collect(),
+        };
+        let last_shown = plans.iter().rposition(|plan| *plan != ColumnPlan::Hidden);
         columns
             .iter()
+            .zip(specs.iter().zip(plans))
             .enumerate()
-            .map(|(i, col)| {
+            .map(|(i, (col, (spec, plan)))| {
                 let padding_direction = col.padding_direction();
+                let (width, compact) = match plan {
+                    ColumnPlan::Shown { width, compact } => (width, compact),
+                    ColumnPlan::Hidden => (0, false),
+                };
 
-                let padding = match &max_lengths {
-                    Some(lengths)
-                        // Last column does not need padding if it's left-aligned
-                        if !(i == columns.len() - 1 && padding_direction == PaddingDirection::Left) =>
-                            Some((*lengths.get(i).unwrap_or(&0), padding_direction)),
-                    _ => None,
+                // Last column does not need padding if it's left-aligned
+                let last = Some(i) == last_shown && padding_direction == PaddingDirection::Left;
+                let padding = (self.padding && !last).then_some((width, padding_direction));
+                let natural = match spec.compact {
+                    Some(compact_width) if compact => compact_width,
+                    _ => spec.natural,
                 };
+                let limit = (width < natural).then_some(width);
 
-                TodoColumnBasicMeta::new(col, padding)
+                TodoColumnBasicMeta::new(col, padding, self.hyperlinks)
+                    .with_limit(limit)
+                    .with_plan(plan)
             })
             .collect()
     }
 
+    fn is_shown(&self, column: &TodoColumnBasicMeta<'a, T, C>) -> bool {
+        column.plan != ColumnPlan::Hidden
+    }
+
     fn cell_stylize(
         &self,
         data: &'a T,
@@ -194,34 +285,259 @@ pub enum PaddingDirection {
     Right,
 }
 
+/// How a column takes room when the table is wider than the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// As wide as its widest cell.
+    Fixed,
+    /// Shrunk first, with cut cells ending in `…`: down to the ideal width, the second number,
+    /// before columns of lower priority drop out, and down to the minimum, the first, after.
+    Flexible(usize, usize),
+}
+
+/// How important a column is, deciding the order columns drop out of a table wider than the
+/// terminal, see [`plan_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColumnPriority {
+    /// Dropped out by this rank, the lowest first.
+    Optional(u8),
+    /// Never dropped out, such as the summary.
+    Essential,
+}
+
+/// A column as the layout sees it: how it takes room and how wide its cells are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSpec {
+    pub width: ColumnWidth,
+    pub priority: ColumnPriority,
+    /// Display width of the widest cell.
+    pub natural: usize,
+    /// Display width of the widest cell in compact form, if the column has one.
+    pub compact: Option<usize>,
+}
+
+impl ColumnSpec {
+    /// Measures the cells of the columns in `table`, formatted from `data`.
+    fn measure<T, C: TableColumn<T>>(
+        columns: &[C],
+        data: &[T],
+        table: &[Vec<Cow<'_, str>>],
+    ) -> Vec<Self> {
+        let natural = get_column_max_width(table);
+        columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                // Cells without a compact form keep their full form
+                let mut has_compact = false;
+                let compact = table
+                    .iter()
+                    .zip(data)
+                    .map(|(cells, data)| match col.compact(data) {
+                        Some(cell) => {
+                            has_compact = true;
+                            display_width(&cell)
+                        }
+                        None => cells.get(i).map_or(0, |cell| display_width(cell)),
+                    })
+                    .max()
+                    .unwrap_or(0);
+                ColumnSpec {
+                    width: col.width(),
+                    priority: col.priority(),
+                    natural: natural.get(i).copied().unwrap_or(0),
+                    compact: has_compact.then_some(compact),
+                }
+            })
+            .collect()
+    }
+
+    fn natural_plan(&self) -> ColumnPlan {
+        ColumnPlan::Shown {
+            width: self.natural,
+            compact: false,
+        }
+    }
+}
+
+/// How a column is rendered, see [`plan_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnPlan {
+    /// Left out for lack of room.
+    Hidden,
+    /// Shown this many characters wide, in compact form if set.
+    Shown { width: usize, compact: bool },
+}
+
+/// Lays out a table with a space between columns in `max_width` characters.
+///
+/// A table that fits is left as it is. Otherwise, until it fits:
+///
+/// 1. columns with a compact form take it, such as a glyph for a status or a relative date;
+/// 2. columns drop out from the lowest [`ColumnPriority`], the rightmost of equal ones first,
+///    until the rest fit with their flexible columns at their ideal width;
+/// 3. flexible columns shrink, from the lowest priority and the widest of equal ones, first to
+///    their ideal width and then to their minimum, leaving the summary whatever remains.
+///
+/// Essential and fixed columns are never dropped nor shrunk, so the table may still be wider
+/// than `max_width`.
+pub fn plan_layout(columns: &[ColumnSpec], max_width: usize) -> Vec<ColumnPlan> {
+    let mut plans: Vec<_> = columns.iter().map(ColumnSpec::natural_plan).collect();
+    if table_width(&plans) <= max_width {
+        return plans;
+    }
+
+    for (plan, spec) in plans.iter_mut().zip(columns) {
+        if let Some(width) = spec.compact {
+            *plan = ColumnPlan::Shown {
+                width,
+                compact: true,
+            };
+        }
+    }
+    if table_width(&plans) <= max_width {
+        return plans;
+    }
+
+    loop {
+        let ideal: Vec<_> = plans
+            .iter()
+            .zip(columns)
+            .map(|(plan, spec)| match (*plan, spec.width) {
+                (ColumnPlan::Shown { width, compact }, ColumnWidth::Flexible(_, ideal)) => {
+                    ColumnPlan::Shown {
+                        width: width.min(ideal),
+                        compact,
+                    }
+                }
+                (plan, _) => plan,
+            })
+            .collect();
+        if table_width(&ideal) <= max_width {
+            break;
+        }
+        let dropped = plans
+            .iter()
+            .zip(columns)
+            .enumerate()
+            .filter(|(_, (plan, spec))| {
+                **plan != ColumnPlan::Hidden && spec.priority != ColumnPriority::Essential
+            })
+            .min_by_key(|(i, (_, spec))| (spec.priority, std::cmp::Reverse(*i)));
+        let Some((i, _)) = dropped else {
+            break;
+        };
+        if let Some(plan) = plans.get_mut(i) {
+            *plan = ColumnPlan::Hidden;
+        }
+    }
+
+    let (widths, shown): (Vec<_>, Vec<_>) = plans
+        .iter()
+        .zip(columns)
+        .filter_map(|(plan, spec)| match plan {
+            ColumnPlan::Shown { width, .. } => Some((*width, (spec.width, spec.priority))),
+            ColumnPlan::Hidden => None,
+        })
+        .unzip();
+    let mut widths = balance_widths(&widths, &shown, max_width).into_iter();
+    for plan in &mut plans {
+        if let ColumnPlan::Shown { width, .. } = plan {
+            *width = widths.next().unwrap_or(*width);
+        }
+    }
+    plans
+}
+
+/// Width of a table laid out by `plans`, with a space between the shown columns.
+fn table_width(plans: &[ColumnPlan]) -> usize {
+    let widths: Vec<_> = plans
+        .iter()
+        .filter_map(|plan| match plan {
+            ColumnPlan::Shown { width, .. } => Some(*width),
+            ColumnPlan::Hidden => None,
+        })
+        .collect();
+    widths.iter().sum::<usize>() + widths.len().saturating_sub(1)
+}
+
 #[derive(Debug, Clone)]
 pub struct TodoColumnBasicMeta<'a, T, C: TableColumn<T>> {
     column: &'a C,
     /// padding width and direction
     padding: Option<(usize, PaddingDirection)>,
+    /// width cells are cut to, if the column was shrunk
+    limit: Option<usize>,
+    /// whether and how the column is shown
+    plan: ColumnPlan,
+    /// whether cells are wrapped in their links
+    hyperlinks: bool,
     _marker: PhantomData<T>,
 }
 
 impl<'a, T, C: TableColumn<T>> TodoColumnBasicMeta<'a, T, C> {
-    pub fn new(column: &'a C, padding: Option<(usize, PaddingDirection)>) -> Self {
+    pub fn new(
+        column: &'a C,
+        padding: Option<(usize, PaddingDirection)>,
+        hyperlinks: bool,
+    ) -> Self {
         Self {
             column,
             padding,
+            limit: None,
+            plan: ColumnPlan::Shown {
+                width: 0,
+                compact: false,
+            },
+            hyperlinks,
             _marker: PhantomData,
         }
     }
 
-    pub fn stylize_cell(&self, data: &T, cell: Cow<'a, str>) -> Cow<'a, str> {
-        let cell = match self.padding {
-            Some((width, PaddingDirection::Left)) => format!("{cell:<width$}").into(),
-            Some((width, PaddingDirection::Right)) => format!("{cell:>width$}").into(),
+    /// Cuts the cells to `limit` characters wide, if any.
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Shows the column as laid out by [`plan_layout`].
+    pub fn with_plan(mut self, plan: ColumnPlan) -> Self {
+        self.plan = plan;
+        self
+    }
+
+    pub fn stylize_cell(&self, data: &'a T, cell: Cow<'a, str>) -> Cow<'a, str> {
+        let cell = match self.plan {
+            ColumnPlan::Shown { compact: true, .. } => self.column.compact(data).unwrap_or(cell),
+            _ => cell,
+        };
+        let cell = match self.limit {
+            Some(limit) if display_width(&cell) > limit => truncate(&cell, limit).into(),
+            _ => cell,
+        };
+        let cell = match self.column.get_link(data) {
+            Some(url) if self.hyperlinks => hyperlink(&url, &cell).into(),
             _ => cell,
         };
 
-        self.column
-            .get_color(data)
-            .map(|color| cell.color(color).to_string().into())
-            .unwrap_or(cell)
+        // Padded by display width, as the link escapes take no room on the screen
+        let cell = match self.padding {
+            Some((width, direction)) => {
+                let fill = " ".repeat(width.saturating_sub(display_width(&cell)));
+                match direction {
+                    PaddingDirection::Left => format!("{cell}{fill}").into(),
+                    PaddingDirection::Right => format!("{fill}{cell}").into(),
+                }
+            }
+            None => cell,
+        };
+
+        match (self.column.get_color(data), self.column.is_bold(data)) {
+            (Some(color), true) => cell.color(color).bold().to_string().into(),
+            (Some(color), false) => cell.color(color).to_string().into(),
+            (None, true) => cell.bold().to_string().into(),
+            (None, false) => cell,
+        }
     }
 }
 
@@ -241,6 +557,7 @@ impl<'a, T, C: 'a + TableColumn<T>> TableStyle<'a, T, C> for TableStyleJson {
     fn build<'b>(
         &self,
         columns: &'a [C],
+        _data: &'a [T],
         _table: &'b [Vec<Cow<'a, str>>],
     ) -> Vec<Self::ColumnMeta> {
         columns.iter().map(|col| col.name()).collect()
@@ -288,13 +605,163 @@ impl<'a, T, C: 'a + TableColumn<T>> TableStyle<'a, T, C> for TableStyleJson {
     }
 }
 
+/// Whether output is wrapped in OSC 8 hyperlinks, see [`set_hyperlinks`].
+static HYPERLINKS: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables hyperlinks in the output, decided once from the config and terminal.
+pub fn set_hyperlinks(enabled: bool) {
+    HYPERLINKS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn hyperlinks_enabled() -> bool {
+    HYPERLINKS.load(Ordering::Relaxed)
+}
+
+/// Guesses whether stdout is a terminal that shows OSC 8 hyperlinks, from its environment.
+pub fn terminal_supports_hyperlinks() -> bool {
+    std::io::stdout().is_terminal() && supports_hyperlinks(|name| std::env::var(name).ok())
+}
+
+/// Guesses whether the terminal with the environment `var` shows OSC 8 hyperlinks.
+///
+/// Terminals that don't support them usually print the escapes as garbage, so only the
+/// terminals known to support them are trusted, and `FORCE_HYPERLINK` overrides the guess.
+fn supports_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if var("TERM").is_some_and(|term| term == "dumb") {
+        return false;
+    }
+    if var("DOMTERM").is_some()
+        || var("WT_SESSION").is_some()
+        || var("KONSOLE_VERSION").is_some()
+        || var("KITTY_WINDOW_ID").is_some()
+    {
+        return true;
+    }
+    if let Some(program) = var("TERM_PROGRAM")
+        && matches!(
+            program.as_str(),
+            "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "Tabby" | "rio"
+        )
+    {
+        return true;
+    }
+    // Hyperlinks arrived in VTE 0.50, which GNOME Terminal and others are built on
+    if let Some(version) = var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()) {
+        return version >= 5000;
+    }
+    var("TERM").is_some_and(|term| {
+        ["xterm-kitty", "alacritty", "foot", "wezterm"]
+            .iter()
+            .any(|t| term.starts_with(t))
+    })
+}
+
+/// Wraps `text` in an OSC 8 hyperlink to `url`, which terminals show as `text` alone.
+pub fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Computes the display width of a string, leaving out OSC 8 hyperlink escapes.
+pub fn display_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut rest = s;
+    while let Some((text, escape)) = rest.split_once("\x1b]8;") {
+        width += text.width();
+        // The escape ends with ST (ESC \) or BEL, its parameters and URL have neither
+        rest = match escape.find(['\x1b', '\x07']) {
+            Some(end) if escape.get(end..).is_some_and(|e| e.starts_with('\x07')) => {
+                escape.get(end + 1..).unwrap_or_default()
+            }
+            Some(end) => escape.get(end + 2..).unwrap_or_default(),
+            None => "",
+        };
+    }
+    width + rest.width()
+}
+
+/// Width of the terminal stdout is, if it is one, as piped output is not cut.
+fn terminal_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal::size().ok().map(|(width, _)| usize::from(width))
+}
+
+/// Shrinks the flexible columns until the table with a space between columns fits in
+/// `max_width`, taking a character at a time from the one of the lowest priority, the widest of
+/// equal ones, first down to their ideal width and then to their minimum.
+///
+/// Fixed columns are never shrunk, so the table may still be wider than `max_width`.
+fn balance_widths(
+    widths: &[usize],
+    columns: &[(ColumnWidth, ColumnPriority)],
+    max_width: usize,
+) -> Vec<usize> {
+    let mut widths = widths.to_vec();
+    let separators = widths.len().saturating_sub(1);
+    let mut excess = (widths.iter().sum::<usize>() + separators).saturating_sub(max_width);
+    let floors: [fn(usize, usize) -> usize; 2] = [|min, ideal| ideal.max(min), |min, _| min];
+    for floor in floors {
+        while excess > 0 {
+            let next = widths
+                .iter()
+                .zip(columns)
+                .enumerate()
+                .filter_map(|(i, (width, (column, priority)))| match column {
+                    ColumnWidth::Flexible(min, ideal) if *width > floor(*min, *ideal) => {
+                        Some((i, *width, *priority))
+                    }
+                    _ => None,
+                })
+                .max_by_key(|(i, width, priority)| {
+                    (std::cmp::Reverse(*priority), *width, std::cmp::Reverse(*i))
+                });
+            let Some((i, _, _)) = next else {
+                break;
+            };
+            if let Some(width) = widths.get_mut(i) {
+                *width -= 1;
+            }
+            excess -= 1;
+        }
+    }
+    widths
+}
+
+/// Cuts `s` to `width` display characters, ending it with `…` if anything was cut.
+///
+/// It is cut between grapheme clusters, so an emoji made of several characters is kept whole.
+fn truncate(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for g in s.graphemes(true) {
+        let w = g.width();
+        // Leave room for the ellipsis
+        if used + w + 1 > width {
+            break;
+        }
+        cut.push_str(g);
+        used += w;
+    }
+    if width > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
 /// Computes the maximum display width for each column in a 2D table of strings.
 fn get_column_max_width(table: &[Vec<Cow<'_, str>>]) -> Vec<usize> {
     let mut max_width = vec![0; table.first().map_or(0, Vec::len)];
     for row in table {
         for (i, cell) in row.iter().enumerate() {
             if let Some(max_width) = max_width.get_mut(i) {
-                let width = cell.width();
+                let width = display_width(cell);
                 if width > *max_width {
                     *max_width = width;
                 }
@@ -365,6 +832,21 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct LinkedNameColumn;
+
+    impl TableColumn<TestData> for LinkedNameColumn {
+        fn name(&self) -> Cow<'_, str> {
+            "Name".into()
+        }
+        fn format<'a>(&self, data: &'a TestData) -> Cow<'a, str> {
+            (&data.name).into()
+        }
+        fn get_link(&self, data: &TestData) -> Option<String> {
+            Some(format!("aim://person/{}", data.name.to_lowercase()))
+        }
+    }
+
     type DynColumn = Box<dyn TableColumn<TestData>>;
 
     fn create_test_data() -> Vec<TestData> {
@@ -585,6 +1067,84 @@ Charlie 35 Yes\
         assert_eq!(table.to_string(), r#"[{"Name":"Test\"Quote"}]"#);
     }
 
+    #[test]
+    fn renders_links_without_counting_escapes_in_width() {
+        let data = create_test_data();
+        let columns: Vec<DynColumn> = vec![Box::new(LinkedNameColumn), Box::new(AgeColumn)];
+        let mut style = TableStyleBasic::new();
+        style.hyperlinks = true;
+        let table = Table::new(style, &columns, &data);
+
+        let result = {
+            let _guard = colored_control().lock().unwrap();
+            colored::control::set_override(false);
+            table.to_string()
+        };
+        let link = |name: &str| hyperlink(&format!("aim://person/{}", name.to_lowercase()), name);
+        let expected = format!(
+            "{}   30\n{}     25\n{} 35",
+            link("Alice"),
+            link("Bob"),
+            link("Charlie")
+        );
+        assert_eq!(result, expected);
+
+        // Without hyperlinks, the cells are plain
+        let mut style = TableStyleBasic::new();
+        style.hyperlinks = false;
+        let table = Table::new(style, &columns, &data);
+        let result = {
+            let _guard = colored_control().lock().unwrap();
+            colored::control::set_override(false);
+            table.to_string()
+        };
+        assert_eq!(result, "Alice   30\nBob     25\nCharlie 35");
+    }
+
+    #[test]
+    fn measures_display_width_without_link_escapes() {
+        assert_eq!(display_width("plain"), 5);
+        assert_eq!(display_width(&hyperlink("https://example.com", "link")), 4);
+        assert_eq!(display_width(&hyperlink("file:///tmp/a.ics", "你好")), 4);
+        assert_eq!(
+            display_width(&format!("a {} b", hyperlink("aim://todo/1", "#1"))),
+            6
+        );
+        // Terminated by BEL instead of ST
+        assert_eq!(
+            display_width("\x1b]8;;https://example.com\x07x\x1b]8;;\x07"),
+            1
+        );
+        // An unterminated escape takes the rest of the string
+        assert_eq!(display_width("ab\x1b]8;;https://example.com"), 2);
+    }
+
+    #[test]
+    fn detects_hyperlink_support_from_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| (*v).to_string())
+            }
+        };
+        assert!(!supports_hyperlinks(env(&[])));
+        assert!(!supports_hyperlinks(env(&[("TERM", "xterm-256color")])));
+        assert!(!supports_hyperlinks(env(&[
+            ("TERM", "dumb"),
+            ("WT_SESSION", "1")
+        ])));
+        assert!(supports_hyperlinks(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supports_hyperlinks(env(&[("TERM", "xterm-kitty")])));
+        assert!(supports_hyperlinks(env(&[("VTE_VERSION", "7600")])));
+        assert!(!supports_hyperlinks(env(&[("VTE_VERSION", "4601")])));
+        assert!(supports_hyperlinks(env(&[("FORCE_HYPERLINK", "1")])));
+        assert!(!supports_hyperlinks(env(&[
+            ("FORCE_HYPERLINK", "0"),
+            ("TERM_PROGRAM", "iTerm.app")
+        ])));
+    }
+
     #[test]
     fn calculates_max_width_for_empty_table() {
         let widths = get_column_max_width(&[]);
@@ -613,4 +1173,223 @@ Charlie 35 Yes\
         assert_eq!(widths.first(), Some(&16)); // "very long string"
         assert_eq!(widths.get(1), Some(&6)); // "medium"
     }
+
+    #[derive(Debug)]
+    struct FlexibleNameColumn;
+
+    impl TableColumn<TestData> for FlexibleNameColumn {
+        fn name(&self) -> Cow<'_, str> {
+            "Name".into()
+        }
+        fn format<'a>(&self, data: &'a TestData) -> Cow<'a, str> {
+            (&data.name).into()
+        }
+        fn width(&self) -> ColumnWidth {
+            ColumnWidth::Flexible(4, 4)
+        }
+    }
+
+    #[test]
+    fn shrinks_flexible_columns_to_fit_the_terminal() {
+        let data = create_test_data();
+        let columns: Vec<DynColumn> = vec![Box::new(FlexibleNameColumn), Box::new(AgeColumn)];
+        let mut style = TableStyleBasic::new();
+        style.max_width = Some(8);
+        let table = Table::new(style, &columns, &data);
+
+        let result = {
+            let _guard = colored_control().lock().unwrap();
+            colored::control::set_override(false);
+            table.to_string()
+        };
+        assert_eq!(result, "Alice 30\nBob   25\nChar… 35");
+    }
+
+    #[test]
+    fn balances_widths_from_the_widest_flexible_column() {
+        use ColumnWidth::{Fixed, Flexible};
+
+        let columns =
+            [Fixed, Flexible(5, 5), Flexible(5, 5)].map(|w| (w, ColumnPriority::Essential));
+        // Fits already, 3 + 10 + 8 and two separators
+        assert_eq!(balance_widths(&[3, 10, 8], &columns, 23), [3, 10, 8]);
+        // The widest flexible column is shrunk first, then both alike
+        assert_eq!(balance_widths(&[3, 10, 8], &columns, 21), [3, 8, 8]);
+        assert_eq!(balance_widths(&[3, 10, 8], &columns, 19), [3, 7, 7]);
+        // Never below their minimum, nor are fixed columns shrunk
+        assert_eq!(balance_widths(&[3, 10, 8], &columns, 5), [3, 5, 5]);
+    }
+
+    #[test]
+    fn balances_widths_from_the_lowest_priority_down_to_ideal_widths_first() {
+        use ColumnPriority::{Essential, Optional};
+
+        let columns = [
+            (ColumnWidth::Flexible(10, 20), Essential),
+            (ColumnWidth::Flexible(5, 10), Optional(0)),
+        ];
+        // The optional column shrinks to its ideal width, then the essential one to its
+        assert_eq!(balance_widths(&[30, 20], &columns, 41), [30, 10]);
+        assert_eq!(balance_widths(&[30, 20], &columns, 31), [20, 10]);
+        // Then the optional one to its minimum first
+        assert_eq!(balance_widths(&[30, 20], &columns, 26), [20, 5]);
+        assert_eq!(balance_widths(&[30, 20], &columns, 16), [10, 5]);
+    }
+
+    /// Status, ID, due, summary and description columns of a todo listing.
+    fn todo_specs() -> [ColumnSpec; 5] {
+        use ColumnPriority::{Essential, Optional};
+        use ColumnWidth::{Fixed, Flexible};
+
+        let spec = |width, priority, natural, compact| ColumnSpec {
+            width,
+            priority,
+            natural,
+            compact,
+        };
+        [
+            spec(Fixed, Essential, 3, Some(1)),
+            spec(Fixed, Essential, 2, None),
+            spec(Fixed, Essential, 16, Some(3)),
+            spec(Flexible(20, 40), Essential, 60, None),
+            spec(Flexible(10, 30), Optional(0), 40, None),
+        ]
+    }
+
+    #[test]
+    fn plans_layout_at_several_widths() {
+        use ColumnPlan::{Hidden, Shown};
+
+        let full = |width| Shown {
+            width,
+            compact: false,
+        };
+        let compact = |width| Shown {
+            width,
+            compact: true,
+        };
+        let specs = todo_specs();
+
+        // Wide enough for every cell
+        assert_eq!(
+            plan_layout(&specs, 125),
+            [full(3), full(2), full(16), full(60), full(40)]
+        );
+        // Compact forms, then the description shrinks to its ideal width
+        assert_eq!(
+            plan_layout(&specs, 100),
+            [compact(1), full(2), compact(3), full(60), full(30)]
+        );
+        // The summary shrinks to its ideal width before the description drops out
+        assert_eq!(
+            plan_layout(&specs, 80),
+            [compact(1), full(2), compact(3), full(40), full(30)]
+        );
+        // Then the description drops out, leaving the summary the rest
+        assert_eq!(
+            plan_layout(&specs, 60),
+            [compact(1), full(2), compact(3), full(51), Hidden]
+        );
+        // Never below the minimum of the summary, nor are essential columns dropped
+        assert_eq!(
+            plan_layout(&specs, 10),
+            [compact(1), full(2), compact(3), full(20), Hidden]
+        );
+    }
+
+    #[test]
+    fn drops_columns_by_priority_and_the_rightmost_first() {
+        use ColumnPriority::{Essential, Optional};
+
+        let spec = |priority| ColumnSpec {
+            width: ColumnWidth::Fixed,
+            priority,
+            natural: 4,
+            compact: None,
+        };
+        let specs = [
+            spec(Essential),
+            spec(Optional(1)),
+            spec(Optional(0)),
+            spec(Optional(1)),
+        ];
+        let shown = |max_width| {
+            plan_layout(&specs, max_width)
+                .iter()
+                .map(|plan| *plan != ColumnPlan::Hidden)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown(14), [true, true, false, true]);
+        assert_eq!(shown(9), [true, true, false, false]);
+        assert_eq!(shown(1), [true, false, false, false]);
+    }
+
+    #[derive(Debug)]
+    struct CompactActiveColumn;
+
+    impl TableColumn<TestData> for CompactActiveColumn {
+        fn name(&self) -> Cow<'_, str> {
+            "Active".into()
+        }
+        fn format<'a>(&self, data: &'a TestData) -> Cow<'a, str> {
+            if data.active { "active" } else { "inactive" }.into()
+        }
+        fn compact<'a>(&self, data: &'a TestData) -> Option<Cow<'a, str>> {
+            Some(if data.active { "✓" } else { "✗" }.into())
+        }
+    }
+
+    #[derive(Debug)]
+    struct OptionalAgeColumn;
+
+    impl TableColumn<TestData> for OptionalAgeColumn {
+        fn name(&self) -> Cow<'_, str> {
+            "Age".into()
+        }
+        fn format<'a>(&self, data: &'a TestData) -> Cow<'a, str> {
+            data.age.to_string().into()
+        }
+        fn priority(&self) -> ColumnPriority {
+            ColumnPriority::Optional(0)
+        }
+    }
+
+    #[test]
+    fn renders_compact_cells_and_leaves_out_dropped_columns() {
+        let data = create_test_data();
+        let columns: Vec<DynColumn> = vec![
+            Box::new(CompactActiveColumn),
+            Box::new(FlexibleNameColumn),
+            Box::new(OptionalAgeColumn),
+        ];
+        let mut style = TableStyleBasic::new();
+        style.max_width = Some(7);
+        let table = Table::new(style, &columns, &data);
+
+        let result = {
+            let _guard = colored_control().lock().unwrap();
+            colored::control::set_override(false);
+            table.to_string()
+        };
+        assert_eq!(result, "✓ Alice\n✗ Bob\n✓ Char…");
+    }
+
+    #[test]
+    fn truncates_by_display_width() {
+        assert_eq!(truncate("Charlie", 7), "Charlie");
+        assert_eq!(truncate("Charlie", 5), "Char…");
+        assert_eq!(truncate("你好世界", 5), "你好…");
+        assert_eq!(truncate("abc", 1), "…");
+    }
+
+    #[test]
+    fn truncates_between_grapheme_clusters() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        assert_eq!(
+            truncate(&format!("{family} trip"), 4),
+            format!("{family} …")
+        );
+        assert_eq!(truncate(&format!("{family} trip"), 2), "…");
+        assert_eq!(truncate("e\u{301}te\u{301}s", 3), "e\u{301}t…");
+    }
 }
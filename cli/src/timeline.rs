@@ -0,0 +1,410 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact timeline of the working hours of a day, with its events and a "now line".
+
+use jiff::{SignedDuration, Timestamp, Zoned};
+use ratatui::backend::IntoCrossterm;
+use ratatui::crossterm::style::ContentStyle;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::UnicodeWidthChar;
+
+/// Narrowest width the bar is drawn in, narrower terminals get a line of text instead.
+pub const MIN_BAR_WIDTH: u16 = 32;
+
+/// Width of the `HH:MM` labels on both ends of the bar.
+const TIME_WIDTH: u16 = 5;
+
+/// Number of upcoming events labelled beneath the bar.
+const LABELS: usize = 2;
+
+/// Color of the events whose calendar has none.
+const DEFAULT_COLOR: Color = Color::Cyan;
+
+/// Color of the marker for the current time.
+const NOW_COLOR: Color = Color::Red;
+
+/// An event placed on the timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEvent {
+    pub summary: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Color of the calendar of the event.
+    pub color: Option<Color>,
+}
+
+/// What a cell of the bar shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Free,
+    Busy(Color),
+    Overlap(Color),
+    Now,
+}
+
+impl Cell {
+    fn span(self, len: usize, styled: bool) -> Span<'static> {
+        let (glyph, style) = match self {
+            Cell::Free if styled => ('─', Style::new().fg(Color::DarkGray)),
+            Cell::Busy(color) if styled => ('█', Style::new().fg(color)),
+            Cell::Overlap(color) if styled => darker('█', color),
+            Cell::Now if styled => ('┃', Style::new().fg(NOW_COLOR).bold()),
+            Cell::Free => ('-', Style::new()),
+            Cell::Busy(_) => ('=', Style::new()),
+            Cell::Overlap(_) => ('#', Style::new()),
+            Cell::Now => ('|', Style::new()),
+        };
+        Span::styled(glyph.to_string().repeat(len), style)
+    }
+}
+
+/// Renders the timeline of today's `events` within the working hours `window`, `width` columns
+/// wide: a bar with the events and the current time, then the next events with a countdown.
+///
+/// Events are clamped to the window. Without `styled`, the bar is drawn in ASCII so it reads
+/// without colors, and below [`MIN_BAR_WIDTH`] it becomes a line of text.
+pub fn render_timeline(
+    events: &[TimelineEvent],
+    window: &(Zoned, Zoned),
+    now: &Zoned,
+    width: u16,
+    styled: bool,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::with_capacity(1 + LABELS);
+    lines.push(if width >= MIN_BAR_WIDTH {
+        render_bar(events, window, now, width, styled)
+    } else {
+        render_summary(window, now)
+    });
+
+    let mut upcoming: Vec<_> = events
+        .iter()
+        .filter(|e| e.start > now.timestamp())
+        .collect();
+    upcoming.sort_by_key(|e| e.start);
+    if upcoming.is_empty() {
+        let style = if styled {
+            Style::new().italic()
+        } else {
+            Style::new()
+        };
+        lines.push(Line::styled("No more events today", style));
+    }
+    for event in upcoming.into_iter().take(LABELS) {
+        lines.push(render_label(event, now, width, styled));
+    }
+    lines
+}
+
+/// Renders lines with ANSI escapes for the terminal.
+pub fn to_ansi(lines: &[Line<'_>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| {
+                    let style: ContentStyle = line.style.patch(span.style).into_crossterm();
+                    style.apply(span.content.as_ref()).to_string()
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_bar(
+    events: &[TimelineEvent],
+    (start, end): &(Zoned, Zoned),
+    now: &Zoned,
+    width: u16,
+    styled: bool,
+) -> Line<'static> {
+    let cells = width - 2 * (TIME_WIDTH + 1);
+    let secs = end.duration_since(start).as_secs();
+    let bound = |i: u16| {
+        let offset = secs * i64::from(i) / i64::from(cells);
+        start.timestamp() + SignedDuration::from_secs(offset)
+    };
+    let now = now.timestamp();
+
+    let mut spans = vec![Span::raw(format!("{} ", format_time(start)))];
+    let mut run: Option<(Cell, usize)> = None;
+    for i in 0..cells {
+        let (from, to) = (bound(i), bound(i + 1));
+        let cell = if from <= now && now < to {
+            Cell::Now
+        } else {
+            let mut covering = events.iter().filter(|e| {
+                e.start < to && (from < e.end || (e.start == e.end && from <= e.start))
+            });
+            let first = covering.clone().min_by_key(|e| e.start);
+            match (first, covering.nth(1)) {
+                (None, _) => Cell::Free,
+                (Some(e), None) => Cell::Busy(e.color.unwrap_or(DEFAULT_COLOR)),
+                (Some(e), Some(_)) => Cell::Overlap(e.color.unwrap_or(DEFAULT_COLOR)),
+            }
+        };
+        run = match run {
+            Some((last, len)) if last == cell => Some((last, len + 1)),
+            Some((last, len)) => {
+                spans.push(last.span(len, styled));
+                Some((cell, 1))
+            }
+            None => Some((cell, 1)),
+        };
+    }
+    if let Some((last, len)) = run {
+        spans.push(last.span(len, styled));
+    }
+    spans.push(Span::raw(format!(" {}", format_time(end))));
+    Line::from(spans)
+}
+
+fn render_summary((start, end): &(Zoned, Zoned), now: &Zoned) -> Line<'static> {
+    let text = if now < start {
+        format!(
+            "Working hours {}-{} start in {}",
+            format_time(start),
+            format_time(end),
+            format_countdown(now.duration_until(start)),
+        )
+    } else if now < end {
+        format!(
+            "{}, {} of working hours left",
+            format_time(now),
+            format_countdown(now.duration_until(end)),
+        )
+    } else {
+        format!("Working hours ended at {}", format_time(end))
+    };
+    Line::raw(text)
+}
+
+fn render_label(event: &TimelineEvent, now: &Zoned, width: u16, styled: bool) -> Line<'static> {
+    let start = event.start.to_zoned(now.time_zone().clone());
+    let time = format!("{} ", format_time(&start));
+    let countdown = format!(" (in {})", format_countdown(now.duration_until(&start)));
+    let (bullet, bullet_style, time_style, countdown_style) = if styled {
+        let color = event.color.unwrap_or(DEFAULT_COLOR);
+        let dim = Style::new().fg(Color::DarkGray);
+        ("  ● ", Style::new().fg(color), Style::new().bold(), dim)
+    } else {
+        ("  * ", Style::new(), Style::new(), Style::new())
+    };
+
+    let used = [bullet, &time, &countdown]
+        .iter()
+        .map(|s| s.chars().filter_map(UnicodeWidthChar::width).sum::<usize>())
+        .sum::<usize>();
+    let summary = truncate(&event.summary, usize::from(width).saturating_sub(used));
+    Line::from(vec![
+        Span::styled(bullet, bullet_style),
+        Span::styled(time, time_style),
+        Span::raw(summary),
+        Span::styled(countdown, countdown_style),
+    ])
+}
+
+/// A darker shade of `color`, for the cells where events overlap.
+fn darker(glyph: char, color: Color) -> (char, Style) {
+    let color = match color {
+        Color::Rgb(r, g, b) => {
+            let shade = |c: u8| c / 5 * 3;
+            Color::Rgb(shade(r), shade(g), shade(b))
+        }
+        Color::LightRed => Color::Red,
+        Color::LightGreen => Color::Green,
+        Color::LightYellow => Color::Yellow,
+        Color::LightBlue => Color::Blue,
+        Color::LightMagenta => Color::Magenta,
+        Color::LightCyan => Color::Cyan,
+        Color::White => Color::Gray,
+        Color::Gray => Color::DarkGray,
+        color => return (glyph, Style::new().fg(color).add_modifier(Modifier::DIM)),
+    };
+    (glyph, Style::new().fg(color))
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut out = String::new();
+    for c in s.chars() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            out.pop();
+            out.push('…');
+            return out;
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn format_time(zoned: &Zoned) -> String {
+    zoned.strftime("%H:%M").to_string()
+}
+
+/// Formats a duration as `1h 5m`, rounded up to the minute.
+fn format_countdown(duration: SignedDuration) -> String {
+    let mins = (duration.as_secs().max(0) + 59) / 60;
+    match (mins / 60, mins % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+
+    use super::*;
+
+    fn at(h: i8, m: i8) -> Zoned {
+        date(2026, 3, 6)
+            .to_datetime(time(h, m, 0, 0))
+            .in_tz("UTC")
+            .unwrap()
+    }
+
+    fn event(summary: &str, start: (i8, i8), end: (i8, i8), color: Option<Color>) -> TimelineEvent {
+        TimelineEvent {
+            summary: summary.to_string(),
+            start: at(start.0, start.1).timestamp(),
+            end: at(end.0, end.1).timestamp(),
+            color,
+        }
+    }
+
+    fn window() -> (Zoned, Zoned) {
+        (at(9, 0), at(17, 0))
+    }
+
+    fn text(lines: &[Line<'_>]) -> String {
+        lines
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn events() -> Vec<TimelineEvent> {
+        vec![
+            event("Standup", (9, 30), (10, 0), Some(Color::Blue)),
+            event("Design review", (11, 0), (12, 30), Some(Color::Green)),
+            event("Lunch", (12, 0), (13, 0), None),
+            event("Late call", (16, 30), (18, 0), None),
+        ]
+    }
+
+    #[test]
+    fn renders_plain_bar_and_next_events() {
+        // 32 cells of 15 minutes
+        let lines = render_timeline(&events(), &window(), &at(10, 20), 44, false);
+        let expected = "\
+09:00 --==-|--====##==--------------== 17:00
+  * 11:00 Design review (in 40m)
+  * 12:00 Lunch (in 1h 40m)";
+        assert_eq!(text(&lines), expected);
+    }
+
+    #[test]
+    fn renders_styled_bar_with_calendar_colors() {
+        let lines = render_timeline(&events(), &window(), &at(10, 20), 44, true);
+        let bar = lines.first().unwrap();
+        assert_eq!(
+            bar.to_string(),
+            "09:00 ──██─┃──████████──────────────██ 17:00"
+        );
+
+        let styles: Vec<_> = bar
+            .spans
+            .iter()
+            .map(|s| (s.content.chars().count(), s.style))
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                (6, Style::new()),
+                (2, Style::new().fg(Color::DarkGray)),
+                (2, Style::new().fg(Color::Blue)),
+                (1, Style::new().fg(Color::DarkGray)),
+                (1, Style::new().fg(NOW_COLOR).bold()),
+                (2, Style::new().fg(Color::DarkGray)),
+                (4, Style::new().fg(Color::Green)),
+                (2, Style::new().fg(Color::Green).add_modifier(Modifier::DIM)),
+                (2, Style::new().fg(DEFAULT_COLOR)),
+                (14, Style::new().fg(Color::DarkGray)),
+                (2, Style::new().fg(DEFAULT_COLOR)),
+                (6, Style::new()),
+            ]
+        );
+
+        let label = lines.get(1).unwrap();
+        assert_eq!(label.to_string(), "  ● 11:00 Design review (in 40m)");
+        let bullet = label.spans.first().unwrap();
+        assert_eq!(bullet.style, Style::new().fg(Color::Green));
+    }
+
+    #[test]
+    fn clamps_events_outside_the_window() {
+        let events = vec![
+            event("Breakfast", (7, 0), (9, 30), None),
+            event("Dinner", (19, 0), (20, 0), None),
+        ];
+        let lines = render_timeline(&events, &window(), &at(8, 0), 44, false);
+        let expected = "\
+09:00 ==------------------------------ 17:00
+  * 19:00 Dinner (in 11h)";
+        assert_eq!(text(&lines), expected);
+    }
+
+    #[test]
+    fn darkens_overlapping_rgb_colors() {
+        let (_, style) = darker('█', Color::Rgb(30, 144, 255));
+        assert_eq!(style.fg, Some(Color::Rgb(18, 84, 153)));
+    }
+
+    #[test]
+    fn degrades_to_text_when_narrow() {
+        let lines = render_timeline(&events(), &window(), &at(10, 20), 30, false);
+        let expected = "\
+10:20, 6h 40m of working hours left
+  * 11:00 Design rev… (in 40m)
+  * 12:00 Lunch (in 1h 40m)";
+        assert_eq!(text(&lines), expected);
+
+        let lines = render_timeline(&events(), &window(), &at(8, 0), 20, false);
+        assert_eq!(
+            lines.first().unwrap().to_string(),
+            "Working hours 09:00-17:00 start in 1h"
+        );
+
+        let lines = render_timeline(&[], &window(), &at(17, 30), 20, false);
+        let expected = "\
+Working hours ended at 17:00
+No more events today";
+        assert_eq!(text(&lines), expected);
+    }
+
+    #[test]
+    fn truncates_long_summaries_to_the_width() {
+        let events = vec![event("A rather long meeting title", (11, 0), (12, 0), None)];
+        let lines = render_timeline(&events, &window(), &at(10, 0), 30, false);
+        assert_eq!(
+            lines.get(1).unwrap().to_string(),
+            "  * 11:00 A rather lo… (in 1h)"
+        );
+    }
+
+    #[test]
+    fn formats_countdowns_rounded_up_to_the_minute() {
+        assert_eq!(format_countdown(SignedDuration::from_secs(61)), "2m");
+        assert_eq!(format_countdown(SignedDuration::from_mins(60)), "1h");
+        assert_eq!(format_countdown(SignedDuration::from_mins(125)), "2h 5m");
+    }
+}
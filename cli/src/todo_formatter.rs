@@ -2,20 +2,33 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::{HashMap, HashSet};
 use std::{borrow::Cow, fmt};
 
-use aimcal_core::{LooseDateTime, Priority, RangePosition, Todo, TodoStatus};
+use aimcal_core::{
+    Kind, LooseDateTime, OverdueConfig, Priority, SubtaskProgress, Todo, TodoStatus, overdue_age,
+};
 use colored::Color;
 use jiff::{SignedDuration, Zoned};
 
-use crate::table::{PaddingDirection, Table, TableColumn, TableStyleBasic, TableStyleJson};
-use crate::util::{OutputFormat, format_datetime};
+use crate::column::{Column, ColumnData, ColumnDef};
+use crate::table::ColumnPriority::{Essential, Optional};
+use crate::table::ColumnWidth::{Fixed, Flexible};
+use crate::table::PaddingDirection::{Left, Right};
+use crate::table::{
+    ColumnPriority, ColumnWidth, PaddingDirection, Table, TableColumn, TableStyleBasic,
+    TableStyleJson,
+};
+use crate::util::{OutputFormat, format_datetime, item_url};
 
 #[derive(Debug, Clone)]
 pub struct TodoFormatter {
     now: Zoned,
     columns: Vec<TodoColumn>,
     format: OutputFormat,
+    dimmed: HashSet<String>,
+    subtasks: HashMap<String, SubtaskProgress>,
+    overdue: OverdueConfig,
 }
 
 impl TodoFormatter {
@@ -24,9 +37,30 @@ impl TodoFormatter {
             now,
             columns,
             format,
+            dimmed: HashSet::new(),
+            subtasks: HashMap::new(),
+            overdue: OverdueConfig::default(),
         }
     }
 
+    /// Dims the todos with these UIDs, such as those only shown for context.
+    pub fn with_dimmed(mut self, uids: impl IntoIterator<Item = String>) -> Self {
+        self.dimmed = uids.into_iter().collect();
+        self
+    }
+
+    /// Shows the subtasks of todos by their UIDs in [`TodoColumn::Subtasks`].
+    pub fn with_subtasks(mut self, subtasks: HashMap<String, SubtaskProgress>) -> Self {
+        self.subtasks = subtasks;
+        self
+    }
+
+    /// Styles overdue todos by how long they are overdue, with the thresholds of `overdue`.
+    pub fn with_overdue(mut self, overdue: OverdueConfig) -> Self {
+        self.overdue = overdue;
+        self
+    }
+
     pub fn format<'a, T: Todo>(&'a self, todos: &'a [T]) -> Display<'a, T> {
         Display {
             todos,
@@ -50,6 +84,10 @@ impl<T: Todo> fmt::Display for Display<'_, T> {
             .map(|column| ColumnMeta {
                 column,
                 now: self.formatter.now.clone(),
+                format: self.formatter.format,
+                dimmed: &self.formatter.dimmed,
+                subtasks: &self.formatter.subtasks,
+                overdue: &self.formatter.overdue,
             })
             .collect();
 
@@ -66,64 +104,172 @@ impl<T: Todo> fmt::Display for Display<'_, T> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TodoColumn {
+    Calendar,
+    Categories,
+    Description,
     Due,
+    FollowUp,
     Id,
+    Location,
     Priority,
+    Progress,
     ShortId,
     Status,
+    Subtasks,
     Summary,
     Uid,
+    Url,
+    WaitingOn,
+}
+
+impl Column for TodoColumn {
+    const KIND: &'static str = "todo";
+
+    // In a narrow terminal, the optional columns drop out in the order of their rank: the
+    // description, categories, UID, URL, location, calendar, short ID, waiting on, follow-up,
+    // subtasks, progress and priority, see `plan_layout`
+    #[rustfmt::skip]
+    const DEFS: &'static [ColumnDef<Self>] = &[
+        ColumnDef::new("status",      "Status",      Self::Status,      Left,  Fixed,            Essential,    ColumnData::None),
+        ColumnDef::new("id",          "ID",          Self::Id,          Right, Fixed,            Essential,    ColumnData::None),
+        ColumnDef::new("short-id",    "Short ID",    Self::ShortId,     Right, Fixed,            Optional(6),  ColumnData::None),
+        ColumnDef::new("uid",         "UID",         Self::Uid,         Right, Fixed,            Optional(2),  ColumnData::None),
+        ColumnDef::new("priority",    "Priority",    Self::Priority,    Right, Fixed,            Optional(11), ColumnData::None),
+        ColumnDef::new("due",         "Due",         Self::Due,         Left,  Fixed,            Essential,    ColumnData::None),
+        ColumnDef::new("summary",     "Summary",     Self::Summary,     Left,  Flexible(20, 40), Essential,    ColumnData::None),
+        ColumnDef::new("description", "Description", Self::Description, Left,  Flexible(10, 30), Optional(0),  ColumnData::None),
+        ColumnDef::new("calendar",    "Calendar",    Self::Calendar,    Left,  Fixed,            Optional(5),  ColumnData::None),
+        ColumnDef::new("categories",  "Categories",  Self::Categories,  Left,  Flexible(10, 20), Optional(1),  ColumnData::None),
+        ColumnDef::new("location",    "Location",    Self::Location,    Left,  Flexible(10, 20), Optional(4),  ColumnData::None),
+        ColumnDef::new("progress",    "Progress",    Self::Progress,    Left,  Fixed,            Optional(10), ColumnData::None),
+        ColumnDef::new("subtasks",    "Subtasks",    Self::Subtasks,    Right, Fixed,            Optional(9),  ColumnData::Subtasks),
+        ColumnDef::new("waiting-on",  "Waiting on",  Self::WaitingOn,   Left,  Fixed,            Optional(7),  ColumnData::None),
+        ColumnDef::new("follow-up",   "Follow-up",   Self::FollowUp,    Left,  Fixed,            Optional(8),  ColumnData::None),
+        ColumnDef::new("url",         "URL",         Self::Url,         Left,  Flexible(10, 30), Optional(3),  ColumnData::None),
+    ];
 }
 
 #[derive(Debug, Clone)]
 struct ColumnMeta<'a> {
     column: &'a TodoColumn,
     now: Zoned,
+    format: OutputFormat,
+    dimmed: &'a HashSet<String>,
+    subtasks: &'a HashMap<String, SubtaskProgress>,
+    overdue: &'a OverdueConfig,
+}
+
+impl ColumnMeta<'_> {
+    /// The level of a todo overdue by `due`, and how long it is overdue.
+    fn overdue_level(&self, due: &LooseDateTime) -> Option<(usize, SignedDuration)> {
+        let age = overdue_age(due, &self.now)?;
+        Some((self.overdue.level(age), age))
+    }
+
+    /// Whether a todo due at `due` is overdue by all the thresholds.
+    fn is_long_overdue(&self, due: &LooseDateTime) -> bool {
+        self.overdue_level(due)
+            .is_some_and(|(level, _)| level >= self.overdue.max_level())
+    }
+
+    fn format_due<'b>(&self, todo: &'b impl Todo) -> Cow<'b, str> {
+        let Some(due) = todo.due() else {
+            return "".into();
+        };
+        match self.overdue_level(&due) {
+            Some((level, age))
+                if level >= self.overdue.max_level() && self.format == OutputFormat::Table =>
+            {
+                format!("{} (overdue {})", format_datetime(due), format_age(age)).into()
+            }
+            _ => format_datetime(due).into(),
+        }
+    }
+
+    fn get_color_due(&self, due: &LooseDateTime) -> Option<Color> {
+        get_color_due_impl(due, &self.now, self.overdue)
+    }
 }
 
 impl<T: Todo> TableColumn<T> for ColumnMeta<'_> {
     fn name(&self) -> Cow<'_, str> {
-        match self.column {
-            TodoColumn::Due => "Due",
-            TodoColumn::Id => "ID",
-            TodoColumn::Priority => "Priority",
-            TodoColumn::ShortId => "Short ID",
-            TodoColumn::Status => "Status",
-            TodoColumn::Summary => "Summary",
-            TodoColumn::Uid => "UID",
-        }
-        .into()
+        self.column.def().header.into()
     }
 
     fn format<'b>(&self, data: &'b T) -> Cow<'b, str> {
         match self.column {
-            TodoColumn::Due => format_due(data),
+            TodoColumn::Calendar => data.calendar_id().unwrap_or_default(),
+            TodoColumn::Categories => data.categories().join(", ").into(),
+            TodoColumn::Description => format_description(data),
+            TodoColumn::Due => self.format_due(data),
+            TodoColumn::FollowUp => format_follow_up(data),
             TodoColumn::Id => format_id(data),
+            TodoColumn::Location => data.location().unwrap_or_default(),
             TodoColumn::Priority => format_priority(data),
+            TodoColumn::Progress => format_progress(data, self.format),
             TodoColumn::ShortId => format_short_id(data),
             TodoColumn::Status => format_status(data),
+            TodoColumn::Subtasks => format_subtasks(data, self.subtasks),
             TodoColumn::Summary => format_summary(data),
             TodoColumn::Uid => format_uid(data),
+            TodoColumn::Url => data.url().unwrap_or_default(),
+            TodoColumn::WaitingOn => data.waiting_on().unwrap_or_default(),
         }
     }
 
     fn padding_direction(&self) -> PaddingDirection {
-        use TodoColumn::{Id, Priority, ShortId, Uid};
+        self.column.def().alignment
+    }
+
+    fn width(&self) -> ColumnWidth {
+        self.column.def().width
+    }
+
+    fn priority(&self) -> ColumnPriority {
+        self.column.def().priority
+    }
+
+    fn compact<'b>(&self, data: &'b T) -> Option<Cow<'b, str>> {
         match self.column {
-            Id | Priority | Uid | ShortId => PaddingDirection::Right,
-            _ => PaddingDirection::Left,
+            TodoColumn::Due => Some(format_relative(data.due()?, &self.now).into()),
+            TodoColumn::FollowUp => Some(format_relative(data.follow_up()?, &self.now).into()),
+            TodoColumn::Status => Some(format_status_glyph(data).into()),
+            _ => None,
         }
     }
 
     fn get_color(&self, data: &T) -> Option<Color> {
+        if self.dimmed.contains(data.uid().as_ref()) {
+            return Some(Color::BrightBlack);
+        }
         match self.column {
-            TodoColumn::Due => get_color_due(data, &self.now),
+            TodoColumn::Due => self.get_color_due(&data.due()?),
+            TodoColumn::FollowUp => self.get_color_due(&data.follow_up()?),
             TodoColumn::Priority => get_color_priority(),
             _ => None,
         }
     }
+
+    fn is_bold(&self, data: &T) -> bool {
+        match self.column {
+            TodoColumn::Due if !self.dimmed.contains(data.uid().as_ref()) => {
+                data.due().is_some_and(|due| self.is_long_overdue(&due))
+            }
+            _ => false,
+        }
+    }
+
+    fn get_link(&self, data: &T) -> Option<String> {
+        match self.column {
+            TodoColumn::Id | TodoColumn::ShortId | TodoColumn::Uid => {
+                Some(item_url(Kind::Todo, &data.uid()))
+            }
+            TodoColumn::Url => data.url().map(Cow::into_owned),
+            _ => None,
+        }
+    }
 }
 
 fn format_id(todo: &impl Todo) -> Cow<'_, str> {
@@ -139,57 +285,54 @@ fn format_id(todo: &impl Todo) -> Cow<'_, str> {
     }
 }
 
-fn format_due(todo: &impl Todo) -> Cow<'_, str> {
-    todo.due().map_or("".into(), |a| format_datetime(a).into())
+fn format_follow_up(todo: &impl Todo) -> Cow<'_, str> {
+    todo.follow_up()
+        .map_or("".into(), |a| format_datetime(a).into())
 }
 
-fn get_color_due(todo: &impl Todo, now: &Zoned) -> Option<Color> {
-    let due = todo.due()?; // Ensure due date is present
-    get_color_due_impl(&due, now)
+/// Formats how long a todo is overdue, in days once it is a day or more.
+fn format_age(age: SignedDuration) -> String {
+    let mins = age.as_secs().max(0) / 60;
+    match (mins / (24 * 60), mins / 60) {
+        (0, 0) => format!("{mins}m"),
+        (0, h) => format!("{h}h"),
+        (d, _) => format!("{d}d"),
+    }
 }
 
-fn get_color_due_impl(due: &LooseDateTime, now: &Zoned) -> Option<Color> {
-    #[rustfmt::skip]
-    const COLOR_OVERDUE_LT_24H: Color = Color::TrueColor { r: 255, g: 162, b: 162 };
-    #[rustfmt::skip]
-    const COLOR_OVERDUE_LT_48H: Color = Color::TrueColor { r: 251, g: 43, b: 55 };
-    const COLOR_OVERDUE_LT_72H: Color = Color::TrueColor { r: 193, g: 2, b: 7 };
-    #[rustfmt::skip]
-    const COLOR_OVERDUE_GT_72H: Color = Color::TrueColor { r: 130, g: 24, b: 26 };
-    const COLOR_COMING: Color = Color::Yellow;
+/// Formats a date relative to now, for narrow terminals: `today`, or how long until or since it,
+/// such as `3d` ahead or `-5h` overdue.
+fn format_relative(at: LooseDateTime, now: &Zoned) -> String {
+    if let LooseDateTime::DateOnly(date) = at {
+        let days = (date - now.date()).get_days();
+        return match days {
+            0 => "today".to_string(),
+            _ => format!("{days}d"),
+        };
+    }
+    let Some(at) = at.to_timestamp_in(now.time_zone()) else {
+        return format_datetime(at);
+    };
+    let delta = at.duration_since(now.timestamp());
+    let sign = if delta.is_negative() { "-" } else { "" };
+    format!("{sign}{}", format_age(delta.abs()))
+}
 
-    let now_dt = now.datetime();
-    let due_dt = due.with_end_of_day();
-    let same_day = due.date() == now.date();
-
-    match LooseDateTime::position_in_range(&now_dt, &None, &Some(due.clone())) {
-        RangePosition::InRange if same_day => Some(COLOR_COMING), // not due && due in today
-        RangePosition::InRange => None,                           // not due
-        RangePosition::After => {
-            let overdue_lt_24h = due_dt
-                .checked_add(SignedDuration::from_hours(24))
-                .is_ok_and(|boundary| now_dt < boundary);
-            let overdue_lt_48h = due_dt
-                .checked_add(SignedDuration::from_hours(48))
-                .is_ok_and(|boundary| now_dt < boundary);
-            let overdue_lt_72h = due_dt
-                .checked_add(SignedDuration::from_hours(72))
-                .is_ok_and(|boundary| now_dt < boundary);
-
-            if overdue_lt_24h {
-                Some(COLOR_OVERDUE_LT_24H)
-            } else if overdue_lt_48h {
-                Some(COLOR_OVERDUE_LT_48H)
-            } else if overdue_lt_72h {
-                Some(COLOR_OVERDUE_LT_72H)
-            } else {
-                Some(COLOR_OVERDUE_GT_72H)
-            }
-        }
-        pos => {
-            tracing::error!(?due, now = ?now, ?pos, "Invalid state when computing due date color.");
-            None
-        }
+/// The color of a todo due at `due`: yellow if due today or overdue by less than the first
+/// threshold, orange past it, and red once overdue by all of them.
+fn get_color_due_impl(due: &LooseDateTime, now: &Zoned, overdue: &OverdueConfig) -> Option<Color> {
+    const COLOR_COMING: Color = Color::Yellow;
+    const COLOR_OVERDUE: Color = Color::Yellow;
+    #[rustfmt::skip]
+    const COLOR_AGING: Color = Color::TrueColor { r: 255, g: 135, b: 0 };
+    const COLOR_LONG_OVERDUE: Color = Color::Red;
+
+    match overdue_age(due, now).map(|age| overdue.level(age)) {
+        Some(level) if level >= overdue.max_level() => Some(COLOR_LONG_OVERDUE),
+        Some(0) => Some(COLOR_OVERDUE),
+        Some(_) => Some(COLOR_AGING),
+        None if due.date() == now.date() => Some(COLOR_COMING),
+        None => None,
     }
 }
 
@@ -224,10 +367,63 @@ fn format_status(todo: &impl Todo) -> Cow<'_, str> {
     }
 }
 
+/// Formats the status as a single glyph, for narrow terminals.
+fn format_status_glyph(todo: &impl Todo) -> &'static str {
+    match (todo.status(), todo.percent_complete().unwrap_or_default()) {
+        (TodoStatus::Completed, _) | (TodoStatus::InProcess, 100) => "✓",
+        (TodoStatus::NeedsAction, _) | (TodoStatus::InProcess, 0) => "○",
+        (TodoStatus::InProcess, _) => "◐",
+        (TodoStatus::Cancelled, _) => "✗",
+    }
+}
+
+fn format_progress(todo: &impl Todo, format: OutputFormat) -> Cow<'_, str> {
+    /// Number of characters of the progress bar.
+    const BAR_WIDTH: usize = 10;
+
+    match (todo.percent_complete(), format) {
+        (None, _) => "".into(),
+        (Some(percent), OutputFormat::Json) => percent.to_string().into(),
+        (Some(percent), OutputFormat::Table) => {
+            format!("{} {percent:>3}%", progress_bar(percent, BAR_WIDTH)).into()
+        }
+    }
+}
+
+/// Renders `percent` as a bar of `width` characters, using eighth blocks for the last one.
+fn progress_bar(percent: u8, width: usize) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = usize::from(percent.min(100)) * width * 8 / 100;
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(&partial) = PARTIAL.get(eighths % 8)
+        && eighths / 8 < width
+    {
+        bar.push(partial);
+    }
+    format!("{bar:<width$}")
+}
+
 fn format_summary(todo: &impl Todo) -> Cow<'_, str> {
     todo.summary().replace('\n', "↵").into()
 }
 
+fn format_description(todo: &impl Todo) -> Cow<'_, str> {
+    todo.description()
+        .map(|a| a.replace('\n', "↵").into())
+        .unwrap_or_default()
+}
+
+fn format_subtasks<'a>(
+    todo: &impl Todo,
+    subtasks: &HashMap<String, SubtaskProgress>,
+) -> Cow<'a, str> {
+    match subtasks.get(todo.uid().as_ref()) {
+        Some(s) if s.total > 0 => format!("{}/{}", s.done, s.total).into(),
+        _ => "".into(),
+    }
+}
+
 fn format_short_id(todo: &impl Todo) -> Cow<'_, str> {
     todo.short_id()
         .map(|a| a.to_string())
@@ -251,15 +447,16 @@ mod tests {
         let due_date = date(2025, 8, 5);
         let due_time = time(12, 0, 0, 0);
         let due = LooseDateTime::Floating(DateTime::from_parts(due_date, due_time));
+        let overdue = OverdueConfig::default();
 
         #[rustfmt::skip]
         let cases = [
             ("Today before due time", 2025, 8, 5, 12,  0, 0, Some(Color::Yellow)),
-            ("Today after due time",  2025, 8, 5, 14,  0, 0, Some(Color::TrueColor { r: 255, g: 162, b: 162 })),
-            ("Overdue by 23h59m",     2025, 8, 6, 11, 59, 0, Some(Color::TrueColor { r: 255, g: 162, b: 162 })),
-            ("Overdue by 24h",        2025, 8, 6, 12,  0, 0, Some(Color::TrueColor { r: 251, g: 43, b: 55 })),
-            ("Overdue by 48h",        2025, 8, 7, 12,  0, 0, Some(Color::TrueColor { r: 193, g: 2, b: 7 })),
-            ("Overdue by 72h",        2025, 8, 8, 12,  0, 0, Some(Color::TrueColor { r: 130, g: 24, b: 26 })),
+            ("Today after due time",  2025, 8, 5, 14,  0, 0, Some(Color::Yellow)),
+            ("Overdue by 23h59m",     2025, 8, 6, 11, 59, 0, Some(Color::Yellow)),
+            ("Overdue by 24h",        2025, 8, 6, 12,  0, 0, Some(Color::TrueColor { r: 255, g: 135, b: 0 })),
+            ("Overdue by 6d",         2025, 8, 11, 12, 0, 0, Some(Color::TrueColor { r: 255, g: 135, b: 0 })),
+            ("Overdue by 7d",         2025, 8, 12, 12, 0, 0, Some(Color::Red)),
             ("Future date",           2025, 8, 4, 10,  0, 0, None),
         ];
 
@@ -269,8 +466,58 @@ mod tests {
             let now = DateTime::from_parts(date, time)
                 .to_zoned(jiff::tz::TimeZone::system())
                 .unwrap();
-            let color = get_color_due_impl(&due, &now);
+            let color = get_color_due_impl(&due, &now, &overdue);
             assert_eq!(color, expected, "Failed for case: {title}");
         }
     }
+
+    #[test]
+    fn ages_date_only_dues_from_the_end_of_their_day() {
+        let due = LooseDateTime::DateOnly(date(2025, 8, 4));
+        let overdue = OverdueConfig::default();
+        let breakfast = date(2025, 8, 5)
+            .to_datetime(time(8, 0, 0, 0))
+            .to_zoned(jiff::tz::TimeZone::system())
+            .unwrap();
+        assert_eq!(
+            get_color_due_impl(&due, &breakfast, &overdue),
+            Some(Color::Yellow)
+        );
+    }
+
+    #[test]
+    fn formats_overdue_age() {
+        assert_eq!(format_age(SignedDuration::from_mins(5)), "5m");
+        assert_eq!(format_age(SignedDuration::from_hours(23)), "23h");
+        assert_eq!(format_age(SignedDuration::from_hours(12 * 24 + 5)), "12d");
+    }
+
+    #[test]
+    fn formats_dates_relative_to_now() {
+        let now = date(2025, 8, 5)
+            .to_datetime(time(12, 0, 0, 0))
+            .to_zoned(jiff::tz::TimeZone::system())
+            .unwrap();
+        let at = |day, hour| {
+            let dt = DateTime::from_parts(date(2025, 8, day), time(hour, 0, 0, 0));
+            format_relative(LooseDateTime::Floating(dt), &now)
+        };
+        assert_eq!(at(5, 17), "5h");
+        assert_eq!(at(5, 9), "-3h");
+        assert_eq!(at(8, 12), "3d");
+        let day = |day| format_relative(LooseDateTime::DateOnly(date(2025, 8, day)), &now);
+        assert_eq!(day(5), "today");
+        assert_eq!(day(7), "2d");
+        assert_eq!(day(1), "-4d");
+    }
+
+    #[test]
+    fn renders_progress_bar_with_eighth_blocks() {
+        assert_eq!(progress_bar(0, 10), "          ");
+        assert_eq!(progress_bar(50, 10), "█████     ");
+        assert_eq!(progress_bar(55, 10), "█████▌    ");
+        assert_eq!(progress_bar(99, 10), "█████████▉");
+        assert_eq!(progress_bar(100, 10), "██████████");
+        assert_eq!(progress_bar(100, 4), "████");
+    }
 }
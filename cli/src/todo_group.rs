@@ -0,0 +1,481 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use aimcal_core::{
+    Collation, DateRangeAnchor, DateTimeAnchor, LooseDateTime, Priority, RangePosition, Todo,
+    TodoStatus,
+};
+use jiff::Zoned;
+use jiff::civil::{Date, Weekday};
+
+/// The key to group todos by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TodoGroupBy {
+    /// Today, tomorrow, this week, later, scheduled to start later, or no due date
+    DueDate,
+    /// High, medium, low, or no priority
+    Priority,
+    /// The status of the todo
+    Status,
+    /// Each category of the todo, or no category
+    Category,
+}
+
+/// Todos sharing a group key, in the order they were given.
+#[derive(Debug, Clone)]
+pub struct TodoGroup<T> {
+    pub label: Cow<'static, str>,
+    pub todos: Vec<T>,
+}
+
+const DUE_DATE_LABELS: &[&str] = &[
+    "Today",
+    "Tomorrow",
+    "This week",
+    "Later",
+    "Scheduled",
+    "No due date",
+];
+const PRIORITY_LABELS: &[&str] = &["High", "Medium", "Low", "No priority"];
+const STATUS_LABELS: &[&str] = &["Needs action", "In process", "Completed", "Cancelled"];
+const NO_CATEGORY_LABEL: &str = "No category";
+
+/// Splits `todos` into groups ordered by key, keeping the order of the todos within each group.
+///
+/// Overdue todos are grouped with today's, and the week ends on the day before `week_start`.
+/// Todos that start after `now` are scheduled, whatever their due date.
+/// Categories are ordered and matched with `collation`, and a todo with several categories is
+/// listed in each of them. Empty groups are left out.
+pub fn group_todos<T: Todo + Clone>(
+    todos: Vec<T>,
+    by: TodoGroupBy,
+    now: &Zoned,
+    week_start: Weekday,
+    collation: Collation,
+) -> Result<Vec<TodoGroup<T>>, String> {
+    let due_buckets = DueBuckets::new(now, week_start)?;
+    Ok(match by {
+        TodoGroupBy::DueDate => group_by_index(todos, DUE_DATE_LABELS, |todo| {
+            let start = todo.start();
+            match LooseDateTime::position_in_range(&now.datetime(), &start, &None) {
+                RangePosition::Before => DueBuckets::SCHEDULED,
+                _ => due_buckets.index(todo.due().map(|due| due.date())),
+            }
+        }),
+        TodoGroupBy::Priority => group_by_index(todos, PRIORITY_LABELS, |todo| {
+            priority_index(todo.priority())
+        }),
+        TodoGroupBy::Status => {
+            group_by_index(todos, STATUS_LABELS, |todo| status_index(todo.status()))
+        }
+        TodoGroupBy::Category => group_by_category(todos, collation),
+    })
+}
+
+fn group_by_index<T: Todo>(
+    todos: Vec<T>,
+    labels: &[&'static str],
+    index: impl Fn(&T) -> usize,
+) -> Vec<TodoGroup<T>> {
+    let mut groups: Vec<_> = labels
+        .iter()
+        .map(|&label| TodoGroup {
+            label: label.into(),
+            todos: Vec::new(),
+        })
+        .collect();
+    for todo in todos {
+        if let Some(group) = groups.get_mut(index(&todo)) {
+            group.todos.push(todo);
+        }
+    }
+    groups.retain(|group| !group.todos.is_empty());
+    groups
+}
+
+fn group_by_category<T: Todo + Clone>(todos: Vec<T>, collation: Collation) -> Vec<TodoGroup<T>> {
+    let mut groups: Vec<TodoGroup<T>> = Vec::new();
+    let mut uncategorized = Vec::new();
+    for todo in todos {
+        let categories = todo.categories();
+        if categories.is_empty() {
+            uncategorized.push(todo);
+            continue;
+        }
+        for category in categories {
+            // Spellings differing only in case or accents share the group of the first one seen
+            match groups
+                .iter_mut()
+                .find(|g| collation.equivalent(&g.label, &category))
+            {
+                Some(group) => group.todos.push(todo.clone()),
+                None => groups.push(TodoGroup {
+                    label: category.into(),
+                    todos: vec![todo.clone()],
+                }),
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| collation.compare(&a.label, &b.label));
+    if !uncategorized.is_empty() {
+        groups.push(TodoGroup {
+            label: NO_CATEGORY_LABEL.into(),
+            todos: uncategorized,
+        });
+    }
+    groups
+}
+
+/// Last days of the due date buckets.
+#[derive(Debug, Clone, Copy)]
+struct DueBuckets {
+    today: Date,
+    tomorrow: Date,
+    end_of_week: Date,
+}
+
+impl DueBuckets {
+    const SCHEDULED: usize = 4;
+
+    fn new(now: &Zoned, week_start: Weekday) -> Result<Self, String> {
+        let last_day = |anchor: DateTimeAnchor| anchor.resolve_at_end_of_day(now).map(|a| a.date());
+        Ok(Self {
+            today: last_day(DateTimeAnchor::today())?,
+            tomorrow: last_day(DateTimeAnchor::tomorrow())?,
+            end_of_week: DateRangeAnchor::this_week()
+                .resolve_dates(now.date(), week_start)?
+                .1,
+        })
+    }
+
+    fn index(&self, due: Option<Date>) -> usize {
+        match due {
+            Some(due) if due <= self.today => 0,
+            Some(due) if due <= self.tomorrow => 1,
+            Some(due) if due <= self.end_of_week => 2,
+            Some(_) => 3,
+            None => 5,
+        }
+    }
+}
+
+const fn priority_index(priority: Priority) -> usize {
+    match priority {
+        Priority::P1 | Priority::P2 | Priority::P3 => 0,
+        Priority::P4 | Priority::P5 | Priority::P6 => 1,
+        Priority::P7 | Priority::P8 | Priority::P9 => 2,
+        Priority::None => 3,
+    }
+}
+
+const fn status_index(status: TodoStatus) -> usize {
+    match status {
+        TodoStatus::NeedsAction => 0,
+        TodoStatus::InProcess => 1,
+        TodoStatus::Completed => 2,
+        TodoStatus::Cancelled => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_core::LooseDateTime;
+    use jiff::civil::date;
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestTodo {
+        summary: &'static str,
+        start: Option<Date>,
+        due: Option<Date>,
+        priority: Priority,
+        status: TodoStatus,
+        categories: Vec<&'static str>,
+    }
+
+    impl TestTodo {
+        fn new(summary: &'static str) -> Self {
+            Self {
+                summary,
+                start: None,
+                due: None,
+                priority: Priority::None,
+                status: TodoStatus::NeedsAction,
+                categories: Vec::new(),
+            }
+        }
+    }
+
+    impl Todo for TestTodo {
+        fn uid(&self) -> Cow<'_, str> {
+            self.summary.into()
+        }
+
+        fn completed(&self) -> Option<Zoned> {
+            None
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn start(&self) -> Option<LooseDateTime> {
+            self.start.map(LooseDateTime::DateOnly)
+        }
+
+        fn due(&self) -> Option<LooseDateTime> {
+            self.due.map(LooseDateTime::DateOnly)
+        }
+
+        fn percent_complete(&self) -> Option<u8> {
+            None
+        }
+
+        fn priority(&self) -> Priority {
+            self.priority
+        }
+
+        fn status(&self) -> TodoStatus {
+            self.status
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.summary.into()
+        }
+
+        fn categories(&self) -> Vec<String> {
+            self.categories.iter().map(ToString::to_string).collect()
+        }
+    }
+
+    fn summaries(groups: &[TodoGroup<TestTodo>]) -> Vec<(&str, Vec<&str>)> {
+        groups
+            .iter()
+            .map(|g| {
+                (
+                    g.label.as_ref(),
+                    g.todos.iter().map(|t| t.summary).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn due(summary: &'static str, due: Date) -> TestTodo {
+        TestTodo {
+            due: Some(due),
+            ..TestTodo::new(summary)
+        }
+    }
+
+    #[test]
+    fn groups_by_due_date_buckets() {
+        // 2026-10-14 is a Wednesday
+        let now = date(2026, 10, 14).at(10, 0, 0, 0).in_tz("UTC").unwrap();
+        let todos = vec![
+            due("next month", date(2026, 11, 14)),
+            TestTodo::new("someday"),
+            due("overdue", date(2026, 10, 1)),
+            due("sunday", date(2026, 10, 18)),
+            due("tomorrow", date(2026, 10, 15)),
+            due("today", date(2026, 10, 14)),
+            due("next monday", date(2026, 10, 19)),
+        ];
+
+        let groups = group_todos(
+            todos.clone(),
+            TodoGroupBy::DueDate,
+            &now,
+            Weekday::Monday,
+            Collation::Unicode,
+        );
+        assert_eq!(
+            summaries(&groups.unwrap()),
+            [
+                ("Today", vec!["overdue", "today"]),
+                ("Tomorrow", vec!["tomorrow"]),
+                ("This week", vec!["sunday"]),
+                ("Later", vec!["next month", "next monday"]),
+                ("No due date", vec!["someday"]),
+            ]
+        );
+
+        // A week starting on Sunday ends on Saturday
+        let groups = group_todos(
+            todos,
+            TodoGroupBy::DueDate,
+            &now,
+            Weekday::Sunday,
+            Collation::Unicode,
+        );
+        let groups = groups.unwrap();
+        let later = groups.iter().find(|g| g.label == "Later").unwrap();
+        assert!(later.todos.iter().any(|t| t.summary == "sunday"));
+    }
+
+    #[test]
+    fn groups_todos_starting_later_as_scheduled() {
+        let now = date(2026, 10, 14).at(10, 0, 0, 0).in_tz("UTC").unwrap();
+        let starting = |summary, start| TestTodo {
+            start: Some(start),
+            ..TestTodo::new(summary)
+        };
+        let todos = vec![
+            starting("started", date(2026, 10, 14)),
+            TestTodo {
+                due: Some(date(2026, 10, 15)),
+                ..starting("monday", date(2026, 10, 19))
+            },
+            starting("someday", date(2026, 10, 16)),
+            TestTodo::new("unscheduled"),
+        ];
+
+        let groups = group_todos(
+            todos,
+            TodoGroupBy::DueDate,
+            &now,
+            Weekday::Monday,
+            Collation::Unicode,
+        );
+        assert_eq!(
+            summaries(&groups.unwrap()),
+            [
+                ("Scheduled", vec!["monday", "someday"]),
+                ("No due date", vec!["started", "unscheduled"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_by_priority_keeping_order() {
+        let now = Zoned::now();
+        let with_priority = |summary, priority| TestTodo {
+            priority,
+            ..TestTodo::new(summary)
+        };
+        let todos = vec![
+            with_priority("b", Priority::P9),
+            with_priority("a", Priority::P1),
+            with_priority("c", Priority::None),
+            with_priority("d", Priority::P3),
+        ];
+
+        let groups = group_todos(
+            todos,
+            TodoGroupBy::Priority,
+            &now,
+            Weekday::Monday,
+            Collation::Unicode,
+        )
+        .unwrap();
+        assert_eq!(
+            summaries(&groups),
+            [
+                ("High", vec!["a", "d"]),
+                ("Low", vec!["b"]),
+                ("No priority", vec!["c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_by_status() {
+        let now = Zoned::now();
+        let with_status = |summary, status| TestTodo {
+            status,
+            ..TestTodo::new(summary)
+        };
+        let todos = vec![
+            with_status("done", TodoStatus::Completed),
+            with_status("todo", TodoStatus::NeedsAction),
+            with_status("doing", TodoStatus::InProcess),
+        ];
+
+        let groups = group_todos(
+            todos,
+            TodoGroupBy::Status,
+            &now,
+            Weekday::Monday,
+            Collation::Unicode,
+        )
+        .unwrap();
+        assert_eq!(
+            summaries(&groups),
+            [
+                ("Needs action", vec!["todo"]),
+                ("In process", vec!["doing"]),
+                ("Completed", vec!["done"]),
+            ]
+        );
+        assert!(
+            group_todos(
+                Vec::<TestTodo>::new(),
+                TodoGroupBy::Status,
+                &now,
+                Weekday::Monday,
+                Collation::Unicode
+            )
+            .unwrap()
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn groups_by_category_with_collation() {
+        let now = Zoned::now();
+        let with_categories = |summary, categories| TestTodo {
+            categories,
+            ..TestTodo::new(summary)
+        };
+        let todos = vec![
+            with_categories("call", vec!["Zoo"]),
+            with_categories("book", vec!["Ärzte", "family"]),
+            with_categories("loose", Vec::new()),
+            with_categories("pick up", vec!["ärzte"]),
+            with_categories("feed", vec!["zoo", "Family"]),
+        ];
+
+        let groups = group_todos(
+            todos.clone(),
+            TodoGroupBy::Category,
+            &now,
+            Weekday::Monday,
+            Collation::Unicode,
+        );
+        assert_eq!(
+            summaries(&groups.unwrap()),
+            [
+                ("Ärzte", vec!["book", "pick up"]),
+                ("family", vec!["book", "feed"]),
+                ("Zoo", vec!["call", "feed"]),
+                ("No category", vec!["loose"]),
+            ]
+        );
+
+        // Binary collation keeps spellings apart and sorts accented letters last
+        let groups = group_todos(
+            todos,
+            TodoGroupBy::Category,
+            &now,
+            Weekday::Monday,
+            Collation::Binary,
+        );
+        let labels: Vec<_> = groups.unwrap().into_iter().map(|g| g.label).collect();
+        assert_eq!(
+            labels,
+            [
+                "Family",
+                "Zoo",
+                "family",
+                "zoo",
+                "Ärzte",
+                "ärzte",
+                "No category"
+            ]
+        );
+    }
+}
@@ -0,0 +1,424 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU32;
+
+use aimcal_core::{Aim, AimError, Id, LooseDateTime, Priority, SubtaskProgress, Todo, TodoStatus};
+use jiff::{SignedDuration, Zoned};
+
+/// The default of how many levels deep subtasks are indented.
+pub const DEFAULT_MAX_DEPTH: usize = 3;
+
+/// A todo of any type, such as those listed and their ancestors got one by one.
+pub type BoxedTodo = Box<dyn Todo + Send + Sync>;
+
+/// A todo placed below its parent, see [`nest_todos`].
+pub struct NestedTodo {
+    pub todo: BoxedTodo,
+    /// How many levels deep the todo is indented, 0 for the root of a tree.
+    pub depth: usize,
+    /// Whether the todo is only listed as the ancestor of a listed one.
+    pub context: bool,
+    /// The direct subtasks of the todo, if it has any.
+    pub subtasks: Option<SubtaskProgress>,
+}
+
+impl std::fmt::Debug for NestedTodo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NestedTodo")
+            .field("uid", &self.todo.uid())
+            .field("depth", &self.depth)
+            .field("context", &self.context)
+            .field("subtasks", &self.subtasks)
+            .finish()
+    }
+}
+
+/// The summary is indented by two spaces a level, with a glyph before subtasks and the number of
+/// done subtasks after parents.
+impl Todo for NestedTodo {
+    fn short_id(&self) -> Option<NonZeroU32> {
+        self.todo.short_id()
+    }
+
+    fn uid(&self) -> Cow<'_, str> {
+        self.todo.uid()
+    }
+
+    fn completed(&self) -> Option<Zoned> {
+        self.todo.completed()
+    }
+
+    fn description(&self) -> Option<Cow<'_, str>> {
+        self.todo.description()
+    }
+
+    fn start(&self) -> Option<LooseDateTime> {
+        self.todo.start()
+    }
+
+    fn due(&self) -> Option<LooseDateTime> {
+        self.todo.due()
+    }
+
+    fn percent_complete(&self) -> Option<u8> {
+        self.todo.percent_complete()
+    }
+
+    fn priority(&self) -> Priority {
+        self.todo.priority()
+    }
+
+    fn status(&self) -> TodoStatus {
+        self.todo.status()
+    }
+
+    fn summary(&self) -> Cow<'_, str> {
+        let indent = match self.depth {
+            0 => String::new(),
+            depth => format!("{}└ ", "  ".repeat(depth - 1)),
+        };
+        let progress = match self.subtasks {
+            Some(s) if s.total > 0 => format!(" ({}/{})", s.done, s.total),
+            _ => String::new(),
+        };
+        format!("{indent}{}{progress}", self.todo.summary()).into()
+    }
+
+    fn snoozed_from(&self) -> Vec<LooseDateTime> {
+        self.todo.snoozed_from()
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.todo.location()
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.todo.organizer()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.todo.url()
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.todo.categories()
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.todo.color()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.todo.meta()
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.todo.parent_uid()
+    }
+
+    fn in_inbox(&self) -> bool {
+        self.todo.in_inbox()
+    }
+
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        self.todo.waiting_on()
+    }
+
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        self.todo.follow_up()
+    }
+
+    fn estimate(&self) -> Option<SignedDuration> {
+        self.todo.estimate()
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        self.todo.calendar_id()
+    }
+}
+
+/// Nests the listed `todos` below their parents, returning one tree per root in pre-order.
+///
+/// The `ancestors` of listed todos that are not listed themselves are added as context, so a
+/// subtask is never shown without its parents. Roots and the children of each todo keep the order
+/// of the listing, where an ancestor takes the place of its first listed descendant. Todos deeper
+/// than `max_depth` are flattened to it, and links forming a cycle are ignored.
+pub fn nest_todos(
+    todos: Vec<BoxedTodo>,
+    ancestors: Vec<BoxedTodo>,
+    subtasks: &HashMap<String, SubtaskProgress>,
+    max_depth: usize,
+) -> Vec<Vec<NestedTodo>> {
+    let listed = todos.len();
+    let mut index = HashMap::new();
+    let mut nodes = Vec::with_capacity(listed + ancestors.len());
+    for (i, todo) in todos.into_iter().chain(ancestors).enumerate() {
+        let uid = todo.uid().into_owned();
+        if index.contains_key(&uid) {
+            continue;
+        }
+        index.insert(uid, nodes.len());
+        nodes.push((todo, i >= listed));
+    }
+
+    let mut parents: Vec<Option<usize>> = nodes
+        .iter()
+        .map(|(todo, _)| {
+            todo.parent_uid()
+                .and_then(|p| index.get(p.as_ref()).copied())
+        })
+        .collect();
+    break_cycles(&mut parents);
+
+    // Listed todos rank by their position, ancestors by the first listed descendant
+    let mut ranks = vec![usize::MAX; nodes.len()];
+    for i in 0..listed.min(nodes.len()) {
+        let mut node = Some(i);
+        while let Some(n) = node {
+            match ranks.get_mut(n) {
+                Some(rank) if *rank > i => *rank = i,
+                _ => break,
+            }
+            node = parents.get(n).copied().flatten();
+        }
+    }
+
+    let mut children = vec![Vec::new(); nodes.len()];
+    let mut roots = Vec::new();
+    for (i, parent) in parents.iter().enumerate() {
+        match parent.and_then(|p| children.get_mut(p)) {
+            Some(siblings) => siblings.push(i),
+            None => roots.push(i),
+        }
+    }
+    let by_rank = |i: &usize| ranks.get(*i).copied().unwrap_or(usize::MAX);
+    roots.sort_by_key(by_rank);
+    for siblings in &mut children {
+        siblings.sort_by_key(by_rank);
+    }
+
+    let mut nodes: Vec<_> = nodes.into_iter().map(Some).collect();
+    let mut trees = Vec::with_capacity(roots.len());
+    for root in roots {
+        let mut tree = Vec::new();
+        let mut stack = vec![(root, 0)];
+        while let Some((i, depth)) = stack.pop() {
+            let Some((todo, context)) = nodes.get_mut(i).and_then(Option::take) else {
+                continue;
+            };
+            let progress = subtasks.get(todo.uid().as_ref()).copied();
+            tree.push(NestedTodo {
+                todo,
+                depth: depth.min(max_depth),
+                context,
+                subtasks: progress,
+            });
+            if let Some(siblings) = children.get(i) {
+                stack.extend(siblings.iter().rev().map(|&c| (c, depth + 1)));
+            }
+        }
+        trees.push(tree);
+    }
+    trees
+}
+
+/// Gets the ancestors of the `todos` that are not among them, skipping parents that are not
+/// found, such as those of disabled calendars.
+pub async fn fetch_ancestors(aim: &Aim, todos: &[BoxedTodo]) -> Result<Vec<BoxedTodo>, AimError> {
+    let mut seen: HashSet<String> = todos.iter().map(|t| t.uid().into_owned()).collect();
+    let mut pending: Vec<String> = todos
+        .iter()
+        .filter_map(|t| t.parent_uid().map(Cow::into_owned))
+        .collect();
+    let mut ancestors: Vec<BoxedTodo> = Vec::new();
+    while let Some(uid) = pending.pop() {
+        if !seen.insert(uid.clone()) {
+            continue;
+        }
+        match aim.get_todo(&Id::Uid(uid)).await {
+            Ok(todo) => {
+                pending.extend(todo.parent_uid().map(Cow::into_owned));
+                ancestors.push(Box::new(todo));
+            }
+            Err(AimError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(ancestors)
+}
+
+/// Cuts the link closing each cycle of parents, so every todo leads up to a root.
+fn break_cycles(parents: &mut [Option<usize>]) {
+    for start in 0..parents.len() {
+        let mut path = HashSet::from([start]);
+        let mut node = start;
+        while let Some(parent) = parents.get(node).copied().flatten() {
+            if !path.insert(parent) {
+                if let Some(link) = parents.get_mut(node) {
+                    *link = None;
+                }
+                break;
+            }
+            node = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestTodo {
+        uid: &'static str,
+        parent: Option<&'static str>,
+    }
+
+    impl Todo for TestTodo {
+        fn uid(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn completed(&self) -> Option<Zoned> {
+            None
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn due(&self) -> Option<LooseDateTime> {
+            None
+        }
+
+        fn percent_complete(&self) -> Option<u8> {
+            None
+        }
+
+        fn priority(&self) -> Priority {
+            Priority::None
+        }
+
+        fn status(&self) -> TodoStatus {
+            TodoStatus::NeedsAction
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn parent_uid(&self) -> Option<Cow<'_, str>> {
+            self.parent.map(Into::into)
+        }
+    }
+
+    fn todos(todos: &[(&'static str, Option<&'static str>)]) -> Vec<BoxedTodo> {
+        todos
+            .iter()
+            .map(|&(uid, parent)| -> BoxedTodo { Box::new(TestTodo { uid, parent }) })
+            .collect()
+    }
+
+    fn summaries(trees: &[Vec<NestedTodo>]) -> Vec<Vec<String>> {
+        trees
+            .iter()
+            .map(|tree| tree.iter().map(|t| t.summary().into_owned()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn nests_children_below_their_parent_in_listing_order() {
+        let listed = todos(&[
+            ("b-2", Some("b")),
+            ("a", None),
+            ("b", None),
+            ("b-1", Some("b")),
+            ("b-1-x", Some("b-1")),
+        ]);
+
+        let trees = nest_todos(listed, Vec::new(), &HashMap::new(), DEFAULT_MAX_DEPTH);
+
+        assert_eq!(
+            summaries(&trees),
+            [vec!["b", "└ b-2", "└ b-1", "  └ b-1-x"], vec!["a"]]
+        );
+        assert!(trees.iter().flatten().all(|t| !t.context));
+    }
+
+    #[test]
+    fn shows_unlisted_ancestors_as_context() {
+        let listed = todos(&[("a", None), ("b-1-x", Some("b-1"))]);
+        let ancestors = todos(&[("b-1", Some("b")), ("b", None)]);
+
+        let trees = nest_todos(listed, ancestors, &HashMap::new(), DEFAULT_MAX_DEPTH);
+
+        assert_eq!(
+            summaries(&trees),
+            [vec!["a"], vec!["b", "└ b-1", "  └ b-1-x"]]
+        );
+        let context: Vec<_> = trees.iter().flatten().map(|t| t.context).collect();
+        assert_eq!(context, [false, true, true, false]);
+    }
+
+    #[test]
+    fn shows_subtask_progress_of_parents() {
+        let listed = todos(&[("a", None), ("a-1", Some("a"))]);
+        let subtasks = HashMap::from([
+            ("a".to_string(), SubtaskProgress { done: 2, total: 5 }),
+            ("a-1".to_string(), SubtaskProgress { done: 0, total: 0 }),
+        ]);
+
+        let trees = nest_todos(listed, Vec::new(), &subtasks, DEFAULT_MAX_DEPTH);
+
+        assert_eq!(summaries(&trees), [vec!["a (2/5)", "└ a-1"]]);
+    }
+
+    #[test]
+    fn flattens_todos_deeper_than_max_depth() {
+        let listed = todos(&[
+            ("a", None),
+            ("b", Some("a")),
+            ("c", Some("b")),
+            ("d", Some("c")),
+        ]);
+
+        let trees = nest_todos(listed, Vec::new(), &HashMap::new(), 2);
+
+        assert_eq!(summaries(&trees), [vec!["a", "└ b", "  └ c", "  └ d"]]);
+    }
+
+    #[test]
+    fn tolerates_cycles() {
+        let listed = todos(&[
+            ("a", Some("c")),
+            ("b", Some("a")),
+            ("c", Some("b")),
+            ("d", Some("d")),
+        ]);
+
+        let trees = nest_todos(listed, Vec::new(), &HashMap::new(), DEFAULT_MAX_DEPTH);
+
+        let all: Vec<_> = trees
+            .iter()
+            .flatten()
+            .map(|t| t.uid().into_owned())
+            .collect();
+        assert_eq!(all.len(), 4);
+        assert_eq!(summaries(&trees).last().unwrap(), &["d"]);
+    }
+
+    #[test]
+    fn keeps_duplicated_todos_once() {
+        let listed = todos(&[("a", None), ("a-1", Some("a"))]);
+        let ancestors = todos(&[("a", None)]);
+
+        let trees = nest_todos(listed, ancestors, &HashMap::new(), DEFAULT_MAX_DEPTH);
+
+        assert_eq!(summaries(&trees), [vec!["a", "└ a-1"]]);
+        assert!(trees.iter().flatten().all(|t| !t.context));
+    }
+}
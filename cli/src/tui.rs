@@ -4,6 +4,7 @@
 
 mod app;
 mod component;
+mod component_date_picker;
 mod component_form;
 mod component_form_util;
 mod component_page;
@@ -11,6 +12,7 @@ mod dispatcher;
 mod event_editor;
 mod event_store;
 mod event_todo_editor;
+mod state;
 mod todo_editor;
 mod todo_store;
 
@@ -22,13 +24,15 @@ use aimcal_core::{Aim, Event, EventDraft, EventPatch, Kind, Todo, TodoDraft, Tod
 use ratatui::Terminal;
 use ratatui::crossterm::event::{self, KeyEventKind};
 use ratatui::layout::Rect;
-use ratatui::prelude::Backend;
+use ratatui::prelude::{Backend, Color, Line, Stylize};
+use ratatui::widgets::Clear;
 
 use crate::tui::component::{Component, Message};
 use crate::tui::dispatcher::Dispatcher;
 use crate::tui::event_editor::new_event_editor;
 use crate::tui::event_store::EventStore;
 use crate::tui::event_todo_editor::{EventTodoStore, new_event_todo_editor};
+use crate::tui::state::{STATE_FILE, StateFile};
 use crate::tui::todo_editor::new_todo_editor;
 use crate::tui::todo_store::TodoStore;
 
@@ -57,7 +61,8 @@ pub fn patch_event(
 }
 
 pub fn draft_todo(aim: &mut Aim, draft: TodoDraft) -> Result<Option<TodoDraft>, Box<dyn Error>> {
-    let store = TodoStore::from_draft(draft);
+    let priority = aim.todo_draft_priority(&draft);
+    let store = TodoStore::from_draft(draft, priority);
     let store = run_todo_editor(aim, store)?;
     if store.submit {
         store.submit_draft(aim).map(Some)
@@ -86,8 +91,13 @@ pub fn draft_event_or_todo(
     event_draft: EventDraft,
     todo_draft: TodoDraft,
 ) -> Result<Option<EventOrTodoDraft>, Box<dyn Error>> {
-    let store = EventTodoStore::new(kind, event_draft, todo_draft);
-    let store = run_event_todo_editor(aim, store)?;
+    let state = aim
+        .state_dir()
+        .map(|dir| StateFile::load(dir.join(STATE_FILE)));
+    let priority = aim.todo_draft_priority(&todo_draft);
+    let store = EventTodoStore::new(kind, event_draft, todo_draft, priority, state);
+    let mut store = run_event_todo_editor(aim, store)?;
+    store.save_state();
     if store.submit {
         store.submit_draft(aim).map(Some)
     } else {
@@ -99,13 +109,14 @@ macro_rules! run_editor {
     ($fn: ident, $view: ident, $store: ident) => {
         fn $fn(aim: &mut Aim, store: $store) -> Result<$store, Box<dyn Error>> {
             let store = Rc::new(RefCell::new(store));
+            let week_start = aim.week_start();
 
             let result = {
                 let mut dispatcher = Dispatcher::new();
                 $store::register_to(store.clone(), &mut dispatcher);
 
                 ratatui::run(|mut terminal| {
-                    let mut app = App::new($view(), dispatcher, &store, &mut terminal);
+                    let mut app = App::new($view(week_start), dispatcher, &store, &mut terminal);
 
                     loop {
                         if let Err(e) = app.draw(&store, &mut terminal) {
@@ -114,7 +125,18 @@ macro_rules! run_editor {
 
                         match app.read_event(&store) {
                             Err(e) => break Err(e),
-                            Ok(Some(Message::Exit)) => break Ok(()),
+                            Ok(Some(Message::Exit)) => {
+                                let mut store = store.borrow_mut();
+                                if !store.submit {
+                                    break Ok(());
+                                }
+                                // Keep the editor open on invalid input, so it can be fixed in place
+                                if store.validate(aim) {
+                                    break Ok(());
+                                }
+                                store.submit = false;
+                                app.error = Some("Fix the marked fields to submit".to_string());
+                            }
                             Ok(_) => {} // Continue the loop to render the next frame
                         }
                     }
@@ -139,6 +161,7 @@ struct App<S, C: Component<S>> {
     dispatcher: Dispatcher,
     area: Rect, // TODO: support resize
     cursor_pos: Option<(u16, u16)>,
+    error: Option<String>, // shown on the last line until the next key press
     view: C,
     _phantom: std::marker::PhantomData<S>,
 }
@@ -162,6 +185,7 @@ impl<S, C: Component<S>> App<S, C> {
             dispatcher,
             area,
             cursor_pos: view.get_cursor_position(store, area),
+            error: None,
             view,
             _phantom: std::marker::PhantomData,
         }
@@ -176,6 +200,13 @@ impl<S, C: Component<S>> App<S, C> {
             self.area = frame.area();
             self.view.render(store, frame.area(), frame.buffer_mut());
 
+            if let Some(error) = &self.error {
+                let area = frame.area();
+                let line = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+                frame.render_widget(Clear, line);
+                frame.render_widget(Line::from(format!("Error: {error}")).fg(Color::Red), line);
+            }
+
             if let Some(pos) = self.cursor_pos {
                 frame.set_cursor_position(pos);
             }
@@ -186,6 +217,8 @@ impl<S, C: Component<S>> App<S, C> {
     pub fn read_event(&mut self, store: &RefCell<S>) -> Result<Option<Message>, Box<dyn Error>> {
         Ok(match event::read()? {
             event::Event::Key(e) if e.kind == KeyEventKind::Press => {
+                self.error = None;
+
                 // Handle key events for the current component
                 match self.view.on_key(&mut self.dispatcher, store, self.area, e) {
                     Some(msg) => match msg {
@@ -0,0 +1,330 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+
+use aimcal_core::LooseDateTime;
+use jiff::civil::{Date, DateTime, Time, Weekday};
+use jiff::{ToSpan, Zoned};
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::symbols::border;
+use ratatui::widgets::{Block, Clear, Paragraph};
+
+use crate::tui::component::{Component, Message};
+use crate::tui::component_form::{Access, FormItem, FormItemState, Input};
+use crate::tui::dispatcher::Dispatcher;
+use crate::util::{format_datetime, parse_datetime};
+
+/// A text input for dates, which opens a month calendar on `<Enter>`.
+pub struct DateInput<S, A: Access<S, String>> {
+    input: Input<S, A>,
+    week_start: Weekday,
+    picker: Option<MonthPicker>,
+}
+
+impl<S, A: Access<S, String>> DateInput<S, A> {
+    pub fn new(title: &(impl ToString + ?Sized), week_start: Weekday) -> Self {
+        Self {
+            input: Input::new(title),
+            week_start,
+            picker: None,
+        }
+    }
+}
+
+impl<S, A: Access<S, String>> Component<S> for DateInput<S, A> {
+    fn render(&self, store: &RefCell<S>, area: Rect, buf: &mut Buffer) {
+        self.input.render(store, area, buf);
+    }
+
+    fn get_cursor_position(&self, store: &RefCell<S>, area: Rect) -> Option<(u16, u16)> {
+        match self.picker {
+            Some(_) => None, // the calendar highlights its own cursor
+            None => self.input.get_cursor_position(store, area),
+        }
+    }
+
+    fn on_key(
+        &mut self,
+        dispatcher: &mut Dispatcher,
+        store: &RefCell<S>,
+        area: Rect,
+        event: KeyEvent,
+    ) -> Option<Message> {
+        let Some(picker) = &mut self.picker else {
+            if matches!(self.input.item_state(store), FormItemState::Active)
+                && event.code == KeyCode::Enter
+            {
+                let now = Zoned::now();
+                let value = parse_datetime(&now, &A::get(store)).ok().flatten();
+                self.picker = Some(MonthPicker::new(
+                    now.date(),
+                    value.as_ref(),
+                    self.week_start,
+                ));
+                return Some(Message::CursorUpdated);
+            }
+            return self.input.on_key(dispatcher, store, area, event);
+        };
+
+        match event.code {
+            KeyCode::Enter => {
+                A::set(dispatcher, picker.value());
+                self.picker = None;
+                self.input.activate(dispatcher, store); // reset the text cursor
+                Some(Message::CursorUpdated)
+            }
+            KeyCode::Esc => {
+                self.picker = None;
+                Some(Message::CursorUpdated)
+            }
+            _ => {
+                picker.on_key(event.code);
+                Some(Message::Handled) // swallow everything else while the calendar is open
+            }
+        }
+    }
+
+    fn activate(&mut self, dispatcher: &mut Dispatcher, store: &RefCell<S>) {
+        self.input.activate(dispatcher, store);
+    }
+
+    fn deactivate(&mut self, dispatcher: &mut Dispatcher, store: &RefCell<S>) {
+        self.picker = None;
+        self.input.deactivate(dispatcher, store);
+    }
+}
+
+impl<S, A: Access<S, String>> FormItem<S> for DateInput<S, A> {
+    fn item_title(&self, store: &RefCell<S>) -> &str {
+        self.input.item_title(store)
+    }
+
+    fn item_state(&self, store: &RefCell<S>) -> FormItemState {
+        self.input.item_state(store)
+    }
+
+    fn item_error(&self, store: &RefCell<S>) -> Option<String> {
+        self.input.item_error(store)
+    }
+
+    fn render_overlay(&self, _store: &RefCell<S>, area: Rect, bounds: Rect, buf: &mut Buffer) {
+        if let Some(picker) = &self.picker {
+            picker.render(area, bounds, buf);
+        }
+    }
+}
+
+/// Month grid state of an open date picker.
+#[derive(Debug, Clone, Copy)]
+struct MonthPicker {
+    cursor: Date,
+    selected: Option<Date>,
+    time: Option<Time>,
+    today: Date,
+    week_start: Weekday,
+}
+
+// 7 columns of 2-digit days, plus separators and borders
+const WIDTH_WIDE: u16 = 7 * 3 - 1 + 2;
+const WIDTH_COMPACT: u16 = 7 * 2 + 2;
+// weekday header and up to 6 weeks, plus borders
+const HEIGHT: u16 = 1 + 6 + 2;
+
+impl MonthPicker {
+    fn new(today: Date, value: Option<&LooseDateTime>, week_start: Weekday) -> Self {
+        let selected = value.map(LooseDateTime::date);
+        Self {
+            cursor: selected.unwrap_or(today),
+            selected,
+            time: value.and_then(LooseDateTime::time),
+            today,
+            week_start,
+        }
+    }
+
+    fn on_key(&mut self, code: KeyCode) {
+        let moved = match code {
+            KeyCode::Left => self.cursor.checked_sub(1.day()),
+            KeyCode::Right => self.cursor.checked_add(1.day()),
+            KeyCode::Up => self.cursor.checked_sub(1.week()),
+            KeyCode::Down => self.cursor.checked_add(1.week()),
+            KeyCode::PageUp => self.cursor.checked_sub(1.month()),
+            KeyCode::PageDown => self.cursor.checked_add(1.month()),
+            KeyCode::Char('t') => Ok(self.today),
+            _ => Ok(self.cursor),
+        };
+        if let Ok(date) = moved {
+            self.cursor = date;
+        }
+    }
+
+    /// The field value for the date under the cursor, keeping the original time of day.
+    fn value(&self) -> String {
+        format_datetime(match self.time {
+            Some(time) => LooseDateTime::Floating(DateTime::from_parts(self.cursor, time)),
+            None => LooseDateTime::DateOnly(self.cursor),
+        })
+    }
+
+    fn render(&self, area: Rect, bounds: Rect, buf: &mut Buffer) {
+        let compact = bounds.width < WIDTH_WIDE;
+        let width = if compact { WIDTH_COMPACT } else { WIDTH_WIDE };
+
+        // below the input line if possible, otherwise above it
+        let input_y = area.y + 1;
+        let y = if input_y + 1 + HEIGHT <= bounds.bottom() {
+            input_y + 1
+        } else {
+            input_y.saturating_sub(HEIGHT).max(bounds.y)
+        };
+        let x = (area.x + 2).min(bounds.right().saturating_sub(width));
+        let popup = Rect::new(x, y, width, HEIGHT).intersection(bounds);
+
+        let title = self
+            .cursor
+            .strftime(if compact { " %b %Y " } else { " %B %Y " })
+            .to_string();
+        let block = Block::bordered()
+            .border_set(border::ROUNDED)
+            .title(Line::from(title.bold()).centered())
+            .fg(Color::Blue);
+
+        let sep = if compact { "" } else { " " };
+        let mut lines = Vec::with_capacity(7);
+        let header = weekdays(self.week_start).map(|d| weekday_abbr(d).to_string());
+        lines.push(Line::from(header.collect::<Vec<_>>().join(sep)).fg(Color::Gray));
+        for week in month_weeks(self.cursor, self.week_start) {
+            let mut spans = Vec::with_capacity(2 * week.len());
+            for (i, day) in week.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(sep));
+                }
+                spans.push(match day {
+                    Some(d) => Span::styled(format!("{:>2}", d.day()), self.day_style(*d)),
+                    None => Span::raw("  "),
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+
+        Clear.render(popup, buf);
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+        Paragraph::new(lines).fg(Color::White).render(inner, buf);
+    }
+
+    fn day_style(&self, day: Date) -> Style {
+        let mut style = Style::new();
+        if day == self.today {
+            style = style.fg(Color::Yellow).bold();
+        }
+        if Some(day) == self.selected {
+            style = style.underlined();
+        }
+        if day == self.cursor {
+            style = style.fg(Color::Black).bg(Color::Blue);
+        }
+        style
+    }
+}
+
+/// Splits the month containing `date` into weeks starting at `week_start`.
+fn month_weeks(date: Date, week_start: Weekday) -> Vec<[Option<Date>; 7]> {
+    let first = date.first_of_month();
+    let mut weeks = Vec::with_capacity(6);
+    let mut week = [None; 7];
+    let mut col = usize::try_from(first.weekday().since(week_start)).unwrap_or_default();
+    let mut day = Some(first);
+    while let Some(d) = day.filter(|d| d.month() == first.month()) {
+        if let Some(slot) = week.get_mut(col) {
+            *slot = Some(d);
+        }
+        col += 1;
+        if col == week.len() {
+            weeks.push(week);
+            week = [None; 7];
+            col = 0;
+        }
+        day = d.tomorrow().ok();
+    }
+    if col > 0 {
+        weeks.push(week);
+    }
+    weeks
+}
+
+fn weekdays(week_start: Weekday) -> impl Iterator<Item = Weekday> {
+    week_start.cycle_forward().take(7)
+}
+
+const fn weekday_abbr(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "Mo",
+        Weekday::Tuesday => "Tu",
+        Weekday::Wednesday => "We",
+        Weekday::Thursday => "Th",
+        Weekday::Friday => "Fr",
+        Weekday::Saturday => "Sa",
+        Weekday::Sunday => "Su",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+
+    use super::*;
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn month_weeks_spans_six_weeks() {
+        // 2026-08-01 is a Saturday and August has 31 days
+        let weeks = month_weeks(date(2026, 8, 15), Weekday::Monday);
+        assert_eq!(weeks.len(), 6);
+        assert_eq!(weeks[0][5], Some(date(2026, 8, 1)));
+        assert_eq!(weeks[5][0], Some(date(2026, 8, 31)));
+        assert_eq!(weeks[5][1], None);
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn month_weeks_respects_week_start() {
+        // 2026-02-01 is a Sunday and February has 28 days
+        let weeks = month_weeks(date(2026, 2, 1), Weekday::Sunday);
+        assert_eq!(weeks.len(), 4);
+        assert_eq!(weeks[0][0], Some(date(2026, 2, 1)));
+        assert_eq!(weeks[3][6], Some(date(2026, 2, 28)));
+
+        let weeks = month_weeks(date(2026, 2, 1), Weekday::Monday);
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0][6], Some(date(2026, 2, 1)));
+    }
+
+    #[test]
+    fn month_picker_navigates_and_keeps_time() {
+        let value = LooseDateTime::Floating(date(2026, 1, 31).at(14, 30, 0, 0));
+        let mut picker = MonthPicker::new(date(2026, 1, 10), Some(&value), Weekday::Monday);
+        assert_eq!(picker.time, Some(time(14, 30, 0, 0)));
+
+        picker.on_key(KeyCode::PageDown);
+        assert_eq!(picker.cursor, date(2026, 2, 28));
+        picker.on_key(KeyCode::Down);
+        assert_eq!(picker.cursor, date(2026, 3, 7));
+        assert_eq!(picker.value(), "2026-03-07 14:30");
+
+        picker.on_key(KeyCode::Char('t'));
+        picker.on_key(KeyCode::Left);
+        assert_eq!(picker.value(), "2026-01-09 14:30");
+    }
+
+    #[test]
+    fn month_picker_starts_at_today_without_value() {
+        let mut picker = MonthPicker::new(date(2026, 10, 15), None, Weekday::Sunday);
+        picker.on_key(KeyCode::Right);
+        assert_eq!(picker.selected, None);
+        assert_eq!(picker.value(), "2026-10-16");
+    }
+}
@@ -2,8 +2,9 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+use aimcal_core::AimError;
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::*;
 use ratatui::widgets::{Clear, Paragraph};
@@ -88,6 +89,15 @@ impl<S, C: FormItem<S>> Component<S> for Form<S, C> {
                 is_last = false;
             }
         }
+
+        // draw popups of the active item on top of all items
+        if let Some((item, item_area)) = self
+            .items
+            .get(self.item_index)
+            .zip(areas.get(self.item_index))
+        {
+            item.render_overlay(store, *item_area, area, buf);
+        }
     }
 
     fn get_cursor_position(&self, store: &RefCell<S>, area: Rect) -> Option<(u16, u16)> {
@@ -152,6 +162,14 @@ impl<S, C: FormItem<S>> Component<S> for Form<S, C> {
 pub trait FormItem<S>: Component<S> {
     fn item_title(&self, store: &RefCell<S>) -> &str;
     fn item_state(&self, store: &RefCell<S>) -> FormItemState;
+
+    /// Why the value of the item cannot be submitted, shown next to its title.
+    fn item_error(&self, _store: &RefCell<S>) -> Option<String> {
+        None
+    }
+
+    /// Renders a popup of the item above the whole form, clipped to `bounds`.
+    fn render_overlay(&self, _store: &RefCell<S>, _area: Rect, _bounds: Rect, _buf: &mut Buffer) {}
 }
 
 impl<S> FormItem<S> for Box<dyn FormItem<S>> {
@@ -162,6 +180,14 @@ impl<S> FormItem<S> for Box<dyn FormItem<S>> {
     fn item_state(&self, store: &RefCell<S>) -> FormItemState {
         (**self).item_state(store)
     }
+
+    fn item_error(&self, store: &RefCell<S>) -> Option<String> {
+        (**self).item_error(store)
+    }
+
+    fn render_overlay(&self, store: &RefCell<S>, area: Rect, bounds: Rect, buf: &mut Buffer) {
+        (**self).render_overlay(store, area, bounds, buf);
+    }
 }
 
 pub enum FormItemState {
@@ -178,6 +204,65 @@ pub enum FormItemState {
 pub trait Access<S, T: ToOwned> {
     fn get(store: &RefCell<S>) -> T;
     fn set(dispatcher: &mut Dispatcher, value: T) -> bool;
+
+    /// Why the value cannot be submitted, if the store found it invalid.
+    fn error(_store: &RefCell<S>) -> Option<String> {
+        None
+    }
+}
+
+/// Whether a form creates an item or edits one, which differ in the fields required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormMode {
+    /// A new item, which needs a summary.
+    Create,
+
+    /// Changes to an item, where every field may be left as it is.
+    Patch,
+}
+
+/// Errors found in the fields of a form on submit, by field name, shown next to the fields
+/// until they are edited.
+#[derive(Debug, Default)]
+pub struct FieldErrors(HashMap<&'static str, String>);
+
+impl FieldErrors {
+    pub fn get(&self, field: &str) -> Option<String> {
+        self.0.get(field).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Forgets the error of a field once it is edited.
+    pub fn edited(&mut self, field: &str) {
+        self.0.remove(field);
+    }
+
+    /// Records an error if the field is empty.
+    pub fn require(&mut self, field: &'static str, value: &str) {
+        if value.trim().is_empty() {
+            self.0.insert(field, "required".to_string());
+        }
+    }
+
+    /// Records the error of parsing a field, under the field the error names if it does.
+    pub fn check<T>(&mut self, field: &'static str, result: Result<T, AimError>) {
+        match result {
+            Ok(_) => {}
+            Err(AimError::InvalidInput { field, reason }) => {
+                self.0.entry(field).or_insert(reason);
+            }
+            Err(e) => {
+                self.0.entry(field).or_insert_with(|| e.to_string());
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -284,6 +369,10 @@ impl<S, A: Access<S, String>> FormItem<S> for Input<S, A> {
             FormItemState::Inactive
         }
     }
+
+    fn item_error(&self, store: &RefCell<S>) -> Option<String> {
+        A::error(store)
+    }
 }
 
 #[derive(Debug)]
@@ -401,7 +490,7 @@ impl<S, T: Eq + Clone, A: Access<S, T>> FormItem<S> for RadioGroup<S, T, A> {
 const S_STEP_ACTIVE: &str = "◆";
 const S_STEP_INACTIVE: &str = "◇";
 // const S_STEP_CANCEL: &str = "■";
-// const S_STEP_ERROR: &str = "▲";
+const S_STEP_ERROR: &str = "▲";
 
 // const S_SIDER_TOP: &str = "┌";
 const S_SIDER_CONNECTOR: &str = "│";
@@ -414,21 +503,25 @@ fn item_render<S>(
     area: Rect,
     buf: &mut Buffer,
 ) {
+    let error = item.item_error(store);
     let color = match item.item_state(store) {
+        FormItemState::Invisible => return,
+        _ if error.is_some() => Color::Red,
         FormItemState::Active => Color::Blue,
         FormItemState::Inactive => Color::Gray,
-        FormItemState::Invisible => return,
     };
 
     let area_title = Rect::new(area.x + 2, area.y, area.width.saturating_sub(2), 1);
     Clear.render(area_title, buf);
-    Paragraph::new(item.item_title(store))
-        .bold()
-        .fg(color)
-        .render(area_title, buf);
+    let mut title = vec![Span::from(item.item_title(store)).bold().fg(color)];
+    if let Some(error) = error.as_deref() {
+        title.push(Span::from(format!("  {error}")).fg(color));
+    }
+    Paragraph::new(Line::from(title)).render(area_title, buf);
 
     if let Some(c) = buf.cell_mut((area.x, area.y)) {
         let symbol = match item.item_state(store) {
+            _ if error.is_some() => S_STEP_ERROR,
             FormItemState::Active => S_STEP_ACTIVE,
             FormItemState::Inactive => S_STEP_INACTIVE,
             FormItemState::Invisible => unreachable!(),
@@ -467,3 +560,34 @@ fn item_inner(area: Rect) -> Rect {
 fn item_is_visible<S>(item: &impl FormItem<S>, store: &RefCell<S>) -> bool {
     !matches!(item.item_state(store), FormItemState::Invisible)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_errors_are_recorded_by_field_until_edited() {
+        let mut errors = FieldErrors::default();
+        errors.require("summary", "  ");
+        errors.require("description", "Notes");
+        errors.check::<()>(
+            "due",
+            Err(AimError::InvalidInput {
+                field: "start",
+                reason: "invalid date".to_string(),
+            }),
+        );
+        errors.check("end", Ok(()));
+
+        assert_eq!(errors.get("summary").as_deref(), Some("required"));
+        assert_eq!(errors.get("description"), None);
+        assert_eq!(errors.get("start").as_deref(), Some("invalid date"));
+        assert_eq!(errors.get("end"), None);
+
+        errors.edited("summary");
+        assert_eq!(errors.get("summary"), None);
+        assert!(!errors.is_empty());
+        errors.clear();
+        assert!(errors.is_empty());
+    }
+}
@@ -83,6 +83,14 @@ where
             FormItemState::Invisible
         }
     }
+
+    fn item_error(&self, store: &RefCell<S>) -> Option<String> {
+        self.item.item_error(store)
+    }
+
+    fn render_overlay(&self, store: &RefCell<S>, area: Rect, bounds: Rect, buf: &mut Buffer) {
+        self.item.render_overlay(store, area, bounds, buf);
+    }
 }
 
 pub struct FormItemSwitch<S, T, K, F>
@@ -181,6 +189,22 @@ where
             self.off.item_state(store)
         }
     }
+
+    fn item_error(&self, store: &RefCell<S>) -> Option<String> {
+        if (self.on_or_off)(store) {
+            self.on.item_error(store)
+        } else {
+            self.off.item_error(store)
+        }
+    }
+
+    fn render_overlay(&self, store: &RefCell<S>, area: Rect, bounds: Rect, buf: &mut Buffer) {
+        if (self.on_or_off)(store) {
+            self.on.render_overlay(store, area, bounds, buf);
+        } else {
+            self.off.render_overlay(store, area, bounds, buf);
+        }
+    }
 }
 
 /// An access that converts between `Option<T>` and `String`, where `T` is a positive integer type.
@@ -217,4 +241,8 @@ where
             false
         }
     }
+
+    fn error(s: &RefCell<S>) -> Option<String> {
+        A::error(s)
+    }
 }
@@ -56,9 +56,12 @@ impl<S, C: Component<S>> Component<S> for SinglePage<S, C> {
         area: Rect,
         event: KeyEvent,
     ) -> Option<Message> {
-        // match global key events first
+        // match global key events first, letting an open popup close on Esc
         match event.code {
-            KeyCode::Esc => return Some(Message::Exit),
+            KeyCode::Esc => {
+                let msg = self.inner.on_key(dispatcher, store, area, event);
+                return msg.or(Some(Message::Exit));
+            }
             KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
                 return Some(Message::Exit);
             }
@@ -192,9 +195,18 @@ impl<S, C: Component<S>, A: Access<S, Kind>> Component<S> for TabPages<S, C, A>
         area: Rect,
         event: KeyEvent,
     ) -> Option<Message> {
-        // match global key events first
+        // match global key events first, letting an open popup close on Esc
         match event.code {
-            KeyCode::Esc => return Some(Message::Exit),
+            KeyCode::Esc => {
+                let msg = match self.active_index(store) {
+                    Some(i) if !self.tab_active => self
+                        .pages
+                        .get_mut(i)
+                        .and_then(|page| page.on_key(dispatcher, store, area, event)),
+                    _ => None,
+                };
+                return msg.or(Some(Message::Exit));
+            }
             KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
                 return Some(Message::Exit);
             }
@@ -5,22 +5,27 @@
 use std::cell::RefCell;
 
 use aimcal_core::EventStatus;
+use jiff::civil::Weekday;
 
+use crate::tui::component_date_picker::DateInput;
 use crate::tui::component_form::{Access, Form, FormItem, Input, RadioGroup};
 use crate::tui::component_page::SinglePage;
 use crate::tui::dispatcher::{Action, Dispatcher};
 use crate::tui::event_store::EventStoreLike;
 
-pub fn new_event_editor<S: EventStoreLike + 'static>()
--> SinglePage<S, Form<S, Box<dyn FormItem<S>>>> {
-    SinglePage::new(&"Event Editor", new_event_form())
+pub fn new_event_editor<S: EventStoreLike + 'static>(
+    week_start: Weekday,
+) -> SinglePage<S, Form<S, Box<dyn FormItem<S>>>> {
+    SinglePage::new(&"Event Editor", new_event_form(week_start))
 }
 
-pub fn new_event_form<S: EventStoreLike + 'static>() -> Form<S, Box<dyn FormItem<S>>> {
+pub fn new_event_form<S: EventStoreLike + 'static>(
+    week_start: Weekday,
+) -> Form<S, Box<dyn FormItem<S>>> {
     Form::new(vec![
         Box::new(new_summary()),
-        Box::new(new_start()),
-        Box::new(new_end()),
+        Box::new(new_start(week_start)),
+        Box::new(new_end(week_start)),
         Box::new(new_status()),
         Box::new(new_description()),
     ])
@@ -32,6 +37,22 @@ macro_rules! new_input {
             Input::new($title)
         }
 
+        new_access!($acc, $field, $action);
+    };
+}
+
+macro_rules! new_date_input {
+    ($fn: ident, $title:expr, $acc: ident, $field: ident, $action: ident) => {
+        fn $fn<S: EventStoreLike>(week_start: Weekday) -> DateInput<S, $acc> {
+            DateInput::new($title, week_start)
+        }
+
+        new_access!($acc, $field, $action);
+    };
+}
+
+macro_rules! new_access {
+    ($acc: ident, $field: ident, $action: ident) => {
         struct $acc;
 
         impl<S: EventStoreLike> Access<S, String> for $acc {
@@ -43,6 +64,10 @@ macro_rules! new_input {
                 dispatcher.dispatch(&Action::$action(value));
                 true
             }
+
+            fn error(store: &RefCell<S>) -> Option<String> {
+                store.borrow().event().errors.get(stringify!($field))
+            }
         }
     };
 }
@@ -61,8 +86,8 @@ new_input!(
     description,
     UpdateEventDescription
 );
-new_input!(new_start, "Start", StartAccess, start, UpdateEventStart);
-new_input!(new_end, "End", EndAccess, end, UpdateEventEnd);
+new_date_input!(new_start, "Start", StartAccess, start, UpdateEventStart);
+new_date_input!(new_end, "End", EndAccess, end, UpdateEventEnd);
 
 fn new_status<S: EventStoreLike>() -> RadioGroup<S, EventStatus, StatusAccess> {
     use EventStatus::{Cancelled, Confirmed, Tentative};
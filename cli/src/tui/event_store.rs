@@ -4,10 +4,12 @@
 
 use std::{cell::RefCell, error::Error, ops::Deref, rc::Rc};
 
-use aimcal_core::{Aim, Event, EventDraft, EventPatch, EventStatus};
+use aimcal_core::{Aim, AlarmTrigger, Event, EventDraft, EventPatch, EventStatus};
+use jiff::SignedDuration;
 
+use crate::tui::component_form::{FieldErrors, FormMode};
 use crate::tui::dispatcher::{Action, Dispatcher};
-use crate::util::{format_datetime, parse_datetime};
+use crate::util::{format_datetime, parse_anchor_field, parse_datetime_field};
 
 pub trait EventStoreLike {
     type Output<'a>: Deref<Target = EventStore>
@@ -17,70 +19,119 @@ pub trait EventStoreLike {
     fn event(&self) -> Self::Output<'_>;
 }
 
+#[expect(clippy::option_option)]
 #[derive(Debug)]
 pub struct EventStore {
     pub data: EventData,
     pub dirty: EventMarker,
 
+    /// Whether the form creates an event or edits one
+    pub mode: FormMode,
+
+    /// Errors found in the fields on submit, until they are edited
+    pub errors: FieldErrors,
+
+    /// Reminder given on the command line, passed through as the editor has no field for it
+    pub alarm: Option<Option<AlarmTrigger>>,
+
+    /// Duration given on the command line, standing in for the end while it is left empty
+    pub duration: Option<SignedDuration>,
+
     /// Whether the user submit the changes
     pub submit: bool,
 }
 
 impl EventStore {
     pub fn from_draft(draft: EventDraft) -> Self {
-        Self::new(EventData {
-            description: draft.description.unwrap_or_default(),
-            end: draft.end.map(format_datetime).unwrap_or_default(),
-            start: draft.start.map(format_datetime).unwrap_or_default(),
-            status: draft.status,
-            summary: draft.summary,
-        })
+        Self {
+            duration: draft.duration,
+            ..Self::new(
+                FormMode::Create,
+                draft.alarm,
+                EventData {
+                    description: draft.description.unwrap_or_default(),
+                    end: draft.end.map(format_datetime).unwrap_or_default(),
+                    start: draft.start.map(format_datetime).unwrap_or_default(),
+                    status: draft.status,
+                    summary: draft.summary,
+                },
+            )
+        }
     }
 
     pub fn from_patch(event: &impl Event, patch: EventPatch) -> Self {
-        Self::new(EventData {
-            description: match patch.description {
-                Some(v) => v.unwrap_or_default(),
-                None => event.description().unwrap_or_default().into_owned(),
-            },
-            start: match patch.start {
-                Some(v) => v.map(format_datetime).unwrap_or_default(),
-                None => event.start().map(format_datetime).unwrap_or_default(),
+        Self::new(
+            FormMode::Patch,
+            patch.alarm,
+            EventData {
+                description: match patch.description {
+                    Some(v) => v.unwrap_or_default(),
+                    None => event.description().unwrap_or_default().into_owned(),
+                },
+                start: match patch.start {
+                    Some(v) => v.map(format_datetime).unwrap_or_default(),
+                    None => event.start().map(format_datetime).unwrap_or_default(),
+                },
+                end: match patch.end {
+                    Some(v) => v.map(format_datetime).unwrap_or_default(),
+                    None => event.end().map(format_datetime).unwrap_or_default(),
+                },
+                status: patch.status.or_else(|| event.status()).unwrap_or_default(),
+                summary: patch.summary.unwrap_or_else(|| event.summary().to_string()),
             },
-            end: match patch.end {
-                Some(v) => v.map(format_datetime).unwrap_or_default(),
-                None => event.end().map(format_datetime).unwrap_or_default(),
-            },
-            status: patch.status.or_else(|| event.status()).unwrap_or_default(),
-            summary: patch.summary.unwrap_or_else(|| event.summary().to_string()),
-        })
+        )
     }
 
-    fn new(data: EventData) -> Self {
+    #[expect(clippy::option_option)]
+    fn new(mode: FormMode, alarm: Option<Option<AlarmTrigger>>, data: EventData) -> Self {
         Self {
             data,
+            alarm,
+            duration: None,
             dirty: EventMarker::default(),
+            mode,
+            errors: FieldErrors::default(),
             submit: false,
         }
     }
 
+    /// Checks that the input can be submitted, recording the errors by field so the editor can
+    /// mark them in place.
+    pub fn validate(&mut self, aim: &Aim) -> bool {
+        self.errors.clear();
+        if self.mode == FormMode::Create {
+            self.errors.require("summary", &self.data.summary);
+        }
+        let start = parse_datetime_field(&aim.now(), "start", &self.data.start);
+        self.errors.check("start", start);
+        let end = parse_datetime_field(&aim.now(), "end", &self.data.end);
+        self.errors.check("end", end);
+        self.errors.is_empty()
+    }
+
     pub fn submit_draft(self, aim: &Aim) -> Result<EventDraft, Box<dyn Error>> {
-        Ok(EventDraft {
+        let start = parse_anchor_field("start", &self.data.start)?;
+        let end = parse_anchor_field("end", &self.data.end)?;
+        let mut draft = EventDraft {
+            alarm: self.alarm,
             calendar_id: None,
             description: self.dirty.description.then_some(self.data.description),
-            start: parse_datetime(&aim.now(), &self.data.start)?,
-            end: parse_datetime(&aim.now(), &self.data.end)?,
+            start: None,
+            end: None,
+            duration: None,
+            repeat: None,
+            related_to: None,
             status: self.data.status,
-            summary: if self.data.summary.is_empty() {
-                "New event".to_string()
-            } else {
-                self.data.summary
-            },
-        })
+            summary: self.data.summary,
+        };
+        let duration = self.duration.filter(|_| end.is_none());
+        draft.set_times(&aim.now(), start, end, duration)?;
+        Ok(draft)
     }
 
     pub fn submit_patch(self, aim: &Aim) -> Result<EventPatch, Box<dyn Error>> {
         Ok(EventPatch {
+            alarm: self.alarm,
             description: if self.dirty.description {
                 if self.data.description.is_empty() {
                     Some(None)
@@ -91,17 +142,22 @@ impl EventStore {
                 None
             },
             start: if self.dirty.start {
-                Some(parse_datetime(&aim.now(), &self.data.start)?)
+                Some(parse_datetime_field(&aim.now(), "start", &self.data.start)?)
             } else {
                 None
             },
             end: if self.dirty.end {
-                Some(parse_datetime(&aim.now(), &self.data.end)?)
+                Some(parse_datetime_field(&aim.now(), "end", &self.data.end)?)
             } else {
                 None
             },
             status: self.dirty.status.then_some(self.data.status),
             summary: self.dirty.summary.then(|| self.data.summary.clone()),
+            ex_dates: Vec::new(),
+            rdates: Vec::new(),
+            until: None,
+            meta: Vec::new(),
+            check_in: None,
         })
     }
 
@@ -111,26 +167,31 @@ impl EventStore {
                 let mut that = that.borrow_mut();
                 that.data.description.clone_from(v);
                 that.dirty.description = true;
+                that.errors.edited("description");
             }
             Action::UpdateEventStart(v) => {
                 let mut that = that.borrow_mut();
                 that.data.start.clone_from(v);
                 that.dirty.start = true;
+                that.errors.edited("start");
             }
             Action::UpdateEventEnd(v) => {
                 let mut that = that.borrow_mut();
                 that.data.end.clone_from(v);
                 that.dirty.end = true;
+                that.errors.edited("end");
             }
             Action::UpdateEventStatus(v) => {
                 let mut that = that.borrow_mut();
                 that.data.status = *v;
                 that.dirty.status = true;
+                that.errors.edited("status");
             }
             Action::UpdateEventSummary(v) => {
                 let mut that = that.borrow_mut();
                 that.data.summary.clone_from(v);
                 that.dirty.summary = true;
+                that.errors.edited("summary");
             }
             Action::SubmitChanges => {
                 let mut that = that.borrow_mut();
@@ -6,13 +6,15 @@ use std::cell::{Ref, RefCell};
 use std::error::Error;
 use std::rc::Rc;
 
-use aimcal_core::{Aim, EventDraft, Kind, TodoDraft};
+use aimcal_core::{Aim, EventDraft, Kind, Priority, TodoDraft};
+use jiff::civil::Weekday;
 
 use crate::tui::component_form::{Access, Form, FormItem};
 use crate::tui::component_page::TabPages;
 use crate::tui::dispatcher::{Action, Dispatcher};
 use crate::tui::event_editor::new_event_form;
 use crate::tui::event_store::{EventStore, EventStoreLike};
+use crate::tui::state::StateFile;
 use crate::tui::todo_editor::new_todo_form;
 use crate::tui::todo_store::{TodoStore, TodoStoreLike};
 
@@ -30,15 +32,27 @@ pub struct EventTodoStore {
     pub todo: Rc<RefCell<TodoStore>>,
     pub active: Kind,
     pub submit: bool,
+    pub state: Option<StateFile>,
 }
 
 impl EventTodoStore {
-    pub fn new(kind: Option<Kind>, event: EventDraft, todo: TodoDraft) -> Self {
+    pub fn new(
+        kind: Option<Kind>,
+        event: EventDraft,
+        todo: TodoDraft,
+        todo_priority: Priority,
+        state: Option<StateFile>,
+    ) -> Self {
+        // active the last used tab, or todo by default since it is more common to draft todo
+        let active = kind
+            .or_else(|| state.as_ref().and_then(StateFile::active))
+            .unwrap_or(Kind::Todo);
         Self {
             event: Rc::new(RefCell::new(EventStore::from_draft(event))),
-            todo: Rc::new(RefCell::new(TodoStore::from_draft(todo))),
-            active: kind.unwrap_or(Kind::Todo), // active todo by default since it is more common to draft todo
+            todo: Rc::new(RefCell::new(TodoStore::from_draft(todo, todo_priority))),
+            active,
             submit: false,
+            state,
         }
     }
 
@@ -47,13 +61,36 @@ impl EventTodoStore {
         TodoStore::register_to(that.borrow().todo.clone(), dispatcher);
 
         let callback = Rc::new(RefCell::new(move |action: &Action| match action {
-            Action::Activate(v) => that.borrow_mut().active = *v,
+            Action::Activate(v) => {
+                let mut store = that.borrow_mut();
+                store.active = *v;
+                if let Some(state) = &mut store.state {
+                    state.set_active(*v);
+                }
+            }
             Action::SubmitChanges => that.borrow_mut().submit = true,
             _ => {}
         }));
         dispatcher.register(callback);
     }
 
+    /// Saves the UI state on a clean exit.
+    pub fn save_state(&mut self) {
+        let active = self.active;
+        if let Some(state) = &mut self.state {
+            state.set_active(active);
+            state.save();
+        }
+    }
+
+    /// Checks the input of the active form, see [`EventStore::validate`].
+    pub fn validate(&mut self, aim: &Aim) -> bool {
+        match self.active {
+            Kind::Event => self.event.borrow_mut().validate(aim),
+            Kind::Todo => self.todo.borrow_mut().validate(aim),
+        }
+    }
+
     pub fn submit_draft(self, aim: &Aim) -> Result<EventOrTodoDraft, Box<dyn Error>> {
         match self.active {
             Kind::Event => {
@@ -107,9 +144,11 @@ impl<S: EventTodoStoreLike> Access<S, Kind> for EventTodoStoreActiveAccess<S> {
 
 type EventTodoEditor<S> = TabPages<S, Form<S, Box<dyn FormItem<S>>>, EventTodoStoreActiveAccess<S>>;
 
-pub fn new_event_todo_editor<S: EventTodoStoreLike + 'static>() -> EventTodoEditor<S> {
+pub fn new_event_todo_editor<S: EventTodoStoreLike + 'static>(
+    week_start: Weekday,
+) -> EventTodoEditor<S> {
     TabPages::new(vec![
-        (Kind::Event, "Event", new_event_form()),
-        (Kind::Todo, "Todo", new_todo_form()),
+        (Kind::Event, "Event", new_event_form(week_start)),
+        (Kind::Todo, "Todo", new_todo_form(week_start)),
     ])
 }
@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use aimcal_core::Kind;
+
+use crate::util::write_json_atomic;
+
+/// Name of the TUI state file in the state directory.
+pub const STATE_FILE: &str = "tui-state.json";
+
+/// Version of the state schema, bumped whenever a saved state can no longer be read as is.
+const STATE_VERSION: u32 = 1;
+
+/// Minimum time between two writes of the state while the TUI is running.
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// UI state kept between sessions.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TuiState {
+    pub version: u32,
+
+    /// The tab that was active last, if any.
+    #[serde(default)]
+    pub active: Option<StateKind>,
+}
+
+impl Default for TuiState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            active: None,
+        }
+    }
+}
+
+/// [`Kind`] as written to the state file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateKind {
+    Event,
+    Todo,
+}
+
+impl From<Kind> for StateKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Event => StateKind::Event,
+            Kind::Todo => StateKind::Todo,
+        }
+    }
+}
+
+impl From<StateKind> for Kind {
+    fn from(kind: StateKind) -> Self {
+        match kind {
+            StateKind::Event => Kind::Event,
+            StateKind::Todo => Kind::Todo,
+        }
+    }
+}
+
+/// The state file, saved at most every [`SAVE_INTERVAL`] while the TUI is running.
+#[derive(Debug)]
+pub struct StateFile {
+    path: PathBuf,
+    state: TuiState,
+    last_saved: Option<Instant>,
+}
+
+impl StateFile {
+    /// Loads the state from `path`, starting over when the file is missing, unreadable or from
+    /// another schema version.
+    pub fn load(path: PathBuf) -> Self {
+        let state = match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<TuiState>(&content) {
+                Ok(state) if state.version == STATE_VERSION => state,
+                Ok(state) => {
+                    tracing::info!(
+                        version = state.version,
+                        "discarding TUI state of another version"
+                    );
+                    TuiState::default()
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "discarding corrupt TUI state");
+                    TuiState::default()
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => TuiState::default(),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "failed to read TUI state");
+                TuiState::default()
+            }
+        };
+
+        Self {
+            path,
+            state,
+            last_saved: None,
+        }
+    }
+
+    /// The tab that was active last, if any.
+    pub fn active(&self) -> Option<Kind> {
+        self.state.active.map(Into::into)
+    }
+
+    /// Records the active tab, saving the state unless it was saved just before.
+    pub fn set_active(&mut self, kind: Kind) {
+        self.state.active = Some(kind.into());
+        if self.last_saved.is_none_or(|t| t.elapsed() >= SAVE_INTERVAL) {
+            self.save();
+        }
+    }
+
+    /// Saves the state, logging rather than failing since losing it is harmless.
+    pub fn save(&mut self) {
+        match write_json_atomic(&self.path, &self.state) {
+            Ok(()) => self.last_saved = Some(Instant::now()),
+            Err(e) => {
+                tracing::warn!(path = %self.path.display(), error = %e, "failed to save TUI state");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_saved_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+
+        let mut file = StateFile::load(path.clone());
+        assert_eq!(file.active(), None);
+        file.set_active(Kind::Event);
+
+        // Switching again right away is kept in memory until the next save
+        file.set_active(Kind::Todo);
+        assert_eq!(StateFile::load(path.clone()).active(), Some(Kind::Event));
+        file.save();
+        assert_eq!(StateFile::load(path).active(), Some(Kind::Todo));
+    }
+
+    #[test]
+    fn falls_back_on_missing_or_stale_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        assert_eq!(StateFile::load(path.clone()).state, TuiState::default());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(StateFile::load(path.clone()).state, TuiState::default());
+
+        fs::write(&path, r#"{ "version": 999, "active": "event" }"#).unwrap();
+        assert_eq!(StateFile::load(path.clone()).active(), None);
+
+        fs::write(&path, r#"{ "version": 1, "active": "calendar" }"#).unwrap();
+        assert_eq!(StateFile::load(path).active(), None);
+    }
+}
@@ -5,22 +5,27 @@
 use std::cell::RefCell;
 
 use aimcal_core::{Priority, TodoStatus};
+use jiff::civil::Weekday;
 
+use crate::tui::component_date_picker::DateInput;
 use crate::tui::component_form::{Access, Form, FormItem, Input, RadioGroup};
 use crate::tui::component_form_util::{FormItemSwitch, PositiveIntegerAccess, VisibleIf};
 use crate::tui::component_page::SinglePage;
 use crate::tui::dispatcher::{Action, Dispatcher};
 use crate::tui::todo_store::TodoStoreLike;
 
-pub fn new_todo_editor<S: TodoStoreLike + 'static>() -> SinglePage<S, Form<S, Box<dyn FormItem<S>>>>
-{
-    SinglePage::new(&"Todo Editor", new_todo_form())
+pub fn new_todo_editor<S: TodoStoreLike + 'static>(
+    week_start: Weekday,
+) -> SinglePage<S, Form<S, Box<dyn FormItem<S>>>> {
+    SinglePage::new(&"Todo Editor", new_todo_form(week_start))
 }
 
-pub fn new_todo_form<S: TodoStoreLike + 'static>() -> Form<S, Box<dyn FormItem<S>>> {
+pub fn new_todo_form<S: TodoStoreLike + 'static>(
+    week_start: Weekday,
+) -> Form<S, Box<dyn FormItem<S>>> {
     Form::new(vec![
         Box::new(new_summary()),
-        Box::new(new_due()),
+        Box::new(new_due(week_start)),
         Box::new(new_priority()),
         Box::new(new_status()),
         Box::new(new_percent_complete()),
@@ -34,6 +39,22 @@ macro_rules! new_input {
             Input::new(&$title)
         }
 
+        new_access!($acc, $field, $action);
+    };
+}
+
+macro_rules! new_date_input {
+    ($fn: ident, $title:expr, $acc: ident, $field: ident, $action: ident) => {
+        fn $fn<S: TodoStoreLike>(week_start: Weekday) -> DateInput<S, $acc> {
+            DateInput::new(&$title, week_start)
+        }
+
+        new_access!($acc, $field, $action);
+    };
+}
+
+macro_rules! new_access {
+    ($acc: ident, $field: ident, $action: ident) => {
         struct $acc;
 
         impl<S: TodoStoreLike> Access<S, String> for $acc {
@@ -45,6 +66,10 @@ macro_rules! new_input {
                 dispatcher.dispatch(&Action::$action(value));
                 true
             }
+
+            fn error(store: &RefCell<S>) -> Option<String> {
+                store.borrow().todo().errors.get(stringify!($field))
+            }
         }
     };
 }
@@ -63,7 +88,7 @@ new_input!(
     description,
     UpdateTodoDescription
 );
-new_input!(new_due, "Due", DueAccess, due, UpdateTodoDue);
+new_date_input!(new_due, "Due", DueAccess, due, UpdateTodoDue);
 
 struct PercentCompleteAccess;
 
@@ -4,10 +4,11 @@
 
 use std::{cell::RefCell, error::Error, ops::Deref, rc::Rc};
 
-use aimcal_core::{Aim, Priority, Todo, TodoDraft, TodoPatch, TodoStatus};
+use aimcal_core::{Aim, AlarmTrigger, Priority, Todo, TodoDraft, TodoPatch, TodoStatus};
 
+use crate::tui::component_form::{FieldErrors, FormMode};
 use crate::tui::dispatcher::{Action, Dispatcher};
-use crate::util::{format_datetime, parse_datetime};
+use crate::util::{format_datetime, parse_due_field};
 
 pub trait TodoStoreLike {
     type Output<'a>: Deref<Target = TodoStore>
@@ -17,11 +18,29 @@ pub trait TodoStoreLike {
     fn todo(&self) -> Self::Output<'_>;
 }
 
+#[expect(clippy::option_option, clippy::struct_excessive_bools)]
 #[derive(Debug)]
 pub struct TodoStore {
     pub data: TodoData,
     pub dirty: TodoMarker,
 
+    /// Whether the form creates a todo or edits one
+    pub mode: FormMode,
+
+    /// Errors found in the fields on submit, until they are edited
+    pub errors: FieldErrors,
+
+    /// Reminder given on the command line, passed through as the editor has no field for it
+    pub alarm: Option<Option<AlarmTrigger>>,
+
+    /// Whether to encrypt the description, passed through from the command line like the alarm
+    pub encrypt_description: bool,
+
+    /// Calendar, categories and whether to leave out their defaults, passed through as well
+    pub calendar_id: Option<String>,
+    pub categories: Vec<String>,
+    pub no_defaults: bool,
+
     /// Whether to show verbose priority options
     pub verbose_priority: bool,
 
@@ -30,69 +49,112 @@ pub struct TodoStore {
 }
 
 impl TodoStore {
-    pub fn from_draft(draft: TodoDraft) -> Self {
-        Self::new(TodoData {
-            description: draft.description.unwrap_or_default(),
-            due: draft.due.map(format_datetime).unwrap_or_default(),
-            percent_complete: draft.percent_complete,
-            priority: draft.priority.unwrap_or_default(),
-            status: draft.status,
-            summary: draft.summary,
-        })
+    /// Creates the form for a draft, showing `priority` unless the draft sets one, which is the
+    /// priority the todo takes from its categories or the config if left as it is.
+    pub fn from_draft(draft: TodoDraft, priority: Priority) -> Self {
+        let encrypt_description = draft.encrypt_description;
+        let given_priority = draft.priority.is_some();
+        let mut store = Self::new(
+            FormMode::Create,
+            draft.alarm,
+            TodoData {
+                description: draft.description.unwrap_or_default(),
+                due: draft.due.map(format_datetime).unwrap_or_default(),
+                percent_complete: draft.percent_complete,
+                priority: draft.priority.unwrap_or(priority),
+                status: draft.status,
+                summary: draft.summary,
+            },
+        );
+        store.encrypt_description = encrypt_description;
+        store.calendar_id = draft.calendar_id;
+        store.categories = draft.categories;
+        store.no_defaults = draft.no_defaults;
+        store.dirty.priority = given_priority;
+        store
     }
 
     pub fn from_patch(todo: &impl Todo, patch: TodoPatch) -> Self {
-        Self::new(TodoData {
-            description: match patch.description {
-                Some(v) => v.unwrap_or_default(),
-                None => todo.description().unwrap_or_default().into_owned(),
+        Self::new(
+            FormMode::Patch,
+            patch.alarm,
+            TodoData {
+                description: match patch.description {
+                    Some(v) => v.unwrap_or_default(),
+                    None => todo.description().unwrap_or_default().into_owned(),
+                },
+                due: match patch.due {
+                    Some(v) => v.map(format_datetime).unwrap_or_default(),
+                    None => todo.due().map(format_datetime).unwrap_or_default(),
+                },
+                percent_complete: patch
+                    .percent_complete
+                    .unwrap_or_else(|| todo.percent_complete()),
+                priority: patch.priority.unwrap_or_else(|| todo.priority()),
+                status: patch.status.unwrap_or_else(|| todo.status()),
+                summary: patch.summary.unwrap_or_else(|| todo.summary().into_owned()),
             },
-            due: match patch.due {
-                Some(v) => v.map(format_datetime).unwrap_or_default(),
-                None => todo.due().map(format_datetime).unwrap_or_default(),
-            },
-            percent_complete: patch
-                .percent_complete
-                .unwrap_or_else(|| todo.percent_complete()),
-            priority: patch.priority.unwrap_or_else(|| todo.priority()),
-            status: patch.status.unwrap_or_else(|| todo.status()),
-            summary: patch.summary.unwrap_or_else(|| todo.summary().into_owned()),
-        })
+        )
     }
 
-    fn new(data: TodoData) -> Self {
+    #[expect(clippy::option_option)]
+    fn new(mode: FormMode, alarm: Option<Option<AlarmTrigger>>, data: TodoData) -> Self {
         use Priority::{P1, P3, P4, P6, P7, P9};
         let verbose_priority = matches!(data.priority, P1 | P3 | P4 | P6 | P7 | P9);
         Self {
             data,
+            alarm,
+            encrypt_description: false,
+            calendar_id: None,
+            categories: Vec::new(),
+            no_defaults: false,
             dirty: TodoMarker::default(),
+            mode,
+            errors: FieldErrors::default(),
             verbose_priority,
             submit: false,
         }
     }
 
+    /// Checks that the input can be submitted, recording the errors by field so the editor can
+    /// mark them in place.
+    pub fn validate(&mut self, aim: &Aim) -> bool {
+        self.errors.clear();
+        if self.mode == FormMode::Create {
+            self.errors.require("summary", &self.data.summary);
+        }
+        let due = parse_due_field(&aim.now(), aim.default_due_time(), &self.data.due);
+        self.errors.check("due", due);
+        self.errors.is_empty()
+    }
+
     pub fn submit_draft(self, aim: &Aim) -> Result<TodoDraft, Box<dyn Error>> {
         Ok(TodoDraft {
-            calendar_id: None,
+            alarm: self.alarm,
+            calendar_id: self.calendar_id,
+            categories: self.categories,
             description: self.dirty.description.then_some(self.data.description),
-            due: parse_datetime(&aim.now(), &self.data.due)?,
+            due: parse_due_field(&aim.now(), aim.default_due_time(), &self.data.due)?,
+            estimate: None,
             percent_complete: self
                 .dirty
                 .percent_complete
                 .then_some(self.data.percent_complete)
                 .flatten(),
-            priority: Some(self.data.priority), // Always commit since it was confirmed by the user
+            // Left as shown, the defaults of the categories or the config give it again
+            priority: self.dirty.priority.then_some(self.data.priority),
             status: self.data.status,
-            summary: if self.data.summary.is_empty() {
-                "New todo".to_string()
-            } else {
-                self.data.summary
-            },
+            summary: self.data.summary,
+            encrypt_description: self.encrypt_description,
+            recur_from: None,
+            parent: None,
+            no_defaults: self.no_defaults,
         })
     }
 
     pub fn submit_patch(self, aim: &Aim) -> Result<TodoPatch, Box<dyn Error>> {
         Ok(TodoPatch {
+            alarm: self.alarm,
             description: if self.dirty.description {
                 if self.data.description.is_empty() {
                     Some(None)
@@ -103,10 +165,15 @@ impl TodoStore {
                 None
             },
             due: if self.dirty.due {
-                Some(parse_datetime(&aim.now(), &self.data.due)?)
+                Some(parse_due_field(
+                    &aim.now(),
+                    aim.default_due_time(),
+                    &self.data.due,
+                )?)
             } else {
                 None
             },
+            estimate: None,
             percent_complete: self
                 .dirty
                 .percent_complete
@@ -114,6 +181,12 @@ impl TodoStore {
             priority: self.dirty.priority.then_some(self.data.priority),
             status: self.dirty.status.then_some(self.data.status),
             summary: self.dirty.summary.then(|| self.data.summary.clone()),
+            encrypted_description: None,
+            snoozed_from: None,
+            meta: Vec::new(),
+            waiting: None,
+            recur_from: None,
+            completed_at: None,
         })
     }
 
@@ -123,31 +196,37 @@ impl TodoStore {
                 let mut that = that.borrow_mut();
                 that.data.description.clone_from(v);
                 that.dirty.description = true;
+                that.errors.edited("description");
             }
             Action::UpdateTodoDue(v) => {
                 let mut that = that.borrow_mut();
                 that.data.due.clone_from(v);
                 that.dirty.due = true;
+                that.errors.edited("due");
             }
             Action::UpdateTodoPercentComplete(v) => {
                 let mut that = that.borrow_mut();
                 that.data.percent_complete = *v;
                 that.dirty.percent_complete = true;
+                that.errors.edited("percent_complete");
             }
             Action::UpdateTodoPriority(v) => {
                 let mut that = that.borrow_mut();
                 that.data.priority = *v;
                 that.dirty.priority = true;
+                that.errors.edited("priority");
             }
             Action::UpdateTodoStatus(v) => {
                 let mut that = that.borrow_mut();
                 that.data.status = *v;
                 that.dirty.status = true;
+                that.errors.edited("status");
             }
             Action::UpdateTodoSummary(v) => {
                 let mut that = that.borrow_mut();
                 that.data.summary.clone_from(v);
                 that.dirty.summary = true;
+                that.errors.edited("summary");
             }
             Action::SubmitChanges => {
                 let mut that = that.borrow_mut();
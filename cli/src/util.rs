@@ -3,9 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::error::Error;
-
-use aimcal_core::{DateTimeAnchor, LooseDateTime};
-use jiff::Zoned;
+use std::fmt::Write;
+use std::io::{self, BufRead, BufWriter, IsTerminal, Read};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use aimcal_core::{AimError, CalendarExport, DateTimeAnchor, Kind, LooseDateTime, write_atomic};
+use jiff::civil::{Date, Time};
+use jiff::{Span, Zoned};
+use tokio::fs;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
@@ -27,6 +35,115 @@ pub fn parse_datetime(now: &Zoned, anchor: &str) -> Result<Option<LooseDateTime>
     }
 }
 
+/// Parses when something happened, such as `yesterday 18:00`, `last friday 09:00` or
+/// `2025-01-01 18:00`. A time alone is the last time it was that time.
+pub fn parse_past_datetime(now: &Zoned, value: &str) -> Result<Zoned, String> {
+    let value = value.trim();
+    let tz = now.time_zone();
+    let at_time = |date: Date, time: Time| {
+        date.to_datetime(time)
+            .to_zoned(tz.clone())
+            .map_err(|e| format!("Failed to resolve '{value}': {e}"))
+    };
+    let day_and_time = value.rsplit_once(' ').map(|(day, time)| {
+        (
+            day.parse::<DateTimeAnchor>(),
+            time.parse::<DateTimeAnchor>(),
+        )
+    });
+    match day_and_time {
+        // A day followed by the time of that day
+        Some((Ok(day), Ok(DateTimeAnchor::Time(time)))) => {
+            let date = match day {
+                DateTimeAnchor::InDays(n) => now
+                    .date()
+                    .checked_add(Span::new().days(n))
+                    .map_err(|e| format!("Failed to add days to date: {e}"))?,
+                day => day.resolve_since_zoned(now)?.date(),
+            };
+            at_time(date, time)
+        }
+        _ => match value.parse::<DateTimeAnchor>()? {
+            DateTimeAnchor::Time(time) if now.time() >= time => at_time(now.date(), time),
+            DateTimeAnchor::Time(time) => {
+                let yesterday = now
+                    .date()
+                    .yesterday()
+                    .map_err(|e| format!("Failed to resolve '{value}': {e}"))?;
+                at_time(yesterday, time)
+            }
+            anchor => anchor
+                .resolve_since_zoned(now)?
+                .to_timestamp_in(tz)
+                .map(|at| at.to_zoned(tz.clone()))
+                .ok_or_else(|| format!("Failed to resolve '{value}'")),
+        },
+    }
+}
+
+/// Parses a date and time anchor, resolved later against the item, where empty means none.
+pub fn parse_anchor(anchor: &str) -> Result<Option<DateTimeAnchor>, String> {
+    if anchor.is_empty() {
+        Ok(None)
+    } else {
+        anchor.parse().map(Some)
+    }
+}
+
+/// Parses the anchor of an input field, see [`parse_anchor`] and [`parse_datetime_field`].
+pub fn parse_anchor_field(
+    field: &'static str,
+    value: &str,
+) -> Result<Option<DateTimeAnchor>, AimError> {
+    parse_anchor(value).map_err(|reason| AimError::InvalidInput { field, reason })
+}
+
+/// Parses the datetime of an input field, reporting failures as invalid input of `field`.
+pub fn parse_datetime_field(
+    now: &Zoned,
+    field: &'static str,
+    value: &str,
+) -> Result<Option<LooseDateTime>, AimError> {
+    parse_datetime(now, value).map_err(|e| AimError::InvalidInput {
+        field,
+        reason: e.to_string(),
+    })
+}
+
+/// Parses a due date, giving dates without a time `default_time` if one is configured.
+pub fn parse_due(
+    now: &Zoned,
+    default_time: Option<Time>,
+    anchor: &str,
+) -> Result<Option<LooseDateTime>, Box<dyn Error>> {
+    let due = parse_datetime(now, anchor)?;
+    Ok(due.map(|due| with_due_time(now, default_time, due)))
+}
+
+/// Gives a due date without a time `default_time` if one is configured.
+pub fn with_due_time(now: &Zoned, default_time: Option<Time>, due: LooseDateTime) -> LooseDateTime {
+    match (due, default_time) {
+        (LooseDateTime::DateOnly(date), Some(time)) => {
+            let dt = date.to_datetime(time);
+            dt.to_zoned(now.time_zone().clone())
+                .map_or(LooseDateTime::Floating(dt), LooseDateTime::Local)
+        }
+        (due, _) => due,
+    }
+}
+
+/// Parses the due date of an input field, see [`parse_due`] and [`parse_datetime_field`].
+pub fn parse_due_field(
+    now: &Zoned,
+    default_time: Option<Time>,
+    value: &str,
+) -> Result<Option<LooseDateTime>, AimError> {
+    parse_due(now, default_time, value).map_err(|e| AimError::InvalidInput {
+        field: "due",
+        reason: e.to_string(),
+    })
+}
+
 /// Parses a date range from two strings, where the first is the start date and the second is the end date.
 ///
 /// NOTE: Don't assert that the start date is before the end date, as this function does not enforce that.
@@ -62,6 +179,149 @@ pub fn format_datetime(t: LooseDateTime) -> String {
     .to_string()
 }
 
+/// The `aim://` URL of an item, for terminals with a handler for it to open the item.
+///
+/// Characters of the UID other than unreserved ones and `@` are percent-encoded.
+pub fn item_url(kind: Kind, uid: &str) -> String {
+    let kind = match kind {
+        Kind::Event => "event",
+        Kind::Todo => "todo",
+    };
+    let mut url = format!("aim://{kind}/");
+    for byte in uid.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'@' => {
+                url.push(char::from(byte));
+            }
+            _ => _ = write!(url, "%{byte:02X}"),
+        }
+    }
+    url
+}
+
+/// Writes an exported calendar to `path` at once, or to stdout without one, line by line.
+pub fn write_export(path: Option<&Path>, export: CalendarExport) -> Result<(), Box<dyn Error>> {
+    match path {
+        Some(path) => {
+            let failed = |e: io::Error| format!("Failed to write {}: {e}", path.display());
+            let mut content = Vec::new();
+            export.write_to(&mut content).map_err(failed)?;
+            write_atomic(path, &content).map_err(failed)?;
+        }
+        None => export.write_to(BufWriter::new(io::stdout().lock()))?,
+    }
+    Ok(())
+}
+
+/// Writes `value` as JSON to a temporary file first, so a crash never leaves a half-written file.
+pub fn write_json_atomic<T: serde::Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    write_atomic(path, &serde_json::to_vec_pretty(value)?)
+}
+
+/// The value of an argument that reads it from stdin instead.
+pub const STDIN_ARG: &str = "-";
+
+/// The most bytes read from stdin, so that a runaway pipe is not stored.
+const STDIN_LIMIT: u64 = 1024 * 1024;
+
+/// How long to wait for stdin before hinting that aim is reading it.
+const STDIN_HINT_DELAY: Duration = Duration::from_secs(1);
+
+/// Resolves the arguments set to [`STDIN_ARG`] from stdin, which only one of them can read.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdinArgs {
+    used: Option<&'static str>,
+}
+
+impl StdinArgs {
+    /// Reads the value of the argument `name` from stdin if it is [`STDIN_ARG`], without the
+    /// trailing line breaks.
+    pub fn resolve(
+        &mut self,
+        name: &'static str,
+        value: &mut Option<String>,
+    ) -> Result<(), Box<dyn Error>> {
+        if value.as_deref() == Some(STDIN_ARG) {
+            let text = self.read(name)?;
+            *value = Some(text.trim_end_matches(['\r', '\n']).to_string());
+        }
+        Ok(())
+    }
+
+    /// Reads the file at `path` as the `name`, or stdin if the path is [`STDIN_ARG`].
+    pub async fn read_file(
+        &mut self,
+        name: &'static str,
+        path: &Path,
+    ) -> Result<String, Box<dyn Error>> {
+        if path == Path::new(STDIN_ARG) {
+            self.read(name)
+        } else {
+            let content = fs::read_to_string(path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+            Ok(content)
+        }
+    }
+
+    fn read(&mut self, name: &'static str) -> Result<String, Box<dyn Error>> {
+        if let Some(used) = self.used.replace(name) {
+            return Err(format!("Cannot read both the {used} and the {name} from stdin").into());
+        }
+        read_stdin(name)
+    }
+}
+
+/// The name of the input at `path` in messages, which is `<stdin>` for [`STDIN_ARG`].
+pub fn input_source(path: &Path) -> &Path {
+    if path == Path::new(STDIN_ARG) {
+        Path::new("<stdin>")
+    } else {
+        path
+    }
+}
+
+/// Reads stdin as text, failing if it is larger than 1 MiB or binary.
+///
+/// If nothing arrives for a moment, a hint is printed to stderr, so that aim does not seem to
+/// hang on a `-` that was passed by mistake.
+pub fn read_stdin(what: &str) -> Result<String, Box<dyn Error>> {
+    let stdin = io::stdin();
+    let hint = if stdin.is_terminal() {
+        format!("Reading the {what} from stdin, press Ctrl-D when done")
+    } else {
+        format!("Waiting for the {what} on stdin, which `{STDIN_ARG}` reads from")
+    };
+    read_input(what, stdin.lock(), hint)
+}
+
+fn read_input(what: &str, reader: impl BufRead, hint: String) -> Result<String, Box<dyn Error>> {
+    let (arrived, waiting) = mpsc::channel::<()>();
+    thread::spawn(move || {
+        if let Err(RecvTimeoutError::Timeout) = waiting.recv_timeout(STDIN_HINT_DELAY) {
+            eprintln!("{hint}");
+        }
+    });
+
+    let mut reader = reader.take(STDIN_LIMIT + 1);
+    reader
+        .fill_buf()
+        .map_err(|e| format!("Failed to read the {what} from stdin: {e}"))?;
+    drop(arrived);
+
+    let mut buf = Vec::new();
+    reader
+        .read_to_end(&mut buf)
+        .map_err(|e| format!("Failed to read the {what} from stdin: {e}"))?;
+    if buf.len() as u64 > STDIN_LIMIT {
+        return Err(format!("The {what} on stdin is larger than 1 MiB").into());
+    }
+    if buf.contains(&0) {
+        return Err(format!("The {what} on stdin looks like binary data").into());
+    }
+    String::from_utf8(buf).map_err(|_| format!("The {what} on stdin is not UTF-8 text").into())
+}
+
 pub fn unicode_width_of_slice(s: &str, first_n_chars: usize) -> usize {
     if first_n_chars == 0 || s.is_empty() {
         0
@@ -93,6 +353,52 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn builds_item_urls() {
+        assert_eq!(
+            item_url(Kind::Todo, "123e4567-e89b@example.com"),
+            "aim://todo/123e4567-e89b@example.com"
+        );
+        assert_eq!(item_url(Kind::Event, "a b/c"), "aim://event/a%20b%2Fc");
+    }
+
+    #[test]
+    fn reads_text_input() {
+        let input = io::Cursor::new("commit a\ncommit b\n");
+        let text = read_input("description", input, String::new()).unwrap();
+        assert_eq!(text, "commit a\ncommit b\n");
+    }
+
+    #[test]
+    fn rejects_binary_and_oversized_input() {
+        let binary = io::Cursor::new(b"PK\x03\x04\x00\x00".to_vec());
+        let err = read_input("description", binary, String::new()).unwrap_err();
+        assert!(err.to_string().contains("binary"), "{err}");
+
+        let invalid = io::Cursor::new(b"caf\xe9".to_vec());
+        let err = read_input("description", invalid, String::new()).unwrap_err();
+        assert!(err.to_string().contains("UTF-8"), "{err}");
+
+        let oversized = io::Cursor::new(vec![b'a'; 1024 * 1024 + 1]);
+        let err = read_input("description", oversized, String::new()).unwrap_err();
+        assert!(err.to_string().contains("1 MiB"), "{err}");
+    }
+
+    #[test]
+    fn resolves_only_stdin_args() {
+        let mut stdin = StdinArgs::default();
+        let mut summary = Some("Review".to_string());
+        stdin.resolve("summary", &mut summary).unwrap();
+        assert_eq!(summary.as_deref(), Some("Review"));
+
+        let mut description = None;
+        stdin.resolve("description", &mut description).unwrap();
+        assert_eq!(description, None);
+
+        assert_eq!(input_source(Path::new("-")), Path::new("<stdin>"));
+        assert_eq!(input_source(Path::new("a.ics")), Path::new("a.ics"));
+    }
+
     #[test]
     fn calculates_width_for_ascii_only() {
         let s = "hello world";
@@ -148,6 +454,27 @@ mod tests {
         assert_eq!(parse_datetime(&now, "").unwrap(), None);
     }
 
+    #[test]
+    fn parses_datetime_field_reports_invalid_input() {
+        let now = default_datetime();
+        let err = parse_datetime_field(&now, "due", "not a date").unwrap_err();
+        assert!(matches!(err, AimError::InvalidInput { field: "due", .. }));
+        assert!(err.to_string().starts_with("Invalid due: "), "{err}");
+    }
+
+    #[test]
+    fn parses_past_datetime() {
+        let now = default_datetime();
+        let at = |value| parse_past_datetime(&now, value).unwrap().datetime();
+        assert_eq!(at("yesterday 18:00"), datetime(2024, 12, 31, 18, 0, 0, 0));
+        assert_eq!(at("2024-12-20 09:30"), datetime(2024, 12, 20, 9, 30, 0, 0));
+        // A time alone is the last time it was that time
+        assert_eq!(at("18:00"), datetime(2024, 12, 31, 18, 0, 0, 0));
+        assert_eq!(at("08:00"), datetime(2025, 1, 1, 8, 0, 0, 0));
+        assert_eq!(at("yesterday"), datetime(2024, 12, 31, 9, 0, 0, 0));
+        assert!(parse_past_datetime(&now, "not a time").is_err());
+    }
+
     #[test]
     fn parses_datetime_date_only() {
         let now = default_datetime();
@@ -158,6 +485,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_due_with_default_time() {
+        let now = default_datetime();
+        let five_pm = Some(time(17, 0, 0, 0));
+        match parse_due(&now, five_pm, "2023-12-25").unwrap().unwrap() {
+            LooseDateTime::Local(dt) => {
+                assert_eq!(dt.date(), date(2023, 12, 25));
+                assert_eq!(dt.time(), time(17, 0, 0, 0));
+            }
+            other => panic!("Expected Local variant, got {other:?}"),
+        }
+
+        // An explicit time wins, and without a default the due stays date-only
+        let due = parse_due(&now, five_pm, "2023-12-25 09:30")
+            .unwrap()
+            .unwrap();
+        assert_eq!(due.time(), Some(time(9, 30, 0, 0)));
+        let due = parse_due(&now, None, "2023-12-25").unwrap().unwrap();
+        assert_eq!(due, LooseDateTime::DateOnly(date(2023, 12, 25)));
+        assert_eq!(parse_due(&now, five_pm, "").unwrap(), None);
+    }
+
     #[test]
     fn parses_datetime_date_and_time() {
         let now = default_datetime();
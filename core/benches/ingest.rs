@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache build benchmarks over a synthetic calendar directory.
+//!
+//! The directory holds one file per event or todo, as written by aim or vdirsyncer. Building the
+//! cache from it is compared with reading and parsing the same files on a single thread, which is
+//! the least the cache build took before files were parsed in parallel.
+
+use std::hint::black_box;
+use std::path::Path;
+
+use aimcal_core::{Aim, Config};
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use tempfile::TempDir;
+
+/// Number of files in the synthetic calendar directory.
+const FILES: usize = 10_000;
+
+/// Writes `count` calendar files, every fourth one a todo and the others events.
+fn generate_corpus(dir: &Path, count: usize) {
+    for i in 0..count {
+        let (day, hour) = (i % 28 + 1, i % 10 + 8);
+        let component = if i % 4 == 0 {
+            format!(
+                "BEGIN:VTODO\r\n\
+                 UID:todo-{i}\r\n\
+                 DTSTAMP:20260101T000000Z\r\n\
+                 SUMMARY:Todo {i}\r\n\
+                 DESCRIPTION:Synthetic todo number {i} of the ingest benchmark.\r\n\
+                 DUE;TZID=Europe/Berlin:202602{day:02}T{hour:02}0000\r\n\
+                 PRIORITY:5\r\n\
+                 STATUS:NEEDS-ACTION\r\n\
+                 END:VTODO\r\n"
+            )
+        } else {
+            format!(
+                "BEGIN:VEVENT\r\n\
+                 UID:event-{i}\r\n\
+                 DTSTAMP:20260101T000000Z\r\n\
+                 SUMMARY:Event {i}\r\n\
+                 DESCRIPTION:Synthetic event number {i} of the ingest benchmark.\r\n\
+                 LOCATION:Room {}\r\n\
+                 DTSTART;TZID=Europe/Berlin:202601{day:02}T{hour:02}0000\r\n\
+                 DTEND;TZID=Europe/Berlin:202601{day:02}T{hour:02}3000\r\n\
+                 RRULE:FREQ=WEEKLY;COUNT=10\r\n\
+                 STATUS:CONFIRMED\r\n\
+                 END:VEVENT\r\n",
+                i % 50
+            )
+        };
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//aim//bench//EN\r\n{component}END:VCALENDAR\r\n"
+        );
+        std::fs::write(dir.join(format!("{i}.ics")), src).expect("failed to write corpus file");
+    }
+}
+
+fn config(calendar_path: &Path, state_dir: &Path) -> Config {
+    let src = format!(
+        "calendar_path = {:?}\nstate_dir = {:?}\n",
+        calendar_path.display().to_string(),
+        state_dir.display().to_string()
+    );
+    toml::from_str(&src).expect("failed to parse config")
+}
+
+fn bench_ingest(c: &mut Criterion) {
+    let corpus = TempDir::new().expect("failed to create corpus directory");
+    generate_corpus(corpus.path(), FILES);
+    let rt = tokio::runtime::Runtime::new().expect("failed to start runtime");
+
+    let mut group = c.benchmark_group("ingest");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(FILES as u64));
+
+    group.bench_function("parse_sequential", |b| {
+        b.iter(|| {
+            for entry in std::fs::read_dir(corpus.path()).expect("failed to read corpus") {
+                let content = std::fs::read_to_string(entry.expect("bad entry").path())
+                    .expect("failed to read corpus file");
+                let calendars = aimcal_ical::parse(&content).expect("failed to parse");
+                black_box(
+                    calendars
+                        .into_iter()
+                        .map(|c| c.to_owned())
+                        .collect::<Vec<_>>(),
+                );
+            }
+        });
+    });
+
+    group.bench_function("build_cache", |b| {
+        b.iter_batched(
+            || TempDir::new().expect("failed to create state directory"),
+            |state_dir| {
+                rt.block_on(async {
+                    let aim = Aim::new(config(corpus.path(), state_dir.path()))
+                        .await
+                        .expect("failed to build cache");
+                    aim.close().await.expect("failed to close");
+                });
+                state_dir
+            },
+            BatchSize::PerIteration,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_ingest);
+criterion_main!(benches);
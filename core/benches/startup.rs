@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Startup benchmark of listing todos with a warm cache, as `aim todo list` does.
+//!
+//! Every start builds [`Aim`] over a state directory and a local calendar cached by an earlier
+//! start, lists the todos and closes it. A warm start has a budget of [`BUDGET`], which the
+//! median of a few starts is checked against before measuring, so that the benchmark fails on a
+//! regression rather than only reporting it.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aimcal_core::{Aim, Config, Pager, TodoConditions};
+use criterion::{Criterion, criterion_group, criterion_main};
+use tempfile::TempDir;
+
+/// Number of todos in the calendar.
+const TODOS: usize = 500;
+
+/// Most a warm start may take.
+const BUDGET: Duration = Duration::from_millis(100);
+
+/// Number of starts the budget is checked with.
+const BUDGET_RUNS: usize = 11;
+
+/// Writes `count` todo files, as written by aim.
+fn generate_todos(dir: &Path, count: usize) {
+    for i in 0..count {
+        let (day, hour) = (i % 28 + 1, i % 10 + 8);
+        let src = format!(
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             PRODID:-//aim//bench//EN\r\n\
+             BEGIN:VTODO\r\n\
+             UID:todo-{i}\r\n\
+             DTSTAMP:20260101T000000Z\r\n\
+             SUMMARY:Todo {i}\r\n\
+             DUE;TZID=Europe/Berlin:202602{day:02}T{hour:02}0000\r\n\
+             PRIORITY:5\r\n\
+             STATUS:NEEDS-ACTION\r\n\
+             END:VTODO\r\n\
+             END:VCALENDAR\r\n"
+        );
+        std::fs::write(dir.join(format!("todo-{i}.ics")), src).expect("failed to write todo");
+    }
+}
+
+fn config(calendar_path: &Path, state_dir: &Path) -> Config {
+    let src = format!(
+        "calendar_path = {:?}\nstate_dir = {:?}\n",
+        calendar_path.display().to_string(),
+        state_dir.display().to_string()
+    );
+    toml::from_str(&src).expect("failed to parse config")
+}
+
+fn all_todos() -> TodoConditions {
+    TodoConditions {
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        calendar_id: None,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    }
+}
+
+/// Starts aim, lists the todos and closes it again.
+async fn start_and_list(config: Config) {
+    let aim = Aim::builder(config)
+        .read_only()
+        .build()
+        .await
+        .expect("failed to start");
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let todos = aim
+        .list_todos(&all_todos(), &[], &pager)
+        .await
+        .expect("failed to list todos");
+    black_box(todos.len());
+    aim.close().await.expect("failed to close");
+}
+
+fn check_budget(rt: &tokio::runtime::Runtime, config: &Config) {
+    let mut timings: Vec<_> = (0..BUDGET_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            rt.block_on(start_and_list(config.clone()));
+            start.elapsed()
+        })
+        .collect();
+    timings.sort();
+    let median = timings[BUDGET_RUNS / 2];
+    assert!(
+        median <= BUDGET,
+        "warm start took {median:?}, over the budget of {BUDGET:?}"
+    );
+}
+
+fn bench_startup(c: &mut Criterion) {
+    let calendar = TempDir::new().expect("failed to create calendar directory");
+    let state_dir = TempDir::new().expect("failed to create state directory");
+    generate_todos(calendar.path(), TODOS);
+    let config = config(calendar.path(), state_dir.path());
+    let rt = tokio::runtime::Runtime::new().expect("failed to start runtime");
+
+    // The first start builds the cache and migrates the database, the ones measured are warm
+    rt.block_on(start_and_list(config.clone()));
+    check_budget(&rt, &config);
+
+    let mut group = c.benchmark_group("startup");
+    group.sample_size(20);
+    group.bench_function("warm_list_todos", |b| {
+        b.iter(|| rt.block_on(start_and_list(config.clone())));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_startup);
+criterion_main!(benches);
@@ -2,21 +2,62 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
+mod transaction;
+
+pub use transaction::{Transaction, TransactionFuture};
+
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::fmt;
-
-use jiff::Zoned;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use aimcal_caldav::CalDavClient;
+use aimcal_ical::{CalendarComponent, VEvent, VTodo};
+use jiff::civil::{Date, Time, Weekday};
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Timestamp, Zoned};
 use tokio::fs;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::backup::{self, BackupManifest};
 use crate::config::StoreDef;
-use crate::db::{Db, calendars::CalendarRecord};
+use crate::crypt::{Decrypted, ENCRYPTED_NO_KEY, Keyring, write_sealed};
+use crate::db::pending_ops::PendingOperation;
+use crate::db::{DB_FILE_NAME, Db, calendars::CalendarRecord};
+use crate::dedupe::{self, DedupeContext};
+use crate::details::ItemDetails;
+use crate::error::{AimError, StoreContext};
+use crate::event::ResolvedEventConditions;
+use crate::export::{CalendarExport, export_event, export_expanded, export_todo};
+use crate::file::sync_folder;
+use crate::home_zone::{change_notice, find_shifts, home_zone};
+use crate::import::{self, ForeignTodo, ImportReport};
+use crate::lock::{DataDirLock, LOCK_TIMEOUT};
+use crate::markdown::{self, MarkdownSyncReport};
+use crate::meta::{check_meta_value, normalize_meta_key};
+use crate::plan::{self, PlanItem};
+use crate::resolve::{IdCandidate, IdMatch, is_uid_prefix, narrow};
+use crate::series::{SeriesOccurrence, SeriesSplit, ThisAndFuture, follow_start, is_override};
 use crate::short_id::ShortIds;
-use crate::store::{CaldavStore, LocalStore, Store, SyncResult};
+use crate::staging::{
+    SyncPlan, plan_pending, plan_pull, push_pending, push_planned, read_staged, write_staged,
+};
+use crate::store::{
+    BrokenItem, CaldavStore, LocalStore, RegisteredStore, Store, StoreError, SubscriptionConfig,
+    SubscriptionStore, SyncResult,
+};
+use crate::todo::{ResolvedTodoDraft, created_at, mark_inbox};
 use crate::{
-    Config, Event, EventConditions, EventDraft, EventPatch, Id, Kind, Pager, Todo, TodoConditions,
-    TodoDraft, TodoPatch, TodoSort,
+    AllDayPolicy, Attendance, BlockRelease, CacheStats, CalendarPrivileges, CheckIn, Collation,
+    Config, DateTimeAnchor, DayLoad, DedupeMode, DedupedEvent, Event, EventConditions, EventDraft,
+    EventPatch, EventStatus, ExpandedTodo, HOUSEKEEPING_INTERVAL, HousekeepingConfig,
+    HousekeepingReport, Id, Invitation, Kind, LooseDateTime, OverdueConfig, PLAN_PREFIX, Pager,
+    Plan, PlanOptions, Priority, RekeyReport, RuleTest, RulesReport, SmartLists, SubtaskProgress,
+    Templates, Todo, TodoConditions, TodoDraft, TodoPatch, TodoSort, TodoStatus, Unplanned,
+    UnplannedReason, Waiting, WorkingHours, ZoneRebase, ZoneShift,
 };
 
 /// Detailed information for a single calendar.
@@ -34,6 +75,20 @@ pub struct CalendarDetails {
     pub enabled: bool,
     /// Whether this calendar is used by default for new items.
     pub is_default: bool,
+    /// Principal owning the calendar, when shared by someone else.
+    pub owner: Option<String>,
+    /// What the current user may do with the calendar.
+    pub privileges: CalendarPrivileges,
+    /// Color of the calendar, when set in config.
+    pub color: Option<String>,
+    /// Number of events in the local cache.
+    pub events: i64,
+    /// Number of todos in the local cache.
+    pub todos: i64,
+    /// When the last successful sync finished.
+    pub last_synced_at: Option<String>,
+    /// Error of the last sync, if it failed after the last success.
+    pub last_sync_error: Option<String>,
     /// Creation timestamp.
     pub created_at: String,
     /// Last update timestamp.
@@ -66,6 +121,17 @@ pub enum CalendarStoreDetails {
         /// User agent used for HTTP requests.
         user_agent: String,
     },
+    /// Read-only subscription details.
+    Subscription {
+        /// URL of the subscribed feed.
+        url: String,
+        /// Minimum number of seconds between two refreshes, `None` to follow the feed.
+        refresh_interval_secs: Option<u64>,
+        /// Request timeout in seconds.
+        timeout_secs: u64,
+        /// User agent used for HTTP requests.
+        user_agent: String,
+    },
 }
 
 /// AIM calendar application core.
@@ -75,12 +141,79 @@ pub struct Aim {
     db: Db,
     short_ids: ShortIds,
     stores: HashMap<String, Box<dyn Store>>,
+    /// The clients of the `CalDAV` stores by store name, shared by the calendars of a store so
+    /// that they reuse its connections.
+    caldav_clients: HashMap<String, CalDavClient>,
     default_calendar: String,
     startup_notices: Vec<String>,
+    keyring: Keyring,
+    /// Whether a transaction is open, see [`Aim::begin`].
+    in_transaction: AtomicBool,
+    /// The lock of the state directory, held as long as the instance.
+    _lock: Option<DataDirLock>,
+}
+
+/// Builder for an [`Aim`] instance with custom stores, see [`Aim::builder`].
+pub struct AimBuilder {
+    config: Config,
+    stores: Vec<(String, Box<dyn Store>)>,
+    skip_sync: bool,
+    read_only: bool,
+}
+
+impl AimBuilder {
+    /// Registers a custom store as a calendar named `name`, identified by the
+    /// [`Store::calendar_id`] of the store.
+    #[must_use]
+    pub fn store(mut self, name: impl Into<String>, store: Box<dyn Store>) -> Self {
+        self.stores.push((name.into(), store));
+        self
+    }
+
+    /// Skips synchronizing the stores when building, so the instance is ready without waiting on
+    /// them, such as to capture a todo with [`Aim::capture_todo`].
+    #[must_use]
+    pub fn skip_sync(mut self) -> Self {
+        self.skip_sync = true;
+        self
+    }
+
+    /// Builds the instance for a command that only reads, which leaves the `CalDAV` calendars
+    /// synced within [`Config::sync_max_age`] as cached instead of syncing them, so that it does
+    /// not wait on the network. Subscriptions are refreshed once stale as always.
+    ///
+    /// Nor does it wait on another aim holding the state directory: the cache is then read without
+    /// syncing, with a startup notice.
+    #[must_use]
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Creates the AIM instance.
+    ///
+    /// # Errors
+    /// If initialization fails, or a store has the ID of another calendar.
+    pub async fn build(self) -> Result<Aim, AimError> {
+        Aim::build(self.config, self.stores, self.skip_sync, self.read_only).await
+    }
+}
+
+impl fmt::Debug for AimBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stores: Vec<_> = self.stores.iter().map(|(name, _)| name).collect();
+        f.debug_struct("AimBuilder")
+            .field("config", &self.config)
+            .field("stores", &stores)
+            .field("skip_sync", &self.skip_sync)
+            .field("read_only", &self.read_only)
+            .finish()
+    }
 }
 
 struct InitializedStores {
     stores: HashMap<String, Box<dyn Store>>,
+    caldav_clients: HashMap<String, CalDavClient>,
     default_calendar: String,
     startup_notices: Vec<String>,
 }
@@ -93,9 +226,12 @@ impl fmt::Debug for Aim {
             .field("db", &self.db)
             .field("short_ids", &self.short_ids)
             .field("stores", &self.stores.len())
+            .field("caldav_clients", &self.caldav_clients.len())
             .field("default_calendar", &self.default_calendar)
             .field("startup_notices", &self.startup_notices)
-            .finish()
+            .field("keyring", &self.keyring)
+            .field("in_transaction", &self.in_transaction)
+            .finish_non_exhaustive()
     }
 }
 
@@ -114,6 +250,7 @@ impl Aim {
                 auth,
                 timeout_secs,
                 user_agent,
+                ..
             } => CalendarStoreDetails::Caldav {
                 base_url: base_url.clone(),
                 calendar_home: calendar_home.clone(),
@@ -126,6 +263,17 @@ impl Aim {
                 timeout_secs: *timeout_secs,
                 user_agent: user_agent.clone(),
             },
+            StoreDef::Subscription {
+                url,
+                refresh_interval_secs,
+                timeout_secs,
+                user_agent,
+            } => CalendarStoreDetails::Subscription {
+                url: url.clone(),
+                refresh_interval_secs: *refresh_interval_secs,
+                timeout_secs: *timeout_secs,
+                user_agent: user_agent.clone(),
+            },
         }
     }
 
@@ -135,24 +283,21 @@ impl Aim {
         entry: &crate::CalendarEntry,
         store_def: &StoreDef,
         db: &Db,
-        state_dir: Option<&std::path::Path>,
-    ) -> Result<Box<dyn Store>, Box<dyn Error>> {
+        state_dir: Option<&Path>,
+        caldav_clients: &mut HashMap<String, CalDavClient>,
+    ) -> Result<Box<dyn Store>, AimError> {
         match store_def {
             StoreDef::Local { .. } => {
-                let calendar_path = entry.calendar_path.as_ref().map_or_else(
-                    || {
-                        state_dir.map_or_else(
-                            || std::path::PathBuf::from("calendar"),
-                            |p| p.join("calendar"),
-                        )
-                    },
-                    std::path::PathBuf::from,
-                );
-                Ok(Box::new(LocalStore::with_db(
-                    calendar_path,
-                    db.clone(),
-                    calendar_id,
-                )))
+                let default_path =
+                    state_dir.map_or_else(|| PathBuf::from("calendar"), |p| p.join("calendar"));
+                let calendar_path = entry
+                    .calendar_path
+                    .as_ref()
+                    .map_or_else(|| default_path.clone(), PathBuf::from);
+                // Items written before the calendar path was configured are moved on their next write
+                let store = LocalStore::with_db(calendar_path, db.clone(), calendar_id)
+                    .adopting(default_path);
+                Ok(Box::new(store))
             }
             StoreDef::Caldav {
                 base_url,
@@ -160,69 +305,280 @@ impl Aim {
                 auth,
                 timeout_secs,
                 user_agent,
+                pool_max_idle_per_host,
+                pool_idle_timeout_secs,
+                max_response_bytes,
             } => {
                 let calendar_href = entry.calendar_href.as_deref().ok_or_else(|| {
-                    format!(
+                    AimError::Config(format!(
                         "Calendar '{calendar_id}' references caldav store but has no calendar_href"
-                    )
+                    ))
                 })?;
-                let caldav_config = aimcal_caldav::CalDavConfig {
-                    base_url: base_url.clone(),
-                    calendar_home: calendar_home.clone(),
-                    auth: auth.clone(),
-                    timeout_secs: *timeout_secs,
-                    user_agent: user_agent.clone(),
+                let client = if let Some(client) = caldav_clients.get(&entry.store) {
+                    client.clone()
+                } else {
+                    let caldav_config = aimcal_caldav::CalDavConfig {
+                        base_url: base_url.clone(),
+                        calendar_home: calendar_home.clone(),
+                        auth: auth.clone(),
+                        timeout_secs: *timeout_secs,
+                        user_agent: user_agent.clone(),
+                        pool_max_idle_per_host: *pool_max_idle_per_host,
+                        pool_idle_timeout_secs: *pool_idle_timeout_secs,
+                        max_response_bytes: *max_response_bytes,
+                    };
+                    let client = CalDavClient::new(caldav_config).map_err(|e| {
+                        AimError::Config(format!("Failed to create CalDAV store: {e}"))
+                    })?;
+                    caldav_clients.insert(entry.store.clone(), client.clone());
+                    client
                 };
-                let backend = CaldavStore::new(
-                    caldav_config,
+                let backend = CaldavStore::with_client(
+                    client,
                     calendar_href.to_string(),
                     db.clone(),
                     calendar_id,
-                )
-                .map_err(|e| format!("Failed to create CalDAV store: {e}"))?;
+                );
+                Ok(Box::new(backend))
+            }
+            StoreDef::Subscription {
+                url,
+                refresh_interval_secs,
+                timeout_secs,
+                user_agent,
+            } => {
+                let config = SubscriptionConfig {
+                    url: url.clone(),
+                    refresh_interval_secs: *refresh_interval_secs,
+                    timeout_secs: *timeout_secs,
+                    user_agent: user_agent.clone(),
+                    name_from_feed: entry.name.is_empty(),
+                };
+                let backend =
+                    SubscriptionStore::new(config, db.clone(), calendar_id).map_err(|e| {
+                        AimError::Config(format!("Failed to create subscription store: {e}"))
+                    })?;
                 Ok(Box::new(backend))
             }
         }
     }
 
+    /// Whether the calendar is backed by a read-only subscription.
+    fn is_caldav(config: &Config, calendar_id: &str) -> bool {
+        matches!(
+            config.resolve_store(calendar_id),
+            Some((_, StoreDef::Caldav { .. }))
+        )
+    }
+
+    fn is_subscription(config: &Config, calendar_id: &str) -> bool {
+        matches!(
+            config.resolve_store(calendar_id),
+            Some((_, StoreDef::Subscription { .. }))
+        )
+    }
+
     /// Get a store by calendar ID.
-    fn get_store(&self, calendar_id: &str) -> Result<&dyn Store, Box<dyn Error>> {
+    fn get_store(&self, calendar_id: &str) -> Result<&dyn Store, AimError> {
         self.stores
             .get(calendar_id)
             .map(Box::as_ref)
-            .ok_or_else(|| format!("Store not found for calendar: {calendar_id}").into())
+            .ok_or_else(|| AimError::NotFound {
+                kind: "Calendar",
+                id: calendar_id.to_string(),
+            })
+    }
+
+    /// Get a store by calendar ID, checking that it accepts writing items of `kind`.
+    fn get_writable_store(&self, calendar_id: &str, kind: Kind) -> Result<&dyn Store, AimError> {
+        let store = self.get_store(calendar_id)?;
+        let capabilities = store.capabilities();
+        let reason = if capabilities.read_only {
+            let what = if Self::is_subscription(&self.config, calendar_id) {
+                "subscription"
+            } else {
+                "calendar"
+            };
+            format!("'{calendar_id}' is a read-only {what}")
+        } else if kind == Kind::Event && !capabilities.supports_events {
+            format!("calendar '{calendar_id}' does not support events")
+        } else if kind == Kind::Todo && !capabilities.supports_todos {
+            format!("calendar '{calendar_id}' does not support todos")
+        } else {
+            return Ok(store);
+        };
+        Err(AimError::InvalidInput {
+            field: "calendar",
+            reason,
+        })
+    }
+
+    /// Get a writable store for an event, falling back from `calendar_id` to another calendar
+    /// holding a copy of it, see [`Aim::dedupe_events`].
+    async fn get_writable_event_store(
+        &self,
+        uid: &str,
+        calendar_id: &str,
+    ) -> Result<&dyn Store, AimError> {
+        let err = match self.get_writable_store(calendar_id, Kind::Event) {
+            Ok(store) => return Ok(store),
+            Err(e) => e,
+        };
+        for other in self.db.resources.list_calendars_by_uid(uid).await? {
+            if other != calendar_id
+                && let Ok(store) = self.get_writable_store(&other, Kind::Event)
+            {
+                return Ok(store);
+            }
+        }
+        Err(err)
+    }
+
+    /// Resolves an id to the UID of the single item it refers to.
+    ///
+    /// # Errors
+    /// If no item matches the id, or more than one does, see [`Aim::resolve_id`].
+    async fn resolve_uid(&self, id: &Id, kind_hint: Option<Kind>) -> Result<String, AimError> {
+        Ok(self.resolve_single(id, kind_hint).await?.uid)
+    }
+
+    async fn resolve_single(
+        &self,
+        id: &Id,
+        kind_hint: Option<Kind>,
+    ) -> Result<IdCandidate, AimError> {
+        let mut candidates = self.resolve_id(id, kind_hint).await?;
+        if candidates.len() > 1 {
+            return Err(AimError::AmbiguousShortId {
+                id: id.as_uid().to_string(),
+                candidates: candidates.into_iter().map(|c| c.uid).collect(),
+            });
+        }
+        candidates
+            .pop()
+            .ok_or_else(|| not_found(kind_name(kind_hint), id))
     }
 
     /// Creates a new AIM instance with the given configuration.
     ///
     /// # Errors
     /// If initialization fails.
-    pub async fn new(mut config: Config) -> Result<Self, Box<dyn Error>> {
+    pub async fn new(config: Config) -> Result<Self, AimError> {
+        Self::builder(config).build().await
+    }
+
+    /// Starts building an AIM instance, to register custom stores along with the
+    /// configured calendars.
+    #[must_use]
+    pub fn builder(config: Config) -> AimBuilder {
+        AimBuilder {
+            config,
+            stores: Vec::new(),
+            skip_sync: false,
+            read_only: false,
+        }
+    }
+
+    async fn build(
+        mut config: Config,
+        registered: Vec<(String, Box<dyn Store>)>,
+        skip_sync: bool,
+        read_only: bool,
+    ) -> Result<Self, AimError> {
         let now = Zoned::now();
 
         config.expand_env_vars()?;
         config.normalize()?;
         prepare(&config).await?;
+        // A command that only reads does not wait on another aim, it reads the cache as is
+        let (lock, locked_by) = match lock_state_dir(&config, read_only).await {
+            Err(AimError::Locked { holder, .. }) if read_only => (None, Some(holder)),
+            lock => (lock?, None),
+        };
+        let keyring = Keyring::from_config(&config.encryption).map_err(AimError::Config)?;
 
         let db = initialize_db(&config).await?;
         let short_ids = ShortIds::new(db.clone());
 
         // Handle legacy vs multi-calendar format
+        let registered_ids: HashSet<_> = registered
+            .iter()
+            .map(|(_, store)| store.calendar_id().to_string())
+            .collect();
         let InitializedStores {
-            stores,
+            mut stores,
+            caldav_clients,
             default_calendar,
-            startup_notices,
+            mut startup_notices,
         } = if config.is_legacy_format() {
             Self::initialize_legacy_calendar(&config, &db).await?
         } else {
-            Self::initialize_multi_calendars(&config, &db).await?
+            Self::initialize_multi_calendars(&config, &db, &registered_ids).await?
         };
 
-        // Sync all stores with local cache
-        for (calendar_id, backend) in &stores {
-            backend.sync_cache().await.map_err(|e| {
-                format!("Failed to sync store cache for calendar '{calendar_id}': {e}")
-            })?;
+        for (name, store) in registered {
+            let calendar_id = store.calendar_id().to_string();
+            if stores.contains_key(&calendar_id) {
+                return Err(AimError::Config(format!(
+                    "Calendar '{calendar_id}' is registered more than once"
+                )));
+            }
+
+            let record =
+                CalendarRecord::new(calendar_id.clone(), name, "custom".to_string(), 0, true);
+            db.calendars.upsert(record).await?;
+            let store = RegisteredStore::new(store, db.clone());
+            stores.insert(calendar_id, Box::new(store));
+        }
+
+        startup_notices.extend(sync_folder_notices(&config));
+        if let Some(holder) = &locked_by {
+            startup_notices.push(format!(
+                "The state directory is in use by {holder}, the cache is read without syncing"
+            ));
+        }
+
+        // A database of a newer aim is a copy, the stores are not synced into it
+        if let Some(stamp) = db.newer_schema() {
+            startup_notices.push(format!(
+                "Database was created by {}, it is read as is and nothing is saved to it",
+                stamp.created_by()
+            ));
+        }
+
+        // Sync all stores with local cache, after pushing the writes staged for them
+        if !skip_sync && locked_by.is_none() && db.newer_schema().is_none() {
+            let report = push_pending(&db, &stores).await?;
+            for (uid, reason) in report.failed {
+                startup_notices.push(format!("'{uid}' is not in its calendar yet: {reason}"));
+            }
+
+            for (calendar_id, backend) in &stores {
+                if read_only
+                    && Self::is_caldav(&config, calendar_id)
+                    && is_fresh(&db, calendar_id, config.sync_max_age, &now).await?
+                {
+                    tracing::debug!(calendar_id, "calendar synced recently, not syncing");
+                    continue;
+                }
+
+                let result = traced_sync(calendar_id, backend.sync_cache()).await;
+                record_sync(&db, calendar_id, &result).await?;
+                match result {
+                    Ok(_) => {
+                        startup_notices.extend(backend.broken_items().iter().map(broken_notice));
+                    }
+                    // Subscriptions keep serving their last cached copy
+                    Err(e) if Self::is_subscription(&config, calendar_id) => {
+                        startup_notices.push(format!("Calendar '{calendar_id}' is stale: {e}"));
+                    }
+                    Err(e) => return Err(sync_error(calendar_id, e)),
+                }
+            }
+        }
+
+        if db.newer_schema().is_none() {
+            startup_notices.extend(home_zone_notice(&config, &db, &now).await?);
         }
 
         Ok(Self {
@@ -231,15 +587,19 @@ impl Aim {
             db,
             short_ids,
             stores,
+            caldav_clients,
             default_calendar,
             startup_notices,
+            keyring,
+            in_transaction: AtomicBool::new(false),
+            _lock: lock,
         })
     }
 
     async fn initialize_legacy_calendar(
         config: &Config,
         db: &Db,
-    ) -> Result<InitializedStores, Box<dyn Error>> {
+    ) -> Result<InitializedStores, AimError> {
         let default_calendar_id = "default".to_string();
 
         // Legacy mode: create a local store using calendar_path or state_dir
@@ -256,17 +616,20 @@ impl Aim {
             calendar_path,
             priority: 0,
             enabled: true,
+            color: None,
         };
         let store_def = StoreDef::Local {
             calendar_path: None,
         };
 
+        let mut caldav_clients = HashMap::new();
         let backend = Self::create_store(
             default_calendar_id.clone(),
             &entry,
             &store_def,
             db,
             config.state_dir.as_deref(),
+            &mut caldav_clients,
         )?;
 
         let calendar = CalendarRecord::new(
@@ -283,20 +646,60 @@ impl Aim {
 
         Ok(InitializedStores {
             stores,
+            caldav_clients,
             default_calendar: default_calendar_id,
             startup_notices: Vec::new(),
         })
     }
 
+    /// Picks the default calendar among the enabled ones: the one set with
+    /// `aim calendar set-default`, then the configured one, then the highest priority.
+    fn resolve_default_calendar(
+        config: &Config,
+        stores: &HashMap<String, Box<dyn Store>>,
+        user_default: Option<String>,
+    ) -> Result<String, AimError> {
+        if let Some(id) = user_default.filter(|id| stores.contains_key(id)) {
+            return Ok(id);
+        }
+        if stores.contains_key(&config.default_calendar) {
+            return Ok(config.default_calendar.clone());
+        }
+        config
+            .calendars
+            .iter()
+            .filter(|calendar| stores.contains_key(&calendar.id))
+            .min_by_key(|calendar| calendar.priority)
+            .map(|calendar| calendar.id.clone())
+            .ok_or_else(|| {
+                AimError::Config("No enabled calendars found in configuration".to_string())
+            })
+    }
+
+    #[expect(clippy::too_many_lines)]
     async fn initialize_multi_calendars(
         config: &Config,
         db: &Db,
-    ) -> Result<InitializedStores, Box<dyn Error>> {
+        registered_ids: &HashSet<String>,
+    ) -> Result<InitializedStores, AimError> {
         if config.calendars.is_empty() {
-            return Err("No calendars configured".into());
+            return Err(AimError::Config("No calendars configured".to_string()));
         }
 
         let existing = db.calendars.list().await?;
+        let user_enabled: HashMap<_, _> = existing
+            .iter()
+            .filter_map(|calendar| Some((calendar.id.clone(), calendar.user_enabled?)))
+            .collect();
+        let user_default = existing
+            .iter()
+            .find(|calendar| calendar.is_default)
+            .map(|calendar| calendar.id.clone());
+        // Calendars without a configured name keep the one they got, such as from their feed
+        let known_names: HashMap<_, _> = existing
+            .iter()
+            .map(|calendar| (calendar.id.clone(), calendar.name.clone()))
+            .collect();
         let configured_ids: HashSet<_> = config
             .calendars
             .iter()
@@ -304,7 +707,10 @@ impl Aim {
             .collect();
         let mut auto_disabled = Vec::new();
         for calendar in existing {
-            if configured_ids.contains(&calendar.id) || !calendar.enabled {
+            if configured_ids.contains(&calendar.id)
+                || registered_ids.contains(&calendar.id)
+                || !calendar.enabled
+            {
                 continue;
             }
 
@@ -315,27 +721,38 @@ impl Aim {
         let mut effective = Vec::with_capacity(config.calendars.len());
         for calendar in &config.calendars {
             let store_def = config.stores.get(&calendar.store).ok_or_else(|| {
-                format!(
+                AimError::Config(format!(
                     "Store '{}' not found for calendar '{}'",
                     calendar.store, calendar.id
-                )
+                ))
             })?;
             let calendar_kind = match store_def {
                 StoreDef::Local { .. } => "local",
                 StoreDef::Caldav { .. } => "caldav",
+                StoreDef::Subscription { .. } => "subscription",
             };
+            // Enabling or disabling with `aim calendar` overrides the config
+            let enabled = user_enabled
+                .get(&calendar.id)
+                .copied()
+                .unwrap_or(calendar.enabled);
+            let name = Some(&calendar.name)
+                .filter(|name| !name.is_empty())
+                .or_else(|| known_names.get(&calendar.id))
+                .unwrap_or(&calendar.id);
             let record = CalendarRecord::new(
                 calendar.id.clone(),
-                calendar.name.clone(),
+                name.clone(),
                 calendar_kind.to_string(),
                 calendar.priority,
-                calendar.enabled,
+                enabled,
             );
             db.calendars.upsert(record).await?;
-            effective.push((calendar, calendar.enabled));
+            effective.push((calendar, enabled));
         }
 
         let mut stores = HashMap::new();
+        let mut caldav_clients = HashMap::new();
         for (calendar, enabled) in &effective {
             if !enabled {
                 continue;
@@ -344,7 +761,7 @@ impl Aim {
             let store_def = config
                 .stores
                 .get(&calendar.store)
-                .ok_or_else(|| format!("Store '{}' not found", calendar.store))?;
+                .ok_or_else(|| AimError::Config(format!("Store '{}' not found", calendar.store)))?;
 
             let backend = Self::create_store(
                 calendar.id.clone(),
@@ -352,25 +769,18 @@ impl Aim {
                 store_def,
                 db,
                 config.state_dir.as_deref(),
+                &mut caldav_clients,
             )?;
             stores.insert(calendar.id.clone(), backend);
         }
 
         if stores.is_empty() {
-            return Err("No enabled calendars found in configuration".into());
+            return Err(AimError::Config(
+                "No enabled calendars found in configuration".to_string(),
+            ));
         }
 
-        let default_calendar = if stores.contains_key(&config.default_calendar) {
-            config.default_calendar.clone()
-        } else {
-            config
-                .calendars
-                .iter()
-                .filter(|calendar| stores.contains_key(&calendar.id))
-                .min_by_key(|calendar| calendar.priority)
-                .map(|calendar| calendar.id.clone())
-                .ok_or("No enabled calendars found in configuration")?
-        };
+        let default_calendar = Self::resolve_default_calendar(config, &stores, user_default)?;
 
         let startup_notices = if auto_disabled.is_empty() {
             Vec::new()
@@ -383,6 +793,7 @@ impl Aim {
 
         Ok(InitializedStores {
             stores,
+            caldav_clients,
             default_calendar,
             startup_notices,
         })
@@ -402,20 +813,131 @@ impl Aim {
     /// Create a default event draft based on the AIM configuration.
     #[must_use]
     pub fn default_event_draft(&self) -> EventDraft {
-        EventDraft::default(&self.now)
+        EventDraft::default(&self.now, self.config.default_event_duration)
+    }
+
+    /// Create an event draft from the single event of iCalendar content, such as a forwarded
+    /// invitation, taking its summary, description, time and status.
+    ///
+    /// `source` names the content in errors, such as the file it was read from.
+    ///
+    /// # Errors
+    /// If the content cannot be parsed or does not hold exactly one event.
+    pub fn event_draft_from_ics(
+        &self,
+        source: &Path,
+        content: &str,
+    ) -> Result<EventDraft, AimError> {
+        let event = import::parse_event(source, content)?;
+        let mut draft = self.default_event_draft();
+        import::fill_event_draft(&mut draft, &event);
+        Ok(draft)
     }
 
     /// Get a event by its id.
     ///
     /// # Errors
     /// If the event is not found or database access fails.
-    pub async fn get_event(&self, id: &Id) -> Result<impl Event + 'static, Box<dyn Error>> {
-        let uid = self.short_ids.get_uid(id).await?;
-        match self.db.events.get(&uid).await {
-            Ok(Some(event)) => Ok(self.short_ids.event(event).await?),
-            Ok(None) => Err("Event not found".into()),
-            Err(e) => Err(e.into()),
+    pub async fn get_event(&self, id: &Id) -> Result<impl Event + 'static, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Event)).await?;
+        match self.db.events.get(&uid).await? {
+            Some(event) => Ok(self.short_ids.event(event).await?),
+            None => Err(not_found("Event", id)),
+        }
+    }
+
+    /// Get the details of an event kept in its store, see [`ItemDetails`].
+    ///
+    /// Only the calendar and resource are filled in if the store can't be read.
+    ///
+    /// # Errors
+    /// If the event is not found or database access fails.
+    pub async fn get_event_details(&self, id: &Id) -> Result<ItemDetails, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(event) = self.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
+        };
+
+        let store = self.get_store(&event.calendar_id)?;
+        let mut details = match store.get_event(&uid).await {
+            Ok(event) => ItemDetails::from_event(event, &self.now),
+            Err(e) => {
+                tracing::warn!(uid, error = %e, "failed to read event from store");
+                ItemDetails::default()
+            }
+        };
+        if let Some(from) = event.split_from() {
+            details.split_from = Some(match self.db.events.get(&from).await? {
+                Some(master) => {
+                    let master = self.short_ids.event(master).await?;
+                    match master.short_id() {
+                        Some(short_id) => format!("#{short_id} {}", master.summary()),
+                        None => master.summary().to_string(),
+                    }
+                }
+                None => from.into_owned(),
+            });
+        }
+        self.fill_item_location(&mut details, &uid, event.calendar_id)
+            .await?;
+        Ok(details)
+    }
+
+    /// Export an event as a standalone iCalendar object, to share it outside of its calendar.
+    ///
+    /// The calendar has `METHOD:PUBLISH` and a `VTIMEZONE` for each time zone the event refers
+    /// to. With `occurrence`, only the occurrence of a recurring event on that date is exported,
+    /// as a non-recurring event. The calendar is written out with [`CalendarExport::write_to`].
+    ///
+    /// # Errors
+    /// If the event is not found, does not occur on `occurrence`, or its store can't be read.
+    pub async fn export_event(
+        &self,
+        id: &Id,
+        occurrence: Option<Date>,
+    ) -> Result<CalendarExport, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(event) = self.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
+        };
+
+        let store = self.get_store(&event.calendar_id)?;
+        let event = store
+            .get_event(&uid)
+            .await
+            .map_err(|e| AimError::from_store(&uid, e))?;
+        export_event(event, occurrence, &self.now)
+    }
+
+    /// Export the events of all enabled calendars from `from` to `to` as standalone events, one
+    /// per occurrence, for tools that don't understand recurrence rules.
+    ///
+    /// Overrides of occurrences take their place, excluded and cancelled occurrences are left
+    /// out, and each occurrence gets the UID of its series followed by the time it recurs at. The
+    /// calendar is marked with `X-AIM-EXPANDED`, which [`Aim::import_ics`] reports.
+    ///
+    /// # Errors
+    /// If there are more than `limit` occurrences, or a store can't be read.
+    pub async fn export_expanded(
+        &self,
+        from: Date,
+        to: Date,
+        limit: usize,
+    ) -> Result<CalendarExport, AimError> {
+        if from > to {
+            return Err(AimError::InvalidInput {
+                field: "to",
+                reason: format!("{to} is before {from}"),
+            });
+        }
+
+        let mut events = Vec::new();
+        let mut overrides = Vec::new();
+        for store in self.stores.values() {
+            events.extend(store.list_events().await?.into_iter().map(|(_, e)| e));
+            overrides.extend(store.list_event_overrides().await?);
         }
+        export_expanded(events, &overrides, from, to, limit, &self.now)
     }
 
     /// Find the latest event matching the given summary.
@@ -425,7 +947,7 @@ impl Aim {
     pub async fn find_latest_event_by_summary(
         &self,
         summary: &str,
-    ) -> Result<Option<impl Event + 'static>, Box<dyn Error>> {
+    ) -> Result<Option<impl Event + 'static>, AimError> {
         let Some(event) = self.db.events.find_latest_by_summary(summary).await? else {
             return Ok(None);
         };
@@ -437,25 +959,15 @@ impl Aim {
     ///
     /// # Errors
     /// If the event is not found, database or backend access fails.
-    pub async fn new_event(
-        &self,
-        draft: EventDraft,
-    ) -> Result<impl Event + 'static, Box<dyn Error>> {
-        let uid = self.generate_uid(Kind::Event).await?;
-        let event = draft.resolve(&self.now).into_ics(&uid);
-
-        // Resolve calendar: use draft.calendar_id or fall back to default
-        let calendar_id = draft
-            .calendar_id
-            .as_deref()
-            .unwrap_or(&self.default_calendar);
-        let backend = self.get_store(calendar_id)?;
+    pub async fn new_event(&self, draft: EventDraft) -> Result<impl Event + 'static, AimError> {
+        let (uid, event, calendar_id) = self.prepare_event(&draft).await?;
+        let backend = self.get_writable_store(calendar_id, Kind::Event)?;
 
         // Create event in store
         let resource_id = backend
             .create_event(&uid, &event)
             .await
-            .map_err(|e| format!("Failed to create event in store: {e}"))?;
+            .map_err(|e| AimError::from_store(&uid, e))?;
 
         // Store in database with resource mapping
         self.db.upsert_event(&uid, &event, calendar_id).await?;
@@ -468,6 +980,24 @@ impl Aim {
         Ok(event)
     }
 
+    /// Resolves a draft into the event to create, along with its UID and the calendar to create
+    /// it in, which must accept events.
+    async fn prepare_event<'a>(
+        &'a self,
+        draft: &'a EventDraft,
+    ) -> Result<(String, VEvent<String>, &'a str), AimError> {
+        let uid = self.generate_uid(Kind::Event).await?;
+        let event = draft.resolve(&self.config, &self.now)?.into_ics(&uid);
+
+        // Resolve calendar: use draft.calendar_id or fall back to default
+        let calendar_id = draft
+            .calendar_id
+            .as_deref()
+            .unwrap_or(&self.default_calendar);
+        self.get_writable_store(calendar_id, Kind::Event)?;
+        Ok((uid, event, calendar_id))
+    }
+
     /// Upsert an event into the calendar.
     ///
     /// # Errors
@@ -476,22 +1006,23 @@ impl Aim {
         &self,
         id: &Id,
         patch: EventPatch,
-    ) -> Result<impl Event + 'static, Box<dyn Error>> {
-        let uid = self.short_ids.get_uid(id).await?;
-        let Some(_event) = self.db.events.get(&uid).await? else {
-            return Err("Event not found".into());
+    ) -> Result<impl Event + 'static, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(event_record) = self.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
         };
 
         // Get calendar_id from event record
-        let event_record = self.db.events.get(&uid).await?.ok_or("Event not found")?;
-        let backend = self.get_store(&event_record.calendar_id)?;
+        let backend = self
+            .get_writable_event_store(&uid, &event_record.calendar_id)
+            .await?;
         let calendar_id = backend.calendar_id();
 
         // Update event through backend
         let updated_event = backend
             .update_event(&uid, &patch)
             .await
-            .map_err(|e| format!("Failed to update event in store: {e}"))?;
+            .map_err(|e| AimError::from_store(&uid, e))?;
 
         // Update database
         self.db
@@ -502,261 +1033,2087 @@ impl Aim {
         Ok(event_with_id)
     }
 
-    /// Get the kind of the given id, which can be either an event or a todo.
+    /// Checks an event in, recording whether it was attended or skipped with an optional note on
+    /// how it went, see [`Event::check_in`].
+    ///
+    /// The check-in replaces the one the event had. It is kept in properties of the event's own,
+    /// so it survives syncing while its status and other clients are left as they are. A
+    /// recurring event has a single check-in, for the event as a whole.
     ///
     /// # Errors
-    /// If the id is not found or database access fails.
-    pub async fn get_kind(&self, id: &Id) -> Result<Kind, Box<dyn Error>> {
-        tracing::debug!(?id, "getting kind of id");
-        if let Some(data) = self.short_ids.get(id).await? {
-            return Ok(data.kind);
+    /// If the note is empty, the event is not found, database or backend access fails.
+    pub async fn check_in_event(
+        &self,
+        id: &Id,
+        attendance: Attendance,
+        note: Option<&str>,
+    ) -> Result<impl Event + 'static, AimError> {
+        let note = note.map(str::trim);
+        if note.is_some_and(str::is_empty) {
+            return Err(AimError::InvalidInput {
+                field: "note",
+                reason: "must not be empty".to_string(),
+            });
         }
 
-        let uid = id.as_uid();
+        let patch = EventPatch {
+            check_in: Some(Some(CheckIn {
+                attendance,
+                note: note.map(ToString::to_string),
+            })),
+            ..Default::default()
+        };
+        self.update_event(id, patch).await
+    }
+
+    /// Update the occurrence of a recurring event on `occurrence`, along with the occurrences
+    /// after it.
+    ///
+    /// With [`ThisAndFuture::Split`], the event ends before the occurrence and the patch applies
+    /// to a new event starting from it, which records the event it was split from. With
+    /// [`ThisAndFuture::Override`], the patch applies to an override with
+    /// `RECURRENCE-ID;RANGE=THISANDFUTURE` kept by the store next to the event. From the first
+    /// occurrence on, the event is updated as a whole. Moving the start moves the end, `UNTIL`,
+    /// `RDATE` and `EXDATE` along, unless the patch sets them.
+    ///
+    /// Returns the event from the occurrence on.
+    ///
+    /// # Errors
+    /// If the event is not found, does not occur on `occurrence`, or database or backend access
+    /// fails.
+    pub async fn update_event_from(
+        &self,
+        id: &Id,
+        occurrence: Date,
+        mut patch: EventPatch,
+        mode: ThisAndFuture,
+    ) -> Result<impl Event + 'static, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(event_record) = self.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
+        };
 
-        tracing::debug!(uid, "checking if id is an event");
-        if self.db.events.get(uid).await?.is_some() {
-            return Ok(Kind::Event);
+        let backend = self
+            .get_writable_event_store(&uid, &event_record.calendar_id)
+            .await?;
+        let calendar_id = backend.calendar_id();
+        let event = backend
+            .get_event(&uid)
+            .await
+            .map_err(|e| AimError::from_store(&uid, e))?;
+
+        let occurrence = SeriesOccurrence::find(&event, occurrence)?;
+        if occurrence.is_first() {
+            follow_start(&mut patch, &event);
+            let updated = backend
+                .update_event(&uid, &patch)
+                .await
+                .map_err(|e| AimError::from_store(&uid, e))?;
+            self.db.upsert_event(&uid, &updated, calendar_id).await?;
+            return Ok(self.short_ids.event(updated).await?);
         }
 
-        tracing::debug!(uid, "checking if id is a todo");
-        if self.db.todos.get(uid).await?.is_some() {
-            return Ok(Kind::Todo);
+        match mode {
+            ThisAndFuture::Split => {
+                let new_uid = self.generate_uid(Kind::Event).await?;
+                let SeriesSplit { master, mut future } =
+                    occurrence.split(&event, &new_uid, &self.now)?;
+                follow_start(&mut patch, &future);
+                patch.resolve(self.now.clone()).apply_to(&mut future);
+
+                // Create the new series first, so the occurrences are not lost if it fails
+                let resource_id = backend
+                    .create_event(&new_uid, &future)
+                    .await
+                    .map_err(|e| AimError::from_store(&new_uid, e))?;
+                self.db.upsert_event(&new_uid, &future, calendar_id).await?;
+                self.db
+                    .resources
+                    .insert(&new_uid, calendar_id, &resource_id, None)
+                    .await?;
+
+                let updated = backend
+                    .update_event(&uid, &master)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                self.db.upsert_event(&uid, &updated, calendar_id).await?;
+
+                Ok(self.short_ids.event(future).await?)
+            }
+            ThisAndFuture::Override => {
+                let mut over = occurrence.override_from(&event, &self.now);
+                follow_start(&mut patch, &over);
+                patch.resolve(self.now.clone()).apply_to(&mut over);
+                backend
+                    .put_event_override(&uid, &over)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Ok(self.short_ids.event(over).await?)
+            }
         }
-
-        Err("Id not found".into())
     }
 
-    /// List events matching the given conditions.
+    /// Get the kind of the given id, which can be either an event or a todo.
     ///
     /// # Errors
-    /// If database access fails.
-    pub async fn list_events(
-        &self,
-        conds: &EventConditions,
-        pager: &Pager,
-    ) -> Result<Vec<impl Event + 'static>, Box<dyn Error>> {
-        let conds = conds.resolve(&self.now)?;
-        let events = self.db.events.list(&conds, pager).await?;
-        let events = self.short_ids.events(events).await?;
-        Ok(events)
+    /// If the id is not found, ambiguous or database access fails.
+    pub async fn get_kind(&self, id: &Id) -> Result<Kind, AimError> {
+        tracing::debug!(?id, "getting kind of id");
+        Ok(self.resolve_single(id, None).await?.kind)
     }
 
-    /// Counts the number of events matching the given conditions.
+    /// Finds the items an id may refer to, narrowed to those of the hinted kind if given.
+    ///
+    /// The id matches the item with that short ID, the item with that UID, and, when no item
+    /// matches exactly, the items whose UID starts with it, if it has at least
+    /// [`MIN_UID_PREFIX_LEN`](crate::MIN_UID_PREFIX_LEN) characters. A [`Id::Uid`] only matches
+    /// the item with that UID.
+    ///
+    /// Returns a single candidate if the id is unambiguous, and all of them otherwise, for the
+    /// caller to choose from rather than guess.
     ///
     /// # Errors
-    /// If database access fails.
-    pub async fn count_events(&self, conds: &EventConditions) -> Result<i64, Box<dyn Error>> {
-        let conds = conds.resolve(&self.now)?;
-        Ok(self.db.events.count(&conds).await?)
+    /// If no item matches the id or database access fails.
+    pub async fn resolve_id(
+        &self,
+        id: &Id,
+        kind_hint: Option<Kind>,
+    ) -> Result<Vec<IdCandidate>, AimError> {
+        let literal = id.as_uid();
+        let mut candidates = Vec::new();
+        if let Some(data) = self.short_ids.get(id).await? {
+            candidates.extend(
+                self.id_candidate(&data.uid, data.kind, IdMatch::ShortId)
+                    .await?,
+            );
+        }
+        if let Some(event) = self.db.events.get(literal).await? {
+            candidates.push(self.event_candidate(event, IdMatch::Uid).await?);
+        }
+        if let Some(todo) = self.db.todos.get(literal).await? {
+            candidates.push(self.todo_candidate(todo, IdMatch::Uid).await?);
+        }
+        // Prefixes only count without an exact match, so spare the scan if there is one
+        let exact = candidates
+            .iter()
+            .any(|c| kind_hint.is_none_or(|kind| c.kind == kind));
+        if !exact && matches!(id, Id::ShortIdOrUid(_)) && is_uid_prefix(literal) {
+            for event in self.db.events.list_by_uid_prefix(literal).await? {
+                candidates.push(self.event_candidate(event, IdMatch::UidPrefix).await?);
+            }
+            for todo in self.db.todos.list_by_uid_prefix(literal).await? {
+                candidates.push(self.todo_candidate(todo, IdMatch::UidPrefix).await?);
+            }
+        }
+
+        let candidates = narrow(candidates, kind_hint);
+        tracing::debug!(?id, count = candidates.len(), "resolved id");
+        if candidates.is_empty() {
+            return Err(not_found(kind_name(kind_hint), id));
+        }
+        Ok(candidates)
     }
 
-    /// Create a default todo draft based on the AIM configuration.
+    async fn id_candidate(
+        &self,
+        uid: &str,
+        kind: Kind,
+        matched: IdMatch,
+    ) -> Result<Option<IdCandidate>, AimError> {
+        // Short IDs outlive the items they were handed out to, until flushed
+        Ok(match kind {
+            Kind::Event => match self.db.events.get(uid).await? {
+                Some(event) => Some(self.event_candidate(event, matched).await?),
+                None => None,
+            },
+            Kind::Todo => match self.db.todos.get(uid).await? {
+                Some(todo) => Some(self.todo_candidate(todo, matched).await?),
+                None => None,
+            },
+        })
+    }
+
+    async fn event_candidate(
+        &self,
+        event: impl Event,
+        matched: IdMatch,
+    ) -> Result<IdCandidate, AimError> {
+        let event = self.short_ids.event(event).await?;
+        Ok(IdCandidate {
+            uid: event.uid().into_owned(),
+            short_id: event.short_id,
+            kind: Kind::Event,
+            summary: event.summary().into_owned(),
+            date: event.start(),
+            matched,
+        })
+    }
+
+    async fn todo_candidate(
+        &self,
+        todo: impl Todo,
+        matched: IdMatch,
+    ) -> Result<IdCandidate, AimError> {
+        let todo = self.short_ids.todo(todo).await?;
+        Ok(IdCandidate {
+            uid: todo.uid().into_owned(),
+            short_id: todo.short_id,
+            kind: Kind::Todo,
+            summary: todo.summary().into_owned(),
+            date: todo.due(),
+            matched,
+        })
+    }
+
+    /// Get the custom metadata of an event or todo, sorted by key.
     ///
     /// # Errors
-    /// If date/time resolution fails.
-    pub fn default_todo_draft(&self) -> Result<TodoDraft, String> {
-        TodoDraft::default(&self.config, &self.now)
+    /// If the item is not found or database access fails.
+    pub async fn get_meta(&self, id: &Id) -> Result<Vec<(String, String)>, AimError> {
+        let uid = self.resolve_uid(id, None).await?;
+        Ok(self.db.meta.get(&uid).await?)
     }
 
-    /// Find the latest todo matching the given summary.
+    /// Set custom metadata of an event or todo, replacing the value of the key if it is set.
+    ///
+    /// Keys are case-insensitive, see [`normalize_meta_key`](crate::normalize_meta_key), and
+    /// values are plain text of at most [`MAX_META_VALUE_LEN`](crate::MAX_META_VALUE_LEN)
+    /// characters. Returns the metadata of the item afterwards.
+    ///
+    /// # Errors
+    /// If the key or value is invalid, the item is not found, database or backend access fails.
+    pub async fn set_meta(
+        &self,
+        id: &Id,
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<(String, String)>, AimError> {
+        let key = normalize_meta_key(key).map_err(|reason| AimError::InvalidInput {
+            field: "metadata key",
+            reason,
+        })?;
+        check_meta_value(value).map_err(|reason| AimError::InvalidInput {
+            field: "metadata value",
+            reason,
+        })?;
+        self.edit_meta(id, (key, Some(value.to_string()))).await
+    }
+
+    /// Remove custom metadata of an event or todo, doing nothing if the key is not set.
+    ///
+    /// Returns the metadata of the item afterwards.
+    ///
+    /// # Errors
+    /// If the key is invalid, the item is not found, database or backend access fails.
+    pub async fn unset_meta(&self, id: &Id, key: &str) -> Result<Vec<(String, String)>, AimError> {
+        let key = normalize_meta_key(key).map_err(|reason| AimError::InvalidInput {
+            field: "metadata key",
+            reason,
+        })?;
+        self.edit_meta(id, (key, None)).await
+    }
+
+    async fn edit_meta(
+        &self,
+        id: &Id,
+        edit: (String, Option<String>),
+    ) -> Result<Vec<(String, String)>, AimError> {
+        let meta = match self.get_kind(id).await? {
+            Kind::Event => {
+                let patch = EventPatch {
+                    meta: vec![edit],
+                    ..Default::default()
+                };
+                self.update_event(id, patch).await?.meta()
+            }
+            Kind::Todo => {
+                let patch = TodoPatch {
+                    meta: vec![edit],
+                    ..Default::default()
+                };
+                self.update_todo(id, patch).await?.meta()
+            }
+        };
+        Ok(meta)
+    }
+
+    /// List events matching the given conditions.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn list_events(
+        &self,
+        conds: &EventConditions,
+        pager: &Pager,
+    ) -> Result<Vec<impl Event + 'static>, AimError> {
+        let conds = conds.resolve(&self.now).map_err(invalid_conditions)?;
+        let events = self.db.events.list(&conds, pager).await?;
+        let events = self.short_ids.events(events).await?;
+        Ok(events)
+    }
+
+    /// Counts the number of events matching the given conditions.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn count_events(&self, conds: &EventConditions) -> Result<i64, AimError> {
+        let conds = conds.resolve(&self.now).map_err(invalid_conditions)?;
+        Ok(self.db.events.count(&conds).await?)
+    }
+
+    /// Collapses the copies of an event kept in several calendars into one, see [`DedupeMode`].
+    ///
+    /// The primary copy of each event is in a writable calendar when there is any, which is also
+    /// where [`Aim::update_event`] writes to. With [`DedupeMode::Heuristic`], the attendees of
+    /// events that may be copies of each other are read from their stores.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn dedupe_events<E: Event>(
+        &self,
+        events: Vec<E>,
+        mode: DedupeMode,
+    ) -> Result<Vec<DedupedEvent<E>>, AimError> {
+        let mut ctx = DedupeContext::default();
+        if mode != DedupeMode::Off {
+            ctx.writable = self
+                .stores
+                .keys()
+                .filter(|id| self.get_writable_store(id, Kind::Event).is_ok())
+                .cloned()
+                .collect();
+            for event in &events {
+                let uid = event.uid();
+                let calendars: Vec<_> = self
+                    .db
+                    .resources
+                    .list_calendars_by_uid(&uid)
+                    .await?
+                    .into_iter()
+                    .filter(|id| self.stores.contains_key(id))
+                    .collect();
+                if calendars.len() > 1 {
+                    ctx.mirrors.insert(uid.into_owned(), calendars);
+                }
+            }
+        }
+
+        if mode == DedupeMode::Heuristic {
+            // Only events sharing a summary, start and end may be copies of each other
+            let mut candidates: HashMap<_, Vec<(String, String)>> = HashMap::new();
+            for event in &events {
+                if let Some(key) = DedupeContext::heuristic_key(event)
+                    && let Some(calendar_id) = event.calendar_id()
+                {
+                    let copy = (calendar_id.into_owned(), event.uid().into_owned());
+                    candidates.entry(key).or_default().push(copy);
+                }
+            }
+            let candidates: Vec<_> = candidates
+                .into_values()
+                .filter(|copies| copies.len() > 1)
+                .flatten()
+                .collect();
+            for (calendar_id, uid) in candidates {
+                let Some(store) = self.stores.get(&calendar_id) else {
+                    continue;
+                };
+                match store.get_event(&uid).await {
+                    Ok(vevent) => {
+                        let attendees = vevent
+                            .attendees
+                            .iter()
+                            .map(|a| a.cal_address.to_lowercase())
+                            .collect();
+                        ctx.attendees.insert(uid, attendees);
+                    }
+                    Err(e) => tracing::warn!(uid, error = %e, "failed to read attendees"),
+                }
+            }
+        }
+
+        Ok(dedupe::collapse(events, mode, &ctx))
+    }
+
+    /// The next event to start after now, in any enabled calendar.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn next_event(&self) -> Result<Option<impl Event + 'static>, AimError> {
+        let Some(event) = self.db.events.next_after(&self.now).await? else {
+            return Ok(None);
+        };
+        let event = self.short_ids.event(event).await?;
+        Ok(Some(event))
+    }
+
+    /// Create a default todo draft based on the AIM configuration.
+    ///
+    /// # Errors
+    /// If date/time resolution fails.
+    pub fn default_todo_draft(&self) -> Result<TodoDraft, AimError> {
+        TodoDraft::default(&self.config, &self.now)
+            .map_err(|e| AimError::Config(format!("Failed to resolve default due: {e}")))
+    }
+
+    /// Find the latest todo matching the given summary.
     ///
     /// # Errors
     /// If database access fails.
     pub async fn find_latest_todo_by_summary(
         &self,
-        summary: &str,
-    ) -> Result<Option<impl Todo + 'static>, Box<dyn Error>> {
-        let Some(todo) = self.db.todos.find_latest_by_summary(summary).await? else {
-            return Ok(None);
+        summary: &str,
+    ) -> Result<Option<impl Todo + 'static>, AimError> {
+        let Some(todo) = self.db.todos.find_latest_by_summary(summary).await? else {
+            return Ok(None);
+        };
+        let todo = self.short_ids.todo(todo).await?;
+        Ok(Some(Decrypted::new(todo, &self.keyring)))
+    }
+
+    /// Add a new todo from the given draft.
+    ///
+    /// # Errors
+    /// If the todo is not found, database or backend access fails.
+    pub async fn new_todo(&self, draft: TodoDraft) -> Result<impl Todo + 'static, AimError> {
+        let (uid, todo, calendar_id) = self.prepare_todo(&draft).await?;
+        let backend = self.get_writable_store(calendar_id, Kind::Todo)?;
+
+        // Create todo in store
+        let resource_id = backend
+            .create_todo(&uid, &todo)
+            .await
+            .map_err(|e| AimError::from_store(&uid, e))?;
+
+        // Store in database with resource mapping
+        self.db.upsert_todo(&uid, &todo, calendar_id).await?;
+        self.db
+            .resources
+            .insert(&uid, calendar_id, &resource_id, None)
+            .await?;
+
+        let todo_with_id = self.short_ids.todo(todo).await?;
+        Ok(Decrypted::new(todo_with_id, &self.keyring))
+    }
+
+    /// Resolves a draft into the todo to create, along with its UID and the calendar to create it
+    /// in, which must accept todos.
+    async fn prepare_todo<'a>(
+        &'a self,
+        draft: &'a TodoDraft,
+    ) -> Result<(String, VTodo<String>, &'a str), AimError> {
+        let uid = self.generate_uid(Kind::Todo).await?;
+        let mut todo = draft.resolve(&self.config, &self.now).into_ics(&uid);
+        if draft.encrypt_description
+            && let Some(description) = todo.description.take()
+        {
+            let sealed = self
+                .keyring
+                .seal(&uid, &description.content.to_string())
+                .map_err(|reason| AimError::InvalidInput {
+                    field: "description",
+                    reason,
+                })?;
+            write_sealed(&mut todo.x_properties, Some(&sealed));
+        }
+
+        // Resolve calendar: use draft.calendar_id, or the one of its categories, or the default
+        let calendar_id = match draft.calendar_id.as_deref() {
+            Some(calendar_id) => calendar_id,
+            None if draft.no_defaults => &self.default_calendar,
+            None => self
+                .category_calendar(&draft.categories)?
+                .unwrap_or(&self.default_calendar),
+        };
+        self.get_writable_store(calendar_id, Kind::Todo)?;
+        Ok((uid, todo, calendar_id))
+    }
+
+    /// The ID of the calendar todos with `categories` are created in, if one of them sets it, see
+    /// [`CategoryDefaults`](crate::CategoryDefaults).
+    fn category_calendar(&self, categories: &[String]) -> Result<Option<&str>, AimError> {
+        let Some(name) = self
+            .config
+            .categories
+            .first(categories, |c| c.calendar.as_deref())
+        else {
+            return Ok(None);
+        };
+        self.config
+            .find_calendar_id(name)
+            .map(Some)
+            .ok_or_else(|| AimError::Config(format!("Calendar of category not found: {name}")))
+    }
+
+    /// The priority a todo created from `draft` takes: its own, or else the one of its categories
+    /// or the default of the config, see [`CategoryDefaults`](crate::CategoryDefaults).
+    #[must_use]
+    pub fn todo_draft_priority(&self, draft: &TodoDraft) -> Priority {
+        draft
+            .resolve(&self.config, &self.now)
+            .priority
+            .unwrap_or_default()
+    }
+
+    /// Captures a todo with nothing but a summary into the inbox, to triage later, see
+    /// [`Todo::in_inbox`].
+    ///
+    /// The todo is cached and staged for the default calendar right away, then pushed to its store
+    /// by the next sync, so capturing never waits on the store. No default due, priority or
+    /// reminder of the config is applied.
+    ///
+    /// # Errors
+    /// If the summary is empty, the default calendar does not accept todos, or database access
+    /// fails.
+    pub async fn capture_todo(&self, summary: &str) -> Result<impl Todo + 'static, AimError> {
+        let summary = summary.trim();
+        if summary.is_empty() {
+            return Err(AimError::InvalidInput {
+                field: "summary",
+                reason: "must not be empty".to_string(),
+            });
+        }
+
+        let calendar_id = &self.default_calendar;
+        self.get_writable_store(calendar_id, Kind::Todo)?;
+        let uid = self.generate_uid(Kind::Todo).await?;
+        let mut todo = ResolvedTodoDraft {
+            alarm: None,
+            categories: &[],
+            color: None,
+            description: None,
+            due: None,
+            estimate: None,
+            parent: None,
+            percent_complete: None,
+            priority: None,
+            status: TodoStatus::NeedsAction,
+            summary,
+            now: &self.now,
+            recur_from: None,
+        }
+        .into_ics(&uid);
+        mark_inbox(&mut todo);
+
+        // Staged first, so a todo that failed to be cached still reaches its store
+        let ics = write_staged(CalendarComponent::Todo(todo.clone()))?;
+        self.db
+            .pending_ops
+            .insert(
+                &uid,
+                calendar_id,
+                Kind::Todo,
+                PendingOperation::Create,
+                &ics,
+            )
+            .await?;
+        self.db.upsert_todo(&uid, &todo, calendar_id).await?;
+
+        let todo = self.short_ids.todo(todo).await?;
+        Ok(Decrypted::new(todo, &self.keyring))
+    }
+
+    /// Gets a todo staged for its store but not pushed yet, along with the ID of the operation.
+    async fn get_staged_todo(&self, uid: &str) -> Result<Option<(i64, VTodo<String>)>, AimError> {
+        let Some(op) = self.db.pending_ops.get_by_uid(uid).await? else {
+            return Ok(None);
+        };
+        match read_staged(&op.ics)? {
+            CalendarComponent::Todo(todo) => Ok(Some((op.id, todo))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Upsert a todo into the calendar.
+    ///
+    /// A todo staged for its store but not pushed yet is edited in place, see
+    /// [`Aim::capture_todo`].
+    ///
+    /// # Errors
+    /// If the todo is not found, database or backend access fails.
+    pub async fn update_todo(
+        &self,
+        id: &Id,
+        patch: TodoPatch,
+    ) -> Result<impl Todo + 'static, AimError> {
+        patch.validate().map_err(|reason| AimError::InvalidInput {
+            field: "percent complete",
+            reason,
+        })?;
+        let uid = self.resolve_uid(id, Some(Kind::Todo)).await?;
+        let Some(todo_record) = self.db.todos.get(&uid).await? else {
+            return Err(not_found("Todo", id));
+        };
+        let patch = self.seal_patch(&uid, &todo_record, self.recur_patch(patch))?;
+        let calendar_id = &todo_record.calendar_id;
+
+        let updated_todo = if let Some((op_id, mut todo)) = self.get_staged_todo(&uid).await? {
+            self.check_completed_at(&todo, &patch)?;
+            patch.resolve(&self.now).apply_to(&mut todo);
+            let ics = write_staged(CalendarComponent::Todo(todo.clone()))?;
+            self.db.pending_ops.update_ics(op_id, &ics).await?;
+            todo
+        } else {
+            // Update todo through backend
+            let backend = self.get_writable_store(calendar_id, Kind::Todo)?;
+            if patch.completed_at.is_some() {
+                let todo = backend
+                    .get_todo(&uid)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                self.check_completed_at(&todo, &patch)?;
+            }
+            backend
+                .update_todo(&uid, &patch)
+                .await
+                .map_err(|e| AimError::from_store(&uid, e))?
+        };
+
+        // Update database
+        self.db
+            .upsert_todo(&uid, &updated_todo, calendar_id)
+            .await?;
+
+        let todo = self.short_ids.todo(updated_todo).await?;
+        Ok(Decrypted::new(todo, &self.keyring))
+    }
+
+    /// Fills in where recurring todos completed by the patch advance from, if it does not say.
+    fn recur_patch(&self, patch: TodoPatch) -> TodoPatch {
+        TodoPatch {
+            recur_from: patch.recur_from.or(Some(self.config.recur_from)),
+            ..patch
+        }
+    }
+
+    /// Checks the completion time of a patch against the todo it completes, see
+    /// [`TodoPatch::completed_at`].
+    fn check_completed_at(&self, todo: &VTodo<String>, patch: &TodoPatch) -> Result<(), AimError> {
+        let Some(at) = &patch.completed_at else {
+            return Ok(());
+        };
+        let invalid = |reason: String| AimError::InvalidInput {
+            field: "completed at",
+            reason,
+        };
+        if patch.status != Some(TodoStatus::Completed) {
+            return Err(invalid(
+                "only a todo being completed takes a completion time".into(),
+            ));
+        }
+        if *at > self.now {
+            return Err(invalid(format!("{} is in the future", at.timestamp())));
+        }
+        if let Some(created) = created_at(todo)
+            && *at < created
+        {
+            return Err(invalid(format!(
+                "{} is before the todo was created at {}",
+                at.timestamp(),
+                created.timestamp()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Seals the description set by the patch if the todo has an encrypted one, so editing it
+    /// never stores it in plain text.
+    fn seal_patch(
+        &self,
+        uid: &str,
+        todo: &impl Todo,
+        mut patch: TodoPatch,
+    ) -> Result<TodoPatch, AimError> {
+        if todo.encrypted_description().is_none() || patch.encrypted_description.is_some() {
+            return Ok(patch);
+        }
+        match patch.description.take() {
+            // Left as shown, such as by an editor without the key
+            Some(Some(description)) if description == ENCRYPTED_NO_KEY => {}
+            Some(Some(description)) => {
+                let sealed = self.keyring.seal(uid, &description).map_err(|reason| {
+                    AimError::InvalidInput {
+                        field: "description",
+                        reason: format!("the description is encrypted, but {reason}"),
+                    }
+                })?;
+                patch.description = Some(None);
+                patch.encrypted_description = Some(sealed);
+            }
+            description => patch.description = description,
+        }
+        Ok(patch)
+    }
+
+    /// Seals the encrypted descriptions of all todos again with the current key, archived ones
+    /// included, so previous keys can be dropped from the config.
+    ///
+    /// Todos already sealed with the current key are left as they are, as are those no key
+    /// opens or whose calendar refuses the change, which are reported as skipped.
+    ///
+    /// # Errors
+    /// If no key is configured, or database access fails.
+    pub async fn rekey_descriptions(&self) -> Result<RekeyReport, AimError> {
+        if !self.keyring.can_seal() {
+            return Err(AimError::Config(
+                "no encryption key to rekey with, set `key` in the `[core.encryption]` section"
+                    .to_string(),
+            ));
+        }
+
+        let mut report = RekeyReport::default();
+        for (uid, calendar_id, sealed) in self.db.todos.list_encrypted().await? {
+            let Some(opened) = self.keyring.open(&uid, &sealed) else {
+                report.skipped.push((uid, "no key opens it".to_string()));
+                continue;
+            };
+            if opened.current {
+                report.up_to_date += 1;
+                continue;
+            }
+
+            let result = async {
+                let sealed = self
+                    .keyring
+                    .seal(&uid, &opened.description)
+                    .map_err(AimError::Config)?;
+                let patch = TodoPatch {
+                    encrypted_description: Some(sealed),
+                    ..Default::default()
+                };
+                let backend = self.get_writable_store(&calendar_id, Kind::Todo)?;
+                let todo = backend
+                    .update_todo(&uid, &patch)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                self.db.upsert_todo(&uid, &todo, &calendar_id).await?;
+                Ok::<_, AimError>(())
+            };
+            match result.await {
+                Ok(()) => report.rekeyed += 1,
+                Err(e) => report.skipped.push((uid, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Evaluates the categorization rules of the config again for the given items, or all of
+    /// them if none is given, archived ones included, such as after the rules changed.
+    ///
+    /// What the rules add is kept in the cache, so the stores are left untouched and what a
+    /// removed rule added goes away.
+    ///
+    /// # Errors
+    /// If an id is not found or ambiguous, or database access fails.
+    pub async fn apply_rules(&self, ids: &[Id]) -> Result<RulesReport, AimError> {
+        let rules = self.db.rules();
+        let mut report = RulesReport::default();
+        if ids.is_empty() {
+            report.events = self.db.events.apply_rules(rules, None).await?;
+            report.todos = self.db.todos.apply_rules(rules, None).await?;
+        }
+        for id in ids {
+            let item = self.resolve_single(id, None).await?;
+            match item.kind {
+                Kind::Event => {
+                    report.events += self.db.events.apply_rules(rules, Some(&item.uid)).await?;
+                }
+                Kind::Todo => {
+                    report.todos += self.db.todos.apply_rules(rules, Some(&item.uid)).await?;
+                }
+            }
+        }
+        tracing::info!(
+            events = report.events,
+            todos = report.todos,
+            "applied categorization rules"
+        );
+        Ok(report)
+    }
+
+    /// Evaluates the categorization rules for the item without applying them, telling which
+    /// rules fire and what they add.
+    ///
+    /// # Errors
+    /// If the id is not found or ambiguous, or database access fails.
+    pub async fn test_rules(&self, id: &Id) -> Result<RuleTest, AimError> {
+        let item = self.resolve_single(id, None).await?;
+        let rules = self.db.rules();
+        let (traces, outcome) = match item.kind {
+            Kind::Event => match self.db.events.get(&item.uid).await? {
+                Some(event) => rules.trace(&event.rule_subject()),
+                None => return Err(not_found("event", id)),
+            },
+            Kind::Todo => match self.db.todos.get(&item.uid).await? {
+                Some(todo) => rules.trace(&todo.rule_subject()),
+                None => return Err(not_found("todo", id)),
+            },
+        };
+        Ok(RuleTest {
+            uid: item.uid,
+            kind: item.kind,
+            summary: item.summary,
+            traces,
+            outcome,
+        })
+    }
+
+    /// Snooze a todo, pushing its due by `delta` from the current due, or from now if it has none.
+    ///
+    /// Whole-day shifts keep the original time of day. The previous due is recorded in the todo,
+    /// see [`Todo::snoozed_from`].
+    ///
+    /// # Errors
+    /// If the todo is not found, database or backend access fails.
+    pub async fn snooze_todo(
+        &self,
+        id: &Id,
+        delta: DateTimeAnchor,
+    ) -> Result<impl Todo + 'static, AimError> {
+        let patch = self.snooze_patch(id, delta).await?;
+        self.update_todo(id, patch).await
+    }
+
+    /// Patch snoozing a todo, see [`Aim::snooze_todo`].
+    async fn snooze_patch(&self, id: &Id, delta: DateTimeAnchor) -> Result<TodoPatch, AimError> {
+        let due = self.get_todo(id).await?.due();
+        let base = due
+            .clone()
+            .unwrap_or_else(|| LooseDateTime::Local(self.now.clone()));
+        let new_due = delta.resolve_at(&base);
+        Ok(postpone_patch(due, new_due))
+    }
+
+    /// Defer a todo, setting its due to `anchor` resolved from now.
+    ///
+    /// The previous due is recorded in the todo, see [`Todo::snoozed_from`].
+    ///
+    /// # Errors
+    /// If the todo is not found, the anchor cannot be resolved, database or backend access fails.
+    pub async fn defer_todo(
+        &self,
+        id: &Id,
+        anchor: DateTimeAnchor,
+    ) -> Result<impl Todo + 'static, AimError> {
+        let patch = self.defer_patch(id, anchor).await?;
+        self.update_todo(id, patch).await
+    }
+
+    /// Patch deferring a todo, see [`Aim::defer_todo`].
+    async fn defer_patch(&self, id: &Id, anchor: DateTimeAnchor) -> Result<TodoPatch, AimError> {
+        let due = self.get_todo(id).await?.due();
+        let new_due =
+            anchor
+                .resolve_since_zoned(&self.now)
+                .map_err(|reason| AimError::InvalidInput {
+                    field: "due",
+                    reason,
+                })?;
+        Ok(postpone_patch(due, new_due))
+    }
+
+    /// Marks a todo as waiting on someone, such as a task handed off, see [`Todo::waiting_on`].
+    ///
+    /// Todos waiting on someone are left out of listings that hide them until `follow_up`, resolved
+    /// from now, passes, or until cleared with [`Aim::unwait_todo`] if there is none. With `notify`,
+    /// a reminder goes off at the follow-up. The status of the todo is kept as it is.
+    ///
+    /// # Errors
+    /// If `on` is empty, `notify` is set without a follow-up, the follow-up cannot be resolved, the
+    /// todo is not found, database or backend access fails.
+    pub async fn wait_todo(
+        &self,
+        id: &Id,
+        on: &str,
+        follow_up: Option<DateTimeAnchor>,
+        notify: bool,
+    ) -> Result<impl Todo + 'static, AimError> {
+        let on = on.trim();
+        if on.is_empty() {
+            return Err(AimError::InvalidInput {
+                field: "waiting on",
+                reason: "must not be empty".to_string(),
+            });
+        }
+        if notify && follow_up.is_none() {
+            return Err(AimError::InvalidInput {
+                field: "follow-up",
+                reason: "a reminder needs a follow-up date".to_string(),
+            });
+        }
+        let follow_up = follow_up
+            .map(|anchor| anchor.resolve_since_zoned(&self.now))
+            .transpose()
+            .map_err(|reason| AimError::InvalidInput {
+                field: "follow-up",
+                reason,
+            })?;
+
+        let patch = TodoPatch {
+            waiting: Some(Some(Waiting {
+                on: on.to_string(),
+                follow_up,
+                notify,
+            })),
+            ..Default::default()
+        };
+        self.update_todo(id, patch).await
+    }
+
+    /// Stops a todo waiting on someone, along with its follow-up and reminder, see
+    /// [`Aim::wait_todo`].
+    ///
+    /// # Errors
+    /// If the todo is not found, database or backend access fails.
+    pub async fn unwait_todo(&self, id: &Id) -> Result<impl Todo + 'static, AimError> {
+        let patch = TodoPatch {
+            waiting: Some(None),
+            ..Default::default()
+        };
+        self.update_todo(id, patch).await
+    }
+
+    /// Get a todo by its id.
+    ///
+    /// # Errors
+    /// If the todo is not found or database access fails.
+    pub async fn get_todo(&self, id: &Id) -> Result<impl Todo + 'static, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Todo)).await?;
+        match self.db.todos.get(&uid).await? {
+            Some(todo) => {
+                let todo = self.short_ids.todo(todo).await?;
+                Ok(Decrypted::new(todo, &self.keyring))
+            }
+            None => Err(not_found("Todo", id)),
+        }
+    }
+
+    /// Get the details of a todo kept in its store, see [`ItemDetails`].
+    ///
+    /// Only the calendar and resource are filled in if the store can't be read.
+    ///
+    /// # Errors
+    /// If the todo is not found or database access fails.
+    pub async fn get_todo_details(&self, id: &Id) -> Result<ItemDetails, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Todo)).await?;
+        let Some(todo) = self.db.todos.get(&uid).await? else {
+            return Err(not_found("Todo", id));
+        };
+
+        let store = self.get_store(&todo.calendar_id)?;
+        let stored = match self.get_staged_todo(&uid).await? {
+            Some((_, staged)) => Ok(staged),
+            None => store.get_todo(&uid).await,
+        };
+        let mut details = match stored {
+            Ok(todo) => ItemDetails::from_todo(todo, &self.now),
+            Err(e) => {
+                tracing::warn!(uid, error = %e, "failed to read todo from store");
+                ItemDetails::default()
+            }
+        };
+        self.fill_item_location(&mut details, &uid, todo.calendar_id)
+            .await?;
+        Ok(details)
+    }
+
+    /// Export a todo as a standalone iCalendar object, see [`Aim::export_event`].
+    ///
+    /// # Errors
+    /// If the todo is not found, does not occur on `occurrence`, or its store can't be read.
+    pub async fn export_todo(
+        &self,
+        id: &Id,
+        occurrence: Option<Date>,
+    ) -> Result<CalendarExport, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Todo)).await?;
+        let Some(todo) = self.db.todos.get(&uid).await? else {
+            return Err(not_found("Todo", id));
+        };
+
+        let todo = if let Some((_, staged)) = self.get_staged_todo(&uid).await? {
+            staged
+        } else {
+            let store = self.get_store(&todo.calendar_id)?;
+            store
+                .get_todo(&uid)
+                .await
+                .map_err(|e| AimError::from_store(&uid, e))?
+        };
+        export_todo(todo, occurrence, &self.now)
+    }
+
+    async fn fill_item_location(
+        &self,
+        details: &mut ItemDetails,
+        uid: &str,
+        calendar_id: String,
+    ) -> Result<(), AimError> {
+        let resource = self.db.resources.get(uid, &calendar_id).await?;
+        details.resource_id = resource.map(|r| r.resource_id);
+        details.calendar_id = calendar_id;
+        Ok(())
+    }
+
+    /// List todos matching the given conditions, sorted and paginated.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn list_todos(
+        &self,
+        conds: &TodoConditions,
+        sort: &[TodoSort],
+        pager: &Pager,
+    ) -> Result<Vec<impl Todo + Clone + 'static>, AimError> {
+        let conds = conds.resolve(&self.now).map_err(invalid_conditions)?;
+        let sort = TodoSort::resolve_vec(sort, &self.config);
+        let todos = self.db.todos.list(&conds, &sort, pager).await?;
+        let todos = self.short_ids.todos(todos).await?;
+        Ok(todos
+            .into_iter()
+            .map(|todo| Decrypted::new(todo, &self.keyring))
+            .collect())
+    }
+
+    /// Counts the direct subtasks of each of the todos, archived ones included, by their UIDs.
+    ///
+    /// Todos without subtasks are left out.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn count_subtasks(
+        &self,
+        uids: &[&str],
+    ) -> Result<HashMap<String, SubtaskProgress>, AimError> {
+        let counts = self.db.todos.count_subtasks(uids).await?;
+        Ok(counts
+            .into_iter()
+            .map(|(uid, done, total)| {
+                let count = |n: i64| u32::try_from(n).unwrap_or_default();
+                let progress = SubtaskProgress {
+                    done: count(done),
+                    total: count(total),
+                };
+                (uid, progress)
+            })
+            .collect())
+    }
+
+    /// Counts the number of todos matching the given conditions.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn count_todos(&self, conds: &TodoConditions) -> Result<i64, AimError> {
+        let conds = conds.resolve(&self.now).map_err(invalid_conditions)?;
+        Ok(self.db.todos.count(&conds).await?)
+    }
+
+    /// List known calendars ordered by priority.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn list_calendars(&self) -> Result<Vec<CalendarRecord>, AimError> {
+        Ok(self.db.calendars.list().await?)
+    }
+
+    /// List the details of known calendars ordered by priority, with item counts and the
+    /// outcome of their last syncs.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn list_calendar_details(&self) -> Result<Vec<CalendarDetails>, AimError> {
+        let events: HashMap<_, _> = self
+            .db
+            .events
+            .count_by_calendar()
+            .await?
+            .into_iter()
+            .collect();
+        let todos: HashMap<_, _> = self
+            .db
+            .todos
+            .count_by_calendar()
+            .await?
+            .into_iter()
+            .collect();
+        let mut details = Vec::new();
+        for record in self.db.calendars.list().await? {
+            let counts = (
+                events.get(&record.id).copied().unwrap_or_default(),
+                todos.get(&record.id).copied().unwrap_or_default(),
+            );
+            details.push(self.calendar_details(record, counts).await?);
+        }
+        Ok(details)
+    }
+
+    /// Get detailed information for a single calendar.
+    ///
+    /// # Errors
+    /// If the calendar is not found or database access fails.
+    pub async fn get_calendar_details(&self, id: &str) -> Result<CalendarDetails, AimError> {
+        let record = self
+            .db
+            .calendars
+            .get(id)
+            .await?
+            .ok_or_else(|| AimError::NotFound {
+                kind: "Calendar",
+                id: id.to_string(),
+            })?;
+
+        let count = |counts: Vec<(String, i64)>| {
+            counts
+                .into_iter()
+                .find_map(|(calendar_id, count)| (calendar_id == record.id).then_some(count))
+                .unwrap_or_default()
+        };
+        let counts = (
+            count(self.db.events.count_by_calendar().await?),
+            count(self.db.todos.count_by_calendar().await?),
+        );
+        self.calendar_details(record, counts).await
+    }
+
+    async fn calendar_details(
+        &self,
+        record: CalendarRecord,
+        (events, todos): (i64, i64),
+    ) -> Result<CalendarDetails, AimError> {
+        let entry = self
+            .config
+            .calendars
+            .iter()
+            .find(|calendar| calendar.id == record.id);
+        let backend = entry.and_then(|calendar| {
+            self.config
+                .stores
+                .get(&calendar.store)
+                .map(|store_def| Self::calendar_store_details(calendar, store_def))
+        });
+        let status = self.db.calendars.sync_status(&record.id).await?;
+
+        Ok(CalendarDetails {
+            is_default: self.default_calendar == record.id,
+            color: entry.and_then(|calendar| calendar.color.clone()),
+            events,
+            todos,
+            last_synced_at: status.as_ref().and_then(|s| s.last_success_at.clone()),
+            last_sync_error: status.and_then(|s| s.last_error),
+            id: record.id,
+            name: record.name,
+            kind: record.kind,
+            priority: record.priority,
+            enabled: record.enabled,
+            owner: record.owner,
+            privileges: record.privileges,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            store: backend,
+        })
+    }
+
+    /// Makes the calendar with the given ID or name the default for new items, overriding
+    /// `default_calendar` of the config. Returns the ID of the calendar.
+    ///
+    /// # Errors
+    /// If the calendar is not found or disabled, or database access fails.
+    pub async fn set_default_calendar(&mut self, id_or_name: &str) -> Result<String, AimError> {
+        let record = self.find_calendar(id_or_name).await?;
+        if !self.stores.contains_key(&record.id) {
+            return Err(AimError::InvalidInput {
+                field: "calendar",
+                reason: format!("'{}' is disabled, enable it first", record.id),
+            });
+        }
+
+        self.db.calendars.set_default(&record.id).await?;
+        self.default_calendar.clone_from(&record.id);
+        Ok(record.id)
+    }
+
+    /// Enables or disables the calendar with the given ID or name, overriding `enabled` of the
+    /// config. Disabled calendars are not synced and their items are left out of listings.
+    /// Returns the ID of the calendar.
+    ///
+    /// # Errors
+    /// If the calendar is not found, not configured, is the default calendar being disabled,
+    /// or the store fails to start or sync.
+    pub async fn set_calendar_enabled(
+        &mut self,
+        id_or_name: &str,
+        enabled: bool,
+    ) -> Result<String, AimError> {
+        let record = self.find_calendar(id_or_name).await?;
+        let id = record.id;
+        if enabled == self.stores.contains_key(&id) {
+            self.db.calendars.set_user_enabled(&id, enabled).await?;
+            return Ok(id);
+        }
+
+        if enabled {
+            let entry = self.config.calendars.iter().find(|c| c.id == id);
+            let Some((entry, store_def)) =
+                entry.and_then(|c| Some((c, self.config.stores.get(&c.store)?)))
+            else {
+                return Err(AimError::InvalidInput {
+                    field: "calendar",
+                    reason: format!("'{id}' is not in the config"),
+                });
+            };
+            let store = Self::create_store(
+                id.clone(),
+                entry,
+                store_def,
+                &self.db,
+                self.config.state_dir.as_deref(),
+                &mut self.caldav_clients,
+            )?;
+            let result = traced_sync(&id, store.sync_cache()).await;
+            record_sync(&self.db, &id, &result).await?;
+            result.map_err(|e| sync_error(&id, e))?;
+            self.stores.insert(id.clone(), store);
+        } else {
+            if id == self.default_calendar {
+                return Err(AimError::InvalidInput {
+                    field: "calendar",
+                    reason: format!("'{id}' is the default calendar, set another default first"),
+                });
+            }
+            self.stores.remove(&id);
+        }
+        self.db.calendars.set_user_enabled(&id, enabled).await?;
+        Ok(id)
+    }
+
+    /// Finds a known calendar by ID, or else by name.
+    async fn find_calendar(&self, id_or_name: &str) -> Result<CalendarRecord, AimError> {
+        let mut calendars = self.db.calendars.list().await?;
+        calendars
+            .iter()
+            .position(|c| c.id == id_or_name)
+            .or_else(|| calendars.iter().position(|c| c.name == id_or_name))
+            .map(|i| calendars.swap_remove(i))
+            .ok_or_else(|| AimError::NotFound {
+                kind: "Calendar",
+                id: id_or_name.to_string(),
+            })
+    }
+
+    /// Startup notices produced while reconciling config and database state.
+    #[must_use]
+    pub fn startup_notices(&self) -> &[String] {
+        &self.startup_notices
+    }
+
+    /// Directory for application state, if any.
+    #[must_use]
+    pub fn state_dir(&self) -> Option<&Path> {
+        self.config.state_dir.as_deref()
+    }
+
+    /// Whether the database was written by a newer aim and is read from an in-memory copy, see
+    /// [`Config::read_only_compat`]. Nothing written to the cache is kept then.
+    #[must_use]
+    pub fn read_only_compat(&self) -> bool {
+        self.db.newer_schema().is_some()
+    }
+
+    /// First day of the week configured for calendar views.
+    #[must_use]
+    pub fn week_start(&self) -> Weekday {
+        self.config.week_start
+    }
+
+    /// Retention policy for archiving old items out of the cache.
+    #[must_use]
+    pub fn housekeeping(&self) -> HousekeepingConfig {
+        self.config.housekeeping
+    }
+
+    /// How overdue todos escalate as they age past their due.
+    #[must_use]
+    pub fn overdue(&self) -> &OverdueConfig {
+        &self.config.overdue
+    }
+
+    /// The smart lists, built-in ones first, see [`SmartLists`].
+    #[must_use]
+    pub fn smart_lists(&self) -> &SmartLists {
+        &self.config.lists
+    }
+
+    /// The todo templates, see [`Templates`].
+    #[must_use]
+    pub fn templates(&self) -> &Templates {
+        &self.config.templates
+    }
+
+    /// Expands the template `name` with the variables `vars`, without creating anything, see
+    /// [`Template::expand`](crate::Template::expand).
+    ///
+    /// The todo of the template is due at `due`, or else at the due of the template resolved as
+    /// with [`Aim::resolve_due`] for `calendar_id` and its categories. Business days of the
+    /// offsets skip the days off and holidays of [`Aim::working_hours`].
+    ///
+    /// # Errors
+    /// If the template is not found, the variables do not match it, a due cannot be resolved, or
+    /// the holidays cannot be loaded.
+    pub async fn expand_template(
+        &self,
+        name: &str,
+        vars: &[(String, String)],
+        due: Option<LooseDateTime>,
+        calendar_id: Option<&str>,
+    ) -> Result<Vec<ExpandedTodo>, AimError> {
+        let template = self
+            .config
+            .templates
+            .get(name)
+            .ok_or_else(|| AimError::NotFound {
+                kind: "Template",
+                id: name.to_string(),
+            })?;
+        let due = match (due, &template.due) {
+            (Some(due), _) => Some(due),
+            (None, Some(anchor)) => Some(
+                self.resolve_due(anchor.clone(), calendar_id, &template.categories)
+                    .await?,
+            ),
+            (None, None) => None,
+        };
+        let hours = self.working_hours().await?;
+        template
+            .expand(vars, due, &hours)
+            .map_err(|reason| AimError::InvalidInput {
+                field: "template",
+                reason,
+            })
+    }
+
+    /// Creates the todos of the template `name` expanded with `vars`, see
+    /// [`Aim::expand_template`], in `calendar_id` or the default calendar.
+    ///
+    /// The subtasks are linked to their parents with `RELATED-TO`, and all the todos are created
+    /// at once in a transaction, so none of them is if any fails. Returns their UIDs, the todo of
+    /// the template first.
+    ///
+    /// # Errors
+    /// If the template cannot be expanded, the calendar does not accept todos, or store or
+    /// database access fails.
+    pub async fn new_todos_from_template(
+        &self,
+        name: &str,
+        vars: &[(String, String)],
+        due: Option<LooseDateTime>,
+        calendar_id: Option<&str>,
+    ) -> Result<Vec<String>, AimError> {
+        let todos = self.expand_template(name, vars, due, calendar_id).await?;
+        let calendar_id = calendar_id.map(ToString::to_string);
+        self.transaction(|txn| {
+            Box::pin(async move {
+                let mut uids: Vec<String> = Vec::with_capacity(todos.len());
+                for todo in todos {
+                    let draft = TodoDraft {
+                        alarm: None,
+                        calendar_id: calendar_id.clone(),
+                        categories: todo.categories,
+                        description: todo.description,
+                        encrypt_description: false,
+                        due: todo.due,
+                        estimate: None,
+                        parent: todo.parent.and_then(|i| uids.get(i).cloned()),
+                        percent_complete: None,
+                        priority: todo.priority,
+                        status: TodoStatus::NeedsAction,
+                        summary: todo.summary,
+                        recur_from: None,
+                        no_defaults: false,
+                    };
+                    uids.push(txn.new_todo(draft).await?);
+                }
+                Ok(uids)
+            })
+        })
+        .await
+    }
+
+    /// Collation configured for sorting and grouping summaries and categories.
+    #[must_use]
+    pub fn collation(&self) -> Collation {
+        self.config.collation
+    }
+
+    /// How copies of an event in several calendars are collapsed in listings by default.
+    #[must_use]
+    pub fn dedupe_mode(&self) -> DedupeMode {
+        self.config.dedupe_events
+    }
+
+    /// Whether all-day events block time, see [`AllDayPolicy`].
+    #[must_use]
+    pub fn all_day_policy(&self) -> AllDayPolicy {
+        self.config.all_day_events
+    }
+
+    /// Whether todos scheduled to start later are left out of todo lists by default.
+    #[must_use]
+    pub fn hide_unstarted(&self) -> bool {
+        self.config.hide_unstarted
+    }
+
+    /// Time of day given to due dates entered without a time, if configured.
+    #[must_use]
+    pub fn default_due_time(&self) -> Option<Time> {
+        self.config.default_due_time
+    }
+
+    /// Resolves the due of a todo in `calendar_id`, or the calendar of its categories or the
+    /// default calendar, with `categories`.
+    ///
+    /// Days count as business days if the anchor is in business days, or if it is in days and
+    /// the todo is in one of the calendars or has one of the categories of the `business_days`
+    /// config, or one of its categories sets `business_days`. Business days skip the days off and
+    /// holidays of [`Aim::working_hours`], and such dues are at the end of the working day. Other
+    /// anchors resolve as with [`DateTimeAnchor::resolve_since_zoned`].
+    ///
+    /// # Errors
+    /// If the anchor cannot be resolved, the calendar of a category is not configured, or the
+    /// holidays cannot be loaded.
+    pub async fn resolve_due(
+        &self,
+        anchor: DateTimeAnchor,
+        calendar_id: Option<&str>,
+        categories: &[String],
+    ) -> Result<LooseDateTime, AimError> {
+        let calendar_id = match calendar_id {
+            Some(calendar_id) => calendar_id,
+            None => self
+                .category_calendar(categories)?
+                .unwrap_or(&self.default_calendar),
+        };
+        let anchor = match anchor {
+            DateTimeAnchor::InDays(n) if n != 0 => {
+                let name = self
+                    .config
+                    .calendars
+                    .iter()
+                    .find(|c| c.id == calendar_id)
+                    .map(|c| c.name.as_str());
+                if self
+                    .config
+                    .business_days
+                    .applies_to(calendar_id, name, categories)
+                    || self.config.categories.business_days(categories)
+                {
+                    DateTimeAnchor::InBusinessDays(n)
+                } else {
+                    anchor
+                }
+            }
+            anchor => anchor,
+        };
+
+        let invalid = |reason| AimError::InvalidInput {
+            field: "due",
+            reason,
+        };
+        let anchor = match anchor {
+            DateTimeAnchor::InBusinessDays(_) => {
+                let hours = self.working_hours().await?;
+                anchor
+                    .resolve_business_days(self.now.date(), &hours)
+                    .map_err(invalid)?
+            }
+            anchor => anchor,
+        };
+        anchor.resolve_since_zoned(&self.now).map_err(invalid)
+    }
+
+    /// Working hours, with the days of the events in the holiday calendar off.
+    ///
+    /// # Errors
+    /// If the holiday calendar is not configured or database access fails.
+    pub async fn working_hours(&self) -> Result<WorkingHours, AimError> {
+        let mut hours = self.config.working_hours().clone();
+        let Some(name) = hours.holiday_calendar() else {
+            return Ok(hours);
+        };
+        let calendar = self
+            .config
+            .calendars
+            .iter()
+            .find(|c| c.name == name || c.id == name)
+            .ok_or_else(|| AimError::Config(format!("Holiday calendar not found: {name}")))?;
+
+        let conds = EventConditions {
+            calendar_id: Some(calendar.id.clone()),
+            ..Default::default()
+        };
+        let pager = Pager {
+            limit: i64::MAX,
+            offset: 0,
+        };
+        for event in self
+            .db
+            .events
+            .list(
+                &conds.resolve(&self.now).map_err(invalid_conditions)?,
+                &pager,
+            )
+            .await?
+        {
+            let Some(start) = event.start() else {
+                continue;
+            };
+            let first = start.date();
+            let last = match event.end() {
+                // The end date of all-day events is exclusive
+                Some(LooseDateTime::DateOnly(end)) => end.yesterday().unwrap_or(end),
+                Some(end) => end.date(),
+                None => first,
+            };
+            let mut date = first;
+            loop {
+                hours.add_holiday(date);
+                match date.tomorrow() {
+                    Ok(next) if next <= last => date = next,
+                    _ => break,
+                }
+            }
+        }
+        Ok(hours)
+    }
+
+    /// Plans a block of working time for each of the todos, see [`PlanOptions`].
+    ///
+    /// Each todo gets a block as long as its estimate, or the `default_estimate` of the config,
+    /// in the first working time from now to the end of the horizon that no event takes. The
+    /// todos due sooner are planned first, then the ones of higher priority, so the same todos
+    /// and calendars always give the same plan. Todos done or with blocks that have not ended are
+    /// left out. Unless it is a dry run, the blocks are created all at once as events related to
+    /// their todos, with their summary prefixed by [`PLAN_PREFIX`].
+    ///
+    /// # Errors
+    /// If a todo is not found, the horizon cannot be resolved, database or backend access fails.
+    pub async fn plan_todos(&self, ids: &[Id], options: &PlanOptions) -> Result<Plan, AimError> {
+        let until = options
+            .until
+            .resolve_at_end_of_day(&self.now)
+            .map_err(|reason| AimError::InvalidInput {
+                field: "until",
+                reason,
+            })?;
+        let tz = self.now.time_zone();
+
+        let mut unplanned = Vec::new();
+        let mut items = Vec::new();
+        let mut seen = HashSet::new();
+        for id in ids {
+            let todo = self.get_todo(id).await?;
+            let uid = todo.uid().into_owned();
+            if !seen.insert(uid.clone()) {
+                continue;
+            }
+            let summary = todo.summary().into_owned();
+            let reason = if matches!(todo.status(), TodoStatus::Completed | TodoStatus::Cancelled) {
+                Some(UnplannedReason::Done)
+            } else if !self.planned_blocks(&Id::Uid(uid.clone())).await?.is_empty() {
+                Some(UnplannedReason::AlreadyPlanned)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                unplanned.push(Unplanned {
+                    todo_uid: uid,
+                    summary,
+                    reason,
+                });
+                continue;
+            }
+            items.push(PlanItem {
+                due: todo.due().and_then(|due| due.to_end_timestamp_in(tz)),
+                priority: todo.priority(),
+                estimate: todo.estimate().unwrap_or(self.config.default_estimate),
+                uid,
+                summary,
+            });
+        }
+
+        let busy: Vec<_> = self
+            .busy_intervals(&until)
+            .await?
+            .into_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect();
+        let slots = self
+            .working_hours()
+            .await?
+            .free_slots(&self.now, &until, &busy);
+
+        let mut plan = plan::assign(items, slots);
+        unplanned.append(&mut plan.unplanned);
+        plan.unplanned = unplanned;
+        if options.dry_run || plan.blocks.is_empty() {
+            return Ok(plan);
+        }
+
+        let mut txn = self.begin()?;
+        for block in &mut plan.blocks {
+            let draft = EventDraft {
+                alarm: None,
+                calendar_id: options.calendar_id.clone(),
+                description: None,
+                start: Some(LooseDateTime::Local(block.start.clone())),
+                end: Some(LooseDateTime::Local(block.end.clone())),
+                duration: None,
+                repeat: None,
+                related_to: Some(block.todo_uid.clone()),
+                status: EventStatus::Confirmed,
+                summary: format!("{PLAN_PREFIX} {}", block.summary),
+            };
+            block.event_uid = Some(txn.new_event(draft).await?);
+        }
+        txn.commit().await?;
+        Ok(plan)
+    }
+
+    /// Weighs the work left for today against the working time left, for a warning when the day
+    /// is overcommitted.
+    ///
+    /// The load is the estimates of the open todos due today, and the time left of the blocks
+    /// planned today, see [`Aim::plan_todos`], whose todos are not counted again. Todos without an
+    /// estimate count for the `default_estimate` of the config, or not at all with
+    /// `exclude_unestimated`. The free time is the working time from now to the end of the day
+    /// that no other event takes.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn day_load(&self) -> Result<DayLoad, AimError> {
+        let tz = self.now.time_zone();
+        let today = DateTimeAnchor::today();
+        let start_of_day = today
+            .resolve_at_start_of_day(&self.now)
+            .map_err(AimError::Other)?;
+        let end_of_day = today
+            .resolve_at_end_of_day(&self.now)
+            .map_err(AimError::Other)?;
+
+        let mut busy = Vec::new();
+        let mut blocks = Vec::new();
+        let mut planned = HashSet::new();
+        for (start, end, block_of) in self.busy_intervals(&end_of_day).await? {
+            match block_of {
+                Some(uid) => {
+                    blocks.push((start, end));
+                    planned.insert(uid);
+                }
+                None => busy.push((start, end)),
+            }
+        }
+
+        let conds = TodoConditions {
+            status: Some(TodoStatus::NeedsAction),
+            due: Some(today),
+            start_after: None,
+            hide_unstarted: self.config.hide_unstarted,
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: true,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let pager: Pager = (i64::MAX, 0).into();
+        let estimates: Vec<_> = self
+            .list_todos(&conds, &[], &pager)
+            .await?
+            .iter()
+            // Overdue todos are left out, their time is not today's to plan
+            .filter(|todo| {
+                todo.due()
+                    .and_then(|due| due.to_end_timestamp_in(tz))
+                    .is_some_and(|due| due >= start_of_day.timestamp())
+            })
+            .filter(|todo| !planned.contains(todo.uid().as_ref()))
+            .map(Todo::estimate)
+            .collect();
+
+        let default_estimate =
+            (!self.config.exclude_unestimated).then_some(self.config.default_estimate);
+        let hours = self.working_hours().await?;
+        Ok(plan::day_load(
+            &hours,
+            &self.now,
+            &busy,
+            &blocks,
+            &estimates,
+            default_estimate,
+        ))
+    }
+
+    /// The intervals from now to `until` that events take, with the UID of the todo of those that
+    /// are blocks planned for one, see [`Aim::plan_todos`].
+    async fn busy_intervals(
+        &self,
+        until: &Zoned,
+    ) -> Result<Vec<(Timestamp, Timestamp, Option<String>)>, AimError> {
+        let tz = self.now.time_zone();
+        // Transparent and cancelled events take no time, nor do all-day ones unless they are
+        // configured to
+        let conds = ResolvedEventConditions {
+            start_before: Some(until.clone()),
+            end_after: Some(self.now.clone()),
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
-        let todo = self.short_ids.todo(todo).await?;
-        Ok(Some(todo))
+        let pager = Pager {
+            limit: i64::MAX,
+            offset: 0,
+        };
+        let policy = self.all_day_policy();
+        Ok(self
+            .db
+            .events
+            .list(&conds, &pager)
+            .await?
+            .iter()
+            .filter(|event| !event.transparent() && event.status() != Some(EventStatus::Cancelled))
+            .filter_map(|event| {
+                let start = event.start()?.to_timestamp_in(tz)?;
+                if event.is_all_day() {
+                    if policy == AllDayPolicy::Ignore {
+                        return None;
+                    }
+                    let next = event.last_day()?.tomorrow().ok()?;
+                    let end = LooseDateTime::DateOnly(next).to_timestamp_in(tz)?;
+                    return Some((start, end, None));
+                }
+                let end = event.end().and_then(|end| end.to_timestamp_in(tz));
+                let block_of = event
+                    .related_to()
+                    .filter(|_| event.summary().starts_with(PLAN_PREFIX))
+                    .map(Cow::into_owned);
+                Some((start, end.unwrap_or(start), block_of))
+            })
+            .collect())
     }
 
-    /// Add a new todo from the given draft.
+    /// The blocks planned for a todo that have not ended yet, see [`Aim::plan_todos`].
     ///
     /// # Errors
-    /// If the todo is not found, database or backend access fails.
-    pub async fn new_todo(&self, draft: TodoDraft) -> Result<impl Todo + 'static, Box<dyn Error>> {
-        let uid = self.generate_uid(Kind::Todo).await?;
-        let todo = draft.resolve(&self.config, &self.now).into_ics(&uid);
-
-        // Resolve calendar: use draft.calendar_id or fall back to default
-        let calendar_id = draft
-            .calendar_id
-            .as_deref()
-            .unwrap_or(&self.default_calendar);
-        let backend = self.get_store(calendar_id)?;
-
-        // Create todo in store
-        let resource_id = backend
-            .create_todo(&uid, &todo)
-            .await
-            .map_err(|e| format!("Failed to create todo in store: {e}"))?;
-
-        // Store in database with resource mapping
-        self.db.upsert_todo(&uid, &todo, calendar_id).await?;
-        self.db
-            .resources
-            .insert(&uid, calendar_id, &resource_id, None)
-            .await?;
-
-        let todo_with_id = self.short_ids.todo(todo).await?;
-        Ok(todo_with_id)
+    /// If the todo is not found or database access fails.
+    pub async fn planned_blocks(&self, id: &Id) -> Result<Vec<impl Event + 'static>, AimError> {
+        let uid = self.resolve_uid(id, Some(Kind::Todo)).await?;
+        let conds = ResolvedEventConditions {
+            start_before: None,
+            end_after: Some(self.now.clone()),
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: Some(uid),
+            unchecked: false,
+            categories: Vec::new(),
+        };
+        let pager = Pager {
+            limit: i64::MAX,
+            offset: 0,
+        };
+        let events = self.db.events.list(&conds, &pager).await?;
+        Ok(self.short_ids.events(events).await?)
     }
 
-    /// Upsert a todo into the calendar.
+    /// Releases the blocks planned for a todo that have not ended yet, such as once it is done,
+    /// returning how many were changed. The todo itself is left as it is.
     ///
     /// # Errors
-    /// If the todo is not found, database or backend access fails.
-    pub async fn update_todo(
+    /// If the todo is not found, a calendar is read-only, database or backend access fails.
+    pub async fn release_planned_blocks(
         &self,
         id: &Id,
-        patch: TodoPatch,
-    ) -> Result<impl Todo + 'static, Box<dyn Error>> {
-        let uid = self.short_ids.get_uid(id).await?;
-        let Some(_todo) = self.db.todos.get(&uid).await? else {
-            return Err("Todo not found".into());
-        };
-
-        // Get calendar_id from todo record
-        let todo_record = self.db.todos.get(&uid).await?.ok_or("Todo not found")?;
-        let backend = self.get_store(&todo_record.calendar_id)?;
-        let calendar_id = backend.calendar_id();
-
-        // Update todo through backend
-        let updated_todo = backend
-            .update_todo(&uid, &patch)
-            .await
-            .map_err(|e| format!("Failed to update todo in store: {e}"))?;
+        release: BlockRelease,
+    ) -> Result<usize, AimError> {
+        let blocks = self.planned_blocks(id).await?;
+        if blocks.is_empty() {
+            return Ok(0);
+        }
 
-        // Update database
-        self.db
-            .upsert_todo(&uid, &updated_todo, calendar_id)
-            .await?;
+        let now = self.now.timestamp();
+        let tz = self.now.time_zone();
+        let mut txn = self.begin()?;
+        for block in &blocks {
+            let id = Id::Uid(block.uid().into_owned());
+            let started = block
+                .start()
+                .and_then(|start| start.to_timestamp_in(tz))
+                .is_some_and(|start| start < now);
+            if release == BlockRelease::Shorten && started {
+                let patch = EventPatch {
+                    end: Some(Some(LooseDateTime::Local(self.now.clone()))),
+                    ..Default::default()
+                };
+                txn.update_event(&id, patch).await?;
+            } else {
+                txn.delete_event(&id).await?;
+            }
+        }
+        txn.commit().await?;
+        Ok(blocks.len())
+    }
 
-        let todo = self.short_ids.todo(updated_todo).await?;
-        Ok(todo)
+    /// Flush the short IDs to remove all entries.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn flush_short_ids(&self) -> Result<(), AimError> {
+        Ok(self.short_ids.flush().await?)
     }
 
-    /// Get a todo by its id.
+    /// Moves the todos completed and the events ended longer ago than the retention policy
+    /// allows to the archive of the cache, where listings leave them out unless asked for.
+    ///
+    /// Only the cache is changed, the calendar files and remote stores keep the items.
     ///
     /// # Errors
-    /// If the todo is not found or database access fails.
-    pub async fn get_todo(&self, id: &Id) -> Result<impl Todo + 'static, Box<dyn Error>> {
-        let uid = self.short_ids.get_uid(id).await?;
-        match self.db.todos.get(&uid).await {
-            Ok(Some(todo)) => Ok(self.short_ids.todo(todo).await?),
-            Ok(None) => Err("Event not found".into()),
-            Err(e) => Err(e.into()),
+    /// If database access fails.
+    pub async fn run_housekeeping(&self) -> Result<HousekeepingReport, AimError> {
+        let policy = self.config.housekeeping;
+        let mut report = HousekeepingReport::default();
+        if let Some(age) = policy.archive_events_after {
+            let cutoff = age.before(&self.now);
+            report.events = self
+                .db
+                .events
+                .archive_ended_before(&cutoff, &self.now)
+                .await?;
         }
+        if let Some(age) = policy.archive_completed_after {
+            let cutoff = age.before(&self.now);
+            report.todos = self
+                .db
+                .todos
+                .archive_completed_before(&cutoff, &self.now)
+                .await?;
+        }
+        self.db.record_housekeeping(self.now.timestamp()).await?;
+        tracing::info!(
+            events = report.events,
+            todos = report.todos,
+            "archived old items"
+        );
+        Ok(report)
     }
 
-    /// List todos matching the given conditions, sorted and paginated.
+    /// Runs housekeeping if a retention policy is configured and it has not run within
+    /// [`HOUSEKEEPING_INTERVAL`], returning `None` otherwise.
     ///
     /// # Errors
     /// If database access fails.
-    pub async fn list_todos(
-        &self,
-        conds: &TodoConditions,
-        sort: &[TodoSort],
-        pager: &Pager,
-    ) -> Result<Vec<impl Todo + 'static>, Box<dyn Error>> {
-        let conds = conds.resolve(&self.now)?;
-        let sort = TodoSort::resolve_vec(sort, &self.config);
-        let todos = self.db.todos.list(&conds, &sort, pager).await?;
-        let todos = self.short_ids.todos(todos).await?;
-        Ok(todos)
+    pub async fn run_housekeeping_if_due(&self) -> Result<Option<HousekeepingReport>, AimError> {
+        if !self.config.housekeeping.is_enabled() {
+            return Ok(None);
+        }
+        if let Some(last) = self.db.last_housekeeping().await?
+            && self.now.timestamp().duration_since(last) < HOUSEKEEPING_INTERVAL
+        {
+            return Ok(None);
+        }
+        self.run_housekeeping().await.map(Some)
     }
 
-    /// Counts the number of todos matching the given conditions.
+    /// The home time zones seen, with when each was first seen, the current one last, see
+    /// [`Config::home_timezone`].
     ///
     /// # Errors
     /// If database access fails.
-    pub async fn count_todos(&self, conds: &TodoConditions) -> Result<i64, Box<dyn Error>> {
-        let conds = conds.resolve(&self.now)?;
-        Ok(self.db.todos.count(&conds).await?)
+    pub async fn home_time_zones(&self) -> Result<Vec<(String, Zoned)>, AimError> {
+        let tz = self.now.time_zone();
+        Ok(self
+            .db
+            .home_zones()
+            .await?
+            .into_iter()
+            .map(|(zone, at)| (zone, at.to_zoned(tz.clone())))
+            .collect())
     }
 
-    /// List known calendars ordered by priority.
+    /// Lists the times of the cached events and open todos that read differently in the home
+    /// time zone `to` than they did in `from`.
+    ///
+    /// Those are the floating times, and unless `floating_only`, the times in `from`, which are
+    /// told apart by their offset as the zone itself is not cached. Date-only times are left out.
     ///
     /// # Errors
     /// If database access fails.
-    pub async fn list_calendars(&self) -> Result<Vec<CalendarRecord>, Box<dyn Error>> {
-        Ok(self.db.calendars.list().await?)
+    pub async fn audit_time_zone(
+        &self,
+        from: &TimeZone,
+        to: &TimeZone,
+        floating_only: bool,
+    ) -> Result<Vec<ZoneShift>, AimError> {
+        let fields = self.db.timed_fields().await?;
+        Ok(find_shifts(fields, from, to, floating_only))
     }
 
-    /// Get detailed information for a single calendar.
+    /// Rewrites the times listed by [`Aim::audit_time_zone`] so they keep the instant they had in
+    /// `from`, updating each item through its store like any other edit.
+    ///
+    /// Floating times and times in `from` are all written in `to`, so a second rebase finds none of
+    /// them. With `dry_run`, nothing is written and the times that would be rebased are
+    /// returned. Items whose update fails, such as in a read-only calendar, are reported as
+    /// skipped.
     ///
     /// # Errors
-    /// If the calendar is not found or database access fails.
-    pub async fn get_calendar_details(&self, id: &str) -> Result<CalendarDetails, Box<dyn Error>> {
-        let record = self
-            .db
-            .calendars
-            .get(id)
-            .await?
-            .ok_or_else(|| format!("Calendar not found: {id}"))?;
+    /// If database access fails.
+    pub async fn rebase_time_zone(
+        &self,
+        from: &TimeZone,
+        to: &TimeZone,
+        floating_only: bool,
+        dry_run: bool,
+    ) -> Result<ZoneRebase, AimError> {
+        let shifts = self.audit_time_zone(from, to, floating_only).await?;
+        let mut report = ZoneRebase::default();
+        if dry_run {
+            report.rebased = shifts;
+            return Ok(report);
+        }
 
-        let backend = self
-            .config
-            .calendars
-            .iter()
-            .find(|calendar| calendar.id == record.id)
-            .and_then(|calendar| {
-                self.config
-                    .stores
-                    .get(&calendar.store)
-                    .map(|store_def| Self::calendar_store_details(calendar, store_def))
-            });
+        // The start and end of an event are rebased by a single patch
+        let mut items: Vec<Vec<ZoneShift>> = Vec::new();
+        for shift in shifts {
+            match items.iter_mut().find(|item| {
+                item.first()
+                    .is_some_and(|first| first.kind == shift.kind && first.uid == shift.uid)
+            }) {
+                Some(item) => item.push(shift),
+                None => items.push(vec![shift]),
+            }
+        }
 
-        Ok(CalendarDetails {
-            id: record.id.clone(),
-            name: record.name.clone(),
-            kind: record.kind.clone(),
-            priority: record.priority,
-            enabled: record.enabled,
-            is_default: self.default_calendar == record.id,
-            created_at: record.created_at.clone(),
-            updated_at: record.updated_at.clone(),
-            store: backend,
+        for item in items {
+            let Some(first) = item.first() else {
+                continue;
+            };
+            let (kind, uid) = (first.kind, first.uid.clone());
+            let id = Id::Uid(uid.clone());
+            let result = match kind {
+                Kind::Event => {
+                    let mut patch = EventPatch::default();
+                    for shift in &item {
+                        match shift.field {
+                            "start" => patch.start = Some(Some(shift.rebased())),
+                            _ => patch.end = Some(Some(shift.rebased())),
+                        }
+                    }
+                    self.update_event(&id, patch).await.map(|_| ())
+                }
+                Kind::Todo => {
+                    let patch = TodoPatch {
+                        due: Some(Some(first.rebased())),
+                        ..Default::default()
+                    };
+                    self.update_todo(&id, patch).await.map(|_| ())
+                }
+            };
+            match result {
+                Ok(()) => report.rebased.extend(item),
+                Err(e) => report.skipped.push((uid, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Counts the items in the hot and archive tables of the cache, disabled calendars included.
+    ///
+    /// # Errors
+    /// If database access fails.
+    pub async fn cache_stats(&self) -> Result<CacheStats, AimError> {
+        let sum = |counts: Vec<(String, i64)>| counts.into_iter().map(|(_, n)| n).sum();
+        let last_housekeeping = self.db.last_housekeeping().await?;
+        Ok(CacheStats {
+            events: sum(self.db.events.count_by_calendar().await?),
+            archived_events: self.db.events.count_archived().await?,
+            todos: sum(self.db.todos.count_by_calendar().await?),
+            archived_todos: self.db.todos.count_archived().await?,
+            last_housekeeping: last_housekeeping.map(|a| a.to_zoned(self.now.time_zone().clone())),
         })
     }
 
-    /// Startup notices produced while reconciling config and database state.
-    #[must_use]
-    pub fn startup_notices(&self) -> &[String] {
-        &self.startup_notices
+    /// Synchronizes the store with the local cache.
+    ///
+    /// The writes staged for the stores, such as by [`Aim::capture_todo`], are pushed first. Those
+    /// a store refuses are kept for the next sync, with a warning. Same as [`Aim::apply_sync`]
+    /// with the plan of [`Aim::plan_sync`], but without reading the stores twice to plan the
+    /// changes pulled, as nothing previews them.
+    ///
+    /// # Errors
+    /// If synchronization fails.
+    pub async fn sync(&self) -> Result<SyncResult, AimError> {
+        let plan = plan_pending(&self.db).await?;
+        self.apply_sync(plan).await
     }
 
-    /// Flush the short IDs to remove all entries.
+    /// Plans the writes the next sync pushes to the stores and the changes it pulls from them,
+    /// without sending anything to the stores or changing the cache, to preview or confirm them
+    /// before [`Aim::apply_sync`].
+    ///
+    /// The calendars are planned in order of their IDs. A subscription that cannot be read is
+    /// skipped with a warning, as the sync skips it too.
     ///
     /// # Errors
-    /// If database access fails.
-    pub async fn flush_short_ids(&self) -> Result<(), Box<dyn Error>> {
-        self.short_ids.flush().await
+    /// If database access fails, or a store other than a subscription cannot be read.
+    pub async fn plan_sync(&self) -> Result<SyncPlan, AimError> {
+        let mut plan = plan_pending(&self.db).await?;
+        let mut calendar_ids: Vec<_> = self.stores.keys().collect();
+        calendar_ids.sort();
+        for calendar_id in calendar_ids {
+            let Some(backend) = self.stores.get(calendar_id) else {
+                continue;
+            };
+            match plan_pull(&mut plan, calendar_id, backend.as_ref()).await {
+                Ok(()) => {}
+                Err(e) if Self::is_subscription(&self.config, calendar_id) => {
+                    tracing::warn!(calendar_id, err = %e, "failed to plan subscription refresh");
+                }
+                Err(e) => return Err(sync_error(calendar_id, e)),
+            }
+        }
+        Ok(plan)
     }
 
-    /// Synchronizes the store with the local cache.
+    /// Synchronizes the store with the local cache, pushing the writes of `plan` first, see
+    /// [`Aim::sync`].
     ///
     /// # Errors
     /// If synchronization fails.
-    pub async fn sync(&self) -> Result<SyncResult, Box<dyn Error>> {
+    pub async fn apply_sync(&self, plan: SyncPlan) -> Result<SyncResult, AimError> {
+        push_planned(&self.db, &self.stores, plan).await?;
+
         let mut created = 0;
         let mut updated = 0;
         let mut deleted = 0;
 
         for (calendar_id, backend) in &self.stores {
-            match backend.sync_cache().await {
+            let result = traced_sync(calendar_id, backend.sync_cache()).await;
+            record_sync(&self.db, calendar_id, &result).await?;
+            match result {
                 Ok(result) => {
                     created += result.created;
                     updated += result.updated;
                     deleted += result.deleted;
                 }
-                Err(e) => {
-                    return Err(format!("Failed to sync calendar '{calendar_id}': {e}").into());
+                Err(e) if Self::is_subscription(&self.config, calendar_id) => {
+                    tracing::warn!(calendar_id, err = %e, "failed to refresh subscription");
                 }
+                Err(e) => return Err(sync_error(calendar_id, e)),
             }
         }
 
@@ -767,15 +3124,246 @@ impl Aim {
         })
     }
 
+    /// Lists the scheduling messages waiting on the servers of the calendars, such as meeting
+    /// invitations, newest first.
+    ///
+    /// Calendars on the same server share an inbox, so each message is listed once. A
+    /// calendar whose inbox cannot be read is skipped with a warning.
+    pub async fn pending_invitations(&self) -> Vec<Invitation> {
+        let mut invitations: Vec<Invitation> = Vec::new();
+        for (calendar_id, backend) in &self.stores {
+            match backend.pending_invitations().await {
+                Ok(found) => {
+                    for invitation in found {
+                        let seen = invitations.iter().any(|i| {
+                            i.uid == invitation.uid
+                                && i.method == invitation.method
+                                && i.sent == invitation.sent
+                        });
+                        if !seen {
+                            invitations.push(invitation);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(calendar_id, err = %e, "failed to read inbox");
+                }
+            }
+        }
+        invitations.sort_by(|a, b| b.sent.cmp(&a.sent));
+        invitations
+    }
+
+    /// Refreshes all enabled subscriptions, regardless of their refresh interval.
+    ///
+    /// Returns the outcome for each subscribed calendar, in configuration order.
+    /// A failed refresh keeps the last cached copy of the feed.
+    pub async fn sync_subscriptions(&self) -> Vec<(String, Result<SyncResult, AimError>)> {
+        let mut results = Vec::new();
+        for calendar in &self.config.calendars {
+            if !Self::is_subscription(&self.config, &calendar.id) {
+                continue;
+            }
+            if let Some(backend) = self.stores.get(&calendar.id) {
+                let result = traced_sync(&calendar.id, backend.refresh()).await;
+                if let Err(e) = record_sync(&self.db, &calendar.id, &result).await {
+                    tracing::warn!(calendar_id = calendar.id, err = %e, "failed to record sync");
+                }
+                results.push((calendar.id.clone(), result.map_err(AimError::from)));
+            }
+        }
+        results
+    }
+
+    /// Mirrors the `- [ ]` task lists of the Markdown files under `dir` with todos.
+    ///
+    /// New unchecked tasks become todos in the default calendar. A checkbox checked or
+    /// unchecked in a file that changed since the last sync completes or reopens its todo;
+    /// otherwise the checkbox is updated from the todo. Tasks whose text was edited or that
+    /// moved are matched to their todo by similarity, and anything that cannot be matched
+    /// with certainty is reported as a conflict instead of creating a duplicate.
+    ///
+    /// # Errors
+    /// If a notes file cannot be read or written, database or backend access fails.
+    pub async fn sync_markdown(&self, dir: &Path) -> Result<MarkdownSyncReport, AimError> {
+        markdown::sync(self, &self.db, dir).await
+    }
+
+    /// Begins a transaction, to change many items all at once, see [`Transaction`].
+    ///
+    /// # Errors
+    /// If a transaction is already open, as they cannot be nested.
+    pub fn begin(&self) -> Result<Transaction<'_>, AimError> {
+        Transaction::new(self)
+    }
+
+    /// Runs `body` in a transaction, committing it once `body` succeeds.
+    ///
+    /// If `body` fails, nothing it did is written. If committing fails, the cache is left as it was
+    /// and the items created in the stores are deleted again, see [`Transaction::commit`].
+    ///
+    /// # Errors
+    /// If a transaction is already open, `body` fails, or committing does.
+    pub async fn transaction<'a, T, F>(&'a self, body: F) -> Result<T, AimError>
+    where
+        F: for<'t> FnOnce(&'t mut Transaction<'a>) -> TransactionFuture<'t, T>,
+    {
+        let mut txn = self.begin()?;
+        let value = body(&mut txn).await?;
+        txn.commit().await?;
+        Ok(value)
+    }
+
+    /// Imports the events and todos of iCalendar content into a calendar, the default one if
+    /// `calendar_id` is `None`.
+    ///
+    /// Items keep their UID, and the ones whose UID already exists in aim are skipped rather
+    /// than overwritten. Overrides of occurrences are written next to their recurring event if
+    /// it was imported along with them. The occurrences of an expanded export of aim, see
+    /// [`Aim::export_expanded`], are imported as events of their own, which the report notes.
+    ///
+    /// `source` names the content in errors, such as the file it was read from. The content is
+    /// imported all at once in a transaction, so nothing of it is if any item fails.
+    ///
+    /// # Errors
+    /// If the content cannot be parsed, or database or backend access fails.
+    pub async fn import_ics(
+        &self,
+        source: &Path,
+        content: &str,
+        calendar_id: Option<&str>,
+    ) -> Result<ImportReport, AimError> {
+        let parsed = import::parse_calendars(source, content)?;
+        let calendar_id = calendar_id.unwrap_or(&self.default_calendar);
+
+        let mut txn = self.begin()?;
+        let mut report = ImportReport {
+            expanded: parsed.expanded,
+            ..ImportReport::default()
+        };
+        let mut overrides = Vec::new();
+        for component in parsed.components {
+            let (uid, kind) = match &component {
+                CalendarComponent::Event(event) if is_override(event) => {
+                    overrides.push(component);
+                    continue;
+                }
+                CalendarComponent::Event(event) => (event.uid().into_owned(), Kind::Event),
+                CalendarComponent::Todo(todo) => (todo.uid().into_owned(), Kind::Todo),
+                _ => continue,
+            };
+            let exists = txn.creates(&uid)
+                || self.db.events.get(&uid).await?.is_some()
+                || self.db.todos.get(&uid).await?.is_some();
+            if exists {
+                tracing::info!(uid, "skipping item that already exists");
+                report.skipped.push(uid);
+                continue;
+            }
+
+            txn.import(component, uid, calendar_id)?;
+            match kind {
+                Kind::Event => report.events += 1,
+                Kind::Todo => report.todos += 1,
+            }
+        }
+
+        for component in overrides {
+            let CalendarComponent::Event(event) = component else {
+                continue;
+            };
+            let uid = event.uid().into_owned();
+            if !txn.creates(&uid) {
+                tracing::info!(uid, "skipping override of an event that was not imported");
+                if !report.skipped.contains(&uid) {
+                    report.skipped.push(uid);
+                }
+                continue;
+            }
+            txn.put_event_override(uid, calendar_id, event)?;
+            report.overrides += 1;
+        }
+        txn.commit().await?;
+        Ok(report)
+    }
+
+    /// Imports todos read from the export of another todo app into a calendar, or the default
+    /// calendar if none is given.
+    ///
+    /// Like [`Aim::import_ics`], todos whose UID already exists in aim are skipped, so an export
+    /// can be imported again after it was updated, and the todos are imported all at once.
+    ///
+    /// # Errors
+    /// If database or backend access fails.
+    pub async fn import_todos(
+        &self,
+        todos: Vec<ForeignTodo>,
+        calendar_id: Option<&str>,
+    ) -> Result<ImportReport, AimError> {
+        let calendar_id = calendar_id.unwrap_or(&self.default_calendar);
+        let now = self.now();
+
+        let mut txn = self.begin()?;
+        let mut report = ImportReport::default();
+        for todo in todos {
+            let uid = todo.uid.clone();
+            let exists = txn.creates(&uid)
+                || self.db.events.get(&uid).await?.is_some()
+                || self.db.todos.get(&uid).await?.is_some();
+            if exists {
+                tracing::info!(uid, "skipping todo that already exists");
+                report.skipped.push(uid);
+                continue;
+            }
+
+            let todo = todo.into_ics(&self.config, &now);
+            txn.import(CalendarComponent::Todo(todo), uid, calendar_id)?;
+            report.todos += 1;
+        }
+        txn.commit().await?;
+        Ok(report)
+    }
+
+    /// Writes a backup archive of the database, the config file and all local calendars to `dest`.
+    ///
+    /// # Errors
+    /// If the database cannot be snapshotted or the archive cannot be written.
+    pub async fn create_backup(
+        &self,
+        dest: &Path,
+        config_file: Option<&Path>,
+    ) -> Result<BackupManifest, AimError> {
+        backup::create(&self.db, &self.config, dest, config_file).await
+    }
+
+    /// Restores a backup archive created by [`Aim::create_backup`].
+    ///
+    /// The archive is verified and unpacked into the state directory, and its
+    /// database is migrated, before anything is replaced. No [`Aim`] may be
+    /// open on the same state while restoring.
+    ///
+    /// # Errors
+    /// [`AimError::Parse`] for a corrupt archive, [`AimError::InvalidInput`] for
+    /// an archive made by a newer version, or if any file cannot be replaced.
+    pub async fn restore_backup(
+        mut config: Config,
+        archive: &Path,
+        config_file: Option<&Path>,
+    ) -> Result<BackupManifest, AimError> {
+        config.expand_env_vars()?;
+        config.normalize()?;
+        backup::restore(&config, archive, config_file).await
+    }
+
     /// Close the AIM instance, saving any changes to the database.
     ///
     /// # Errors
     /// If closing the database fails.
-    pub async fn close(self) -> Result<(), Box<dyn Error>> {
+    pub async fn close(self) -> Result<(), AimError> {
         self.db.close().await
     }
 
-    async fn generate_uid(&self, kind: Kind) -> Result<String, Box<dyn Error>> {
+    async fn generate_uid(&self, kind: Kind) -> Result<String, AimError> {
         for i in 0..16 {
             let uid = Uuid::new_v4().to_string(); // TODO: better uid
             tracing::debug!(
@@ -797,11 +3385,109 @@ impl Aim {
         }
 
         tracing::warn!("failed to generate a unique uid after multiple attempts");
-        Err("Failed to generate a unique UID after multiple attempts".into())
+        Err(AimError::Other(
+            "Failed to generate a unique UID after multiple attempts".to_string(),
+        ))
+    }
+}
+
+fn not_found(kind: &'static str, id: &Id) -> AimError {
+    AimError::NotFound {
+        kind,
+        id: id.as_uid().to_string(),
     }
 }
 
-async fn prepare(config: &Config) -> Result<(), Box<dyn Error>> {
+/// Patch moving the due of a todo from `due` to `new_due`, recording where it was moved from.
+fn postpone_patch(due: Option<LooseDateTime>, new_due: LooseDateTime) -> TodoPatch {
+    TodoPatch {
+        due: Some(Some(new_due)),
+        snoozed_from: due,
+        ..Default::default()
+    }
+}
+
+fn kind_name(kind: Option<Kind>) -> &'static str {
+    match kind {
+        Some(Kind::Event) => "Event",
+        Some(Kind::Todo) => "Todo",
+        None => "Item",
+    }
+}
+
+fn invalid_conditions(reason: String) -> AimError {
+    AimError::InvalidInput {
+        field: "conditions",
+        reason,
+    }
+}
+
+/// Records the outcome of syncing a calendar, shown by `aim calendar list`.
+/// Runs the sync of a calendar in a span with its ID, logging the outcome and how long it took.
+async fn traced_sync(
+    calendar_id: &str,
+    sync: impl Future<Output = Result<SyncResult, StoreError>>,
+) -> Result<SyncResult, StoreError> {
+    let span = tracing::info_span!("sync_calendar", calendar_id);
+    let start = Instant::now();
+    let result = sync.instrument(span.clone()).await;
+    span.in_scope(|| match &result {
+        Ok(r) => tracing::info!(
+            created = r.created,
+            updated = r.updated,
+            deleted = r.deleted,
+            duration = ?start.elapsed(),
+            "synced calendar"
+        ),
+        Err(e) => tracing::debug!(err = %e, duration = ?start.elapsed(), "failed to sync calendar"),
+    });
+    result
+}
+
+async fn record_sync<T>(
+    db: &Db,
+    calendar_id: &str,
+    result: &Result<T, StoreError>,
+) -> Result<(), AimError> {
+    match result {
+        Ok(_) => db.calendars.record_sync_success(calendar_id).await?,
+        Err(e) => {
+            db.calendars
+                .record_sync_error(calendar_id, &e.to_string())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the calendar was synced successfully within `max_age` of `now`.
+async fn is_fresh(
+    db: &Db,
+    calendar_id: &str,
+    max_age: SignedDuration,
+    now: &Zoned,
+) -> Result<bool, AimError> {
+    let synced_at = db
+        .calendars
+        .sync_status(calendar_id)
+        .await?
+        .and_then(|status| status.last_success_at)
+        .and_then(|at| at.parse::<Timestamp>().ok());
+    Ok(synced_at.is_some_and(|at| now.timestamp().duration_since(at) < max_age))
+}
+
+/// Adds the calendar to store errors that carry no classification of their own.
+fn sync_error(calendar_id: &str, e: StoreError) -> AimError {
+    match AimError::from(e) {
+        AimError::Backend(source) => AimError::Backend(Box::new(StoreContext {
+            context: format!("Failed to sync calendar '{calendar_id}'"),
+            source,
+        })),
+        e => e,
+    }
+}
+
+async fn prepare(config: &Config) -> Result<(), AimError> {
     if let Some(parent) = &config.state_dir {
         tracing::debug!(path = %parent.display(), "ensuring state directory exists");
         fs::create_dir_all(parent).await?;
@@ -809,14 +3495,95 @@ async fn prepare(config: &Config) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn initialize_db(config: &Config) -> Result<Db, Box<dyn Error>> {
-    const NAME: &str = "aim.db";
-    let db = if let Some(parent) = &config.state_dir {
-        Db::open(Some(&parent.join(NAME))).await
+/// Locks the state directory against other aim processes, leaving a database read as a copy
+/// with [`Config::read_only_compat`] unlocked. For a command that only reads, the lock is tried
+/// once rather than waited for.
+async fn lock_state_dir(config: &Config, read_only: bool) -> Result<Option<DataDirLock>, AimError> {
+    let Some(dir) = config.state_dir.clone() else {
+        return Ok(None);
+    };
+    if config.read_only_compat {
+        return Ok(None);
+    }
+    let timeout = if read_only {
+        Duration::ZERO
     } else {
-        Db::open(None).await
+        LOCK_TIMEOUT
+    };
+    tokio::task::spawn_blocking(move || DataDirLock::acquire(&dir, timeout))
+        .await
+        .map_err(|e| AimError::Other(format!("Failed to lock the state directory: {e}")))?
+        .map(Some)
+}
+
+/// Warns of the state directory and local calendars kept in folders of sync clients.
+fn sync_folder_notices(config: &Config) -> Vec<String> {
+    let calendars = config
+        .calendars
+        .iter()
+        .filter_map(|calendar| calendar.calendar_path.as_deref().map(Path::new));
+    let paths = config
+        .state_dir
+        .as_deref()
+        .into_iter()
+        .chain(config.calendar_path.as_deref())
+        .chain(calendars);
+
+    let mut notices = Vec::new();
+    for path in paths {
+        if let Some(client) = sync_folder(path) {
+            notices.push(format!(
+                "{} is in a {client} folder, which can corrupt the files aim writes there; \
+                 move it out of the folder",
+                path.display()
+            ));
+        }
     }
-    .map_err(|e| format!("Failed to initialize db: {e}"))?;
+    notices
+}
+
+/// Records the home time zone, warning once it changed if items read differently since.
+async fn home_zone_notice(
+    config: &Config,
+    db: &Db,
+    now: &Zoned,
+) -> Result<Option<String>, AimError> {
+    let Some((name, tz)) = home_zone(config) else {
+        return Ok(None);
+    };
+    let Some(previous) = db.record_home_zone(&name, now.timestamp()).await? else {
+        return Ok(None);
+    };
+    let Ok(previous_tz) = TimeZone::get(&previous) else {
+        return Ok(None);
+    };
+
+    let shifts = find_shifts(db.timed_fields().await?, &previous_tz, &tz, false);
+    Ok(change_notice(&previous, &name, &shifts))
+}
+
+/// Tells of an item left out of the cache, with the errors found in it.
+fn broken_notice(item: &BrokenItem) -> String {
+    let item_name = match &item.uid {
+        Some(uid) => format!("{} '{uid}'", item.component),
+        None => format!("A {}", item.component),
+    };
+    format!(
+        "{item_name} in {} is skipped as it cannot be read: {}",
+        item.source,
+        item.errors.join("; ")
+    )
+}
+
+async fn initialize_db(config: &Config) -> Result<Db, AimError> {
+    let mut db = match &config.state_dir {
+        Some(parent) if config.read_only_compat => {
+            Db::open_read_only_compat(&parent.join(DB_FILE_NAME), TimeZone::system()).await
+        }
+        Some(parent) => Db::open(Some(&parent.join(DB_FILE_NAME))).await,
+        None => Db::open(None).await,
+    }?;
 
+    db.set_rules(config.rules.clone());
     Ok(db)
 }
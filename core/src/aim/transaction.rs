@@ -0,0 +1,678 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+
+use aimcal_ical::{CalendarComponent, VEvent, VTodo};
+
+use crate::aim::not_found;
+use crate::staging::write_staged;
+use crate::{
+    Aim, AimError, DateTimeAnchor, EventDraft, EventPatch, Id, Kind, TodoDraft, TodoPatch,
+};
+
+/// Future run by the body of [`Aim::transaction`], borrowing the transaction for `'t`.
+pub type TransactionFuture<'t, T> = Pin<Box<dyn Future<Output = Result<T, AimError>> + Send + 't>>;
+
+/// Changes to many items applied all at once, see [`Aim::transaction`].
+///
+/// Changes are checked as they are made, against the items as they were when the transaction
+/// began, and written to the stores and the cache on [`Transaction::commit`]. Items created in the
+/// transaction can be changed again by their UID before it is committed. A transaction dropped
+/// without committing leaves everything untouched.
+pub struct Transaction<'a> {
+    aim: &'a Aim,
+    writes: Vec<Write>,
+    /// Writes holding the component of an item by its UID, which later changes apply to.
+    pending: HashMap<String, usize>,
+}
+
+/// A change to write to a store on commit.
+enum Write {
+    CreateEvent {
+        uid: String,
+        calendar_id: String,
+        event: VEvent<String>,
+    },
+    CreateTodo {
+        uid: String,
+        calendar_id: String,
+        todo: VTodo<String>,
+    },
+    UpdateEvent {
+        uid: String,
+        calendar_id: String,
+        patch: EventPatch,
+    },
+    UpdateTodo {
+        uid: String,
+        calendar_id: String,
+        patch: TodoPatch,
+    },
+    /// A todo staged for its store but not pushed yet, which is edited in the cache only.
+    UpdateStagedTodo {
+        uid: String,
+        calendar_id: String,
+        op_id: i64,
+        todo: VTodo<String>,
+    },
+    DeleteEvent {
+        uid: String,
+        calendar_id: String,
+    },
+    DeleteTodo {
+        uid: String,
+        calendar_id: String,
+    },
+    PutEventOverride {
+        uid: String,
+        calendar_id: String,
+        event: VEvent<String>,
+    },
+}
+
+/// A change written to a store, to cache once all of them are.
+enum Written {
+    Event {
+        uid: String,
+        calendar_id: String,
+        event: VEvent<String>,
+        /// Resource of an event created by the transaction.
+        resource_id: Option<String>,
+    },
+    Todo {
+        uid: String,
+        calendar_id: String,
+        todo: VTodo<String>,
+        /// Resource of a todo created by the transaction.
+        resource_id: Option<String>,
+    },
+    StagedTodo {
+        uid: String,
+        calendar_id: String,
+        op_id: i64,
+        todo: VTodo<String>,
+    },
+    Deleted {
+        uid: String,
+        calendar_id: String,
+        kind: Kind,
+    },
+    EventOverride {
+        uid: String,
+    },
+}
+
+impl<'a> Transaction<'a> {
+    pub(super) fn new(aim: &'a Aim) -> Result<Self, AimError> {
+        if aim.in_transaction.swap(true, Ordering::AcqRel) {
+            return Err(AimError::Other(
+                "Nested transactions are not supported".to_string(),
+            ));
+        }
+        Ok(Self {
+            aim,
+            writes: Vec::new(),
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Adds a new event from the given draft, returning its UID.
+    ///
+    /// # Errors
+    /// If the draft is invalid, the calendar does not accept events, or database access fails.
+    pub async fn new_event(&mut self, draft: EventDraft) -> Result<String, AimError> {
+        let (uid, event, calendar_id) = self.aim.prepare_event(&draft).await?;
+        let calendar_id = calendar_id.to_string();
+        self.push_pending(Write::CreateEvent {
+            uid: uid.clone(),
+            calendar_id,
+            event,
+        });
+        Ok(uid)
+    }
+
+    /// Adds a new todo from the given draft, returning its UID.
+    ///
+    /// # Errors
+    /// If the draft is invalid, the calendar does not accept todos, or database access fails.
+    pub async fn new_todo(&mut self, draft: TodoDraft) -> Result<String, AimError> {
+        let (uid, todo, calendar_id) = self.aim.prepare_todo(&draft).await?;
+        let calendar_id = calendar_id.to_string();
+        self.push_pending(Write::CreateTodo {
+            uid: uid.clone(),
+            calendar_id,
+            todo,
+        });
+        Ok(uid)
+    }
+
+    /// Updates an event, returning its UID, see [`Aim::update_event`].
+    ///
+    /// # Errors
+    /// If the event is not found, its calendar is read-only, or database access fails.
+    pub async fn update_event(&mut self, id: &Id, patch: EventPatch) -> Result<String, AimError> {
+        let aim = self.aim;
+        if let Some(Write::CreateEvent { uid, event, .. }) = self.pending_mut(id) {
+            patch.resolve(aim.now.clone()).apply_to(event);
+            return Ok(uid.clone());
+        }
+
+        let uid = aim.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(record) = aim.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
+        };
+        let backend = aim
+            .get_writable_event_store(&uid, &record.calendar_id)
+            .await?;
+        self.writes.push(Write::UpdateEvent {
+            uid: uid.clone(),
+            calendar_id: backend.calendar_id().to_string(),
+            patch,
+        });
+        Ok(uid)
+    }
+
+    /// Updates a todo, returning its UID, see [`Aim::update_todo`].
+    ///
+    /// # Errors
+    /// If the patch is invalid, the todo is not found, its calendar is read-only, or database
+    /// access fails.
+    pub async fn update_todo(&mut self, id: &Id, patch: TodoPatch) -> Result<String, AimError> {
+        patch.validate().map_err(|reason| AimError::InvalidInput {
+            field: "percent complete",
+            reason,
+        })?;
+        let aim = self.aim;
+        let patch = aim.recur_patch(patch);
+        if let Some(
+            Write::CreateTodo { uid, todo, .. } | Write::UpdateStagedTodo { uid, todo, .. },
+        ) = self.pending_mut(id)
+        {
+            let patch = aim.seal_patch(uid, &*todo, patch)?;
+            aim.check_completed_at(todo, &patch)?;
+            patch.resolve(&aim.now).apply_to(todo);
+            return Ok(uid.clone());
+        }
+
+        let uid = aim.resolve_uid(id, Some(Kind::Todo)).await?;
+        let Some(record) = aim.db.todos.get(&uid).await? else {
+            return Err(not_found("Todo", id));
+        };
+        let patch = aim.seal_patch(&uid, &record, patch)?;
+        let calendar_id = record.calendar_id;
+        if let Some((op_id, mut todo)) = aim.get_staged_todo(&uid).await? {
+            aim.check_completed_at(&todo, &patch)?;
+            patch.resolve(&aim.now).apply_to(&mut todo);
+            self.push_pending(Write::UpdateStagedTodo {
+                uid: uid.clone(),
+                calendar_id,
+                op_id,
+                todo,
+            });
+        } else {
+            let store = aim.get_writable_store(&calendar_id, Kind::Todo)?;
+            if patch.completed_at.is_some() {
+                let todo = store
+                    .get_todo(&uid)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                aim.check_completed_at(&todo, &patch)?;
+            }
+            self.writes.push(Write::UpdateTodo {
+                uid: uid.clone(),
+                calendar_id,
+                patch,
+            });
+        }
+        Ok(uid)
+    }
+
+    /// Snoozes a todo from the due it had when the transaction began, returning its UID, see
+    /// [`Aim::snooze_todo`].
+    ///
+    /// # Errors
+    /// If the todo is not found, its calendar is read-only, or database access fails.
+    pub async fn snooze_todo(
+        &mut self,
+        id: &Id,
+        delta: DateTimeAnchor,
+    ) -> Result<String, AimError> {
+        let patch = self.aim.snooze_patch(id, delta).await?;
+        self.update_todo(id, patch).await
+    }
+
+    /// Defers a todo, returning its UID, see [`Aim::defer_todo`].
+    ///
+    /// # Errors
+    /// If the todo is not found, the anchor cannot be resolved, its calendar is read-only, or
+    /// database access fails.
+    pub async fn defer_todo(
+        &mut self,
+        id: &Id,
+        anchor: DateTimeAnchor,
+    ) -> Result<String, AimError> {
+        let patch = self.aim.defer_patch(id, anchor).await?;
+        self.update_todo(id, patch).await
+    }
+
+    /// Deletes an event from its calendar, returning its UID.
+    ///
+    /// # Errors
+    /// If the event is not found, its calendar is read-only, or database access fails.
+    pub async fn delete_event(&mut self, id: &Id) -> Result<String, AimError> {
+        if let Some(uid) = self.drop_pending(id) {
+            return Ok(uid);
+        }
+
+        let uid = self.aim.resolve_uid(id, Some(Kind::Event)).await?;
+        let Some(record) = self.aim.db.events.get(&uid).await? else {
+            return Err(not_found("Event", id));
+        };
+        self.aim
+            .get_writable_store(&record.calendar_id, Kind::Event)?;
+        self.writes.push(Write::DeleteEvent {
+            uid: uid.clone(),
+            calendar_id: record.calendar_id,
+        });
+        Ok(uid)
+    }
+
+    /// Deletes a todo from its calendar, returning its UID.
+    ///
+    /// # Errors
+    /// If the todo is not found or staged for its store but not pushed yet, its calendar is
+    /// read-only, or database access fails.
+    pub async fn delete_todo(&mut self, id: &Id) -> Result<String, AimError> {
+        if let Some(uid) = self.drop_pending(id) {
+            return Ok(uid);
+        }
+
+        let uid = self.aim.resolve_uid(id, Some(Kind::Todo)).await?;
+        let Some(record) = self.aim.db.todos.get(&uid).await? else {
+            return Err(not_found("Todo", id));
+        };
+        if self.aim.get_staged_todo(&uid).await?.is_some() {
+            return Err(AimError::InvalidInput {
+                field: "todo",
+                reason: format!("{uid} is not pushed to its store yet, sync first"),
+            });
+        }
+        self.aim
+            .get_writable_store(&record.calendar_id, Kind::Todo)?;
+        self.writes.push(Write::DeleteTodo {
+            uid: uid.clone(),
+            calendar_id: record.calendar_id,
+        });
+        Ok(uid)
+    }
+
+    /// Whether an item with the UID is created by the transaction.
+    pub(super) fn creates(&self, uid: &str) -> bool {
+        self.pending.get(uid).is_some_and(|&i| {
+            matches!(
+                self.writes.get(i),
+                Some(Write::CreateEvent { .. } | Write::CreateTodo { .. })
+            )
+        })
+    }
+
+    /// Creates an item read from elsewhere, keeping its UID.
+    ///
+    /// # Errors
+    /// If the calendar does not accept items of its kind.
+    pub(super) fn import(
+        &mut self,
+        component: CalendarComponent<String>,
+        uid: String,
+        calendar_id: &str,
+    ) -> Result<(), AimError> {
+        let calendar_id = calendar_id.to_string();
+        match component {
+            CalendarComponent::Event(event) => {
+                self.aim.get_writable_store(&calendar_id, Kind::Event)?;
+                self.push_pending(Write::CreateEvent {
+                    uid,
+                    calendar_id,
+                    event,
+                });
+            }
+            CalendarComponent::Todo(todo) => {
+                self.aim.get_writable_store(&calendar_id, Kind::Todo)?;
+                self.push_pending(Write::CreateTodo {
+                    uid,
+                    calendar_id,
+                    todo,
+                });
+            }
+            _ => unreachable!("only events and todos are imported"),
+        }
+        Ok(())
+    }
+
+    /// Puts an override of occurrences next to its recurring event.
+    ///
+    /// # Errors
+    /// If the calendar does not accept events.
+    pub(super) fn put_event_override(
+        &mut self,
+        uid: String,
+        calendar_id: &str,
+        event: VEvent<String>,
+    ) -> Result<(), AimError> {
+        self.aim.get_writable_store(calendar_id, Kind::Event)?;
+        self.writes.push(Write::PutEventOverride {
+            uid,
+            calendar_id: calendar_id.to_string(),
+            event,
+        });
+        Ok(())
+    }
+
+    /// Writes the changes to the stores, then caches them at once.
+    ///
+    /// The stores are written in the order the changes were made. If a write fails, or caching
+    /// them does, the cache is left as it was and the items created in the stores are deleted
+    /// again. Updates and deletions already written cannot be taken back, and are logged.
+    ///
+    /// # Errors
+    /// If store or database access fails.
+    pub async fn commit(mut self) -> Result<(), AimError> {
+        let writes = std::mem::take(&mut self.writes);
+        tracing::debug!(writes = writes.len(), "committing transaction");
+
+        let mut written = Vec::with_capacity(writes.len());
+        for write in writes {
+            match self.write(write).await {
+                Ok(w) => written.push(w),
+                Err(e) => {
+                    self.roll_back(&written).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Err(e) = self.cache(&written).await {
+            self.roll_back(&written).await;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    #[expect(clippy::too_many_lines)]
+    async fn write(&self, write: Write) -> Result<Written, AimError> {
+        let aim = self.aim;
+        let written = match write {
+            Write::CreateEvent {
+                uid,
+                calendar_id,
+                event,
+            } => {
+                let resource_id = aim
+                    .get_writable_store(&calendar_id, Kind::Event)?
+                    .create_event(&uid, &event)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Event {
+                    uid,
+                    calendar_id,
+                    event,
+                    resource_id: Some(resource_id),
+                }
+            }
+            Write::CreateTodo {
+                uid,
+                calendar_id,
+                todo,
+            } => {
+                let resource_id = aim
+                    .get_writable_store(&calendar_id, Kind::Todo)?
+                    .create_todo(&uid, &todo)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Todo {
+                    uid,
+                    calendar_id,
+                    todo,
+                    resource_id: Some(resource_id),
+                }
+            }
+            Write::UpdateEvent {
+                uid,
+                calendar_id,
+                patch,
+            } => {
+                let event = aim
+                    .get_writable_store(&calendar_id, Kind::Event)?
+                    .update_event(&uid, &patch)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Event {
+                    uid,
+                    calendar_id,
+                    event,
+                    resource_id: None,
+                }
+            }
+            Write::UpdateTodo {
+                uid,
+                calendar_id,
+                patch,
+            } => {
+                let todo = aim
+                    .get_writable_store(&calendar_id, Kind::Todo)?
+                    .update_todo(&uid, &patch)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Todo {
+                    uid,
+                    calendar_id,
+                    todo,
+                    resource_id: None,
+                }
+            }
+            Write::UpdateStagedTodo {
+                uid,
+                calendar_id,
+                op_id,
+                todo,
+            } => Written::StagedTodo {
+                uid,
+                calendar_id,
+                op_id,
+                todo,
+            },
+            Write::DeleteEvent { uid, calendar_id } => {
+                aim.get_writable_store(&calendar_id, Kind::Event)?
+                    .delete_event(&uid)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Deleted {
+                    uid,
+                    calendar_id,
+                    kind: Kind::Event,
+                }
+            }
+            Write::DeleteTodo { uid, calendar_id } => {
+                aim.get_writable_store(&calendar_id, Kind::Todo)?
+                    .delete_todo(&uid)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::Deleted {
+                    uid,
+                    calendar_id,
+                    kind: Kind::Todo,
+                }
+            }
+            Write::PutEventOverride {
+                uid,
+                calendar_id,
+                event,
+            } => {
+                aim.get_writable_store(&calendar_id, Kind::Event)?
+                    .put_event_override(&uid, &event)
+                    .await
+                    .map_err(|e| AimError::from_store(&uid, e))?;
+                Written::EventOverride { uid }
+            }
+        };
+        Ok(written)
+    }
+
+    /// Caches the written changes in a single database transaction, which is rolled back if any
+    /// of them fails.
+    async fn cache(&self, written: &[Written]) -> Result<(), AimError> {
+        let mut tx = self.aim.db.begin().await?;
+        for w in written {
+            match w {
+                Written::Event {
+                    uid,
+                    calendar_id,
+                    event,
+                    resource_id,
+                } => {
+                    tx.upsert_event(uid, event, calendar_id).await?;
+                    if let Some(resource_id) = resource_id {
+                        tx.insert_resource(uid, calendar_id, resource_id, None)
+                            .await?;
+                    }
+                }
+                Written::Todo {
+                    uid,
+                    calendar_id,
+                    todo,
+                    resource_id,
+                } => {
+                    tx.upsert_todo(uid, todo, calendar_id).await?;
+                    if let Some(resource_id) = resource_id {
+                        tx.insert_resource(uid, calendar_id, resource_id, None)
+                            .await?;
+                    }
+                }
+                Written::StagedTodo {
+                    uid,
+                    calendar_id,
+                    op_id,
+                    todo,
+                } => {
+                    let ics = write_staged(CalendarComponent::Todo(todo.clone()))?;
+                    tx.update_pending_ics(*op_id, &ics).await?;
+                    tx.upsert_todo(uid, todo, calendar_id).await?;
+                }
+                Written::Deleted {
+                    uid,
+                    calendar_id,
+                    kind: Kind::Event,
+                } => tx.delete_event(uid, calendar_id).await?,
+                Written::Deleted {
+                    uid,
+                    calendar_id,
+                    kind: Kind::Todo,
+                } => tx.delete_todo(uid, calendar_id).await?,
+                Written::EventOverride { .. } => {}
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes the items created in the stores again, as far as possible.
+    async fn roll_back(&self, written: &[Written]) {
+        for w in written.iter().rev() {
+            let (uid, calendar_id, kind) = match w {
+                Written::Event {
+                    uid,
+                    calendar_id,
+                    resource_id: Some(_),
+                    ..
+                } => (uid, calendar_id, Kind::Event),
+                Written::Todo {
+                    uid,
+                    calendar_id,
+                    resource_id: Some(_),
+                    ..
+                } => (uid, calendar_id, Kind::Todo),
+                Written::StagedTodo { .. } => continue,
+                Written::Event { uid, .. }
+                | Written::Todo { uid, .. }
+                | Written::Deleted { uid, .. }
+                | Written::EventOverride { uid } => {
+                    tracing::warn!(uid, "cannot roll back change written to the store");
+                    continue;
+                }
+            };
+
+            let result = match (self.aim.get_store(calendar_id), kind) {
+                (Ok(store), Kind::Event) => store.delete_event(uid).await,
+                (Ok(store), Kind::Todo) => store.delete_todo(uid).await,
+                (Err(e), _) => Err(e.into()),
+            };
+            match result {
+                Ok(()) => tracing::warn!(uid, calendar_id, "rolled back item created in the store"),
+                Err(e) => tracing::warn!(
+                    uid,
+                    calendar_id,
+                    error = %e,
+                    "failed to roll back item created in the store"
+                ),
+            }
+        }
+    }
+
+    fn push_pending(&mut self, write: Write) {
+        let uid = match &write {
+            Write::CreateEvent { uid, .. }
+            | Write::CreateTodo { uid, .. }
+            | Write::UpdateStagedTodo { uid, .. } => uid.clone(),
+            _ => unreachable!("only writes holding a component are pending"),
+        };
+        self.pending.insert(uid, self.writes.len());
+        self.writes.push(write);
+    }
+
+    fn pending_mut(&mut self, id: &Id) -> Option<&mut Write> {
+        let &index = self.pending.get(id.as_uid())?;
+        self.writes.get_mut(index)
+    }
+
+    /// Drops the pending write of an item created by the transaction, returning its UID.
+    fn drop_pending(&mut self, id: &Id) -> Option<String> {
+        if !self.creates(id.as_uid()) {
+            return None;
+        }
+        let (uid, index) = self.pending.remove_entry(id.as_uid())?;
+        self.writes.remove(index);
+        for i in self.pending.values_mut() {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+        Some(uid)
+    }
+}
+
+impl fmt::Debug for Transaction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transaction")
+            .field("writes", &self.writes.len())
+            .field("pending", &self.pending.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.writes.is_empty() {
+            tracing::debug!(
+                writes = self.writes.len(),
+                "transaction dropped without committing"
+            );
+        }
+        self.aim.in_transaction.store(false, Ordering::Release);
+    }
+}
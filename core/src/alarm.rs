@@ -0,0 +1,179 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::str::FromStr;
+
+use aimcal_ical::{
+    self as ical, Action, ActionValue, AlarmTriggerRelationship, Description, Trigger,
+    TriggerValue, VAlarm, Value, ValueDuration, ValueText, XNameProperty,
+};
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
+
+use crate::datetime::parse_duration;
+
+/// Property marking the reminder at the follow-up of a todo waiting on someone.
+const X_AIM_FOLLOW_UP_ALARM: &str = "X-AIM-FOLLOW-UP-ALARM";
+
+/// When a reminder goes off, relative to the start of an event or the due of a todo.
+///
+/// Parsed either from a lead time such as `30m` or `1 day`, which goes off that long before the
+/// item, or from an RFC 5545 duration such as `-PT15M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlarmTrigger(ValueDuration);
+
+impl AlarmTrigger {
+    /// The trigger as written to the `TRIGGER` property.
+    #[must_use]
+    pub const fn duration(self) -> ValueDuration {
+        self.0
+    }
+
+    /// Builds a `DISPLAY` alarm showing `description`, relative to the end for todos.
+    pub(crate) fn to_display_alarm(
+        self,
+        related: Option<AlarmTriggerRelationship>,
+        description: &str,
+    ) -> VAlarm<String> {
+        VAlarm {
+            action: Action {
+                value: ActionValue::Display,
+                x_parameters: Vec::new(),
+                retained_parameters: Vec::new(),
+                span: (),
+            },
+            trigger: Trigger {
+                value: TriggerValue::Duration(self.0),
+                related,
+                x_parameters: Vec::new(),
+                retained_parameters: Vec::new(),
+                span: (),
+            },
+            repeat: None,
+            duration: None,
+            description: Some(Description::new(description.to_string())),
+            summary: None,
+            attendees: Vec::new(),
+            attach: None,
+            x_properties: Vec::new(),
+            retained_properties: Vec::new(),
+        }
+    }
+}
+
+impl From<ValueDuration> for AlarmTrigger {
+    fn from(duration: ValueDuration) -> Self {
+        Self(duration)
+    }
+}
+
+impl FromStr for AlarmTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+        if t.trim_start_matches(['+', '-']).starts_with('P') {
+            return t.parse().map(Self);
+        }
+
+        let invalid = || format!("Invalid alarm: {s}, expected a duration such as 30m or -PT15M");
+        let (days, seconds) = parse_duration(t).ok_or_else(invalid)?;
+        let hours = seconds / (60 * 60);
+        let minutes = seconds / 60 % 60;
+        Ok(Self(ValueDuration::DateTime {
+            positive: false,
+            day: u32::try_from(days).map_err(|_| invalid())?,
+            hour: u32::try_from(hours).map_err(|_| invalid())?,
+            minute: u32::try_from(minutes).map_err(|_| invalid())?,
+            second: u32::try_from(seconds % 60).map_err(|_| invalid())?,
+        }))
+    }
+}
+
+impl fmt::Display for AlarmTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Replaces the `DISPLAY` alarms of an item, keeping alarms of other actions and follow-up
+/// reminders.
+pub(crate) fn replace_display_alarms(
+    alarms: &mut Vec<VAlarm<String>>,
+    alarm: Option<VAlarm<String>>,
+) {
+    alarms.retain(|a| a.action.value != ActionValue::Display || is_follow_up_alarm(a));
+    alarms.extend(alarm);
+}
+
+/// Builds a `DISPLAY` alarm going off at `at`, reminding to follow up on a waiting todo.
+pub(crate) fn follow_up_alarm(at: Timestamp, description: &str) -> VAlarm<String> {
+    let at = at.to_zoned(TimeZone::UTC).datetime();
+    VAlarm {
+        action: Action {
+            value: ActionValue::Display,
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        },
+        trigger: Trigger {
+            value: TriggerValue::DateTime(ical::DateTime::Utc {
+                date: at.date().into(),
+                time: at.time().into(),
+            }),
+            related: None,
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        },
+        repeat: None,
+        duration: None,
+        description: Some(Description::new(description.to_string())),
+        summary: None,
+        attendees: Vec::new(),
+        attach: None,
+        x_properties: vec![XNameProperty {
+            name: X_AIM_FOLLOW_UP_ALARM.to_string(),
+            parameters: Vec::new(),
+            value: Value::Text {
+                values: vec![ValueText::new("TRUE".to_string())],
+                span: (),
+            },
+            span: (),
+        }],
+        retained_properties: Vec::new(),
+    }
+}
+
+/// Whether the alarm is a follow-up reminder, see [`follow_up_alarm`].
+pub(crate) fn is_follow_up_alarm(alarm: &VAlarm<String>) -> bool {
+    alarm
+        .x_properties
+        .iter()
+        .any(|p| p.name.eq_ignore_ascii_case(X_AIM_FOLLOW_UP_ALARM))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lead_times_as_negative_durations() {
+        for (s, expected) in [
+            ("30m", "-PT30M"),
+            ("90 minutes", "-PT1H30M"),
+            ("1 hour", "-PT1H"),
+            ("1 day", "-P1D"),
+            ("2w", "-P14D"),
+            ("-PT9H", "-PT9H"),
+            ("PT5M", "PT5M"),
+        ] {
+            let trigger: AlarmTrigger = s.parse().unwrap();
+            assert_eq!(trigger.to_string(), expected, "Failed to parse '{s}'");
+        }
+        assert!("tomorrow".parse::<AlarmTrigger>().is_err());
+        assert!("P".parse::<AlarmTrigger>().is_err());
+    }
+}
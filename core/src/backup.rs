@@ -0,0 +1,574 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backup archives of the complete AIM state.
+//!
+//! An archive is a zstd-compressed tarball. Its first entry is `manifest.json`,
+//! which lists every other entry with its size and SHA-256 checksum, so that a
+//! truncated or corrupt archive is rejected before anything is overwritten.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::Config;
+use crate::config::StoreDef;
+use crate::db::{DB_FILE_NAME, Db};
+use crate::error::AimError;
+use crate::file::rename_with_retry;
+
+/// Version of the archive layout written by this build.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DB_ENTRY: &str = "aim.db";
+const CONFIG_ENTRY: &str = "config.toml";
+const CALENDARS_ENTRY: &str = "calendars";
+
+/// Manifest stored as the first entry of a backup archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Version of the archive layout.
+    pub format_version: u32,
+    /// Version of AIM that created the archive.
+    pub aim_version: String,
+    /// Database schema version, i.e. the latest migration applied.
+    pub schema_version: i64,
+    /// Creation time of the archive.
+    pub created_at: Timestamp,
+    /// Local calendars included in the archive.
+    pub calendars: Vec<BackupCalendar>,
+    /// All other entries of the archive.
+    pub files: Vec<BackupFile>,
+}
+
+/// A local calendar directory included in a backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupCalendar {
+    /// Calendar identifier, also its directory name under `calendars/` in the archive.
+    pub id: String,
+    /// Directory the calendar was backed up from.
+    pub path: PathBuf,
+}
+
+/// A file stored in a backup archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path inside the archive.
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Hex-encoded SHA-256 checksum.
+    pub sha256: String,
+}
+
+/// Writes an archive of the database, the config file and all local calendars to `dest`.
+pub(crate) async fn create(
+    db: &Db,
+    config: &Config,
+    dest: &Path,
+    config_file: Option<&Path>,
+) -> Result<BackupManifest, AimError> {
+    let parent = match dest.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+    let staging = parent.join(format!(".aim-backup-{}", Uuid::new_v4()));
+    fs::create_dir(&staging)?;
+
+    let result = create_in(db, config, dest, config_file, &staging).await;
+    remove_path(&staging);
+    result
+}
+
+async fn create_in(
+    db: &Db,
+    config: &Config,
+    dest: &Path,
+    config_file: Option<&Path>,
+    staging: &Path,
+) -> Result<BackupManifest, AimError> {
+    // Gather a consistent copy of everything first, the calendars may change while we archive
+    db.snapshot(&staging.join(DB_ENTRY)).await?;
+    let mut entries = vec![DB_ENTRY.to_string()];
+
+    if let Some(path) = config_file.filter(|p| p.is_file()) {
+        fs::copy(path, staging.join(CONFIG_ENTRY))?;
+        entries.push(CONFIG_ENTRY.to_string());
+    }
+
+    let mut calendars = Vec::new();
+    for (id, dir) in local_calendar_dirs(config) {
+        if !is_plain_name(&id) {
+            return Err(AimError::Config(format!(
+                "Calendar id '{id}' cannot be used as a directory name in a backup"
+            )));
+        }
+
+        let staged = staging.join(CALENDARS_ENTRY).join(&id);
+        fs::create_dir_all(&staged)?;
+        for path in ics_files(&dir)? {
+            let Some(name) = path.file_name().and_then(|a| a.to_str()) else {
+                continue;
+            };
+            fs::copy(&path, staged.join(name))?;
+            entries.push(format!("{CALENDARS_ENTRY}/{id}/{name}"));
+        }
+        calendars.push(BackupCalendar { id, path: dir });
+    }
+
+    let mut files = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let data = fs::read(staging.join(&entry))?;
+        files.push(BackupFile {
+            path: entry,
+            size: data.len() as u64,
+            sha256: sha256_hex(&data),
+        });
+    }
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        aim_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: db.schema_version().await?,
+        created_at: Timestamp::now(),
+        calendars,
+        files,
+    };
+
+    // Write next to the destination and rename, so `dest` is never left half-written
+    let archive = staging.join("archive.tar.zst");
+    write_archive(&archive, &manifest, staging)?;
+    rename_with_retry(&archive, dest)?;
+    tracing::info!(path = %dest.display(), files = manifest.files.len(), "backup created");
+    Ok(manifest)
+}
+
+fn write_archive(path: &Path, manifest: &BackupManifest, staging: &Path) -> io::Result<()> {
+    let mut tar = tar::Builder::new(zstd::Encoder::new(File::create(path)?, 0)?);
+
+    let json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(
+        manifest
+            .created_at
+            .as_second()
+            .try_into()
+            .unwrap_or_default(),
+    );
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_ENTRY, json.as_slice())?;
+
+    for file in &manifest.files {
+        tar.append_path_with_name(staging.join(&file.path), &file.path)?;
+    }
+
+    tar.into_inner()?.finish()?.sync_all()
+}
+
+/// Restores an archive created by [`create`] over the state described by `config`.
+pub(crate) async fn restore(
+    config: &Config,
+    archive: &Path,
+    config_file: Option<&Path>,
+) -> Result<BackupManifest, AimError> {
+    let state_dir = config.state_dir.as_deref().ok_or_else(|| {
+        AimError::Config("Restoring a backup requires a state directory".to_string())
+    })?;
+    fs::create_dir_all(state_dir)?;
+    let staging = state_dir.join(format!(".aim-restore-{}", Uuid::new_v4()));
+    fs::create_dir(&staging)?;
+
+    let result = restore_from(config, archive, config_file, state_dir, &staging).await;
+    remove_path(&staging);
+    result
+}
+
+async fn restore_from(
+    config: &Config,
+    archive: &Path,
+    config_file: Option<&Path>,
+    state_dir: &Path,
+    staging: &Path,
+) -> Result<BackupManifest, AimError> {
+    let manifest = extract(archive, staging)?;
+
+    // Bring an older schema up to date before it goes live
+    let db = Db::open(Some(&staging.join(DB_ENTRY))).await?;
+    db.close().await?;
+
+    let db_path = state_dir.join(DB_FILE_NAME);
+    let mut swaps = vec![(staging.join(DB_ENTRY), db_path.clone())];
+    if let Some(path) = config_file
+        && manifest.files.iter().any(|f| f.path == CONFIG_ENTRY)
+    {
+        swaps.push((staging.join(CONFIG_ENTRY), path.to_owned()));
+    }
+
+    let dirs: HashMap<_, _> = local_calendar_dirs(config).into_iter().collect();
+    for calendar in &manifest.calendars {
+        let target = dirs.get(&calendar.id).unwrap_or(&calendar.path);
+        swaps.push((
+            staging.join(CALENDARS_ENTRY).join(&calendar.id),
+            target.clone(),
+        ));
+    }
+
+    // The write-ahead log of the old database must not be replayed into the restored one
+    let stale = ["-wal", "-shm"].map(|suffix| {
+        let mut name = db_path.clone().into_os_string();
+        name.push(suffix);
+        PathBuf::from(name)
+    });
+
+    swap_all(&swaps, &stale)?;
+    tracing::info!(path = %archive.display(), "backup restored");
+    Ok(manifest)
+}
+
+/// Reads and verifies an archive, unpacking its entries into `staging`.
+fn extract(archive: &Path, staging: &Path) -> Result<BackupManifest, AimError> {
+    let corrupt = |reason: &dyn Display| AimError::Parse {
+        file: archive.to_owned(),
+        errors: vec![reason.to_string()],
+    };
+
+    let decoder = zstd::Decoder::new(File::open(archive)?).map_err(|e| corrupt(&e))?;
+    let mut tar = tar::Archive::new(decoder);
+    let mut entries = tar.entries().map_err(|e| corrupt(&e))?;
+
+    let mut first = entries
+        .next()
+        .ok_or_else(|| corrupt(&"archive is empty"))?
+        .map_err(|e| corrupt(&e))?;
+    if first.path().ok().as_deref() != Some(Path::new(MANIFEST_ENTRY)) {
+        return Err(corrupt(&"missing manifest"));
+    }
+    let manifest: BackupManifest = serde_json::from_reader(&mut first)
+        .map_err(|e| corrupt(&format!("invalid manifest: {e}")))?;
+    check_compatible(&manifest)?;
+
+    let mut expected: HashMap<_, _> = manifest
+        .files
+        .iter()
+        .map(|f| (f.path.as_str(), f))
+        .collect();
+    if !expected.contains_key(DB_ENTRY) {
+        return Err(corrupt(&"missing database"));
+    }
+
+    for entry in entries {
+        let mut entry = entry.map_err(|e| corrupt(&e))?;
+        let path = entry
+            .path()
+            .map_err(|e| corrupt(&e))?
+            .to_string_lossy()
+            .into_owned();
+        let Some(file) = expected.remove(path.as_str()) else {
+            return Err(corrupt(&format!("unexpected entry {path}")));
+        };
+        let Some(relative) = safe_relative(&file.path) else {
+            return Err(corrupt(&format!("unsafe entry path {path}")));
+        };
+
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(|e| corrupt(&e))?;
+        if data.len() as u64 != file.size || sha256_hex(&data) != file.sha256 {
+            return Err(corrupt(&format!("checksum mismatch for {path}")));
+        }
+
+        let dest = staging.join(relative);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(dest, data)?;
+    }
+
+    let mut missing: Vec<_> = expected.into_keys().collect();
+    if !missing.is_empty() {
+        missing.sort_unstable();
+        return Err(corrupt(&format!("missing entries {}", missing.join(", "))));
+    }
+
+    for calendar in &manifest.calendars {
+        fs::create_dir_all(staging.join(CALENDARS_ENTRY).join(&calendar.id))?;
+    }
+    Ok(manifest)
+}
+
+fn check_compatible(manifest: &BackupManifest) -> Result<(), AimError> {
+    if manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(AimError::InvalidInput {
+            field: "backup",
+            reason: format!(
+                "unsupported archive format version {}, expected {BACKUP_FORMAT_VERSION}",
+                manifest.format_version
+            ),
+        });
+    }
+
+    let latest = Db::latest_schema_version();
+    if manifest.schema_version > latest {
+        return Err(AimError::InvalidInput {
+            field: "backup",
+            reason: format!(
+                "archive was created by aim {} with a newer database schema ({} > {latest}), please upgrade",
+                manifest.aim_version, manifest.schema_version
+            ),
+        });
+    }
+
+    if let Some(calendar) = manifest.calendars.iter().find(|c| !is_plain_name(&c.id)) {
+        return Err(AimError::InvalidInput {
+            field: "backup",
+            reason: format!("invalid calendar id '{}'", calendar.id),
+        });
+    }
+    Ok(())
+}
+
+/// Directories of all local calendars, resolved the same way as their stores.
+fn local_calendar_dirs(config: &Config) -> Vec<(String, PathBuf)> {
+    let default_dir = || {
+        config
+            .state_dir
+            .as_ref()
+            .map_or_else(|| PathBuf::from("calendar"), |p| p.join("calendar"))
+    };
+
+    if config.is_legacy_format() {
+        let dir = config.calendar_path.clone().unwrap_or_else(default_dir);
+        return vec![("default".to_string(), dir)];
+    }
+
+    config
+        .calendars
+        .iter()
+        .filter(|c| matches!(config.stores.get(&c.store), Some(StoreDef::Local { .. })))
+        .map(|c| {
+            let dir = c
+                .calendar_path
+                .as_ref()
+                .map_or_else(default_dir, PathBuf::from);
+            (c.id.clone(), dir)
+        })
+        .collect()
+}
+
+fn ics_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "ics") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Moves every staged item over its target, keeping the replaced ones until all moves succeeded.
+///
+/// The `stale` paths are removed along with the replaced items, and restored on rollback.
+fn swap_all(swaps: &[(PathBuf, PathBuf)], stale: &[PathBuf]) -> io::Result<()> {
+    // Stage beside each target first, so the final renames never cross filesystems
+    let mut beside: Vec<(PathBuf, &PathBuf)> = Vec::with_capacity(swaps.len());
+    for (staged, target) in swaps {
+        let next = sibling(target, "restore");
+        if let Err(e) = move_path(staged, &next) {
+            for (next, _) in &beside {
+                remove_path(next);
+            }
+            return Err(e);
+        }
+        beside.push((next, target));
+    }
+
+    let plan = stale
+        .iter()
+        .map(|path| (None, path))
+        .chain(beside.iter().map(|(next, target)| (Some(next), *target)));
+
+    let mut done: Vec<(&PathBuf, Option<PathBuf>)> = Vec::new();
+    let mut result = Ok(());
+    for (next, target) in plan {
+        match swap_one(next.map(PathBuf::as_path), target) {
+            Ok(old) => done.push((target, old)),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    if let Err(e) = result {
+        tracing::warn!(err = %e, "restore failed, rolling back");
+        for (target, old) in done.into_iter().rev() {
+            if let Some(old) = old {
+                remove_path(target);
+                if let Err(e) = rename_with_retry(&old, target) {
+                    tracing::error!(path = %old.display(), err = %e, "failed to roll back");
+                }
+            }
+        }
+        for (next, _) in &beside {
+            remove_path(next);
+        }
+        return Err(e);
+    }
+
+    for (_, old) in done {
+        if let Some(old) = old {
+            remove_path(&old);
+        }
+    }
+    Ok(())
+}
+
+/// Moves `target` aside and `next` into its place, returning where the old item went.
+fn swap_one(next: Option<&Path>, target: &Path) -> io::Result<Option<PathBuf>> {
+    let old = if target.symlink_metadata().is_ok() {
+        let old = sibling(target, "old");
+        rename_with_retry(target, &old)?;
+        Some(old)
+    } else {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        None
+    };
+
+    if let Some(next) = next
+        && let Err(e) = rename_with_retry(next, target)
+    {
+        if let Some(old) = &old {
+            rename_with_retry(old, target)?;
+        }
+        return Err(e);
+    }
+    Ok(old)
+}
+
+fn sibling(path: &Path, tag: &str) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{name}.aim-{tag}-{}", Uuid::new_v4().simple()))
+}
+
+/// Renames `from` to `to`, falling back to a copy when they are on different filesystems.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_path(from, to)?;
+    remove_path(from);
+    Ok(())
+}
+
+fn copy_path(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+fn remove_path(path: &Path) {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match result {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!(path = %path.display(), err = %e, "failed to remove"),
+    }
+}
+
+/// Converts an archive path into a relative path that cannot escape the extraction directory.
+fn safe_relative(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| path.to_owned())
+}
+
+fn is_plain_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_relative_rejects_escaping_paths() {
+        assert_eq!(
+            safe_relative("calendars/work/a.ics"),
+            Some(PathBuf::from("calendars/work/a.ics"))
+        );
+        assert_eq!(safe_relative("../aim.db"), None);
+        assert_eq!(safe_relative("/etc/passwd"), None);
+    }
+
+    #[test]
+    fn is_plain_name_accepts_single_components_only() {
+        assert!(is_plain_name("personal"));
+        assert!(!is_plain_name("work/personal"));
+        assert!(!is_plain_name(".."));
+        assert!(!is_plain_name(""));
+    }
+
+    #[test]
+    fn swap_all_replaces_targets_and_removes_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let staged = dir.path().join("staged");
+        let target = dir.path().join("target");
+        let stale = dir.path().join("target-wal");
+        fs::write(&staged, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+        fs::write(&stale, "wal").unwrap();
+
+        swap_all(
+            &[(staged.clone(), target.clone())],
+            std::slice::from_ref(&stale),
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert!(!staged.exists());
+        assert!(!stale.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1); // no leftovers
+    }
+}
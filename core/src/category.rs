@@ -0,0 +1,206 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Defaults of the todos in a category, from the `[core.categories.<name>]` sections.
+//!
+//! A new todo takes the defaults of its categories for the fields its draft leaves unset, before
+//! the defaults of the config, unless the draft asks for none with
+//! [`TodoDraft::no_defaults`](crate::TodoDraft::no_defaults). Where several of its categories
+//! set the same field, the first of them in the order of the todo wins, so a todo in `work` and
+//! `urgent` takes the calendar of `work` and the priority of `urgent` if only it sets one.
+//! Categories compare ignoring ASCII case, as with the `business_days` config.
+
+use std::collections::BTreeMap;
+
+use crate::{AlarmTrigger, Priority};
+
+/// The category defaults of the config.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(try_from = "BTreeMap<String, CategoryDef>")]
+pub struct CategoryDefaults {
+    categories: Vec<Category>,
+}
+
+impl CategoryDefaults {
+    /// The categories with defaults, by name.
+    #[must_use]
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// The defaults of the category with the given name, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Category> {
+        self.categories
+            .iter()
+            .find(|category| category.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The first value of a field set by one of `categories`, in their order.
+    #[must_use]
+    pub fn first<'a, T>(
+        &'a self,
+        categories: &[String],
+        field: impl Fn(&'a Category) -> Option<T>,
+    ) -> Option<T> {
+        categories
+            .iter()
+            .filter_map(|name| self.get(name))
+            .find_map(field)
+    }
+
+    /// Whether one of `categories` counts relative dues in business days.
+    #[must_use]
+    pub fn business_days(&self, categories: &[String]) -> bool {
+        categories
+            .iter()
+            .filter_map(|name| self.get(name))
+            .any(|category| category.business_days)
+    }
+}
+
+impl TryFrom<BTreeMap<String, CategoryDef>> for CategoryDefaults {
+    type Error = String;
+
+    fn try_from(defs: BTreeMap<String, CategoryDef>) -> Result<Self, Self::Error> {
+        let categories = defs
+            .into_iter()
+            .map(|(name, def)| def.compile(name))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { categories })
+    }
+}
+
+/// The defaults of the todos in a category, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    /// The name of the category, such as `work`.
+    pub name: String,
+    /// The priority of the todos, if set.
+    pub priority: Option<Priority>,
+    /// The reminder before the due, if set. `Some(None)` creates the todos without a reminder.
+    pub alarm: Option<Option<AlarmTrigger>>,
+    /// The name or ID of the calendar the todos are created in, if set.
+    pub calendar: Option<String>,
+    /// Whether relative dues in days count business days, see
+    /// [`Aim::resolve_due`](crate::Aim::resolve_due).
+    pub business_days: bool,
+    /// The color of the todos, written to their `COLOR`, such as `"#1e90ff"`.
+    pub color: Option<String>,
+}
+
+/// A `[core.categories.<name>]` section as written in the config file.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryDef {
+    priority: Option<Priority>,
+    alarm: Option<String>,
+    calendar: Option<String>,
+    #[serde(default)]
+    business_days: bool,
+    color: Option<String>,
+}
+
+impl CategoryDef {
+    fn compile(self, name: String) -> Result<Category, String> {
+        let invalid = |reason: &str| format!("Invalid category '{name}': {reason}");
+        if name.trim().is_empty() {
+            return Err(invalid("names cannot be empty"));
+        }
+        let alarm = match self.alarm.as_deref().map(str::trim) {
+            Some("") => Some(None),
+            Some(alarm) => Some(Some(alarm.parse().map_err(|e: String| invalid(&e))?)),
+            None => None,
+        };
+        if self
+            .calendar
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            return Err(invalid("`calendar` cannot be empty"));
+        }
+        if self.color.as_deref().is_some_and(|c| c.trim().is_empty()) {
+            return Err(invalid("`color` cannot be empty"));
+        }
+        Ok(Category {
+            name,
+            priority: self.priority,
+            alarm,
+            calendar: self.calendar,
+            business_days: self.business_days,
+            color: self.color,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Result<CategoryDefaults, String> {
+        toml::from_str(toml).map_err(|e| e.to_string())
+    }
+
+    const TOML: &str = r##"
+[work]
+priority = "high"
+alarm = "30m"
+calendar = "Work"
+business_days = true
+color = "#1e90ff"
+
+[urgent]
+priority = 1
+alarm = ""
+"##;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_category_defaults() {
+        let defaults = parse(TOML).unwrap();
+        let work = defaults.get("Work").unwrap();
+        assert_eq!(work.priority, Some(Priority::P2));
+        assert_eq!(work.alarm, Some(Some("30m".parse().unwrap())));
+        assert_eq!(work.calendar.as_deref(), Some("Work"));
+        assert!(work.business_days);
+        assert_eq!(work.color.as_deref(), Some("#1e90ff"));
+
+        let urgent = defaults.get("urgent").unwrap();
+        assert_eq!(urgent.alarm, Some(None));
+        assert_eq!(urgent.calendar, None);
+        assert!(defaults.get("home").is_none());
+    }
+
+    #[test]
+    fn first_category_setting_a_field_wins() {
+        let defaults = parse(TOML).unwrap();
+        let both = names(&["urgent", "work"]);
+        assert_eq!(defaults.first(&both, |c| c.priority), Some(Priority::P1));
+        assert_eq!(
+            defaults.first(&both, |c| c.calendar.as_deref()),
+            Some("Work")
+        );
+        let reversed = names(&["work", "urgent"]);
+        assert_eq!(
+            defaults.first(&reversed, |c| c.priority),
+            Some(Priority::P2)
+        );
+        assert_eq!(defaults.first(&names(&["home"]), |c| c.priority), None);
+
+        assert!(defaults.business_days(&both));
+        assert!(!defaults.business_days(&names(&["urgent"])));
+    }
+
+    #[test]
+    fn rejects_invalid_categories() {
+        let err = parse("[work]\nalarm = \"soon\"\n").unwrap_err();
+        assert!(err.contains("Invalid category 'work'"), "{err}");
+        let err = parse("[work]\ncolor = \"\"\n").unwrap_err();
+        assert!(err.contains("`color` cannot be empty"), "{err}");
+        assert!(parse("[work]\nicon = \"x\"\n").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Locale-independent ordering of text, such as summaries and categories.
+
+use std::cmp::Ordering;
+use std::iter;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Name of the sqlite collation registered on every connection for [`Collation::Unicode`].
+pub(crate) const UNICODE_COLLATION: &str = "AIM_UNICODE";
+
+/// How text is compared when sorting or grouping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Collation {
+    /// Case- and accent-insensitive, so `"Ärzte"` sorts next to `"arzt"` rather than after `"Zoo"`.
+    #[default]
+    Unicode,
+    /// Byte-wise, as sqlite compares by default. Faster, but case and accents scatter items.
+    Binary,
+}
+
+impl Collation {
+    /// Compares two strings with this collation.
+    #[must_use]
+    pub fn compare(self, a: &str, b: &str) -> Ordering {
+        match self {
+            Collation::Unicode => unicode_compare(a, b),
+            Collation::Binary => a.cmp(b),
+        }
+    }
+
+    /// Whether two strings are the same with this collation, such as `"Café"` and `"cafe"` with
+    /// [`Collation::Unicode`].
+    #[must_use]
+    pub fn equivalent(self, a: &str, b: &str) -> bool {
+        match self {
+            Collation::Unicode => fold(a).eq(fold(b)),
+            Collation::Binary => a == b,
+        }
+    }
+
+    /// The sqlite collation name, for a `COLLATE` clause.
+    pub(crate) const fn sql_name(self) -> &'static str {
+        match self {
+            Collation::Unicode => UNICODE_COLLATION,
+            Collation::Binary => "BINARY",
+        }
+    }
+}
+
+/// Compares the case-folded strings with their combining marks stripped, then byte-wise to keep
+/// the order total for strings that only differ in case or accents.
+pub(crate) fn unicode_compare(a: &str, b: &str) -> Ordering {
+    fold(a).cmp(fold(b)).then_with(|| a.cmp(b))
+}
+
+fn fold(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .flat_map(char::to_lowercase)
+        .flat_map(|c| match c {
+            // The one full case folding that lowercasing misses in Latin scripts
+            'ß' => iter::repeat_n('s', 2),
+            _ => iter::repeat_n(c, 1),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut words: Vec<&str>) -> Vec<&str> {
+        words.sort_by(|a, b| unicode_compare(a, b));
+        words
+    }
+
+    #[test]
+    fn sorts_german_umlauts_with_their_base_letters() {
+        assert_eq!(
+            sorted(vec!["Zoo", "Ärzte anrufen", "apotheke", "Bäcker"]),
+            ["apotheke", "Ärzte anrufen", "Bäcker", "Zoo"]
+        );
+        assert_eq!(unicode_compare("straße", "STRASSE"), Ordering::Greater);
+        assert_eq!(unicode_compare("Straße", "strasse z"), Ordering::Less);
+    }
+
+    #[test]
+    fn ignores_french_accents_and_case() {
+        assert_eq!(
+            sorted(vec!["être", "Eve", "école", "Zèbre", "Élan"]),
+            ["école", "Élan", "être", "Eve", "Zèbre"]
+        );
+        assert_eq!(unicode_compare("Crème", "creme z"), Ordering::Less);
+    }
+
+    #[test]
+    fn folds_turkish_dotted_capital_i_but_keeps_dotless_i() {
+        // İ decomposes to I with a combining dot above, which is stripped
+        assert_eq!(unicode_compare("İstanbul", "istanbul z"), Ordering::Less);
+        assert_eq!(unicode_compare("İzmir", "Istanbul"), Ordering::Greater);
+        // ı has no decomposition, so it stays apart from i
+        assert_eq!(unicode_compare("ılık", "izmir"), Ordering::Greater);
+        assert_eq!(unicode_compare("ılık", "zeytin"), Ordering::Greater);
+    }
+
+    #[test]
+    fn orders_folded_ties_byte_wise() {
+        assert_eq!(unicode_compare("Eve", "eve"), Ordering::Less);
+        assert_eq!(unicode_compare("eve", "ève"), Ordering::Less);
+        assert_eq!(unicode_compare("same", "same"), Ordering::Equal);
+    }
+
+    #[test]
+    fn unicode_collation_treats_folded_strings_as_equivalent() {
+        assert!(Collation::Unicode.equivalent("Café", "CAFE"));
+        assert!(Collation::Unicode.equivalent("Straße", "strasse"));
+        assert!(!Collation::Unicode.equivalent("ılık", "ilik"));
+        assert!(!Collation::Binary.equivalent("Café", "cafe"));
+    }
+
+    #[test]
+    fn binary_collation_compares_bytes() {
+        assert_eq!(
+            Collation::Binary.compare("Ärzte anrufen", "Zoo"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Collation::Unicode.compare("Ärzte anrufen", "Zoo"),
+            Ordering::Less
+        );
+    }
+}
@@ -9,10 +9,19 @@
 )]
 
 use std::collections::HashMap;
-use std::error::Error;
 use std::path::{Path, PathBuf};
 
-use crate::{DateTimeAnchor, Priority};
+use jiff::SignedDuration;
+use jiff::civil::{Time, Weekday};
+use jiff::tz::TimeZone;
+
+use crate::datetime::{parse_signed_duration, parse_weekday_name};
+use crate::profile::ProfileDirs;
+use crate::{
+    AimError, AlarmTrigger, AllDayPolicy, BusinessDays, CategoryDefaults, Collation,
+    DateTimeAnchor, DedupeMode, EncryptionConfig, HousekeepingConfig, OverdueConfig, Priority,
+    Profile, RecurFrom, Rules, SmartLists, Templates, WorkingHours,
+};
 use aimcal_caldav::AuthMethod;
 
 /// The name of the AIM application.
@@ -26,6 +35,18 @@ fn default_user_agent() -> String {
     "aimcal/0.11.0".to_string()
 }
 
+fn default_pool_max_idle_per_host() -> usize {
+    8
+}
+
+fn default_pool_idle_timeout_secs() -> u64 {
+    90
+}
+
+fn default_max_response_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
 /// Store definition for shared connection configuration.
 ///
 /// Stores define how to connect to a calendar storage. Multiple calendars
@@ -54,6 +75,32 @@ pub enum StoreDef {
         /// User agent string for HTTP requests.
         #[serde(default = "default_user_agent")]
         user_agent: String,
+        /// Maximum number of idle connections kept open to the server for reuse.
+        #[serde(default = "default_pool_max_idle_per_host")]
+        pool_max_idle_per_host: usize,
+        /// Seconds an idle connection is kept open for reuse before it is closed.
+        #[serde(default = "default_pool_idle_timeout_secs")]
+        pool_idle_timeout_secs: u64,
+        /// Maximum size in bytes of a listing or query response from the server.
+        #[serde(default = "default_max_response_bytes")]
+        max_response_bytes: u64,
+    },
+    /// Read-only subscription to a remote ICS feed.
+    #[serde(rename = "subscription")]
+    Subscription {
+        /// URL of the feed, `webcal://` URLs are fetched over HTTPS.
+        url: String,
+        /// Minimum number of seconds between two refreshes of the feed.
+        ///
+        /// Defaults to the `REFRESH-INTERVAL` of the feed, or one day without it.
+        #[serde(default)]
+        refresh_interval_secs: Option<u64>,
+        /// Request timeout in seconds.
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+        /// User agent string for HTTP requests.
+        #[serde(default = "default_user_agent")]
+        user_agent: String,
     },
 }
 
@@ -66,6 +113,9 @@ pub struct CalendarEntry {
     /// Unique calendar identifier.
     pub id: String,
     /// Display name for the calendar.
+    ///
+    /// Empty when not configured, subscriptions then take the name of their feed.
+    #[serde(default)]
     pub name: String,
     /// Reference to a store definition in `stores`.
     pub store: String,
@@ -79,6 +129,9 @@ pub struct CalendarEntry {
     /// Whether the calendar is enabled.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Color shown for the calendar, such as `"#1e90ff"`.
+    #[serde(default)]
+    pub color: Option<String>,
 }
 
 fn default_enabled() -> bool {
@@ -87,6 +140,7 @@ fn default_enabled() -> bool {
 
 /// Configuration for the AIM application.
 #[derive(Debug, Clone, serde::Deserialize)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct Config {
     /// Path to the calendar directory (optional ICS export/import).
     ///
@@ -105,6 +159,57 @@ pub struct Config {
     #[serde(default)]
     pub default_due: Option<DateTimeAnchor>,
 
+    /// Time of day given to due dates entered without a time, e.g. `"17:00"`.
+    ///
+    /// If not set, such todos stay date-only and are due at the end of the day.
+    #[serde(default, deserialize_with = "deserialize_time")]
+    pub default_due_time: Option<Time>,
+
+    /// Reminder for new events with a start time, e.g. `"-PT15M"` or `"15m"` before the start.
+    ///
+    /// Set to `""` to create events without a reminder.
+    #[serde(
+        default = "default_event_alarm",
+        deserialize_with = "deserialize_alarm"
+    )]
+    pub default_event_alarm: Option<AlarmTrigger>,
+
+    /// Duration of new events given neither an end nor a duration, e.g. `"25m"`.
+    #[serde(
+        default = "default_event_duration",
+        deserialize_with = "deserialize_positive_duration"
+    )]
+    pub default_event_duration: SignedDuration,
+
+    /// Estimate of the todos planned without one, e.g. `"45m"`, see
+    /// [`Aim::plan_todos`](crate::Aim::plan_todos).
+    #[serde(
+        default = "default_estimate",
+        deserialize_with = "deserialize_positive_duration"
+    )]
+    pub default_estimate: SignedDuration,
+
+    /// If true, todos without an estimate are left out of the load of the day, instead of counted
+    /// with the `default_estimate`, see [`Aim::day_load`](crate::Aim::day_load).
+    #[serde(default)]
+    pub exclude_unestimated: bool,
+
+    /// Reminder for new all-day events, relative to midnight, e.g. `"-PT9H"` for the evening
+    /// before.
+    ///
+    /// Set to `""` to create all-day events without a reminder.
+    #[serde(
+        default = "default_allday_alarm",
+        deserialize_with = "deserialize_alarm"
+    )]
+    pub default_allday_alarm: Option<AlarmTrigger>,
+
+    /// Reminder for new todos with a due date, e.g. `"-PT1H"` before the due.
+    ///
+    /// Set to `""` to create todos without a reminder.
+    #[serde(default = "default_todo_alarm", deserialize_with = "deserialize_alarm")]
+    pub default_todo_alarm: Option<AlarmTrigger>,
+
     /// Default priority for new tasks.
     #[serde(default)]
     pub default_priority: Priority,
@@ -113,6 +218,97 @@ pub struct Config {
     #[serde(default)]
     pub default_priority_none_fist: bool,
 
+    /// If true, todos scheduled to start later are left out of todo lists unless asked for.
+    #[serde(default)]
+    pub hide_unstarted: bool,
+
+    /// First day of the week shown in calendar views, e.g. `"sunday"`.
+    #[serde(
+        default = "default_week_start",
+        deserialize_with = "deserialize_weekday"
+    )]
+    pub week_start: Weekday,
+
+    /// IANA name of the home time zone, e.g. `"Europe/Lisbon"`, the one of the system if not set.
+    ///
+    /// The home time zone seen is recorded at every start, and a change of it is warned about
+    /// once, since floating times and times in the old zone then read differently, see
+    /// `aim tz audit`.
+    #[serde(default, deserialize_with = "deserialize_time_zone")]
+    pub home_timezone: Option<String>,
+
+    /// Working hours per weekday, and the holidays: the events of a calendar and dates.
+    #[serde(default)]
+    pub working_hours: WorkingHours,
+
+    /// Calendars and categories whose todos count relative dues in days as business days.
+    #[serde(default)]
+    pub business_days: BusinessDays,
+
+    /// When old completed todos and past events are archived, see `aim housekeeping run`.
+    #[serde(default)]
+    pub housekeeping: HousekeepingConfig,
+
+    /// How long a `CalDAV` calendar stays fresh after it was last synced, e.g. `"15m"`. Commands
+    /// that only read leave fresh calendars as cached instead of syncing them at startup, see
+    /// [`AimBuilder::read_only`](crate::AimBuilder::read_only). Zero syncs them every time.
+    #[serde(
+        default = "default_sync_max_age",
+        deserialize_with = "deserialize_sync_max_age"
+    )]
+    pub sync_max_age: SignedDuration,
+
+    /// How overdue todos escalate as they age past their due, see [`OverdueConfig`].
+    #[serde(default)]
+    pub overdue: OverdueConfig,
+
+    /// Where recurring todos advance from once completed, unless they choose with
+    /// `X-AIM-RECUR-FROM`.
+    ///
+    /// `"schedule"` moves them to the occurrence after the one they were due at, `"completion"`
+    /// by the interval of their rule from the day they are completed.
+    #[serde(default)]
+    pub recur_from: RecurFrom,
+
+    /// How summaries and categories are compared when sorting and grouping todos.
+    ///
+    /// `"unicode"` ignores case and accents, `"binary"` compares bytes, which is faster.
+    #[serde(default)]
+    pub collation: Collation,
+
+    /// How copies of an event in several calendars are collapsed in listings.
+    ///
+    /// `"uid"` collapses copies sharing a UID, `"heuristic"` also copies with the same summary,
+    /// start, end and attendees in common, and `"off"` lists every copy.
+    #[serde(default)]
+    pub dedupe_events: DedupeMode,
+
+    /// Whether all-day events block time, such as on the timeline of the working day.
+    ///
+    /// `"ignore"` leaves them out, `"busy"` counts them as taking the whole day.
+    #[serde(default)]
+    pub all_day_events: AllDayPolicy,
+
+    /// Keys for encrypted todo descriptions, see `aim todo new --encrypt-description`.
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+
+    /// Categorization rules applied to items as they are cached, see `aim rules`.
+    #[serde(default)]
+    pub rules: Rules,
+
+    /// Smart lists next to the built-in ones, see `aim list`.
+    #[serde(default)]
+    pub lists: SmartLists,
+
+    /// Todo templates expanding into subtasks, see `aim todo new --from-template`.
+    #[serde(default)]
+    pub templates: Templates,
+
+    /// Defaults of the new todos in a category, see [`CategoryDefaults`].
+    #[serde(default)]
+    pub categories: CategoryDefaults,
+
     /// Parent directory of the config file.
     ///
     /// Set by the CLI layer after parsing. Used by `normalize()` to resolve
@@ -127,6 +323,20 @@ pub struct Config {
     #[serde(skip)]
     pub dev_mode: bool,
 
+    /// Profile the config belongs to, see [`Profile`].
+    ///
+    /// Set by the CLI layer after parsing. Used by `normalize()` to keep the state directory of
+    /// each profile apart when `state_dir` is not set.
+    #[serde(skip)]
+    pub profile: Profile,
+
+    /// Whether a database written by a newer aim is opened for reading, instead of refused.
+    ///
+    /// Set by the CLI layer for commands that only list items. See
+    /// [`Aim::read_only_compat`](crate::Aim::read_only_compat).
+    #[serde(skip)]
+    pub read_only_compat: bool,
+
     /// Store definitions — shared connection configurations keyed by name.
     #[serde(default)]
     pub stores: HashMap<String, StoreDef>,
@@ -154,6 +364,101 @@ fn default_calendar_id() -> String {
     "default".to_string()
 }
 
+fn default_week_start() -> Weekday {
+    Weekday::Monday
+}
+
+fn default_event_duration() -> SignedDuration {
+    SignedDuration::from_hours(1)
+}
+
+fn default_estimate() -> SignedDuration {
+    SignedDuration::from_hours(1)
+}
+
+fn default_sync_max_age() -> SignedDuration {
+    SignedDuration::from_mins(5)
+}
+
+fn default_event_alarm() -> Option<AlarmTrigger> {
+    "-PT15M".parse().ok()
+}
+
+fn default_allday_alarm() -> Option<AlarmTrigger> {
+    "-PT9H".parse().ok()
+}
+
+fn default_todo_alarm() -> Option<AlarmTrigger> {
+    "-PT1H".parse().ok()
+}
+
+fn deserialize_alarm<'de, D>(deserializer: D) -> Result<Option<AlarmTrigger>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    if s.trim().is_empty() {
+        return Ok(None);
+    }
+    s.parse().map(Some).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_positive_duration<'de, D>(deserializer: D) -> Result<SignedDuration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    let duration = parse_signed_duration(&s).map_err(serde::de::Error::custom)?;
+    if !duration.is_positive() {
+        return Err(serde::de::Error::custom(format!(
+            "Invalid duration '{s}', expected a positive duration"
+        )));
+    }
+    Ok(duration)
+}
+
+fn deserialize_sync_max_age<'de, D>(deserializer: D) -> Result<SignedDuration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    let duration = parse_signed_duration(&s).map_err(serde::de::Error::custom)?;
+    if duration.is_negative() {
+        return Err(serde::de::Error::custom(format!(
+            "Invalid duration '{s}', expected zero or a positive duration"
+        )));
+    }
+    Ok(duration)
+}
+
+fn deserialize_time<'de, D>(deserializer: D) -> Result<Option<Time>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    Time::strptime("%H:%M", &s)
+        .map(Some)
+        .map_err(|e| serde::de::Error::custom(format!("Invalid time '{s}': {e}")))
+}
+
+fn deserialize_time_zone<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    TimeZone::get(&s)
+        .map(|_| Some(s.clone()))
+        .map_err(|e| serde::de::Error::custom(format!("Invalid time zone '{s}': {e}")))
+}
+
+fn deserialize_weekday<'de, D>(deserializer: D) -> Result<Weekday, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    parse_weekday_name(&s).ok_or_else(|| serde::de::Error::custom(format!("Invalid weekday: {s}")))
+}
+
 impl Config {
     /// Expand `${ENV:VAR_NAME}` references in all string fields.
     ///
@@ -163,7 +468,7 @@ impl Config {
     ///
     /// # Errors
     /// Returns an error if a referenced variable is not found.
-    pub fn expand_env_vars(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn expand_env_vars(&mut self) -> Result<(), AimError> {
         let secrets = load_secrets_files(&self.secrets_files, self.config_dir.as_deref())?;
 
         for store_def in self.stores.values_mut() {
@@ -194,9 +499,23 @@ impl Config {
                         }
                     }
                 }
+                StoreDef::Subscription {
+                    url, user_agent, ..
+                } => {
+                    *url = expand_env_var(url, &secrets)?;
+                    *user_agent = expand_env_var(user_agent, &secrets)?;
+                }
             }
         }
 
+        let encryption = &mut self.encryption;
+        if let Some(key) = encryption.key.take() {
+            encryption.key = Some(expand_env_var(&key, &secrets)?);
+        }
+        for key in &mut encryption.previous_keys {
+            *key = expand_env_var(key, &secrets)?;
+        }
+
         for calendar in &mut self.calendars {
             if let Some(ref href) = calendar.calendar_href {
                 calendar.calendar_href = Some(expand_env_var(href, &secrets)?);
@@ -214,7 +533,7 @@ impl Config {
     /// # Errors
     /// If path normalization fails.
     #[tracing::instrument(skip(self))]
-    pub fn normalize(&mut self) -> Result<(), Box<dyn Error>> {
+    pub fn normalize(&mut self) -> Result<(), AimError> {
         let config_parent = self.config_dir.as_deref();
 
         // Normalize calendar path if set (legacy format)
@@ -224,21 +543,31 @@ impl Config {
 
         // Normalize state directory
         if let Some(a) = &self.state_dir {
-            let state_dir = expand_path(a, config_parent)
-                .map_err(|e| format!("Failed to expand state directory path: {e}"))?;
+            let state_dir = expand_path(a, config_parent)?;
             self.state_dir = Some(state_dir);
         } else {
             if self.dev_mode {
-                return Err(
-                    "Development mode requires state_dir to be explicitly configured".into(),
-                );
+                return Err(AimError::Config(
+                    "Development mode requires state_dir to be explicitly configured".to_string(),
+                ));
+            }
+            match ProfileDirs::from_env() {
+                Ok(dirs) => self.state_dir = Some(dirs.state_dir(&self.profile)),
+                Err(err) => tracing::warn!(%err, "failed to get state directory"),
             }
-            match get_state_dir() {
-                Ok(a) => self.state_dir = Some(a.join(APP_NAME)),
-                Err(err) => tracing::warn!(err, "failed to get state directory"),
+        }
+
+        // Subscriptions are fetched over HTTP(S), so rewrite webcal URLs
+        for store_def in self.stores.values_mut() {
+            if let StoreDef::Subscription { url, .. } = store_def {
+                *url = normalize_subscription_url(url);
             }
         }
 
+        // Read the templates kept in .ics files, next to the config unless absolute
+        self.templates
+            .load_files(|path| expand_path(path, config_parent))?;
+
         // Normalize calendar paths for multi-calendar configuration
         for i in 0..self.calendars.len() {
             let calendar = self.calendars.get(i).unwrap();
@@ -248,10 +577,7 @@ impl Config {
             let calendar_path =
                 if matches!(store_def, Some(StoreDef::Local { .. })) || calendar.store == "local" {
                     if let Some(ref path) = calendar.calendar_path {
-                        let p = expand_path(&PathBuf::from(path), None)
-                            .map_err(|e| {
-                                format!("Failed to expand calendar path for {}: {e}", calendar.id)
-                            })?
+                        let p = expand_path(&PathBuf::from(path), None)?
                             .to_string_lossy()
                             .to_string();
                         Some(p)
@@ -277,6 +603,7 @@ impl Config {
                 calendar_path,
                 priority: calendar.priority,
                 enabled: calendar.enabled,
+                color: calendar.color.clone(),
             };
         }
 
@@ -337,6 +664,16 @@ impl Config {
         calendars
     }
 
+    /// The ID of the calendar with the given name or ID, if it is configured or the default one.
+    #[must_use]
+    pub fn find_calendar_id(&self, name: &str) -> Option<&str> {
+        self.calendars
+            .iter()
+            .find(|c| c.name == name || c.id == name)
+            .map(|c| c.id.as_str())
+            .or_else(|| (name == self.default_calendar).then_some(self.default_calendar.as_str()))
+    }
+
     /// Resolve the store definition for a calendar entry.
     ///
     /// Returns `None` if the calendar is not found or the store reference is invalid.
@@ -347,6 +684,14 @@ impl Config {
         Some((entry, store))
     }
 
+    /// Working hours, without the holidays of the holiday calendar.
+    ///
+    /// Use [`Aim::working_hours`](crate::Aim::working_hours) to include them.
+    #[must_use]
+    pub fn working_hours(&self) -> &WorkingHours {
+        &self.working_hours
+    }
+
     /// Get a specific calendar entry by ID.
     #[must_use]
     pub fn get_calendar(&self, id: &str) -> Option<&CalendarEntry> {
@@ -358,12 +703,14 @@ impl Config {
 ///
 /// Relative paths that don't match any special prefix are resolved against
 /// `config_parent` when provided, or returned as-is otherwise.
-fn expand_path(path: &Path, config_parent: Option<&Path>) -> Result<PathBuf, Box<dyn Error>> {
+fn expand_path(path: &Path, config_parent: Option<&Path>) -> Result<PathBuf, AimError> {
     if path.is_absolute() {
         return Ok(path.to_owned());
     }
 
-    let path = path.to_str().ok_or("Invalid path")?;
+    let path = path
+        .to_str()
+        .ok_or_else(|| AimError::Config(format!("Invalid path: {}", path.display())))?;
 
     // Handle tilde and home directory
     let home_prefixes: &[&str] = if cfg!(unix) {
@@ -397,26 +744,39 @@ fn expand_path(path: &Path, config_parent: Option<&Path>) -> Result<PathBuf, Box
     }
 }
 
-fn get_home_dir() -> Result<PathBuf, Box<dyn Error>> {
-    dirs::home_dir().ok_or_else(|| "User-specific home directory not found".into())
+/// Rewrite `webcal://` and `webcals://` URLs to `https://`.
+fn normalize_subscription_url(url: &str) -> String {
+    for scheme in ["webcals://", "webcal://"] {
+        if let Some(prefix) = url.get(..scheme.len())
+            && prefix.eq_ignore_ascii_case(scheme)
+        {
+            return format!("https://{}", &url[scheme.len()..]);
+        }
+    }
+    url.to_string()
 }
 
-fn get_config_dir() -> Result<PathBuf, Box<dyn Error>> {
+fn get_home_dir() -> Result<PathBuf, AimError> {
+    dirs::home_dir()
+        .ok_or_else(|| AimError::Config("User-specific home directory not found".to_string()))
+}
+
+pub(crate) fn get_config_dir() -> Result<PathBuf, AimError> {
     #[cfg(unix)]
     let config_dir = xdg::BaseDirectories::new().get_config_home();
     #[cfg(windows)]
     let config_dir = dirs::config_dir();
 
-    config_dir.ok_or_else(|| "User-specific home directory not found".into())
+    config_dir.ok_or_else(|| AimError::Config("User-specific home directory not found".to_string()))
 }
 
-fn get_state_dir() -> Result<PathBuf, Box<dyn Error>> {
+pub(crate) fn get_state_dir() -> Result<PathBuf, AimError> {
     #[cfg(unix)]
     let state_dir = xdg::BaseDirectories::new().get_state_home();
     #[cfg(windows)]
     let state_dir = dirs::data_dir();
 
-    state_dir.ok_or_else(|| "User-specific state directory not found".into())
+    state_dir.ok_or_else(|| AimError::Config("User-specific state directory not found".to_string()))
 }
 
 /// Expand `${ENV:VAR_NAME}` references in a string.
@@ -425,10 +785,7 @@ fn get_state_dir() -> Result<PathBuf, Box<dyn Error>> {
 ///
 /// # Errors
 /// Returns an error if a referenced variable is not found in either source.
-fn expand_env_var(
-    input: &str,
-    secrets: &HashMap<String, String>,
-) -> Result<String, Box<dyn Error>> {
+fn expand_env_var(input: &str, secrets: &HashMap<String, String>) -> Result<String, AimError> {
     use regex::Regex;
 
     static RE: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
@@ -446,7 +803,9 @@ fn expand_env_var(
             .cloned()
             .or_else(|| std::env::var(var_name).ok())
             .ok_or_else(|| {
-                format!("Variable '{var_name}' not found (not in secrets files or environment)")
+                AimError::Config(format!(
+                    "Variable '{var_name}' not found (not in secrets files or environment)"
+                ))
             })?;
 
         result.push_str(&input[last_end..m.start()]);
@@ -465,7 +824,7 @@ fn parse_secrets_content(
     contents: &str,
     path: &Path,
     secrets: &mut HashMap<String, String>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), AimError> {
     for (line_num, line) in contents.lines().enumerate() {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') {
@@ -475,22 +834,20 @@ fn parse_secrets_content(
             let key = key.trim().to_string();
             let value = value.trim().to_string();
             if key.is_empty() {
-                return Err(format!(
+                return Err(AimError::Config(format!(
                     "Empty key in secrets file '{}' at line {}",
                     path.display(),
                     line_num + 1
-                )
-                .into());
+                )));
             }
             secrets.insert(key, value);
         } else {
-            return Err(format!(
+            return Err(AimError::Config(format!(
                 "Invalid line in secrets file '{}' at line {}: expected KEY=VALUE, got '{}'",
                 path.display(),
                 line_num + 1,
                 trimmed
-            )
-            .into());
+            )));
         }
     }
     Ok(())
@@ -503,7 +860,7 @@ fn parse_secrets_content(
 fn load_secrets_files(
     secrets_files: &[String],
     config_dir: Option<&Path>,
-) -> Result<HashMap<String, String>, Box<dyn Error>> {
+) -> Result<HashMap<String, String>, AimError> {
     let mut secrets = HashMap::new();
     for file_path_str in secrets_files {
         let path = if Path::new(file_path_str).is_absolute() {
@@ -518,8 +875,12 @@ fn load_secrets_files(
             tracing::warn!(path = %path.display(), "secrets file not found, skipping");
             continue;
         }
-        let contents = std::fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to read secrets file '{}': {e}", path.display()))?;
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            AimError::Config(format!(
+                "Failed to read secrets file '{}': {e}",
+                path.display()
+            ))
+        })?;
         parse_secrets_content(&contents, &path, &mut secrets)?;
     }
     Ok(secrets)
@@ -530,6 +891,7 @@ mod tests {
     use std::str::FromStr;
 
     use super::*;
+    use crate::RetentionAge;
 
     #[test]
     fn parses_full_toml_config() {
@@ -537,16 +899,23 @@ mod tests {
 calendar_path = "calendar"
 state_dir = "state"
 default_due = "1d"
+default_due_time = "17:00"
 default_priority = "high"
 default_priority_none_fist = true
+week_start = "Sun"
 "#;
 
         let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
         assert_eq!(config.calendar_path, Some(PathBuf::from("calendar")));
         assert_eq!(config.state_dir, Some(PathBuf::from("state")));
         assert_eq!(config.default_due, Some(DateTimeAnchor::InDays(1)));
+        assert_eq!(
+            config.default_due_time,
+            Some(jiff::civil::time(17, 0, 0, 0))
+        );
         assert_eq!(config.default_priority, Priority::P2);
         assert!(config.default_priority_none_fist);
+        assert_eq!(config.week_start, Weekday::Sunday);
     }
 
     #[test]
@@ -559,8 +928,127 @@ default_priority_none_fist = true
         assert_eq!(config.calendar_path, None);
         assert_eq!(config.state_dir, None);
         assert_eq!(config.default_due, None);
+        assert_eq!(config.default_due_time, None);
         assert_eq!(config.default_priority, Priority::None);
         assert!(!config.default_priority_none_fist);
+        assert_eq!(config.week_start, Weekday::Monday);
+        assert!(!config.housekeeping.is_enabled());
+    }
+
+    #[test]
+    fn parses_housekeeping_section() {
+        const TOML: &str = r#"
+[housekeeping]
+archive_completed_after = "90d"
+archive_events_after = "1y"
+"#;
+
+        let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
+        let policy = config.housekeeping;
+        assert_eq!(
+            policy.archive_completed_after,
+            Some(RetentionAge::from_days(90))
+        );
+        assert_eq!(
+            policy.archive_events_after,
+            Some(RetentionAge::from_days(365))
+        );
+
+        let invalid = "[housekeeping]\narchive_events_after = \"1 year\"\n";
+        assert!(toml::from_str::<Config>(invalid).is_err());
+    }
+
+    #[test]
+    fn parses_rules_section() {
+        const TOML: &str = r#"
+[[rules]]
+name = "work"
+match = { organizer_domain = "@Example.com", calendar = "work" }
+actions = { categories = ["Work"], priority = 3 }
+continue = true
+
+[[rules]]
+name = "gym"
+match = { summary = "(?i)^gym" }
+actions = { transparent = true }
+"#;
+
+        let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
+        let rules = config.rules.rules();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].name, "work");
+        assert_eq!(
+            rules[0].conditions.organizer_domain.as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(rules[0].actions.priority, Some(Priority::P3));
+        assert!(rules[0].continues);
+        assert!(rules[1].actions.transparent);
+        assert!(!rules[1].continues);
+
+        let invalid = "[[rules]]\nname = \"broken\"\nmatch = { summary = \"(unclosed\" }\n";
+        let err = toml::from_str::<Config>(invalid).unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid rule 'broken': bad summary regex"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parses_lists_section() {
+        const TOML: &str = r#"
+[lists.work]
+icon = "💼"
+categories = ["Work"]
+
+[lists.today]
+due = "tomorrow"
+"#;
+
+        let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
+        let names: Vec<_> = config
+            .lists
+            .lists()
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(names, ["today", "upcoming", "someday", "next", "work"]);
+
+        let invalid = "[lists.broken]\nkind = \"events\"\nundated = true\n";
+        let err = toml::from_str::<Config>(invalid).unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid list 'broken': `undated` only applies to todo lists"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parses_home_timezone() {
+        let config: Config = toml::from_str("home_timezone = \"Europe/Lisbon\"\n").unwrap();
+        assert_eq!(config.home_timezone.as_deref(), Some("Europe/Lisbon"));
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.home_timezone, None);
+
+        let err = toml::from_str::<Config>("home_timezone = \"Europe/Nowhere\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid time zone 'Europe/Nowhere'"), "{err}");
+    }
+
+    #[test]
+    fn parses_business_days() {
+        const TOML: &str = r#"
+[business_days]
+calendars = ["Work"]
+categories = ["work"]
+"#;
+        let config: Config = toml::from_str(TOML).unwrap();
+        assert_eq!(config.business_days.calendars, ["Work"]);
+        assert_eq!(config.business_days.categories, ["work"]);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.business_days, BusinessDays::default());
     }
 
     #[test]
@@ -706,6 +1194,84 @@ calendar_href = "/dav/calendars/user/work/"
         ));
     }
 
+    #[test]
+    fn parses_caldav_store_pool_options() {
+        const TOML: &str = r#"
+[stores.radicale]
+type = "caldav"
+base_url = "https://caldav.example.com"
+calendar_home = "/dav/calendars/user/"
+auth = { type = "none" }
+pool_max_idle_per_host = 2
+
+[[calendars]]
+id = "work"
+name = "Work"
+store = "radicale"
+calendar_href = "/dav/calendars/user/work/"
+"#;
+
+        let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
+        match config.stores.get("radicale") {
+            Some(StoreDef::Caldav {
+                pool_max_idle_per_host,
+                pool_idle_timeout_secs,
+                max_response_bytes,
+                ..
+            }) => {
+                assert_eq!(*pool_max_idle_per_host, 2);
+                assert_eq!(*pool_idle_timeout_secs, 90);
+                assert_eq!(*max_response_bytes, 64 * 1024 * 1024);
+            }
+            other => panic!("Expected caldav store, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_subscription_store_with_defaults() {
+        const TOML: &str = r#"
+[stores.holidays]
+type = "subscription"
+url = "webcal://example.com/holidays.ics"
+
+[[calendars]]
+id = "holidays"
+name = "Holidays"
+store = "holidays"
+"#;
+
+        let config: Config = toml::from_str(TOML).expect("Failed to parse TOML");
+        match config.stores.get("holidays") {
+            Some(StoreDef::Subscription {
+                url,
+                refresh_interval_secs,
+                timeout_secs,
+                ..
+            }) => {
+                assert_eq!(url, "webcal://example.com/holidays.ics");
+                assert_eq!(*refresh_interval_secs, None);
+                assert_eq!(*timeout_secs, 30);
+            }
+            other => panic!("Expected subscription store, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn normalize_rewrites_webcal_subscription_urls() {
+        assert_eq!(
+            normalize_subscription_url("webcal://example.com/a.ics"),
+            "https://example.com/a.ics"
+        );
+        assert_eq!(
+            normalize_subscription_url("WEBCALS://example.com/a.ics"),
+            "https://example.com/a.ics"
+        );
+        assert_eq!(
+            normalize_subscription_url("http://example.com/a.ics"),
+            "http://example.com/a.ics"
+        );
+    }
+
     #[test]
     fn is_legacy_format_detects_legacy_config() {
         const TOML: &str = r#"
@@ -875,6 +1441,76 @@ priority = 2
         }
     }
 
+    #[test]
+    fn parses_default_alarms() {
+        let config: Config = toml::from_str("").unwrap();
+        let alarm = |a: Option<AlarmTrigger>| a.map(|a| a.to_string());
+        assert_eq!(
+            alarm(config.default_event_alarm),
+            Some("-PT15M".to_string())
+        );
+        assert_eq!(
+            alarm(config.default_allday_alarm),
+            Some("-PT9H".to_string())
+        );
+        assert_eq!(alarm(config.default_todo_alarm), Some("-PT1H".to_string()));
+
+        let config: Config = toml::from_str(
+            r#"
+default_event_alarm = "30m"
+default_todo_alarm = ""
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            alarm(config.default_event_alarm),
+            Some("-PT30M".to_string())
+        );
+        assert_eq!(alarm(config.default_todo_alarm), None);
+
+        let result: Result<Config, _> = toml::from_str(r#"default_event_alarm = "soon""#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_default_event_duration() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.default_event_duration, SignedDuration::from_hours(1));
+
+        let config: Config = toml::from_str(r#"default_event_duration = "25m""#).unwrap();
+        assert_eq!(config.default_event_duration, SignedDuration::from_mins(25));
+
+        for value in ["0m", "-30m", "soon"] {
+            let result: Result<Config, _> =
+                toml::from_str(&format!(r#"default_event_duration = "{value}""#));
+            assert!(result.is_err(), "{value} should be rejected");
+        }
+    }
+
+    #[test]
+    fn parses_default_estimate() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.default_estimate, SignedDuration::from_hours(1));
+
+        let config: Config = toml::from_str(r#"default_estimate = "1h 30m""#).unwrap();
+        assert_eq!(config.default_estimate, SignedDuration::from_mins(90));
+
+        let result: Result<Config, _> = toml::from_str(r#"default_estimate = "0m""#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_recur_from() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.recur_from, RecurFrom::Schedule);
+
+        let config: Config = toml::from_str(r#"recur_from = "completion""#).unwrap();
+        assert_eq!(config.recur_from, RecurFrom::Completion);
+
+        let result: Result<Config, _> = toml::from_str(r#"recur_from = "due""#);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn expand_env_var_no_placeholders() {
         let secrets = HashMap::new();
@@ -1037,7 +1673,7 @@ calendar_href = "/dav/${ENV:USER}/work/"
                     _ => panic!("Expected Basic auth"),
                 }
             }
-            StoreDef::Local { .. } => panic!("Expected Caldav store"),
+            _ => panic!("Expected Caldav store"),
         }
 
         // Calendar href
@@ -0,0 +1,425 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Field-level encryption of todo descriptions, keeping them from the operators of the servers
+//! the todos are synced to.
+//!
+//! A sealed description is kept in the `X-AIM-ENCRYPTED-DESC` property, as the base64 of a random
+//! nonce followed by the XChaCha20-Poly1305 ciphertext bound to the UID of the todo, and the
+//! `DESCRIPTION` property is left out. Local attackers are out of scope: the cache and the config
+//! holding the key are not protected.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::num::NonZeroU32;
+
+use aimcal_ical::{StringStorage, Value, ValueText, XNameProperty};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use jiff::{SignedDuration, Zoned};
+
+use crate::{LooseDateTime, Priority, Todo, TodoStatus};
+
+/// Property holding the sealed description of a todo.
+pub(crate) const X_AIM_ENCRYPTED_DESC: &str = "X-AIM-ENCRYPTED-DESC";
+
+/// Length of the random nonce prefixed to the ciphertext.
+const NONCE_LEN: usize = 24;
+
+/// Shown in place of an encrypted description that none of the configured keys opens.
+pub const ENCRYPTED_NO_KEY: &str = "[encrypted — no key]";
+
+/// Keys for encrypted descriptions, from the `[core.encryption]` section.
+///
+/// Keys are 32 random bytes in base64, such as made by `aim crypt generate-key`, and are best
+/// kept out of the config with `${ENV:VAR_NAME}` references.
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    /// The key sealing descriptions.
+    pub key: Option<String>,
+
+    /// Keys replaced by `key`, still opening descriptions until they are rekeyed.
+    pub previous_keys: Vec<String>,
+}
+
+impl fmt::Debug for EncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionConfig")
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field("previous_keys", &self.previous_keys.len())
+            .finish()
+    }
+}
+
+/// Generates a new random key, in base64.
+#[must_use]
+pub fn generate_key() -> String {
+    BASE64.encode(XChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+/// Outcome of rekeying the encrypted descriptions, see [`crate::Aim::rekey_descriptions`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RekeyReport {
+    /// Number of todos sealed again with the current key.
+    pub rekeyed: usize,
+    /// Number of todos already sealed with the current key.
+    pub up_to_date: usize,
+    /// UIDs of the todos left as they were, with the reason.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// The keys of the config, decoded.
+#[derive(Clone, Default)]
+pub(crate) struct Keyring {
+    current: Option<Key>,
+    previous: Vec<Key>,
+}
+
+impl Keyring {
+    /// Decodes the keys of the config.
+    ///
+    /// # Errors
+    /// If a key is not 32 bytes in base64.
+    pub fn from_config(config: &EncryptionConfig) -> Result<Self, String> {
+        let current = config.key.as_deref().map(decode_key).transpose()?;
+        let previous = config
+            .previous_keys
+            .iter()
+            .map(|k| decode_key(k))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { current, previous })
+    }
+
+    /// Seals the description of the todo with the current key.
+    ///
+    /// # Errors
+    /// If no key is configured.
+    pub fn seal(&self, uid: &str, description: &str) -> Result<String, String> {
+        let key = self.current.as_ref().ok_or(
+            "no encryption key is configured, set `key` in the `[core.encryption]` section",
+        )?;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: description.as_bytes(),
+            aad: uid.as_bytes(),
+        };
+        let ciphertext = XChaCha20Poly1305::new(key)
+            .encrypt(&nonce, payload)
+            .map_err(|e| format!("failed to encrypt the description: {e}"))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(BASE64.encode(sealed))
+    }
+
+    /// Opens a sealed description of the todo with the first key that fits, or `None` if none
+    /// does.
+    pub fn open(&self, uid: &str, sealed: &str) -> Option<Opened> {
+        let bytes = BASE64.decode(sealed.trim()).ok()?;
+        let (nonce, ciphertext) = bytes.split_at_checked(NONCE_LEN)?;
+        let nonce = XNonce::from_slice(nonce);
+        let keys = self.current.iter().map(|k| (k, true));
+        let keys = keys.chain(self.previous.iter().map(|k| (k, false)));
+        keys.into_iter().find_map(|(key, current)| {
+            let payload = Payload {
+                msg: ciphertext,
+                aad: uid.as_bytes(),
+            };
+            let plain = XChaCha20Poly1305::new(key).decrypt(nonce, payload).ok()?;
+            let description = String::from_utf8(plain).ok()?;
+            Some(Opened {
+                description,
+                current,
+            })
+        })
+    }
+
+    /// Whether a key seals descriptions.
+    pub fn can_seal(&self) -> bool {
+        self.current.is_some()
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field("current", &self.current.is_some())
+            .field("previous", &self.previous.len())
+            .finish()
+    }
+}
+
+/// A description opened by a [`Keyring`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Opened {
+    /// The description in plain text.
+    pub description: String,
+    /// Whether it was sealed with the current key rather than a previous one.
+    pub current: bool,
+}
+
+fn decode_key(key: &str) -> Result<Key, String> {
+    let bytes = BASE64
+        .decode(key.trim())
+        .map_err(|e| format!("encryption key is not valid base64: {e}"))?;
+    if bytes.len() == 32 {
+        Ok(*Key::from_slice(&bytes))
+    } else {
+        Err(format!(
+            "encryption key must be 32 bytes, got {}",
+            bytes.len()
+        ))
+    }
+}
+
+/// Reads the sealed description of a component.
+pub(crate) fn read_sealed<S: StringStorage>(props: &[XNameProperty<S>]) -> Option<String> {
+    props
+        .iter()
+        .find(|p| {
+            p.name
+                .as_str_lossy()
+                .eq_ignore_ascii_case(X_AIM_ENCRYPTED_DESC)
+        })
+        .and_then(|p| match &p.value {
+            Value::Text { values, .. } => values.first().map(ToString::to_string),
+            Value::XName { raw, .. } | Value::Unrecognized { raw, .. } => {
+                Some(raw.as_str_lossy().into_owned())
+            }
+            _ => None,
+        })
+}
+
+/// Replaces the sealed description of a component, or removes it if `None`.
+pub(crate) fn write_sealed(props: &mut Vec<XNameProperty<String>>, sealed: Option<&str>) {
+    props.retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_ENCRYPTED_DESC));
+    if let Some(sealed) = sealed {
+        props.push(XNameProperty {
+            name: X_AIM_ENCRYPTED_DESC.to_string(),
+            parameters: Vec::new(),
+            value: Value::Text {
+                values: vec![ValueText::new(sealed.to_string())],
+                span: (),
+            },
+            span: (),
+        });
+    }
+}
+
+/// A todo whose encrypted description is opened for display, or replaced by
+/// [`ENCRYPTED_NO_KEY`] when no key opens it.
+#[derive(Debug, Clone)]
+pub(crate) struct Decrypted<T: Todo> {
+    inner: T,
+    description: Option<String>,
+}
+
+impl<T: Todo> Decrypted<T> {
+    pub fn new(inner: T, keyring: &Keyring) -> Self {
+        let description = inner.encrypted_description().map(|sealed| {
+            keyring
+                .open(&inner.uid(), &sealed)
+                .map_or_else(|| ENCRYPTED_NO_KEY.to_string(), |o| o.description)
+        });
+        Self { inner, description }
+    }
+}
+
+impl<T: Todo> Todo for Decrypted<T> {
+    fn short_id(&self) -> Option<NonZeroU32> {
+        self.inner.short_id()
+    }
+
+    fn uid(&self) -> Cow<'_, str> {
+        self.inner.uid()
+    }
+
+    fn completed(&self) -> Option<Zoned> {
+        self.inner.completed()
+    }
+
+    fn description(&self) -> Option<Cow<'_, str>> {
+        match &self.description {
+            Some(description) => Some(description.as_str().into()),
+            None => self.inner.description(),
+        }
+    }
+
+    fn start(&self) -> Option<LooseDateTime> {
+        self.inner.start()
+    }
+
+    fn due(&self) -> Option<LooseDateTime> {
+        self.inner.due()
+    }
+
+    fn percent_complete(&self) -> Option<u8> {
+        self.inner.percent_complete()
+    }
+
+    fn priority(&self) -> Priority {
+        self.inner.priority()
+    }
+
+    fn status(&self) -> TodoStatus {
+        self.inner.status()
+    }
+
+    fn summary(&self) -> Cow<'_, str> {
+        self.inner.summary()
+    }
+
+    fn snoozed_from(&self) -> Vec<LooseDateTime> {
+        self.inner.snoozed_from()
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.inner.location()
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.inner.organizer()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.inner.url()
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.inner.categories()
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.inner.color()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.inner.meta()
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.inner.parent_uid()
+    }
+
+    fn encrypted_description(&self) -> Option<Cow<'_, str>> {
+        self.inner.encrypted_description()
+    }
+
+    fn in_inbox(&self) -> bool {
+        self.inner.in_inbox()
+    }
+
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        self.inner.waiting_on()
+    }
+
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        self.inner.follow_up()
+    }
+
+    fn estimate(&self) -> Option<SignedDuration> {
+        self.inner.estimate()
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        self.inner.calendar_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring(key: Option<&str>, previous: &[&str]) -> Keyring {
+        let config = EncryptionConfig {
+            key: key.map(ToString::to_string),
+            previous_keys: previous.iter().map(ToString::to_string).collect(),
+        };
+        Keyring::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn seals_and_opens_descriptions() {
+        let key = generate_key();
+        let keyring = keyring(Some(&key), &[]);
+        let sealed = keyring.seal("todo-1", "door code 1234").unwrap();
+        assert!(!sealed.contains("1234"));
+        assert_ne!(sealed, keyring.seal("todo-1", "door code 1234").unwrap());
+
+        let opened = keyring.open("todo-1", &sealed).unwrap();
+        assert_eq!(opened.description, "door code 1234");
+        assert!(opened.current);
+    }
+
+    #[test]
+    fn opening_fails_for_other_todos_and_keys() {
+        let sealed = keyring(Some(&generate_key()), &[])
+            .seal("todo-1", "secret")
+            .unwrap();
+        let other = keyring(Some(&generate_key()), &[]);
+        assert_eq!(other.open("todo-1", &sealed), None);
+        assert_eq!(keyring(None, &[]).open("todo-1", &sealed), None);
+        assert_eq!(other.open("todo-1", "not base64!"), None);
+
+        let key = generate_key();
+        let keyring = keyring(Some(&key), &[]);
+        let sealed = keyring.seal("todo-1", "secret").unwrap();
+        assert_eq!(keyring.open("todo-2", &sealed), None);
+    }
+
+    #[test]
+    fn previous_keys_open_but_do_not_seal() {
+        let (old, new) = (generate_key(), generate_key());
+        let sealed = keyring(Some(&old), &[]).seal("todo-1", "secret").unwrap();
+
+        let rotated = keyring(Some(&new), &[&old]);
+        let opened = rotated.open("todo-1", &sealed).unwrap();
+        assert_eq!(opened.description, "secret");
+        assert!(!opened.current);
+
+        let retired = keyring(None, &[&old]);
+        assert!(retired.open("todo-1", &sealed).is_some());
+        assert!(!retired.can_seal());
+        assert!(retired.seal("todo-1", "secret").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        let config = |key: &str| EncryptionConfig {
+            key: Some(key.to_string()),
+            previous_keys: Vec::new(),
+        };
+        assert!(Keyring::from_config(&config("not base64!")).is_err());
+        assert!(Keyring::from_config(&config(&BASE64.encode([0u8; 16]))).is_err());
+        assert!(Keyring::from_config(&config(&BASE64.encode([0u8; 32]))).is_ok());
+    }
+
+    #[test]
+    fn writes_and_reads_sealed_property() {
+        let mut props = Vec::new();
+        write_sealed(&mut props, Some("c2VhbGVk"));
+        write_sealed(&mut props, Some("c2VhbGVkIGFnYWlu"));
+        assert_eq!(props.len(), 1);
+        assert_eq!(read_sealed(&props).as_deref(), Some("c2VhbGVkIGFnYWlu"));
+
+        write_sealed(&mut props, None);
+        assert!(props.is_empty());
+        assert_eq!(read_sealed(&props), None);
+    }
+
+    #[test]
+    fn debug_output_redacts_keys() {
+        let key = generate_key();
+        let config = EncryptionConfig {
+            key: Some(key.clone()),
+            previous_keys: vec![generate_key()],
+        };
+        assert!(!format!("{config:?}").contains(&key));
+        let keyring = Keyring::from_config(&config).unwrap();
+        assert!(!format!("{keyring:?}").contains(&key));
+    }
+}
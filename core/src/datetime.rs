@@ -3,10 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod anchor;
+mod business;
 mod loose;
+mod range;
 mod util;
 
 pub use anchor::DateTimeAnchor;
+pub use anchor::parse_signed_duration;
+pub(crate) use anchor::{parse_duration, parse_weekday_name};
+pub use business::add_business_days;
+pub(crate) use business::end_of_business;
 pub use loose::LooseDateTime;
+pub use range::DateRangeAnchor;
 pub use util::RangePosition;
-pub(crate) use util::{STABLE_FORMAT_DATEONLY, STABLE_FORMAT_LOCAL};
+pub(crate) use util::STABLE_FORMAT_LOCAL;
@@ -5,12 +5,13 @@
 use std::{fmt, str::FromStr, sync::OnceLock};
 
 use jiff::civil::{Date, DateTime, Time, Weekday, time};
-use jiff::{Span, Zoned};
+use jiff::{SignedDuration, Span, Zoned};
 use regex::Regex;
 use serde::de;
 use tracing::warn;
 
-use crate::LooseDateTime;
+use crate::datetime::{add_business_days, end_of_business};
+use crate::{LooseDateTime, WorkingHours};
 
 /// Represents a date and time anchor that can be used to calculate relative dates and times.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -18,6 +19,12 @@ use crate::LooseDateTime;
 pub enum DateTimeAnchor {
     /// A specific number of days in the future or past.
     InDays(i64),
+    /// A specific number of business days in the future or past, due at the end of the working
+    /// day, see [`add_business_days`].
+    ///
+    /// Resolved by the default [`WorkingHours`], Monday to Friday without holidays, unless
+    /// resolved with [`DateTimeAnchor::resolve_business_days`] first.
+    InBusinessDays(i64),
     /// A specific number of seconds in the future or past.
     Relative(i64),
     /// A specific date and time.
@@ -66,6 +73,32 @@ impl DateTimeAnchor {
         DateTimeAnchor::InDays(-1)
     }
 
+    /// Counts the days of the anchor as business days, leaving other anchors as they are.
+    #[must_use]
+    pub fn in_business_days(self) -> Self {
+        match self {
+            DateTimeAnchor::InDays(n) => DateTimeAnchor::InBusinessDays(n),
+            anchor => anchor,
+        }
+    }
+
+    /// Resolves business days from `date` by `hours`, to the end of the working day, leaving
+    /// other anchors as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no business day within a year.
+    pub fn resolve_business_days(self, date: Date, hours: &WorkingHours) -> Result<Self, String> {
+        match self {
+            DateTimeAnchor::InBusinessDays(n) => {
+                let date = add_business_days(date, n, hours)
+                    .ok_or_else(|| format!("No business day {n} business days from {date}"))?;
+                Ok(DateTimeAnchor::DateTime(end_of_business(date, hours)))
+            }
+            anchor => Ok(anchor),
+        }
+    }
+
     /// Resolve datetime at the start of the day based on the provided current local time.
     ///
     /// # Errors
@@ -78,6 +111,9 @@ impl DateTimeAnchor {
                 .map_err(|e| format!("Failed to get start of day: {e}"))?
                 .checked_add(Span::new().days(*n))
                 .map_err(|e| format!("Failed to add days to start of day: {e}")),
+            DateTimeAnchor::InBusinessDays(n) => business_date(now.date(), *n)?
+                .to_zoned(now.time_zone().clone())
+                .map_err(|e| format!("Failed to convert to zoned: {e}")),
             DateTimeAnchor::Relative(n) => now
                 .checked_add(Span::new().seconds(*n))
                 .map_err(|e| format!("Failed to add relative seconds: {e}")),
@@ -114,6 +150,11 @@ impl DateTimeAnchor {
                 .map_err(|e| format!("Failed to get end of day: {e}"))?
                 .checked_add(Span::new().days(*n))
                 .map_err(|e| format!("Failed to add days to end of day: {e}")),
+            DateTimeAnchor::InBusinessDays(n) => business_date(now.date(), *n)?
+                .to_zoned(now.time_zone().clone())
+                .map_err(|e| format!("Failed to convert to zoned: {e}"))?
+                .end_of_day()
+                .map_err(|e| format!("Failed to get end of day: {e}")),
             DateTimeAnchor::Relative(n) => now
                 .checked_add(Span::new().seconds(*n))
                 .map_err(|e| format!("Failed to add relative seconds: {e}")),
@@ -147,6 +188,14 @@ impl DateTimeAnchor {
     pub fn resolve_at(self, now: &LooseDateTime) -> LooseDateTime {
         match self {
             DateTimeAnchor::InDays(n) => now.clone() + Span::new().days(n),
+            DateTimeAnchor::InBusinessDays(n) => {
+                // Keeps the time of day, as for days
+                let date = now.date();
+                match business_date(date, n).map(|d| d.since(date)) {
+                    Ok(Ok(span)) => now.clone() + span,
+                    _ => now.clone(), // Fallback to now if out of range
+                }
+            }
             DateTimeAnchor::Relative(n) => now.clone() + Span::new().seconds(n),
             DateTimeAnchor::DateTime(dt) => dt,
             DateTimeAnchor::Time(t) => {
@@ -190,6 +239,10 @@ impl DateTimeAnchor {
                     Ok(LooseDateTime::from_local_datetime(dt))
                 }
             }
+            DateTimeAnchor::InBusinessDays(n) => Ok(end_of_business(
+                business_date(start.date(), n)?,
+                &WorkingHours::default(),
+            )),
             DateTimeAnchor::Relative(n) => Ok(start.clone() + Span::new().seconds(n)),
             DateTimeAnchor::DateTime(dt) => Ok(dt),
             DateTimeAnchor::Time(t) => {
@@ -237,6 +290,10 @@ impl DateTimeAnchor {
                     Ok(LooseDateTime::from_local_datetime(dt))
                 }
             }
+            DateTimeAnchor::InBusinessDays(n) => Ok(end_of_business(
+                business_date(start.date(), n)?,
+                &WorkingHours::default(),
+            )),
             DateTimeAnchor::Relative(n) => {
                 let zoned = start
                     .checked_add(Span::new().seconds(n))
@@ -312,9 +369,15 @@ impl FromStr for DateTimeAnchor {
         if let Some(hours) = parse_hours(t) {
             return Ok(Self::Relative(hours * 60 * 60));
         }
+        if let Some(days) = parse_business_days(t) {
+            return Ok(Self::InBusinessDays(days));
+        }
         if let Some(days) = parse_days(t) {
             return Ok(Self::InDays(days));
         }
+        if let Some(weeks) = parse_weeks(t) {
+            return Ok(Self::InDays(weeks * 7));
+        }
 
         // Try weekday expressions (e.g., "monday", "next friday", "last tuesday")
         if let Some((day, offset)) = parse_weekday_expression(t) {
@@ -391,15 +454,54 @@ macro_rules! parse_with_regex_and_deprecated_in {
     };
 }
 
-parse_with_regex!(parse_seconds, r"^\s*(\d+)\s*s(?:ec|econds)?\s*$"); // "10s", "10 sec", "10 seconds"
-parse_with_regex!(parse_minutes, r"^\s*(\d+)\s*m(?:in|inutes)?\s*$"); // "10m", "10 min", "10minutes"
+parse_with_regex!(parse_seconds, r"^\s*(\d+)\s*s(?:ec|econds?)?\s*$"); // "10s", "10 sec", "1 second"
+parse_with_regex!(parse_minutes, r"^\s*(\d+)\s*m(?:in|inutes?)?\s*$"); // "10m", "10 min", "1 minute"
+parse_with_regex!(parse_weeks, r"(?i)^\s*(\d+)\s*w(?:eeks?)?\s*$"); // "2w", "2 weeks", "1 week"
+parse_with_regex!(
+    parse_business_days,
+    r"(?i)^\s*(?:in\s*)?([+-]?\d+)\s*(?:bd|business\s*days?)\s*$"
+); // "3bd", "+3bd", "-2bd", "3 business days", "in 3 business days"
 
 parse_with_regex_and_deprecated_in!(
     parse_hours,
-    r"(?i)^\s*(?:in\s*)?(\d+)\s*h(?:ours)?\s*$",
+    r"(?i)^\s*(?:in\s*)?(\d+)\s*h(?:ours?)?\s*$",
     "h"
-); // "10h", "10 hours", "in 10h", "in 10 hours"
-parse_with_regex_and_deprecated_in!(parse_days, r"(?i)^\s*(?:in\s*)?(\d+)\s*d(?:ays)?\s*$", "d"); // "10d", "10 days", "in 10d", "in 10 days"
+); // "10h", "1 hour", "in 10h", "in 10 hours"
+parse_with_regex_and_deprecated_in!(parse_days, r"(?i)^\s*(?:in\s*)?(\d+)\s*d(?:ays?)?\s*$", "d"); // "10d", "1 day", "in 10d", "in 10 days"
+
+/// Parses a duration such as "30m", "1 day" or "2 weeks" into days and seconds.
+pub(crate) fn parse_duration(s: &str) -> Option<(i64, i64)> {
+    if let Some(seconds) = parse_seconds(s) {
+        Some((0, seconds))
+    } else if let Some(minutes) = parse_minutes(s) {
+        Some((0, minutes * 60))
+    } else if let Some(hours) = parse_hours(s) {
+        Some((0, hours * 60 * 60))
+    } else if let Some(days) = parse_days(s) {
+        Some((days, 0))
+    } else {
+        parse_weeks(s).map(|weeks| (weeks * 7, 0))
+    }
+}
+
+/// Parses a duration such as "30m", "1 day", "1h 30m" or "PT45M", with an optional sign.
+///
+/// # Errors
+/// If the string is not a duration.
+pub fn parse_signed_duration(s: &str) -> Result<SignedDuration, String> {
+    let t = s.trim();
+    let (negative, unsigned) = match t.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, t.strip_prefix('+').unwrap_or(t)),
+    };
+    let duration = match parse_duration(unsigned) {
+        Some((days, seconds)) => SignedDuration::from_secs(days * 24 * 60 * 60 + seconds),
+        None => unsigned
+            .parse()
+            .map_err(|_| format!("Invalid duration '{s}', expected e.g. 30m, 1h 30m or PT45M"))?,
+    };
+    Ok(if negative { -duration } else { duration })
+}
 
 const HOURS: [i8; 3] = [9, 13, 18];
 
@@ -455,7 +557,7 @@ fn is_valid_month_day(month: i8, day: i8) -> bool {
 }
 
 /// Parse weekday name (full or abbreviated, case-insensitive) to `Weekday`.
-fn parse_weekday_name(s: &str) -> Option<Weekday> {
+pub(crate) fn parse_weekday_name(s: &str) -> Option<Weekday> {
     let lower = s.to_lowercase();
     match lower.as_str() {
         "monday" | "mon" => Some(Weekday::Monday),
@@ -530,6 +632,12 @@ fn resolve_weekday_date(ref_date: Date, target: Weekday, offset: i8) -> Date {
         .unwrap_or(ref_date)
 }
 
+/// The date `n` business days from `date` by the default working hours, Monday to Friday.
+fn business_date(date: Date, n: i64) -> Result<Date, String> {
+    add_business_days(date, n, &WorkingHours::default())
+        .ok_or_else(|| format!("No business day {n} business days from {date}"))
+}
+
 #[cfg(test)]
 mod tests {
     use jiff::civil::{date, datetime};
@@ -992,6 +1100,101 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_singular_durations() {
+        for (s, expected) in [
+            ("1 second", DateTimeAnchor::Relative(1)),
+            ("1 minute", DateTimeAnchor::Relative(60)),
+            ("1 hour", DateTimeAnchor::Relative(60 * 60)),
+            ("1 day", DateTimeAnchor::InDays(1)),
+        ] {
+            assert_eq!(
+                s.parse::<DateTimeAnchor>().unwrap(),
+                expected,
+                "Failed to parse '{s}'"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_weeks_durations() {
+        for s in ["2w", "2 W", "2weeks", "2 WEEKS", "   2   week   "] {
+            let anchor: DateTimeAnchor = s.parse().unwrap();
+            assert_eq!(anchor, DateTimeAnchor::InDays(14), "Failed to parse '{s}'");
+        }
+    }
+
+    #[test]
+    fn parses_business_days_durations() {
+        for s in [
+            "3bd",
+            "+3bd",
+            "3 BD",
+            "3 business days",
+            "in 3 business days",
+        ] {
+            let anchor: DateTimeAnchor = s.parse().unwrap();
+            assert_eq!(
+                anchor,
+                DateTimeAnchor::InBusinessDays(3),
+                "Failed to parse '{s}'"
+            );
+        }
+        let anchor: DateTimeAnchor = "-1 business day".parse().unwrap();
+        assert_eq!(anchor, DateTimeAnchor::InBusinessDays(-1));
+
+        assert_eq!(
+            DateTimeAnchor::InDays(3).in_business_days(),
+            DateTimeAnchor::InBusinessDays(3)
+        );
+        assert_eq!(
+            DateTimeAnchor::tomorrow().in_business_days(),
+            DateTimeAnchor::InBusinessDays(1)
+        );
+        assert_eq!(
+            DateTimeAnchor::Relative(60).in_business_days(),
+            DateTimeAnchor::Relative(60)
+        );
+    }
+
+    #[test]
+    fn resolves_business_days_to_the_end_of_the_working_day() {
+        // A Friday
+        let now = date(2026, 3, 6)
+            .at(15, 0, 0, 0)
+            .to_zoned(TimeZone::system())
+            .unwrap();
+        let tuesday = LooseDateTime::from_local_datetime(datetime(2026, 3, 10, 17, 0, 0, 0));
+        let anchor = DateTimeAnchor::InBusinessDays(2);
+        assert_eq!(anchor.clone().resolve_since_zoned(&now).unwrap(), tuesday);
+        assert_eq!(
+            anchor.clone().resolve_at_start_of_day(&now).unwrap().date(),
+            date(2026, 3, 10)
+        );
+        assert_eq!(
+            anchor
+                .clone()
+                .resolve_at(&LooseDateTime::Local(now.clone()))
+                .date(),
+            date(2026, 3, 10)
+        );
+
+        // Monday is a holiday
+        let mut hours = WorkingHours::default();
+        hours.add_holiday(date(2026, 3, 9));
+        let wednesday = LooseDateTime::from_local_datetime(datetime(2026, 3, 11, 17, 0, 0, 0));
+        assert_eq!(
+            anchor.resolve_business_days(now.date(), &hours).unwrap(),
+            DateTimeAnchor::DateTime(wednesday)
+        );
+        assert_eq!(
+            DateTimeAnchor::InDays(2)
+                .resolve_business_days(now.date(), &hours)
+                .unwrap(),
+            DateTimeAnchor::InDays(2)
+        );
+    }
+
     #[test]
     fn suggests_next_available_time_slot() {
         for (hour, min, expected_hour, description) in [
@@ -1406,4 +1609,16 @@ mod tests {
             assert!(result.is_err(), "Should reject invalid '{s}'");
         }
     }
+
+    #[test]
+    fn parses_signed_durations() {
+        let minutes = |m| SignedDuration::from_mins(m);
+        assert_eq!(parse_signed_duration("30m").unwrap(), minutes(30));
+        assert_eq!(parse_signed_duration("1 day").unwrap(), minutes(24 * 60));
+        assert_eq!(parse_signed_duration("1h 30m").unwrap(), minutes(90));
+        assert_eq!(parse_signed_duration("PT45M").unwrap(), minutes(45));
+        assert_eq!(parse_signed_duration("-30m").unwrap(), minutes(-30));
+        assert_eq!(parse_signed_duration("+2h").unwrap(), minutes(120));
+        assert!(parse_signed_duration("soon").is_err());
+    }
 }
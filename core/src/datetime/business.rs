@@ -0,0 +1,176 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use jiff::civil::Date;
+
+use crate::{LooseDateTime, TimeRange, WorkingHours};
+
+/// How far [`add_business_days`] looks for the next business day before giving up.
+const SEARCH_DAYS: usize = 366;
+
+/// The date `n` business days after `date`, or before it if `n` is negative.
+///
+/// Business days are the days with working time in `hours`, so neither the days off of the week
+/// nor the holidays. With `n` zero, this is `date` itself if it is a business day, or else the
+/// next one.
+///
+/// Returns `None` if there is no business day within a year of another.
+#[must_use]
+pub fn add_business_days(date: Date, n: i64, hours: &WorkingHours) -> Option<Date> {
+    if n == 0 {
+        return if hours.is_working_day(date) {
+            Some(date)
+        } else {
+            next_business_day(date, Date::tomorrow, hours)
+        };
+    }
+
+    let step: fn(Date) -> Result<Date, jiff::Error> = if n > 0 {
+        Date::tomorrow
+    } else {
+        Date::yesterday
+    };
+    let mut date = date;
+    for _ in 0..n.unsigned_abs() {
+        date = next_business_day(date, step, hours)?;
+    }
+    Some(date)
+}
+
+/// The end of the working day on `date`, or the date alone if work goes on until midnight or
+/// there is none.
+pub(crate) fn end_of_business(date: Date, hours: &WorkingHours) -> LooseDateTime {
+    match hours.day(date.weekday()).last().and_then(TimeRange::end) {
+        Some(end) => LooseDateTime::from_local_datetime(date.to_datetime(end)),
+        None => LooseDateTime::DateOnly(date),
+    }
+}
+
+/// The first business day after `date` in the direction of `step`.
+fn next_business_day(
+    mut date: Date,
+    step: fn(Date) -> Result<Date, jiff::Error>,
+    hours: &WorkingHours,
+) -> Option<Date> {
+    for _ in 0..SEARCH_DAYS {
+        date = step(date).ok()?;
+        if hours.is_working_day(date) {
+            return Some(date);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, datetime};
+
+    use super::*;
+
+    #[test]
+    fn adds_business_days_over_weekends() {
+        let hours = WorkingHours::default();
+        let thursday = date(2026, 3, 5);
+        assert_eq!(
+            add_business_days(thursday, 1, &hours),
+            Some(date(2026, 3, 6))
+        );
+        assert_eq!(
+            add_business_days(thursday, 3, &hours),
+            Some(date(2026, 3, 10))
+        );
+        assert_eq!(
+            add_business_days(thursday, 7, &hours),
+            Some(date(2026, 3, 16))
+        );
+
+        // From a weekend, the first business day is the Monday
+        let saturday = date(2026, 3, 7);
+        assert_eq!(
+            add_business_days(saturday, 1, &hours),
+            Some(date(2026, 3, 9))
+        );
+        assert_eq!(
+            add_business_days(saturday, 0, &hours),
+            Some(date(2026, 3, 9))
+        );
+        assert_eq!(add_business_days(thursday, 0, &hours), Some(thursday));
+    }
+
+    #[test]
+    fn adds_business_days_over_holidays_next_to_weekends() {
+        let mut hours = WorkingHours::default();
+        // A long weekend, from Friday to Monday
+        hours.add_holiday(date(2026, 4, 3));
+        hours.add_holiday(date(2026, 4, 6));
+
+        let thursday = date(2026, 4, 2);
+        assert_eq!(
+            add_business_days(thursday, 1, &hours),
+            Some(date(2026, 4, 7))
+        );
+        assert_eq!(
+            add_business_days(thursday, 3, &hours),
+            Some(date(2026, 4, 9))
+        );
+        assert_eq!(
+            add_business_days(date(2026, 4, 4), 0, &hours),
+            Some(date(2026, 4, 7))
+        );
+    }
+
+    #[test]
+    fn subtracts_business_days() {
+        let mut hours = WorkingHours::default();
+        let tuesday = date(2026, 3, 10);
+        assert_eq!(
+            add_business_days(tuesday, -1, &hours),
+            Some(date(2026, 3, 9))
+        );
+        assert_eq!(
+            add_business_days(tuesday, -2, &hours),
+            Some(date(2026, 3, 6))
+        );
+
+        // Back over a holiday on the Friday before the weekend
+        hours.add_holiday(date(2026, 3, 6));
+        assert_eq!(
+            add_business_days(tuesday, -2, &hours),
+            Some(date(2026, 3, 5))
+        );
+        assert_eq!(
+            add_business_days(date(2026, 3, 8), -1, &hours),
+            Some(date(2026, 3, 5))
+        );
+    }
+
+    #[test]
+    fn finds_no_business_day_without_working_time() {
+        let hours = WorkingHours::empty();
+        assert_eq!(add_business_days(date(2026, 3, 5), 1, &hours), None);
+        assert_eq!(add_business_days(date(2026, 3, 5), -1, &hours), None);
+    }
+
+    #[test]
+    fn ends_business_at_the_end_of_the_last_range() {
+        let mut hours = WorkingHours::default();
+        let friday = date(2026, 3, 6);
+        assert_eq!(
+            end_of_business(friday, &hours),
+            LooseDateTime::from_local_datetime(datetime(2026, 3, 6, 17, 0, 0, 0))
+        );
+
+        hours.set_day(
+            friday.weekday(),
+            vec![
+                "09:00-12:00".parse().unwrap(),
+                "20:00-24:00".parse().unwrap(),
+            ],
+        );
+        assert_eq!(
+            end_of_business(friday, &hours),
+            LooseDateTime::DateOnly(friday)
+        );
+    }
+}
@@ -4,10 +4,10 @@
 
 use std::ops::Add;
 
-use aimcal_ical::{self as ical, Segments};
+use aimcal_ical::{self as ical, StringStorage};
 use jiff::civil::{self, Date, DateTime};
 use jiff::tz::TimeZone;
-use jiff::{Span, Zoned};
+use jiff::{Span, Timestamp, Zoned};
 
 use crate::RangePosition;
 use crate::datetime::util::{
@@ -100,6 +100,32 @@ impl LooseDateTime {
         }
     }
 
+    /// The instant of this date and time, with floating times in `tz` and dates at the start of
+    /// their day.
+    #[must_use]
+    pub fn to_timestamp_in(&self, tz: &TimeZone) -> Option<Timestamp> {
+        match self {
+            LooseDateTime::Local(zoned) => Some(zoned.timestamp()),
+            _ => self
+                .with_start_of_day()
+                .to_zoned(tz.clone())
+                .ok()
+                .map(|zoned| zoned.timestamp()),
+        }
+    }
+
+    /// Like [`Self::to_timestamp_in`], but with dates at the end of their day.
+    pub(crate) fn to_end_timestamp_in(&self, tz: &TimeZone) -> Option<Timestamp> {
+        match self {
+            LooseDateTime::Local(zoned) => Some(zoned.timestamp()),
+            _ => self
+                .with_end_of_day()
+                .to_zoned(tz.clone())
+                .ok()
+                .map(|zoned| zoned.timestamp()),
+        }
+    }
+
     /// Creates a `LooseDateTime` from a `DateTime` in the local timezone.
     pub(crate) fn from_local_datetime(dt: DateTime) -> LooseDateTime {
         // Try to interpret the datetime in the system timezone
@@ -116,6 +142,73 @@ impl LooseDateTime {
         }
     }
 
+    /// The value form this is written in as an iCalendar property.
+    ///
+    /// Local times are written with a `TZID` when the time zone has an IANA name, and in UTC
+    /// otherwise.
+    #[must_use]
+    pub fn value_form(&self) -> ical::ValueForm {
+        match self {
+            LooseDateTime::DateOnly(_) => ical::ValueForm::Date,
+            LooseDateTime::Floating(_) => ical::ValueForm::Floating,
+            LooseDateTime::Local(zoned) => {
+                let tz = zoned.time_zone();
+                if *tz != TimeZone::UTC && tz.iana_name().is_some() {
+                    ical::ValueForm::Zoned
+                } else {
+                    ical::ValueForm::Utc
+                }
+            }
+        }
+    }
+
+    /// Converts to a property written the way `original` is, for patching it.
+    ///
+    /// The original is returned as is when the value is unchanged, so that it stays
+    /// byte-identical. Otherwise its `TZID` and parameters are kept when the new value is in
+    /// the same form or time zone, and the value is written in its own form if not.
+    pub(crate) fn to_property_like(
+        &self,
+        original: &ical::DateTimeProperty<String>,
+    ) -> ical::DateTimeProperty<String> {
+        if LooseDateTime::from(original.clone()) == *self {
+            return original.clone();
+        }
+
+        let tz_id = original.tz_id.as_deref().filter(|_| original.is_zoned());
+        let civil = match self {
+            // A time zone unknown to us is read as floating, so floating times stay in it
+            LooseDateTime::Floating(dt) if tz_id.is_some_and(|id| TimeZone::get(id).is_err()) => {
+                Some((*dt, None))
+            }
+            LooseDateTime::Local(zoned)
+                if tz_id
+                    .and_then(|id| TimeZone::get(id).ok())
+                    .is_some_and(|tz| tz == *zoned.time_zone()) =>
+            {
+                Some((zoned.datetime(), Some(zoned.time_zone().clone())))
+            }
+            _ => None,
+        };
+        let mut prop = match (civil, tz_id) {
+            (Some((dt, tz)), Some(tz_id)) => ical::DateTimeProperty::zoned(
+                dt.date().into(),
+                dt.time().into(),
+                tz_id.to_string(),
+                tz,
+                Vec::new(),
+                Vec::new(),
+                (),
+            ),
+            _ if self.value_form() == original.value_form() => self.clone().into(),
+            _ => return self.clone().into(),
+        };
+        prop.x_parameters.clone_from(&original.x_parameters);
+        prop.retained_parameters
+            .clone_from(&original.retained_parameters);
+        prop
+    }
+
     /// Converts to a string representation of date and time.
     pub(crate) fn format_stable(&self) -> String {
         match self {
@@ -144,9 +237,8 @@ impl LooseDateTime {
     }
 }
 
-impl From<ical::DateTimeProperty<Segments<'_>>> for LooseDateTime {
-    #[tracing::instrument]
-    fn from(dt: ical::DateTimeProperty<Segments<'_>>) -> Self {
+impl<S: StringStorage> From<ical::DateTimeProperty<S>> for LooseDateTime {
+    fn from(dt: ical::DateTimeProperty<S>) -> Self {
         let date = dt.date();
         let time = dt.time();
 
@@ -161,47 +253,7 @@ impl From<ical::DateTimeProperty<Segments<'_>>> for LooseDateTime {
         } else if dt.is_zoned() {
             let civil_dt = DateTime::from_parts(date.civil_date(), time.unwrap().civil_time());
             if let Some(tz_id) = &dt.tz_id {
-                let tz_id_str = tz_id.to_string();
-                if let Ok(tz) = TimeZone::get(tz_id_str.as_str()) {
-                    if let Ok(zoned) = civil_dt.to_zoned(tz) {
-                        LooseDateTime::Local(zoned)
-                    } else {
-                        tracing::warn!(tzid = %tz_id_str, "unknown timezone, treating as floating");
-                        LooseDateTime::Floating(civil_dt)
-                    }
-                } else {
-                    tracing::warn!(tzid = %tz_id_str, "unknown timezone, treating as floating");
-                    LooseDateTime::Floating(civil_dt)
-                }
-            } else {
-                tracing::warn!("zoned datetime without tz_id, treating as floating");
-                LooseDateTime::Floating(civil_dt)
-            }
-        } else {
-            // This should not happen, but fallback to floating
-            let civil_dt = DateTime::from_parts(date.civil_date(), time.unwrap().civil_time());
-            LooseDateTime::Floating(civil_dt)
-        }
-    }
-}
-
-impl From<ical::DateTimeProperty<String>> for LooseDateTime {
-    fn from(dt: ical::DateTimeProperty<String>) -> Self {
-        let date = dt.date();
-        let time = dt.time();
-
-        if dt.is_date_only() {
-            LooseDateTime::DateOnly(date.into())
-        } else if dt.is_utc() {
-            let civil_dt = DateTime::from_parts(date.civil_date(), time.unwrap().civil_time());
-            LooseDateTime::Local(civil_dt.to_zoned(TimeZone::UTC).unwrap())
-        } else if dt.is_floating() {
-            let civil_dt = DateTime::from_parts(date.civil_date(), time.unwrap().civil_time());
-            LooseDateTime::Floating(civil_dt)
-        } else if dt.is_zoned() {
-            let civil_dt = DateTime::from_parts(date.civil_date(), time.unwrap().civil_time());
-            if let Some(tz_id) = &dt.tz_id {
-                if let Ok(tz) = TimeZone::get(tz_id.as_str()) {
+                if let Ok(tz) = TimeZone::get(&tz_id.as_str_lossy()) {
                     if let Ok(zoned) = civil_dt.to_zoned(tz) {
                         LooseDateTime::Local(zoned)
                     } else {
@@ -562,6 +614,30 @@ mod tests {
         assert_eq!(added, expected);
     }
 
+    #[test]
+    fn resolves_instants_with_floating_times_in_time_zone() {
+        let tz = TimeZone::fixed(jiff::tz::offset(8));
+        let floating = LooseDateTime::Floating(datetime(2025, 3, 10, 9, 0, 0, 0));
+        let utc = LooseDateTime::Local(
+            datetime(2025, 3, 10, 8, 0, 0, 0)
+                .to_zoned(TimeZone::UTC)
+                .unwrap(),
+        );
+        let day = LooseDateTime::DateOnly(date(2025, 3, 10));
+
+        let at = |s: &str| s.parse::<Timestamp>().unwrap();
+        assert_eq!(
+            floating.to_timestamp_in(&tz),
+            Some(at("2025-03-10T01:00:00Z"))
+        );
+        assert_eq!(utc.to_timestamp_in(&tz), Some(at("2025-03-10T08:00:00Z")));
+        assert_eq!(day.to_timestamp_in(&tz), Some(at("2025-03-09T16:00:00Z")));
+        assert_eq!(
+            day.to_end_timestamp_in(&tz),
+            Some(at("2025-03-10T15:59:59.999999999Z"))
+        );
+    }
+
     #[test]
     fn adds_span_to_floating() {
         let d = date(2025, 1, 1);
@@ -588,4 +664,56 @@ mod tests {
         let excepted = LooseDateTime::Local(expected_datetime.to_zoned(tz).unwrap());
         assert_eq!(added, excepted);
     }
+
+    #[test]
+    fn reports_value_form() {
+        let dt = datetime(2025, 1, 15, 9, 0, 0, 0);
+        let berlin = dt
+            .to_zoned(TimeZone::get("Europe/Berlin").unwrap())
+            .unwrap();
+        let utc = dt.to_zoned(TimeZone::UTC).unwrap();
+        let form = |dt: LooseDateTime| dt.value_form();
+        assert_eq!(
+            form(LooseDateTime::DateOnly(dt.date())),
+            ical::ValueForm::Date
+        );
+        assert_eq!(form(LooseDateTime::Floating(dt)), ical::ValueForm::Floating);
+        assert_eq!(form(LooseDateTime::Local(berlin)), ical::ValueForm::Zoned);
+        assert_eq!(form(LooseDateTime::Local(utc)), ical::ValueForm::Utc);
+    }
+
+    #[test]
+    fn converts_to_property_like_original() {
+        let dt = datetime(2025, 1, 15, 9, 0, 0, 0);
+        let tz = TimeZone::get("UTC").unwrap();
+        let mut original = ical::DateTimeProperty::zoned(
+            dt.date().into(),
+            dt.time().into(),
+            "UTC".to_string(),
+            Some(tz.clone()),
+            Vec::new(),
+            Vec::new(),
+            (),
+        );
+        original.x_parameters.push(ical::syntax::RawParameter {
+            name: "X-ORIGIN".to_string(),
+            values: Vec::new(),
+            span: (),
+        });
+
+        // A later time in the same zone keeps the TZID and parameters
+        let later = LooseDateTime::Local(dt.to_zoned(tz).unwrap() + Span::new().hours(1));
+        let prop = later.to_property_like(&original);
+        assert!(prop.is_zoned());
+        assert_eq!(prop.tz_id.as_deref(), Some("UTC"));
+        assert_eq!(prop.x_parameters.len(), 1);
+        assert_eq!(LooseDateTime::from(prop), later);
+
+        // Another form is written in its own
+        let date_only = LooseDateTime::DateOnly(dt.date());
+        let prop = date_only.to_property_like(&original);
+        assert!(prop.is_date_only());
+        assert!(prop.tz_id.is_none());
+        assert!(prop.x_parameters.is_empty());
+    }
 }
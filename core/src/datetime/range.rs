@@ -0,0 +1,296 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use jiff::civil::{Date, Weekday};
+use jiff::{ToSpan, Zoned};
+
+use crate::{DateTimeAnchor, LooseDateTime};
+
+/// A range of whole days relative to today, such as the current week or the next month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DateRangeAnchor {
+    /// The week a number of weeks from the current one, starting on the configured week start.
+    ///
+    /// - `0` = This week
+    /// - `1` = Next week
+    /// - `-1` = Last week
+    Week(i32),
+    /// The calendar month a number of months from the current one.
+    Month(i32),
+    /// A number of days starting today, so that `1` is today only.
+    Days(i64),
+}
+
+impl DateRangeAnchor {
+    /// Represents the current week.
+    #[must_use]
+    pub fn this_week() -> Self {
+        DateRangeAnchor::Week(0)
+    }
+
+    /// Represents the week after the current one.
+    #[must_use]
+    pub fn next_week() -> Self {
+        DateRangeAnchor::Week(1)
+    }
+
+    /// Represents the current calendar month.
+    #[must_use]
+    pub fn this_month() -> Self {
+        DateRangeAnchor::Month(0)
+    }
+
+    /// Represents the calendar month after the current one.
+    #[must_use]
+    pub fn next_month() -> Self {
+        DateRangeAnchor::Month(1)
+    }
+
+    /// Resolve the first and the last day of the range, both inclusive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range has no days or is out of the supported dates.
+    pub fn resolve_dates(self, today: Date, week_start: Weekday) -> Result<(Date, Date), String> {
+        match self {
+            DateRangeAnchor::Week(offset) => {
+                let days_since_start = i64::from(today.weekday().since(week_start));
+                let first = today
+                    .checked_add((i64::from(offset) * 7 - days_since_start).days())
+                    .map_err(|e| format!("Failed to get start of week: {e}"))?;
+                let last = first
+                    .checked_add(6.days())
+                    .map_err(|e| format!("Failed to get end of week: {e}"))?;
+                Ok((first, last))
+            }
+            DateRangeAnchor::Month(offset) => {
+                let first = today
+                    .first_of_month()
+                    .checked_add(offset.months())
+                    .map_err(|e| format!("Failed to get start of month: {e}"))?;
+                Ok((first, first.last_of_month()))
+            }
+            DateRangeAnchor::Days(n) if n < 1 => Err(format!(
+                "Invalid range of {n} days, expected at least 1 day"
+            )),
+            DateRangeAnchor::Days(n) => {
+                let last = today
+                    .checked_add((n - 1).days())
+                    .map_err(|e| format!("Failed to add days to today: {e}"))?;
+                Ok((today, last))
+            }
+        }
+    }
+
+    /// Resolve the range to the start of its first day, inclusive, and the start of the day after
+    /// its last day, exclusive, in the time zone of `now`.
+    ///
+    /// The days are calendar days, so a range spanning a DST shift is an hour shorter or longer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range has no days or is out of the supported dates.
+    pub fn resolve(self, now: &Zoned, week_start: Weekday) -> Result<(Zoned, Zoned), String> {
+        let (first, last) = self.resolve_dates(now.date(), week_start)?;
+        let start_of = |date: Date| {
+            date.to_zoned(now.time_zone().clone())
+                .and_then(|zoned| zoned.start_of_day())
+                .map_err(|e| format!("Failed to get start of day: {e}"))
+        };
+        let end = last
+            .tomorrow()
+            .map_err(|e| format!("Failed to get end of range: {e}"))?;
+        Ok((start_of(first)?, start_of(end)?))
+    }
+
+    /// Resolve the range to the anchors of its first and last day, as used by the startable and
+    /// cutoff of [`EventConditions`](crate::EventConditions) or the due of
+    /// [`TodoConditions`](crate::TodoConditions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range has no days or is out of the supported dates.
+    pub fn resolve_anchors(
+        self,
+        today: Date,
+        week_start: Weekday,
+    ) -> Result<(DateTimeAnchor, DateTimeAnchor), String> {
+        let (first, last) = self.resolve_dates(today, week_start)?;
+        Ok((
+            DateTimeAnchor::DateTime(LooseDateTime::DateOnly(first)),
+            DateTimeAnchor::DateTime(LooseDateTime::DateOnly(last)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+
+    fn dates(range: DateRangeAnchor, today: Date, week_start: Weekday) -> (Date, Date) {
+        range.resolve_dates(today, week_start).unwrap()
+    }
+
+    #[test]
+    fn resolves_weeks_from_week_start() {
+        // 2026-01-07 is a Wednesday
+        let today = date(2026, 1, 7);
+        assert_eq!(
+            dates(DateRangeAnchor::this_week(), today, Weekday::Monday),
+            (date(2026, 1, 5), date(2026, 1, 11))
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::this_week(), today, Weekday::Sunday),
+            (date(2026, 1, 4), date(2026, 1, 10))
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::next_week(), today, Weekday::Sunday),
+            (date(2026, 1, 11), date(2026, 1, 17))
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::Week(-1), today, Weekday::Monday),
+            (date(2025, 12, 29), date(2026, 1, 4))
+        );
+    }
+
+    #[test]
+    fn resolves_week_on_its_first_and_last_day() {
+        // A Sunday is the first day of a week starting on Sunday and the last starting on Monday
+        let sunday = date(2026, 1, 11);
+        assert_eq!(
+            dates(DateRangeAnchor::this_week(), sunday, Weekday::Sunday),
+            (date(2026, 1, 11), date(2026, 1, 17))
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::this_week(), sunday, Weekday::Monday),
+            (date(2026, 1, 5), date(2026, 1, 11))
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::next_week(), sunday, Weekday::Monday),
+            (date(2026, 1, 12), date(2026, 1, 18))
+        );
+    }
+
+    #[test]
+    fn resolves_calendar_months() {
+        assert_eq!(
+            dates(
+                DateRangeAnchor::this_month(),
+                date(2026, 1, 31),
+                Weekday::Monday
+            ),
+            (date(2026, 1, 1), date(2026, 1, 31))
+        );
+        assert_eq!(
+            dates(
+                DateRangeAnchor::next_month(),
+                date(2026, 1, 31),
+                Weekday::Monday
+            ),
+            (date(2026, 2, 1), date(2026, 2, 28))
+        );
+        assert_eq!(
+            dates(
+                DateRangeAnchor::this_month(),
+                date(2026, 12, 1),
+                Weekday::Monday
+            ),
+            (date(2026, 12, 1), date(2026, 12, 31))
+        );
+        assert_eq!(
+            dates(
+                DateRangeAnchor::next_month(),
+                date(2026, 12, 15),
+                Weekday::Monday
+            ),
+            (date(2027, 1, 1), date(2027, 1, 31))
+        );
+        assert_eq!(
+            dates(
+                DateRangeAnchor::Month(-1),
+                date(2026, 1, 15),
+                Weekday::Monday
+            ),
+            (date(2025, 12, 1), date(2025, 12, 31))
+        );
+        assert_eq!(
+            dates(
+                DateRangeAnchor::this_month(),
+                date(2028, 2, 10),
+                Weekday::Monday
+            ),
+            (date(2028, 2, 1), date(2028, 2, 29))
+        );
+    }
+
+    #[test]
+    fn resolves_days_from_today() {
+        let today = date(2026, 12, 30);
+        assert_eq!(
+            dates(DateRangeAnchor::Days(1), today, Weekday::Monday),
+            (today, today)
+        );
+        assert_eq!(
+            dates(DateRangeAnchor::Days(7), today, Weekday::Monday),
+            (today, date(2027, 1, 5))
+        );
+        assert!(
+            DateRangeAnchor::Days(0)
+                .resolve_dates(today, Weekday::Monday)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn resolves_range_spanning_dst_shift() {
+        // DST starts on 2026-03-08 and ends on 2026-11-01 in New York
+        let tz = TimeZone::get("America/New_York").unwrap();
+        let now = date(2026, 3, 9)
+            .at(10, 0, 0, 0)
+            .to_zoned(tz.clone())
+            .unwrap();
+        let (start, end) = DateRangeAnchor::this_week()
+            .resolve(&now, Weekday::Sunday)
+            .unwrap();
+        assert_eq!(start.datetime(), date(2026, 3, 8).at(0, 0, 0, 0));
+        assert_eq!(start.offset().seconds(), -5 * 3600);
+        assert_eq!(end.datetime(), date(2026, 3, 15).at(0, 0, 0, 0));
+        assert_eq!(end.offset().seconds(), -4 * 3600);
+        assert_eq!(start.duration_until(&end).as_hours(), 7 * 24 - 1);
+
+        let now = date(2026, 11, 15).at(10, 0, 0, 0).to_zoned(tz).unwrap();
+        let (start, end) = DateRangeAnchor::Month(-1)
+            .resolve(&now, Weekday::Monday)
+            .unwrap();
+        assert_eq!(start.datetime(), date(2026, 10, 1).at(0, 0, 0, 0));
+        assert_eq!(end.datetime(), date(2026, 11, 1).at(0, 0, 0, 0));
+        assert_eq!(start.duration_until(&end).as_hours(), 31 * 24);
+
+        let (start, end) = DateRangeAnchor::this_month()
+            .resolve(&now, Weekday::Monday)
+            .unwrap();
+        assert_eq!(start.datetime(), date(2026, 11, 1).at(0, 0, 0, 0));
+        assert_eq!(end.datetime(), date(2026, 12, 1).at(0, 0, 0, 0));
+        assert_eq!(start.duration_until(&end).as_hours(), 30 * 24 + 1);
+    }
+
+    #[test]
+    fn resolves_anchors_of_first_and_last_day() {
+        let (start, cutoff) = DateRangeAnchor::this_week()
+            .resolve_anchors(date(2026, 1, 7), Weekday::Monday)
+            .unwrap();
+        assert_eq!(
+            start,
+            DateTimeAnchor::DateTime(LooseDateTime::DateOnly(date(2026, 1, 5)))
+        );
+        assert_eq!(
+            cutoff,
+            DateTimeAnchor::DateTime(LooseDateTime::DateOnly(date(2026, 1, 11)))
+        );
+    }
+}
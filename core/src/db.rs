@@ -3,106 +3,292 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod calendars;
-mod events;
+pub mod events;
+mod meta;
+pub mod pending_ops;
 mod resources;
+mod schema;
 mod short_ids;
-mod todos;
+pub mod subscriptions;
+pub mod todos;
 
 #[cfg(test)]
 mod migrations_tests;
 #[cfg(test)]
 mod tests_utils;
 
-use std::error::Error;
 use std::path::Path;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
+use sqlx::migrate::Migrator;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::{Sqlite, Transaction};
 
+use crate::collation::{UNICODE_COLLATION, unicode_compare};
 use crate::db::calendars::Calendars;
 use crate::db::events::{EventRecord, Events};
+use crate::db::meta::ItemMeta;
+use crate::db::pending_ops::PendingOps;
 use crate::db::resources::Resources;
+use crate::db::schema::SchemaStamp;
 use crate::db::short_ids::ShortIds;
+use crate::db::subscriptions::Subscriptions;
 use crate::db::todos::{TodoRecord, Todos};
-use crate::{Event, Todo};
+use crate::home_zone::TimedField;
+use crate::{AimError, Event, Kind, LooseDateTime, Rules, Todo};
+
+/// File name of the database inside the state directory.
+pub const DB_FILE_NAME: &str = "aim.db";
 
 /// Global counter for generating unique in-memory database names.
 static IN_MEMORY_DB_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+static MIGRATOR: Migrator = sqlx::migrate!("src/db/migrations"); // relative path from the crate root
+
 #[derive(Debug, Clone)]
 pub struct Db {
     pool: SqlitePool,
 
     pub events: Events,
     pub todos: Todos,
+    pub meta: ItemMeta,
     pub short_ids: ShortIds,
     pub resources: Resources,
     pub calendars: Calendars,
+    pub subscriptions: Subscriptions,
+    pub pending_ops: PendingOps,
+
+    /// Time zone of floating times and dates, when resolving them to instants for range queries.
+    tz: TimeZone,
+    /// Rules applied to the items as they are upserted, see [`Db::set_rules`].
+    rules: Arc<Rules>,
+    /// Stamp of the newer schema, when opened with [`Db::open_read_only_compat`].
+    newer_schema: Option<SchemaStamp>,
 }
 
 impl Db {
     /// Opens a sqlite database connection.
     /// If `state_dir` is `None`, it opens an in-memory database.
-    pub async fn open(filename: Option<&Path>) -> Result<Self, Box<dyn Error>> {
-        let pool_opts = SqlitePoolOptions::new();
-        let (conn_opts, pool_opts) = if let Some(filename) = filename {
+    pub async fn open(filename: Option<&Path>) -> Result<Self, AimError> {
+        Self::open_in(filename, TimeZone::system()).await
+    }
+
+    /// Opens a sqlite database connection like [`Db::open`], resolving floating times and dates
+    /// in `tz` for range queries.
+    ///
+    /// A database written by a newer aim is refused with [`AimError::SchemaTooNew`].
+    pub async fn open_in(filename: Option<&Path>, tz: TimeZone) -> Result<Self, AimError> {
+        Self::open_with(filename, tz, false).await
+    }
+
+    /// Opens a sqlite database connection like [`Db::open_in`], but opens a database written by a
+    /// newer aim as well, as long as it has all the columns this build reads.
+    ///
+    /// Such a database is neither migrated nor written to: it is copied into memory, so what is
+    /// written through the returned handle is dropped on close. See [`Db::newer_schema`].
+    pub async fn open_read_only_compat(filename: &Path, tz: TimeZone) -> Result<Self, AimError> {
+        Self::open_with(Some(filename), tz, true).await
+    }
+
+    async fn open_with(
+        filename: Option<&Path>,
+        tz: TimeZone,
+        read_only_compat: bool,
+    ) -> Result<Self, AimError> {
+        // Checked before connecting, which creates a missing database
+        let checked = filename.is_some_and(schema::is_checked);
+        let pool = if let Some(filename) = filename {
             tracing::info!(dir = %filename.display(), "connecting to SQLite database");
             let conn_opts = SqliteConnectOptions::new()
-                .filename(filename.to_str().ok_or("Invalid path encoding")?)
-                .create_if_missing(true);
+                .filename(utf8_path(filename)?)
+                .create_if_missing(true)
+                .collation(UNICODE_COLLATION, unicode_compare);
 
-            (conn_opts, pool_opts)
+            SqlitePoolOptions::new()
+                .connect_with(conn_opts)
+                .await
+                .map_err(|source| AimError::Database {
+                    context: "Failed to connect to SQLite database",
+                    source,
+                })?
         } else {
             tracing::info!("connecting to in-memory SQLite database");
-            // Use shared in-memory database so all connections in the pool can access it
-            // Generate a unique name per call for test isolation
-            let db_id = IN_MEMORY_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
-            let db_name = format!("file:memdb_{db_id}:?mode=memory&cache=shared");
+            Self::connect_in_memory()
+                .await
+                .map_err(|source| AimError::Database {
+                    context: "Failed to connect to SQLite database",
+                    source,
+                })?
+        };
 
-            let conn_opts = SqliteConnectOptions::new()
-                .filename(db_name)
-                .in_memory(true);
+        if checked {
+            tracing::debug!("schema already checked by this aim, not migrating");
+            return Self::from_pool(pool, tz, None).await;
+        }
 
-            (conn_opts, pool_opts.max_connections(1)) // Single connection for in-memory databases
-        };
+        // Checked before migrating, which would fail on the migrations it does not know
+        if let Some(stamp) = schema::read(&pool).await?.filter(SchemaStamp::is_newer) {
+            pool.close().await;
+            return match filename {
+                Some(filename) if read_only_compat => Self::open_copy(filename, tz, stamp).await,
+                _ => Err(AimError::SchemaTooNew {
+                    created_by: stamp.created_by(),
+                }),
+            };
+        }
+
+        MIGRATOR.run(&pool).await.map_err(|e| AimError::Database {
+            context: "Failed to run migrations",
+            source: e.into(),
+        })?;
+        schema::write(&pool).await?;
+        if let Some(filename) = filename {
+            schema::record_checked(filename);
+        }
 
-        let pool = pool_opts
+        Self::from_pool(pool, tz, None).await
+    }
+
+    /// Opens an in-memory copy of a database written by a newer aim, refusing it if it lacks
+    /// columns this build reads.
+    async fn open_copy(
+        filename: &Path,
+        tz: TimeZone,
+        stamp: SchemaStamp,
+    ) -> Result<Self, AimError> {
+        tracing::info!(dir = %filename.display(), "copying SQLite database of a newer aim");
+        let conn_opts = SqliteConnectOptions::new()
+            .filename(utf8_path(filename)?)
+            .read_only(true);
+        let source = SqlitePoolOptions::new()
+            .max_connections(1)
             .connect_with(conn_opts)
             .await
-            .map_err(|e| format!("Failed to connect to SQLite database: {e}"))?;
+            .map_err(|source| AimError::Database {
+                context: "Failed to connect to SQLite database",
+                source,
+            })?;
+
+        let missing = schema::missing_columns(&source).await?;
+        if !missing.is_empty() {
+            source.close().await;
+            return Err(AimError::SchemaIncompatible {
+                created_by: stamp.created_by(),
+                missing,
+            });
+        }
 
-        sqlx::migrate!("src/db/migrations") // relative path from the crate root
-            .run(&pool)
+        let (pool, db_name) = Self::connect_named_in_memory().await?;
+        sqlx::query("VACUUM INTO ?;")
+            .bind(db_name)
+            .execute(&source)
             .await
-            .map_err(|e| format!("Failed to run migrations: {e}"))?;
+            .map_err(|source| AimError::Database {
+                context: "Failed to copy database",
+                source,
+            })?;
+        source.close().await;
+
+        Self::from_pool(pool, tz, Some(stamp)).await
+    }
+
+    /// Connects to a new in-memory database.
+    async fn connect_in_memory() -> Result<SqlitePool, sqlx::Error> {
+        Self::connect_named_in_memory().await.map(|(pool, _)| pool)
+    }
+
+    /// Connects to a new in-memory database, returning its name as well.
+    async fn connect_named_in_memory() -> Result<(SqlitePool, String), sqlx::Error> {
+        // Use shared in-memory database so all connections in the pool can access it
+        // Generate a unique name per call for test isolation
+        let db_id = IN_MEMORY_DB_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let db_name = format!("file:memdb_{db_id}:?mode=memory&cache=shared");
 
+        // Registered through the options, so every connection the pool opens gets it
+        let conn_opts = SqliteConnectOptions::new()
+            .filename(&db_name)
+            .in_memory(true)
+            .collation(UNICODE_COLLATION, unicode_compare);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1) // Single connection for in-memory databases
+            .connect_with(conn_opts)
+            .await?;
+        Ok((pool, db_name))
+    }
+
+    async fn from_pool(
+        pool: SqlitePool,
+        tz: TimeZone,
+        newer_schema: Option<SchemaStamp>,
+    ) -> Result<Self, AimError> {
         tracing::debug!("ensuring tables in the database");
-        let events = Events::new(pool.clone());
-        let todos = Todos::new(pool.clone());
+        let events = Events::new(pool.clone(), tz.clone());
+        let todos = Todos::new(pool.clone(), tz.clone());
+
+        // Rows cached before their instants were stored have none yet
+        let backfilled = events.backfill_instants().await? + todos.backfill_instants().await?;
+        if backfilled > 0 {
+            tracing::info!(backfilled, "filled in the instants of cached items");
+        }
+
+        let meta = ItemMeta::new(pool.clone());
         let short_ids = ShortIds::new(pool.clone());
         let resources = Resources::new(pool.clone());
         let calendars = Calendars::new(pool.clone());
+        let subscriptions = Subscriptions::new(pool.clone());
+        let pending_ops = PendingOps::new(pool.clone());
         Ok(Db {
             pool,
             events,
             todos,
+            meta,
             short_ids,
             resources,
             calendars,
+            subscriptions,
+            pending_ops,
+            tz,
+            rules: Arc::default(),
+            newer_schema,
         })
     }
 
+    /// Stamp of the schema of the database, if it was written by a newer aim and opened with
+    /// [`Db::open_read_only_compat`].
+    pub fn newer_schema(&self) -> Option<&SchemaStamp> {
+        self.newer_schema.as_ref()
+    }
+
+    /// Sets the rules applied to the items upserted from now on, through this handle and the
+    /// transactions it begins.
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = Arc::new(rules);
+    }
+
+    /// The rules applied to the items as they are upserted.
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
     pub async fn upsert_event(
         &self,
         uid: &str,
         event: &impl Event,
         calendar_id: &str,
-    ) -> Result<(), Box<dyn Error>> {
-        let record = EventRecord::from_event(uid, event, calendar_id);
+    ) -> Result<(), AimError> {
+        let mut record = EventRecord::from_event(uid, event, calendar_id);
+        record.apply_rules(&self.rules);
         self.events
             .upsert(record)
             .await
-            .map_err(|e| format!("Failed to upsert event: {e}").into())
+            .map_err(|source| AimError::Database {
+                context: "Failed to upsert event",
+                source,
+            })
     }
 
     pub async fn upsert_todo(
@@ -110,17 +296,262 @@ impl Db {
         uid: &str,
         todo: &impl Todo,
         calendar_id: &str,
-    ) -> Result<(), Box<dyn Error>> {
-        let record = TodoRecord::from_todo(uid, todo, calendar_id);
+    ) -> Result<(), AimError> {
+        let mut record = TodoRecord::from_todo(uid, todo, calendar_id);
+        record.apply_rules(&self.rules);
         self.todos
             .upsert(&record)
             .await
-            .map_err(|e| format!("Failed to upsert todo: {e}").into())
+            .map_err(|source| AimError::Database {
+                context: "Failed to upsert todo",
+                source,
+            })
+    }
+
+    /// Begins a transaction, to write many items at once.
+    pub async fn begin(&self) -> Result<DbTransaction, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        Ok(DbTransaction {
+            tx,
+            tz: self.tz.clone(),
+            rules: self.rules.clone(),
+        })
+    }
+
+    /// Writes a consistent copy of the live database to `dest`, which must not exist yet.
+    pub async fn snapshot(&self, dest: &Path) -> Result<(), AimError> {
+        tracing::debug!(dest = %dest.display(), "snapshotting database");
+        let dest = utf8_path(dest)?;
+        sqlx::query("VACUUM INTO ?;")
+            .bind(dest)
+            .execute(&self.pool)
+            .await
+            .map_err(|source| AimError::Database {
+                context: "Failed to snapshot database",
+                source,
+            })?;
+        Ok(())
+    }
+
+    /// When housekeeping last ran, if ever.
+    pub async fn last_housekeeping(&self) -> Result<Option<Timestamp>, sqlx::Error> {
+        const SQL: &str = "SELECT last_run_at FROM housekeeping WHERE id = 0;";
+        let last: Option<String> = sqlx::query_scalar(SQL).fetch_optional(&self.pool).await?;
+        Ok(last.and_then(|a| a.parse().ok()))
+    }
+
+    /// Records that housekeeping ran at `at`.
+    pub async fn record_housekeeping(&self, at: Timestamp) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+INSERT INTO housekeeping (id, last_run_at)
+VALUES (0, ?)
+ON CONFLICT(id) DO UPDATE SET last_run_at = excluded.last_run_at;
+";
+        sqlx::query(SQL)
+            .bind(at.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// The home time zones seen, with when each was first seen, the current one last.
+    pub async fn home_zones(&self) -> Result<Vec<(String, Timestamp)>, sqlx::Error> {
+        const SQL: &str = "SELECT timezone, seen_at FROM timezone_history ORDER BY id;";
+        let rows: Vec<(String, String)> = sqlx::query_as(SQL).fetch_all(&self.pool).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(zone, at)| Some((zone, at.parse().ok()?)))
+            .collect())
+    }
+
+    /// Records `zone` as the home time zone seen at `at`, unless it is the last one recorded.
+    ///
+    /// Returns the home time zone recorded before, if it changed.
+    pub async fn record_home_zone(
+        &self,
+        zone: &str,
+        at: Timestamp,
+    ) -> Result<Option<String>, sqlx::Error> {
+        const SQL_LAST: &str = "SELECT timezone FROM timezone_history ORDER BY id DESC LIMIT 1;";
+        const SQL_INSERT: &str = "INSERT INTO timezone_history (timezone, seen_at) VALUES (?, ?);";
+
+        let last: Option<String> = sqlx::query_scalar(SQL_LAST)
+            .fetch_optional(&self.pool)
+            .await?;
+        if last.as_deref() == Some(zone) {
+            return Ok(None);
+        }
+        sqlx::query(SQL_INSERT)
+            .bind(zone)
+            .bind(at.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(last)
     }
 
-    pub async fn close(self) -> Result<(), Box<dyn Error>> {
+    /// The times with a time of day a change of the home time zone may shift: the start and end
+    /// of events and the due of the todos still open, as stored.
+    pub(crate) async fn timed_fields(&self) -> Result<Vec<TimedField>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT 'event', uid, summary, 'start', start FROM events WHERE length(start) > 10
+UNION ALL
+SELECT 'event', uid, summary, 'end', end FROM events WHERE length(end) > 10
+UNION ALL
+SELECT 'todo', uid, summary, 'due', due FROM todos
+    WHERE length(due) > 10 AND status NOT IN ('COMPLETED', 'CANCELLED');
+";
+        let rows: Vec<(String, String, String, String, String)> =
+            sqlx::query_as(SQL).fetch_all(&self.pool).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(kind, uid, summary, field, stored)| {
+                let field = match field.as_str() {
+                    "start" => "start",
+                    "end" => "end",
+                    _ => "due",
+                };
+                Some(TimedField {
+                    kind: Kind::parse_stable(&kind)?,
+                    uid,
+                    summary,
+                    field,
+                    stored,
+                })
+            })
+            .collect())
+    }
+
+    /// Version of the latest migration applied to this database.
+    pub async fn schema_version(&self) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success;";
+        sqlx::query_scalar(SQL).fetch_one(&self.pool).await
+    }
+
+    /// Version of the latest migration known to this build.
+    pub fn latest_schema_version() -> i64 {
+        MIGRATOR.iter().map(|m| m.version).max().unwrap_or_default()
+    }
+
+    pub async fn close(self) -> Result<(), AimError> {
         tracing::debug!("closing database connection");
         self.pool.close().await;
         Ok(())
     }
 }
+
+/// A transaction on the database, see [`Db::begin`].
+///
+/// A failed write leaves the transaction usable, so the other writes can still be committed.
+#[derive(Debug)]
+pub struct DbTransaction {
+    tx: Transaction<'static, Sqlite>,
+    tz: TimeZone,
+    rules: Arc<Rules>,
+}
+
+impl DbTransaction {
+    pub async fn upsert_event(
+        &mut self,
+        uid: &str,
+        event: &impl Event,
+        calendar_id: &str,
+    ) -> Result<(), AimError> {
+        let record = EventRecord::from_event(uid, event, calendar_id);
+        self.upsert_event_record(record).await
+    }
+
+    /// Upserts an event whose columns were extracted beforehand, such as on another thread.
+    pub async fn upsert_event_record(&mut self, mut record: EventRecord) -> Result<(), AimError> {
+        record.apply_rules(&self.rules);
+        Events::upsert_with(&mut self.tx, &record, &self.tz).await?;
+        Ok(())
+    }
+
+    pub async fn upsert_todo(
+        &mut self,
+        uid: &str,
+        todo: &impl Todo,
+        calendar_id: &str,
+    ) -> Result<(), AimError> {
+        let record = TodoRecord::from_todo(uid, todo, calendar_id);
+        self.upsert_todo_record(record).await
+    }
+
+    /// Upserts a todo whose columns were extracted beforehand, such as on another thread.
+    pub async fn upsert_todo_record(&mut self, mut record: TodoRecord) -> Result<(), AimError> {
+        record.apply_rules(&self.rules);
+        Todos::upsert_with(&mut self.tx, &record, &self.tz).await?;
+        Ok(())
+    }
+
+    pub async fn insert_resource(
+        &mut self,
+        uid: &str,
+        calendar_id: &str,
+        resource_id: &str,
+        metadata: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        Resources::insert_with(&mut *self.tx, uid, calendar_id, resource_id, metadata).await
+    }
+
+    pub async fn update_pending_ics(&mut self, id: i64, ics: &str) -> Result<(), sqlx::Error> {
+        PendingOps::update_ics_with(&mut *self.tx, id, ics).await
+    }
+
+    pub async fn delete_event(&mut self, uid: &str, calendar_id: &str) -> Result<(), sqlx::Error> {
+        Events::delete_with(&mut self.tx, uid).await?;
+        Resources::delete_with(&mut *self.tx, uid, calendar_id).await
+    }
+
+    pub async fn delete_todo(&mut self, uid: &str, calendar_id: &str) -> Result<(), sqlx::Error> {
+        Todos::delete_with(&mut self.tx, uid).await?;
+        Resources::delete_with(&mut *self.tx, uid, calendar_id).await
+    }
+
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
+    }
+}
+
+/// The path as a string, as sqlite takes it.
+fn utf8_path(path: &Path) -> Result<&str, AimError> {
+    path.to_str().ok_or_else(|| AimError::InvalidInput {
+        field: "path",
+        reason: format!("{} is not valid UTF-8", path.display()),
+    })
+}
+
+/// Parses a date and time as stored, reading RFC 3339 timestamps such as `2025-03-10T08:00:00Z`
+/// from older databases as well.
+fn parse_stored(stored: &str) -> Option<LooseDateTime> {
+    LooseDateTime::parse_stable(stored).or_else(|| {
+        let timestamp: Timestamp = stored.parse().ok()?;
+        Some(LooseDateTime::Local(timestamp.to_zoned(TimeZone::UTC)))
+    })
+}
+
+/// Seconds since the Unix epoch of a date and time as stored, with floating times and dates in
+/// `tz`, and dates at the start of their day.
+fn unix_seconds(stored: &str, tz: &TimeZone) -> Option<i64> {
+    let timestamp = parse_stored(stored)?.to_timestamp_in(tz)?;
+    Some(timestamp.as_second())
+}
+
+/// The categories of an item, from its store and then the ones the rules add, both stored as
+/// JSON arrays.
+fn layered_categories(intrinsic: &str, added: &str) -> Vec<String> {
+    let mut categories: Vec<String> = serde_json::from_str(intrinsic).unwrap_or_default();
+    let added: Vec<String> = serde_json::from_str(added).unwrap_or_default();
+    for category in added {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    categories
+}
+
+/// Like [`unix_seconds`], but with dates at the end of their day.
+fn unix_seconds_at_end_of_day(stored: &str, tz: &TimeZone) -> Option<i64> {
+    let timestamp = parse_stored(stored)?.to_end_timestamp_in(tz)?;
+    Some(timestamp.as_second())
+}
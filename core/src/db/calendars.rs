@@ -43,7 +43,8 @@ ON CONFLICT(id) DO UPDATE SET
 
     pub async fn get(&self, id: &str) -> Result<Option<CalendarRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT id, name, kind, priority, enabled, created_at, updated_at
+SELECT id, name, kind, priority, enabled, user_enabled, is_default, owner, can_read,
+       can_write_content, can_bind, can_unbind, created_at, updated_at
 FROM calendars
 WHERE id = ?;
 ";
@@ -56,7 +57,8 @@ WHERE id = ?;
 
     pub async fn list(&self) -> Result<Vec<CalendarRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT id, name, kind, priority, enabled, created_at, updated_at
+SELECT id, name, kind, priority, enabled, user_enabled, is_default, owner, can_read,
+       can_write_content, can_bind, can_unbind, created_at, updated_at
 FROM calendars
 ORDER BY priority ASC;
 ";
@@ -66,7 +68,8 @@ ORDER BY priority ASC;
 
     pub async fn list_enabled(&self) -> Result<Vec<CalendarRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT id, name, kind, priority, enabled, created_at, updated_at
+SELECT id, name, kind, priority, enabled, user_enabled, is_default, owner, can_read,
+       can_write_content, can_bind, can_unbind, created_at, updated_at
 FROM calendars
 WHERE enabled = 1
 ORDER BY priority ASC;
@@ -82,11 +85,130 @@ SET enabled = ?, updated_at = ?
 WHERE id = ?;
 ";
 
-        let now = Zoned::now().strftime("%Y-%m-%dT%H:%M:%S%.f%:z").to_string();
+        sqlx::query(SQL)
+            .bind(enabled)
+            .bind(timestamp())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Renames the calendar.
+    pub async fn set_name(&self, id: &str, name: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+UPDATE calendars
+SET name = ?, updated_at = ?
+WHERE id = ?;
+";
 
         sqlx::query(SQL)
+            .bind(name)
+            .bind(timestamp())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Enables or disables the calendar by choice of the user, which overrides the config.
+    pub async fn set_user_enabled(&self, id: &str, enabled: bool) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+UPDATE calendars
+SET user_enabled = ?, enabled = ?, updated_at = ?
+WHERE id = ?;
+";
+
+        sqlx::query(SQL)
+            .bind(enabled)
             .bind(enabled)
-            .bind(&now)
+            .bind(timestamp())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Makes the calendar the default for new items, in place of any other.
+    pub async fn set_default(&self, id: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "UPDATE calendars SET is_default = (id = ?);";
+
+        sqlx::query(SQL).bind(id).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Records that syncing the calendar succeeded, clearing the last error.
+    pub async fn record_sync_success(&self, id: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+INSERT INTO calendar_sync_status (calendar_id, last_success_at, last_error, last_error_at)
+VALUES (?, ?, NULL, NULL)
+ON CONFLICT(calendar_id) DO UPDATE SET
+    last_success_at = excluded.last_success_at,
+    last_error      = NULL,
+    last_error_at   = NULL;
+";
+
+        sqlx::query(SQL)
+            .bind(id)
+            .bind(timestamp())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Records that syncing the calendar failed, keeping the time of the last success.
+    pub async fn record_sync_error(&self, id: &str, error: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+INSERT INTO calendar_sync_status (calendar_id, last_error, last_error_at)
+VALUES (?, ?, ?)
+ON CONFLICT(calendar_id) DO UPDATE SET
+    last_error    = excluded.last_error,
+    last_error_at = excluded.last_error_at;
+";
+
+        sqlx::query(SQL)
+            .bind(id)
+            .bind(error)
+            .bind(timestamp())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn sync_status(&self, id: &str) -> Result<Option<SyncStatusRecord>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT calendar_id, last_success_at, last_error, last_error_at
+FROM calendar_sync_status
+WHERE calendar_id = ?;
+";
+
+        sqlx::query_as(SQL)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn set_sharing(
+        &self,
+        id: &str,
+        owner: Option<&str>,
+        privileges: CalendarPrivileges,
+    ) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+UPDATE calendars
+SET owner = ?, can_read = ?, can_write_content = ?, can_bind = ?, can_unbind = ?
+WHERE id = ?;
+";
+
+        sqlx::query(SQL)
+            .bind(owner)
+            .bind(privileges.read)
+            .bind(privileges.write_content)
+            .bind(privileges.bind)
+            .bind(privileges.unbind)
             .bind(id)
             .execute(&self.pool)
             .await?;
@@ -116,6 +238,15 @@ pub struct CalendarRecord {
     pub priority: i32,
     /// Whether the calendar is enabled for queries and backend initialization.
     pub enabled: bool,
+    /// Whether the user enabled or disabled the calendar, overriding the config.
+    pub user_enabled: Option<bool>,
+    /// Whether the user made this the default calendar, overriding the config.
+    pub is_default: bool,
+    /// Principal owning the calendar, when shared by someone else.
+    pub owner: Option<String>,
+    /// What the current user may do with the calendar.
+    #[sqlx(flatten)]
+    pub privileges: CalendarPrivileges,
     /// Creation timestamp.
     pub created_at: String,
     /// Last update timestamp.
@@ -126,19 +257,90 @@ impl CalendarRecord {
     /// Creates a new calendar record with the given parameters.
     #[must_use]
     pub fn new(id: String, name: String, kind: String, priority: i32, enabled: bool) -> Self {
-        let now = Zoned::now().strftime("%Y-%m-%dT%H:%M:%S%.f%:z").to_string();
+        let now = timestamp();
         Self {
             id,
             name,
             kind,
             priority,
             enabled,
+            user_enabled: None,
+            is_default: false,
+            owner: None,
+            privileges: CalendarPrivileges::ALL,
             created_at: now.clone(),
             updated_at: now,
         }
     }
 }
 
+/// What the current user may do with a calendar, as reported by its server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow, serde::Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CalendarPrivileges {
+    /// Whether the user may read the items.
+    #[sqlx(rename = "can_read")]
+    pub read: bool,
+    /// Whether the user may edit existing items.
+    #[sqlx(rename = "can_write_content")]
+    pub write_content: bool,
+    /// Whether the user may create items.
+    #[sqlx(rename = "can_bind")]
+    pub bind: bool,
+    /// Whether the user may delete items.
+    #[sqlx(rename = "can_unbind")]
+    pub unbind: bool,
+}
+
+impl CalendarPrivileges {
+    /// Every privilege, which calendars have unless their server says otherwise.
+    pub const ALL: Self = Self {
+        read: true,
+        write_content: true,
+        bind: true,
+        unbind: true,
+    };
+
+    /// Whether the user may not change the calendar in any way.
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        !self.write_content && !self.bind && !self.unbind
+    }
+}
+
+impl Default for CalendarPrivileges {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl From<aimcal_caldav::Privileges> for CalendarPrivileges {
+    fn from(privileges: aimcal_caldav::Privileges) -> Self {
+        Self {
+            read: privileges.read,
+            write_content: privileges.write_content,
+            bind: privileges.bind,
+            unbind: privileges.unbind,
+        }
+    }
+}
+
+/// Outcome of the last syncs of a calendar.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SyncStatusRecord {
+    pub calendar_id: String,
+    /// When the last successful sync finished.
+    pub last_success_at: Option<String>,
+    /// Message of the last failed sync, if it failed after the last success.
+    pub last_error: Option<String>,
+    /// When the last failed sync finished.
+    pub last_error_at: Option<String>,
+}
+
+fn timestamp() -> String {
+    Zoned::now().strftime("%Y-%m-%dT%H:%M:%S%.f%:z").to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,6 +488,94 @@ mod tests {
         assert!(retrieved.enabled);
     }
 
+    #[tokio::test]
+    async fn calendars_set_sharing_survives_upsert() {
+        let db = setup_test_db().await;
+
+        let calendar = CalendarRecord::new(
+            "shared".to_string(),
+            "Shared".to_string(),
+            "caldav".to_string(),
+            0,
+            true,
+        );
+        db.calendars.upsert(calendar.clone()).await.unwrap();
+        db.calendars
+            .set_sharing(
+                "shared",
+                Some("/principals/alice/"),
+                CalendarPrivileges {
+                    read: true,
+                    write_content: true,
+                    bind: false,
+                    unbind: false,
+                },
+            )
+            .await
+            .unwrap();
+
+        // Reconciling with the config must keep what the server reported
+        db.calendars.upsert(calendar).await.unwrap();
+
+        let retrieved = db.calendars.get("shared").await.unwrap().unwrap();
+        assert_eq!(retrieved.owner.as_deref(), Some("/principals/alice/"));
+        assert!(retrieved.privileges.write_content);
+        assert!(!retrieved.privileges.bind && !retrieved.privileges.unbind);
+        assert!(!retrieved.privileges.is_read_only());
+    }
+
+    #[tokio::test]
+    async fn calendars_user_choices_survive_upsert() {
+        let db = setup_test_db().await;
+
+        for id in ["personal", "work"] {
+            let calendar =
+                CalendarRecord::new(id.to_string(), id.to_string(), "local".to_string(), 0, true);
+            db.calendars.upsert(calendar).await.unwrap();
+        }
+        db.calendars.set_user_enabled("work", false).await.unwrap();
+        db.calendars.set_default("personal").await.unwrap();
+        db.calendars.set_default("work").await.unwrap();
+
+        let calendar = CalendarRecord::new(
+            "work".to_string(),
+            "Work".to_string(),
+            "local".to_string(),
+            0,
+            true,
+        );
+        db.calendars.upsert(calendar).await.unwrap();
+
+        let work = db.calendars.get("work").await.unwrap().unwrap();
+        assert_eq!(work.user_enabled, Some(false));
+        assert!(work.is_default);
+        let personal = db.calendars.get("personal").await.unwrap().unwrap();
+        assert_eq!(personal.user_enabled, None);
+        assert!(!personal.is_default);
+    }
+
+    #[tokio::test]
+    async fn calendars_record_sync_keeps_last_success_and_clears_error() {
+        let db = setup_test_db().await;
+        assert!(db.calendars.sync_status("work").await.unwrap().is_none());
+
+        db.calendars.record_sync_success("work").await.unwrap();
+        db.calendars
+            .record_sync_error("work", "connection refused")
+            .await
+            .unwrap();
+        let status = db.calendars.sync_status("work").await.unwrap().unwrap();
+        assert!(status.last_success_at.is_some());
+        assert_eq!(status.last_error.as_deref(), Some("connection refused"));
+        assert!(status.last_error_at.is_some());
+
+        db.calendars.record_sync_success("work").await.unwrap();
+        let status = db.calendars.sync_status("work").await.unwrap().unwrap();
+        assert!(status.last_success_at.is_some());
+        assert_eq!(status.last_error, None);
+        assert_eq!(status.last_error_at, None);
+    }
+
     #[tokio::test]
     async fn calendars_delete_removes_calendar() {
         let db = setup_test_db().await;
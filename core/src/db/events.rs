@@ -3,37 +3,121 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::borrow::Cow;
-
-use jiff::{Zoned, civil::Date};
-use sqlx::{Sqlite, SqlitePool, query::QueryAs, sqlite::SqliteArguments};
-
-use crate::datetime::{STABLE_FORMAT_DATEONLY, STABLE_FORMAT_LOCAL};
-use crate::event::ResolvedEventConditions;
-use crate::{Event, EventStatus, LooseDateTime, Pager};
+use std::num::NonZeroU32;
+
+use jiff::{Zoned, tz::TimeZone};
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, query::QueryAs, sqlite::SqliteArguments};
+
+use crate::db::meta::ItemMeta;
+use crate::db::{layered_categories, parse_stored, unix_seconds};
+use crate::event::{
+    ResolvedEventConditions, X_AIM_CHECKIN, X_AIM_CHECKIN_NOTE, X_AIM_SPLIT_FROM, last_day,
+};
+use crate::{CheckIn, Event, EventStatus, Kind, LooseDateTime, Pager, RuleSubject, Rules};
+
+/// The hot and archived events together, for listings that include the archive.
+const ALL_EVENTS: &str = "\
+(SELECT uid, calendar_id, summary, description, status, start, end, split_from, location,
+        organizer, url, related_to, categories, transparent, rule_categories, rule_color,
+        rule_transparent, checkin, checkin_note, start_utc, end_utc
+ FROM events
+ UNION ALL
+ SELECT uid, calendar_id, summary, description, status, start, end, split_from, location,
+        organizer, url, related_to, categories, transparent, rule_categories, rule_color,
+        rule_transparent, checkin, checkin_note, start_utc, end_utc
+ FROM events_archive) AS events";
 
 #[derive(Debug, Clone)]
 pub struct Events {
     pool: SqlitePool,
+    tz: TimeZone,
 }
 
 impl Events {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, tz: TimeZone) -> Self {
+        Self { pool, tz }
     }
 
     pub async fn upsert(&self, event: EventRecord) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::upsert_with(&mut conn, &event, &self.tz).await
+    }
+
+    /// Upserts the event on `conn`, such as within a transaction, resolving its floating
+    /// times and dates in `tz`.
+    ///
+    /// An archived event is left in the archive if it is unchanged, and moved back otherwise. Its
+    /// custom metadata and what the rules add to it are replaced either way.
+    pub async fn upsert_with(
+        conn: &mut SqliteConnection,
+        event: &EventRecord,
+        tz: &TimeZone,
+    ) -> Result<(), sqlx::Error> {
+        const UNARCHIVE: &str = "\
+DELETE FROM events_archive
+WHERE uid = ? AND NOT (calendar_id IS ? AND summary IS ? AND description IS ? AND status IS ?
+                       AND start IS ? AND end IS ? AND split_from IS ? AND location IS ?
+                       AND organizer IS ? AND url IS ? AND related_to IS ? AND categories IS ?
+                       AND transparent IS ? AND checkin IS ? AND checkin_note IS ?);
+";
+        const ARCHIVED: &str = "SELECT EXISTS (SELECT 1 FROM events_archive WHERE uid = ?);";
         const SQL: &str = "\
-INSERT INTO events (uid, calendar_id, summary, description, status, start, end)
-VALUES (?, ?, ?, ?, ?, ?, ?)
+INSERT INTO events (uid, calendar_id, summary, description, status, start, end, split_from,
+                    location, organizer, url, related_to, categories, transparent,
+                    rule_categories, rule_color, rule_transparent, checkin, checkin_note,
+                    start_utc, end_utc)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 ON CONFLICT(uid) DO UPDATE SET
     calendar_id  = excluded.calendar_id,
     summary      = excluded.summary,
     description  = excluded.description,
     status       = excluded.status,
     start        = excluded.start,
-    end          = excluded.end;
+    end          = excluded.end,
+    split_from   = excluded.split_from,
+    location     = excluded.location,
+    organizer    = excluded.organizer,
+    url          = excluded.url,
+    related_to   = excluded.related_to,
+    categories   = excluded.categories,
+    transparent  = excluded.transparent,
+    rule_categories  = excluded.rule_categories,
+    rule_color       = excluded.rule_color,
+    rule_transparent = excluded.rule_transparent,
+    checkin      = excluded.checkin,
+    checkin_note = excluded.checkin_note,
+    start_utc    = excluded.start_utc,
+    end_utc      = excluded.end_utc;
 ";
 
+        ItemMeta::replace_with(&mut *conn, &event.uid, &event.meta).await?;
+        sqlx::query(UNARCHIVE)
+            .bind(&event.uid)
+            .bind(&event.calendar_id)
+            .bind(&event.summary)
+            .bind(&event.description)
+            .bind(&event.status)
+            .bind(&event.start)
+            .bind(&event.end)
+            .bind(&event.split_from)
+            .bind(&event.location)
+            .bind(&event.organizer)
+            .bind(&event.url)
+            .bind(&event.related_to)
+            .bind(&event.categories)
+            .bind(event.transparent)
+            .bind(&event.checkin)
+            .bind(&event.checkin_note)
+            .execute(&mut *conn)
+            .await?;
+        let archived: bool = sqlx::query_scalar(ARCHIVED)
+            .bind(&event.uid)
+            .fetch_one(&mut *conn)
+            .await?;
+        if archived {
+            return Self::set_rule_outcome_with(conn, true, event).await;
+        }
+
         sqlx::query(SQL)
             .bind(&event.uid)
             .bind(&event.calendar_id)
@@ -42,38 +126,150 @@ ON CONFLICT(uid) DO UPDATE SET
             .bind(&event.status)
             .bind(&event.start)
             .bind(&event.end)
-            .execute(&self.pool)
+            .bind(&event.split_from)
+            .bind(&event.location)
+            .bind(&event.organizer)
+            .bind(&event.url)
+            .bind(&event.related_to)
+            .bind(&event.categories)
+            .bind(event.transparent)
+            .bind(&event.rule_categories)
+            .bind(&event.rule_color)
+            .bind(event.rule_transparent)
+            .bind(&event.checkin)
+            .bind(&event.checkin_note)
+            .bind(unix_seconds(&event.start, tz))
+            .bind(end_seconds(&event.start, &event.end, tz))
+            .execute(conn)
             .await?;
 
         Ok(())
     }
 
+    /// Evaluates the rules again for the event with the UID, or all events if `None`, archived
+    /// ones included, returning how many changed.
+    pub async fn apply_rules(&self, rules: &Rules, uid: Option<&str>) -> Result<u64, sqlx::Error> {
+        const SQL: &str = "\
+SELECT uid, calendar_id, summary, description, status, start, end, split_from, location, organizer,
+       url, related_to, categories, transparent, rule_categories, rule_color, rule_transparent,
+       checkin, checkin_note
+FROM events
+WHERE ?1 IS NULL OR uid = ?1;
+";
+        const SQL_ARCHIVED: &str = "\
+SELECT uid, calendar_id, summary, description, status, start, end, split_from, location, organizer,
+       url, related_to, categories, transparent, rule_categories, rule_color, rule_transparent,
+       checkin, checkin_note
+FROM events_archive
+WHERE ?1 IS NULL OR uid = ?1;
+";
+
+        let mut changed = 0;
+        let mut tx = self.pool.begin().await?;
+        for (archived, sql) in [(false, SQL), (true, SQL_ARCHIVED)] {
+            let records: Vec<EventRecord> =
+                sqlx::query_as(sql).bind(uid).fetch_all(&mut *tx).await?;
+            for mut record in records {
+                if record.apply_rules(rules) {
+                    Self::set_rule_outcome_with(&mut tx, archived, &record).await?;
+                    changed += 1;
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(changed)
+    }
+
+    /// Writes what the rules add to the event, in the archive if `archived`.
+    async fn set_rule_outcome_with(
+        conn: &mut SqliteConnection,
+        archived: bool,
+        event: &EventRecord,
+    ) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+UPDATE events SET rule_categories = ?, rule_color = ?, rule_transparent = ? WHERE uid = ?;
+";
+        const SQL_ARCHIVED: &str = "\
+UPDATE events_archive SET rule_categories = ?, rule_color = ?, rule_transparent = ? WHERE uid = ?;
+";
+
+        let sql = if archived { SQL_ARCHIVED } else { SQL };
+        sqlx::query(sql)
+            .bind(&event.rule_categories)
+            .bind(&event.rule_color)
+            .bind(event.rule_transparent)
+            .bind(&event.uid)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Gets the event, from the archive if it was archived.
     pub async fn get(&self, uid: &str) -> Result<Option<EventRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT uid, calendar_id, summary, description, status, start, end
+SELECT uid, calendar_id, summary, description, status, start, end, split_from,
+       location, organizer, url, related_to, categories, transparent, rule_categories,
+       rule_color, rule_transparent, checkin, checkin_note
 FROM events
+WHERE uid = ?
+UNION ALL
+SELECT uid, calendar_id, summary, description, status, start, end, split_from,
+       location, organizer, url, related_to, categories, transparent, rule_categories,
+       rule_color, rule_transparent, checkin, checkin_note
+FROM events_archive
 WHERE uid = ?;
 ";
 
         sqlx::query_as(SQL)
+            .bind(uid)
             .bind(uid)
             .fetch_optional(&self.pool)
             .await
     }
 
+    /// Lists the events whose UID starts with the prefix, archived ones included.
+    pub async fn list_by_uid_prefix(&self, prefix: &str) -> Result<Vec<EventRecord>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT uid, calendar_id, summary, description, status, start, end, split_from,
+       location, organizer, url, related_to, categories, transparent, rule_categories,
+       rule_color, rule_transparent, checkin, checkin_note
+FROM events
+WHERE substr(uid, 1, length(?1)) = ?1
+UNION ALL
+SELECT uid, calendar_id, summary, description, status, start, end, split_from,
+       location, organizer, url, related_to, categories, transparent, rule_categories,
+       rule_color, rule_transparent, checkin, checkin_note
+FROM events_archive
+WHERE substr(uid, 1, length(?1)) = ?1
+ORDER BY uid;
+";
+
+        sqlx::query_as(SQL).bind(prefix).fetch_all(&self.pool).await
+    }
+
     pub async fn list(
         &self,
         conds: &ResolvedEventConditions,
         pager: &Pager,
     ) -> Result<Vec<EventRecord>, sqlx::Error> {
-        let mut sql = "\
-SELECT uid, calendar_id, summary, description, status, start, end
-FROM events
+        let mut sql = format!(
+            "\
+SELECT events.uid, events.calendar_id, events.summary, events.description, events.status,
+       events.start, events.end, events.split_from, events.location, events.organizer,
+       events.url, events.related_to, events.categories, events.transparent,
+       events.rule_categories, events.rule_color, events.rule_transparent, events.checkin,
+       events.checkin_note, short_ids.short_id
+FROM {}
 JOIN calendars ON calendars.id = events.calendar_id
-"
-        .to_string();
+LEFT JOIN short_ids ON short_ids.uid = events.uid
+",
+            Self::source(conds)
+        );
         sql += &Self::build_where(conds);
-        sql += "ORDER BY calendars.priority ASC, start ASC LIMIT ? OFFSET ?;";
+        // All-day events come first among those starting at the same time, such as midnight, and
+        // unscheduled events last
+        sql += "ORDER BY calendars.priority ASC, start_utc ASC NULLS LAST, \
+                length(events.start) != 10 ASC LIMIT ? OFFSET ?;";
 
         let mut executable = sqlx::query_as(sqlx::AssertSqlSafe(sql));
         executable = Self::bind_conditions(conds, executable);
@@ -90,7 +286,9 @@ JOIN calendars ON calendars.id = events.calendar_id
         summary: &str,
     ) -> Result<Option<EventRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT e.uid, e.calendar_id, e.summary, e.description, e.status, e.start, e.end
+SELECT e.uid, e.calendar_id, e.summary, e.description, e.status, e.start, e.end, e.split_from,
+       e.location, e.organizer, e.url, e.related_to, e.categories, e.transparent,
+       e.rule_categories, e.rule_color, e.rule_transparent, e.checkin, e.checkin_note
 FROM events e
 JOIN short_ids si ON e.uid = si.uid
 WHERE si.kind = 'event' AND e.summary = ?
@@ -103,10 +301,62 @@ LIMIT 1;
             .await
     }
 
+    /// The first event of the enabled calendars starting after the given time.
+    pub async fn next_after(&self, after: &Zoned) -> Result<Option<EventRecord>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT events.uid, events.calendar_id, events.summary, events.description, events.status,
+       events.start, events.end, events.split_from, events.location, events.organizer,
+       events.url, events.related_to, events.categories, events.transparent,
+       events.rule_categories, events.rule_color, events.rule_transparent, events.checkin,
+       events.checkin_note, short_ids.short_id
+FROM events
+JOIN calendars ON calendars.id = events.calendar_id
+LEFT JOIN short_ids ON short_ids.uid = events.uid
+WHERE calendars.enabled = 1 AND start_utc > ?
+ORDER BY start_utc ASC
+LIMIT 1;
+";
+        sqlx::query_as(SQL)
+            .bind(after.timestamp().as_second())
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Fills in the instants of the events without them, returning how many were filled in.
+    pub async fn backfill_instants(&self) -> Result<u64, sqlx::Error> {
+        const SELECT: &str = "\
+SELECT uid, start, end
+FROM events
+WHERE (start != '' AND start_utc IS NULL) OR (end != '' AND end_utc IS NULL);
+";
+        const UPDATE: &str = "UPDATE events SET start_utc = ?, end_utc = ? WHERE uid = ?;";
+
+        let rows: Vec<(String, String, String)> =
+            sqlx::query_as(SELECT).fetch_all(&self.pool).await?;
+        let mut tx = self.pool.begin().await?;
+        for (uid, start, end) in &rows {
+            sqlx::query(UPDATE)
+                .bind(unix_seconds(start, &self.tz))
+                .bind(end_seconds(start, end, &self.tz))
+                .bind(uid)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(rows.len() as u64)
+    }
+
+    /// Counts the events of each calendar, disabled ones included.
+    pub async fn count_by_calendar(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        const SQL: &str = "SELECT calendar_id, COUNT(*) FROM events GROUP BY calendar_id;";
+        sqlx::query_as(SQL).fetch_all(&self.pool).await
+    }
+
     pub async fn count(&self, conds: &ResolvedEventConditions) -> Result<i64, sqlx::Error> {
-        let mut sql =
-            "SELECT COUNT(*) FROM events JOIN calendars ON calendars.id = events.calendar_id"
-                .to_string();
+        let mut sql = format!(
+            "SELECT COUNT(*) FROM {} JOIN calendars ON calendars.id = events.calendar_id",
+            Self::source(conds)
+        );
         sql += &Self::build_where(conds);
         sql += ";";
 
@@ -117,23 +367,104 @@ LIMIT 1;
         Ok(row.0)
     }
 
+    /// Moves the events that ended before `cutoff` to the archive, returning how many were moved.
+    ///
+    /// Events without an end are taken to end when they start.
+    pub async fn archive_ended_before(
+        &self,
+        cutoff: &Zoned,
+        now: &Zoned,
+    ) -> Result<u64, sqlx::Error> {
+        const ARCHIVE: &str = "\
+INSERT OR REPLACE INTO events_archive (uid, calendar_id, summary, description, status, start, end,
+                                       split_from, location, organizer, url, related_to,
+                                       categories, transparent, rule_categories, rule_color,
+                                       rule_transparent, checkin, checkin_note, start_utc, end_utc,
+                                       archived_at)
+SELECT uid, calendar_id, summary, description, status, start, end, split_from, location, organizer,
+       url, related_to, categories, transparent, rule_categories, rule_color, rule_transparent,
+       checkin, checkin_note, start_utc, end_utc, ?
+FROM events
+WHERE COALESCE(end_utc, start_utc) < ?;
+";
+        const DELETE: &str = "DELETE FROM events WHERE COALESCE(end_utc, start_utc) < ?;";
+
+        let cutoff = cutoff.timestamp().as_second();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(ARCHIVE)
+            .bind(now.timestamp().to_string())
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        let moved = sqlx::query(DELETE)
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    /// Counts the archived events.
+    pub async fn count_archived(&self) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "SELECT COUNT(*) FROM events_archive;";
+        sqlx::query_scalar(SQL).fetch_one(&self.pool).await
+    }
+
+    /// Deletes the event, from the archive as well, with its custom metadata.
     pub async fn delete(&self, uid: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::delete_with(&mut tx, uid).await?;
+        tx.commit().await
+    }
+
+    /// Deletes the event with `conn`, such as within a transaction.
+    pub async fn delete_with(conn: &mut SqliteConnection, uid: &str) -> Result<(), sqlx::Error> {
         const SQL: &str = "DELETE FROM events WHERE uid = ?;";
-        sqlx::query(SQL).bind(uid).execute(&self.pool).await?;
-        Ok(())
+        const ARCHIVED: &str = "DELETE FROM events_archive WHERE uid = ?;";
+        sqlx::query(SQL).bind(uid).execute(&mut *conn).await?;
+        sqlx::query(ARCHIVED).bind(uid).execute(&mut *conn).await?;
+        ItemMeta::delete_with(conn, uid).await
+    }
+
+    /// The table to list from, with the archived events if asked for.
+    fn source(conds: &ResolvedEventConditions) -> &'static str {
+        if conds.include_archived {
+            ALL_EVENTS
+        } else {
+            "events"
+        }
     }
 
     fn build_where(conds: &ResolvedEventConditions) -> String {
         let mut where_clauses = vec!["calendars.enabled = 1"];
+        // Unscheduled events, without a start, are listed in any range rather than never
         if conds.start_before.is_some() {
-            where_clauses.push("start <= ?");
+            where_clauses.push("(start_utc <= ? OR events.start = '')");
         }
         if conds.end_after.is_some() {
-            where_clauses.push("(end >= ? OR end = ?)");
+            where_clauses.push("(end_utc >= ? OR events.start = '')");
         }
         if conds.calendar_id.is_some() {
             where_clauses.push("events.calendar_id = ?");
         }
+        if conds.related_to.is_some() {
+            where_clauses.push("events.related_to = ?");
+        }
+        if conds.unchecked {
+            where_clauses.push("events.checkin IS NULL");
+        }
+        // One key-value pair per condition, so all of them must match
+        where_clauses.extend(std::iter::repeat_n(
+            "EXISTS (SELECT 1 FROM item_meta AS m WHERE m.uid = events.uid AND m.key = ? AND m.value = ?)",
+            conds.meta.len(),
+        ));
+        // Likewise one category per condition, among the own ones and those added by rules
+        where_clauses.extend(std::iter::repeat_n(
+            "lower(?) IN (SELECT lower(value) FROM json_each(events.categories)
+                          UNION ALL SELECT lower(value) FROM json_each(events.rule_categories))",
+            conds.categories.len(),
+        ));
 
         format!(" WHERE {} ", where_clauses.join(" AND "))
     }
@@ -143,20 +474,41 @@ LIMIT 1;
         mut query: QueryAs<'a, Sqlite, O, SqliteArguments>,
     ) -> QueryAs<'a, Sqlite, O, SqliteArguments> {
         if let Some(ref start_before) = conds.start_before {
-            query = query.bind(format_dt(start_before));
+            query = query.bind(start_before.timestamp().as_second());
         }
         if let Some(ref end_after) = conds.end_after {
-            query = query
-                .bind(format_dt(end_after))
-                .bind(format_date(end_after.date()));
+            query = query.bind(end_after.timestamp().as_second());
         }
         if let Some(ref calendar_id) = conds.calendar_id {
             query = query.bind(calendar_id);
         }
+        if let Some(ref related_to) = conds.related_to {
+            query = query.bind(related_to);
+        }
+        for (key, value) in &conds.meta {
+            query = query.bind(key).bind(value);
+        }
+        for category in &conds.categories {
+            query = query.bind(category);
+        }
         query
     }
 }
 
+/// Seconds since the Unix epoch of the end of an event as stored, the end of its last day for an
+/// end date, which is exclusive, so that an all-day event is not listed on the day after it.
+fn end_seconds(start: &str, end: &str, tz: &TimeZone) -> Option<i64> {
+    let end = parse_stored(end)?;
+    let timestamp = match end {
+        LooseDateTime::DateOnly(_) => {
+            let last = last_day(parse_stored(start).as_ref(), Some(&end))?;
+            LooseDateTime::DateOnly(last).to_end_timestamp_in(tz)?
+        }
+        _ => end.to_timestamp_in(tz)?,
+    };
+    Some(timestamp.as_second())
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct EventRecord {
     uid: String,
@@ -165,12 +517,42 @@ pub struct EventRecord {
     status: String,
     start: String,
     end: String,
+    split_from: Option<String>,
+    /// Where the event takes place, empty if unset.
+    location: String,
+    /// Address of the organizer, empty if unset.
+    organizer: String,
+    /// URL of the event, empty if unset.
+    url: String,
+    /// UID of the item the event relates to, see [`Event::related_to`].
+    related_to: Option<String>,
+    /// Categories of the event in its store, as a JSON array.
+    categories: String,
+    /// Whether the event is transparent in its store.
+    transparent: bool,
+    /// Categories added by the rules, as a JSON array.
+    rule_categories: String,
+    /// Color tag set by the rules.
+    rule_color: Option<String>,
+    /// Whether the rules made the event transparent.
+    rule_transparent: bool,
+    /// Attendance recorded by the check-in, see [`Event::check_in`].
+    checkin: Option<String>,
+    /// Note of the check-in.
+    checkin_note: Option<String>,
     /// Calendar ID for this event.
     pub calendar_id: String,
+    /// Short ID, when the query joins one in.
+    #[sqlx(default)]
+    short_id: Option<NonZeroU32>,
+    /// Custom metadata to mirror into the cache, never read back with the record.
+    #[sqlx(skip)]
+    meta: Vec<(String, String)>,
 }
 
 impl EventRecord {
     pub fn from_event(uid: &str, event: &impl Event, calendar_id: &str) -> Self {
+        let check_in = event.check_in();
         Self {
             uid: uid.to_string(),
             calendar_id: calendar_id.to_string(),
@@ -182,9 +564,73 @@ impl EventRecord {
             status: event.status().map(|s| s.to_string()).unwrap_or_default(),
             start: event.start().map(|a| a.format_stable()).unwrap_or_default(),
             end: event.end().map(|a| a.format_stable()).unwrap_or_default(),
+            split_from: event.split_from().map(|a| a.to_string()),
+            location: event.location().unwrap_or_default().to_string(),
+            organizer: event.organizer().unwrap_or_default().to_string(),
+            url: event.url().unwrap_or_default().to_string(),
+            related_to: event.related_to().map(|r| r.to_string()),
+            categories: serde_json::to_string(&event.categories()).unwrap_or_default(),
+            transparent: event.transparent(),
+            rule_categories: "[]".to_string(),
+            rule_color: None,
+            rule_transparent: false,
+            checkin: check_in.as_ref().map(|c| c.attendance.to_string()),
+            checkin_note: check_in.and_then(|c| c.note),
+            short_id: None,
+            meta: event.meta(),
         }
     }
 
+    /// Names of the properties cached differently in `other`, ordered by name, such as
+    /// `SUMMARY`. What the rules add is left out, as it is not a property of the event.
+    pub fn changed_properties(&self, other: &Self) -> Vec<&'static str> {
+        let mut changed: Vec<_> = [
+            ("CATEGORIES", self.categories != other.categories),
+            ("DESCRIPTION", self.description != other.description),
+            ("DTEND", self.end != other.end),
+            ("DTSTART", self.start != other.start),
+            ("LOCATION", self.location != other.location),
+            ("ORGANIZER", self.organizer != other.organizer),
+            ("RELATED-TO", self.related_to != other.related_to),
+            ("STATUS", self.status != other.status),
+            ("SUMMARY", self.summary != other.summary),
+            ("TRANSP", self.transparent != other.transparent),
+            ("URL", self.url != other.url),
+            (X_AIM_CHECKIN, self.checkin != other.checkin),
+            (X_AIM_CHECKIN_NOTE, self.checkin_note != other.checkin_note),
+            (X_AIM_SPLIT_FROM, self.split_from != other.split_from),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect();
+        changed.sort_unstable();
+        changed
+    }
+
+    /// The properties of the event that rules match on.
+    pub fn rule_subject(&self) -> RuleSubject<'_> {
+        RuleSubject {
+            kind: Kind::Event,
+            summary: &self.summary,
+            calendar_id: &self.calendar_id,
+            location: &self.location,
+            organizer: &self.organizer,
+        }
+    }
+
+    /// Sets what the rules add to the event, returning whether it changed.
+    pub fn apply_rules(&mut self, rules: &Rules) -> bool {
+        let outcome = rules.evaluate(&self.rule_subject());
+        let categories = serde_json::to_string(&outcome.categories).unwrap_or_default();
+        let changed = categories != self.rule_categories
+            || outcome.color != self.rule_color
+            || outcome.transparent != self.rule_transparent;
+        self.rule_categories = categories;
+        self.rule_color = outcome.color;
+        self.rule_transparent = outcome.transparent;
+        changed
+    }
+
     #[allow(dead_code)]
     pub fn calendar_id(&self) -> &str {
         &self.calendar_id
@@ -192,6 +638,10 @@ impl EventRecord {
 }
 
 impl Event for EventRecord {
+    fn short_id(&self) -> Option<NonZeroU32> {
+        self.short_id
+    }
+
     fn uid(&self) -> Cow<'_, str> {
         (&self.uid).into()
     }
@@ -215,14 +665,49 @@ impl Event for EventRecord {
     fn status(&self) -> Option<EventStatus> {
         self.status.as_str().parse().ok()
     }
-}
 
-fn format_date(date: Date) -> String {
-    date.strftime(STABLE_FORMAT_DATEONLY).to_string()
-}
+    fn location(&self) -> Option<Cow<'_, str>> {
+        (!self.location.is_empty()).then_some(self.location.as_str().into())
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        (!self.organizer.is_empty()).then_some(self.organizer.as_str().into())
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        (!self.url.is_empty()).then_some(self.url.as_str().into())
+    }
+
+    fn categories(&self) -> Vec<String> {
+        layered_categories(&self.categories, &self.rule_categories)
+    }
+
+    fn transparent(&self) -> bool {
+        self.transparent || self.rule_transparent
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.rule_color.as_deref().map(Into::into)
+    }
+
+    fn split_from(&self) -> Option<Cow<'_, str>> {
+        self.split_from.as_deref().map(Into::into)
+    }
 
-fn format_dt(dt: &Zoned) -> String {
-    dt.strftime(STABLE_FORMAT_LOCAL).to_string()
+    fn check_in(&self) -> Option<CheckIn> {
+        Some(CheckIn {
+            attendance: self.checkin.as_deref()?.parse().ok()?,
+            note: self.checkin_note.clone(),
+        })
+    }
+
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        self.related_to.as_deref().map(Into::into)
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        Some(self.calendar_id.as_str().into())
+    }
 }
 
 #[cfg(test)]
@@ -231,10 +716,11 @@ mod tests {
     use jiff::tz::TimeZone;
 
     use super::*;
+    use crate::Attendance;
 
-    /// Test helper to create a test database
+    /// Test helper to create a test database, with floating times in UTC
     async fn setup_test_db() -> crate::db::Db {
-        crate::db::Db::open(None)
+        crate::db::Db::open_in(None, TimeZone::UTC)
             .await
             .expect("Failed to create test database")
     }
@@ -380,6 +866,11 @@ mod tests {
             start_before: None,
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 10,
@@ -391,6 +882,189 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn events_list_filters_unchecked_events() {
+        // Arrange
+        let db = setup_test_db().await;
+        let check_in = CheckIn {
+            attendance: Attendance::Skipped,
+            note: Some("Overslept".to_string()),
+        };
+        for event in [
+            test_event("event-1", "Standup").with_check_in(check_in.clone()),
+            test_event("event-2", "Retro"),
+        ] {
+            db.events
+                .upsert(EventRecord::from_event(&event.uid, &event, "default"))
+                .await
+                .unwrap();
+        }
+
+        // Act
+        let mut conds = all_conditions(false);
+        conds.unchecked = true;
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let results = db.events.list(&conds, &pager).await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = results.iter().map(Event::uid).collect();
+        assert_eq!(uids, ["event-2"]);
+        let checked = db.events.get("event-1").await.unwrap().unwrap();
+        assert_eq!(checked.check_in(), Some(check_in));
+    }
+
+    #[tokio::test]
+    #[expect(clippy::indexing_slicing)]
+    async fn events_list_filters_by_related_to() {
+        // Arrange
+        let db = setup_test_db().await;
+        for event in [
+            test_event("block-1", "[Plan] Write report").with_related_to("todo-1"),
+            test_event("block-2", "[Plan] Review").with_related_to("todo-2"),
+            test_event("event-3", "Standup"),
+        ] {
+            db.events
+                .upsert(EventRecord::from_event(&event.uid, &event, "default"))
+                .await
+                .unwrap();
+        }
+
+        // Act
+        let mut conds = all_conditions(false);
+        conds.related_to = Some("todo-1".to_string());
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let results = db.events.list(&conds, &pager).await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = results.iter().map(Event::uid).collect();
+        assert_eq!(uids, ["block-1"]);
+        assert_eq!(results[0].related_to().as_deref(), Some("todo-1"));
+        let unrelated = db.events.get("event-3").await.unwrap().unwrap();
+        assert_eq!(unrelated.related_to(), None);
+    }
+
+    #[tokio::test]
+    #[expect(clippy::indexing_slicing)]
+    async fn events_upsert_keeps_url() {
+        // Arrange
+        let db = setup_test_db().await;
+        let event = test_event("event-1", "Planning").with_url("https://example.com/ticket/1");
+        db.events
+            .upsert(EventRecord::from_event("event-1", &event, "default"))
+            .await
+            .unwrap();
+
+        // Act
+        let retrieved = db.events.get("event-1").await.unwrap().unwrap();
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let listed = db
+            .events
+            .list(&all_conditions(false), &pager)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            retrieved.url().as_deref(),
+            Some("https://example.com/ticket/1")
+        );
+        assert_eq!(listed[0].url(), retrieved.url());
+        let other = test_event("event-2", "Lunch");
+        let other = EventRecord::from_event("event-2", &other, "default");
+        assert_eq!(other.url(), None);
+    }
+
+    #[tokio::test]
+    async fn events_list_all_day_events_first_and_not_after_their_last_day() {
+        // Arrange
+        let db = setup_test_db().await;
+        let tz = TimeZone::system();
+        let at = |day: i8, hour: i8| civil::date(2025, 6, day).at(hour, 0, 0, 0);
+        let midnight = test_event("midnight", "Deploy")
+            .with_start(LooseDateTime::Local(
+                at(10, 0).to_zoned(tz.clone()).unwrap(),
+            ))
+            .with_end(LooseDateTime::Local(
+                at(10, 1).to_zoned(tz.clone()).unwrap(),
+            ));
+        let holiday = test_event("holiday", "Holiday")
+            .with_start(LooseDateTime::DateOnly(civil::date(2025, 6, 10)))
+            .with_end(LooseDateTime::DateOnly(civil::date(2025, 6, 11)));
+        for event in [&midnight, &holiday] {
+            db.events
+                .upsert(EventRecord::from_event(&event.uid, event, "default"))
+                .await
+                .unwrap();
+        }
+        let day = |day: i8| ResolvedEventConditions {
+            start_before: Some(at(day, 23).to_zoned(tz.clone()).unwrap()),
+            end_after: Some(at(day, 0).to_zoned(tz.clone()).unwrap()),
+            ..all_conditions(false)
+        };
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+
+        // Act
+        let june_10 = db.events.list(&day(10), &pager).await.unwrap();
+        let june_11 = db.events.list(&day(11), &pager).await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = june_10.iter().map(Event::uid).collect();
+        assert_eq!(uids, ["holiday", "midnight"]);
+        assert!(june_11.is_empty(), "the end date is exclusive");
+    }
+
+    #[tokio::test]
+    async fn events_list_includes_assigned_short_ids() {
+        // Arrange
+        let db = setup_test_db().await;
+        for uid in ["event-1", "event-2"] {
+            let event = test_event(uid, "Event");
+            db.events
+                .upsert(EventRecord::from_event(uid, &event, "default"))
+                .await
+                .unwrap();
+        }
+        let short_id = db
+            .short_ids
+            .get_or_assign_short_id("event-2", Kind::Event)
+            .await
+            .unwrap();
+
+        // Act
+        let conds = ResolvedEventConditions {
+            start_before: None,
+            end_after: None,
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
+        };
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let results = db.events.list(&conds, &pager).await.unwrap();
+
+        // Assert
+        let short_ids: Vec<_> = results.iter().map(|e| (e.uid(), e.short_id())).collect();
+        assert!(short_ids.contains(&("event-1".into(), None)));
+        assert!(short_ids.contains(&("event-2".into(), Some(short_id))));
+    }
+
     #[tokio::test]
     #[expect(clippy::indexing_slicing)]
     async fn events_list_filters_by_start_before() {
@@ -428,6 +1102,11 @@ mod tests {
             start_before: Some(cutoff),
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 10,
@@ -440,6 +1119,98 @@ mod tests {
         assert_eq!(results[0].uid(), "event-1");
     }
 
+    #[tokio::test]
+    async fn events_list_compares_instants_across_forms() {
+        // Arrange
+        let tz = TimeZone::fixed(jiff::tz::offset(8));
+        let db = crate::db::Db::open_in(None, tz).await.unwrap();
+        let floating = test_event("event-1", "Floating").with_start(LooseDateTime::Floating(
+            civil::date(2025, 3, 10).at(9, 0, 0, 0),
+        ));
+        let utc = test_event("event-2", "UTC").with_start(LooseDateTime::Local(
+            civil::date(2025, 3, 10)
+                .at(8, 0, 0, 0)
+                .to_zoned(TimeZone::UTC)
+                .unwrap(),
+        ));
+        for (uid, event) in [("event-1", &floating), ("event-2", &utc)] {
+            db.events
+                .upsert(EventRecord::from_event(uid, event, "default"))
+                .await
+                .unwrap();
+        }
+
+        // Act
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let mut conds = ResolvedEventConditions {
+            start_before: None,
+            end_after: None,
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
+        };
+        let all = db.events.list(&conds, &pager).await.unwrap();
+        conds.start_before = Some(
+            civil::date(2025, 3, 10)
+                .at(5, 0, 0, 0)
+                .to_zoned(TimeZone::UTC)
+                .unwrap(),
+        );
+        let early = db.events.list(&conds, &pager).await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = all.iter().map(|e| e.uid().to_string()).collect();
+        assert_eq!(uids, ["event-1", "event-2"]);
+        let uids: Vec<_> = early.iter().map(|e| e.uid().to_string()).collect();
+        assert_eq!(uids, ["event-1"]);
+    }
+
+    #[tokio::test]
+    async fn events_next_after_returns_the_first_later_event() {
+        // Arrange
+        let db = setup_test_db().await;
+        let at = |day| {
+            LooseDateTime::Local(
+                civil::date(2025, 1, day)
+                    .at(9, 0, 0, 0)
+                    .to_zoned(TimeZone::UTC)
+                    .unwrap(),
+            )
+        };
+        for (uid, day) in [("event-1", 10), ("event-2", 20), ("event-3", 16)] {
+            let event = test_event(uid, "Event").with_start(at(day));
+            db.events
+                .upsert(EventRecord::from_event(uid, &event, "default"))
+                .await
+                .unwrap();
+        }
+        let now = civil::date(2025, 1, 15)
+            .at(0, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+
+        // Act
+        let next = db.events.next_after(&now).await.unwrap();
+        let none = db
+            .events
+            .next_after(&now.checked_add(jiff::Span::new().days(10)).unwrap())
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            next.map(|e| e.uid().into_owned()).as_deref(),
+            Some("event-3")
+        );
+        assert!(none.is_none());
+    }
+
     #[tokio::test]
     #[expect(clippy::indexing_slicing)]
     async fn events_list_filters_by_both_conditions() {
@@ -497,6 +1268,11 @@ mod tests {
             start_before: Some(start_cutoff),
             end_after: Some(end_after),
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 10,
@@ -530,6 +1306,11 @@ mod tests {
             start_before: None,
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 3,
@@ -562,6 +1343,11 @@ mod tests {
             start_before: None,
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 10,
@@ -616,6 +1402,11 @@ mod tests {
             start_before: None,
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let pager = Pager {
             limit: 10,
@@ -651,6 +1442,11 @@ mod tests {
             start_before: None,
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let count = db.events.count(&conds).await.unwrap();
 
@@ -694,6 +1490,11 @@ mod tests {
             start_before: Some(cutoff),
             end_after: None,
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let count = db.events.count(&conds).await.unwrap();
 
@@ -759,6 +1560,11 @@ mod tests {
             start_before: None,
             end_after: Some(end_after),
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let count = db.events.count(&conds).await.unwrap();
 
@@ -776,7 +1582,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("event-1", crate::Kind::Event)
+            .get_or_assign_short_id("event-1", Kind::Event)
             .await
             .unwrap();
 
@@ -805,7 +1611,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("event-1", crate::Kind::Event)
+            .get_or_assign_short_id("event-1", Kind::Event)
             .await
             .unwrap();
 
@@ -831,7 +1637,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("event-1", crate::Kind::Event)
+            .get_or_assign_short_id("event-1", Kind::Event)
             .await
             .unwrap();
 
@@ -841,7 +1647,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("event-2", crate::Kind::Event)
+            .get_or_assign_short_id("event-2", Kind::Event)
             .await
             .unwrap();
 
@@ -909,10 +1715,140 @@ mod tests {
             start_before: Some(start_cutoff),
             end_after: Some(end_after),
             calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         };
         let count = db.events.count(&conds).await.unwrap();
 
         // Assert
         assert_eq!(count, 1);
     }
+
+    /// Inserts an event that ended in 2020 and one that ends in 2026, then archives the events
+    /// that ended before 2025.
+    async fn setup_archived_event(db: &crate::db::Db) {
+        let at = |year| {
+            LooseDateTime::Local(
+                civil::date(year, 1, 1)
+                    .at(9, 0, 0, 0)
+                    .to_zoned(TimeZone::UTC)
+                    .unwrap(),
+            )
+        };
+        let old = test_event("old", "Old")
+            .with_start(at(2020))
+            .with_end(at(2020));
+        let new = test_event("new", "New")
+            .with_start(at(2026))
+            .with_end(at(2026));
+        for (uid, event) in [("old", &old), ("new", &new)] {
+            db.events
+                .upsert(EventRecord::from_event(uid, event, "default"))
+                .await
+                .unwrap();
+        }
+
+        let cutoff = civil::date(2025, 1, 1).to_zoned(TimeZone::UTC).unwrap();
+        let moved = db
+            .events
+            .archive_ended_before(&cutoff, &cutoff)
+            .await
+            .unwrap();
+        assert_eq!(moved, 1);
+    }
+
+    fn all_conditions(include_archived: bool) -> ResolvedEventConditions {
+        ResolvedEventConditions {
+            start_before: None,
+            end_after: None,
+            calendar_id: None,
+            include_archived,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn events_archive_hides_past_events_from_listings() {
+        // Arrange
+        let db = setup_test_db().await;
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+
+        // Act
+        setup_archived_event(&db).await;
+
+        // Assert
+        let hot = db
+            .events
+            .list(&all_conditions(false), &pager)
+            .await
+            .unwrap();
+        let uids: Vec<_> = hot.iter().map(|e| e.uid().into_owned()).collect();
+        assert_eq!(uids, ["new"]);
+        let all = db.events.list(&all_conditions(true), &pager).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(db.events.count(&all_conditions(true)).await.unwrap(), 2);
+        assert_eq!(db.events.count_archived().await.unwrap(), 1);
+        assert!(db.events.get("old").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn events_upsert_keeps_unchanged_archived_event() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_event(&db).await;
+        let archived = db.events.get("old").await.unwrap().unwrap();
+
+        // Act
+        db.events.upsert(archived).await.unwrap();
+
+        // Assert
+        assert_eq!(db.events.count(&all_conditions(false)).await.unwrap(), 1);
+        assert_eq!(db.events.count_archived().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn events_upsert_unarchives_changed_event() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_event(&db).await;
+        let archived = db.events.get("old").await.unwrap().unwrap();
+        let changed = test_event("old", "Renamed")
+            .with_start(archived.start().unwrap())
+            .with_end(archived.end().unwrap());
+
+        // Act
+        db.events
+            .upsert(EventRecord::from_event("old", &changed, "default"))
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(db.events.count(&all_conditions(false)).await.unwrap(), 2);
+        assert_eq!(db.events.count_archived().await.unwrap(), 0);
+        let event = db.events.get("old").await.unwrap().unwrap();
+        assert_eq!(event.summary(), "Renamed");
+    }
+
+    #[tokio::test]
+    async fn events_delete_removes_archived_event() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_event(&db).await;
+
+        // Act
+        db.events.delete("old").await.unwrap();
+
+        // Assert
+        assert!(db.events.get("old").await.unwrap().is_none());
+        assert_eq!(db.events.count_archived().await.unwrap(), 0);
+    }
 }
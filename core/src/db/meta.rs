@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use sqlx::{SqliteConnection, SqlitePool};
+
+/// The custom metadata of events and todos, mirrored from their components.
+#[derive(Debug, Clone)]
+pub struct ItemMeta {
+    pool: SqlitePool,
+}
+
+impl ItemMeta {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Gets the metadata of the item, sorted by key.
+    pub async fn get(&self, uid: &str) -> Result<Vec<(String, String)>, sqlx::Error> {
+        const SQL: &str = "SELECT key, value FROM item_meta WHERE uid = ? ORDER BY key;";
+        sqlx::query_as(SQL).bind(uid).fetch_all(&self.pool).await
+    }
+
+    /// Replaces the metadata of the item on `conn`, such as within a transaction.
+    pub async fn replace_with(
+        conn: &mut SqliteConnection,
+        uid: &str,
+        meta: &[(String, String)],
+    ) -> Result<(), sqlx::Error> {
+        const INSERT: &str = "INSERT OR REPLACE INTO item_meta (uid, key, value) VALUES (?, ?, ?);";
+
+        Self::delete_with(&mut *conn, uid).await?;
+        for (key, value) in meta {
+            sqlx::query(INSERT)
+                .bind(uid)
+                .bind(key)
+                .bind(value)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the metadata of the item on `conn`.
+    pub async fn delete_with(conn: &mut SqliteConnection, uid: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "DELETE FROM item_meta WHERE uid = ?;";
+        sqlx::query(SQL).bind(uid).execute(conn).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests_utils::setup_test_db;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn item_meta_replace_overwrites_previous_rows() {
+        let db = setup_test_db().await;
+        let mut conn = db.pool.acquire().await.unwrap();
+        let meta = pairs(&[("ticket", "JIRA-1"), ("client", "Acme")]);
+        ItemMeta::replace_with(&mut conn, "todo-1", &meta)
+            .await
+            .unwrap();
+        ItemMeta::replace_with(&mut conn, "todo-2", &pairs(&[("ticket", "JIRA-2")]))
+            .await
+            .unwrap();
+
+        let meta = pairs(&[("ticket", "JIRA-123")]);
+        ItemMeta::replace_with(&mut conn, "todo-1", &meta)
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert_eq!(db.meta.get("todo-1").await.unwrap(), meta);
+        assert_eq!(
+            db.meta.get("todo-2").await.unwrap(),
+            pairs(&[("ticket", "JIRA-2")])
+        );
+    }
+
+    #[tokio::test]
+    async fn item_meta_get_sorts_by_key() {
+        let db = setup_test_db().await;
+        let mut conn = db.pool.acquire().await.unwrap();
+        let meta = pairs(&[("ticket", "JIRA-1"), ("client", "Acme")]);
+        ItemMeta::replace_with(&mut conn, "todo-1", &meta)
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert_eq!(
+            db.meta.get("todo-1").await.unwrap(),
+            pairs(&[("client", "Acme"), ("ticket", "JIRA-1")])
+        );
+        assert!(db.meta.get("missing").await.unwrap().is_empty());
+    }
+}
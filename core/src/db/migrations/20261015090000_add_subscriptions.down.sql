@@ -0,0 +1,3 @@
+-- Revert calendar subscription support
+
+DROP TABLE IF EXISTS subscriptions;
@@ -0,0 +1,12 @@
+-- Add calendar subscription support
+-- Each row caches the last successful download of a subscribed feed along
+-- with the validators used for conditional requests.
+
+CREATE TABLE subscriptions (
+    calendar_id TEXT PRIMARY KEY,  -- Owning calendar identifier
+    url TEXT NOT NULL,             -- Normalized feed URL
+    etag TEXT,                     -- ETag header of the last response
+    last_modified TEXT,            -- Last-Modified header of the last response
+    body TEXT NOT NULL,            -- Cached ICS content
+    fetched_at TEXT NOT NULL       -- Time of the last successful refresh
+);
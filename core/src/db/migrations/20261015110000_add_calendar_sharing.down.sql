@@ -0,0 +1,4 @@
+-- Revert calendar sharing details
+
+ALTER TABLE calendars DROP COLUMN read_only;
+ALTER TABLE calendars DROP COLUMN owner;
@@ -0,0 +1,5 @@
+-- Add sharing details of calendars owned by other principals
+-- Both columns are filled in from the server when a CalDAV calendar is synced.
+
+ALTER TABLE calendars ADD COLUMN owner TEXT;                        -- Owning principal, if shared
+ALTER TABLE calendars ADD COLUMN read_only INTEGER NOT NULL DEFAULT 0; -- Whether writes are refused
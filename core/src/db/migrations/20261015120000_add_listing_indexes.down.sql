@@ -0,0 +1,10 @@
+-- Revert listing indexes
+
+DROP INDEX IF EXISTS idx_events_end;
+DROP INDEX IF EXISTS idx_events_start;
+DROP INDEX IF EXISTS idx_events_calendar;
+
+DROP INDEX IF EXISTS idx_todos_priority;
+DROP INDEX IF EXISTS idx_todos_status;
+DROP INDEX IF EXISTS idx_todos_due;
+DROP INDEX IF EXISTS idx_todos_calendar;
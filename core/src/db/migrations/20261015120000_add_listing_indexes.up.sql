@@ -0,0 +1,10 @@
+-- Add indexes for the columns used to filter and sort listings
+
+CREATE INDEX idx_todos_calendar ON todos(calendar_id);
+CREATE INDEX idx_todos_due ON todos(due);
+CREATE INDEX idx_todos_status ON todos(status);
+CREATE INDEX idx_todos_priority ON todos(priority);
+
+CREATE INDEX idx_events_calendar ON events(calendar_id);
+CREATE INDEX idx_events_start ON events(start);
+CREATE INDEX idx_events_end ON events(end);
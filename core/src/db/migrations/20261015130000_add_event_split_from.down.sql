@@ -0,0 +1,3 @@
+-- Revert the series an event was split from
+
+ALTER TABLE events DROP COLUMN split_from;
@@ -0,0 +1,3 @@
+-- Add the series an event was split from, filled in from its X-AIM-SPLIT-FROM property
+
+ALTER TABLE events ADD COLUMN split_from TEXT; -- UID of the series, if split from one
@@ -0,0 +1,3 @@
+-- Revert the categories of todos
+
+ALTER TABLE todos DROP COLUMN categories;
@@ -0,0 +1,3 @@
+-- Add the categories of todos, filled in from their CATEGORIES property
+
+ALTER TABLE todos ADD COLUMN categories TEXT NOT NULL DEFAULT '[]'; -- JSON array of category names
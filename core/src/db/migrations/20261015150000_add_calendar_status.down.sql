@@ -0,0 +1,6 @@
+-- Revert the calendar choices and sync status
+
+DROP TABLE calendar_sync_status;
+
+ALTER TABLE calendars DROP COLUMN is_default;
+ALTER TABLE calendars DROP COLUMN user_enabled;
@@ -0,0 +1,11 @@
+-- Add the choices made with `aim calendar` and the outcome of the last syncs of each calendar
+
+ALTER TABLE calendars ADD COLUMN user_enabled INTEGER;                 -- Overrides `enabled` of the config, if set
+ALTER TABLE calendars ADD COLUMN is_default INTEGER NOT NULL DEFAULT 0; -- Overrides `default_calendar` of the config
+
+CREATE TABLE calendar_sync_status (
+    calendar_id TEXT PRIMARY KEY,
+    last_success_at TEXT,          -- When the last successful sync finished
+    last_error TEXT,               -- Message of the last failed sync, cleared by a successful one
+    last_error_at TEXT
+);
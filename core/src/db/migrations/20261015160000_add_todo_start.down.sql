@@ -0,0 +1,3 @@
+-- Revert the start of todos
+
+ALTER TABLE todos DROP COLUMN start;
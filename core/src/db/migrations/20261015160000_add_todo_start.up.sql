@@ -0,0 +1,3 @@
+-- Add the start of todos, filled in from their DTSTART property
+
+ALTER TABLE todos ADD COLUMN start TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,15 @@
+-- Revert the instants of starts, ends and dues
+
+DROP INDEX IF EXISTS idx_events_start_utc;
+DROP INDEX IF EXISTS idx_events_end_utc;
+DROP INDEX IF EXISTS idx_todos_start_utc;
+DROP INDEX IF EXISTS idx_todos_due_utc;
+
+CREATE INDEX idx_events_start ON events(start);
+CREATE INDEX idx_events_end ON events(end);
+CREATE INDEX idx_todos_due ON todos(due);
+
+ALTER TABLE events DROP COLUMN start_utc;
+ALTER TABLE events DROP COLUMN end_utc;
+ALTER TABLE todos DROP COLUMN start_utc;
+ALTER TABLE todos DROP COLUMN due_utc;
@@ -0,0 +1,17 @@
+-- Add the instants of starts, ends and dues as seconds since the Unix epoch, for range queries.
+-- They are NULL where the original value is empty, and filled in for existing rows by `Db::open`,
+-- which resolves floating times in the local time zone.
+
+ALTER TABLE events ADD COLUMN start_utc INTEGER;
+ALTER TABLE events ADD COLUMN end_utc INTEGER;
+ALTER TABLE todos ADD COLUMN start_utc INTEGER;
+ALTER TABLE todos ADD COLUMN due_utc INTEGER;
+
+DROP INDEX IF EXISTS idx_events_start;
+DROP INDEX IF EXISTS idx_events_end;
+DROP INDEX IF EXISTS idx_todos_due;
+
+CREATE INDEX idx_events_start_utc ON events(start_utc);
+CREATE INDEX idx_events_end_utc ON events(end_utc);
+CREATE INDEX idx_todos_start_utc ON todos(start_utc);
+CREATE INDEX idx_todos_due_utc ON todos(due_utc);
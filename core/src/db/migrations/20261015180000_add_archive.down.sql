@@ -0,0 +1,16 @@
+-- Revert the archive tables, moving the archived rows back
+
+INSERT OR IGNORE INTO events (uid, calendar_id, summary, description, status, start, end,
+                              split_from, start_utc, end_utc)
+SELECT uid, calendar_id, summary, description, status, start, end, split_from, start_utc, end_utc
+FROM events_archive;
+
+INSERT OR IGNORE INTO todos (uid, calendar_id, completed, description, percent, priority, status,
+                             summary, due, categories, start, start_utc, due_utc)
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, due,
+       categories, start, start_utc, due_utc
+FROM todos_archive;
+
+DROP TABLE IF EXISTS housekeeping;
+DROP TABLE IF EXISTS todos_archive;
+DROP TABLE IF EXISTS events_archive;
@@ -0,0 +1,40 @@
+-- Add archive tables for old completed todos and past events
+-- Archived rows are moved out of events and todos by housekeeping, so they stop slowing down
+-- listings. They keep the columns of the hot tables, plus the time they were archived.
+
+CREATE TABLE events_archive (
+    uid TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    description TEXT NOT NULL,
+    status TEXT NOT NULL,
+    start TEXT NOT NULL,
+    end TEXT NOT NULL,
+    split_from TEXT,
+    start_utc INTEGER,
+    end_utc INTEGER,
+    archived_at TEXT NOT NULL      -- Time the event was archived
+);
+
+CREATE TABLE todos_archive (
+    uid TEXT PRIMARY KEY,
+    calendar_id TEXT NOT NULL,
+    completed TEXT NOT NULL,
+    description TEXT NOT NULL,
+    percent INTEGER,
+    priority INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    due TEXT NOT NULL,
+    categories TEXT NOT NULL DEFAULT '[]',
+    start TEXT NOT NULL DEFAULT '',
+    start_utc INTEGER,
+    due_utc INTEGER,
+    archived_at TEXT NOT NULL      -- Time the todo was archived
+);
+
+-- The single row records the last housekeeping run, to rate-limit automatic runs
+CREATE TABLE housekeeping (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    last_run_at TEXT NOT NULL      -- Time of the last run
+);
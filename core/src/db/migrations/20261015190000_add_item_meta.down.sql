@@ -0,0 +1,4 @@
+-- Revert the custom metadata table
+
+DROP INDEX IF EXISTS idx_item_meta_key_value;
+DROP TABLE IF EXISTS item_meta;
@@ -0,0 +1,12 @@
+-- Add the custom metadata of events and todos
+-- Mirrors the X-AIM-META-<KEY> properties of the items, so listings can filter on them. The rows
+-- of an item are replaced whenever it is upserted, and are kept while it is archived.
+
+CREATE TABLE item_meta (
+    uid TEXT NOT NULL,             -- Event/todo UID
+    key TEXT NOT NULL,             -- Normalized key, lowercase letters, digits and dashes
+    value TEXT NOT NULL,
+    PRIMARY KEY (uid, key)
+);
+
+CREATE INDEX idx_item_meta_key_value ON item_meta (key, value);
@@ -0,0 +1,6 @@
+-- Revert the parent of todos
+
+DROP INDEX IF EXISTS idx_todos_parent;
+
+ALTER TABLE todos_archive DROP COLUMN parent;
+ALTER TABLE todos DROP COLUMN parent;
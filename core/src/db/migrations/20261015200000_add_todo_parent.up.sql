@@ -0,0 +1,8 @@
+-- Add the parent of todos
+-- Mirrors the RELATED-TO property of todos with the PARENT relationship, so listings can nest
+-- subtasks below their parent and count the completed ones.
+
+ALTER TABLE todos ADD COLUMN parent TEXT;
+ALTER TABLE todos_archive ADD COLUMN parent TEXT;
+
+CREATE INDEX idx_todos_parent ON todos(parent);
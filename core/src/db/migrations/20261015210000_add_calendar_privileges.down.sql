@@ -0,0 +1,11 @@
+-- Revert the privileges of calendars to whether they are read-only
+
+ALTER TABLE calendars ADD COLUMN read_only INTEGER NOT NULL DEFAULT 0;
+
+UPDATE calendars
+SET read_only = NOT (can_write_content OR can_bind OR can_unbind);
+
+ALTER TABLE calendars DROP COLUMN can_unbind;
+ALTER TABLE calendars DROP COLUMN can_bind;
+ALTER TABLE calendars DROP COLUMN can_write_content;
+ALTER TABLE calendars DROP COLUMN can_read;
@@ -0,0 +1,13 @@
+-- Replace whether calendars are read-only with the privileges of the current user
+-- The privileges are filled in from the current-user-privilege-set of CalDAV calendars when they
+-- are synced, and grant everything for other calendars.
+
+ALTER TABLE calendars ADD COLUMN can_read INTEGER NOT NULL DEFAULT 1;          -- May read items
+ALTER TABLE calendars ADD COLUMN can_write_content INTEGER NOT NULL DEFAULT 1; -- May edit items
+ALTER TABLE calendars ADD COLUMN can_bind INTEGER NOT NULL DEFAULT 1;          -- May create items
+ALTER TABLE calendars ADD COLUMN can_unbind INTEGER NOT NULL DEFAULT 1;        -- May delete items
+
+UPDATE calendars
+SET can_write_content = NOT read_only, can_bind = NOT read_only, can_unbind = NOT read_only;
+
+ALTER TABLE calendars DROP COLUMN read_only;
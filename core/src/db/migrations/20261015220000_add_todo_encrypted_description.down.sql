@@ -0,0 +1,4 @@
+-- Revert the encrypted description of todos
+
+ALTER TABLE todos_archive DROP COLUMN encrypted_description;
+ALTER TABLE todos DROP COLUMN encrypted_description;
@@ -0,0 +1,6 @@
+-- Add the encrypted description of todos
+-- Mirrors the X-AIM-ENCRYPTED-DESC property of todos, so their description can be decrypted for
+-- display without reading the store. The plain description column is empty for such todos.
+
+ALTER TABLE todos ADD COLUMN encrypted_description TEXT;
+ALTER TABLE todos_archive ADD COLUMN encrypted_description TEXT;
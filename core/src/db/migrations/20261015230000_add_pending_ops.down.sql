@@ -0,0 +1,4 @@
+-- Revert the operations staged for the stores
+
+DROP INDEX IF EXISTS idx_pending_ops_uid;
+DROP TABLE IF EXISTS pending_ops;
@@ -0,0 +1,15 @@
+-- Add the operations staged for the stores
+-- Writes that must not wait on their store, such as todos captured into the inbox, are cached
+-- right away and recorded here, to be pushed to the store of their calendar by the next sync.
+
+CREATE TABLE pending_ops (
+    id INTEGER PRIMARY KEY,        -- Order in which the operations were staged
+    uid TEXT NOT NULL,             -- Event/todo UID
+    calendar_id TEXT NOT NULL,     -- Calendar whose store the operation is pushed to
+    kind TEXT NOT NULL,            -- 'event' or 'todo'
+    operation TEXT NOT NULL,       -- 'create'
+    ics TEXT NOT NULL,             -- The component to push, as a VCALENDAR
+    created_at TEXT NOT NULL       -- Time the operation was staged
+);
+
+CREATE INDEX idx_pending_ops_uid ON pending_ops (uid);
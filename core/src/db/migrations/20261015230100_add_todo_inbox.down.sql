@@ -0,0 +1,4 @@
+-- Revert the inbox flag of todos
+
+ALTER TABLE todos_archive DROP COLUMN inbox;
+ALTER TABLE todos DROP COLUMN inbox;
@@ -0,0 +1,6 @@
+-- Add the inbox flag of todos
+-- Mirrors the X-AIM-INBOX property of todos captured with `aim in`, so the untriaged ones can be
+-- listed. Cleared once the todo gets a due, a priority or a category.
+
+ALTER TABLE todos ADD COLUMN inbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE todos_archive ADD COLUMN inbox INTEGER NOT NULL DEFAULT 0;
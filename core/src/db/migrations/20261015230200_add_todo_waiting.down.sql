@@ -0,0 +1,8 @@
+-- Revert the waiting state of todos
+
+ALTER TABLE todos_archive DROP COLUMN follow_up_utc;
+ALTER TABLE todos_archive DROP COLUMN follow_up;
+ALTER TABLE todos_archive DROP COLUMN waiting_on;
+ALTER TABLE todos DROP COLUMN follow_up_utc;
+ALTER TABLE todos DROP COLUMN follow_up;
+ALTER TABLE todos DROP COLUMN waiting_on;
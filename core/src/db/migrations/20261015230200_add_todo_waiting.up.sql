@@ -0,0 +1,11 @@
+-- Add the waiting state of todos
+-- Mirrors the X-AIM-WAITING-ON and X-AIM-FOLLOW-UP properties set by `aim wait`, so todos handed
+-- off to someone can be left out of listings until their follow-up, and searched by who they
+-- wait on. `waiting_on` is NULL for todos not waiting on anyone.
+
+ALTER TABLE todos ADD COLUMN waiting_on TEXT;
+ALTER TABLE todos ADD COLUMN follow_up TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos ADD COLUMN follow_up_utc INTEGER;
+ALTER TABLE todos_archive ADD COLUMN waiting_on TEXT;
+ALTER TABLE todos_archive ADD COLUMN follow_up TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos_archive ADD COLUMN follow_up_utc INTEGER;
@@ -0,0 +1,27 @@
+-- Revert the inputs and results of categorization rules
+
+ALTER TABLE todos_archive DROP COLUMN rule_priority;
+ALTER TABLE todos_archive DROP COLUMN rule_color;
+ALTER TABLE todos_archive DROP COLUMN rule_categories;
+ALTER TABLE todos_archive DROP COLUMN organizer;
+ALTER TABLE todos_archive DROP COLUMN location;
+ALTER TABLE todos DROP COLUMN rule_priority;
+ALTER TABLE todos DROP COLUMN rule_color;
+ALTER TABLE todos DROP COLUMN rule_categories;
+ALTER TABLE todos DROP COLUMN organizer;
+ALTER TABLE todos DROP COLUMN location;
+
+ALTER TABLE events_archive DROP COLUMN rule_transparent;
+ALTER TABLE events_archive DROP COLUMN rule_color;
+ALTER TABLE events_archive DROP COLUMN rule_categories;
+ALTER TABLE events_archive DROP COLUMN transparent;
+ALTER TABLE events_archive DROP COLUMN categories;
+ALTER TABLE events_archive DROP COLUMN organizer;
+ALTER TABLE events_archive DROP COLUMN location;
+ALTER TABLE events DROP COLUMN rule_transparent;
+ALTER TABLE events DROP COLUMN rule_color;
+ALTER TABLE events DROP COLUMN rule_categories;
+ALTER TABLE events DROP COLUMN transparent;
+ALTER TABLE events DROP COLUMN categories;
+ALTER TABLE events DROP COLUMN organizer;
+ALTER TABLE events DROP COLUMN location;
@@ -0,0 +1,31 @@
+-- Add the inputs and results of categorization rules
+-- `location`, `organizer`, and for events `categories` and `transparent`, mirror the properties of
+-- the items that rules match on. The `rule_*` columns hold what the rules of the config add on top
+-- of them, kept apart so the items in the stores are never changed and the results go away with
+-- the rule on `aim rules apply --all`.
+
+ALTER TABLE events ADD COLUMN location TEXT NOT NULL DEFAULT '';
+ALTER TABLE events ADD COLUMN organizer TEXT NOT NULL DEFAULT '';
+ALTER TABLE events ADD COLUMN categories TEXT NOT NULL DEFAULT '[]'; -- JSON array of category names
+ALTER TABLE events ADD COLUMN transparent INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE events ADD COLUMN rule_categories TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE events ADD COLUMN rule_color TEXT;
+ALTER TABLE events ADD COLUMN rule_transparent INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE events_archive ADD COLUMN location TEXT NOT NULL DEFAULT '';
+ALTER TABLE events_archive ADD COLUMN organizer TEXT NOT NULL DEFAULT '';
+ALTER TABLE events_archive ADD COLUMN categories TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE events_archive ADD COLUMN transparent INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE events_archive ADD COLUMN rule_categories TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE events_archive ADD COLUMN rule_color TEXT;
+ALTER TABLE events_archive ADD COLUMN rule_transparent INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE todos ADD COLUMN location TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos ADD COLUMN organizer TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos ADD COLUMN rule_categories TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE todos ADD COLUMN rule_color TEXT;
+ALTER TABLE todos ADD COLUMN rule_priority INTEGER NOT NULL DEFAULT 0; -- 0 when no rule sets a floor
+ALTER TABLE todos_archive ADD COLUMN location TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos_archive ADD COLUMN organizer TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos_archive ADD COLUMN rule_categories TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE todos_archive ADD COLUMN rule_color TEXT;
+ALTER TABLE todos_archive ADD COLUMN rule_priority INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Revert the check-in of events
+
+ALTER TABLE events_archive DROP COLUMN checkin_note;
+ALTER TABLE events_archive DROP COLUMN checkin;
+ALTER TABLE events DROP COLUMN checkin_note;
+ALTER TABLE events DROP COLUMN checkin;
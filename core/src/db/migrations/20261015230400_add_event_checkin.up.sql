@@ -0,0 +1,8 @@
+-- Add the check-in of events
+-- `checkin` mirrors the `X-AIM-CHECKIN` property of an event, `ATTENDED` or `SKIPPED`, and is NULL
+-- until the event is checked in. `checkin_note` mirrors `X-AIM-CHECKIN-NOTE`.
+
+ALTER TABLE events ADD COLUMN checkin TEXT;
+ALTER TABLE events ADD COLUMN checkin_note TEXT;
+ALTER TABLE events_archive ADD COLUMN checkin TEXT;
+ALTER TABLE events_archive ADD COLUMN checkin_note TEXT;
@@ -0,0 +1,7 @@
+-- Revert the instant of the end of an all-day event to the start of its end date
+
+UPDATE events SET end_utc = NULL WHERE length(end) = 10;
+
+UPDATE events_archive
+SET end_utc = end_utc + 1
+WHERE length(start) = 10 AND length(end) = 10 AND end_utc IS NOT NULL;
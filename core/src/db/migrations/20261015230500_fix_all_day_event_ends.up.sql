@@ -0,0 +1,10 @@
+-- Make the instant of the end of an all-day event the end of its last day
+-- The end date of an all-day event is exclusive, so `end_utc` is the end of the day before it, and
+-- never before the end of the day it starts on. Hot rows are filled in again by `Db::open`, while
+-- archived ones are moved back by a second, or up to the end of their start day.
+
+UPDATE events SET end_utc = NULL WHERE length(end) = 10;
+
+UPDATE events_archive
+SET end_utc = MAX(end_utc - 1, start_utc + 86399)
+WHERE length(start) = 10 AND length(end) = 10 AND end_utc IS NOT NULL AND start_utc IS NOT NULL;
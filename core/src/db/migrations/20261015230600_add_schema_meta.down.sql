@@ -0,0 +1,3 @@
+-- Revert the version stamp of the schema
+
+DROP TABLE schema_meta;
@@ -0,0 +1,10 @@
+-- Add the version stamp of the schema
+-- `migration` is the latest migration applied, and `min_aim_version` the version of aim that
+-- applied it, the oldest one that understands the schema. Written by aim after migrating, so that
+-- older versions can refuse the database instead of failing mid-query.
+
+CREATE TABLE schema_meta (
+    id              INTEGER PRIMARY KEY CHECK (id = 0),
+    migration       INTEGER NOT NULL,
+    min_aim_version TEXT NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Revert the URL of events and todos
+
+ALTER TABLE todos_archive DROP COLUMN url;
+ALTER TABLE todos DROP COLUMN url;
+
+ALTER TABLE events_archive DROP COLUMN url;
+ALTER TABLE events DROP COLUMN url;
@@ -0,0 +1,8 @@
+-- Add the URL of events and todos
+-- Mirrors the URL property, such as a ticket or a meeting, for the detail views and `aim open`.
+
+ALTER TABLE events ADD COLUMN url TEXT NOT NULL DEFAULT '';
+ALTER TABLE events_archive ADD COLUMN url TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE todos ADD COLUMN url TEXT NOT NULL DEFAULT '';
+ALTER TABLE todos_archive ADD COLUMN url TEXT NOT NULL DEFAULT '';
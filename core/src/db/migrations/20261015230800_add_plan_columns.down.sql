@@ -0,0 +1,8 @@
+-- Revert the estimate of todos and the item events relate to
+
+DROP INDEX IF EXISTS idx_events_related_to;
+ALTER TABLE events_archive DROP COLUMN related_to;
+ALTER TABLE events DROP COLUMN related_to;
+
+ALTER TABLE todos_archive DROP COLUMN estimate;
+ALTER TABLE todos DROP COLUMN estimate;
@@ -0,0 +1,10 @@
+-- Add the estimate of todos and the item events relate to
+-- Mirrors X-AIM-ESTIMATE in seconds and RELATED-TO, so `aim plan` finds the blocks of a todo.
+
+ALTER TABLE todos ADD COLUMN estimate INTEGER;
+ALTER TABLE todos_archive ADD COLUMN estimate INTEGER;
+
+ALTER TABLE events ADD COLUMN related_to TEXT;
+ALTER TABLE events_archive ADD COLUMN related_to TEXT;
+
+CREATE INDEX idx_events_related_to ON events(related_to);
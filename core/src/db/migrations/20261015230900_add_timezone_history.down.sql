@@ -0,0 +1,3 @@
+-- Revert the history of the home time zone
+
+DROP TABLE IF EXISTS timezone_history;
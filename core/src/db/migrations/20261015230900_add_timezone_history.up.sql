@@ -0,0 +1,9 @@
+-- Add the history of the home time zone
+-- A row is added whenever aim starts in a home time zone other than the last one seen, so a
+-- move is noticed and the times read differently since can be audited.
+
+CREATE TABLE timezone_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timezone TEXT NOT NULL,        -- IANA name of the home time zone
+    seen_at TEXT NOT NULL          -- Time it was first seen
+);
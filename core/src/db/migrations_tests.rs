@@ -1254,3 +1254,714 @@ async fn migrations_large_dataset_performance() {
 
     println!("Migration of {batch_size} rows took: {duration:?}");
 }
+
+// =============================================================================
+// Normalized Instant Tests
+// =============================================================================
+
+const ADD_NORMALIZED_TIMES: &str = "20261015170000_add_normalized_times";
+
+/// Applies all migrations ordered before the given one.
+async fn apply_migrations_before(pool: &SqlitePool, migration_name: &str) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let dir = PathBuf::from(manifest_dir).join("src/db/migrations");
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            name.strip_suffix(".up.sql").map(str::to_string)
+        })
+        .filter(|name| name.as_str() < migration_name)
+        .collect();
+    names.sort();
+    for name in names {
+        apply_migration(pool, &name).await;
+    }
+}
+
+/// Inserts events and todos with dates and times in every form stored so far.
+async fn insert_mixed_forms(pool: &SqlitePool) {
+    for (uid, start, end) in [
+        ("floating", "2025-03-10T09:00:00", "2025-03-10T10:00:00"),
+        (
+            "offset",
+            "2025-03-10T08:00:00+0000",
+            "2025-03-10T09:00:00+0000",
+        ),
+        ("legacy-utc", "2025-03-10T07:30:00Z", ""),
+        ("date-only", "2025-03-11", "2025-03-12"),
+    ] {
+        sqlx::query(
+            "INSERT INTO events (uid, calendar_id, summary, description, status, start, end) \
+             VALUES (?, 'default', '', '', '', ?, ?)",
+        )
+        .bind(uid)
+        .bind(start)
+        .bind(end)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    for (uid, due) in [
+        ("date-only", "2025-03-10"),
+        ("floating", "2025-03-10T09:00:00"),
+        ("none", ""),
+    ] {
+        sqlx::query(
+            "INSERT INTO todos (uid, calendar_id, completed, description, percent, priority, \
+             status, summary, due) VALUES (?, 'default', '', '', NULL, 0, '', '', ?)",
+        )
+        .bind(uid)
+        .bind(due)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+async fn event_uids_ordered_by(pool: &SqlitePool, column: &str) -> Vec<String> {
+    let sql = format!("SELECT uid FROM events ORDER BY {column}");
+    sqlx::query_scalar(sqlx::AssertSqlSafe(sql))
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+fn unix_seconds(timestamp: &str) -> i64 {
+    timestamp.parse::<jiff::Timestamp>().unwrap().as_second()
+}
+
+#[tokio::test]
+async fn migrations_add_normalized_times_backfills_mixed_forms() {
+    use jiff::tz::{TimeZone, offset};
+
+    use crate::db::events::Events;
+    use crate::db::todos::Todos;
+
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_NORMALIZED_TIMES).await;
+    insert_mixed_forms(&pool).await;
+
+    // Comparing the strings puts the floating time after the instants it comes before
+    assert_eq!(
+        event_uids_ordered_by(&pool, "start").await,
+        ["legacy-utc", "offset", "floating", "date-only"]
+    );
+
+    apply_migration(&pool, ADD_NORMALIZED_TIMES).await;
+    let tz = TimeZone::fixed(offset(8));
+    let events = Events::new(pool.clone(), tz.clone());
+    let todos = Todos::new(pool.clone(), tz);
+    assert_eq!(events.backfill_instants().await.unwrap(), 4);
+    assert_eq!(todos.backfill_instants().await.unwrap(), 2);
+
+    assert_eq!(
+        event_uids_ordered_by(&pool, "start_utc").await,
+        ["floating", "legacy-utc", "offset", "date-only"]
+    );
+    let instants: Vec<(String, Option<i64>, Option<i64>)> =
+        sqlx::query_as("SELECT uid, start_utc, end_utc FROM events ORDER BY uid")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        instants,
+        [
+            (
+                "date-only".to_string(),
+                Some(unix_seconds("2025-03-10T16:00:00Z")),
+                Some(unix_seconds("2025-03-11T15:59:59Z"))
+            ),
+            (
+                "floating".to_string(),
+                Some(unix_seconds("2025-03-10T01:00:00Z")),
+                Some(unix_seconds("2025-03-10T02:00:00Z"))
+            ),
+            (
+                "legacy-utc".to_string(),
+                Some(unix_seconds("2025-03-10T07:30:00Z")),
+                None
+            ),
+            (
+                "offset".to_string(),
+                Some(unix_seconds("2025-03-10T08:00:00Z")),
+                Some(unix_seconds("2025-03-10T09:00:00Z"))
+            ),
+        ]
+    );
+
+    let dues: Vec<(String, Option<i64>)> =
+        sqlx::query_as("SELECT uid, due_utc FROM todos ORDER BY uid")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        dues,
+        [
+            (
+                "date-only".to_string(),
+                Some(unix_seconds("2025-03-10T15:59:59Z"))
+            ),
+            (
+                "floating".to_string(),
+                Some(unix_seconds("2025-03-10T01:00:00Z"))
+            ),
+            ("none".to_string(), None),
+        ]
+    );
+
+    // Every row has its instants now, so nothing is left to fill in
+    assert_eq!(events.backfill_instants().await.unwrap(), 0);
+    assert_eq!(todos.backfill_instants().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn migrations_add_normalized_times_down_drops_columns() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_NORMALIZED_TIMES).await;
+    insert_mixed_forms(&pool).await;
+
+    apply_migration(&pool, ADD_NORMALIZED_TIMES).await;
+    apply_down_migration(&pool, ADD_NORMALIZED_TIMES).await;
+
+    let events_columns = get_table_columns(&pool, "events").await;
+    assert!(!events_columns.iter().any(|c| c.name.ends_with("_utc")));
+    let todos_columns = get_table_columns(&pool, "todos").await;
+    assert!(!todos_columns.iter().any(|c| c.name.ends_with("_utc")));
+    assert_eq!(get_row_count(&pool, "events").await, 4);
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_ARCHIVE: &str = "20261015180000_add_archive";
+
+#[tokio::test]
+async fn migrations_add_archive_down_moves_archived_rows_back() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_ARCHIVE).await;
+    apply_migration(&pool, ADD_ARCHIVE).await;
+
+    sqlx::query(
+        "INSERT INTO events_archive (uid, calendar_id, summary, description, status, start, end, archived_at)
+         VALUES ('old-event', 'default', 'Old', '', '', '2020-01-01', '2020-01-02', '2026-01-01T00:00:00Z')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO todos_archive (uid, calendar_id, completed, description, priority, status, summary, due, archived_at)
+         VALUES ('old-todo', 'default', '2020-01-01T00:00:00+00:00', '', 0, 'COMPLETED', 'Old', '', '2026-01-01T00:00:00Z')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert_eq!(get_row_count(&pool, "events").await, 0);
+
+    apply_down_migration(&pool, ADD_ARCHIVE).await;
+
+    assert_table_not_exists(&pool, "events_archive").await;
+    assert_table_not_exists(&pool, "todos_archive").await;
+    assert_table_not_exists(&pool, "housekeeping").await;
+    assert_eq!(get_row_count(&pool, "events").await, 1);
+    assert_eq!(get_row_count(&pool, "todos").await, 1);
+}
+
+const ADD_ITEM_META: &str = "20261015190000_add_item_meta";
+
+#[tokio::test]
+async fn migrations_add_item_meta_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_ITEM_META).await;
+    apply_migration(&pool, ADD_ITEM_META).await;
+
+    assert_table_exists(&pool, "item_meta").await;
+    sqlx::query("INSERT INTO item_meta (uid, key, value) VALUES ('todo-1', 'ticket', 'JIRA-1')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let duplicate = sqlx::query(
+        "INSERT INTO item_meta (uid, key, value) VALUES ('todo-1', 'ticket', 'JIRA-2')",
+    )
+    .execute(&pool)
+    .await;
+    assert!(duplicate.is_err(), "A key should be set once per item");
+
+    apply_down_migration(&pool, ADD_ITEM_META).await;
+    assert_table_not_exists(&pool, "item_meta").await;
+}
+
+const ADD_TODO_PARENT: &str = "20261015200000_add_todo_parent";
+
+#[tokio::test]
+async fn migrations_add_todo_parent_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_TODO_PARENT).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_TODO_PARENT).await;
+
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        let parent = columns.iter().find(|c| c.name == "parent");
+        assert!(parent.is_some_and(|c| !c.not_null), "{table}.parent");
+    }
+    let parents: Vec<(Option<String>,)> = sqlx::query_as("SELECT parent FROM todos")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(!parents.is_empty());
+    assert!(parents.iter().all(|(p,)| p.is_none()));
+
+    apply_down_migration(&pool, ADD_TODO_PARENT).await;
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(
+            !columns.iter().any(|c| c.name == "parent"),
+            "{table}.parent"
+        );
+    }
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_CALENDAR_PRIVILEGES: &str = "20261015210000_add_calendar_privileges";
+
+#[tokio::test]
+async fn migrations_add_calendar_privileges_up_and_down() {
+    const INSERT: &str = "\
+        INSERT INTO calendars (id, name, kind, created_at, updated_at, read_only) \
+        VALUES (?, ?, 'caldav', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z', ?);";
+    const PRIVILEGES: &str = "\
+        SELECT id, can_read, can_write_content, can_bind, can_unbind FROM calendars \
+        WHERE kind = 'caldav' ORDER BY id;";
+
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_CALENDAR_PRIVILEGES).await;
+    for (id, read_only) in [("holidays", true), ("work", false)] {
+        sqlx::query(INSERT)
+            .bind(id)
+            .bind(id)
+            .bind(read_only)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    apply_migration(&pool, ADD_CALENDAR_PRIVILEGES).await;
+
+    let columns = get_table_columns(&pool, "calendars").await;
+    assert!(!columns.iter().any(|c| c.name == "read_only"));
+    for name in ["can_read", "can_write_content", "can_bind", "can_unbind"] {
+        let column = columns.iter().find(|c| c.name == name);
+        assert!(column.is_some_and(|c| c.not_null), "calendars.{name}");
+    }
+    let privileges: Vec<(String, bool, bool, bool, bool)> =
+        sqlx::query_as(PRIVILEGES).fetch_all(&pool).await.unwrap();
+    assert_eq!(
+        privileges,
+        [
+            ("holidays".to_string(), true, false, false, false),
+            ("work".to_string(), true, true, true, true),
+        ]
+    );
+
+    apply_down_migration(&pool, ADD_CALENDAR_PRIVILEGES).await;
+    let columns = get_table_columns(&pool, "calendars").await;
+    assert!(!columns.iter().any(|c| c.name.starts_with("can_")));
+    let read_only: Vec<(String, bool)> =
+        sqlx::query_as("SELECT id, read_only FROM calendars WHERE kind = 'caldav' ORDER BY id;")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        read_only,
+        [("holidays".to_string(), true), ("work".to_string(), false)]
+    );
+}
+
+const ADD_TODO_ENCRYPTED_DESCRIPTION: &str = "20261015220000_add_todo_encrypted_description";
+
+#[tokio::test]
+async fn migrations_add_todo_encrypted_description_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_TODO_ENCRYPTED_DESCRIPTION).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_TODO_ENCRYPTED_DESCRIPTION).await;
+
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        let column = columns.iter().find(|c| c.name == "encrypted_description");
+        assert!(
+            column.is_some_and(|c| !c.not_null),
+            "{table}.encrypted_description"
+        );
+    }
+    let sealed: Vec<(Option<String>,)> = sqlx::query_as("SELECT encrypted_description FROM todos")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(!sealed.is_empty());
+    assert!(sealed.iter().all(|(s,)| s.is_none()));
+
+    apply_down_migration(&pool, ADD_TODO_ENCRYPTED_DESCRIPTION).await;
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(
+            !columns.iter().any(|c| c.name == "encrypted_description"),
+            "{table}.encrypted_description"
+        );
+    }
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_PENDING_OPS: &str = "20261015230000_add_pending_ops";
+
+#[tokio::test]
+async fn migrations_add_pending_ops_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_PENDING_OPS).await;
+    apply_migration(&pool, ADD_PENDING_OPS).await;
+
+    assert_table_exists(&pool, "pending_ops").await;
+    for uid in ["todo-2", "todo-1"] {
+        sqlx::query(
+            "INSERT INTO pending_ops (uid, calendar_id, kind, operation, ics, created_at) \
+             VALUES (?, 'default', 'todo', 'create', '', '2026-10-15T00:00:00Z')",
+        )
+        .bind(uid)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let uids: Vec<(String,)> = sqlx::query_as("SELECT uid FROM pending_ops ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        uids,
+        [("todo-2".to_string(),), ("todo-1".to_string(),)],
+        "IDs should follow the staging order"
+    );
+
+    apply_down_migration(&pool, ADD_PENDING_OPS).await;
+    assert_table_not_exists(&pool, "pending_ops").await;
+}
+
+const ADD_TODO_INBOX: &str = "20261015230100_add_todo_inbox";
+
+#[tokio::test]
+async fn migrations_add_todo_inbox_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_TODO_INBOX).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_TODO_INBOX).await;
+
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        let column = columns.iter().find(|c| c.name == "inbox");
+        assert!(column.is_some_and(|c| c.not_null), "{table}.inbox");
+    }
+    let inbox: Vec<(bool,)> = sqlx::query_as("SELECT inbox FROM todos")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(!inbox.is_empty());
+    assert!(inbox.iter().all(|(i,)| !i), "Existing todos are triaged");
+
+    apply_down_migration(&pool, ADD_TODO_INBOX).await;
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(!columns.iter().any(|c| c.name == "inbox"), "{table}.inbox");
+    }
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_TODO_WAITING: &str = "20261015230200_add_todo_waiting";
+
+#[tokio::test]
+async fn migrations_add_todo_waiting_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_TODO_WAITING).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_TODO_WAITING).await;
+
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        for name in ["waiting_on", "follow_up", "follow_up_utc"] {
+            assert!(columns.iter().any(|c| c.name == name), "{table}.{name}");
+        }
+    }
+    let waiting: Vec<(Option<String>, String)> =
+        sqlx::query_as("SELECT waiting_on, follow_up FROM todos")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert!(!waiting.is_empty());
+    assert!(
+        waiting.iter().all(|(w, f)| w.is_none() && f.is_empty()),
+        "Existing todos are not waiting"
+    );
+
+    apply_down_migration(&pool, ADD_TODO_WAITING).await;
+    for table in ["todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(
+            !columns.iter().any(|c| c.name.contains("follow_up")),
+            "{table}"
+        );
+        assert!(!columns.iter().any(|c| c.name == "waiting_on"), "{table}");
+    }
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_RULE_COLUMNS: &str = "20261015230300_add_rule_columns";
+
+#[tokio::test]
+async fn migrations_add_rule_columns_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_RULE_COLUMNS).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_RULE_COLUMNS).await;
+
+    let event_columns = [
+        "location",
+        "organizer",
+        "categories",
+        "transparent",
+        "rule_categories",
+        "rule_color",
+        "rule_transparent",
+    ];
+    let todo_columns = [
+        "location",
+        "organizer",
+        "rule_categories",
+        "rule_color",
+        "rule_priority",
+    ];
+    for (table, names) in [
+        ("events", &event_columns[..]),
+        ("events_archive", &event_columns[..]),
+        ("todos", &todo_columns[..]),
+        ("todos_archive", &todo_columns[..]),
+    ] {
+        let columns = get_table_columns(&pool, table).await;
+        for name in names {
+            assert!(columns.iter().any(|c| c.name == *name), "{table}.{name}");
+        }
+    }
+    let events: Vec<(String, String, bool)> =
+        sqlx::query_as("SELECT categories, rule_categories, rule_transparent FROM events")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert!(!events.is_empty());
+    assert!(
+        events.iter().all(|(c, r, t)| c == "[]" && r == "[]" && !*t),
+        "Existing events are not categorized by rules"
+    );
+    let todos: Vec<(String, Option<String>, i64)> =
+        sqlx::query_as("SELECT rule_categories, rule_color, rule_priority FROM todos")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert!(!todos.is_empty());
+    assert!(
+        todos
+            .iter()
+            .all(|(r, c, p)| r == "[]" && c.is_none() && *p == 0),
+        "Existing todos are not categorized by rules"
+    );
+
+    apply_down_migration(&pool, ADD_RULE_COLUMNS).await;
+    for table in ["events", "events_archive", "todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(
+            !columns.iter().any(|c| c.name.starts_with("rule_")),
+            "{table}"
+        );
+        assert!(!columns.iter().any(|c| c.name == "location"), "{table}");
+        assert!(!columns.iter().any(|c| c.name == "organizer"), "{table}");
+    }
+    let columns = get_table_columns(&pool, "todos").await;
+    assert!(columns.iter().any(|c| c.name == "categories"));
+    assert_eq!(get_row_count(&pool, "todos").await, 3);
+}
+
+const ADD_EVENT_CHECKIN: &str = "20261015230400_add_event_checkin";
+
+#[tokio::test]
+async fn migrations_add_event_checkin_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_EVENT_CHECKIN).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_EVENT_CHECKIN).await;
+
+    for table in ["events", "events_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(columns.iter().any(|c| c.name == "checkin"), "{table}");
+        assert!(columns.iter().any(|c| c.name == "checkin_note"), "{table}");
+    }
+    let events: Vec<(Option<String>, Option<String>)> =
+        sqlx::query_as("SELECT checkin, checkin_note FROM events")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert!(!events.is_empty());
+    assert!(
+        events.iter().all(|(c, n)| c.is_none() && n.is_none()),
+        "Existing events are awaiting their check-in"
+    );
+
+    apply_down_migration(&pool, ADD_EVENT_CHECKIN).await;
+    for table in ["events", "events_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(
+            !columns.iter().any(|c| c.name.starts_with("checkin")),
+            "{table}"
+        );
+    }
+    assert_eq!(get_row_count(&pool, "events").await, 4);
+}
+
+const FIX_ALL_DAY_EVENT_ENDS: &str = "20261015230500_fix_all_day_event_ends";
+
+#[tokio::test]
+async fn migrations_fix_all_day_event_ends_up_and_down() {
+    use jiff::tz::{TimeZone, offset};
+
+    use crate::db::events::Events;
+
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, FIX_ALL_DAY_EVENT_ENDS).await;
+    insert_mixed_forms(&pool).await;
+    let events = Events::new(pool.clone(), TimeZone::fixed(offset(8)));
+    assert_eq!(events.backfill_instants().await.unwrap(), 4);
+    sqlx::query(
+        "INSERT INTO events_archive (uid, calendar_id, summary, description, status, start, end,
+                                     start_utc, end_utc, archived_at)
+         VALUES ('trip', 'default', 'Trip', '', '', '2020-01-01', '2020-01-03', 1577836800,
+                 1578009600, '2026-01-01T00:00:00Z'),
+                ('same-day', 'default', 'Day', '', '', '2020-01-01', '2020-01-01', 1577836800,
+                 1577836800, '2026-01-01T00:00:00Z')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    apply_migration(&pool, FIX_ALL_DAY_EVENT_ENDS).await;
+
+    // The hot rows are filled in again, the archived ones moved to the end of their last day
+    assert_eq!(events.backfill_instants().await.unwrap(), 1);
+    let end: Option<i64> = sqlx::query_scalar("SELECT end_utc FROM events WHERE uid = 'date-only'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(end, Some(unix_seconds("2025-03-11T15:59:59Z")));
+    let archived: Vec<(String, i64)> =
+        sqlx::query_as("SELECT uid, end_utc FROM events_archive ORDER BY uid")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        archived,
+        [
+            ("same-day".to_string(), unix_seconds("2020-01-01T23:59:59Z")),
+            ("trip".to_string(), unix_seconds("2020-01-02T23:59:59Z")),
+        ]
+    );
+
+    apply_down_migration(&pool, FIX_ALL_DAY_EVENT_ENDS).await;
+    let end: Option<i64> = sqlx::query_scalar("SELECT end_utc FROM events WHERE uid = 'date-only'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(end, None);
+    let trip: i64 = sqlx::query_scalar("SELECT end_utc FROM events_archive WHERE uid = 'trip'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(trip, unix_seconds("2020-01-03T00:00:00Z"));
+}
+
+const ADD_URL: &str = "20261015230700_add_url";
+
+#[tokio::test]
+async fn migrations_add_url_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_URL).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_URL).await;
+
+    for table in ["events", "events_archive", "todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(columns.iter().any(|c| c.name == "url"), "{table}");
+    }
+    let urls: Vec<String> = sqlx::query_scalar("SELECT url FROM events")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(!urls.is_empty());
+    assert!(
+        urls.iter().all(String::is_empty),
+        "Existing events have no URL"
+    );
+
+    apply_down_migration(&pool, ADD_URL).await;
+    for table in ["events", "events_archive", "todos", "todos_archive"] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(!columns.iter().any(|c| c.name == "url"), "{table}");
+    }
+    assert_eq!(get_row_count(&pool, "events").await, 4);
+}
+
+const ADD_PLAN_COLUMNS: &str = "20261015230800_add_plan_columns";
+
+#[tokio::test]
+async fn migrations_add_plan_columns_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_PLAN_COLUMNS).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_PLAN_COLUMNS).await;
+
+    for (table, column) in [
+        ("events", "related_to"),
+        ("events_archive", "related_to"),
+        ("todos", "estimate"),
+        ("todos_archive", "estimate"),
+    ] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(columns.iter().any(|c| c.name == column), "{table}");
+    }
+    let related: Vec<Option<String>> = sqlx::query_scalar("SELECT related_to FROM events")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(!related.is_empty());
+    assert!(
+        related.iter().all(Option::is_none),
+        "Existing events relate to nothing"
+    );
+
+    apply_down_migration(&pool, ADD_PLAN_COLUMNS).await;
+    for (table, column) in [("events", "related_to"), ("todos", "estimate")] {
+        let columns = get_table_columns(&pool, table).await;
+        assert!(!columns.iter().any(|c| c.name == column), "{table}");
+    }
+    assert_eq!(get_row_count(&pool, "events").await, 4);
+}
+
+const ADD_TIMEZONE_HISTORY: &str = "20261015230900_add_timezone_history";
+
+#[tokio::test]
+async fn migrations_add_timezone_history_up_and_down() {
+    let pool = create_pool_without_migrations().await;
+    apply_migrations_before(&pool, ADD_TIMEZONE_HISTORY).await;
+    insert_mixed_forms(&pool).await;
+    apply_migration(&pool, ADD_TIMEZONE_HISTORY).await;
+
+    assert_table_exists(&pool, "timezone_history").await;
+    assert_has_autoincrement(&pool, "timezone_history").await;
+    assert_eq!(get_row_count(&pool, "timezone_history").await, 0);
+
+    apply_down_migration(&pool, ADD_TIMEZONE_HISTORY).await;
+    assert_table_not_exists(&pool, "timezone_history").await;
+    assert_eq!(get_row_count(&pool, "events").await, 4);
+}
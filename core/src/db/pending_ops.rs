@@ -0,0 +1,205 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use jiff::Timestamp;
+use sqlx::{SqliteExecutor, SqlitePool};
+
+use crate::Kind;
+
+/// Writes staged in the cache, waiting to be pushed to the stores of their calendars by the next
+/// sync, so they never wait on a slow or unreachable store.
+#[derive(Debug, Clone)]
+pub struct PendingOps {
+    pool: SqlitePool,
+}
+
+impl PendingOps {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stages an operation on the item, returning its ID.
+    pub async fn insert(
+        &self,
+        uid: &str,
+        calendar_id: &str,
+        kind: Kind,
+        operation: PendingOperation,
+        ics: &str,
+    ) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "
+INSERT INTO pending_ops (uid, calendar_id, kind, operation, ics, created_at)
+VALUES (?, ?, ?, ?, ?, ?)
+RETURNING id;
+";
+
+        sqlx::query_scalar(SQL)
+            .bind(uid)
+            .bind(calendar_id)
+            .bind(kind.to_str_stable())
+            .bind(operation.as_str())
+            .bind(ics)
+            .bind(Timestamp::now().to_string())
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Lists the staged operations, in the order they were staged.
+    pub async fn list(&self) -> Result<Vec<PendingOpRecord>, sqlx::Error> {
+        const SQL: &str = "
+SELECT id, uid, calendar_id, kind, operation, ics
+FROM pending_ops
+ORDER BY id;
+";
+
+        sqlx::query_as(SQL).fetch_all(&self.pool).await
+    }
+
+    /// Gets the latest operation staged on the item.
+    pub async fn get_by_uid(&self, uid: &str) -> Result<Option<PendingOpRecord>, sqlx::Error> {
+        const SQL: &str = "
+SELECT id, uid, calendar_id, kind, operation, ics
+FROM pending_ops
+WHERE uid = ?
+ORDER BY id DESC
+LIMIT 1;
+";
+
+        sqlx::query_as(SQL)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Replaces the component of a staged operation, such as when the item is edited before it
+    /// was pushed.
+    pub async fn update_ics(&self, id: i64, ics: &str) -> Result<(), sqlx::Error> {
+        Self::update_ics_with(&self.pool, id, ics).await
+    }
+
+    /// Replaces the component of a staged operation with `executor`, such as within a transaction.
+    pub async fn update_ics_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        id: i64,
+        ics: &str,
+    ) -> Result<(), sqlx::Error> {
+        const SQL: &str = "UPDATE pending_ops SET ics = ? WHERE id = ?;";
+        sqlx::query(SQL)
+            .bind(ics)
+            .bind(id)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Removes a staged operation, once it was pushed.
+    pub async fn delete(&self, id: i64) -> Result<(), sqlx::Error> {
+        const SQL: &str = "DELETE FROM pending_ops WHERE id = ?;";
+        sqlx::query(SQL).bind(id).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Counts the staged operations.
+    pub async fn count(&self) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "SELECT COUNT(*) FROM pending_ops;";
+        sqlx::query_scalar(SQL).fetch_one(&self.pool).await
+    }
+}
+
+/// What a staged operation does to its item once pushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingOperation {
+    /// Creates the item in the store.
+    Create,
+}
+
+impl PendingOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PendingOperation::Create => "create",
+        }
+    }
+
+    pub fn parse(operation: &str) -> Option<Self> {
+        match operation {
+            "create" => Some(PendingOperation::Create),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingOpRecord {
+    pub id: i64,
+    pub uid: String,
+    pub calendar_id: String,
+    kind: String,
+    operation: String,
+    /// The component to push, as a `VCALENDAR`.
+    pub ics: String,
+}
+
+impl PendingOpRecord {
+    pub fn kind(&self) -> Option<Kind> {
+        Kind::parse_stable(&self.kind)
+    }
+
+    pub fn operation(&self) -> Option<PendingOperation> {
+        PendingOperation::parse(&self.operation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests_utils::setup_test_db;
+
+    #[tokio::test]
+    async fn pending_ops_list_in_staging_order() {
+        let db = setup_test_db().await;
+        let create = PendingOperation::Create;
+        db.pending_ops
+            .insert("todo-2", "work", Kind::Todo, create, "ICS 2")
+            .await
+            .unwrap();
+        db.pending_ops
+            .insert("todo-1", "home", Kind::Todo, create, "ICS 1")
+            .await
+            .unwrap();
+
+        let ops = db.pending_ops.list().await.unwrap();
+        let uids: Vec<_> = ops.iter().map(|op| op.uid.as_str()).collect();
+        assert_eq!(uids, ["todo-2", "todo-1"]);
+        let op = ops.last().unwrap();
+        assert_eq!(op.calendar_id, "home");
+        assert_eq!(op.kind(), Some(Kind::Todo));
+        assert_eq!(op.operation(), Some(PendingOperation::Create));
+        assert_eq!(db.pending_ops.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn pending_ops_update_and_delete() {
+        let db = setup_test_db().await;
+        let id = db
+            .pending_ops
+            .insert(
+                "todo-1",
+                "home",
+                Kind::Todo,
+                PendingOperation::Create,
+                "old",
+            )
+            .await
+            .unwrap();
+
+        db.pending_ops.update_ics(id, "new").await.unwrap();
+        let op = db.pending_ops.get_by_uid("todo-1").await.unwrap().unwrap();
+        assert_eq!(op.id, id);
+        assert_eq!(op.ics, "new");
+
+        db.pending_ops.delete(id).await.unwrap();
+        assert!(db.pending_ops.get_by_uid("todo-1").await.unwrap().is_none());
+        assert_eq!(db.pending_ops.count().await.unwrap(), 0);
+    }
+}
@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use sqlx::SqlitePool;
+use sqlx::{SqliteExecutor, SqlitePool};
 
 #[derive(Debug, Clone)]
 pub struct Resources {
@@ -20,6 +20,17 @@ impl Resources {
         calendar_id: &str,
         resource_id: &str,
         metadata: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        Self::insert_with(&self.pool, uid, calendar_id, resource_id, metadata).await
+    }
+
+    /// Inserts the resource with `executor`, such as within a transaction.
+    pub async fn insert_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        uid: &str,
+        calendar_id: &str,
+        resource_id: &str,
+        metadata: Option<&str>,
     ) -> Result<(), sqlx::Error> {
         const SQL: &str = "
 INSERT INTO resources (uid, calendar_id, resource_id, metadata)
@@ -34,7 +45,7 @@ ON CONFLICT(uid, calendar_id) DO UPDATE SET
             .bind(calendar_id)
             .bind(resource_id)
             .bind(metadata)
-            .execute(&self.pool)
+            .execute(executor)
             .await?;
 
         Ok(())
@@ -60,12 +71,21 @@ WHERE uid = ? AND calendar_id = ?;
 
     #[allow(dead_code)]
     pub async fn delete(&self, uid: &str, calendar_id: &str) -> Result<(), sqlx::Error> {
+        Self::delete_with(&self.pool, uid, calendar_id).await
+    }
+
+    /// Deletes the resource with `executor`, such as within a transaction.
+    pub async fn delete_with<'e>(
+        executor: impl SqliteExecutor<'e>,
+        uid: &str,
+        calendar_id: &str,
+    ) -> Result<(), sqlx::Error> {
         const SQL: &str = "DELETE FROM resources WHERE uid = ? AND calendar_id = ?;";
 
         sqlx::query(SQL)
             .bind(uid)
             .bind(calendar_id)
-            .execute(&self.pool)
+            .execute(executor)
             .await?;
 
         Ok(())
@@ -84,6 +104,31 @@ WHERE uid = ? AND calendar_id = ?;
 
         Ok(rows.into_iter().map(|r| r.0).collect())
     }
+
+    /// The IDs of the calendars holding a copy of `uid`.
+    pub async fn list_calendars_by_uid(&self, uid: &str) -> Result<Vec<String>, sqlx::Error> {
+        const SQL: &str = "SELECT calendar_id FROM resources WHERE uid = ? ORDER BY calendar_id;";
+
+        let rows: Vec<(String,)> = sqlx::query_as(SQL).bind(uid).fetch_all(&self.pool).await?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    pub async fn list_by_calendar(
+        &self,
+        calendar_id: &str,
+    ) -> Result<Vec<ResourceRecord>, sqlx::Error> {
+        const SQL: &str = "
+SELECT uid, calendar_id, resource_id, metadata
+FROM resources
+WHERE calendar_id = ?;
+";
+
+        sqlx::query_as(SQL)
+            .bind(calendar_id)
+            .fetch_all(&self.pool)
+            .await
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -256,4 +301,36 @@ mod tests {
         assert_eq!(metadata.etag, "\"abc123\"");
         assert_eq!(metadata.version, 1);
     }
+
+    #[tokio::test]
+    async fn resources_list_calendars_by_uid_lists_every_copy() {
+        let db = setup_test_db().await;
+
+        // Insert a todo first to satisfy FK constraint
+        let todo = crate::db::tests_utils::test_todo("test-uid", "Test Todo");
+        let todo_record = crate::db::todos::TodoRecord::from_todo("test-uid", &todo, "default");
+        db.todos.upsert(&todo_record).await.unwrap();
+
+        // NOTE: Due to migration design with dual FK constraints,
+        // we also need to insert into events table to satisfy both FKs
+        let event = crate::db::tests_utils::test_event("test-uid", "Test Event");
+        let event_record = crate::db::events::EventRecord::from_event("test-uid", &event, "team");
+        db.events.upsert(event_record).await.unwrap();
+
+        for calendar_id in ["team", "personal"] {
+            db.resources
+                .insert("test-uid", calendar_id, "/dav/test.ics", None)
+                .await
+                .unwrap();
+        }
+
+        let calendars = db
+            .resources
+            .list_calendars_by_uid("test-uid")
+            .await
+            .unwrap();
+        assert_eq!(calendars, ["personal", "team"]);
+        let calendars = db.resources.list_calendars_by_uid("missing").await.unwrap();
+        assert!(calendars.is_empty());
+    }
 }
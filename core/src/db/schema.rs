@@ -0,0 +1,374 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Version stamp of the database schema, so that a database written by a newer aim is refused
+//! up front rather than failing mid-query or being migrated into a hybrid.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use sqlx::SqlitePool;
+
+use crate::db::{Db, MIGRATOR};
+
+/// Version of this build, stamped on the schemas it migrates.
+const AIM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The schema a database was stamped with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaStamp {
+    /// Version of the latest migration applied.
+    pub migration: i64,
+    /// Version of the aim that applied it, `None` if the database has migrations but no stamp.
+    pub min_aim_version: Option<String>,
+}
+
+impl SchemaStamp {
+    /// The aim that created the schema, for messages.
+    pub fn created_by(&self) -> String {
+        match &self.min_aim_version {
+            Some(version) => format!("aim {version}"),
+            None => "a newer aim".to_string(),
+        }
+    }
+
+    /// Whether the schema has migrations this build does not know.
+    pub fn is_newer(&self) -> bool {
+        self.migration > Db::latest_schema_version()
+    }
+}
+
+/// Reads the stamp of the schema, falling back to the migrations applied for databases written
+/// before the stamp was added. `None` for a new database.
+pub async fn read(pool: &SqlitePool) -> Result<Option<SchemaStamp>, sqlx::Error> {
+    if has_table(pool, "schema_meta").await? {
+        const SQL: &str = "SELECT migration, min_aim_version FROM schema_meta WHERE id = 0;";
+        let row: Option<(i64, String)> = sqlx::query_as(SQL).fetch_optional(pool).await?;
+        if let Some((migration, min_aim_version)) = row {
+            return Ok(Some(SchemaStamp {
+                migration,
+                min_aim_version: Some(min_aim_version),
+            }));
+        }
+    }
+
+    if has_table(pool, "_sqlx_migrations").await? {
+        const SQL: &str = "SELECT MAX(version) FROM _sqlx_migrations WHERE success;";
+        let migration: Option<i64> = sqlx::query_scalar(SQL).fetch_one(pool).await?;
+        return Ok(migration.map(|migration| SchemaStamp {
+            migration,
+            min_aim_version: None,
+        }));
+    }
+    Ok(None)
+}
+
+/// Stamps the schema with the latest migration of this build, once it is migrated. The version
+/// of aim is only replaced along with the migration, so it stays the oldest that understands it.
+pub async fn write(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    const SQL: &str = "\
+INSERT INTO schema_meta (id, migration, min_aim_version)
+VALUES (0, ?, ?)
+ON CONFLICT(id) DO UPDATE SET
+    migration = excluded.migration,
+    min_aim_version = excluded.min_aim_version
+WHERE excluded.migration > schema_meta.migration;
+";
+    sqlx::query(SQL)
+        .bind(Db::latest_schema_version())
+        .bind(AIM_VERSION)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Whether this build checked and migrated the schema of the database at `filename` before, as
+/// recorded by [`record_checked`], so that it can be opened without checking again.
+///
+/// Another aim opening the database replaces the record with its own, and a database replaced by
+/// another file is told apart by its creation time, so either is checked again.
+pub fn is_checked(filename: &Path) -> bool {
+    let Some(expected) = checked_record(filename) else {
+        return false;
+    };
+    fs::read_to_string(checked_path(filename)).is_ok_and(|recorded| recorded == expected)
+}
+
+/// Records that this build checked and migrated the schema of the database at `filename`.
+pub fn record_checked(filename: &Path) {
+    let Some(record) = checked_record(filename) else {
+        return;
+    };
+    if let Err(e) = fs::write(checked_path(filename), record) {
+        tracing::debug!(path = %filename.display(), err = %e, "failed to record the schema check");
+    }
+}
+
+/// The file next to the database recording the aim that last checked its schema.
+fn checked_path(filename: &Path) -> PathBuf {
+    let mut path = filename.as_os_str().to_owned();
+    path.push(".checked");
+    PathBuf::from(path)
+}
+
+/// What the check of the database at `filename` by this build is recorded as, `None` if there
+/// is no such database.
+fn checked_record(filename: &Path) -> Option<String> {
+    let created = fs::metadata(filename)
+        .ok()?
+        .created()
+        .ok()
+        .and_then(|created| created.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |created| created.as_nanos());
+    Some(format!(
+        "aim {AIM_VERSION}\nmigration {}\ncreated {created}\n",
+        Db::latest_schema_version()
+    ))
+}
+
+/// The columns this build reads that the database lacks, as `table.column`.
+pub async fn missing_columns(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+    let reference = Db::connect_in_memory().await?;
+    MIGRATOR.run(&reference).await?;
+    let known = columns(&reference).await?;
+    reference.close().await;
+
+    let present = columns(pool).await?;
+    Ok(known.difference(&present).cloned().collect())
+}
+
+/// The columns of all tables of the database, as `table.column`.
+async fn columns(pool: &SqlitePool) -> Result<BTreeSet<String>, sqlx::Error> {
+    const SQL: &str = "\
+SELECT m.name || '.' || p.name
+FROM sqlite_master AS m
+JOIN pragma_table_info(m.name) AS p
+WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' AND m.name != '_sqlx_migrations';
+";
+    let columns: Vec<String> = sqlx::query_scalar(SQL).fetch_all(pool).await?;
+    Ok(columns.into_iter().collect())
+}
+
+async fn has_table(pool: &SqlitePool, name: &str) -> Result<bool, sqlx::Error> {
+    const SQL: &str =
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?);";
+    sqlx::query_scalar(SQL).bind(name).fetch_one(pool).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use jiff::tz::TimeZone;
+
+    use super::*;
+    use crate::AimError;
+    use crate::db::calendars::CalendarRecord;
+
+    async fn open(path: &Path) -> Result<Db, AimError> {
+        Db::open_in(Some(path), TimeZone::UTC).await
+    }
+
+    /// Makes the database look written by `aim 99.0.0`, with a migration unknown here.
+    async fn stamp_as_newer(db: &Db, path: &Path) {
+        sqlx::query("UPDATE schema_meta SET migration = ?, min_aim_version = '99.0.0';")
+            .bind(Db::latest_schema_version() + 1)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        forget_checked(path);
+    }
+
+    /// Drops the record of the check by this build, as another aim writing to the database does.
+    fn forget_checked(path: &Path) {
+        fs::remove_file(checked_path(path)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stamps_the_schema_with_this_build() {
+        let db = Db::open(None).await.unwrap();
+        let stamp = read(&db.pool).await.unwrap().unwrap();
+        assert_eq!(stamp.migration, Db::latest_schema_version());
+        assert_eq!(stamp.min_aim_version.as_deref(), Some(AIM_VERSION));
+        assert!(!stamp.is_newer());
+    }
+
+    #[tokio::test]
+    async fn migrates_and_restamps_an_older_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        let db = open(&path).await.unwrap();
+        // Back to before the stamp and the check-in columns, as written by an older aim
+        MIGRATOR.undo(&db.pool, 20_261_015_230_300).await.unwrap();
+        forget_checked(&path);
+        assert!(
+            read(&db.pool)
+                .await
+                .unwrap()
+                .unwrap()
+                .min_aim_version
+                .is_none()
+        );
+        db.close().await.unwrap();
+
+        let db = open(&path).await.unwrap();
+        let stamp = read(&db.pool).await.unwrap().unwrap();
+        assert_eq!(stamp.migration, Db::latest_schema_version());
+        assert_eq!(stamp.min_aim_version.as_deref(), Some(AIM_VERSION));
+        assert!(missing_columns(&db.pool).await.unwrap().is_empty());
+
+        // A newer aim without migrations of its own keeps the version that applied them
+        sqlx::query("UPDATE schema_meta SET min_aim_version = '0.1.0';")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        db.close().await.unwrap();
+        let db = open(&path).await.unwrap();
+        let stamp = read(&db.pool).await.unwrap().unwrap();
+        assert_eq!(stamp.min_aim_version.as_deref(), Some("0.1.0"));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_newer_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        let db = open(&path).await.unwrap();
+        stamp_as_newer(&db, &path).await;
+        db.close().await.unwrap();
+
+        let err = open(&path).await.unwrap_err();
+        let AimError::SchemaTooNew { created_by } = &err else {
+            panic!("expected SchemaTooNew, got {err}");
+        };
+        assert_eq!(created_by, "aim 99.0.0");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Database was created by aim 99.0.0, this is aim {AIM_VERSION}; upgrade aim or \
+                 restore a backup"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_the_check_until_another_aim_opens_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        assert!(!is_checked(&path));
+
+        open(&path).await.unwrap().close().await.unwrap();
+        assert!(is_checked(&path));
+
+        fs::write(checked_path(&path), "aim 99.0.0\n").unwrap();
+        assert!(!is_checked(&path));
+        open(&path).await.unwrap().close().await.unwrap();
+        assert!(is_checked(&path));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_newer_schema_without_stamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        let db = open(&path).await.unwrap();
+        sqlx::query("DELETE FROM schema_meta;")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "\
+INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+VALUES (?, 'from the future', TRUE, X'00', 0);
+",
+        )
+        .bind(Db::latest_schema_version() + 1)
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        forget_checked(&path);
+        db.close().await.unwrap();
+
+        let err = open(&path).await.unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                AimError::SchemaTooNew { created_by } if created_by == "a newer aim"
+            ),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_only_compat_opens_a_newer_schema_without_writing_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        let db = open(&path).await.unwrap();
+        let record = CalendarRecord::new("work".into(), "Work".into(), "local".into(), 0, true);
+        db.calendars.upsert(record).await.unwrap();
+        // Columns added by the newer aim are left alone
+        sqlx::query("ALTER TABLE events ADD COLUMN from_the_future TEXT;")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        stamp_as_newer(&db, &path).await;
+        db.close().await.unwrap();
+
+        let db = Db::open_read_only_compat(&path, TimeZone::UTC)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.newer_schema().and_then(|s| s.min_aim_version.as_deref()),
+            Some("99.0.0")
+        );
+        let ids: Vec<_> = db
+            .calendars
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert!(ids.contains(&"work".to_string()), "{ids:?}");
+
+        // Writes go to the copy only
+        let record = CalendarRecord::new("home".into(), "Home".into(), "local".into(), 0, true);
+        db.calendars.upsert(record).await.unwrap();
+        db.close().await.unwrap();
+
+        let err = open(&path).await.unwrap_err();
+        assert!(matches!(err, AimError::SchemaTooNew { .. }), "{err}");
+        let db = Db::open_read_only_compat(&path, TimeZone::UTC)
+            .await
+            .unwrap();
+        assert_eq!(db.calendars.list().await.unwrap().len(), ids.len());
+    }
+
+    #[tokio::test]
+    async fn read_only_compat_refuses_a_newer_schema_lacking_known_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aim.db");
+        let db = open(&path).await.unwrap();
+        sqlx::query("ALTER TABLE events DROP COLUMN checkin_note;")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        stamp_as_newer(&db, &path).await;
+        db.close().await.unwrap();
+
+        let err = Db::open_read_only_compat(&path, TimeZone::UTC)
+            .await
+            .unwrap_err();
+        let AimError::SchemaIncompatible {
+            created_by,
+            missing,
+        } = &err
+        else {
+            panic!("expected SchemaIncompatible, got {err}");
+        };
+        assert_eq!(created_by, "aim 99.0.0");
+        assert_eq!(missing, &["events.checkin_note"]);
+        assert!(err.to_string().contains("(events.checkin_note)"), "{err}");
+    }
+}
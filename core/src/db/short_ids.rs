@@ -131,7 +131,7 @@ mod tests {
         VEvent {
             uid: Uid::new(uid.to_string()),
             dt_stamp: DtStamp::new(now.datetime()),
-            dt_start: DtStart::new(LooseDateTime::Local(now.clone())),
+            dt_start: Some(DtStart::new(LooseDateTime::Local(now.clone()))),
             dt_end: Some(DtEnd::new(LooseDateTime::Local(now.clone()))),
             duration: None,
             summary: Some(Summary::new(summary.to_string())),
@@ -147,8 +147,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -182,8 +188,14 @@ mod tests {
             priority: Some(IcalPriority::new(5)),
             percent_complete: Some(PercentComplete::new(0)),
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -626,61 +638,6 @@ mod tests {
         assert!(result.is_none());
     }
 
-    #[tokio::test]
-    async fn short_ids_get_uid_resolves_short_id_to_uid() {
-        // Arrange
-        let db = setup_test_db().await;
-        let short_ids = ShortIds::new(db.clone());
-        let uid = "test-uid-todo-1";
-
-        // Assign a short ID
-        let assigned_short_id = db
-            .short_ids
-            .get_or_assign_short_id(uid, Kind::Todo)
-            .await
-            .expect("Failed to assign short ID");
-
-        let id = Id::ShortIdOrUid(assigned_short_id.get().to_string());
-
-        // Act
-        let resolved_uid = short_ids.get_uid(&id).await.expect("Failed to resolve UID");
-
-        // Assert
-        assert_eq!(resolved_uid, uid);
-    }
-
-    #[tokio::test]
-    async fn short_ids_get_uid_returns_uid_string_for_uid_variant() {
-        // Arrange
-        let db = setup_test_db().await;
-        let short_ids = ShortIds::new(db);
-        let uid = "direct-uid-string";
-        let id = Id::Uid(uid.to_string());
-
-        // Act
-        let resolved_uid = short_ids.get_uid(&id).await.expect("Failed to resolve UID");
-
-        // Assert
-        assert_eq!(resolved_uid, uid);
-    }
-
-    #[tokio::test]
-    async fn short_ids_get_uid_returns_string_for_short_id_or_uid_lookalike() {
-        // Arrange
-        let db = setup_test_db().await;
-        let short_ids = ShortIds::new(db);
-        // A UUID-like string that could be a UID
-        let uid_string = "abc-123-def-456";
-        let id = Id::ShortIdOrUid(uid_string.to_string());
-
-        // Act
-        let resolved_uid = short_ids.get_uid(&id).await.expect("Failed to resolve UID");
-
-        // Assert
-        // Should return the string as-is since it's not a valid short ID
-        assert_eq!(resolved_uid, uid_string);
-    }
-
     #[tokio::test]
     async fn short_ids_get_returns_none_for_invalid_short_id_zero() {
         // Arrange
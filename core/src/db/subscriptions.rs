@@ -0,0 +1,149 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone)]
+pub struct Subscriptions {
+    pool: SqlitePool,
+}
+
+impl Subscriptions {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn upsert(&self, record: &SubscriptionRecord) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+INSERT INTO subscriptions (calendar_id, url, etag, last_modified, body, fetched_at)
+VALUES (?, ?, ?, ?, ?, ?)
+ON CONFLICT(calendar_id) DO UPDATE SET
+    url = excluded.url,
+    etag = excluded.etag,
+    last_modified = excluded.last_modified,
+    body = excluded.body,
+    fetched_at = excluded.fetched_at;
+";
+
+        sqlx::query(SQL)
+            .bind(&record.calendar_id)
+            .bind(&record.url)
+            .bind(&record.etag)
+            .bind(&record.last_modified)
+            .bind(&record.body)
+            .bind(&record.fetched_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, calendar_id: &str) -> Result<Option<SubscriptionRecord>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT calendar_id, url, etag, last_modified, body, fetched_at
+FROM subscriptions
+WHERE calendar_id = ?;
+";
+
+        sqlx::query_as(SQL)
+            .bind(calendar_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Marks a cached feed as fresh without changing its content.
+    pub async fn touch(&self, calendar_id: &str, fetched_at: &str) -> Result<(), sqlx::Error> {
+        const SQL: &str = "UPDATE subscriptions SET fetched_at = ? WHERE calendar_id = ?;";
+
+        sqlx::query(SQL)
+            .bind(fetched_at)
+            .bind(calendar_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Cached copy of a subscribed feed.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SubscriptionRecord {
+    /// Owning calendar identifier.
+    pub calendar_id: String,
+    /// Normalized feed URL.
+    pub url: String,
+    /// `ETag` header of the last response, sent back as `If-None-Match`.
+    pub etag: Option<String>,
+    /// `Last-Modified` header of the last response, sent back as `If-Modified-Since`.
+    pub last_modified: Option<String>,
+    /// Cached ICS content.
+    pub body: String,
+    /// Time of the last successful refresh.
+    pub fetched_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> crate::db::Db {
+        crate::db::Db::open(None)
+            .await
+            .expect("Failed to create test database")
+    }
+
+    fn test_record(body: &str) -> SubscriptionRecord {
+        SubscriptionRecord {
+            calendar_id: "holidays".to_string(),
+            url: "https://example.com/holidays.ics".to_string(),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            body: body.to_string(),
+            fetched_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscriptions_get_returns_none_for_unknown_calendar() {
+        let db = setup_test_db().await;
+
+        let record = db.subscriptions.get("missing").await.unwrap();
+        assert!(record.is_none());
+    }
+
+    #[tokio::test]
+    async fn subscriptions_upsert_replaces_cached_feed() {
+        let db = setup_test_db().await;
+
+        db.subscriptions.upsert(&test_record("v1")).await.unwrap();
+        let mut updated = test_record("v2");
+        updated.etag = None;
+        updated.last_modified = Some("Wed, 01 Jan 2026 00:00:00 GMT".to_string());
+        db.subscriptions.upsert(&updated).await.unwrap();
+
+        let record = db.subscriptions.get("holidays").await.unwrap().unwrap();
+        assert_eq!(record.body, "v2");
+        assert_eq!(record.etag, None);
+        assert_eq!(
+            record.last_modified.as_deref(),
+            Some("Wed, 01 Jan 2026 00:00:00 GMT")
+        );
+    }
+
+    #[tokio::test]
+    async fn subscriptions_touch_updates_fetched_at_only() {
+        let db = setup_test_db().await;
+
+        db.subscriptions.upsert(&test_record("v1")).await.unwrap();
+        db.subscriptions
+            .touch("holidays", "2026-02-01T00:00:00Z")
+            .await
+            .unwrap();
+
+        let record = db.subscriptions.get("holidays").await.unwrap().unwrap();
+        assert_eq!(record.fetched_at, "2026-02-01T00:00:00Z");
+        assert_eq!(record.body, "v1");
+        assert_eq!(record.etag.as_deref(), Some("\"v1\""));
+    }
+}
@@ -10,7 +10,7 @@
 use std::borrow::Cow;
 
 use crate::db::Db;
-use crate::{Event, EventStatus, LooseDateTime, Priority, Todo, TodoStatus};
+use crate::{CheckIn, Event, EventStatus, LooseDateTime, Priority, Todo, TodoStatus};
 
 /// Creates an in-memory test database.
 ///
@@ -47,6 +47,12 @@ pub struct TestEvent {
     pub end: Option<LooseDateTime>,
     /// The status of the event.
     pub status: Option<EventStatus>,
+    /// The check-in of the event.
+    pub check_in: Option<CheckIn>,
+    /// The URL of the event, if available.
+    pub url: Option<String>,
+    /// The UID of the item the event relates to, if any.
+    pub related_to: Option<String>,
 }
 
 impl TestEvent {
@@ -65,6 +71,9 @@ impl TestEvent {
             start: None,
             end: None,
             status: None,
+            check_in: None,
+            url: None,
+            related_to: None,
         }
     }
 
@@ -91,6 +100,24 @@ impl TestEvent {
         self.status = Some(status);
         self
     }
+
+    /// Sets the check-in for the test event.
+    pub fn with_check_in(mut self, check_in: CheckIn) -> Self {
+        self.check_in = Some(check_in);
+        self
+    }
+
+    /// Sets the URL for the test event.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the UID of the item the test event relates to.
+    pub fn with_related_to(mut self, uid: impl Into<String>) -> Self {
+        self.related_to = Some(uid.into());
+        self
+    }
 }
 
 impl Event for TestEvent {
@@ -117,6 +144,18 @@ impl Event for TestEvent {
     fn status(&self) -> Option<EventStatus> {
         self.status
     }
+
+    fn check_in(&self) -> Option<CheckIn> {
+        self.check_in.clone()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.url.as_deref().map(Cow::Borrowed)
+    }
+
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        self.related_to.as_deref().map(Cow::Borrowed)
+    }
 }
 
 /// Creates a test event with the given UID and summary.
@@ -142,6 +181,8 @@ pub struct TestTodo {
     pub summary: String,
     /// The description of the todo, if available.
     pub description: Option<String>,
+    /// The start date and time of the todo.
+    pub start: Option<LooseDateTime>,
     /// The due date and time of the todo.
     pub due: Option<LooseDateTime>,
     /// The completion datetime of the todo.
@@ -152,6 +193,12 @@ pub struct TestTodo {
     pub priority: Priority,
     /// The status of the todo.
     pub status: TodoStatus,
+    /// The categories of the todo.
+    pub categories: Vec<String>,
+    /// The custom metadata of the todo.
+    pub meta: Vec<(String, String)>,
+    /// The UID of the parent todo.
+    pub parent: Option<String>,
 }
 
 impl TestTodo {
@@ -167,11 +214,15 @@ impl TestTodo {
             uid: uid.into(),
             summary: summary.into(),
             description: None,
+            start: None,
             due: None,
             completed: None,
             percent_complete: None,
             priority: Priority::default(),
             status: TodoStatus::default(),
+            categories: Vec::new(),
+            meta: Vec::new(),
+            parent: None,
         }
     }
 
@@ -181,6 +232,12 @@ impl TestTodo {
         self
     }
 
+    /// Sets the start datetime for the test todo.
+    pub fn with_start(mut self, start: LooseDateTime) -> Self {
+        self.start = Some(start);
+        self
+    }
+
     /// Sets the due datetime for the test todo.
     pub fn with_due(mut self, due: LooseDateTime) -> Self {
         self.due = Some(due);
@@ -211,6 +268,27 @@ impl TestTodo {
         self.status = status;
         self
     }
+
+    /// Sets the categories for the test todo.
+    pub fn with_categories(mut self, categories: &[&str]) -> Self {
+        self.categories = categories.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Sets the custom metadata for the test todo.
+    pub fn with_meta(mut self, meta: &[(&str, &str)]) -> Self {
+        self.meta = meta
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        self
+    }
+
+    /// Sets the parent for the test todo.
+    pub fn with_parent(mut self, parent: &str) -> Self {
+        self.parent = Some(parent.to_string());
+        self
+    }
 }
 
 impl Todo for TestTodo {
@@ -226,6 +304,10 @@ impl Todo for TestTodo {
         self.description.as_deref().map(Cow::Borrowed)
     }
 
+    fn start(&self) -> Option<LooseDateTime> {
+        self.start.clone()
+    }
+
     fn due(&self) -> Option<LooseDateTime> {
         self.due.clone()
     }
@@ -245,6 +327,18 @@ impl Todo for TestTodo {
     fn status(&self) -> TodoStatus {
         self.status
     }
+
+    fn categories(&self) -> Vec<String> {
+        self.categories.clone()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.meta.clone()
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.parent.as_deref().map(Into::into)
+    }
 }
 
 /// Creates a test todo with the given UID and summary.
@@ -3,28 +3,92 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::borrow::Cow;
+use std::num::NonZeroU32;
 
-use jiff::Zoned;
-use sqlx::{Sqlite, SqlitePool, query::QueryAs, sqlite::SqliteArguments};
+use jiff::{SignedDuration, Zoned, tz::TimeZone};
+use sqlx::{
+    Sqlite, SqliteConnection, SqlitePool,
+    query::{Query, QueryAs},
+    sqlite::SqliteArguments,
+};
 
+use crate::crypt::X_AIM_ENCRYPTED_DESC;
 use crate::datetime::STABLE_FORMAT_LOCAL;
-use crate::todo::{ResolvedTodoConditions, ResolvedTodoSort};
-use crate::{LooseDateTime, Pager, Priority, Todo, TodoStatus};
+use crate::db::meta::ItemMeta;
+use crate::db::{layered_categories, unix_seconds, unix_seconds_at_end_of_day};
+use crate::rules::raise_priority;
+use crate::todo::{
+    ResolvedTodoConditions, ResolvedTodoSort, X_AIM_ESTIMATE, X_AIM_FOLLOW_UP, X_AIM_INBOX,
+    X_AIM_WAITING_ON,
+};
+use crate::{Kind, LooseDateTime, Pager, Priority, RuleSubject, Rules, Todo, TodoStatus};
+
+/// The hot and archived todos together, for listings that include the archive.
+const ALL_TODOS: &str = "\
+(SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+        categories, parent, encrypted_description, inbox, waiting_on, follow_up, location,
+        organizer, url, estimate, rule_categories, rule_color, rule_priority, start_utc, due_utc,
+        follow_up_utc
+ FROM todos
+ UNION ALL
+ SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+        categories, parent, encrypted_description, inbox, waiting_on, follow_up, location,
+        organizer, url, estimate, rule_categories, rule_color, rule_priority, start_utc, due_utc,
+        follow_up_utc
+ FROM todos_archive) AS t";
+
+/// The priority of todos raised to the floor of the rules, see [`raise_priority`].
+const EFFECTIVE_PRIORITY: &str = "\
+(CASE WHEN t.rule_priority != 0 AND (t.priority = 0 OR t.priority > t.rule_priority)
+      THEN t.rule_priority ELSE t.priority END) ";
+
+/// Like [`EFFECTIVE_PRIORITY`], but with todos without a priority after the ones with one.
+const EFFECTIVE_PRIORITY_NONE_LAST: &str = "\
+(((CASE WHEN t.rule_priority != 0 AND (t.priority = 0 OR t.priority > t.rule_priority)
+        THEN t.rule_priority ELSE t.priority END) + 9) % 10) ";
 
 #[derive(Debug, Clone)]
 pub struct Todos {
     pool: SqlitePool,
+    tz: TimeZone,
 }
 
 impl Todos {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, tz: TimeZone) -> Self {
+        Self { pool, tz }
     }
 
     pub async fn upsert(&self, todo: &TodoRecord) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::upsert_with(&mut conn, todo, &self.tz).await
+    }
+
+    /// Upserts the todo on `conn`, such as within a transaction, resolving its floating
+    /// times and dates in `tz`.
+    ///
+    /// An archived todo is left in the archive if it is unchanged, and moved back otherwise. Its
+    /// custom metadata and what the rules add to it are replaced either way.
+    pub async fn upsert_with(
+        conn: &mut SqliteConnection,
+        todo: &TodoRecord,
+        tz: &TimeZone,
+    ) -> Result<(), sqlx::Error> {
+        const UNARCHIVE: &str = "\
+DELETE FROM todos_archive
+WHERE uid = ? AND NOT (calendar_id IS ? AND completed IS ? AND description IS ? AND percent IS ?
+                       AND priority IS ? AND status IS ? AND summary IS ? AND start IS ?
+                       AND due IS ? AND categories IS ? AND parent IS ?
+                       AND encrypted_description IS ? AND inbox IS ? AND waiting_on IS ?
+                       AND follow_up IS ? AND location IS ? AND organizer IS ? AND url IS ?
+                       AND estimate IS ?);
+";
+        const ARCHIVED: &str = "SELECT EXISTS (SELECT 1 FROM todos_archive WHERE uid = ?);";
         const SQL: &str = "\
-INSERT INTO todos (uid, calendar_id, completed, description, percent, priority, status, summary, due)
-VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+INSERT INTO todos (uid, calendar_id, completed, description, percent, priority, status, summary, start, due, categories,
+                   parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+                   url, estimate, rule_categories, rule_color, rule_priority, start_utc, due_utc,
+                   follow_up_utc)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 ON CONFLICT(uid) DO UPDATE SET
     calendar_id  = excluded.calendar_id,
     completed    = excluded.completed,
@@ -33,10 +97,58 @@ ON CONFLICT(uid) DO UPDATE SET
     priority     = excluded.priority,
     status       = excluded.status,
     summary      = excluded.summary,
-    due          = excluded.due;
+    start        = excluded.start,
+    due          = excluded.due,
+    categories   = excluded.categories,
+    parent       = excluded.parent,
+    encrypted_description = excluded.encrypted_description,
+    inbox        = excluded.inbox,
+    waiting_on   = excluded.waiting_on,
+    follow_up    = excluded.follow_up,
+    location     = excluded.location,
+    organizer    = excluded.organizer,
+    url          = excluded.url,
+    estimate     = excluded.estimate,
+    rule_categories = excluded.rule_categories,
+    rule_color      = excluded.rule_color,
+    rule_priority   = excluded.rule_priority,
+    start_utc    = excluded.start_utc,
+    due_utc      = excluded.due_utc,
+    follow_up_utc = excluded.follow_up_utc;
 ";
 
-        sqlx::query(SQL)
+        ItemMeta::replace_with(&mut *conn, &todo.uid, &todo.meta).await?;
+        Self::bind_columns(todo, sqlx::query(UNARCHIVE))
+            .execute(&mut *conn)
+            .await?;
+        let archived: bool = sqlx::query_scalar(ARCHIVED)
+            .bind(&todo.uid)
+            .fetch_one(&mut *conn)
+            .await?;
+        if archived {
+            return Self::set_rule_outcome_with(conn, true, todo).await;
+        }
+
+        Self::bind_columns(todo, sqlx::query(SQL))
+            .bind(&todo.rule_categories)
+            .bind(&todo.rule_color)
+            .bind(todo.rule_priority)
+            .bind(unix_seconds(&todo.start, tz))
+            .bind(unix_seconds_at_end_of_day(&todo.due, tz))
+            .bind(unix_seconds(&todo.follow_up, tz))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Binds the columns of the todo itself, in the order of the table, leaving the ones added by
+    /// rules and the UTC ones to the caller.
+    fn bind_columns<'a>(
+        todo: &'a TodoRecord,
+        query: Query<'a, Sqlite, SqliteArguments>,
+    ) -> Query<'a, Sqlite, SqliteArguments> {
+        query
             .bind(&todo.uid)
             .bind(&todo.calendar_id)
             .bind(&todo.completed)
@@ -45,39 +157,139 @@ ON CONFLICT(uid) DO UPDATE SET
             .bind(todo.priority)
             .bind(&todo.status)
             .bind(&todo.summary)
+            .bind(&todo.start)
             .bind(&todo.due)
-            .execute(&self.pool)
-            .await?;
+            .bind(&todo.categories)
+            .bind(&todo.parent)
+            .bind(&todo.encrypted_description)
+            .bind(todo.inbox)
+            .bind(&todo.waiting_on)
+            .bind(&todo.follow_up)
+            .bind(&todo.location)
+            .bind(&todo.organizer)
+            .bind(&todo.url)
+            .bind(todo.estimate)
+    }
 
+    /// Evaluates the rules again for the todo with the UID, or all todos if `None`, archived
+    /// ones included, returning how many changed.
+    pub async fn apply_rules(&self, rules: &Rules, uid: Option<&str>) -> Result<u64, sqlx::Error> {
+        const SQL: &str = "\
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
+FROM todos
+WHERE ?1 IS NULL OR uid = ?1;
+";
+        const SQL_ARCHIVED: &str = "\
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
+FROM todos_archive
+WHERE ?1 IS NULL OR uid = ?1;
+";
+
+        let mut changed = 0;
+        let mut tx = self.pool.begin().await?;
+        for (archived, sql) in [(false, SQL), (true, SQL_ARCHIVED)] {
+            let records: Vec<TodoRecord> =
+                sqlx::query_as(sql).bind(uid).fetch_all(&mut *tx).await?;
+            for mut record in records {
+                if record.apply_rules(rules) {
+                    Self::set_rule_outcome_with(&mut tx, archived, &record).await?;
+                    changed += 1;
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(changed)
+    }
+
+    /// Writes what the rules add to the todo, in the archive if `archived`.
+    async fn set_rule_outcome_with(
+        conn: &mut SqliteConnection,
+        archived: bool,
+        todo: &TodoRecord,
+    ) -> Result<(), sqlx::Error> {
+        const SQL: &str = "\
+UPDATE todos SET rule_categories = ?, rule_color = ?, rule_priority = ? WHERE uid = ?;
+";
+        const SQL_ARCHIVED: &str = "\
+UPDATE todos_archive SET rule_categories = ?, rule_color = ?, rule_priority = ? WHERE uid = ?;
+";
+
+        let sql = if archived { SQL_ARCHIVED } else { SQL };
+        sqlx::query(sql)
+            .bind(&todo.rule_categories)
+            .bind(&todo.rule_color)
+            .bind(todo.rule_priority)
+            .bind(&todo.uid)
+            .execute(conn)
+            .await?;
         Ok(())
     }
 
+    /// Gets the todo, from the archive if it was archived.
     pub async fn get(&self, uid: &str) -> Result<Option<TodoRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT uid, calendar_id, completed, description, percent, priority, status, summary, due
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
 FROM todos
+WHERE uid = ?
+UNION ALL
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
+FROM todos_archive
 WHERE uid = ?;
 ";
 
         sqlx::query_as(SQL)
+            .bind(uid)
             .bind(uid)
             .fetch_optional(&self.pool)
             .await
     }
 
+    /// Lists the todos whose UID starts with the prefix, archived ones included.
+    pub async fn list_by_uid_prefix(&self, prefix: &str) -> Result<Vec<TodoRecord>, sqlx::Error> {
+        const SQL: &str = "\
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
+FROM todos
+WHERE substr(uid, 1, length(?1)) = ?1
+UNION ALL
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority
+FROM todos_archive
+WHERE substr(uid, 1, length(?1)) = ?1
+ORDER BY uid;
+";
+
+        sqlx::query_as(SQL).bind(prefix).fetch_all(&self.pool).await
+    }
+
     pub async fn list(
         &self,
         conds: &ResolvedTodoConditions,
         sort: &[ResolvedTodoSort],
         pager: &Pager,
     ) -> Result<Vec<TodoRecord>, sqlx::Error> {
-        let mut sql = "\
+        let mut sql = format!(
+            "\
 SELECT t.uid, t.calendar_id, t.completed, t.description, t.percent,
-       t.priority AS priority, t.status, t.summary, t.due
-FROM todos AS t
+       t.priority AS priority, t.status, t.summary, t.start, t.due, t.categories, t.parent,
+       t.encrypted_description, t.inbox, t.waiting_on, t.follow_up, t.location, t.organizer,
+       t.url, t.estimate, t.rule_categories, t.rule_color, t.rule_priority, si.short_id
+FROM {}
 JOIN calendars AS c ON c.id = t.calendar_id
-"
-        .to_string();
+LEFT JOIN short_ids AS si ON si.uid = t.uid
+",
+            Self::source(conds)
+        );
         sql += &Self::build_where(conds);
 
         sql += "ORDER BY c.priority ASC";
@@ -86,16 +298,22 @@ JOIN calendars AS c ON c.id = t.calendar_id
             for (i, s) in sort.iter().enumerate() {
                 match s {
                     ResolvedTodoSort::Due(order) => {
-                        sql += "t.due ";
+                        sql += "t.due_utc ";
                         sql += order.sql_keyword();
                     }
                     ResolvedTodoSort::Priority { order, none_first } => {
                         sql += match none_first {
-                            true => "t.priority ",
-                            false => "((t.priority + 9) % 10) ",
+                            true => EFFECTIVE_PRIORITY,
+                            false => EFFECTIVE_PRIORITY_NONE_LAST,
                         };
                         sql += order.sql_keyword();
                     }
+                    ResolvedTodoSort::Summary { order, collation } => {
+                        sql += "t.summary COLLATE ";
+                        sql += collation.sql_name();
+                        sql += " ";
+                        sql += order.sql_keyword();
+                    }
                 }
 
                 if i < sort.len() - 1 {
@@ -120,7 +338,10 @@ JOIN calendars AS c ON c.id = t.calendar_id
         summary: &str,
     ) -> Result<Option<TodoRecord>, sqlx::Error> {
         const SQL: &str = "\
-SELECT t.uid, t.calendar_id, t.completed, t.description, t.percent, t.priority, t.status, t.summary, t.due
+SELECT t.uid, t.calendar_id, t.completed, t.description, t.percent, t.priority, t.status, t.summary,
+       t.start, t.due, t.categories, t.parent, t.encrypted_description, t.inbox, t.waiting_on,
+       t.follow_up, t.location, t.organizer, t.url, t.estimate, t.rule_categories, t.rule_color,
+       t.rule_priority
 FROM todos t
 JOIN short_ids si ON t.uid = si.uid
 WHERE si.kind = 'todo' AND t.summary = ?
@@ -133,9 +354,91 @@ LIMIT 1;
             .await
     }
 
+    /// Fills in the instants of the todos without them, returning how many were filled in.
+    pub async fn backfill_instants(&self) -> Result<u64, sqlx::Error> {
+        const SELECT: &str = "\
+SELECT uid, start, due
+FROM todos
+WHERE (start != '' AND start_utc IS NULL) OR (due != '' AND due_utc IS NULL);
+";
+        const UPDATE: &str = "UPDATE todos SET start_utc = ?, due_utc = ? WHERE uid = ?;";
+
+        let rows: Vec<(String, String, String)> =
+            sqlx::query_as(SELECT).fetch_all(&self.pool).await?;
+        let mut tx = self.pool.begin().await?;
+        for (uid, start, due) in &rows {
+            sqlx::query(UPDATE)
+                .bind(unix_seconds(start, &self.tz))
+                .bind(unix_seconds_at_end_of_day(due, &self.tz))
+                .bind(uid)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(rows.len() as u64)
+    }
+
+    /// Counts the direct subtasks of each of the parents, archived ones included, as the number
+    /// of completed ones and the number of those not cancelled.
+    ///
+    /// Parents without subtasks are left out.
+    pub async fn count_subtasks(
+        &self,
+        parents: &[&str],
+    ) -> Result<Vec<(String, i64, i64)>, sqlx::Error> {
+        if parents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; parents.len()].join(", ");
+        let sql = format!(
+            "\
+SELECT t.parent, COALESCE(SUM(t.status = ?), 0), COALESCE(SUM(t.status != ?), 0)
+FROM {ALL_TODOS}
+WHERE t.parent IN ({placeholders})
+GROUP BY t.parent
+ORDER BY t.parent;
+"
+        );
+
+        let completed: &str = TodoStatus::Completed.as_ref();
+        let cancelled: &str = TodoStatus::Cancelled.as_ref();
+        let mut query = sqlx::query_as(sqlx::AssertSqlSafe(sql))
+            .bind(completed)
+            .bind(cancelled);
+        for parent in parents {
+            query = query.bind(*parent);
+        }
+        query.fetch_all(&self.pool).await
+    }
+
+    /// Lists the UID, calendar and sealed description of the todos with an encrypted description,
+    /// archived ones included.
+    pub async fn list_encrypted(&self) -> Result<Vec<(String, String, String)>, sqlx::Error> {
+        let sql = format!(
+            "\
+SELECT t.uid, t.calendar_id, t.encrypted_description
+FROM {ALL_TODOS}
+WHERE t.encrypted_description IS NOT NULL
+ORDER BY t.uid;
+"
+        );
+        sqlx::query_as(sqlx::AssertSqlSafe(sql))
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Counts the todos of each calendar, disabled ones included.
+    pub async fn count_by_calendar(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        const SQL: &str = "SELECT calendar_id, COUNT(*) FROM todos GROUP BY calendar_id;";
+        sqlx::query_as(SQL).fetch_all(&self.pool).await
+    }
+
     pub async fn count(&self, conds: &ResolvedTodoConditions) -> Result<i64, sqlx::Error> {
-        let mut sql = "SELECT COUNT(*) FROM todos AS t JOIN calendars AS c ON c.id = t.calendar_id"
-            .to_string();
+        let mut sql = format!(
+            "SELECT COUNT(*) FROM {} JOIN calendars AS c ON c.id = t.calendar_id",
+            Self::source(conds)
+        );
         sql += &Self::build_where(conds);
         sql += ";";
 
@@ -145,23 +448,134 @@ LIMIT 1;
         Ok(row.0)
     }
 
+    /// Moves the todos completed before `cutoff` to the archive, returning how many were moved.
+    pub async fn archive_completed_before(
+        &self,
+        cutoff: &Zoned,
+        now: &Zoned,
+    ) -> Result<u64, sqlx::Error> {
+        const SELECT: &str =
+            "SELECT uid, completed FROM todos WHERE status = ? AND completed != '';";
+        const ARCHIVE: &str = "\
+INSERT OR REPLACE INTO todos_archive (uid, calendar_id, completed, description, percent, priority,
+                                      status, summary, start, due, categories, parent,
+                                      encrypted_description, inbox, waiting_on, follow_up,
+                                      location, organizer, url, estimate, rule_categories,
+                                      rule_color, rule_priority, start_utc, due_utc, follow_up_utc,
+                                      archived_at)
+SELECT uid, calendar_id, completed, description, percent, priority, status, summary, start, due,
+       categories, parent, encrypted_description, inbox, waiting_on, follow_up, location, organizer,
+       url, estimate, rule_categories, rule_color, rule_priority, start_utc, due_utc, follow_up_utc,
+       ?
+FROM todos
+WHERE uid = ?;
+";
+        const DELETE: &str = "DELETE FROM todos WHERE uid = ?;";
+
+        // Completion times are stored as local times rather than instants, so compare them here
+        let status: &str = TodoStatus::Completed.as_ref();
+        let rows: Vec<(String, String)> = sqlx::query_as(SELECT)
+            .bind(status)
+            .fetch_all(&self.pool)
+            .await?;
+        let archived_at = now.timestamp().to_string();
+        let mut moved = 0;
+        let mut tx = self.pool.begin().await?;
+        for (uid, completed) in &rows {
+            let Ok(completed) = Zoned::strptime(STABLE_FORMAT_LOCAL, completed) else {
+                continue;
+            };
+            if completed.timestamp() >= cutoff.timestamp() {
+                continue;
+            }
+            sqlx::query(ARCHIVE)
+                .bind(&archived_at)
+                .bind(uid)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(DELETE).bind(uid).execute(&mut *tx).await?;
+            moved += 1;
+        }
+        tx.commit().await?;
+        Ok(moved)
+    }
+
+    /// Counts the archived todos.
+    pub async fn count_archived(&self) -> Result<i64, sqlx::Error> {
+        const SQL: &str = "SELECT COUNT(*) FROM todos_archive;";
+        sqlx::query_scalar(SQL).fetch_one(&self.pool).await
+    }
+
+    /// Deletes the todo, from the archive as well, with its custom metadata.
     pub async fn delete(&self, uid: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::delete_with(&mut tx, uid).await?;
+        tx.commit().await
+    }
+
+    /// Deletes the todo with `conn`, such as within a transaction.
+    pub async fn delete_with(conn: &mut SqliteConnection, uid: &str) -> Result<(), sqlx::Error> {
         const SQL: &str = "DELETE FROM todos WHERE uid = ?;";
-        sqlx::query(SQL).bind(uid).execute(&self.pool).await?;
-        Ok(())
+        const ARCHIVED: &str = "DELETE FROM todos_archive WHERE uid = ?;";
+        sqlx::query(SQL).bind(uid).execute(&mut *conn).await?;
+        sqlx::query(ARCHIVED).bind(uid).execute(&mut *conn).await?;
+        ItemMeta::delete_with(conn, uid).await
+    }
+
+    /// The table to list from, with the archived todos if asked for.
+    fn source(conds: &ResolvedTodoConditions) -> &'static str {
+        if conds.include_archived {
+            ALL_TODOS
+        } else {
+            "todos AS t"
+        }
     }
 
     fn build_where(conds: &ResolvedTodoConditions) -> String {
         let mut where_clauses = vec!["c.enabled = 1"];
         if conds.status.is_some() {
-            where_clauses.push("status = ?");
+            where_clauses.push("t.status = ?");
         }
         if conds.due.is_some() {
-            where_clauses.push("due <= ?");
+            where_clauses.push("t.due_utc <= ?");
+        }
+        if conds.start_after.is_some() {
+            where_clauses.push("t.start_utc > ?");
+        }
+        if conds.started_by.is_some() {
+            where_clauses.push("(t.start_utc IS NULL OR t.start_utc <= ?)");
         }
         if conds.calendar_id.is_some() {
             where_clauses.push("t.calendar_id = ?");
         }
+        if conds.inbox {
+            where_clauses.push("t.inbox = 1");
+        }
+        if conds.waiting {
+            where_clauses.push("t.waiting_on IS NOT NULL");
+        }
+        if conds.waiting_for.is_some() {
+            where_clauses.push("instr(lower(t.waiting_on), lower(?)) > 0");
+        }
+        if conds.followed_up_by.is_some() {
+            where_clauses.push(
+                "(t.waiting_on IS NULL OR (t.follow_up_utc IS NOT NULL AND t.follow_up_utc <= ?))",
+            );
+        }
+        if conds.undated {
+            where_clauses.push("t.due_utc IS NULL");
+        }
+        // One key-value pair per condition, so all of them must match
+        where_clauses.extend(std::iter::repeat_n(
+            "EXISTS (SELECT 1 FROM item_meta AS m WHERE m.uid = t.uid AND m.key = ? AND m.value = ?)",
+            conds.meta.len(),
+        ));
+        // Likewise one category per condition, among the own ones and those added by rules
+        where_clauses.extend(std::iter::repeat_n(
+            "lower(?) IN (SELECT lower(value) FROM json_each(t.categories)
+                          UNION ALL SELECT lower(value) FROM json_each(t.rule_categories))",
+            conds.categories.len(),
+        ));
 
         format!(" WHERE {} ", where_clauses.join(" AND "))
     }
@@ -175,11 +589,29 @@ LIMIT 1;
             query = query.bind(status);
         }
         if let Some(ref due) = conds.due {
-            query = query.bind(format_dt(due));
+            query = query.bind(due.timestamp().as_second());
+        }
+        if let Some(ref start) = conds.start_after {
+            query = query.bind(start.timestamp().as_second());
+        }
+        if let Some(ref start) = conds.started_by {
+            query = query.bind(start.timestamp().as_second());
         }
         if let Some(ref calendar_id) = conds.calendar_id {
             query = query.bind(calendar_id);
         }
+        if let Some(ref waiting_for) = conds.waiting_for {
+            query = query.bind(waiting_for);
+        }
+        if let Some(ref followed_up_by) = conds.followed_up_by {
+            query = query.bind(followed_up_by.timestamp().as_second());
+        }
+        for (key, value) in &conds.meta {
+            query = query.bind(key).bind(value);
+        }
+        for category in &conds.categories {
+            query = query.bind(category);
+        }
         query
     }
 }
@@ -195,7 +627,40 @@ pub struct TodoRecord {
     priority: u8,
     status: String,
     summary: String,
+    start: String,
     due: String,
+    /// Categories as a JSON array.
+    categories: String,
+    /// UID of the parent todo.
+    parent: Option<String>,
+    /// Sealed description, see [`crate::crypt`].
+    encrypted_description: Option<String>,
+    /// Whether the todo is in the inbox, see [`Todo::in_inbox`].
+    inbox: bool,
+    /// Who the todo is waiting on, see [`Todo::waiting_on`].
+    waiting_on: Option<String>,
+    /// When to follow up on a waiting todo, empty if never.
+    follow_up: String,
+    /// Where the todo takes place, empty if unset.
+    location: String,
+    /// Address of the organizer, empty if unset.
+    organizer: String,
+    /// URL of the todo, empty if unset.
+    url: String,
+    /// Estimate of the todo in seconds, see [`Todo::estimate`].
+    estimate: Option<i64>,
+    /// Categories added by the rules, as a JSON array.
+    rule_categories: String,
+    /// Color tag set by the rules.
+    rule_color: Option<String>,
+    /// Lowest priority set by the rules, 0 if none.
+    rule_priority: u8,
+    /// Short ID, when the query joins one in.
+    #[sqlx(default)]
+    short_id: Option<NonZeroU32>,
+    /// Custom metadata to mirror into the cache, never read back with the record.
+    #[sqlx(skip)]
+    meta: Vec<(String, String)>,
 }
 
 impl TodoRecord {
@@ -205,6 +670,7 @@ impl TodoRecord {
             calendar_id: calendar_id.to_string(),
             summary: todo.summary().to_string(),
             description: todo.description().unwrap_or_default().to_string(),
+            start: todo.start().map(|a| a.format_stable()).unwrap_or_default(),
             due: todo.due().map(|a| a.format_stable()).unwrap_or_default(),
             completed: todo
                 .completed()
@@ -213,9 +679,85 @@ impl TodoRecord {
             percent: todo.percent_complete(),
             priority: todo.priority().into(),
             status: todo.status().to_string(),
+            categories: serde_json::to_string(&todo.categories()).unwrap_or_default(),
+            parent: todo.parent_uid().map(|p| p.to_string()),
+            encrypted_description: todo.encrypted_description().map(|d| d.to_string()),
+            inbox: todo.in_inbox(),
+            waiting_on: todo.waiting_on().map(|w| w.to_string()),
+            follow_up: todo
+                .follow_up()
+                .map(|a| a.format_stable())
+                .unwrap_or_default(),
+            location: todo.location().unwrap_or_default().to_string(),
+            organizer: todo.organizer().unwrap_or_default().to_string(),
+            url: todo.url().unwrap_or_default().to_string(),
+            estimate: todo.estimate().map(|e| e.as_secs()),
+            rule_categories: "[]".to_string(),
+            rule_color: None,
+            rule_priority: 0,
+            short_id: None,
+            meta: todo.meta(),
         }
     }
 
+    /// Names of the properties cached differently in `other`, ordered by name, such as
+    /// `SUMMARY`. What the rules add is left out, as it is not a property of the todo.
+    pub fn changed_properties(&self, other: &Self) -> Vec<&'static str> {
+        let mut changed: Vec<_> = [
+            ("CATEGORIES", self.categories != other.categories),
+            ("COMPLETED", self.completed != other.completed),
+            ("DESCRIPTION", self.description != other.description),
+            ("DTSTART", self.start != other.start),
+            ("DUE", self.due != other.due),
+            ("LOCATION", self.location != other.location),
+            ("ORGANIZER", self.organizer != other.organizer),
+            ("PERCENT-COMPLETE", self.percent != other.percent),
+            ("PRIORITY", self.priority != other.priority),
+            ("RELATED-TO", self.parent != other.parent),
+            ("STATUS", self.status != other.status),
+            ("SUMMARY", self.summary != other.summary),
+            ("URL", self.url != other.url),
+            (
+                X_AIM_ENCRYPTED_DESC,
+                self.encrypted_description != other.encrypted_description,
+            ),
+            (X_AIM_ESTIMATE, self.estimate != other.estimate),
+            (X_AIM_FOLLOW_UP, self.follow_up != other.follow_up),
+            (X_AIM_INBOX, self.inbox != other.inbox),
+            (X_AIM_WAITING_ON, self.waiting_on != other.waiting_on),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect();
+        changed.sort_unstable();
+        changed
+    }
+
+    /// The properties of the todo that rules match on.
+    pub fn rule_subject(&self) -> RuleSubject<'_> {
+        RuleSubject {
+            kind: Kind::Todo,
+            summary: &self.summary,
+            calendar_id: &self.calendar_id,
+            location: &self.location,
+            organizer: &self.organizer,
+        }
+    }
+
+    /// Sets what the rules add to the todo, returning whether it changed.
+    pub fn apply_rules(&mut self, rules: &Rules) -> bool {
+        let outcome = rules.evaluate(&self.rule_subject());
+        let categories = serde_json::to_string(&outcome.categories).unwrap_or_default();
+        let priority: u8 = outcome.priority.into();
+        let changed = categories != self.rule_categories
+            || outcome.color != self.rule_color
+            || priority != self.rule_priority;
+        self.rule_categories = categories;
+        self.rule_color = outcome.color;
+        self.rule_priority = priority;
+        changed
+    }
+
     #[allow(dead_code)]
     pub fn calendar_id(&self) -> &str {
         &self.calendar_id
@@ -223,6 +765,10 @@ impl TodoRecord {
 }
 
 impl Todo for TodoRecord {
+    fn short_id(&self) -> Option<NonZeroU32> {
+        self.short_id
+    }
+
     fn uid(&self) -> Cow<'_, str> {
         self.uid.as_str().into()
     }
@@ -237,6 +783,10 @@ impl Todo for TodoRecord {
         (!self.description.is_empty()).then_some(self.description.as_str().into())
     }
 
+    fn start(&self) -> Option<LooseDateTime> {
+        LooseDateTime::parse_stable(&self.start)
+    }
+
     fn due(&self) -> Option<LooseDateTime> {
         LooseDateTime::parse_stable(&self.due)
     }
@@ -246,7 +796,7 @@ impl Todo for TodoRecord {
     }
 
     fn priority(&self) -> Priority {
-        self.priority.into()
+        raise_priority(self.priority.into(), self.rule_priority.into())
     }
 
     fn status(&self) -> TodoStatus {
@@ -256,6 +806,54 @@ impl Todo for TodoRecord {
     fn summary(&self) -> Cow<'_, str> {
         self.summary.as_str().into()
     }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        (!self.location.is_empty()).then_some(self.location.as_str().into())
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        (!self.organizer.is_empty()).then_some(self.organizer.as_str().into())
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        (!self.url.is_empty()).then_some(self.url.as_str().into())
+    }
+
+    fn estimate(&self) -> Option<SignedDuration> {
+        self.estimate.map(SignedDuration::from_secs)
+    }
+
+    fn categories(&self) -> Vec<String> {
+        layered_categories(&self.categories, &self.rule_categories)
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.rule_color.as_deref().map(Into::into)
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.parent.as_deref().map(Into::into)
+    }
+
+    fn encrypted_description(&self) -> Option<Cow<'_, str>> {
+        self.encrypted_description.as_deref().map(Into::into)
+    }
+
+    fn in_inbox(&self) -> bool {
+        self.inbox
+    }
+
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        self.waiting_on.as_deref().map(Into::into)
+    }
+
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        LooseDateTime::parse_stable(&self.follow_up)
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        Some(self.calendar_id.as_str().into())
+    }
 }
 
 fn format_dt(dt: &Zoned) -> String {
@@ -268,10 +866,11 @@ mod tests {
     use jiff::tz::TimeZone;
 
     use super::*;
+    use crate::Collation;
 
-    /// Test helper to create a test database
+    /// Test helper to create a test database, with floating times in UTC
     async fn setup_test_db() -> crate::db::Db {
-        crate::db::Db::open(None)
+        crate::db::Db::open_in(None, TimeZone::UTC)
             .await
             .expect("Failed to create test database")
     }
@@ -480,6 +1079,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -519,6 +1128,16 @@ mod tests {
             status: Some(TodoStatus::NeedsAction),
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -569,6 +1188,16 @@ mod tests {
             status: None,
             due: Some(cutoff),
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -627,6 +1256,16 @@ mod tests {
             status: Some(TodoStatus::NeedsAction),
             due: Some(cutoff),
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -683,6 +1322,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![ResolvedTodoSort::Due(crate::SortOrder::Asc)];
         let pager = Pager {
@@ -698,6 +1347,59 @@ mod tests {
         assert_eq!(results[2].uid(), "todo-1");
     }
 
+    #[tokio::test]
+    async fn todos_list_places_date_only_due_at_end_of_day() {
+        // Arrange
+        let db = setup_test_db().await;
+        let day = civil::date(2025, 1, 15);
+        let all_day = test_todo("todo-1", "All day").with_due(LooseDateTime::DateOnly(day));
+        let evening =
+            test_todo("todo-2", "Evening").with_due(LooseDateTime::Floating(day.at(17, 0, 0, 0)));
+        let earlier = test_todo("todo-3", "Earlier")
+            .with_due(LooseDateTime::DateOnly(civil::date(2025, 1, 14)));
+        for (uid, todo) in [
+            ("todo-1", &all_day),
+            ("todo-2", &evening),
+            ("todo-3", &earlier),
+        ] {
+            db.todos
+                .upsert(&TodoRecord::from_todo(uid, todo, "default"))
+                .await
+                .unwrap();
+        }
+
+        // Act
+        let mut conds = ResolvedTodoConditions {
+            status: None,
+            due: None,
+            calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let sort = vec![ResolvedTodoSort::Due(crate::SortOrder::Asc)];
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let sorted = db.todos.list(&conds, &sort, &pager).await.unwrap();
+        conds.due = Some(day.at(12, 0, 0, 0).to_zoned(TimeZone::UTC).unwrap());
+        let due_by_noon = db.todos.list(&conds, &sort, &pager).await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = sorted.iter().map(|t| t.uid().to_string()).collect();
+        assert_eq!(uids, ["todo-3", "todo-2", "todo-1"]);
+        let uids: Vec<_> = due_by_noon.iter().map(|t| t.uid().to_string()).collect();
+        assert_eq!(uids, ["todo-3"]);
+    }
+
     #[tokio::test]
     #[expect(clippy::indexing_slicing)]
     async fn todos_list_sorts_by_due_desc() {
@@ -741,6 +1443,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![ResolvedTodoSort::Due(crate::SortOrder::Desc)];
         let pager = Pager {
@@ -784,6 +1496,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![ResolvedTodoSort::Priority {
             order: crate::SortOrder::Asc,
@@ -830,6 +1552,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![ResolvedTodoSort::Priority {
             order: crate::SortOrder::Asc,
@@ -848,6 +1580,350 @@ mod tests {
         assert_eq!(results[2].uid(), "todo-1");
     }
 
+    #[tokio::test]
+    async fn todos_list_sorts_by_priority_raised_by_rules() {
+        // Arrange - a floor of 3 for the todos about bills
+        let db = setup_test_db().await;
+        let rules: Rules = serde_json::from_str(
+            r#"[{"name": "bills", "match": {"summary": "bill"}, "actions": {"priority": 3}}]"#,
+        )
+        .unwrap();
+        for (uid, summary, priority) in [
+            ("todo-1", "Pay bill", Priority::None),
+            ("todo-2", "Call back", Priority::P2),
+            ("todo-3", "File bill", Priority::P8),
+            ("todo-4", "Urgent bill", Priority::P1),
+        ] {
+            let todo = test_todo(uid, summary).with_priority(priority);
+            let mut record = TodoRecord::from_todo(uid, &todo, "default");
+            record.apply_rules(&rules);
+            db.todos.upsert(&record).await.unwrap();
+        }
+
+        // Act
+        let conds = ResolvedTodoConditions {
+            status: None,
+            due: None,
+            calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let sort = vec![
+            ResolvedTodoSort::Priority {
+                order: crate::SortOrder::Asc,
+                none_first: false,
+            },
+            ResolvedTodoSort::Summary {
+                order: crate::SortOrder::Asc,
+                collation: Collation::Binary,
+            },
+        ];
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let results = db.todos.list(&conds, &sort, &pager).await.unwrap();
+
+        // Assert - P1 stays above the floor, the others about bills are raised to P3
+        let listed: Vec<_> = results.iter().map(|t| (t.uid(), t.priority())).collect();
+        assert_eq!(
+            listed,
+            [
+                ("todo-4".into(), Priority::P1),
+                ("todo-2".into(), Priority::P2),
+                ("todo-3".into(), Priority::P3),
+                ("todo-1".into(), Priority::P3),
+            ]
+        );
+        let stored = db.todos.get("todo-3").await.unwrap().unwrap();
+        assert_eq!(stored.priority, 8, "the priority of the todo is kept");
+    }
+
+    async fn list_by_summary(db: &crate::db::Db, collation: Collation) -> Vec<String> {
+        let conds = ResolvedTodoConditions {
+            status: None,
+            due: None,
+            calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let sort = vec![ResolvedTodoSort::Summary {
+            order: crate::SortOrder::Asc,
+            collation,
+        }];
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let results = db.todos.list(&conds, &sort, &pager).await.unwrap();
+        results.iter().map(|t| t.summary().into_owned()).collect()
+    }
+
+    #[tokio::test]
+    async fn todos_list_sorts_by_summary_with_collation() {
+        // Arrange
+        let db = setup_test_db().await;
+        for (uid, summary) in [
+            ("todo-1", "Zoo"),
+            ("todo-2", "Ärzte anrufen"),
+            ("todo-3", "apotheke"),
+            ("todo-4", "École"),
+        ] {
+            db.todos
+                .upsert(&TodoRecord::from_todo(
+                    uid,
+                    &test_todo(uid, summary),
+                    "default",
+                ))
+                .await
+                .unwrap();
+        }
+
+        // Act & Assert
+        assert_eq!(
+            list_by_summary(&db, Collation::Unicode).await,
+            ["apotheke", "Ärzte anrufen", "École", "Zoo"]
+        );
+        assert_eq!(
+            list_by_summary(&db, Collation::Binary).await,
+            ["Zoo", "apotheke", "Ärzte anrufen", "École"]
+        );
+    }
+
+    #[tokio::test]
+    async fn todos_collation_is_registered_on_every_pooled_connection() {
+        // Arrange - a file database, whose pool opens several connections
+        let dir = tempfile::tempdir().unwrap();
+        let db = crate::db::Db::open(Some(&dir.path().join("aim.db")))
+            .await
+            .unwrap();
+
+        // Act - hold a few connections at once, so the pool cannot reuse the first
+        let mut conns = Vec::new();
+        for _ in 0..3 {
+            conns.push(db.pool.acquire().await.unwrap());
+        }
+
+        // Assert
+        for conn in &mut conns {
+            let (less,): (bool,) = sqlx::query_as("SELECT 'Ärzte' < 'Zoo' COLLATE AIM_UNICODE;")
+                .fetch_one(&mut **conn)
+                .await
+                .unwrap();
+            assert!(less);
+        }
+    }
+
+    #[tokio::test]
+    async fn todos_upsert_stores_categories() {
+        // Arrange
+        let db = setup_test_db().await;
+        let todo = test_todo("todo-1", "Call").with_categories(&["Work", "Ärzte, Termine"]);
+
+        // Act
+        db.todos
+            .upsert(&TodoRecord::from_todo("todo-1", &todo, "default"))
+            .await
+            .unwrap();
+
+        // Assert
+        let retrieved = db.todos.get("todo-1").await.unwrap().unwrap();
+        assert_eq!(retrieved.categories(), ["Work", "Ärzte, Termine"]);
+    }
+
+    #[tokio::test]
+    async fn todos_list_filters_by_meta() {
+        // Arrange
+        let db = setup_test_db().await;
+        let todos = [
+            test_todo("todo-1", "Fix login").with_meta(&[("ticket", "JIRA-1"), ("client", "acme")]),
+            test_todo("todo-2", "Fix logout")
+                .with_meta(&[("ticket", "JIRA-2"), ("client", "acme")]),
+            test_todo("todo-3", "Water plants"),
+        ];
+        for todo in &todos {
+            db.todos
+                .upsert(&TodoRecord::from_todo(&todo.uid, todo, "default"))
+                .await
+                .unwrap();
+        }
+        let pager = Pager {
+            limit: 100,
+            offset: 0,
+        };
+        let list = async |meta: &[(&str, &str)]| {
+            let conds = ResolvedTodoConditions {
+                meta: meta
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect(),
+                ..all_conditions(false)
+            };
+            let mut uids: Vec<_> = (db.todos.list(&conds, &[], &pager).await.unwrap())
+                .iter()
+                .map(|t| t.uid().to_string())
+                .collect();
+            uids.sort();
+            uids
+        };
+
+        // Act & Assert
+        assert_eq!(list(&[("client", "acme")]).await, ["todo-1", "todo-2"]);
+        assert_eq!(
+            list(&[("client", "acme"), ("ticket", "JIRA-2")]).await,
+            ["todo-2"]
+        );
+        assert!(list(&[("ticket", "jira-2")]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn todos_list_filters_by_categories_and_undated() {
+        // Arrange
+        let db = setup_test_db().await;
+        let due = LooseDateTime::DateOnly(civil::date(2025, 1, 15));
+        let todos = [
+            test_todo("todo-1", "Report").with_categories(&["Work", "Urgent"]),
+            test_todo("todo-2", "Review")
+                .with_categories(&["work"])
+                .with_due(due.clone()),
+            test_todo("todo-3", "Water plants").with_due(due),
+        ];
+        for todo in &todos {
+            db.todos
+                .upsert(&TodoRecord::from_todo(&todo.uid, todo, "default"))
+                .await
+                .unwrap();
+        }
+        let pager = Pager {
+            limit: 100,
+            offset: 0,
+        };
+        let list = async |categories: &[&str], undated: bool| {
+            let conds = ResolvedTodoConditions {
+                categories: categories.iter().map(ToString::to_string).collect(),
+                undated,
+                ..all_conditions(false)
+            };
+            let count = db.todos.count(&conds).await.unwrap();
+            let mut uids: Vec<_> = (db.todos.list(&conds, &[], &pager).await.unwrap())
+                .iter()
+                .map(|t| t.uid().to_string())
+                .collect();
+            uids.sort();
+            assert_eq!(usize::try_from(count).unwrap(), uids.len());
+            uids
+        };
+
+        // Act & Assert
+        assert_eq!(list(&["WORK"], false).await, ["todo-1", "todo-2"]);
+        assert_eq!(list(&["work", "urgent"], false).await, ["todo-1"]);
+        assert_eq!(list(&[], true).await, ["todo-1"]);
+        assert_eq!(list(&["work"], true).await, ["todo-1"]);
+        assert!(list(&["home"], false).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn todos_upsert_and_delete_replace_meta() {
+        // Arrange
+        let db = setup_test_db().await;
+        let todo = test_todo("todo-1", "Fix login").with_meta(&[("ticket", "JIRA-1")]);
+        db.todos
+            .upsert(&TodoRecord::from_todo("todo-1", &todo, "default"))
+            .await
+            .unwrap();
+
+        // Act
+        let todo = todo.with_meta(&[("client", "acme")]);
+        db.todos
+            .upsert(&TodoRecord::from_todo("todo-1", &todo, "default"))
+            .await
+            .unwrap();
+
+        // Assert
+        let meta = db.meta.get("todo-1").await.unwrap();
+        assert_eq!(meta, [("client".to_string(), "acme".to_string())]);
+        db.todos.delete("todo-1").await.unwrap();
+        assert!(db.meta.get("todo-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn todos_list_filters_by_start() {
+        // Arrange
+        let db = setup_test_db().await;
+        let now = civil::date(2025, 1, 15)
+            .at(12, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let todos = [
+            test_todo("todo-1", "Unscheduled"),
+            test_todo("todo-2", "Started")
+                .with_start(LooseDateTime::DateOnly(civil::date(2025, 1, 15))),
+            test_todo("todo-3", "Later today").with_start(LooseDateTime::Local(
+                now.checked_add(jiff::Span::new().hours(2)).unwrap(),
+            )),
+            test_todo("todo-4", "Next week")
+                .with_start(LooseDateTime::DateOnly(civil::date(2025, 1, 20))),
+        ];
+        for todo in &todos {
+            db.todos
+                .upsert(&TodoRecord::from_todo(&todo.uid, todo, "default"))
+                .await
+                .unwrap();
+        }
+        let list = async |start_after: Option<&Zoned>, started_by: Option<&Zoned>| {
+            let conds = ResolvedTodoConditions {
+                status: None,
+                due: None,
+                start_after: start_after.cloned(),
+                started_by: started_by.cloned(),
+                calendar_id: None,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                waiting: false,
+                waiting_for: None,
+                followed_up_by: None,
+                categories: Vec::new(),
+                undated: false,
+            };
+            let sort = [ResolvedTodoSort::Summary {
+                order: crate::SortOrder::Asc,
+                collation: Collation::Binary,
+            }];
+            let pager = Pager {
+                limit: 10,
+                offset: 0,
+            };
+            let todos = db.todos.list(&conds, &sort, &pager).await.unwrap();
+            todos
+                .iter()
+                .map(|t| t.summary().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Act & Assert
+        assert_eq!(list(None, Some(&now)).await, ["Started", "Unscheduled"]);
+        assert_eq!(list(Some(&now), None).await, ["Later today", "Next week"]);
+        let today_end = now.with().time(civil::time(23, 59, 59, 0)).build().unwrap();
+        assert_eq!(list(Some(&today_end), None).await, ["Next week"]);
+    }
+
     #[tokio::test]
     async fn todos_list_respects_limit() {
         // Arrange
@@ -869,6 +1945,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -902,6 +1988,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let sort = vec![];
         let pager = Pager {
@@ -935,6 +2031,16 @@ mod tests {
             status: None,
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let count = db.todos.count(&conds).await.unwrap();
 
@@ -968,6 +2074,16 @@ mod tests {
             status: Some(TodoStatus::NeedsAction),
             due: None,
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let count = db.todos.count(&conds).await.unwrap();
 
@@ -1011,6 +2127,16 @@ mod tests {
             status: None,
             due: Some(cutoff),
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let count = db.todos.count(&conds).await.unwrap();
 
@@ -1028,7 +2154,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("todo-1", crate::Kind::Todo)
+            .get_or_assign_short_id("todo-1", Kind::Todo)
             .await
             .unwrap();
 
@@ -1053,7 +2179,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("todo-1", crate::Kind::Todo)
+            .get_or_assign_short_id("todo-1", Kind::Todo)
             .await
             .unwrap();
 
@@ -1079,7 +2205,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("todo-1", crate::Kind::Todo)
+            .get_or_assign_short_id("todo-1", Kind::Todo)
             .await
             .unwrap();
 
@@ -1089,7 +2215,7 @@ mod tests {
             .await
             .unwrap();
         db.short_ids
-            .get_or_assign_short_id("todo-2", crate::Kind::Todo)
+            .get_or_assign_short_id("todo-2", Kind::Todo)
             .await
             .unwrap();
 
@@ -1145,10 +2271,295 @@ mod tests {
             status: Some(TodoStatus::NeedsAction),
             due: Some(cutoff),
             calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
         };
         let count = db.todos.count(&conds).await.unwrap();
 
         // Assert
         assert_eq!(count, 1);
     }
+
+    #[tokio::test]
+    async fn todos_list_10k_rows_within_budget() {
+        const ROWS: i64 = 10_000;
+        const BUDGET: std::time::Duration = std::time::Duration::from_millis(500);
+
+        // Arrange
+        let db = setup_test_db().await;
+        sqlx::query(
+            "\
+WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < ?)
+INSERT INTO todos (uid, calendar_id, completed, description, percent, priority, status, summary, due)
+SELECT 'todo-' || i, 'default', '', 'A longer description of the todo', NULL, i % 10,
+       'NEEDS-ACTION', 'Todo ' || i, '2026-01-01T09:00:00'
+FROM n;
+",
+        )
+        .bind(ROWS)
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let short_ids = crate::short_id::ShortIds::new(db.clone());
+        let conds = ResolvedTodoConditions {
+            status: Some(TodoStatus::NeedsAction),
+            due: None,
+            calendar_id: None,
+            start_after: None,
+            started_by: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let sort = vec![ResolvedTodoSort::Due(crate::SortOrder::Asc)];
+        let pager = Pager {
+            limit: ROWS,
+            offset: 0,
+        };
+
+        // The first listing assigns the short IDs
+        let todos = db.todos.list(&conds, &sort, &pager).await.unwrap();
+        short_ids.todos(todos).await.unwrap();
+
+        // Act
+        let start = std::time::Instant::now();
+        let todos = db.todos.list(&conds, &sort, &pager).await.unwrap();
+        let todos = short_ids.todos(todos).await.unwrap();
+        let elapsed = start.elapsed();
+
+        // Assert
+        assert_eq!(todos.len(), 10_000);
+        assert!(
+            elapsed < BUDGET,
+            "listing {ROWS} todos took {elapsed:?}, budget is {BUDGET:?}"
+        );
+    }
+
+    /// Inserts a todo completed in 2020, one completed in 2026 and an open one, then archives
+    /// the todos completed before 2025.
+    async fn setup_archived_todo(db: &crate::db::Db) {
+        let at = |year| {
+            civil::date(year, 1, 1)
+                .at(9, 0, 0, 0)
+                .to_zoned(TimeZone::UTC)
+                .unwrap()
+        };
+        let todos = [
+            test_todo("old", "Old")
+                .with_status(TodoStatus::Completed)
+                .with_completed(at(2020)),
+            test_todo("recent", "Recent")
+                .with_status(TodoStatus::Completed)
+                .with_completed(at(2026)),
+            test_todo("open", "Open"),
+        ];
+        for todo in &todos {
+            let record = TodoRecord::from_todo(&todo.uid, todo, "default");
+            db.todos.upsert(&record).await.unwrap();
+        }
+
+        let cutoff = civil::date(2025, 1, 1).to_zoned(TimeZone::UTC).unwrap();
+        let moved = db
+            .todos
+            .archive_completed_before(&cutoff, &cutoff)
+            .await
+            .unwrap();
+        assert_eq!(moved, 1);
+    }
+
+    fn all_conditions(include_archived: bool) -> ResolvedTodoConditions {
+        ResolvedTodoConditions {
+            status: None,
+            due: None,
+            start_after: None,
+            started_by: None,
+            calendar_id: None,
+            include_archived,
+            meta: Vec::new(),
+            inbox: false,
+            waiting: false,
+            waiting_for: None,
+            followed_up_by: None,
+            categories: Vec::new(),
+            undated: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn todos_archive_hides_old_completed_todos_from_listings() {
+        // Arrange
+        let db = setup_test_db().await;
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+
+        // Act
+        setup_archived_todo(&db).await;
+
+        // Assert
+        let hot = db
+            .todos
+            .list(&all_conditions(false), &[], &pager)
+            .await
+            .unwrap();
+        let mut uids: Vec<_> = hot.iter().map(|t| t.uid().into_owned()).collect();
+        uids.sort();
+        assert_eq!(uids, ["open", "recent"]);
+        let all = db
+            .todos
+            .list(&all_conditions(true), &[], &pager)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(db.todos.count(&all_conditions(true)).await.unwrap(), 3);
+        assert_eq!(db.todos.count_archived().await.unwrap(), 1);
+        assert!(db.todos.get("old").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn todos_upsert_keeps_unchanged_archived_todo() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_todo(&db).await;
+        let archived = db.todos.get("old").await.unwrap().unwrap();
+
+        // Act
+        db.todos.upsert(&archived).await.unwrap();
+
+        // Assert
+        assert_eq!(db.todos.count(&all_conditions(false)).await.unwrap(), 2);
+        assert_eq!(db.todos.count_archived().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn todos_upsert_unarchives_reopened_todo() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_todo(&db).await;
+        let reopened = test_todo("old", "Old");
+
+        // Act
+        let record = TodoRecord::from_todo("old", &reopened, "default");
+        db.todos.upsert(&record).await.unwrap();
+
+        // Assert
+        assert_eq!(db.todos.count(&all_conditions(false)).await.unwrap(), 3);
+        assert_eq!(db.todos.count_archived().await.unwrap(), 0);
+        let todo = db.todos.get("old").await.unwrap().unwrap();
+        assert_eq!(todo.status(), TodoStatus::NeedsAction);
+    }
+
+    #[tokio::test]
+    async fn todos_list_by_uid_prefix_includes_archived_todos() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_todo(&db).await;
+        for uid in ["old-2", "other"] {
+            let record = TodoRecord::from_todo(uid, &test_todo(uid, "Todo"), "default");
+            db.todos.upsert(&record).await.unwrap();
+        }
+
+        // Act
+        let todos = db.todos.list_by_uid_prefix("old").await.unwrap();
+
+        // Assert
+        let uids: Vec<_> = todos.iter().map(|t| t.uid().into_owned()).collect();
+        assert_eq!(uids, ["old", "old-2"]);
+        assert!(
+            db.todos
+                .list_by_uid_prefix("old-3")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn todos_upsert_stores_parent() {
+        // Arrange
+        let db = setup_test_db().await;
+        let todo = test_todo("child", "Child").with_parent("parent");
+        let record = TodoRecord::from_todo("child", &todo, "default");
+
+        // Act
+        db.todos.upsert(&record).await.unwrap();
+
+        // Assert
+        let retrieved = db.todos.get("child").await.unwrap().unwrap();
+        assert_eq!(retrieved.parent_uid().as_deref(), Some("parent"));
+        let root = test_todo("root", "Root");
+        let record = TodoRecord::from_todo("root", &root, "default");
+        db.todos.upsert(&record).await.unwrap();
+        let retrieved = db.todos.get("root").await.unwrap().unwrap();
+        assert_eq!(retrieved.parent_uid(), None);
+    }
+
+    #[tokio::test]
+    async fn todos_count_subtasks_includes_archived_and_skips_cancelled() {
+        // Arrange
+        let db = setup_test_db().await;
+        let completed = civil::date(2020, 1, 1)
+            .at(9, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let todos = [
+            test_todo("a-1", "Done")
+                .with_parent("a")
+                .with_status(TodoStatus::Completed)
+                .with_completed(completed.clone()),
+            test_todo("a-2", "Open").with_parent("a"),
+            test_todo("a-3", "Dropped")
+                .with_parent("a")
+                .with_status(TodoStatus::Cancelled),
+            test_todo("b-1", "Open").with_parent("b"),
+            test_todo("c-1", "Other parent").with_parent("c"),
+        ];
+        for todo in &todos {
+            let record = TodoRecord::from_todo(&todo.uid, todo, "default");
+            db.todos.upsert(&record).await.unwrap();
+        }
+        let cutoff = civil::date(2025, 1, 1).to_zoned(TimeZone::UTC).unwrap();
+        let moved = db
+            .todos
+            .archive_completed_before(&cutoff, &cutoff)
+            .await
+            .unwrap();
+        assert_eq!(moved, 1);
+
+        // Act
+        let counts = db.todos.count_subtasks(&["a", "b", "none"]).await.unwrap();
+
+        // Assert
+        assert_eq!(counts, [("a".to_string(), 1, 2), ("b".to_string(), 0, 1)]);
+        assert!(db.todos.count_subtasks(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn todos_delete_removes_archived_todo() {
+        // Arrange
+        let db = setup_test_db().await;
+        setup_archived_todo(&db).await;
+
+        // Act
+        db.todos.delete("old").await.unwrap();
+
+        // Assert
+        assert!(db.todos.get("old").await.unwrap().is_none());
+        assert_eq!(db.todos.count_archived().await.unwrap(), 0);
+    }
 }
@@ -0,0 +1,465 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Collapsing the copies of an event kept in several calendars, such as a meeting in both a
+//! personal and a team calendar.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+use crate::{CheckIn, Event, EventStatus, LooseDateTime};
+
+/// How the copies of an event in several calendars are collapsed in listings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupeMode {
+    /// Copies sharing a UID are listed once.
+    #[default]
+    Uid,
+    /// Also events with different UIDs, as left by copying an event by hand, when their summary,
+    /// start and end are the same and they have attendees in common.
+    Heuristic,
+    /// Every copy is listed.
+    Off,
+}
+
+impl FromStr for DedupeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uid" => Ok(Self::Uid),
+            "heuristic" => Ok(Self::Heuristic),
+            "off" => Ok(Self::Off),
+            _ => Err(format!(
+                "Invalid dedupe mode '{s}', expected uid, heuristic or off"
+            )),
+        }
+    }
+}
+
+/// An event standing for its copies in several calendars, see
+/// [`Aim::dedupe_events`](crate::Aim::dedupe_events).
+///
+/// The primary copy is one in a writable calendar when there is any, and its calendar comes
+/// first in [`Event::calendar_id`] and [`Event::mirrored_in`].
+#[derive(Debug)]
+pub struct DedupedEvent<E: Event> {
+    primary: E,
+    calendar_ids: Vec<String>,
+    copies: Vec<E>,
+}
+
+impl<E: Event> DedupedEvent<E> {
+    fn single(event: E) -> Self {
+        let calendar_ids = event
+            .calendar_id()
+            .map(Cow::into_owned)
+            .into_iter()
+            .collect();
+        Self {
+            primary: event,
+            calendar_ids,
+            copies: Vec::new(),
+        }
+    }
+
+    /// The copy that details and edits go to.
+    pub fn primary(&self) -> &E {
+        &self.primary
+    }
+
+    /// The other copies with a UID of their own, collapsed by [`DedupeMode::Heuristic`].
+    pub fn copies(&self) -> &[E] {
+        &self.copies
+    }
+
+    /// The IDs of the calendars holding a copy, the calendar of the primary copy first.
+    pub fn calendar_ids(&self) -> &[String] {
+        &self.calendar_ids
+    }
+
+    /// Consumes the group, returning the primary copy.
+    pub fn into_primary(self) -> E {
+        self.primary
+    }
+}
+
+impl<E: Event> Event for DedupedEvent<E> {
+    fn short_id(&self) -> Option<NonZeroU32> {
+        self.primary.short_id()
+    }
+
+    fn uid(&self) -> Cow<'_, str> {
+        self.primary.uid()
+    }
+
+    fn description(&self) -> Option<Cow<'_, str>> {
+        self.primary.description()
+    }
+
+    fn start(&self) -> Option<LooseDateTime> {
+        self.primary.start()
+    }
+
+    fn end(&self) -> Option<LooseDateTime> {
+        self.primary.end()
+    }
+
+    fn status(&self) -> Option<EventStatus> {
+        self.primary.status()
+    }
+
+    fn summary(&self) -> Cow<'_, str> {
+        self.primary.summary()
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.primary.location()
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.primary.organizer()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.primary.url()
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.primary.categories()
+    }
+
+    fn transparent(&self) -> bool {
+        self.primary.transparent()
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.primary.color()
+    }
+
+    fn split_from(&self) -> Option<Cow<'_, str>> {
+        self.primary.split_from()
+    }
+
+    fn check_in(&self) -> Option<CheckIn> {
+        self.primary.check_in()
+    }
+
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        self.primary.related_to()
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        match self.calendar_ids.first() {
+            Some(id) => Some(id.into()),
+            None => self.primary.calendar_id(),
+        }
+    }
+
+    fn mirrored_in(&self) -> Vec<Cow<'_, str>> {
+        self.calendar_ids.iter().skip(1).map(Into::into).collect()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.primary.meta()
+    }
+}
+
+/// What is known about the events being collapsed beyond the [`Event`] trait.
+#[derive(Debug, Default)]
+pub(crate) struct DedupeContext {
+    /// The IDs of every calendar holding a copy of each UID.
+    pub mirrors: HashMap<String, Vec<String>>,
+    /// The lowercased addresses of the attendees of each UID, for [`DedupeMode::Heuristic`].
+    pub attendees: HashMap<String, HashSet<String>>,
+    /// The IDs of the calendars events may be written to.
+    pub writable: HashSet<String>,
+}
+
+impl DedupeContext {
+    /// The summary, start and end that copies made by hand share, if the event has an end.
+    pub fn heuristic_key(event: &impl Event) -> Option<(String, String, String)> {
+        let start = event.start()?.format_stable();
+        let end = event.end()?.format_stable();
+        Some((event.summary().into_owned(), start, end))
+    }
+
+    /// Whether two events have any attendee in common.
+    fn share_attendees(&self, a: &str, b: &str) -> bool {
+        match (self.attendees.get(a), self.attendees.get(b)) {
+            (Some(a), Some(b)) => !a.is_disjoint(b),
+            _ => false,
+        }
+    }
+
+    /// Orders `calendar_ids` with the writable ones first, keeping the order otherwise.
+    fn writable_first(&self, calendar_ids: &mut [String]) {
+        calendar_ids.sort_by_key(|id| !self.writable.contains(id));
+    }
+}
+
+/// Collapses `events` with `mode`, keeping the order of their first copy.
+pub(crate) fn collapse<E: Event>(
+    events: Vec<E>,
+    mode: DedupeMode,
+    ctx: &DedupeContext,
+) -> Vec<DedupedEvent<E>> {
+    if mode == DedupeMode::Off {
+        return events.into_iter().map(DedupedEvent::single).collect();
+    }
+
+    // Copies sharing a UID
+    let mut groups: Vec<DedupedEvent<E>> = Vec::new();
+    let mut by_uid: HashMap<String, usize> = HashMap::new();
+    for event in events {
+        let uid = event.uid().into_owned();
+        if let Some(group) = by_uid.get(&uid).and_then(|&i| groups.get_mut(i)) {
+            group.merge(DedupedEvent::single(event), ctx);
+        } else {
+            by_uid.insert(uid, groups.len());
+            groups.push(DedupedEvent::single(event));
+        }
+    }
+    for group in &mut groups {
+        let uid = group.primary.uid();
+        if let Some(mirrors) = ctx.mirrors.get(uid.as_ref()) {
+            for id in mirrors {
+                if !group.calendar_ids.contains(id) {
+                    group.calendar_ids.push(id.clone());
+                }
+            }
+        }
+        ctx.writable_first(&mut group.calendar_ids);
+    }
+    if mode == DedupeMode::Uid {
+        return groups;
+    }
+
+    // Copies made by hand, in other calendars and with attendees in common
+    let mut collapsed: Vec<DedupedEvent<E>> = Vec::with_capacity(groups.len());
+    for group in groups {
+        let key = DedupeContext::heuristic_key(&group.primary);
+        let same = collapsed.iter_mut().find(|other| {
+            key.is_some()
+                && DedupeContext::heuristic_key(&other.primary) == key
+                && other
+                    .calendar_ids
+                    .iter()
+                    .all(|id| !group.calendar_ids.contains(id))
+                && ctx.share_attendees(&other.primary.uid(), &group.primary.uid())
+        });
+        match same {
+            Some(other) => other.merge(group, ctx),
+            None => collapsed.push(group),
+        }
+    }
+    collapsed
+}
+
+impl<E: Event> DedupedEvent<E> {
+    /// Merges `other` into this group, making the copy in a writable calendar the primary one.
+    fn merge(&mut self, mut other: Self, ctx: &DedupeContext) {
+        let writable = |group: &Self| {
+            group
+                .calendar_ids
+                .first()
+                .is_some_and(|id| ctx.writable.contains(id))
+        };
+        if !writable(self) && writable(&other) {
+            std::mem::swap(self, &mut other);
+        }
+        for id in other.calendar_ids {
+            if !self.calendar_ids.contains(&id) {
+                self.calendar_ids.push(id);
+            }
+        }
+        ctx.writable_first(&mut self.calendar_ids);
+        if other.primary.uid() != self.primary.uid() {
+            self.copies.push(other.primary);
+        }
+        self.copies.extend(other.copies);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestCopy {
+        uid: &'static str,
+        calendar_id: &'static str,
+        summary: &'static str,
+        start: i8,
+        end: i8,
+    }
+
+    impl Event for TestCopy {
+        fn uid(&self) -> Cow<'_, str> {
+            self.uid.into()
+        }
+
+        fn description(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn start(&self) -> Option<LooseDateTime> {
+            Some(LooseDateTime::Floating(
+                date(2025, 1, 15).at(self.start, 0, 0, 0),
+            ))
+        }
+
+        fn end(&self) -> Option<LooseDateTime> {
+            Some(LooseDateTime::Floating(
+                date(2025, 1, 15).at(self.end, 0, 0, 0),
+            ))
+        }
+
+        fn status(&self) -> Option<EventStatus> {
+            None
+        }
+
+        fn summary(&self) -> Cow<'_, str> {
+            self.summary.into()
+        }
+
+        fn calendar_id(&self) -> Option<Cow<'_, str>> {
+            Some(self.calendar_id.into())
+        }
+    }
+
+    fn copy(uid: &'static str, calendar_id: &'static str, summary: &'static str) -> TestCopy {
+        TestCopy {
+            uid,
+            calendar_id,
+            summary,
+            start: 9,
+            end: 10,
+        }
+    }
+
+    fn context(attendees: &[(&str, &[&str])]) -> DedupeContext {
+        DedupeContext {
+            mirrors: HashMap::new(),
+            attendees: attendees
+                .iter()
+                .map(|(uid, addresses)| {
+                    let addresses = addresses.iter().map(ToString::to_string).collect();
+                    (uid.to_string(), addresses)
+                })
+                .collect(),
+            writable: ["personal".to_string()].into(),
+        }
+    }
+
+    fn calendars<E: Event>(groups: &[DedupedEvent<E>]) -> Vec<Vec<&str>> {
+        groups
+            .iter()
+            .map(|g| g.calendar_ids().iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn dedupe_mode_parses() {
+        assert_eq!("uid".parse(), Ok(DedupeMode::Uid));
+        assert_eq!("heuristic".parse(), Ok(DedupeMode::Heuristic));
+        assert_eq!("off".parse(), Ok(DedupeMode::Off));
+        assert!("all".parse::<DedupeMode>().is_err());
+    }
+
+    #[test]
+    fn dedupe_uid_lists_mirrors_with_writable_first() {
+        let mut ctx = context(&[]);
+        ctx.mirrors.insert(
+            "sync".to_string(),
+            vec!["personal".to_string(), "team".to_string()],
+        );
+        let events = vec![copy("sync", "team", "Sync"), copy("other", "team", "Other")];
+
+        let groups = collapse(events, DedupeMode::Uid, &ctx);
+        assert_eq!(calendars(&groups), [vec!["personal", "team"], vec!["team"]]);
+        let sync = groups.first().unwrap();
+        assert_eq!(sync.calendar_id().as_deref(), Some("personal"));
+        assert_eq!(sync.mirrored_in(), ["team"]);
+    }
+
+    #[test]
+    fn dedupe_uid_collapses_rows_sharing_a_uid() {
+        let events = vec![
+            copy("sync", "team", "Sync"),
+            copy("sync", "personal", "Sync"),
+        ];
+        let groups = collapse(events, DedupeMode::Uid, &context(&[]));
+        assert_eq!(calendars(&groups), [vec!["personal", "team"]]);
+        assert_eq!(groups.first().unwrap().primary().calendar_id, "personal");
+        assert!(groups.first().unwrap().copies().is_empty());
+    }
+
+    #[test]
+    fn dedupe_heuristic_collapses_copies_with_common_attendees() {
+        let ctx = context(&[
+            ("a", &["mailto:ann@example.com", "mailto:bo@example.com"]),
+            ("b", &["mailto:bo@example.com"]),
+        ]);
+        let events = vec![copy("b", "team", "Review"), copy("a", "personal", "Review")];
+
+        let groups = collapse(events, DedupeMode::Heuristic, &ctx);
+        assert_eq!(calendars(&groups), [vec!["personal", "team"]]);
+        let group = groups.first().unwrap();
+        assert_eq!(group.uid(), "a", "the writable copy is the primary one");
+        assert_eq!(group.copies().len(), 1);
+
+        let events = vec![copy("b", "team", "Review"), copy("a", "personal", "Review")];
+        assert_eq!(collapse(events, DedupeMode::Uid, &ctx).len(), 2);
+    }
+
+    #[test]
+    fn dedupe_heuristic_keeps_distinct_events_apart() {
+        // Standups of different teams at the same time
+        let ctx = context(&[
+            ("a", &["mailto:ann@example.com"]),
+            ("b", &["mailto:bo@example.com"]),
+            ("c", &["mailto:ann@example.com"]),
+        ]);
+        let events = vec![
+            copy("a", "personal", "Standup"),
+            copy("b", "team", "Standup"),
+        ];
+        assert_eq!(collapse(events, DedupeMode::Heuristic, &ctx).len(), 2);
+
+        // Without attendees, nothing tells copies from distinct events
+        let events = vec![
+            copy("x", "personal", "Standup"),
+            copy("y", "team", "Standup"),
+        ];
+        assert_eq!(collapse(events, DedupeMode::Heuristic, &ctx).len(), 2);
+
+        // Different end
+        let mut longer = copy("c", "team", "Standup");
+        longer.end = 11;
+        let events = vec![copy("a", "personal", "Standup"), longer];
+        assert_eq!(collapse(events, DedupeMode::Heuristic, &ctx).len(), 2);
+
+        // Same calendar
+        let events = vec![
+            copy("a", "personal", "Standup"),
+            copy("c", "personal", "Standup"),
+        ];
+        assert_eq!(collapse(events, DedupeMode::Heuristic, &ctx).len(), 2);
+    }
+
+    #[test]
+    fn dedupe_off_lists_every_copy() {
+        let events = vec![
+            copy("sync", "team", "Sync"),
+            copy("sync", "personal", "Sync"),
+        ];
+        assert_eq!(collapse(events, DedupeMode::Off, &context(&[])).len(), 2);
+    }
+}
@@ -0,0 +1,321 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use aimcal_ical::{
+    AlarmTriggerRelationship, Attachment, AttachmentValue, Attendee, CalendarComponent, Categories,
+    Conference, ICalendar, LastModified, Location, Organizer, Property, TriggerValue, Url, VAlarm,
+    VEvent, VTodo, ValueDuration, fmt::format, property::RRule,
+};
+use jiff::Zoned;
+
+use crate::meta::read_meta;
+use crate::recurrence::describe_recurrence;
+
+/// Properties of an event or todo beyond the ones kept in the database, read from its store.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ItemDetails {
+    /// Calendar the item belongs to.
+    pub calendar_id: String,
+    /// Location of the item in its store, such as the file path or the `CalDAV` href.
+    pub resource_id: Option<String>,
+    /// Categories of the item.
+    pub categories: Vec<String>,
+    /// Where the item takes place.
+    pub location: Option<String>,
+    /// URL associated with the item.
+    pub url: Option<String>,
+    /// Organizer of the item.
+    pub organizer: Option<String>,
+    /// When the item was created.
+    pub created: Option<Zoned>,
+    /// When the item was last modified.
+    pub last_modified: Option<Zoned>,
+    /// Human-readable recurrence rule, see [`describe_recurrence`].
+    pub recurrence: Option<String>,
+    /// Human-readable alarms, such as "display 15 minutes before start".
+    pub alarms: Vec<String>,
+    /// Attendees with their participation status.
+    pub attendees: Vec<String>,
+    /// URIs of the attachments, or a description of the ones held inline.
+    pub attachments: Vec<String>,
+    /// URIs to join the item online, from `CONFERENCE` properties, see RFC 7986 section 5.11.
+    pub conferences: Vec<String>,
+    /// The event this event was split from, as its short ID and summary, or its UID if it is
+    /// gone.
+    pub split_from: Option<String>,
+    /// Custom metadata as key-value pairs, sorted by key.
+    pub meta: Vec<(String, String)>,
+    /// The component as serialized in the store.
+    pub raw: Option<String>,
+}
+
+/// The properties shared by events and todos that make up [`ItemDetails`].
+struct ComponentParts<'a> {
+    categories: &'a [Categories<String>],
+    attachments: &'a [Attachment<String>],
+    conferences: &'a [Conference<String>],
+    location: Option<&'a Location<String>>,
+    url: Option<&'a Url<String>>,
+    organizer: Option<&'a Organizer<String>>,
+    last_modified: Option<&'a LastModified<String>>,
+    retained_properties: &'a [Property<String>],
+    rrule: Option<&'a RRule<String>>,
+    alarms: &'a [VAlarm<String>],
+    attendees: &'a [Attendee<String>],
+    /// What a trigger relative to the end refers to, "end" for events and "due" for todos.
+    end_name: &'static str,
+}
+
+impl ItemDetails {
+    /// Builds the details of an event read from its store, in the time zone of `now`.
+    pub(crate) fn from_event(event: VEvent<String>, now: &Zoned) -> Self {
+        let mut details = Self::from_parts(
+            &ComponentParts {
+                categories: &event.categories,
+                attachments: &event.attachments,
+                conferences: &event.conferences,
+                location: event.location.as_ref(),
+                url: event.url.as_ref(),
+                organizer: event.organizer.as_ref(),
+                last_modified: event.last_modified.as_ref(),
+                retained_properties: &event.retained_properties,
+                rrule: event.rrule.as_ref(),
+                alarms: &event.alarms,
+                attendees: &event.attendees,
+                end_name: "end",
+            },
+            now,
+        );
+        details.meta = read_meta(&event.x_properties);
+        details.raw = serialize_component(CalendarComponent::Event(event));
+        details
+    }
+
+    /// Builds the details of a todo read from its store, in the time zone of `now`.
+    pub(crate) fn from_todo(todo: VTodo<String>, now: &Zoned) -> Self {
+        let mut details = Self::from_parts(
+            &ComponentParts {
+                categories: &todo.categories,
+                attachments: &todo.attachments,
+                conferences: &todo.conferences,
+                location: todo.location.as_ref(),
+                url: todo.url.as_ref(),
+                organizer: todo.organizer.as_ref(),
+                last_modified: todo.last_modified.as_ref(),
+                retained_properties: &todo.retained_properties,
+                rrule: todo.rrule.as_ref(),
+                alarms: &todo.alarms,
+                attendees: &todo.attendees,
+                end_name: "due",
+            },
+            now,
+        );
+        details.meta = read_meta(&todo.x_properties);
+        details.raw = serialize_component(CalendarComponent::Todo(todo));
+        details
+    }
+
+    fn from_parts(parts: &ComponentParts<'_>, now: &Zoned) -> Self {
+        let tz = now.time_zone();
+        Self {
+            categories: parts
+                .categories
+                .iter()
+                .flat_map(|c| c.values.iter().map(ToString::to_string))
+                .collect(),
+            location: parts.location.map(|l| l.content.to_string()),
+            url: parts.url.map(|u| u.uri.clone()),
+            organizer: parts
+                .organizer
+                .map(|o| describe_address(&o.cal_address, o.cn.as_deref())),
+            created: parts.retained_properties.iter().find_map(|p| match p {
+                Property::Created(c) => c.zoned().map(|c| c.with_time_zone(tz.clone())),
+                _ => None,
+            }),
+            last_modified: parts
+                .last_modified
+                .and_then(|m| m.zoned())
+                .map(|m| m.with_time_zone(tz.clone())),
+            recurrence: parts
+                .rrule
+                .map(|r| describe_recurrence(&r.value, now.date())),
+            alarms: parts
+                .alarms
+                .iter()
+                .map(|a| describe_alarm(a, parts.end_name))
+                .collect(),
+            attendees: parts
+                .attendees
+                .iter()
+                .map(|a| {
+                    let address = describe_address(&a.cal_address, a.cn.as_deref());
+                    format!("{address} ({})", a.part_stat.to_string().to_lowercase())
+                })
+                .collect(),
+            attachments: parts.attachments.iter().map(describe_attachment).collect(),
+            conferences: parts.conferences.iter().map(|c| c.uri.clone()).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Describes an attachment by its URI, or by its media type if it is held inline.
+fn describe_attachment(attachment: &Attachment<String>) -> String {
+    match &attachment.value {
+        AttachmentValue::Uri(uri) => uri.clone(),
+        AttachmentValue::Binary(_) => match &attachment.fmt_type {
+            Some(fmt_type) => format!("inline {fmt_type}"),
+            None => "inline attachment".to_string(),
+        },
+    }
+}
+
+/// Formats a calendar user address as "Name <email>", dropping the `mailto:` scheme.
+pub(crate) fn describe_address(address: &str, name: Option<&str>) -> String {
+    let address = match address.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => address.get(7..).unwrap_or(""),
+        _ => address,
+    };
+    match name {
+        Some(name) => format!("{name} <{address}>"),
+        None => address.to_string(),
+    }
+}
+
+fn describe_alarm(alarm: &VAlarm<String>, end_name: &str) -> String {
+    let action = alarm.action.value.as_str().to_lowercase();
+    let when = match &alarm.trigger.value {
+        TriggerValue::Duration(duration) => {
+            let anchor = match alarm.trigger.related {
+                Some(AlarmTriggerRelationship::End) => end_name,
+                _ => "start",
+            };
+            match describe_duration(duration) {
+                None => format!("at {anchor}"),
+                Some((text, true)) => format!("{text} after {anchor}"),
+                Some((text, false)) => format!("{text} before {anchor}"),
+            }
+        }
+        TriggerValue::DateTime(dt) => match dt.civil_date_time() {
+            Some(dt) => format!("at {}", dt.strftime("%Y-%m-%d %H:%M")),
+            None => format!("on {}", dt.date().civil_date()),
+        },
+    };
+    format!("{action} {when}")
+}
+
+/// Describes a duration as e.g. "1 day 2 hours", with whether it is positive, or `None` if zero.
+fn describe_duration(duration: &ValueDuration) -> Option<(String, bool)> {
+    let (positive, units) = match *duration {
+        ValueDuration::DateTime {
+            positive,
+            day,
+            hour,
+            minute,
+            second,
+        } => (
+            positive,
+            vec![
+                (day, "day"),
+                (hour, "hour"),
+                (minute, "minute"),
+                (second, "second"),
+            ],
+        ),
+        ValueDuration::Week { positive, week } => (positive, vec![(week, "week")]),
+    };
+    let parts: Vec<_> = units
+        .into_iter()
+        .filter(|&(n, _)| n > 0)
+        .map(|(n, unit)| match n {
+            1 => format!("1 {unit}"),
+            n => format!("{n} {unit}s"),
+        })
+        .collect();
+    (!parts.is_empty()).then(|| (parts.join(" "), positive))
+}
+
+/// Serializes the component alone, without the enclosing `VCALENDAR`.
+fn serialize_component(component: CalendarComponent<String>) -> Option<String> {
+    let calendar = ICalendar {
+        components: vec![component],
+        ..ICalendar::new()
+    };
+    let content = match format(&calendar) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to serialize component");
+            return None;
+        }
+    };
+    let start = content.find("\nBEGIN:")? + 1;
+    let end = content.rfind("END:VCALENDAR")?;
+    let lines: Vec<_> = content.get(start..end)?.lines().collect();
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_addresses() {
+        assert_eq!(
+            describe_address("mailto:jane@example.com", Some("Jane")),
+            "Jane <jane@example.com>"
+        );
+        assert_eq!(
+            describe_address("MAILTO:jane@example.com", None),
+            "jane@example.com"
+        );
+        assert_eq!(describe_address("urn:uuid:1", None), "urn:uuid:1");
+    }
+
+    #[test]
+    fn describes_durations() {
+        let duration = ValueDuration::DateTime {
+            positive: false,
+            day: 1,
+            hour: 2,
+            minute: 0,
+            second: 0,
+        };
+        assert_eq!(
+            describe_duration(&duration),
+            Some(("1 day 2 hours".to_string(), false))
+        );
+        let duration = ValueDuration::Week {
+            positive: true,
+            week: 0,
+        };
+        assert_eq!(describe_duration(&duration), None);
+    }
+
+    #[test]
+    fn collects_attachments_and_conferences() {
+        let src = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:e1\r\n\
+DTSTAMP:20260101T000000Z\r\n\
+DTSTART:20260105T090000Z\r\n\
+ATTACH:https://example.com/agenda.pdf\r\n\
+ATTACH;FMTTYPE=text/plain;ENCODING=BASE64;VALUE=BINARY:aGVsbG8=\r\n\
+CONFERENCE;VALUE=URI;FEATURE=VIDEO:https://meet.example.com/abc\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+        let calendar = aimcal_ical::parse(src).unwrap().remove(0).to_owned();
+        let Some(CalendarComponent::Event(event)) = calendar.components.into_iter().next() else {
+            panic!("expected an event");
+        };
+        let now = Zoned::now();
+        let details = ItemDetails::from_event(event, &now);
+        assert_eq!(
+            details.attachments,
+            ["https://example.com/agenda.pdf", "inline text/plain"]
+        );
+        assert_eq!(details.conferences, ["https://meet.example.com/abc"]);
+    }
+}
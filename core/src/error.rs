@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::io;
+use std::path::PathBuf;
+
+use aimcal_caldav::CalDavError;
+
+use crate::store::StoreError;
+
+/// Errors returned by the [`Aim`](crate::Aim) API.
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum AimError {
+    /// No item matches the given identifier.
+    #[error("{kind} not found: {id}")]
+    NotFound {
+        /// What was looked up, e.g. `"Todo"` or `"Calendar"`.
+        kind: &'static str,
+        /// The identifier as given by the caller.
+        id: String,
+    },
+
+    /// A short ID, UID or UID prefix resolves to more than one item.
+    #[error("Ambiguous id {id}, candidates: {}", candidates.join(", "))]
+    AmbiguousShortId {
+        /// The identifier as given by the caller.
+        id: String,
+        /// UIDs of all items matching the identifier.
+        candidates: Vec<String>,
+    },
+
+    /// The calendar store failed, e.g. the `CalDAV` server is unreachable or a file cannot be written.
+    #[error("Backend error: {0}")]
+    Backend(#[source] StoreError),
+
+    /// A calendar source file cannot be parsed.
+    #[error("Failed to parse {}: {}", file.display(), errors.join("; "))]
+    Parse {
+        /// Path or URL of the source.
+        file: PathBuf,
+        /// Parse errors, in order of appearance.
+        errors: Vec<String>,
+    },
+
+    /// The item was modified concurrently in the store.
+    #[error("Conflicting changes to {uid}, it was modified elsewhere")]
+    Conflict {
+        /// UID of the conflicting item.
+        uid: String,
+    },
+
+    /// An input value is invalid.
+    #[error("Invalid {field}: {reason}")]
+    InvalidInput {
+        /// Name of the offending field.
+        field: &'static str,
+        /// Why the value is rejected.
+        reason: String,
+    },
+
+    /// The configuration is invalid.
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    /// The local database failed.
+    #[error("{context}: {source}")]
+    Database {
+        /// What failed, such as `Failed to run migrations`.
+        context: &'static str,
+        /// The error of the database.
+        #[source]
+        source: sqlx::Error,
+    },
+
+    /// The local database was written by a newer aim, with a schema this build does not know.
+    #[error(
+        "Database was created by {created_by}, this is aim {}; upgrade aim or restore a backup",
+        env!("CARGO_PKG_VERSION")
+    )]
+    SchemaTooNew {
+        /// The aim that created the schema, such as `aim 0.9.0`, or `a newer aim` if unknown.
+        created_by: String,
+    },
+
+    /// The local database was written by a newer aim that dropped columns this build reads.
+    #[error(
+        "Database was created by {created_by}, which dropped columns this aim reads ({}); upgrade \
+         aim or restore a backup",
+        missing.join(", ")
+    )]
+    SchemaIncompatible {
+        /// The aim that created the schema, such as `aim 0.9.0`, or `a newer aim` if unknown.
+        created_by: String,
+        /// The columns this build reads that the database lacks.
+        missing: Vec<String>,
+    },
+
+    /// Another aim holds the lock of the state directory.
+    #[error("The state directory is in use by {holder}, see {}", path.display())]
+    Locked {
+        /// Path of the lock file.
+        path: PathBuf,
+        /// The aim holding the lock, such as its PID and host.
+        holder: String,
+    },
+
+    /// Any other error.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AimError {
+    /// Converts a store error, unwrapping errors the store already classified.
+    ///
+    /// A failed `CalDAV` precondition means the `ETag` changed under us, so it
+    /// is reported as a conflict on `uid`.
+    pub(crate) fn from_store(uid: &str, e: StoreError) -> Self {
+        match e.downcast_ref::<CalDavError>() {
+            Some(CalDavError::PreconditionFailed(_)) => AimError::Conflict {
+                uid: uid.to_string(),
+            },
+            _ => e.into(),
+        }
+    }
+
+    /// Whether a store failed to reach its server, as a `CalDAV` server or subscription
+    /// feed does, rather than failing on local files.
+    #[must_use]
+    pub fn is_network(&self) -> bool {
+        let AimError::Backend(e) = self else {
+            return false;
+        };
+        let mut source: Option<&(dyn Error + 'static)> = Some(e.as_ref());
+        while let Some(e) = source {
+            if e.is::<CalDavError>() || e.is::<reqwest::Error>() {
+                return true;
+            }
+            source = e.source();
+        }
+        false
+    }
+}
+
+/// A store error prefixed with what failed, keeping the error as its source.
+#[derive(Debug, thiserror::Error)]
+#[error("{context}: {source}")]
+pub(crate) struct StoreContext {
+    pub(crate) context: String,
+    #[source]
+    pub(crate) source: StoreError,
+}
+
+impl From<StoreError> for AimError {
+    fn from(e: StoreError) -> Self {
+        match e.downcast::<AimError>() {
+            Ok(e) => *e,
+            Err(e) => AimError::Backend(e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AimError {
+    fn from(source: sqlx::Error) -> Self {
+        AimError::Database {
+            context: "Database error",
+            source,
+        }
+    }
+}
+
+impl From<io::Error> for AimError {
+    fn from(e: io::Error) -> Self {
+        AimError::Backend(Box::new(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_store_unwraps_classified_errors() {
+        let parse = AimError::Parse {
+            file: PathBuf::from("work/a.ics"),
+            errors: vec!["unexpected end of input".to_string()],
+        };
+        let err = AimError::from_store("uid-1", Box::new(parse));
+        assert!(matches!(err, AimError::Parse { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse work/a.ics: unexpected end of input"
+        );
+    }
+
+    #[test]
+    fn from_store_maps_precondition_failed_to_conflict() {
+        let e = CalDavError::PreconditionFailed("ETag mismatch".to_string());
+        let err = AimError::from_store("uid-1", Box::new(e));
+        assert!(matches!(err, AimError::Conflict { uid } if uid == "uid-1"));
+
+        let e = CalDavError::Http("connection refused".to_string());
+        let err = AimError::from_store("uid-1", Box::new(e));
+        assert!(matches!(err, AimError::Backend(_)));
+    }
+
+    #[test]
+    fn is_network_finds_caldav_errors_under_context() {
+        let e = CalDavError::Http("connection refused".to_string());
+        let context = StoreContext {
+            context: "Failed to sync calendar 'work'".to_string(),
+            source: Box::new(e),
+        };
+        let err = AimError::from(StoreError::from(Box::new(context)));
+        assert!(err.is_network());
+        assert_eq!(
+            err.to_string(),
+            "Backend error: Failed to sync calendar 'work': HTTP error: connection refused"
+        );
+
+        let e = io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+        assert!(!AimError::from(e).is_network());
+        assert!(!AimError::from(sqlx::Error::PoolClosed).is_network());
+    }
+}
@@ -5,10 +5,27 @@
 use std::{borrow::Cow, fmt::Display, num::NonZeroU32, str::FromStr};
 
 use aimcal_ical as ical;
-use aimcal_ical::{Description, DtEnd, DtStamp, DtStart, EventStatusValue, Summary, Uid, VEvent};
-use jiff::{Span, ToSpan, Zoned};
+use aimcal_ical::{
+    DateTimeProperty, Description, DtEnd, DtStamp, DtStart, EventStatusValue, ExDate, Property,
+    RDate, RelatedTo, RelationshipType, StringStorage, Summary, TimeTransparencyValue, Uid, VEvent,
+    ValueDate, ValueDateTime, ValueRecurrenceRule, ValueText, ValueTime, property::RRule,
+};
+use jiff::civil::Date;
+use jiff::{SignedDuration, Span, Zoned};
 
-use crate::{DateTimeAnchor, LooseDateTime};
+use crate::alarm::replace_display_alarms;
+use crate::meta::{read_meta, resolve_meta_conditions, write_meta};
+use crate::todo::{x_text, x_text_property};
+use crate::{AimError, AlarmTrigger, Config, DateTimeAnchor, LooseDateTime, Repeat};
+
+/// Property recording the UID of the series an event was split from.
+pub(crate) const X_AIM_SPLIT_FROM: &str = "X-AIM-SPLIT-FROM";
+
+/// Property recording whether an event checked in was attended or skipped.
+pub(crate) const X_AIM_CHECKIN: &str = "X-AIM-CHECKIN";
+
+/// Property holding the note of an event checked in.
+pub(crate) const X_AIM_CHECKIN_NOTE: &str = "X-AIM-CHECKIN-NOTE";
 
 /// Trait representing a calendar event.
 pub trait Event {
@@ -36,21 +53,100 @@ pub trait Event {
 
     /// The summary of the event.
     fn summary(&self) -> Cow<'_, str>;
+
+    /// Whether the event takes whole days, starting on a date without a time.
+    fn is_all_day(&self) -> bool {
+        matches!(self.start(), Some(LooseDateTime::DateOnly(_)))
+    }
+
+    /// The last day the event takes, if it has a start or an end.
+    ///
+    /// The end date of an all-day event is exclusive, see RFC 5545 section 3.6.1, so an event from
+    /// `20250610` to `20250611` takes June 10 only.
+    fn last_day(&self) -> Option<Date> {
+        last_day(self.start().as_ref(), self.end().as_ref())
+    }
+
+    /// Where the event takes place, if available.
+    fn location(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The address of the organizer, such as `mailto:alice@example.com`, if available.
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The URL of the event, such as a ticket or a document, if available.
+    fn url(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The categories of the event. Events read from the cache include the ones added by the
+    /// [`Rules`](crate::Rules).
+    fn categories(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the event is transparent, not blocking time, possibly because of the
+    /// [`Rules`](crate::Rules).
+    fn transparent(&self) -> bool {
+        false
+    }
+
+    /// The color tag set by the [`Rules`](crate::Rules), if any.
+    fn color(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The UID of the recurring event this one was split from, see
+    /// [`Aim::update_event_from`](crate::Aim::update_event_from).
+    fn split_from(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// What actually happened at the event, if it was checked in, see
+    /// [`Aim::check_in_event`](crate::Aim::check_in_event).
+    ///
+    /// The status of a checked in event stays as it was, so other clients see it as usual.
+    fn check_in(&self) -> Option<CheckIn> {
+        None
+    }
+
+    /// The UID of the item the event relates to, such as the todo it is a block of time planned
+    /// for, see [`Aim::plan_todos`](crate::Aim::plan_todos).
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The ID of the calendar the event belongs to, if known.
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The IDs of the other calendars holding a copy of the event, see
+    /// [`Aim::dedupe_events`](crate::Aim::dedupe_events).
+    fn mirrored_in(&self) -> Vec<Cow<'_, str>> {
+        Vec::new()
+    }
+
+    /// The custom metadata of the event as key-value pairs, sorted by key.
+    fn meta(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
 }
 
-impl Event for VEvent<String> {
+impl<S: StringStorage> Event for VEvent<S> {
     fn uid(&self) -> Cow<'_, str> {
-        self.uid.content.to_string().into() // PERF: avoid allocation
+        self.uid.content.as_str_lossy()
     }
 
     fn description(&self) -> Option<Cow<'_, str>> {
-        self.description
-            .as_ref()
-            .map(|a| a.content.to_string().into()) // PERF: avoid allocation
+        self.description.as_ref().map(|a| a.content.as_str_lossy())
     }
 
     fn start(&self) -> Option<LooseDateTime> {
-        Some(self.dt_start.0.clone().into())
+        self.dt_start.as_ref().map(|dt| dt.0.clone().into())
     }
 
     fn end(&self) -> Option<LooseDateTime> {
@@ -64,13 +160,145 @@ impl Event for VEvent<String> {
     fn summary(&self) -> Cow<'_, str> {
         self.summary
             .as_ref()
-            .map_or_else(|| "".into(), |s| s.content.to_string().into()) // PERF: avoid allocation
+            .map_or_else(|| "".into(), |s| s.content.as_str_lossy())
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.location.as_ref().map(|l| l.content.as_str_lossy())
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.organizer
+            .as_ref()
+            .map(|o| o.cal_address.as_str_lossy())
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.url.as_ref().map(|u| u.uri.as_str_lossy())
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .flat_map(|c| c.values.iter().map(ToString::to_string))
+            .collect()
+    }
+
+    fn transparent(&self) -> bool {
+        self.transparency
+            .as_ref()
+            .is_some_and(|t| t.value == TimeTransparencyValue::Transparent)
+    }
+
+    fn split_from(&self) -> Option<Cow<'_, str>> {
+        self.x_properties
+            .iter()
+            .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_SPLIT_FROM))
+            .find_map(x_text)
+            .map(Into::into)
+    }
+
+    fn check_in(&self) -> Option<CheckIn> {
+        let attendance = self
+            .x_properties
+            .iter()
+            .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_CHECKIN))
+            .filter_map(x_text)
+            .find_map(|v| v.trim().parse().ok())?;
+        let note = self
+            .x_properties
+            .iter()
+            .filter(|p| {
+                p.name
+                    .as_str_lossy()
+                    .eq_ignore_ascii_case(X_AIM_CHECKIN_NOTE)
+            })
+            .filter_map(x_text)
+            .find(|v| !v.trim().is_empty());
+        Some(CheckIn { attendance, note })
+    }
+
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        self.retained_properties.iter().find_map(|p| match p {
+            Property::RelatedTo(r) if matches!(r.reltype, RelationshipType::Parent) => {
+                Some(r.content.as_str_lossy())
+            }
+            _ => None,
+        })
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        read_meta(&self.x_properties)
     }
 }
 
+/// Whether an event was attended, as recorded by its check-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attendance {
+    /// The event took place and was attended.
+    Attended,
+    /// The event was skipped, or did not take place.
+    Skipped,
+}
+
+const ATTENDANCE_ATTENDED: &str = "ATTENDED";
+const ATTENDANCE_SKIPPED: &str = "SKIPPED";
+
+impl AsRef<str> for Attendance {
+    fn as_ref(&self) -> &str {
+        match self {
+            Attendance::Attended => ATTENDANCE_ATTENDED,
+            Attendance::Skipped => ATTENDANCE_SKIPPED,
+        }
+    }
+}
+
+impl Display for Attendance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl FromStr for Attendance {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case(ATTENDANCE_ATTENDED) {
+            Ok(Attendance::Attended)
+        } else if value.eq_ignore_ascii_case(ATTENDANCE_SKIPPED) {
+            Ok(Attendance::Skipped)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// What actually happened at an event, see [`Event::check_in`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckIn {
+    /// Whether the event was attended or skipped.
+    pub attendance: Attendance,
+    /// A note on how it went, such as that it ran over.
+    pub note: Option<String>,
+}
+
+/// How all-day events count where time is blocked, such as on the timeline of the working day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AllDayPolicy {
+    /// All-day events block no time, as birthdays and holidays from subscribed feeds do not.
+    #[default]
+    Ignore,
+    /// All-day events block the whole of each day they take, unless they are transparent.
+    Busy,
+}
+
 /// Darft for an event, used for creating new events.
 #[derive(Debug, Clone)]
 pub struct EventDraft {
+    /// The reminder before the start, or `None` for the default of the config.
+    /// `Some(None)` creates the event without a reminder.
+    pub alarm: Option<Option<AlarmTrigger>>,
     /// The calendar ID to create the event in. Uses default calendar if None.
     pub calendar_id: Option<String>,
     /// The description of the event, if available.
@@ -79,6 +307,13 @@ pub struct EventDraft {
     pub start: Option<LooseDateTime>,
     /// The end date and time of the event, if available.
     pub end: Option<LooseDateTime>,
+    /// The duration of the event, as an alternative to the end. Without both, the event lasts
+    /// for the default duration of the config.
+    pub duration: Option<SignedDuration>,
+    /// How the event repeats, compiled into its recurrence rule from the start.
+    pub repeat: Option<Repeat>,
+    /// The UID of the item the event relates to, see [`Event::related_to`].
+    pub related_to: Option<String>,
     /// The status of the event.
     pub status: EventStatus,
     /// The summary of the event.
@@ -87,7 +322,7 @@ pub struct EventDraft {
 
 impl EventDraft {
     /// Creates a new empty patch.
-    pub(crate) fn default(now: &Zoned) -> Self {
+    pub(crate) fn default(now: &Zoned, duration: SignedDuration) -> Self {
         // next 00 or 30 minute
         let start = if now.time().minute() < 30 {
             now.with()
@@ -107,69 +342,216 @@ impl EventDraft {
         };
 
         Self {
+            alarm: None,
             calendar_id: None,
             description: None,
             start: Some(start.clone().into()),
-            end: Some((start.checked_add(1.hours()).unwrap()).into()),
+            end: Some((start.checked_add(duration).unwrap()).into()),
+            duration: None,
+            repeat: None,
+            related_to: None,
             status: EventStatus::default(),
             summary: String::new(),
         }
     }
 
-    pub(crate) fn resolve<'a>(&'a self, now: &'a Zoned) -> ResolvedEventDraft<'a> {
-        let default_duration = 1.hours();
-        let (start, end) = match (self.start.as_ref(), self.end.as_ref()) {
-            (Some(start), Some(end)) => (start.clone(), end.clone()),
-            (None, Some(end)) => {
-                // If start is not specified, but end is, set start to end - duration
-                let neg_duration = Span::new().hours(-1);
-                let start = match end {
-                    LooseDateTime::DateOnly(d) => (*d).into(),
-                    LooseDateTime::Floating(dt) => {
-                        LooseDateTime::Floating(dt.checked_add(neg_duration).unwrap())
-                    }
-                    LooseDateTime::Local(dt) => {
-                        LooseDateTime::Local(dt.checked_add(neg_duration).unwrap())
-                    }
-                };
-                (start, end.clone())
+    /// Sets the times of the draft as given by the user, on the command line or in an editor.
+    ///
+    /// The start is resolved from `now`. A bare time as the end is on the date of the start, or
+    /// the day after if it is earlier than the start. Without an end nor a duration, the end is
+    /// left to the duration of the config, and without a start, the start is left to be inferred
+    /// from the end. An end and a duration given together are rejected when the draft is
+    /// created.
+    ///
+    /// # Errors
+    /// If the start or end cannot be resolved.
+    pub fn set_times(
+        &mut self,
+        now: &Zoned,
+        start: Option<DateTimeAnchor>,
+        end: Option<DateTimeAnchor>,
+        duration: Option<SignedDuration>,
+    ) -> Result<(), AimError> {
+        let invalid = |field| move |reason| AimError::InvalidInput { field, reason };
+        let start_given = start.is_some();
+        if let Some(start) = start {
+            self.start = Some(start.resolve_since_zoned(now).map_err(invalid("start"))?);
+        }
+
+        if let Some(end) = end {
+            let end = match (&self.start, start_given) {
+                (Some(start), true) => end_at(start, end),
+                _ => end.resolve_since_zoned(now),
+            };
+            self.end = Some(end.map_err(invalid("end"))?);
+            if !start_given {
+                self.start = None;
+            }
+        } else if start_given || duration.is_some() {
+            self.end = None;
+        }
+        self.duration = duration;
+        Ok(())
+    }
+
+    pub(crate) fn resolve<'a>(
+        &'a self,
+        config: &Config,
+        now: &'a Zoned,
+    ) -> Result<ResolvedEventDraft<'a>, AimError> {
+        let duration = match (self.duration, &self.end) {
+            (Some(_), Some(_)) => {
+                return Err(AimError::InvalidInput {
+                    field: "duration",
+                    reason: "cannot be given together with an end".to_string(),
+                });
             }
-            (Some(start), None) => {
-                // If end is not specified, but start is, set it to start + duration
-                let end = match start {
-                    LooseDateTime::DateOnly(d) => (*d).into(),
-                    LooseDateTime::Floating(dt) => {
-                        LooseDateTime::Floating(dt.checked_add(default_duration).unwrap())
-                    }
-                    LooseDateTime::Local(dt) => {
-                        LooseDateTime::Local(dt.checked_add(default_duration).unwrap())
-                    }
-                };
-                (start.clone(), end)
+            (Some(duration), None) if duration.is_zero() => {
+                return Err(AimError::InvalidInput {
+                    field: "duration",
+                    reason: "must be longer than zero".to_string(),
+                });
             }
+            (Some(duration), None) if duration.is_negative() => {
+                return Err(AimError::InvalidInput {
+                    field: "duration",
+                    reason: format!("cannot be negative, got -{:#}", duration.abs()),
+                });
+            }
+            (Some(duration), None) => duration,
+            (None, _) => config.default_event_duration,
+        };
+
+        let (start, end) = match (self.start.as_ref(), self.end.as_ref()) {
+            (Some(start), Some(end)) => (start.clone(), end.clone()),
+            // If start is not specified, but end is, set start to end - duration
+            (None, Some(end)) => (shift_by(end, -duration), end.clone()),
+            // If end is not specified, but start is, set it to start + duration
+            (Some(start), None) => (start.clone(), shift_by(start, duration)),
             (None, None) => {
-                let end = now.checked_add(default_duration).unwrap();
-                (LooseDateTime::Local(now.clone()), LooseDateTime::Local(end))
+                let start = LooseDateTime::Local(now.clone());
+                let end = shift_by(&start, duration);
+                (start, end)
             }
         };
 
-        ResolvedEventDraft {
+        if is_before(&end, &start) {
+            return Err(AimError::InvalidInput {
+                field: "end",
+                reason: format!(
+                    "{} is before the start {}",
+                    describe(&end),
+                    describe(&start)
+                ),
+            });
+        }
+
+        let alarm = self.alarm.unwrap_or(match start {
+            LooseDateTime::DateOnly(_) => config.default_allday_alarm,
+            _ => config.default_event_alarm,
+        });
+
+        let rrule =
+            match &self.repeat {
+                Some(repeat) => Some(repeat.to_rule(&start, config.week_start).map_err(
+                    |reason| AimError::InvalidInput {
+                        field: "repeat",
+                        reason,
+                    },
+                )?),
+                None => None,
+            };
+
+        Ok(ResolvedEventDraft {
+            alarm,
             description: self.description.as_deref(),
             start,
             end,
+            rrule,
+            related_to: self.related_to.as_deref(),
             status: self.status,
             summary: &self.summary,
 
             now,
+        })
+    }
+}
+
+/// The last day of an event from `start` to `end`, the day before an end date without a time,
+/// but never before the start.
+pub(crate) fn last_day(start: Option<&LooseDateTime>, end: Option<&LooseDateTime>) -> Option<Date> {
+    match (start, end) {
+        (start, Some(LooseDateTime::DateOnly(d))) => {
+            let last = d.yesterday().unwrap_or(*d);
+            Some(start.map_or(last, |s| last.max(s.date())))
+        }
+        (_, Some(end)) => Some(end.date()),
+        (Some(start), None) => Some(start.date()),
+        (None, None) => None,
+    }
+}
+
+/// Resolves the end of an event starting at `start`, putting a bare time on the date of the
+/// start, or the day after if it is earlier than the start.
+fn end_at(start: &LooseDateTime, end: DateTimeAnchor) -> Result<LooseDateTime, String> {
+    let DateTimeAnchor::Time(time) = end else {
+        return end.resolve_since(start);
+    };
+
+    let mut date = start.date();
+    if start.time().is_some_and(|t| time < t) {
+        date = date
+            .tomorrow()
+            .map_err(|e| format!("Failed to add day to date: {e}"))?;
+    }
+    let dt = date.to_datetime(time);
+    Ok(match start {
+        // Keep the time zone of the start, whatever the local one is
+        LooseDateTime::Local(zoned) => dt
+            .to_zoned(zoned.time_zone().clone())
+            .map_or(LooseDateTime::Floating(dt), LooseDateTime::Local),
+        LooseDateTime::Floating(_) => LooseDateTime::Floating(dt),
+        LooseDateTime::DateOnly(_) => LooseDateTime::from_local_datetime(dt),
+    })
+}
+
+/// Moves a date and time by `duration`, in whole days for dates without a time.
+fn shift_by(dt: &LooseDateTime, duration: SignedDuration) -> LooseDateTime {
+    match dt {
+        LooseDateTime::DateOnly(d) => {
+            let days = duration.as_secs() / (24 * 60 * 60);
+            LooseDateTime::DateOnly(d.checked_add(Span::new().days(days)).unwrap())
         }
+        LooseDateTime::Floating(dt) => LooseDateTime::Floating(dt.checked_add(duration).unwrap()),
+        LooseDateTime::Local(dt) => LooseDateTime::Local(dt.checked_add(duration).unwrap()),
+    }
+}
+
+fn is_before(a: &LooseDateTime, b: &LooseDateTime) -> bool {
+    match (a, b) {
+        (LooseDateTime::Local(a), LooseDateTime::Local(b)) => a < b,
+        _ => a.with_start_of_day() < b.with_start_of_day(),
+    }
+}
+
+fn describe(dt: &LooseDateTime) -> String {
+    match dt {
+        LooseDateTime::DateOnly(d) => d.to_string(),
+        dt => dt
+            .with_start_of_day()
+            .strftime("%Y-%m-%d %H:%M")
+            .to_string(),
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct ResolvedEventDraft<'a> {
+    pub alarm: Option<AlarmTrigger>,
     pub description: Option<&'a str>,
     pub start: LooseDateTime,
     pub end: LooseDateTime,
+    pub rrule: Option<ValueRecurrenceRule>,
+    pub related_to: Option<&'a str>,
     pub status: EventStatus,
     pub summary: &'a str,
 
@@ -182,10 +564,15 @@ impl ResolvedEventDraft<'_> {
         // Convert to UTC for DTSTAMP (required by RFC 5545)
         let utc_now = self.now.with_time_zone(jiff::tz::TimeZone::UTC);
         let dt_stamp = DtStamp::new(utc_now.datetime());
+        let alarms = self
+            .alarm
+            .map(|a| a.to_display_alarm(None, self.summary))
+            .into_iter()
+            .collect();
         VEvent {
             uid: Uid::new(uid.to_string()),
             dt_stamp,
-            dt_start: DtStart::new(self.start),
+            dt_start: Some(DtStart::new(self.start)),
             dt_end: Some(DtEnd::new(self.end)),
             duration: None,
             summary: Some(Summary::new(self.summary.to_string())),
@@ -201,14 +588,37 @@ impl ResolvedEventDraft<'_> {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
-            rrule: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
+            rrule: self.rrule.map(|rule| RRule {
+                value: Box::new(rule),
+                x_parameters: Vec::new(),
+                retained_parameters: Vec::new(),
+                span: (),
+            }),
             rdates: Vec::new(),
             ex_dates: Vec::new(),
             x_properties: Vec::new(),
-            retained_properties: Vec::new(),
-            alarms: Vec::new(),
+            retained_properties: self
+                .related_to
+                .map(|uid| {
+                    Property::RelatedTo(RelatedTo {
+                        content: ValueText::new(uid.to_string()),
+                        reltype: RelationshipType::Parent,
+                        x_parameters: Vec::new(),
+                        retained_parameters: Vec::new(),
+                        span: (),
+                    })
+                })
+                .into_iter()
+                .collect(),
+            alarms,
         }
     }
 }
@@ -216,6 +626,9 @@ impl ResolvedEventDraft<'_> {
 /// Patch for an event, allowing partial updates.
 #[derive(Debug, Default, Clone)]
 pub struct EventPatch {
+    /// The reminder before the start, replacing the `DISPLAY` alarms of the event if set.
+    /// `Some(None)` removes them.
+    pub alarm: Option<Option<AlarmTrigger>>,
     /// The description of the event, if available.
     pub description: Option<Option<String>>,
     /// The start date and time of the event, if available.
@@ -226,26 +639,51 @@ pub struct EventPatch {
     pub status: Option<EventStatus>,
     /// The summary of the event, if available.
     pub summary: Option<String>,
+    /// Exception dates (`EXDATE`) to add or remove.
+    pub ex_dates: Vec<RecurrenceDateEdit>,
+    /// Extra occurrence dates (`RDATE`) to add or remove.
+    pub rdates: Vec<RecurrenceDateEdit>,
+    /// The last date-time of the recurrence, inclusive, replacing its `COUNT` or `UNTIL`.
+    ///
+    /// It is written in UTC for events in a time zone, and has no effect on events that do not
+    /// recur by a rule.
+    pub until: Option<LooseDateTime>,
+    /// Custom metadata to set, or to remove where the value is `None`, see [`Event::meta`].
+    pub meta: Vec<(String, Option<String>)>,
+    /// The check-in of the event, see [`Event::check_in`]. `Some(None)` clears it.
+    pub check_in: Option<Option<CheckIn>>,
 }
 
 impl EventPatch {
     /// Is this patch empty, meaning no fields are set
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.description.is_none()
+        self.alarm.is_none()
+            && self.description.is_none()
             && self.start.is_none()
             && self.end.is_none()
             && self.status.is_none()
             && self.summary.is_none()
+            && self.ex_dates.is_empty()
+            && self.rdates.is_empty()
+            && self.until.is_none()
+            && self.meta.is_empty()
+            && self.check_in.is_none()
     }
 
     pub(crate) fn resolve(&self, now: Zoned) -> ResolvedEventPatch<'_> {
         ResolvedEventPatch {
+            alarm: self.alarm,
             description: self.description.as_ref().map(|opt| opt.as_deref()),
             start: self.start.clone(),
             end: self.end.clone(),
             status: self.status,
             summary: self.summary.as_deref(),
+            ex_dates: &self.ex_dates,
+            rdates: &self.rdates,
+            until: self.until.as_ref(),
+            meta: &self.meta,
+            check_in: self.check_in.as_ref().map(Option::as_ref),
 
             now,
         }
@@ -254,25 +692,99 @@ impl EventPatch {
 
 impl From<EventDraft> for EventPatch {
     fn from(draft: EventDraft) -> EventPatch {
+        let end = match (&draft.start, draft.duration) {
+            (Some(start), Some(duration)) if draft.end.is_none() => Some(shift_by(start, duration)),
+            _ => draft.end,
+        };
         EventPatch {
+            alarm: draft.alarm,
             description: draft.description.map(Some),
             start: draft.start.map(Some),
-            end: draft.end.map(Some),
+            end: end.map(Some),
             status: Some(draft.status),
             summary: Some(draft.summary),
+            ex_dates: Vec::new(),
+            rdates: Vec::new(),
+            until: None,
+            meta: Vec::new(),
+            check_in: None,
         }
     }
 }
 
+/// Adds or removes a date of a multi-valued recurrence property, such as `EXDATE`.
+///
+/// The date is converted to the value type of the event's `DTSTART`: a date for all-day
+/// events, otherwise a date-time in the same timezone. A date without time takes the time
+/// of `DTSTART`, so that it matches the occurrence on that day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurrenceDateEdit {
+    /// Add the date.
+    Add(LooseDateTime),
+    /// Remove the date.
+    Remove(LooseDateTime),
+}
+
+impl RecurrenceDateEdit {
+    /// Converts the date to the value type of `dt_start`, with the TZID to write. Without a
+    /// start, the date is written as it is.
+    fn resolve(&self, dt_start: Option<&DtStart<String>>) -> (ical::DateTime, Option<String>) {
+        let (Self::Add(at) | Self::Remove(at)) = self;
+        let start = dt_start.map_or_else(|| at.clone(), |s| LooseDateTime::from(s.0.clone()));
+        let civil = match (at, &start) {
+            (_, LooseDateTime::DateOnly(_)) => None,
+            (LooseDateTime::DateOnly(d), _) => start.time().map(|t| d.to_datetime(t)),
+            (LooseDateTime::Floating(dt), _) => Some(*dt),
+            (LooseDateTime::Local(zoned), LooseDateTime::Local(s)) => {
+                Some(zoned.with_time_zone(s.time_zone().clone()).datetime())
+            }
+            (LooseDateTime::Local(zoned), _) => Some(zoned.datetime()),
+        };
+        let value = match (civil, &start) {
+            (None, _) => LooseDateTime::DateOnly(at.date()),
+            (Some(dt), LooseDateTime::Local(s)) => match dt.to_zoned(s.time_zone().clone()) {
+                Ok(zoned) => LooseDateTime::Local(zoned),
+                Err(_) => LooseDateTime::Floating(dt),
+            },
+            (Some(dt), _) => LooseDateTime::Floating(dt),
+        };
+        let prop = DateTimeProperty::from(value);
+        (prop.value, prop.tz_id)
+    }
+}
+
+/// Converts the end of a recurrence to an `UNTIL` value.
+///
+/// A date is written as midnight, which is taken as the whole day, and a date-time with a time
+/// zone in UTC, as RFC 5545 requires for events with a time zone.
+pub(crate) fn until_value(until: &LooseDateTime) -> Option<ValueDateTime> {
+    let (dt, utc) = match until {
+        LooseDateTime::DateOnly(date) => (date.to_datetime(jiff::civil::Time::midnight()), false),
+        LooseDateTime::Floating(dt) => (*dt, false),
+        LooseDateTime::Local(zoned) => (
+            zoned.with_time_zone(jiff::tz::TimeZone::UTC).datetime(),
+            true,
+        ),
+    };
+    let time = ValueTime::new(dt.hour(), dt.minute(), dt.second(), utc).ok()?;
+    Some(ValueDateTime::new(ValueDate::from(dt.date()), time))
+}
+
 /// Patch for an event, allowing partial updates.
 #[derive(Debug, Default, Clone)]
 #[expect(clippy::option_option)]
 pub struct ResolvedEventPatch<'a> {
+    pub alarm: Option<Option<AlarmTrigger>>,
     pub description: Option<Option<&'a str>>,
     pub start: Option<Option<LooseDateTime>>,
     pub end: Option<Option<LooseDateTime>>,
     pub status: Option<EventStatus>,
     pub summary: Option<&'a str>,
+    pub ex_dates: &'a [RecurrenceDateEdit],
+    pub rdates: &'a [RecurrenceDateEdit],
+    pub until: Option<&'a LooseDateTime>,
+    pub meta: &'a [(String, Option<String>)],
+    pub check_in: Option<Option<&'a CheckIn>>,
 
     pub now: Zoned,
 }
@@ -287,11 +799,17 @@ impl ResolvedEventPatch<'_> {
         }
 
         if let Some(Some(ref start)) = self.start {
-            e.dt_start = DtStart::new(start.clone());
+            e.dt_start = Some(DtStart::new(match &e.dt_start {
+                Some(original) => start.to_property_like(original),
+                None => start.clone().into(),
+            }));
         }
 
         if let Some(Some(ref end)) = self.end {
-            e.dt_end = Some(DtEnd::new(end.clone()));
+            e.dt_end = Some(DtEnd::new(match &e.dt_end {
+                Some(original) => end.to_property_like(original),
+                None => end.clone().into(),
+            }));
         } else if self.end.is_some() {
             e.dt_end = None;
         }
@@ -304,6 +822,55 @@ impl ResolvedEventPatch<'_> {
             e.summary = Some(Summary::new(summary.to_string()));
         }
 
+        // Multiple EXDATE / RDATE lines are merged into one per TZID when editing
+        if !self.ex_dates.is_empty() {
+            let mut props = std::mem::take(&mut e.ex_dates);
+            for edit in self.ex_dates {
+                let (date, tz_id) = edit.resolve(e.dt_start.as_ref());
+                match edit {
+                    RecurrenceDateEdit::Add(_) => props.push(ExDate::new(vec![date], tz_id)),
+                    RecurrenceDateEdit::Remove(_) => props
+                        .iter_mut()
+                        .filter(|p| p.tz_id == tz_id)
+                        .for_each(|p| _ = p.remove(&date)),
+                }
+            }
+            e.ex_dates = ExDate::merge(props);
+        }
+
+        if !self.rdates.is_empty() {
+            let mut props = std::mem::take(&mut e.rdates);
+            for edit in self.rdates {
+                let (date, tz_id) = edit.resolve(e.dt_start.as_ref());
+                match edit {
+                    RecurrenceDateEdit::Add(_) => props.push(RDate::new(vec![date], tz_id)),
+                    RecurrenceDateEdit::Remove(_) => props
+                        .iter_mut()
+                        .filter(|p| p.tz_id == tz_id)
+                        .for_each(|p| _ = p.remove(&date)),
+                }
+            }
+            e.rdates = RDate::merge(props);
+        }
+
+        if let (Some(until), Some(rrule)) = (self.until.and_then(until_value), &mut e.rrule) {
+            rrule.value.until = Some(until);
+            rrule.value.count = None;
+        }
+
+        if let Some(alarm) = self.alarm {
+            let alarm = alarm.map(|a| a.to_display_alarm(None, &Event::summary(e)));
+            replace_display_alarms(&mut e.alarms, alarm);
+        }
+
+        if !self.meta.is_empty() {
+            write_meta(&mut e.x_properties, self.meta);
+        }
+
+        if let Some(check_in) = self.check_in {
+            set_check_in(e, check_in);
+        }
+
         // Set the creation time to now if it is not already set
         if e.dt_stamp.date.year == 1970 {
             // TODO: better check for unset
@@ -315,6 +882,28 @@ impl ResolvedEventPatch<'_> {
     }
 }
 
+/// Sets the check-in of an event, replacing the one it had, or clears it.
+fn set_check_in(e: &mut VEvent<String>, check_in: Option<&CheckIn>) {
+    e.x_properties.retain(|p| {
+        !p.name.eq_ignore_ascii_case(X_AIM_CHECKIN)
+            && !p.name.eq_ignore_ascii_case(X_AIM_CHECKIN_NOTE)
+    });
+    let Some(check_in) = check_in else {
+        return;
+    };
+
+    e.x_properties.push(x_text_property(
+        X_AIM_CHECKIN,
+        check_in.attendance.to_string(),
+    ));
+    if let Some(note) = check_in.note.as_deref().map(str::trim)
+        && !note.is_empty()
+    {
+        e.x_properties
+            .push(x_text_property(X_AIM_CHECKIN_NOTE, note.to_string()));
+    }
+}
+
 /// The status of an event, which can be tentative, confirmed, or cancelled.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -391,6 +980,16 @@ pub struct EventConditions {
     pub cutoff: Option<DateTimeAnchor>,
     /// The calendar ID to filter events by
     pub calendar_id: Option<String>,
+    /// Whether to include the events archived by housekeeping.
+    pub include_archived: bool,
+    /// Only events with all of these custom metadata, as key-value pairs.
+    pub meta: Vec<(String, String)>,
+    /// Only events related to the item with this UID, see [`Event::related_to`].
+    pub related_to: Option<String>,
+    /// Whether to include only events not checked in yet, see [`Event::check_in`].
+    pub unchecked: bool,
+    /// Only events with all of these categories, their own or added by rules, ignoring case.
+    pub categories: Vec<String>,
 }
 
 impl EventConditions {
@@ -407,6 +1006,11 @@ impl EventConditions {
                 .map(|w| w.resolve_at_start_of_day(now))
                 .transpose()?,
             calendar_id: self.calendar_id.clone(),
+            include_archived: self.include_archived,
+            meta: resolve_meta_conditions(&self.meta)?,
+            related_to: self.related_to.clone(),
+            unchecked: self.unchecked,
+            categories: self.categories.clone(),
         })
     }
 }
@@ -419,26 +1023,71 @@ pub struct ResolvedEventConditions {
     pub end_after: Option<Zoned>,
     /// The calendar ID to filter events by
     pub calendar_id: Option<String>,
+    /// Whether to include the archived events
+    pub include_archived: bool,
+    /// Custom metadata the events must have, with normalized keys
+    pub meta: Vec<(String, String)>,
+    /// The UID of the item the events must relate to
+    pub related_to: Option<String>,
+    /// Whether to include only the events not checked in yet
+    pub unchecked: bool,
+    /// Categories the events must have, their own or added by rules
+    pub categories: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use aimcal_ical::{Description, DtEnd, DtStart, Summary, Uid, VEvent};
-    use jiff::{civil::date, tz::TimeZone};
+    use jiff::civil::{date, time};
+    use jiff::tz::TimeZone;
 
     /// Helper function to create a test `EventDraft` with minimal fields
     fn test_event_draft() -> EventDraft {
         EventDraft {
+            alarm: None,
             calendar_id: None,
             description: None,
             start: None,
             end: None,
+            duration: None,
+            repeat: None,
+            related_to: None,
             status: EventStatus::Confirmed,
             summary: String::new(),
         }
     }
 
+    fn test_config() -> Config {
+        toml::from_str("").unwrap()
+    }
+
+    #[test]
+    fn last_day_excludes_the_end_date_of_all_day_events() {
+        let day = |d| LooseDateTime::DateOnly(date(2025, 6, d));
+        let timed = |d, h| LooseDateTime::Floating(date(2025, 6, d).at(h, 0, 0, 0));
+
+        assert_eq!(
+            last_day(Some(&day(10)), Some(&day(11))),
+            Some(date(2025, 6, 10))
+        );
+        assert_eq!(
+            last_day(Some(&day(10)), Some(&day(13))),
+            Some(date(2025, 6, 12))
+        );
+        // An end on the start date, which RFC 5545 does not allow, still takes that day
+        assert_eq!(
+            last_day(Some(&day(10)), Some(&day(10))),
+            Some(date(2025, 6, 10))
+        );
+        assert_eq!(last_day(Some(&day(10)), None), Some(date(2025, 6, 10)));
+        assert_eq!(
+            last_day(Some(&timed(10, 22)), Some(&timed(11, 2))),
+            Some(date(2025, 6, 11))
+        );
+        assert_eq!(last_day(None, None), None);
+    }
+
     fn vevent_dt_stamp() -> DtStamp<String> {
         // Create a DateTimeUtc for June 15, 2024 at 10:30:00 UTC
         let date = ical::Date::new(2024, 6, 15).unwrap();
@@ -469,7 +1118,7 @@ mod tests {
         VEvent {
             uid: Uid::new(uid.to_string()),
             dt_stamp: vevent_dt_stamp(),
-            dt_start: DtStart::new(dt_start),
+            dt_start: Some(DtStart::new(dt_start)),
             dt_end: Some(DtEnd::new(dt_end)),
             duration: None,
             summary: Some(Summary::new(summary.to_string())),
@@ -485,8 +1134,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rdates: Vec::new(),
             rrule: None,
             ex_dates: Vec::new(),
@@ -512,7 +1167,7 @@ mod tests {
     fn event_draft_default_creates_draft_with_rounded_time() {
         let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
 
-        let draft = EventDraft::default(&now);
+        let draft = EventDraft::default(&now, SignedDuration::from_hours(1));
 
         // Time should be rounded to next 00 or 30 minute
         let _minute = now.time().minute();
@@ -534,7 +1189,7 @@ mod tests {
             .to_zoned(TimeZone::UTC)
             .unwrap();
 
-        let draft = EventDraft::default(&now);
+        let draft = EventDraft::default(&now, SignedDuration::from_hours(1));
 
         // Should round to 10:30
         assert!(draft.start.is_some());
@@ -558,7 +1213,7 @@ mod tests {
             .to_zoned(TimeZone::UTC)
             .unwrap();
 
-        let draft = EventDraft::default(&now);
+        let draft = EventDraft::default(&now, SignedDuration::from_hours(1));
 
         // Should round to 11:00
         assert!(draft.start.is_some());
@@ -598,7 +1253,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
 
         assert_eq!(resolved.summary, "");
         assert_eq!(resolved.start, start);
@@ -622,7 +1277,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
 
         assert_eq!(resolved.start, start);
         // End should be start + 1 hour
@@ -648,7 +1303,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
 
         // Start should be end - 1 hour
         assert!(
@@ -667,7 +1322,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
 
         // Should use now for start and now + 1 hour for end
         assert!(matches!(resolved.start, LooseDateTime::Local(_)));
@@ -686,12 +1341,165 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
 
         // DateOnly should be preserved, end should be start + 1 day (via hour math that gets truncated)
         assert_eq!(resolved.start, start);
     }
 
+    #[test]
+    fn event_draft_resolve_applies_durations() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let start = date(2025, 1, 15)
+            .at(10, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let end_of = |draft: EventDraft, config: &Config| match draft.resolve(config, &now) {
+            Ok(resolved) => resolved.end,
+            Err(e) => panic!("{e}"),
+        };
+
+        let draft = EventDraft {
+            start: Some(start.clone().into()),
+            duration: Some(SignedDuration::from_mins(30)),
+            ..test_event_draft()
+        };
+        let expected = start.checked_add(SignedDuration::from_mins(30)).unwrap();
+        assert_eq!(end_of(draft, &test_config()), expected.into());
+
+        let config: Config = toml::from_str(r#"default_event_duration = "25m""#).unwrap();
+        let draft = EventDraft {
+            start: Some(start.clone().into()),
+            ..test_event_draft()
+        };
+        let expected = start.checked_add(SignedDuration::from_mins(25)).unwrap();
+        assert_eq!(end_of(draft, &config), expected.into());
+    }
+
+    #[test]
+    fn event_draft_resolve_rejects_invalid_times() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let at = |hour| {
+            LooseDateTime::Local(
+                date(2025, 1, 15)
+                    .at(hour, 0, 0, 0)
+                    .to_zoned(TimeZone::UTC)
+                    .unwrap(),
+            )
+        };
+        let error = |draft: EventDraft| match draft.resolve(&test_config(), &now) {
+            Ok(_) => panic!("draft should be rejected"),
+            Err(e) => e.to_string(),
+        };
+
+        let both = EventDraft {
+            start: Some(at(10)),
+            end: Some(at(11)),
+            duration: Some(SignedDuration::from_mins(30)),
+            ..test_event_draft()
+        };
+        assert_eq!(
+            error(both),
+            "Invalid duration: cannot be given together with an end"
+        );
+
+        let zero = EventDraft {
+            start: Some(at(10)),
+            duration: Some(SignedDuration::ZERO),
+            ..test_event_draft()
+        };
+        assert_eq!(error(zero), "Invalid duration: must be longer than zero");
+
+        let negative = EventDraft {
+            start: Some(at(10)),
+            duration: Some(SignedDuration::from_mins(-30)),
+            ..test_event_draft()
+        };
+        assert_eq!(
+            error(negative),
+            "Invalid duration: cannot be negative, got -30m"
+        );
+
+        let reversed = EventDraft {
+            start: Some(at(10)),
+            end: Some(at(9)),
+            ..test_event_draft()
+        };
+        assert_eq!(
+            error(reversed),
+            "Invalid end: 2025-01-15 09:00 is before the start 2025-01-15 10:00"
+        );
+    }
+
+    #[test]
+    fn event_draft_set_times_puts_bare_end_time_on_start_date() {
+        let now = date(2025, 1, 15)
+            .at(8, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let mut draft = test_event_draft();
+
+        let start = "2025-01-20 14:00".parse().ok();
+        let end = "15:30".parse().ok();
+        draft.set_times(&now, start, end, None).unwrap();
+        let end = draft.end.unwrap();
+        assert_eq!(end.date(), date(2025, 1, 20));
+        assert_eq!(end.time(), Some(time(15, 30, 0, 0)));
+    }
+
+    #[test]
+    fn event_draft_set_times_rolls_end_over_midnight() {
+        let now = date(2025, 1, 15)
+            .at(8, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let mut draft = test_event_draft();
+
+        let start = "2025-01-20 23:30".parse().ok();
+        let end = "00:15".parse().ok();
+        draft.set_times(&now, start, end, None).unwrap();
+        let end = draft.end.clone().unwrap();
+        assert_eq!(end.date(), date(2025, 1, 21));
+        assert_eq!(end.time(), Some(time(0, 15, 0, 0)));
+        assert!(draft.resolve(&test_config(), &now).is_ok());
+    }
+
+    #[test]
+    fn event_draft_durations_span_dst_gaps() {
+        // Clocks spring forward from 14:00 to 15:00 on 2025-03-09
+        let tz = TimeZone::posix("XST5XDT,M3.2.0/14,M11.1.0/14").unwrap();
+        let now = date(2025, 3, 1)
+            .at(8, 0, 0, 0)
+            .to_zoned(tz.clone())
+            .unwrap();
+        // 14:30 does not exist on that day, and is taken as 15:30 instead
+        let start = date(2025, 3, 9).at(14, 30, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(start.time(), time(15, 30, 0, 0));
+
+        let draft = EventDraft {
+            start: Some(start.clone().into()),
+            duration: Some(SignedDuration::from_mins(30)),
+            ..test_event_draft()
+        };
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
+        let LooseDateTime::Local(end) = resolved.end else {
+            panic!("end should keep the time zone of the start");
+        };
+        assert_eq!(end.time(), time(16, 0, 0, 0));
+        assert_eq!(start.duration_until(&end), SignedDuration::from_mins(30));
+
+        // A bare end time stays in the time zone of the start
+        let mut draft = test_event_draft();
+        let start = Some(DateTimeAnchor::DateTime(start.into()));
+        let end = "16:15".parse().ok();
+        draft.set_times(&now, start, end, None).unwrap();
+        let Some(LooseDateTime::Local(end)) = draft.end else {
+            panic!("end should keep the time zone of the start");
+        };
+        assert_eq!(end.date(), date(2025, 3, 9));
+        assert_eq!(end.offset(), jiff::tz::offset(-4));
+    }
+
     #[test]
     fn event_draft_into_ics_creates_valid_vevent() {
         let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
@@ -710,15 +1518,19 @@ mod tests {
         );
 
         let draft = EventDraft {
+            alarm: None,
             calendar_id: None,
             summary: "Test Event".to_string(),
             description: Some("Test Description".to_string()),
             start: Some(dt_start),
             end: Some(dt_end),
+            duration: None,
+            repeat: None,
+            related_to: None,
             status: EventStatus::Confirmed,
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
         let vevent = resolved.into_ics("test-uid");
 
         assert_eq!(vevent.uid.content.to_string(), "test-uid");
@@ -736,6 +1548,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_draft_resolve_uses_default_alarms() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let config = test_config();
+        let alarm = |draft: EventDraft| {
+            let resolved = draft.resolve(&config, &now).unwrap();
+            resolved.alarm.map(|a| a.to_string())
+        };
+
+        let timed = EventDraft {
+            start: Some(LooseDateTime::Local(now.clone())),
+            ..test_event_draft()
+        };
+        assert_eq!(alarm(timed.clone()), Some("-PT15M".to_string()));
+
+        let all_day = EventDraft {
+            start: Some(LooseDateTime::DateOnly(date(2025, 1, 15))),
+            ..test_event_draft()
+        };
+        assert_eq!(alarm(all_day), Some("-PT9H".to_string()));
+
+        let custom = EventDraft {
+            alarm: Some(Some("30m".parse().unwrap())),
+            ..timed.clone()
+        };
+        assert_eq!(alarm(custom), Some("-PT30M".to_string()));
+
+        let disabled = EventDraft {
+            alarm: Some(None),
+            ..timed
+        };
+        assert_eq!(alarm(disabled), None);
+    }
+
+    #[test]
+    fn event_patch_only_replaces_alarms_when_set() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let draft = EventDraft {
+            start: Some(LooseDateTime::Local(now.clone())),
+            summary: "Test Event".to_string(),
+            ..test_event_draft()
+        };
+        let mut vevent = draft
+            .resolve(&test_config(), &now)
+            .unwrap()
+            .into_ics("test-uid");
+        assert_eq!(vevent.alarms.len(), 1);
+
+        let patch = EventPatch {
+            summary: Some("New Summary".to_string()),
+            ..Default::default()
+        };
+        patch.resolve(now.clone()).apply_to(&mut vevent);
+        assert_eq!(vevent.alarms.len(), 1);
+
+        let patch = EventPatch {
+            alarm: Some(None),
+            ..Default::default()
+        };
+        patch.resolve(now).apply_to(&mut vevent);
+        assert!(vevent.alarms.is_empty());
+    }
+
     #[test]
     fn event_draft_resolve_preserves_status() {
         let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
@@ -750,7 +1625,7 @@ mod tests {
                 ..test_event_draft()
             };
 
-            let resolved = draft.resolve(&now);
+            let resolved = draft.resolve(&test_config(), &now).unwrap();
             assert_eq!(resolved.status, status);
         }
     }
@@ -764,7 +1639,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
         assert_eq!(resolved.summary, "My Event Summary");
     }
 
@@ -777,7 +1652,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
         assert_eq!(resolved.description, Some("Event description"));
     }
 
@@ -790,7 +1665,7 @@ mod tests {
             ..test_event_draft()
         };
 
-        let resolved = draft.resolve(&now);
+        let resolved = draft.resolve(&test_config(), &now).unwrap();
         assert!(resolved.description.is_none());
     }
 
@@ -798,7 +1673,7 @@ mod tests {
     fn event_draft_default_status_is_confirmed() {
         let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
 
-        let draft = EventDraft::default(&now);
+        let draft = EventDraft::default(&now, SignedDuration::from_hours(1));
         assert_eq!(draft.status, EventStatus::Confirmed);
     }
 
@@ -912,7 +1787,7 @@ mod tests {
 
         // Check that the start was updated to June 1, 2025 at 14:00 UTC
         assert!(
-            matches!(vevent.dt_start.0.value, ical::DateTime::Utc { date, time } if
+            matches!(vevent.dt_start.as_ref().unwrap().0.value, ical::DateTime::Utc { date, time } if
                 date.year == 2025 &&
                 date.month == 6 &&
                 date.day == 1 &&
@@ -942,7 +1817,7 @@ mod tests {
         let mut vevent = VEvent {
             uid: Uid::new("test-uid".to_string()),
             dt_stamp: vevent_dt_stamp(),
-            dt_start: DtStart::new(dt_start),
+            dt_start: Some(DtStart::new(dt_start)),
             dt_end: Some(DtEnd::new(dt_end)),
             duration: None,
             summary: None,
@@ -958,8 +1833,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rdates: Vec::new(),
             rrule: None,
             ex_dates: Vec::new(),
@@ -1001,6 +1882,58 @@ mod tests {
         }
     }
 
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn event_patch_apply_to_edits_ex_dates_matching_dt_start() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let mut vevent = create_test_vevent("test-uid", "Test");
+
+        // A date without time takes the time of DTSTART, in UTC
+        let day = LooseDateTime::DateOnly(date(2025, 1, 8));
+        let patch = EventPatch {
+            ex_dates: vec![
+                RecurrenceDateEdit::Add(day.clone()),
+                RecurrenceDateEdit::Add(day.clone()),
+            ],
+            ..Default::default()
+        };
+        patch.resolve(now.clone()).apply_to(&mut vevent);
+        assert_eq!(vevent.ex_dates.len(), 1);
+        assert_eq!(vevent.ex_dates[0].dates.len(), 1);
+        let exdate = &vevent.ex_dates[0].dates[0];
+        assert!(exdate.is_utc());
+        assert_eq!(
+            exdate.civil_date_time(),
+            Some(date(2025, 1, 8).at(10, 0, 0, 0))
+        );
+
+        let patch = EventPatch {
+            ex_dates: vec![RecurrenceDateEdit::Remove(day)],
+            ..Default::default()
+        };
+        patch.resolve(now).apply_to(&mut vevent);
+        assert!(vevent.ex_dates.is_empty(), "Empty EXDATE should be dropped");
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn event_patch_apply_to_adds_rdates_as_dates_for_all_day_events() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let mut vevent = create_test_vevent("test-uid", "Test");
+        vevent.dt_start = Some(DtStart::new(LooseDateTime::DateOnly(date(2025, 4, 17))));
+
+        let at = date(2025, 4, 18).at(9, 0, 0, 0);
+        let patch = EventPatch {
+            rdates: vec![RecurrenceDateEdit::Add(LooseDateTime::Floating(at))],
+            ..Default::default()
+        };
+        patch.resolve(now).apply_to(&mut vevent);
+
+        assert_eq!(vevent.rdates.len(), 1);
+        assert!(vevent.rdates[0].contains(&ical::DateTime::Date(date(2025, 4, 18).into())));
+        assert!(vevent.rdates[0].tz_id.is_none());
+    }
+
     #[test]
     fn event_patch_resolve_with_now_sets_dt_stamp_if_unset() {
         let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
@@ -1056,7 +1989,7 @@ mod tests {
         let mut vevent = VEvent {
             uid: Uid::new("test-uid".to_string()),
             dt_stamp: vevent_dt_stamp(),
-            dt_start: DtStart::new(dt_start_val),
+            dt_start: Some(DtStart::new(dt_start_val)),
             dt_end: Some(DtEnd::new(dt_end_val)),
             duration: None,
             summary: Some(Summary::new("Test".to_string())),
@@ -1072,8 +2005,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rdates: Vec::new(),
             rrule: None,
             ex_dates: Vec::new(),
@@ -1149,11 +2088,17 @@ mod tests {
         );
 
         let patch = EventPatch {
+            alarm: None,
             description: Some(Some("New Description".to_string())),
             start: Some(Some(new_start)),
             end: Some(Some(new_end)),
             status: Some(EventStatus::Cancelled),
             summary: Some("New Summary".to_string()),
+            ex_dates: Vec::new(),
+            rdates: Vec::new(),
+            until: None,
+            meta: Vec::new(),
+            check_in: None,
         };
 
         let resolved = patch.resolve(now.clone());
@@ -1174,14 +2119,14 @@ mod tests {
 
         // Check start and end were updated
         assert!(
-            matches!(vevent.dt_start.0.value, ical::DateTime::Utc { date, .. } if
+            matches!(vevent.dt_start.as_ref().unwrap().0.value, ical::DateTime::Utc { date, .. } if
                 date.year == 2025 &&
                 date.month == 6 &&
                 date.day == 1
             )
         );
         // The time should be 14:00:00 for start and 15:00:00 for end
-        if let ical::DateTime::Utc { time, .. } = vevent.dt_start.0.value {
+        if let ical::DateTime::Utc { time, .. } = vevent.dt_start.as_ref().unwrap().0.value {
             assert_eq!(time.hour, 14);
         }
         assert!(vevent.dt_end.is_some());
@@ -1196,4 +2141,54 @@ mod tests {
             assert_eq!(time.hour, 15);
         }
     }
+
+    #[test]
+    fn event_patch_check_in_replaces_and_clears_the_check_in() {
+        let now = Zoned::new(jiff::Timestamp::now(), TimeZone::UTC);
+        let mut vevent = create_test_vevent("test-uid", "Standup");
+        let status = Event::status(&vevent);
+
+        let check_in = |attendance, note: Option<&str>| EventPatch {
+            check_in: Some(Some(CheckIn {
+                attendance,
+                note: note.map(ToString::to_string),
+            })),
+            ..Default::default()
+        };
+        check_in(Attendance::Attended, Some("ran 20 min over"))
+            .resolve(now.clone())
+            .apply_to(&mut vevent);
+        assert_eq!(
+            Event::check_in(&vevent),
+            Some(CheckIn {
+                attendance: Attendance::Attended,
+                note: Some("ran 20 min over".to_string()),
+            })
+        );
+
+        check_in(Attendance::Skipped, None)
+            .resolve(now.clone())
+            .apply_to(&mut vevent);
+        assert_eq!(
+            Event::check_in(&vevent),
+            Some(CheckIn {
+                attendance: Attendance::Skipped,
+                note: None,
+            })
+        );
+        assert_eq!(vevent.x_properties.len(), 1, "the note is removed");
+        assert_eq!(
+            Event::status(&vevent),
+            status,
+            "the status is left as it was"
+        );
+
+        let clear = EventPatch {
+            check_in: Some(None),
+            ..Default::default()
+        };
+        clear.resolve(now).apply_to(&mut vevent);
+        assert_eq!(Event::check_in(&vevent), None);
+        assert!(vevent.x_properties.is_empty());
+    }
 }
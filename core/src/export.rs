@@ -0,0 +1,553 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::{self, Write};
+
+use aimcal_ical::fmt::{FormatOptions, Formatter};
+use aimcal_ical::{
+    CalendarComponent, DtEnd, DtStart, Due, EventStatusValue, ICalendar, MethodValue, Property,
+    Uid, VEvent, VTodo, Value, ValueText, XNameProperty, ops::standalone_calendar,
+};
+use jiff::Zoned;
+use jiff::civil::{self, Date};
+
+use crate::series::{
+    Recurrence, civil_in_frame, dt_stamp, is_this_and_future, recurrence_id, shift_of,
+    shifted_property, with_civil,
+};
+use crate::{AimError, LooseDateTime};
+
+/// Property marking a calendar of occurrences expanded by aim, with the dates they were expanded
+/// between, so that importing it back is told apart from importing the series.
+pub(crate) const X_AIM_EXPANDED: &str = "X-AIM-EXPANDED";
+
+/// Number of occurrences an expanded export holds at most unless told otherwise.
+pub const DEFAULT_EXPAND_LIMIT: usize = 10_000;
+
+/// A standalone calendar of exported events or todos, ready to be written out.
+#[derive(Debug, Clone)]
+pub struct CalendarExport {
+    /// The calendar properties and the time zones the items refer to.
+    calendar: ICalendar<String>,
+    /// The exported items.
+    components: Vec<CalendarComponent<String>>,
+}
+
+impl CalendarExport {
+    /// Number of events and todos exported.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Whether nothing is exported, such as no event occurring between the dates expanded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Writes the calendar to `w` line by line.
+    ///
+    /// # Errors
+    /// If writing fails.
+    pub fn write_to(self, w: impl Write) -> io::Result<()> {
+        let mut formatter = Formatter::new(w, FormatOptions::default());
+        formatter.write_calendar_streaming(&self.calendar, self.components)?;
+        formatter.flush()
+    }
+}
+
+/// Prepares an event as a standalone calendar to publish, see [`standalone_calendar`].
+///
+/// With `occurrence`, only the occurrence of a recurring event on that date is exported, as an
+/// event of its own.
+pub(crate) fn export_event(
+    mut event: VEvent<String>,
+    occurrence: Option<Date>,
+    now: &Zoned,
+) -> Result<CalendarExport, AimError> {
+    if let Some(date) = occurrence {
+        let recurrence = Recurrence::of_event(&event);
+        let start = recurrence.start("Event")?;
+        let at = recurrence.occurrence_on(&start, date, "Event")?;
+        let shift = shift_of(&start, &at);
+
+        event.dt_start = event
+            .dt_start
+            .map(|s| DtStart::new(at.to_property_like(&s)));
+        event.dt_end = event
+            .dt_end
+            .map(|end| DtEnd::new(shifted_property(&end, shift)));
+        event.uid = Uid::new(occurrence_uid(&event.uid.content.to_string(), &at));
+        event.rrule = None;
+        event.rdates.clear();
+        event.ex_dates.clear();
+    }
+    event.dt_stamp = dt_stamp(now);
+    Ok(standalone(vec![CalendarComponent::Event(event)]))
+}
+
+/// Prepares a todo as a standalone calendar to publish, see [`standalone_calendar`].
+///
+/// With `occurrence`, only the occurrence of a recurring todo on that date is exported, as a
+/// todo of its own.
+pub(crate) fn export_todo(
+    mut todo: VTodo<String>,
+    occurrence: Option<Date>,
+    now: &Zoned,
+) -> Result<CalendarExport, AimError> {
+    if let Some(date) = occurrence {
+        let recurrence = Recurrence::of_todo(&todo);
+        let start = recurrence.start("Todo")?;
+        let at = recurrence.occurrence_on(&start, date, "Todo")?;
+        let shift = shift_of(&start, &at);
+
+        todo.dt_start = todo
+            .dt_start
+            .map(|s| DtStart::new(shifted_property(&s, shift)));
+        todo.due = todo.due.map(|d| Due::new(shifted_property(&d, shift)));
+        todo.uid = Uid::new(occurrence_uid(&todo.uid.content.to_string(), &at));
+        todo.rrule = None;
+        todo.rdates.clear();
+        todo.ex_dates.clear();
+    }
+    todo.dt_stamp = dt_stamp(now);
+    Ok(standalone(vec![CalendarComponent::Todo(todo)]))
+}
+
+/// Expands events into their occurrences from `from` to `to`, each an event of its own without
+/// recurrence, for tools that don't understand recurrence rules.
+///
+/// Overrides of occurrences take their place, and excluded and cancelled occurrences are left
+/// out, so the events match the calendar as it is shown. An occurrence gets the UID of its series
+/// followed by the time it recurs at, events that don't recur keep theirs. Events that cannot be
+/// expanded are skipped with a warning. The calendar is marked with `X-AIM-EXPANDED`.
+///
+/// # Errors
+/// If there are more than `limit` occurrences, such as from a rule recurring every minute.
+pub(crate) fn export_expanded(
+    events: Vec<VEvent<String>>,
+    overrides: &[VEvent<String>],
+    from: Date,
+    to: Date,
+    limit: usize,
+    now: &Zoned,
+) -> Result<CalendarExport, AimError> {
+    let mut occurrences = Vec::new();
+    for event in events {
+        let uid = event.uid.content.to_string();
+        let of_event: Vec<_> = overrides
+            .iter()
+            .filter(|over| over.uid.content.to_string() == uid)
+            .collect();
+        match expand_event(event, &of_event, from, to) {
+            Ok(expanded) => occurrences.extend(expanded),
+            Err(e) => tracing::warn!(uid, err = %e, "skipping event that cannot be expanded"),
+        }
+        if occurrences.len() > limit {
+            return Err(AimError::InvalidInput {
+                field: "limit",
+                reason: format!(
+                    "more than {limit} occurrences from {from} to {to}, narrow the dates or raise the limit"
+                ),
+            });
+        }
+    }
+
+    occurrences.sort_by_key(|event| {
+        event
+            .dt_start
+            .as_ref()
+            .map(|start| LooseDateTime::from(start.0.clone()).with_start_of_day())
+    });
+    let components = occurrences
+        .into_iter()
+        .map(|mut event| {
+            event.dt_stamp = dt_stamp(now);
+            CalendarComponent::Event(event)
+        })
+        .collect();
+    let mut export = standalone(components);
+    export.calendar.x_properties.push(XNameProperty {
+        name: X_AIM_EXPANDED.to_string(),
+        parameters: Vec::new(),
+        value: Value::Text {
+            values: vec![ValueText::new(format!("{from}/{to}"))],
+            span: (),
+        },
+        span: (),
+    });
+    Ok(export)
+}
+
+/// The occurrences of an event starting from `from` to `to`, with the `overrides` of the event
+/// merged in.
+///
+/// An override with `RANGE=THISANDFUTURE` stands for the occurrences after it as well, moved by
+/// as much as each of them is from the occurrence it names.
+fn expand_event(
+    event: VEvent<String>,
+    overrides: &[&VEvent<String>],
+    from: Date,
+    to: Date,
+) -> Result<Vec<VEvent<String>>, AimError> {
+    let starts_within = |event: &VEvent<String>| {
+        event.dt_start.as_ref().is_some_and(|start| {
+            let date = LooseDateTime::from(start.0.clone()).date();
+            (from..=to).contains(&date)
+        })
+    };
+
+    let recurrence = Recurrence::of_event(&event);
+    if recurrence.rrule.is_none() && recurrence.rdates.is_empty() {
+        return Ok(if starts_within(&event) {
+            vec![event]
+        } else {
+            Vec::new()
+        });
+    }
+    let start = recurrence.start("Event")?;
+    let mut ats = recurrence.occurrences_between(&start, from, to)?;
+
+    let overrides: Vec<_> = overrides
+        .iter()
+        .filter_map(|over| {
+            let id = recurrence_id(over)?;
+            let at = civil_in_frame(&id.0.value, id.0.tz_id.as_ref(), &start);
+            Some((at, is_this_and_future(id), *over))
+        })
+        .collect();
+    // An override may move its occurrence into the dates from outside of them
+    ats.extend(
+        overrides
+            .iter()
+            .filter(|(_, future, _)| !future)
+            .map(|(at, ..)| *at),
+    );
+    ats.sort();
+    ats.dedup();
+
+    let uid = event.uid.content.to_string();
+    let first = start.with_start_of_day();
+    let mut expanded = Vec::new();
+    for at in ats {
+        let exact = overrides
+            .iter()
+            .find(|(id, future, _)| !future && *id == at);
+        let future = overrides
+            .iter()
+            .filter(|(id, future, _)| *future && *id <= at)
+            .max_by_key(|(id, ..)| *id);
+        let mut occurrence = match (exact, future) {
+            (Some((_, _, over)), _) => (*over).clone(),
+            (None, Some((id, _, over))) => moved(over, *id, at),
+            (None, None) => moved(&event, first, at),
+        };
+        occurrence.uid = Uid::new(expanded_uid(&uid, &with_civil(&start, at)));
+        occurrence.rrule = None;
+        occurrence.rdates.clear();
+        occurrence.ex_dates.clear();
+        occurrence
+            .retained_properties
+            .retain(|p| !matches!(p, Property::RecurrenceId(_)));
+
+        let cancelled = occurrence
+            .status
+            .as_ref()
+            .is_some_and(|status| matches!(status.value, EventStatusValue::Cancelled));
+        if !cancelled && starts_within(&occurrence) {
+            expanded.push(occurrence);
+        }
+    }
+    Ok(expanded)
+}
+
+/// The event at `from` moved to the occurrence at `to`, in the civil time of the series.
+fn moved(event: &VEvent<String>, from: civil::DateTime, to: civil::DateTime) -> VEvent<String> {
+    let shift = from.duration_until(to);
+    let mut event = event.clone();
+    event.dt_start = event
+        .dt_start
+        .map(|start| DtStart::new(shifted_property(&start, shift)));
+    event.dt_end = event
+        .dt_end
+        .map(|end| DtEnd::new(shifted_property(&end, shift)));
+    event
+}
+
+fn standalone(components: Vec<CalendarComponent<String>>) -> CalendarExport {
+    // Only the time zones are kept, the items are streamed after them
+    let mut calendar = standalone_calendar(components.clone(), Some(MethodValue::Publish));
+    calendar
+        .components
+        .retain(|c| matches!(c, CalendarComponent::VTimeZone(_)));
+    CalendarExport {
+        calendar,
+        components,
+    }
+}
+
+/// The UID of an exported occurrence, so that it doesn't replace the whole series on import.
+fn occurrence_uid(uid: &str, at: &LooseDateTime) -> String {
+    format!("{uid}_{}", recurrence_stamp(at))
+}
+
+/// The UID of an occurrence of an expanded export, the UID of the series followed by the time
+/// the occurrence recurs at, which stays the same when an override moves it.
+fn expanded_uid(uid: &str, at: &LooseDateTime) -> String {
+    format!("{uid}-{}", recurrence_stamp(at))
+}
+
+fn recurrence_stamp(at: &LooseDateTime) -> String {
+    match at {
+        LooseDateTime::DateOnly(date) => date.strftime("%Y%m%d").to_string(),
+        _ => at.with_start_of_day().strftime("%Y%m%dT%H%M%S").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_ical::{CalendarComponent, parse};
+    use jiff::civil::date;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+
+    fn event(lines: &str) -> VEvent<String> {
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VEVENT\r\nUID:e1\r\nDTSTAMP:20260101T000000Z\r\n{lines}END:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = parse(&src).unwrap();
+        match calendars
+            .into_iter()
+            .next()
+            .unwrap()
+            .components
+            .into_iter()
+            .next()
+        {
+            Some(CalendarComponent::Event(event)) => event.to_owned(),
+            _ => panic!("expected an event"),
+        }
+    }
+
+    fn now() -> Zoned {
+        date(2026, 3, 1)
+            .at(12, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap()
+    }
+
+    fn export(event: VEvent<String>, occurrence: Date) -> Result<String, AimError> {
+        let mut ics = Vec::new();
+        export_event(event, Some(occurrence), &now())?
+            .write_to(&mut ics)
+            .unwrap();
+        Ok(String::from_utf8(ics).unwrap())
+    }
+
+    #[test]
+    fn exports_occurrence_as_single_event() {
+        let event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             DTEND;TZID=Europe/Berlin:20260105T100000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+             EXDATE;TZID=Europe/Berlin:20260112T090000\r\n",
+        );
+        let ics = export(event.clone(), date(2026, 4, 6)).unwrap();
+        assert!(ics.contains("UID:e1_20260406T090000\r\n"));
+        assert!(ics.contains("DTSTART;TZID=Europe/Berlin:20260406T090000\r\n"));
+        assert!(ics.contains("DTEND;TZID=Europe/Berlin:20260406T100000\r\n"));
+        assert!(!ics.contains("RRULE"));
+        assert!(!ics.contains("EXDATE"));
+
+        let err = export(event.clone(), date(2026, 1, 12)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid occurrence: Event does not occur on 2026-01-12"
+        );
+        let err = export(event, date(2026, 1, 13)).unwrap_err();
+        assert!(matches!(err, AimError::InvalidInput { .. }));
+    }
+
+    #[test]
+    fn exports_occurrence_of_all_day_event() {
+        let event = event(
+            "DTSTART;VALUE=DATE:20260131\r\n\
+             DTEND;VALUE=DATE:20260201\r\n\
+             RRULE:FREQ=MONTHLY;BYMONTHDAY=-1\r\n",
+        );
+        let ics = export(event, date(2026, 2, 28)).unwrap();
+        assert!(ics.contains("UID:e1_20260228\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260228\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20260301\r\n"));
+    }
+
+    #[test]
+    fn rejects_occurrence_of_single_event() {
+        let event = event("DTSTART:20260105T090000Z\r\n");
+        let err = export(event, date(2026, 1, 5)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid occurrence: Event is not recurring"
+        );
+    }
+    #[test]
+    fn rejects_occurrence_of_unscheduled_event() {
+        let event = event("RRULE:FREQ=WEEKLY\r\n");
+        let err = export(event, date(2026, 1, 5)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid occurrence: Event has no start to recur from"
+        );
+    }
+
+    /// The events of a calendar, apart from the overrides, and the overrides.
+    fn series(events: &[&str]) -> (Vec<VEvent<String>>, Vec<VEvent<String>>) {
+        let blocks: String = events
+            .iter()
+            .flat_map(|lines| {
+                [
+                    "BEGIN:VEVENT\r\nDTSTAMP:20260101T000000Z\r\n",
+                    lines,
+                    "END:VEVENT\r\n",
+                ]
+            })
+            .collect();
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\n{blocks}END:VCALENDAR\r\n"
+        );
+        parse(&src)
+            .unwrap()
+            .remove(0)
+            .components
+            .into_iter()
+            .filter_map(|c| match c {
+                CalendarComponent::Event(event) => Some(event.to_owned()),
+                _ => None,
+            })
+            .partition(|event| recurrence_id(event).is_none())
+    }
+
+    fn expand(events: &[&str], from: Date, to: Date, limit: usize) -> Result<String, AimError> {
+        let (events, overrides) = series(events);
+        let export = export_expanded(events, &overrides, from, to, limit, &now())?;
+        let mut ics = Vec::new();
+        export.write_to(&mut ics).unwrap();
+        Ok(String::from_utf8(ics).unwrap())
+    }
+
+    #[test]
+    fn expands_occurrences_with_overrides_and_exclusions() {
+        let ics = expand(
+            &[
+                "UID:standup\r\n\
+                 DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+                 DTEND;TZID=Europe/Berlin:20260105T091500\r\n\
+                 RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+                 EXDATE;TZID=Europe/Berlin:20260112T090000\r\n\
+                 SUMMARY:Standup\r\n",
+                "UID:standup\r\n\
+                 RECURRENCE-ID;TZID=Europe/Berlin:20260119T090000\r\n\
+                 DTSTART;TZID=Europe/Berlin:20260120T100000\r\n\
+                 DTEND;TZID=Europe/Berlin:20260120T103000\r\n\
+                 SUMMARY:Standup (moved)\r\n",
+                "UID:standup\r\n\
+                 RECURRENCE-ID;TZID=Europe/Berlin:20260126T090000\r\n\
+                 DTSTART;TZID=Europe/Berlin:20260126T090000\r\n\
+                 STATUS:CANCELLED\r\n",
+                "UID:lunch\r\nDTSTART:20260107T120000Z\r\nSUMMARY:Lunch\r\n",
+                "UID:later\r\nDTSTART:20260301T120000Z\r\nSUMMARY:Later\r\n",
+            ],
+            date(2026, 1, 1),
+            date(2026, 1, 31),
+            100,
+        )
+        .unwrap();
+
+        assert!(ics.contains("X-AIM-EXPANDED:2026-01-01/2026-01-31\r\n"));
+        let uids: Vec<_> = ics.lines().filter(|l| l.starts_with("UID:")).collect();
+        assert_eq!(
+            uids,
+            [
+                "UID:standup-20260105T090000",
+                "UID:lunch",
+                "UID:standup-20260119T090000",
+            ]
+        );
+        assert!(ics.contains("DTSTART;TZID=Europe/Berlin:20260120T100000\r\n"));
+        assert!(ics.contains("SUMMARY:Standup (moved)\r\n"));
+        assert!(!ics.contains("RRULE"));
+        assert!(!ics.contains("EXDATE"));
+        assert!(!ics.contains("RECURRENCE-ID"));
+    }
+
+    #[test]
+    fn expands_overrides_of_this_and_future() {
+        let ics = expand(
+            &[
+                "UID:review\r\n\
+                 DTSTART;VALUE=DATE:20260105\r\n\
+                 RRULE:FREQ=WEEKLY;COUNT=4\r\n\
+                 SUMMARY:Review\r\n",
+                "UID:review\r\n\
+                 RECURRENCE-ID;RANGE=THISANDFUTURE;VALUE=DATE:20260119\r\n\
+                 DTSTART;VALUE=DATE:20260120\r\n\
+                 SUMMARY:Review on Tuesdays\r\n",
+            ],
+            date(2026, 1, 1),
+            date(2026, 12, 31),
+            100,
+        )
+        .unwrap();
+
+        for line in [
+            "UID:review-20260112\r\nDTSTAMP:20260301T120000Z\r\nDTSTART;VALUE=DATE:20260112\r\n",
+            "UID:review-20260119\r\nDTSTAMP:20260301T120000Z\r\nDTSTART;VALUE=DATE:20260120\r\n",
+            "UID:review-20260126\r\nDTSTAMP:20260301T120000Z\r\nDTSTART;VALUE=DATE:20260127\r\n",
+        ] {
+            assert!(ics.contains(line), "{line} in {ics}");
+        }
+        assert_eq!(ics.matches("SUMMARY:Review on Tuesdays").count(), 2);
+        assert!(!ics.contains("20260202"), "the count is used up");
+    }
+
+    #[test]
+    fn refuses_more_occurrences_than_the_limit() {
+        let daily = "UID:daily\r\nDTSTART:20200101T080000Z\r\nRRULE:FREQ=DAILY\r\n";
+        let err = expand(&[daily], date(2026, 1, 1), date(2026, 12, 31), 100).unwrap_err();
+        assert!(
+            matches!(err, AimError::InvalidInput { field: "limit", .. }),
+            "{err}"
+        );
+
+        // Only the dates asked for are expanded, however long ago the series started
+        let ics = expand(&[daily], date(2026, 1, 1), date(2026, 1, 31), 100).unwrap();
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 31);
+    }
+
+    #[test]
+    fn exports_occurrence_of_todo_from_its_due() {
+        // As written by Apple Reminders, without DTSTART
+        let src = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Apple Inc.//iOS 18.2//EN\r\n\
+                   BEGIN:VTODO\r\nUID:9E3B5C1A-7F42-4D8B-A1E6-2C0F8D4B7A31\r\n\
+                   DTSTAMP:20260101T000000Z\r\nSUMMARY:Take out the trash\r\n\
+                   DUE;TZID=Europe/Berlin:20260105T190000\r\nRRULE:FREQ=WEEKLY\r\n\
+                   X-APPLE-SORT-ORDER:786432000\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+        let calendar = parse(src).unwrap().remove(0);
+        let Some(CalendarComponent::Todo(todo)) = calendar.components.first() else {
+            panic!("expected a todo");
+        };
+
+        let mut ics = Vec::new();
+        export_todo(todo.to_owned(), Some(date(2026, 1, 19)), &now())
+            .unwrap()
+            .write_to(&mut ics)
+            .unwrap();
+        let ics = String::from_utf8(ics).unwrap();
+        assert!(ics.contains("UID:9E3B5C1A-7F42-4D8B-A1E6-2C0F8D4B7A31_20260119T190000\r\n"));
+        assert!(ics.contains("DUE;TZID=Europe/Berlin:20260119T190000\r\n"));
+        assert!(!ics.contains("RRULE"));
+    }
+}
@@ -0,0 +1,211 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Crash-safe file writes, and detection of folders kept by sync clients.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// How many times a rename refused by a transient sharing violation is tried.
+const RENAME_ATTEMPTS: u32 = 10;
+
+/// Writes `content` to `path` so that a crash leaves either the old or the new content, never a
+/// mix of both.
+///
+/// The content goes to a temporary file next to `path` first, which is flushed to disk and then
+/// renamed over `path`, see [`rename_with_retry`].
+///
+/// # Errors
+/// If the parent directory cannot be created, or the file cannot be written or renamed.
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = temp_sibling(path);
+    let written = File::create(&tmp).and_then(|mut file| {
+        file.write_all(content)?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| rename_with_retry(&tmp, path)) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    sync_parent(path);
+    Ok(())
+}
+
+/// Renames `from` to `to`, replacing `to`, trying again for a while when the rename is refused
+/// because another process has one of the files open.
+///
+/// On Windows, virus scanners, indexers and sync clients such as `OneDrive` open files briefly,
+/// failing renames with a sharing violation until they let go. Elsewhere the rename is tried once.
+///
+/// # Errors
+/// If the rename fails, or keeps being refused.
+pub fn rename_with_retry(from: &Path, to: &Path) -> io::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match fs::rename(from, to) {
+            Err(e) if attempt < RENAME_ATTEMPTS && is_sharing_violation(&e) => {
+                tracing::debug!(path = %to.display(), attempt, err = %e, "rename refused, retrying");
+                thread::sleep(Duration::from_millis(50) * attempt);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether the error is one that goes away once other processes close the file.
+#[cfg(windows)]
+fn is_sharing_violation(e: &io::Error) -> bool {
+    const ERROR_ACCESS_DENIED: i32 = 5;
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+    matches!(
+        e.raw_os_error(),
+        Some(ERROR_ACCESS_DENIED | ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+    )
+}
+
+/// Whether the error is one that goes away once other processes close the file.
+#[cfg(not(windows))]
+fn is_sharing_violation(_: &io::Error) -> bool {
+    false
+}
+
+/// A unique temporary path next to `path`, hidden and without the extension of `path`, so that
+/// it is not taken for a calendar file.
+fn temp_sibling(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{name}.aim-tmp-{}", Uuid::new_v4().simple()))
+}
+
+/// Flushes the rename of a file in its directory to disk.
+#[cfg(unix)]
+fn sync_parent(path: &Path) {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty())
+        && let Err(e) = File::open(parent).and_then(|dir| dir.sync_all())
+    {
+        tracing::debug!(path = %parent.display(), err = %e, "failed to sync directory");
+    }
+}
+
+/// Flushes the rename of a file in its directory to disk, which only unix supports.
+#[cfg(not(unix))]
+fn sync_parent(_: &Path) {}
+
+/// Finds the sync client keeping the folder at `path`, such as `OneDrive` or `Dropbox`, from the
+/// names of the folders it is in.
+///
+/// Sync clients replace and lock files behind the back of aim, breaking the atomic renames and
+/// the file locking the database relies on.
+#[must_use]
+pub fn sync_folder(path: &Path) -> Option<&'static str> {
+    // OneDrive tells where its folders are, whatever they are named
+    #[cfg(windows)]
+    for var in ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"] {
+        if let Some(root) = std::env::var_os(var).filter(|root| !root.is_empty())
+            && path.starts_with(root)
+        {
+            return Some("OneDrive");
+        }
+    }
+
+    path.components().find_map(|component| {
+        let Component::Normal(name) = component else {
+            return None;
+        };
+        let name = name.to_string_lossy().to_lowercase();
+        if name == "onedrive" || name.starts_with("onedrive - ") {
+            Some("OneDrive")
+        } else if name == "dropbox" || name.starts_with("dropbox (") {
+            Some("Dropbox")
+        } else if name == "google drive" || name == "googledrive" || name == "my drive" {
+            Some("Google Drive")
+        } else if name == "icloud drive" || name == "iclouddrive" || name == "mobile documents" {
+            Some("iCloud Drive")
+        } else if name == "box sync" {
+            Some("Box")
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_atomically_replacing_the_old_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("a.ics");
+
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        let entries: Vec<_> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, ["a.ics"], "no temporary file is left behind");
+    }
+
+    #[test]
+    fn leaves_the_old_content_when_the_rename_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory cannot be replaced by a file
+        let path = dir.path().join("a.ics");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("inner"), "old").unwrap();
+
+        assert!(write_atomic(&path, b"new").is_err());
+        assert!(path.is_dir());
+        let entries = fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(entries, 1, "the temporary file is removed");
+    }
+
+    #[test]
+    fn detects_folders_of_sync_clients() {
+        let cases = [
+            ("/home/alice/OneDrive/aim", Some("OneDrive")),
+            ("C:/Users/alice/OneDrive - Contoso/aim", Some("OneDrive")),
+            ("/Users/alice/Dropbox/aim", Some("Dropbox")),
+            ("/Users/alice/Dropbox (Personal)/aim", Some("Dropbox")),
+            ("/Volumes/GoogleDrive/My Drive/aim", Some("Google Drive")),
+            (
+                "/Users/alice/Library/Mobile Documents/com~apple~CloudDocs/aim",
+                Some("iCloud Drive"),
+            ),
+            ("/home/alice/.local/state/aim", None),
+            ("/home/alice/dropbox-backup/aim", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(sync_folder(Path::new(path)), expected, "{path}");
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn retries_renames_refused_by_sharing_violations() {
+        assert!(is_sharing_violation(&io::Error::from_raw_os_error(32)));
+        assert!(is_sharing_violation(&io::Error::from_raw_os_error(5)));
+        assert!(!is_sharing_violation(&io::Error::from_raw_os_error(2)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_retry_renames_on_unix() {
+        let e = io::Error::from_raw_os_error(16); // EBUSY
+        assert!(!is_sharing_violation(&e));
+    }
+}
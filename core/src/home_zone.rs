@@ -0,0 +1,265 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Changes of the home time zone.
+//!
+//! Floating times are read in whatever zone is home, so after a move they happen at another
+//! instant. Times written in the old zone keep their instant, but their wall clock in the new zone
+//! differs. Both are found from the times cached, and can be rebased onto the new zone.
+
+use std::collections::HashSet;
+
+use jiff::Zoned;
+use jiff::tz::TimeZone;
+
+use crate::{Config, Kind, LooseDateTime};
+
+/// A time of an item that reads differently since the home time zone changed, see
+/// [`Aim::audit_time_zone`](crate::Aim::audit_time_zone).
+#[derive(Debug, Clone)]
+pub struct ZoneShift {
+    /// Kind of the item.
+    pub kind: Kind,
+    /// UID of the item.
+    pub uid: String,
+    /// Summary of the item.
+    pub summary: String,
+    /// Which time of the item it is, `start` or `end` of an event, or `due` of a todo.
+    pub field: &'static str,
+    /// The time as stored, floating or in the old zone.
+    pub time: LooseDateTime,
+    /// The time as it was read in the old zone.
+    pub was: Zoned,
+    /// The time as it is read in the new zone.
+    pub now: Zoned,
+}
+
+impl ZoneShift {
+    /// Whether the time is floating, rather than in the old zone.
+    #[must_use]
+    pub fn is_floating(&self) -> bool {
+        matches!(self.time, LooseDateTime::Floating(_))
+    }
+
+    /// The time moved onto the new zone at the instant it had in the old one.
+    ///
+    /// Floating times are pinned to the new zone as well, so they are not rebased again once
+    /// rebased.
+    #[must_use]
+    pub fn rebased(&self) -> LooseDateTime {
+        LooseDateTime::Local(self.was.with_time_zone(self.now.time_zone().clone()))
+    }
+}
+
+/// Outcome of rebasing the times onto a new home time zone, see
+/// [`Aim::rebase_time_zone`](crate::Aim::rebase_time_zone).
+#[derive(Debug, Clone, Default)]
+pub struct ZoneRebase {
+    /// The times rebased, or that would be on a dry run.
+    pub rebased: Vec<ZoneShift>,
+    /// UIDs of the items left as they were, with the reason.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// A time of day of an item as cached, see [`Db::timed_fields`](crate::db::Db::timed_fields).
+#[derive(Debug, Clone)]
+pub(crate) struct TimedField {
+    pub kind: Kind,
+    pub uid: String,
+    pub summary: String,
+    /// `start` or `end` of an event, or `due` of a todo.
+    pub field: &'static str,
+    /// The time in its stable form.
+    pub stored: String,
+}
+
+/// The home time zone, by its IANA name: the one of the config, or else the one of the system if
+/// it has a name.
+pub(crate) fn home_zone(config: &Config) -> Option<(String, TimeZone)> {
+    if let Some(name) = &config.home_timezone {
+        TimeZone::get(name).ok().map(|tz| (name.clone(), tz))
+    } else {
+        let tz = TimeZone::system();
+        let name = tz.iana_name()?.to_string();
+        Some((name, tz))
+    }
+}
+
+/// Finds the times of the fields that read differently in `to` than in `from`.
+///
+/// A time with an offset is taken to be in `from` if the offset is the one `from` has at that
+/// instant, since the zone itself is not cached.
+pub(crate) fn find_shifts(
+    fields: Vec<TimedField>,
+    from: &TimeZone,
+    to: &TimeZone,
+    floating_only: bool,
+) -> Vec<ZoneShift> {
+    fields
+        .into_iter()
+        .filter_map(|f| {
+            let time = LooseDateTime::parse_stable(&f.stored)?;
+            let (was, now) = match &time {
+                LooseDateTime::Floating(dt) => (
+                    dt.to_zoned(from.clone()).ok()?,
+                    dt.to_zoned(to.clone()).ok()?,
+                ),
+                LooseDateTime::Local(zoned)
+                    if !floating_only && zoned.offset() == from.to_offset(zoned.timestamp()) =>
+                {
+                    (
+                        zoned.with_time_zone(from.clone()),
+                        zoned.with_time_zone(to.clone()),
+                    )
+                }
+                _ => return None,
+            };
+            (was.offset() != now.offset()).then_some(ZoneShift {
+                kind: f.kind,
+                uid: f.uid,
+                summary: f.summary,
+                field: f.field,
+                time,
+                was,
+                now,
+            })
+        })
+        .collect()
+}
+
+/// The warning shown once the home time zone changed, if any item reads differently since.
+pub(crate) fn change_notice(from: &str, to: &str, shifts: &[ZoneShift]) -> Option<String> {
+    let mut floating = HashSet::new();
+    let mut zoned = HashSet::new();
+    for shift in shifts {
+        let item = (shift.kind.to_str_stable(), shift.uid.as_str());
+        if shift.is_floating() {
+            floating.insert(item);
+        } else {
+            zoned.insert(item);
+        }
+    }
+    if floating.is_empty() && zoned.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "Home time zone changed from {from} to {to}: {} with floating times and {} with times in \
+         {from} now read differently, see `aim tz audit`",
+        items(floating.len()),
+        items(zoned.len()),
+    ))
+}
+
+fn items(count: usize) -> String {
+    match count {
+        1 => "1 item".to_string(),
+        n => format!("{n} items"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::SignedDuration;
+    use jiff::civil::datetime;
+
+    use super::*;
+
+    fn field(kind: Kind, uid: &str, field: &'static str, stored: &str) -> TimedField {
+        TimedField {
+            kind,
+            uid: uid.to_string(),
+            summary: uid.to_string(),
+            field,
+            stored: stored.to_string(),
+        }
+    }
+
+    /// The fields of the shifts as stored once rebased.
+    fn rebased_fields(shifts: &[ZoneShift]) -> Vec<TimedField> {
+        shifts
+            .iter()
+            .map(|s| TimedField {
+                kind: s.kind,
+                uid: s.uid.clone(),
+                summary: s.summary.clone(),
+                field: s.field,
+                stored: s.rebased().format_stable(),
+            })
+            .collect()
+    }
+
+    fn zones() -> (TimeZone, TimeZone) {
+        (
+            TimeZone::get("Europe/Berlin").unwrap(),
+            TimeZone::get("Europe/Lisbon").unwrap(),
+        )
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn finds_floating_times_and_times_in_the_old_zone() {
+        let (berlin, lisbon) = zones();
+        let fields = vec![
+            field(Kind::Event, "floating", "start", "2026-03-02T09:00:00"),
+            field(Kind::Event, "berlin", "start", "2026-03-02T09:00:00+0100"),
+            field(Kind::Todo, "utc", "due", "2026-03-02T09:00:00+0000"),
+            field(Kind::Todo, "date", "due", "2026-03-02"),
+        ];
+        let shifts = find_shifts(fields.clone(), &berlin, &lisbon, false);
+        let uids: Vec<_> = shifts.iter().map(|s| s.uid.as_str()).collect();
+        assert_eq!(uids, ["floating", "berlin"]);
+
+        let floating = &shifts[0];
+        assert!(floating.is_floating());
+        assert_eq!(floating.was.datetime(), datetime(2026, 3, 2, 9, 0, 0, 0));
+        assert_eq!(
+            floating.now.timestamp(),
+            floating.was.timestamp() + SignedDuration::from_hours(1)
+        );
+        let LooseDateTime::Local(rebased) = floating.rebased() else {
+            panic!("expected the floating time to be pinned to the new zone");
+        };
+        assert_eq!(rebased.datetime(), datetime(2026, 3, 2, 8, 0, 0, 0));
+        assert_eq!(rebased.time_zone().iana_name(), Some("Europe/Lisbon"));
+        assert!(find_shifts(rebased_fields(&shifts), &berlin, &lisbon, false).is_empty());
+
+        let zoned = &shifts[1];
+        assert!(!zoned.is_floating());
+        assert_eq!(zoned.now.datetime(), datetime(2026, 3, 2, 8, 0, 0, 0));
+        assert_eq!(zoned.now.timestamp(), zoned.was.timestamp());
+
+        let shifts = find_shifts(fields, &berlin, &lisbon, true);
+        let uids: Vec<_> = shifts.iter().map(|s| s.uid.as_str()).collect();
+        assert_eq!(uids, ["floating"]);
+    }
+
+    #[test]
+    fn finds_nothing_between_zones_with_the_same_offsets() {
+        let berlin = TimeZone::get("Europe/Berlin").unwrap();
+        let paris = TimeZone::get("Europe/Paris").unwrap();
+        let fields = vec![
+            field(Kind::Event, "floating", "start", "2026-03-02T09:00:00"),
+            field(Kind::Event, "berlin", "end", "2026-03-02T10:00:00+0100"),
+        ];
+        assert!(find_shifts(fields, &berlin, &paris, false).is_empty());
+    }
+
+    #[test]
+    fn notices_items_once_each() {
+        let (berlin, lisbon) = zones();
+        let fields = vec![
+            field(Kind::Event, "meeting", "start", "2026-03-02T09:00:00"),
+            field(Kind::Event, "meeting", "end", "2026-03-02T10:00:00"),
+            field(Kind::Todo, "report", "due", "2026-03-02T17:00:00+0100"),
+        ];
+        let shifts = find_shifts(fields, &berlin, &lisbon, false);
+        assert_eq!(
+            change_notice("Europe/Berlin", "Europe/Lisbon", &shifts).unwrap(),
+            "Home time zone changed from Europe/Berlin to Europe/Lisbon: 1 item with floating \
+             times and 1 item with times in Europe/Berlin now read differently, see `aim tz audit`"
+        );
+        assert_eq!(change_notice("Europe/Berlin", "Europe/Lisbon", &[]), None);
+    }
+}
@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Moving old completed todos and past events out of the hot tables of the cache.
+//!
+//! Archiving is a cache-tier distinction only: the calendar files and remote stores keep the
+//! items, and an archived item that changes on its store is moved back on the next sync.
+
+use std::fmt;
+use std::str::FromStr;
+
+use jiff::{SignedDuration, Zoned};
+
+/// Minimum time between automatic housekeeping runs.
+pub const HOUSEKEEPING_INTERVAL: SignedDuration = SignedDuration::from_hours(24);
+
+/// Retention policy, from the `[core.housekeeping]` section.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct HousekeepingConfig {
+    /// Archive todos completed longer ago than this, e.g. `"90d"`.
+    pub archive_completed_after: Option<RetentionAge>,
+
+    /// Archive events that ended longer ago than this, e.g. `"1y"`.
+    pub archive_events_after: Option<RetentionAge>,
+}
+
+impl HousekeepingConfig {
+    /// Whether anything is ever archived.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.archive_completed_after.is_some() || self.archive_events_after.is_some()
+    }
+}
+
+/// How old an item must be before it is archived, in days, weeks or years such as `"90d"`,
+/// `"12w"` or `"1y"`.
+///
+/// A year is 365 days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionAge {
+    days: u32,
+}
+
+impl RetentionAge {
+    /// Creates an age of `days` days.
+    #[must_use]
+    pub fn from_days(days: u32) -> Self {
+        Self { days }
+    }
+
+    /// The age in days.
+    #[must_use]
+    pub fn days(self) -> u32 {
+        self.days
+    }
+
+    /// The instant this long before `now`.
+    pub(crate) fn before(self, now: &Zoned) -> Zoned {
+        let age = SignedDuration::from_hours(i64::from(self.days) * 24);
+        now.saturating_sub(age)
+    }
+}
+
+impl FromStr for RetentionAge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid age, expected a number of days, weeks or years: {s}");
+        let s = s.trim();
+        let unit = s.chars().last().ok_or_else(invalid)?;
+        let amount: u32 = s
+            .strip_suffix(unit)
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(invalid)?;
+        let days = match unit {
+            'd' => Some(amount),
+            'w' => amount.checked_mul(7),
+            'y' => amount.checked_mul(365),
+            _ => return Err(invalid()),
+        };
+        days.map(Self::from_days).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for RetentionAge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}d", self.days)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RetentionAge {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Outcome of a housekeeping run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HousekeepingReport {
+    /// Number of events archived.
+    pub events: u64,
+    /// Number of todos archived.
+    pub todos: u64,
+}
+
+/// Number of items in the hot and archive tables of the cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of events in the hot table.
+    pub events: i64,
+    /// Number of archived events.
+    pub archived_events: i64,
+    /// Number of todos in the hot table.
+    pub todos: i64,
+    /// Number of archived todos.
+    pub archived_todos: i64,
+    /// When housekeeping last ran, if ever.
+    pub last_housekeeping: Option<Zoned>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_retention_ages() {
+        assert_eq!("90d".parse(), Ok(RetentionAge::from_days(90)));
+        assert_eq!("2w".parse(), Ok(RetentionAge::from_days(14)));
+        assert_eq!(" 1y ".parse(), Ok(RetentionAge::from_days(365)));
+        assert!("90".parse::<RetentionAge>().is_err());
+        assert!("1m".parse::<RetentionAge>().is_err());
+        assert!("d".parse::<RetentionAge>().is_err());
+        assert!("".parse::<RetentionAge>().is_err());
+    }
+
+    #[test]
+    fn retention_age_is_counted_back_from_now() {
+        let now: Zoned = "2026-03-10T12:00:00[UTC]".parse().unwrap();
+        let cutoff = RetentionAge::from_days(10).before(&now);
+        assert_eq!(cutoff, "2026-02-28T12:00:00[UTC]".parse().unwrap());
+    }
+}
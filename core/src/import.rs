@@ -0,0 +1,497 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reading events and todos from iCalendar content made elsewhere, such as a calendar file or an
+//! invitation piped to aim, and todos from the exports of other todo apps.
+
+mod ticktick;
+mod todoist;
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::path::Path;
+
+use aimcal_ical::{
+    self as ical, CalendarComponent, Compat, DateTime, DateTimeProperty, ICalendar, ParseOptions,
+    PartialParse, Property, RDateValue, Segments, Severity, VEvent, VTodo, ValueDuration,
+    ValueRecurrenceRule, property::RRule,
+};
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Zoned};
+
+use crate::export::X_AIM_EXPANDED;
+use crate::series::{is_override, shifted};
+use crate::todo::ResolvedTodoDraft;
+use crate::{AimError, Config, Event, EventDraft, LooseDateTime, Priority, TodoStatus};
+
+/// Outcome of importing a calendar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Number of events created.
+    pub events: usize,
+    /// Number of todos created.
+    pub todos: usize,
+    /// Overrides of recurring events written next to them.
+    pub overrides: usize,
+    /// UIDs of the items skipped because they already exist.
+    pub skipped: Vec<String>,
+    /// The dates of an expanded export of aim, whose occurrences were imported as events of their
+    /// own rather than as the series they came from, see [`Aim::export_expanded`](crate::Aim::export_expanded).
+    pub expanded: Option<String>,
+}
+
+/// Export format of another todo app that aim imports from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ForeignFormat {
+    /// The JSON export of Todoist, on its own or in a zip archive.
+    Todoist,
+    /// The CSV backup of `TickTick`.
+    #[cfg_attr(feature = "clap", value(name = "ticktick"))]
+    TickTick,
+}
+
+impl ForeignFormat {
+    /// Reads the todos of an export.
+    ///
+    /// Completed todos are skipped unless `include_completed` is set. What cannot be mapped to
+    /// a todo of aim, such as an unsupported recurrence, is dropped with a warning rather than
+    /// failing the import.
+    ///
+    /// `source` names the content in errors, such as the path of the file it was read from.
+    ///
+    /// # Errors
+    /// If the content is not an export of this format.
+    pub fn read(
+        self,
+        source: &Path,
+        content: &[u8],
+        include_completed: bool,
+    ) -> Result<ForeignImport, AimError> {
+        let result = match self {
+            ForeignFormat::Todoist => todoist::read(content, include_completed),
+            ForeignFormat::TickTick => ticktick::read(content, include_completed),
+        };
+        result.map_err(|error| AimError::Parse {
+            file: source.to_path_buf(),
+            errors: vec![error],
+        })
+    }
+}
+
+/// Todos read from the export of another todo app.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForeignImport {
+    /// The todos, in the order of the export.
+    pub todos: Vec<ForeignTodo>,
+    /// What could not be mapped, one message per construct.
+    pub warnings: Vec<String>,
+}
+
+/// A todo read from the export of another todo app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignTodo {
+    /// UID derived from the ID in the export, so that importing it again skips it.
+    pub uid: String,
+    /// The summary of the todo.
+    pub summary: String,
+    /// The description of the todo, if any.
+    pub description: Option<String>,
+    /// When the todo is due, if ever.
+    pub due: Option<LooseDateTime>,
+    /// The priority of the todo.
+    pub priority: Priority,
+    /// The project or list of the todo, followed by its labels or tags.
+    pub categories: Vec<String>,
+    /// How the todo repeats, if it does.
+    pub rrule: Option<ValueRecurrenceRule>,
+    /// When the todo was completed, if it was.
+    pub completed: Option<Zoned>,
+}
+
+impl ForeignTodo {
+    /// Converts the todo into an aimcal-ical `VTodo` component.
+    pub(crate) fn into_ics(self, config: &Config, now: &Zoned) -> VTodo<String> {
+        let status = match self.completed {
+            Some(_) => TodoStatus::Completed,
+            None => TodoStatus::NeedsAction,
+        };
+        let alarm = match self.completed {
+            Some(_) => None,
+            None => self.due.as_ref().and(config.default_todo_alarm),
+        };
+        let draft = ResolvedTodoDraft {
+            alarm,
+            categories: &self.categories,
+            color: None,
+            description: self.description.as_deref(),
+            due: self.due.clone(),
+            estimate: None,
+            parent: None,
+            percent_complete: self.completed.as_ref().map(|_| 100),
+            priority: Some(self.priority),
+            status,
+            summary: &self.summary,
+            now,
+            recur_from: None,
+        };
+        let mut todo = draft.into_ics(&self.uid);
+        if let Some(completed) = &self.completed {
+            let utc = completed.with_time_zone(TimeZone::UTC);
+            todo.completed = Some(ical::Completed::new(utc.datetime()));
+        }
+        todo.rrule = self.rrule.map(|rule| RRule {
+            value: Box::new(rule),
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        });
+        todo
+    }
+}
+
+/// Parses iCalendar content made elsewhere, repairing the malformations common in files from the
+/// wild and logging each repair as a warning.
+///
+/// `source` names the content in the log and errors, which are the messages of the problems.
+pub(crate) fn parse_foreign<'src>(
+    source: &dyn Display,
+    content: &'src str,
+) -> Result<Vec<ICalendar<Segments<'src>>>, Vec<String>> {
+    let options = ParseOptions::new().compat(Compat::all());
+    let (calendars, diagnostics) = ical::parse_with_diagnostics_and_options(content, options);
+    for warning in diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+    {
+        tracing::warn!(%source, code = warning.code, "{}", warning.message);
+    }
+    calendars.ok_or_else(|| {
+        diagnostics
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.message)
+            .collect()
+    })
+}
+
+/// Parses iCalendar content made elsewhere as [`parse_foreign`] does, leaving out the components
+/// with errors instead of failing, see [`ical::parse_partial`].
+pub(crate) fn parse_foreign_partial<'src>(
+    source: &dyn Display,
+    content: &'src str,
+) -> Result<PartialParse<'src>, Vec<String>> {
+    let options = ParseOptions::new().compat(Compat::all());
+    let parsed = ical::parse_partial(content, options)
+        .map_err(|errors| errors.iter().map(ToString::to_string).collect::<Vec<_>>())?;
+    for warning in &parsed.warnings {
+        let warning = ical::Diagnostic::from_parse_error(content, warning);
+        tracing::warn!(%source, code = warning.code, "{}", warning.message);
+    }
+    Ok(parsed)
+}
+
+/// Parses the events and todos of iCalendar content, from every calendar it holds.
+///
+/// `source` names the content in errors, such as the path of the file it was read from.
+pub(crate) fn parse_components(
+    source: &Path,
+    content: &str,
+) -> Result<Vec<CalendarComponent<String>>, AimError> {
+    parse_calendars(source, content).map(|parsed| parsed.components)
+}
+
+/// The events and todos of iCalendar content, see [`parse_calendars`].
+pub(crate) struct ParsedCalendars {
+    pub(crate) components: Vec<CalendarComponent<String>>,
+    /// The dates of an expanded export of aim, from `X-AIM-EXPANDED`.
+    pub(crate) expanded: Option<String>,
+}
+
+/// Parses the events and todos of iCalendar content, noting whether it is an expanded export of
+/// aim.
+pub(crate) fn parse_calendars(source: &Path, content: &str) -> Result<ParsedCalendars, AimError> {
+    let parse_error = |errors: Vec<String>| AimError::Parse {
+        file: source.to_path_buf(),
+        errors,
+    };
+    let calendars = parse_foreign(&source.display(), content).map_err(parse_error)?;
+    if calendars.is_empty() {
+        return Err(parse_error(vec!["No calendars found".to_string()]));
+    }
+
+    let mut expanded = None;
+    let mut components = Vec::new();
+    for calendar in calendars {
+        let mut calendar = calendar.to_owned();
+        pin_floating_times(&mut calendar);
+        expanded = expanded.or_else(|| calendar.x_text(X_AIM_EXPANDED));
+        components.extend(
+            calendar
+                .components
+                .into_iter()
+                .filter(|c| matches!(c, CalendarComponent::Event(_) | CalendarComponent::Todo(_))),
+        );
+    }
+    Ok(ParsedCalendars {
+        components,
+        expanded,
+    })
+}
+
+/// Pins the floating times of the events and todos of a calendar to the time zone its publisher
+/// intends for them, as given by `X-WR-TIMEZONE`.
+pub(crate) fn pin_floating_times(calendar: &mut ICalendar<String>) {
+    let Some(tz_id) = calendar.timezone() else {
+        return;
+    };
+    let tz = match TimeZone::get(&tz_id) {
+        Ok(tz) => tz,
+        Err(e) => {
+            tracing::warn!(tzid = %tz_id, error = %e, "unknown calendar timezone, keeping floating times");
+            return;
+        }
+    };
+
+    let pin = |prop: &mut DateTimeProperty<String>| {
+        if pin_floating(&mut prop.value, &tz) {
+            prop.tz_id = Some(tz_id.clone());
+        }
+    };
+    for component in &mut calendar.components {
+        let (start, end, retained, rdates, ex_dates) = match component {
+            CalendarComponent::Event(event) => (
+                event.dt_start.as_mut(),
+                event.dt_end.as_deref_mut(),
+                &mut event.retained_properties,
+                &mut event.rdates,
+                &mut event.ex_dates,
+            ),
+            CalendarComponent::Todo(todo) => (
+                todo.dt_start.as_mut(),
+                todo.due.as_deref_mut(),
+                &mut todo.retained_properties,
+                &mut todo.rdates,
+                &mut todo.ex_dates,
+            ),
+            _ => continue,
+        };
+
+        if let Some(start) = start {
+            pin(start);
+        }
+        if let Some(end) = end {
+            pin(end);
+        }
+        for prop in retained {
+            if let Property::RecurrenceId(id) = prop {
+                pin(id);
+            }
+        }
+        for rdate in rdates.iter_mut().filter(|r| r.tz_id.is_none()) {
+            let mut pinned = false;
+            for date in &mut rdate.dates {
+                if let RDateValue::DateTime(dt) = date {
+                    pinned |= pin_floating(dt, &tz);
+                }
+            }
+            if pinned {
+                rdate.tz_id = Some(tz_id.clone());
+            }
+        }
+        for ex_date in ex_dates.iter_mut().filter(|e| e.tz_id.is_none()) {
+            let mut pinned = false;
+            for dt in &mut ex_date.dates {
+                pinned |= pin_floating(dt, &tz);
+            }
+            if pinned {
+                ex_date.tz_id = Some(tz_id.clone());
+            }
+        }
+    }
+}
+
+/// Turns a floating time into one in `tz`, returning whether it was floating.
+fn pin_floating(dt: &mut DateTime, tz: &TimeZone) -> bool {
+    let DateTime::Floating { date, time } = *dt else {
+        return false;
+    };
+    *dt = DateTime::Zoned {
+        date,
+        time,
+        tz_jiff: Some(tz.clone()),
+    };
+    true
+}
+
+/// Parses the one event of iCalendar content, ignoring overrides of its occurrences.
+pub(crate) fn parse_event(source: &Path, content: &str) -> Result<VEvent<String>, AimError> {
+    let mut events: Vec<_> = parse_components(source, content)?
+        .into_iter()
+        .filter_map(|c| match c {
+            CalendarComponent::Event(event) if !is_override(&event) => Some(event),
+            _ => None,
+        })
+        .collect();
+
+    match events.len() {
+        1 => Ok(events.remove(0)),
+        n => Err(AimError::Parse {
+            file: source.to_path_buf(),
+            errors: vec![format!("Expected a single VEVENT, found {n}")],
+        }),
+    }
+}
+
+/// Fills a draft with the summary, description, time and status of an event.
+pub(crate) fn fill_event_draft(draft: &mut EventDraft, event: &VEvent<String>) {
+    draft.summary = event.summary().into_owned();
+    draft.description = event.description().map(Cow::into_owned);
+    draft.start = event.start();
+    draft.end = event.end().or_else(|| {
+        let duration = signed_duration(&event.duration.as_ref()?.value);
+        Some(shifted(event.dt_start.as_ref()?.0.clone(), duration))
+    });
+    if let Some(status) = event.status() {
+        draft.status = status;
+    }
+}
+
+/// Converts an iCalendar duration, where a day is always 24 hours.
+pub(crate) fn signed_duration(duration: &ValueDuration) -> SignedDuration {
+    let (positive, seconds) = match *duration {
+        ValueDuration::DateTime {
+            positive,
+            day,
+            hour,
+            minute,
+            second,
+        } => (
+            positive,
+            i64::from(day) * 86400
+                + i64::from(hour) * 3600
+                + i64::from(minute) * 60
+                + i64::from(second),
+        ),
+        ValueDuration::Week { positive, week } => (positive, i64::from(week) * 7 * 86400),
+    };
+    SignedDuration::from_secs(if positive { seconds } else { -seconds })
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+    use crate::{EventStatus, LooseDateTime};
+
+    const CALENDAR: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Invite//EN\r
+METHOD:REQUEST\r
+BEGIN:VEVENT\r
+UID:invite@example.com\r
+DTSTAMP:20250110T120000Z\r
+DTSTART:20250115T100000\r
+DURATION:PT1H30M\r
+SUMMARY:Planning\r
+DESCRIPTION:Quarterly planning\r
+STATUS:TENTATIVE\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:todo@example.com\r
+DTSTAMP:20250110T120000Z\r
+SUMMARY:Prepare slides\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn parses_events_and_todos() {
+        let components = parse_components(Path::new("<stdin>"), CALENDAR).unwrap();
+        assert_eq!(components.len(), 2);
+        assert!(matches!(
+            components.first(),
+            Some(CalendarComponent::Event(_))
+        ));
+        assert!(matches!(
+            components.get(1),
+            Some(CalendarComponent::Todo(_))
+        ));
+    }
+
+    #[test]
+    fn pins_floating_times_to_calendar_timezone() {
+        let content = CALENDAR.replace(
+            "METHOD:REQUEST\r\n",
+            "METHOD:REQUEST\r\nX-WR-TIMEZONE:Asia/Tokyo\r\n",
+        );
+        let event = parse_event(Path::new("<stdin>"), &content).unwrap();
+
+        let tz = TimeZone::get("Asia/Tokyo").unwrap();
+        let start = date(2025, 1, 15).at(10, 0, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(event.start(), Some(LooseDateTime::Local(start)));
+        let dt_start = event.dt_start.as_ref().unwrap();
+        assert_eq!(dt_start.tz_id.as_deref(), Some("Asia/Tokyo"));
+    }
+
+    #[test]
+    fn repairs_malformed_content_from_the_wild() {
+        let content = CALENDAR
+            .replace(
+                "DTSTART:20250115T100000",
+                "DTSTART;TZID=\"(UTC+09:00) Osaka, Sapporo, Tokyo\":20250115T240000",
+            )
+            .replace("SUMMARY:Planning\r\n", "SUMMARY:Planning\r\n\r\n");
+        let event = parse_event(Path::new("<stdin>"), &content).unwrap();
+
+        let tz = TimeZone::get("Asia/Tokyo").unwrap();
+        let start = date(2025, 1, 16).at(0, 0, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(event.start(), Some(LooseDateTime::Local(start)));
+    }
+
+    #[test]
+    fn keeps_the_components_around_an_invalid_one() {
+        let content = CALENDAR.replace(
+            "BEGIN:VTODO\r\n",
+            "BEGIN:VEVENT\r\nUID:no-dtstamp@example.com\r\nEND:VEVENT\r\nBEGIN:VTODO\r\n",
+        );
+        let components = parse_components(Path::new("<stdin>"), &content).unwrap();
+        assert_eq!(components.len(), 2);
+    }
+
+    #[test]
+    fn reports_the_source_of_invalid_content() {
+        let err = parse_components(Path::new("<stdin>"), "not a calendar").unwrap_err();
+        assert!(err.to_string().contains("<stdin>"), "{err}");
+    }
+
+    #[test]
+    fn fills_draft_from_event() {
+        let event = parse_event(Path::new("<stdin>"), CALENDAR).unwrap();
+        let mut draft = EventDraft::default(&Zoned::now(), SignedDuration::from_hours(1));
+        fill_event_draft(&mut draft, &event);
+
+        assert_eq!(draft.summary, "Planning");
+        assert_eq!(draft.description.as_deref(), Some("Quarterly planning"));
+        assert_eq!(draft.status, EventStatus::Tentative);
+        let start = date(2025, 1, 15).at(10, 0, 0, 0);
+        assert_eq!(draft.start, Some(LooseDateTime::Floating(start)));
+        assert_eq!(
+            draft.end,
+            Some(LooseDateTime::Floating(date(2025, 1, 15).at(11, 30, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn rejects_several_events() {
+        let content = CALENDAR.replace(
+            "BEGIN:VTODO",
+            "BEGIN:VEVENT\r\nUID:other\r\nDTSTAMP:20250110T120000Z\r\nDTSTART:20250116T100000\r\nEND:VEVENT\r\nBEGIN:VTODO",
+        );
+        let err = parse_event(Path::new("<stdin>"), &content).unwrap_err();
+        assert!(err.to_string().contains("found 2"), "{err}");
+    }
+}
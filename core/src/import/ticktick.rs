@@ -0,0 +1,256 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mapping the CSV backup of `TickTick`, a few lines about the backup followed by a table of
+//! tasks with a header row.
+
+use aimcal_ical::ValueRecurrenceRule;
+use csv::StringRecord;
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+
+use crate::import::{ForeignImport, ForeignTodo};
+use crate::{LooseDateTime, Priority};
+
+/// First column of the header row of the task table.
+const HEADER_START: &str = "Folder Name";
+
+/// Columns of the task table.
+struct Columns(StringRecord);
+
+impl Columns {
+    fn get<'a>(&self, record: &'a StringRecord, name: &str) -> &'a str {
+        self.0
+            .iter()
+            .position(|c| c == name)
+            .and_then(|i| record.get(i))
+            .unwrap_or_default()
+            .trim()
+    }
+}
+
+/// Reads the todos of a backup.
+pub(super) fn read(content: &[u8], include_completed: bool) -> Result<ForeignImport, String> {
+    let invalid = |e: csv::Error| format!("Invalid TickTick backup: {e}");
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content);
+    let mut records = reader.records();
+
+    let mut columns = None;
+    for record in records.by_ref() {
+        let record = record.map_err(invalid)?;
+        if record.get(0).map(|c| c.trim_start_matches('\u{feff}')) == Some(HEADER_START) {
+            columns = Some(Columns(record));
+            break;
+        }
+    }
+    let columns = columns.ok_or("Invalid TickTick backup: no task table found")?;
+
+    let mut import = ForeignImport::default();
+    for record in records {
+        let record = record.map_err(invalid)?;
+        let get = |name| columns.get(&record, name);
+        let summary = get("Title").to_string();
+        if get("Kind").eq_ignore_ascii_case("note") {
+            import
+                .warnings
+                .push(format!("{summary}: note skipped, only tasks are imported"));
+            continue;
+        }
+
+        let tz = TimeZone::get(get("Timezone")).unwrap_or_else(|_| TimeZone::system());
+        let completed = match get("Status") {
+            "0" => None,
+            "1" | "2" if !include_completed => continue,
+            "1" | "2" => Some(
+                parse_time(get("Completed Time"))
+                    .map_or_else(Zoned::now, |ts| ts.to_zoned(tz.clone())),
+            ),
+            status => {
+                import.warnings.push(format!(
+                    "{summary}: unknown status {status:?}, imported as an open todo"
+                ));
+                None
+            }
+        };
+        if !get("parentId").is_empty() {
+            import
+                .warnings
+                .push(format!("{summary}: sub-task imported as a separate todo"));
+        }
+        if !get("Reminder").is_empty() {
+            import.warnings.push(format!(
+                "{summary}: reminder {:?} not imported",
+                get("Reminder")
+            ));
+        }
+
+        let due = match get("Due Date") {
+            "" => None,
+            due => {
+                let due = map_due(due, &tz, get("Is All Day"), get("Is Floating"));
+                if due.is_none() {
+                    import
+                        .warnings
+                        .push(format!("{summary}: invalid due date {:?}", get("Due Date")));
+                }
+                due
+            }
+        };
+        let rrule = match get("Repeat") {
+            "" => None,
+            repeat => {
+                let rrule = recurrence(repeat);
+                if rrule.is_none() {
+                    import.warnings.push(format!(
+                        "{summary}: repeat {repeat:?} is not supported, imported as a one-off todo"
+                    ));
+                }
+                rrule
+            }
+        };
+
+        let mut categories: Vec<String> = Some(get("List Name"))
+            .filter(|l| !l.is_empty())
+            .map(ToString::to_string)
+            .into_iter()
+            .collect();
+        categories.extend(
+            get("Tags")
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(ToString::to_string),
+        );
+
+        import.todos.push(ForeignTodo {
+            uid: format!("ticktick-{}", get("taskId")),
+            summary,
+            description: Some(get("Content").to_string()).filter(|c| !c.is_empty()),
+            due,
+            priority: priority(get("Priority")),
+            categories,
+            rrule,
+            completed,
+        });
+    }
+    Ok(import)
+}
+
+/// Priorities are none at 0, then low at 1, medium at 3 and high at 5.
+fn priority(priority: &str) -> Priority {
+    match priority {
+        "5" => Priority::P2,
+        "3" => Priority::P5,
+        "1" => Priority::P8,
+        _ => Priority::None,
+    }
+}
+
+/// Parses the times of a backup, such as `2025-01-15T10:00:00+0000`.
+fn parse_time(time: &str) -> Option<Timestamp> {
+    jiff::fmt::strtime::parse("%Y-%m-%dT%H:%M:%S%z", time)
+        .and_then(|t| t.to_timestamp())
+        .ok()
+}
+
+/// Maps a due, stored as the instant it falls at in the time zone of the task.
+fn map_due(due: &str, tz: &TimeZone, all_day: &str, floating: &str) -> Option<LooseDateTime> {
+    let due = parse_time(due)?.to_zoned(tz.clone());
+    Some(if all_day == "true" {
+        LooseDateTime::DateOnly(due.date())
+    } else if floating == "true" {
+        LooseDateTime::Floating(due.datetime())
+    } else {
+        LooseDateTime::Local(due)
+    })
+}
+
+/// Recurrences are kept as RRULEs, sometimes with extensions of their own that are not
+/// supported.
+fn recurrence(repeat: &str) -> Option<ValueRecurrenceRule> {
+    repeat.strip_prefix("RRULE:").unwrap_or(repeat).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+
+    const BACKUP: &str = "\
+\"Date: 2025-01-20+0000\"
+\"Version: 7.1\"
+\"Status:
+0 Normal
+1 Completed
+2 Archived\"
+\"Folder Name\",\"List Name\",\"Title\",\"Kind\",\"Tags\",\"Content\",\"Is Check list\",\"Start Date\",\"Due Date\",\"Reminder\",\"Repeat\",\"Priority\",\"Status\",\"Created Time\",\"Completed Time\",\"Order\",\"Timezone\",\"Is All Day\",\"Is Floating\",\"Column Name\",\"Column Order\",\"View Mode\",\"taskId\",\"parentId\"
+\"\",\"Work\",\"Write report\",\"TEXT\",\"writing, q1\",\"Quarterly numbers\",\"N\",\"\",\"2025-01-14T16:00:00+0000\",\"\",\"\",\"5\",\"0\",\"2025-01-01T08:00:00+0000\",\"\",\"1\",\"Asia/Shanghai\",\"true\",\"false\",\"\",\"\",\"list\",\"a1\",\"\"
+\"\",\"Home\",\"Water plants\",\"TEXT\",\"\",\"\",\"N\",\"\",\"2025-01-10T01:00:00+0000\",\"\",\"RRULE:FREQ=WEEKLY;INTERVAL=2\",\"0\",\"0\",\"2025-01-01T08:00:00+0000\",\"\",\"2\",\"Asia/Shanghai\",\"false\",\"false\",\"\",\"\",\"list\",\"a2\",\"\"
+\"\",\"Home\",\"Old task\",\"TEXT\",\"\",\"\",\"N\",\"\",\"\",\"\",\"\",\"1\",\"2\",\"2024-12-01T08:00:00+0000\",\"2024-12-20T10:00:00+0000\",\"3\",\"UTC\",\"false\",\"false\",\"\",\"\",\"list\",\"a3\",\"\"
+\"\",\"Home\",\"Ideas\",\"NOTE\",\"\",\"\",\"N\",\"\",\"\",\"\",\"\",\"0\",\"0\",\"2024-12-01T08:00:00+0000\",\"\",\"4\",\"UTC\",\"false\",\"false\",\"\",\"\",\"list\",\"a4\",\"\"
+\"\",\"Work\",\"Stand-up\",\"TEXT\",\"\",\"\",\"N\",\"\",\"2025-01-13T01:30:00+0000\",\"-PT0S\",\"RRULE:FREQ=WEEKLY;TT_SKIP=HOLIDAY\",\"3\",\"0\",\"2025-01-01T08:00:00+0000\",\"\",\"5\",\"Asia/Shanghai\",\"false\",\"true\",\"\",\"\",\"list\",\"a5\",\"\"
+";
+
+    #[test]
+    fn maps_tasks_of_a_backup() {
+        let import = read(BACKUP.as_bytes(), false).unwrap();
+        let uids: Vec<_> = import.todos.iter().map(|t| t.uid.as_str()).collect();
+        assert_eq!(uids, ["ticktick-a1", "ticktick-a2", "ticktick-a5"]);
+
+        let report = import.todos.first().unwrap();
+        assert_eq!(report.summary, "Write report");
+        assert_eq!(report.description.as_deref(), Some("Quarterly numbers"));
+        assert_eq!(report.priority, Priority::P2);
+        assert_eq!(report.categories, ["Work", "writing", "q1"]);
+        assert_eq!(report.due, Some(LooseDateTime::DateOnly(date(2025, 1, 15))));
+
+        let plants = import.todos.get(1).unwrap();
+        let tz = TimeZone::get("Asia/Shanghai").unwrap();
+        let due = date(2025, 1, 10).at(9, 0, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(plants.due, Some(LooseDateTime::Local(due)));
+        assert_eq!(plants.priority, Priority::None);
+        assert_eq!(plants.rrule, "FREQ=WEEKLY;INTERVAL=2".parse().ok());
+
+        let standup = import.todos.get(2).unwrap();
+        assert_eq!(
+            standup.due,
+            Some(LooseDateTime::Floating(date(2025, 1, 13).at(9, 30, 0, 0)))
+        );
+        assert_eq!(standup.priority, Priority::P5);
+        assert_eq!(standup.rrule, None);
+        assert_eq!(
+            import.warnings,
+            [
+                "Ideas: note skipped, only tasks are imported",
+                "Stand-up: reminder \"-PT0S\" not imported",
+                "Stand-up: repeat \"RRULE:FREQ=WEEKLY;TT_SKIP=HOLIDAY\" is not supported, imported as a one-off todo",
+            ]
+        );
+    }
+
+    #[test]
+    fn includes_completed_tasks_on_request() {
+        let import = read(BACKUP.as_bytes(), true).unwrap();
+        let old = import
+            .todos
+            .iter()
+            .find(|t| t.uid == "ticktick-a3")
+            .unwrap();
+        assert_eq!(old.priority, Priority::P8);
+        let completed: Timestamp = "2024-12-20T10:00:00Z".parse().unwrap();
+        assert_eq!(
+            old.completed.as_ref().map(Zoned::timestamp),
+            Some(completed)
+        );
+    }
+
+    #[test]
+    fn rejects_content_without_a_task_table() {
+        assert!(read(b"\"Date: 2025-01-20+0000\"\n", false).is_err());
+    }
+}
@@ -0,0 +1,427 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mapping the JSON export of Todoist, in the shape of its Sync API: `projects`, `items` and
+//! the `completed` history, given as the JSON file or a zip archive holding it.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use aimcal_ical::ValueRecurrenceRule;
+use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+use serde::de::Deserializer;
+
+use crate::import::{ForeignImport, ForeignTodo};
+use crate::{LooseDateTime, Priority, Repeat};
+
+#[derive(Debug, serde::Deserialize)]
+struct Export {
+    #[serde(default)]
+    projects: Vec<Project>,
+    #[serde(default)]
+    items: Vec<Item>,
+    #[serde(default, alias = "completed_items")]
+    completed: Vec<CompletedItem>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Project {
+    #[serde(deserialize_with = "id")]
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Item {
+    #[serde(deserialize_with = "id")]
+    id: String,
+    #[serde(default, deserialize_with = "optional_id")]
+    project_id: Option<String>,
+    #[serde(default, deserialize_with = "optional_id")]
+    parent_id: Option<String>,
+    content: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default = "lowest_priority")]
+    priority: u8,
+    #[serde(default)]
+    labels: Vec<String>,
+    due: Option<Due>,
+    #[serde(default)]
+    checked: bool,
+    completed_at: Option<String>,
+    #[serde(default)]
+    is_deleted: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Due {
+    date: String,
+    string: Option<String>,
+    #[serde(default)]
+    is_recurring: bool,
+    timezone: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CompletedItem {
+    #[serde(deserialize_with = "id", alias = "item_id")]
+    task_id: String,
+    #[serde(default, deserialize_with = "optional_id")]
+    project_id: Option<String>,
+    content: String,
+    completed_at: String,
+}
+
+fn lowest_priority() -> u8 {
+    1
+}
+
+/// IDs are strings in the current API and numbers in older exports.
+fn id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    optional_id(deserializer)?.ok_or_else(|| serde::de::Error::custom("missing id"))
+}
+
+fn optional_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match serde::Deserialize::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Some(s),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// Reads the todos of a Todoist export.
+pub(super) fn read(content: &[u8], include_completed: bool) -> Result<ForeignImport, String> {
+    let json = if content.starts_with(b"PK") {
+        unzip_json(content)?
+    } else {
+        content.to_vec()
+    };
+    let export: Export =
+        serde_json::from_slice(&json).map_err(|e| format!("Invalid Todoist export: {e}"))?;
+
+    let projects: HashMap<_, _> = export
+        .projects
+        .iter()
+        .map(|p| (p.id.as_str(), p.name.as_str()))
+        .collect();
+    let project_of = |id: Option<&String>| id.and_then(|id| projects.get(id.as_str()));
+
+    let mut import = ForeignImport::default();
+    for item in export.items.iter().filter(|i| !i.is_deleted) {
+        let completed = match (item.checked, &item.completed_at) {
+            (false, _) => None,
+            (true, _) if !include_completed => continue,
+            (true, at) => Some(completed_at(
+                at.as_deref(),
+                &item.content,
+                &mut import.warnings,
+            )),
+        };
+        if item.parent_id.is_some() {
+            import.warnings.push(format!(
+                "{}: sub-task imported as a separate todo",
+                item.content
+            ));
+        }
+
+        let mut categories: Vec<String> = project_of(item.project_id.as_ref())
+            .map(|name| (*name).to_string())
+            .into_iter()
+            .collect();
+        categories.extend(item.labels.iter().cloned());
+
+        let (due, rrule) = match &item.due {
+            Some(due) => map_due(due, &item.content, &mut import.warnings),
+            None => (None, None),
+        };
+        import.todos.push(ForeignTodo {
+            uid: uid(&item.id),
+            summary: item.content.clone(),
+            description: Some(item.description.clone()).filter(|d| !d.is_empty()),
+            due,
+            priority: priority(item.priority),
+            categories,
+            rrule,
+            completed,
+        });
+    }
+
+    if include_completed {
+        let imported: Vec<_> = import.todos.iter().map(|t| t.uid.clone()).collect();
+        for item in &export.completed {
+            let uid = uid(&item.task_id);
+            if imported.contains(&uid) {
+                continue;
+            }
+            let completed = completed_at(
+                Some(&item.completed_at),
+                &item.content,
+                &mut import.warnings,
+            );
+            import.todos.push(ForeignTodo {
+                uid,
+                summary: item.content.clone(),
+                description: None,
+                due: None,
+                priority: Priority::None,
+                categories: project_of(item.project_id.as_ref())
+                    .map(|name| (*name).to_string())
+                    .into_iter()
+                    .collect(),
+                rrule: None,
+                completed: Some(completed),
+            });
+        }
+    }
+    Ok(import)
+}
+
+/// Reads the JSON file of a zip archive.
+fn unzip_json(content: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = |e: zip::result::ZipError| format!("Invalid zip archive: {e}");
+    let mut archive = zip::ZipArchive::new(Cursor::new(content)).map_err(invalid)?;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(invalid)?;
+        let name = file.name().map_err(invalid)?.into_owned();
+        if file.is_file() && name.to_lowercase().ends_with(".json") {
+            let mut json = Vec::new();
+            file.read_to_end(&mut json)
+                .map_err(|e| format!("Failed to read {name}: {e}"))?;
+            return Ok(json);
+        }
+    }
+    Err("No JSON export found in the zip archive".to_string())
+}
+
+fn uid(id: &str) -> String {
+    format!("todoist-{id}")
+}
+
+/// Todoist ranks from 1 for normal to 4 for urgent, the other way around from RFC 5545, so
+/// they map to no priority and the mid, high and highest priorities of aim.
+fn priority(priority: u8) -> Priority {
+    match priority {
+        4 => Priority::P1,
+        3 => Priority::P2,
+        2 => Priority::P5,
+        _ => Priority::None,
+    }
+}
+
+/// When an item was completed, or now if the export does not tell.
+fn completed_at(at: Option<&str>, summary: &str, warnings: &mut Vec<String>) -> Zoned {
+    let Some(at) = at else {
+        return Zoned::now();
+    };
+    if let Ok(ts) = at.parse::<Timestamp>() {
+        ts.to_zoned(TimeZone::system())
+    } else {
+        warnings.push(format!(
+            "{summary}: invalid completion time {at:?}, completed now"
+        ));
+        Zoned::now()
+    }
+}
+
+/// Maps the due of an item, which is a date, a floating date and time, or a UTC time to be
+/// shown in the time zone of the due.
+fn map_due(
+    due: &Due,
+    summary: &str,
+    warnings: &mut Vec<String>,
+) -> (Option<LooseDateTime>, Option<ValueRecurrenceRule>) {
+    let date = if let Ok(ts) = due.date.parse::<Timestamp>() {
+        let tz = due
+            .timezone
+            .as_deref()
+            .and_then(|tz| TimeZone::get(tz).ok())
+            .unwrap_or(TimeZone::UTC);
+        Some(LooseDateTime::Local(ts.to_zoned(tz)))
+    } else if !due.date.contains('T')
+        && let Ok(date) = due.date.parse::<Date>()
+    {
+        Some(LooseDateTime::DateOnly(date))
+    } else if let Ok(dt) = due.date.parse::<DateTime>() {
+        Some(LooseDateTime::Floating(dt))
+    } else {
+        warnings.push(format!("{summary}: invalid due date {:?}", due.date));
+        None
+    };
+
+    let rrule = match (&due.string, due.is_recurring) {
+        (Some(string), true) => {
+            let rrule = recurrence(string);
+            if rrule.is_none() {
+                warnings.push(format!(
+                    "{summary}: recurring due {string:?} is not supported, imported as a one-off todo"
+                ));
+            }
+            rrule
+        }
+        _ => None,
+    };
+    (date, rrule)
+}
+
+/// Maps the common recurring due strings, such as `every day`, `every monday` or
+/// `every 2 weeks`, ignoring the time of day they may end with.
+fn recurrence(string: &str) -> Option<ValueRecurrenceRule> {
+    let string = string.trim().to_lowercase();
+    let string = string.split(" at ").next().unwrap_or_default();
+    string.parse::<Repeat>().ok().map(|repeat| repeat.rule())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use jiff::civil::date;
+
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "projects": [{"id": "p1", "name": "Work"}, {"id": 7, "name": "Home"}],
+        "items": [
+            {
+                "id": "1", "project_id": "p1", "content": "Write report",
+                "description": "Quarterly numbers", "priority": 4, "labels": ["writing"],
+                "due": {"date": "2025-01-15", "string": "Jan 15", "is_recurring": false}
+            },
+            {
+                "id": "2", "project_id": 7, "content": "Water plants", "priority": 1,
+                "due": {"date": "2025-01-10T09:00:00", "string": "every 2 weeks at 9am", "is_recurring": true}
+            },
+            {
+                "id": "3", "project_id": "p1", "content": "Stand-up", "priority": 2,
+                "due": {
+                    "date": "2025-01-13T01:30:00Z", "timezone": "Asia/Shanghai",
+                    "string": "every 3rd friday", "is_recurring": true
+                }
+            },
+            {
+                "id": "4", "content": "Old task", "checked": true,
+                "completed_at": "2025-01-02T08:00:00Z"
+            },
+            {"id": "5", "content": "Gone", "is_deleted": true}
+        ],
+        "completed": [
+            {"task_id": "9", "project_id": "p1", "content": "Shipped", "completed_at": "2024-12-20T10:00:00Z"},
+            {"task_id": "4", "content": "Old task", "completed_at": "2025-01-02T08:00:00Z"}
+        ]
+    }"#;
+
+    #[test]
+    fn maps_items_of_an_export() {
+        let import = read(EXPORT.as_bytes(), false).unwrap();
+        let uids: Vec<_> = import.todos.iter().map(|t| t.uid.as_str()).collect();
+        assert_eq!(uids, ["todoist-1", "todoist-2", "todoist-3"]);
+
+        let report = import.todos.first().unwrap();
+        assert_eq!(report.summary, "Write report");
+        assert_eq!(report.description.as_deref(), Some("Quarterly numbers"));
+        assert_eq!(report.priority, Priority::P1);
+        assert_eq!(report.categories, ["Work", "writing"]);
+        assert_eq!(report.due, Some(LooseDateTime::DateOnly(date(2025, 1, 15))));
+        assert_eq!(report.rrule, None);
+        assert_eq!(report.completed, None);
+
+        let plants = import.todos.get(1).unwrap();
+        assert_eq!(plants.categories, ["Home"]);
+        assert_eq!(plants.priority, Priority::None);
+        assert_eq!(
+            plants.due,
+            Some(LooseDateTime::Floating(date(2025, 1, 10).at(9, 0, 0, 0)))
+        );
+        assert_eq!(plants.rrule, "FREQ=WEEKLY;INTERVAL=2".parse().ok());
+
+        let standup = import.todos.get(2).unwrap();
+        let tz = TimeZone::get("Asia/Shanghai").unwrap();
+        let due = date(2025, 1, 13).at(9, 30, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(standup.due, Some(LooseDateTime::Local(due)));
+        assert_eq!(standup.priority, Priority::P5);
+        assert_eq!(standup.rrule, None);
+        assert_eq!(
+            import.warnings,
+            [
+                "Stand-up: recurring due \"every 3rd friday\" is not supported, imported as a one-off todo"
+            ]
+        );
+    }
+
+    #[test]
+    fn includes_completed_items_on_request() {
+        let import = read(EXPORT.as_bytes(), true).unwrap();
+        let uids: Vec<_> = import.todos.iter().map(|t| t.uid.as_str()).collect();
+        assert_eq!(
+            uids,
+            [
+                "todoist-1",
+                "todoist-2",
+                "todoist-3",
+                "todoist-4",
+                "todoist-9"
+            ]
+        );
+
+        let shipped = import.todos.last().unwrap();
+        assert_eq!(shipped.categories, ["Work"]);
+        let completed: Timestamp = "2024-12-20T10:00:00Z".parse().unwrap();
+        assert_eq!(
+            shipped.completed.as_ref().map(Zoned::timestamp),
+            Some(completed)
+        );
+    }
+
+    #[test]
+    fn reads_the_json_of_a_zip_archive() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("todoist.json", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(EXPORT.as_bytes()).unwrap();
+        let archive = zip.finish().unwrap().into_inner();
+
+        let import = read(&archive, false).unwrap();
+        assert_eq!(import.todos.len(), 3);
+    }
+
+    #[test]
+    fn rejects_invalid_exports() {
+        assert!(read(b"not json", false).is_err());
+        assert!(read(b"PK\x03\x04broken", false).is_err());
+    }
+
+    #[test]
+    fn maps_common_recurring_due_strings() {
+        let rule = |s: &str| recurrence(s).map(|r| r.to_string());
+        assert_eq!(rule("every day").as_deref(), Some("FREQ=DAILY"));
+        assert_eq!(
+            rule("Every Monday").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO")
+        );
+        assert_eq!(
+            rule("every 2 weeks").as_deref(),
+            Some("FREQ=WEEKLY;INTERVAL=2")
+        );
+        assert_eq!(
+            rule("every other month").as_deref(),
+            Some("FREQ=MONTHLY;INTERVAL=2")
+        );
+        assert_eq!(rule("every year").as_deref(), Some("FREQ=YEARLY"));
+        assert_eq!(
+            rule("every mon, wed and fri at 8am").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,WE,FR")
+        );
+        assert_eq!(
+            rule("every weekday").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")
+        );
+        assert_eq!(rule("daily").as_deref(), Some("FREQ=DAILY"));
+        assert_eq!(rule("every! day"), None);
+        assert_eq!(rule("every last day"), None);
+        assert_eq!(rule("every 2 mondays"), None);
+    }
+}
@@ -0,0 +1,111 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scheduling messages delivered to the user, such as meeting invitations (RFC 6638).
+
+use aimcal_ical::{CalendarComponent, ICalendar};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+
+use crate::details::describe_address;
+use crate::series::is_override;
+use crate::{Event, LooseDateTime};
+
+/// A scheduling message waiting in the inbox of a calendar server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invitation {
+    /// Calendar whose server delivered the message.
+    pub calendar_id: String,
+    /// The iTIP method, such as `REQUEST` for an invitation, `CANCEL` or `REPLY`.
+    pub method: Option<String>,
+    /// UID of the event the message is about.
+    pub uid: String,
+    /// Summary of the event.
+    pub summary: String,
+    /// Who sent the invitation.
+    pub organizer: Option<String>,
+    /// When the event starts.
+    pub start: Option<LooseDateTime>,
+    /// When the message was created.
+    pub sent: Zoned,
+}
+
+impl Invitation {
+    /// Reads the invitations of a scheduling message, one per event apart from overrides of
+    /// its occurrences.
+    pub(crate) fn from_message(calendar_id: &str, message: &ICalendar<String>) -> Vec<Self> {
+        let method = message.method.as_ref().map(|m| m.value.to_string());
+        message
+            .components
+            .iter()
+            .filter_map(|c| match c {
+                CalendarComponent::Event(event) if !is_override(event) => Some(Invitation {
+                    calendar_id: calendar_id.to_string(),
+                    method: method.clone(),
+                    uid: event.uid().into_owned(),
+                    summary: event.summary().into_owned(),
+                    organizer: event
+                        .organizer
+                        .as_ref()
+                        .map(|o| describe_address(&o.cal_address, o.cn.as_deref())),
+                    start: event.start(),
+                    // Stamps in the last hours of year 9999 are past the range of timestamps
+                    sent: event
+                        .dt_stamp
+                        .zoned()
+                        .unwrap_or_else(|| Timestamp::MAX.to_zoned(TimeZone::UTC)),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+
+    #[test]
+    fn reads_invitations_of_a_request() {
+        let content = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Server//EN\r
+METHOD:REQUEST\r
+BEGIN:VEVENT\r
+UID:meeting@example.com\r
+DTSTAMP:20250108T120000Z\r
+DTSTART:20250110T090000\r
+SUMMARY:Planning\r
+ORGANIZER;CN=Alice:mailto:alice@example.com\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:meeting@example.com\r
+RECURRENCE-ID:20250117T090000\r
+DTSTAMP:20250108T120000Z\r
+DTSTART:20250117T100000\r
+SUMMARY:Planning\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let calendar = aimcal_ical::parse(content).unwrap().remove(0).to_owned();
+        let invitations = Invitation::from_message("work", &calendar);
+
+        assert_eq!(invitations.len(), 1);
+        let invitation = invitations.first().unwrap();
+        assert_eq!(invitation.method.as_deref(), Some("REQUEST"));
+        assert_eq!(invitation.uid, "meeting@example.com");
+        assert_eq!(invitation.summary, "Planning");
+        assert_eq!(
+            invitation.organizer.as_deref(),
+            Some("Alice <alice@example.com>")
+        );
+        assert_eq!(
+            invitation.start,
+            Some(LooseDateTime::Floating(date(2025, 1, 10).at(9, 0, 0, 0)))
+        );
+    }
+}
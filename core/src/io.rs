@@ -2,12 +2,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::error::Error;
 use std::path::{Path, PathBuf};
 
 use aimcal_ical::{CalendarComponent, ICalendar, fmt::format, parse};
 use tokio::fs;
 
+use crate::AimError;
 use crate::db::Db;
 
 /// Add ICS files from calendar directory to database.
@@ -16,7 +16,7 @@ use crate::db::Db;
 pub async fn add_calendar_if_enabled(
     db: &Db,
     calendar_path: Option<&PathBuf>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), AimError> {
     let Some(path) = calendar_path else {
         tracing::info!("calendar_path not configured, skipping ICS import");
         return Ok(());
@@ -34,10 +34,8 @@ pub async fn add_calendar_if_enabled(
 }
 
 #[tracing::instrument(skip(db))]
-async fn add_calendar(db: &Db, calendar_path: &PathBuf) -> Result<(), Box<dyn Error>> {
-    let mut reader = fs::read_dir(calendar_path)
-        .await
-        .map_err(|e| format!("Failed to read directory: {e}"))?;
+async fn add_calendar(db: &Db, calendar_path: &PathBuf) -> Result<(), AimError> {
+    let mut reader = fs::read_dir(calendar_path).await?;
 
     let mut handles = vec![];
     while let Some(entry) = reader.next_entry().await? {
@@ -47,7 +45,7 @@ async fn add_calendar(db: &Db, calendar_path: &PathBuf) -> Result<(), Box<dyn Er
                 let db = db.clone();
                 handles.push(tokio::spawn(async move {
                     if let Err(err) = add_ics(db, &path).await {
-                        tracing::error!(path = %path.display(), err, "failed to process file");
+                        tracing::error!(path = %path.display(), err = %err, "failed to process file");
                     }
                 }));
             }
@@ -57,7 +55,9 @@ async fn add_calendar(db: &Db, calendar_path: &PathBuf) -> Result<(), Box<dyn Er
 
     let count = handles.len();
     for handle in handles {
-        handle.await?;
+        handle
+            .await
+            .map_err(|e| AimError::Other(format!("Failed to process file: {e}")))?;
     }
 
     tracing::debug!(count = count, "total .ics files processed");
@@ -65,31 +65,31 @@ async fn add_calendar(db: &Db, calendar_path: &PathBuf) -> Result<(), Box<dyn Er
 }
 
 // TODO: support multiple calendars in one file
-pub async fn parse_ics(path: &Path) -> Result<ICalendar<String>, Box<dyn Error>> {
-    let content = fs::read_to_string(path)
-        .await
-        .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
+pub async fn parse_ics(path: &Path) -> Result<ICalendar<String>, AimError> {
+    let content = fs::read_to_string(path).await?;
+    let parse_error = |errors| AimError::Parse {
+        file: path.to_path_buf(),
+        errors,
+    };
 
-    let calendars = parse(&content)
-        .map_err(|e| -> Box<dyn Error> { format!("Failed to parse calendar: {e:?}").into() })?;
-
-    if calendars.is_empty() {
-        return Err("No calendars found in file".into());
-    }
+    let calendars = parse(&content).map_err(|e| parse_error(vec![format!("{e:?}")]))?;
 
     // Hybrid: parse borrowed, convert to owned for storage
-    Ok(calendars.into_iter().next().unwrap().to_owned())
+    let Some(calendar) = calendars.into_iter().next() else {
+        return Err(parse_error(vec!["No calendars found in file".to_string()]));
+    };
+    Ok(calendar.to_owned())
 }
 
-pub async fn write_ics(path: &Path, calendar: &ICalendar<String>) -> Result<(), String> {
-    let ics_content = format(calendar).map_err(|e| format!("Failed to format calendar: {e}"))?;
+pub async fn write_ics(path: &Path, calendar: &ICalendar<String>) -> Result<(), AimError> {
+    let ics_content =
+        format(calendar).map_err(|e| AimError::Other(format!("Failed to format calendar: {e}")))?;
 
-    fs::write(path, ics_content)
-        .await
-        .map_err(|e| format!("Failed to write calendar file: {e}"))
+    fs::write(path, ics_content).await?;
+    Ok(())
 }
 
-async fn add_ics(db: Db, path: &Path) -> Result<(), Box<dyn Error>> {
+async fn add_ics(db: Db, path: &Path) -> Result<(), AimError> {
     tracing::debug!(path = %path.display(), "parsing file");
     let calendar = parse_ics(path).await?;
 
@@ -20,23 +20,100 @@
 )]
 
 mod aim;
+mod alarm;
+mod backup;
+mod category;
+mod collation;
 mod config;
+mod crypt;
 mod datetime;
 mod db;
+mod dedupe;
+mod details;
+mod error;
 mod event;
+mod export;
+mod file;
+mod home_zone;
+mod housekeeping;
+mod import;
+mod invitation;
+mod lock;
+mod markdown;
+mod meta;
+mod overdue;
+mod plan;
+mod profile;
+mod recurrence;
+mod resolve;
+mod rules;
+mod series;
 mod short_id;
+mod smart_list;
+mod staging;
 mod store;
+mod template;
 mod todo;
 mod types;
+mod working_hours;
 
-pub use crate::aim::{Aim, CalendarDetails, CalendarStoreDetails};
+pub use crate::aim::{
+    Aim, AimBuilder, CalendarDetails, CalendarStoreDetails, Transaction, TransactionFuture,
+};
+pub use crate::alarm::AlarmTrigger;
+pub use crate::backup::{BACKUP_FORMAT_VERSION, BackupCalendar, BackupFile, BackupManifest};
+pub use crate::category::{Category, CategoryDefaults};
+pub use crate::collation::Collation;
 pub use crate::config::{APP_NAME, CalendarEntry, Config, StoreDef};
-pub use crate::db::calendars::CalendarRecord;
-pub use crate::store::{CaldavStore, LocalStore, Store, StoreError, SyncResult}; // TODO: don't export this directly
+pub use crate::crypt::{ENCRYPTED_NO_KEY, EncryptionConfig, RekeyReport, generate_key};
+pub use crate::db::calendars::{CalendarPrivileges, CalendarRecord};
+pub use crate::dedupe::{DedupeMode, DedupedEvent};
+pub use crate::details::ItemDetails;
+pub use crate::error::AimError;
+pub use crate::export::{CalendarExport, DEFAULT_EXPAND_LIMIT};
+pub use crate::file::{rename_with_retry, sync_folder, write_atomic};
+pub use crate::home_zone::{ZoneRebase, ZoneShift};
+pub use crate::housekeeping::{
+    CacheStats, HOUSEKEEPING_INTERVAL, HousekeepingConfig, HousekeepingReport, RetentionAge,
+};
+pub use crate::import::{ForeignFormat, ForeignImport, ForeignTodo, ImportReport};
+pub use crate::invitation::Invitation;
+pub use crate::markdown::{MarkdownConflict, MarkdownConflictKind, MarkdownSyncReport};
+pub use crate::meta::{MAX_META_VALUE_LEN, normalize_meta_key};
+pub use crate::overdue::{OverdueConfig, overdue_age};
+pub use crate::plan::{
+    BlockRelease, DayLoad, LoadLevel, PLAN_PREFIX, Plan, PlanOptions, PlannedBlock, Unplanned,
+    UnplannedReason,
+};
+pub use crate::profile::{Profile, ProfileDirs};
+pub use crate::recurrence::{Repeat, RepeatEnd, describe_recurrence};
+pub use crate::resolve::{IdCandidate, IdMatch, MIN_UID_PREFIX_LEN};
+pub use crate::rules::{
+    Rule, RuleActions, RuleConditions, RuleOutcome, RuleSubject, RuleTest, RuleTrace, RuleVerdict,
+    Rules, RulesReport,
+};
+pub use crate::store::{
+    BrokenItem, CaldavStore, LocalStore, PulledChange, Store, StoreCapabilities, StoreError,
+    StoreResource, SubscriptionConfig, SubscriptionStore, SyncResult,
+}; // TODO: don't export this directly
 
 // Re-export AuthMethod for use in config
-pub use crate::datetime::{DateTimeAnchor, LooseDateTime, RangePosition};
-pub use crate::event::{Event, EventConditions, EventDraft, EventPatch, EventStatus};
-pub use crate::todo::{Todo, TodoConditions, TodoDraft, TodoPatch, TodoSort, TodoStatus};
+pub use crate::datetime::{
+    DateRangeAnchor, DateTimeAnchor, LooseDateTime, RangePosition, add_business_days,
+    parse_signed_duration,
+};
+pub use crate::event::{
+    AllDayPolicy, Attendance, CheckIn, Event, EventConditions, EventDraft, EventPatch, EventStatus,
+    RecurrenceDateEdit,
+};
+pub use crate::series::ThisAndFuture;
+pub use crate::smart_list::{SmartList, SmartListQuery, SmartLists};
+pub use crate::staging::{SyncChange, SyncDirection, SyncOperation, SyncPlan};
+pub use crate::template::{DueOffset, ExpandedTodo, Template, TemplateItem, Templates};
+pub use crate::todo::{
+    RecurFrom, SubtaskProgress, Todo, TodoConditions, TodoDraft, TodoPatch, TodoSort, TodoStatus,
+    Waiting,
+};
 pub use crate::types::{Id, Kind, Pager, Priority, SortOrder, StoreKind};
+pub use crate::working_hours::{BusinessDays, TimeRange, WorkingHours};
 pub use aimcal_caldav::AuthMethod;
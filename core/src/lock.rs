@@ -0,0 +1,325 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! The lock of the state directory, keeping two aim processes from writing to it at once.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use jiff::Timestamp;
+
+use crate::AimError;
+
+/// Name of the lock file in the state directory.
+pub(crate) const LOCK_FILE_NAME: &str = "aim.lock";
+
+/// How long to wait for another aim to release the state directory.
+pub(crate) const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the lock is tried again while waiting.
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Locks held by this process, by state directory, shared by the instances of [`Aim`](crate::Aim)
+/// in the process.
+static HELD: LazyLock<Mutex<HashMap<PathBuf, Weak<Held>>>> = LazyLock::new(Mutex::default);
+
+/// An exclusive lock of the state directory, released when the last clone is dropped.
+///
+/// The lock is an OS lock of the lock file, which goes away with the process holding it, and the
+/// file records the PID and host of the holder to tell who has it. Once the OS lock is acquired,
+/// the record is overwritten whatever it says, as it may be left by a crash or synced from another
+/// host. Only where the OS lock is unsupported, a lock file naming another running process keeps
+/// the directory locked, while one naming a process that is gone is stale and taken over.
+#[derive(Debug, Clone)]
+pub(crate) struct DataDirLock {
+    _held: Arc<Held>,
+}
+
+#[derive(Debug)]
+struct Held {
+    path: PathBuf,
+    file: File,
+}
+
+impl DataDirLock {
+    /// Locks the state directory `dir`, waiting up to `timeout` for another aim to release it.
+    ///
+    /// # Errors
+    /// [`AimError::Locked`] if another aim keeps the lock, or an I/O error on the lock file.
+    pub(crate) fn acquire(dir: &Path, timeout: Duration) -> Result<Self, AimError> {
+        fs::create_dir_all(dir)?;
+        let path = dir.canonicalize()?.join(LOCK_FILE_NAME);
+
+        let mut held = HELD
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(lock) = held.get(&path).and_then(Weak::upgrade) {
+            return Ok(Self { _held: lock });
+        }
+
+        let deadline = Instant::now() + timeout;
+        let lock = loop {
+            match try_acquire(&path)? {
+                Ok(lock) => break Arc::new(lock),
+                Err(holder) if Instant::now() >= deadline => {
+                    return Err(AimError::Locked { path, holder });
+                }
+                Err(holder) => {
+                    tracing::debug!(path = %path.display(), %holder, "waiting for the lock");
+                    thread::sleep(RETRY_INTERVAL);
+                }
+            }
+        };
+        held.retain(|_, lock| lock.strong_count() > 0);
+        held.insert(path, Arc::downgrade(&lock));
+        Ok(Self { _held: lock })
+    }
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        // Clear the holder first, so the file left after the OS lock is released is not stale
+        if let Err(e) = self.file.set_len(0) {
+            tracing::warn!(path = %self.path.display(), err = %e, "failed to release the lock");
+        }
+    }
+}
+
+/// Tries to lock once, giving the holder of the lock if another process has it.
+fn try_acquire(path: &Path) -> io::Result<Result<Held, String>> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    let os_locked = match file.try_lock() {
+        Ok(()) => true,
+        Err(TryLockError::WouldBlock) => {
+            let holder = Holder::read(&mut file)
+                .map_or_else(|| "another aim".to_string(), |holder| holder.to_string());
+            return Ok(Err(holder));
+        }
+        Err(TryLockError::Error(e)) => {
+            tracing::debug!(path = %path.display(), err = %e, "file locking is unsupported");
+            false
+        }
+    };
+
+    // Without the OS lock, the holder recorded is all that keeps two processes apart
+    if !os_locked && let Some(holder) = Holder::read(&mut file) {
+        if holder.is_alive() {
+            return Ok(Err(holder.to_string()));
+        }
+        tracing::warn!(%holder, "taking over a stale lock");
+    }
+
+    let holder = Holder {
+        pid: process::id(),
+        host: host_name(),
+        since: Timestamp::now(),
+    };
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(holder.encode().as_bytes())?;
+    file.sync_all()?;
+    Ok(Ok(Held {
+        path: path.to_path_buf(),
+        file,
+    }))
+}
+
+/// The process holding the lock, as recorded in the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Holder {
+    pid: u32,
+    host: String,
+    since: Timestamp,
+}
+
+impl Holder {
+    fn read(file: &mut File) -> Option<Self> {
+        let mut content = String::new();
+        file.rewind().ok()?;
+        file.read_to_string(&mut content).ok()?;
+        Self::decode(&content)
+    }
+
+    fn encode(&self) -> String {
+        format!(
+            "pid {}\nhost {}\nsince {}\n",
+            self.pid, self.host, self.since
+        )
+    }
+
+    fn decode(content: &str) -> Option<Self> {
+        let mut fields: HashMap<_, _> = content
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .collect();
+        Some(Self {
+            pid: fields.remove("pid")?.parse().ok()?,
+            host: fields.remove("host")?.to_string(),
+            since: fields.remove("since")?.parse().ok()?,
+        })
+    }
+
+    /// Whether the holder may still be running. A process on another host cannot be checked, so
+    /// it is taken to be.
+    fn is_alive(&self) -> bool {
+        if self.host != host_name() {
+            return true;
+        }
+        self.pid != process::id() && is_running(self.pid)
+    }
+}
+
+impl std::fmt::Display for Holder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "aim (PID {} on {}, since {})",
+            self.pid, self.host, self.since
+        )
+    }
+}
+
+/// The name of this host, to tell the lock files of processes elsewhere apart.
+fn host_name() -> String {
+    let from_env = || {
+        ["COMPUTERNAME", "HOSTNAME"]
+            .into_iter()
+            .find_map(|var| std::env::var(var).ok())
+    };
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(from_env)
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn is_running(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_running(pid: u32) -> bool {
+    process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn is_running(pid: u32) -> bool {
+    let filter = format!("PID eq {pid}");
+    process::Command::new("tasklist")
+        .args(["/FI", &filter, "/NH", "/FO", "CSV"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{pid}\"")))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_running(_: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(pid: u32) -> Holder {
+        Holder {
+            pid,
+            host: host_name(),
+            since: Timestamp::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn shares_the_lock_within_the_process_and_clears_it_on_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = DataDirLock::acquire(dir.path(), Duration::ZERO).unwrap();
+        let second = DataDirLock::acquire(dir.path(), Duration::ZERO).unwrap();
+
+        let path = dir.path().join(LOCK_FILE_NAME);
+        let content = fs::read_to_string(&path).unwrap();
+        let recorded = Holder::decode(&content).unwrap();
+        assert_eq!(recorded.pid, process::id());
+
+        drop(first);
+        assert!(!fs::read_to_string(&path).unwrap().is_empty());
+        drop(second);
+        assert!(fs::read_to_string(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn round_trips_the_holder() {
+        let holder = holder(42);
+        assert_eq!(Holder::decode(&holder.encode()), Some(holder));
+        assert_eq!(Holder::decode("garbage"), None);
+        assert_eq!(Holder::decode(""), None);
+    }
+
+    #[test]
+    fn takes_holders_elsewhere_for_alive() {
+        let mut holder = holder(u32::MAX);
+        assert!(!holder.is_alive());
+        holder.host = format!("{}-elsewhere", host_name());
+        assert!(holder.is_alive());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn trusts_the_os_lock_over_the_holder_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+
+        // Left by a process whose PID is in use again, or synced from another host
+        let mut child = process::Command::new("sleep").arg("30").spawn().unwrap();
+        let mut elsewhere = holder(child.id());
+        elsewhere.host = format!("{}-elsewhere", host_name());
+        for recorded in [holder(child.id()), elsewhere] {
+            fs::write(&path, recorded.encode()).unwrap();
+            let lock = DataDirLock::acquire(dir.path(), Duration::ZERO).unwrap();
+            let content = fs::read_to_string(&path).unwrap();
+            assert_eq!(Holder::decode(&content).unwrap().pid, process::id());
+            drop(lock);
+        }
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn refuses_the_lock_held_by_another_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+        let other = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .unwrap();
+        if other.try_lock().is_err() {
+            return; // File locking is unsupported here
+        }
+        fs::write(&path, holder(42).encode()).unwrap();
+
+        let err = DataDirLock::acquire(dir.path(), Duration::ZERO).unwrap_err();
+        assert!(
+            matches!(&err, AimError::Locked { holder, .. } if holder.contains("PID 42")),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,753 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Two-way mirroring of todos with Markdown task lists.
+//!
+//! Every `- [ ] task` line synced into AIM is linked to its todo through a resource under
+//! [`RESOURCE_CALENDAR`], whose id is the file path and a line anchor derived from the task
+//! text. Links remember the text, checkbox and line seen at the last sync, so that edited or
+//! moved tasks can be matched back to their todo instead of being created again.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Db;
+use crate::error::AimError;
+use crate::file::write_atomic;
+use crate::{Aim, Id, Todo, TodoPatch, TodoStatus};
+
+/// Pseudo calendar id under which Markdown links are stored in the resources table.
+pub(crate) const RESOURCE_CALENDAR: &str = "@markdown";
+
+/// Minimum similarity for an edited task to be matched to its previous text.
+const MIN_SIMILARITY: f64 = 0.6;
+
+/// Candidates scoring within this margin of the best match make it ambiguous.
+const AMBIGUITY_MARGIN: f64 = 0.05;
+
+/// Outcome of syncing a notes directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownSyncReport {
+    /// Number of notes files scanned.
+    pub files: usize,
+    /// Todos created for new unchecked tasks.
+    pub created: usize,
+    /// Todos completed because their task was checked in the file.
+    pub completed: usize,
+    /// Todos reopened because their task was unchecked in the file.
+    pub reopened: usize,
+    /// Checkboxes checked because their todo was completed in AIM.
+    pub checked: usize,
+    /// Checkboxes unchecked because their todo was reopened in AIM.
+    pub unchecked: usize,
+    /// Tasks that could not be matched with certainty.
+    pub conflicts: Vec<MarkdownConflict>,
+}
+
+/// A task that could not be mirrored with certainty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownConflict {
+    /// The notes file containing the task.
+    pub path: PathBuf,
+    /// 1-based line of the task, or of its last known position if it is gone.
+    pub line: usize,
+    /// What went wrong.
+    pub kind: MarkdownConflictKind,
+}
+
+/// The kind of a [`MarkdownConflict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownConflictKind {
+    /// The task text changed and was matched to its todo by similarity; the todo summary was
+    /// updated to the new text.
+    Edited {
+        /// Text at the last sync.
+        from: String,
+        /// Text in the file now.
+        to: String,
+    },
+    /// Several tasks resemble a linked task equally well, so none was linked and no todo was
+    /// created for them.
+    Ambiguous {
+        /// Text at the last sync.
+        text: String,
+        /// 1-based lines of the candidate tasks.
+        candidates: Vec<usize>,
+    },
+    /// The linked task is no longer in the file; the todo was kept and unlinked.
+    Missing {
+        /// Text at the last sync.
+        text: String,
+    },
+    /// The linked todo no longer exists in AIM; the task was kept and unlinked.
+    TodoDeleted {
+        /// Text of the task.
+        text: String,
+    },
+}
+
+impl fmt::Display for MarkdownConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.path.display(), self.line)?;
+        match &self.kind {
+            MarkdownConflictKind::Edited { from, to } => {
+                write!(f, "task text changed from {from:?} to {to:?}")
+            }
+            MarkdownConflictKind::Ambiguous { text, candidates } => {
+                let lines: Vec<_> = candidates.iter().map(ToString::to_string).collect();
+                write!(
+                    f,
+                    "task {text:?} matches lines {} equally well, left unlinked",
+                    lines.join(", ")
+                )
+            }
+            MarkdownConflictKind::Missing { text } => {
+                write!(f, "task {text:?} was removed, its todo is kept")
+            }
+            MarkdownConflictKind::TodoDeleted { text } => {
+                write!(
+                    f,
+                    "todo for task {text:?} was deleted, the task is unlinked"
+                )
+            }
+        }
+    }
+}
+
+/// A `- [ ] text` line in a Markdown file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MarkdownTask {
+    /// 0-based line number.
+    pub line: usize,
+    pub checked: bool,
+    pub text: String,
+}
+
+/// State of a task at the last sync, stored as resource metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct MarkdownLink {
+    pub path: PathBuf,
+    pub anchor: String,
+    pub text: String,
+    pub checked: bool,
+    /// 0-based line number.
+    pub line: usize,
+    /// Modification time of the file after the last sync, in nanoseconds since the epoch.
+    pub mtime: i128,
+}
+
+impl MarkdownLink {
+    /// The resource id of the link.
+    pub fn resource_id(&self) -> String {
+        format!("{}#{}", self.path.display(), self.anchor)
+    }
+}
+
+/// How a linked task was found in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MatchKind {
+    /// Same text on the same line.
+    Exact,
+    /// Same text on another line.
+    Moved,
+    /// Similar text.
+    Edited,
+}
+
+/// Result of matching the links of a file to the tasks in it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Matching {
+    /// `(link, task, kind)` index pairs.
+    pub pairs: Vec<(usize, usize, MatchKind)>,
+    /// Links with several equally good candidate tasks.
+    pub ambiguous: Vec<(usize, Vec<usize>)>,
+    /// Links without a matching task.
+    pub missing: Vec<usize>,
+    /// Tasks neither matched nor candidates of an ambiguous link.
+    pub new_tasks: Vec<usize>,
+}
+
+/// Mirrors the tasks of the Markdown files under `dir` with todos, see [`Aim::sync_markdown`].
+pub(crate) async fn sync(aim: &Aim, db: &Db, dir: &Path) -> Result<MarkdownSyncReport, AimError> {
+    let dir = dir.canonicalize()?;
+    let mut links: HashMap<PathBuf, Vec<(String, MarkdownLink)>> = HashMap::new();
+    for record in db.resources.list_by_calendar(RESOURCE_CALENDAR).await? {
+        match record.metadata_json::<MarkdownLink>() {
+            Some(link) if link.path.starts_with(&dir) => {
+                links
+                    .entry(link.path.clone())
+                    .or_default()
+                    .push((record.uid, link));
+            }
+            Some(_) => {} // linked from another notes directory
+            None => tracing::warn!(uid = record.uid, "invalid markdown link metadata"),
+        }
+    }
+
+    let mut report = MarkdownSyncReport::default();
+    for path in find_notes(&dir)? {
+        let file_links = links.remove(&path).unwrap_or_default();
+        sync_file(aim, db, &path, file_links, &mut report).await?;
+        report.files += 1;
+    }
+
+    // Files that were deleted or renamed
+    for (path, file_links) in links {
+        for (uid, link) in file_links {
+            db.resources.delete(&uid, RESOURCE_CALENDAR).await?;
+            report.conflicts.push(MarkdownConflict {
+                path: path.clone(),
+                line: link.line + 1,
+                kind: MarkdownConflictKind::Missing { text: link.text },
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[expect(clippy::too_many_lines)]
+async fn sync_file(
+    aim: &Aim,
+    db: &Db,
+    path: &Path,
+    file_links: Vec<(String, MarkdownLink)>,
+    report: &mut MarkdownSyncReport,
+) -> Result<(), AimError> {
+    let original = fs::read_to_string(path)?;
+    let tasks = parse_tasks(&original);
+    let anchors = anchors(&tasks);
+    let (uids, links): (Vec<_>, Vec<_>) = file_links.into_iter().unzip();
+    let matching = match_tasks(&links, &tasks);
+
+    // The checkboxes can only have been edited if the file changed since the last sync
+    let mtime_before = mtime(path)?;
+    let file_changed = links.first().is_none_or(|l| l.mtime != mtime_before);
+    let conflict = |line: usize, kind| MarkdownConflict {
+        path: path.to_path_buf(),
+        line: line + 1,
+        kind,
+    };
+
+    let mut content = original.clone();
+    let mut synced = Vec::new();
+    for &(l, t, kind) in &matching.pairs {
+        let (Some(uid), Some(link), Some(task)) = (uids.get(l), links.get(l), tasks.get(t)) else {
+            continue;
+        };
+        let id = Id::Uid(uid.clone());
+        let todo = match aim.get_todo(&id).await {
+            Ok(todo) => todo,
+            Err(AimError::NotFound { .. }) => {
+                db.resources.delete(uid, RESOURCE_CALENDAR).await?;
+                let text = task.text.clone();
+                report.conflicts.push(conflict(
+                    task.line,
+                    MarkdownConflictKind::TodoDeleted { text },
+                ));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut todo_patch = TodoPatch::default();
+        if kind == MatchKind::Edited {
+            todo_patch.summary = Some(task.text.clone());
+            report.conflicts.push(conflict(
+                task.line,
+                MarkdownConflictKind::Edited {
+                    from: link.text.clone(),
+                    to: task.text.clone(),
+                },
+            ));
+        }
+
+        let done = todo.status() == TodoStatus::Completed;
+        if file_changed && task.checked != link.checked {
+            if task.checked != done {
+                todo_patch.status = Some(if task.checked {
+                    report.completed += 1;
+                    TodoStatus::Completed
+                } else {
+                    report.reopened += 1;
+                    TodoStatus::NeedsAction
+                });
+            }
+        } else if task.checked != done {
+            content = set_checked(&content, task.line, done);
+            if done {
+                report.checked += 1;
+            } else {
+                report.unchecked += 1;
+            }
+        }
+        if !todo_patch.is_empty() {
+            aim.update_todo(&id, todo_patch).await?;
+        }
+        synced.push((uid.clone(), t));
+    }
+
+    for (l, candidates) in &matching.ambiguous {
+        if let Some(link) = links.get(*l) {
+            let candidates = candidates
+                .iter()
+                .filter_map(|t| tasks.get(*t))
+                .map(|task| task.line + 1)
+                .collect();
+            let kind = MarkdownConflictKind::Ambiguous {
+                text: link.text.clone(),
+                candidates,
+            };
+            report.conflicts.push(conflict(link.line, kind));
+            // Keep the link so that it can be matched once the ambiguity is resolved
+            synced.push((uids.get(*l).cloned().unwrap_or_default(), usize::MAX));
+        }
+    }
+
+    for &l in &matching.missing {
+        if let (Some(uid), Some(link)) = (uids.get(l), links.get(l)) {
+            db.resources.delete(uid, RESOURCE_CALENDAR).await?;
+            let text = link.text.clone();
+            report
+                .conflicts
+                .push(conflict(link.line, MarkdownConflictKind::Missing { text }));
+        }
+    }
+
+    for &t in &matching.new_tasks {
+        let Some(task) = tasks.get(t).filter(|task| !task.checked) else {
+            continue;
+        };
+        let mut draft = aim.default_todo_draft()?;
+        draft.summary.clone_from(&task.text);
+        let todo = aim.new_todo(draft).await?;
+        report.created += 1;
+        synced.push((todo.uid().to_string(), t));
+    }
+
+    if content != original {
+        write_atomic(path, content.as_bytes())?;
+    }
+    let mtime = mtime(path)?;
+    let current = parse_tasks(&content);
+    for (uid, t) in synced {
+        let link = match (current.get(t), anchors.get(t)) {
+            (Some(task), Some(anchor)) => MarkdownLink {
+                path: path.to_path_buf(),
+                anchor: anchor.clone(),
+                text: task.text.clone(),
+                checked: task.checked,
+                line: task.line,
+                mtime,
+            },
+            // Unresolved links keep their last state, with the new mtime
+            _ => match uids
+                .iter()
+                .position(|u| *u == uid)
+                .and_then(|l| links.get(l))
+            {
+                Some(link) => MarkdownLink {
+                    mtime,
+                    ..link.clone()
+                },
+                None => continue,
+            },
+        };
+        let metadata = serde_json::to_string(&link).map_err(|e| AimError::Other(e.to_string()))?;
+        db.resources
+            .insert(
+                &uid,
+                RESOURCE_CALENDAR,
+                &link.resource_id(),
+                Some(&metadata),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Modification time of `path` in nanoseconds since the epoch.
+fn mtime(path: &Path) -> io::Result<i128> {
+    let modified = fs::metadata(path)?.modified()?;
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    Ok(i128::try_from(since_epoch.as_nanos()).unwrap_or(i128::MAX))
+}
+
+/// Finds the Markdown files under `dir`, skipping hidden entries, in path order.
+pub(crate) fn find_notes(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut notes = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let hidden = path
+                .file_name()
+                .and_then(|a| a.to_str())
+                .is_some_and(|a| a.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                notes.push(path);
+            }
+        }
+    }
+    notes.sort();
+    Ok(notes)
+}
+
+/// Parses the task list items of a Markdown document.
+pub(crate) fn parse_tasks(content: &str) -> Vec<MarkdownTask> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let (checked, text) = parse_task_line(text)?;
+            Some(MarkdownTask {
+                line,
+                checked,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Splits a task line into its checkbox state and text.
+fn parse_task_line(line: &str) -> Option<(bool, &str)> {
+    let (_, rest) = split_checkbox(line)?;
+    let checked = match rest.get(1..2)? {
+        " " => false,
+        "x" | "X" => true,
+        _ => return None,
+    };
+    let text = rest.get(3..)?.strip_prefix(' ')?.trim();
+    (!text.is_empty()).then_some((checked, text))
+}
+
+/// Splits a list item line before its `[ ]` checkbox.
+fn split_checkbox(line: &str) -> Option<(&str, &str)> {
+    let indent = line.len() - line.trim_start().len();
+    let item = line.get(indent..)?;
+    let marker = if item.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = item.bytes().take_while(u8::is_ascii_digit).count();
+        let delimiter = item.get(digits..digits + 1)?;
+        if digits == 0 || (delimiter != "." && delimiter != ")") {
+            return None;
+        }
+        digits + 1
+    };
+    let rest = item.get(marker..)?.strip_prefix(' ')?;
+    let prefix_len = line.len() - rest.len();
+    (rest.starts_with('[') && rest.get(2..3) == Some("]")).then(|| line.split_at(prefix_len))
+}
+
+/// Sets the checkbox of the 0-based `line`, keeping everything else byte for byte.
+pub(crate) fn set_checked(content: &str, line: usize, checked: bool) -> String {
+    let mut out = String::with_capacity(content.len());
+    for (i, text) in content.split_inclusive('\n').enumerate() {
+        match split_checkbox(text).filter(|_| i == line) {
+            Some((prefix, rest)) => {
+                out.push_str(prefix);
+                out.push_str(if checked { "[x]" } else { "[ ]" });
+                out.push_str(rest.get(3..).unwrap_or_default());
+            }
+            None => out.push_str(text),
+        }
+    }
+    out
+}
+
+/// Stable anchor of a task: a hash of its normalized text, with the occurrence number of
+/// identical tasks before it in the file.
+pub(crate) fn anchor(text: &str, occurrence: usize) -> String {
+    // FNV-1a, which is stable across builds unlike the std hasher
+    let hash = normalize(text)
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    match occurrence {
+        0 => format!("{hash:016x}"),
+        n => format!("{hash:016x}-{n}"),
+    }
+}
+
+/// Anchors of `tasks`, in order.
+pub(crate) fn anchors(tasks: &[MarkdownTask]) -> Vec<String> {
+    let mut seen: Vec<String> = Vec::new();
+    tasks
+        .iter()
+        .map(|task| {
+            let text = normalize(&task.text);
+            let occurrence = seen.iter().filter(|a| **a == text).count();
+            seen.push(text);
+            anchor(&task.text, occurrence)
+        })
+        .collect()
+}
+
+/// Matches the `links` of a file to its current `tasks`.
+///
+/// Unchanged and moved tasks are matched by text first, preferring the closest line. The
+/// remaining links are matched to the most similar remaining task, unless several tasks are
+/// about as similar, which is reported as ambiguous.
+pub(crate) fn match_tasks(links: &[MarkdownLink], tasks: &[MarkdownTask]) -> Matching {
+    let mut matching = Matching::default();
+    let mut claimed = vec![false; tasks.len()];
+    let mut unmatched = Vec::new();
+
+    for (l, link) in links.iter().enumerate() {
+        let text = normalize(&link.text);
+        let found = tasks
+            .iter()
+            .enumerate()
+            .filter(|(t, task)| {
+                !claimed.get(*t).copied().unwrap_or(true) && normalize(&task.text) == text
+            })
+            .min_by_key(|(_, task)| task.line.abs_diff(link.line));
+        match found {
+            Some((t, task)) => {
+                if let Some(c) = claimed.get_mut(t) {
+                    *c = true;
+                }
+                let kind = if task.line == link.line {
+                    MatchKind::Exact
+                } else {
+                    MatchKind::Moved
+                };
+                matching.pairs.push((l, t, kind));
+            }
+            None => unmatched.push(l),
+        }
+    }
+
+    let mut blocked = vec![false; tasks.len()];
+    for l in unmatched {
+        let Some(link) = links.get(l) else { continue };
+        let mut scored: Vec<_> = tasks
+            .iter()
+            .enumerate()
+            .filter(|(t, _)| !claimed.get(*t).copied().unwrap_or(true))
+            .map(|(t, task)| (t, similarity(&link.text, &task.text)))
+            .filter(|(_, score)| *score >= MIN_SIMILARITY)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let Some(&(best, best_score)) = scored.first() else {
+            matching.missing.push(l);
+            continue;
+        };
+        let candidates: Vec<_> = scored
+            .iter()
+            .take_while(|(_, score)| best_score - score <= AMBIGUITY_MARGIN)
+            .map(|(t, _)| *t)
+            .collect();
+        if candidates.len() > 1 {
+            for &t in &candidates {
+                if let Some(b) = blocked.get_mut(t) {
+                    *b = true;
+                }
+            }
+            matching.ambiguous.push((l, candidates));
+        } else {
+            if let Some(c) = claimed.get_mut(best) {
+                *c = true;
+            }
+            matching.pairs.push((l, best, MatchKind::Edited));
+        }
+    }
+
+    matching.new_tasks = (0..tasks.len())
+        .filter(|&t| {
+            !claimed.get(t).copied().unwrap_or(true) && !blocked.get(t).copied().unwrap_or(true)
+        })
+        .collect();
+    matching
+}
+
+/// Collapses whitespace and case so that cosmetic edits keep the same anchor.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two texts from 0 to 1, based on the edit distance of their normalized forms.
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalize(a).chars().collect();
+    let b: Vec<char> = normalize(b).chars().collect();
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        let mut left = i + 1;
+        let mut diagonal = i;
+        for (j, cb) in b.iter().enumerate() {
+            let up = prev.get(j + 1).copied().unwrap_or_default();
+            let cost = usize::from(ca != cb);
+            let value = (diagonal + cost).min(left + 1).min(up + 1);
+            if let Some(cell) = curr.get_mut(j + 1) {
+                *cell = value;
+            }
+            diagonal = up;
+            left = value;
+        }
+        if let Some(first) = curr.first_mut() {
+            *first = i + 1;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let distance = prev.last().copied().unwrap_or(len);
+
+    #[expect(clippy::cast_precision_loss)]
+    let ratio = distance as f64 / len as f64;
+    1.0 - ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(line: usize, checked: bool, text: &str) -> MarkdownTask {
+        MarkdownTask {
+            line,
+            checked,
+            text: text.to_string(),
+        }
+    }
+
+    fn link(line: usize, text: &str) -> MarkdownLink {
+        MarkdownLink {
+            path: PathBuf::from("notes.md"),
+            anchor: anchor(text, 0),
+            text: text.to_string(),
+            checked: false,
+            line,
+            mtime: 0,
+        }
+    }
+
+    #[test]
+    fn parses_task_list_items() {
+        let content = "\
+# Project
+
+- [ ] write the spec
+  * [x] review  draft
+1. [X] ship it
+2) [ ] numbered
+- [] not a task
+- [ ]
+- plain item
+[ ] no marker
+";
+        assert_eq!(
+            parse_tasks(content),
+            [
+                task(2, false, "write the spec"),
+                task(3, true, "review  draft"),
+                task(4, true, "ship it"),
+                task(5, false, "numbered"),
+            ]
+        );
+    }
+
+    #[test]
+    fn sets_checkbox_keeping_the_rest() {
+        let content = "- [ ] one\r\n  - [ ] two\n- [x] three";
+        assert_eq!(
+            set_checked(content, 1, true),
+            "- [ ] one\r\n  - [x] two\n- [x] three"
+        );
+        assert_eq!(
+            set_checked(content, 2, false),
+            "- [ ] one\r\n  - [ ] two\n- [ ] three"
+        );
+        // Lines that are not tasks are left alone
+        assert_eq!(set_checked("plain\n", 0, true), "plain\n");
+    }
+
+    #[test]
+    fn anchors_ignore_cosmetic_edits_and_count_duplicates() {
+        assert_eq!(anchor("Buy  milk", 0), anchor("buy milk", 0));
+        assert_ne!(anchor("buy milk", 0), anchor("buy bread", 0));
+
+        let tasks = [
+            task(0, false, "call mom"),
+            task(1, false, "Call mom"),
+            task(2, false, "other"),
+        ];
+        let anchors = anchors(&tasks);
+        assert_eq!(anchors.first(), Some(&anchor("call mom", 0)));
+        assert_eq!(anchors.get(1), Some(&anchor("call mom", 1)));
+        assert_eq!(anchors.get(2), Some(&anchor("other", 0)));
+    }
+
+    #[test]
+    fn matches_moved_and_edited_tasks() {
+        let links = [
+            link(0, "write the spec"),
+            link(1, "review the draft"),
+            link(2, "book flights"),
+        ];
+        let tasks = [
+            task(0, false, "new task"),
+            task(1, false, "write the spec"),
+            task(2, false, "review the drafts"),
+        ];
+
+        let matching = match_tasks(&links, &tasks);
+        assert_eq!(
+            matching.pairs,
+            [(0, 1, MatchKind::Moved), (1, 2, MatchKind::Edited)]
+        );
+        assert_eq!(matching.missing, [2]);
+        assert_eq!(matching.new_tasks, [0]);
+        assert!(matching.ambiguous.is_empty());
+    }
+
+    #[test]
+    fn reports_ambiguous_edits_without_new_tasks() {
+        let links = [link(0, "email the team")];
+        let tasks = [
+            task(0, false, "email the teams"),
+            task(1, false, "email the tram"),
+        ];
+
+        let matching = match_tasks(&links, &tasks);
+        assert!(matching.pairs.is_empty());
+        assert_eq!(matching.ambiguous, [(0, vec![0, 1])]);
+        assert!(matching.new_tasks.is_empty());
+    }
+
+    #[test]
+    fn conflicts_display_location_and_reason() {
+        let conflict = MarkdownConflict {
+            path: PathBuf::from("notes/todo.md"),
+            line: 3,
+            kind: MarkdownConflictKind::Edited {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            },
+        };
+        assert_eq!(
+            conflict.to_string(),
+            "notes/todo.md:3: task text changed from \"a\" to \"b\""
+        );
+    }
+}
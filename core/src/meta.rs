@@ -0,0 +1,228 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Custom metadata of events and todos: arbitrary key-value notes kept as `X-AIM-META-<KEY>`
+//! properties, so they sync with the item and survive round-trips through other clients.
+
+use std::collections::BTreeMap;
+
+use aimcal_ical::{StringStorage, Value, ValueText, XNameProperty};
+
+/// Prefix of the properties holding the metadata, followed by the key in upper case.
+const X_AIM_META_PREFIX: &str = "X-AIM-META-";
+
+/// The longest metadata value accepted, in characters.
+pub const MAX_META_VALUE_LEN: usize = 1024;
+
+/// Normalizes a metadata key, which is case-insensitive.
+///
+/// Property names only allow letters, digits and dashes, so the key is lowercased and any other
+/// run of characters becomes a single dash, such as `Due Soon!` becoming `due-soon`.
+///
+/// # Errors
+/// If the key has no letter or digit.
+pub fn normalize_meta_key(key: &str) -> Result<String, String> {
+    let mut normalized = String::with_capacity(key.len());
+    for c in key.chars() {
+        if c.is_ascii_alphanumeric() {
+            normalized.push(c.to_ascii_lowercase());
+        } else if !normalized.is_empty() && !normalized.ends_with('-') {
+            normalized.push('-');
+        }
+    }
+    let normalized = normalized.trim_end_matches('-');
+    if normalized.is_empty() {
+        Err(format!("key {key:?} has no letter or digit"))
+    } else {
+        Ok(normalized.to_string())
+    }
+}
+
+/// Checks that a metadata value is plain text on a single line, of at most
+/// [`MAX_META_VALUE_LEN`] characters.
+///
+/// # Errors
+/// If the value is too long or has control characters, such as line breaks.
+pub(crate) fn check_meta_value(value: &str) -> Result<(), String> {
+    if value.chars().count() > MAX_META_VALUE_LEN {
+        Err(format!(
+            "value is longer than {MAX_META_VALUE_LEN} characters"
+        ))
+    } else if value.chars().any(char::is_control) {
+        Err("value must be plain text on a single line".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads the metadata of a component, sorted by key.
+pub(crate) fn read_meta<S: StringStorage>(props: &[XNameProperty<S>]) -> Vec<(String, String)> {
+    meta_map(props).into_iter().collect()
+}
+
+/// Sets the metadata of a component, or removes it where the value is `None`.
+///
+/// The metadata properties are written back after the other x-properties, sorted by key, so the
+/// serialized component does not change with the order the keys were set in.
+pub(crate) fn write_meta(
+    props: &mut Vec<XNameProperty<String>>,
+    edits: &[(String, Option<String>)],
+) {
+    let mut meta = meta_map(props);
+    for (key, value) in edits {
+        let Ok(key) = normalize_meta_key(key) else {
+            tracing::warn!(key, "ignoring invalid metadata key");
+            continue;
+        };
+        match value {
+            Some(value) => meta.insert(key, value.clone()),
+            None => meta.remove(&key),
+        };
+    }
+
+    props.retain(|p| meta_key(&p.name).is_none());
+    props.extend(meta.into_iter().map(|(key, value)| XNameProperty {
+        name: format!("{X_AIM_META_PREFIX}{}", key.to_ascii_uppercase()),
+        parameters: Vec::new(),
+        value: Value::Text {
+            values: vec![ValueText::new(value)],
+            span: (),
+        },
+        span: (),
+    }));
+}
+
+/// Normalizes the keys of conditions on metadata.
+pub(crate) fn resolve_meta_conditions(
+    meta: &[(String, String)],
+) -> Result<Vec<(String, String)>, String> {
+    meta.iter()
+        .map(|(key, value)| Ok((normalize_meta_key(key)?, value.clone())))
+        .collect()
+}
+
+fn meta_map<S: StringStorage>(props: &[XNameProperty<S>]) -> BTreeMap<String, String> {
+    props
+        .iter()
+        .filter_map(|p| {
+            let key = meta_key(&p.name.as_str_lossy())?;
+            let value = match &p.value {
+                Value::Text { values, .. } => values.first().map(ToString::to_string),
+                // Without a VALUE parameter, x-name values are parsed as raw strings
+                Value::XName { raw, .. } | Value::Unrecognized { raw, .. } => {
+                    Some(unescape(&raw.as_str_lossy()))
+                }
+                _ => None,
+            }?;
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// The normalized key of a metadata property, or `None` if the property is not one.
+fn meta_key(name: &str) -> Option<String> {
+    let prefix = name.get(..X_AIM_META_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(X_AIM_META_PREFIX) {
+        return None;
+    }
+    normalize_meta_key(name.get(X_AIM_META_PREFIX.len()..)?).ok()
+}
+
+/// Unescapes a raw text value, see RFC 5545 section 3.3.11.
+fn unescape(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('\\' | ';' | ','))) => {
+                value.push(next);
+                chars.next();
+            }
+            ('\\', Some('n' | 'N')) => {
+                value.push('\n');
+                chars.next();
+            }
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prop(name: &str, raw: &str) -> XNameProperty<String> {
+        XNameProperty {
+            name: name.to_string(),
+            parameters: Vec::new(),
+            value: Value::XName {
+                raw: raw.to_string(),
+                kind: String::new(),
+                span: (),
+            },
+            span: (),
+        }
+    }
+
+    #[test]
+    fn normalizes_keys_to_the_x_name_charset() {
+        assert_eq!(normalize_meta_key("Ticket").unwrap(), "ticket");
+        assert_eq!(normalize_meta_key("  Due Soon!").unwrap(), "due-soon");
+        assert_eq!(normalize_meta_key("jira_key.2").unwrap(), "jira-key-2");
+        assert!(normalize_meta_key("--").is_err());
+        assert!(normalize_meta_key("").is_err());
+    }
+
+    #[test]
+    fn checks_values() {
+        assert!(check_meta_value("JIRA-123").is_ok());
+        assert!(check_meta_value("two\nlines").is_err());
+        assert!(check_meta_value(&"x".repeat(MAX_META_VALUE_LEN)).is_ok());
+        assert!(check_meta_value(&"x".repeat(MAX_META_VALUE_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn reads_raw_values_case_insensitively() {
+        let props = vec![
+            prop("x-aim-meta-Ticket", "JIRA-123"),
+            prop("X-OTHER", "kept"),
+            prop("X-AIM-META-CLIENT", "Acme\\, Inc."),
+        ];
+        assert_eq!(
+            read_meta(&props),
+            [
+                ("client".to_string(), "Acme, Inc.".to_string()),
+                ("ticket".to_string(), "JIRA-123".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_metadata_sorted_after_other_properties() {
+        let mut props = vec![
+            prop("X-AIM-META-TICKET", "JIRA-1"),
+            prop("X-OTHER", "kept"),
+            prop("X-AIM-META-OLD", "gone"),
+        ];
+        write_meta(
+            &mut props,
+            &[
+                ("Ticket".to_string(), Some("JIRA-123".to_string())),
+                ("client".to_string(), Some("Acme".to_string())),
+                ("old".to_string(), None),
+            ],
+        );
+
+        let names: Vec<_> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["X-OTHER", "X-AIM-META-CLIENT", "X-AIM-META-TICKET"]);
+        assert_eq!(
+            read_meta(&props),
+            [
+                ("client".to_string(), "Acme".to_string()),
+                ("ticket".to_string(), "JIRA-123".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! How long todos are overdue, and how they escalate as they age past their due.
+
+use jiff::{SignedDuration, Zoned};
+use serde::Deserialize;
+
+use crate::datetime::parse_signed_duration;
+use crate::{LooseDateTime, Priority};
+
+/// Escalation of overdue todos, from the `[overdue]` section.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct OverdueConfig {
+    /// Ages past due at which overdue todos escalate to the next level, e.g. `["1d", "7d"]`.
+    #[serde(deserialize_with = "deserialize_thresholds")]
+    pub thresholds: Vec<SignedDuration>,
+
+    /// If true, overdue todos rank one priority higher for each threshold they passed when
+    /// ordered by urgency, leaving the priority they are stored with as it is.
+    pub escalate: bool,
+}
+
+impl Default for OverdueConfig {
+    fn default() -> Self {
+        Self {
+            thresholds: vec![
+                SignedDuration::from_hours(24),
+                SignedDuration::from_hours(7 * 24),
+            ],
+            escalate: false,
+        }
+    }
+}
+
+impl OverdueConfig {
+    /// The level of a todo overdue by `age`, the number of thresholds it passed.
+    #[must_use]
+    pub fn level(&self, age: SignedDuration) -> usize {
+        self.thresholds.iter().filter(|&&t| age >= t).count()
+    }
+
+    /// The highest level, reached once all the thresholds are passed.
+    #[must_use]
+    pub fn max_level(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    /// The priority to rank a todo overdue by `age` with, raised one step for each threshold it
+    /// passed if escalating. A todo without a priority starts below the lowest one.
+    #[must_use]
+    pub fn effective_priority(&self, priority: Priority, age: Option<SignedDuration>) -> Priority {
+        let levels = match age {
+            Some(age) if self.escalate => self.level(age),
+            _ => 0,
+        };
+        if levels == 0 {
+            return priority;
+        }
+        let rank = match u8::from(priority) {
+            0 => 10,
+            n => n,
+        };
+        let levels = u8::try_from(levels).unwrap_or(u8::MAX);
+        Priority::from(rank.saturating_sub(levels).max(1))
+    }
+}
+
+/// How long a todo due at `due` is overdue at `now`, `None` if it is not.
+///
+/// A todo due on a date is due by the end of that day, so one due yesterday is overdue by the
+/// hours since midnight, not since the start of yesterday.
+#[must_use]
+pub fn overdue_age(due: &LooseDateTime, now: &Zoned) -> Option<SignedDuration> {
+    let due = match due {
+        LooseDateTime::Local(zoned) => zoned.with_time_zone(now.time_zone().clone()).datetime(),
+        due => due.with_end_of_day(),
+    };
+    let age = due.duration_until(now.datetime());
+    (age > SignedDuration::ZERO).then_some(age)
+}
+
+fn deserialize_thresholds<'de, D>(deserializer: D) -> Result<Vec<SignedDuration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut thresholds = Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|s| match parse_signed_duration(s) {
+            Ok(d) if d > SignedDuration::ZERO => Ok(d),
+            Ok(_) => Err(format!("threshold must be positive: {s}")),
+            Err(e) => Err(e),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(serde::de::Error::custom)?;
+    thresholds.sort();
+    Ok(thresholds)
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+    use jiff::tz::TimeZone;
+
+    use super::*;
+
+    fn at(day: i8, hour: i8) -> Zoned {
+        date(2025, 8, day)
+            .to_datetime(time(hour, 0, 0, 0))
+            .to_zoned(TimeZone::UTC)
+            .unwrap()
+    }
+
+    #[test]
+    fn dates_are_overdue_from_the_end_of_their_day() {
+        let due = LooseDateTime::DateOnly(date(2025, 8, 4));
+        assert_eq!(overdue_age(&due, &at(4, 20)), None);
+
+        let age = overdue_age(&due, &at(5, 8)).unwrap();
+        assert!(age < SignedDuration::from_hours(9), "{age:?}");
+        assert_eq!(OverdueConfig::default().level(age), 0);
+    }
+
+    #[test]
+    fn times_are_overdue_from_their_time() {
+        let due = LooseDateTime::Local(at(4, 12));
+        assert_eq!(overdue_age(&due, &at(4, 12)), None);
+        assert_eq!(
+            overdue_age(&due, &at(12, 12)),
+            Some(SignedDuration::from_hours(8 * 24))
+        );
+    }
+
+    #[test]
+    fn levels_count_the_thresholds_passed() {
+        let config = OverdueConfig::default();
+        assert_eq!(config.level(SignedDuration::from_hours(23)), 0);
+        assert_eq!(config.level(SignedDuration::from_hours(24)), 1);
+        assert_eq!(config.level(SignedDuration::from_hours(7 * 24)), 2);
+        assert_eq!(config.max_level(), 2);
+    }
+
+    #[test]
+    fn escalates_the_priority_only_if_enabled() {
+        let week = Some(SignedDuration::from_hours(7 * 24));
+        let mut config = OverdueConfig::default();
+        assert_eq!(config.effective_priority(Priority::P5, week), Priority::P5);
+
+        config.escalate = true;
+        assert_eq!(config.effective_priority(Priority::P5, week), Priority::P3);
+        assert_eq!(config.effective_priority(Priority::P2, week), Priority::P1);
+        assert_eq!(
+            config.effective_priority(Priority::None, week),
+            Priority::P8
+        );
+        assert_eq!(config.effective_priority(Priority::P5, None), Priority::P5);
+    }
+
+    #[test]
+    fn parses_overdue_section() {
+        let config: OverdueConfig =
+            toml::from_str("thresholds = [\"3d\", \"12h\"]\nescalate = true").unwrap();
+        assert_eq!(
+            config.thresholds,
+            [
+                SignedDuration::from_hours(12),
+                SignedDuration::from_hours(72)
+            ]
+        );
+        assert!(config.escalate);
+
+        assert!(toml::from_str::<OverdueConfig>("thresholds = [\"-1d\"]").is_err());
+        assert!(toml::from_str::<OverdueConfig>("thresholds = [\"soon\"]").is_err());
+    }
+}
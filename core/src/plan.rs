@@ -0,0 +1,378 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Planning todos into blocks of working time, see [`Aim::plan_todos`](crate::Aim::plan_todos),
+//! and weighing the load of the day against the working time left, see
+//! [`Aim::day_load`](crate::Aim::day_load).
+
+use jiff::{SignedDuration, Timestamp, Zoned};
+
+use crate::{DateTimeAnchor, Priority, WorkingHours};
+
+/// Prefix of the summary of the events planned for todos.
+pub const PLAN_PREFIX: &str = "[Plan]";
+
+/// Blocks start on the quarter hour.
+const QUARTER_HOUR: SignedDuration = SignedDuration::from_mins(15);
+
+/// Options of [`Aim::plan_todos`](crate::Aim::plan_todos).
+#[derive(Debug, Clone)]
+pub struct PlanOptions {
+    /// The end of the planning horizon, resolved at the end of its day.
+    pub until: DateTimeAnchor,
+    /// The calendar ID to create the blocks in. Uses default calendar if None.
+    pub calendar_id: Option<String>,
+    /// Whether to only propose the blocks, creating nothing.
+    pub dry_run: bool,
+}
+
+/// The blocks planned for todos, and the todos left out.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    /// The blocks planned, in the order the todos were planned in.
+    pub blocks: Vec<PlannedBlock>,
+    /// The todos left out, in the order they were given.
+    pub unplanned: Vec<Unplanned>,
+}
+
+/// A block of working time planned for a todo.
+#[derive(Debug, Clone)]
+pub struct PlannedBlock {
+    /// The UID of the todo.
+    pub todo_uid: String,
+    /// The summary of the todo.
+    pub summary: String,
+    /// The start of the block.
+    pub start: Zoned,
+    /// The end of the block.
+    pub end: Zoned,
+    /// The UID of the event created for the block, `None` on a dry run.
+    pub event_uid: Option<String>,
+}
+
+/// A todo left out of the plan.
+#[derive(Debug, Clone)]
+pub struct Unplanned {
+    /// The UID of the todo.
+    pub todo_uid: String,
+    /// The summary of the todo.
+    pub summary: String,
+    /// Why the todo was left out.
+    pub reason: UnplannedReason,
+}
+
+/// Why a todo was left out of the plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnplannedReason {
+    /// The todo is completed or cancelled.
+    Done,
+    /// The todo already has blocks planned that have not ended yet.
+    AlreadyPlanned,
+    /// No free working time within the horizon is as long as the estimate of the todo.
+    NoFreeSlot(SignedDuration),
+}
+
+/// What to do with the blocks planned for a todo once it is done, see
+/// [`Aim::release_planned_blocks`](crate::Aim::release_planned_blocks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockRelease {
+    /// Delete the blocks that have not ended yet.
+    Delete,
+    /// End the block in progress now, and delete the ones that have not started yet.
+    Shorten,
+}
+
+/// The work left for the rest of today against the working time left, see
+/// [`Aim::day_load`](crate::Aim::day_load).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DayLoad {
+    /// The estimates of the todos due today, and the time left of the blocks planned today.
+    pub load: SignedDuration,
+    /// The working time left today that no event takes.
+    pub free: SignedDuration,
+    /// How many of the todos due today have no estimate, counted with the default one or left
+    /// out as configured.
+    pub unestimated: usize,
+}
+
+/// How much of the free time the load of the day takes, see [`DayLoad::level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoadLevel {
+    /// Under 80% of the free time.
+    Comfortable,
+    /// From 80% up to all of the free time.
+    Tight,
+    /// More than the free time.
+    Overcommitted,
+}
+
+impl DayLoad {
+    /// How much of the free time the load takes.
+    #[must_use]
+    pub fn level(&self) -> LoadLevel {
+        let (load, free) = (self.load.as_secs(), self.free.as_secs());
+        if load == 0 || load.saturating_mul(5) < free.saturating_mul(4) {
+            LoadLevel::Comfortable
+        } else if load <= free {
+            LoadLevel::Tight
+        } else {
+            LoadLevel::Overcommitted
+        }
+    }
+}
+
+/// Weighs the todos due today and the blocks planned today against the working time from `now`
+/// to the end of the day that none of the `busy` intervals take.
+///
+/// The blocks count for the time left of them, as the time of their todos is already planned.
+/// Todos without an estimate count for `default_estimate`, or not at all without one.
+pub(crate) fn day_load(
+    hours: &WorkingHours,
+    now: &Zoned,
+    busy: &[(Timestamp, Timestamp)],
+    blocks: &[(Timestamp, Timestamp)],
+    estimates: &[Option<SignedDuration>],
+    default_estimate: Option<SignedDuration>,
+) -> DayLoad {
+    let Ok(end) = now.end_of_day() else {
+        return DayLoad::default();
+    };
+    let free = hours
+        .free_slots(now, &end, busy)
+        .iter()
+        .fold(SignedDuration::ZERO, |total, (start, end)| {
+            total + start.duration_until(end)
+        });
+
+    let (now, end) = (now.timestamp(), end.timestamp());
+    let planned = blocks
+        .iter()
+        .map(|&(start, stop)| (start.max(now), stop.min(end)))
+        .filter(|(start, stop)| start < stop)
+        .fold(SignedDuration::ZERO, |total, (start, stop)| {
+            total + start.duration_until(stop)
+        });
+    let estimated_total = estimates
+        .iter()
+        .filter_map(|estimate| estimate.or(default_estimate))
+        .fold(SignedDuration::ZERO, |total, estimate| total + estimate);
+    DayLoad {
+        load: planned + estimated_total,
+        free,
+        unestimated: estimates.iter().filter(|e| e.is_none()).count(),
+    }
+}
+
+/// A todo to plan a block for.
+#[derive(Debug, Clone)]
+pub(crate) struct PlanItem {
+    pub uid: String,
+    pub summary: String,
+    pub due: Option<Timestamp>,
+    pub priority: Priority,
+    pub estimate: SignedDuration,
+}
+
+/// Assigns the todos the first free slot each fits in, the ones due sooner first, then the ones of
+/// higher priority, then by UID, so that the same inputs always give the same plan.
+pub(crate) fn assign(mut items: Vec<PlanItem>, mut slots: Vec<(Zoned, Zoned)>) -> Plan {
+    items.sort_by(|a, b| {
+        let key = |item: &PlanItem| {
+            // Priority 0 is none, after the lowest one of 9
+            let priority = match u8::from(item.priority) {
+                0 => 10,
+                p => p,
+            };
+            (item.due.is_none(), item.due, priority)
+        };
+        key(a).cmp(&key(b)).then_with(|| a.uid.cmp(&b.uid))
+    });
+
+    let mut plan = Plan::default();
+    for item in items {
+        let fit = slots.iter().enumerate().find_map(|(i, (start, end))| {
+            let start = quarter_hour_at_or_after(start)?;
+            let block_end = start.checked_add(item.estimate).ok()?;
+            (block_end <= *end).then_some((i, start, block_end))
+        });
+        let Some((i, start, end)) = fit else {
+            plan.unplanned.push(Unplanned {
+                todo_uid: item.uid,
+                summary: item.summary,
+                reason: UnplannedReason::NoFreeSlot(item.estimate),
+            });
+            continue;
+        };
+
+        // Keep the free time left on both sides of the block
+        let (slot_start, slot_end) = slots.remove(i);
+        if end < slot_end {
+            slots.insert(i, (end.clone(), slot_end));
+        }
+        if slot_start < start {
+            slots.insert(i, (slot_start, start.clone()));
+        }
+        plan.blocks.push(PlannedBlock {
+            todo_uid: item.uid,
+            summary: item.summary,
+            start,
+            end,
+            event_uid: None,
+        });
+    }
+    plan
+}
+
+fn quarter_hour_at_or_after(dt: &Zoned) -> Option<Zoned> {
+    let past = SignedDuration::from_secs(i64::from(dt.minute() % 15) * 60 + i64::from(dt.second()))
+        + SignedDuration::from_nanos(i64::from(dt.subsec_nanosecond()));
+    if past.is_zero() {
+        Some(dt.clone())
+    } else {
+        dt.checked_add(QUARTER_HOUR - past).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+
+    use super::*;
+
+    fn at(h: i8, m: i8) -> Zoned {
+        date(2026, 3, 6)
+            .to_datetime(time(h, m, 0, 0))
+            .in_tz("UTC")
+            .unwrap()
+    }
+
+    fn item(uid: &str, due: Option<Zoned>, priority: u8, mins: i64) -> PlanItem {
+        PlanItem {
+            uid: uid.to_string(),
+            summary: uid.to_uppercase(),
+            due: due.map(|d| d.timestamp()),
+            priority: Priority::from(priority),
+            estimate: SignedDuration::from_mins(mins),
+        }
+    }
+
+    fn spans(plan: &Plan) -> Vec<(&str, Zoned, Zoned)> {
+        plan.blocks
+            .iter()
+            .map(|b| (b.todo_uid.as_str(), b.start.clone(), b.end.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn assigns_todos_due_sooner_and_of_higher_priority_first() {
+        let slots = vec![(at(9, 10), at(12, 0)), (at(13, 0), at(17, 0))];
+        let items = vec![
+            item("c", None, 0, 60),
+            item("b", None, 1, 60),
+            item("a", Some(at(18, 0)), 5, 90),
+            item("d", None, 0, 60),
+        ];
+        let plan = assign(items, slots);
+        assert_eq!(
+            spans(&plan),
+            [
+                ("a", at(9, 15), at(10, 45)),
+                ("b", at(10, 45), at(11, 45)),
+                ("c", at(13, 0), at(14, 0)),
+                ("d", at(14, 0), at(15, 0)),
+            ]
+        );
+        assert!(plan.unplanned.is_empty());
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn fills_the_time_left_around_blocks_and_reports_todos_that_do_not_fit() {
+        let slots = vec![(at(9, 0), at(10, 0)), (at(13, 0), at(14, 0))];
+        let items = vec![
+            item("a", None, 1, 45),
+            item("b", None, 2, 120),
+            item("c", None, 3, 15),
+            item("d", None, 4, 60),
+        ];
+        let plan = assign(items, slots);
+        assert_eq!(
+            spans(&plan),
+            [
+                ("a", at(9, 0), at(9, 45)),
+                ("c", at(9, 45), at(10, 0)),
+                ("d", at(13, 0), at(14, 0)),
+            ]
+        );
+        let unplanned: Vec<_> = plan.unplanned.iter().map(|u| &u.todo_uid).collect();
+        assert_eq!(unplanned, ["b"]);
+        assert_eq!(
+            plan.unplanned[0].reason,
+            UnplannedReason::NoFreeSlot(SignedDuration::from_hours(2))
+        );
+    }
+
+    #[test]
+    fn weighs_the_load_against_the_working_time_left() {
+        let hours = WorkingHours::default(); // 09:00-17:00 on weekdays
+        let meeting = (at(13, 0).timestamp(), at(14, 0).timestamp());
+        let estimates = [Some(SignedDuration::from_hours(2)), None];
+        let hour = Some(SignedDuration::from_hours(1));
+
+        // Before work, the whole day but the meeting is free
+        let load = day_load(&hours, &at(8, 0), &[meeting], &[], &estimates, hour);
+        assert_eq!(load.free, SignedDuration::from_hours(7));
+        assert_eq!(load.load, SignedDuration::from_hours(3));
+        assert_eq!(load.unestimated, 1);
+        assert_eq!(load.level(), LoadLevel::Comfortable);
+
+        // Halfway through the afternoon, with the meeting over
+        let load = day_load(&hours, &at(14, 30), &[meeting], &[], &estimates, hour);
+        assert_eq!(load.free, SignedDuration::from_mins(150));
+        assert_eq!(load.level(), LoadLevel::Overcommitted);
+
+        // Todos without an estimate can be left out
+        let load = day_load(&hours, &at(14, 30), &[meeting], &[], &estimates, None);
+        assert_eq!(load.load, SignedDuration::from_hours(2));
+        assert_eq!(load.level(), LoadLevel::Tight);
+    }
+
+    #[test]
+    fn counts_the_time_left_of_blocks() {
+        let hours = WorkingHours::default();
+        let blocks = [
+            (at(9, 0).timestamp(), at(10, 0).timestamp()),
+            (at(11, 0).timestamp(), at(12, 30).timestamp()),
+        ];
+        let load = day_load(&hours, &at(12, 0), &[], &blocks, &[], None);
+        assert_eq!(load.load, SignedDuration::from_mins(30));
+        assert_eq!(load.free, SignedDuration::from_hours(5));
+    }
+
+    #[test]
+    fn leaves_no_free_time_under_all_day_events() {
+        let hours = WorkingHours::default();
+        let day = (
+            at(0, 0).timestamp(),
+            date(2026, 3, 7)
+                .to_zoned(jiff::tz::TimeZone::UTC)
+                .unwrap()
+                .timestamp(),
+        );
+        let estimates = [Some(SignedDuration::from_mins(15))];
+        let load = day_load(&hours, &at(8, 0), &[day], &[], &estimates, None);
+        assert_eq!(load.free, SignedDuration::ZERO);
+        assert_eq!(load.level(), LoadLevel::Overcommitted);
+
+        let idle = day_load(&hours, &at(8, 0), &[day], &[], &[], None);
+        assert_eq!(idle.level(), LoadLevel::Comfortable);
+    }
+
+    #[test]
+    fn rounds_starts_up_to_the_quarter_hour() {
+        assert_eq!(quarter_hour_at_or_after(&at(9, 0)), Some(at(9, 0)));
+        assert_eq!(quarter_hour_at_or_after(&at(9, 1)), Some(at(9, 15)));
+        assert_eq!(quarter_hour_at_or_after(&at(9, 59)), Some(at(10, 0)));
+    }
+}
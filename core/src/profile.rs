@@ -0,0 +1,312 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Profiles, separate sets of config, database and calendars, such as one for work and one for
+//! personal use.
+//!
+//! The default profile keeps the config file and state directory aim has always used. The others
+//! have theirs in a `profiles/<name>` directory under both, so nothing is shared between them.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::config::{APP_NAME, get_config_dir, get_state_dir};
+use crate::error::AimError;
+use crate::file::write_atomic;
+
+/// Directory holding the profiles other than the default one.
+const PROFILES_DIR: &str = "profiles";
+
+/// Name of the config file of a profile.
+const CONFIG_FILE: &str = "config.toml";
+
+/// Longest name a profile can have.
+const MAX_NAME_LEN: usize = 64;
+
+/// Names kept for listing several profiles at once.
+const RESERVED_NAMES: &[&str] = &["all"];
+
+/// Config file written for a new profile.
+const NEW_CONFIG: &str = "\
+# Config of the profile, see `aim --help` for the other settings.
+# Without a state_dir, the database and local calendars of the profile are kept apart from the
+# other profiles in its own state directory.
+
+[core]
+";
+
+/// A named profile, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Profile(String);
+
+impl Profile {
+    /// Name of the profile used unless another one is chosen.
+    pub const DEFAULT: &str = "default";
+
+    /// The name of the profile.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is the default profile.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = AimError;
+
+    /// Parses a profile name, made of ASCII letters, digits, `-` and `_`, starting with a letter
+    /// or digit.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| AimError::InvalidInput {
+            field: "profile",
+            reason,
+        };
+        if s.is_empty() || s.len() > MAX_NAME_LEN {
+            return Err(invalid(format!(
+                "'{s}' must have 1 to {MAX_NAME_LEN} characters"
+            )));
+        }
+        if !s.starts_with(|c: char| c.is_ascii_alphanumeric())
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(invalid(format!(
+                "'{s}' must be made of letters, digits, '-' and '_', starting with a letter or digit"
+            )));
+        }
+        if RESERVED_NAMES.contains(&s) {
+            return Err(invalid(format!("'{s}' is reserved")));
+        }
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Where the profiles keep their config files and state, the app directories of aim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileDirs {
+    config_dir: PathBuf,
+    state_dir: PathBuf,
+}
+
+impl ProfileDirs {
+    /// Profiles in the given app directories, such as `~/.config/aim` and `~/.local/state/aim`.
+    #[must_use]
+    pub fn new(config_dir: PathBuf, state_dir: PathBuf) -> Self {
+        Self {
+            config_dir,
+            state_dir,
+        }
+    }
+
+    /// Profiles in the app directories of the user, `$XDG_CONFIG_HOME/aim` and
+    /// `$XDG_STATE_HOME/aim` on Linux and macOS, `%LOCALAPPDATA%/aim` on Windows.
+    ///
+    /// # Errors
+    /// If the home directory of the user is not found.
+    pub fn from_env() -> Result<Self, AimError> {
+        let config_dir = get_config_dir()?;
+        let state_dir = get_state_dir()?;
+        Ok(Self::new(
+            config_dir.join(APP_NAME),
+            state_dir.join(APP_NAME),
+        ))
+    }
+
+    /// Path of the config file of the profile.
+    #[must_use]
+    pub fn config_file(&self, profile: &Profile) -> PathBuf {
+        Self::dir_of(&self.config_dir, profile).join(CONFIG_FILE)
+    }
+
+    /// Directory of the database and local calendars of the profile, unless its config sets
+    /// `state_dir`.
+    #[must_use]
+    pub fn state_dir(&self, profile: &Profile) -> PathBuf {
+        Self::dir_of(&self.state_dir, profile)
+    }
+
+    fn dir_of(root: &Path, profile: &Profile) -> PathBuf {
+        if profile.is_default() {
+            root.to_path_buf()
+        } else {
+            root.join(PROFILES_DIR).join(profile.name())
+        }
+    }
+
+    /// The profiles with a config file, the default one first and then the others by name.
+    ///
+    /// # Errors
+    /// If the directory of the profiles cannot be read.
+    pub fn list(&self) -> Result<Vec<Profile>, AimError> {
+        let mut profiles = Vec::new();
+        let dir = self.config_dir.join(PROFILES_DIR);
+        match fs::read_dir(&dir) {
+            Ok(entries) => {
+                for entry in entries {
+                    let entry = entry.map_err(|e| io_error(&dir, &e))?;
+                    // Directories that are not valid names were not made by aim, so skip them
+                    if let Some(profile) = entry.file_name().to_str().and_then(|n| n.parse().ok())
+                        && self.config_file(&profile).is_file()
+                    {
+                        profiles.push(profile);
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(io_error(&dir, &e)),
+        }
+        profiles.sort();
+
+        let default = Profile::default();
+        profiles.retain(|p| !p.is_default());
+        if self.config_file(&default).is_file() {
+            profiles.insert(0, default);
+        }
+        Ok(profiles)
+    }
+
+    /// Creates the profile with an empty config, returning the path of its config file.
+    ///
+    /// # Errors
+    /// If the profile exists already, or its config file cannot be written.
+    pub fn create(&self, profile: &Profile) -> Result<PathBuf, AimError> {
+        let path = self.config_file(profile);
+        if path.exists() {
+            return Err(AimError::InvalidInput {
+                field: "profile",
+                reason: format!("'{profile}' exists already, at {}", path.display()),
+            });
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(parent, &e))?;
+        }
+        write_atomic(&path, NEW_CONFIG.as_bytes()).map_err(|e| io_error(&path, &e))?;
+        Ok(path)
+    }
+
+    /// Removes the config of the profile, and its state directory as well with `purge`.
+    ///
+    /// The default profile cannot be removed, as it stands for the config aim has always used.
+    ///
+    /// # Errors
+    /// If the profile is the default one or does not exist, or cannot be removed.
+    pub fn remove(&self, profile: &Profile, purge: bool) -> Result<(), AimError> {
+        if profile.is_default() {
+            return Err(AimError::InvalidInput {
+                field: "profile",
+                reason: "the default profile cannot be removed".to_string(),
+            });
+        }
+        let config = Self::dir_of(&self.config_dir, profile);
+        if !self.config_file(profile).is_file() {
+            return Err(AimError::NotFound {
+                kind: "Profile",
+                id: profile.to_string(),
+            });
+        }
+        fs::remove_dir_all(&config).map_err(|e| io_error(&config, &e))?;
+        if purge {
+            let state = self.state_dir(profile);
+            match fs::remove_dir_all(&state) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(io_error(&state, &e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn io_error(path: &Path, err: &io::Error) -> AimError {
+    AimError::Other(format!("Failed to access {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dirs(temp: &Path) -> ProfileDirs {
+        ProfileDirs::new(temp.join("config"), temp.join("state"))
+    }
+
+    #[test]
+    fn profile_parses_valid_names_only() {
+        assert_eq!("work".parse::<Profile>().unwrap().name(), "work");
+        assert!("Home_2".parse::<Profile>().is_ok());
+        for name in ["", "-work", "a/b", "..", "all", &"x".repeat(65)] {
+            assert!(name.parse::<Profile>().is_err(), "{name:?}");
+        }
+        assert!(Profile::default().is_default());
+    }
+
+    #[test]
+    fn profile_dirs_keep_the_default_where_it_was() {
+        let dirs = ProfileDirs::new("/c/aim".into(), "/s/aim".into());
+        let work: Profile = "work".parse().unwrap();
+        assert_eq!(
+            dirs.config_file(&Profile::default()),
+            PathBuf::from("/c/aim/config.toml")
+        );
+        assert_eq!(dirs.state_dir(&Profile::default()), PathBuf::from("/s/aim"));
+        assert_eq!(
+            dirs.config_file(&work),
+            PathBuf::from("/c/aim/profiles/work/config.toml")
+        );
+        assert_eq!(dirs.state_dir(&work), PathBuf::from("/s/aim/profiles/work"));
+    }
+
+    #[test]
+    fn profile_dirs_create_list_and_remove() {
+        let temp = tempfile::tempdir().unwrap();
+        let dirs = dirs(temp.path());
+        let work: Profile = "work".parse().unwrap();
+        let home: Profile = "home".parse().unwrap();
+        assert!(dirs.list().unwrap().is_empty());
+
+        dirs.create(&Profile::default()).unwrap();
+        dirs.create(&work).unwrap();
+        dirs.create(&home).unwrap();
+        assert!(dirs.create(&work).is_err(), "exists already");
+        assert_eq!(
+            dirs.list().unwrap(),
+            [Profile::default(), home.clone(), work.clone()]
+        );
+
+        let state = dirs.state_dir(&work);
+        fs::create_dir_all(&state).unwrap();
+        dirs.remove(&work, false).unwrap();
+        assert!(state.exists(), "the data is kept without purge");
+        assert_eq!(dirs.list().unwrap(), [Profile::default(), home.clone()]);
+
+        dirs.create(&work).unwrap();
+        dirs.remove(&work, true).unwrap();
+        assert!(!state.exists());
+        assert!(matches!(
+            dirs.remove(&work, false),
+            Err(AimError::NotFound { .. })
+        ));
+        assert!(dirs.remove(&Profile::default(), false).is_err());
+    }
+}
@@ -0,0 +1,840 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use aimcal_ical::{RecurrenceFrequency, ValueRecurrenceRule, WeekDay, WeekDayNum};
+use jiff::civil::{Date, Weekday};
+
+use crate::LooseDateTime;
+use crate::event::until_value;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Describes a recurrence rule in plain English, e.g. "every 2 weeks on Mon, Wed until Jun 30".
+///
+/// The year of the end date is left out when it is the year of `today`.
+#[must_use]
+pub fn describe_recurrence(rule: &ValueRecurrenceRule, today: Date) -> String {
+    let mut parts = vec![describe_frequency(rule.freq, rule.interval.unwrap_or(1))];
+
+    if !rule.by_month.is_empty() {
+        let months: Vec<_> = rule.by_month.iter().map(|&m| month_name(m)).collect();
+        parts.push(format!("in {}", months.join(", ")));
+    }
+    if !rule.by_week_no.is_empty() {
+        let weeks: Vec<_> = rule.by_week_no.iter().map(|&w| ordinal(w.into())).collect();
+        parts.push(format!("in the {} week", weeks.join(", ")));
+    }
+    if !rule.by_year_day.is_empty() {
+        let days: Vec<_> = rule
+            .by_year_day
+            .iter()
+            .map(|&d| ordinal(d.into()))
+            .collect();
+        parts.push(format!("on the {} day of the year", days.join(", ")));
+    }
+    if !rule.by_month_day.is_empty() {
+        let days: Vec<_> = rule
+            .by_month_day
+            .iter()
+            .map(|&d| ordinal(d.into()))
+            .collect();
+        let days = days.join(", ");
+        if rule.by_month_day.iter().any(|&d| d < 0) {
+            parts.push(format!("on the {days} day"));
+        } else {
+            parts.push(format!("on the {days}"));
+        }
+    }
+    if !rule.by_day.is_empty() {
+        parts.push(describe_days(&rule.by_day, &rule.by_set_pos));
+    } else if !rule.by_set_pos.is_empty() {
+        let positions: Vec<_> = rule.by_set_pos.iter().map(|&p| ordinal(p.into())).collect();
+        parts.push(format!("only the {} of each period", positions.join(", ")));
+    }
+    if let Some(times) = describe_times(rule) {
+        parts.push(times);
+    }
+
+    match rule.count {
+        Some(1) => parts.push("once".to_string()),
+        Some(count) => parts.push(format!("for {count} occurrences")),
+        None => {}
+    }
+    if let Some(until) = &rule.until {
+        let month = month_name(until.date.month.try_into().unwrap_or_default());
+        let day = until.date.day;
+        let year = until.date.year;
+        if year == today.year() {
+            parts.push(format!("until {month} {day}"));
+        } else {
+            parts.push(format!("until {month} {day}, {year}"));
+        }
+    }
+
+    parts.join(" ")
+}
+
+fn describe_frequency(freq: RecurrenceFrequency, interval: u32) -> String {
+    let unit = match freq {
+        RecurrenceFrequency::Secondly => "second",
+        RecurrenceFrequency::Minutely => "minute",
+        RecurrenceFrequency::Hourly => "hour",
+        RecurrenceFrequency::Daily => "day",
+        RecurrenceFrequency::Weekly => "week",
+        RecurrenceFrequency::Monthly => "month",
+        RecurrenceFrequency::Yearly => "year",
+    };
+    match interval {
+        0 | 1 => format!("every {unit}"),
+        n => format!("every {n} {unit}s"),
+    }
+}
+
+/// Describes the `BYDAY` part, narrowed down by `BYSETPOS` if any.
+fn describe_days(by_day: &[WeekDayNum], by_set_pos: &[i16]) -> String {
+    let mut days = by_day.to_vec();
+    days.sort_by_key(|d| (d.occurrence, weekday_index(d.day)));
+
+    let plain: Vec<_> = days
+        .iter()
+        .filter(|d| d.occurrence.is_none())
+        .map(|d| d.day)
+        .collect();
+    let group = match plain.as_slice() {
+        _ if plain.len() != days.len() => None,
+        [
+            WeekDay::Monday,
+            WeekDay::Tuesday,
+            WeekDay::Wednesday,
+            WeekDay::Thursday,
+            WeekDay::Friday,
+        ] => Some(("weekdays", "weekday")),
+        [WeekDay::Saturday, WeekDay::Sunday] => Some(("weekends", "weekend day")),
+        _ => None,
+    };
+
+    if !by_set_pos.is_empty() {
+        let positions: Vec<_> = by_set_pos.iter().map(|&p| ordinal(p.into())).collect();
+        let positions = positions.join(", ");
+        if let Some((_, single)) = group {
+            return format!("on the {positions} {single}");
+        }
+        let days: Vec<_> = days.iter().copied().map(describe_day).collect();
+        return format!("on the {positions} of {}", days.join(", "));
+    }
+
+    match group {
+        Some((plural, _)) => format!("on {plural}"),
+        None if days.iter().any(|d| d.occurrence.is_some()) => {
+            let days: Vec<_> = days.iter().copied().map(describe_day).collect();
+            format!("on the {}", days.join(", "))
+        }
+        None => {
+            let days: Vec<_> = days.iter().copied().map(describe_day).collect();
+            format!("on {}", days.join(", "))
+        }
+    }
+}
+
+fn describe_day(day: WeekDayNum) -> String {
+    let name = weekday_name(day.day);
+    match day.occurrence {
+        Some(n) => format!("{} {name}", ordinal(n.into())),
+        None => name.to_string(),
+    }
+}
+
+/// Describes the `BYHOUR`, `BYMINUTE` and `BYSECOND` parts.
+fn describe_times(rule: &ValueRecurrenceRule) -> Option<String> {
+    let list = |values: &[u8]| {
+        let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+        values.join(", ")
+    };
+
+    let mut parts = Vec::new();
+    match (rule.by_hour.as_slice(), rule.by_minute.as_slice()) {
+        ([], []) => {}
+        (hours, []) => parts.push(format!("at hour {}", list(hours))),
+        ([], minutes) => parts.push(format!("at minute {}", list(minutes))),
+        (hours, minutes) => {
+            let times: Vec<_> = hours
+                .iter()
+                .flat_map(|h| minutes.iter().map(move |m| format!("{h:02}:{m:02}")))
+                .collect();
+            parts.push(format!("at {}", times.join(", ")));
+        }
+    }
+    if !rule.by_second.is_empty() {
+        parts.push(format!("at second {}", list(&rule.by_second)));
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Formats a position such as `2` as "2nd", counting from the end for negative positions.
+fn ordinal(n: i32) -> String {
+    match n {
+        -1 => "last".to_string(),
+        n if n < 0 => format!("{} to last", ordinal(-n)),
+        n => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{n}{suffix}")
+        }
+    }
+}
+
+fn month_name(month: u8) -> &'static str {
+    usize::from(month)
+        .checked_sub(1)
+        .and_then(|i| MONTHS.get(i))
+        .copied()
+        .unwrap_or("?")
+}
+
+const fn weekday_name(day: WeekDay) -> &'static str {
+    match day {
+        WeekDay::Monday => "Mon",
+        WeekDay::Tuesday => "Tue",
+        WeekDay::Wednesday => "Wed",
+        WeekDay::Thursday => "Thu",
+        WeekDay::Friday => "Fri",
+        WeekDay::Saturday => "Sat",
+        WeekDay::Sunday => "Sun",
+    }
+}
+
+/// How an item repeats, as written by people, e.g. `weekly` or `every 2 weeks on mon,wed`.
+///
+/// It reads `daily`, `weekly`, `monthly`, `yearly` or `annually`, or `every` followed by an
+/// interval and a unit (`every 2 weeks`, `every other month`) or by days of the week
+/// (`every mon, wed`, `every weekday`). Either may end with `on` and the days it falls on, such
+/// as `on mon,wed`, `on the 2nd tue` or `on the 15th` for the monthly ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repeat {
+    /// The unit of the interval.
+    pub freq: RecurrenceFrequency,
+    /// The number of units between occurrences.
+    pub interval: u32,
+    /// The days of the week it falls on, derived from the start for weekly and monthly ones
+    /// without any.
+    pub days: Vec<WeekDayNum>,
+    /// The day of the month it falls on, negative from the end of the month, for monthly and
+    /// yearly ones.
+    pub month_day: Option<i8>,
+    /// When it stops repeating, if ever.
+    pub end: Option<RepeatEnd>,
+}
+
+/// When an item stops repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepeatEnd {
+    /// At this date, inclusive.
+    Until(LooseDateTime),
+    /// After this many occurrences.
+    Times(u32),
+}
+
+impl Repeat {
+    /// Repeats every `interval` units of `freq`, on the days of the start.
+    #[must_use]
+    pub fn every(freq: RecurrenceFrequency, interval: u32) -> Self {
+        Self {
+            freq,
+            interval,
+            days: Vec::new(),
+            month_day: None,
+            end: None,
+        }
+    }
+
+    /// The recurrence rule as written, without anything derived from a start.
+    #[must_use]
+    pub fn rule(&self) -> ValueRecurrenceRule {
+        ValueRecurrenceRule {
+            freq: self.freq,
+            until: None,
+            count: None,
+            interval: (self.interval > 1).then_some(self.interval),
+            by_second: Vec::new(),
+            by_minute: Vec::new(),
+            by_hour: Vec::new(),
+            by_month_day: self.month_day.into_iter().collect(),
+            by_year_day: Vec::new(),
+            by_week_no: Vec::new(),
+            by_month: Vec::new(),
+            by_day: self.days.clone(),
+            by_set_pos: Vec::new(),
+            wkst: None,
+        }
+    }
+
+    /// Compiles into the recurrence rule of an item starting at `start`.
+    ///
+    /// Weekly rules without days fall on the day of the week of the start, and monthly ones on
+    /// the day of the month of the start, or on its position in the month for days of the week
+    /// given without one, e.g. the 2nd Tue. Weeks start on `week_start`.
+    ///
+    /// # Errors
+    /// If the end is before the start, or the rule does not fit its frequency.
+    pub fn to_rule(
+        &self,
+        start: &LooseDateTime,
+        week_start: Weekday,
+    ) -> Result<ValueRecurrenceRule, String> {
+        let date = start.date();
+        let mut rule = self.rule();
+        let in_month = matches!(
+            self.freq,
+            RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly
+        );
+        if self.month_day.is_some() && !in_month {
+            return Err(MONTH_DAY_IN_MONTH_ONLY.to_string());
+        }
+        if self.month_day == Some(0) {
+            return Err("there is no day 0 of a month".to_string());
+        }
+        match self.freq {
+            RecurrenceFrequency::Weekly => {
+                if rule.by_day.is_empty() {
+                    rule.by_day.push(WeekDayNum {
+                        day: week_day(date.weekday()),
+                        occurrence: None,
+                    });
+                }
+                rule.wkst = Some(week_day(week_start));
+            }
+            RecurrenceFrequency::Monthly => {
+                let position = (date.day() - 1) / 7 + 1;
+                let position = if position == 5 { -1 } else { position };
+                for day in &mut rule.by_day {
+                    day.occurrence.get_or_insert(position);
+                }
+                if rule.by_day.is_empty() && rule.by_month_day.is_empty() {
+                    rule.by_month_day.push(date.day());
+                }
+            }
+            RecurrenceFrequency::Yearly if self.month_day.is_some() => {
+                rule.by_month.push(date.month().unsigned_abs());
+            }
+            _ => {}
+        }
+
+        match &self.end {
+            Some(RepeatEnd::Times(0)) => return Err("must repeat at least once".to_string()),
+            Some(RepeatEnd::Times(times)) => rule.count = Some(*times),
+            Some(RepeatEnd::Until(until)) => {
+                if until.date() < date {
+                    return Err(format!("ends on {} before the start {date}", until.date()));
+                }
+                rule.until = until_value(&until_of(until, start));
+            }
+            None => {}
+        }
+        Ok(rule)
+    }
+}
+
+/// The last moment of repeating until `until`, the end of the day for a date after a start with
+/// a time.
+fn until_of(until: &LooseDateTime, start: &LooseDateTime) -> LooseDateTime {
+    let LooseDateTime::DateOnly(date) = until else {
+        return until.clone();
+    };
+    let last = date.at(23, 59, 59, 0);
+    match start {
+        LooseDateTime::DateOnly(_) => until.clone(),
+        LooseDateTime::Floating(_) => LooseDateTime::Floating(last),
+        LooseDateTime::Local(zoned) => last
+            .to_zoned(zoned.time_zone().clone())
+            .map_or(LooseDateTime::Floating(last), LooseDateTime::Local),
+    }
+}
+
+impl FromStr for Repeat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim().to_lowercase();
+        let unreadable = |reason: &str| {
+            format!(
+                "cannot read {s:?} as a repetition, {reason}; try e.g. \"weekly\", \"every 2 weeks \
+                 on mon,wed\", \"monthly on the last fri\" or \"every other year\""
+            )
+        };
+
+        let (head, on) = match input.split_once(" on ") {
+            Some((head, on)) => (head.trim(), Some(on)),
+            None => (input.as_str(), None),
+        };
+        let mut repeat = match head {
+            "daily" => Self::every(RecurrenceFrequency::Daily, 1),
+            "weekly" => Self::every(RecurrenceFrequency::Weekly, 1),
+            "monthly" => Self::every(RecurrenceFrequency::Monthly, 1),
+            "yearly" | "annually" => Self::every(RecurrenceFrequency::Yearly, 1),
+            _ => {
+                let rest = head.strip_prefix("every ").ok_or_else(|| {
+                    unreadable("it starts with neither \"every\" nor a frequency")
+                })?;
+                every(rest.trim()).map_err(|reason| unreadable(&reason))?
+            }
+        };
+        if let Some(on) = on {
+            repeat_on(&mut repeat, on).map_err(|reason| unreadable(&reason))?;
+        }
+        Ok(repeat)
+    }
+}
+
+const MONTH_DAY_IN_MONTH_ONLY: &str = "a day of the month only applies to monthly and yearly ones";
+const POSITION_IN_MONTH_ONLY: &str =
+    "the position of a day in the month only applies to monthly ones";
+
+/// Reads what follows `every`: an interval and a unit, or days of the week.
+fn every(rest: &str) -> Result<Repeat, String> {
+    let (interval, unit) = match rest.split_once(' ') {
+        Some(("other", unit)) => (2, unit.trim()),
+        Some((n, unit)) if n.bytes().all(|b| b.is_ascii_digit()) => {
+            let n = n
+                .parse()
+                .map_err(|_| format!("{n} is too large an interval"))?;
+            (n, unit.trim())
+        }
+        _ => (1, rest),
+    };
+    if interval == 0 {
+        return Err("the interval must be at least 1".to_string());
+    }
+
+    let freq = match unit {
+        "day" | "days" => RecurrenceFrequency::Daily,
+        "week" | "weeks" => RecurrenceFrequency::Weekly,
+        "month" | "months" => RecurrenceFrequency::Monthly,
+        "year" | "years" => RecurrenceFrequency::Yearly,
+        _ if interval == 1 => {
+            let mut repeat = Repeat::every(RecurrenceFrequency::Weekly, 1);
+            repeat.days = week_days(unit)?;
+            if repeat.days.iter().any(|d| d.occurrence.is_some()) {
+                return Err(POSITION_IN_MONTH_ONLY.to_string());
+            }
+            return Ok(repeat);
+        }
+        _ => {
+            return Err(format!(
+                "{unit:?} is not a unit of days, weeks, months or years"
+            ));
+        }
+    };
+    Ok(Repeat::every(freq, interval))
+}
+
+/// Reads what follows `on`: days of the week, or a day of the month.
+fn repeat_on(repeat: &mut Repeat, on: &str) -> Result<(), String> {
+    let on = on.trim();
+    let on = on.strip_prefix("the ").unwrap_or(on);
+    let in_month = matches!(
+        repeat.freq,
+        RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly
+    );
+    if let Some(day) = month_day(on) {
+        if !in_month {
+            return Err(MONTH_DAY_IN_MONTH_ONLY.to_string());
+        }
+        repeat.month_day = Some(day);
+        return Ok(());
+    }
+
+    let days = week_days(on)?;
+    if !in_month && days.iter().any(|d| d.occurrence.is_some()) {
+        return Err(POSITION_IN_MONTH_ONLY.to_string());
+    }
+    repeat.days = days;
+    Ok(())
+}
+
+/// Reads a list of days of the week such as `mon, wed and fri`, `weekdays` or `2nd tue`.
+fn week_days(list: &str) -> Result<Vec<WeekDayNum>, String> {
+    const WEEKDAYS: [WeekDay; 5] = [
+        WeekDay::Monday,
+        WeekDay::Tuesday,
+        WeekDay::Wednesday,
+        WeekDay::Thursday,
+        WeekDay::Friday,
+    ];
+    let plain = |day| WeekDayNum {
+        day,
+        occurrence: None,
+    };
+
+    let mut days = Vec::new();
+    let mut position = None;
+    for word in list.split([',', ' ']).filter(|w| !w.is_empty()) {
+        match word {
+            "and" | "the" => continue,
+            "weekday" | "weekdays" | "workday" | "workdays" => days.extend(WEEKDAYS.map(plain)),
+            "weekend" | "weekends" => days.extend([WeekDay::Saturday, WeekDay::Sunday].map(plain)),
+            _ => {
+                if let Some(n) = position_in_month(word) {
+                    position = Some(n);
+                    continue;
+                }
+                let day = week_day_named(word)
+                    .ok_or_else(|| format!("{word:?} is not a day of the week"))?;
+                days.push(WeekDayNum {
+                    day,
+                    occurrence: position.take(),
+                });
+            }
+        }
+        if position.is_some() {
+            return Err(format!("{word:?} does not take a position in the month"));
+        }
+    }
+    if position.is_some() {
+        return Err("a position in the month is not followed by a day of the week".to_string());
+    }
+    if days.is_empty() {
+        return Err("no days of the week are given".to_string());
+    }
+    Ok(days)
+}
+
+/// Reads a day of the month such as `15`, `15th` or `last day`.
+fn month_day(word: &str) -> Option<i8> {
+    if word == "last day" {
+        return Some(-1);
+    }
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = word.get(digits.len()..)?;
+    if !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+/// Reads a position of a day in the month such as `2nd`, `second` or `last`.
+fn position_in_month(word: &str) -> Option<i8> {
+    Some(match word {
+        "1st" | "first" => 1,
+        "2nd" | "second" => 2,
+        "3rd" | "third" => 3,
+        "4th" | "fourth" => 4,
+        "5th" | "fifth" => 5,
+        "last" => -1,
+        _ => return None,
+    })
+}
+
+fn week_day_named(word: &str) -> Option<WeekDay> {
+    Some(match word {
+        "monday" | "mon" | "mo" => WeekDay::Monday,
+        "tuesday" | "tue" | "tu" => WeekDay::Tuesday,
+        "wednesday" | "wed" | "we" => WeekDay::Wednesday,
+        "thursday" | "thu" | "th" => WeekDay::Thursday,
+        "friday" | "fri" | "fr" => WeekDay::Friday,
+        "saturday" | "sat" | "sa" => WeekDay::Saturday,
+        "sunday" | "sun" | "su" => WeekDay::Sunday,
+        _ => return None,
+    })
+}
+
+const fn week_day(day: Weekday) -> WeekDay {
+    match day {
+        Weekday::Monday => WeekDay::Monday,
+        Weekday::Tuesday => WeekDay::Tuesday,
+        Weekday::Wednesday => WeekDay::Wednesday,
+        Weekday::Thursday => WeekDay::Thursday,
+        Weekday::Friday => WeekDay::Friday,
+        Weekday::Saturday => WeekDay::Saturday,
+        Weekday::Sunday => WeekDay::Sunday,
+    }
+}
+
+/// Days since Monday, so that weeks are listed starting on Monday.
+const fn weekday_index(day: WeekDay) -> u8 {
+    match day {
+        WeekDay::Monday => 0,
+        WeekDay::Tuesday => 1,
+        WeekDay::Wednesday => 2,
+        WeekDay::Thursday => 3,
+        WeekDay::Friday => 4,
+        WeekDay::Saturday => 5,
+        WeekDay::Sunday => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+
+    fn describe(rule: &str) -> String {
+        let rule: ValueRecurrenceRule = rule.parse().unwrap();
+        describe_recurrence(&rule, date(2026, 1, 1))
+    }
+
+    #[test]
+    fn describes_frequency_and_interval() {
+        assert_eq!(describe("FREQ=DAILY"), "every day");
+        assert_eq!(describe("FREQ=WEEKLY;INTERVAL=1"), "every week");
+        assert_eq!(describe("FREQ=WEEKLY;INTERVAL=2"), "every 2 weeks");
+        assert_eq!(describe("FREQ=YEARLY;INTERVAL=10"), "every 10 years");
+        assert_eq!(describe("FREQ=HOURLY;INTERVAL=3"), "every 3 hours");
+    }
+
+    #[test]
+    fn describes_week_days() {
+        assert_eq!(
+            describe("FREQ=WEEKLY;INTERVAL=2;BYDAY=WE,MO"),
+            "every 2 weeks on Mon, Wed"
+        );
+        assert_eq!(describe("FREQ=WEEKLY;BYDAY=SU"), "every week on Sun");
+        assert_eq!(
+            describe("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR"),
+            "every day on weekdays"
+        );
+        assert_eq!(
+            describe("FREQ=WEEKLY;BYDAY=SA,SU"),
+            "every week on weekends"
+        );
+    }
+
+    #[test]
+    fn describes_nth_week_days() {
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYDAY=1MO"),
+            "every month on the 1st Mon"
+        );
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYDAY=-1FR"),
+            "every month on the last Fri"
+        );
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYDAY=-2FR,3TH"),
+            "every month on the 2nd to last Fri, 3rd Thu"
+        );
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1"),
+            "every month on the last weekday"
+        );
+    }
+
+    #[test]
+    fn describes_month_and_year_parts() {
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYMONTHDAY=1,15"),
+            "every month on the 1st, 15th"
+        );
+        assert_eq!(
+            describe("FREQ=MONTHLY;BYMONTHDAY=-1"),
+            "every month on the last day"
+        );
+        assert_eq!(
+            describe("FREQ=YEARLY;BYMONTH=1,6;BYMONTHDAY=22"),
+            "every year in Jan, Jun on the 22nd"
+        );
+        assert_eq!(
+            describe("FREQ=YEARLY;BYYEARDAY=100"),
+            "every year on the 100th day of the year"
+        );
+        assert_eq!(
+            describe("FREQ=DAILY;BYHOUR=9,17;BYMINUTE=30"),
+            "every day at 09:30, 17:30"
+        );
+    }
+
+    #[test]
+    fn describes_count_and_until() {
+        assert_eq!(describe("FREQ=DAILY;COUNT=1"), "every day once");
+        assert_eq!(
+            describe("FREQ=DAILY;COUNT=5"),
+            "every day for 5 occurrences"
+        );
+        assert_eq!(
+            describe("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20260630"),
+            "every 2 weeks on Mon, Wed until Jun 30"
+        );
+        assert_eq!(
+            describe("FREQ=WEEKLY;UNTIL=20270105T000000Z"),
+            "every week until Jan 5, 2027"
+        );
+    }
+
+    fn repeat(input: &str) -> String {
+        input.parse::<Repeat>().unwrap().rule().to_string()
+    }
+
+    fn compile(input: &str, start: &LooseDateTime, end: Option<RepeatEnd>) -> String {
+        let mut repeat: Repeat = input.parse().unwrap();
+        repeat.end = end;
+        repeat.to_rule(start, Weekday::Sunday).unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_repeats() {
+        assert_eq!(repeat("daily"), "FREQ=DAILY");
+        assert_eq!(repeat(" Weekly "), "FREQ=WEEKLY");
+        assert_eq!(repeat("annually"), "FREQ=YEARLY");
+        assert_eq!(repeat("every 3 days"), "FREQ=DAILY;INTERVAL=3");
+        assert_eq!(repeat("every other month"), "FREQ=MONTHLY;INTERVAL=2");
+        assert_eq!(
+            repeat("every 2 weeks on mon,wed"),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE"
+        );
+        assert_eq!(repeat("every monday and friday"), "FREQ=WEEKLY;BYDAY=MO,FR");
+        assert_eq!(repeat("every weekday"), "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR");
+        assert_eq!(
+            repeat("monthly on the 2nd tue, last fri"),
+            "FREQ=MONTHLY;BYDAY=2TU,-1FR"
+        );
+        assert_eq!(repeat("monthly on the 15th"), "FREQ=MONTHLY;BYMONTHDAY=15");
+        assert_eq!(
+            repeat("every 3 months on 2"),
+            "FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=2"
+        );
+        assert_eq!(
+            repeat("monthly on the last day"),
+            "FREQ=MONTHLY;BYMONTHDAY=-1"
+        );
+    }
+
+    #[test]
+    fn rejects_unreadable_repeats_with_examples() {
+        for input in [
+            "sometimes",
+            "every",
+            "every 0 days",
+            "every 2 mondays",
+            "every fortnight",
+            "weekly on the 2nd tue",
+            "daily on the 15th",
+            "every 3rd fri",
+        ] {
+            let err = input.parse::<Repeat>().unwrap_err();
+            assert!(err.contains(&format!("{input:?}")), "{err}");
+            assert!(err.contains("every 2 weeks on mon,wed"), "{err}");
+        }
+    }
+
+    #[test]
+    fn derives_days_from_the_start() {
+        // A Wednesday, in the 2nd week of the month
+        let start = LooseDateTime::Floating(date(2025, 1, 8).at(9, 0, 0, 0));
+        assert_eq!(
+            compile("weekly", &start, None),
+            "FREQ=WEEKLY;BYDAY=WE;WKST=SU"
+        );
+        assert_eq!(
+            compile("every 2 weeks on mon,wed", &start, None),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;WKST=SU"
+        );
+        assert_eq!(
+            compile("monthly", &start, None),
+            "FREQ=MONTHLY;BYMONTHDAY=8"
+        );
+        assert_eq!(
+            compile("monthly on wed", &start, None),
+            "FREQ=MONTHLY;BYDAY=2WE"
+        );
+        assert_eq!(compile("yearly", &start, None), "FREQ=YEARLY");
+
+        let late = LooseDateTime::DateOnly(date(2025, 1, 29));
+        assert_eq!(
+            compile("monthly on wed", &late, None),
+            "FREQ=MONTHLY;BYDAY=-1WE"
+        );
+    }
+
+    #[test]
+    fn compiles_the_day_of_the_month() {
+        let start = LooseDateTime::DateOnly(date(2025, 3, 1));
+        let mut repeat: Repeat = "monthly".parse().unwrap();
+        repeat.month_day = Some(15);
+        let rule = repeat.to_rule(&start, Weekday::Monday).unwrap();
+        assert_eq!(rule.to_string(), "FREQ=MONTHLY;BYMONTHDAY=15");
+
+        repeat.freq = RecurrenceFrequency::Yearly;
+        let rule = repeat.to_rule(&start, Weekday::Monday).unwrap();
+        assert_eq!(rule.to_string(), "FREQ=YEARLY;BYMONTHDAY=15;BYMONTH=3");
+
+        repeat.freq = RecurrenceFrequency::Weekly;
+        let err = repeat.to_rule(&start, Weekday::Monday).unwrap_err();
+        assert!(err.contains("monthly and yearly"), "{err}");
+    }
+
+    #[test]
+    fn compiles_the_end() {
+        let start = LooseDateTime::DateOnly(date(2025, 1, 8));
+        assert_eq!(
+            compile("daily", &start, Some(RepeatEnd::Times(10))),
+            "FREQ=DAILY;COUNT=10"
+        );
+        let until = RepeatEnd::Until(LooseDateTime::DateOnly(date(2025, 12, 31)));
+        assert_eq!(
+            compile("daily", &start, Some(until.clone())),
+            "FREQ=DAILY;UNTIL=20251231T000000"
+        );
+
+        // The whole last day of a timed start in a time zone, in UTC
+        let tz = jiff::tz::TimeZone::get("Asia/Shanghai").unwrap();
+        let zoned = date(2025, 1, 8).at(9, 0, 0, 0).to_zoned(tz).unwrap();
+        assert_eq!(
+            compile("daily", &LooseDateTime::Local(zoned), Some(until)),
+            "FREQ=DAILY;UNTIL=20251231T155959Z"
+        );
+
+        let mut repeat: Repeat = "daily".parse().unwrap();
+        repeat.end = Some(RepeatEnd::Until(LooseDateTime::DateOnly(date(2025, 1, 1))));
+        let err = repeat.to_rule(&start, Weekday::Monday).unwrap_err();
+        assert!(err.contains("before the start"), "{err}");
+        repeat.end = Some(RepeatEnd::Times(0));
+        assert!(repeat.to_rule(&start, Weekday::Monday).is_err());
+    }
+
+    #[test]
+    fn describes_compiled_repeats() {
+        let start = LooseDateTime::DateOnly(date(2026, 1, 8));
+        let mut repeat: Repeat = "every 2 weeks on mon,wed".parse().unwrap();
+        repeat.end = Some(RepeatEnd::Until(LooseDateTime::DateOnly(date(2026, 6, 30))));
+        let rule = repeat.to_rule(&start, Weekday::Monday).unwrap();
+        assert_eq!(
+            describe_recurrence(&rule, date(2026, 1, 1)),
+            "every 2 weeks on Mon, Wed until Jun 30"
+        );
+    }
+
+    #[test]
+    fn formats_ordinals() {
+        let ordinals: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 101, -1, -2]
+            .into_iter()
+            .map(ordinal)
+            .collect();
+        assert_eq!(
+            ordinals,
+            [
+                "1st",
+                "2nd",
+                "3rd",
+                "4th",
+                "11th",
+                "12th",
+                "13th",
+                "21st",
+                "22nd",
+                "101st",
+                "last",
+                "2nd to last"
+            ]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolving the identifiers typed by users, which may be a short ID, a UID or a prefix of a UID,
+//! to the items they refer to.
+
+use std::num::NonZeroU32;
+
+use crate::{Kind, LooseDateTime};
+
+/// The shortest UID prefix matched against the UIDs of items, shorter ones are too likely to
+/// match unrelated items.
+pub const MIN_UID_PREFIX_LEN: usize = 6;
+
+/// How an identifier matched an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IdMatch {
+    /// The identifier is the short ID of the item.
+    ShortId,
+    /// The identifier is the UID of the item.
+    Uid,
+    /// The identifier is a prefix of the UID of the item.
+    UidPrefix,
+}
+
+/// An item an identifier may refer to, with what tells it apart from the other candidates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdCandidate {
+    /// The UID of the item.
+    pub uid: String,
+    /// The short ID of the item.
+    pub short_id: NonZeroU32,
+    /// Whether the item is an event or a todo.
+    pub kind: Kind,
+    /// The summary of the item.
+    pub summary: String,
+    /// The start of an event, or the due of a todo.
+    pub date: Option<LooseDateTime>,
+    /// How the identifier matched the item.
+    pub matched: IdMatch,
+}
+
+/// Narrows the candidates of an identifier to those of the hinted kind, then to the exact
+/// matches if any, since a prefix only stands for the items nothing else is named after.
+///
+/// Items matched more than once are kept once, by their closest match.
+pub(crate) fn narrow(
+    mut candidates: Vec<IdCandidate>,
+    kind_hint: Option<Kind>,
+) -> Vec<IdCandidate> {
+    if let Some(kind) = kind_hint {
+        candidates.retain(|c| c.kind == kind);
+    }
+
+    // Stable, so candidates matched the same way keep their order
+    candidates.sort_by_key(|c| c.matched);
+    let mut seen = Vec::with_capacity(candidates.len());
+    candidates.retain(|c| {
+        let first = !seen.contains(&c.uid);
+        if first {
+            seen.push(c.uid.clone());
+        }
+        first
+    });
+
+    if candidates.iter().any(|c| c.matched != IdMatch::UidPrefix) {
+        candidates.retain(|c| c.matched != IdMatch::UidPrefix);
+    }
+    candidates
+}
+
+/// Whether the identifier is long enough to be matched as a UID prefix.
+pub(crate) fn is_uid_prefix(id: &str) -> bool {
+    id.chars().count() >= MIN_UID_PREFIX_LEN
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(uid: &str, short_id: u32, kind: Kind, matched: IdMatch) -> IdCandidate {
+        IdCandidate {
+            uid: uid.to_string(),
+            short_id: NonZeroU32::new(short_id).unwrap(),
+            kind,
+            summary: format!("Summary of {uid}"),
+            date: None,
+            matched,
+        }
+    }
+
+    fn uids(candidates: &[IdCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.uid.as_str()).collect()
+    }
+
+    #[test]
+    fn narrow_keeps_no_candidates() {
+        assert!(narrow(Vec::new(), None).is_empty());
+        assert!(narrow(Vec::new(), Some(Kind::Todo)).is_empty());
+    }
+
+    #[test]
+    fn narrow_keeps_a_single_candidate_of_any_match() {
+        for matched in [IdMatch::ShortId, IdMatch::Uid, IdMatch::UidPrefix] {
+            let narrowed = narrow(vec![candidate("a", 1, Kind::Todo, matched)], None);
+            assert_eq!(uids(&narrowed), ["a"], "{matched:?}");
+        }
+    }
+
+    #[test]
+    fn narrow_keeps_short_id_and_uid_matches_of_different_items() {
+        let narrowed = narrow(
+            vec![
+                candidate("12", 3, Kind::Todo, IdMatch::Uid),
+                candidate("abc", 12, Kind::Todo, IdMatch::ShortId),
+            ],
+            None,
+        );
+        assert_eq!(uids(&narrowed), ["abc", "12"]);
+    }
+
+    #[test]
+    fn narrow_uses_kind_hint_before_declaring_ambiguity() {
+        let candidates = vec![
+            candidate("abc", 12, Kind::Event, IdMatch::ShortId),
+            candidate("12", 3, Kind::Todo, IdMatch::Uid),
+        ];
+        assert_eq!(uids(&narrow(candidates.clone(), None)), ["abc", "12"]);
+        assert_eq!(uids(&narrow(candidates.clone(), Some(Kind::Todo))), ["12"]);
+        assert_eq!(uids(&narrow(candidates, Some(Kind::Event))), ["abc"]);
+    }
+
+    #[test]
+    fn narrow_drops_everything_of_another_kind() {
+        let candidates = vec![
+            candidate("abc", 12, Kind::Event, IdMatch::ShortId),
+            candidate("abcdef-1", 1, Kind::Event, IdMatch::UidPrefix),
+        ];
+        assert!(narrow(candidates, Some(Kind::Todo)).is_empty());
+    }
+
+    #[test]
+    fn narrow_prefers_exact_matches_to_prefixes() {
+        let narrowed = narrow(
+            vec![
+                candidate("abcdef-1", 1, Kind::Todo, IdMatch::UidPrefix),
+                candidate("abcdef", 2, Kind::Todo, IdMatch::Uid),
+                candidate("abcdef-2", 3, Kind::Event, IdMatch::UidPrefix),
+            ],
+            None,
+        );
+        assert_eq!(uids(&narrowed), ["abcdef"]);
+    }
+
+    #[test]
+    fn narrow_falls_back_to_prefixes_of_the_hinted_kind() {
+        let narrowed = narrow(
+            vec![
+                candidate("abcdef", 2, Kind::Event, IdMatch::Uid),
+                candidate("abcdef-1", 1, Kind::Todo, IdMatch::UidPrefix),
+            ],
+            Some(Kind::Todo),
+        );
+        assert_eq!(uids(&narrowed), ["abcdef-1"]);
+    }
+
+    #[test]
+    fn narrow_keeps_all_matching_prefixes() {
+        let narrowed = narrow(
+            vec![
+                candidate("abcdef-1", 1, Kind::Todo, IdMatch::UidPrefix),
+                candidate("abcdef-2", 2, Kind::Event, IdMatch::UidPrefix),
+                candidate("abcdef-3", 3, Kind::Todo, IdMatch::UidPrefix),
+            ],
+            None,
+        );
+        assert_eq!(uids(&narrowed), ["abcdef-1", "abcdef-2", "abcdef-3"]);
+    }
+
+    #[test]
+    fn narrow_keeps_items_matched_twice_once() {
+        let narrowed = narrow(
+            vec![
+                candidate("123456", 1, Kind::Todo, IdMatch::UidPrefix),
+                candidate("123456", 1, Kind::Todo, IdMatch::Uid),
+                candidate("123456", 1, Kind::Todo, IdMatch::ShortId),
+            ],
+            None,
+        );
+        assert_eq!(narrowed.len(), 1);
+        assert_eq!(narrowed.first().unwrap().matched, IdMatch::ShortId);
+    }
+
+    #[test]
+    fn uid_prefix_needs_min_len_in_characters() {
+        assert!(!is_uid_prefix(""));
+        assert!(!is_uid_prefix("abcde"));
+        assert!(is_uid_prefix("abcdef"));
+        assert!(is_uid_prefix("日程表日程表"));
+        assert!(!is_uid_prefix("日程表日程"));
+    }
+}
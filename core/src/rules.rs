@@ -0,0 +1,432 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Categorization rules, from the `[[core.rules]]` sections.
+//!
+//! Rules are evaluated in order when an item is cached. The first rule that matches stops the
+//! evaluation unless it has `continue = true`, in which case the actions of the rules matching
+//! after it are added to its own. What the rules add is kept in the cache next to the properties
+//! of the item, never written to its store, so it goes away with the rule on
+//! [`Aim::apply_rules`](crate::Aim::apply_rules).
+
+use std::collections::HashSet;
+
+use regex::Regex;
+
+use crate::{Kind, Priority};
+
+/// The categorization rules of the config, in the order they are evaluated.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(try_from = "Vec<RuleDef>")]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    /// The rules, in the order they are evaluated.
+    #[must_use]
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Whether there are no rules.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// What the rules add to the item.
+    #[must_use]
+    pub fn evaluate(&self, subject: &RuleSubject<'_>) -> RuleOutcome {
+        self.trace(subject).1
+    }
+
+    /// Which rules fire for the item, with what they add to it, without applying anything.
+    #[must_use]
+    pub fn trace(&self, subject: &RuleSubject<'_>) -> (Vec<RuleTrace>, RuleOutcome) {
+        let mut outcome = RuleOutcome::default();
+        let mut traces = Vec::with_capacity(self.rules.len());
+        let mut stopped = false;
+        for rule in &self.rules {
+            let verdict = if stopped {
+                RuleVerdict::Unreached
+            } else if rule.conditions.matches(subject) {
+                outcome.add(rule, subject.kind);
+                stopped = !rule.continues;
+                RuleVerdict::Fired
+            } else {
+                RuleVerdict::NotMatched
+            };
+            traces.push(RuleTrace {
+                name: rule.name.clone(),
+                verdict,
+            });
+        }
+        (traces, outcome)
+    }
+}
+
+impl TryFrom<Vec<RuleDef>> for Rules {
+    type Error = String;
+
+    fn try_from(defs: Vec<RuleDef>) -> Result<Self, Self::Error> {
+        let mut names = HashSet::new();
+        let mut rules = Vec::with_capacity(defs.len());
+        for def in defs {
+            let name = def.name.trim().to_string();
+            if name.is_empty() {
+                return Err("Invalid rule: a name is required".to_string());
+            }
+            if !names.insert(name.clone()) {
+                return Err(format!(
+                    "Invalid rule '{name}': the name is used by another rule"
+                ));
+            }
+            let summary = def
+                .conditions
+                .summary
+                .map(|s| Regex::new(&s))
+                .transpose()
+                .map_err(|e| format!("Invalid rule '{name}': bad summary regex: {e}"))?;
+            rules.push(Rule {
+                name,
+                conditions: RuleConditions {
+                    summary,
+                    organizer_domain: def
+                        .conditions
+                        .organizer_domain
+                        .map(|d| d.trim().trim_start_matches('@').to_lowercase()),
+                    calendar: def.conditions.calendar,
+                    location: def.conditions.location.map(|l| l.to_lowercase()),
+                },
+                actions: def.actions,
+                continues: def.continues,
+            });
+        }
+        Ok(Self { rules })
+    }
+}
+
+/// A rule as written in the config.
+#[derive(Debug, serde::Deserialize)]
+struct RuleDef {
+    name: String,
+    #[serde(rename = "match", default)]
+    conditions: RuleConditionsDef,
+    #[serde(default)]
+    actions: RuleActions,
+    #[serde(rename = "continue", default)]
+    continues: bool,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct RuleConditionsDef {
+    summary: Option<String>,
+    organizer_domain: Option<String>,
+    calendar: Option<String>,
+    location: Option<String>,
+}
+
+/// A categorization rule, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct Rule {
+    /// The name of the rule, unique among the rules.
+    pub name: String,
+    /// What the item must match, all of them if several are given.
+    pub conditions: RuleConditions,
+    /// What the rule adds to the items it matches.
+    pub actions: RuleActions,
+    /// Whether the rules after this one are evaluated as well when it matches.
+    pub continues: bool,
+}
+
+/// What an item must match for a rule to fire, from the `match` table of the rule.
+///
+/// A rule without any condition matches every item.
+#[derive(Debug, Clone, Default)]
+pub struct RuleConditions {
+    /// A regex the summary must match.
+    pub summary: Option<Regex>,
+    /// The domain of the organizer, or a parent domain of it, in lowercase.
+    pub organizer_domain: Option<String>,
+    /// The ID of the calendar the item belongs to.
+    pub calendar: Option<String>,
+    /// Text the location must contain, in lowercase, ignoring case.
+    pub location: Option<String>,
+}
+
+impl RuleConditions {
+    fn matches(&self, subject: &RuleSubject<'_>) -> bool {
+        self.summary
+            .as_ref()
+            .is_none_or(|re| re.is_match(subject.summary))
+            && self
+                .organizer_domain
+                .as_ref()
+                .is_none_or(|domain| organizer_in_domain(subject.organizer, domain))
+            && self
+                .calendar
+                .as_ref()
+                .is_none_or(|calendar| calendar == subject.calendar_id)
+            && self
+                .location
+                .as_ref()
+                .is_none_or(|text| subject.location.to_lowercase().contains(text))
+    }
+}
+
+/// Whether the address of the organizer, such as `mailto:alice@mail.example.com`, is in the
+/// domain, such as `example.com`.
+fn organizer_in_domain(organizer: &str, domain: &str) -> bool {
+    let Some((_, host)) = organizer.rsplit_once('@') else {
+        return false;
+    };
+    let host = host.trim().to_lowercase();
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.'))
+}
+
+/// What a rule adds to the items it matches, from the `actions` table of the rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct RuleActions {
+    /// Categories to add.
+    pub categories: Vec<String>,
+    /// A color tag, such as `"#1e90ff"`.
+    pub color: Option<String>,
+    /// The lowest priority of todos, raising the ones below it or without any.
+    pub priority: Option<Priority>,
+    /// Whether events are transparent, not blocking time.
+    pub transparent: bool,
+}
+
+/// The properties of an item that rules match on.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleSubject<'a> {
+    /// Whether the item is an event or a todo.
+    pub kind: Kind,
+    /// The summary of the item.
+    pub summary: &'a str,
+    /// The ID of the calendar the item belongs to.
+    pub calendar_id: &'a str,
+    /// The location of the item, empty if unset.
+    pub location: &'a str,
+    /// The address of the organizer, such as `mailto:alice@example.com`, empty if unset.
+    pub organizer: &'a str,
+}
+
+/// What the rules add to an item.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct RuleOutcome {
+    /// Categories added to the ones of the item, in the order of the rules.
+    pub categories: Vec<String>,
+    /// The color tag of the first rule setting one.
+    pub color: Option<String>,
+    /// The lowest priority of a todo, the highest of the rules setting one.
+    pub priority: Priority,
+    /// Whether an event is transparent.
+    pub transparent: bool,
+}
+
+impl RuleOutcome {
+    fn add(&mut self, rule: &Rule, kind: Kind) {
+        let actions = &rule.actions;
+        for category in &actions.categories {
+            if !self.categories.contains(category) {
+                self.categories.push(category.clone());
+            }
+        }
+        if self.color.is_none() {
+            self.color.clone_from(&actions.color);
+        }
+        match kind {
+            Kind::Todo => {
+                if let Some(floor) = actions.priority {
+                    self.priority = raise_priority(self.priority, floor);
+                }
+            }
+            Kind::Event => self.transparent |= actions.transparent,
+        }
+    }
+}
+
+/// The priority raised to at least `floor`, where 1 is the highest and none the lowest.
+pub(crate) fn raise_priority(priority: Priority, floor: Priority) -> Priority {
+    let (value, floor_value): (u8, u8) = (priority.into(), floor.into());
+    if floor_value != 0 && (value == 0 || value > floor_value) {
+        floor
+    } else {
+        priority
+    }
+}
+
+/// Outcome of applying the rules again, see [`Aim::apply_rules`](crate::Aim::apply_rules).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RulesReport {
+    /// Number of events whose categorization changed.
+    pub events: u64,
+    /// Number of todos whose categorization changed.
+    pub todos: u64,
+}
+
+/// A dry run of the rules for an item, see [`Aim::test_rules`](crate::Aim::test_rules).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTest {
+    /// The UID of the item.
+    pub uid: String,
+    /// Whether the item is an event or a todo.
+    pub kind: Kind,
+    /// The summary of the item.
+    pub summary: String,
+    /// Whether each rule fired, in the order they are evaluated.
+    pub traces: Vec<RuleTrace>,
+    /// What the rules add to the item.
+    pub outcome: RuleOutcome,
+}
+
+/// Whether a rule fired for an item, see [`Rules::trace`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RuleTrace {
+    /// The name of the rule.
+    pub name: String,
+    /// Whether it fired.
+    pub verdict: RuleVerdict,
+}
+
+/// Whether a rule fired for an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleVerdict {
+    /// The item matches the rule, which added its actions.
+    Fired,
+    /// The item does not match the rule.
+    NotMatched,
+    /// An earlier rule matched without `continue = true`, so this one was not evaluated.
+    Unreached,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml: &str) -> Result<Rules, toml::de::Error> {
+        #[derive(Debug, serde::Deserialize)]
+        struct Wrapper {
+            rules: Rules,
+        }
+        toml::from_str::<Wrapper>(toml).map(|w| w.rules)
+    }
+
+    fn event<'a>(summary: &'a str, organizer: &'a str) -> RuleSubject<'a> {
+        RuleSubject {
+            kind: Kind::Event,
+            summary,
+            calendar_id: "work",
+            location: "Room 4.2, HQ",
+            organizer,
+        }
+    }
+
+    const RULES: &str = r##"
+[[rules]]
+name = "standups"
+match = { summary = "(?i)stand-?up" }
+actions = { categories = ["meetings"], color = "#1e90ff" }
+continue = true
+
+[[rules]]
+name = "vendors"
+match = { organizer_domain = "vendor.com" }
+actions = { categories = ["external"], transparent = true }
+
+[[rules]]
+name = "everything at work"
+match = { calendar = "work", location = "hq" }
+actions = { categories = ["work"], color = "#ff0000", priority = 3 }
+"##;
+
+    #[test]
+    fn rules_first_match_wins_unless_continue() {
+        let rules = rules(RULES).unwrap();
+
+        let subject = event("Daily Standup", "mailto:bob@mail.vendor.com");
+        let (traces, outcome) = rules.trace(&subject);
+        let verdicts: Vec<_> = traces.iter().map(|t| t.verdict).collect();
+        assert_eq!(
+            verdicts,
+            [
+                RuleVerdict::Fired,
+                RuleVerdict::Fired,
+                RuleVerdict::Unreached
+            ]
+        );
+        assert_eq!(outcome.categories, ["meetings", "external"]);
+        assert_eq!(outcome.color.as_deref(), Some("#1e90ff"));
+        assert!(outcome.transparent);
+
+        let outcome = rules.evaluate(&event("Planning", "mailto:alice@example.com"));
+        assert_eq!(outcome.categories, ["work"]);
+        assert_eq!(outcome.color.as_deref(), Some("#ff0000"));
+        // Priorities are for todos only
+        assert_eq!(outcome.priority, Priority::None);
+    }
+
+    #[test]
+    fn rules_match_all_conditions() {
+        let rules = rules(RULES).unwrap();
+        let subject = RuleSubject {
+            calendar_id: "home",
+            ..event("Planning", "mailto:alice@notvendor.com")
+        };
+        assert_eq!(rules.evaluate(&subject), RuleOutcome::default());
+
+        let subject = RuleSubject {
+            kind: Kind::Todo,
+            location: "",
+            ..event("Planning", "")
+        };
+        assert_eq!(rules.evaluate(&subject), RuleOutcome::default());
+    }
+
+    #[test]
+    fn rules_raise_priority_of_todos() {
+        let rules = rules(RULES).unwrap();
+        let subject = RuleSubject {
+            kind: Kind::Todo,
+            ..event("Review", "")
+        };
+        let outcome = rules.evaluate(&subject);
+        assert_eq!(outcome.priority, Priority::P3);
+        assert!(!outcome.transparent);
+
+        assert_eq!(raise_priority(Priority::None, Priority::P3), Priority::P3);
+        assert_eq!(raise_priority(Priority::P8, Priority::P3), Priority::P3);
+        assert_eq!(raise_priority(Priority::P1, Priority::P3), Priority::P1);
+        assert_eq!(raise_priority(Priority::P5, Priority::None), Priority::P5);
+    }
+
+    #[test]
+    fn rules_report_invalid_regex_with_rule_name() {
+        let err = rules(
+            r#"
+[[rules]]
+name = "broken"
+match = { summary = "(unclosed" }
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid rule 'broken'"), "{err}");
+
+        let err = rules("[[rules]]\nname = \"a\"\n[[rules]]\nname = \"a\"\n").unwrap_err();
+        assert!(err.to_string().contains("used by another rule"), "{err}");
+    }
+
+    #[test]
+    fn rules_default_to_none() {
+        let rules = rules("rules = []").unwrap();
+        assert!(rules.is_empty());
+    }
+}
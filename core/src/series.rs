@@ -0,0 +1,1009 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Occurrences of recurring series, and edits of an occurrence along with the ones after it.
+//!
+//! Such an edit is written either by splitting the series in two at the occurrence, which any
+//! client understands, or as an override with `RECURRENCE-ID;RANGE=THISANDFUTURE` kept next to
+//! the series, see RFC 5545 section 3.8.4.4.
+
+use aimcal_ical::{
+    self as ical, DateTimeProperty, DtEnd, DtStamp, DtStart, Due, ExDate, Parameter, Property,
+    RDate, RDateValue, RecurrenceFrequency, RecurrenceId, RecurrenceIdRange, StringStorage, Uid,
+    VEvent, VTodo, Value, ValueRecurrenceRule, ValueText, ValueTime, XNameProperty,
+    ops::{DateRange, RRuleExt},
+    property::RRule,
+};
+use jiff::civil::{self, Date};
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, ToSpan, Zoned};
+
+use crate::event::X_AIM_SPLIT_FROM;
+use crate::{AimError, EventPatch, LooseDateTime, RecurFrom, RecurrenceDateEdit};
+
+/// How an edit of an occurrence and the occurrences after it is written to the store, see
+/// [`Aim::update_event_from`](crate::Aim::update_event_from).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ThisAndFuture {
+    /// End the series before the occurrence, and start a new series from it.
+    #[default]
+    Split,
+    /// Add an override with `RECURRENCE-ID;RANGE=THISANDFUTURE` to the series, for stores and
+    /// clients that support it.
+    Override,
+}
+
+/// The recurrence properties of an event or todo.
+pub(crate) struct Recurrence<'a> {
+    /// The date the recurrence is anchored on, the `DTSTART`, or the `DUE` of a todo without it.
+    pub(crate) anchor: Option<LooseDateTime>,
+    pub(crate) rrule: Option<&'a RRule<String>>,
+    pub(crate) rdates: &'a [RDate<String>],
+    pub(crate) ex_dates: &'a [ExDate<String>],
+}
+
+impl<'a> Recurrence<'a> {
+    pub(crate) fn of_event(event: &'a VEvent<String>) -> Self {
+        Self {
+            anchor: event.dt_start.as_ref().map(|dt| dt.0.clone().into()),
+            rrule: event.rrule.as_ref(),
+            rdates: &event.rdates,
+            ex_dates: &event.ex_dates,
+        }
+    }
+
+    pub(crate) fn of_todo(todo: &'a VTodo<String>) -> Self {
+        // A todo without DTSTART recurs from its due
+        let anchor = match (&todo.dt_start, &todo.due) {
+            (Some(start), _) => Some(start.0.clone().into()),
+            (None, Some(due)) => Some(due.0.clone().into()),
+            (None, None) => None,
+        };
+        Self {
+            anchor,
+            rrule: todo.rrule.as_ref(),
+            rdates: &todo.rdates,
+            ex_dates: &todo.ex_dates,
+        }
+    }
+
+    /// The start of the recurrence, from which it is expanded.
+    pub(crate) fn start(&self, kind: &'static str) -> Result<LooseDateTime, AimError> {
+        if self.rrule.is_none() && self.rdates.is_empty() {
+            return Err(not_recurring(kind));
+        }
+        self.anchor.clone().ok_or_else(|| AimError::InvalidInput {
+            field: "occurrence",
+            reason: format!("{kind} has no start to recur from"),
+        })
+    }
+
+    /// The first occurrence on `date` of a recurrence starting at `start`, which is an
+    /// occurrence itself.
+    pub(crate) fn occurrence_on(
+        &self,
+        start: &LooseDateTime,
+        date: Date,
+        kind: &'static str,
+    ) -> Result<LooseDateTime, AimError> {
+        if self.rrule.is_none() && self.rdates.is_empty() {
+            return Err(not_recurring(kind));
+        }
+
+        let first = start.with_start_of_day();
+        let mut candidates = vec![first];
+        if let Some(rrule) = self.rrule {
+            candidates.extend(expand(&rrule.value, start, date)?);
+        }
+        candidates.extend(self.extra_dates(start));
+
+        let excluded = self.excluded_dates(start);
+        candidates
+            .into_iter()
+            .filter(|dt| dt.date() == date && !excluded.contains(dt))
+            .min()
+            .map(|dt| with_civil(start, dt))
+            .ok_or_else(|| AimError::InvalidInput {
+                field: "occurrence",
+                reason: format!("{kind} does not occur on {date}"),
+            })
+    }
+
+    /// The occurrences of a recurrence starting at `start` from `from` to `to`, inclusive, in the
+    /// civil time of `start`. The start is an occurrence itself.
+    pub(crate) fn occurrences_between(
+        &self,
+        start: &LooseDateTime,
+        from: Date,
+        to: Date,
+    ) -> Result<Vec<civil::DateTime>, AimError> {
+        let first = start.with_start_of_day();
+        let mut candidates = vec![first];
+        if let Some(rrule) = self.rrule {
+            // Only the window is expanded, so long-running series don't hit the limit of the rule
+            let occurrences = rule_in_frame(&rrule.value, start)
+                .expand(first, DateRange::new(from, to))
+                .map_err(|e| AimError::InvalidInput {
+                    field: "recurrence rule",
+                    reason: e.to_string(),
+                })?;
+            candidates.extend(occurrences);
+        }
+        candidates.extend(self.extra_dates(start));
+
+        let excluded = self.excluded_dates(start);
+        candidates.retain(|dt| (from..=to).contains(&dt.date()) && !excluded.contains(dt));
+        candidates.sort();
+        candidates.dedup();
+        Ok(candidates)
+    }
+
+    /// The first occurrence after `start`, the start of the recurrence, if any is left.
+    fn next_after(&self, start: &LooseDateTime) -> Option<LooseDateTime> {
+        let first = start.with_start_of_day();
+        let excluded = self.excluded_dates(start);
+        let next = |candidates: Vec<civil::DateTime>| {
+            candidates
+                .into_iter()
+                .chain(self.extra_dates(start))
+                .filter(|dt| *dt > first && !excluded.contains(dt))
+                .min()
+        };
+
+        // Widening, so that frequent rules are not expanded far ahead
+        let Some(rrule) = self.rrule else {
+            return next(Vec::new()).map(|dt| with_civil(start, dt));
+        };
+        [1.week(), 1.year(), 10.years(), 100.years()]
+            .into_iter()
+            .filter_map(|span| first.date().checked_add(span).ok())
+            .find_map(|until| next(expand(&rrule.value, start, until).ok()?))
+            .map(|dt| with_civil(start, dt))
+    }
+
+    /// The dates of `RDATE`, in the civil time of `start`.
+    fn extra_dates(&self, start: &LooseDateTime) -> impl Iterator<Item = civil::DateTime> {
+        self.rdates.iter().flat_map(move |rdate| {
+            rdate.dates.iter().filter_map(move |value| match value {
+                RDateValue::DateTime(value) => {
+                    Some(civil_in_frame(value, rdate.tz_id.as_ref(), start))
+                }
+                RDateValue::Period(_) => None,
+            })
+        })
+    }
+
+    /// The dates of `EXDATE`, in the civil time of `start`.
+    fn excluded_dates(&self, start: &LooseDateTime) -> Vec<civil::DateTime> {
+        self.ex_dates
+            .iter()
+            .flat_map(|ex| {
+                ex.dates
+                    .iter()
+                    .map(|value| civil_in_frame(value, ex.tz_id.as_ref(), start))
+            })
+            .collect()
+    }
+}
+
+/// Moves a recurring todo completed at `completed` on to its next occurrence, returning whether
+/// it has one left.
+///
+/// From the schedule, the todo moves to the occurrence after the one it is at, however late it
+/// was completed. From the completion, it moves by the interval of the rule from the day it was
+/// completed, at the time of day of the series, with the `BY*` parts left out; a monthly todo
+/// completed on January 31 is next due on the last day of February. The start and the due move
+/// together, and a `COUNT` counts the occurrence done.
+pub(crate) fn roll_forward(todo: &mut VTodo<String>, from: RecurFrom, completed: &Zoned) -> bool {
+    let recurrence = Recurrence::of_todo(todo);
+    let (Some(rrule), Ok(start)) = (recurrence.rrule, recurrence.start("Todo")) else {
+        return false;
+    };
+    if rrule.value.count.is_some_and(|count| count <= 1) {
+        return false;
+    }
+    let next = match from {
+        RecurFrom::Schedule => recurrence.next_after(&start),
+        RecurFrom::Completion => after_completion(&rrule.value, &start, completed),
+    };
+    let Some(next) = next else {
+        return false;
+    };
+
+    let shift = shift_of(&start, &next);
+    todo.dt_start = todo
+        .dt_start
+        .take()
+        .map(|s| DtStart::new(shifted_property(&s, shift)));
+    todo.due = todo
+        .due
+        .take()
+        .map(|d| Due::new(shifted_property(&d, shift)));
+    if let Some(count) = todo.rrule.as_mut().and_then(|r| r.value.count.as_mut()) {
+        *count -= 1;
+    }
+    true
+}
+
+/// The occurrence of a rule by its interval from the day of `completed`, at the time of day of
+/// `start`, unless it is past the `UNTIL` of the rule.
+fn after_completion(
+    rule: &ValueRecurrenceRule,
+    start: &LooseDateTime,
+    completed: &Zoned,
+) -> Option<LooseDateTime> {
+    let completed = match start {
+        LooseDateTime::Local(zoned) => completed.with_time_zone(zoned.time_zone().clone()),
+        _ => completed.clone(),
+    }
+    .datetime();
+    let interval = i64::from(rule.interval.unwrap_or(1).max(1));
+    let day = completed
+        .date()
+        .to_datetime(start.with_start_of_day().time());
+    let (from, span) = match rule.freq {
+        RecurrenceFrequency::Secondly => (completed, interval.seconds()),
+        RecurrenceFrequency::Minutely => (completed, interval.minutes()),
+        RecurrenceFrequency::Hourly => (completed, interval.hours()),
+        RecurrenceFrequency::Daily => (day, interval.days()),
+        RecurrenceFrequency::Weekly => (day, interval.weeks()),
+        RecurrenceFrequency::Monthly => (day, interval.months()),
+        RecurrenceFrequency::Yearly => (day, interval.years()),
+    };
+    // Days past the end of the month are clamped to its last day
+    let next = from.checked_add(span).ok()?;
+
+    // A date, written as midnight, is taken as the whole day
+    if let Some(until) = &rule_in_frame(rule, start).until {
+        let past = if until.time.utc || until.civil_date_time().time() != civil::Time::midnight() {
+            next > *until.civil_date_time()
+        } else {
+            next.date() > until.civil_date_time().date()
+        };
+        if past {
+            return None;
+        }
+    }
+    Some(with_civil(start, next))
+}
+
+/// The instances of a rule from `start` up to the end of `until`, in the civil time of `start`.
+fn expand(
+    rule: &ValueRecurrenceRule,
+    start: &LooseDateTime,
+    until: Date,
+) -> Result<Vec<civil::DateTime>, AimError> {
+    let first = start.with_start_of_day();
+    rule_in_frame(rule, start)
+        .expand(first, DateRange::new(first.date(), until))
+        .map_err(|e| AimError::InvalidInput {
+            field: "recurrence rule",
+            reason: e.to_string(),
+        })
+}
+
+/// The rule with an `UTC` `UNTIL` moved to the time zone of `start`, as rules are expanded in
+/// the civil time of their start.
+fn rule_in_frame(rule: &ValueRecurrenceRule, start: &LooseDateTime) -> ValueRecurrenceRule {
+    let mut rule = rule.clone();
+    if let (Some(until), LooseDateTime::Local(start)) = (&mut rule.until, start)
+        && until.time.utc
+        && let Ok(utc) = until.civil_date_time().to_zoned(TimeZone::UTC)
+    {
+        let dt = utc.with_time_zone(start.time_zone().clone()).datetime();
+        // Still marked as UTC, so that a midnight isn't taken as the whole day
+        if let Ok(time) = ValueTime::new(dt.hour(), dt.minute(), dt.second(), true) {
+            *until = ical::ValueDateTime::new(dt.date().into(), time);
+        }
+    }
+    rule
+}
+
+pub(crate) fn not_recurring(kind: &'static str) -> AimError {
+    AimError::InvalidInput {
+        field: "occurrence",
+        reason: format!("{kind} is not recurring"),
+    }
+}
+
+/// Converts a date of a recurrence property to the civil time of `start`, in which the
+/// recurrence is expanded.
+pub(crate) fn civil_in_frame(
+    value: &ical::DateTime,
+    tz_id: Option<&String>,
+    start: &LooseDateTime,
+) -> civil::DateTime {
+    match (loose(value, tz_id), start) {
+        (LooseDateTime::Local(zoned), LooseDateTime::Local(s)) => {
+            zoned.with_time_zone(s.time_zone().clone()).datetime()
+        }
+        (at, _) => at.with_start_of_day(),
+    }
+}
+
+fn loose(value: &ical::DateTime, tz_id: Option<&String>) -> LooseDateTime {
+    LooseDateTime::from(DateTimeProperty {
+        value: value.clone(),
+        tz_id: tz_id.cloned(),
+        x_parameters: Vec::new(),
+        retained_parameters: Vec::new(),
+        span: (),
+    })
+}
+
+/// Moves `start` to the civil time `dt`, keeping its value type and time zone.
+pub(crate) fn with_civil(start: &LooseDateTime, dt: civil::DateTime) -> LooseDateTime {
+    match start {
+        LooseDateTime::DateOnly(_) => LooseDateTime::DateOnly(dt.date()),
+        LooseDateTime::Floating(_) => LooseDateTime::Floating(dt),
+        LooseDateTime::Local(zoned) => match dt.to_zoned(zoned.time_zone().clone()) {
+            Ok(zoned) => LooseDateTime::Local(zoned),
+            Err(_) => LooseDateTime::Floating(dt),
+        },
+    }
+}
+
+/// How far `at` is from `start`, in civil time.
+pub(crate) fn shift_of(start: &LooseDateTime, at: &LooseDateTime) -> SignedDuration {
+    start
+        .with_start_of_day()
+        .duration_until(at.with_start_of_day())
+}
+
+/// Moves a date of the item by `shift`, so it stays at the same place relative to the start.
+pub(crate) fn shifted<S: StringStorage>(
+    prop: DateTimeProperty<S>,
+    shift: SignedDuration,
+) -> LooseDateTime {
+    let value = LooseDateTime::from(prop);
+    match value.with_start_of_day().checked_add(shift) {
+        Ok(dt) => with_civil(&value, dt),
+        Err(_) => value,
+    }
+}
+
+/// Moves a date property of the item by `shift`, keeping the way it is written.
+pub(crate) fn shifted_property(
+    prop: &DateTimeProperty<String>,
+    shift: SignedDuration,
+) -> DateTimeProperty<String> {
+    shifted(prop.clone(), shift).to_property_like(prop)
+}
+
+/// The stamp of a component created now, see RFC 5545 section 3.8.7.2.
+pub(crate) fn dt_stamp(now: &Zoned) -> DtStamp<String> {
+    DtStamp::new(now.with_time_zone(TimeZone::UTC).datetime())
+}
+
+/// Whether the event overrides occurrences of a recurring event, rather than being one itself.
+pub(crate) fn is_override<S: StringStorage>(event: &VEvent<S>) -> bool {
+    recurrence_id(event).is_some()
+}
+
+/// The `RECURRENCE-ID` of an override.
+pub(crate) fn recurrence_id<S: StringStorage>(event: &VEvent<S>) -> Option<&RecurrenceId<S>> {
+    event.retained_properties.iter().find_map(|p| match p {
+        Property::RecurrenceId(id) => Some(id),
+        _ => None,
+    })
+}
+
+/// Whether an override is for the occurrence it names and all the ones after it.
+pub(crate) fn is_this_and_future<S: StringStorage>(id: &RecurrenceId<S>) -> bool {
+    id.0.retained_parameters.iter().any(|p| {
+        matches!(
+            p,
+            Parameter::RecurrenceIdRange {
+                value: RecurrenceIdRange::ThisAndFuture,
+                ..
+            }
+        )
+    })
+}
+
+/// Whether two overrides are for the same occurrences.
+pub(crate) fn same_recurrence_id(a: &VEvent<String>, b: &VEvent<String>) -> bool {
+    match (recurrence_id(a), recurrence_id(b)) {
+        (Some(a), Some(b)) => LooseDateTime::from(a.0.clone()) == LooseDateTime::from(b.0.clone()),
+        _ => false,
+    }
+}
+
+/// Completes a patch moving the start of a recurring event, so that the rest of it moves along.
+///
+/// Unless the patch sets them, the end keeps its distance to the start, and `UNTIL`, `RDATE` and
+/// `EXDATE` move by as much as the start. Nothing moves along when the start changes between a
+/// date and a date-time.
+pub(crate) fn follow_start(patch: &mut EventPatch, event: &VEvent<String>) {
+    let (Some(Some(to)), Some(start)) = (&patch.start, &event.dt_start) else {
+        return;
+    };
+    let from = LooseDateTime::from(start.0.clone());
+    if matches!(from, LooseDateTime::DateOnly(_)) != matches!(to, LooseDateTime::DateOnly(_)) {
+        return;
+    }
+    let shift = shift_of(&from, to);
+    if shift.is_zero() {
+        return;
+    }
+
+    if patch.end.is_none()
+        && let Some(end) = &event.dt_end
+    {
+        patch.end = Some(Some(shifted(end.0.clone(), shift)));
+    }
+
+    let to = to.clone();
+    let moved = |dt: civil::DateTime| dt.checked_add(shift).ok().map(|dt| with_civil(&to, dt));
+    if patch.until.is_none()
+        && let Some(rrule) = &event.rrule
+        && let Some(until) = &rule_in_frame(&rrule.value, &from).until
+    {
+        // A midnight that is not in UTC is the whole day, see `RRuleExt::expand`
+        let whole_day = !until.time.utc && until.time.civil_time() == civil::Time::midnight();
+        patch.until = moved(*until.civil_date_time()).map(|dt| {
+            if whole_day {
+                LooseDateTime::DateOnly(dt.date())
+            } else {
+                dt
+            }
+        });
+    }
+
+    if patch.ex_dates.is_empty() {
+        let dates = event
+            .ex_dates
+            .iter()
+            .flat_map(|p| p.dates.iter().map(move |d| (d, p.tz_id.as_ref())));
+        patch.ex_dates = move_dates(dates, &from, moved);
+    }
+    if patch.rdates.is_empty() {
+        let dates = event.rdates.iter().flat_map(|p| {
+            p.dates.iter().filter_map(move |d| match d {
+                RDateValue::DateTime(d) => Some((d, p.tz_id.as_ref())),
+                RDateValue::Period(_) => None,
+            })
+        });
+        patch.rdates = move_dates(dates, &from, moved);
+    }
+}
+
+/// Edits removing the dates, then adding them moved.
+fn move_dates<'a>(
+    dates: impl Iterator<Item = (&'a ical::DateTime, Option<&'a String>)>,
+    from: &LooseDateTime,
+    moved: impl Fn(civil::DateTime) -> Option<LooseDateTime>,
+) -> Vec<RecurrenceDateEdit> {
+    let (removes, adds): (Vec<_>, Vec<_>) = dates
+        .filter_map(|(value, tz_id)| {
+            let to = moved(civil_in_frame(value, tz_id, from))?;
+            Some((
+                RecurrenceDateEdit::Remove(loose(value, tz_id)),
+                RecurrenceDateEdit::Add(to),
+            ))
+        })
+        .unzip();
+    removes.into_iter().chain(adds).collect()
+}
+
+/// An occurrence of a recurring event, from which on the event is edited.
+#[derive(Debug)]
+pub(crate) struct SeriesOccurrence {
+    start: LooseDateTime,
+    at: LooseDateTime,
+}
+
+/// A recurring event split at an occurrence, see [`SeriesOccurrence::split`].
+#[derive(Debug)]
+pub(crate) struct SeriesSplit {
+    /// Patch ending the series before the occurrence.
+    pub(crate) master: EventPatch,
+    /// The series from the occurrence on.
+    pub(crate) future: VEvent<String>,
+}
+
+impl SeriesOccurrence {
+    /// Finds the occurrence of a recurring event on `date`.
+    pub(crate) fn find(event: &VEvent<String>, date: Date) -> Result<Self, AimError> {
+        let recurrence = Recurrence::of_event(event);
+        let start = recurrence.start("Event")?;
+        let at = recurrence.occurrence_on(&start, date, "Event")?;
+        Ok(Self { start, at })
+    }
+
+    /// Whether this is the first occurrence, from which on the event is edited as a whole.
+    pub(crate) fn is_first(&self) -> bool {
+        self.at.with_start_of_day() == self.start.with_start_of_day()
+    }
+
+    /// Splits the event in a series ending before the occurrence and a series with the UID `uid`
+    /// starting from it, which records the event it was split from.
+    ///
+    /// The new series takes over the rest of the `COUNT`, which counts the instances of the rule
+    /// whether they are excluded or not, and the `RDATE` and `EXDATE` values from the occurrence
+    /// on. The series before it ends with an `UNTIL` just before the occurrence.
+    pub(crate) fn split(
+        &self,
+        event: &VEvent<String>,
+        uid: &str,
+        now: &Zoned,
+    ) -> Result<SeriesSplit, AimError> {
+        let at = self.at.with_start_of_day();
+        let Some(rrule) = &event.rrule else {
+            return Err(self.not_rule_occurrence());
+        };
+        let instances = expand(&rrule.value, &self.start, self.at.date())?;
+        if !instances.contains(&at) {
+            return Err(self.not_rule_occurrence());
+        }
+        let before = instances.iter().filter(|dt| **dt < at).count();
+        let before = u32::try_from(before).unwrap_or(u32::MAX);
+
+        let mut future = event.clone();
+        future.uid = Uid::new(uid.to_string());
+        future.dt_stamp = dt_stamp(now);
+        future.dt_start = future
+            .dt_start
+            .map(|start| DtStart::new(self.at.to_property_like(&start)));
+        let shift = shift_of(&self.start, &self.at);
+        future.dt_end = future
+            .dt_end
+            .map(|end| DtEnd::new(shifted_property(&end, shift)));
+        if let Some(rrule) = &mut future.rrule {
+            rrule.value.count = rrule.value.count.map(|count| count.saturating_sub(before));
+        }
+        future
+            .x_properties
+            .retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_SPLIT_FROM));
+        future.x_properties.push(XNameProperty {
+            name: X_AIM_SPLIT_FROM.to_string(),
+            parameters: Vec::new(),
+            value: Value::Text {
+                values: vec![ValueText::new(event.uid.content.to_string())],
+                span: (),
+            },
+            span: (),
+        });
+
+        // The dates from the occurrence on go to the new series
+        let later = |value: &ical::DateTime, tz_id: Option<&String>| {
+            civil_in_frame(value, tz_id, &self.start) >= at
+        };
+        let mut master = EventPatch {
+            until: Some(self.until_before()),
+            ..EventPatch::default()
+        };
+        for prop in &mut future.ex_dates {
+            let tz_id = prop.tz_id.as_ref();
+            for value in prop.dates.iter().filter(|d| later(d, tz_id)) {
+                master
+                    .ex_dates
+                    .push(RecurrenceDateEdit::Remove(loose(value, tz_id)));
+            }
+            prop.dates.retain(|d| later(d, tz_id));
+        }
+        future.ex_dates.retain(|p| !p.dates.is_empty());
+
+        for prop in &mut future.rdates {
+            let tz_id = prop.tz_id.as_ref();
+            for value in &prop.dates {
+                if let RDateValue::DateTime(value) = value
+                    && later(value, tz_id)
+                {
+                    master
+                        .rdates
+                        .push(RecurrenceDateEdit::Remove(loose(value, tz_id)));
+                }
+            }
+            prop.dates.retain(|d| match d {
+                RDateValue::DateTime(d) => later(d, tz_id),
+                RDateValue::Period(_) => false,
+            });
+        }
+        future.rdates.retain(|p| !p.dates.is_empty());
+
+        Ok(SeriesSplit { master, future })
+    }
+
+    /// Builds an override of the occurrence and the ones after it, to keep with the event.
+    ///
+    /// The override is the event at the occurrence without its recurrence, to be patched.
+    pub(crate) fn override_from(&self, event: &VEvent<String>, now: &Zoned) -> VEvent<String> {
+        let mut over = event.clone();
+        over.dt_stamp = dt_stamp(now);
+        over.dt_start = over
+            .dt_start
+            .map(|start| DtStart::new(self.at.to_property_like(&start)));
+        let shift = shift_of(&self.start, &self.at);
+        over.dt_end = over
+            .dt_end
+            .map(|end| DtEnd::new(shifted_property(&end, shift)));
+        over.rrule = None;
+        over.rdates.clear();
+        over.ex_dates.clear();
+
+        let mut id = DateTimeProperty::from(self.at.clone());
+        id.retained_parameters.push(Parameter::RecurrenceIdRange {
+            value: RecurrenceIdRange::ThisAndFuture,
+            span: (),
+        });
+        over.retained_properties
+            .retain(|p| !matches!(p, Property::RecurrenceId(_)));
+        over.retained_properties
+            .push(Property::RecurrenceId(RecurrenceId(id)));
+        over
+    }
+
+    /// The last date-time before the occurrence, as the inclusive end of a recurrence.
+    fn until_before(&self) -> LooseDateTime {
+        match &self.at {
+            LooseDateTime::DateOnly(date) => {
+                LooseDateTime::DateOnly(date.checked_sub(1.day()).unwrap_or(*date))
+            }
+            LooseDateTime::Floating(dt) => {
+                LooseDateTime::Floating(dt.checked_sub(1.second()).unwrap_or(*dt))
+            }
+            LooseDateTime::Local(zoned) => {
+                LooseDateTime::Local(zoned.checked_sub(1.second()).unwrap_or(zoned.clone()))
+            }
+        }
+    }
+
+    fn not_rule_occurrence(&self) -> AimError {
+        AimError::InvalidInput {
+            field: "occurrence",
+            reason: format!(
+                "Event can only be split at an occurrence of its recurrence rule, not on {}",
+                self.at.date()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_ical::{CalendarComponent, ICalendar, fmt::format, parse};
+    use jiff::civil::date;
+
+    use super::*;
+
+    fn event(lines: &str) -> VEvent<String> {
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VEVENT\r\nUID:e1\r\nDTSTAMP:20260101T000000Z\r\n{lines}END:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = parse(&src).unwrap();
+        match calendars
+            .into_iter()
+            .next()
+            .unwrap()
+            .components
+            .into_iter()
+            .next()
+        {
+            Some(CalendarComponent::Event(event)) => event.to_owned(),
+            _ => panic!("expected an event"),
+        }
+    }
+
+    fn todo(lines: &str) -> VTodo<String> {
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VTODO\r\nUID:t1\r\nDTSTAMP:20260101T000000Z\r\n{lines}END:VTODO\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = parse(&src).unwrap();
+        match calendars
+            .into_iter()
+            .next()
+            .unwrap()
+            .components
+            .into_iter()
+            .next()
+        {
+            Some(CalendarComponent::Todo(todo)) => todo.to_owned(),
+            _ => panic!("expected a todo"),
+        }
+    }
+
+    /// The due of a todo after completing it on `day`, `None` if it has no occurrence left.
+    fn due_after(todo: &VTodo<String>, from: RecurFrom, day: Date) -> Option<LooseDateTime> {
+        let mut todo = todo.clone();
+        let completed = day.at(18, 0, 0, 0).to_zoned(TimeZone::UTC).unwrap();
+        roll_forward(&mut todo, from, &completed).then(|| LooseDateTime::from(todo.due.unwrap().0))
+    }
+
+    fn now() -> Zoned {
+        date(2026, 3, 1)
+            .at(12, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap()
+    }
+
+    fn ics(event: &VEvent<String>) -> String {
+        format(&ICalendar {
+            components: vec![CalendarComponent::Event(event.clone())],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    /// The occurrences of the rule of an event in 2026, in the civil time of its start, without
+    /// the excluded ones.
+    fn occurrences(event: &VEvent<String>) -> Vec<civil::DateTime> {
+        let start = LooseDateTime::from(event.dt_start.as_ref().unwrap().0.clone());
+        let rrule = event.rrule.as_ref().unwrap();
+        let excluded: Vec<_> = event
+            .ex_dates
+            .iter()
+            .flat_map(|p| {
+                p.dates
+                    .iter()
+                    .map(|d| civil_in_frame(d, p.tz_id.as_ref(), &start))
+            })
+            .collect();
+        expand(&rrule.value, &start, date(2026, 12, 31))
+            .unwrap()
+            .into_iter()
+            .filter(|dt| !excluded.contains(dt))
+            .collect()
+    }
+
+    /// Splits the event on `date`, returning the series before and after it.
+    fn split(event: &VEvent<String>, date: Date) -> (VEvent<String>, VEvent<String>) {
+        let SeriesSplit { master, future } = SeriesOccurrence::find(event, date)
+            .unwrap()
+            .split(event, "e2", &now())
+            .unwrap();
+        let mut before = event.clone();
+        master.resolve(now()).apply_to(&mut before);
+        (before, future)
+    }
+
+    /// Checks that the split series have the occurrences of the event between them.
+    fn assert_partition(event: &VEvent<String>, before: &VEvent<String>, after: &VEvent<String>) {
+        let mut joined = occurrences(before);
+        joined.extend(occurrences(after));
+        assert_eq!(joined, occurrences(event));
+    }
+
+    #[test]
+    fn split_carries_remaining_count_over() {
+        let event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             DTEND;TZID=Europe/Berlin:20260105T100000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO;COUNT=10\r\n\
+             EXDATE;TZID=Europe/Berlin:20260112T090000\r\n",
+        );
+        let (before, after) = split(&event, date(2026, 2, 2));
+        assert_partition(&event, &before, &after);
+        assert_eq!(occurrences(&before).len(), 3);
+        assert_eq!(occurrences(&after).len(), 6);
+
+        // The excluded occurrence still counts, as in the event
+        let before = ics(&before);
+        assert!(before.contains("RRULE:FREQ=WEEKLY;UNTIL=20260202T075959Z;BYDAY=MO\r\n"));
+        assert!(before.contains("EXDATE;TZID=Europe/Berlin:20260112T090000\r\n"));
+        let after = ics(&after);
+        assert!(after.contains("UID:e2\r\n"));
+        assert!(after.contains("DTSTART;TZID=Europe/Berlin:20260202T090000\r\n"));
+        assert!(after.contains("DTEND;TZID=Europe/Berlin:20260202T100000\r\n"));
+        assert!(after.contains("RRULE:FREQ=WEEKLY;COUNT=6;BYDAY=MO\r\n"));
+        assert!(after.contains("X-AIM-SPLIT-FROM:e1\r\n"));
+        assert!(!after.contains("EXDATE"));
+    }
+
+    #[test]
+    fn split_ends_all_day_series_the_day_before() {
+        let event = event(
+            "DTSTART;VALUE=DATE:20260101\r\n\
+             RRULE:FREQ=DAILY;UNTIL=20260110\r\n",
+        );
+        let (before, after) = split(&event, date(2026, 1, 5));
+        assert_partition(&event, &before, &after);
+        assert_eq!(occurrences(&before).len(), 4);
+        assert_eq!(occurrences(&after).len(), 6);
+        let until = before.rrule.unwrap().value.until.unwrap();
+        assert_eq!(until.date.civil_date(), date(2026, 1, 4));
+        let after = ics(&after);
+        assert!(after.contains("DTSTART;VALUE=DATE:20260105\r\n"));
+        assert!(after.contains("RRULE:FREQ=DAILY;UNTIL=20260110"));
+    }
+
+    #[test]
+    fn split_ends_floating_series_just_before_occurrence() {
+        let event = event(
+            "DTSTART:20260105T090000\r\n\
+             RRULE:FREQ=DAILY;COUNT=5\r\n",
+        );
+        let (before, after) = split(&event, date(2026, 1, 7));
+        assert_partition(&event, &before, &after);
+        assert!(ics(&before).contains("RRULE:FREQ=DAILY;UNTIL=20260107T085959\r\n"));
+        assert!(ics(&after).contains("RRULE:FREQ=DAILY;COUNT=3\r\n"));
+    }
+
+    #[test]
+    fn split_compares_utc_until_in_time_zone_of_start() {
+        // 20:00 in New York is 01:00 UTC the next day
+        let event = event(
+            "DTSTART;TZID=America/New_York:20260105T200000\r\n\
+             RRULE:FREQ=DAILY;UNTIL=20260110T010000Z\r\n",
+        );
+        let (before, after) = split(&event, date(2026, 1, 7));
+        assert_partition(&event, &before, &after);
+        assert_eq!(occurrences(&before).len(), 2);
+        assert_eq!(occurrences(&after).len(), 3);
+        assert!(ics(&before).contains("UNTIL=20260108T005959Z"));
+        assert!(ics(&after).contains("UNTIL=20260110T010000Z"));
+    }
+
+    #[test]
+    fn split_moves_later_dates_to_new_series() {
+        let event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+             EXDATE;TZID=Europe/Berlin:20260112T090000,20260209T090000\r\n\
+             RDATE;TZID=Europe/Berlin:20260114T090000,20260211T090000\r\n",
+        );
+        let (before, after) = split(&event, date(2026, 2, 2));
+        let before = ics(&before);
+        assert!(before.contains("EXDATE;TZID=Europe/Berlin:20260112T090000\r\n"));
+        assert!(before.contains("RDATE;TZID=Europe/Berlin:20260114T090000\r\n"));
+        let after = ics(&after);
+        assert!(after.contains("EXDATE;TZID=Europe/Berlin:20260209T090000\r\n"));
+        assert!(after.contains("RDATE;TZID=Europe/Berlin:20260211T090000\r\n"));
+
+        // An extra date is not an occurrence of the rule to split at
+        let err = SeriesOccurrence::find(&event, date(2026, 1, 14))
+            .unwrap()
+            .split(&event, "e2", &now())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AimError::InvalidInput {
+                field: "occurrence",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn first_occurrence_is_the_whole_series() {
+        let event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO\r\n",
+        );
+        assert!(
+            SeriesOccurrence::find(&event, date(2026, 1, 5))
+                .unwrap()
+                .is_first()
+        );
+        assert!(
+            !SeriesOccurrence::find(&event, date(2026, 1, 12))
+                .unwrap()
+                .is_first()
+        );
+    }
+
+    #[test]
+    fn override_has_range_this_and_future() {
+        let event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             DTEND;TZID=Europe/Berlin:20260105T100000\r\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+             EXDATE;TZID=Europe/Berlin:20260112T090000\r\n",
+        );
+        let over = SeriesOccurrence::find(&event, date(2026, 2, 2))
+            .unwrap()
+            .override_from(&event, &now());
+        assert!(is_override(&over));
+        let ics = ics(&over);
+        assert!(ics.contains("UID:e1\r\n"));
+        assert!(ics.contains("RANGE=THISANDFUTURE"));
+        assert!(ics.contains("TZID=Europe/Berlin:20260202T090000\r\n"));
+        assert!(ics.contains("DTEND;TZID=Europe/Berlin:20260202T100000\r\n"));
+        assert!(!ics.contains("RRULE"));
+        assert!(!ics.contains("EXDATE"));
+    }
+
+    #[test]
+    fn follow_start_moves_recurrence_along() {
+        let mut event = event(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             DTEND;TZID=Europe/Berlin:20260105T100000\r\n\
+             RRULE:FREQ=DAILY;UNTIL=20260110T080000Z\r\n\
+             EXDATE;TZID=Europe/Berlin:20260107T090000\r\n",
+        );
+        let start = date(2026, 1, 5)
+            .at(10, 30, 0, 0)
+            .to_zoned(TimeZone::get("Europe/Berlin").unwrap())
+            .unwrap();
+        let mut patch = EventPatch {
+            start: Some(Some(LooseDateTime::Local(start))),
+            ..EventPatch::default()
+        };
+        follow_start(&mut patch, &event);
+        patch.resolve(now()).apply_to(&mut event);
+
+        let ics = ics(&event);
+        assert!(ics.contains("DTEND;TZID=Europe/Berlin:20260105T113000\r\n"));
+        assert!(ics.contains("RRULE:FREQ=DAILY;UNTIL=20260110T093000Z\r\n"));
+        assert!(ics.contains("EXDATE;TZID=Europe/Berlin:20260107T103000\r\n"));
+        assert_eq!(occurrences(&event).len(), 5);
+    }
+
+    #[test]
+    fn weekly_todo_rolls_forward_from_schedule_or_completion() {
+        let todo = todo("DUE;VALUE=DATE:20260105\r\nRRULE:FREQ=WEEKLY\r\n");
+        let day = date(2026, 1, 8);
+        assert_eq!(
+            due_after(&todo, RecurFrom::Schedule, day),
+            Some(LooseDateTime::DateOnly(date(2026, 1, 12)))
+        );
+        assert_eq!(
+            due_after(&todo, RecurFrom::Completion, day),
+            Some(LooseDateTime::DateOnly(date(2026, 1, 15)))
+        );
+    }
+
+    #[test]
+    fn monthly_todo_on_month_end_rolls_forward_from_schedule_or_completion() {
+        let todo = todo("DUE;VALUE=DATE:20260131\r\nRRULE:FREQ=MONTHLY\r\n");
+        let day = date(2026, 2, 2);
+        assert_eq!(
+            due_after(&todo, RecurFrom::Schedule, day),
+            Some(LooseDateTime::DateOnly(date(2026, 3, 31)))
+        );
+        assert_eq!(
+            due_after(&todo, RecurFrom::Completion, day),
+            Some(LooseDateTime::DateOnly(date(2026, 3, 2)))
+        );
+
+        // Completed on time, the next month is short
+        assert_eq!(
+            due_after(&todo, RecurFrom::Completion, date(2026, 1, 31)),
+            Some(LooseDateTime::DateOnly(date(2026, 2, 28)))
+        );
+    }
+
+    #[test]
+    fn roll_forward_moves_start_and_due_at_their_time() {
+        let mut todo = todo(
+            "DTSTART;TZID=Europe/Berlin:20260105T090000\r\n\
+             DUE;TZID=Europe/Berlin:20260105T170000\r\n\
+             RRULE:FREQ=WEEKLY;COUNT=3\r\n",
+        );
+        let completed = date(2026, 1, 8)
+            .at(20, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        assert!(roll_forward(&mut todo, RecurFrom::Completion, &completed));
+
+        let ics = format(&ICalendar {
+            components: vec![CalendarComponent::Todo(todo)],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(ics.contains("DTSTART;TZID=Europe/Berlin:20260115T090000\r\n"));
+        assert!(ics.contains("DUE;TZID=Europe/Berlin:20260115T170000\r\n"));
+        assert!(ics.contains("RRULE:FREQ=WEEKLY;COUNT=2\r\n"));
+    }
+
+    #[test]
+    fn roll_forward_stops_at_the_end_of_the_series() {
+        let last = todo("DUE;VALUE=DATE:20260105\r\nRRULE:FREQ=WEEKLY;COUNT=1\r\n");
+        assert_eq!(
+            due_after(&last, RecurFrom::Schedule, date(2026, 1, 5)),
+            None
+        );
+
+        let until = todo("DUE;VALUE=DATE:20260105\r\nRRULE:FREQ=WEEKLY;UNTIL=20260115\r\n");
+        let day = date(2026, 1, 9);
+        assert_eq!(
+            due_after(&until, RecurFrom::Schedule, day),
+            Some(LooseDateTime::DateOnly(date(2026, 1, 12)))
+        );
+        assert_eq!(due_after(&until, RecurFrom::Completion, day), None);
+
+        let single = todo("DUE;VALUE=DATE:20260105\r\n");
+        assert_eq!(due_after(&single, RecurFrom::Schedule, day), None);
+    }
+}
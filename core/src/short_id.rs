@@ -2,12 +2,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{borrow::Cow, error::Error, num::NonZeroU32};
+use std::{borrow::Cow, num::NonZeroU32};
 
-use jiff::Zoned;
+use jiff::{SignedDuration, Zoned};
 
 use crate::db::Db;
-use crate::{Event, EventStatus, Id, Kind, LooseDateTime, Priority, Todo, TodoStatus};
+use crate::{CheckIn, Event, EventStatus, Id, Kind, LooseDateTime, Priority, Todo, TodoStatus};
 
 #[derive(Debug, Clone)]
 pub struct ShortIds {
@@ -19,25 +19,14 @@ impl ShortIds {
         Self { db }
     }
 
-    pub async fn get(&self, id: &Id) -> Result<Option<UidAndShortId>, Box<dyn Error>> {
+    pub async fn get(&self, id: &Id) -> Result<Option<UidAndShortId>, sqlx::Error> {
         Ok(match id.maybe_short_id() {
             Some(short_id) => self.db.short_ids.get_by_short_id(short_id).await?,
             None => None,
         })
     }
 
-    /// Converts the Id to a UID.
-    pub async fn get_uid(&self, id: &Id) -> Result<String, Box<dyn Error>> {
-        if let Some(short_id) = id.maybe_short_id()
-            && let Some(data) = self.db.short_ids.get_by_short_id(short_id).await?
-        {
-            return Ok(data.uid);
-        }
-
-        Ok(id.as_uid().to_owned())
-    }
-
-    pub async fn event<E: Event>(&self, event: E) -> Result<EventWithShortId<E>, Box<dyn Error>> {
+    pub async fn event<E: Event>(&self, event: E) -> Result<EventWithShortId<E>, sqlx::Error> {
         let short_id = match event.short_id() {
             Some(short_id) => short_id, // If the todo already has a short ID, use it directly
             None => {
@@ -57,7 +46,7 @@ impl ShortIds {
     pub async fn events<E: Event>(
         &self,
         events: Vec<E>,
-    ) -> Result<Vec<EventWithShortId<E>>, Box<dyn Error>> {
+    ) -> Result<Vec<EventWithShortId<E>>, sqlx::Error> {
         let mut with_id = Vec::with_capacity(events.len());
         for event in events {
             with_id.push(self.event(event).await?);
@@ -65,7 +54,7 @@ impl ShortIds {
         Ok(with_id)
     }
 
-    pub async fn todo<T: Todo>(&self, todo: T) -> Result<TodoWithShortId<T>, Box<dyn Error>> {
+    pub async fn todo<T: Todo>(&self, todo: T) -> Result<TodoWithShortId<T>, sqlx::Error> {
         let short_id = match todo.short_id() {
             Some(short_id) => short_id, // If the todo already has a short ID, use it directly
             None => {
@@ -85,7 +74,7 @@ impl ShortIds {
     pub async fn todos<T: Todo>(
         &self,
         todos: Vec<T>,
-    ) -> Result<Vec<TodoWithShortId<T>>, Box<dyn Error>> {
+    ) -> Result<Vec<TodoWithShortId<T>>, sqlx::Error> {
         let mut with_id = Vec::with_capacity(todos.len());
         for todo in todos {
             with_id.push(self.todo(todo).await?);
@@ -93,7 +82,7 @@ impl ShortIds {
         Ok(with_id)
     }
 
-    pub async fn flush(&self) -> Result<(), Box<dyn Error>> {
+    pub async fn flush(&self) -> Result<(), sqlx::Error> {
         self.db.short_ids.truncate().await?;
         Ok(())
     }
@@ -133,9 +122,57 @@ impl<E: Event> Event for EventWithShortId<E> {
     fn summary(&self) -> Cow<'_, str> {
         self.inner.summary()
     }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.inner.location()
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.inner.organizer()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.inner.url()
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.inner.categories()
+    }
+
+    fn transparent(&self) -> bool {
+        self.inner.transparent()
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.inner.color()
+    }
+
+    fn split_from(&self) -> Option<Cow<'_, str>> {
+        self.inner.split_from()
+    }
+
+    fn check_in(&self) -> Option<CheckIn> {
+        self.inner.check_in()
+    }
+
+    fn related_to(&self) -> Option<Cow<'_, str>> {
+        self.inner.related_to()
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        self.inner.calendar_id()
+    }
+
+    fn mirrored_in(&self) -> Vec<Cow<'_, str>> {
+        self.inner.mirrored_in()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.inner.meta()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TodoWithShortId<T: Todo> {
     pub inner: T,
     pub short_id: NonZeroU32,
@@ -158,6 +195,10 @@ impl<T: Todo> Todo for TodoWithShortId<T> {
         self.inner.description()
     }
 
+    fn start(&self) -> Option<LooseDateTime> {
+        self.inner.start()
+    }
+
     fn due(&self) -> Option<LooseDateTime> {
         self.inner.due()
     }
@@ -177,6 +218,62 @@ impl<T: Todo> Todo for TodoWithShortId<T> {
     fn summary(&self) -> Cow<'_, str> {
         self.inner.summary()
     }
+
+    fn snoozed_from(&self) -> Vec<LooseDateTime> {
+        self.inner.snoozed_from()
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.inner.location()
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.inner.organizer()
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.inner.url()
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.inner.categories()
+    }
+
+    fn color(&self) -> Option<Cow<'_, str>> {
+        self.inner.color()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        self.inner.meta()
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.inner.parent_uid()
+    }
+
+    fn encrypted_description(&self) -> Option<Cow<'_, str>> {
+        self.inner.encrypted_description()
+    }
+
+    fn in_inbox(&self) -> bool {
+        self.inner.in_inbox()
+    }
+
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        self.inner.waiting_on()
+    }
+
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        self.inner.follow_up()
+    }
+
+    fn estimate(&self) -> Option<SignedDuration> {
+        self.inner.estimate()
+    }
+
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        self.inner.calendar_id()
+    }
 }
 
 #[derive(Debug, Clone)]
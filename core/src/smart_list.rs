@@ -0,0 +1,418 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Smart lists, named queries from the `[core.lists.<name>]` sections next to the built-in ones.
+//!
+//! A smart list is compiled to the same [`TodoConditions`] or [`EventConditions`] as the
+//! listings when the config is loaded, so a list that would not run is rejected right away,
+//! and counting its items is a `COUNT` query like any other.
+
+use std::collections::BTreeMap;
+
+use crate::meta::normalize_meta_key;
+use crate::{DateTimeAnchor, EventConditions, SortOrder, TodoConditions, TodoSort, TodoStatus};
+
+/// Number of todos the built-in `next` list is limited to.
+const NEXT_LIMIT: i64 = 10;
+
+/// The smart lists of the config, after the built-in ones they do not replace.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(try_from = "BTreeMap<String, SmartListDef>")]
+pub struct SmartLists {
+    lists: Vec<SmartList>,
+}
+
+impl Default for SmartLists {
+    fn default() -> Self {
+        Self {
+            lists: SmartList::builtins(),
+        }
+    }
+}
+
+impl SmartLists {
+    /// The lists, built-in ones first.
+    #[must_use]
+    pub fn lists(&self) -> &[SmartList] {
+        &self.lists
+    }
+
+    /// The list with the given name, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SmartList> {
+        self.lists.iter().find(|list| list.name == name)
+    }
+}
+
+impl TryFrom<BTreeMap<String, SmartListDef>> for SmartLists {
+    type Error = String;
+
+    fn try_from(defs: BTreeMap<String, SmartListDef>) -> Result<Self, Self::Error> {
+        let mut lists = SmartList::builtins();
+        for (name, def) in defs {
+            let list = def.compile(name)?;
+            match lists.iter_mut().find(|builtin| builtin.name == list.name) {
+                Some(builtin) => *builtin = list,
+                None => lists.push(list),
+            }
+        }
+        Ok(Self { lists })
+    }
+}
+
+/// A named query over the todos or the events, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct SmartList {
+    /// The name of the list, such as `today`.
+    pub name: String,
+    /// Shown before the name where lists are listed, such as an emoji.
+    pub icon: Option<String>,
+    /// What the list holds.
+    pub query: SmartListQuery,
+}
+
+/// What a smart list holds.
+#[derive(Debug, Clone)]
+pub enum SmartListQuery {
+    /// Todos matching the conditions, in the given order.
+    Todos {
+        /// The conditions the todos match.
+        conds: TodoConditions,
+        /// The order of the todos, the most urgent first.
+        sort: Vec<TodoSort>,
+        /// Most todos the list holds, if limited.
+        limit: Option<i64>,
+    },
+    /// Events matching the conditions.
+    Events(EventConditions),
+}
+
+impl SmartList {
+    /// The built-in lists: `today` (due by the end of today, overdue ones included), `upcoming`
+    /// (due within 7 days), `someday` (without a due date) and `next` (the most urgent todos).
+    #[must_use]
+    pub fn builtins() -> Vec<Self> {
+        let by_due = || vec![TodoSort::Due(SortOrder::Asc), priority_sort()];
+        let todos = |name: &str, conds, sort, limit| Self {
+            name: name.to_string(),
+            icon: None,
+            query: SmartListQuery::Todos { conds, sort, limit },
+        };
+        vec![
+            todos(
+                "today",
+                TodoConditions {
+                    due: Some(DateTimeAnchor::today()),
+                    ..open_todos()
+                },
+                by_due(),
+                None,
+            ),
+            todos(
+                "upcoming",
+                TodoConditions {
+                    due: Some(DateTimeAnchor::InDays(7)),
+                    ..open_todos()
+                },
+                by_due(),
+                None,
+            ),
+            todos(
+                "someday",
+                TodoConditions {
+                    undated: true,
+                    ..open_todos()
+                },
+                vec![priority_sort()],
+                None,
+            ),
+            todos(
+                "next",
+                open_todos(),
+                vec![priority_sort(), TodoSort::Due(SortOrder::Asc)],
+                Some(NEXT_LIMIT),
+            ),
+        ]
+    }
+}
+
+/// The todos still to do, which the lists narrow down.
+fn open_todos() -> TodoConditions {
+    TodoConditions {
+        status: Some(TodoStatus::NeedsAction),
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        calendar_id: None,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    }
+}
+
+fn priority_sort() -> TodoSort {
+    TodoSort::Priority {
+        order: SortOrder::Asc,
+        none_first: None,
+    }
+}
+
+/// A smart list as written in the config.
+#[expect(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SmartListDef {
+    icon: Option<String>,
+    kind: SmartListKind,
+    calendar: Option<String>,
+    categories: Vec<String>,
+    meta: BTreeMap<String, String>,
+    // Todo lists only
+    status: Option<String>,
+    due: Option<DateTimeAnchor>,
+    undated: bool,
+    inbox: bool,
+    waiting: bool,
+    waiting_for: Option<String>,
+    sort: Vec<SmartListSort>,
+    limit: Option<i64>,
+    // Event lists only
+    from: Option<DateTimeAnchor>,
+    to: Option<DateTimeAnchor>,
+    unchecked: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SmartListKind {
+    #[default]
+    Todos,
+    Events,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SmartListSort {
+    Priority,
+    Due,
+    Summary,
+}
+
+impl SmartListDef {
+    fn compile(self, name: String) -> Result<SmartList, String> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(format!(
+                "Invalid list '{name}': names cannot be empty or contain spaces"
+            ));
+        }
+        let icon = self.icon.clone();
+        let query = match self.kind {
+            SmartListKind::Todos => self.todo_query(&name)?,
+            SmartListKind::Events => self.event_query(&name)?,
+        };
+        Ok(SmartList { name, icon, query })
+    }
+
+    fn todo_query(self, name: &str) -> Result<SmartListQuery, String> {
+        let misplaced = [
+            ("from", self.from.is_some()),
+            ("to", self.to.is_some()),
+            ("unchecked", self.unchecked),
+        ];
+        if let Some((field, _)) = misplaced.iter().find(|(_, set)| *set) {
+            return Err(format!(
+                "Invalid list '{name}': `{field}` only applies to event lists"
+            ));
+        }
+        if self.undated && self.due.is_some() {
+            return Err(format!(
+                "Invalid list '{name}': `due` and `undated` cannot be combined"
+            ));
+        }
+        if self.waiting && self.waiting_for.is_some() {
+            return Err(format!(
+                "Invalid list '{name}': `waiting` and `waiting_for` cannot be combined"
+            ));
+        }
+        if self.limit.is_some_and(|limit| limit <= 0) {
+            return Err(format!("Invalid list '{name}': `limit` must be positive"));
+        }
+        let status = match self.status {
+            Some(status) => status
+                .to_uppercase()
+                .parse()
+                .map_err(|()| format!("Invalid list '{name}': unknown status '{status}'"))?,
+            None => TodoStatus::NeedsAction,
+        };
+        let sort = match self.sort.as_slice() {
+            [] => vec![priority_sort(), TodoSort::Due(SortOrder::Asc)],
+            keys => keys
+                .iter()
+                .map(|key| match key {
+                    SmartListSort::Priority => priority_sort(),
+                    SmartListSort::Due => TodoSort::Due(SortOrder::Asc),
+                    SmartListSort::Summary => TodoSort::Summary(SortOrder::Asc),
+                })
+                .collect(),
+        };
+        Ok(SmartListQuery::Todos {
+            conds: TodoConditions {
+                status: Some(status),
+                due: self.due,
+                calendar_id: self.calendar,
+                meta: meta_conditions(name, self.meta)?,
+                inbox: self.inbox,
+                waiting: self.waiting,
+                waiting_for: self.waiting_for,
+                categories: self.categories,
+                undated: self.undated,
+                ..open_todos()
+            },
+            sort,
+            limit: self.limit,
+        })
+    }
+
+    fn event_query(self, name: &str) -> Result<SmartListQuery, String> {
+        let misplaced = [
+            ("status", self.status.is_some()),
+            ("due", self.due.is_some()),
+            ("undated", self.undated),
+            ("inbox", self.inbox),
+            ("waiting", self.waiting),
+            ("waiting_for", self.waiting_for.is_some()),
+            ("sort", !self.sort.is_empty()),
+            ("limit", self.limit.is_some()),
+        ];
+        if let Some((field, _)) = misplaced.iter().find(|(_, set)| *set) {
+            return Err(format!(
+                "Invalid list '{name}': `{field}` only applies to todo lists"
+            ));
+        }
+        Ok(SmartListQuery::Events(EventConditions {
+            // Like `aim event list`, from today on unless told otherwise
+            startable: Some(self.from.unwrap_or_else(DateTimeAnchor::today)),
+            cutoff: self.to,
+            calendar_id: self.calendar,
+            meta: meta_conditions(name, self.meta)?,
+            categories: self.categories,
+            unchecked: self.unchecked,
+            ..EventConditions::default()
+        }))
+    }
+}
+
+fn meta_conditions(
+    name: &str,
+    meta: BTreeMap<String, String>,
+) -> Result<Vec<(String, String)>, String> {
+    meta.into_iter()
+        .map(|(key, value)| Ok((normalize_meta_key(&key)?, value)))
+        .collect::<Result<_, String>>()
+        .map_err(|e| format!("Invalid list '{name}': bad meta {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(toml: &str) -> Result<SmartLists, String> {
+        toml::from_str::<BTreeMap<String, SmartListDef>>(toml)
+            .map_err(|e| e.to_string())
+            .and_then(SmartLists::try_from)
+    }
+
+    #[test]
+    fn builtins_come_first() {
+        let lists = SmartLists::default();
+        let names: Vec<_> = lists.lists().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["today", "upcoming", "someday", "next"]);
+
+        let Some(SmartListQuery::Todos { conds, limit, .. }) =
+            lists.get("someday").map(|l| &l.query)
+        else {
+            panic!("someday is a todo list");
+        };
+        assert!(conds.undated);
+        assert_eq!(*limit, None);
+    }
+
+    #[test]
+    fn compiles_user_lists_replacing_builtins() {
+        let lists = parse(
+            r#"
+[work]
+icon = "💼"
+categories = ["Work"]
+meta = { Client = "acme" }
+sort = ["due"]
+limit = 5
+
+[meetings]
+kind = "events"
+from = "today"
+to = "7d"
+
+[today]
+due = "tomorrow"
+"#,
+        )
+        .unwrap();
+        let names: Vec<_> = lists.lists().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["today", "upcoming", "someday", "next", "meetings", "work"]
+        );
+
+        let work = lists.get("work").unwrap();
+        assert_eq!(work.icon.as_deref(), Some("💼"));
+        let SmartListQuery::Todos { conds, sort, limit } = &work.query else {
+            panic!("work is a todo list");
+        };
+        assert_eq!(conds.status, Some(TodoStatus::NeedsAction));
+        assert_eq!(conds.categories, ["Work"]);
+        assert_eq!(conds.meta, [("client".to_string(), "acme".to_string())]);
+        assert!(matches!(sort.as_slice(), [TodoSort::Due(SortOrder::Asc)]));
+        assert_eq!(*limit, Some(5));
+
+        let SmartListQuery::Events(conds) = &lists.get("meetings").unwrap().query else {
+            panic!("meetings is an event list");
+        };
+        assert_eq!(conds.cutoff, Some(DateTimeAnchor::InDays(7)));
+
+        let SmartListQuery::Todos { conds, .. } = &lists.get("today").unwrap().query else {
+            panic!("today is a todo list");
+        };
+        assert_eq!(conds.due, Some(DateTimeAnchor::tomorrow()));
+    }
+
+    #[test]
+    fn rejects_lists_that_would_not_run() {
+        let cases = [
+            ("[a]\ndue = \"today\"\nundated = true", "cannot be combined"),
+            ("[a]\nunchecked = true", "only applies to event lists"),
+            (
+                "[a]\nkind = \"events\"\nlimit = 3",
+                "only applies to todo lists",
+            ),
+            ("[a]\nlimit = 0", "must be positive"),
+            ("[a]\nstatus = \"done\"", "unknown status"),
+            ("[a]\nmeta = { \"--\" = \"x\" }", "bad meta"),
+            (
+                "[\"a b\"]\ninbox = true",
+                "cannot be empty or contain spaces",
+            ),
+            ("[a]\ncategory = \"work\"", "unknown field"),
+        ];
+        for (toml, expected) in cases {
+            let err = parse(toml).unwrap_err();
+            assert!(err.contains(expected), "{toml}: {err}");
+        }
+    }
+}
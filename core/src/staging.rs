@@ -0,0 +1,381 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes staged in the cache instead of going to their store right away, so they never wait on a
+//! slow or unreachable store.
+//!
+//! A staged item is cached like any other, while the operation on it is kept in the
+//! `pending_ops` table with the component to push. The next sync pushes the operations in the
+//! order they were staged, before refreshing the cache from the stores. An operation that fails,
+//! such as when the store is offline, is kept to be tried again by the following sync.
+//!
+//! The operations are first planned as a [`SyncPlan`], which is then pushed as is, so a plan
+//! previewed before a sync lists exactly what the sync writes to the stores. The plan lists what
+//! the sync pulls from each store as well, see [`Store::plan_pull`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use aimcal_ical::ops::{ComponentChange, diff};
+use aimcal_ical::{CalendarComponent, ICalendar, formatter::format};
+
+use crate::db::Db;
+use crate::db::pending_ops::{PendingOpRecord, PendingOperation};
+use crate::store::{PulledChange, Store, StoreError};
+use crate::{AimError, Kind};
+
+/// Outcome of pushing the staged operations to their stores.
+#[derive(Debug, Default)]
+pub(crate) struct PushReport {
+    /// Number of operations pushed.
+    pub pushed: usize,
+    /// UIDs of the items whose operation failed, along with why, kept to be tried again.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Which way a sync carries a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// From a store into the cache.
+    Pull,
+    /// From the cache to a store.
+    Push,
+}
+
+impl SyncDirection {
+    /// Name of the direction, such as `push`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncDirection::Pull => "pull",
+            SyncDirection::Push => "push",
+        }
+    }
+}
+
+/// What a sync does to an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOperation {
+    /// Creates the item.
+    Create,
+    /// Updates the item.
+    Update,
+    /// Deletes the item.
+    Delete,
+}
+
+impl SyncOperation {
+    /// Name of the operation, such as `create`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncOperation::Create => "create",
+            SyncOperation::Update => "update",
+            SyncOperation::Delete => "delete",
+        }
+    }
+}
+
+/// A change a sync makes to an item, see [`SyncPlan`].
+#[derive(Debug, Clone)]
+pub struct SyncChange {
+    /// UID of the item.
+    pub uid: String,
+    /// Whether the item is an event or a todo.
+    pub kind: Kind,
+    /// Whether the change is pushed to the store or pulled from it.
+    pub direction: SyncDirection,
+    /// What is done to the item.
+    pub operation: SyncOperation,
+    /// ID of the calendar whose store the item is pushed to or pulled from.
+    pub calendar_id: String,
+    /// Summary of the item, if it has one.
+    pub summary: Option<String>,
+    /// Properties that differ between the store and the cache, ordered by name, such as
+    /// `SUMMARY`. A created item is not on the other side yet, so these are all of its
+    /// properties, while a deleted one lists none.
+    pub fields: Vec<String>,
+    /// The staged operation a push carries out, along with the component it pushes.
+    staged: Option<(i64, CalendarComponent<String>)>,
+}
+
+impl SyncChange {
+    fn pulled(calendar_id: &str, change: PulledChange) -> Self {
+        Self {
+            uid: change.uid,
+            kind: change.kind,
+            direction: SyncDirection::Pull,
+            operation: change.operation,
+            calendar_id: calendar_id.to_string(),
+            summary: change.summary,
+            fields: change.fields,
+            staged: None,
+        }
+    }
+}
+
+/// The changes a sync makes, planned by [`Aim::plan_sync`]: the writes pushed to the stores, in
+/// order, then the changes pulled from them into the cache.
+///
+/// A plan is the very one [`Aim::apply_sync`] carries out, so the writes it lists are all that
+/// reaches the stores. Only staged writes are pushed, which create items, as other edits and
+/// deletions go to the stores right away. The changes pulled are those found when planning, by
+/// comparing each store with the cache, see [`Store::plan_pull`].
+///
+/// [`Aim::plan_sync`]: crate::Aim::plan_sync
+/// [`Aim::apply_sync`]: crate::Aim::apply_sync
+#[derive(Debug, Clone, Default)]
+pub struct SyncPlan {
+    /// The changes, the writes to push first, in the order they were staged.
+    pub changes: Vec<SyncChange>,
+    /// UIDs of the items whose staged write cannot be read, along with why, kept as they are.
+    pub skipped: Vec<(String, String)>,
+}
+
+impl SyncPlan {
+    /// Whether the sync changes nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.skipped.is_empty()
+    }
+
+    /// The writes pushed to the stores, in order.
+    pub fn pushes(&self) -> impl Iterator<Item = &SyncChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.direction == SyncDirection::Push)
+    }
+
+    /// The changes pulled from the stores into the cache.
+    pub fn pulls(&self) -> impl Iterator<Item = &SyncChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.direction == SyncDirection::Pull)
+    }
+}
+
+/// Names of all the properties of a component, ordered by name, such as `SUMMARY`.
+pub(crate) fn properties_of(component: &CalendarComponent<String>) -> Vec<String> {
+    let calendar = ICalendar {
+        components: vec![component.clone()],
+        ..ICalendar::new()
+    };
+    diff(&ICalendar::new(), &calendar)
+        .iter()
+        .flat_map(ComponentChange::properties)
+        .map(|property| property.to_string())
+        .collect()
+}
+
+/// Serializes a component to stage, as a `VCALENDAR`.
+pub(crate) fn write_staged(component: CalendarComponent<String>) -> Result<String, AimError> {
+    let calendar = ICalendar {
+        components: vec![component],
+        ..ICalendar::new()
+    };
+    format(&calendar).map_err(|e| AimError::Other(format!("Failed to serialize component: {e}")))
+}
+
+/// Parses the component of a staged operation.
+pub(crate) fn read_staged(ics: &str) -> Result<CalendarComponent<String>, AimError> {
+    let parse_error = |errors| AimError::Parse {
+        file: PathBuf::from("pending_ops"),
+        errors,
+    };
+    aimcal_ical::parse(ics)
+        .map_err(|errors| parse_error(errors.iter().map(ToString::to_string).collect()))?
+        .into_iter()
+        .next()
+        .and_then(|calendar| calendar.to_owned().components.into_iter().next())
+        .ok_or_else(|| parse_error(vec!["No component found".to_string()]))
+}
+
+/// Plans pushing the staged operations to the stores of their calendars, in the order they were
+/// staged.
+///
+/// # Errors
+/// If database access fails. Operations that cannot be read are skipped instead.
+pub(crate) async fn plan_pending(db: &Db) -> Result<SyncPlan, AimError> {
+    let mut plan = SyncPlan::default();
+    for op in db.pending_ops.list().await? {
+        match plan_op(&op) {
+            Ok(change) => plan.changes.push(change),
+            Err(e) => plan.skipped.push((op.uid, e.to_string())),
+        }
+    }
+    Ok(plan)
+}
+
+/// Plans the changes pulled from the store of `calendar_id` into the cache, adding them to `plan`
+/// ordered by UID.
+///
+/// # Errors
+/// If the store cannot be read.
+pub(crate) async fn plan_pull(
+    plan: &mut SyncPlan,
+    calendar_id: &str,
+    store: &dyn Store,
+) -> Result<(), StoreError> {
+    let mut pulls = store.plan_pull().await?;
+    pulls.sort_by(|a, b| a.uid.cmp(&b.uid));
+    plan.changes.extend(
+        pulls
+            .into_iter()
+            .map(|change| SyncChange::pulled(calendar_id, change)),
+    );
+    Ok(())
+}
+
+fn plan_op(op: &PendingOpRecord) -> Result<SyncChange, AimError> {
+    let Some(PendingOperation::Create) = op.operation() else {
+        return Err(AimError::Other("unknown staged operation".to_string()));
+    };
+    let component = read_staged(&op.ics)?;
+    let (kind, summary) = match (op.kind(), &component) {
+        (Some(Kind::Todo), CalendarComponent::Todo(todo)) => (Kind::Todo, todo.summary.as_ref()),
+        (Some(Kind::Event), CalendarComponent::Event(event)) => {
+            (Kind::Event, event.summary.as_ref())
+        }
+        _ => {
+            return Err(AimError::Other(
+                "staged component of the wrong kind".to_string(),
+            ));
+        }
+    };
+
+    Ok(SyncChange {
+        summary: summary.map(|s| s.content.to_string()),
+        uid: op.uid.clone(),
+        kind,
+        direction: SyncDirection::Push,
+        operation: SyncOperation::Create,
+        calendar_id: op.calendar_id.clone(),
+        // A created item has nothing in the store to compare with, so all its properties differ
+        fields: properties_of(&component),
+        staged: Some((op.id, component)),
+    })
+}
+
+/// Pushes the writes of a plan to the stores, in order, leaving the changes to pull to the
+/// synchronization of the stores.
+///
+/// # Errors
+/// If database access fails. Writes the stores refuse are reported as failed instead.
+pub(crate) async fn push_planned(
+    db: &Db,
+    stores: &HashMap<String, Box<dyn Store>>,
+    plan: SyncPlan,
+) -> Result<PushReport, AimError> {
+    let mut report = PushReport {
+        pushed: 0,
+        failed: plan.skipped,
+    };
+    for change in plan.changes {
+        let Some((op_id, component)) = &change.staged else {
+            continue;
+        };
+        match push(db, stores, &change, component).await {
+            Ok(()) => {
+                db.pending_ops.delete(*op_id).await?;
+                report.pushed += 1;
+            }
+            Err(e) => {
+                tracing::warn!(uid = change.uid, calendar_id = change.calendar_id, err = %e, "failed to push staged operation");
+                report.failed.push((change.uid, e.to_string()));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Pushes the staged operations to the stores of their calendars, in the order they were staged.
+///
+/// # Errors
+/// If database access fails. Operations the stores refuse are reported as failed instead.
+pub(crate) async fn push_pending(
+    db: &Db,
+    stores: &HashMap<String, Box<dyn Store>>,
+) -> Result<PushReport, AimError> {
+    let plan = plan_pending(db).await?;
+    push_planned(db, stores, plan).await
+}
+
+async fn push(
+    db: &Db,
+    stores: &HashMap<String, Box<dyn Store>>,
+    change: &SyncChange,
+    component: &CalendarComponent<String>,
+) -> Result<(), AimError> {
+    let store = stores
+        .get(&change.calendar_id)
+        .ok_or_else(|| AimError::NotFound {
+            kind: "Calendar",
+            id: change.calendar_id.clone(),
+        })?;
+
+    let resource_id = match (change.operation, component) {
+        (SyncOperation::Create, CalendarComponent::Todo(todo)) => {
+            store.create_todo(&change.uid, todo).await
+        }
+        (SyncOperation::Create, CalendarComponent::Event(event)) => {
+            store.create_event(&change.uid, event).await
+        }
+        _ => {
+            return Err(AimError::Other(
+                "staged component of the wrong kind".to_string(),
+            ));
+        }
+    }
+    .map_err(|e| AimError::from_store(&change.uid, e))?;
+    db.resources
+        .insert(&change.uid, &change.calendar_id, &resource_id, None)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Zoned;
+
+    use super::*;
+    use crate::TodoStatus;
+    use crate::todo::ResolvedTodoDraft;
+
+    #[test]
+    fn staged_components_round_trip() {
+        let now = Zoned::now();
+        let todo = ResolvedTodoDraft {
+            alarm: None,
+            categories: &[],
+            color: None,
+            description: None,
+            due: None,
+            estimate: None,
+            parent: None,
+            percent_complete: None,
+            priority: None,
+            status: TodoStatus::NeedsAction,
+            summary: "Call the bank",
+            now: &now,
+            recur_from: None,
+        }
+        .into_ics("todo-1");
+        let ics = write_staged(CalendarComponent::Todo(todo)).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR"));
+
+        let Ok(CalendarComponent::Todo(todo)) = read_staged(&ics) else {
+            panic!("expected a todo");
+        };
+        assert_eq!(todo.uid.content.to_string(), "todo-1");
+        assert_eq!(
+            todo.summary.map(|s| s.content.to_string()).as_deref(),
+            Some("Call the bank")
+        );
+    }
+
+    #[test]
+    fn rejects_staged_text_without_component() {
+        assert!(read_staged("").is_err());
+    }
+}
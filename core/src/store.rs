@@ -4,16 +4,25 @@
 
 pub mod caldav;
 pub mod local;
+mod registered;
+pub mod subscription;
 
 pub use caldav::CaldavStore;
 pub use local::LocalStore;
+pub(crate) use registered::RegisteredStore;
+pub use subscription::{SubscriptionConfig, SubscriptionStore};
 
 use std::error::Error;
 
-use aimcal_ical::{VEvent, VTodo};
+use aimcal_ical::ops::ComponentChange;
+use aimcal_ical::{CalendarComponent, VEvent, VTodo};
 use async_trait::async_trait;
 
-use crate::{EventPatch, TodoPatch};
+use crate::db::Db;
+use crate::db::events::EventRecord;
+use crate::db::todos::TodoRecord;
+use crate::staging::properties_of;
+use crate::{AimError, Event, EventPatch, Invitation, Kind, SyncOperation, Todo, TodoPatch};
 
 /// Error type for store operations that is Send + Sync.
 pub type StoreError = Box<dyn Error + Send + Sync>;
@@ -29,10 +38,183 @@ pub struct SyncResult {
     pub deleted: usize,
 }
 
+/// An item of a calendar left out of the cache, as it could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenItem {
+    /// Where the item is, such as the path of its file.
+    pub source: String,
+    /// Name of the component of the item, such as `VEVENT`.
+    pub component: String,
+    /// UID of the item, if it could be read.
+    pub uid: Option<String>,
+    /// Messages of the errors found in the item.
+    pub errors: Vec<String>,
+}
+
+/// What a store supports, checked before aim writes to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreCapabilities {
+    /// Whether the store refuses creating and editing items.
+    pub read_only: bool,
+    /// Whether the store can hold events.
+    pub supports_events: bool,
+    /// Whether the store can hold todos.
+    pub supports_todos: bool,
+}
+
+impl Default for StoreCapabilities {
+    fn default() -> Self {
+        Self {
+            read_only: false,
+            supports_events: true,
+            supports_todos: true,
+        }
+    }
+}
+
+/// A resource holding an item in a store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreResource {
+    /// Opaque identifier of the resource, scoped to its store, such as a file URL or a
+    /// `CalDAV` href.
+    pub id: String,
+    /// Token that changes whenever the resource does, such as an `ETag`, if the store has one.
+    pub change_token: Option<String>,
+}
+
+/// A change the next synchronization of the cache pulls from a store, see [`Store::plan_pull`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PulledChange {
+    /// UID of the item.
+    pub uid: String,
+    /// Whether the item is an event or a todo.
+    pub kind: Kind,
+    /// What is done to the cached item.
+    pub operation: SyncOperation,
+    /// Summary of the item, if it has one.
+    pub summary: Option<String>,
+    /// Properties that differ from the cache, ordered by name, such as `SUMMARY`. A created item
+    /// is not cached yet, so these are all of its properties, while a deleted one lists none.
+    pub fields: Vec<String>,
+}
+
+impl PulledChange {
+    /// Plans pulling `component` into the cache of `calendar_id`, comparing it with the item cached
+    /// if `cached`, or `None` if the cached item holds the same.
+    ///
+    /// An item cached without its properties, such as one only known by its resource, lists all of
+    /// them.
+    pub(crate) async fn of(
+        db: &Db,
+        calendar_id: &str,
+        component: &CalendarComponent<String>,
+        cached: bool,
+    ) -> Result<Option<Self>, StoreError> {
+        let (uid, kind, summary, changed) = match component {
+            CalendarComponent::Event(event) => {
+                let uid = event.uid.content.to_string();
+                let record = if cached {
+                    db.events.get(&uid).await?
+                } else {
+                    None
+                };
+                let changed = record.map(|r| {
+                    r.changed_properties(&EventRecord::from_event(&uid, event, calendar_id))
+                });
+                (uid, Kind::Event, event.summary.as_ref(), changed)
+            }
+            CalendarComponent::Todo(todo) => {
+                let uid = todo.uid.content.to_string();
+                let record = if cached {
+                    db.todos.get(&uid).await?
+                } else {
+                    None
+                };
+                let changed = record
+                    .map(|r| r.changed_properties(&TodoRecord::from_todo(&uid, todo, calendar_id)));
+                (uid, Kind::Todo, todo.summary.as_ref(), changed)
+            }
+            _ => return Ok(None),
+        };
+
+        let (operation, fields) = match changed {
+            Some(changed) if changed.is_empty() => return Ok(None),
+            Some(changed) => (
+                SyncOperation::Update,
+                changed.into_iter().map(ToString::to_string).collect(),
+            ),
+            None if cached => (SyncOperation::Update, properties_of(component)),
+            None => (SyncOperation::Create, properties_of(component)),
+        };
+        Ok(Some(Self {
+            uid,
+            kind,
+            operation,
+            summary: summary.map(|s| s.content.to_string()),
+            fields,
+        }))
+    }
+
+    /// Plans removing the item `uid` from the cache, or `None` if it holds no such item.
+    pub(crate) async fn removal(db: &Db, uid: &str) -> Result<Option<Self>, StoreError> {
+        let (kind, summary) = if let Some(event) = db.events.get(uid).await? {
+            (Kind::Event, event.summary().into_owned())
+        } else if let Some(todo) = db.todos.get(uid).await? {
+            (Kind::Todo, todo.summary().into_owned())
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            uid: uid.to_string(),
+            kind,
+            operation: SyncOperation::Delete,
+            summary: (!summary.is_empty()).then_some(summary),
+            fields: Vec::new(),
+        }))
+    }
+
+    /// Plans a change found by comparing the copy of a store cached with the store, see
+    /// [`aimcal_ical::ops::diff`].
+    pub(crate) fn of_diff(change: &ComponentChange<'_>) -> Option<Self> {
+        let (operation, component) = match change {
+            ComponentChange::Added(component) => (SyncOperation::Create, *component),
+            ComponentChange::Removed(component) => (SyncOperation::Delete, *component),
+            ComponentChange::Modified { new, .. } => (SyncOperation::Update, *new),
+        };
+        let (uid, kind, summary) = match component {
+            CalendarComponent::Event(event) => (&event.uid, Kind::Event, event.summary.as_ref()),
+            CalendarComponent::Todo(todo) => (&todo.uid, Kind::Todo, todo.summary.as_ref()),
+            _ => return None,
+        };
+        let fields = match operation {
+            SyncOperation::Delete => Vec::new(),
+            _ => change
+                .properties()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        };
+        Some(Self {
+            uid: uid.content.to_string(),
+            kind,
+            operation,
+            summary: summary.map(|s| s.content.to_string()),
+            fields,
+        })
+    }
+}
+
 /// Store trait for storing and synchronizing events and todos.
 ///
 /// This trait abstracts different storage backends (local ICS files, `CalDAV` servers, etc.)
 /// providing a unified interface for CRUD operations on calendar items.
+///
+/// Stores other than the built-in ones can be added with [`AimBuilder::store`]. Each item
+/// lives in a resource whose ID only has to be meaningful to the store that returned it.
+/// Updates and deletions should be guarded against concurrent changes, failing with
+/// [`AimError::Conflict`] when the item changed in the store since aim last read it.
+///
+/// [`AimBuilder::store`]: crate::AimBuilder::store
 #[async_trait]
 pub trait Store: Send + Sync {
     /// Creates a new event in the store.
@@ -85,6 +267,27 @@ pub trait Store: Send + Sync {
     /// Returns an error if the event is not found or cannot be deleted.
     async fn delete_event(&self, uid: &str) -> Result<(), StoreError>;
 
+    /// Puts an override of occurrences of a recurring event next to it, replacing any override
+    /// with the same `RECURRENCE-ID`.
+    ///
+    /// # Arguments
+    ///
+    /// * `uid` - The unique identifier of the recurring event
+    /// * `event` - The override, with a `RECURRENCE-ID` property
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is not found or the override cannot be written. The default
+    /// implementation always fails, for stores that keep one component per resource.
+    async fn put_event_override(
+        &self,
+        uid: &str,
+        event: &VEvent<String>,
+    ) -> Result<(), StoreError> {
+        let _ = event;
+        Err(format!("store does not support overrides of recurring event {uid}").into())
+    }
+
     /// Creates a new todo in the store.
     ///
     /// # Arguments
@@ -131,14 +334,26 @@ pub trait Store: Send + Sync {
     /// Returns an error if the todo is not found or cannot be deleted.
     async fn delete_todo(&self, uid: &str) -> Result<(), StoreError>;
 
-    /// Lists all events in the store.
+    /// Lists all events in the store, along with the IDs of their resources.
     ///
     /// # Errors
     ///
     /// Returns an error if the events cannot be listed.
     async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError>;
 
-    /// Lists all todos in the store.
+    /// Lists the overrides of occurrences of recurring events kept next to them, see
+    /// [`Store::put_event_override`].
+    ///
+    /// The default implementation lists none, for stores that keep one component per resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events cannot be listed.
+    async fn list_event_overrides(&self) -> Result<Vec<VEvent<String>>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    /// Lists all todos in the store, along with the IDs of their resources.
     ///
     /// # Errors
     ///
@@ -165,6 +380,79 @@ pub trait Store: Send + Sync {
     /// This identifies which calendar in the database items from this store belong to.
     fn calendar_id(&self) -> &str;
 
+    /// Returns what the store supports.
+    ///
+    /// The default implementation allows writing events and todos.
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities::default()
+    }
+
+    /// Lists the scheduling messages, such as meeting invitations, waiting for the user on
+    /// the server of the store.
+    ///
+    /// The default implementation lists none, for stores without scheduling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the messages cannot be fetched.
+    async fn pending_invitations(&self) -> Result<Vec<Invitation>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the resources in the store, along with their change tokens.
+    ///
+    /// The default implementation lists the events and todos, without change tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resources cannot be listed.
+    async fn list_resources(&self) -> Result<Vec<StoreResource>, StoreError> {
+        let events = self.list_events().await?;
+        let todos = self.list_todos().await?;
+        Ok(events
+            .into_iter()
+            .map(|(id, _)| id)
+            .chain(todos.into_iter().map(|(id, _)| id))
+            .map(|id| StoreResource {
+                id,
+                change_token: None,
+            })
+            .collect())
+    }
+
+    /// Fetches the event or todo held by a resource.
+    ///
+    /// The default implementation searches the listed events and todos.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource is not found or cannot be read.
+    async fn get_component(
+        &self,
+        resource_id: &str,
+    ) -> Result<CalendarComponent<String>, StoreError> {
+        if let Some((_, event)) = self
+            .list_events()
+            .await?
+            .into_iter()
+            .find(|(id, _)| id == resource_id)
+        {
+            return Ok(CalendarComponent::Event(event));
+        }
+        if let Some((_, todo)) = self
+            .list_todos()
+            .await?
+            .into_iter()
+            .find(|(id, _)| id == resource_id)
+        {
+            return Ok(CalendarComponent::Todo(todo));
+        }
+        Err(Box::new(AimError::NotFound {
+            kind: "Resource",
+            id: resource_id.to_string(),
+        }))
+    }
+
     /// Synchronizes the store with the local cache (database).
     ///
     /// This operation scans the store for changes and updates the local
@@ -174,8 +462,47 @@ pub trait Store: Send + Sync {
     ///
     /// A `SyncResult` containing counts of created, updated, and deleted items.
     ///
+    /// The default implementation does nothing, for stores that have no cache of their own.
+    ///
     /// # Errors
     ///
     /// Returns an error if synchronization fails.
-    async fn sync_cache(&self) -> Result<SyncResult, StoreError>;
+    async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
+        Ok(SyncResult {
+            created: 0,
+            updated: 0,
+            deleted: 0,
+        })
+    }
+
+    /// Plans what the next [`Store::sync_cache`] pulls from the store into the cache, one change
+    /// per item, without changing either of them.
+    ///
+    /// The default implementation plans nothing, for stores that have no cache of their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    async fn plan_pull(&self) -> Result<Vec<PulledChange>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the items the last synchronization of the cache left out, as they could not be
+    /// parsed.
+    ///
+    /// The default implementation lists none, for stores that leave out no items.
+    fn broken_items(&self) -> Vec<BrokenItem> {
+        Vec::new()
+    }
+
+    /// Refreshes the store from its remote source, even if the cache is fresh.
+    ///
+    /// Stores without a notion of freshness simply synchronize their cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the refresh fails.
+    async fn refresh(&self) -> Result<SyncResult, StoreError> {
+        self.sync_cache().await
+    }
 }
@@ -4,7 +4,12 @@
 
 //! `CalDAV` store implementation for storing and synchronizing calendar data.
 
-use aimcal_caldav::{CalDavClient, CalDavConfig, CalendarQueryRequest, ETag, Href};
+use std::collections::{HashMap, HashSet};
+
+use aimcal_caldav::{
+    CalDavClient, CalDavConfig, CalDavError, CalendarQueryRequest, CalendarResource, ETag, Href,
+    ScheduleTag, SyncState, sync_calendar,
+};
 use aimcal_ical::{ICalendar, VEvent, VTodo, semantic::CalendarComponent};
 use async_trait::async_trait;
 use jiff::Zoned;
@@ -12,8 +17,8 @@ use serde::{Deserialize, Serialize};
 use tracing::{error, instrument};
 
 use crate::db::Db;
-use crate::store::{Store, StoreError, SyncResult};
-use crate::{EventPatch, TodoPatch};
+use crate::store::{PulledChange, Store, StoreError, StoreResource, SyncResult};
+use crate::{EventPatch, Invitation, TodoPatch};
 
 /// Metadata stored with `CalDAV` resources in the database.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +27,9 @@ pub struct CaldavMetadata {
     pub etag: String,
     /// Last-Modified header value from server.
     pub last_modified: Option<String>,
+    /// `Schedule-Tag` of scheduling objects, such as the events the user was invited to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_tag: Option<String>,
 }
 
 /// `CalDAV` store implementation.
@@ -40,6 +48,14 @@ pub struct CaldavStore {
     calendar_id: String,
 }
 
+/// A change to the items of a calendar, each needing its own privilege.
+#[derive(Debug, Clone, Copy)]
+enum Write {
+    Create,
+    Update,
+    Delete,
+}
+
 impl CaldavStore {
     /// Creates a new `CalDAV` backend from configuration.
     ///
@@ -53,13 +69,50 @@ impl CaldavStore {
         calendar_id: String,
     ) -> Result<Self, StoreError> {
         let client = CalDavClient::new(config)?;
+        Ok(Self::with_client(client, calendar_href, db, calendar_id))
+    }
 
-        Ok(Self {
+    /// Creates a new `CalDAV` backend with a client, such as one shared with the other calendars
+    /// of the server, so that they reuse its connections.
+    #[must_use]
+    pub fn with_client(
+        client: CalDavClient,
+        calendar_href: String,
+        db: Db,
+        calendar_id: String,
+    ) -> Self {
+        Self {
             client,
             calendar_href: Href::new(calendar_href),
             db,
             calendar_id,
-        })
+        }
+    }
+
+    /// Refuses writes the privileges the server reported for the current user do not allow, so
+    /// they fail before reaching the server.
+    async fn ensure_allowed(&self, write: Write) -> Result<(), StoreError> {
+        let Some(record) = self.db.calendars.get(&self.calendar_id).await? else {
+            return Ok(());
+        };
+        let privileges = record.privileges;
+        let (allowed, action) = match write {
+            Write::Create => (privileges.bind, "creating"),
+            Write::Update => (privileges.write_content, "editing"),
+            Write::Delete => (privileges.unbind, "deleting"),
+        };
+        if allowed {
+            return Ok(());
+        }
+        let shared_by = record
+            .owner
+            .map(|owner| format!(" shared by {owner}"))
+            .unwrap_or_default();
+        Err(format!(
+            "You don't have write access to calendar '{}'{shared_by}: {action} items is not allowed",
+            self.calendar_id
+        )
+        .into())
     }
 
     /// Extracts a single `VEvent` from an `ICalendar`.
@@ -89,6 +142,11 @@ impl CaldavStore {
             version: aimcal_ical::Version::default(),
             calscale: None,
             method: None,
+            names: Vec::new(),
+            descriptions: Vec::new(),
+            color: None,
+            refresh_interval: None,
+            source: None,
             components: vec![CalendarComponent::Event(event.clone())],
             x_properties: Vec::new(),
             retained_properties: Vec::new(),
@@ -102,6 +160,11 @@ impl CaldavStore {
             version: aimcal_ical::Version::default(),
             calscale: None,
             method: None,
+            names: Vec::new(),
+            descriptions: Vec::new(),
+            color: None,
+            refresh_interval: None,
+            source: None,
             components: vec![CalendarComponent::Todo(todo.clone())],
             x_properties: Vec::new(),
             retained_properties: Vec::new(),
@@ -156,6 +219,183 @@ impl CaldavStore {
             None => Ok(None),
         }
     }
+
+    /// Queries the resources holding `component` with REPORT, or `None` if the server lacks
+    /// REPORT.
+    async fn query_resources(
+        &self,
+        component: &str,
+    ) -> Result<Option<Vec<CalendarResource>>, StoreError> {
+        if !self.client.detect_capabilities().await?.can_query() {
+            return Ok(None);
+        }
+
+        let request = CalendarQueryRequest::new().component(component.to_string());
+        match self.client.query(&self.calendar_href, &request).await {
+            Ok(resources) => Ok(Some(resources)),
+            Err(CalDavError::UnsupportedCapability(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Fetches every resource holding a component matching `filter` with PROPFIND and GET,
+    /// for servers that lack REPORT.
+    async fn fetch_resources(
+        &self,
+        filter: fn(&CalendarComponent<String>) -> bool,
+    ) -> Result<Vec<CalendarResource>, StoreError> {
+        let changes =
+            sync_calendar(&self.client, &self.calendar_href, &SyncState::default()).await?;
+        Ok(changes
+            .added
+            .into_iter()
+            .filter(|resource| resource.data.components.iter().any(filter))
+            .collect())
+    }
+
+    /// Records the href and `ETag` of a resource, counting it as created or updated.
+    async fn cache_resource(
+        &self,
+        uid: &str,
+        resource: &CalendarResource,
+        result: &mut SyncResult,
+    ) -> Result<(), StoreError> {
+        let href = resource.href.as_str();
+        let etag = Self::etag_to_string(&resource.etag);
+        match self.get_resource_or_none(uid).await? {
+            // Same resource with the same ETag, nothing changed on the server
+            Some((existing_href, existing))
+                if existing_href == href
+                    && ETag::new(existing.etag.clone()).weak_eq(&resource.etag) =>
+            {
+                return Ok(());
+            }
+            Some((existing_href, _)) if existing_href == href => result.updated += 1,
+            // New resource, or a different href with the same UID, which should not happen
+            // with unique UIDs
+            _ => result.created += 1,
+        }
+
+        let metadata = CaldavMetadata {
+            etag,
+            last_modified: None,
+            schedule_tag: resource.schedule_tag.as_ref().map(ToString::to_string),
+        };
+        let metadata_json = serde_json::to_string(&metadata)?;
+        self.db
+            .resources
+            .insert(uid, &self.calendar_id, href, Some(&metadata_json))
+            .await?;
+        Ok(())
+    }
+
+    /// Uploads an updated resource, returning its new metadata.
+    ///
+    /// aim never organizes meetings itself, so an item with an organizer is the copy of an
+    /// invitation. For these, the update is conditioned on the `Schedule-Tag` the server
+    /// sent, so that it merges the participation of the user with replies of other
+    /// attendees instead of rejecting it, see [`CalDavClient::update_as_attendee`].
+    ///
+    /// Other updates are conditioned on the `ETag` cached, or the one of `resource` if none is.
+    /// Without either, the update is refused unless `overwrite` opts in to replacing whatever
+    /// the server holds.
+    async fn put_resource(
+        &self,
+        resource: &CalendarResource,
+        metadata: &CaldavMetadata,
+        calendar: &ICalendar<String>,
+        invited: bool,
+        overwrite: bool,
+    ) -> Result<CaldavMetadata, StoreError> {
+        let href = &resource.href;
+        let schedule_tag = resource
+            .schedule_tag
+            .clone()
+            .or_else(|| metadata.schedule_tag.clone().map(ScheduleTag::new));
+        let (etag, schedule_tag) = match schedule_tag {
+            Some(tag) if invited => {
+                let response = self.client.update_as_attendee(href, &tag, calendar).await?;
+                // Servers leave out the ETag when they merged the update
+                let etag = match response.etag {
+                    Some(etag) => etag,
+                    None => self.client.get_event(href).await?.etag,
+                };
+                (etag, response.schedule_tag.or(Some(tag)))
+            }
+            tag => {
+                let etag = match ETag::new(metadata.etag.clone()) {
+                    cached if cached.is_missing() => resource.etag.clone(),
+                    cached => cached,
+                };
+                let etag = if etag.is_missing() && overwrite {
+                    tracing::warn!(href = %href, "overwriting a resource without ETag");
+                    self.client.overwrite_event(href, calendar).await?
+                } else {
+                    self.client.update_event(href, &etag, calendar).await?
+                };
+                (etag, tag)
+            }
+        };
+        Ok(CaldavMetadata {
+            etag: Self::etag_to_string(&etag),
+            last_modified: None,
+            schedule_tag: schedule_tag.map(|tag| tag.to_string()),
+        })
+    }
+
+    /// Syncs a calendar on a server lacking REPORT, fetching only the resources whose `ETag`
+    /// changed since the last sync and forgetting the ones gone from the server.
+    async fn sync_with_etags(&self) -> Result<SyncResult, StoreError> {
+        let mut state = SyncState::default();
+        let mut uids = HashMap::new();
+        for record in self
+            .db
+            .resources
+            .list_by_calendar(&self.calendar_id)
+            .await?
+        {
+            if let Some(metadata) = record.metadata_json::<CaldavMetadata>() {
+                state.resource_etags.insert(
+                    Href::new(record.resource_id.clone()),
+                    ETag::new(metadata.etag),
+                );
+            }
+            uids.insert(record.resource_id, record.uid);
+        }
+
+        let changes = sync_calendar(&self.client, &self.calendar_href, &state).await?;
+
+        let mut result = SyncResult {
+            created: 0,
+            updated: 0,
+            deleted: 0,
+        };
+        for resource in changes.added.iter().chain(&changes.modified) {
+            let uid = resource.data.components.iter().find_map(|c| match c {
+                CalendarComponent::Event(event) => Some(event.uid.content.to_string()),
+                CalendarComponent::Todo(todo) => Some(todo.uid.content.to_string()),
+                _ => None,
+            });
+            if let Some(uid) = uid {
+                self.cache_resource(&uid, resource, &mut result).await?;
+            }
+        }
+        for href in &changes.deleted {
+            if let Some(uid) = uids.get(href.as_str()) {
+                self.forget(uid).await?;
+                result.deleted += 1;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Removes an item deleted from the server from the cache.
+    async fn forget(&self, uid: &str) -> Result<(), StoreError> {
+        self.db.events.delete(uid).await?;
+        self.db.todos.delete(uid).await?;
+        self.db.resources.delete(uid, &self.calendar_id).await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -163,6 +403,7 @@ impl CaldavStore {
 impl Store for CaldavStore {
     // #[instrument]
     async fn create_event(&self, uid: &str, event: &VEvent<String>) -> Result<String, StoreError> {
+        self.ensure_allowed(Write::Create).await?;
         let calendar = Self::wrap_event(event);
         let href = self.generate_href(uid);
         let etag = self.client.create_event(&href, &calendar).await?;
@@ -170,6 +411,7 @@ impl Store for CaldavStore {
         let metadata = CaldavMetadata {
             etag: Self::etag_to_string(&etag),
             last_modified: None,
+            schedule_tag: None,
         };
 
         let metadata_json = serde_json::to_string(&metadata)?;
@@ -198,6 +440,7 @@ impl Store for CaldavStore {
         uid: &str,
         patch: &EventPatch,
     ) -> Result<VEvent<String>, StoreError> {
+        self.ensure_allowed(Write::Update).await?;
         let (href, metadata) = self
             .get_resource(uid)
             .await?
@@ -214,17 +457,17 @@ impl Store for CaldavStore {
 
         // Upload updated event
         let calendar = Self::wrap_event(&event);
-        let etag = ETag::new(metadata.etag.clone());
-        let new_etag = self
-            .client
-            .update_event(&Href::new(href.clone()), &etag, &calendar)
+        let new_metadata = self
+            .put_resource(
+                &resource,
+                &metadata,
+                &calendar,
+                event.organizer.is_some(),
+                false,
+            )
             .await?;
 
         // Update metadata in database
-        let new_metadata = CaldavMetadata {
-            etag: Self::etag_to_string(&new_etag),
-            last_modified: None,
-        };
 
         let metadata_json = serde_json::to_string(&new_metadata)?;
         self.db
@@ -237,6 +480,7 @@ impl Store for CaldavStore {
 
     // #[instrument]
     async fn delete_event(&self, uid: &str) -> Result<(), StoreError> {
+        self.ensure_allowed(Write::Delete).await?;
         let (href, metadata) = self
             .get_resource(uid)
             .await?
@@ -254,6 +498,7 @@ impl Store for CaldavStore {
 
     // #[instrument]
     async fn create_todo(&self, uid: &str, todo: &VTodo<String>) -> Result<String, StoreError> {
+        self.ensure_allowed(Write::Create).await?;
         let calendar = Self::wrap_todo(todo);
         let href = self.generate_href(uid);
         let etag = self.client.create_todo(&href, &calendar).await?;
@@ -261,6 +506,7 @@ impl Store for CaldavStore {
         let metadata = CaldavMetadata {
             etag: Self::etag_to_string(&etag),
             last_modified: None,
+            schedule_tag: None,
         };
 
         let metadata_json = serde_json::to_string(&metadata)?;
@@ -285,6 +531,7 @@ impl Store for CaldavStore {
 
     // #[instrument]
     async fn update_todo(&self, uid: &str, patch: &TodoPatch) -> Result<VTodo<String>, StoreError> {
+        self.ensure_allowed(Write::Update).await?;
         let (href, metadata) = self
             .get_resource(uid)
             .await?
@@ -301,17 +548,17 @@ impl Store for CaldavStore {
 
         // Upload updated todo
         let calendar = Self::wrap_todo(&todo);
-        let etag = ETag::new(metadata.etag.clone());
-        let new_etag = self
-            .client
-            .update_todo(&Href::new(href.clone()), &etag, &calendar)
+        let new_metadata = self
+            .put_resource(
+                &resource,
+                &metadata,
+                &calendar,
+                todo.organizer.is_some(),
+                false,
+            )
             .await?;
 
         // Update metadata in database
-        let new_metadata = CaldavMetadata {
-            etag: Self::etag_to_string(&new_etag),
-            last_modified: None,
-        };
 
         let metadata_json = serde_json::to_string(&new_metadata)?;
         self.db
@@ -324,6 +571,7 @@ impl Store for CaldavStore {
 
     // #[instrument]
     async fn delete_todo(&self, uid: &str) -> Result<(), StoreError> {
+        self.ensure_allowed(Write::Delete).await?;
         let (href, metadata) = self
             .get_resource(uid)
             .await?
@@ -341,8 +589,13 @@ impl Store for CaldavStore {
 
     #[instrument(skip(self))]
     async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError> {
-        let request = CalendarQueryRequest::new().component("VEVENT".to_string());
-        let resources = self.client.query(&self.calendar_href, &request).await?;
+        let resources = match self.query_resources("VEVENT").await? {
+            Some(resources) => resources,
+            None => {
+                self.fetch_resources(|c| matches!(c, CalendarComponent::Event(_)))
+                    .await?
+            }
+        };
 
         let mut result = Vec::new();
         for resource in resources {
@@ -379,8 +632,13 @@ impl Store for CaldavStore {
 
     #[instrument(skip(self))]
     async fn list_todos(&self) -> Result<Vec<(String, VTodo<String>)>, StoreError> {
-        let request = CalendarQueryRequest::new().component("VTODO".to_string());
-        let resources = self.client.query(&self.calendar_href, &request).await?;
+        let resources = match self.query_resources("VTODO").await? {
+            Some(resources) => resources,
+            None => {
+                self.fetch_resources(|c| matches!(c, CalendarComponent::Todo(_)))
+                    .await?
+            }
+        };
 
         let mut result = Vec::new();
         for resource in resources {
@@ -421,155 +679,161 @@ impl Store for CaldavStore {
     }
 
     // #[instrument]
+    async fn pending_invitations(&self) -> Result<Vec<Invitation>, StoreError> {
+        let messages = match self.client.fetch_inbox().await {
+            Ok(messages) => messages,
+            // Servers without scheduling have no invitations to deliver
+            Err(CalDavError::UnsupportedCapability(_)) => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(messages
+            .iter()
+            .flat_map(|message| Invitation::from_message(&self.calendar_id, &message.resource.data))
+            .collect())
+    }
+
     fn calendar_id(&self) -> &str {
         &self.calendar_id
     }
 
-    // #[instrument]
-    async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
-        // Ensure capabilities are discovered before querying
-        self.client.discover().await?;
-
-        let mut created = 0;
-        let mut updated = 0;
-        let deleted = 0;
+    /// Lists the hrefs in the calendar collection, with their `ETag` as change token.
+    #[instrument(skip(self))]
+    async fn list_resources(&self) -> Result<Vec<StoreResource>, StoreError> {
+        let etags = self.client.list_etags(&self.calendar_href).await?;
+        Ok(etags
+            .into_iter()
+            .map(|(href, etag)| StoreResource {
+                id: href.as_str().to_string(),
+                change_token: Some(Self::etag_to_string(&etag)),
+            })
+            .collect())
+    }
 
-        // Query all VEVENT resources
-        let event_request = CalendarQueryRequest::new().component("VEVENT".to_string());
-        let event_resources = self
+    #[instrument(skip(self))]
+    async fn get_component(
+        &self,
+        resource_id: &str,
+    ) -> Result<CalendarComponent<String>, StoreError> {
+        let resource = self
             .client
-            .query(&self.calendar_href, &event_request)
+            .get_event(&Href::new(resource_id.to_string()))
+            .await?;
+        resource
+            .data
+            .components
+            .into_iter()
+            .find(|c| matches!(c, CalendarComponent::Event(_) | CalendarComponent::Todo(_)))
+            .ok_or_else(|| format!("No event or todo found at {resource_id}").into())
+    }
+
+    #[instrument(skip(self), fields(href = %self.calendar_href.as_str()))]
+    async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
+        // Ensure capabilities are detected before querying
+        self.client.detect_capabilities().await?;
+
+        // Record who shares the calendar and whether we may write to it
+        let collection = self.client.get_calendar(&self.calendar_href).await?;
+        self.db
+            .calendars
+            .set_sharing(
+                &self.calendar_id,
+                collection.owner.as_ref().map(Href::as_str),
+                collection.privileges.into(),
+            )
             .await?;
 
-        for resource in event_resources {
+        // Query all VEVENT and VTODO resources, or fall back to comparing ETags
+        let Some(event_resources) = self.query_resources("VEVENT").await? else {
+            return self.sync_with_etags().await;
+        };
+        let Some(todo_resources) = self.query_resources("VTODO").await? else {
+            return self.sync_with_etags().await;
+        };
+
+        let mut result = SyncResult {
+            created: 0,
+            updated: 0,
+            deleted: 0,
+        };
+        for resource in &event_resources {
             if let Ok(event) = Self::extract_event(&resource.data) {
                 let uid = event.uid.content.to_string();
-                let href = resource.href.as_str().to_string();
-                let etag_str = Self::etag_to_string(&resource.etag);
-
-                if let Some((existing_href, existing_metadata)) =
-                    self.get_resource_or_none(&uid).await?
-                {
-                    if existing_href == href {
-                        // Same resource - check if ETag changed
-                        if existing_metadata.etag != etag_str {
-                            // Resource was updated on server
-                            let metadata = CaldavMetadata {
-                                etag: etag_str,
-                                last_modified: None,
-                            };
-                            let metadata_json = serde_json::to_string(&metadata)?;
-                            self.db
-                                .resources
-                                .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                                .await?;
-                            updated += 1;
-                        }
-                    } else {
-                        // Different href - this is a new resource with same UID
-                        // In practice, this should not happen with unique UIDs
-                        let metadata = CaldavMetadata {
-                            etag: etag_str,
-                            last_modified: None,
-                        };
-                        let metadata_json = serde_json::to_string(&metadata)?;
-                        self.db
-                            .resources
-                            .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                            .await?;
-                        created += 1;
-                    }
-                } else {
-                    // New resource
-                    let metadata = CaldavMetadata {
-                        etag: etag_str,
-                        last_modified: None,
-                    };
-                    let metadata_json = serde_json::to_string(&metadata)?;
-                    self.db
-                        .resources
-                        .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                        .await?;
-                    created += 1;
-                }
+                self.cache_resource(&uid, resource, &mut result).await?;
             }
         }
-
-        // Query all VTODO resources
-        let todo_request = CalendarQueryRequest::new().component("VTODO".to_string());
-        let todo_resources = self
-            .client
-            .query(&self.calendar_href, &todo_request)
-            .await?;
-
-        for resource in todo_resources {
+        for resource in &todo_resources {
             if let Ok(todo) = Self::extract_todo(&resource.data) {
                 let uid = todo.uid.content.to_string();
-                let href = resource.href.as_str().to_string();
-                let etag_str = Self::etag_to_string(&resource.etag);
-
-                if let Some((existing_href, existing_metadata)) =
-                    self.get_resource_or_none(&uid).await?
-                {
-                    if existing_href == href {
-                        // Same resource - check if ETag changed
-                        if existing_metadata.etag != etag_str {
-                            // Resource was updated on server
-                            let metadata = CaldavMetadata {
-                                etag: etag_str,
-                                last_modified: None,
-                            };
-                            let metadata_json = serde_json::to_string(&metadata)?;
-                            self.db
-                                .resources
-                                .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                                .await?;
-                            updated += 1;
-                        }
-                    } else {
-                        // Different href - this is a new resource with same UID
-                        let metadata = CaldavMetadata {
-                            etag: etag_str,
-                            last_modified: None,
-                        };
-                        let metadata_json = serde_json::to_string(&metadata)?;
-                        self.db
-                            .resources
-                            .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                            .await?;
-                        created += 1;
-                    }
-                } else {
-                    // New resource
-                    let metadata = CaldavMetadata {
-                        etag: etag_str,
-                        last_modified: None,
-                    };
-                    let metadata_json = serde_json::to_string(&metadata)?;
-                    self.db
-                        .resources
-                        .insert(&uid, &self.calendar_id, &href, Some(&metadata_json))
-                        .await?;
-                    created += 1;
-                }
+                self.cache_resource(&uid, resource, &mut result).await?;
             }
         }
 
-        // Note: We don't handle deletions here because we'd need to track
-        // all known UIDs and compare with what's on the server.
-        // This is a more complex operation that may be added later.
+        // The resources cached but no longer on the server were deleted there
+        let listed: HashSet<&str> = event_resources
+            .iter()
+            .chain(&todo_resources)
+            .map(|r| r.href.as_str())
+            .collect();
+        for record in self
+            .db
+            .resources
+            .list_by_calendar(&self.calendar_id)
+            .await?
+        {
+            if !listed.contains(record.resource_id.as_str()) {
+                self.forget(&record.uid).await?;
+                result.deleted += 1;
+            }
+        }
 
-        Ok(SyncResult {
-            created,
-            updated,
-            deleted,
-        })
+        Ok(result)
+    }
+
+    #[instrument(skip(self), fields(href = %self.calendar_href.as_str()))]
+    async fn plan_pull(&self) -> Result<Vec<PulledChange>, StoreError> {
+        let mut cached: HashMap<String, (String, Option<CaldavMetadata>)> = self
+            .db
+            .resources
+            .list_by_calendar(&self.calendar_id)
+            .await?
+            .into_iter()
+            .map(|record| {
+                let metadata = record.metadata_json::<CaldavMetadata>();
+                (record.resource_id, (record.uid, metadata))
+            })
+            .collect();
+
+        let mut changes = Vec::new();
+        for resource in self.list_resources().await? {
+            let known = cached.remove(&resource.id);
+            // Same ETag as cached, nothing changed on the server, see `cache_resource`
+            let unchanged = known
+                .as_ref()
+                .and_then(|(_, metadata)| metadata.as_ref())
+                .zip(resource.change_token)
+                .is_some_and(|(metadata, etag)| {
+                    ETag::new(metadata.etag.clone()).weak_eq(&ETag::new(etag))
+                });
+            if unchanged {
+                continue;
+            }
+            let component = self.get_component(&resource.id).await?;
+            let planned =
+                PulledChange::of(&self.db, &self.calendar_id, &component, known.is_some()).await?;
+            changes.extend(planned);
+        }
+
+        for (uid, _) in cached.into_values() {
+            changes.extend(PulledChange::removal(&self.db, &uid).await?);
+        }
+        Ok(changes)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aimcal_caldav_mock::{MockCalDav, Scenario};
     use aimcal_ical::TodoStatusValue;
     use aimcal_ical::{Description, DtEnd, DtStamp, DtStart, Summary, Uid};
     use jiff::{civil, tz};
@@ -577,6 +841,7 @@ mod tests {
     use wiremock::matchers::{header, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    use crate::CalendarPrivileges;
     use crate::db::Db;
     use aimcal_ical::TodoStatus;
 
@@ -590,12 +855,12 @@ mod tests {
                 retained_parameters: Vec::new(),
                 span: (),
             }),
-            dt_start: DtStart::new(crate::LooseDateTime::Local(
+            dt_start: Some(DtStart::new(crate::LooseDateTime::Local(
                 civil::date(2025, 1, 15)
                     .at(10, 0, 0, 0)
                     .to_zoned(tz::TimeZone::UTC)
                     .unwrap(),
-            )),
+            ))),
             dt_end: Some(DtEnd::new(crate::LooseDateTime::Local(
                 civil::date(2025, 1, 15)
                     .at(11, 0, 0, 0)
@@ -618,8 +883,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -661,8 +932,14 @@ mod tests {
             last_modified: None,
             sequence: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -692,6 +969,11 @@ mod tests {
             version: aimcal_ical::Version::default(),
             calscale: None,
             method: None,
+            names: Vec::new(),
+            descriptions: Vec::new(),
+            color: None,
+            refresh_interval: None,
+            source: None,
             components: vec![CalendarComponent::Todo(test_vtodo())],
             x_properties: Vec::new(),
             retained_properties: Vec::new(),
@@ -720,6 +1002,11 @@ mod tests {
             version: aimcal_ical::Version::default(),
             calscale: None,
             method: None,
+            names: Vec::new(),
+            descriptions: Vec::new(),
+            color: None,
+            refresh_interval: None,
+            source: None,
             components: vec![CalendarComponent::Event(test_vevent())],
             x_properties: Vec::new(),
             retained_properties: Vec::new(),
@@ -778,6 +1065,7 @@ mod tests {
         let metadata = CaldavMetadata {
             etag: "abc123".to_string(),
             last_modified: Some("Wed, 15 Jan 2025 10:00:00 GMT".to_string()),
+            schedule_tag: None,
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
@@ -841,6 +1129,54 @@ mod tests {
         assert_eq!(backend.calendar_href.as_str(), "/dav/calendars/user/");
     }
 
+    #[tokio::test]
+    async fn backend_caldav_refuses_writes_to_read_only_calendar() {
+        let mock_server = MockServer::start().await;
+
+        let config = CalDavConfig {
+            base_url: mock_server.uri(),
+            calendar_home: "/dav/calendars/user/".to_string(),
+            auth: aimcal_caldav::AuthMethod::None,
+            ..Default::default()
+        };
+
+        let db = Db::open(None)
+            .await
+            .expect("Failed to create test database");
+        let privileges = CalendarPrivileges {
+            read: true,
+            write_content: true,
+            bind: false,
+            unbind: false,
+        };
+        db.calendars
+            .set_sharing("default", Some("/principals/alice/"), privileges)
+            .await
+            .unwrap();
+
+        let backend = CaldavStore::new(
+            config,
+            "/dav/calendars/alice/shared/".to_string(),
+            db,
+            "default".to_string(),
+        )
+        .expect("Failed to create CaldavStore");
+
+        let err = backend
+            .create_event("event-1", &test_vevent())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "You don't have write access to calendar 'default' shared by /principals/alice/: \
+             creating items is not allowed"
+        );
+        let err = backend.delete_todo("todo-1").await.unwrap_err();
+        assert!(err.to_string().contains("deleting items is not allowed"));
+        // No request reaches the server
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn backend_caldav_etag_to_string() {
         let etag = ETag::new("\"abc123\"".to_string());
@@ -966,6 +1302,7 @@ mod tests {
         let metadata = CaldavMetadata {
             etag: "\"abc123\"".to_string(),
             last_modified: None,
+            schedule_tag: None,
         };
         let metadata_json = serde_json::to_string(&metadata).unwrap();
         db.resources
@@ -992,59 +1329,200 @@ mod tests {
         assert!(!not_exists);
     }
 
+    /// Starts the mock server on the `basic` scenario, with a backend for its work calendar.
+    async fn basic_scenario() -> (MockCalDav, CaldavStore, Db) {
+        let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+        let config = CalDavConfig {
+            base_url: server.uri(),
+            calendar_home: "/dav/calendars/alice/".to_string(),
+            auth: aimcal_caldav::AuthMethod::Basic {
+                username: "alice".to_string(),
+                password: "secret".to_string(),
+            },
+            ..Default::default()
+        };
+        let db = Db::open(None)
+            .await
+            .expect("Failed to create test database");
+        let backend = CaldavStore::new(
+            config,
+            "/dav/calendars/alice/work/".to_string(),
+            db.clone(),
+            "default".to_string(),
+        )
+        .expect("Failed to create CaldavStore");
+        (server, backend, db)
+    }
+
     #[tokio::test]
-    #[ignore = "Requires mock server with calendar-query support"]
     async fn backend_caldav_sync_cache_with_new_items() {
-        let mock_server = MockServer::start().await;
+        let (_server, backend, _db) = basic_scenario().await;
 
-        // Mock OPTIONS request
-        Mock::given(method("OPTIONS"))
-            .and(path("/dav/calendars/"))
-            .respond_with(ResponseTemplate::new(200).insert_header("DAV", "1, 2, calendar-access"))
-            .mount(&mock_server)
-            .await;
+        let result = backend.sync_cache().await.expect("Failed to sync cache");
 
-        // Mock REPORT request for calendar-query (events)
-        let event_ical = "\
-BEGIN:VCALENDAR\r\n\
-VERSION:2.0\r\n\
-PRODID:-//Test//Test//EN\r\n\
-BEGIN:VEVENT\r\n\
-UID:test-event-1\r\n\
-DTSTAMP:20250115T100000Z\r\n\
-DTSTART:20250115T100000Z\r\n\
-DTEND:20250115T110000Z\r\n\
-SUMMARY:Test Event 1\r\n\
-END:VEVENT\r\n\
-END:VCALENDAR\r\n";
+        assert_eq!(result.created, 3);
+        assert_eq!(result.updated, 0);
+        assert_eq!(result.deleted, 0);
+    }
 
-        Mock::given(method("REPORT"))
+    #[tokio::test]
+    async fn backend_caldav_sync_cache_with_updated_items() {
+        let (server, backend, db) = basic_scenario().await;
+
+        // Cache the event with an ETag the server has since replaced
+        let href = "/dav/calendars/alice/work/review.ics";
+        let metadata = CaldavMetadata {
+            etag: "\"old-etag\"".to_string(),
+            last_modified: None,
+            schedule_tag: None,
+        };
+        let metadata_json = serde_json::to_string(&metadata).unwrap();
+        db.resources
+            .insert("review", "default", href, Some(&metadata_json))
+            .await
+            .expect("Failed to insert resource");
+
+        let result = backend.sync_cache().await.expect("Failed to sync cache");
+
+        assert_eq!(result.created, 2);
+        assert_eq!(result.updated, 1);
+        assert_eq!(result.deleted, 0);
+        let resource = db
+            .resources
+            .get("review", "default")
+            .await
+            .unwrap()
+            .unwrap();
+        let metadata: CaldavMetadata =
+            serde_json::from_str(resource.metadata.as_deref().unwrap()).unwrap();
+        assert_eq!(metadata.etag, server.resource(href).unwrap().etag);
+    }
+
+    /// Mocks PROPFIND on a calendar at `/dav/calendars/default/` holding `members`.
+    async fn mount_calendar(mock_server: &MockServer, members: &str) {
+        Mock::given(method("PROPFIND"))
             .and(path("/dav/calendars/default/"))
-            .and(header("Content-Type", "application/xml; charset=utf-8"))
-            .respond_with(
-                ResponseTemplate::new(207)
-                    .set_body_raw(
-                        format!(
-                            r#"<?xml version="1.0" encoding="utf-8" ?>
+            .and(header("Depth", "0"))
+            .respond_with(ResponseTemplate::new(207).set_body_raw(
+                r#"<?xml version="1.0" encoding="utf-8" ?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/dav/calendars/default/</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>"#,
+                "application/xml",
+            ))
+            .mount(mock_server)
+            .await;
+
+        Mock::given(method("PROPFIND"))
+            .and(path("/dav/calendars/default/"))
+            .and(header("Depth", "1"))
+            .respond_with(ResponseTemplate::new(207).set_body_raw(
+                format!(
+                    r#"<?xml version="1.0" encoding="utf-8" ?>
 <D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
   <D:response>
-    <D:href>/dav/calendars/default/test-event-1.ics</D:href>
+    <D:href>/dav/calendars/default/</D:href>
     <D:propstat>
       <D:prop>
-        <D:getetag>"new-etag-1"</D:getetag>
-        <C:calendar-data>{event_ical}</C:calendar-data>
+        <D:resourcetype><D:collection/><C:calendar/></D:resourcetype>
+        <D:getetag>"collection"</D:getetag>
       </D:prop>
       <D:status>HTTP/1.1 200 OK</D:status>
     </D:propstat>
   </D:response>
+  {members}
 </D:multistatus>"#
-                        ),
-                        "application/xml",
-                    )
-                    .insert_header("ETag", "\"new-etag-1\""),
+                ),
+                "application/xml",
+            ))
+            .mount(mock_server)
+            .await;
+    }
+
+    /// A `<D:response>` listing a calendar object with its `ETag`.
+    fn member(name: &str, etag: &str) -> String {
+        format!(
+            r#"<D:response>
+    <D:href>/dav/calendars/default/{name}</D:href>
+    <D:propstat>
+      <D:prop>
+        <D:resourcetype/>
+        <D:getetag>"{etag}"</D:getetag>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>"#
+        )
+    }
+
+    async fn mount_ics(mock_server: &MockServer, name: &str, etag: &str, component: &str) {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\n{component}END:VCALENDAR\r\n"
+        );
+        Mock::given(method("GET"))
+            .and(path(format!("/dav/calendars/default/{name}")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", format!("\"{etag}\""))
+                    .set_body_raw(ics, "text/calendar"),
+            )
+            .mount(mock_server)
+            .await;
+    }
+
+    const TEST_VEVENT: &str = "BEGIN:VEVENT\r\nUID:event-1\r\nDTSTAMP:20250115T100000Z\r\n\
+DTSTART:20250115T100000Z\r\nSUMMARY:Event\r\nEND:VEVENT\r\n";
+
+    const TEST_VTODO: &str = "BEGIN:VTODO\r\nUID:todo-1\r\nDTSTAMP:20250115T100000Z\r\n\
+SUMMARY:Todo\r\nEND:VTODO\r\n";
+
+    async fn insert_resource(db: &Db, uid: &str, name: &str, etag: &str) {
+        let metadata = CaldavMetadata {
+            etag: format!("\"{etag}\""),
+            last_modified: None,
+            schedule_tag: None,
+        };
+        db.resources
+            .insert(
+                uid,
+                "default",
+                &format!("/dav/calendars/default/{name}"),
+                Some(&serde_json::to_string(&metadata).unwrap()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn backend_caldav_sync_cache_compares_etags_without_report() {
+        // Not pooled, as capabilities are cached per server address
+        let mock_server = MockServer::builder().start().await;
+        let members = [
+            member("event-1.ics", "e1"),
+            member("todo-1.ics", "t2"),
+            member("todo-2.ics", "t3"),
+        ];
+        // Advertises calendar-access, but leaves REPORT out of the Allow header
+        Mock::given(method("OPTIONS"))
+            .and(path("/dav/calendars/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("DAV", "1, 2, calendar-access")
+                    .insert_header("Allow", "OPTIONS, GET, PUT, DELETE, PROPFIND"),
             )
             .mount(&mock_server)
             .await;
+        mount_calendar(&mock_server, &members.join("\n")).await;
+        mount_ics(&mock_server, "event-1.ics", "e1", TEST_VEVENT).await;
+        mount_ics(&mock_server, "todo-1.ics", "t2", TEST_VTODO).await;
 
         let config = CalDavConfig {
             base_url: mock_server.uri(),
@@ -1052,77 +1530,118 @@ END:VCALENDAR\r\n";
             auth: aimcal_caldav::AuthMethod::None,
             ..Default::default()
         };
-
-        let db = Db::open(None)
-            .await
-            .expect("Failed to create test database");
+        let db = Db::open(None).await.unwrap();
+        // todo-1 changed on the server, todo-2 did not and gone-1 was deleted
+        insert_resource(&db, "todo-1", "todo-1.ics", "t1").await;
+        insert_resource(&db, "todo-2", "todo-2.ics", "t3").await;
+        insert_resource(&db, "gone-1", "gone-1.ics", "g1").await;
 
         let backend = CaldavStore::new(
             config,
             "/dav/calendars/default/".to_string(),
-            db,
+            db.clone(),
             "default".to_string(),
         )
-        .expect("Failed to create CaldavStore");
+        .unwrap();
 
-        let result = backend.sync_cache().await.expect("Failed to sync cache");
+        let result = backend.sync_cache().await.unwrap();
+        assert_eq!(
+            result,
+            SyncResult {
+                created: 1,
+                updated: 1,
+                deleted: 1,
+            }
+        );
 
-        assert_eq!(result.created, 1);
-        assert_eq!(result.updated, 0);
-        assert_eq!(result.deleted, 0);
+        let (href, metadata) = backend.get_resource("todo-1").await.unwrap().unwrap();
+        assert_eq!(href, "/dav/calendars/default/todo-1.ics");
+        assert_eq!(metadata.etag, "\"t2\"");
+        assert!(backend.get_resource("event-1").await.unwrap().is_some());
+        assert!(backend.get_resource("gone-1").await.unwrap().is_none());
+
+        // Only the changed resources are fetched, and REPORT is never tried
+        let requests = mock_server.received_requests().await.unwrap();
+        let fetched: Vec<_> = requests
+            .iter()
+            .filter(|r| r.method.as_str() == "GET")
+            .map(|r| r.url.path())
+            .collect();
+        assert_eq!(
+            fetched,
+            [
+                "/dav/calendars/default/event-1.ics",
+                "/dav/calendars/default/todo-1.ics"
+            ]
+        );
+        assert!(requests.iter().all(|r| r.method.as_str() != "REPORT"));
     }
 
     #[tokio::test]
-    #[ignore = "Requires mock server with calendar-query support"]
-    async fn backend_caldav_sync_cache_with_updated_items() {
-        let mock_server = MockServer::start().await;
-
-        // Mock OPTIONS request
-        Mock::given(method("OPTIONS"))
-            .and(path("/dav/calendars/"))
-            .respond_with(ResponseTemplate::new(200).insert_header("DAV", "1, 2, calendar-access"))
+    async fn backend_caldav_updates_invitation_with_schedule_tag() {
+        let mock_server = MockServer::builder().start().await;
+        let invitation = "BEGIN:VEVENT\r\nUID:event-1\r\nDTSTAMP:20250115T100000Z\r\n\
+DTSTART:20250115T100000Z\r\nSUMMARY:Event\r\nORGANIZER:mailto:alice@example.com\r\n\
+END:VEVENT\r\n";
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\n{invitation}END:VCALENDAR\r\n"
+        );
+        Mock::given(method("GET"))
+            .and(path("/dav/calendars/default/event-1.ics"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"e2\"")
+                    .insert_header("Schedule-Tag", "\"s1\"")
+                    .set_body_raw(ics, "text/calendar"),
+            )
+            .mount(&mock_server)
+            .await;
+        // Merged with the replies of other attendees, so no ETag comes back
+        Mock::given(method("PUT"))
+            .and(path("/dav/calendars/default/event-1.ics"))
+            .and(header("If-Schedule-Tag-Match", "\"s1\""))
+            .respond_with(ResponseTemplate::new(204).insert_header("Schedule-Tag", "\"s1\""))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
-        // Mock REPORT request for calendar-query with updated ETag
-        let event_ical = "\
-BEGIN:VCALENDAR\r\n\
-VERSION:2.0\r\n\
-PRODID:-//Test//Test//EN\r\n\
-BEGIN:VEVENT\r\n\
-UID:test-event-2\r\n\
-DTSTAMP:20250115T100000Z\r\n\
-DTSTART:20250115T100000Z\r\n\
-DTEND:20250115T110000Z\r\n\
-SUMMARY:Updated Event\r\n\
-END:VEVENT\r\n\
-END:VCALENDAR\r\n";
+        let config = CalDavConfig {
+            base_url: mock_server.uri(),
+            calendar_home: "/dav/calendars/".to_string(),
+            auth: aimcal_caldav::AuthMethod::None,
+            ..Default::default()
+        };
+        let db = Db::open(None).await.unwrap();
+        insert_resource(&db, "event-1", "event-1.ics", "e1").await;
+        let backend = CaldavStore::new(
+            config,
+            "/dav/calendars/default/".to_string(),
+            db,
+            "default".to_string(),
+        )
+        .unwrap();
 
-        Mock::given(method("REPORT"))
-            .and(path("/dav/calendars/default/"))
-            .and(header("Content-Type", "application/xml; charset=utf-8"))
-            .respond_with(
-                ResponseTemplate::new(207)
-                    .set_body_raw(
-                        format!(
-                            r#"<?xml version="1.0" encoding="utf-8" ?>
-<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
-  <D:response>
-    <D:href>/dav/calendars/default/test-event-2.ics</D:href>
-    <D:propstat>
-      <D:prop>
-        <D:getetag>"updated-etag"</D:getetag>
-        <C:calendar-data>{event_ical}</C:calendar-data>
-      </D:prop>
-      <D:status>HTTP/1.1 200 OK</D:status>
-    </D:propstat>
-  </D:response>
-</D:multistatus>"#
-                        ),
-                        "application/xml",
-                    )
-                    .insert_header("ETag", "\"updated-etag\""),
-            )
+        let patch = EventPatch {
+            summary: Some("Renamed".to_string()),
+            ..Default::default()
+        };
+        backend.update_event("event-1", &patch).await.unwrap();
+
+        let (_, metadata) = backend.get_resource("event-1").await.unwrap().unwrap();
+        assert_eq!(metadata.etag, "\"e2\"");
+        assert_eq!(metadata.schedule_tag.as_deref(), Some("\"s1\""));
+    }
+
+    #[tokio::test]
+    async fn backend_caldav_update_sends_etag_quoted_and_refuses_without_one() {
+        let mock_server = MockServer::builder().start().await;
+        mount_ics(&mock_server, "todo-1.ics", "C=2@U=1", TEST_VTODO).await;
+        // iCloud leaves the quotes out of getetag, but expects them in If-Match
+        Mock::given(method("PUT"))
+            .and(path("/dav/calendars/default/todo-1.ics"))
+            .and(header("If-Match", "\"C=2@U=1\""))
+            .respond_with(ResponseTemplate::new(204).insert_header("ETag", "\"C=3@U=1\""))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
@@ -1132,46 +1651,102 @@ END:VCALENDAR\r\n";
             auth: aimcal_caldav::AuthMethod::None,
             ..Default::default()
         };
-
-        let db = Db::open(None)
+        let db = Db::open(None).await.unwrap();
+        let cache = |etag: &str| CaldavMetadata {
+            etag: etag.to_string(),
+            last_modified: None,
+            schedule_tag: None,
+        };
+        let href = "/dav/calendars/default/todo-1.ics";
+        let metadata = serde_json::to_string(&cache("C=2@U=1")).unwrap();
+        db.resources
+            .insert("todo-1", "default", href, Some(&metadata))
             .await
-            .expect("Failed to create test database");
-
+            .unwrap();
         let backend = CaldavStore::new(
             config,
             "/dav/calendars/default/".to_string(),
-            db.clone(),
+            db,
             "default".to_string(),
         )
-        .expect("Failed to create CaldavStore");
+        .unwrap();
 
-        // Insert existing resource with old ETag
-        let metadata = CaldavMetadata {
-            etag: "\"old-etag\"".to_string(),
-            last_modified: None,
+        let patch = TodoPatch {
+            summary: Some("Renamed".to_string()),
+            ..Default::default()
         };
-        let metadata_json = serde_json::to_string(&metadata).unwrap();
-        db.resources
-            .insert(
-                "test-event-2",
-                "default",
-                "/dav/calendars/default/test-event-2.ics",
-                Some(&metadata_json),
-            )
+        backend.update_todo("todo-1", &patch).await.unwrap();
+        let (_, metadata) = backend.get_resource("todo-1").await.unwrap().unwrap();
+        assert_eq!(metadata.etag, "\"C=3@U=1\"");
+
+        // Neither cached nor sent by the server, so there is nothing to condition on
+        let resource = CalendarResource::new(
+            Href::new(href.to_string()),
+            ETag::new(String::new()),
+            ICalendar::new(),
+        );
+        let err = backend
+            .put_resource(&resource, &cache(""), &ICalendar::new(), false, false)
             .await
-            .expect("Failed to insert resource");
+            .unwrap_err();
+        assert!(err.to_string().contains("refusing"), "{err}");
+    }
 
-        let result = backend.sync_cache().await.expect("Failed to sync cache");
+    #[tokio::test]
+    async fn backend_caldav_lists_todos_when_report_is_rejected() {
+        let mock_server = MockServer::builder().start().await;
 
-        assert_eq!(result.created, 0);
-        assert_eq!(result.updated, 1);
-        assert_eq!(result.deleted, 0);
+        // Advertises calendar-access without an Allow header, but rejects REPORT
+        Mock::given(method("OPTIONS"))
+            .and(path("/dav/calendars/"))
+            .respond_with(ResponseTemplate::new(200).insert_header("DAV", "1, 2, calendar-access"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("REPORT"))
+            .respond_with(ResponseTemplate::new(501))
+            .mount(&mock_server)
+            .await;
+        let members = [member("event-1.ics", "e1"), member("todo-1.ics", "t1")];
+        mount_calendar(&mock_server, &members.join("\n")).await;
+        mount_ics(&mock_server, "event-1.ics", "e1", TEST_VEVENT).await;
+        mount_ics(&mock_server, "todo-1.ics", "t1", TEST_VTODO).await;
+
+        let config = CalDavConfig {
+            base_url: mock_server.uri(),
+            calendar_home: "/dav/calendars/".to_string(),
+            auth: aimcal_caldav::AuthMethod::None,
+            ..Default::default()
+        };
+        let db = Db::open(None).await.unwrap();
+        let new_backend = || {
+            CaldavStore::new(
+                config.clone(),
+                "/dav/calendars/default/".to_string(),
+                db.clone(),
+                "default".to_string(),
+            )
+            .unwrap()
+        };
+
+        let todos = new_backend().list_todos().await.unwrap();
+        let [(href, todo)] = todos.as_slice() else {
+            panic!("Expected a single todo, got {todos:?}");
+        };
+        assert_eq!(href, "/dav/calendars/default/todo-1.ics");
+        assert_eq!(todo.uid.content.to_string(), "todo-1");
+
+        // Another store of the same server reuses the detected capabilities
+        let events = new_backend().list_events().await.unwrap();
+        assert_eq!(events.len(), 1);
+        let requests = mock_server.received_requests().await.unwrap();
+        let count = |m: &str| requests.iter().filter(|r| r.method.as_str() == m).count();
+        assert_eq!(count("OPTIONS"), 1);
+        assert_eq!(count("REPORT"), 1);
     }
 
     #[test]
     fn backend_caldav_error_from_caldav_http() {
-        let error: Box<dyn Error> =
-            aimcal_caldav::CalDavError::Http("HTTP error occurred".to_string()).into();
+        let error: Box<dyn Error> = CalDavError::Http("HTTP error occurred".to_string()).into();
 
         let error_msg = error.to_string();
         assert!(error_msg.contains("HTTP error"));
@@ -1179,8 +1754,7 @@ END:VCALENDAR\r\n";
 
     #[test]
     fn backend_caldav_error_from_caldav_auth() {
-        let error: Box<dyn Error> =
-            aimcal_caldav::CalDavError::Auth("Authentication failed".to_string()).into();
+        let error: Box<dyn Error> = CalDavError::Auth("Authentication failed".to_string()).into();
 
         let error_msg = error.to_string();
         assert!(error_msg.contains("Authentication failed"));
@@ -1189,7 +1763,7 @@ END:VCALENDAR\r\n";
     #[test]
     fn backend_caldav_error_from_caldav_not_found() {
         let error: Box<dyn Error> =
-            aimcal_caldav::CalDavError::NotFound(Href::new("/test/event.ics".to_string())).into();
+            CalDavError::NotFound(Href::new("/test/event.ics".to_string())).into();
 
         let error_msg = error.to_string();
         assert!(error_msg.contains("Resource not found"));
@@ -1199,7 +1773,7 @@ END:VCALENDAR\r\n";
     #[test]
     fn backend_caldav_error_from_caldav_precondition_failed() {
         let error: Box<dyn Error> =
-            aimcal_caldav::CalDavError::PreconditionFailed("ETag mismatch".to_string()).into();
+            CalDavError::PreconditionFailed("ETag mismatch".to_string()).into();
 
         let error_msg = error.to_string();
         assert!(error_msg.contains("Precondition failed"));
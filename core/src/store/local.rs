@@ -2,47 +2,40 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use aimcal_ical::{
     self as ical, CalendarComponent, Completed, Description, DtEnd, DtStamp, DtStart, Due,
-    ICalendar, PercentComplete, Summary, Uid,
+    ICalendar, PercentComplete, Segments, Summary, Uid,
 };
 use async_trait::async_trait;
 use jiff::Zoned;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tokio::fs;
+use tokio::sync::mpsc;
 
+use crate::backup::sha256_hex;
 use crate::db::Db;
-use crate::store::{StoreError, SyncResult};
-use crate::{Event, EventPatch, LooseDateTime, Todo, TodoPatch};
-
-/// Convert `Box<dyn Error>` (non-Send+Sync) to `StoreError` by wrapping in a String.
-///
-/// Use this for errors from parsing or database operations that return `Box<dyn Error>`.
-#[allow(clippy::borrowed_box, clippy::needless_pass_by_value)]
-fn into_store_error(e: Box<dyn Error>) -> StoreError {
-    format!("{e}").into()
-}
+use crate::db::events::EventRecord;
+use crate::db::todos::TodoRecord;
+use crate::error::AimError;
+use crate::file;
+use crate::import::{parse_foreign, parse_foreign_partial};
+use crate::series::{is_override, same_recurrence_id};
+use crate::store::{BrokenItem, PulledChange, StoreError, StoreResource, SyncResult};
+use crate::{Event, EventPatch, Todo, TodoPatch};
 
 /// Reconstructs a [`aimcal_ical::VEvent`] from an Event trait object for database-only updates.
 fn reconstruct_event_from_db<E: Event>(event: &E, now: &Zoned) -> aimcal_ical::VEvent<String> {
     let utc_now = now.with_time_zone(jiff::tz::TimeZone::UTC);
     let dt_stamp = DtStamp::new(utc_now.datetime());
 
-    let dt_start = event.start().map_or_else(
-        || {
-            let default_start: LooseDateTime = now.clone().into();
-            DtStart::new(default_start)
-        },
-        DtStart::new,
-    );
-
     aimcal_ical::VEvent {
         uid: Uid::new(event.uid().into_owned()),
         dt_stamp,
-        dt_start,
+        dt_start: event.start().map(DtStart::new),
         dt_end: event.end().map(DtEnd::new),
         duration: None,
         summary: Some(Summary::new(event.summary().into_owned())),
@@ -60,8 +53,14 @@ fn reconstruct_event_from_db<E: Event>(event: &E, now: &Zoned) -> aimcal_ical::V
         sequence: None,
         priority: None,
         classification: None,
-        resources: None,
-        categories: None,
+        resources: Vec::new(),
+        categories: Vec::new(),
+        comments: Vec::new(),
+        attachments: Vec::new(),
+        contacts: Vec::new(),
+        color: None,
+        images: Vec::new(),
+        conferences: Vec::new(),
         rrule: None,
         rdates: Vec::new(),
         ex_dates: Vec::new(),
@@ -98,8 +97,14 @@ fn reconstruct_todo_from_db<T: Todo>(todo: &T, now: &Zoned) -> aimcal_ical::VTod
         last_modified: None,
         sequence: None,
         classification: None,
-        resources: None,
-        categories: None,
+        resources: Vec::new(),
+        categories: Vec::new(),
+        comments: Vec::new(),
+        attachments: Vec::new(),
+        contacts: Vec::new(),
+        color: None,
+        images: Vec::new(),
+        conferences: Vec::new(),
         rrule: None,
         rdates: Vec::new(),
         ex_dates: Vec::new(),
@@ -109,6 +114,36 @@ fn reconstruct_todo_from_db<T: Todo>(todo: &T, now: &Zoned) -> aimcal_ical::VTod
     }
 }
 
+/// How often a todo is re-read and patched again when its file keeps changing while writing.
+const MAX_WRITE_ATTEMPTS: usize = 3;
+
+/// Number of components written to the database in one transaction when syncing the directory.
+const INGEST_BATCH_SIZE: usize = 500;
+
+/// Number of parsed files waiting for the writer before the parsers wait in turn.
+const INGEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Number of files between progress reports when syncing the directory.
+const INGEST_PROGRESS_INTERVAL: usize = 1000;
+
+/// Metadata of a local resource, stored as JSON in the resources table.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct LocalMetadata {
+    /// Hex-encoded SHA-256 of the file content as aim last read or wrote it.
+    sha256: String,
+}
+
+/// Whether the properties set by `patch` have the same values in both todos.
+fn patched_properties_unchanged(patch: &TodoPatch, a: &impl Todo, b: &impl Todo) -> bool {
+    let due_set = patch.due.is_some() || patch.snoozed_from.is_some();
+    (patch.description.is_none() || a.description() == b.description())
+        && (!due_set || a.due() == b.due())
+        && (patch.percent_complete.is_none() || a.percent_complete() == b.percent_complete())
+        && (patch.priority.is_none() || a.priority() == b.priority())
+        && (patch.status.is_none() || a.status() == b.status())
+        && (patch.summary.is_none() || a.summary() == b.summary())
+}
+
 /// Local file-based store for storing events and todos as ICS files.
 ///
 /// This store stores each event/todo as a separate ICS file in the configured
@@ -121,6 +156,10 @@ pub struct LocalStore {
     db: Option<Db>,
     /// The calendar identifier.
     calendar_id: String,
+    /// Items the last sync of the directory left out, see [`crate::Store::broken_items`]
+    broken: Arc<Mutex<Vec<BrokenItem>>>,
+    /// Directory of the files written before this one was configured, see [`LocalStore::adopting`]
+    adopted_path: Option<PathBuf>,
 }
 
 impl LocalStore {
@@ -131,6 +170,8 @@ impl LocalStore {
             calendar_path,
             db: None,
             calendar_id,
+            broken: Arc::default(),
+            adopted_path: None,
         }
     }
 
@@ -141,9 +182,20 @@ impl LocalStore {
             calendar_path,
             db: Some(db),
             calendar_id,
+            broken: Arc::default(),
+            adopted_path: None,
         }
     }
 
+    /// Keeps the cached items whose files are in `path`, such as the directory used before a
+    /// calendar path was configured, rather than removing them as missing from this directory.
+    /// Their files are written to this directory once they are updated.
+    #[must_use]
+    pub fn adopting(mut self, path: PathBuf) -> Self {
+        self.adopted_path = (path != self.calendar_path).then_some(path);
+        self
+    }
+
     /// Gets the file path for a given UID.
     fn file_path(&self, uid: &str) -> PathBuf {
         self.calendar_path.join(format!("{uid}.ics"))
@@ -151,7 +203,101 @@ impl LocalStore {
 
     /// Gets the resource ID (file:// URL) for a given UID.
     fn resource_id(&self, uid: &str) -> String {
-        format!("file://{}", self.file_path(uid).display())
+        file_url(&self.file_path(uid))
+    }
+
+    /// Hash of the file of `uid` as aim last read or wrote it, if recorded.
+    async fn seen_hash(&self, uid: &str) -> Option<String> {
+        let db = self.db.as_ref()?;
+        let record = db.resources.get(uid, &self.calendar_id).await.ok()??;
+        record
+            .metadata_json::<LocalMetadata>()
+            .map(|metadata| metadata.sha256)
+    }
+
+    /// Records the resource of `uid` along with the hash of its file.
+    async fn insert_resource(&self, db: &Db, uid: &str, sha256: String) -> Result<(), StoreError> {
+        let metadata = serde_json::to_string(&LocalMetadata { sha256 })?;
+        db.resources
+            .insert(
+                uid,
+                &self.calendar_id,
+                &self.resource_id(uid),
+                Some(&metadata),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Patches the todo file, guarding against changes made by other programs.
+    ///
+    /// If the file changed since aim last read it, or changes again before it is written, the
+    /// patch is applied to the fresh todo as long as the properties it sets are unchanged there.
+    /// Otherwise the update fails with [`AimError::Conflict`] and the file is left alone.
+    async fn update_todo_file(
+        &self,
+        uid: &str,
+        patch: &TodoPatch,
+        mut base: aimcal_ical::VTodo<String>,
+        mut base_hash: String,
+    ) -> Result<aimcal_ical::VTodo<String>, StoreError> {
+        let now = Zoned::now();
+        let file_path = self.file_path(uid);
+        let conflict = || -> StoreError {
+            Box::new(AimError::Conflict {
+                uid: uid.to_string(),
+            })
+        };
+
+        // The patch was made against the cached todo, which is outdated if the file changed
+        if let Some(db) = &self.db
+            && self
+                .seen_hash(uid)
+                .await
+                .is_some_and(|seen| seen != base_hash)
+            && let Some(cached) = db.todos.get(uid).await?
+            && !patched_properties_unchanged(patch, &cached, &base)
+        {
+            return Err(conflict());
+        }
+
+        for _ in 0..MAX_WRITE_ATTEMPTS {
+            let mut todo = base.clone();
+            patch.resolve(&now).apply_to(&mut todo);
+            let calendar = ICalendar {
+                components: vec![CalendarComponent::Todo(todo.clone())],
+                ..Default::default()
+            };
+            if let Some(hash) = write_ics_if_unchanged(&file_path, &base_hash, &calendar).await? {
+                if let Some(db) = &self.db {
+                    self.insert_resource(db, uid, hash).await?;
+                }
+                return Ok(todo);
+            }
+
+            tracing::info!(uid, path = %file_path.display(), "todo file changed while updating, retrying");
+            let (fresh, fresh_hash) = read_todo(&file_path).await?;
+            if !patched_properties_unchanged(patch, &base, &fresh) {
+                return Err(conflict());
+            }
+            (base, base_hash) = (fresh, fresh_hash);
+        }
+        Err(conflict())
+    }
+
+    /// Whether the cached item `uid` has its file in the adopted directory, see
+    /// [`LocalStore::adopting`].
+    async fn is_adopted(&self, db: &Db, uid: &str) -> bool {
+        let Some(adopted_path) = &self.adopted_path else {
+            return false;
+        };
+        let resource_id = file_url(&adopted_path.join(format!("{uid}.ics")));
+        db.resources
+            .get(uid, &self.calendar_id)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|record| record.resource_id == resource_id)
     }
 
     /// Removes stale database entries whose files no longer exist on disk.
@@ -168,7 +314,7 @@ impl LocalStore {
 
         let mut deleted = 0;
         for uid in &db_uids {
-            if !disk_uids.contains(uid) {
+            if !disk_uids.contains(uid) && !self.is_adopted(db, uid).await {
                 if let Err(e) = db.events.delete(uid).await {
                     tracing::warn!(uid = %uid, err = %e, "failed to delete stale event");
                 }
@@ -187,23 +333,198 @@ impl LocalStore {
 
     /// Scans the calendar directory for .ics files and syncs with the database.
     ///
-    /// This is the implementation of `sync_cache` for the local store.
+    /// This is the implementation of `sync_cache` for the local store. Files are parsed in
+    /// parallel, while a single writer upserts their components in batched transactions. A file
+    /// or component that fails is logged and skipped, and a component with errors is recorded in
+    /// the broken items as well.
     async fn sync_from_directory(&self, db: &Db) -> Result<SyncResult, StoreError> {
-        let mut created = 0;
+        let Some(paths) = list_ics_files(&self.calendar_path).await? else {
+            // Directory doesn't exist yet, nothing to sync
+            self.set_broken(Vec::new());
+            return Ok(SyncResult {
+                created: 0,
+                updated: 0,
+                deleted: 0,
+            });
+        };
+
+        let total = paths.len();
+        let (tx, mut rx) = mpsc::channel(INGEST_CHANNEL_CAPACITY);
+        let calendar_id = self.calendar_id.clone();
+        // The files are parsed on other threads, so their spans are put under the sync by hand
+        let parent = tracing::Span::current();
+        let parser = tokio::task::spawn_blocking(move || {
+            // Stops early once the writer is gone, as the results are of no use then
+            paths
+                .into_par_iter()
+                .try_for_each_with(tx, |tx, path| {
+                    let span =
+                        tracing::debug_span!(parent: &parent, "ingest_file", path = %path.display());
+                    let parsed = span.in_scope(|| read_ingest_blocking(&path, &calendar_id));
+                    tx.blocking_send((path, parsed)).map_err(|_| ())
+                })
+                .ok();
+        });
 
-        // Track UIDs found on disk
+        let mut created = 0;
+        let mut done = 0;
         let mut disk_uids: HashSet<String> = HashSet::new();
+        let mut batch: Vec<IngestItem> = Vec::with_capacity(INGEST_BATCH_SIZE);
+        let mut broken = Vec::new();
+        while let Some((path, parsed)) = rx.recv().await {
+            done += 1;
+            match parsed {
+                Ok((items, skipped)) => {
+                    batch.extend(items);
+                    broken.extend(skipped);
+                }
+                Err(e) => {
+                    tracing::error!(path = %path.display(), err = %e, "failed to parse ICS file");
+                }
+            }
+
+            if batch.len() >= INGEST_BATCH_SIZE {
+                created += self.write_batch(db, &mut batch, &mut disk_uids).await?;
+            }
+            if done % INGEST_PROGRESS_INTERVAL == 0 {
+                tracing::info!(calendar_id = %self.calendar_id, done, total, "ingesting calendar files");
+            }
+        }
+        created += self.write_batch(db, &mut batch, &mut disk_uids).await?;
+        parser
+            .await
+            .map_err(|e| format!("Failed to parse calendar files: {e}"))?;
+
+        // Remove stale DB entries whose files no longer exist on disk.
+        // TODO: should we use db or store as golden source here? If we use db as golden source, we
+        // might end up deleting files that were just created on disk but haven't been synced yet.
+        // If we use disk as golden source, we might end up keeping stale entries in the db if files
+        // were deleted outside of Aim. For now, we'll use disk as golden source since it's more
+        // likely to reflect the user's intent.
+        let deleted = self.remove_stale_entries(db, &disk_uids).await?;
+        self.set_broken(broken);
+
+        Ok(SyncResult {
+            created,
+            updated: 0,
+            deleted,
+        })
+    }
+
+    /// Plans what [`Self::sync_from_directory`] pulls into the cache: the items of the files
+    /// that changed since they were cached, and the removal of those whose file is gone.
+    async fn plan_from_directory(&self, db: &Db) -> Result<Vec<PulledChange>, StoreError> {
+        let hashes: HashMap<String, Option<String>> = db
+            .resources
+            .list_by_calendar(&self.calendar_id)
+            .await?
+            .into_iter()
+            .map(|record| {
+                let hash = record.metadata_json::<LocalMetadata>().map(|m| m.sha256);
+                (record.uid, hash)
+            })
+            .collect();
+
+        let mut changes = Vec::new();
+        let mut disk_uids = HashSet::new();
+        let paths = list_ics_files(&self.calendar_path).await?;
+        for path in paths.unwrap_or_default() {
+            // Files that cannot be parsed are skipped by the sync as well
+            let Ok((calendar, hash)) = read_ics(&path).await else {
+                continue;
+            };
+            for component in &calendar.components {
+                let uid = match component {
+                    CalendarComponent::Event(event) if is_override(event) => continue,
+                    CalendarComponent::Event(event) => event.uid.content.to_string(),
+                    CalendarComponent::Todo(todo) => todo.uid.content.to_string(),
+                    _ => continue,
+                };
+                let cached = hashes.get(&uid);
+                if cached.is_none_or(|cached| cached.as_ref() != Some(&hash))
+                    && let Some(change) =
+                        PulledChange::of(db, &self.calendar_id, component, cached.is_some()).await?
+                {
+                    changes.push(change);
+                }
+                disk_uids.insert(uid);
+            }
+        }
+
+        for uid in hashes.keys().filter(|uid| !disk_uids.contains(*uid)) {
+            if !self.is_adopted(db, uid).await
+                && let Some(change) = PulledChange::removal(db, uid).await?
+            {
+                changes.push(change);
+            }
+        }
+        Ok(changes)
+    }
+
+    fn set_broken(&self, items: Vec<BrokenItem>) {
+        *self.broken.lock().unwrap_or_else(PoisonError::into_inner) = items;
+    }
+
+    /// Upserts the parsed components in one transaction, draining `batch`.
+    ///
+    /// Returns the number of components written, whose UIDs are added to `disk_uids`.
+    async fn write_batch(
+        &self,
+        db: &Db,
+        batch: &mut Vec<IngestItem>,
+        disk_uids: &mut HashSet<String>,
+    ) -> Result<usize, StoreError> {
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let mut written = 0;
+        let mut tx = db.begin().await?;
+        for item in batch.drain(..) {
+            let IngestItem {
+                path,
+                uid,
+                record,
+                hash,
+            } = item;
+            let upserted = match record {
+                IngestRecord::Event(record) => tx.upsert_event_record(record).await,
+                IngestRecord::Todo(record) => tx.upsert_todo_record(record).await,
+            };
+            if let Err(e) = upserted {
+                tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to upsert component");
+                continue;
+            }
+
+            let metadata = serde_json::to_string(&LocalMetadata { sha256: hash })?;
+            let resource_id = self.resource_id(&uid);
+            if let Err(e) = tx
+                .insert_resource(&uid, &self.calendar_id, &resource_id, Some(&metadata))
+                .await
+            {
+                tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to insert resource");
+                continue;
+            }
+            disk_uids.insert(uid);
+            written += 1;
+        }
+        tx.commit().await?;
+        Ok(written)
+    }
+
+    /// Reads the events of the calendar files, either the overrides of occurrences or the rest,
+    /// along with the IDs of their resources.
+    async fn read_events(
+        &self,
+        overrides: bool,
+    ) -> Result<Vec<(String, aimcal_ical::VEvent<String>)>, StoreError> {
+        let mut events = Vec::new();
 
-        // Read directory and process each .ics file
         let mut entries = match fs::read_dir(&self.calendar_path).await {
             Ok(e) => e,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                // Directory doesn't exist yet, nothing to sync
-                return Ok(SyncResult {
-                    created: 0,
-                    updated: 0,
-                    deleted: 0,
-                });
+                // Directory doesn't exist yet
+                return Ok(events);
             }
             Err(e) => {
                 return Err(format!("Failed to read calendar directory: {e}").into());
@@ -214,84 +535,61 @@ impl LocalStore {
             let path = entry.path();
             match path.extension() {
                 Some(ext) if ext == "ics" => {
-                    // Parse the ICS file - log errors and continue
-                    let calendar = match parse_ics(&path).await {
-                        Ok(c) => c,
-                        Err(e) => {
-                            tracing::error!(path = %path.display(), err = %e, "failed to parse ICS file");
-                            continue;
-                        }
-                    };
-
-                    // Process each component in the calendar
-                    for component in calendar.components {
-                        match component {
-                            CalendarComponent::Event(event) => {
-                                let uid = event.uid.content.to_string();
-
-                                if let Err(e) =
-                                    db.upsert_event(&uid, &event, &self.calendar_id).await
-                                {
-                                    tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to upsert event");
-                                    continue;
-                                }
-                                if let Err(e) = db
-                                    .resources
-                                    .insert(&uid, &self.calendar_id, &self.resource_id(&uid), None)
-                                    .await
-                                {
-                                    tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to insert resource");
-                                    continue;
-                                }
-                                disk_uids.insert(uid);
-                                created += 1;
-                            }
-                            CalendarComponent::Todo(todo) => {
-                                let uid = todo.uid.content.to_string();
-
-                                if let Err(e) = db.upsert_todo(&uid, &todo, &self.calendar_id).await
-                                {
-                                    tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to upsert todo");
-                                    continue;
-                                }
-                                if let Err(e) = db
-                                    .resources
-                                    .insert(&uid, &self.calendar_id, &self.resource_id(&uid), None)
-                                    .await
+                    match parse_ics(&path).await.map_err(StoreError::from) {
+                        Ok(calendar) => {
+                            for component in calendar.components {
+                                if let CalendarComponent::Event(event) = component
+                                    && is_override(&event) == overrides
                                 {
-                                    tracing::error!(path = %path.display(), uid = %uid, err = %e, "failed to insert resource");
-                                    continue;
+                                    events.push((file_url(&path), event));
                                 }
-                                disk_uids.insert(uid);
-                                created += 1;
-                            }
-                            _ => {
-                                tracing::warn!(
-                                    path = %path.display(),
-                                    "Unsupported component type in ICS file"
-                                );
                             }
                         }
+                        Err(e) => tracing::warn!(
+                            path = %path.display(),
+                            err = %e,
+                            "Failed to parse ICS file"
+                        ),
                     }
                 }
                 _ => {}
             }
         }
 
-        // Remove stale DB entries whose files no longer exist on disk.
-        // TODO: should we use db or store as golden source here? If we use db as golden source, we
-        // might end up deleting files that were just created on disk but haven't been synced yet.
-        // If we use disk as golden source, we might end up keeping stale entries in the db if files
-        // were deleted outside of Aim. For now, we'll use disk as golden source since it's more
-        // likely to reflect the user's intent.
-        let deleted = self.remove_stale_entries(db, &disk_uids).await?;
+        Ok(events)
+    }
+}
 
-        Ok(SyncResult {
-            created,
-            updated: 0,
-            deleted,
-        })
+/// A component parsed from a calendar file, waiting to be written to the database.
+struct IngestItem {
+    path: PathBuf,
+    uid: String,
+    record: IngestRecord,
+    hash: String,
+}
+
+/// The columns of a component, extracted on the threads parsing the files.
+enum IngestRecord {
+    Event(EventRecord),
+    Todo(TodoRecord),
+}
+
+/// Lists the .ics files in the calendar directory, or `None` if it doesn't exist.
+async fn list_ics_files(dir: &Path) -> Result<Option<Vec<PathBuf>>, StoreError> {
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read calendar directory: {e}").into()),
+    };
+
+    let mut paths = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "ics") {
+            paths.push(path);
+        }
     }
+    Ok(Some(paths))
 }
 
 #[async_trait]
@@ -317,11 +615,13 @@ impl crate::store::Store for LocalStore {
 
     async fn get_event(&self, uid: &str) -> Result<aimcal_ical::VEvent<String>, StoreError> {
         let path = self.file_path(uid);
-        let calendar = parse_ics(&path).await.map_err(into_store_error)?;
+        let calendar = parse_ics(&path).await?;
 
-        // Extract the first event component
+        // Extract the first event component, which is not an override of its occurrences
         for component in calendar.components {
-            if let CalendarComponent::Event(event) = component {
+            if let CalendarComponent::Event(event) = component
+                && !is_override(&event)
+            {
                 return Ok(event);
             }
         }
@@ -337,16 +637,21 @@ impl crate::store::Store for LocalStore {
         let now = Zoned::now();
 
         // Try to get existing event from file
+        let file_path = self.file_path(uid);
         match self.get_event(uid).await {
             Ok(mut event) => {
-                // Case 1: File exists - apply patch and write back
+                // Case 1: File exists - apply patch and write back, along with any overrides
                 patch.resolve(now.clone()).apply_to(&mut event);
 
-                let file_path = self.file_path(uid);
-                let calendar = ICalendar {
-                    components: vec![CalendarComponent::Event(event.clone())],
-                    ..Default::default()
-                };
+                let mut calendar = parse_ics(&file_path).await?;
+                for component in &mut calendar.components {
+                    if let CalendarComponent::Event(e) = component
+                        && !is_override(e)
+                    {
+                        *e = event.clone();
+                        break;
+                    }
+                }
                 write_ics(&file_path, &calendar).await?;
 
                 Ok(event)
@@ -365,7 +670,6 @@ impl crate::store::Store for LocalStore {
                 patch.resolve(now.clone()).apply_to(&mut event);
 
                 // Write to file
-                let file_path = self.file_path(uid);
                 let calendar = ICalendar {
                     components: vec![CalendarComponent::Event(event.clone())],
                     ..Default::default()
@@ -377,9 +681,7 @@ impl crate::store::Store for LocalStore {
                     .insert(uid, &self.calendar_id, &self.resource_id(uid), None)
                     .await
                     .map_err(|e| StoreError::from(format!("{e}")))?;
-                db.upsert_event(uid, &event, &self.calendar_id)
-                    .await
-                    .map_err(|e| StoreError::from(format!("{e}")))?;
+                db.upsert_event(uid, &event, &self.calendar_id).await?;
 
                 Ok(event)
             }
@@ -395,6 +697,25 @@ impl crate::store::Store for LocalStore {
         Ok(())
     }
 
+    async fn put_event_override(
+        &self,
+        uid: &str,
+        event: &aimcal_ical::VEvent<String>,
+    ) -> Result<(), StoreError> {
+        // Overrides are kept in the file of their series
+        let file_path = self.file_path(uid);
+        let mut calendar = parse_ics(&file_path).await?;
+        calendar.components.retain(|c| match c {
+            CalendarComponent::Event(e) => !same_recurrence_id(e, event),
+            _ => true,
+        });
+        calendar
+            .components
+            .push(CalendarComponent::Event(event.clone()));
+        write_ics(&file_path, &calendar).await?;
+        Ok(())
+    }
+
     async fn create_todo(
         &self,
         uid: &str,
@@ -415,17 +736,8 @@ impl crate::store::Store for LocalStore {
     }
 
     async fn get_todo(&self, uid: &str) -> Result<aimcal_ical::VTodo<String>, StoreError> {
-        let path = self.file_path(uid);
-        let calendar = parse_ics(&path).await.map_err(into_store_error)?;
-
-        // Extract the first todo component
-        for component in calendar.components {
-            if let CalendarComponent::Todo(todo) = component {
-                return Ok(todo);
-            }
-        }
-
-        Err(format!("Todo not found in file: {}", path.display()).into())
+        let (todo, _) = read_todo(&self.file_path(uid)).await?;
+        Ok(todo)
     }
 
     async fn update_todo(
@@ -433,24 +745,12 @@ impl crate::store::Store for LocalStore {
         uid: &str,
         patch: &TodoPatch,
     ) -> Result<aimcal_ical::VTodo<String>, StoreError> {
-        let now = Zoned::now();
-
         // Try to get existing todo from file
-        match self.get_todo(uid).await {
-            Ok(mut todo) => {
-                // File exists: apply patch and write back
-                patch.resolve(&now).apply_to(&mut todo);
-
-                let file_path = self.file_path(uid);
-                let calendar = ICalendar {
-                    components: vec![CalendarComponent::Todo(todo.clone())],
-                    ..Default::default()
-                };
-                write_ics(&file_path, &calendar).await?;
-
-                Ok(todo)
-            }
+        match read_todo(&self.file_path(uid)).await {
+            // File exists: apply patch and write back
+            Ok((todo, hash)) => self.update_todo_file(uid, patch, todo, hash).await,
             Err(_) if self.db.is_some() => {
+                let now = Zoned::now();
                 // File doesn't exist but we have DB: reconstruct from DB
                 let db = self.db.as_ref().unwrap();
                 let db_todo = db
@@ -476,9 +776,7 @@ impl crate::store::Store for LocalStore {
                     .insert(uid, &self.calendar_id, &self.resource_id(uid), None)
                     .await
                     .map_err(|e| StoreError::from(format!("{e}")))?;
-                db.upsert_todo(uid, &todo, &self.calendar_id)
-                    .await
-                    .map_err(|e| StoreError::from(format!("{e}")))?;
+                db.upsert_todo(uid, &todo, &self.calendar_id).await?;
 
                 Ok(todo)
             }
@@ -495,44 +793,12 @@ impl crate::store::Store for LocalStore {
     }
 
     async fn list_events(&self) -> Result<Vec<(String, aimcal_ical::VEvent<String>)>, StoreError> {
-        let mut events = Vec::new();
-
-        let mut entries = match fs::read_dir(&self.calendar_path).await {
-            Ok(e) => e,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                // Directory doesn't exist yet
-                return Ok(events);
-            }
-            Err(e) => {
-                return Err(format!("Failed to read calendar directory: {e}").into());
-            }
-        };
-
-        while let Some(entry) = entries.next_entry().await? {
-            let path = entry.path();
-            match path.extension() {
-                Some(ext) if ext == "ics" => {
-                    match parse_ics(&path).await.map_err(into_store_error) {
-                        Ok(calendar) => {
-                            for component in calendar.components {
-                                if let CalendarComponent::Event(event) = component {
-                                    let uid = event.uid.content.to_string();
-                                    events.push((uid, event));
-                                }
-                            }
-                        }
-                        Err(e) => tracing::warn!(
-                            path = %path.display(),
-                            err = %e,
-                            "Failed to parse ICS file"
-                        ),
-                    }
-                }
-                _ => {}
-            }
-        }
+        self.read_events(false).await
+    }
 
-        Ok(events)
+    async fn list_event_overrides(&self) -> Result<Vec<aimcal_ical::VEvent<String>>, StoreError> {
+        let overrides = self.read_events(true).await?;
+        Ok(overrides.into_iter().map(|(_, event)| event).collect())
     }
 
     async fn list_todos(&self) -> Result<Vec<(String, aimcal_ical::VTodo<String>)>, StoreError> {
@@ -551,12 +817,11 @@ impl crate::store::Store for LocalStore {
             let path = entry.path();
             match path.extension() {
                 Some(ext) if ext == "ics" => {
-                    match parse_ics(&path).await.map_err(into_store_error) {
+                    match parse_ics(&path).await.map_err(StoreError::from) {
                         Ok(calendar) => {
                             for component in calendar.components {
                                 if let CalendarComponent::Todo(todo) = component {
-                                    let uid = todo.uid.content.to_string();
-                                    todos.push((uid, todo));
+                                    todos.push((file_url(&path), todo));
                                 }
                             }
                         }
@@ -583,6 +848,59 @@ impl crate::store::Store for LocalStore {
         &self.calendar_id
     }
 
+    fn broken_items(&self) -> Vec<BrokenItem> {
+        self.broken
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Lists the ICS files, with the SHA-256 of their content as change token.
+    async fn list_resources(&self) -> Result<Vec<StoreResource>, StoreError> {
+        let mut resources = Vec::new();
+
+        let mut entries = match fs::read_dir(&self.calendar_path).await {
+            Ok(e) => e,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(resources),
+            Err(e) => return Err(format!("Failed to read calendar directory: {e}").into()),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "ics") {
+                let content = fs::read(&path).await?;
+                resources.push(StoreResource {
+                    id: file_url(&path),
+                    change_token: Some(sha256_hex(&content)),
+                });
+            }
+        }
+
+        Ok(resources)
+    }
+
+    async fn get_component(
+        &self,
+        resource_id: &str,
+    ) -> Result<CalendarComponent<String>, StoreError> {
+        let path = resource_id
+            .strip_prefix("file://")
+            .ok_or_else(|| format!("Not a local resource: {resource_id}"))?;
+        parse_ics(Path::new(path))
+            .await?
+            .components
+            .into_iter()
+            .find(|c| matches!(c, CalendarComponent::Event(_) | CalendarComponent::Todo(_)))
+            .ok_or_else(|| format!("No event or todo found in {path}").into())
+    }
+
+    async fn plan_pull(&self) -> Result<Vec<PulledChange>, StoreError> {
+        match &self.db {
+            Some(db) => self.plan_from_directory(db).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
     async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
         // For the local store, we sync from files on disk to the database
         match &self.db {
@@ -598,21 +916,128 @@ impl crate::store::Store for LocalStore {
     }
 }
 
+/// Resource ID of an ICS file.
+fn file_url(path: &Path) -> String {
+    format!("file://{}", path.display())
+}
+
 // TODO: support multiple calendars in one file
-pub async fn parse_ics(path: &Path) -> Result<ICalendar<String>, Box<dyn Error>> {
+pub async fn parse_ics(path: &Path) -> Result<ICalendar<String>, AimError> {
+    let (calendar, _) = read_ics(path).await?;
+    Ok(calendar)
+}
+
+/// Parses an ICS file, returning the calendar and the SHA-256 of the file content.
+async fn read_ics(path: &Path) -> Result<(ICalendar<String>, String), AimError> {
     let content = fs::read_to_string(path)
         .await
-        .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?;
-
-    let calendars = aimcal_ical::parse(&content)
-        .map_err(|e| -> Box<dyn Error> { format!("Failed to parse calendar: {e:?}").into() })?;
+        .map_err(|e| read_error(path, &e))?;
+    parse_ics_content(path, &content)
+}
 
-    if calendars.is_empty() {
-        return Err("No calendars found in file".into());
+/// Reads an ICS file on the threads of the ingest, extracting the columns of its events and todos.
+///
+/// The columns are read from the borrowed parse, as the file is not kept, so no owned copies of
+/// the components are made. Components with errors are left out and returned as broken items,
+/// while the others of the file are read.
+fn read_ingest_blocking(
+    path: &Path,
+    calendar_id: &str,
+) -> Result<(Vec<IngestItem>, Vec<BrokenItem>), AimError> {
+    let content = std::fs::read_to_string(path).map_err(|e| read_error(path, &e))?;
+    let parse_error = |errors: Vec<String>| AimError::Parse {
+        file: path.to_path_buf(),
+        errors,
+    };
+    let parsed = parse_foreign_partial(&path.display(), &content).map_err(parse_error)?;
+    let calendar = parsed
+        .calendars
+        .first()
+        .ok_or_else(|| parse_error(vec!["No calendars found in file".to_string()]))?;
+    let hash = sha256_hex(content.as_bytes());
+
+    let broken = parsed
+        .broken
+        .iter()
+        .map(|component| {
+            let errors: Vec<_> = component.errors.iter().map(ToString::to_string).collect();
+            tracing::warn!(path = %path.display(), component = %component.name, uid = ?component.uid, ?errors, "skipped a broken component");
+            BrokenItem {
+                source: path.display().to_string(),
+                component: component.name.clone(),
+                uid: component.uid.clone(),
+                errors,
+            }
+        })
+        .collect();
+
+    let mut items = Vec::with_capacity(calendar.components.len());
+    for component in &calendar.components {
+        let (uid, record) = match component {
+            // Overrides of occurrences share the UID of their series
+            CalendarComponent::Event(event) if is_override(event) => continue,
+            CalendarComponent::Event(event) => {
+                let uid = event.uid.content.to_string();
+                let record = EventRecord::from_event(&uid, event, calendar_id);
+                (uid, IngestRecord::Event(record))
+            }
+            CalendarComponent::Todo(todo) => {
+                let uid = todo.uid.content.to_string();
+                let record = TodoRecord::from_todo(&uid, todo, calendar_id);
+                (uid, IngestRecord::Todo(record))
+            }
+            _ => {
+                tracing::warn!(path = %path.display(), "Unsupported component type in ICS file");
+                continue;
+            }
+        };
+        items.push(IngestItem {
+            path: path.to_path_buf(),
+            uid,
+            record,
+            hash: hash.clone(),
+        });
     }
+    Ok((items, broken))
+}
 
+fn read_error(path: &Path, e: &std::io::Error) -> AimError {
+    AimError::Backend(format!("Failed to read file {}: {}", path.display(), e).into())
+}
+
+fn parse_ics_content(path: &Path, content: &str) -> Result<(ICalendar<String>, String), AimError> {
     // Hybrid: parse borrowed, convert to owned for storage
-    Ok(calendars.into_iter().next().unwrap().to_owned())
+    let calendar = parse_first_calendar(path, content)?.to_owned();
+    Ok((calendar, sha256_hex(content.as_bytes())))
+}
+
+/// Parses the first calendar of an ICS file, borrowing from its content.
+fn parse_first_calendar<'src>(
+    path: &Path,
+    content: &'src str,
+) -> Result<ICalendar<Segments<'src>>, AimError> {
+    let parse_error = |errors: Vec<String>| AimError::Parse {
+        file: path.to_path_buf(),
+        errors,
+    };
+    let calendars = parse_foreign(&path.display(), content).map_err(parse_error)?;
+    calendars
+        .into_iter()
+        .next()
+        .ok_or_else(|| parse_error(vec!["No calendars found in file".to_string()]))
+}
+
+/// Reads the first todo of an ICS file, along with the SHA-256 of the file content.
+async fn read_todo(path: &Path) -> Result<(aimcal_ical::VTodo<String>, String), StoreError> {
+    let (calendar, hash) = read_ics(path).await?;
+    calendar
+        .components
+        .into_iter()
+        .find_map(|component| match component {
+            CalendarComponent::Todo(todo) => Some((todo, hash.clone())),
+            _ => None,
+        })
+        .ok_or_else(|| format!("Todo not found in file: {}", path.display()).into())
 }
 
 pub async fn write_ics(path: &Path, calendar: &ICalendar<String>) -> Result<(), String> {
@@ -626,8 +1051,38 @@ pub async fn write_ics(path: &Path, calendar: &ICalendar<String>) -> Result<(),
     let ics_content = aimcal_ical::formatter::format(calendar)
         .map_err(|e| format!("Failed to format calendar: {e}"))?;
 
-    fs::write(path, ics_content)
+    write_atomic(path, ics_content.as_bytes()).await
+}
+
+/// Writes the calendar to `path` if the file still has the content hashed as `expected`.
+///
+/// Returns the hash of the written content, or `None` if the file changed in the meantime.
+async fn write_ics_if_unchanged(
+    path: &Path,
+    expected: &str,
+    calendar: &ICalendar<String>,
+) -> Result<Option<String>, String> {
+    let ics_content = aimcal_ical::formatter::format(calendar)
+        .map_err(|e| format!("Failed to format calendar: {e}"))?;
+
+    let current = fs::read(path)
         .await
+        .map_err(|e| format!("Failed to read calendar file: {e}"))?;
+    if sha256_hex(&current) != expected {
+        return Ok(None);
+    }
+
+    write_atomic(path, ics_content.as_bytes()).await?;
+    Ok(Some(sha256_hex(ics_content.as_bytes())))
+}
+
+/// Writes to a temporary file next to `path`, flushed to disk and renamed over `path`, so readers
+/// such as vdirsyncer never see a partially written file, nor does a crash leave one.
+async fn write_atomic(path: &Path, content: &[u8]) -> Result<(), String> {
+    let (path, content) = (path.to_path_buf(), content.to_vec());
+    tokio::task::spawn_blocking(move || file::write_atomic(&path, &content))
+        .await
+        .map_err(|e| format!("Failed to write calendar file: {e}"))?
         .map_err(|e| format!("Failed to write calendar file: {e}"))
 }
 
@@ -639,6 +1094,7 @@ mod tests {
 
     use super::*;
 
+    use crate::LooseDateTime;
     use crate::store::Store;
 
     /// Helper function to create a test `VEvent`.
@@ -661,7 +1117,7 @@ mod tests {
         aimcal_ical::VEvent {
             uid: Uid::new(uid.to_string()),
             dt_stamp: DtStamp::new(utc_now.datetime()),
-            dt_start: DtStart::new(dt_start),
+            dt_start: Some(DtStart::new(dt_start)),
             dt_end: Some(DtEnd::new(dt_end)),
             duration: None,
             summary: Some(Summary::new(summary.to_string())),
@@ -677,8 +1133,14 @@ mod tests {
             sequence: None,
             priority: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -713,8 +1175,14 @@ mod tests {
             last_modified: None,
             sequence: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories: Vec::new(),
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: None,
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
@@ -1011,8 +1479,14 @@ mod tests {
         events_sorted.sort_by(|a, b| a.0.cmp(&b.0));
 
         assert_eq!(events_sorted.len(), 2);
-        assert_eq!(events_sorted.first().unwrap().0, "event-1");
-        assert_eq!(events_sorted.get(1).unwrap().0, "event-2");
+        assert_eq!(
+            events_sorted.first().unwrap().0,
+            backend.resource_id("event-1")
+        );
+        assert_eq!(
+            events_sorted.get(1).unwrap().0,
+            backend.resource_id("event-2")
+        );
     }
 
     #[tokio::test]
@@ -1035,8 +1509,14 @@ mod tests {
         todos_sorted.sort_by(|a, b| a.0.cmp(&b.0));
 
         assert_eq!(todos_sorted.len(), 2);
-        assert_eq!(todos_sorted.first().unwrap().0, "todo-1");
-        assert_eq!(todos_sorted.get(1).unwrap().0, "todo-2");
+        assert_eq!(
+            todos_sorted.first().unwrap().0,
+            backend.resource_id("todo-1")
+        );
+        assert_eq!(
+            todos_sorted.get(1).unwrap().0,
+            backend.resource_id("todo-2")
+        );
     }
 
     #[tokio::test]
@@ -1054,4 +1534,182 @@ mod tests {
         assert!(events.is_empty());
         assert!(todos.is_empty());
     }
+
+    /// Rewrites the todo file as another program would, returning the todo it now holds.
+    async fn edit_externally(
+        backend: &LocalStore,
+        uid: &str,
+        edit: impl FnOnce(&mut aimcal_ical::VTodo<String>),
+    ) -> aimcal_ical::VTodo<String> {
+        let mut todo = backend.get_todo(uid).await.unwrap();
+        edit(&mut todo);
+        let calendar = ICalendar {
+            components: vec![CalendarComponent::Todo(todo.clone())],
+            ..Default::default()
+        };
+        write_ics(&backend.file_path(uid), &calendar).await.unwrap();
+        todo
+    }
+
+    #[tokio::test]
+    async fn local_backend_update_todo_reapplies_patch_after_external_change() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let backend = LocalStore::new(temp_dir.path().to_path_buf(), "default".to_string());
+        let uid = "test-todo-race";
+        backend
+            .create_todo(uid, &create_test_vtodo(uid, "Original Summary"))
+            .await
+            .unwrap();
+        let (base, base_hash) = read_todo(&backend.file_path(uid)).await.unwrap();
+
+        // Another program changes a property the patch does not touch
+        edit_externally(&backend, uid, |todo| {
+            todo.description = Some(Description::new("Edited elsewhere".to_string()));
+        })
+        .await;
+
+        let patch = TodoPatch {
+            summary: Some("Updated Summary".to_string()),
+            ..Default::default()
+        };
+        let updated = backend
+            .update_todo_file(uid, &patch, base, base_hash)
+            .await
+            .unwrap();
+
+        let retrieved = backend.get_todo(uid).await.unwrap();
+        assert_eq!(retrieved.summary(), "Updated Summary");
+        assert_eq!(retrieved.description().as_deref(), Some("Edited elsewhere"));
+        assert_eq!(updated.description().as_deref(), Some("Edited elsewhere"));
+    }
+
+    #[tokio::test]
+    async fn local_backend_update_todo_conflicts_on_external_change_of_patched_property() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let backend = LocalStore::new(temp_dir.path().to_path_buf(), "default".to_string());
+        let uid = "test-todo-conflict";
+        backend
+            .create_todo(uid, &create_test_vtodo(uid, "Original Summary"))
+            .await
+            .unwrap();
+        let (base, base_hash) = read_todo(&backend.file_path(uid)).await.unwrap();
+
+        edit_externally(&backend, uid, |todo| {
+            todo.summary = Some(Summary::new("Edited elsewhere".to_string()));
+        })
+        .await;
+
+        let patch = TodoPatch {
+            summary: Some("Updated Summary".to_string()),
+            ..Default::default()
+        };
+        let err = backend
+            .update_todo_file(uid, &patch, base, base_hash)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            AimError::from(err),
+            AimError::Conflict { uid: conflict_uid } if conflict_uid == uid
+        ));
+
+        // The external change is kept
+        let retrieved = backend.get_todo(uid).await.unwrap();
+        assert_eq!(retrieved.summary(), "Edited elsewhere");
+    }
+
+    #[tokio::test]
+    async fn local_backend_sync_cache_ingests_in_batches_and_skips_broken_files() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db = Db::open(None).await.unwrap();
+        let backend =
+            LocalStore::with_db(temp_dir.path().to_path_buf(), db.clone(), "default".into());
+
+        // More components than fit in one transaction
+        let count = INGEST_BATCH_SIZE + 10;
+        for i in 0..count {
+            let uid = format!("todo-{i}");
+            backend
+                .create_todo(&uid, &create_test_vtodo(&uid, "Summary"))
+                .await
+                .unwrap();
+        }
+        fs::write(
+            temp_dir.path().join("broken.ics"),
+            "BEGIN:VCALENDAR\r\nnot ics",
+        )
+        .await
+        .unwrap();
+
+        let result = backend.sync_cache().await.unwrap();
+        assert_eq!(result.created, count);
+        let last = format!("todo-{}", count - 1);
+        assert!(db.todos.get(&last).await.unwrap().is_some());
+        let resource = db.resources.get(&last, "default").await.unwrap().unwrap();
+        assert_eq!(resource.resource_id, backend.resource_id(&last));
+
+        // Files removed on disk are removed from the cache
+        fs::remove_file(backend.file_path("todo-0")).await.unwrap();
+        let result = backend.sync_cache().await.unwrap();
+        assert_eq!(result.deleted, 1);
+        assert!(db.todos.get("todo-0").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn local_backend_sync_cache_keeps_only_items_of_the_adopted_directory() {
+        let adopted_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let other_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db = Db::open(None).await.unwrap();
+        for (dir, uid) in [(&adopted_dir, "adopted"), (&other_dir, "other")] {
+            let todo = create_test_vtodo(uid, "Summary");
+            let store = LocalStore::with_db(dir.path().to_path_buf(), db.clone(), "default".into());
+            let resource_id = store.create_todo(uid, &todo).await.unwrap();
+            db.upsert_todo(uid, &todo, "default").await.unwrap();
+            db.resources
+                .insert(uid, "default", &resource_id, None)
+                .await
+                .unwrap();
+        }
+
+        let backend =
+            LocalStore::with_db(temp_dir.path().to_path_buf(), db.clone(), "default".into())
+                .adopting(adopted_dir.path().to_path_buf());
+        let result = backend.sync_cache().await.unwrap();
+        assert_eq!(result.deleted, 1);
+        assert!(db.todos.get("adopted").await.unwrap().is_some());
+        assert!(db.todos.get("other").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn write_ics_if_unchanged_checks_hash_and_leaves_no_temp_files() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("todo.ics");
+        let calendar = |summary: &str| ICalendar {
+            components: vec![CalendarComponent::Todo(create_test_vtodo("todo", summary))],
+            ..Default::default()
+        };
+        write_ics(&path, &calendar("First")).await.unwrap();
+        let (_, hash) = read_ics(&path).await.unwrap();
+
+        let written = write_ics_if_unchanged(&path, &hash, &calendar("Second"))
+            .await
+            .unwrap();
+        let (_, new_hash) = read_ics(&path).await.unwrap();
+        assert_eq!(written, Some(new_hash));
+
+        // The old hash no longer matches, so nothing is written
+        let written = write_ics_if_unchanged(&path, &hash, &calendar("Third"))
+            .await
+            .unwrap();
+        assert_eq!(written, None);
+        let (todo, _) = read_todo(&path).await.unwrap();
+        assert_eq!(todo.summary(), "Second");
+
+        let mut entries = fs::read_dir(temp_dir.path()).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name());
+        }
+        assert_eq!(names, ["todo.ics"]);
+    }
 }
@@ -0,0 +1,295 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wrapper that keeps the local cache of a custom store up to date.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use aimcal_ical::{CalendarComponent, VEvent, VTodo};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Db;
+use crate::store::{
+    BrokenItem, PulledChange, Store, StoreCapabilities, StoreError, StoreResource, SyncResult,
+};
+use crate::{EventPatch, Invitation, TodoPatch};
+
+/// Metadata stored with the resources of custom stores in the database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RegisteredMetadata {
+    /// Change token of the resource when it was last cached.
+    change_token: Option<String>,
+}
+
+/// A store registered with [`AimBuilder::store`](crate::AimBuilder::store).
+///
+/// Custom stores only deal with their own data, so this wrapper mirrors their items
+/// into the database after they synchronize, skipping resources whose change token
+/// did not change.
+pub(crate) struct RegisteredStore {
+    store: Box<dyn Store>,
+    db: Db,
+}
+
+impl RegisteredStore {
+    pub(crate) fn new(store: Box<dyn Store>, db: Db) -> Self {
+        Self { store, db }
+    }
+
+    /// Compares the items of the store with the database, listing those to mirror.
+    async fn changes(&self) -> Result<Vec<Mirror>, StoreError> {
+        let tokens: HashMap<_, _> = self
+            .store
+            .list_resources()
+            .await?
+            .into_iter()
+            .map(|r| (r.id, r.change_token))
+            .collect();
+        let known: HashMap<_, _> = self
+            .db
+            .resources
+            .list_by_calendar(self.store.calendar_id())
+            .await?
+            .into_iter()
+            .map(|r| {
+                let token = r
+                    .metadata_json::<RegisteredMetadata>()
+                    .and_then(|m| m.change_token);
+                (r.uid, (r.resource_id, token))
+            })
+            .collect();
+
+        let events = self
+            .store
+            .list_events()
+            .await?
+            .into_iter()
+            .map(|(id, event)| {
+                let uid = event.uid.content.to_string();
+                (uid, id, CalendarComponent::Event(event))
+            });
+        let todos = self
+            .store
+            .list_todos()
+            .await?
+            .into_iter()
+            .map(|(id, todo)| {
+                let uid = todo.uid.content.to_string();
+                (uid, id, CalendarComponent::Todo(todo))
+            });
+
+        let mut changes = Vec::new();
+        let mut seen = HashSet::new();
+        for (uid, resource_id, component) in events.chain(todos) {
+            let change_token = tokens.get(&resource_id).cloned().flatten();
+            let cached = known.get(&uid);
+            let unchanged = cached.is_some_and(|(cached_id, cached_token)| {
+                *cached_id == resource_id && change_token.is_some() && *cached_token == change_token
+            });
+            if !unchanged
+                && matches!(
+                    component,
+                    CalendarComponent::Event(_) | CalendarComponent::Todo(_)
+                )
+            {
+                changes.push(Mirror::Cache {
+                    uid: uid.clone(),
+                    resource_id,
+                    component: Box::new(component),
+                    change_token,
+                    cached: cached.is_some(),
+                });
+            }
+            seen.insert(uid);
+        }
+
+        for uid in known.into_keys().filter(|uid| !seen.contains(uid)) {
+            changes.push(Mirror::Remove(uid));
+        }
+        Ok(changes)
+    }
+
+    /// Mirrors the items of the store into the database, adding to the counts of `result`.
+    async fn mirror(&self, mut result: SyncResult) -> Result<SyncResult, StoreError> {
+        let calendar_id = self.store.calendar_id();
+        for change in self.changes().await? {
+            match change {
+                Mirror::Cache {
+                    uid,
+                    resource_id,
+                    component,
+                    change_token,
+                    cached,
+                } => {
+                    match &*component {
+                        CalendarComponent::Event(event) => {
+                            self.db.upsert_event(&uid, event, calendar_id).await?;
+                        }
+                        CalendarComponent::Todo(todo) => {
+                            self.db.upsert_todo(&uid, todo, calendar_id).await?;
+                        }
+                        _ => continue,
+                    }
+                    let metadata = serde_json::to_string(&RegisteredMetadata { change_token })?;
+                    self.db
+                        .resources
+                        .insert(&uid, calendar_id, &resource_id, Some(&metadata))
+                        .await?;
+                    if cached {
+                        result.updated += 1;
+                    } else {
+                        result.created += 1;
+                    }
+                }
+                Mirror::Remove(uid) => {
+                    self.db.events.delete(&uid).await?;
+                    self.db.todos.delete(&uid).await?;
+                    self.db.resources.delete(&uid, calendar_id).await?;
+                    result.deleted += 1;
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// A change to mirror from a custom store into the database, see [`RegisteredStore::changes`].
+enum Mirror {
+    /// Caches an item new to the database or whose change token changed.
+    Cache {
+        uid: String,
+        resource_id: String,
+        component: Box<CalendarComponent<String>>,
+        change_token: Option<String>,
+        /// Whether the item was cached before.
+        cached: bool,
+    },
+    /// Removes an item gone from the store.
+    Remove(String),
+}
+
+impl fmt::Debug for RegisteredStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredStore")
+            .field("calendar_id", &self.store.calendar_id())
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Store for RegisteredStore {
+    async fn create_event(&self, uid: &str, event: &VEvent<String>) -> Result<String, StoreError> {
+        self.store.create_event(uid, event).await
+    }
+
+    async fn get_event(&self, uid: &str) -> Result<VEvent<String>, StoreError> {
+        self.store.get_event(uid).await
+    }
+
+    async fn update_event(
+        &self,
+        uid: &str,
+        patch: &EventPatch,
+    ) -> Result<VEvent<String>, StoreError> {
+        self.store.update_event(uid, patch).await
+    }
+
+    async fn delete_event(&self, uid: &str) -> Result<(), StoreError> {
+        self.store.delete_event(uid).await
+    }
+
+    async fn put_event_override(
+        &self,
+        uid: &str,
+        event: &VEvent<String>,
+    ) -> Result<(), StoreError> {
+        self.store.put_event_override(uid, event).await
+    }
+
+    async fn create_todo(&self, uid: &str, todo: &VTodo<String>) -> Result<String, StoreError> {
+        self.store.create_todo(uid, todo).await
+    }
+
+    async fn get_todo(&self, uid: &str) -> Result<VTodo<String>, StoreError> {
+        self.store.get_todo(uid).await
+    }
+
+    async fn update_todo(&self, uid: &str, patch: &TodoPatch) -> Result<VTodo<String>, StoreError> {
+        self.store.update_todo(uid, patch).await
+    }
+
+    async fn delete_todo(&self, uid: &str) -> Result<(), StoreError> {
+        self.store.delete_todo(uid).await
+    }
+
+    async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError> {
+        self.store.list_events().await
+    }
+
+    async fn list_event_overrides(&self) -> Result<Vec<VEvent<String>>, StoreError> {
+        self.store.list_event_overrides().await
+    }
+
+    async fn list_todos(&self) -> Result<Vec<(String, VTodo<String>)>, StoreError> {
+        self.store.list_todos().await
+    }
+
+    async fn uid_exists(&self, uid: &str) -> Result<bool, StoreError> {
+        self.store.uid_exists(uid).await
+    }
+
+    fn calendar_id(&self) -> &str {
+        self.store.calendar_id()
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        self.store.capabilities()
+    }
+
+    async fn pending_invitations(&self) -> Result<Vec<Invitation>, StoreError> {
+        self.store.pending_invitations().await
+    }
+
+    async fn list_resources(&self) -> Result<Vec<StoreResource>, StoreError> {
+        self.store.list_resources().await
+    }
+
+    async fn get_component(
+        &self,
+        resource_id: &str,
+    ) -> Result<CalendarComponent<String>, StoreError> {
+        self.store.get_component(resource_id).await
+    }
+
+    async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
+        let result = self.store.sync_cache().await?;
+        self.mirror(result).await
+    }
+
+    async fn plan_pull(&self) -> Result<Vec<PulledChange>, StoreError> {
+        let calendar_id = self.store.calendar_id();
+        let mut planned = Vec::new();
+        for change in self.changes().await? {
+            let change = match change {
+                Mirror::Cache {
+                    component, cached, ..
+                } => PulledChange::of(&self.db, calendar_id, &component, cached).await?,
+                Mirror::Remove(uid) => PulledChange::removal(&self.db, &uid).await?,
+            };
+            planned.extend(change);
+        }
+        Ok(planned)
+    }
+
+    fn broken_items(&self) -> Vec<BrokenItem> {
+        self.store.broken_items()
+    }
+
+    async fn refresh(&self) -> Result<SyncResult, StoreError> {
+        let result = self.store.refresh().await?;
+        self.mirror(result).await
+    }
+}
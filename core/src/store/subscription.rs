@@ -0,0 +1,714 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read-only store backed by a remote ICS feed (webcal subscription).
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use aimcal_ical::ops::diff;
+use aimcal_ical::{CalendarComponent, ICalendar, VEvent, VTodo};
+use async_trait::async_trait;
+use jiff::Timestamp;
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+use crate::db::Db;
+use crate::db::subscriptions::SubscriptionRecord;
+use crate::error::StoreContext;
+use crate::import::{parse_foreign, pin_floating_times, signed_duration};
+use crate::store::{PulledChange, Store, StoreCapabilities, StoreError, SyncResult};
+use crate::{EventPatch, TodoPatch};
+
+/// Refresh interval of feeds that don't suggest one with `REFRESH-INTERVAL`.
+const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+/// Configuration of a subscription store.
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    /// URL of the ICS feed.
+    pub url: String,
+    /// Minimum number of seconds between two refreshes of the feed, `None` to follow the
+    /// `REFRESH-INTERVAL` of the feed.
+    pub refresh_interval_secs: Option<u64>,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+    /// User agent string for HTTP requests.
+    pub user_agent: String,
+    /// Whether to name the calendar after the feed, for calendars without a configured name.
+    pub name_from_feed: bool,
+}
+
+/// A copy of a feed as downloaded, along with its validators.
+struct Download {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// Subscription store implementation.
+///
+/// The feed is downloaded as a whole and cached in the database, together with
+/// the validators needed for conditional requests. Its events and todos are
+/// read-only: every write operation fails.
+#[derive(Debug)]
+pub struct SubscriptionStore {
+    /// The HTTP client used to fetch the feed.
+    client: reqwest::Client,
+    /// URL of the ICS feed.
+    url: String,
+    /// Minimum number of seconds between two refreshes of the feed, if configured.
+    refresh_interval_secs: Option<u64>,
+    /// Whether to name the calendar after the feed.
+    name_from_feed: bool,
+    /// The database for local cache.
+    db: Db,
+    /// The calendar identifier in the database.
+    calendar_id: String,
+}
+
+impl SubscriptionStore {
+    /// Creates a new subscription store from configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if client initialization fails.
+    pub fn new(
+        config: SubscriptionConfig,
+        db: Db,
+        calendar_id: String,
+    ) -> Result<Self, StoreError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent)
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: config.url,
+            refresh_interval_secs: config.refresh_interval_secs,
+            name_from_feed: config.name_from_feed,
+            db,
+            calendar_id,
+        })
+    }
+
+    /// Resource ID of the item `uid` in the feed.
+    fn resource_id(&self, uid: &str) -> String {
+        format!("{}#{uid}", self.url)
+    }
+
+    /// Error returned by every write operation.
+    fn read_only_error(&self) -> StoreError {
+        format!(
+            "Calendar '{}' is a read-only subscription to {}",
+            self.calendar_id, self.url
+        )
+        .into()
+    }
+
+    /// Whether the cached copy is older than the refresh interval.
+    ///
+    /// Without a configured interval, the one suggested by the cached copy applies.
+    fn is_stale(&self, cached: &SubscriptionRecord, now: Timestamp) -> bool {
+        let Ok(fetched_at) = cached.fetched_at.parse::<Timestamp>() else {
+            return true;
+        };
+        let refresh_interval_secs = self
+            .refresh_interval_secs
+            .or_else(|| feed_refresh_interval_secs(&parse_feed(&self.url, &cached.body).ok()?))
+            .unwrap_or(DEFAULT_REFRESH_INTERVAL_SECS);
+        let elapsed = now.duration_since(fetched_at).as_secs();
+        u64::try_from(elapsed).map_or(true, |secs| secs >= refresh_interval_secs)
+    }
+
+    /// Loads the cached copy of this feed, ignoring copies of a previously configured URL.
+    async fn cached(&self) -> Result<Option<SubscriptionRecord>, StoreError> {
+        let record = self.db.subscriptions.get(&self.calendar_id).await?;
+        Ok(record.filter(|r| r.url == self.url))
+    }
+
+    /// Parses the cached copy of this feed.
+    async fn cached_calendars(&self) -> Result<Vec<ICalendar<String>>, StoreError> {
+        match self.cached().await? {
+            Some(record) => parse_feed(&self.url, &record.body),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Downloads the feed, or returns `None` if it did not change since the cached copy.
+    async fn download(
+        &self,
+        cached: Option<&SubscriptionRecord>,
+    ) -> Result<Option<Download>, StoreError> {
+        let mut request = self.client.get(&self.url);
+        if let Some(etag) = cached.and_then(|r| r.etag.as_deref()) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = cached.and_then(|r| r.last_modified.as_deref()) {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED && cached.is_some() {
+            tracing::debug!(url = %self.url, "subscription not modified");
+            return Ok(None);
+        }
+
+        let response = response.error_for_status()?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await?;
+        Ok(Some(Download {
+            etag,
+            last_modified,
+            body,
+        }))
+    }
+
+    /// Downloads the feed and replaces the cached copy if it changed.
+    async fn fetch(&self, cached: Option<&SubscriptionRecord>) -> Result<SyncResult, StoreError> {
+        let download = self.download(cached).await?;
+        let fetched_at = Timestamp::now().to_string();
+        let Some(Download {
+            etag,
+            last_modified,
+            body,
+        }) = download
+        else {
+            self.db
+                .subscriptions
+                .touch(&self.calendar_id, &fetched_at)
+                .await?;
+            return Ok(SyncResult {
+                created: 0,
+                updated: 0,
+                deleted: 0,
+            });
+        };
+
+        // Validate the new content before replacing the cached copy
+        let calendars = parse_feed(&self.url, &body)?;
+        let result = self.ingest(&calendars).await?;
+        if self.name_from_feed
+            && let Some(name) = calendars.iter().find_map(ICalendar::name)
+        {
+            self.db.calendars.set_name(&self.calendar_id, &name).await?;
+        }
+
+        let record = SubscriptionRecord {
+            calendar_id: self.calendar_id.clone(),
+            url: self.url.clone(),
+            etag,
+            last_modified,
+            body,
+            fetched_at,
+        };
+        self.db.subscriptions.upsert(&record).await?;
+        Ok(result)
+    }
+
+    /// Mirrors the components of the feed into the database.
+    async fn ingest(&self, calendars: &[ICalendar<String>]) -> Result<SyncResult, StoreError> {
+        let known: HashSet<String> = self
+            .db
+            .resources
+            .list_uids_by_calendar(&self.calendar_id)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut seen = HashSet::new();
+        for component in calendars.iter().flat_map(|c| &c.components) {
+            let uid = match component {
+                CalendarComponent::Event(event) => {
+                    let uid = event.uid.content.to_string();
+                    self.db.upsert_event(&uid, event, &self.calendar_id).await?;
+                    uid
+                }
+                CalendarComponent::Todo(todo) => {
+                    let uid = todo.uid.content.to_string();
+                    self.db.upsert_todo(&uid, todo, &self.calendar_id).await?;
+                    uid
+                }
+                _ => continue,
+            };
+
+            let resource_id = self.resource_id(&uid);
+            self.db
+                .resources
+                .insert(&uid, &self.calendar_id, &resource_id, None)
+                .await?;
+            seen.insert(uid);
+        }
+
+        let mut deleted = 0;
+        for uid in known.difference(&seen) {
+            self.db.events.delete(uid).await?;
+            self.db.todos.delete(uid).await?;
+            self.db.resources.delete(uid, &self.calendar_id).await?;
+            deleted += 1;
+        }
+
+        Ok(SyncResult {
+            created: seen.difference(&known).count(),
+            updated: seen.intersection(&known).count(),
+            deleted,
+        })
+    }
+}
+
+#[async_trait]
+impl Store for SubscriptionStore {
+    async fn create_event(
+        &self,
+        _uid: &str,
+        _event: &VEvent<String>,
+    ) -> Result<String, StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn get_event(&self, uid: &str) -> Result<VEvent<String>, StoreError> {
+        self.list_events()
+            .await?
+            .into_iter()
+            .find_map(|(_, event)| (event.uid.content.to_string() == uid).then_some(event))
+            .ok_or_else(|| format!("Event not found in subscription: {uid}").into())
+    }
+
+    async fn update_event(
+        &self,
+        _uid: &str,
+        _patch: &EventPatch,
+    ) -> Result<VEvent<String>, StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn delete_event(&self, _uid: &str) -> Result<(), StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn create_todo(&self, _uid: &str, _todo: &VTodo<String>) -> Result<String, StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn get_todo(&self, uid: &str) -> Result<VTodo<String>, StoreError> {
+        self.list_todos()
+            .await?
+            .into_iter()
+            .find_map(|(_, todo)| (todo.uid.content.to_string() == uid).then_some(todo))
+            .ok_or_else(|| format!("Todo not found in subscription: {uid}").into())
+    }
+
+    async fn update_todo(
+        &self,
+        _uid: &str,
+        _patch: &TodoPatch,
+    ) -> Result<VTodo<String>, StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn delete_todo(&self, _uid: &str) -> Result<(), StoreError> {
+        Err(self.read_only_error())
+    }
+
+    async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError> {
+        let calendars = self.cached_calendars().await?;
+        let events = calendars
+            .into_iter()
+            .flat_map(|c| c.components)
+            .filter_map(|component| match component {
+                CalendarComponent::Event(event) => {
+                    Some((self.resource_id(&event.uid.content.to_string()), event))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(events)
+    }
+
+    async fn list_todos(&self) -> Result<Vec<(String, VTodo<String>)>, StoreError> {
+        let calendars = self.cached_calendars().await?;
+        let todos = calendars
+            .into_iter()
+            .flat_map(|c| c.components)
+            .filter_map(|component| match component {
+                CalendarComponent::Todo(todo) => {
+                    Some((self.resource_id(&todo.uid.content.to_string()), todo))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(todos)
+    }
+
+    async fn uid_exists(&self, uid: &str) -> Result<bool, StoreError> {
+        let record = self.db.resources.get(uid, &self.calendar_id).await?;
+        Ok(record.is_some())
+    }
+
+    fn calendar_id(&self) -> &str {
+        &self.calendar_id
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities {
+            read_only: true,
+            ..StoreCapabilities::default()
+        }
+    }
+
+    /// Refreshes the feed if the cached copy is missing or stale.
+    async fn sync_cache(&self) -> Result<SyncResult, StoreError> {
+        if let Some(cached) = self.cached().await?
+            && !self.is_stale(&cached, Timestamp::now())
+        {
+            return Ok(SyncResult {
+                created: 0,
+                updated: 0,
+                deleted: 0,
+            });
+        }
+
+        self.refresh().await
+    }
+
+    /// Plans the changes of the feed since its cached copy, if the copy is stale, downloading the
+    /// feed without caching it.
+    async fn plan_pull(&self) -> Result<Vec<PulledChange>, StoreError> {
+        let cached = self.cached().await?;
+        if let Some(cached) = &cached
+            && !self.is_stale(cached, Timestamp::now())
+        {
+            return Ok(Vec::new());
+        }
+        let Some(download) = self.download(cached.as_ref()).await? else {
+            return Ok(Vec::new());
+        };
+
+        let merge = |calendars: Vec<ICalendar<String>>| ICalendar {
+            components: calendars.into_iter().flat_map(|c| c.components).collect(),
+            ..ICalendar::new()
+        };
+        let old = merge(self.cached_calendars().await?);
+        let new = merge(parse_feed(&self.url, &download.body)?);
+        Ok(diff(&old, &new)
+            .iter()
+            .filter_map(PulledChange::of_diff)
+            .collect())
+    }
+
+    /// Downloads the feed regardless of the refresh interval.
+    ///
+    /// On failure the cached copy is kept, and the error mentions when it was
+    /// last refreshed.
+    #[tracing::instrument(skip(self), fields(url = %self.url))]
+    async fn refresh(&self) -> Result<SyncResult, StoreError> {
+        let cached = self.cached().await?;
+        self.fetch(cached.as_ref()).await.map_err(|e| -> StoreError {
+            let fallback = cached.as_ref().map_or_else(
+                || "no cached copy is available".to_string(),
+                |r| format!("showing cached copy from {}", r.fetched_at),
+            );
+            Box::new(StoreContext {
+                context: format!("Failed to refresh subscription {}, {fallback}", self.url),
+                source: e,
+            })
+        })
+    }
+}
+
+fn parse_feed(url: &str, body: &str) -> Result<Vec<ICalendar<String>>, StoreError> {
+    let calendars = parse_foreign(&url, body)
+        .map_err(|errors| format!("Failed to parse subscription feed: {}", errors.join("; ")))?;
+    Ok(calendars
+        .into_iter()
+        .map(|c| {
+            let mut calendar = c.to_owned();
+            pin_floating_times(&mut calendar);
+            calendar
+        })
+        .collect())
+}
+
+/// The refresh interval suggested by the feed, in seconds.
+fn feed_refresh_interval_secs(calendars: &[ICalendar<String>]) -> Option<u64> {
+    let interval = calendars.iter().find_map(|c| c.refresh_interval.as_ref())?;
+    u64::try_from(signed_duration(&interval.value).as_secs()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+    use crate::db::calendars::CalendarRecord;
+    use crate::{Event, SyncOperation};
+
+    const FEED: &str = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:new-year\r\n\
+DTSTAMP:20260101T000000Z\r\n\
+DTSTART;VALUE=DATE:20260101\r\n\
+SUMMARY:New Year\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    async fn test_store(
+        server: &MockServer,
+        refresh_interval_secs: Option<u64>,
+    ) -> SubscriptionStore {
+        let db = Db::open(None)
+            .await
+            .expect("Failed to create test database");
+        let config = SubscriptionConfig {
+            url: format!("{}/holidays.ics", server.uri()),
+            refresh_interval_secs,
+            timeout_secs: 5,
+            user_agent: "aimcal-test".to_string(),
+            name_from_feed: true,
+        };
+        SubscriptionStore::new(config, db, "holidays".to_string())
+            .expect("Failed to create SubscriptionStore")
+    }
+
+    #[tokio::test]
+    async fn subscription_sync_cache_ingests_feed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/holidays.ics"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = test_store(&server, Some(3600)).await;
+        let result = store.sync_cache().await.unwrap();
+        assert_eq!(result.created, 1);
+
+        let event = store.db.events.get("new-year").await.unwrap().unwrap();
+        assert_eq!(event.calendar_id(), "holidays");
+        assert!(store.uid_exists("new-year").await.unwrap());
+        assert!(store.get_event("new-year").await.is_ok());
+
+        // Fresh cache: no second request
+        let result = store.sync_cache().await.unwrap();
+        assert_eq!(result.created, 0);
+    }
+
+    #[tokio::test]
+    async fn subscription_sync_cache_keeps_the_events_around_an_invalid_one() {
+        let server = MockServer::start().await;
+        let feed = FEED.replace(
+            "END:VCALENDAR\r\n",
+            "BEGIN:VEVENT\r\nUID:no-dtstamp\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        );
+        Mock::given(method("GET"))
+            .and(path("/holidays.ics"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(feed))
+            .mount(&server)
+            .await;
+
+        let store = test_store(&server, Some(3600)).await;
+        let result = store.sync_cache().await.unwrap();
+        assert_eq!(result.created, 1);
+        assert!(store.db.events.get("new-year").await.unwrap().is_some());
+        assert!(store.db.events.get("no-dtstamp").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn subscription_refresh_sends_conditional_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/holidays.ics"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/holidays.ics"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string(FEED),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = test_store(&server, Some(3600)).await;
+        store.refresh().await.unwrap();
+        let result = store.refresh().await.unwrap();
+        assert_eq!(result.created + result.updated + result.deleted, 0);
+        assert!(store.db.events.get("new-year").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn subscription_refresh_removes_dropped_components() {
+        let server = MockServer::start().await;
+        let store = test_store(&server, Some(0)).await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        store.sync_cache().await.unwrap();
+
+        let empty =
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\nEND:VCALENDAR\r\n";
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(empty))
+            .mount(&server)
+            .await;
+        let result = store.sync_cache().await.unwrap();
+
+        assert_eq!(result.deleted, 1);
+        assert!(store.db.events.get("new-year").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn subscription_plan_pull_compares_the_stale_feed_without_caching_it() {
+        let server = MockServer::start().await;
+        let store = test_store(&server, Some(0)).await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        store.sync_cache().await.unwrap();
+
+        let feed = FEED
+            .replace("SUMMARY:New Year", "SUMMARY:New Year's Day")
+            .replace(
+                "END:VCALENDAR\r\n",
+                "BEGIN:VEVENT\r\nUID:labor-day\r\nDTSTAMP:20260101T000000Z\r\n\
+             DTSTART;VALUE=DATE:20260501\r\nSUMMARY:Labor Day\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            );
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(feed))
+            .mount(&server)
+            .await;
+        let planned = store.plan_pull().await.unwrap();
+
+        let changes: Vec<_> = planned
+            .iter()
+            .map(|c| (c.uid.as_str(), c.operation, c.fields.join(",")))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("new-year", SyncOperation::Update, "SUMMARY".to_string()),
+                (
+                    "labor-day",
+                    SyncOperation::Create,
+                    "DTSTAMP,DTSTART,SUMMARY,UID".to_string()
+                ),
+            ]
+        );
+        let cached = store.db.events.get("new-year").await.unwrap().unwrap();
+        assert_eq!(cached.summary(), "New Year");
+        assert!(store.db.events.get("labor-day").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn subscription_refresh_failure_keeps_cached_copy() {
+        let server = MockServer::start().await;
+        let store = test_store(&server, Some(0)).await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        store.sync_cache().await.unwrap();
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let err = store.sync_cache().await.unwrap_err();
+
+        assert!(err.to_string().contains("showing cached copy from"));
+        assert!(store.db.events.get("new-year").await.unwrap().is_some());
+        assert_eq!(store.list_events().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn subscription_follows_refresh_interval_of_feed() {
+        let server = MockServer::start().await;
+        let feed = FEED.replace(
+            "PRODID:-//Test//Test//EN\r\n",
+            "PRODID:-//Test//Test//EN\r\nREFRESH-INTERVAL;VALUE=DURATION:PT0S\r\n",
+        );
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(feed))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        // Without the feed's interval, the default of one day would keep the cache fresh
+        let store = test_store(&server, None).await;
+        store.sync_cache().await.unwrap();
+        store.sync_cache().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn subscription_takes_name_and_timezone_of_feed() {
+        let server = MockServer::start().await;
+        let feed = FEED
+            .replace(
+                "PRODID:-//Test//Test//EN\r\n",
+                "PRODID:-//Test//Test//EN\r\nX-WR-CALNAME:Holidays in Germany\r\nX-WR-TIMEZONE:Europe/Berlin\r\n",
+            )
+            .replace("DTSTART;VALUE=DATE:20260101", "DTSTART:20260101T090000");
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(feed))
+            .mount(&server)
+            .await;
+
+        let store = test_store(&server, Some(3600)).await;
+        let record = CalendarRecord::new(
+            "holidays".to_string(),
+            "holidays".to_string(),
+            "subscription".to_string(),
+            0,
+            true,
+        );
+        store.db.calendars.upsert(record).await.unwrap();
+        store.sync_cache().await.unwrap();
+
+        let calendar = store.db.calendars.get("holidays").await.unwrap().unwrap();
+        assert_eq!(calendar.name, "Holidays in Germany");
+        let event = store.get_event("new-year").await.unwrap();
+        let dt_start = event.dt_start.as_ref().unwrap();
+        assert_eq!(dt_start.tz_id.as_deref(), Some("Europe/Berlin"));
+    }
+
+    #[tokio::test]
+    async fn subscription_rejects_writes() {
+        let server = MockServer::start().await;
+        let store = test_store(&server, Some(3600)).await;
+
+        let err = store.delete_event("new-year").await.unwrap_err();
+        assert!(err.to_string().contains("read-only subscription"));
+        let err = store
+            .update_todo("x", &TodoPatch::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only subscription"));
+    }
+}
@@ -0,0 +1,1034 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Todo templates, from the `[core.templates.<name>]` sections: a todo and the checklist of
+//! subtasks it expands into, such as the steps of a release.
+//!
+//! Summaries and descriptions may hold `{name}` placeholders for the variables the template
+//! declares, filled in when it is expanded, with `{{` and `}}` for literal braces. A subtask is
+//! due at an offset from the due of its parent, such as `+2d` or `-1bd`, or with its parent
+//! without one. A template is either written in the config, or kept in an `.ics` file holding
+//! the todo and its subtasks linked by `RELATED-TO`, with their offsets in `X-AIM-DUE-OFFSET`.
+//!
+//! Templates are checked when the config is loaded, so one that would not expand is rejected
+//! right away.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aimcal_ical::CalendarComponent;
+use jiff::Span;
+
+use crate::datetime::parse_duration;
+use crate::todo::x_text;
+use crate::{
+    AimError, DateTimeAnchor, LooseDateTime, Priority, Todo, WorkingHours, add_business_days,
+};
+
+/// Property of a subtask in a template file holding its offset, see [`DueOffset`].
+const X_AIM_DUE_OFFSET: &str = "X-AIM-DUE-OFFSET";
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The todo templates of the config.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(try_from = "BTreeMap<String, TemplateDef>")]
+pub struct Templates {
+    templates: Vec<Template>,
+    /// Templates kept in `.ics` files, read once the config is normalized.
+    files: Vec<TemplateFile>,
+}
+
+impl Templates {
+    /// The templates, by name.
+    #[must_use]
+    pub fn templates(&self) -> &[Template] {
+        &self.templates
+    }
+
+    /// The template with the given name, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates.iter().find(|template| template.name == name)
+    }
+
+    /// Reads the templates kept in `.ics` files, with their paths resolved by `resolve`.
+    pub(crate) fn load_files(
+        &mut self,
+        resolve: impl Fn(&Path) -> Result<PathBuf, AimError>,
+    ) -> Result<(), AimError> {
+        for file in std::mem::take(&mut self.files) {
+            let name = file.name.clone();
+            let invalid =
+                |reason: String| AimError::Config(format!("Invalid template '{name}': {reason}"));
+            let path = resolve(&file.path)?;
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| invalid(format!("failed to read {}: {e}", path.display())))?;
+            let template = file.parse(&content).map_err(invalid)?;
+            self.templates.push(template);
+        }
+        self.templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(())
+    }
+}
+
+impl TryFrom<BTreeMap<String, TemplateDef>> for Templates {
+    type Error = String;
+
+    fn try_from(defs: BTreeMap<String, TemplateDef>) -> Result<Self, Self::Error> {
+        let mut templates = Self::default();
+        for (name, def) in defs {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(format!(
+                    "Invalid template '{name}': names cannot be empty or contain spaces"
+                ));
+            }
+            let invalid = |reason: String| format!("Invalid template '{name}': {reason}");
+            match def.file.clone() {
+                Some(path) => templates
+                    .files
+                    .push(def.into_file(&name, path).map_err(invalid)?),
+                None => templates
+                    .templates
+                    .push(def.compile(&name).map_err(invalid)?),
+            }
+        }
+        Ok(templates)
+    }
+}
+
+/// A todo and the subtasks it expands into, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct Template {
+    /// The name of the template, such as `release`.
+    pub name: String,
+    /// The variables the summaries and descriptions may hold, all of which need a value.
+    pub variables: Vec<String>,
+    /// When the todo is due unless told otherwise, if at all.
+    pub due: Option<DateTimeAnchor>,
+    /// The summary of the todo.
+    pub summary: String,
+    /// The description of the todo, if any.
+    pub description: Option<String>,
+    /// The priority of the todo, or `None` for the default of the config.
+    pub priority: Option<Priority>,
+    /// The categories of the todo.
+    pub categories: Vec<String>,
+    /// The subtasks, each after its parent, and its own subtasks right after it.
+    pub items: Vec<TemplateItem>,
+}
+
+/// A subtask of a [`Template`].
+#[derive(Debug, Clone)]
+pub struct TemplateItem {
+    /// The ID other items name as their parent, if any.
+    pub id: Option<String>,
+    /// The ID of the item this is a subtask of, or `None` for the todo of the template.
+    pub parent: Option<String>,
+    /// The summary of the subtask.
+    pub summary: String,
+    /// The description of the subtask, if any.
+    pub description: Option<String>,
+    /// The priority of the subtask, or `None` for the default of the config.
+    pub priority: Option<Priority>,
+    /// The categories of the subtask.
+    pub categories: Vec<String>,
+    /// When the subtask is due from the due of its parent, or with it if `None`.
+    pub due: Option<DueOffset>,
+}
+
+impl TemplateItem {
+    /// The ID of the item, or else its summary, to name it in errors.
+    fn label(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.summary)
+    }
+}
+
+/// A todo of an expanded template, see [`Template::expand`].
+#[derive(Debug, Clone)]
+pub struct ExpandedTodo {
+    /// The index of the parent among the todos expanded, or `None` for the todo of the template.
+    pub parent: Option<usize>,
+    /// The summary, with the variables filled in.
+    pub summary: String,
+    /// The description, with the variables filled in.
+    pub description: Option<String>,
+    /// The priority, or `None` for the default of the config.
+    pub priority: Option<Priority>,
+    /// The categories.
+    pub categories: Vec<String>,
+    /// The due, if the todo of the template has one.
+    pub due: Option<LooseDateTime>,
+}
+
+impl Template {
+    /// Expands the template into its todo followed by the subtasks, each after its parent, with
+    /// the variables filled in by `vars` and the todo due at `due`.
+    ///
+    /// Business days of the offsets skip the days off and holidays of `hours`.
+    ///
+    /// # Errors
+    /// If a variable is not given a value or is not one of the template, or a due is out of range.
+    pub fn expand(
+        &self,
+        vars: &[(String, String)],
+        due: Option<LooseDateTime>,
+        hours: &WorkingHours,
+    ) -> Result<Vec<ExpandedTodo>, String> {
+        if let Some((name, _)) = vars.iter().find(|(name, _)| !self.variables.contains(name)) {
+            return Err(format!(
+                "Template '{}' has no variable '{name}'{}",
+                self.name,
+                self.declared()
+            ));
+        }
+        let values: HashMap<&str, &str> = vars
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        if let Some(name) = self
+            .variables
+            .iter()
+            .find(|name| !values.contains_key(name.as_str()))
+        {
+            return Err(format!(
+                "Template '{}' needs a value for variable '{name}'",
+                self.name
+            ));
+        }
+
+        let mut todos = Vec::with_capacity(self.items.len() + 1);
+        todos.push(ExpandedTodo {
+            parent: None,
+            summary: fill(&self.summary, &values),
+            description: self.description.as_deref().map(|d| fill(d, &values)),
+            priority: self.priority,
+            categories: self.categories.clone(),
+            due,
+        });
+        let mut indices = HashMap::new();
+        for item in &self.items {
+            // Parents come first, as checked when the template was loaded
+            let parent = item
+                .parent
+                .as_deref()
+                .and_then(|id| indices.get(id).copied())
+                .unwrap_or(0);
+            let due = match (
+                todos.get(parent).and_then(|todo| todo.due.as_ref()),
+                item.due,
+            ) {
+                (Some(due), Some(offset)) => Some(
+                    offset
+                        .apply(due, hours)
+                        .ok_or_else(|| format!("Due of '{}' is out of range", item.label()))?,
+                ),
+                (due, _) => due.cloned(),
+            };
+            if let Some(id) = &item.id {
+                indices.insert(id.as_str(), todos.len());
+            }
+            todos.push(ExpandedTodo {
+                parent: Some(parent),
+                summary: fill(&item.summary, &values),
+                description: item.description.as_deref().map(|d| fill(d, &values)),
+                priority: item.priority,
+                categories: item.categories.clone(),
+                due,
+            });
+        }
+        Ok(todos)
+    }
+
+    /// The variables of the template for errors, such as `, it has: version, codename`.
+    fn declared(&self) -> String {
+        if self.variables.is_empty() {
+            ", it has none".to_string()
+        } else {
+            format!(", it has: {}", self.variables.join(", "))
+        }
+    }
+
+    /// Checks the variables and the placeholders, and orders the items after their parents.
+    fn check(mut self) -> Result<Self, String> {
+        let mut declared = HashSet::new();
+        for name in &self.variables {
+            if !is_variable_name(name) {
+                return Err(format!(
+                    "invalid variable name '{name}', use letters, digits, `_` and `-`"
+                ));
+            }
+            if !declared.insert(name.as_str()) {
+                return Err(format!("variable '{name}' is declared twice"));
+            }
+        }
+
+        if self.summary.trim().is_empty() {
+            return Err("`summary` cannot be empty".to_string());
+        }
+        check_text("summary", &self.summary, &declared)?;
+        if let Some(description) = &self.description {
+            check_text("description", description, &declared)?;
+        }
+        for item in &self.items {
+            if item.summary.trim().is_empty() {
+                return Err(format!("item '{}' has an empty summary", item.label()));
+            }
+            let what = format!("summary of item '{}'", item.label());
+            check_text(&what, &item.summary, &declared)?;
+            if let Some(description) = &item.description {
+                let what = format!("description of item '{}'", item.label());
+                check_text(&what, description, &declared)?;
+            }
+        }
+
+        self.items = order_items(std::mem::take(&mut self.items))?;
+        Ok(self)
+    }
+}
+
+/// Orders the items so each comes after its parent, with its own subtasks right after it.
+fn order_items(items: Vec<TemplateItem>) -> Result<Vec<TemplateItem>, String> {
+    let mut ids = HashSet::new();
+    for id in items.iter().filter_map(|item| item.id.as_deref()) {
+        if !ids.insert(id) {
+            return Err(format!("item id '{id}' is used twice"));
+        }
+    }
+    for item in &items {
+        if let Some(parent) = &item.parent
+            && !ids.contains(parent.as_str())
+        {
+            return Err(format!(
+                "item '{}' has an unknown parent '{parent}'",
+                item.label()
+            ));
+        }
+    }
+
+    let mut order = Vec::with_capacity(items.len());
+    push_subtasks(&items, None, &mut order);
+    if order.len() < items.len() {
+        // Items in a cycle are out of reach of the todo of the template
+        let start = (0..items.len()).find(|i| !order.contains(i)).unwrap_or(0);
+        return Err(describe_cycle(&items, start));
+    }
+
+    let mut ranked: Vec<_> = items.into_iter().enumerate().collect();
+    ranked.sort_by_key(|(i, _)| order.iter().position(|j| j == i));
+    Ok(ranked.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Pushes the indices of the subtasks of `parent`, each followed by its own.
+fn push_subtasks(items: &[TemplateItem], parent: Option<&str>, order: &mut Vec<usize>) {
+    for (i, item) in items.iter().enumerate() {
+        if item.parent.as_deref() == parent {
+            order.push(i);
+            if let Some(id) = &item.id {
+                push_subtasks(items, Some(id), order);
+            }
+        }
+    }
+}
+
+/// Describes the cycle that the parents of the item at `start` lead into, such as
+/// `items form a cycle: a -> b -> a`.
+fn describe_cycle(items: &[TemplateItem], start: usize) -> String {
+    let parent_of = |id: &str| {
+        items
+            .iter()
+            .find(|item| item.id.as_deref() == Some(id))
+            .and_then(|item| item.parent.as_deref())
+    };
+    let mut chain: Vec<&str> = Vec::new();
+    let mut next = items.get(start).and_then(|item| item.parent.as_deref());
+    while let Some(id) = next {
+        if let Some(pos) = chain.iter().position(|seen| *seen == id) {
+            let mut cycle = chain.split_off(pos);
+            cycle.push(id);
+            return format!("items form a cycle: {}", cycle.join(" -> "));
+        }
+        chain.push(id);
+        next = parent_of(id);
+    }
+    "items form a cycle".to_string()
+}
+
+/// Checks that the placeholders of `text` are well-formed and name declared variables.
+fn check_text(what: &str, text: &str, declared: &HashSet<&str>) -> Result<(), String> {
+    let pieces = parse_text(text).map_err(|e| format!("{what} {e}"))?;
+    for piece in pieces {
+        if let Piece::Var(name) = piece
+            && !declared.contains(name)
+        {
+            return Err(format!(
+                "{what} uses the undeclared variable '{name}', add it to `variables`"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fills the placeholders of `text` with their values.
+fn fill(text: &str, values: &HashMap<&str, &str>) -> String {
+    // Checked when the template was loaded
+    let Ok(pieces) = parse_text(text) else {
+        return text.to_string();
+    };
+    pieces
+        .into_iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => text,
+            Piece::Var(name) => values.get(name).copied().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+/// A part of a text with placeholders.
+#[derive(Debug, PartialEq, Eq)]
+enum Piece<'a> {
+    Text(String),
+    Var(&'a str),
+}
+
+/// Splits `text` into literal text and the names of its `{name}` placeholders.
+fn parse_text(text: &str) -> Result<Vec<Piece<'_>>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if chars.next_if(|&(_, c)| c == '{').is_some() => literal.push('{'),
+            '}' if chars.next_if(|&(_, c)| c == '}').is_some() => literal.push('}'),
+            '{' => {
+                let rest = &text[i + 1..];
+                let Some(end) = rest.find('}') else {
+                    return Err("has an unclosed `{`, write `{{` for a brace".to_string());
+                };
+                let name = &rest[..end];
+                if !is_variable_name(name) {
+                    return Err(format!(
+                        "has an invalid placeholder `{{{name}}}`, write `{{{{` for a brace"
+                    ));
+                }
+                // Names are ASCII, so skip them and the closing brace by bytes
+                for _ in 0..=name.len() {
+                    chars.next();
+                }
+                if !literal.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut literal)));
+                }
+                pieces.push(Piece::Var(name));
+            }
+            '}' => return Err("has an unmatched `}`, write `}}` for a brace".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Text(literal));
+    }
+    Ok(pieces)
+}
+
+/// The priority of a todo in a template file, `None` if it has none.
+fn priority(todo: &impl Todo) -> Option<Priority> {
+    Some(todo.priority()).filter(|p| *p != Priority::None)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// When a subtask of a template is due, from the due of its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueOffset {
+    /// Days and seconds after the due of the parent, or before it if negative.
+    Span {
+        /// Whole days, keeping the time of day.
+        days: i64,
+        /// Seconds, which move a due without a time of day by whole days only.
+        seconds: i64,
+    },
+    /// Business days after the due of the parent, or before it if negative, keeping the time of
+    /// day, see [`add_business_days`].
+    BusinessDays(i64),
+}
+
+impl DueOffset {
+    /// The due at this offset from `due`, with business days by `hours`.
+    ///
+    /// Returns `None` if the due is out of range, or there is no business day within a year.
+    #[must_use]
+    pub fn apply(self, due: &LooseDateTime, hours: &WorkingHours) -> Option<LooseDateTime> {
+        let span = match self {
+            DueOffset::Span { days, seconds } => match due {
+                LooseDateTime::DateOnly(_) => {
+                    Span::new().try_days(days.checked_add(seconds / SECONDS_PER_DAY)?)
+                }
+                _ => Span::new()
+                    .try_days(days)
+                    .and_then(|span| span.try_seconds(seconds)),
+            }
+            .ok()?,
+            DueOffset::BusinessDays(n) => {
+                let date = due.date();
+                add_business_days(date, n, hours)?.since(date).ok()?
+            }
+        };
+        match due {
+            LooseDateTime::DateOnly(d) => d.checked_add(span).ok().map(LooseDateTime::DateOnly),
+            LooseDateTime::Floating(dt) => dt.checked_add(span).ok().map(LooseDateTime::Floating),
+            LooseDateTime::Local(zoned) => zoned.checked_add(span).ok().map(LooseDateTime::Local),
+        }
+    }
+}
+
+impl FromStr for DueOffset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+        let (sign, unsigned) = match t.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, t.strip_prefix('+').unwrap_or(t)),
+        };
+        if let Some((days, seconds)) = parse_duration(unsigned) {
+            return Ok(DueOffset::Span {
+                days: sign * days,
+                seconds: sign * seconds,
+            });
+        }
+        match unsigned.parse() {
+            Ok(DateTimeAnchor::InBusinessDays(n)) if n >= 0 => {
+                Ok(DueOffset::BusinessDays(sign * n))
+            }
+            _ => Err(format!(
+                "Invalid due offset '{s}', expected e.g. +2d, -1bd, 4h or 1w"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DueOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DueOffset::Span { days, seconds: 0 } => write!(f, "{days:+}d"),
+            DueOffset::Span { days, seconds } if seconds % 3600 == 0 => {
+                write!(f, "{:+}h", days * 24 + seconds / 3600)
+            }
+            DueOffset::Span { days, seconds } if seconds % 60 == 0 => {
+                write!(f, "{:+}m", days * 24 * 60 + seconds / 60)
+            }
+            DueOffset::Span { days, seconds } => {
+                write!(f, "{:+}s", days * SECONDS_PER_DAY + seconds)
+            }
+            DueOffset::BusinessDays(n) => write!(f, "{n:+}bd"),
+        }
+    }
+}
+
+/// A template as written in the config.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TemplateDef {
+    file: Option<PathBuf>,
+    variables: Vec<String>,
+    due: Option<DateTimeAnchor>,
+    summary: Option<String>,
+    description: Option<String>,
+    priority: Option<Priority>,
+    categories: Vec<String>,
+    items: Vec<TemplateItemDef>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateItemDef {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    parent: Option<String>,
+    summary: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    priority: Option<Priority>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    due: Option<String>,
+}
+
+impl TemplateDef {
+    fn compile(self, name: &str) -> Result<Template, String> {
+        let Some(summary) = self.summary else {
+            return Err("`summary` or `file` is required".to_string());
+        };
+        let items = self
+            .items
+            .into_iter()
+            .map(|def| {
+                let label = def.id.as_ref().unwrap_or(&def.summary);
+                let due = def
+                    .due
+                    .as_deref()
+                    .map(str::parse)
+                    .transpose()
+                    .map_err(|e| format!("item '{label}': {e}"))?;
+                Ok(TemplateItem {
+                    id: def.id,
+                    parent: def.parent,
+                    summary: def.summary,
+                    description: def.description,
+                    priority: def.priority,
+                    categories: def.categories,
+                    due,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Template {
+            name: name.to_string(),
+            variables: self.variables,
+            due: self.due,
+            summary,
+            description: self.description,
+            priority: self.priority,
+            categories: self.categories,
+            items,
+        }
+        .check()
+    }
+
+    fn into_file(self, name: &str, path: PathBuf) -> Result<TemplateFile, String> {
+        let misplaced = [
+            ("summary", self.summary.is_some()),
+            ("description", self.description.is_some()),
+            ("priority", self.priority.is_some()),
+            ("categories", !self.categories.is_empty()),
+            ("items", !self.items.is_empty()),
+        ];
+        if let Some((field, _)) = misplaced.iter().find(|(_, set)| *set) {
+            return Err(format!("`{field}` is read from the file"));
+        }
+        Ok(TemplateFile {
+            name: name.to_string(),
+            path,
+            variables: self.variables,
+            due: self.due,
+        })
+    }
+}
+
+/// A template kept in an `.ics` file, until the file is read.
+#[derive(Debug, Clone)]
+struct TemplateFile {
+    name: String,
+    path: PathBuf,
+    variables: Vec<String>,
+    due: Option<DateTimeAnchor>,
+}
+
+impl TemplateFile {
+    /// Reads the template from the content of the file: the one todo without a parent, and the
+    /// others as its subtasks, by their UIDs.
+    fn parse(self, content: &str) -> Result<Template, String> {
+        let calendars = aimcal_ical::parse(content).map_err(|errors| {
+            let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            format!(
+                "failed to parse {}: {}",
+                self.path.display(),
+                errors.join("; ")
+            )
+        })?;
+        let todos: Vec<_> = calendars
+            .into_iter()
+            .flat_map(|calendar| calendar.to_owned().components)
+            .filter_map(|component| match component {
+                CalendarComponent::Todo(todo) => Some(todo),
+                _ => None,
+            })
+            .collect();
+
+        let uids: HashSet<_> = todos.iter().map(|todo| todo.uid().into_owned()).collect();
+        let (roots, subtasks): (Vec<_>, Vec<_>) = todos
+            .into_iter()
+            .partition(|todo| todo.parent_uid().is_none_or(|p| !uids.contains(&*p)));
+        let [root] = roots.as_slice() else {
+            return Err(format!(
+                "the file must hold one todo without a parent, it has {}",
+                roots.len()
+            ));
+        };
+        let root_uid = root.uid();
+
+        let items = subtasks
+            .iter()
+            .map(|todo| {
+                let uid = todo.uid().into_owned();
+                let due = todo
+                    .x_properties
+                    .iter()
+                    .find(|p| p.name.eq_ignore_ascii_case(X_AIM_DUE_OFFSET))
+                    .and_then(x_text)
+                    .map(|offset| offset.parse())
+                    .transpose()
+                    .map_err(|e| format!("item '{uid}': {e}"))?;
+                Ok(TemplateItem {
+                    parent: todo.parent_uid().filter(|p| *p != root_uid).map(Into::into),
+                    summary: todo.summary().into_owned(),
+                    description: todo.description().map(Into::into),
+                    priority: priority(todo),
+                    categories: todo.categories(),
+                    due,
+                    id: Some(uid),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Template {
+            name: self.name,
+            variables: self.variables,
+            due: self.due,
+            summary: root.summary().into_owned(),
+            description: root.description().map(Into::into),
+            priority: priority(root),
+            categories: root.categories(),
+            items,
+        }
+        .check()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, datetime};
+
+    use super::*;
+
+    fn parse(toml: &str) -> Result<Templates, String> {
+        toml::from_str::<BTreeMap<String, TemplateDef>>(toml)
+            .map_err(|e| e.to_string())
+            .and_then(Templates::try_from)
+    }
+
+    const RELEASE: &str = r#"
+[release]
+summary = "Release {version}"
+description = "Ship {version} to {{everyone}}"
+variables = ["version"]
+due = "7d"
+
+[[release.items]]
+id = "announce"
+parent = "tag"
+summary = "Announce {version}"
+due = "+2d"
+
+[[release.items]]
+id = "tag"
+summary = "Tag v{version}"
+due = "-1bd"
+
+[[release.items]]
+summary = "Update the changelog"
+"#;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn expands_items_after_their_parents() {
+        let templates = parse(RELEASE).unwrap();
+        let release = templates.get("release").unwrap();
+        assert_eq!(release.due, Some(DateTimeAnchor::InDays(7)));
+
+        // A Monday, so a business day back is the Friday before
+        let due = LooseDateTime::from_local_datetime(datetime(2026, 3, 9, 17, 0, 0, 0));
+        let todos = release
+            .expand(
+                &vars(&[("version", "1.4")]),
+                Some(due),
+                &WorkingHours::default(),
+            )
+            .unwrap();
+        let summaries: Vec<_> = todos.iter().map(|t| t.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            [
+                "Release 1.4",
+                "Tag v1.4",
+                "Announce 1.4",
+                "Update the changelog"
+            ]
+        );
+        assert_eq!(
+            todos[0].description.as_deref(),
+            Some("Ship 1.4 to {everyone}")
+        );
+        let parents: Vec<_> = todos.iter().map(|t| t.parent).collect();
+        assert_eq!(parents, [None, Some(0), Some(1), Some(0)]);
+
+        let dues: Vec<_> = todos.iter().map(|t| t.due.clone().unwrap()).collect();
+        assert_eq!(
+            dues[1],
+            LooseDateTime::from_local_datetime(datetime(2026, 3, 6, 17, 0, 0, 0))
+        );
+        assert_eq!(
+            dues[2],
+            LooseDateTime::from_local_datetime(datetime(2026, 3, 8, 17, 0, 0, 0))
+        );
+        assert_eq!(dues[3], dues[0]);
+
+        let undated = release
+            .expand(&vars(&[("version", "1.4")]), None, &WorkingHours::default())
+            .unwrap();
+        assert!(undated.iter().all(|t| t.due.is_none()));
+    }
+
+    #[test]
+    fn checks_the_variables_given() {
+        let templates = parse(RELEASE).unwrap();
+        let release = templates.get("release").unwrap();
+        let hours = WorkingHours::default();
+
+        let err = release.expand(&[], None, &hours).unwrap_err();
+        assert_eq!(
+            err,
+            "Template 'release' needs a value for variable 'version'"
+        );
+
+        let given = vars(&[("version", "1.4"), ("codename", "owl")]);
+        let err = release.expand(&given, None, &hours).unwrap_err();
+        assert_eq!(
+            err,
+            "Template 'release' has no variable 'codename', it has: version"
+        );
+    }
+
+    #[test]
+    fn rejects_templates_that_would_not_expand() {
+        let item = |fields: &str| format!("[t]\nsummary = \"T\"\n[[t.items]]\n{fields}\n");
+        let cases = [
+            (
+                "[t]\nvariables = [\"v\"]".to_string(),
+                "`summary` or `file` is required",
+            ),
+            (
+                "[t]\nsummary = \"Hi {name}\"".to_string(),
+                "summary uses the undeclared variable 'name', add it to `variables`",
+            ),
+            (
+                "[t]\nsummary = \"Hi {name\"".to_string(),
+                "summary has an unclosed `{`",
+            ),
+            (
+                "[t]\nsummary = \"Hi name}\"".to_string(),
+                "summary has an unmatched `}`",
+            ),
+            (
+                "[t]\nsummary = \"Hi { }\"".to_string(),
+                "summary has an invalid placeholder `{ }`",
+            ),
+            (
+                "[t]\nsummary = \"T\"\nvariables = [\"a b\"]".to_string(),
+                "invalid variable name 'a b'",
+            ),
+            (
+                "[t]\nsummary = \"T\"\nvariables = [\"v\", \"v\"]".to_string(),
+                "variable 'v' is declared twice",
+            ),
+            (
+                "[t]\nfile = \"t.ics\"\nsummary = \"T\"".to_string(),
+                "`summary` is read from the file",
+            ),
+            (
+                item("summary = \"{who}\""),
+                "summary of item '{who}' uses the undeclared variable 'who'",
+            ),
+            (
+                item("id = \"a\"\nsummary = \"A\"\ndue = \"tomorrow\""),
+                "item 'a': Invalid due offset 'tomorrow', expected e.g. +2d, -1bd, 4h or 1w",
+            ),
+            (
+                item("id = \"a\"\nparent = \"b\"\nsummary = \"A\""),
+                "item 'a' has an unknown parent 'b'",
+            ),
+            (
+                format!(
+                    "{}[[t.items]]\nid = \"a\"\nsummary = \"B\"\n",
+                    item("id = \"a\"\nsummary = \"A\"")
+                ),
+                "item id 'a' is used twice",
+            ),
+            (
+                format!(
+                    "{}[[t.items]]\nid = \"b\"\nparent = \"a\"\nsummary = \"B\"\n",
+                    item("id = \"a\"\nparent = \"b\"\nsummary = \"A\"")
+                ),
+                "items form a cycle: b -> a -> b",
+            ),
+            (
+                item("id = \"a\"\nparent = \"a\"\nsummary = \"A\""),
+                "items form a cycle: a -> a",
+            ),
+        ];
+        for (toml, expected) in cases {
+            let err = parse(&toml).unwrap_err();
+            assert!(err.starts_with("Invalid template 't': "), "{err}");
+            assert!(err.contains(expected), "{toml}: {err}");
+        }
+    }
+
+    #[test]
+    fn parses_due_offsets() {
+        let cases = [
+            (
+                "2d",
+                DueOffset::Span {
+                    days: 2,
+                    seconds: 0,
+                },
+                "+2d",
+            ),
+            (
+                "+1w",
+                DueOffset::Span {
+                    days: 7,
+                    seconds: 0,
+                },
+                "+7d",
+            ),
+            (
+                "-3h",
+                DueOffset::Span {
+                    days: 0,
+                    seconds: -3 * 3600,
+                },
+                "-3h",
+            ),
+            (
+                "30m",
+                DueOffset::Span {
+                    days: 0,
+                    seconds: 1800,
+                },
+                "+30m",
+            ),
+            ("-1bd", DueOffset::BusinessDays(-1), "-1bd"),
+            ("+2 business days", DueOffset::BusinessDays(2), "+2bd"),
+        ];
+        for (s, offset, display) in cases {
+            assert_eq!(s.parse::<DueOffset>().unwrap(), offset, "{s}");
+            assert_eq!(offset.to_string(), display);
+        }
+        for s in ["", "soon", "2026-03-09", "friday", "--1d", "+-1bd"] {
+            assert!(s.parse::<DueOffset>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn applies_hours_to_dates_by_whole_days() {
+        let hours = WorkingHours::default();
+        let day = LooseDateTime::DateOnly(date(2026, 3, 9));
+        let offset = DueOffset::Span {
+            days: 0,
+            seconds: -26 * 3600,
+        };
+        assert_eq!(
+            offset.apply(&day, &hours),
+            Some(LooseDateTime::DateOnly(date(2026, 3, 8)))
+        );
+        let offset = DueOffset::Span {
+            days: 0,
+            seconds: 4 * 3600,
+        };
+        assert_eq!(offset.apply(&day, &hours), Some(day.clone()));
+        assert_eq!(
+            DueOffset::BusinessDays(1).apply(&day, &hours),
+            Some(LooseDateTime::DateOnly(date(2026, 3, 10)))
+        );
+    }
+
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn reads_templates_from_ics_files() {
+        let content = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//aim//template//EN\r
+BEGIN:VTODO\r
+UID:trip\r
+DTSTAMP:20260101T000000Z\r
+SUMMARY:Trip to {place}\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:pack\r
+DTSTAMP:20260101T000000Z\r
+SUMMARY:Pack for {place}\r
+RELATED-TO;RELTYPE=PARENT:trip\r
+X-AIM-DUE-OFFSET:-1d\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:socks\r
+DTSTAMP:20260101T000000Z\r
+SUMMARY:Socks\r
+PRIORITY:1\r
+RELATED-TO;RELTYPE=PARENT:pack\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+        let mut templates =
+            parse("[trip]\nfile = \"trip.ics\"\nvariables = [\"place\"]\n").unwrap();
+        assert!(templates.get("trip").is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("trip.ics"), content).unwrap();
+        templates
+            .load_files(|path| Ok(dir.path().join(path)))
+            .unwrap();
+        let trip = templates.get("trip").unwrap();
+        assert_eq!(trip.summary, "Trip to {place}");
+        let items: Vec<_> = trip
+            .items
+            .iter()
+            .map(|item| (item.id.as_deref(), item.parent.as_deref(), item.due))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                (
+                    Some("pack"),
+                    None,
+                    Some(DueOffset::Span {
+                        days: -1,
+                        seconds: 0
+                    })
+                ),
+                (Some("socks"), Some("pack"), None),
+            ]
+        );
+        assert_eq!(trip.items[1].priority, Some(Priority::P1));
+
+        let mut broken = parse("[trip]\nfile = \"trip.ics\"\n").unwrap();
+        let err = broken
+            .load_files(|path| Ok(dir.path().join(path)))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Invalid configuration: Invalid template 'trip': summary uses the undeclared variable \
+             'place', add it to `variables`"
+        );
+    }
+}
@@ -5,12 +5,41 @@
 use std::{borrow::Cow, fmt::Display, num::NonZeroU32, str::FromStr};
 
 use aimcal_ical::{
-    self as ical, Completed, Description, DtStamp, Due, PercentComplete, Summary, TodoStatusValue,
-    Uid, VTodo,
+    self as ical, AlarmTriggerRelationship, Categories, Color, Completed, Description, DtStamp,
+    Due, PercentComplete, Property, RelatedTo, RelationshipType, StringStorage, Summary,
+    TodoStatusValue, Uid, VTodo, Value, ValueDuration, ValueText, XNameProperty,
 };
-use jiff::Zoned;
+use jiff::{SignedDuration, Zoned};
 
-use crate::{Config, DateTimeAnchor, LooseDateTime, Priority, SortOrder};
+use crate::alarm::{follow_up_alarm, is_follow_up_alarm, replace_display_alarms};
+use crate::crypt::{read_sealed, write_sealed};
+use crate::datetime::parse_signed_duration;
+use crate::import::signed_duration;
+use crate::meta::{read_meta, resolve_meta_conditions, write_meta};
+use crate::series::{roll_forward, shift_of, shifted};
+use crate::{AlarmTrigger, Collation, Config, DateTimeAnchor, LooseDateTime, Priority, SortOrder};
+
+/// Property recording the due date a todo had before it was snoozed.
+const X_AIM_SNOOZED_FROM: &str = "X-AIM-SNOOZED-FROM";
+
+/// Property marking a todo captured into the inbox, until it is triaged.
+pub(crate) const X_AIM_INBOX: &str = "X-AIM-INBOX";
+
+/// Property naming who a todo handed off is waiting on.
+pub(crate) const X_AIM_WAITING_ON: &str = "X-AIM-WAITING-ON";
+
+/// Property recording when to follow up on a todo waiting on someone.
+pub(crate) const X_AIM_FOLLOW_UP: &str = "X-AIM-FOLLOW-UP";
+
+/// Property holding how long a todo is estimated to take, as an ISO 8601 duration.
+pub(crate) const X_AIM_ESTIMATE: &str = "X-AIM-ESTIMATE";
+
+/// Property choosing where a recurring todo advances from once completed, see [`RecurFrom`].
+const X_AIM_RECUR_FROM: &str = "X-AIM-RECUR-FROM";
+
+/// Property recording the completion time a todo had before it was last completed, restored when
+/// the completion is undone.
+const X_AIM_COMPLETED_BEFORE: &str = "X-AIM-COMPLETED-BEFORE";
 
 /// Trait representing a todo item.
 pub trait Todo {
@@ -30,6 +59,11 @@ pub trait Todo {
     /// The description of the todo item, if available.
     fn description(&self) -> Option<Cow<'_, str>>;
 
+    /// The date and time the todo item is scheduled to start, if available.
+    fn start(&self) -> Option<LooseDateTime> {
+        None
+    }
+
     /// The due date and time of the todo item, if available.
     fn due(&self) -> Option<LooseDateTime>;
 
@@ -44,25 +78,111 @@ pub trait Todo {
 
     /// The summary of the todo item.
     fn summary(&self) -> Cow<'_, str>;
+
+    /// The due dates this todo was snoozed from, oldest first.
+    fn snoozed_from(&self) -> Vec<LooseDateTime> {
+        Vec::new()
+    }
+
+    /// Where the todo item takes place, if available.
+    fn location(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The address of the organizer, such as `mailto:alice@example.com`, if available.
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The URL of the todo item, such as a ticket or a document, if available.
+    fn url(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The categories of the todo item. Todos read from the cache include the ones added by the
+    /// [`Rules`](crate::Rules).
+    fn categories(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The color tag set by the [`Rules`](crate::Rules), if any.
+    fn color(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The custom metadata of the todo item as key-value pairs, sorted by key.
+    fn meta(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// The UID of the parent todo, if this todo is a subtask.
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The sealed description of the todo item, if it is encrypted.
+    ///
+    /// Todos read through [`crate::Aim`] show the opened description as their description.
+    fn encrypted_description(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// Whether the todo was captured into the inbox, see [`crate::Aim::capture_todo`], and not
+    /// triaged yet by giving it a due, a priority or a category.
+    fn in_inbox(&self) -> bool {
+        false
+    }
+
+    /// Who the todo is waiting on, if it was handed off, see [`crate::Aim::wait_todo`].
+    ///
+    /// The status of a waiting todo stays as it was, so other clients see it as usual.
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// When to follow up on a todo waiting on someone, after which it is listed again.
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        None
+    }
+
+    /// How long the todo is estimated to take, if available, see
+    /// [`crate::Aim::plan_todos`].
+    fn estimate(&self) -> Option<SignedDuration> {
+        None
+    }
+
+    /// The ID of the calendar the todo belongs to, if known.
+    fn calendar_id(&self) -> Option<Cow<'_, str>> {
+        None
+    }
 }
 
-impl Todo for VTodo<String> {
+impl<S: StringStorage> Todo for VTodo<S> {
     fn uid(&self) -> Cow<'_, str> {
-        self.uid.content.to_string().into()
+        self.uid.content.as_str_lossy()
     }
 
     fn completed(&self) -> Option<Zoned> {
-        self.completed.as_ref().map(|c| c.zoned())
+        self.completed.as_ref().and_then(|c| c.zoned())
     }
 
     fn description(&self) -> Option<Cow<'_, str>> {
-        self.description
-            .as_ref()
-            .map(|a| a.content.to_string().into()) // PERF: avoid allocation
+        self.description.as_ref().map(|a| a.content.as_str_lossy())
+    }
+
+    fn start(&self) -> Option<LooseDateTime> {
+        self.dt_start.as_ref().map(|d| d.0.clone().into())
     }
 
     fn due(&self) -> Option<LooseDateTime> {
-        self.due.as_ref().map(|d| d.0.clone().into())
+        match (&self.due, &self.dt_start, &self.duration) {
+            (Some(due), _, _) => Some(due.0.clone().into()),
+            // Without a due, a todo is due its duration after the start, see RFC 5545 3.8.2.5
+            (None, Some(start), Some(duration)) => {
+                Some(shifted(start.0.clone(), signed_duration(&duration.value)))
+            }
+            _ => None,
+        }
     }
 
     fn percent_complete(&self) -> Option<u8> {
@@ -86,49 +206,220 @@ impl Todo for VTodo<String> {
     fn summary(&self) -> Cow<'_, str> {
         self.summary
             .as_ref()
-            .map_or_else(|| "".into(), |s| s.content.to_string().into()) // PERF: avoid allocation
+            .map_or_else(|| "".into(), |s| s.content.as_str_lossy())
+    }
+
+    fn snoozed_from(&self) -> Vec<LooseDateTime> {
+        self.x_properties
+            .iter()
+            .filter(|p| {
+                p.name
+                    .as_str_lossy()
+                    .eq_ignore_ascii_case(X_AIM_SNOOZED_FROM)
+            })
+            .filter_map(x_text)
+            .filter_map(|v| LooseDateTime::parse_stable(&v))
+            .collect()
+    }
+
+    fn location(&self) -> Option<Cow<'_, str>> {
+        self.location.as_ref().map(|l| l.content.as_str_lossy())
+    }
+
+    fn organizer(&self) -> Option<Cow<'_, str>> {
+        self.organizer
+            .as_ref()
+            .map(|o| o.cal_address.as_str_lossy())
+    }
+
+    fn url(&self) -> Option<Cow<'_, str>> {
+        self.url.as_ref().map(|u| u.uri.as_str_lossy())
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .flat_map(|c| c.values.iter().map(ToString::to_string))
+            .collect()
+    }
+
+    fn meta(&self) -> Vec<(String, String)> {
+        read_meta(&self.x_properties)
+    }
+
+    fn parent_uid(&self) -> Option<Cow<'_, str>> {
+        self.retained_properties.iter().find_map(|p| match p {
+            Property::RelatedTo(r) if matches!(r.reltype, RelationshipType::Parent) => {
+                Some(r.content.as_str_lossy())
+            }
+            _ => None,
+        })
+    }
+
+    fn encrypted_description(&self) -> Option<Cow<'_, str>> {
+        read_sealed(&self.x_properties).map(Into::into)
+    }
+
+    // Triaged by another client if it got a due, a priority or a category but kept the marker
+    fn in_inbox(&self) -> bool {
+        self.x_properties
+            .iter()
+            .any(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_INBOX))
+            && self.due.is_none()
+            && Todo::priority(self) == Priority::None
+            && self.categories.is_empty()
+    }
+
+    fn waiting_on(&self) -> Option<Cow<'_, str>> {
+        self.x_properties
+            .iter()
+            .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_WAITING_ON))
+            .filter_map(x_text)
+            .find(|v| !v.trim().is_empty())
+            .map(Into::into)
+    }
+
+    // Only meaningful while waiting, a leftover follow-up of another client is ignored
+    fn follow_up(&self) -> Option<LooseDateTime> {
+        Todo::waiting_on(self)?;
+        self.x_properties
+            .iter()
+            .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_FOLLOW_UP))
+            .filter_map(x_text)
+            .find_map(|v| LooseDateTime::parse_stable(&v))
+    }
+
+    fn estimate(&self) -> Option<SignedDuration> {
+        self.x_properties
+            .iter()
+            .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_ESTIMATE))
+            .filter_map(x_text)
+            .filter_map(|v| parse_signed_duration(&v).ok())
+            .find(|e| *e > SignedDuration::ZERO)
+    }
+}
+
+/// The text value of an x-property.
+pub(crate) fn x_text<S: StringStorage>(prop: &XNameProperty<S>) -> Option<String> {
+    match &prop.value {
+        Value::Text { values, .. } => values.first().map(ToString::to_string),
+        // Without a VALUE parameter, x-name values are parsed as raw strings
+        Value::XName { raw, .. } | Value::Unrecognized { raw, .. } => {
+            Some(raw.as_str_lossy().into_owned())
+        }
+        _ => None,
     }
 }
 
+/// An x-property with a single text value.
+pub(crate) fn x_text_property(name: &str, value: String) -> XNameProperty<String> {
+    XNameProperty {
+        name: name.to_string(),
+        parameters: Vec::new(),
+        value: Value::Text {
+            values: vec![ValueText::new(value)],
+            span: (),
+        },
+        span: (),
+    }
+}
+
+/// Marks a todo as captured into the inbox.
+pub(crate) fn mark_inbox(todo: &mut VTodo<String>) {
+    todo.x_properties
+        .push(x_text_property(X_AIM_INBOX, "TRUE".to_string()));
+}
+
+/// Who a todo is waiting on and when to follow up, see [`Todo::waiting_on`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Waiting {
+    /// Who the todo is waiting on, such as the person it was handed off to.
+    pub on: String,
+    /// When to follow up, after which the todo is listed again. `None` waits until cleared.
+    pub follow_up: Option<LooseDateTime>,
+    /// Whether to add a reminder going off at the follow-up.
+    pub notify: bool,
+}
+
+/// How many of the direct subtasks of a todo are done, leaving out cancelled ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubtaskProgress {
+    /// The number of completed subtasks.
+    pub done: u32,
+    /// The number of subtasks that are not cancelled.
+    pub total: u32,
+}
+
 /// Darft for a todo item, used for creating new todos.
 #[derive(Debug)]
 pub struct TodoDraft {
+    /// The reminder before the due, or `None` for the default of the config.
+    /// `Some(None)` creates the todo without a reminder.
+    pub alarm: Option<Option<AlarmTrigger>>,
     /// The calendar ID to create the todo in. Uses default calendar if None.
     pub calendar_id: Option<String>,
+    /// The categories of the todo item.
+    pub categories: Vec<String>,
     /// The description of the todo item, if available.
     pub description: Option<String>,
+    /// Whether to encrypt the description, so it is never stored in plain text in the calendar.
+    pub encrypt_description: bool,
     /// The due date and time of the todo item, if available.
     pub due: Option<LooseDateTime>,
+    /// How long the todo item is estimated to take, if available.
+    pub estimate: Option<SignedDuration>,
+    /// The UID of the parent todo, making this todo a subtask, see [`Todo::parent_uid`].
+    pub parent: Option<String>,
     /// The percent complete, from 0 to 100, if available.
     pub percent_complete: Option<u8>,
-    /// The priority of the todo item, if available.
+    /// The priority of the todo item, or `None` for the default of its categories or the config.
     pub priority: Option<Priority>,
     /// The status of the todo item.
     pub status: TodoStatus,
     /// The summary of the todo item.
     pub summary: String,
+    /// Where the todo advances from once completed if it recurs, or `None` for the default of
+    /// the config when it is completed.
+    pub recur_from: Option<RecurFrom>,
+    /// Whether to leave out the defaults of the categories, see
+    /// [`CategoryDefaults`](crate::CategoryDefaults).
+    pub no_defaults: bool,
 }
 
 impl TodoDraft {
     /// Creates a new empty patch.
     pub(crate) fn default(config: &Config, now: &Zoned) -> Result<Self, String> {
         Ok(Self {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             description: None,
+            encrypt_description: false,
             due: config
                 .default_due
                 .as_ref()
                 .map(|d| d.clone().resolve_since_zoned(now))
                 .transpose()?,
+            estimate: None,
+            parent: None,
             percent_complete: None,
-            priority: Some(config.default_priority),
+            priority: None,
             status: TodoStatus::default(),
             summary: String::default(),
+            recur_from: None,
+            no_defaults: false,
         })
     }
 
     /// Converts the draft into a icalendar Todo component.
-    pub(crate) fn resolve<'a>(&'a self, config: &Config, now: &'a Zoned) -> ResolvedTodoDraft<'a> {
+    ///
+    /// Fields left unset take the defaults of the categories of the draft, the first of them
+    /// setting one winning, unless the draft asks for none, and then the defaults of the config.
+    pub(crate) fn resolve<'a>(
+        &'a self,
+        config: &'a Config,
+        now: &'a Zoned,
+    ) -> ResolvedTodoDraft<'a> {
         let due = self.due.clone().or_else(|| {
             config
                 .default_due
@@ -137,17 +428,44 @@ impl TodoDraft {
                 .and_then(Result::ok)
         });
 
-        let percent_complete = self.percent_complete.map(|a| a.max(100));
+        let mut percent_complete = self.percent_complete.map(|a| a.min(100));
+        let mut status = self.status;
+        match (status, percent_complete) {
+            // Needs-action is the default, so a percent complete takes precedence
+            (TodoStatus::NeedsAction, Some(percent)) => {
+                status = TodoStatus::for_percent_complete(percent);
+            }
+            (TodoStatus::Completed, None) => percent_complete = Some(100),
+            _ => {}
+        }
+
+        let defaults = (!self.no_defaults).then_some(&config.categories);
+        let priority = self
+            .priority
+            .or_else(|| defaults?.first(&self.categories, |c| c.priority))
+            .or(Some(config.default_priority));
 
-        let priority = self.priority.or(Some(config.default_priority));
+        // A reminder needs a due to go off before
+        let alarm = self
+            .alarm
+            .or_else(|| defaults?.first(&self.categories, |c| c.alarm))
+            .unwrap_or(config.default_todo_alarm);
+        let alarm = due.as_ref().and(alarm);
+        let color = defaults.and_then(|d| d.first(&self.categories, |c| c.color.as_deref()));
 
         ResolvedTodoDraft {
+            alarm,
+            categories: &self.categories,
+            color,
             description: self.description.as_deref(),
             due,
+            estimate: self.estimate,
+            parent: self.parent.as_deref(),
             percent_complete,
             priority,
-            status: self.status,
+            status,
             summary: &self.summary,
+            recur_from: self.recur_from,
 
             now,
         }
@@ -156,12 +474,18 @@ impl TodoDraft {
 
 #[derive(Debug, Clone)]
 pub struct ResolvedTodoDraft<'a> {
+    pub alarm: Option<AlarmTrigger>,
+    pub categories: &'a [String],
+    pub color: Option<&'a str>,
     pub description: Option<&'a str>,
     pub due: Option<LooseDateTime>,
+    pub estimate: Option<SignedDuration>,
+    pub parent: Option<&'a str>,
     pub percent_complete: Option<u8>,
     pub priority: Option<Priority>,
     pub status: TodoStatus,
     pub summary: &'a str,
+    pub recur_from: Option<RecurFrom>,
 
     pub now: &'a Zoned,
 }
@@ -172,12 +496,35 @@ impl ResolvedTodoDraft<'_> {
         // Convert to UTC for DTSTAMP (required by RFC 5545)
         let utc_now = self.now.with_time_zone(jiff::tz::TimeZone::UTC);
         let dt_stamp = DtStamp::new(utc_now.datetime());
+        let completed =
+            (self.status == TodoStatus::Completed).then(|| Completed::new(utc_now.datetime()));
+        let alarms = self
+            .alarm
+            .map(|a| a.to_display_alarm(Some(AlarmTriggerRelationship::End), self.summary))
+            .into_iter()
+            .collect();
+        let categories = if self.categories.is_empty() {
+            Vec::new()
+        } else {
+            vec![Categories {
+                values: self
+                    .categories
+                    .iter()
+                    .cloned()
+                    .map(ValueText::new)
+                    .collect(),
+                language: None,
+                x_parameters: Vec::new(),
+                retained_parameters: Vec::new(),
+                span: (),
+            }]
+        };
         VTodo {
             uid: Uid::new(uid.to_string()),
             dt_stamp,
             dt_start: None,
             due: self.due.map(Due::new),
-            completed: None,
+            completed,
             duration: None,
             summary: Some(Summary::new(self.summary.to_string())),
             description: self.description.map(|d| Description::new(d.to_string())),
@@ -196,14 +543,40 @@ impl ResolvedTodoDraft<'_> {
             last_modified: None,
             sequence: None,
             classification: None,
-            resources: None,
-            categories: None,
+            resources: Vec::new(),
+            categories,
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            contacts: Vec::new(),
+            color: self.color.map(|c| Color::new(c.to_string())),
+            images: Vec::new(),
+            conferences: Vec::new(),
             rrule: None,
             rdates: Vec::new(),
             ex_dates: Vec::new(),
-            x_properties: Vec::new(),
-            retained_properties: Vec::new(),
-            alarms: Vec::new(),
+            x_properties: self
+                .estimate
+                .map(|e| x_text_property(X_AIM_ESTIMATE, e.to_string()))
+                .into_iter()
+                .chain(
+                    self.recur_from
+                        .map(|r| x_text_property(X_AIM_RECUR_FROM, r.as_ref().to_string())),
+                )
+                .collect(),
+            retained_properties: self
+                .parent
+                .map(|uid| {
+                    Property::RelatedTo(RelatedTo {
+                        content: ValueText::new(uid.to_string()),
+                        reltype: RelationshipType::Parent,
+                        x_parameters: Vec::new(),
+                        retained_parameters: Vec::new(),
+                        span: (),
+                    })
+                })
+                .into_iter()
+                .collect(),
+            alarms,
         }
     }
 }
@@ -211,10 +584,16 @@ impl ResolvedTodoDraft<'_> {
 /// Patch for a todo item, allowing partial updates.
 #[derive(Debug, Default, Clone)]
 pub struct TodoPatch {
+    /// The reminder before the due, replacing the `DISPLAY` alarms of the todo if set.
+    /// `Some(None)` removes them.
+    pub alarm: Option<Option<AlarmTrigger>>,
     /// The description of the todo item, if available.
     pub description: Option<Option<String>>,
     /// The due date and time of the todo item, if available.
     pub due: Option<Option<LooseDateTime>>,
+    /// How long the todo item is estimated to take, see [`Todo::estimate`]. `Some(None)` clears
+    /// it.
+    pub estimate: Option<Option<SignedDuration>>,
     /// The percent complete, from 0 to 100.
     pub percent_complete: Option<Option<u8>>,
     /// The priority of the todo item, from 1 to 9, where 1 is the highest priority.
@@ -223,33 +602,107 @@ pub struct TodoPatch {
     pub status: Option<TodoStatus>,
     /// The summary of the todo item, if available.
     pub summary: Option<String>,
+    /// The previous due date to record as snoozed from, if any.
+    pub snoozed_from: Option<LooseDateTime>,
+    /// Custom metadata to set, or to remove where the value is `None`, see [`Todo::meta`].
+    pub meta: Vec<(String, Option<String>)>,
+    /// A sealed description to keep in place of the plain one, see
+    /// [`Todo::encrypted_description`].
+    ///
+    /// [`crate::Aim::update_todo`] seals the descriptions set on encrypted todos by itself.
+    pub encrypted_description: Option<String>,
+    /// Who the todo is waiting on, see [`Todo::waiting_on`]. `Some(None)` stops waiting.
+    pub waiting: Option<Option<Waiting>>,
+    /// Where the todo advances from if the patch completes it and it recurs, unless the todo
+    /// chooses with `X-AIM-RECUR-FROM`. `None` advances it from its schedule.
+    ///
+    /// [`crate::Aim::update_todo`] fills it in from the config if unset. It changes nothing by
+    /// itself, so it is left out of [`TodoPatch::is_empty`].
+    pub recur_from: Option<RecurFrom>,
+    /// When the todo was completed if the patch completes it, in place of now. A todo completed
+    /// already takes it as its new completion time.
+    ///
+    /// [`crate::Aim::update_todo`] rejects it on a patch not completing the todo, in the future or
+    /// before the todo was created. It changes nothing by itself, so it is left out of
+    /// [`TodoPatch::is_empty`].
+    pub completed_at: Option<Zoned>,
 }
 
 impl TodoPatch {
     /// Is this patch empty, meaning no fields are set
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.description.is_none()
+        self.alarm.is_none()
+            && self.description.is_none()
             && self.due.is_none()
+            && self.estimate.is_none()
             && self.percent_complete.is_none()
             && self.priority.is_none()
             && self.status.is_none()
             && self.summary.is_none()
+            && self.snoozed_from.is_none()
+            && self.meta.is_empty()
+            && self.encrypted_description.is_none()
+            && self.waiting.is_none()
+    }
+
+    /// Checks that the status and the percent complete agree when both are set.
+    ///
+    /// A todo is needs-action at 0%, in process below 100% and completed at 100%, while a
+    /// cancelled todo may be at any percent. When only one of them is set, the other follows when
+    /// the patch is applied: a percent complete sets the status, completing sets 100% and marking
+    /// as needs-action clears the percent complete.
+    ///
+    /// # Errors
+    /// If the status and the percent complete contradict each other.
+    pub fn validate(&self) -> Result<(), String> {
+        let (Some(status), Some(Some(percent))) = (self.status, self.percent_complete) else {
+            return Ok(());
+        };
+        let percent = percent.min(100);
+        let agrees = match status {
+            TodoStatus::NeedsAction => percent == 0,
+            TodoStatus::InProcess => percent < 100,
+            TodoStatus::Completed => percent == 100,
+            TodoStatus::Cancelled => true,
+        };
+        if agrees {
+            Ok(())
+        } else {
+            Err(format!("a {status} todo cannot be {percent}% complete"))
+        }
     }
 
     pub(crate) fn resolve<'a>(&'a self, now: &'a Zoned) -> ResolvedTodoPatch<'a> {
-        let percent_complete = match self.percent_complete {
+        let mut percent_complete = match self.percent_complete {
             Some(Some(v)) => Some(Some(v.min(100))),
             _ => self.percent_complete,
         };
+        let mut status = self.status;
+        match (status, percent_complete) {
+            (None, Some(Some(percent))) => {
+                status = Some(TodoStatus::for_percent_complete(percent));
+            }
+            (Some(TodoStatus::Completed), None) => percent_complete = Some(Some(100)),
+            (Some(TodoStatus::NeedsAction), None) => percent_complete = Some(None),
+            _ => {}
+        }
 
         ResolvedTodoPatch {
+            alarm: self.alarm,
             description: self.description.as_ref().map(|opt| opt.as_deref()),
             due: self.due.clone(),
+            estimate: self.estimate,
             percent_complete,
             priority: self.priority,
-            status: self.status,
+            status,
             summary: self.summary.as_deref(),
+            snoozed_from: self.snoozed_from.as_ref(),
+            meta: &self.meta,
+            encrypted_description: self.encrypted_description.as_deref(),
+            waiting: self.waiting.as_ref().map(Option::as_ref),
+            recur_from: self.recur_from,
+            completed_at: self.completed_at.as_ref(),
             now,
         }
     }
@@ -258,12 +711,20 @@ impl TodoPatch {
 impl From<TodoDraft> for TodoPatch {
     fn from(draft: TodoDraft) -> TodoPatch {
         TodoPatch {
+            alarm: draft.alarm,
             description: draft.description.map(Some),
             due: draft.due.map(Some),
+            estimate: draft.estimate.map(Some),
             percent_complete: draft.percent_complete.map(Some),
             priority: draft.priority,
             status: Some(draft.status),
             summary: Some(draft.summary),
+            snoozed_from: None,
+            meta: Vec::new(),
+            encrypted_description: None,
+            waiting: None,
+            recur_from: None,
+            completed_at: None,
         }
     }
 }
@@ -271,12 +732,20 @@ impl From<TodoDraft> for TodoPatch {
 #[derive(Debug, Clone)]
 #[expect(clippy::option_option)]
 pub struct ResolvedTodoPatch<'a> {
+    pub alarm: Option<Option<AlarmTrigger>>,
     pub description: Option<Option<&'a str>>,
     pub due: Option<Option<LooseDateTime>>,
+    pub estimate: Option<Option<SignedDuration>>,
     pub percent_complete: Option<Option<u8>>,
     pub priority: Option<Priority>,
     pub status: Option<TodoStatus>,
     pub summary: Option<&'a str>,
+    pub snoozed_from: Option<&'a LooseDateTime>,
+    pub meta: &'a [(String, Option<String>)],
+    pub encrypted_description: Option<&'a str>,
+    pub waiting: Option<Option<&'a Waiting>>,
+    pub recur_from: Option<RecurFrom>,
+    pub completed_at: Option<&'a Zoned>,
 
     pub now: &'a Zoned,
 }
@@ -289,11 +758,22 @@ impl ResolvedTodoPatch<'_> {
         } else if self.description.is_some() {
             t.description = None;
         }
+        // A new description replaces the sealed one as well
+        if self.description.is_some() || self.encrypted_description.is_some() {
+            write_sealed(&mut t.x_properties, self.encrypted_description);
+        }
 
-        if let Some(Some(ref due)) = self.due {
-            t.due = Some(Due::new(due.clone()));
-        } else if self.due.is_some() {
-            t.due = None;
+        if let Some(ref due) = self.due {
+            set_due(t, due.as_ref());
+        }
+
+        if let Some(estimate) = self.estimate {
+            t.x_properties
+                .retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_ESTIMATE));
+            if let Some(estimate) = estimate {
+                t.x_properties
+                    .push(x_text_property(X_AIM_ESTIMATE, estimate.to_string()));
+            }
         }
 
         if let Some(Some(v)) = self.percent_complete {
@@ -306,15 +786,34 @@ impl ResolvedTodoPatch<'_> {
             t.priority = Some(ical::Priority::new(Into::<u8>::into(priority)));
         }
 
-        if let Some(status) = self.status {
-            t.status = Some(ical::TodoStatus::new(status.into()));
+        // Giving a due or a priority triages a todo out of the inbox
+        if matches!(self.due, Some(Some(_))) || self.priority.is_some_and(|p| p != Priority::None) {
+            t.x_properties
+                .retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_INBOX));
+        }
 
-            // Handle COMPLETED property
-            if status == TodoStatus::Completed && t.completed.is_none() {
-                let utc_now = self.now.with_time_zone(jiff::tz::TimeZone::UTC);
-                t.completed = Some(Completed::new(utc_now.datetime()));
-            } else if status != TodoStatus::Completed {
+        if let Some(status) = self.status {
+            // A recurring todo moves on to its next occurrence rather than being completed
+            let from = recur_from(t).or(self.recur_from).unwrap_or_default();
+            let completed = self.completed_at.unwrap_or(self.now);
+            let was_completed = Todo::status(t) == TodoStatus::Completed && t.completed.is_some();
+            if status == TodoStatus::Completed && roll_forward(t, from, completed) {
+                t.status = Some(ical::TodoStatus::new(TodoStatus::NeedsAction.into()));
+                t.percent_complete = None;
                 t.completed = None;
+            } else {
+                t.status = Some(ical::TodoStatus::new(status.into()));
+
+                // Handle COMPLETED property, keeping the one it replaces for undoing
+                if status == TodoStatus::Completed {
+                    if !was_completed || self.completed_at.is_some() {
+                        let utc = completed.with_time_zone(jiff::tz::TimeZone::UTC);
+                        let before = t.completed.replace(Completed::new(utc.datetime()));
+                        set_completed_before(t, before.as_ref());
+                    }
+                } else {
+                    t.completed = take_completed_before(t);
+                }
             }
         }
 
@@ -322,6 +821,31 @@ impl ResolvedTodoPatch<'_> {
             t.summary = Some(Summary::new(summary.to_string()));
         }
 
+        match self.alarm {
+            Some(Some(_)) if Todo::due(t).is_none() => {
+                tracing::warn!(uid = %t.uid.content, "not adding a reminder to a todo without due");
+            }
+            Some(alarm) => {
+                let related = Some(AlarmTriggerRelationship::End);
+                let alarm = alarm.map(|a| a.to_display_alarm(related, &Todo::summary(t)));
+                replace_display_alarms(&mut t.alarms, alarm);
+            }
+            None => {}
+        }
+
+        if let Some(from) = self.snoozed_from {
+            t.x_properties
+                .push(x_text_property(X_AIM_SNOOZED_FROM, from.format_stable()));
+        }
+
+        if let Some(waiting) = self.waiting {
+            set_waiting(t, waiting, self.now);
+        }
+
+        if !self.meta.is_empty() {
+            write_meta(&mut t.x_properties, self.meta);
+        }
+
         // Set the creation time to now if it is not already set
         if t.dt_stamp.date().year == 1970 {
             // TODO: better check for unset
@@ -333,6 +857,149 @@ impl ResolvedTodoPatch<'_> {
     }
 }
 
+/// Sets who a todo is waiting on, replacing the follow-up and its reminder, or clears it.
+fn set_waiting(t: &mut VTodo<String>, waiting: Option<&Waiting>, now: &Zoned) {
+    t.x_properties.retain(|p| {
+        !p.name.eq_ignore_ascii_case(X_AIM_WAITING_ON)
+            && !p.name.eq_ignore_ascii_case(X_AIM_FOLLOW_UP)
+    });
+    t.alarms.retain(|a| !is_follow_up_alarm(a));
+    let Some(waiting) = waiting else {
+        return;
+    };
+
+    let on = waiting.on.trim();
+    t.x_properties
+        .push(x_text_property(X_AIM_WAITING_ON, on.to_string()));
+    let Some(follow_up) = &waiting.follow_up else {
+        return;
+    };
+    t.x_properties
+        .push(x_text_property(X_AIM_FOLLOW_UP, follow_up.format_stable()));
+    if waiting.notify
+        && let Some(at) = follow_up.to_timestamp_in(now.time_zone())
+    {
+        let description = format!("Follow up with {on}: {}", Todo::summary(t));
+        t.alarms.push(follow_up_alarm(at, &description));
+    }
+}
+
+/// Sets the due of a todo, keeping it as a duration from the start if it was one.
+fn set_due(t: &mut VTodo<String>, due: Option<&LooseDateTime>) {
+    if t.due.is_none()
+        && let (Some(start), Some(duration)) = (&t.dt_start, &mut t.duration)
+    {
+        match due.and_then(|due| duration_until(&start.0.clone().into(), due)) {
+            Some(value) => {
+                duration.value = value;
+                return;
+            }
+            // DUE and DURATION cannot both be set
+            None => t.duration = None,
+        }
+    }
+    t.due = due.map(|due| {
+        Due::new(match &t.due {
+            Some(original) => due.to_property_like(original),
+            None => due.clone().into(),
+        })
+    });
+}
+
+/// The duration from `start` to `due`, if `due` is not before it and has the same value type.
+fn duration_until(start: &LooseDateTime, due: &LooseDateTime) -> Option<ValueDuration> {
+    let date_only = |dt: &LooseDateTime| matches!(dt, LooseDateTime::DateOnly(_));
+    let seconds = u32::try_from(shift_of(start, due).as_secs()).ok()?;
+    (date_only(start) == date_only(due)).then_some(ValueDuration::DateTime {
+        positive: true,
+        day: seconds / 86400,
+        hour: seconds / 3600 % 24,
+        minute: seconds / 60 % 60,
+        second: seconds % 60,
+    })
+}
+
+/// Where a recurring todo advances from once completed, from `X-AIM-RECUR-FROM` or the
+/// `recur_from` config.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecurFrom {
+    /// From the occurrence it was due at, so one completed late is next due as scheduled.
+    #[default]
+    Schedule,
+    /// From the day it was completed, so one completed late is next due an interval later.
+    Completion,
+}
+
+impl AsRef<str> for RecurFrom {
+    fn as_ref(&self) -> &str {
+        match self {
+            RecurFrom::Schedule => "SCHEDULE",
+            RecurFrom::Completion => "COMPLETION",
+        }
+    }
+}
+
+impl FromStr for RecurFrom {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "SCHEDULE" => Ok(RecurFrom::Schedule),
+            "COMPLETION" => Ok(RecurFrom::Completion),
+            _ => Err(format!("unknown recurrence anchor: {s}")),
+        }
+    }
+}
+
+/// Records the completion time a todo had before it was completed, if any, see
+/// [`X_AIM_COMPLETED_BEFORE`].
+fn set_completed_before(t: &mut VTodo<String>, before: Option<&Completed<String>>) {
+    t.x_properties
+        .retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_COMPLETED_BEFORE));
+    if let Some(before) = before.and_then(|c| c.zoned()) {
+        t.x_properties.push(x_text_property(
+            X_AIM_COMPLETED_BEFORE,
+            before.timestamp().to_string(),
+        ));
+    }
+}
+
+/// Takes the completion time a todo had before it was completed, see [`set_completed_before`].
+fn take_completed_before(t: &mut VTodo<String>) -> Option<Completed<String>> {
+    let before = t
+        .x_properties
+        .iter()
+        .find(|p| p.name.eq_ignore_ascii_case(X_AIM_COMPLETED_BEFORE))
+        .and_then(x_text)
+        .and_then(|v| v.parse::<jiff::Timestamp>().ok());
+    t.x_properties
+        .retain(|p| !p.name.eq_ignore_ascii_case(X_AIM_COMPLETED_BEFORE));
+    before.map(|at| Completed::new(at.to_zoned(jiff::tz::TimeZone::UTC).datetime()))
+}
+
+/// When the todo was created, from its `CREATED`, or else its `DTSTAMP`, which is bumped by edits
+/// as well.
+pub(crate) fn created_at<S: StringStorage>(t: &VTodo<S>) -> Option<Zoned> {
+    let created = t.retained_properties.iter().find_map(|p| match p {
+        Property::Created(created) => Some(created),
+        _ => None,
+    });
+    match created {
+        Some(created) => created.zoned(),
+        None => t.dt_stamp.zoned(),
+    }
+}
+
+/// Where the todo chooses to advance from, see [`X_AIM_RECUR_FROM`].
+fn recur_from<S: StringStorage>(t: &VTodo<S>) -> Option<RecurFrom> {
+    t.x_properties
+        .iter()
+        .filter(|p| p.name.as_str_lossy().eq_ignore_ascii_case(X_AIM_RECUR_FROM))
+        .filter_map(x_text)
+        .find_map(|v| v.trim().parse().ok())
+}
+
 /// The status of a todo item, which can be one of several predefined states.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -348,6 +1015,18 @@ pub enum TodoStatus {
     Cancelled,
 }
 
+impl TodoStatus {
+    /// The status of a todo at the given percent complete, see [`TodoPatch::validate`].
+    #[must_use]
+    pub const fn for_percent_complete(percent: u8) -> Self {
+        match percent {
+            0 => TodoStatus::NeedsAction,
+            1..=99 => TodoStatus::InProcess,
+            _ => TodoStatus::Completed,
+        }
+    }
+}
+
 const STATUS_NEEDS_ACTION: &str = "NEEDS-ACTION";
 const STATUS_COMPLETED: &str = "COMPLETED";
 const STATUS_IN_PROCESS: &str = "IN-PROGRESS";
@@ -408,6 +1087,7 @@ impl From<TodoStatus> for TodoStatusValue {
 
 /// Conditions for filtering todo items, such as current time, status, and due date.
 #[derive(Debug, Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct TodoConditions {
     /// The status of the todo item to filter by, if any.
     pub status: Option<TodoStatus>,
@@ -415,8 +1095,38 @@ pub struct TodoConditions {
     /// The priority of the todo item to filter by, if any.
     pub due: Option<DateTimeAnchor>,
 
+    /// Only todos scheduled to start after this time, if any.
+    pub start_after: Option<DateTimeAnchor>,
+
+    /// Leave out todos scheduled to start later than now.
+    pub hide_unstarted: bool,
+
     /// The calendar ID to filter todos by
     pub calendar_id: Option<String>,
+
+    /// Whether to include the todos archived by housekeeping.
+    pub include_archived: bool,
+
+    /// Only todos with all of these custom metadata, as key-value pairs.
+    pub meta: Vec<(String, String)>,
+
+    /// Only todos in the inbox, see [`Todo::in_inbox`].
+    pub inbox: bool,
+
+    /// Leave out todos waiting on someone, until their follow-up passes.
+    pub hide_waiting: bool,
+
+    /// Only todos waiting on someone, see [`Todo::waiting_on`].
+    pub waiting: bool,
+
+    /// Only todos waiting on someone whose name contains this text, ignoring case.
+    pub waiting_for: Option<String>,
+
+    /// Only todos with all of these categories, their own or added by rules, ignoring case.
+    pub categories: Vec<String>,
+
+    /// Only todos without a due date.
+    pub undated: bool,
 }
 
 impl TodoConditions {
@@ -428,17 +1138,51 @@ impl TodoConditions {
                 .as_ref()
                 .map(|a| a.resolve_at_end_of_day(now))
                 .transpose()?,
+            start_after: self
+                .start_after
+                .as_ref()
+                .map(|a| a.resolve_at_end_of_day(now))
+                .transpose()?,
+            started_by: self.hide_unstarted.then(|| now.clone()),
             calendar_id: self.calendar_id.clone(),
+            include_archived: self.include_archived,
+            meta: resolve_meta_conditions(&self.meta)?,
+            inbox: self.inbox,
+            waiting: self.waiting,
+            waiting_for: self.waiting_for.clone(),
+            followed_up_by: self.hide_waiting.then(|| now.clone()),
+            categories: self.categories.clone(),
+            undated: self.undated,
         })
     }
 }
 
 #[derive(Debug, Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct ResolvedTodoConditions {
     pub status: Option<TodoStatus>,
     pub due: Option<Zoned>,
+    pub start_after: Option<Zoned>,
+    /// Only todos with no start or starting by this time
+    pub started_by: Option<Zoned>,
     /// The calendar ID to filter todos by
     pub calendar_id: Option<String>,
+    /// Whether to include the archived todos
+    pub include_archived: bool,
+    /// Custom metadata the todos must have, with normalized keys
+    pub meta: Vec<(String, String)>,
+    /// Only todos in the inbox
+    pub inbox: bool,
+    /// Only todos waiting on someone
+    pub waiting: bool,
+    /// Only todos waiting on someone matching this text
+    pub waiting_for: Option<String>,
+    /// Only todos not waiting on anyone, or with a follow-up by this time
+    pub followed_up_by: Option<Zoned>,
+    /// Categories the todos must have, their own or added by rules
+    pub categories: Vec<String>,
+    /// Only todos without a due date
+    pub undated: bool,
 }
 
 /// The default sort key for todo items, which is by due date.
@@ -454,6 +1198,9 @@ pub enum TodoSort {
         /// Put items with no priority first or last. If none, use the default
         none_first: Option<bool>,
     },
+
+    /// Sort by the summary of the todo item, with the collation of the config.
+    Summary(SortOrder),
 }
 
 impl TodoSort {
@@ -464,6 +1211,10 @@ impl TodoSort {
                 order,
                 none_first: none_first.unwrap_or(config.default_priority_none_fist),
             },
+            TodoSort::Summary(order) => ResolvedTodoSort::Summary {
+                order,
+                collation: config.collation,
+            },
         }
     }
 
@@ -475,5 +1226,335 @@ impl TodoSort {
 #[derive(Debug, Clone, Copy)]
 pub enum ResolvedTodoSort {
     Due(SortOrder),
-    Priority { order: SortOrder, none_first: bool },
+    Priority {
+        order: SortOrder,
+        none_first: bool,
+    },
+    Summary {
+        order: SortOrder,
+        collation: Collation,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use aimcal_ical::TriggerValue;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+
+    fn test_vtodo(now: &Zoned, status: TodoStatus, percent_complete: Option<u8>) -> VTodo<String> {
+        let draft = TodoDraft {
+            alarm: None,
+            calendar_id: None,
+            categories: Vec::new(),
+            description: None,
+            due: None,
+            estimate: None,
+            percent_complete,
+            priority: None,
+            status,
+            summary: "Test".to_string(),
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
+        };
+        let config: Config = toml::from_str("").unwrap();
+        draft.resolve(&config, now).into_ics("test-uid")
+    }
+
+    fn apply(todo: &mut VTodo<String>, patch: &TodoPatch, now: &Zoned) {
+        patch.resolve(now).apply_to(todo);
+    }
+
+    #[test]
+    fn todo_patch_percent_sets_status() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let mut todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        let percent = |p| TodoPatch {
+            percent_complete: Some(Some(p)),
+            ..Default::default()
+        };
+
+        apply(&mut todo, &percent(60), &now);
+        assert_eq!(todo.status(), TodoStatus::InProcess);
+        assert_eq!(todo.percent_complete(), Some(60));
+        assert!(todo.completed.is_none());
+
+        apply(&mut todo, &percent(250), &now);
+        assert_eq!(todo.status(), TodoStatus::Completed);
+        assert_eq!(todo.percent_complete(), Some(100));
+        assert!(todo.completed.is_some());
+
+        apply(&mut todo, &percent(0), &now);
+        assert_eq!(todo.status(), TodoStatus::NeedsAction);
+        assert_eq!(todo.percent_complete(), Some(0));
+        assert!(todo.completed.is_none());
+    }
+
+    #[test]
+    fn todo_patch_status_sets_percent() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let mut todo = test_vtodo(&now, TodoStatus::InProcess, Some(40));
+        let status = |s| TodoPatch {
+            status: Some(s),
+            ..Default::default()
+        };
+
+        apply(&mut todo, &status(TodoStatus::Cancelled), &now);
+        assert_eq!(todo.percent_complete(), Some(40));
+
+        apply(&mut todo, &status(TodoStatus::Completed), &now);
+        assert_eq!(todo.percent_complete(), Some(100));
+
+        apply(&mut todo, &status(TodoStatus::NeedsAction), &now);
+        assert_eq!(todo.percent_complete(), None);
+    }
+
+    #[test]
+    fn todo_patch_completes_at_given_time_and_undo_restores_completion() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let mut todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        let status = |s, at: Option<&Zoned>| TodoPatch {
+            status: Some(s),
+            completed_at: at.cloned(),
+            ..Default::default()
+        };
+        let earlier = now.checked_sub(SignedDuration::from_hours(5)).unwrap();
+        let earliest = now.checked_sub(SignedDuration::from_hours(9)).unwrap();
+
+        apply(&mut todo, &status(TodoStatus::Completed, None), &now);
+        apply(&mut todo, &status(TodoStatus::NeedsAction, None), &now);
+        assert!(todo.completed.is_none());
+
+        apply(
+            &mut todo,
+            &status(TodoStatus::Completed, Some(&earlier)),
+            &now,
+        );
+        assert_eq!(todo.completed(), Some(earlier.clone()));
+
+        apply(
+            &mut todo,
+            &status(TodoStatus::Completed, Some(&earliest)),
+            &now,
+        );
+        assert_eq!(todo.completed(), Some(earliest));
+
+        apply(&mut todo, &status(TodoStatus::NeedsAction, None), &now);
+        assert_eq!(todo.status(), TodoStatus::NeedsAction);
+        assert_eq!(todo.completed(), Some(earlier));
+    }
+
+    #[test]
+    fn todo_draft_percent_sets_status() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+
+        let todo = test_vtodo(&now, TodoStatus::NeedsAction, Some(30));
+        assert_eq!(todo.status(), TodoStatus::InProcess);
+        assert_eq!(todo.percent_complete(), Some(30));
+
+        let todo = test_vtodo(&now, TodoStatus::Completed, None);
+        assert_eq!(todo.percent_complete(), Some(100));
+        assert!(todo.completed.is_some());
+
+        let todo = test_vtodo(&now, TodoStatus::Cancelled, Some(30));
+        assert_eq!(todo.status(), TodoStatus::Cancelled);
+    }
+
+    fn scheduled_vtodo(now: &Zoned, start: LooseDateTime, duration: &str) -> VTodo<String> {
+        let mut todo = test_vtodo(now, TodoStatus::NeedsAction, None);
+        todo.dt_start = Some(ical::DtStart::new(start));
+        todo.duration = Some(ical::Duration {
+            value: duration.parse().unwrap(),
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        });
+        todo
+    }
+
+    #[test]
+    fn todo_without_due_is_due_its_duration_after_start() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let start = jiff::civil::date(2025, 1, 13).at(9, 0, 0, 0);
+
+        let todo = scheduled_vtodo(&now, LooseDateTime::Floating(start), "PT1H30M");
+        assert_eq!(todo.start(), Some(LooseDateTime::Floating(start)));
+        let due = jiff::civil::date(2025, 1, 13).at(10, 30, 0, 0);
+        assert_eq!(todo.due(), Some(LooseDateTime::Floating(due)));
+
+        let start = LooseDateTime::DateOnly(start.date());
+        let todo = scheduled_vtodo(&now, start, "P2D");
+        let due = jiff::civil::date(2025, 1, 15);
+        assert_eq!(todo.due(), Some(LooseDateTime::DateOnly(due)));
+
+        let mut todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        todo.dt_start = Some(ical::DtStart::new(LooseDateTime::Floating(
+            due.at(0, 0, 0, 0),
+        )));
+        assert_eq!(todo.due(), None);
+    }
+
+    #[test]
+    fn todo_patch_due_keeps_duration_from_start() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let start = jiff::civil::date(2025, 1, 13).at(9, 0, 0, 0);
+        let mut todo = scheduled_vtodo(&now, LooseDateTime::Floating(start), "PT1H");
+        let due = |due| TodoPatch {
+            due: Some(due),
+            ..Default::default()
+        };
+
+        let later = LooseDateTime::Floating(jiff::civil::date(2025, 1, 14).at(11, 15, 0, 0));
+        apply(&mut todo, &due(Some(later.clone())), &now);
+        assert!(todo.due.is_none());
+        let duration = todo.duration.as_ref().map(|d| d.value.to_string());
+        assert_eq!(duration.as_deref(), Some("P1DT2H15M"));
+        assert_eq!(todo.due(), Some(later));
+
+        // A due before the start cannot be a duration, so it is kept as DUE instead
+        let earlier = LooseDateTime::Floating(jiff::civil::date(2025, 1, 12).at(9, 0, 0, 0));
+        apply(&mut todo, &due(Some(earlier.clone())), &now);
+        assert!(todo.duration.is_none());
+        assert_eq!(todo.due(), Some(earlier));
+        assert_eq!(todo.start(), Some(LooseDateTime::Floating(start)));
+
+        let mut todo = scheduled_vtodo(&now, LooseDateTime::Floating(start), "PT1H");
+        apply(&mut todo, &due(None), &now);
+        assert!(todo.duration.is_none() && todo.due.is_none());
+        assert_eq!(todo.start(), Some(LooseDateTime::Floating(start)));
+    }
+
+    #[test]
+    fn todo_draft_alarm_requires_due() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let config: Config = toml::from_str("").unwrap();
+
+        let todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        assert!(todo.alarms.is_empty());
+
+        let draft = TodoDraft {
+            alarm: None,
+            calendar_id: None,
+            categories: Vec::new(),
+            description: None,
+            due: Some(LooseDateTime::Local(now.clone())),
+            estimate: None,
+            percent_complete: None,
+            priority: None,
+            status: TodoStatus::NeedsAction,
+            summary: "Test".to_string(),
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
+        };
+        let todo = draft.resolve(&config, &now).into_ics("test-uid");
+        let [alarm] = todo.alarms.as_slice() else {
+            panic!("expected a single alarm, got {:?}", todo.alarms);
+        };
+        assert_eq!(alarm.trigger.related, Some(AlarmTriggerRelationship::End));
+        let TriggerValue::Duration(duration) = alarm.trigger.value else {
+            panic!("expected a relative trigger");
+        };
+        assert_eq!(duration.to_string(), "-PT1H");
+    }
+
+    #[test]
+    fn todo_draft_takes_defaults_of_its_categories() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let config: Config = toml::from_str(
+            "[categories.work]\nalarm = \"\"\ncolor = \"#1e90ff\"\npriority = \"high\"\n",
+        )
+        .unwrap();
+        let mut draft = TodoDraft::default(&config, &now).unwrap();
+        draft.categories = vec!["Work".to_string()];
+        draft.due = Some(LooseDateTime::Local(now.clone()));
+
+        let todo = draft.resolve(&config, &now).into_ics("test-uid");
+        assert!(todo.alarms.is_empty());
+        assert_eq!(todo.color.unwrap().content.to_string(), "#1e90ff");
+        assert_eq!(todo.priority.unwrap().value, 2);
+
+        draft.no_defaults = true;
+        let todo = draft.resolve(&config, &now).into_ics("test-uid");
+        assert_eq!(todo.alarms.len(), 1);
+        assert!(todo.color.is_none());
+        assert_eq!(todo.priority.unwrap().value, 0);
+    }
+
+    #[test]
+    fn todo_patch_waiting_replaces_follow_up_and_reminder() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let mut todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        let follow_up = LooseDateTime::Floating(jiff::civil::date(2030, 5, 3).at(9, 0, 0, 0));
+        let wait = |on: &str, follow_up: Option<LooseDateTime>, notify| TodoPatch {
+            waiting: Some(Some(Waiting {
+                on: on.to_string(),
+                follow_up,
+                notify,
+            })),
+            ..Default::default()
+        };
+
+        apply(
+            &mut todo,
+            &wait("Alice", Some(follow_up.clone()), true),
+            &now,
+        );
+        assert_eq!(todo.waiting_on().as_deref(), Some("Alice"));
+        assert_eq!(todo.follow_up(), Some(follow_up));
+        assert_eq!(todo.alarms.len(), 1);
+        assert_eq!(todo.status(), TodoStatus::NeedsAction);
+
+        apply(&mut todo, &wait("Bob", None, false), &now);
+        assert_eq!(todo.waiting_on().as_deref(), Some("Bob"));
+        assert_eq!(todo.follow_up(), None);
+        assert!(todo.alarms.is_empty());
+        assert_eq!(todo.x_properties.len(), 1);
+
+        let unwait = TodoPatch {
+            waiting: Some(None),
+            ..Default::default()
+        };
+        apply(&mut todo, &unwait, &now);
+        assert_eq!(todo.waiting_on(), None);
+        assert!(todo.x_properties.is_empty());
+    }
+
+    #[test]
+    fn todo_patch_estimate_replaces_and_clears() {
+        let now = Zoned::now().with_time_zone(TimeZone::UTC);
+        let mut todo = test_vtodo(&now, TodoStatus::NeedsAction, None);
+        assert_eq!(todo.estimate(), None);
+        let estimate = |e| TodoPatch {
+            estimate: Some(e),
+            ..Default::default()
+        };
+
+        apply(
+            &mut todo,
+            &estimate(Some(SignedDuration::from_mins(90))),
+            &now,
+        );
+        assert_eq!(todo.estimate(), Some(SignedDuration::from_mins(90)));
+        apply(
+            &mut todo,
+            &estimate(Some(SignedDuration::from_mins(30))),
+            &now,
+        );
+        assert_eq!(todo.estimate(), Some(SignedDuration::from_mins(30)));
+        assert_eq!(todo.x_properties.len(), 1);
+
+        apply(&mut todo, &estimate(None), &now);
+        assert_eq!(todo.estimate(), None);
+        assert!(todo.x_properties.is_empty());
+
+        // Written by hand or by another client
+        todo.x_properties
+            .push(x_text_property(X_AIM_ESTIMATE, "2h".to_string()));
+        assert_eq!(todo.estimate(), Some(SignedDuration::from_hours(2)));
+    }
 }
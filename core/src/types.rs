@@ -268,6 +268,14 @@ impl<'de> Deserialize<'de> for Priority {
                 }
             }
 
+            // TOML integers are signed
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(E::custom(format!("invalid priority: {v}"))),
+                }
+            }
+
             fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
                 match v {
                     "0" | "none" => Ok(Priority::None),
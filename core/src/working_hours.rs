@@ -0,0 +1,638 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use jiff::civil::{Date, Time, Weekday};
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Timestamp, Zoned};
+
+/// How far ahead [`WorkingHours::next_working_instant`] looks before giving up.
+const SEARCH_DAYS: usize = 366;
+
+/// A range of working time within a day, e.g. `09:00-17:00`.
+///
+/// The end is exclusive, and an end of `24:00` means the end of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    start: Time,
+    end: Option<Time>, // None for the end of the day
+}
+
+impl TimeRange {
+    /// Creates a range from `start` to `end`, or to the end of the day if `end` is `None`.
+    ///
+    /// Returns `None` if the range is empty.
+    #[must_use]
+    pub fn new(start: Time, end: Option<Time>) -> Option<Self> {
+        match end {
+            Some(end) if end <= start => None,
+            _ => Some(Self { start, end }),
+        }
+    }
+
+    /// The start of the range.
+    #[must_use]
+    pub fn start(&self) -> Time {
+        self.start
+    }
+
+    /// The end of the range, `None` for the end of the day.
+    #[must_use]
+    pub fn end(&self) -> Option<Time> {
+        self.end
+    }
+
+    /// The instants the range starts and ends at on `date`.
+    fn instants(&self, date: Date, tz: &TimeZone) -> Option<(Timestamp, Timestamp)> {
+        let start = date.to_datetime(self.start).to_zoned(tz.clone()).ok()?;
+        let end = match self.end {
+            Some(end) => date.to_datetime(end).to_zoned(tz.clone()).ok()?,
+            None => date.tomorrow().ok()?.to_zoned(tz.clone()).ok()?,
+        };
+        Some((start.timestamp(), end.timestamp()))
+    }
+}
+
+impl FromStr for TimeRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid time range, expected HH:MM-HH:MM: {s}"))?;
+        let parse = |t: &str| {
+            t.trim()
+                .parse::<Time>()
+                .map_err(|e| format!("Invalid time in range {s}: {e}"))
+        };
+        let start = parse(start)?;
+        let end = match end.trim() {
+            "24:00" => None,
+            end => Some(parse(end)?),
+        };
+        Self::new(start, end).ok_or_else(|| format!("Empty time range: {s}"))
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-", self.start.strftime("%H:%M"))?;
+        match self.end {
+            Some(end) => write!(f, "{}", end.strftime("%H:%M")),
+            None => write!(f, "24:00"),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TimeRange {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Working hours per weekday, with holidays off.
+///
+/// Times are civil times in the timezone of the queried instant, so a
+/// `09:00-17:00` day is 7 or 9 hours long on days with a DST transition.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(try_from = "WorkingHoursRaw")]
+pub struct WorkingHours {
+    days: [Vec<TimeRange>; 7], // indexed by days since Monday
+    holiday_calendar: Option<String>,
+    holidays: BTreeSet<Date>,
+}
+
+impl Default for WorkingHours {
+    /// Monday to Friday, `09:00-17:00`.
+    fn default() -> Self {
+        let nine_to_five = TimeRange {
+            start: Time::constant(9, 0, 0, 0),
+            end: Some(Time::constant(17, 0, 0, 0)),
+        };
+        let mut hours = Self::empty();
+        for weekday in Weekday::Monday.cycle_forward().take(5) {
+            hours.set_day(weekday, vec![nine_to_five]);
+        }
+        hours
+    }
+}
+
+impl WorkingHours {
+    /// Working hours without any working time.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            days: Default::default(),
+            holiday_calendar: None,
+            holidays: BTreeSet::new(),
+        }
+    }
+
+    /// Sets the working time ranges of `weekday`, replacing the existing ones.
+    pub fn set_day(&mut self, weekday: Weekday, mut ranges: Vec<TimeRange>) {
+        ranges.sort_by_key(|r| r.start);
+        if let Some(day) = self.days.get_mut(day_index(weekday)) {
+            *day = ranges;
+        }
+    }
+
+    /// The working time ranges of `weekday`, sorted by start.
+    #[must_use]
+    pub fn day(&self, weekday: Weekday) -> &[TimeRange] {
+        self.days.get(day_index(weekday)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Name or ID of the calendar whose events are holidays.
+    #[must_use]
+    pub fn holiday_calendar(&self) -> Option<&str> {
+        self.holiday_calendar.as_deref()
+    }
+
+    /// Marks `date` as a holiday, so it has no working time.
+    pub fn add_holiday(&mut self, date: Date) {
+        self.holidays.insert(date);
+    }
+
+    /// Whether `date` is a holiday.
+    #[must_use]
+    pub fn is_holiday(&self, date: Date) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    /// Whether `date` has working time, so it is neither a day off nor a holiday.
+    #[must_use]
+    pub fn is_working_day(&self, date: Date) -> bool {
+        !self.is_holiday(date) && !self.day(date.weekday()).is_empty()
+    }
+
+    /// Whether `dt` falls within working hours.
+    #[must_use]
+    pub fn is_working_time(&self, dt: &Zoned) -> bool {
+        let now = dt.timestamp();
+        self.ranges_on(dt.date(), dt.time_zone())
+            .any(|(start, end)| start <= now && now < end)
+    }
+
+    /// The first instant at or after `dt` that falls within working hours.
+    ///
+    /// Returns `None` if there is no working time within a year.
+    #[must_use]
+    pub fn next_working_instant(&self, dt: &Zoned) -> Option<Zoned> {
+        let now = dt.timestamp();
+        let tz = dt.time_zone();
+        let mut date = dt.date();
+        for _ in 0..SEARCH_DAYS {
+            if let Some((start, _)) = self.ranges_on(date, tz).find(|(_, end)| now < *end) {
+                return Some(start.max(now).to_zoned(tz.clone()));
+            }
+            date = date.tomorrow().ok()?;
+        }
+        None
+    }
+
+    /// The span from the start of the first to the end of the last working time range on the day
+    /// of `dt`, in its timezone, or `None` on days off.
+    #[must_use]
+    pub fn day_window(&self, dt: &Zoned) -> Option<(Zoned, Zoned)> {
+        let tz = dt.time_zone();
+        let mut ranges = self.ranges_on(dt.date(), tz);
+        let (start, end) = ranges.next()?;
+        let end = ranges.last().map_or(end, |(_, end)| end);
+        Some((start.to_zoned(tz.clone()), end.to_zoned(tz.clone())))
+    }
+
+    /// Total working time between `a` and `b`, zero if `b` is not after `a`.
+    ///
+    /// Days are taken in the timezone of `a`.
+    #[must_use]
+    pub fn working_duration_between(&self, a: &Zoned, b: &Zoned) -> SignedDuration {
+        let (from, to) = (a.timestamp(), b.timestamp());
+        let tz = a.time_zone();
+        let last = b.with_time_zone(tz.clone()).date();
+
+        let mut total = SignedDuration::ZERO;
+        let mut date = a.date();
+        while date <= last {
+            for (start, end) in self.ranges_on(date, tz) {
+                let (start, end) = (start.max(from), end.min(to));
+                if start < end {
+                    total += end.duration_since(start);
+                }
+            }
+            match date.tomorrow() {
+                Ok(next) => date = next,
+                Err(_) => break,
+            }
+        }
+        total
+    }
+
+    /// The working time between `from` and `to` that none of the `busy` intervals take, in order.
+    ///
+    /// Days are taken in the timezone of `from`. The busy intervals may overlap and come in any
+    /// order.
+    #[must_use]
+    pub fn free_slots(
+        &self,
+        from: &Zoned,
+        to: &Zoned,
+        busy: &[(Timestamp, Timestamp)],
+    ) -> Vec<(Zoned, Zoned)> {
+        let (from_ts, to_ts) = (from.timestamp(), to.timestamp());
+        let tz = from.time_zone();
+        let last = to.with_time_zone(tz.clone()).date();
+        let mut busy = busy.to_vec();
+        busy.sort_unstable();
+
+        let mut slots = Vec::new();
+        let mut date = from.date();
+        while date <= last {
+            for (start, end) in self.ranges_on(date, tz) {
+                let (mut cursor, end) = (start.max(from_ts), end.min(to_ts));
+                for &(busy_start, busy_end) in &busy {
+                    if busy_start >= end {
+                        break;
+                    }
+                    if busy_start > cursor {
+                        slots.push((cursor, busy_start));
+                    }
+                    cursor = cursor.max(busy_end);
+                }
+                if cursor < end {
+                    slots.push((cursor, end));
+                }
+            }
+            match date.tomorrow() {
+                Ok(next) => date = next,
+                Err(_) => break,
+            }
+        }
+        slots
+            .into_iter()
+            .map(|(start, end)| (start.to_zoned(tz.clone()), end.to_zoned(tz.clone())))
+            .collect()
+    }
+
+    /// Working time ranges on `date` as instants, empty on holidays.
+    fn ranges_on(
+        &self,
+        date: Date,
+        tz: &TimeZone,
+    ) -> impl Iterator<Item = (Timestamp, Timestamp)> + '_ {
+        let ranges = if self.is_holiday(date) {
+            &[]
+        } else {
+            self.day(date.weekday())
+        };
+        let tz = tz.clone();
+        ranges.iter().filter_map(move |r| r.instants(date, &tz))
+    }
+}
+
+/// The calendars and categories whose todos count relative dues in days as business days.
+///
+/// A due of `3d` for a todo in one of the calendars, or with one of the categories, is then due
+/// at the end of the third business day, skipping the days off and holidays of the
+/// [`WorkingHours`], while the other todos keep counting calendar days. See
+/// [`Aim::resolve_due`](crate::Aim::resolve_due).
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BusinessDays {
+    /// Names or IDs of the calendars.
+    #[serde(default)]
+    pub calendars: Vec<String>,
+    /// Categories, compared ignoring ASCII case.
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+impl BusinessDays {
+    /// Whether a todo in the calendar with `calendar_id` and `calendar_name`, with `categories`,
+    /// counts in business days.
+    #[must_use]
+    pub fn applies_to(
+        &self,
+        calendar_id: &str,
+        calendar_name: Option<&str>,
+        categories: &[String],
+    ) -> bool {
+        self.calendars
+            .iter()
+            .any(|c| c == calendar_id || Some(c.as_str()) == calendar_name)
+            || self
+                .categories
+                .iter()
+                .any(|c| categories.iter().any(|a| a.eq_ignore_ascii_case(c)))
+    }
+}
+
+fn day_index(weekday: Weekday) -> usize {
+    weekday.to_monday_zero_offset().unsigned_abs().into()
+}
+
+/// The `[working_hours]` section as written in the config file.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkingHoursRaw {
+    monday: Option<Vec<TimeRange>>,
+    tuesday: Option<Vec<TimeRange>>,
+    wednesday: Option<Vec<TimeRange>>,
+    thursday: Option<Vec<TimeRange>>,
+    friday: Option<Vec<TimeRange>>,
+    saturday: Option<Vec<TimeRange>>,
+    sunday: Option<Vec<TimeRange>>,
+    holidays: Option<String>,
+    holiday_dates: Option<Vec<Date>>,
+}
+
+impl TryFrom<WorkingHoursRaw> for WorkingHours {
+    type Error = String;
+
+    fn try_from(raw: WorkingHoursRaw) -> Result<Self, Self::Error> {
+        let days = [
+            (Weekday::Monday, raw.monday),
+            (Weekday::Tuesday, raw.tuesday),
+            (Weekday::Wednesday, raw.wednesday),
+            (Weekday::Thursday, raw.thursday),
+            (Weekday::Friday, raw.friday),
+            (Weekday::Saturday, raw.saturday),
+            (Weekday::Sunday, raw.sunday),
+        ];
+
+        // Keep the default schedule if only holidays are configured
+        let mut hours = if days.iter().all(|(_, ranges)| ranges.is_none()) {
+            Self::default()
+        } else {
+            Self::empty()
+        };
+        for (weekday, ranges) in days {
+            let Some(mut ranges) = ranges else {
+                continue;
+            };
+            ranges.sort_by_key(|r| r.start);
+            for pair in ranges.windows(2) {
+                if let [a, b] = pair
+                    && a.end.is_none_or(|end| end > b.start)
+                {
+                    return Err(format!(
+                        "Overlapping working hours on {weekday:?}: {a} and {b}"
+                    ));
+                }
+            }
+            hours.set_day(weekday, ranges);
+        }
+        hours.holiday_calendar = raw.holidays;
+        hours.holidays.extend(raw.holiday_dates.unwrap_or_default());
+        Ok(hours)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::{date, time};
+
+    use super::*;
+
+    fn split_schedule() -> WorkingHours {
+        let mut hours = WorkingHours::empty();
+        let ranges = vec![
+            "13:00-17:00".parse().unwrap(),
+            "09:00-12:00".parse().unwrap(),
+        ];
+        for weekday in Weekday::Monday.cycle_forward().take(5) {
+            hours.set_day(weekday, ranges.clone());
+        }
+        hours
+    }
+
+    fn at(d: Date, t: Time, tz: &str) -> Zoned {
+        d.to_datetime(t).in_tz(tz).unwrap()
+    }
+
+    #[test]
+    fn parses_time_ranges() {
+        let range: TimeRange = "09:00-17:30".parse().unwrap();
+        assert_eq!(range.start(), time(9, 0, 0, 0));
+        assert_eq!(range.end(), Some(time(17, 30, 0, 0)));
+        assert_eq!(range.to_string(), "09:00-17:30");
+
+        let range: TimeRange = "22:00-24:00".parse().unwrap();
+        assert_eq!(range.end(), None);
+        assert_eq!(range.to_string(), "22:00-24:00");
+
+        assert!("17:00-09:00".parse::<TimeRange>().is_err());
+        assert!("09:00".parse::<TimeRange>().is_err());
+        assert!("9am-5pm".parse::<TimeRange>().is_err());
+    }
+
+    #[test]
+    fn parses_working_hours_section() {
+        const TOML: &str = r#"
+monday = ["13:00-17:00", "09:00-12:00"]
+saturday = ["10:00-14:00"]
+holidays = "Holidays"
+"#;
+        let hours: WorkingHours = toml::from_str(TOML).unwrap();
+        assert_eq!(hours.day(Weekday::Monday).len(), 2);
+        assert_eq!(
+            hours.day(Weekday::Monday).first().unwrap().start(),
+            time(9, 0, 0, 0)
+        );
+        assert!(hours.day(Weekday::Tuesday).is_empty());
+        assert_eq!(hours.day(Weekday::Saturday).len(), 1);
+        assert_eq!(hours.holiday_calendar(), Some("Holidays"));
+
+        let hours: WorkingHours = toml::from_str("holidays = \"Holidays\"").unwrap();
+        assert_eq!(
+            hours.day(Weekday::Friday),
+            WorkingHours::default().day(Weekday::Friday)
+        );
+
+        let err = toml::from_str::<WorkingHours>(r#"monday = ["09:00-12:00", "11:00-13:00"]"#);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn parses_holiday_dates() {
+        const TOML: &str = r#"holiday_dates = ["2026-12-25", "2026-12-26"]"#;
+        let hours: WorkingHours = toml::from_str(TOML).unwrap();
+        assert!(hours.is_holiday(date(2026, 12, 25)));
+        assert!(!hours.is_working_day(date(2026, 12, 25)));
+        assert!(!hours.is_holiday(date(2026, 12, 24)));
+        assert!(hours.is_working_day(date(2026, 12, 24)));
+        assert_eq!(hours.holiday_calendar(), None);
+
+        let err = toml::from_str::<WorkingHours>(r#"holiday_dates = ["Christmas"]"#);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn business_days_apply_by_calendar_or_category() {
+        const TOML: &str = r#"
+calendars = ["work"]
+categories = ["Work"]
+"#;
+        let business: BusinessDays = toml::from_str(TOML).unwrap();
+        assert!(business.applies_to("work", None, &[]));
+        assert!(business.applies_to("a1b2", Some("work"), &[]));
+        assert!(business.applies_to("personal", None, &["work".to_string()]));
+        assert!(!business.applies_to("personal", Some("Personal"), &["home".to_string()]));
+        assert!(!BusinessDays::default().applies_to("work", Some("work"), &[]));
+    }
+
+    #[test]
+    fn is_working_time_respects_split_days_and_weekends() {
+        let hours = split_schedule();
+        let monday = date(2026, 3, 2);
+        assert!(hours.is_working_time(&at(monday, time(9, 0, 0, 0), "UTC")));
+        assert!(hours.is_working_time(&at(monday, time(11, 59, 0, 0), "UTC")));
+        assert!(!hours.is_working_time(&at(monday, time(12, 30, 0, 0), "UTC")));
+        assert!(!hours.is_working_time(&at(monday, time(17, 0, 0, 0), "UTC")));
+
+        let saturday = date(2026, 3, 7);
+        assert!(!hours.is_working_time(&at(saturday, time(10, 0, 0, 0), "UTC")));
+    }
+
+    #[test]
+    fn next_working_instant_skips_weekends_and_holidays() {
+        let mut hours = split_schedule();
+        let friday = date(2026, 3, 6);
+
+        // Lunch break
+        let next = hours.next_working_instant(&at(friday, time(12, 15, 0, 0), "UTC"));
+        assert_eq!(next, Some(at(friday, time(13, 0, 0, 0), "UTC")));
+
+        // Already working
+        let now = at(friday, time(14, 0, 0, 0), "UTC");
+        assert_eq!(hours.next_working_instant(&now), Some(now));
+
+        // Friday evening to Monday, or Tuesday if Monday is a holiday
+        let evening = at(friday, time(18, 0, 0, 0), "UTC");
+        let next = hours.next_working_instant(&evening);
+        assert_eq!(next, Some(at(date(2026, 3, 9), time(9, 0, 0, 0), "UTC")));
+        hours.add_holiday(date(2026, 3, 9));
+        let next = hours.next_working_instant(&evening);
+        assert_eq!(next, Some(at(date(2026, 3, 10), time(9, 0, 0, 0), "UTC")));
+
+        let never = WorkingHours::empty().next_working_instant(&evening);
+        assert_eq!(never, None);
+    }
+
+    #[test]
+    fn day_window_spans_all_ranges_of_the_day() {
+        let mut hours = split_schedule();
+        let friday = date(2026, 3, 6);
+
+        let window = hours.day_window(&at(friday, time(7, 0, 0, 0), "UTC"));
+        assert_eq!(
+            window,
+            Some((
+                at(friday, time(9, 0, 0, 0), "UTC"),
+                at(friday, time(17, 0, 0, 0), "UTC")
+            ))
+        );
+
+        assert_eq!(
+            hours.day_window(&at(date(2026, 3, 7), time(10, 0, 0, 0), "UTC")),
+            None
+        );
+        hours.add_holiday(friday);
+        assert_eq!(
+            hours.day_window(&at(friday, time(10, 0, 0, 0), "UTC")),
+            None
+        );
+    }
+
+    #[test]
+    fn working_duration_spans_weekends_and_holidays() {
+        let mut hours = split_schedule();
+        let friday = at(date(2026, 3, 6), time(16, 0, 0, 0), "UTC");
+        let tuesday = at(date(2026, 3, 10), time(10, 30, 0, 0), "UTC");
+
+        // 1h on Friday, 7h on Monday, 1.5h on Tuesday
+        let total = hours.working_duration_between(&friday, &tuesday);
+        assert_eq!(total, SignedDuration::from_mins(9 * 60 + 30));
+
+        hours.add_holiday(date(2026, 3, 9));
+        let total = hours.working_duration_between(&friday, &tuesday);
+        assert_eq!(total, SignedDuration::from_mins(2 * 60 + 30));
+
+        let total = hours.working_duration_between(&tuesday, &friday);
+        assert_eq!(total, SignedDuration::ZERO);
+    }
+
+    #[test]
+    fn free_slots_leave_out_busy_time_and_days_off() {
+        let mut hours = split_schedule();
+        let friday = date(2026, 3, 6);
+        let monday = date(2026, 3, 9);
+        let busy = [
+            // Overlapping meetings on Friday, and one over the lunch break
+            (
+                at(friday, time(10, 0, 0, 0), "UTC").timestamp(),
+                at(friday, time(11, 0, 0, 0), "UTC").timestamp(),
+            ),
+            (
+                at(friday, time(11, 30, 0, 0), "UTC").timestamp(),
+                at(friday, time(13, 30, 0, 0), "UTC").timestamp(),
+            ),
+            (
+                at(friday, time(10, 30, 0, 0), "UTC").timestamp(),
+                at(friday, time(11, 15, 0, 0), "UTC").timestamp(),
+            ),
+        ];
+        let from = at(friday, time(9, 30, 0, 0), "UTC");
+        let to = at(monday, time(10, 0, 0, 0), "UTC");
+
+        let slot = |d, (h1, m1), (h2, m2)| {
+            (
+                at(d, time(h1, m1, 0, 0), "UTC"),
+                at(d, time(h2, m2, 0, 0), "UTC"),
+            )
+        };
+        assert_eq!(
+            hours.free_slots(&from, &to, &busy),
+            [
+                slot(friday, (9, 30), (10, 0)),
+                slot(friday, (11, 15), (11, 30)),
+                slot(friday, (13, 30), (17, 0)),
+                slot(monday, (9, 0), (10, 0)),
+            ]
+        );
+
+        hours.add_holiday(monday);
+        let slots = hours.free_slots(&from, &to, &busy);
+        assert_eq!(slots.last(), Some(&slot(friday, (13, 30), (17, 0))));
+        assert!(hours.free_slots(&to, &from, &busy).is_empty());
+    }
+
+    #[test]
+    fn working_duration_counts_elapsed_time_on_dst_days() {
+        let mut hours = WorkingHours::empty();
+        hours.set_day(Weekday::Sunday, vec!["00:00-24:00".parse().unwrap()]);
+        let tz = "America/New_York";
+
+        // Clocks spring forward on 2026-03-08 and fall back on 2026-11-01
+        let spring = date(2026, 3, 8);
+        let total = hours.working_duration_between(
+            &at(spring, time(0, 0, 0, 0), tz),
+            &at(spring.tomorrow().unwrap(), time(0, 0, 0, 0), tz),
+        );
+        assert_eq!(total, SignedDuration::from_hours(23));
+
+        let fall = date(2026, 11, 1);
+        let total = hours.working_duration_between(
+            &at(fall, time(0, 0, 0, 0), tz),
+            &at(fall.tomorrow().unwrap(), time(0, 0, 0, 0), tz),
+        );
+        assert_eq!(total, SignedDuration::from_hours(25));
+
+        // 02:30 does not exist on the spring day, it resolves to 03:30
+        assert!(hours.is_working_time(&at(spring, time(2, 30, 0, 0), tz)));
+    }
+}
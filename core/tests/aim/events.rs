@@ -9,11 +9,16 @@
 use std::collections::HashMap;
 
 use aimcal_core::{
-    Aim, Config, Event, EventConditions, EventDraft, EventPatch, EventStatus, Id, LooseDateTime,
-    Pager, Priority,
+    Aim, AimError, AllDayPolicy, Attendance, BusinessDays, CalendarExport, CategoryDefaults,
+    CheckIn, Collation, Config, DedupeMode, EncryptionConfig, Event, EventConditions, EventDraft,
+    EventPatch, EventStatus, HousekeepingConfig, Id, LooseDateTime, OverdueConfig, Pager, Priority,
+    Profile, RecurFrom, RecurrenceDateEdit, Repeat, RepeatEnd, Rules, SmartLists, Templates,
+    ThisAndFuture, WorkingHours,
 };
+use jiff::SignedDuration;
+use jiff::civil::{Weekday, date};
 
-use crate::common::{setup_temp_dirs, test_event_draft};
+use crate::common::{setup_temp_dirs, test_config_from_dirs, test_event_draft};
 
 #[tokio::test]
 async fn aim_new_event_creates_file_and_database_entry() {
@@ -24,13 +29,39 @@ async fn aim_new_event_creates_file_and_database_entry() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -53,6 +84,95 @@ async fn aim_new_event_creates_file_and_database_entry() {
     assert_eq!(retrieved.summary().as_ref(), "New Meeting");
 }
 
+#[tokio::test]
+async fn aim_new_event_applies_duration_and_rejects_invalid_ones() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.default_event_duration = SignedDuration::from_mins(25);
+    let aim = Aim::new(config).await.unwrap();
+    let start = LooseDateTime::Local(date(2025, 1, 1).at(9, 0, 0, 0).in_tz("UTC").unwrap());
+
+    let draft = EventDraft {
+        start: Some(start.clone()),
+        ..test_event_draft("Default duration")
+    };
+    let event = aim.new_event(draft).await.unwrap();
+    assert_eq!(
+        event.end(),
+        Some(start.clone() + jiff::Span::new().minutes(25))
+    );
+
+    let draft = EventDraft {
+        start: Some(start.clone()),
+        duration: Some(SignedDuration::from_mins(-30)),
+        ..test_event_draft("Negative duration")
+    };
+    let err = aim.new_event(draft).await.err().unwrap();
+    assert!(
+        matches!(
+            err,
+            AimError::InvalidInput {
+                field: "duration",
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn aim_new_event_compiles_repeat_into_recurrence_rule() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.week_start = Weekday::Sunday;
+    let aim = Aim::new(config).await.unwrap();
+    // A Wednesday
+    let start = LooseDateTime::Local(date(2025, 1, 8).at(9, 0, 0, 0).in_tz("UTC").unwrap());
+
+    let mut repeat: Repeat = "every 2 weeks".parse().unwrap();
+    repeat.end = Some(RepeatEnd::Times(10));
+    let draft = EventDraft {
+        start: Some(start.clone()),
+        repeat: Some(repeat),
+        ..test_event_draft("Planning")
+    };
+    let event = aim.new_event(draft).await.unwrap();
+
+    let path = temp_dirs.calendar_path.join(format!("{}.ics", event.uid()));
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(
+        content.contains("RRULE:FREQ=WEEKLY;COUNT=10;INTERVAL=2;BYDAY=WE;WKST=SU\r\n"),
+        "{content}"
+    );
+    let details = aim
+        .get_event_details(&Id::Uid(event.uid().to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        details.recurrence.as_deref(),
+        Some("every 2 weeks on Wed for 10 occurrences")
+    );
+
+    let mut repeat: Repeat = "weekly".parse().unwrap();
+    repeat.month_day = Some(15);
+    let draft = EventDraft {
+        start: Some(start),
+        repeat: Some(repeat),
+        ..test_event_draft("Invalid")
+    };
+    let err = aim.new_event(draft).await.err().unwrap();
+    assert!(
+        matches!(
+            err,
+            AimError::InvalidInput {
+                field: "repeat",
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
 #[tokio::test]
 async fn aim_get_event_resolves_short_id() {
     let temp_dirs = setup_temp_dirs().await.unwrap();
@@ -62,13 +182,39 @@ async fn aim_get_event_resolves_short_id() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -96,13 +242,39 @@ async fn aim_update_event_modifies_file_and_database() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -139,13 +311,39 @@ async fn aim_list_events_returns_all_events() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -162,6 +360,11 @@ async fn aim_list_events_returns_all_events() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 100,
@@ -190,13 +393,39 @@ async fn aim_list_events_with_pagination() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -213,6 +442,11 @@ async fn aim_list_events_with_pagination() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 2,
@@ -229,6 +463,11 @@ async fn aim_list_events_with_pagination() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 2,
@@ -245,6 +484,11 @@ async fn aim_list_events_with_pagination() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 2,
@@ -265,13 +509,39 @@ async fn aim_count_events_returns_correct_count() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -281,6 +551,11 @@ async fn aim_count_events_returns_correct_count() {
             calendar_id: None,
             startable: None,
             cutoff: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         })
         .await
         .unwrap();
@@ -298,6 +573,11 @@ async fn aim_count_events_returns_correct_count() {
             calendar_id: None,
             startable: None,
             cutoff: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         })
         .await
         .unwrap();
@@ -313,13 +593,39 @@ async fn aim_new_event_assigns_sequential_short_ids() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -347,13 +653,39 @@ async fn aim_update_event_clears_optional_fields() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -390,13 +722,39 @@ async fn aim_get_event_returns_error_for_nonexistent() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -414,13 +772,39 @@ async fn aim_update_event_returns_error_for_nonexistent() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -444,13 +828,39 @@ async fn aim_event_status_updates_correctly() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -486,13 +896,39 @@ async fn aim_event_with_custom_datetimes() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -520,13 +956,39 @@ async fn aim_flush_short_ids_removes_mappings() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -561,14 +1023,40 @@ async fn aim_update_db_only_event_without_calendar_path_succeeds() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -613,14 +1101,40 @@ async fn aim_update_db_only_event_with_calendar_path_creates_ics_file() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim_no_calendar = Aim::new(config_no_calendar).await.unwrap();
 
@@ -636,14 +1150,40 @@ async fn aim_update_db_only_event_with_calendar_path_creates_ics_file() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config_with_calendar).await.unwrap();
 
@@ -680,14 +1220,40 @@ async fn aim_update_db_only_event_status_updates_correctly() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -713,3 +1279,323 @@ async fn aim_update_db_only_event_status_updates_correctly() {
         assert_eq!(updated.status(), Some(status));
     }
 }
+
+#[tokio::test]
+async fn aim_update_event_edits_exdates_in_dt_start_timezone() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-sync\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+DTSTART;TZID=Europe/Berlin:20250103T090000\r\n\
+DTEND;TZID=Europe/Berlin:20250103T100000\r\n\
+RRULE:FREQ=WEEKLY\r\n\
+EXDATE;TZID=Europe/Berlin:20250117T090000\r\n\
+EXDATE;TZID=Europe/Berlin:20250110T090000\r\n\
+SUMMARY:Weekly sync\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+    let path = temp_dirs.calendar_path.join("weekly-sync.ics");
+    tokio::fs::write(&path, ics).await.unwrap();
+
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim = Aim::new(config).await.unwrap();
+
+    let patch = EventPatch {
+        ex_dates: vec![
+            RecurrenceDateEdit::Add(LooseDateTime::DateOnly(date(2025, 1, 24))),
+            RecurrenceDateEdit::Remove(LooseDateTime::DateOnly(date(2025, 1, 17))),
+        ],
+        ..Default::default()
+    };
+    let updated = aim
+        .update_event(&Id::Uid("weekly-sync".to_string()), patch)
+        .await
+        .unwrap();
+    assert_eq!(updated.summary(), "Weekly sync");
+
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(
+        content.contains("EXDATE;TZID=Europe/Berlin:20250110T090000,20250124T090000\r\n"),
+        "{content}"
+    );
+    assert_eq!(content.matches("EXDATE").count(), 1, "{content}");
+}
+
+fn exported_ics(export: CalendarExport) -> String {
+    let mut ics = Vec::new();
+    export.write_to(&mut ics).unwrap();
+    String::from_utf8(ics).unwrap()
+}
+
+#[tokio::test]
+async fn aim_export_event_round_trips_as_standalone_calendar() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-sync\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+DTSTART;TZID=Europe/Berlin:20250103T090000\r\n\
+DTEND;TZID=Europe/Berlin:20250103T100000\r\n\
+RRULE:FREQ=WEEKLY\r\n\
+SUMMARY:Weekly sync\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+    tokio::fs::write(temp_dirs.calendar_path.join("weekly-sync.ics"), ics)
+        .await
+        .unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid("weekly-sync".to_string());
+
+    let exported = exported_ics(aim.export_event(&id, None).await.unwrap());
+    let calendars = aimcal_ical::parse(&exported).unwrap();
+    assert_eq!(calendars.len(), 1);
+    for line in [
+        "PRODID:-//yzx9.xyz//aimcal//EN\r\n",
+        "VERSION:2.0\r\n",
+        "METHOD:PUBLISH\r\n",
+        "BEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\n",
+        "DTSTART;TZID=Europe/Berlin:20250103T090000\r\n",
+        "RRULE:FREQ=WEEKLY\r\n",
+    ] {
+        assert!(exported.contains(line), "{exported}");
+    }
+
+    let exported = exported_ics(aim.export_event(&id, Some(date(2025, 7, 4))).await.unwrap());
+    aimcal_ical::parse(&exported).unwrap();
+    assert!(exported.contains("UID:weekly-sync_20250704T090000\r\n"));
+    assert!(exported.contains("DTSTART;TZID=Europe/Berlin:20250704T090000\r\n"));
+    assert!(exported.contains("DTEND;TZID=Europe/Berlin:20250704T100000\r\n"));
+    assert!(!exported.contains("RRULE"), "{exported}");
+    assert!(exported.contains("TZOFFSETTO:+0200\r\n"), "{exported}");
+
+    let err = aim
+        .export_event(&id, Some(date(2025, 7, 5)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AimError::InvalidInput { .. }));
+}
+
+const WEEKLY_SYNC_COUNT: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-sync\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+DTSTART;TZID=Europe/Berlin:20250505T090000\r\n\
+DTEND;TZID=Europe/Berlin:20250505T100000\r\n\
+RRULE:FREQ=WEEKLY;COUNT=8\r\n\
+SUMMARY:Weekly sync\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+fn berlin(date: jiff::civil::Date, hour: i8) -> LooseDateTime {
+    let tz = jiff::tz::TimeZone::get("Europe/Berlin").unwrap();
+    LooseDateTime::Local(date.at(hour, 0, 0, 0).to_zoned(tz).unwrap())
+}
+
+#[tokio::test]
+async fn aim_update_event_from_occurrence_splits_series() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let path = temp_dirs.calendar_path.join("weekly-sync.ics");
+    tokio::fs::write(&path, WEEKLY_SYNC_COUNT).await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid("weekly-sync".to_string());
+
+    let patch = EventPatch {
+        start: Some(Some(berlin(date(2025, 6, 2), 10))),
+        ..Default::default()
+    };
+    let future = aim
+        .update_event_from(&id, date(2025, 6, 2), patch, ThisAndFuture::Split)
+        .await
+        .unwrap();
+    assert_ne!(future.uid(), "weekly-sync");
+    assert_eq!(future.summary(), "Weekly sync");
+    assert_eq!(future.start(), Some(berlin(date(2025, 6, 2), 10)));
+    assert_eq!(future.end(), Some(berlin(date(2025, 6, 2), 11)));
+    assert_eq!(future.split_from().as_deref(), Some("weekly-sync"));
+
+    // Four occurrences before the split, four after it
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(
+        content.contains("RRULE:FREQ=WEEKLY;UNTIL=20250602T065959Z\r\n"),
+        "{content}"
+    );
+    let path = temp_dirs
+        .calendar_path
+        .join(format!("{}.ics", future.uid()));
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(
+        content.contains("RRULE:FREQ=WEEKLY;COUNT=4\r\n"),
+        "{content}"
+    );
+    assert!(
+        content.contains("X-AIM-SPLIT-FROM:weekly-sync\r\n"),
+        "{content}"
+    );
+
+    let details = aim
+        .get_event_details(&Id::Uid(future.uid().to_string()))
+        .await
+        .unwrap();
+    let split_from = details.split_from.unwrap();
+    assert!(split_from.ends_with(" Weekly sync"), "{split_from}");
+
+    // Splitting at the first occurrence edits the whole series instead
+    let patch = EventPatch {
+        summary: Some("Team sync".to_string()),
+        ..Default::default()
+    };
+    let updated = aim
+        .update_event_from(&id, date(2025, 5, 5), patch, ThisAndFuture::Split)
+        .await
+        .unwrap();
+    assert_eq!(updated.uid(), "weekly-sync");
+    assert_eq!(updated.summary(), "Team sync");
+}
+
+#[tokio::test]
+async fn aim_update_event_from_occurrence_writes_override() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let path = temp_dirs.calendar_path.join("weekly-sync.ics");
+    tokio::fs::write(&path, WEEKLY_SYNC_COUNT).await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid("weekly-sync".to_string());
+
+    let patch = EventPatch {
+        summary: Some("Team sync".to_string()),
+        ..Default::default()
+    };
+    aim.update_event_from(&id, date(2025, 6, 2), patch, ThisAndFuture::Override)
+        .await
+        .unwrap();
+
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert_eq!(content.matches("BEGIN:VEVENT").count(), 2, "{content}");
+    assert!(content.contains("RANGE=THISANDFUTURE"), "{content}");
+    assert!(content.contains("SUMMARY:Team sync\r\n"), "{content}");
+
+    // The series itself is unchanged, and the override is kept when it is edited
+    let event = aim.get_event(&id).await.unwrap();
+    assert_eq!(event.summary(), "Weekly sync");
+    let patch = EventPatch {
+        description: Some(Some("Agenda in the wiki".to_string())),
+        ..Default::default()
+    };
+    aim.update_event(&id, patch).await.unwrap();
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert_eq!(content.matches("BEGIN:VEVENT").count(), 2, "{content}");
+
+    // The override does not take the place of the series in the cache
+    aim.close().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let event = aim.get_event(&id).await.unwrap();
+    assert_eq!(event.summary(), "Weekly sync");
+    assert!(event.description().is_some());
+}
+
+#[tokio::test]
+async fn aim_check_in_event_records_attendance_without_touching_status() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config.clone()).await.unwrap();
+    let event = aim.new_event(test_event_draft("Standup")).await.unwrap();
+    let uid = event.uid().to_string();
+    let id = Id::Uid(uid.clone());
+    let path = temp_dirs.calendar_path.join(format!("{uid}.ics"));
+    let unchecked = EventConditions {
+        unchecked: true,
+        ..Default::default()
+    };
+    let pager = Pager {
+        limit: 10,
+        offset: 0,
+    };
+    assert_eq!(aim.list_events(&unchecked, &pager).await.unwrap().len(), 1);
+
+    let err = aim
+        .check_in_event(&id, Attendance::Attended, Some("  "))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, AimError::InvalidInput { field: "note", .. }));
+
+    let checked = aim
+        .check_in_event(&id, Attendance::Attended, Some("ran 20 min over"))
+        .await
+        .unwrap();
+    let expected = CheckIn {
+        attendance: Attendance::Attended,
+        note: Some("ran 20 min over".to_string()),
+    };
+    assert_eq!(checked.check_in(), Some(expected.clone()));
+    assert_eq!(checked.status(), event.status());
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(content.contains("X-AIM-CHECKIN:ATTENDED\r\n"), "{content}");
+    assert!(
+        content.contains("X-AIM-CHECKIN-NOTE:ran 20 min over\r\n"),
+        "{content}"
+    );
+    assert!(
+        aim.list_events(&unchecked, &pager)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The check-in is read back from the calendar when the cache is rebuilt
+    aim.close().await.unwrap();
+    tokio::fs::remove_dir_all(&temp_dirs.state_dir)
+        .await
+        .unwrap();
+    let aim = Aim::new(config).await.unwrap();
+    let event = aim.get_event(&id).await.unwrap();
+    assert_eq!(event.check_in(), Some(expected));
+}
@@ -0,0 +1,236 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Import tests for the Aim application.
+//!
+//! Tests importing events and todos from iCalendar content.
+
+use std::path::Path;
+
+use aimcal_core::{
+    Aim, AimError, DEFAULT_EXPAND_LIMIT, DateTimeAnchor, Event, EventConditions, ForeignFormat, Id,
+    ImportReport, Priority, Todo,
+};
+use jiff::civil::date;
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs};
+
+const CALENDAR: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Export//EN\r
+BEGIN:VEVENT\r
+UID:weekly-review\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250505T100000Z\r
+DTEND:20250505T110000Z\r
+RRULE:FREQ=WEEKLY;COUNT=4\r
+SUMMARY:Weekly review\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:weekly-review\r
+DTSTAMP:20250101T000000Z\r
+RECURRENCE-ID:20250512T100000Z\r
+DTSTART:20250512T140000Z\r
+DTEND:20250512T150000Z\r
+SUMMARY:Weekly review (moved)\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:imported-todo\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Imported todo\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+#[tokio::test]
+async fn aim_import_ics_creates_events_and_todos() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    let report = aim
+        .import_ics(Path::new("<stdin>"), CALENDAR, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        report,
+        ImportReport {
+            events: 1,
+            todos: 1,
+            overrides: 1,
+            skipped: Vec::new(),
+            expanded: None,
+        }
+    );
+
+    let event = aim
+        .get_event(&Id::Uid("weekly-review".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(event.summary(), "Weekly review");
+    assert!(event.short_id().is_some());
+    let todo = aim
+        .get_todo(&Id::Uid("imported-todo".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(todo.summary(), "Imported todo");
+
+    let content = tokio::fs::read_to_string(temp_dirs.calendar_path.join("weekly-review.ics"))
+        .await
+        .unwrap();
+    assert!(
+        content.contains("SUMMARY:Weekly review (moved)"),
+        "{content}"
+    );
+}
+
+#[tokio::test]
+async fn aim_import_ics_skips_existing_items() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    aim.import_ics(Path::new("<stdin>"), CALENDAR, None)
+        .await
+        .unwrap();
+    let report = aim
+        .import_ics(Path::new("<stdin>"), CALENDAR, None)
+        .await
+        .unwrap();
+
+    assert_eq!(report.events + report.todos + report.overrides, 0);
+    assert_eq!(report.skipped, ["weekly-review", "imported-todo"]);
+}
+
+#[tokio::test]
+async fn aim_export_expanded_round_trips_with_a_warning() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    aim.import_ics(Path::new("<stdin>"), CALENDAR, None)
+        .await
+        .unwrap();
+
+    let export = aim
+        .export_expanded(date(2025, 5, 1), date(2025, 5, 31), DEFAULT_EXPAND_LIMIT)
+        .await
+        .unwrap();
+    assert_eq!(export.len(), 4);
+    let mut ics = Vec::new();
+    export.write_to(&mut ics).unwrap();
+    let ics = String::from_utf8(ics).unwrap();
+    assert!(
+        ics.contains("UID:weekly-review-20250512T100000\r\n"),
+        "{ics}"
+    );
+    assert!(ics.contains("DTSTART:20250512T140000Z\r\n"), "{ics}");
+    assert!(ics.contains("SUMMARY:Weekly review (moved)\r\n"), "{ics}");
+
+    let err = aim
+        .export_expanded(date(2025, 5, 1), date(2025, 5, 31), 3)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AimError::InvalidInput { .. }), "{err}");
+
+    let report = aim
+        .import_ics(Path::new("expanded.ics"), &ics, None)
+        .await
+        .unwrap();
+    assert_eq!(report.events, 4);
+    assert_eq!(report.expanded.as_deref(), Some("2025-05-01/2025-05-31"));
+}
+
+#[tokio::test]
+async fn aim_import_ics_rejects_invalid_content() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    let err = aim
+        .import_ics(Path::new("meeting.ics"), "BEGIN:VCALENDAR\r\n", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AimError::Parse { .. }), "{err}");
+}
+
+#[tokio::test]
+async fn aim_import_todos_writes_todos_of_an_export() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    let export = r#"{
+        "projects": [{"id": "p1", "name": "Home"}],
+        "items": [{
+            "id": "42", "project_id": "p1", "content": "Water plants", "priority": 4,
+            "labels": ["garden"],
+            "due": {"date": "2025-01-10", "string": "every monday", "is_recurring": true}
+        }]
+    }"#;
+    let import = ForeignFormat::Todoist
+        .read(Path::new("todoist.json"), export.as_bytes(), false)
+        .unwrap();
+    let report = aim.import_todos(import.todos.clone(), None).await.unwrap();
+    assert_eq!(report.todos, 1);
+
+    let todo = aim
+        .get_todo(&Id::Uid("todoist-42".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(todo.summary(), "Water plants");
+    assert_eq!(todo.priority(), Priority::P1);
+    assert_eq!(todo.categories(), ["Home", "garden"]);
+
+    let content = tokio::fs::read_to_string(temp_dirs.calendar_path.join("todoist-42.ics"))
+        .await
+        .unwrap();
+    assert!(content.contains("RRULE:FREQ=WEEKLY;BYDAY=MO"), "{content}");
+
+    let report = aim.import_todos(import.todos, None).await.unwrap();
+    assert_eq!(report.todos, 0);
+    assert_eq!(report.skipped, ["todoist-42"]);
+}
+
+#[tokio::test]
+async fn aim_import_ics_lists_events_without_dtstart_as_unscheduled() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    // As written by Open-Xchange for an appointment without a date yet
+    let calendar = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Open-Xchange//7.10.6-Rev25//EN\r
+BEGIN:VEVENT\r
+UID:3f1c2a9e-5b7d-4e8a-9c0b-6d2e4f8a1b3c\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Dentist, to be scheduled\r
+CLASS:PUBLIC\r
+TRANSP:OPAQUE\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let report = aim
+        .import_ics(Path::new("<stdin>"), calendar, None)
+        .await
+        .unwrap();
+    assert_eq!(report.events, 1);
+
+    let conds = EventConditions {
+        startable: Some(DateTimeAnchor::today()),
+        cutoff: Some(DateTimeAnchor::today()),
+        ..Default::default()
+    };
+    let events = aim.list_events(&conds, &(100, 0).into()).await.unwrap();
+    let [event] = events.as_slice() else {
+        panic!(
+            "expected the unscheduled event, got {} events",
+            events.len()
+        );
+    };
+    assert_eq!(event.summary(), "Dentist, to be scheduled");
+    assert_eq!(event.start(), None);
+}
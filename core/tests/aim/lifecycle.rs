@@ -10,10 +10,13 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use aimcal_core::{
-    Aim, Config, DateTimeAnchor, Event, EventConditions, EventStatus, Pager, Priority, Todo,
-    TodoConditions,
+    Aim, AimError, AllDayPolicy, BusinessDays, CategoryDefaults, Collation, Config, DateTimeAnchor,
+    DedupeMode, EncryptionConfig, Event, EventConditions, EventStatus, HousekeepingConfig,
+    OverdueConfig, Pager, Priority, Profile, RecurFrom, Rules, SmartLists, Templates, Todo,
+    TodoConditions, WorkingHours,
 };
-use jiff::Zoned;
+use jiff::civil::Weekday;
+use jiff::{SignedDuration, Zoned};
 
 use crate::common::{TestConfigBuilder, setup_temp_dirs};
 
@@ -45,13 +48,39 @@ END:VCALENDAR\r
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -76,6 +105,11 @@ END:VCALENDAR\r
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 100,
@@ -99,13 +133,39 @@ async fn aim_new_creates_empty_state_without_calendar_files() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -116,6 +176,11 @@ async fn aim_new_creates_empty_state_without_calendar_files() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 100,
@@ -136,13 +201,39 @@ async fn aim_now_returns_initial_time() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -164,13 +255,39 @@ async fn aim_refresh_now_updates_current_time() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let mut aim = Aim::new(config).await.unwrap();
 
@@ -189,6 +306,98 @@ async fn aim_refresh_now_updates_current_time() {
     );
 }
 
+#[tokio::test]
+async fn aim_refuses_database_of_newer_aim_unless_read_only_compat() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let ics = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VTODO\r\n\
+UID:todo-1\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+SUMMARY:Listed from a newer database\r\n\
+END:VTODO\r\n\
+END:VCALENDAR\r\n";
+    tokio::fs::write(temp_dirs.calendar_path.join("todo-1.ics"), ics)
+        .await
+        .unwrap();
+    let config = TestConfigBuilder::new()
+        .with_calendar_path(&temp_dirs.calendar_path)
+        .with_state_dir(&temp_dirs.state_dir)
+        .build();
+    Aim::new(config.clone())
+        .await
+        .unwrap()
+        .close()
+        .await
+        .unwrap();
+
+    // As if a newer aim migrated the database past what this build knows
+    let db_path = temp_dirs.state_dir.join("aim.db");
+    let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE schema_meta SET migration = migration + 1, min_aim_version = '99.0.0';")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    // which replaces the record of the schema checked by this build
+    tokio::fs::remove_file(temp_dirs.state_dir.join("aim.db.checked"))
+        .await
+        .unwrap();
+
+    let err = Aim::new(config.clone()).await.unwrap_err();
+    assert!(
+        matches!(&err, AimError::SchemaTooNew { created_by } if created_by == "aim 99.0.0"),
+        "{err}"
+    );
+
+    let aim = Aim::new(Config {
+        read_only_compat: true,
+        ..config
+    })
+    .await
+    .unwrap();
+    assert!(aim.read_only_compat());
+    assert!(
+        aim.startup_notices()
+            .iter()
+            .any(|n| n.contains("created by aim 99.0.0")),
+        "{:?}",
+        aim.startup_notices()
+    );
+    let todos = aim
+        .list_todos(
+            &TodoConditions {
+                calendar_id: None,
+                status: None,
+                due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
+            },
+            &[],
+            &Pager {
+                limit: 100,
+                offset: 0,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].summary().as_ref(), "Listed from a newer database");
+    aim.close().await.unwrap();
+}
+
 #[tokio::test]
 async fn aim_close_cleans_up_database() {
     let temp_dirs = setup_temp_dirs().await.unwrap();
@@ -198,13 +407,39 @@ async fn aim_close_cleans_up_database() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -258,13 +493,39 @@ async fn aim_default_event_draft_creates_draft_with_now() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(1)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -292,13 +553,39 @@ async fn aim_default_todo_draft_includes_config_defaults() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P2,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -311,8 +598,8 @@ async fn aim_default_todo_draft_includes_config_defaults() {
         "Default todo draft should have due date from config"
     );
     assert_eq!(
-        draft.priority,
-        Some(Priority::P2),
+        aim.todo_draft_priority(&draft),
+        Priority::P2,
         "Priority should match config default"
     );
 }
@@ -349,13 +636,39 @@ END:VCALENDAR\r
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -366,6 +679,11 @@ END:VCALENDAR\r
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 100,
@@ -412,13 +730,39 @@ END:VCALENDAR\r
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -429,6 +773,11 @@ END:VCALENDAR\r
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 100,
@@ -443,6 +792,16 @@ END:VCALENDAR\r
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
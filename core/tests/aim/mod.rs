@@ -7,5 +7,7 @@
 //! Tests the main `Aim` struct which coordinates all calendar operations.
 
 mod events;
+mod import;
 mod lifecycle;
+mod resolve;
 mod todos;
@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Id resolution tests for the Aim application.
+//!
+//! Tests resolving short IDs, UIDs and UID prefixes, with and without a kind hint.
+
+use aimcal_core::{Aim, AimError, Event, Id, IdMatch, Kind, LooseDateTime, Todo, TodoPatch};
+use jiff::civil::date;
+
+use crate::common::{
+    TempDirs, TestConfigBuilder, sample_event_ics, sample_todo_ics, setup_temp_dirs,
+};
+
+/// Sets up an Aim with the given events and todos, by UID, keeping the directories alive.
+async fn setup_aim(events: &[&str], todos: &[&str]) -> (Aim, TempDirs) {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    for uid in events {
+        let content = sample_event_ics(uid, &format!("Event {uid}"), "20250115");
+        temp_dirs.create_ics_file(uid, &content).await.unwrap();
+    }
+    for uid in todos {
+        let content = sample_todo_ics(uid, &format!("Todo {uid}"), "20250116");
+        temp_dirs.create_ics_file(uid, &content).await.unwrap();
+    }
+    let config = TestConfigBuilder::new()
+        .with_calendar_path(&temp_dirs.calendar_path)
+        .with_state_dir(&temp_dirs.state_dir)
+        .build();
+    (Aim::new(config).await.unwrap(), temp_dirs)
+}
+
+fn id(id: &str) -> Id {
+    Id::ShortIdOrUid(id.to_string())
+}
+
+async fn resolved_uids(aim: &Aim, input: &str, kind_hint: Option<Kind>) -> Vec<String> {
+    let candidates = aim.resolve_id(&id(input), kind_hint).await.unwrap();
+    candidates.into_iter().map(|c| c.uid).collect()
+}
+
+#[tokio::test]
+async fn aim_resolve_id_matches_short_id() {
+    let (aim, _dirs) = setup_aim(&[], &["todo-aaaaaa"]).await;
+    let todo = aim.get_todo(&id("todo-aaaaaa")).await.unwrap();
+    let short_id = todo.short_id().unwrap().to_string();
+
+    let candidates = aim.resolve_id(&id(&short_id), None).await.unwrap();
+
+    let [candidate] = candidates.as_slice() else {
+        panic!("expected a single candidate, got {candidates:?}");
+    };
+    assert_eq!(candidate.uid, "todo-aaaaaa");
+    assert_eq!(candidate.kind, Kind::Todo);
+    assert_eq!(candidate.matched, IdMatch::ShortId);
+    assert_eq!(candidate.summary, "Todo todo-aaaaaa");
+    assert!(matches!(candidate.date, Some(LooseDateTime::Local(_))));
+}
+
+#[tokio::test]
+async fn aim_resolve_id_matches_exact_uid() {
+    let (aim, _dirs) = setup_aim(&["event-bbbbbb"], &[]).await;
+
+    let candidates = aim.resolve_id(&id("event-bbbbbb"), None).await.unwrap();
+
+    let [candidate] = candidates.as_slice() else {
+        panic!("expected a single candidate, got {candidates:?}");
+    };
+    assert_eq!(candidate.kind, Kind::Event);
+    assert_eq!(candidate.matched, IdMatch::Uid);
+    let start = candidate.date.as_ref().map(LooseDateTime::date);
+    assert_eq!(start, Some(date(2025, 1, 15)));
+}
+
+#[tokio::test]
+async fn aim_resolve_id_matches_unambiguous_uid_prefix() {
+    let (aim, _dirs) = setup_aim(&["event-1234"], &["4f2c9a1e-77b0-4d3e-9a51-0c6e1b2d3f4a"]).await;
+
+    let candidates = aim.resolve_id(&id("4f2c9a"), None).await.unwrap();
+
+    let [candidate] = candidates.as_slice() else {
+        panic!("expected a single candidate, got {candidates:?}");
+    };
+    assert_eq!(candidate.uid, "4f2c9a1e-77b0-4d3e-9a51-0c6e1b2d3f4a");
+    assert_eq!(candidate.matched, IdMatch::UidPrefix);
+}
+
+#[tokio::test]
+async fn aim_resolve_id_ignores_uid_prefix_shorter_than_min_len() {
+    let (aim, _dirs) = setup_aim(&[], &["4f2c9a1e-77b0"]).await;
+
+    let err = aim.resolve_id(&id("4f2c9"), None).await.unwrap_err();
+
+    assert!(
+        matches!(err, AimError::NotFound { kind: "Item", .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn aim_resolve_id_returns_all_candidates_of_ambiguous_prefix() {
+    let (aim, _dirs) = setup_aim(&["meeting-2025-a"], &["meeting-2025-b", "meeting-2025-c"]).await;
+
+    let uids = resolved_uids(&aim, "meeting-2025", None).await;
+
+    assert_eq!(uids, ["meeting-2025-a", "meeting-2025-b", "meeting-2025-c"]);
+    let err = aim.get_todo(&id("meeting-2025")).await.err().unwrap();
+    let AimError::AmbiguousShortId { candidates, .. } = err else {
+        panic!("expected ambiguous id, got {err:?}");
+    };
+    assert_eq!(candidates, ["meeting-2025-b", "meeting-2025-c"]);
+}
+
+#[tokio::test]
+async fn aim_resolve_id_uses_kind_hint_to_narrow_candidates() {
+    let (aim, _dirs) = setup_aim(&["review-event"], &["review-todo"]).await;
+
+    assert_eq!(resolved_uids(&aim, "review", None).await.len(), 2);
+    assert_eq!(
+        resolved_uids(&aim, "review", Some(Kind::Todo)).await,
+        ["review-todo"]
+    );
+    assert_eq!(
+        resolved_uids(&aim, "review", Some(Kind::Event)).await,
+        ["review-event"]
+    );
+
+    // Kind-specific operations pass the hint themselves
+    let todo = aim.get_todo(&id("review")).await.unwrap();
+    assert_eq!(todo.uid(), "review-todo");
+    let event = aim.get_event(&id("review")).await.unwrap();
+    assert_eq!(event.uid(), "review-event");
+}
+
+#[tokio::test]
+async fn aim_resolve_id_prefers_short_id_of_hinted_kind() {
+    let (aim, _dirs) = setup_aim(&["1"], &["todo-cccccc"]).await;
+    // The first item handed out gets short ID 1, which is also the UID of the event
+    let todo = aim.get_todo(&id("todo-cccccc")).await.unwrap();
+    assert_eq!(todo.short_id().map(|a| a.get()), Some(1));
+
+    assert_eq!(resolved_uids(&aim, "1", None).await, ["todo-cccccc", "1"]);
+    assert_eq!(
+        resolved_uids(&aim, "1", Some(Kind::Todo)).await,
+        ["todo-cccccc"]
+    );
+    assert_eq!(resolved_uids(&aim, "1", Some(Kind::Event)).await, ["1"]);
+
+    let patch = TodoPatch {
+        summary: Some("Done by short ID".to_string()),
+        ..Default::default()
+    };
+    let updated = aim.update_todo(&id("1"), patch).await.unwrap();
+    assert_eq!(updated.uid(), "todo-cccccc");
+    let err = aim.get_kind(&id("1")).await.unwrap_err();
+    assert!(matches!(err, AimError::AmbiguousShortId { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn aim_resolve_id_prefers_exact_uid_to_prefix() {
+    let (aim, _dirs) = setup_aim(&[], &["weekly", "weekly-report"]).await;
+
+    assert_eq!(resolved_uids(&aim, "weekly", None).await, ["weekly"]);
+    assert_eq!(
+        resolved_uids(&aim, "weekly-r", None).await,
+        ["weekly-report"]
+    );
+}
+
+#[tokio::test]
+async fn aim_resolve_id_falls_back_to_prefix_of_hinted_kind() {
+    let (aim, _dirs) = setup_aim(&["standup"], &["standup-notes"]).await;
+
+    assert_eq!(resolved_uids(&aim, "standup", None).await, ["standup"]);
+    assert_eq!(
+        resolved_uids(&aim, "standup", Some(Kind::Todo)).await,
+        ["standup-notes"]
+    );
+}
+
+#[tokio::test]
+async fn aim_resolve_id_reports_not_found_for_hinted_kind() {
+    let (aim, _dirs) = setup_aim(&["event-dddddd"], &[]).await;
+
+    let err = aim
+        .resolve_id(&id("event-dddddd"), Some(Kind::Todo))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, AimError::NotFound { kind: "Todo", .. }),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn aim_resolve_id_with_uid_only_matches_exact_uid() {
+    let (aim, _dirs) = setup_aim(&[], &["todo-eeeeee"]).await;
+    let todo = aim.get_todo(&id("todo-eeeeee")).await.unwrap();
+    let short_id = todo.short_id().unwrap().to_string();
+
+    let exact = aim
+        .resolve_id(&Id::Uid("todo-eeeeee".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(exact.len(), 1);
+    let prefix = aim
+        .resolve_id(&Id::Uid("todo-eeee".to_string()), None)
+        .await;
+    assert!(matches!(prefix, Err(AimError::NotFound { .. })));
+    let by_short_id = aim.resolve_id(&Id::Uid(short_id), None).await;
+    assert!(matches!(by_short_id, Err(AimError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn aim_get_kind_resolves_uid_prefix() {
+    let (aim, _dirs) = setup_aim(&["planning-event"], &[]).await;
+
+    assert_eq!(aim.get_kind(&id("planning")).await.unwrap(), Kind::Event);
+}
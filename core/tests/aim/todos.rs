@@ -9,11 +9,18 @@
 use std::collections::HashMap;
 
 use aimcal_core::{
-    Aim, Config, DateTimeAnchor, Id, Pager, Priority, SortOrder, Todo, TodoConditions, TodoDraft,
-    TodoPatch, TodoSort, TodoStatus,
+    Aim, AimError, AllDayPolicy, BusinessDays, CategoryDefaults, Collation, Config, DateTimeAnchor,
+    DedupeMode, ENCRYPTED_NO_KEY, EncryptionConfig, HousekeepingConfig, Id, LooseDateTime,
+    OverdueConfig, Pager, Priority, Profile, RecurFrom, Rules, SmartLists, SortOrder,
+    SubtaskProgress, Templates, Todo, TodoConditions, TodoDraft, TodoPatch, TodoSort, TodoStatus,
+    WorkingHours, generate_key,
 };
+use jiff::SignedDuration;
+use jiff::civil::{Weekday, datetime};
 
-use crate::common::{setup_temp_dirs, test_todo_draft};
+use crate::common::{
+    TestConfigBuilder, sample_todo_ics, setup_temp_dirs, test_config_from_dirs, test_todo_draft,
+};
 
 #[tokio::test]
 async fn aim_new_todo_creates_file_and_database_entry() {
@@ -24,13 +31,39 @@ async fn aim_new_todo_creates_file_and_database_entry() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -62,13 +95,39 @@ async fn aim_get_todo_resolves_short_id() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -96,13 +155,39 @@ async fn aim_update_todo_modifies_file_and_database() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -136,13 +221,39 @@ async fn aim_list_todos_returns_all_todos() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -159,6 +270,16 @@ async fn aim_list_todos_returns_all_todos() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -188,13 +309,39 @@ async fn aim_list_todos_with_pagination() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -211,6 +358,16 @@ async fn aim_list_todos_with_pagination() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -228,6 +385,16 @@ async fn aim_list_todos_with_pagination() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -245,6 +412,16 @@ async fn aim_list_todos_with_pagination() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -266,13 +443,39 @@ async fn aim_count_todos_returns_correct_count() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -282,6 +485,16 @@ async fn aim_count_todos_returns_correct_count() {
             calendar_id: None,
             status: None,
             due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         })
         .await
         .unwrap();
@@ -299,6 +512,16 @@ async fn aim_count_todos_returns_correct_count() {
             calendar_id: None,
             status: None,
             due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         })
         .await
         .unwrap();
@@ -314,19 +537,45 @@ async fn aim_default_todo_draft_uses_config_defaults() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P2,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
     let draft = aim.default_todo_draft().unwrap();
 
-    assert_eq!(draft.priority, Some(Priority::P2));
+    assert_eq!(aim.todo_draft_priority(&draft), Priority::P2);
     assert!(draft.due.is_some(), "Should have due date from config");
 }
 
@@ -339,13 +588,39 @@ async fn aim_update_todo_clears_optional_fields() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -379,13 +654,39 @@ async fn aim_update_todo_status_to_completed_sets_completed_timestamp() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -422,13 +723,39 @@ async fn aim_update_todo_status_from_completed_clears_completed_timestamp() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -462,6 +789,89 @@ async fn aim_update_todo_status_from_completed_clears_completed_timestamp() {
     assert_eq!(updated.status(), TodoStatus::NeedsAction);
 }
 
+#[tokio::test]
+async fn aim_update_todo_completes_at_given_time() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    tokio::fs::write(
+        temp_dirs.calendar_path.join("backdated.ics"),
+        sample_todo_ics("backdated", "Backdated", "20250115"),
+    )
+    .await
+    .unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid("backdated".to_string());
+    let complete_at = |at: jiff::Zoned| TodoPatch {
+        status: Some(TodoStatus::Completed),
+        completed_at: Some(at),
+        ..Default::default()
+    };
+
+    // Neither in the future nor before the todo was created
+    let future = aim
+        .now()
+        .checked_add(SignedDuration::from_hours(1))
+        .unwrap();
+    let before = datetime(2025, 1, 14, 12, 0, 0, 0).in_tz("UTC").unwrap();
+    for at in [future, before] {
+        let err = aim.update_todo(&id, complete_at(at)).await.err().unwrap();
+        assert!(
+            matches!(
+                err,
+                AimError::InvalidInput {
+                    field: "completed at",
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+    }
+    let uncompleting = TodoPatch {
+        status: Some(TodoStatus::InProcess),
+        completed_at: Some(aim.now()),
+        ..Default::default()
+    };
+    assert!(aim.update_todo(&id, uncompleting).await.is_err());
+
+    let at = datetime(2025, 1, 16, 18, 0, 0, 0).in_tz("UTC").unwrap();
+    let todo = aim.update_todo(&id, complete_at(at.clone())).await.unwrap();
+    assert_eq!(todo.status(), TodoStatus::Completed);
+    assert_eq!(todo.completed(), Some(at));
+}
+
+#[tokio::test]
+async fn aim_update_todo_completes_after_creation_rather_than_last_edit() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let ics = sample_todo_ics("edited", "Edited", "20250115").replace(
+        "DTSTAMP:20250115T120000Z",
+        "CREATED:20250101T090000Z\nDTSTAMP:20250120T120000Z",
+    );
+    tokio::fs::write(temp_dirs.calendar_path.join("edited.ics"), ics)
+        .await
+        .unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid("edited".to_string());
+    let complete_at = |at: jiff::Zoned| TodoPatch {
+        status: Some(TodoStatus::Completed),
+        completed_at: Some(at),
+        ..Default::default()
+    };
+
+    let before = datetime(2024, 12, 31, 18, 0, 0, 0).in_tz("UTC").unwrap();
+    let err = aim
+        .update_todo(&id, complete_at(before))
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("2025-01-01T09:00:00Z"), "{err}");
+
+    // Between CREATED and the DTSTAMP of the last edit
+    let at = datetime(2025, 1, 16, 18, 0, 0, 0).in_tz("UTC").unwrap();
+    let todo = aim.update_todo(&id, complete_at(at.clone())).await.unwrap();
+    assert_eq!(todo.completed(), Some(at));
+}
+
 #[tokio::test]
 async fn aim_list_todos_with_status_filter() {
     let temp_dirs = setup_temp_dirs().await.unwrap();
@@ -471,13 +881,39 @@ async fn aim_list_todos_with_status_filter() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -502,6 +938,16 @@ async fn aim_list_todos_with_status_filter() {
         calendar_id: None,
         status: Some(TodoStatus::NeedsAction),
         due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
     };
     let todos = aim
         .list_todos(
@@ -527,13 +973,39 @@ async fn aim_list_todos_with_priority_sort() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -558,6 +1030,16 @@ async fn aim_list_todos_with_priority_sort() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort,
             &Pager {
@@ -575,6 +1057,128 @@ async fn aim_list_todos_with_priority_sort() {
     assert_eq!(todos[0].priority(), Priority::P2);
 }
 
+#[tokio::test]
+async fn aim_get_todo_details_reads_store() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.new_todo(test_todo_draft("Water plants")).await.unwrap();
+    let uid = todo.uid().as_ref().to_string();
+
+    // Add properties only kept in the file
+    let path = temp_dirs.calendar_path.join(format!("{uid}.ics"));
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    let extra = "CATEGORIES:GARDEN\r\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE\r\n\
+BEGIN:VALARM\r\nACTION:DISPLAY\r\nDESCRIPTION:Water\r\nTRIGGER:-PT15M\r\nEND:VALARM\r\n\
+END:VTODO";
+    let content = content.replacen("END:VTODO", extra, 1);
+    tokio::fs::write(&path, content).await.unwrap();
+
+    let details = aim.get_todo_details(&Id::Uid(uid.clone())).await.unwrap();
+    assert_eq!(details.calendar_id, "default");
+    assert!(details.resource_id.is_some_and(|r| r.contains(&uid)));
+    assert_eq!(details.categories, ["GARDEN"]);
+    assert_eq!(
+        details.recurrence.as_deref(),
+        Some("every 2 weeks on Mon, Wed")
+    );
+    assert_eq!(details.alarms, ["display 15 minutes before start"]);
+    let raw = details.raw.unwrap();
+    assert!(raw.starts_with("BEGIN:VTODO"), "{raw}");
+    assert!(raw.ends_with("END:VTODO"), "{raw}");
+    assert!(raw.contains("SUMMARY:Water plants"), "{raw}");
+}
+
+#[tokio::test]
+async fn aim_update_todo_completing_recurring_todo_rolls_it_forward() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.recur_from = RecurFrom::Completion;
+    let aim = Aim::new(config).await.unwrap();
+
+    // Due on the 31st of each month, from the schedule by its own choice or from the config
+    let mut uids = Vec::new();
+    for (summary, extra) in [
+        ("Pay rent", "X-AIM-RECUR-FROM:SCHEDULE\r\n"),
+        ("Water plants", ""),
+    ] {
+        let todo = aim.new_todo(test_todo_draft(summary)).await.unwrap();
+        let uid = todo.uid().as_ref().to_string();
+        let path = temp_dirs.calendar_path.join(format!("{uid}.ics"));
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        let extra = format!("DUE;VALUE=DATE:20200131\r\nRRULE:FREQ=MONTHLY\r\n{extra}END:VTODO");
+        tokio::fs::write(&path, content.replacen("END:VTODO", &extra, 1))
+            .await
+            .unwrap();
+        uids.push(uid);
+    }
+
+    let complete = || TodoPatch {
+        status: Some(TodoStatus::Completed),
+        ..Default::default()
+    };
+    let scheduled = aim
+        .update_todo(&Id::Uid(uids[0].clone()), complete())
+        .await
+        .unwrap();
+    assert_eq!(scheduled.status(), TodoStatus::NeedsAction);
+    assert!(scheduled.completed().is_none());
+    assert_eq!(
+        scheduled.due(),
+        Some(LooseDateTime::DateOnly(jiff::civil::date(2020, 3, 31)))
+    );
+
+    let today = jiff::Zoned::now().date();
+    let completed = aim
+        .update_todo(&Id::Uid(uids[1].clone()), complete())
+        .await
+        .unwrap();
+    assert_eq!(completed.status(), TodoStatus::NeedsAction);
+    let Some(LooseDateTime::DateOnly(due)) = completed.due() else {
+        panic!("expected a due date");
+    };
+    assert!(due > today, "{due} is after {today}");
+}
+
 #[tokio::test]
 async fn aim_get_todo_returns_error_for_nonexistent() {
     let temp_dirs = setup_temp_dirs().await.unwrap();
@@ -584,13 +1188,39 @@ async fn aim_get_todo_returns_error_for_nonexistent() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -608,13 +1238,39 @@ async fn aim_update_todo_returns_error_for_nonexistent() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -640,14 +1296,40 @@ async fn aim_update_db_only_todo_without_calendar_path_succeeds() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -689,14 +1371,40 @@ async fn aim_update_db_only_todo_with_calendar_path_creates_ics_file() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim_no_calendar = Aim::new(config_no_calendar).await.unwrap();
 
@@ -712,14 +1420,40 @@ async fn aim_update_db_only_todo_with_calendar_path_creates_ics_file() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config_with_calendar).await.unwrap();
 
@@ -753,14 +1487,40 @@ async fn aim_update_db_only_todo_status_to_completed_sets_timestamp() {
         calendar_path: None,
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         stores: HashMap::new(),
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -787,3 +1547,931 @@ async fn aim_update_db_only_todo_status_to_completed_sets_timestamp() {
     );
     assert_eq!(updated.status(), TodoStatus::Completed);
 }
+
+#[tokio::test]
+async fn aim_snooze_todo_shifts_due_and_records_original() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim = Aim::new(config).await.unwrap();
+
+    // Create todo due at 10:00
+    let due = LooseDateTime::Floating(datetime(2025, 3, 3, 10, 0, 0, 0));
+    let draft = TodoDraft {
+        due: Some(due.clone()),
+        ..test_todo_draft("Snoozed Task")
+    };
+    let todo = aim.new_todo(draft).await.unwrap();
+    let id = Id::Uid(todo.uid().as_ref().to_string());
+
+    // Snooze twice, keeping the time of day
+    let snoozed = aim
+        .snooze_todo(&id, DateTimeAnchor::InDays(3))
+        .await
+        .unwrap();
+    let first = LooseDateTime::Floating(datetime(2025, 3, 6, 10, 0, 0, 0));
+    assert_eq!(snoozed.due(), Some(first.clone()));
+    assert_eq!(snoozed.snoozed_from(), vec![due.clone()]);
+
+    let snoozed = aim
+        .snooze_todo(&id, DateTimeAnchor::InDays(7))
+        .await
+        .unwrap();
+    assert_eq!(
+        snoozed.due(),
+        Some(LooseDateTime::Floating(datetime(2025, 3, 13, 10, 0, 0, 0)))
+    );
+    assert_eq!(snoozed.snoozed_from(), vec![due, first]);
+}
+
+#[tokio::test]
+async fn aim_snooze_todo_without_due_uses_now() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim = Aim::new(config).await.unwrap();
+
+    let todo = aim.new_todo(test_todo_draft("No Due")).await.unwrap();
+    let id = Id::Uid(todo.uid().as_ref().to_string());
+
+    let snoozed = aim
+        .snooze_todo(&id, DateTimeAnchor::Relative(60 * 60))
+        .await
+        .unwrap();
+
+    let expected = LooseDateTime::Local(aim.now()) + jiff::Span::new().hours(1);
+    assert_eq!(snoozed.due(), Some(expected));
+    assert!(
+        snoozed.snoozed_from().is_empty(),
+        "Nothing to record without a previous due"
+    );
+}
+
+#[tokio::test]
+async fn aim_defer_todo_sets_due_and_records_original() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim = Aim::new(config).await.unwrap();
+
+    let due = LooseDateTime::Floating(datetime(2025, 3, 3, 10, 0, 0, 0));
+    let draft = TodoDraft {
+        due: Some(due.clone()),
+        ..test_todo_draft("Deferred Task")
+    };
+    let todo = aim.new_todo(draft).await.unwrap();
+    let id = Id::Uid(todo.uid().as_ref().to_string());
+
+    let anchor = "2025-04-01".parse::<DateTimeAnchor>().unwrap();
+    let deferred = aim.defer_todo(&id, anchor).await.unwrap();
+
+    assert_eq!(
+        deferred.due(),
+        Some(LooseDateTime::DateOnly(jiff::civil::date(2025, 4, 1)))
+    );
+    assert_eq!(deferred.snoozed_from(), vec![due]);
+}
+
+#[tokio::test]
+async fn aim_get_todo_missing_returns_not_found() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = TestConfigBuilder::new()
+        .with_calendar_path(&temp_dirs.calendar_path)
+        .with_state_dir(&temp_dirs.state_dir)
+        .build();
+    let aim = Aim::new(config).await.unwrap();
+
+    let id = Id::ShortIdOrUid("missing".to_string());
+    let err = aim.get_todo(&id).await.err().unwrap();
+    assert!(
+        matches!(&err, AimError::NotFound { kind: "Todo", id } if id == "missing"),
+        "{err:?}"
+    );
+
+    let err = aim.get_kind(&id).await.unwrap_err();
+    assert!(matches!(err, AimError::NotFound { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn aim_get_todo_short_id_matching_another_uid_is_ambiguous() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    tokio::fs::write(
+        temp_dirs.calendar_path.join("1.ics"),
+        sample_todo_ics("1", "Numeric UID", "20250115"),
+    )
+    .await
+    .unwrap();
+    let config = TestConfigBuilder::new()
+        .with_calendar_path(&temp_dirs.calendar_path)
+        .with_state_dir(&temp_dirs.state_dir)
+        .build();
+    let aim = Aim::new(config).await.unwrap();
+
+    // The first todo handed out gets short ID 1
+    let todo = aim.new_todo(test_todo_draft("Short ID 1")).await.unwrap();
+    assert_eq!(todo.short_id().map(|a| a.get()), Some(1));
+
+    let err = aim
+        .get_todo(&Id::ShortIdOrUid("1".to_string()))
+        .await
+        .err()
+        .unwrap();
+    let AimError::AmbiguousShortId { candidates, .. } = err else {
+        panic!("expected ambiguous short id, got {err:?}");
+    };
+    assert_eq!(candidates, vec![todo.uid().to_string(), "1".to_string()]);
+}
+
+#[tokio::test]
+async fn aim_list_todos_sorts_by_summary_with_configured_collation() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let list = |aim: Aim| async move {
+        for summary in ["Zoo", "Ärzte anrufen", "apotheke"] {
+            aim.new_todo(test_todo_draft(summary)).await.unwrap();
+        }
+        let conds = TodoConditions {
+            calendar_id: None,
+            status: None,
+            due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let pager = Pager {
+            limit: 100,
+            offset: 0,
+        };
+        let sort = [TodoSort::Summary(SortOrder::Asc)];
+        let todos = aim.list_todos(&conds, &sort, &pager).await.unwrap();
+        todos
+            .iter()
+            .map(|t| t.summary().into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    assert_eq!(
+        list(Aim::new(config).await.unwrap()).await,
+        ["apotheke", "Ärzte anrufen", "Zoo"]
+    );
+
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.collation = Collation::Binary;
+    assert_eq!(
+        list(Aim::new(config).await.unwrap()).await,
+        ["Zoo", "apotheke", "Ärzte anrufen"]
+    );
+}
+
+#[tokio::test]
+async fn aim_list_todos_returns_categories() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let calendar = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Export//EN\r
+BEGIN:VTODO\r
+UID:categorized\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Book appointment\r
+CATEGORIES:Ärzte\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    aim.import_ics(std::path::Path::new("<stdin>"), calendar, None)
+        .await
+        .unwrap();
+
+    let conds = TodoConditions {
+        calendar_id: None,
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let todos = aim.list_todos(&conds, &[], &pager).await.unwrap();
+    let categories: Vec<_> = todos.iter().map(Todo::categories).collect();
+    assert_eq!(categories, [vec!["Ärzte"]]);
+}
+
+#[tokio::test]
+async fn aim_todo_meta_round_trips_and_filters_listings() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let calendar = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Export//EN\r
+BEGIN:VTODO\r
+UID:tracked\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Fix login\r
+X-AIM-META-CLIENT:Acme\\, Inc.\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:untracked\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Water plants\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    aim.import_ics(std::path::Path::new("<stdin>"), calendar, None)
+        .await
+        .unwrap();
+    let id = Id::Uid("tracked".to_string());
+
+    // Metadata read at ingest, then set in any order and case
+    let meta = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    };
+    assert_eq!(
+        aim.get_meta(&id).await.unwrap(),
+        meta(&[("client", "Acme, Inc.")])
+    );
+    aim.set_meta(&id, "Ticket", "JIRA-123").await.unwrap();
+    aim.set_meta(&id, "due soon", "yes").await.unwrap();
+    let set = aim.unset_meta(&id, "DUE-SOON").await.unwrap();
+    assert_eq!(
+        set,
+        meta(&[("client", "Acme, Inc."), ("ticket", "JIRA-123")])
+    );
+    assert_eq!(aim.get_meta(&id).await.unwrap(), set);
+
+    // Invalid keys and values are rejected
+    assert!(matches!(
+        aim.set_meta(&id, "!!", "x").await,
+        Err(AimError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        aim.set_meta(&id, "note", "two\nlines").await,
+        Err(AimError::InvalidInput { .. })
+    ));
+
+    // Listings filter on the cached metadata, with case-insensitive keys
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let list = async |pairs: &[(&str, &str)]| {
+        let conds = TodoConditions {
+            calendar_id: None,
+            status: None,
+            due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: meta(pairs),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let todos = aim.list_todos(&conds, &[], &pager).await.unwrap();
+        todos
+            .iter()
+            .map(|t| t.uid().into_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(list(&[("TICKET", "JIRA-123")]).await, ["tracked"]);
+    assert!(list(&[("ticket", "JIRA-1")]).await.is_empty());
+
+    // The details have the metadata, written sorted by key
+    let details = aim.get_todo_details(&id).await.unwrap();
+    assert_eq!(details.meta, set);
+    let raw = details.raw.unwrap();
+    let client = raw.find("X-AIM-META-CLIENT:Acme\\, Inc.").unwrap();
+    let ticket = raw.find("X-AIM-META-TICKET:JIRA-123").unwrap();
+    assert!(client < ticket);
+    assert!(!raw.contains("X-AIM-META-DUE-SOON"));
+}
+
+#[tokio::test]
+async fn aim_list_todos_returns_parents_and_counts_subtasks() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let calendar = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Export//EN\r
+BEGIN:VTODO\r
+UID:trip\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Plan trip\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:trip-flights\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Book flights\r
+RELATED-TO:trip\r
+STATUS:COMPLETED\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:trip-hotel\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Book hotel\r
+RELATED-TO;RELTYPE=PARENT:trip\r
+END:VTODO\r
+BEGIN:VTODO\r
+UID:trip-notes\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Notes\r
+RELATED-TO;RELTYPE=SIBLING:trip\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    aim.import_ics(std::path::Path::new("<stdin>"), calendar, None)
+        .await
+        .unwrap();
+
+    let conds = TodoConditions {
+        calendar_id: None,
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let todos = aim.list_todos(&conds, &[], &pager).await.unwrap();
+    let mut parents: Vec<_> = todos
+        .iter()
+        .map(|t| (t.uid().into_owned(), t.parent_uid().map(|p| p.into_owned())))
+        .collect();
+    parents.sort();
+    assert_eq!(
+        parents,
+        [
+            ("trip".to_string(), None),
+            ("trip-flights".to_string(), Some("trip".to_string())),
+            ("trip-hotel".to_string(), Some("trip".to_string())),
+            ("trip-notes".to_string(), None),
+        ]
+    );
+
+    let counts = aim.count_subtasks(&["trip", "trip-hotel"]).await.unwrap();
+    assert_eq!(counts.len(), 1);
+    assert_eq!(
+        counts.get("trip"),
+        Some(&SubtaskProgress { done: 1, total: 2 })
+    );
+}
+
+#[tokio::test]
+async fn aim_new_todo_encrypts_description_with_configured_key() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.encryption.key = Some(generate_key());
+    let aim = Aim::new(config).await.unwrap();
+
+    let mut draft = test_todo_draft("Secret");
+    draft.description = Some("Door code 1234".to_string());
+    draft.encrypt_description = true;
+    let todo = aim.new_todo(draft).await.unwrap();
+    assert_eq!(todo.description().as_deref(), Some("Door code 1234"));
+
+    let uid = todo.uid().into_owned();
+    let ics = std::fs::read_to_string(temp_dirs.calendar_path.join(format!("{uid}.ics"))).unwrap();
+    assert!(ics.contains("X-AIM-ENCRYPTED-DESC:"));
+    assert!(!ics.contains("DESCRIPTION:"));
+    assert!(!ics.contains("Door code"));
+
+    let retrieved = aim.get_todo(&Id::Uid(uid.clone())).await.unwrap();
+    assert_eq!(retrieved.description().as_deref(), Some("Door code 1234"));
+
+    // Without the key the description is a placeholder
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let retrieved = aim.get_todo(&Id::Uid(uid)).await.unwrap();
+    assert_eq!(retrieved.description().as_deref(), Some(ENCRYPTED_NO_KEY));
+}
+
+#[tokio::test]
+async fn aim_new_todo_without_key_refuses_to_encrypt() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    let mut draft = test_todo_draft("Secret");
+    draft.description = Some("Door code 1234".to_string());
+    draft.encrypt_description = true;
+    let result = aim.new_todo(draft).await;
+    assert!(matches!(
+        result,
+        Err(AimError::InvalidInput {
+            field: "description",
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn aim_rekey_descriptions_seals_with_current_key() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let old_key = generate_key();
+    let new_key = generate_key();
+
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.encryption.key = Some(old_key.clone());
+    let aim = Aim::new(config).await.unwrap();
+    let mut draft = test_todo_draft("Secret");
+    draft.description = Some("Door code 1234".to_string());
+    draft.encrypt_description = true;
+    let uid = aim.new_todo(draft).await.unwrap().uid().into_owned();
+    aim.new_todo(test_todo_draft("Plain")).await.unwrap();
+
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.encryption = EncryptionConfig {
+        key: Some(new_key.clone()),
+        previous_keys: vec![old_key],
+    };
+    let aim = Aim::new(config).await.unwrap();
+    let report = aim.rekey_descriptions().await.unwrap();
+    assert_eq!(report.rekeyed, 1);
+    assert_eq!(report.up_to_date, 0);
+    assert!(report.skipped.is_empty());
+    let report = aim.rekey_descriptions().await.unwrap();
+    assert_eq!(report.rekeyed, 0);
+    assert_eq!(report.up_to_date, 1);
+
+    // The previous key is no longer needed
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.encryption.key = Some(new_key);
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.get_todo(&Id::Uid(uid)).await.unwrap();
+    assert_eq!(todo.description().as_deref(), Some("Door code 1234"));
+}
+
+#[tokio::test]
+async fn aim_capture_todo_stages_until_sync() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::builder(config).skip_sync().build().await.unwrap();
+
+    let todo = aim.capture_todo("  Call the bank ").await.unwrap();
+    assert_eq!(todo.summary().as_ref(), "Call the bank");
+    assert!(todo.in_inbox());
+    assert!(todo.due().is_none());
+    let uid = todo.uid().into_owned();
+    let path = temp_dirs.calendar_path.join(format!("{uid}.ics"));
+    assert!(!path.exists(), "captured todo should only be staged");
+
+    let conds = TodoConditions {
+        calendar_id: None,
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: true,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let inbox = aim.list_todos(&conds, &[], &pager).await.unwrap();
+    assert_eq!(inbox.len(), 1);
+
+    // Details are read from the staged todo, as the store has none yet
+    let details = aim.get_todo_details(&Id::Uid(uid.clone())).await.unwrap();
+    assert_eq!(details.calendar_id, "default");
+    assert_eq!(details.resource_id, None);
+
+    aim.sync().await.unwrap();
+    assert!(path.exists(), "sync should push the captured todo");
+    let todo = aim.get_todo(&Id::Uid(uid)).await.unwrap();
+    assert!(todo.in_inbox());
+}
+
+#[tokio::test]
+async fn aim_capture_todo_rejects_empty_summary() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::builder(config).skip_sync().build().await.unwrap();
+
+    let result = aim.capture_todo("   ").await;
+    assert!(matches!(
+        result,
+        Err(AimError::InvalidInput {
+            field: "summary",
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn aim_triage_clears_inbox() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::builder(config).skip_sync().build().await.unwrap();
+    let uid = aim
+        .capture_todo("Renew passport")
+        .await
+        .unwrap()
+        .uid()
+        .into_owned();
+
+    // Edited while staged
+    let patch = TodoPatch {
+        priority: Some(Priority::P2),
+        ..Default::default()
+    };
+    let todo = aim.update_todo(&Id::Uid(uid.clone()), patch).await.unwrap();
+    assert!(!todo.in_inbox());
+
+    let conds = TodoConditions {
+        calendar_id: None,
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: true,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    assert!(
+        aim.list_todos(&conds, &[], &pager)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The triaged todo is pushed as edited
+    aim.sync().await.unwrap();
+    let todo = aim.get_todo(&Id::Uid(uid)).await.unwrap();
+    assert!(!todo.in_inbox());
+    assert_eq!(todo.priority(), Priority::P2);
+}
+
+/// Conditions of the default listing, hiding todos waiting on someone.
+fn waiting_conditions(
+    hide_waiting: bool,
+    waiting: bool,
+    waiting_for: Option<&str>,
+) -> TodoConditions {
+    TodoConditions {
+        calendar_id: None,
+        status: Some(TodoStatus::NeedsAction),
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting,
+        waiting,
+        waiting_for: waiting_for.map(ToString::to_string),
+        categories: Vec::new(),
+        undated: false,
+    }
+}
+
+async fn list_summaries(aim: &Aim, conds: &TodoConditions) -> Vec<String> {
+    let sort = [TodoSort::Summary(SortOrder::Asc)];
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    let todos = aim.list_todos(conds, &sort, &pager).await.unwrap();
+    todos.iter().map(|t| t.summary().into_owned()).collect()
+}
+
+#[tokio::test]
+async fn aim_wait_todo_hides_it_until_the_follow_up() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let mut uids = Vec::new();
+    for summary in ["Contract review", "Design mockups", "Invoice", "Taxes"] {
+        let todo = aim.new_todo(test_todo_draft(summary)).await.unwrap();
+        uids.push(Id::Uid(todo.uid().into_owned()));
+    }
+
+    let todo = aim
+        .wait_todo(
+            &uids[0],
+            " Alice Smith ",
+            Some(DateTimeAnchor::InDays(3)),
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(todo.waiting_on().as_deref(), Some("Alice Smith"));
+    assert!(todo.follow_up().is_some());
+    assert_eq!(todo.status(), TodoStatus::NeedsAction);
+    // Followed up already, so it is back in the default listing
+    aim.wait_todo(&uids[1], "Bob", Some(DateTimeAnchor::InDays(-1)), false)
+        .await
+        .unwrap();
+    // Without a follow-up, it waits until cleared
+    let todo = aim.wait_todo(&uids[2], "alice", None, false).await.unwrap();
+    assert!(todo.follow_up().is_none());
+
+    let actionable = list_summaries(&aim, &waiting_conditions(true, false, None)).await;
+    assert_eq!(actionable, ["Design mockups", "Taxes"]);
+    let waiting = list_summaries(&aim, &waiting_conditions(false, true, None)).await;
+    assert_eq!(waiting, ["Contract review", "Design mockups", "Invoice"]);
+    let for_alice = list_summaries(&aim, &waiting_conditions(false, false, Some("ALICE"))).await;
+    assert_eq!(for_alice, ["Contract review", "Invoice"]);
+    let count = aim
+        .count_todos(&waiting_conditions(true, false, None))
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+
+    let todo = aim.unwait_todo(&uids[2]).await.unwrap();
+    assert!(todo.waiting_on().is_none());
+    let actionable = list_summaries(&aim, &waiting_conditions(true, false, None)).await;
+    assert_eq!(actionable, ["Design mockups", "Invoice", "Taxes"]);
+
+    // Kept in the file, so the waiting state survives a rebuild of the cache
+    let path = temp_dirs
+        .calendar_path
+        .join(format!("{}.ics", uids[0].as_uid()));
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(
+        content.contains("X-AIM-WAITING-ON:Alice Smith"),
+        "{content}"
+    );
+    assert!(content.contains("X-AIM-FOLLOW-UP:"), "{content}");
+    assert!(content.contains("STATUS:NEEDS-ACTION"), "{content}");
+}
+
+#[tokio::test]
+async fn aim_wait_todo_adds_and_removes_a_follow_up_reminder() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.new_todo(test_todo_draft("Quote")).await.unwrap();
+    let id = Id::Uid(todo.uid().into_owned());
+
+    let follow_up =
+        DateTimeAnchor::DateTime(LooseDateTime::Floating(datetime(2030, 5, 3, 9, 0, 0, 0)));
+    aim.wait_todo(&id, "Carol", Some(follow_up), true)
+        .await
+        .unwrap();
+    let details = aim.get_todo_details(&id).await.unwrap();
+    assert_eq!(details.alarms.len(), 1, "{:?}", details.alarms);
+    assert!(
+        details.alarms[0].starts_with("display at 2030-05-03"),
+        "{:?}",
+        details.alarms
+    );
+
+    // A reminder of the due leaves the follow-up reminder alone
+    let patch = TodoPatch {
+        due: Some(Some(LooseDateTime::Floating(datetime(
+            2030, 6, 1, 9, 0, 0, 0,
+        )))),
+        alarm: Some(Some("15m".parse().unwrap())),
+        ..Default::default()
+    };
+    aim.update_todo(&id, patch).await.unwrap();
+    assert_eq!(aim.get_todo_details(&id).await.unwrap().alarms.len(), 2);
+
+    aim.unwait_todo(&id).await.unwrap();
+    let details = aim.get_todo_details(&id).await.unwrap();
+    assert_eq!(details.alarms, ["display 15 minutes before due"]);
+}
+
+#[tokio::test]
+async fn aim_wait_todo_rejects_invalid_input() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.new_todo(test_todo_draft("Quote")).await.unwrap();
+    let id = Id::Uid(todo.uid().into_owned());
+
+    let result = aim.wait_todo(&id, "  ", None, false).await;
+    assert!(matches!(
+        result,
+        Err(AimError::InvalidInput {
+            field: "waiting on",
+            ..
+        })
+    ));
+    let result = aim.wait_todo(&id, "Dave", None, true).await;
+    assert!(matches!(
+        result,
+        Err(AimError::InvalidInput {
+            field: "follow-up",
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn aim_update_todo_keeps_untouched_date_properties_byte_identical() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let dates = [
+        "DUE;VALUE=DATE:20250115",
+        "DUE:20250115T090000",
+        "DUE;TZID=Europe/Berlin:20250115T090000",
+        "DUE;TZID=UTC:20250115T090000",
+        "DUE;X-ORIGIN=import:20250115T090000Z",
+    ];
+    for (i, due) in dates.iter().enumerate() {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//AIM//Test//EN\r\nBEGIN:VTODO\r\n\
+             UID:form-{i}\r\nDTSTAMP:20250101T000000Z\r\nDTSTART;VALUE=DATE:20250110\r\n\
+             {due}\r\nSUMMARY:Before\r\nSTATUS:NEEDS-ACTION\r\nEND:VTODO\r\nEND:VCALENDAR\r\n"
+        );
+        temp_dirs
+            .create_ics_file(&format!("form-{i}"), &ics)
+            .await
+            .unwrap();
+    }
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    for (i, due) in dates.iter().enumerate() {
+        // Patching another field, then giving the due it already has as an edit form does
+        let id = Id::Uid(format!("form-{i}"));
+        let patch = TodoPatch {
+            summary: Some("After".to_string()),
+            ..Default::default()
+        };
+        let todo = aim.update_todo(&id, patch).await.unwrap();
+        let patch = TodoPatch {
+            due: Some(todo.due()),
+            ..Default::default()
+        };
+        aim.update_todo(&id, patch).await.unwrap();
+
+        let path = temp_dirs.calendar_path.join(format!("form-{i}.ics"));
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(content.contains("SUMMARY:After"), "{content}");
+        assert!(content.contains(&format!("\r\n{due}\r\n")), "{content}");
+        assert!(
+            content.contains("\r\nDTSTART;VALUE=DATE:20250110\r\n"),
+            "{content}"
+        );
+    }
+}
@@ -11,8 +11,13 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use aimcal_core::{
-    Config, DateTimeAnchor, EventDraft, EventStatus, LooseDateTime, Priority, TodoDraft, TodoStatus,
+    AllDayPolicy, BusinessDays, CategoryDefaults, Collation, Config, DateTimeAnchor, DedupeMode,
+    EncryptionConfig, EventDraft, EventStatus, HousekeepingConfig, LooseDateTime, OverdueConfig,
+    Priority, Profile, RecurFrom, Rules, SmartLists, Templates, TodoDraft, TodoStatus,
+    WorkingHours,
 };
+use jiff::SignedDuration;
+use jiff::civil::Weekday;
 
 /// Creates a test configuration with temporary directories.
 ///
@@ -33,13 +38,39 @@ pub fn test_config(calendar_path: &str, state_dir: Option<&str>) -> Config {
         calendar_path: Some(PathBuf::from(calendar_path)),
         state_dir: state_dir.map(PathBuf::from),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     }
 }
 
@@ -61,13 +92,39 @@ pub fn test_config_with_due(
         calendar_path: Some(PathBuf::from(calendar_path)),
         state_dir: state_dir.map(PathBuf::from),
         default_due: Some(default_due),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     }
 }
 
@@ -79,13 +136,39 @@ pub fn test_config_defaults() -> Config {
         calendar_path: Some(PathBuf::from("/tmp/test-calendar")),
         state_dir: Some(PathBuf::from("/tmp/test-state")),
         default_due: Some(DateTimeAnchor::InDays(1)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     }
 }
 
@@ -103,10 +186,14 @@ pub fn test_config_defaults() -> Config {
 #[must_use]
 pub fn test_event_draft(summary: &str) -> EventDraft {
     EventDraft {
+        alarm: None,
         calendar_id: None,
         description: None,
         start: None,
         end: None,
+        duration: None,
+        repeat: None,
+        related_to: None,
         status: EventStatus::Confirmed,
         summary: summary.to_string(),
     }
@@ -128,10 +215,14 @@ pub fn test_event_draft_full(
     end: LooseDateTime,
 ) -> EventDraft {
     EventDraft {
+        alarm: None,
         calendar_id: None,
         description: Some(description.to_string()),
         start: Some(start),
         end: Some(end),
+        duration: None,
+        repeat: None,
+        related_to: None,
         status: EventStatus::Confirmed,
         summary: summary.to_string(),
     }
@@ -145,13 +236,20 @@ pub fn test_event_draft_full(
 #[must_use]
 pub fn test_todo_draft(summary: &str) -> TodoDraft {
     TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: summary.to_string(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     }
 }
 
@@ -171,13 +269,20 @@ pub fn test_todo_draft_full(
     priority: Priority,
 ) -> TodoDraft {
     TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: Some(description.to_string()),
         due: Some(due),
+        estimate: None,
         percent_complete: None,
         priority: Some(priority),
         status: TodoStatus::NeedsAction,
         summary: summary.to_string(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     }
 }
 
@@ -203,6 +308,7 @@ pub struct TestConfigBuilder {
     default_due: Option<DateTimeAnchor>,
     default_priority: Priority,
     default_priority_none_fist: bool,
+    week_start: Weekday,
 }
 
 impl TestConfigBuilder {
@@ -215,6 +321,7 @@ impl TestConfigBuilder {
             default_due: None,
             default_priority: Priority::None,
             default_priority_none_fist: false,
+            week_start: Weekday::Monday,
         }
     }
 
@@ -277,13 +384,39 @@ impl TestConfigBuilder {
             calendar_path: Some(calendar_path),
             state_dir: Some(state_dir),
             default_due: self.default_due,
+            default_due_time: None,
+            default_event_alarm: None,
+            default_event_duration: SignedDuration::from_hours(1),
+            default_estimate: SignedDuration::from_hours(1),
+            exclude_unestimated: false,
+            default_allday_alarm: None,
+            default_todo_alarm: None,
             default_priority: self.default_priority,
             default_priority_none_fist: self.default_priority_none_fist,
+            hide_unstarted: false,
+            week_start: self.week_start,
+            home_timezone: None,
+            working_hours: WorkingHours::default(),
+            business_days: BusinessDays::default(),
+            housekeeping: HousekeepingConfig::default(),
+            overdue: OverdueConfig::default(),
+            recur_from: RecurFrom::default(),
+            collation: Collation::default(),
+            dedupe_events: DedupeMode::default(),
+            all_day_events: AllDayPolicy::default(),
+            profile: Profile::default(),
+            encryption: EncryptionConfig::default(),
+            rules: Rules::default(),
+            lists: SmartLists::default(),
             config_dir: None,
             dev_mode: false,
+            read_only_compat: false,
             calendars: Vec::new(),
             default_calendar: "default".to_string(),
             secrets_files: vec![],
+            sync_max_age: SignedDuration::ZERO,
+            templates: Templates::default(),
+            categories: CategoryDefaults::default(),
         }
     }
 }
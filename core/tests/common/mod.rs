@@ -16,5 +16,9 @@ mod temp_dir;
 #[allow(unused_imports)]
 pub use assertions::{assert_event_matches_draft, assert_file_exists};
 #[allow(unused_imports)]
-pub use fixtures::{TestConfigBuilder, test_config, test_event_draft, test_todo_draft};
-pub use temp_dir::setup_temp_dirs;
+pub use fixtures::{
+    TestConfigBuilder, sample_event_ics, sample_todo_ics, test_config, test_config_from_dirs,
+    test_event_draft, test_todo_draft,
+};
+#[allow(unused_imports)]
+pub use temp_dir::{TempDirs, setup_temp_dirs};
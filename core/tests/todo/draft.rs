@@ -13,13 +13,20 @@ use aimcal_core::{LooseDateTime, Priority, TodoDraft, TodoStatus};
 #[test]
 fn todo_draft_empty_fields_are_none_or_needs_action() {
     let draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert!(draft.description.is_none());
@@ -34,13 +41,20 @@ fn todo_draft_empty_fields_are_none_or_needs_action() {
 fn todo_draft_with_all_fields_populated() {
     let due = LooseDateTime::Local(jiff::Zoned::now());
     let draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: Some("Test description".to_string()),
         due: Some(due.clone()),
+        estimate: None,
         percent_complete: Some(50),
         priority: Some(Priority::P2),
         status: TodoStatus::InProcess,
         summary: "Test Todo".to_string(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert_eq!(draft.description.as_deref(), Some("Test description"));
@@ -54,13 +68,20 @@ fn todo_draft_with_all_fields_populated() {
 #[test]
 fn todo_draft_can_be_created_with_builder_pattern() {
     let mut draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     draft.summary = "Builder Test".to_string();
@@ -75,43 +96,71 @@ fn todo_draft_can_be_created_with_builder_pattern() {
 #[test]
 fn todo_draft_status_can_be_all_variants() {
     let needs_action = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     let completed = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::Completed,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     let in_process = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::InProcess,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     let cancelled = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::Cancelled,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert_eq!(needs_action.status, TodoStatus::NeedsAction);
@@ -135,13 +184,20 @@ fn todo_draft_priority_can_be_all_levels() {
         Priority::P9,
     ] {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: None,
             priority: Some(priority),
             status: TodoStatus::NeedsAction,
             summary: String::new(),
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
 
         assert_eq!(draft.priority, Some(priority));
@@ -151,31 +207,52 @@ fn todo_draft_priority_can_be_all_levels() {
 #[test]
 fn todo_draft_percent_complete_accepts_range() {
     let zero = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
+        estimate: None,
         percent_complete: Some(0),
         description: None,
         due: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let fifty = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
+        estimate: None,
         percent_complete: Some(50),
         description: None,
         due: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let hundred = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
+        estimate: None,
         percent_complete: Some(100),
         description: None,
         due: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert_eq!(zero.percent_complete, Some(0));
@@ -187,13 +264,20 @@ fn todo_draft_percent_complete_accepts_range() {
 fn todo_draft_due_with_different_datetime_types() {
     let local = LooseDateTime::Local(jiff::Zoned::now());
     let draft1 = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         due: Some(local.clone()),
         description: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert!(draft1.due.is_some());
@@ -203,23 +287,37 @@ fn todo_draft_due_with_different_datetime_types() {
 #[test]
 fn todo_draft_description_optional() {
     let with_desc = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: Some("Has description".to_string()),
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     let without_desc = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert_eq!(with_desc.description.as_deref(), Some("Has description"));
@@ -117,12 +117,20 @@ fn todo_patch_with_summary_set_is_not_empty() {
 #[test]
 fn todo_patch_with_all_fields_set_is_not_empty() {
     let patch = TodoPatch {
+        alarm: None,
         description: Some(Some("Description".to_string())),
         due: Some(Some(LooseDateTime::Local(jiff::Zoned::now()))),
+        estimate: None,
         percent_complete: Some(Some(50)),
         priority: Some(Priority::P5),
         status: Some(TodoStatus::InProcess),
         summary: Some("Summary".to_string()),
+        snoozed_from: Some(LooseDateTime::Local(jiff::Zoned::now())),
+        meta: Vec::new(),
+        encrypted_description: None,
+        waiting: None,
+        recur_from: None,
+        completed_at: None,
     };
 
     assert!(!patch.is_empty());
@@ -131,12 +139,20 @@ fn todo_patch_with_all_fields_set_is_not_empty() {
 #[test]
 fn todo_patch_can_set_all_optional_fields_to_none() {
     let patch = TodoPatch {
+        alarm: None,
         description: Some(None),
         due: Some(None),
+        estimate: None,
         percent_complete: Some(None),
         priority: None,
         status: None,
         summary: None,
+        snoozed_from: None,
+        meta: Vec::new(),
+        encrypted_description: None,
+        waiting: None,
+        recur_from: None,
+        completed_at: None,
     };
 
     assert!(!patch.is_empty());
@@ -234,3 +250,55 @@ fn todo_patch_clone_independence() {
     assert_eq!(patch1.summary.as_deref(), Some("Original"));
     assert_eq!(patch2.summary.as_deref(), Some("Modified"));
 }
+
+#[test]
+fn todo_patch_validate_accepts_agreeing_status_and_percent() {
+    let patch = |status, percent| TodoPatch {
+        status: Some(status),
+        percent_complete: Some(Some(percent)),
+        ..Default::default()
+    };
+
+    assert!(patch(TodoStatus::NeedsAction, 0).validate().is_ok());
+    assert!(patch(TodoStatus::InProcess, 0).validate().is_ok());
+    assert!(patch(TodoStatus::InProcess, 99).validate().is_ok());
+    assert!(patch(TodoStatus::Completed, 100).validate().is_ok());
+    assert!(patch(TodoStatus::Cancelled, 40).validate().is_ok());
+
+    // Values over 100 are clamped
+    assert!(patch(TodoStatus::Completed, 150).validate().is_ok());
+}
+
+#[test]
+fn todo_patch_validate_rejects_contradicting_status_and_percent() {
+    let patch = |status, percent| TodoPatch {
+        status: Some(status),
+        percent_complete: Some(Some(percent)),
+        ..Default::default()
+    };
+
+    assert!(patch(TodoStatus::NeedsAction, 10).validate().is_err());
+    assert!(patch(TodoStatus::InProcess, 100).validate().is_err());
+    assert!(patch(TodoStatus::Completed, 60).validate().is_err());
+}
+
+#[test]
+fn todo_patch_validate_accepts_either_alone() {
+    let status_only = TodoPatch {
+        status: Some(TodoStatus::Completed),
+        ..Default::default()
+    };
+    let percent_only = TodoPatch {
+        percent_complete: Some(Some(60)),
+        ..Default::default()
+    };
+    let cleared = TodoPatch {
+        status: Some(TodoStatus::Completed),
+        percent_complete: Some(None),
+        ..Default::default()
+    };
+
+    assert!(status_only.validate().is_ok());
+    assert!(percent_only.validate().is_ok());
+    assert!(cleared.validate().is_ok());
+}
@@ -17,13 +17,20 @@ fn priority_default_is_none() {
 #[test]
 fn todo_draft_priority_can_be_none() {
     let draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
         summary: String::new(),
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
 
     assert!(draft.priority.is_none());
@@ -33,13 +40,20 @@ fn todo_draft_priority_can_be_none() {
 fn todo_draft_priority_can_be_set() {
     for priority in [Priority::None, Priority::P1, Priority::P5, Priority::P9] {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: None,
             priority: Some(priority),
             status: TodoStatus::NeedsAction,
             summary: String::new(),
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
 
         assert_eq!(draft.priority, Some(priority));
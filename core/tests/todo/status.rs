@@ -177,3 +177,12 @@ fn todo_status_const_values_match_rfc_5545() {
     assert_eq!(TodoStatus::InProcess.as_ref(), "IN-PROGRESS");
     assert_eq!(TodoStatus::Cancelled.as_ref(), "CANCELLED");
 }
+
+#[test]
+fn todo_status_for_percent_complete() {
+    assert_eq!(TodoStatus::for_percent_complete(0), TodoStatus::NeedsAction);
+    assert_eq!(TodoStatus::for_percent_complete(1), TodoStatus::InProcess);
+    assert_eq!(TodoStatus::for_percent_complete(99), TodoStatus::InProcess);
+    assert_eq!(TodoStatus::for_percent_complete(100), TodoStatus::Completed);
+    assert_eq!(TodoStatus::for_percent_complete(200), TodoStatus::Completed);
+}
@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Backup and restore workflow tests.
+//!
+//! These tests validate that an archive captures the database, the config
+//! file and the calendar files, and that a damaged archive is rejected
+//! before any of them is overwritten.
+
+use aimcal_core::{Aim, AimError, BACKUP_FORMAT_VERSION, Id, Todo};
+use tokio::fs;
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs, test_todo_draft};
+
+#[tokio::test]
+async fn backup_restore_round_trip_replaces_state() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let config_file = temp_dirs.base().join("config.toml");
+    fs::write(&config_file, "[core]\n").await.unwrap();
+    let archive = temp_dirs.base().join("backups").join("aim.tar.zst");
+
+    let aim = Aim::new(config.clone()).await.unwrap();
+    let kept = aim.new_todo(test_todo_draft("Kept")).await.unwrap();
+    let kept = kept.uid().to_string();
+    let manifest = aim
+        .create_backup(&archive, Some(&config_file))
+        .await
+        .unwrap();
+    assert_eq!(manifest.format_version, BACKUP_FORMAT_VERSION);
+    assert!(manifest.files.iter().any(|f| f.path == "aim.db"));
+    assert!(manifest.files.iter().any(|f| f.path == "config.toml"));
+    assert!(
+        manifest
+            .files
+            .iter()
+            .any(|f| f.path == format!("calendars/default/{kept}.ics"))
+    );
+
+    // Diverge from the backup, then roll back
+    let dropped = aim.new_todo(test_todo_draft("Dropped")).await.unwrap();
+    let dropped = dropped.uid().to_string();
+    aim.close().await.unwrap();
+    fs::write(&config_file, "[core]\nstate_dir = \"elsewhere\"\n")
+        .await
+        .unwrap();
+
+    Aim::restore_backup(config.clone(), &archive, Some(&config_file))
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read_to_string(&config_file).await.unwrap(), "[core]\n");
+    assert!(
+        !temp_dirs
+            .calendar_path
+            .join(format!("{dropped}.ics"))
+            .exists()
+    );
+
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.get_todo(&Id::Uid(kept)).await.unwrap();
+    assert_eq!(todo.summary(), "Kept");
+    assert!(aim.get_todo(&Id::Uid(dropped)).await.is_err());
+}
+
+#[tokio::test]
+async fn backup_restore_rejects_corrupt_archive_without_changes() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let archive = temp_dirs.base().join("aim.tar.zst");
+
+    let aim = Aim::new(config.clone()).await.unwrap();
+    aim.new_todo(test_todo_draft("Before")).await.unwrap();
+    aim.create_backup(&archive, None).await.unwrap();
+    let after = aim.new_todo(test_todo_draft("After")).await.unwrap();
+    let after = after.uid().to_string();
+    aim.close().await.unwrap();
+
+    // Truncate the archive, as if the copy was interrupted
+    let data = fs::read(&archive).await.unwrap();
+    fs::write(&archive, &data[..data.len() / 2]).await.unwrap();
+
+    let err = Aim::restore_backup(config.clone(), &archive, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AimError::Parse { .. }), "{err}");
+
+    let aim = Aim::new(config).await.unwrap();
+    let todo = aim.get_todo(&Id::Uid(after)).await.unwrap();
+    assert_eq!(todo.summary(), "After");
+    let mut entries = std::fs::read_dir(&temp_dirs.state_dir).unwrap();
+    assert!(entries.all(|e| !e.unwrap().file_name().to_string_lossy().starts_with('.')));
+}
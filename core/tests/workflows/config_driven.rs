@@ -12,10 +12,17 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use aimcal_core::{
-    Aim, CalendarEntry, Config, DateTimeAnchor, Event, EventConditions, Id, LooseDateTime, Pager,
-    Priority, SortOrder, StoreDef, Todo, TodoConditions, TodoDraft, TodoSort, TodoStatus,
+    Aim, AllDayPolicy, BusinessDays, CalendarEntry, CategoryDefaults, Collation, Config,
+    DateTimeAnchor, DedupeMode, EncryptionConfig, Event, EventConditions, HousekeepingConfig, Id,
+    LooseDateTime, OverdueConfig, Pager, Priority, Profile, RecurFrom, Rules, SmartLists,
+    SortOrder, StoreDef, Templates, Todo, TodoConditions, TodoDraft, TodoSort, TodoStatus,
+    WorkingHours,
+};
+use jiff::{
+    SignedDuration, Zoned,
+    civil::{Weekday, date},
+    tz::TimeZone,
 };
-use jiff::{Zoned, civil::date, tz::TimeZone};
 
 use crate::common::{TestConfigBuilder, setup_temp_dirs, test_event_draft, test_todo_draft};
 
@@ -60,13 +67,39 @@ async fn config_default_due_applied() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -81,13 +114,20 @@ async fn config_default_due_applied() {
 
     // Act - create todo without due date
     let todo_draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         summary: "Task without due".to_string(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let todo = aim.new_todo(todo_draft).await.unwrap();
 
@@ -115,21 +155,49 @@ async fn config_default_priority_applied() {
             calendar_path: Some(temp_dirs.calendar_path.clone()),
             state_dir: Some(temp_dirs.state_dir.clone()),
             default_due: None,
+            default_due_time: None,
+            default_event_alarm: None,
+            default_event_duration: SignedDuration::from_hours(1),
+            default_estimate: SignedDuration::from_hours(1),
+            exclude_unestimated: false,
+            default_allday_alarm: None,
+            default_todo_alarm: None,
             default_priority,
             default_priority_none_fist: false,
+            hide_unstarted: false,
+            week_start: Weekday::Monday,
+            home_timezone: None,
+            working_hours: WorkingHours::default(),
+            business_days: BusinessDays::default(),
+            housekeeping: HousekeepingConfig::default(),
+            overdue: OverdueConfig::default(),
+            recur_from: RecurFrom::default(),
+            collation: Collation::default(),
+            dedupe_events: DedupeMode::default(),
+            all_day_events: AllDayPolicy::default(),
+            profile: Profile::default(),
+            encryption: EncryptionConfig::default(),
+            rules: Rules::default(),
+            lists: SmartLists::default(),
             config_dir: None,
             dev_mode: false,
+            read_only_compat: false,
             calendars: Vec::new(),
             default_calendar: "default".to_string(),
             secrets_files: vec![],
+            sync_max_age: SignedDuration::ZERO,
+            templates: Templates::default(),
+            categories: CategoryDefaults::default(),
         };
         let aim = Aim::new(config).await.unwrap();
 
-        // Act - get default draft
-        let draft = aim.default_todo_draft().unwrap();
+        // Act - create a todo from the default draft
+        let mut draft = aim.default_todo_draft().unwrap();
+        draft.summary = format!("Task with default priority {expected:?}");
+        let todo = aim.new_todo(draft).await.unwrap();
 
         // Assert - default priority should match config
-        assert_eq!(draft.priority, Some(expected));
+        assert_eq!(todo.priority(), expected);
     }
 }
 
@@ -144,13 +212,39 @@ async fn config_priority_sorting_behavior() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim_none_first = Aim::new(config_none_first).await.unwrap();
 
@@ -175,6 +269,16 @@ async fn config_priority_sorting_behavior() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort,
             &Pager {
@@ -196,13 +300,39 @@ async fn config_priority_sorting_behavior() {
         calendar_path: Some(temp_dirs2.calendar_path.clone()),
         state_dir: Some(temp_dirs2.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim_some_first = Aim::new(config_some_first).await.unwrap();
 
@@ -222,6 +352,16 @@ async fn config_priority_sorting_behavior() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort,
             &Pager {
@@ -246,13 +386,39 @@ async fn config_timezone_handling() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -288,13 +454,39 @@ fn multi_local_config(
         calendar_path: None,
         state_dir: Some(state_dir),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars,
         default_calendar: default_calendar.to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     }
 }
 
@@ -316,6 +508,7 @@ async fn multi_calendar_uses_configured_default_calendar() {
                 calendar_path: Some(work_dir.to_string_lossy().to_string()),
                 priority: 0,
                 enabled: true,
+                color: None,
             },
             CalendarEntry {
                 id: "personal".to_string(),
@@ -325,6 +518,7 @@ async fn multi_calendar_uses_configured_default_calendar() {
                 calendar_path: Some(personal_dir.to_string_lossy().to_string()),
                 priority: 1,
                 enabled: true,
+                color: None,
             },
         ],
         "personal",
@@ -341,6 +535,16 @@ async fn multi_calendar_uses_configured_default_calendar() {
                 status: None,
                 due: None,
                 calendar_id: Some("personal".to_string()),
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -356,6 +560,16 @@ async fn multi_calendar_uses_configured_default_calendar() {
                 status: None,
                 due: None,
                 calendar_id: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -371,6 +585,16 @@ async fn multi_calendar_uses_configured_default_calendar() {
                 status: None,
                 due: None,
                 calendar_id: Some("work".to_string()),
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -392,6 +616,95 @@ async fn multi_calendar_uses_configured_default_calendar() {
     assert!(work_todos.is_empty());
 }
 
+#[tokio::test]
+async fn category_defaults_fill_fields_left_unset() {
+    let root = tempfile::tempdir().unwrap();
+    let calendar = |id: &str, name: &str| CalendarEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        store: "local".to_string(),
+        calendar_href: None,
+        calendar_path: Some(root.path().join(id).to_string_lossy().to_string()),
+        priority: 0,
+        enabled: true,
+        color: None,
+    };
+    let mut config = multi_local_config(
+        root.path().join("state"),
+        vec![calendar("work", "Work"), calendar("personal", "Personal")],
+        "personal",
+    );
+    config.categories = toml::from_str(
+        r#"
+[work]
+priority = "high"
+calendar = "Work"
+
+[urgent]
+priority = 1
+"#,
+    )
+    .unwrap();
+    let aim = Aim::new(config).await.unwrap();
+
+    let new_todo = async |categories: &[&str], priority: Option<Priority>, no_defaults: bool| {
+        let draft = TodoDraft {
+            categories: categories.iter().map(ToString::to_string).collect(),
+            priority,
+            no_defaults,
+            ..test_todo_draft(&categories.join(" "))
+        };
+        let uid = aim.new_todo(draft).await.unwrap().uid().into_owned();
+        let todo = aim.get_todo(&Id::Uid(uid)).await.unwrap();
+        (todo.calendar_id().unwrap().into_owned(), todo.priority())
+    };
+
+    // The first category setting a field wins, whatever sets none is left to the others
+    let work = new_todo(&["work"], None, false).await;
+    assert_eq!(work, ("work".to_string(), Priority::P2));
+    let urgent_work = new_todo(&["urgent", "work"], None, false).await;
+    assert_eq!(urgent_work, ("work".to_string(), Priority::P1));
+    let work_urgent = new_todo(&["work", "urgent"], None, false).await;
+    assert_eq!(work_urgent, ("work".to_string(), Priority::P2));
+
+    // Fields given by the user and drafts asking for no defaults keep theirs
+    let low = new_todo(&["work"], Some(Priority::P8), false).await;
+    assert_eq!(low, ("work".to_string(), Priority::P8));
+    let plain = new_todo(&["work"], None, true).await;
+    assert_eq!(plain, ("personal".to_string(), Priority::None));
+}
+
+#[tokio::test]
+async fn multi_calendar_without_name_is_named_after_its_id() {
+    let root = tempfile::tempdir().unwrap();
+    let state_dir = root.path().join("state");
+    let work_dir = root.path().join("work");
+
+    let config = multi_local_config(
+        state_dir,
+        vec![CalendarEntry {
+            id: "work".to_string(),
+            name: String::new(),
+            store: "local".to_string(),
+            calendar_href: None,
+            calendar_path: Some(work_dir.to_string_lossy().to_string()),
+            priority: 0,
+            enabled: true,
+            color: None,
+        }],
+        "work",
+    );
+    let aim = Aim::new(config).await.unwrap();
+
+    let calendars = aim.list_calendars().await.unwrap();
+    assert!(
+        calendars
+            .iter()
+            .any(|calendar| calendar.id == "work" && calendar.name == "work"),
+        "{calendars:?}"
+    );
+}
+
 #[tokio::test]
 async fn multi_calendar_missing_config_disables_calendar_without_deleting_data() {
     let root = tempfile::tempdir().unwrap();
@@ -410,6 +723,7 @@ async fn multi_calendar_missing_config_disables_calendar_without_deleting_data()
                 calendar_path: Some(personal_dir.to_string_lossy().to_string()),
                 priority: 0,
                 enabled: true,
+                color: None,
             },
             CalendarEntry {
                 id: "work".to_string(),
@@ -419,6 +733,7 @@ async fn multi_calendar_missing_config_disables_calendar_without_deleting_data()
                 calendar_path: Some(work_dir.to_string_lossy().to_string()),
                 priority: 1,
                 enabled: true,
+                color: None,
             },
         ],
         "personal",
@@ -444,6 +759,7 @@ async fn multi_calendar_missing_config_disables_calendar_without_deleting_data()
             calendar_path: Some(personal_dir.to_string_lossy().to_string()),
             priority: 0,
             enabled: true,
+            color: None,
         }],
         "personal",
     );
@@ -455,6 +771,16 @@ async fn multi_calendar_missing_config_disables_calendar_without_deleting_data()
                 status: None,
                 due: None,
                 calendar_id: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -471,6 +797,16 @@ async fn multi_calendar_missing_config_disables_calendar_without_deleting_data()
                 status: None,
                 due: None,
                 calendar_id: Some("work".to_string()),
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -517,6 +853,7 @@ async fn multi_calendar_event_listing_orders_by_calendar_priority() {
                 calendar_path: Some(personal_dir.to_string_lossy().to_string()),
                 priority: 1,
                 enabled: true,
+                color: None,
             },
             CalendarEntry {
                 id: "work".to_string(),
@@ -526,6 +863,7 @@ async fn multi_calendar_event_listing_orders_by_calendar_priority() {
                 calendar_path: Some(work_dir.to_string_lossy().to_string()),
                 priority: 0,
                 enabled: true,
+                color: None,
             },
         ],
         "personal",
@@ -570,6 +908,11 @@ async fn multi_calendar_event_listing_orders_by_calendar_priority() {
                 startable: None,
                 cutoff: None,
                 calendar_id: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -593,13 +936,39 @@ async fn config_mixed_defaults_integration() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P3,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -608,20 +977,27 @@ async fn config_mixed_defaults_integration() {
 
     // Assert - all defaults should be applied
     assert_eq!(draft.summary, "");
-    assert_eq!(draft.priority, Some(Priority::P3));
+    assert_eq!(aim.todo_draft_priority(&draft), Priority::P3);
     assert!(draft.due.is_some());
     assert_eq!(draft.status, TodoStatus::NeedsAction);
 
     // Act - create multiple todos using defaults
     for i in 1..=5 {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             summary: format!("Task {i}"),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: None,
             priority: None,
             status: TodoStatus::NeedsAction,
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
         let todo = aim.new_todo(draft).await.unwrap();
         assert_eq!(todo.summary().as_ref(), format!("Task {i}"));
@@ -638,6 +1014,16 @@ async fn config_mixed_defaults_integration() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort,
             &Pager {
@@ -659,13 +1045,39 @@ async fn config_persistence_across_restarts() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // First instance - create todos
@@ -673,7 +1085,7 @@ async fn config_persistence_across_restarts() {
 
     // Verify defaults in first instance
     let draft1 = aim1.default_todo_draft().unwrap();
-    assert_eq!(draft1.priority, Some(Priority::P5));
+    assert_eq!(aim1.todo_draft_priority(&draft1), Priority::P5);
     assert!(draft1.due.is_some());
 
     // Create a todo
@@ -689,7 +1101,7 @@ async fn config_persistence_across_restarts() {
     let aim2 = Aim::new(config).await.unwrap();
 
     let draft2 = aim2.default_todo_draft().unwrap();
-    assert_eq!(draft2.priority, Some(Priority::P5));
+    assert_eq!(aim2.todo_draft_priority(&draft2), Priority::P5);
     assert!(draft2.due.is_some());
 
     // Verify todo persisted
@@ -706,13 +1118,39 @@ async fn config_default_draft_consistency() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(1)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P2,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -724,7 +1162,7 @@ async fn config_default_draft_consistency() {
     // Assert - all should have same defaults
     assert_eq!(draft1.priority, draft2.priority);
     assert_eq!(draft2.priority, draft3.priority);
-    assert_eq!(draft1.priority, Some(Priority::P2));
+    assert_eq!(aim.todo_draft_priority(&draft1), Priority::P2);
 
     // All should have due dates (calculated from same anchor)
     assert!(draft1.due.is_some());
@@ -746,13 +1184,39 @@ async fn config_event_defaults() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -791,13 +1255,39 @@ async fn config_datetime_anchor_variations() {
             calendar_path: Some(temp_dirs.calendar_path.clone()),
             state_dir: Some(temp_dirs.state_dir.clone()),
             default_due: Some(anchor.clone()),
+            default_due_time: None,
+            default_event_alarm: None,
+            default_event_duration: SignedDuration::from_hours(1),
+            default_estimate: SignedDuration::from_hours(1),
+            exclude_unestimated: false,
+            default_allday_alarm: None,
+            default_todo_alarm: None,
             default_priority: Priority::None,
             default_priority_none_fist: false,
+            hide_unstarted: false,
+            week_start: Weekday::Monday,
+            home_timezone: None,
+            working_hours: WorkingHours::default(),
+            business_days: BusinessDays::default(),
+            housekeeping: HousekeepingConfig::default(),
+            overdue: OverdueConfig::default(),
+            recur_from: RecurFrom::default(),
+            collation: Collation::default(),
+            dedupe_events: DedupeMode::default(),
+            all_day_events: AllDayPolicy::default(),
+            profile: Profile::default(),
+            encryption: EncryptionConfig::default(),
+            rules: Rules::default(),
+            lists: SmartLists::default(),
             config_dir: None,
             dev_mode: false,
+            read_only_compat: false,
             calendars: Vec::new(),
             default_calendar: "default".to_string(),
             secrets_files: vec![],
+            sync_max_age: SignedDuration::ZERO,
+            templates: Templates::default(),
+            categories: CategoryDefaults::default(),
         };
         let aim = Aim::new(config).await.unwrap();
 
@@ -812,3 +1302,159 @@ async fn config_datetime_anchor_variations() {
         );
     }
 }
+
+#[tokio::test]
+async fn working_hours_take_holidays_from_holiday_calendar() {
+    let root = tempfile::tempdir().unwrap();
+    let calendar = |id: &str, name: &str| CalendarEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        store: "local".to_string(),
+        calendar_href: None,
+        calendar_path: Some(root.path().join(id).to_string_lossy().to_string()),
+        priority: 0,
+        enabled: true,
+        color: None,
+    };
+    let mut config = multi_local_config(
+        root.path().join("state"),
+        vec![
+            calendar("personal", "Personal"),
+            calendar("holidays", "Holidays"),
+        ],
+        "personal",
+    );
+    config.working_hours = toml::from_str("holidays = \"Holidays\"").unwrap();
+    let aim = Aim::new(config).await.unwrap();
+
+    let mut christmas = test_event_draft("Christmas");
+    christmas.calendar_id = Some("holidays".to_string());
+    christmas.start = Some(LooseDateTime::DateOnly(date(2026, 12, 25)));
+    christmas.end = Some(LooseDateTime::DateOnly(date(2026, 12, 27)));
+    aim.new_event(christmas).await.unwrap();
+
+    let mut party = test_event_draft("Party");
+    party.calendar_id = Some("personal".to_string());
+    party.start = Some(LooseDateTime::DateOnly(date(2026, 12, 24)));
+    aim.new_event(party).await.unwrap();
+
+    let hours = aim.working_hours().await.unwrap();
+    assert!(!hours.is_holiday(date(2026, 12, 24)));
+    assert!(hours.is_holiday(date(2026, 12, 25)));
+    assert!(hours.is_holiday(date(2026, 12, 26)));
+    assert!(!hours.is_holiday(date(2026, 12, 27)));
+
+    // Friday the 25th is off, so the next working time is Monday morning
+    let eve = date(2026, 12, 24)
+        .at(18, 0, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap();
+    let next = hours.next_working_instant(&eve).unwrap();
+    assert_eq!(next.date(), date(2026, 12, 28));
+}
+
+#[tokio::test]
+async fn multi_calendar_details_report_counts_and_sync_status() {
+    let root = tempfile::tempdir().unwrap();
+    let calendar = |id: &str, name: &str, priority| CalendarEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        store: "local".to_string(),
+        calendar_href: None,
+        calendar_path: Some(root.path().join(id).to_string_lossy().to_string()),
+        priority,
+        enabled: true,
+        color: Some("#1e90ff".to_string()),
+    };
+    let config = multi_local_config(
+        root.path().join("state"),
+        vec![
+            calendar("work", "Work", 0),
+            calendar("personal", "Personal", 1),
+        ],
+        "personal",
+    );
+    let aim = Aim::new(config).await.unwrap();
+    aim.new_todo(test_todo_draft("Personal todo"))
+        .await
+        .unwrap();
+    aim.new_event(test_event_draft("Personal event"))
+        .await
+        .unwrap();
+
+    let details = aim.list_calendar_details().await.unwrap();
+    let personal = details.iter().find(|c| c.id == "personal").unwrap();
+    assert!(personal.is_default);
+    assert_eq!((personal.events, personal.todos), (1, 1));
+    assert_eq!(personal.color.as_deref(), Some("#1e90ff"));
+    assert!(personal.last_synced_at.is_some());
+    assert_eq!(personal.last_sync_error, None);
+    let work = details.iter().find(|c| c.id == "work").unwrap();
+    assert!(!work.is_default);
+    assert_eq!((work.events, work.todos), (0, 0));
+}
+
+#[tokio::test]
+async fn multi_calendar_user_default_and_disabled_persist_across_restarts() {
+    let root = tempfile::tempdir().unwrap();
+    let calendar = |id: &str, name: &str, priority| CalendarEntry {
+        id: id.to_string(),
+        name: name.to_string(),
+        store: "local".to_string(),
+        calendar_href: None,
+        calendar_path: Some(root.path().join(id).to_string_lossy().to_string()),
+        priority,
+        enabled: true,
+        color: None,
+    };
+    let config = multi_local_config(
+        root.path().join("state"),
+        vec![
+            calendar("work", "Work", 0),
+            calendar("personal", "Personal", 1),
+        ],
+        "personal",
+    );
+    let all = TodoConditions {
+        status: None,
+        due: None,
+        calendar_id: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+
+    let mut aim = Aim::new(config.clone()).await.unwrap();
+    aim.new_todo(test_todo_draft("Personal todo"))
+        .await
+        .unwrap();
+    let err = aim
+        .set_calendar_enabled("personal", false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("default calendar"), "{err}");
+    assert_eq!(aim.set_default_calendar("Work").await.unwrap(), "work");
+    aim.set_calendar_enabled("Personal", false).await.unwrap();
+    assert_eq!(aim.count_todos(&all).await.unwrap(), 0);
+    drop(aim);
+
+    // The choices override the config on the next start
+    let mut aim = Aim::new(config).await.unwrap();
+    let details = aim.list_calendar_details().await.unwrap();
+    let personal = details.iter().find(|c| c.id == "personal").unwrap();
+    assert!(!personal.enabled);
+    assert!(details.iter().any(|c| c.id == "work" && c.is_default));
+    let err = aim.set_default_calendar("personal").await.unwrap_err();
+    assert!(err.to_string().contains("disabled"), "{err}");
+    assert_eq!(aim.count_todos(&all).await.unwrap(), 0);
+
+    aim.set_calendar_enabled("personal", true).await.unwrap();
+    assert_eq!(aim.count_todos(&all).await.unwrap(), 1);
+}
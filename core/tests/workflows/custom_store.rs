@@ -0,0 +1,258 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Custom store workflow tests.
+//!
+//! These tests validate that stores registered through the builder are cached
+//! like the configured calendars, and that their capabilities are enforced.
+
+use std::sync::Mutex;
+
+use aimcal_core::{
+    Aim, AimError, EventPatch, Pager, Store, StoreCapabilities, StoreError, StoreResource,
+    TodoConditions, TodoDraft, TodoPatch,
+};
+use aimcal_ical::{CalendarComponent, VEvent, VTodo};
+use async_trait::async_trait;
+
+use crate::common::{setup_temp_dirs, test_config, test_todo_draft};
+
+/// Read-only store serving todos from memory, with a revision as change token.
+struct MemoryStore {
+    todos: Mutex<Vec<(String, u32)>>,
+}
+
+impl MemoryStore {
+    fn new(summaries: &[&str]) -> Self {
+        let todos = summaries.iter().map(|s| ((*s).to_string(), 1)).collect();
+        Self {
+            todos: Mutex::new(todos),
+        }
+    }
+
+    fn todo(index: usize, summary: &str) -> VTodo<String> {
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//Test//EN\r\n\
+BEGIN:VTODO\r\nUID:memory-{index}\r\nDTSTAMP:20260101T000000Z\r\nSUMMARY:{summary}\r\n\
+END:VTODO\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = aimcal_ical::parse(&ics).unwrap();
+        let component = calendars
+            .into_iter()
+            .flat_map(|c| c.to_owned().components)
+            .next();
+        match component {
+            Some(CalendarComponent::Todo(todo)) => todo,
+            _ => panic!("Expected a todo"),
+        }
+    }
+
+    fn rename(&self, index: usize, summary: &str) {
+        let mut todos = self.todos.lock().unwrap();
+        let (current, revision) = todos.get_mut(index).unwrap();
+        *current = summary.to_string();
+        *revision += 1;
+    }
+
+    fn read_only() -> StoreError {
+        "memory store is read-only".into()
+    }
+}
+
+#[async_trait]
+impl Store for MemoryStore {
+    async fn create_event(&self, _: &str, _: &VEvent<String>) -> Result<String, StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn get_event(&self, uid: &str) -> Result<VEvent<String>, StoreError> {
+        Err(Box::new(AimError::NotFound {
+            kind: "Event",
+            id: uid.to_string(),
+        }))
+    }
+
+    async fn update_event(&self, _: &str, _: &EventPatch) -> Result<VEvent<String>, StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn delete_event(&self, _: &str) -> Result<(), StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn create_todo(&self, _: &str, _: &VTodo<String>) -> Result<String, StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn get_todo(&self, uid: &str) -> Result<VTodo<String>, StoreError> {
+        self.list_todos()
+            .await?
+            .into_iter()
+            .find_map(|(_, todo)| (todo.uid.content.to_string() == uid).then_some(todo))
+            .ok_or_else(|| format!("Todo not found: {uid}").into())
+    }
+
+    async fn update_todo(&self, _: &str, _: &TodoPatch) -> Result<VTodo<String>, StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn delete_todo(&self, _: &str) -> Result<(), StoreError> {
+        Err(Self::read_only())
+    }
+
+    async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_todos(&self) -> Result<Vec<(String, VTodo<String>)>, StoreError> {
+        let todos = self.todos.lock().unwrap();
+        Ok(todos
+            .iter()
+            .enumerate()
+            .map(|(i, (summary, _))| (format!("memory:{i}"), Self::todo(i, summary)))
+            .collect())
+    }
+
+    async fn uid_exists(&self, uid: &str) -> Result<bool, StoreError> {
+        Ok(self.get_todo(uid).await.is_ok())
+    }
+
+    fn calendar_id(&self) -> &str {
+        "memory"
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities {
+            read_only: true,
+            supports_events: false,
+            supports_todos: true,
+        }
+    }
+
+    async fn list_resources(&self) -> Result<Vec<StoreResource>, StoreError> {
+        let todos = self.todos.lock().unwrap();
+        Ok(todos
+            .iter()
+            .enumerate()
+            .map(|(i, (_, revision))| StoreResource {
+                id: format!("memory:{i}"),
+                change_token: Some(revision.to_string()),
+            })
+            .collect())
+    }
+}
+
+async fn memory_todo_summaries(aim: &Aim) -> Vec<String> {
+    let conds = TodoConditions {
+        status: None,
+        due: None,
+        calendar_id: Some("memory".to_string()),
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 10,
+        offset: 0,
+    };
+    let mut summaries: Vec<_> = aim
+        .list_todos(&conds, &[], &pager)
+        .await
+        .unwrap()
+        .iter()
+        .map(|t| aimcal_core::Todo::summary(t).to_string())
+        .collect();
+    summaries.sort();
+    summaries
+}
+
+#[tokio::test]
+async fn custom_store_is_cached_and_refreshed() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+
+    let aim = Aim::builder(config.clone())
+        .store("Memory", Box::new(MemoryStore::new(&["Water plants"])))
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(memory_todo_summaries(&aim).await, ["Water plants"]);
+    let calendars = aim.list_calendars().await.unwrap();
+    assert!(
+        calendars
+            .iter()
+            .any(|c| c.id == "memory" && c.name == "Memory")
+    );
+    drop(aim);
+
+    // Changed resources are cached again, removed ones are dropped
+    let store = MemoryStore::new(&["Water plants", "Feed cat"]);
+    store.rename(0, "Water all plants");
+    let aim = Aim::builder(config)
+        .store("Memory", Box::new(store))
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        memory_todo_summaries(&aim).await,
+        ["Feed cat", "Water all plants"]
+    );
+}
+
+#[tokio::test]
+async fn custom_store_capabilities_are_enforced() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let aim = Aim::builder(config)
+        .store("Memory", Box::new(MemoryStore::new(&[])))
+        .build()
+        .await
+        .unwrap();
+
+    let draft = TodoDraft {
+        calendar_id: Some("memory".to_string()),
+        ..test_todo_draft("Not allowed")
+    };
+    let err = aim.new_todo(draft).await.err().unwrap();
+    assert!(
+        matches!(
+            err,
+            AimError::InvalidInput {
+                field: "calendar",
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn custom_store_rejects_duplicate_calendar_id() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let err = Aim::builder(config)
+        .store("Memory", Box::new(MemoryStore::new(&[])))
+        .store("Memory again", Box::new(MemoryStore::new(&[])))
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, AimError::Config(_)), "{err}");
+}
@@ -13,10 +13,13 @@ use std::collections::HashMap;
 use tokio::fs;
 
 use aimcal_core::{
-    Aim, Config, Event, EventConditions, Id, LooseDateTime, Pager, Priority, Todo, TodoConditions,
-    TodoDraft,
+    Aim, AimError, AllDayPolicy, BusinessDays, CategoryDefaults, Collation, Config, DedupeMode,
+    EncryptionConfig, Event, EventConditions, HousekeepingConfig, Id, LooseDateTime, OverdueConfig,
+    Pager, Priority, Profile, RecurFrom, Rules, SmartLists, Templates, Todo, TodoConditions,
+    TodoDraft, TodoPatch, WorkingHours,
 };
-use jiff::{Span, Zoned};
+use jiff::civil::Weekday;
+use jiff::{SignedDuration, Span, Zoned};
 
 use crate::common::{setup_temp_dirs, test_event_draft, test_todo_draft};
 
@@ -29,13 +32,39 @@ async fn file_sync_external_modification_detected() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config.clone()).await.unwrap();
 
@@ -79,6 +108,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -102,13 +136,39 @@ async fn file_sync_database_rebuild_from_files() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Create multiple events and todos
@@ -127,6 +187,11 @@ async fn file_sync_database_rebuild_from_files() {
             calendar_id: None,
             startable: None,
             cutoff: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         })
         .await
         .unwrap();
@@ -135,6 +200,16 @@ async fn file_sync_database_rebuild_from_files() {
             calendar_id: None,
             status: None,
             due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         })
         .await
         .unwrap();
@@ -154,6 +229,11 @@ async fn file_sync_database_rebuild_from_files() {
             calendar_id: None,
             startable: None,
             cutoff: None,
+            include_archived: false,
+            meta: Vec::new(),
+            related_to: None,
+            unchecked: false,
+            categories: Vec::new(),
         })
         .await
         .unwrap();
@@ -162,6 +242,16 @@ async fn file_sync_database_rebuild_from_files() {
             calendar_id: None,
             status: None,
             due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         })
         .await
         .unwrap();
@@ -175,6 +265,11 @@ async fn file_sync_database_rebuild_from_files() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -191,6 +286,16 @@ async fn file_sync_database_rebuild_from_files() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -212,13 +317,39 @@ async fn file_sync_add_remove_calendar_files() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config.clone()).await.unwrap();
 
@@ -229,6 +360,11 @@ async fn file_sync_add_remove_calendar_files() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -269,6 +405,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -300,6 +441,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -324,13 +470,39 @@ async fn file_sync_corrupted_file_handling() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Create valid files
@@ -372,6 +544,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -408,13 +585,39 @@ async fn file_sync_mixed_components_in_single_file() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Create file with multiple components
@@ -457,6 +660,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -472,6 +680,16 @@ END:VCALENDAR
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -499,6 +717,114 @@ END:VCALENDAR
     }
 }
 
+#[tokio::test]
+async fn file_sync_keeps_the_components_around_a_broken_one() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let content = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//AIM//Test//EN\r
+BEGIN:VEVENT\r
+UID:export-event-1\r
+DTSTAMP:20250125T120000Z\r
+DTSTART:20250125T100000Z\r
+SUMMARY:Before\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:export-broken\r
+DTSTAMP:20250125T120000Z\r
+DTSTART:next tuesday\r
+SUMMARY:Broken\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:export-event-2\r
+DTSTAMP:20250125T120000Z\r
+DTSTART:20250127T100000Z\r
+SUMMARY:After\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let file_path = temp_dirs.calendar_path.join("export.ics");
+    fs::write(&file_path, content).await.unwrap();
+
+    // Act
+    let aim = Aim::new(config).await.unwrap();
+
+    // Assert - the valid events are read, the broken one is reported
+    let events = aim
+        .list_events(
+            &EventConditions {
+                calendar_id: None,
+                startable: None,
+                cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
+            },
+            &Pager {
+                limit: 10,
+                offset: 0,
+            },
+        )
+        .await
+        .unwrap();
+    let mut uids: Vec<_> = events
+        .iter()
+        .map(|e| e.uid().as_ref().to_string())
+        .collect();
+    uids.sort();
+    assert_eq!(uids, ["export-event-1", "export-event-2"]);
+
+    let notices: Vec<_> = aim
+        .startup_notices()
+        .iter()
+        .filter(|notice| notice.contains("export-broken"))
+        .collect();
+    assert_eq!(notices.len(), 1, "{:?}", aim.startup_notices());
+    assert!(notices[0].contains(&file_path.display().to_string()));
+}
+
 #[tokio::test]
 async fn file_sync_empty_directory_handling() {
     // Arrange
@@ -508,13 +834,39 @@ async fn file_sync_empty_directory_handling() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Act - load empty directory
@@ -527,6 +879,11 @@ async fn file_sync_empty_directory_handling() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -543,6 +900,16 @@ async fn file_sync_empty_directory_handling() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -564,13 +931,39 @@ async fn file_sync_non_ics_files_ignored() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Create valid .ics file
@@ -609,6 +1002,11 @@ END:VCALENDAR
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -642,13 +1040,39 @@ async fn file_sync_persistence_across_restarts() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // First run - create data
@@ -684,6 +1108,11 @@ async fn file_sync_persistence_across_restarts() {
                 calendar_id: None,
                 startable: None,
                 cutoff: None,
+                include_archived: false,
+                meta: Vec::new(),
+                related_to: None,
+                unchecked: false,
+                categories: Vec::new(),
             },
             &Pager {
                 limit: 10,
@@ -700,6 +1129,16 @@ async fn file_sync_persistence_across_restarts() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -711,3 +1150,91 @@ async fn file_sync_persistence_across_restarts() {
         .unwrap();
     assert_eq!(todos.len(), 1);
 }
+
+#[tokio::test]
+async fn file_sync_update_guards_against_external_modification() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let aim1 = Aim::new(config.clone()).await.unwrap();
+    let todo = aim1.new_todo(test_todo_draft("Shared Todo")).await.unwrap();
+    let uid = todo.uid().as_ref().to_string();
+    let file_path = temp_dirs.calendar_path.join(format!("{uid}.ics"));
+    aim1.close().await.unwrap();
+
+    // Reading the calendar records the files as aim saw them
+    let aim = Aim::new(config).await.unwrap();
+    let id = Id::Uid(uid.clone());
+    let edit_summary = async |from: &str, to: &str| {
+        let content = fs::read_to_string(&file_path).await.unwrap();
+        assert!(content.contains(from), "{content}");
+        fs::write(&file_path, content.replace(from, to))
+            .await
+            .unwrap();
+    };
+
+    // Act - another program renames the todo, then aim changes the priority
+    edit_summary("SUMMARY:Shared Todo", "SUMMARY:Renamed elsewhere").await;
+    let patch = TodoPatch {
+        priority: Some(Priority::P1),
+        ..Default::default()
+    };
+    let updated = aim.update_todo(&id, patch).await.unwrap();
+
+    // Assert - both changes are kept
+    assert_eq!(updated.summary().as_ref(), "Renamed elsewhere");
+    assert_eq!(updated.priority(), Priority::P1);
+    let content = fs::read_to_string(&file_path).await.unwrap();
+    assert!(content.contains("SUMMARY:Renamed elsewhere"));
+
+    // Act - another program renames the todo again, then aim renames it too
+    edit_summary("SUMMARY:Renamed elsewhere", "SUMMARY:Renamed again").await;
+    let patch = TodoPatch {
+        summary: Some("Renamed by aim".to_string()),
+        ..Default::default()
+    };
+    let result = aim.update_todo(&id, patch).await;
+
+    // Assert - the update is refused and the external change is kept
+    assert!(matches!(result, Err(AimError::Conflict { uid: ref u }) if *u == uid));
+    let content = fs::read_to_string(&file_path).await.unwrap();
+    assert!(content.contains("SUMMARY:Renamed again"));
+}
@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Home time zone workflow tests.
+//!
+//! These tests validate that a change of the home time zone is warned about once, and that the
+//! floating times anchored to the old zone are rebased through the calendar files.
+
+use jiff::civil::datetime;
+use jiff::tz::TimeZone;
+use tokio::fs;
+
+use aimcal_core::{Aim, Event, Id, LooseDateTime};
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs};
+
+const STANDUP: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//AIM//Test//EN\r
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTAMP:20260101T120000Z\r
+SUMMARY:Standup\r
+DTSTART:20260302T090000\r
+DTEND:20260302T093000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn has_zone_notice(aim: &Aim) -> bool {
+    aim.startup_notices()
+        .iter()
+        .any(|n| n.starts_with("Home time zone changed"))
+}
+
+#[tokio::test]
+async fn home_zone_change_is_warned_once_and_rebased() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let file = temp_dirs.calendar_path.join("standup.ics");
+    fs::write(&file, STANDUP).await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.home_timezone = Some("Europe/Berlin".to_string());
+    let aim = Aim::new(config.clone()).await.unwrap();
+    assert!(
+        !has_zone_notice(&aim),
+        "the first zone seen is not a change"
+    );
+    aim.close().await.unwrap();
+
+    // Act
+    config.home_timezone = Some("Europe/Lisbon".to_string());
+    let aim = Aim::new(config.clone()).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        aim.startup_notices(),
+        [
+            "Home time zone changed from Europe/Berlin to Europe/Lisbon: 1 item with floating times \
+          and 0 items with times in Europe/Berlin now read differently, see `aim tz audit`"
+        ]
+    );
+
+    let (berlin, lisbon) = (
+        TimeZone::get("Europe/Berlin").unwrap(),
+        TimeZone::get("Europe/Lisbon").unwrap(),
+    );
+    let shifts = aim.audit_time_zone(&berlin, &lisbon, true).await.unwrap();
+    let fields: Vec<_> = shifts.iter().map(|s| (s.uid.as_str(), s.field)).collect();
+    assert_eq!(fields, [("standup", "start"), ("standup", "end")]);
+
+    let id = Id::Uid("standup".to_string());
+    let report = aim
+        .rebase_time_zone(&berlin, &lisbon, true, true)
+        .await
+        .unwrap();
+    assert_eq!(report.rebased.len(), 2);
+    let event = aim.get_event(&id).await.unwrap();
+    assert_eq!(
+        event.start(),
+        Some(LooseDateTime::Floating(datetime(2026, 3, 2, 9, 0, 0, 0))),
+        "a dry run writes nothing"
+    );
+
+    let report = aim
+        .rebase_time_zone(&berlin, &lisbon, true, false)
+        .await
+        .unwrap();
+    assert_eq!(report.rebased.len(), 2);
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    let event = aim.get_event(&id).await.unwrap();
+    let in_lisbon = |hour, minute| {
+        let zoned = datetime(2026, 3, 2, hour, minute, 0, 0)
+            .to_zoned(lisbon.clone())
+            .unwrap();
+        Some(LooseDateTime::Local(zoned))
+    };
+    assert_eq!(event.start(), in_lisbon(8, 0));
+    assert_eq!(event.end(), in_lisbon(8, 30));
+    assert!(
+        fs::read_to_string(&file)
+            .await
+            .unwrap()
+            .contains("DTSTART;TZID=Europe/Lisbon:20260302T080000")
+    );
+    assert!(
+        aim.audit_time_zone(&berlin, &lisbon, false)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    // Rebasing again moves nothing
+    let report = aim
+        .rebase_time_zone(&berlin, &lisbon, true, false)
+        .await
+        .unwrap();
+    assert!(report.rebased.is_empty(), "{:?}", report.rebased);
+    assert_eq!(aim.get_event(&id).await.unwrap().start(), in_lisbon(8, 0));
+    aim.close().await.unwrap();
+
+    // The change is only warned about once
+    let aim = Aim::new(config).await.unwrap();
+    assert!(!has_zone_notice(&aim));
+    let zones: Vec<_> = aim
+        .home_time_zones()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(zone, _)| zone)
+        .collect();
+    assert_eq!(zones, ["Europe/Berlin", "Europe/Lisbon"]);
+}
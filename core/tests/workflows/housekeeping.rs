@@ -0,0 +1,130 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Housekeeping workflow tests.
+//!
+//! These tests validate that old items are archived out of the cache only, and come back when
+//! they change in their calendar files.
+
+use tokio::fs;
+
+use aimcal_core::{
+    Aim, HousekeepingConfig, HousekeepingReport, Id, Pager, Todo, TodoConditions, TodoStatus,
+};
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs};
+
+const OLD_TODO: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//AIM//Test//EN\r
+BEGIN:VTODO\r
+UID:old-todo\r
+DTSTAMP:20200101T120000Z\r
+SUMMARY:Finished long ago\r
+STATUS:COMPLETED\r
+COMPLETED:20200101T120000Z\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+fn all_todos(include_archived: bool) -> TodoConditions {
+    TodoConditions {
+        status: None,
+        due: None,
+        start_after: None,
+        hide_unstarted: false,
+        calendar_id: None,
+        include_archived,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    }
+}
+
+#[tokio::test]
+async fn housekeeping_archives_old_todos_in_the_cache_only() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let file = temp_dirs.calendar_path.join("old-todo.ics");
+    fs::write(&file, OLD_TODO).await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.housekeeping = HousekeepingConfig {
+        archive_completed_after: Some("90d".parse().unwrap()),
+        archive_events_after: None,
+    };
+    let aim = Aim::new(config.clone()).await.unwrap();
+    let pager: Pager = (100, 0).into();
+
+    // Act
+    let report = aim.run_housekeeping_if_due().await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        Some(HousekeepingReport {
+            events: 0,
+            todos: 1
+        })
+    );
+    assert!(fs::try_exists(&file).await.unwrap(), "files are kept");
+    let listed = aim
+        .list_todos(&all_todos(false), &[], &pager)
+        .await
+        .unwrap();
+    assert!(listed.is_empty());
+    let listed = aim.list_todos(&all_todos(true), &[], &pager).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    let todo = aim
+        .get_todo(&Id::Uid("old-todo".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(todo.summary(), "Finished long ago");
+
+    let stats = aim.cache_stats().await.unwrap();
+    assert_eq!((stats.todos, stats.archived_todos), (0, 1));
+    assert!(stats.last_housekeeping.is_some());
+
+    // Rate-limited, so it does not run again right away
+    assert_eq!(aim.run_housekeeping_if_due().await.unwrap(), None);
+    aim.close().await.unwrap();
+
+    // Syncing the unchanged file keeps the todo archived
+    let aim = Aim::new(config.clone()).await.unwrap();
+    assert_eq!(aim.cache_stats().await.unwrap().archived_todos, 1);
+    aim.close().await.unwrap();
+
+    // Reopening the todo in its file brings it back
+    let reopened = OLD_TODO
+        .replace("STATUS:COMPLETED", "STATUS:NEEDS-ACTION")
+        .replace("COMPLETED:20200101T120000Z\r\n", "");
+    fs::write(&file, reopened).await.unwrap();
+    let aim = Aim::new(config).await.unwrap();
+    let stats = aim.cache_stats().await.unwrap();
+    assert_eq!((stats.todos, stats.archived_todos), (1, 0));
+    let todo = aim
+        .get_todo(&Id::Uid("old-todo".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(todo.status(), TodoStatus::NeedsAction);
+}
+
+#[tokio::test]
+async fn housekeeping_without_policy_does_not_run() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+
+    // Act
+    let report = aim.run_housekeeping_if_due().await.unwrap();
+
+    // Assert
+    assert_eq!(report, None);
+    assert_eq!(aim.cache_stats().await.unwrap().last_housekeeping, None);
+}
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Markdown task list mirroring workflow tests.
+//!
+//! These tests validate that tasks in notes files become todos, that
+//! completion flows both ways, and that edited tasks are matched to their
+//! todo instead of being duplicated.
+
+use aimcal_core::{Aim, Id, MarkdownConflictKind, Todo, TodoPatch, TodoStatus};
+use tokio::fs;
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs};
+
+#[tokio::test]
+async fn markdown_sync_mirrors_tasks_both_ways() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let notes = temp_dirs.base().join("notes");
+    fs::create_dir_all(notes.join("project")).await.unwrap();
+    let note = notes.join("project").join("plan.md");
+    fs::write(
+        &note,
+        "# Plan\n\n- [ ] write the spec\n- [ ] book flights\n- [x] already done\n",
+    )
+    .await
+    .unwrap();
+
+    let aim = Aim::new(config).await.unwrap();
+
+    // New unchecked tasks become todos
+    let report = aim.sync_markdown(&notes).await.unwrap();
+    assert_eq!(report.files, 1);
+    assert_eq!(report.created, 2);
+    assert!(report.conflicts.is_empty());
+    let spec = aim
+        .find_latest_todo_by_summary("write the spec")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        aim.find_latest_todo_by_summary("already done")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Completing the todo checks the box
+    let patch = TodoPatch {
+        status: Some(TodoStatus::Completed),
+        ..Default::default()
+    };
+    let spec_id = Id::Uid(spec.uid().to_string());
+    aim.update_todo(&spec_id, patch).await.unwrap();
+    let report = aim.sync_markdown(&notes).await.unwrap();
+    assert_eq!(report.checked, 1);
+    let content = fs::read_to_string(&note).await.unwrap();
+    assert!(content.contains("- [x] write the spec"));
+
+    // Checking an edited task completes its todo instead of creating another
+    let content = content.replace("- [ ] book flights", "- [x] book the flights");
+    fs::write(&note, content).await.unwrap();
+    let report = aim.sync_markdown(&notes).await.unwrap();
+    assert_eq!(report.created, 0);
+    assert_eq!(report.completed, 1);
+    assert_eq!(report.conflicts.len(), 1);
+    let conflict = report.conflicts.first().unwrap();
+    assert_eq!(conflict.line, 4);
+    assert_eq!(
+        conflict.kind,
+        MarkdownConflictKind::Edited {
+            from: "book flights".to_string(),
+            to: "book the flights".to_string(),
+        }
+    );
+    let flights = aim
+        .find_latest_todo_by_summary("book the flights")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(flights.status(), TodoStatus::Completed);
+
+    // Nothing left to do
+    let report = aim.sync_markdown(&notes).await.unwrap();
+    assert_eq!(
+        (report.created, report.completed, report.checked),
+        (0, 0, 0)
+    );
+    assert!(report.conflicts.is_empty());
+}
+
+#[tokio::test]
+async fn markdown_sync_reports_removed_tasks() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let notes = temp_dirs.base().join("notes");
+    fs::create_dir_all(&notes).await.unwrap();
+    let note = notes.join("inbox.md");
+    fs::write(&note, "- [ ] call the bank\n").await.unwrap();
+
+    let aim = Aim::new(config).await.unwrap();
+    aim.sync_markdown(&notes).await.unwrap();
+
+    fs::write(&note, "- [ ] something else entirely\n")
+        .await
+        .unwrap();
+    let report = aim.sync_markdown(&notes).await.unwrap();
+    assert_eq!(report.created, 1);
+    assert_eq!(report.conflicts.len(), 1);
+    assert!(matches!(
+        report.conflicts.first().unwrap().kind,
+        MarkdownConflictKind::Missing { .. }
+    ));
+    // The todo of the removed task is kept
+    assert!(
+        aim.find_latest_todo_by_summary("call the bank")
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
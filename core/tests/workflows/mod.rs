@@ -8,7 +8,17 @@
 //! including file-database coordination, configuration integration, and real-world
 //! usage patterns.
 
+mod backup;
 mod config_driven;
+mod custom_store;
 mod event_lifecycle;
 mod file_sync;
+mod home_zone;
+mod housekeeping;
+mod markdown_sync;
+mod rules;
+mod startup;
+mod subscriptions;
+mod sync_plan;
 mod todo_lifecycle;
+mod transactions;
@@ -0,0 +1,176 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Categorization rule workflow tests.
+//!
+//! These tests validate that rules categorize items as they are cached, without changing their
+//! calendar files, and that what they add goes away once a rule is removed and applied again.
+
+use tokio::fs;
+
+use aimcal_core::{Aim, Event, Id, Kind, Priority, RuleVerdict, Rules, RulesReport, Todo};
+
+use crate::common::{setup_temp_dirs, test_config_from_dirs};
+
+const MEETING: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//AIM//Test//EN\r
+BEGIN:VEVENT\r
+UID:meeting\r
+DTSTAMP:20250101T120000Z\r
+DTSTART:20250115T100000Z\r
+DTEND:20250115T110000Z\r
+SUMMARY:Planning\r
+LOCATION:Room 4\r
+ORGANIZER:mailto:boss@mail.corp.example.com\r
+CATEGORIES:Meetings\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+const INVOICE: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//AIM//Test//EN\r
+BEGIN:VTODO\r
+UID:invoice\r
+DTSTAMP:20250101T120000Z\r
+SUMMARY:Pay the invoice\r
+PRIORITY:5\r
+STATUS:NEEDS-ACTION\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+const RULES: &str = r##"
+[[rules]]
+name = "work"
+match = { organizer_domain = "corp.example.com", location = "room" }
+actions = { categories = ["Work"], color = "#1e90ff", transparent = true }
+continue = true
+
+[[rules]]
+name = "bills"
+match = { summary = "(?i)invoice" }
+actions = { categories = ["Bills"], priority = 2 }
+
+[[rules]]
+name = "everything"
+actions = { categories = ["Inbox"] }
+"##;
+
+fn rules(toml: &str) -> Rules {
+    #[derive(serde::Deserialize)]
+    struct Wrapper {
+        rules: Rules,
+    }
+    toml::from_str::<Wrapper>(toml).unwrap().rules
+}
+
+#[tokio::test]
+async fn rules_categorize_items_as_they_are_cached() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let meeting = temp_dirs.calendar_path.join("meeting.ics");
+    let invoice = temp_dirs.calendar_path.join("invoice.ics");
+    fs::write(&meeting, MEETING).await.unwrap();
+    fs::write(&invoice, INVOICE).await.unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.rules = rules(RULES);
+
+    // Act
+    let aim = Aim::new(config).await.unwrap();
+
+    // Assert
+    let event = aim.get_event(&Id::Uid("meeting".into())).await.unwrap();
+    assert_eq!(event.categories(), ["Meetings", "Work", "Inbox"]);
+    assert_eq!(event.color().as_deref(), Some("#1e90ff"));
+    assert!(event.transparent());
+    let todo = aim.get_todo(&Id::Uid("invoice".into())).await.unwrap();
+    assert_eq!(todo.categories(), ["Bills"]);
+    assert_eq!(todo.priority(), Priority::P2);
+    assert_eq!(todo.color(), None);
+
+    assert_eq!(fs::read_to_string(&meeting).await.unwrap(), MEETING);
+    assert_eq!(fs::read_to_string(&invoice).await.unwrap(), INVOICE);
+}
+
+#[tokio::test]
+async fn rules_test_tells_which_rules_fire() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    fs::write(temp_dirs.calendar_path.join("invoice.ics"), INVOICE)
+        .await
+        .unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.rules = rules(RULES);
+    let aim = Aim::new(config).await.unwrap();
+
+    // Act
+    let test = aim.test_rules(&Id::Uid("invoice".into())).await.unwrap();
+
+    // Assert
+    assert_eq!(test.kind, Kind::Todo);
+    assert_eq!(test.summary, "Pay the invoice");
+    let verdicts: Vec<_> = test
+        .traces
+        .iter()
+        .map(|t| (t.name.as_str(), t.verdict))
+        .collect();
+    assert_eq!(
+        verdicts,
+        [
+            ("work", RuleVerdict::NotMatched),
+            ("bills", RuleVerdict::Fired),
+            ("everything", RuleVerdict::Unreached),
+        ]
+    );
+    assert_eq!(test.outcome.categories, ["Bills"]);
+    assert_eq!(test.outcome.priority, Priority::P2);
+}
+
+#[tokio::test]
+async fn rules_removed_and_applied_again_leave_nothing_behind() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let meeting = temp_dirs.calendar_path.join("meeting.ics");
+    fs::write(&meeting, MEETING).await.unwrap();
+    fs::write(temp_dirs.calendar_path.join("invoice.ics"), INVOICE)
+        .await
+        .unwrap();
+    let mut config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    config.rules = rules(RULES);
+    Aim::new(config.clone())
+        .await
+        .unwrap()
+        .close()
+        .await
+        .unwrap();
+
+    // Act - without syncing, so only applying the rules clears what they added
+    config.rules = Rules::default();
+    let aim = Aim::builder(config).skip_sync().build().await.unwrap();
+    let report = aim.apply_rules(&[]).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        RulesReport {
+            events: 1,
+            todos: 1
+        }
+    );
+    let event = aim.get_event(&Id::Uid("meeting".into())).await.unwrap();
+    assert_eq!(event.categories(), ["Meetings"]);
+    assert_eq!(event.color(), None);
+    assert!(!event.transparent());
+    let todo = aim.get_todo(&Id::Uid("invoice".into())).await.unwrap();
+    assert!(todo.categories().is_empty());
+    assert_eq!(todo.priority(), Priority::P5);
+    assert_eq!(fs::read_to_string(&meeting).await.unwrap(), MEETING);
+
+    let report = aim.apply_rules(&[]).await.unwrap();
+    assert_eq!(report, RulesReport::default(), "nothing left to change");
+}
@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Startup workflow tests.
+//!
+//! These tests validate that commands which only read start from the cache,
+//! leaving CalDAV calendars synced a moment ago alone instead of waiting on
+//! the server, unless they are stale, and without waiting on another aim.
+
+use std::fs::File;
+use std::path::Path;
+
+use aimcal_caldav_mock::{MockCalDav, Scenario};
+use aimcal_core::{Aim, Config};
+
+fn caldav_config(state_dir: &Path, base_url: &str, sync_max_age: &str) -> Config {
+    let src = format!(
+        r#"
+state_dir = {state_dir:?}
+default_calendar = "work"
+sync_max_age = "{sync_max_age}"
+
+[stores.server]
+type = "caldav"
+base_url = "{base_url}"
+calendar_home = "/dav/calendars/alice/"
+auth = {{ type = "basic", username = "alice", password = "secret" }}
+
+[[calendars]]
+id = "work"
+name = "Work"
+store = "server"
+calendar_href = "/dav/calendars/alice/work/"
+"#,
+        state_dir = state_dir.display().to_string(),
+    );
+    toml::from_str(&src).unwrap()
+}
+
+async fn start(config: Config, read_only: bool) {
+    let builder = Aim::builder(config);
+    let builder = if read_only {
+        builder.read_only()
+    } else {
+        builder
+    };
+    builder.build().await.unwrap().close().await.unwrap();
+}
+
+#[tokio::test]
+async fn read_only_start_leaves_fresh_caldav_calendars_as_cached() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = caldav_config(state_dir.path(), &server.uri(), "5m");
+
+    start(config.clone(), false).await;
+    let synced = server.requests().await.len();
+    assert!(synced > 0);
+
+    start(config.clone(), true).await;
+    assert_eq!(server.requests().await.len(), synced, "no request is sent");
+
+    start(config, false).await;
+    assert!(
+        server.requests().await.len() > synced,
+        "other commands sync"
+    );
+}
+
+#[tokio::test]
+async fn read_only_start_syncs_stale_caldav_calendars() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = caldav_config(state_dir.path(), &server.uri(), "0s");
+
+    start(config.clone(), false).await;
+    let synced = server.requests().await.len();
+
+    start(config, true).await;
+    assert!(server.requests().await.len() > synced);
+}
+
+#[tokio::test]
+async fn read_only_start_reads_the_cache_while_another_aim_holds_the_lock() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = caldav_config(state_dir.path(), &server.uri(), "0s");
+    start(config.clone(), false).await;
+    let synced = server.requests().await.len();
+
+    // Held through another open file, as another process would
+    let other = File::create(state_dir.path().join("aim.lock")).unwrap();
+    if other.try_lock().is_err() {
+        return; // File locking is unsupported here
+    }
+
+    let aim = Aim::builder(config).read_only().build().await.unwrap();
+    assert!(
+        aim.startup_notices()
+            .iter()
+            .any(|n| n.starts_with("The state directory is in use by")),
+        "{:?}",
+        aim.startup_notices()
+    );
+    assert_eq!(server.requests().await.len(), synced, "no request is sent");
+    aim.close().await.unwrap();
+}
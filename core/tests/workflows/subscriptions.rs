@@ -0,0 +1,201 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Calendar subscription workflow tests.
+//!
+//! These tests validate that remote ICS feeds are mirrored read-only and
+//! keep being served from cache when the feed becomes unreachable.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use aimcal_core::{
+    Aim, AllDayPolicy, BusinessDays, CalendarEntry, CategoryDefaults, Collation, Config,
+    DedupeMode, EncryptionConfig, HousekeepingConfig, OverdueConfig, Pager, Priority, Profile,
+    RecurFrom, Rules, SmartLists, StoreDef, Templates, TodoConditions, TodoDraft, WorkingHours,
+};
+use jiff::SignedDuration;
+use jiff::civil::Weekday;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common::test_todo_draft;
+
+const FEED: &str = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//Test//EN\r\n\
+BEGIN:VTODO\r\n\
+UID:feed-todo\r\n\
+DTSTAMP:20260101T000000Z\r\n\
+SUMMARY:Renew passport\r\n\
+END:VTODO\r\n\
+END:VCALENDAR\r\n";
+
+fn subscription_config(state_dir: PathBuf, local_dir: PathBuf, url: String) -> Config {
+    let mut stores = HashMap::new();
+    stores.insert(
+        "local".to_string(),
+        StoreDef::Local {
+            calendar_path: None,
+        },
+    );
+    stores.insert(
+        "feed".to_string(),
+        StoreDef::Subscription {
+            url,
+            refresh_interval_secs: Some(0),
+            timeout_secs: 5,
+            user_agent: "aimcal-test".to_string(),
+        },
+    );
+    Config {
+        stores,
+        calendar_path: None,
+        state_dir: Some(state_dir),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: vec![
+            CalendarEntry {
+                id: "personal".to_string(),
+                name: "Personal".to_string(),
+                store: "local".to_string(),
+                calendar_href: None,
+                calendar_path: Some(local_dir.to_string_lossy().to_string()),
+                priority: 0,
+                enabled: true,
+                color: None,
+            },
+            CalendarEntry {
+                id: "feed".to_string(),
+                name: "Feed".to_string(),
+                store: "feed".to_string(),
+                calendar_href: None,
+                calendar_path: None,
+                priority: 1,
+                enabled: true,
+                color: None,
+            },
+        ],
+        default_calendar: "personal".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    }
+}
+
+async fn count_feed_todos(aim: &Aim) -> usize {
+    let conds = TodoConditions {
+        status: None,
+        due: None,
+        calendar_id: Some("feed".to_string()),
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    };
+    let pager = Pager {
+        limit: 10,
+        offset: 0,
+    };
+    aim.list_todos(&conds, &[], &pager).await.unwrap().len()
+}
+
+#[tokio::test]
+async fn subscription_mirrors_feed_and_rejects_writes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+        .mount(&server)
+        .await;
+
+    let root = tempfile::tempdir().unwrap();
+    let config = subscription_config(
+        root.path().join("state"),
+        root.path().join("personal"),
+        format!("{}/feed.ics", server.uri()),
+    );
+    let aim = Aim::new(config).await.unwrap();
+
+    assert_eq!(count_feed_todos(&aim).await, 1);
+
+    let draft = TodoDraft {
+        calendar_id: Some("feed".to_string()),
+        ..test_todo_draft("Not allowed")
+    };
+    let err = aim.new_todo(draft).await.err().unwrap();
+    assert!(err.to_string().contains("read-only subscription"), "{err}");
+}
+
+#[tokio::test]
+async fn subscription_refresh_failure_keeps_cached_copy() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let root = tempfile::tempdir().unwrap();
+    let config = subscription_config(
+        root.path().join("state"),
+        root.path().join("personal"),
+        format!("{}/feed.ics", server.uri()),
+    );
+    let aim = Aim::new(config.clone()).await.unwrap();
+    assert_eq!(count_feed_todos(&aim).await, 1);
+    aim.close().await.unwrap();
+
+    let aim = Aim::new(config).await.unwrap();
+    assert!(
+        aim.startup_notices()
+            .iter()
+            .any(|n| n.contains("showing cached copy from")),
+        "{:?}",
+        aim.startup_notices()
+    );
+    assert_eq!(count_feed_todos(&aim).await, 1);
+
+    let results = aim.sync_subscriptions().await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].1.is_err());
+    assert_eq!(count_feed_todos(&aim).await, 1);
+}
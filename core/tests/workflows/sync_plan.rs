@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sync plan workflow tests.
+//!
+//! These tests validate that planning a sync lists the staged writes and the changes to pull
+//! without sending any write to the CalDAV server, and that applying the plan makes exactly those
+//! changes.
+
+use std::path::Path;
+
+use aimcal_caldav_mock::{MockCalDav, Scenario};
+use aimcal_core::{Aim, Config, Id, Kind, SyncDirection, SyncOperation, SyncPlan, Todo};
+
+use crate::common::{
+    sample_event_ics, sample_todo_ics, setup_temp_dirs, test_config_from_dirs, test_todo_draft,
+};
+
+const LUNCH: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//aimcal//mock//EN\r
+BEGIN:VEVENT\r
+UID:lunch\r
+DTSTAMP:20250115T090000Z\r
+DTSTART:20250115T120000Z\r
+DTEND:20250115T130000Z\r
+SUMMARY:Team lunch\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn caldav_config(state_dir: &Path, base_url: &str) -> Config {
+    let src = format!(
+        r#"
+state_dir = {state_dir:?}
+default_calendar = "work"
+
+[stores.server]
+type = "caldav"
+base_url = "{base_url}"
+calendar_home = "/dav/calendars/alice/"
+auth = {{ type = "basic", username = "alice", password = "secret" }}
+
+[[calendars]]
+id = "work"
+name = "Work"
+store = "server"
+calendar_href = "/dav/calendars/alice/work/"
+"#,
+        state_dir = state_dir.display().to_string(),
+    );
+    toml::from_str(&src).unwrap()
+}
+
+/// The requests sent to the server that change it.
+async fn writes(server: &MockCalDav) -> Vec<(String, String)> {
+    server
+        .requests()
+        .await
+        .into_iter()
+        .filter(|(method, _)| method == "PUT" || method == "DELETE")
+        .collect()
+}
+
+/// The changes pulled by a plan, as the UID, operation and properties of each.
+fn pulls(plan: &SyncPlan) -> Vec<(&str, SyncOperation, Vec<&str>)> {
+    plan.pulls()
+        .map(|c| {
+            let fields = c.fields.iter().map(String::as_str).collect();
+            (c.uid.as_str(), c.operation, fields)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn sync_plan_lists_staged_writes_without_sending_them() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = caldav_config(state_dir.path(), &server.uri());
+    let aim = Aim::new(config).await.unwrap();
+
+    let uid = aim
+        .capture_todo("Call the bank")
+        .await
+        .unwrap()
+        .uid()
+        .into_owned();
+    let plan = aim.plan_sync().await.unwrap();
+    assert!(writes(&server).await.is_empty(), "planning sends no write");
+
+    let [change] = plan.changes.as_slice() else {
+        panic!("expected the captured todo to be planned");
+    };
+    assert_eq!(change.uid, uid);
+    assert_eq!(change.kind, Kind::Todo);
+    assert_eq!(change.operation, SyncOperation::Create);
+    assert_eq!(change.calendar_id, "work");
+    assert_eq!(change.summary.as_deref(), Some("Call the bank"));
+    for field in ["SUMMARY", "UID", "X-AIM-INBOX"] {
+        assert!(change.fields.iter().any(|f| f == field), "{field}");
+    }
+
+    // Planning again finds the same write, as nothing was pushed
+    assert_eq!(aim.plan_sync().await.unwrap().changes.len(), 1);
+
+    aim.apply_sync(plan).await.unwrap();
+    let writes = writes(&server).await;
+    assert_eq!(writes.len(), 1, "{writes:?}");
+    let (method, path) = writes.first().unwrap();
+    assert_eq!(method, "PUT");
+    assert!(path.starts_with("/dav/calendars/alice/work/"), "{path}");
+    assert!(aim.plan_sync().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn sync_plan_lists_changes_pulled_from_the_server_without_writing() {
+    let server = MockCalDav::start(Scenario::bundled("basic").unwrap()).await;
+    let state_dir = tempfile::tempdir().unwrap();
+    let config = caldav_config(state_dir.path(), &server.uri());
+    let aim = Aim::new(config).await.unwrap();
+    let uid = aim
+        .new_todo(test_todo_draft("Pay the rent"))
+        .await
+        .unwrap()
+        .uid()
+        .into_owned();
+
+    // Another client adds an event, renames one and deletes the todo created
+    let work = "/dav/calendars/alice/work/";
+    let _ = server.put_resource(&format!("{work}lunch.ics"), LUNCH);
+    let review = server.resource(&format!("{work}review.ics")).unwrap();
+    let renamed = review.data.replace("Design review", "Design review, moved");
+    let _ = server.put_resource(&format!("{work}review.ics"), &renamed);
+    let href = server
+        .hrefs(work)
+        .into_iter()
+        .find(|href| href.contains(&uid))
+        .unwrap();
+    assert!(server.delete_resource(&href));
+    let written = writes(&server).await.len();
+
+    let plan = aim.plan_sync().await.unwrap();
+    assert_eq!(
+        writes(&server).await.len(),
+        written,
+        "planning sends no write"
+    );
+    assert!(plan.pushes().next().is_none());
+    assert!(plan.pulls().all(|c| c.calendar_id == "work"));
+    let pulled = pulls(&plan);
+    let planned = |uid: &str| {
+        let found = pulled.iter().find(|(u, ..)| *u == uid);
+        found.map(|(_, operation, fields)| (*operation, fields.clone()))
+    };
+    let (operation, fields) = planned("lunch").unwrap();
+    assert_eq!(operation, SyncOperation::Create);
+    assert!(fields.contains(&"SUMMARY"), "{fields:?}");
+    let (operation, fields) = planned("review").unwrap();
+    assert_eq!(operation, SyncOperation::Update);
+    assert!(fields.contains(&"SUMMARY"), "{fields:?}");
+    assert_eq!(planned(&uid), Some((SyncOperation::Delete, Vec::new())));
+    assert_eq!(pulled.len(), 3, "{pulled:?}");
+    let todo = plan.pulls().find(|c| c.uid == uid).unwrap();
+    assert_eq!(todo.kind, Kind::Todo);
+    assert_eq!(todo.summary.as_deref(), Some("Pay the rent"));
+
+    aim.apply_sync(plan).await.unwrap();
+    assert_eq!(
+        writes(&server).await.len(),
+        written,
+        "pulling sends no write"
+    );
+    assert!(aim.get_todo(&Id::Uid(uid)).await.is_err());
+    assert!(aim.plan_sync().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn sync_plan_lists_the_properties_changed_in_local_files() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let path = |name: &str| temp_dirs.calendar_path.join(format!("{name}.ics"));
+    for (uid, summary) in [("rent", "Pay the rent"), ("plants", "Water the plants")] {
+        let ics = sample_todo_ics(uid, summary, "20250115");
+        tokio::fs::write(path(uid), ics).await.unwrap();
+    }
+    let config = test_config_from_dirs(&temp_dirs.calendar_path, &temp_dirs.state_dir);
+    let aim = Aim::new(config).await.unwrap();
+    assert!(aim.plan_sync().await.unwrap().is_empty());
+
+    // Another app edits a todo, deletes the other and adds an event
+    let edited = sample_todo_ics("rent", "Pay the rent today", "20250115");
+    tokio::fs::write(path("rent"), edited).await.unwrap();
+    tokio::fs::remove_file(path("plants")).await.unwrap();
+    let event = sample_event_ics("dentist", "Dentist", "20250120");
+    tokio::fs::write(path("dentist"), event).await.unwrap();
+
+    let plan = aim.plan_sync().await.unwrap();
+    assert!(
+        plan.changes
+            .iter()
+            .all(|c| c.direction == SyncDirection::Pull)
+    );
+    let pulled = pulls(&plan);
+    let [
+        ("dentist", SyncOperation::Create, _),
+        ("plants", SyncOperation::Delete, deleted_fields),
+        ("rent", SyncOperation::Update, edited_fields),
+    ] = pulled.as_slice()
+    else {
+        panic!("unexpected pulls: {pulled:?}");
+    };
+    assert!(deleted_fields.is_empty());
+    assert_eq!(edited_fields, &["SUMMARY"]);
+
+    // Planning changes nothing in the cache until the plan is applied
+    let plants = Id::Uid("plants".to_string());
+    assert!(aim.get_todo(&plants).await.is_ok());
+    aim.apply_sync(plan).await.unwrap();
+    assert!(aim.get_todo(&plants).await.is_err());
+    assert!(aim.plan_sync().await.unwrap().is_empty());
+}
@@ -11,9 +11,13 @@
 use std::collections::HashMap;
 
 use aimcal_core::{
-    Aim, Config, DateTimeAnchor, Id, LooseDateTime, Pager, Priority, SortOrder, Todo,
-    TodoConditions, TodoDraft, TodoPatch, TodoSort, TodoStatus,
+    Aim, AllDayPolicy, BusinessDays, CategoryDefaults, Collation, Config, DateTimeAnchor,
+    DedupeMode, EncryptionConfig, HousekeepingConfig, Id, LooseDateTime, OverdueConfig, Pager,
+    Priority, Profile, RecurFrom, Rules, SmartLists, SortOrder, Templates, Todo, TodoConditions,
+    TodoDraft, TodoPatch, TodoSort, TodoStatus, WorkingHours,
 };
+use jiff::SignedDuration;
+use jiff::civil::Weekday;
 
 use crate::common::{setup_temp_dirs, test_todo_draft};
 
@@ -26,25 +30,58 @@ async fn todo_lifecycle_create_with_config_defaults() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(7)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P2,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
     // Act - create todo without explicit due/priority
     let draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         summary: "Task with defaults".to_string(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let todo = aim.new_todo(draft).await.unwrap();
 
@@ -66,13 +103,39 @@ async fn todo_lifecycle_status_evolution() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
     let draft = test_todo_draft("Workflow Task");
@@ -125,13 +188,39 @@ async fn todo_lifecycle_priority_handling() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -167,13 +256,39 @@ async fn todo_lifecycle_sorting() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -181,13 +296,20 @@ async fn todo_lifecycle_sorting() {
     let priorities = [Priority::P5, Priority::P2, Priority::P9, Priority::None];
     for (i, priority) in priorities.iter().enumerate() {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             summary: format!("Task {i}"),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: None,
             priority: Some(*priority),
             status: TodoStatus::NeedsAction,
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
         aim.new_todo(draft).await.unwrap();
     }
@@ -203,6 +325,16 @@ async fn todo_lifecycle_sorting() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort,
             &Pager {
@@ -233,6 +365,16 @@ async fn todo_lifecycle_sorting() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &sort_desc,
             &Pager {
@@ -259,13 +401,39 @@ async fn todo_lifecycle_filtering() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -280,13 +448,20 @@ async fn todo_lifecycle_filtering() {
 
     for (status, summary) in drafts {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             summary: summary.to_string(),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: None,
             priority: None,
             status,
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
         aim.new_todo(draft).await.unwrap();
     }
@@ -296,6 +471,16 @@ async fn todo_lifecycle_filtering() {
         calendar_id: None,
         status: Some(TodoStatus::NeedsAction),
         due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
     };
     let todos_needs = aim
         .list_todos(
@@ -318,6 +503,16 @@ async fn todo_lifecycle_filtering() {
         calendar_id: None,
         status: Some(TodoStatus::Completed),
         due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
     };
     let todos_completed = aim
         .list_todos(
@@ -338,6 +533,16 @@ async fn todo_lifecycle_filtering() {
         calendar_id: None,
         status: None,
         due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
     };
     let todos_all = aim
         .list_todos(
@@ -362,13 +567,39 @@ async fn todo_lifecycle_batch_operations() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: Some(DateTimeAnchor::InDays(1)),
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::P5,
         default_priority_none_fist: true,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -390,6 +621,16 @@ async fn todo_lifecycle_batch_operations() {
             calendar_id: None,
             status: None,
             due: None,
+            start_after: None,
+            hide_unstarted: false,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
         })
         .await
         .unwrap();
@@ -409,6 +650,16 @@ async fn todo_lifecycle_batch_operations() {
         calendar_id: None,
         status: Some(TodoStatus::Completed),
         due: None,
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: false,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
     };
     let completed = aim
         .list_todos(
@@ -438,26 +689,59 @@ async fn todo_lifecycle_percent_complete_validation() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
     // Act - create with valid percent complete values
     for percent in [0u8, 50u8, 100u8] {
         let draft = TodoDraft {
+            alarm: None,
             calendar_id: None,
+            categories: Vec::new(),
             summary: format!("Task {percent}%"),
             description: None,
             due: None,
+            estimate: None,
             percent_complete: Some(percent),
             priority: None,
             status: TodoStatus::NeedsAction,
+            encrypt_description: false,
+            recur_from: None,
+            parent: None,
+            no_defaults: false,
         };
         let todo = aim.new_todo(draft).await.unwrap();
         // Verify percent_complete was set (implementation may have issues)
@@ -474,13 +758,20 @@ async fn todo_lifecycle_percent_complete_validation() {
 
     // Test: create todo without percent_complete, then update it
     let draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         summary: "Progressive Task".to_string(),
         description: None,
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: None,
         status: TodoStatus::NeedsAction,
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let todo = aim.new_todo(draft).await.unwrap();
     let uid = todo.uid().as_ref().to_string();
@@ -531,25 +822,58 @@ async fn todo_lifecycle_metadata_updates() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
     // Create todo with all fields
     let original_draft = TodoDraft {
+        alarm: None,
         calendar_id: None,
+        categories: Vec::new(),
         summary: "Original Summary".to_string(),
         description: Some("Original Description".to_string()),
         due: None,
+        estimate: None,
         percent_complete: None,
         priority: Some(Priority::P3),
         status: TodoStatus::NeedsAction,
+        encrypt_description: false,
+        recur_from: None,
+        parent: None,
+        no_defaults: false,
     };
     let todo = aim.new_todo(original_draft).await.unwrap();
     let uid = todo.uid().as_ref().to_string();
@@ -612,13 +936,39 @@ async fn todo_lifecycle_with_due_dates() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
     let aim = Aim::new(config).await.unwrap();
 
@@ -644,6 +994,138 @@ async fn todo_lifecycle_with_due_dates() {
     assert!(updated.due().is_some());
 }
 
+#[tokio::test]
+async fn todo_lifecycle_scheduled_with_start_and_duration() {
+    // Arrange
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = Config {
+        stores: HashMap::new(),
+        calendar_path: Some(temp_dirs.calendar_path.clone()),
+        state_dir: Some(temp_dirs.state_dir.clone()),
+        default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
+        default_priority: Priority::None,
+        default_priority_none_fist: false,
+        hide_unstarted: true,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
+        config_dir: None,
+        dev_mode: false,
+        read_only_compat: false,
+        calendars: Vec::new(),
+        default_calendar: "default".to_string(),
+        secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
+    };
+    let todo = |uid: &str, dates: &str| {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//AIM//Test//EN\r\nBEGIN:VTODO\r\n\
+             UID:{uid}\r\nDTSTAMP:20250101T000000Z\r\n{dates}SUMMARY:{uid}\r\n\
+             STATUS:NEEDS-ACTION\r\nEND:VTODO\r\nEND:VCALENDAR\r\n"
+        )
+    };
+    let scheduled = todo("scheduled", "DTSTART:20990105T090000\r\nDURATION:PT2H\r\n");
+    let path = temp_dirs
+        .create_ics_file("scheduled", &scheduled)
+        .await
+        .unwrap();
+    let started = todo("started", "DTSTART;VALUE=DATE:20250101\r\n");
+    temp_dirs
+        .create_ics_file("started", &started)
+        .await
+        .unwrap();
+    temp_dirs
+        .create_ics_file("unscheduled", &todo("unscheduled", ""))
+        .await
+        .unwrap();
+    let aim = Aim::new(config).await.unwrap();
+    assert!(aim.hide_unstarted());
+
+    let list = async |start_after: Option<DateTimeAnchor>, hide_unstarted: bool| {
+        let conds = TodoConditions {
+            status: None,
+            due: None,
+            start_after,
+            hide_unstarted,
+            calendar_id: None,
+            include_archived: false,
+            meta: Vec::new(),
+            inbox: false,
+            hide_waiting: false,
+            waiting: false,
+            waiting_for: None,
+            categories: Vec::new(),
+            undated: false,
+        };
+        let sort = [TodoSort::Summary(SortOrder::Asc)];
+        let pager = Pager {
+            limit: 10,
+            offset: 0,
+        };
+        let todos = aim.list_todos(&conds, &sort, &pager).await.unwrap();
+        todos
+            .iter()
+            .map(|t| t.summary().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Assert - todos starting later are left out, or listed on their own
+    assert_eq!(list(None, true).await, ["started", "unscheduled"]);
+    assert_eq!(
+        list(Some(DateTimeAnchor::now()), false).await,
+        ["scheduled"]
+    );
+    assert_eq!(list(None, false).await.len(), 3);
+
+    // Assert - without a due, the todo is due two hours after its start
+    let todo = aim
+        .get_todo(&Id::Uid("scheduled".to_string()))
+        .await
+        .unwrap();
+    let start = jiff::civil::date(2099, 1, 5).at(9, 0, 0, 0);
+    assert_eq!(todo.start(), Some(LooseDateTime::Floating(start)));
+    let due = jiff::civil::date(2099, 1, 5).at(11, 0, 0, 0);
+    assert_eq!(todo.due(), Some(LooseDateTime::Floating(due)));
+
+    // Act - move the due later
+    let due = jiff::civil::date(2099, 1, 5).at(12, 30, 0, 0);
+    let patch = TodoPatch {
+        due: Some(Some(LooseDateTime::Floating(due))),
+        ..Default::default()
+    };
+    let updated = aim
+        .update_todo(&Id::Uid("scheduled".to_string()), patch)
+        .await
+        .unwrap();
+
+    // Assert - the file keeps its start and duration rather than gaining a due
+    assert_eq!(updated.due(), Some(LooseDateTime::Floating(due)));
+    let content = tokio::fs::read_to_string(&path).await.unwrap();
+    assert!(content.contains("DURATION:PT3H30M"), "{content}");
+    assert!(content.contains("DTSTART:20990105T090000"), "{content}");
+    assert!(!content.contains("DUE"), "{content}");
+}
+
 #[tokio::test]
 async fn todo_lifecycle_rebuild_from_files() {
     // Arrange
@@ -653,13 +1135,39 @@ async fn todo_lifecycle_rebuild_from_files() {
         calendar_path: Some(temp_dirs.calendar_path.clone()),
         state_dir: Some(temp_dirs.state_dir.clone()),
         default_due: None,
+        default_due_time: None,
+        default_event_alarm: None,
+        default_event_duration: SignedDuration::from_hours(1),
+        default_estimate: SignedDuration::from_hours(1),
+        exclude_unestimated: false,
+        default_allday_alarm: None,
+        default_todo_alarm: None,
         default_priority: Priority::None,
         default_priority_none_fist: false,
+        hide_unstarted: false,
+        week_start: Weekday::Monday,
+        home_timezone: None,
+        working_hours: WorkingHours::default(),
+        business_days: BusinessDays::default(),
+        housekeeping: HousekeepingConfig::default(),
+        overdue: OverdueConfig::default(),
+        recur_from: RecurFrom::default(),
+        collation: Collation::default(),
+        dedupe_events: DedupeMode::default(),
+        all_day_events: AllDayPolicy::default(),
+        profile: Profile::default(),
+        encryption: EncryptionConfig::default(),
+        rules: Rules::default(),
+        lists: SmartLists::default(),
         config_dir: None,
         dev_mode: false,
+        read_only_compat: false,
         calendars: Vec::new(),
         default_calendar: "default".to_string(),
         secrets_files: vec![],
+        sync_max_age: SignedDuration::ZERO,
+        templates: Templates::default(),
+        categories: CategoryDefaults::default(),
     };
 
     // Create initial todos
@@ -685,6 +1193,16 @@ async fn todo_lifecycle_rebuild_from_files() {
                 calendar_id: None,
                 status: None,
                 due: None,
+                start_after: None,
+                hide_unstarted: false,
+                include_archived: false,
+                meta: Vec::new(),
+                inbox: false,
+                hide_waiting: false,
+                waiting: false,
+                waiting_for: None,
+                categories: Vec::new(),
+                undated: false,
             },
             &[],
             &Pager {
@@ -0,0 +1,279 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction workflow tests.
+//!
+//! These tests validate that changes made in a transaction are written all at once, and that a
+//! batch failing midway leaves neither the cache nor the stores partially changed.
+
+use std::sync::{Arc, Mutex};
+
+use aimcal_core::{
+    Aim, AimError, EventPatch, Id, Pager, Store, StoreCapabilities, StoreError, TodoConditions,
+    TodoDraft, TodoPatch, TodoStatus,
+};
+use aimcal_ical::{VEvent, VTodo};
+use async_trait::async_trait;
+
+use crate::common::{setup_temp_dirs, test_config, test_todo_draft};
+
+type Todos = Arc<Mutex<Vec<(String, VTodo<String>)>>>;
+
+/// Store creating todos in memory, which fails to create more than `capacity` of them.
+struct CappedStore {
+    capacity: usize,
+    todos: Todos,
+}
+
+impl CappedStore {
+    fn unsupported() -> StoreError {
+        "capped store only creates and deletes todos".into()
+    }
+}
+
+#[async_trait]
+impl Store for CappedStore {
+    async fn create_event(&self, _: &str, _: &VEvent<String>) -> Result<String, StoreError> {
+        Err(Self::unsupported())
+    }
+
+    async fn get_event(&self, _: &str) -> Result<VEvent<String>, StoreError> {
+        Err(Self::unsupported())
+    }
+
+    async fn update_event(&self, _: &str, _: &EventPatch) -> Result<VEvent<String>, StoreError> {
+        Err(Self::unsupported())
+    }
+
+    async fn delete_event(&self, _: &str) -> Result<(), StoreError> {
+        Err(Self::unsupported())
+    }
+
+    async fn create_todo(&self, uid: &str, todo: &VTodo<String>) -> Result<String, StoreError> {
+        let mut todos = self.todos.lock().unwrap();
+        if todos.len() >= self.capacity {
+            return Err("capped store is full".into());
+        }
+        todos.push((uid.to_string(), todo.clone()));
+        Ok(format!("capped:{uid}"))
+    }
+
+    async fn get_todo(&self, uid: &str) -> Result<VTodo<String>, StoreError> {
+        let todos = self.todos.lock().unwrap();
+        todos
+            .iter()
+            .find_map(|(u, todo)| (u == uid).then(|| todo.clone()))
+            .ok_or_else(|| format!("Todo not found: {uid}").into())
+    }
+
+    async fn update_todo(&self, _: &str, _: &TodoPatch) -> Result<VTodo<String>, StoreError> {
+        Err(Self::unsupported())
+    }
+
+    async fn delete_todo(&self, uid: &str) -> Result<(), StoreError> {
+        self.todos.lock().unwrap().retain(|(u, _)| u != uid);
+        Ok(())
+    }
+
+    async fn list_events(&self) -> Result<Vec<(String, VEvent<String>)>, StoreError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_todos(&self) -> Result<Vec<(String, VTodo<String>)>, StoreError> {
+        let todos = self.todos.lock().unwrap();
+        Ok(todos
+            .iter()
+            .map(|(uid, todo)| (format!("capped:{uid}"), todo.clone()))
+            .collect())
+    }
+
+    async fn uid_exists(&self, uid: &str) -> Result<bool, StoreError> {
+        Ok(self.get_todo(uid).await.is_ok())
+    }
+
+    fn calendar_id(&self) -> &str {
+        "capped"
+    }
+
+    fn capabilities(&self) -> StoreCapabilities {
+        StoreCapabilities {
+            read_only: false,
+            supports_events: false,
+            supports_todos: true,
+        }
+    }
+}
+
+fn todo_conditions(calendar_id: Option<&str>) -> TodoConditions {
+    TodoConditions {
+        status: None,
+        due: None,
+        calendar_id: calendar_id.map(ToString::to_string),
+        start_after: None,
+        hide_unstarted: false,
+        include_archived: true,
+        meta: Vec::new(),
+        inbox: false,
+        hide_waiting: false,
+        waiting: false,
+        waiting_for: None,
+        categories: Vec::new(),
+        undated: false,
+    }
+}
+
+async fn count_todos(aim: &Aim, calendar_id: Option<&str>) -> usize {
+    let pager = Pager {
+        limit: 100,
+        offset: 0,
+    };
+    aim.list_todos(&todo_conditions(calendar_id), &[], &pager)
+        .await
+        .unwrap()
+        .len()
+}
+
+fn count_ics_files(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .filter(|e| {
+            e.as_ref()
+                .unwrap()
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "ics")
+        })
+        .count()
+}
+
+#[tokio::test]
+async fn transaction_commits_all_changes_at_once() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let aim = Aim::new(config).await.unwrap();
+    let existing = aim.new_todo(test_todo_draft("Existing")).await.unwrap();
+    let existing = Id::Uid(aimcal_core::Todo::uid(&existing).to_string());
+
+    let uids = aim
+        .transaction(|txn| {
+            let existing = existing.clone();
+            Box::pin(async move {
+                let mut uids = Vec::new();
+                for i in 0..10 {
+                    uids.push(txn.new_todo(test_todo_draft(&format!("Todo {i}"))).await?);
+                }
+                // Items created in the transaction can be changed before it is committed
+                let first = Id::Uid(uids.first().unwrap().clone());
+                let done = TodoPatch {
+                    status: Some(TodoStatus::Completed),
+                    ..Default::default()
+                };
+                txn.update_todo(&first, done.clone()).await?;
+                txn.update_todo(&existing, done).await?;
+                Ok(uids)
+            })
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(uids.len(), 10);
+    assert_eq!(count_todos(&aim, None).await, 11);
+    assert_eq!(count_ics_files(&temp_dirs.calendar_path), 11);
+    let first = aim
+        .get_todo(&Id::Uid(uids.first().unwrap().clone()))
+        .await
+        .unwrap();
+    assert_eq!(aimcal_core::Todo::status(&first), TodoStatus::Completed);
+    let existing = aim.get_todo(&existing).await.unwrap();
+    assert_eq!(aimcal_core::Todo::status(&existing), TodoStatus::Completed);
+}
+
+#[tokio::test]
+async fn transaction_failing_midway_writes_nothing() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let aim = Aim::new(config).await.unwrap();
+
+    let err = aim
+        .transaction(|txn| {
+            Box::pin(async move {
+                for i in 0..10 {
+                    if i == 4 {
+                        let missing = Id::Uid("missing-todo".to_string());
+                        txn.update_todo(&missing, TodoPatch::default()).await?;
+                    } else {
+                        txn.new_todo(test_todo_draft(&format!("Todo {i}"))).await?;
+                    }
+                }
+                Ok(())
+            })
+        })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AimError::NotFound { .. }), "{err}");
+    assert_eq!(count_todos(&aim, None).await, 0);
+    assert_eq!(count_ics_files(&temp_dirs.calendar_path), 0);
+}
+
+#[tokio::test]
+async fn transaction_failing_on_commit_rolls_back() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let todos = Todos::default();
+    let store = CappedStore {
+        capacity: 5,
+        todos: todos.clone(),
+    };
+    let aim = Aim::builder(config)
+        .store("Capped", Box::new(store))
+        .build()
+        .await
+        .unwrap();
+
+    let err = aim
+        .transaction(|txn| {
+            Box::pin(async move {
+                for i in 0..10 {
+                    let draft = TodoDraft {
+                        calendar_id: Some("capped".to_string()),
+                        ..test_todo_draft(&format!("Todo {i}"))
+                    };
+                    txn.new_todo(draft).await?;
+                }
+                Ok(())
+            })
+        })
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("full"), "{err}");
+    assert_eq!(count_todos(&aim, Some("capped")).await, 0);
+    // The todos the store took are deleted again
+    assert!(todos.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn transaction_cannot_be_nested() {
+    let temp_dirs = setup_temp_dirs().await.unwrap();
+    let config = test_config(
+        temp_dirs.calendar_path.to_str().unwrap(),
+        Some(temp_dirs.state_dir.to_str().unwrap()),
+    );
+    let aim = Aim::new(config).await.unwrap();
+
+    let txn = aim.begin().unwrap();
+    assert!(aim.begin().is_err());
+    drop(txn);
+    assert!(aim.begin().is_ok());
+}
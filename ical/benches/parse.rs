@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parse-time benchmarks over the bundled fixture.
+//!
+//! The fixture is expanded by repeating its VEVENT/VTODO components (with
+//! unique UIDs) so that the input is large enough to make allocation and
+//! per-property overhead visible.
+//!
+//! The extraction benchmarks read the columns aim keeps of each event and todo, once from the
+//! borrowed components and once after converting them to owned ones first.
+
+use std::hint::black_box;
+
+use aimcal_ical::{CalendarComponent, ICalendar, StringStorage, parse};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+const FIXTURE: &str = include_str!("fixtures/calendar.ics");
+
+/// Repeat the components after the VTIMEZONE block `copies` times.
+fn expand_fixture(copies: usize) -> String {
+    let (head, rest) = FIXTURE
+        .split_once("END:VTIMEZONE\r\n")
+        .expect("fixture must contain a VTIMEZONE");
+    let body = rest
+        .strip_suffix("END:VCALENDAR\r\n")
+        .expect("fixture must end with END:VCALENDAR");
+
+    let mut src = String::with_capacity(head.len() + body.len() * copies + 64);
+    src.push_str(head);
+    src.push_str("END:VTIMEZONE\r\n");
+    for i in 0..copies {
+        src.push_str(&body.replace("@example.com\r\n", &format!("-{i}@example.com\r\n")));
+    }
+    src.push_str("END:VCALENDAR\r\n");
+    src
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for copies in [1, 100, 1000] {
+        let src = expand_fixture(copies);
+        group.throughput(Throughput::Bytes(src.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(copies), &src, |b, src| {
+            b.iter(|| parse(black_box(src)).expect("fixture must parse"));
+        });
+    }
+    group.finish();
+}
+
+/// Reads the texts of the events and todos that the cache of aim keeps, returning their length.
+fn extract_columns<S: StringStorage>(calendars: &[ICalendar<S>]) -> usize {
+    let mut len = 0;
+    for calendar in calendars {
+        for component in &calendar.components {
+            match component {
+                CalendarComponent::Event(event) => {
+                    len += event.uid.content.as_str_lossy().len();
+                    len += event
+                        .summary
+                        .as_ref()
+                        .map_or(0, |s| s.content.as_str_lossy().len());
+                    len += event
+                        .description
+                        .as_ref()
+                        .map_or(0, |d| d.content.as_str_lossy().len());
+                    len += event
+                        .location
+                        .as_ref()
+                        .map_or(0, |l| l.content.as_str_lossy().len());
+                }
+                CalendarComponent::Todo(todo) => {
+                    len += todo.uid.content.as_str_lossy().len();
+                    len += todo
+                        .summary
+                        .as_ref()
+                        .map_or(0, |s| s.content.as_str_lossy().len());
+                    len += todo
+                        .description
+                        .as_ref()
+                        .map_or(0, |d| d.content.as_str_lossy().len());
+                }
+                _ => {}
+            }
+        }
+    }
+    len
+}
+
+fn bench_extract(c: &mut Criterion) {
+    let src = expand_fixture(1000);
+    let mut group = c.benchmark_group("extract");
+    group.throughput(Throughput::Bytes(src.len() as u64));
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            let calendars = parse(black_box(&src)).expect("fixture must parse");
+            extract_columns(&calendars)
+        });
+    });
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let calendars: Vec<_> = parse(black_box(&src))
+                .expect("fixture must parse")
+                .iter()
+                .map(ICalendar::to_owned)
+                .collect();
+            extract_columns(&calendars)
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_extract);
+criterion_main!(benches);
@@ -0,0 +1,373 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Machine-readable diagnostics for parse errors.
+//!
+//! A [`Diagnostic`] is a stable, structured view of a [`ParseError`] for
+//! editors and other tools: a kebab-case code, a severity, the primary span,
+//! related spans and, where the intended value can be guessed, a fix-it.
+
+use crate::parser::ParseError;
+use crate::property::{
+    ActionValue, BusyTypeValue, CalendarScaleValue, ClassificationValue, MethodValue, PropertyKind,
+    StatusValue, TimeTransparencyValue, VersionValue,
+};
+use crate::semantic::SemanticError;
+use crate::string_storage::{Segments, Span};
+use crate::syntax::{ContentLineError, SyntaxError, TreeBuildError};
+use crate::typed::TypedError;
+
+/// Severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// The source cannot be parsed.
+    Error,
+    /// The source can be parsed, but is likely not what was intended.
+    Warning,
+}
+
+/// A structured parse diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+    /// Stable kebab-case identifier of the kind of problem, e.g. `duplicate-property`
+    pub code: &'static str,
+    /// Severity of the problem
+    pub severity: Severity,
+    /// The span the problem is reported at
+    pub span: Span,
+    /// Human-readable description of the problem
+    pub message: String,
+    /// Other locations involved, e.g. the first occurrence of a duplicate
+    pub related: Vec<RelatedSpan>,
+    /// Suggested replacement, if one can be guessed
+    pub fix: Option<FixIt>,
+}
+
+/// A secondary location attached to a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RelatedSpan {
+    /// The related span
+    pub span: Span,
+    /// Why the span is related
+    pub message: String,
+}
+
+/// A suggested edit that resolves a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FixIt {
+    /// The span to replace
+    pub span: Span,
+    /// The replacement text
+    pub replacement: String,
+}
+
+impl Diagnostic {
+    /// Build a diagnostic from a parse error of `src`.
+    ///
+    /// The source is needed to look up the offending text for fix-its.
+    #[must_use]
+    pub fn from_parse_error(src: &str, err: &ParseError<'_>) -> Self {
+        let mut diagnostic = Self {
+            code: code(err),
+            severity: Severity::Error,
+            span: span(err),
+            message: err.to_string(),
+            related: Vec::new(),
+            fix: None,
+        };
+
+        match err {
+            ParseError::Semantic(SemanticError::DuplicateProperty { first, .. }) => {
+                diagnostic.related.push(RelatedSpan {
+                    span: *first,
+                    message: "first defined here".to_string(),
+                });
+            }
+            ParseError::Semantic(SemanticError::TimezoneMismatch { offset, .. }) => {
+                diagnostic.related.push(RelatedSpan {
+                    span: *offset,
+                    message: "UTC offset given here".to_string(),
+                });
+            }
+            ParseError::Typed(TypedError::PropertyInvalidValue { property, span, .. }) => {
+                diagnostic.fix = keyword_fix(src, property, *span);
+            }
+            _ => {}
+        }
+        diagnostic
+    }
+}
+
+fn code(err: &ParseError<'_>) -> &'static str {
+    match err {
+        ParseError::Syntax(SyntaxError::Scanner(err)) => match err {
+            ContentLineError::MissingColon { .. } => "missing-colon",
+            ContentLineError::EmptyLine { .. } => "empty-line",
+            ContentLineError::InvalidParameter { .. } => "invalid-parameter",
+            ContentLineError::MalformedLine { .. } => "malformed-line",
+            ContentLineError::BareLineEnding { .. } => "bare-line-ending",
+            ContentLineError::LineTooLong { .. } => "line-too-long",
+            ContentLineError::TooManyParameters { .. } => "too-many-parameters",
+            ContentLineError::TooManyValues { .. } => "too-many-values",
+        },
+        ParseError::Syntax(SyntaxError::TreeBuilder(err)) => match err {
+            TreeBuildError::UnmatchedEnd { .. } => "unmatched-end",
+            TreeBuildError::UnmatchedBegin { .. } => "unmatched-begin",
+            TreeBuildError::MismatchedNesting { .. } => "mismatched-nesting",
+            TreeBuildError::BeginEndWithParameters { .. } => "begin-end-with-parameters",
+            TreeBuildError::TooManyProperties { .. } => "too-many-properties",
+            TreeBuildError::TooManyComponents { .. } => "too-many-components",
+            TreeBuildError::NestingTooDeep { .. } => "nesting-too-deep",
+        },
+        ParseError::Typed(err) => match err {
+            TypedError::ParameterDuplicated { .. } => "duplicate-parameter",
+            TypedError::ParameterMultipleValuesDisallowed { .. } => "parameter-multiple-values",
+            TypedError::ParameterValueMustBeQuoted { .. } => "parameter-value-must-be-quoted",
+            TypedError::ParameterValueMustNotBeQuoted { .. } => {
+                "parameter-value-must-not-be-quoted"
+            }
+            TypedError::ParameterValueInvalid { .. } => "invalid-parameter-value",
+            TypedError::ValueTypeDisallowed { .. } => "value-type-disallowed",
+            TypedError::ValueSyntax { .. } => "value-syntax",
+            TypedError::BinaryInvalid { .. } => "invalid-binary",
+            TypedError::BinaryTooLarge { .. } => "binary-too-large",
+            TypedError::PropertyUnexpectedKind { .. } => "unexpected-property-kind",
+            TypedError::PropertyMissingValue { .. } => "missing-value",
+            TypedError::PropertyInvalidValueCount { .. } => "invalid-value-count",
+            TypedError::PropertyInvalidValue { .. } => "invalid-value",
+            TypedError::PropertyUnexpectedValue { .. } => "unexpected-value-type",
+        },
+        ParseError::Semantic(err) => match err {
+            SemanticError::UnknownComponent { .. } => "unknown-component",
+            SemanticError::ExpectedComponent { .. } => "expected-component",
+            SemanticError::DuplicateProperty { .. } => "duplicate-property",
+            SemanticError::MissingProperty { .. } => "missing-property",
+            SemanticError::InvalidValue { .. } => "invalid-value",
+            SemanticError::ConstraintViolation { .. } => "constraint-violation",
+            SemanticError::Unanchored { .. } => "unanchored",
+            SemanticError::TimezoneMismatch { .. } => "timezone-mismatch",
+            SemanticError::TimezoneNotFound { .. } => "timezone-not-found",
+        },
+        ParseError::Repaired(repair) => repair.kind.code(),
+    }
+}
+
+/// The span an error is found at.
+pub(crate) fn span(err: &ParseError<'_>) -> Span {
+    match err {
+        ParseError::Syntax(SyntaxError::Scanner(err)) => err.span(),
+        ParseError::Syntax(SyntaxError::TreeBuilder(err)) => match err {
+            TreeBuildError::UnmatchedEnd { span, .. }
+            | TreeBuildError::UnmatchedBegin { span, .. }
+            | TreeBuildError::MismatchedNesting { span, .. }
+            | TreeBuildError::BeginEndWithParameters { span, .. }
+            | TreeBuildError::TooManyProperties { span, .. }
+            | TreeBuildError::TooManyComponents { span, .. }
+            | TreeBuildError::NestingTooDeep { span, .. } => *span,
+        },
+        ParseError::Typed(err) => err.span(),
+        ParseError::Semantic(err) => err.span(),
+        ParseError::Repaired(repair) => repair.span,
+    }
+}
+
+/// Suggests the closest keyword for a misspelled enumerated property value.
+fn keyword_fix(src: &str, property: &PropertyKind<Segments<'_>>, span: Span) -> Option<FixIt> {
+    let keywords = match property {
+        PropertyKind::Action => ActionValue::KEYWORDS,
+        PropertyKind::BusyType => BusyTypeValue::KEYWORDS,
+        PropertyKind::CalScale => CalendarScaleValue::KEYWORDS,
+        PropertyKind::Class => ClassificationValue::KEYWORDS,
+        PropertyKind::Method => MethodValue::KEYWORDS,
+        PropertyKind::Status => StatusValue::KEYWORDS,
+        PropertyKind::Transp => TimeTransparencyValue::KEYWORDS,
+        PropertyKind::Version => VersionValue::KEYWORDS,
+        _ => return None,
+    };
+    let text = src.get(span.into_range())?.to_ascii_uppercase();
+    keywords
+        .iter()
+        .map(|kw| (edit_distance(&text, kw), *kw))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, kw)| *distance <= (kw.len() / 3).max(1))
+        .map(|(_, kw)| FixIt {
+            span,
+            replacement: kw.to_string(),
+        })
+}
+
+/// Levenshtein distance between two ASCII strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut curr = Vec::with_capacity(prev.len());
+        curr.push(i + 1);
+        for ((cb, diag), up) in b.bytes().zip(&prev).zip(prev.iter().skip(1)) {
+            let left = curr.last().copied().unwrap_or_default();
+            curr.push((diag + usize::from(ca != cb)).min(up + 1).min(left + 1));
+        }
+        prev = curr;
+    }
+    prev.last().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_with_diagnostics;
+
+    use super::*;
+
+    fn event(props: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//CalDAV Client//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:12345\r\n\
+DTSTAMP:20250101T000000Z\r\n\
+DTSTART:20250101T100000Z\r\n\
+{props}\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n"
+        )
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("CONFIRMED", "CONFIRMED"), 0);
+        assert_eq!(edit_distance("TENATIVE", "TENTATIVE"), 1);
+        assert_eq!(edit_distance("", "FINAL"), 5);
+        assert_eq!(edit_distance("DRAFT", "FINAL"), 5);
+    }
+
+    #[test]
+    fn misspelled_status_has_fix_it() {
+        let src = event("STATUS:Confrimed\r\n");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert!(calendars.is_none());
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = diagnostics.first().unwrap();
+        assert_eq!(diagnostic.code, "invalid-value");
+        assert_eq!(diagnostic.severity, Severity::Error);
+        let fix = diagnostic.fix.as_ref().unwrap();
+        assert_eq!(fix.replacement, "CONFIRMED");
+        assert_eq!(src.get(fix.span.into_range()), Some("Confrimed"));
+    }
+
+    #[test]
+    fn unrelated_status_has_no_fix_it() {
+        let src = event("STATUS:SOMEDAY\r\n");
+        let (_, diagnostics) = parse_with_diagnostics(&src);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics.first().unwrap().fix, None);
+    }
+
+    #[test]
+    fn duplicate_property_points_to_first_definition() {
+        let src = event("SUMMARY:First\r\nSUMMARY:Second\r\n");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert!(calendars.is_none());
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = diagnostics.first().unwrap();
+        assert_eq!(diagnostic.code, "duplicate-property");
+        let related = diagnostic.related.first().unwrap();
+        assert!(related.span.start < diagnostic.span.start);
+        assert!(src[related.span.start..].starts_with("SUMMARY:First"));
+        assert!(src[diagnostic.span.start..].starts_with("SUMMARY:Second"));
+    }
+
+    #[test]
+    fn tzid_on_utc_date_time_is_a_warning() {
+        let src = event("DTEND;TZID=Europe/Berlin:20250101T110000Z\r\n");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert!(calendars.is_some());
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = diagnostics.first().unwrap();
+        assert_eq!(diagnostic.code, "timezone-mismatch");
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert!(src[diagnostic.span.start..].starts_with("TZID=Europe/Berlin"));
+        let related = diagnostic.related.first().unwrap();
+        assert!(src[related.span.start..].starts_with("DTEND;"));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn vtimezone_offset_unknown_to_its_timezone_is_a_warning() {
+        let vtimezone = |offset_to: &str| {
+            format!(
+                "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//CalDAV Client//EN\r\n\
+BEGIN:VTIMEZONE\r\n\
+TZID:Europe/Berlin\r\n\
+BEGIN:STANDARD\r\n\
+DTSTART:20241027T030000\r\n\
+TZOFFSETFROM:+0200\r\n\
+TZOFFSETTO:{offset_to}\r\n\
+END:STANDARD\r\n\
+END:VTIMEZONE\r\n\
+END:VCALENDAR\r\n"
+            )
+        };
+
+        let src = vtimezone("+0100");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert!(calendars.is_some());
+        assert!(diagnostics.is_empty());
+
+        let src = vtimezone("+0500");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert!(calendars.is_some());
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = diagnostics.first().unwrap();
+        assert_eq!(diagnostic.code, "timezone-mismatch");
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert!(src[diagnostic.span.start..].starts_with("TZID:Europe/Berlin"));
+        let related = diagnostic.related.first().unwrap();
+        assert!(src[related.span.start..].starts_with("TZOFFSETTO:+0500"));
+    }
+
+    #[test]
+    fn syntax_errors_have_codes() {
+        let src = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nEND:VCALENDAR\r\nEND:VEVENT\r\n";
+        let (calendars, diagnostics) = parse_with_diagnostics(src);
+        assert!(calendars.is_none());
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|d| d.code == "mismatched-nesting"));
+    }
+
+    #[test]
+    fn valid_source_has_no_diagnostics() {
+        let src = event("");
+        let (calendars, diagnostics) = parse_with_diagnostics(&src);
+        assert_eq!(calendars.map(|c| c.len()), Some(1));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[expect(clippy::indexing_slicing)]
+    fn serializes_to_json() {
+        let src = event("STATUS:TENATIVE\r\n");
+        let (_, diagnostics) = parse_with_diagnostics(&src);
+        let json = serde_json::to_value(&diagnostics).unwrap();
+        let diagnostic = json.get(0).unwrap();
+        assert_eq!(diagnostic["code"], "invalid-value");
+        assert_eq!(diagnostic["severity"], "error");
+        assert_eq!(diagnostic["fix"]["replacement"], "TENTATIVE");
+        assert!(diagnostic["span"]["start"].is_u64());
+    }
+}
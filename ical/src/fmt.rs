@@ -21,15 +21,15 @@
 //! println!("{}", ics_string);
 //! ```
 
-mod component;
+pub(crate) mod component;
 mod parameter;
 mod property;
-mod value;
+pub(crate) mod value;
 
 use std::io::{self, Write};
 
-use crate::fmt::component::write_icalendar;
-use crate::semantic::ICalendar;
+use crate::fmt::component::{write_icalendar, write_icalendar_streaming};
+use crate::semantic::{CalendarComponent, ICalendar};
 use crate::string_storage::StringStorage;
 
 /// Convenience function to format an `ICalendar` to a `String` (uses default options).
@@ -156,6 +156,10 @@ impl FoldingStyle {
 
 /// iCalendar formatter that writes to any `Write` implementer.
 ///
+/// Output is written line by line: only the current line is buffered, for folding, and the
+/// wrapped writer receives each line once it is ended or folded. Bytes written through the
+/// [`Write`] impl without ending the line are held until [`Write::flush`] is called.
+///
 /// # Example
 ///
 /// ```ignore
@@ -175,20 +179,27 @@ pub struct Formatter<W: Write> {
     options: FormatOptions,
     /// Current line length in bytes (excluding the pending CRLF).
     line_length: usize,
+    /// Bytes of the current line not yet passed to the writer.
+    line: Vec<u8>,
 }
 
 impl<W: Write> Formatter<W> {
     /// Create a new formatter with options.
     #[must_use]
     pub fn new(writer: W, options: FormatOptions) -> Self {
+        // Room for a full line, its CRLF and a multi-byte character that does not fit on it
+        let capacity = options.folding.map_or(0, |max_len| max_len + 6);
         Self {
             writer,
             options,
             line_length: 0,
+            line: Vec::with_capacity(capacity),
         }
     }
 
     /// Get a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses the current line, so flush the formatter first.
     #[must_use]
     pub fn writer_mut(&mut self) -> &mut W {
         &mut self.writer
@@ -200,10 +211,14 @@ impl<W: Write> Formatter<W> {
         &self.writer
     }
 
-    /// Consumes this formatter, returning the underlying writer.
-    #[must_use]
-    pub fn into_writer(self) -> W {
-        self.writer
+    /// Consumes this formatter, returning the underlying writer after passing it the rest of
+    /// the current line.
+    ///
+    /// # Errors
+    /// Returns an error if writing the rest of the line fails.
+    pub fn into_writer(mut self) -> io::Result<W> {
+        self.write_line()?;
+        Ok(self.writer)
     }
 
     /// Write an `ICalendar` to the underlying writer.
@@ -214,9 +229,26 @@ impl<W: Write> Formatter<W> {
         write_icalendar(self, calendar)
     }
 
+    /// Write a calendar whose components are produced one at a time, such as from a database
+    /// cursor, without holding them all in memory.
+    ///
+    /// The properties and components of `calendar` are written first, such as the time zones
+    /// the streamed components refer to, followed by `components` in order.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    pub fn write_calendar_streaming<S: StringStorage>(
+        &mut self,
+        calendar: &ICalendar<S>,
+        components: impl IntoIterator<Item = CalendarComponent<S>>,
+    ) -> io::Result<()> {
+        write_icalendar_streaming(self, calendar, components)
+    }
+
     /// Write a CRLF line ending.
     pub(crate) fn writeln(&mut self) -> io::Result<()> {
-        write!(self.writer, "\r\n")?;
+        self.line.extend_from_slice(b"\r\n");
+        self.write_line()?;
         self.line_length = 0;
         Ok(())
     }
@@ -226,18 +258,31 @@ impl<W: Write> Formatter<W> {
     /// This inserts the RFC 5545 line folding sequence and updates the
     /// line length counter (the whitespace after CRLF counts as 1 byte).
     fn insert_fold(&mut self) -> io::Result<()> {
-        self.writer
-            .write_all(self.options.folding_style.as_bytes())?;
+        let fold = self.options.folding_style.as_bytes();
+        let (line_end, continuation) = fold.split_at(fold.len() - FoldingStyle::continuation_len());
+        self.line.extend_from_slice(line_end);
+        self.write_line()?;
+        self.line.extend_from_slice(continuation);
         self.line_length = FoldingStyle::continuation_len();
         Ok(())
     }
+
+    /// Pass the current line to the underlying writer.
+    fn write_line(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            self.writer.write_all(&self.line)?;
+            self.line.clear();
+        }
+        Ok(())
+    }
 }
 
 impl<W: Write> Write for Formatter<W> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         let Some(max_len) = self.options.folding else {
-            // Folding disabled, write directly
-            return self.writer.write(buf);
+            // Folding disabled, keep the line as is
+            self.line.extend_from_slice(buf);
+            return Ok(buf.len());
         };
 
         // Track if we're in an escape sequence (backslash followed by char)
@@ -285,8 +330,9 @@ impl<W: Write> Write for Formatter<W> {
             // Scan for UTF-8 continuation bytes to avoid breaking multi-byte sequences
             let bytes_to_write = find_safe_write_length(remaining, bytes_to_write);
 
-            // Write the bytes
-            let written = self.writer.write(&remaining[..bytes_to_write])?;
+            // Add the bytes to the line
+            let written = bytes_to_write;
+            self.line.extend_from_slice(&remaining[..written]);
             self.line_length += written;
 
             // Update escape sequence tracking
@@ -306,6 +352,7 @@ impl<W: Write> Write for Formatter<W> {
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        self.write_line()?;
         self.writer.flush()
     }
 }
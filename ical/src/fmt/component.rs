@@ -11,26 +11,28 @@ use std::io::{self, Write};
 
 use crate::fmt::Formatter;
 use crate::fmt::property::{
-    write_prop_action, write_prop_attach, write_prop_attendee, write_prop_calscale,
-    write_prop_categories, write_prop_class, write_prop_completed, write_prop_contact,
-    write_prop_description, write_prop_dtend, write_prop_dtstamp, write_prop_dtstart,
-    write_prop_due, write_prop_duration, write_prop_ex_date, write_prop_freebusy_inner,
-    write_prop_geo, write_prop_last_modified, write_prop_location, write_prop_method,
-    write_prop_organizer, write_prop_percent_complete, write_prop_priority, write_prop_prodid,
-    write_prop_rdate, write_prop_repeat, write_prop_resources, write_prop_rrule,
-    write_prop_sequence, write_prop_status_value, write_prop_summary, write_prop_transp,
-    write_prop_trigger, write_prop_tz_offset_from, write_prop_tz_offset_to, write_prop_tz_url,
-    write_prop_tzid, write_prop_tzname, write_prop_uid, write_prop_url, write_prop_version,
-    write_prop_xname, write_property,
+    write_prop_action, write_prop_attach, write_prop_attendee, write_prop_busytype,
+    write_prop_calscale, write_prop_categories, write_prop_class, write_prop_color,
+    write_prop_comment, write_prop_completed, write_prop_conference, write_prop_contact,
+    write_prop_created, write_prop_description, write_prop_dtend, write_prop_dtstamp,
+    write_prop_dtstart, write_prop_due, write_prop_duration, write_prop_ex_date,
+    write_prop_freebusy_inner, write_prop_geo, write_prop_image, write_prop_last_modified,
+    write_prop_location, write_prop_method, write_prop_name, write_prop_organizer,
+    write_prop_percent_complete, write_prop_priority, write_prop_prodid, write_prop_rdate,
+    write_prop_recurrence_id, write_prop_refresh_interval, write_prop_repeat, write_prop_resources,
+    write_prop_rrule, write_prop_sequence, write_prop_source, write_prop_status_value,
+    write_prop_summary, write_prop_transp, write_prop_trigger, write_prop_tz_offset_from,
+    write_prop_tz_offset_to, write_prop_tz_url, write_prop_tzid, write_prop_tzname, write_prop_uid,
+    write_prop_url, write_prop_version, write_prop_xname, write_property,
 };
 use crate::keyword::{
-    KW_BEGIN, KW_DAYLIGHT, KW_END, KW_STANDARD, KW_VALARM, KW_VCALENDAR, KW_VEVENT, KW_VFREEBUSY,
-    KW_VJOURNAL, KW_VTIMEZONE, KW_VTODO,
+    KW_AVAILABLE, KW_BEGIN, KW_DAYLIGHT, KW_END, KW_STANDARD, KW_VALARM, KW_VAVAILABILITY,
+    KW_VCALENDAR, KW_VEVENT, KW_VFREEBUSY, KW_VJOURNAL, KW_VTIMEZONE, KW_VTODO,
 };
 use crate::parameter::FreeBusyType;
 use crate::semantic::{
-    CalendarComponent, ICalendar, TimeZoneObservance, UnrecognizedComponent, VAlarm, VEvent,
-    VFreeBusy, VJournal, VTimeZone, VTodo, XComponent,
+    Available, CalendarComponent, ICalendar, TimeZoneObservance, UnrecognizedComponent, VAlarm,
+    VAvailability, VEvent, VFreeBusy, VJournal, VTimeZone, VTodo, XComponent,
 };
 use crate::string_storage::StringStorage;
 
@@ -38,6 +40,15 @@ use crate::string_storage::StringStorage;
 pub fn write_icalendar<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     calendar: &ICalendar<S>,
+) -> io::Result<()> {
+    write_icalendar_streaming(f, calendar, std::iter::empty())
+}
+
+/// Format an `ICalendar` component, followed by `components` after its own ones.
+pub fn write_icalendar_streaming<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    calendar: &ICalendar<S>,
+    components: impl IntoIterator<Item = CalendarComponent<S>>,
 ) -> io::Result<()> {
     with_block(f, KW_VCALENDAR, |f| {
         // Required properties
@@ -51,6 +62,21 @@ pub fn write_icalendar<S: StringStorage>(
         if let Some(method) = &calendar.method {
             write_prop_method(f, method)?;
         }
+        for name in &calendar.names {
+            write_prop_name(f, name)?;
+        }
+        for description in &calendar.descriptions {
+            write_prop_description(f, description)?;
+        }
+        if let Some(color) = &calendar.color {
+            write_prop_color(f, color)?;
+        }
+        if let Some(refresh_interval) = &calendar.refresh_interval {
+            write_prop_refresh_interval(f, refresh_interval)?;
+        }
+        if let Some(source) = &calendar.source {
+            write_prop_source(f, source)?;
+        }
 
         // X-properties
         for prop in &calendar.x_properties {
@@ -66,6 +92,9 @@ pub fn write_icalendar<S: StringStorage>(
         for component in &calendar.components {
             write_calendar_component(f, component)?;
         }
+        for component in components {
+            write_calendar_component(f, &component)?;
+        }
 
         Ok(())
     })
@@ -74,7 +103,7 @@ pub fn write_icalendar<S: StringStorage>(
 /// Format a calendar component (handles all component types).
 ///
 /// This handles `Property::XName` and `Property::Unrecognized` variants.
-fn write_calendar_component<S: StringStorage>(
+pub(crate) fn write_calendar_component<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     component: &CalendarComponent<S>,
 ) -> io::Result<()> {
@@ -85,13 +114,14 @@ fn write_calendar_component<S: StringStorage>(
         CalendarComponent::VFreeBusy(v) => write_vfreebusy(f, v),
         CalendarComponent::VTimeZone(v) => write_vtimezone(f, v),
         CalendarComponent::VAlarm(v) => write_valarm(f, v),
+        CalendarComponent::VAvailability(v) => write_vavailability(f, v),
         CalendarComponent::XComponent(v) => write_x_component(f, v),
         CalendarComponent::Unrecognized(v) => write_unrecognized_component(f, v),
     }
 }
 
 /// Format a `VEvent` component.
-fn write_vevent<S: StringStorage>(
+pub(crate) fn write_vevent<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     event: &VEvent<S>,
 ) -> io::Result<()> {
@@ -99,9 +129,11 @@ fn write_vevent<S: StringStorage>(
         // Required properties
         write_prop_uid(f, &event.uid)?;
         write_prop_dtstamp(f, &event.dt_stamp)?;
-        write_prop_dtstart(f, &event.dt_start)?;
 
         // Optional properties
+        if let Some(dt_start) = &event.dt_start {
+            write_prop_dtstart(f, dt_start)?;
+        }
         if let Some(dt_end) = &event.dt_end {
             write_prop_dtend(f, dt_end)?;
         }
@@ -152,12 +184,30 @@ fn write_vevent<S: StringStorage>(
         if let Some(classification) = &event.classification {
             write_prop_class(f, classification)?;
         }
-        if let Some(resources) = &event.resources {
+        for resources in &event.resources {
             write_prop_resources(f, resources)?;
         }
-        if let Some(categories) = &event.categories {
+        for categories in &event.categories {
             write_prop_categories(f, categories)?;
         }
+        for comment in &event.comments {
+            write_prop_comment(f, comment)?;
+        }
+        for attach in &event.attachments {
+            write_prop_attach(f, attach)?;
+        }
+        for contact in &event.contacts {
+            write_prop_contact(f, contact)?;
+        }
+        if let Some(color) = &event.color {
+            write_prop_color(f, color)?;
+        }
+        for image in &event.images {
+            write_prop_image(f, image)?;
+        }
+        for conference in &event.conferences {
+            write_prop_conference(f, conference)?;
+        }
         if let Some(rrule) = &event.rrule {
             write_prop_rrule(f, rrule)?;
         }
@@ -188,7 +238,11 @@ fn write_vevent<S: StringStorage>(
 }
 
 /// Format a `VTodo` component.
-fn write_vtodo<S: StringStorage>(f: &mut Formatter<impl Write>, todo: &VTodo<S>) -> io::Result<()> {
+#[expect(clippy::too_many_lines)]
+pub(crate) fn write_vtodo<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    todo: &VTodo<S>,
+) -> io::Result<()> {
     with_block(f, KW_VTODO, |f| {
         // Required properties
         write_prop_uid(f, &todo.uid)?;
@@ -251,12 +305,30 @@ fn write_vtodo<S: StringStorage>(f: &mut Formatter<impl Write>, todo: &VTodo<S>)
         if let Some(classification) = &todo.classification {
             write_prop_class(f, classification)?;
         }
-        if let Some(resources) = &todo.resources {
+        for resources in &todo.resources {
             write_prop_resources(f, resources)?;
         }
-        if let Some(categories) = &todo.categories {
+        for categories in &todo.categories {
             write_prop_categories(f, categories)?;
         }
+        for comment in &todo.comments {
+            write_prop_comment(f, comment)?;
+        }
+        for attach in &todo.attachments {
+            write_prop_attach(f, attach)?;
+        }
+        for contact in &todo.contacts {
+            write_prop_contact(f, contact)?;
+        }
+        if let Some(color) = &todo.color {
+            write_prop_color(f, color)?;
+        }
+        for image in &todo.images {
+            write_prop_image(f, image)?;
+        }
+        for conference in &todo.conferences {
+            write_prop_conference(f, conference)?;
+        }
         if let Some(rrule) = &todo.rrule {
             write_prop_rrule(f, rrule)?;
         }
@@ -287,7 +359,7 @@ fn write_vtodo<S: StringStorage>(f: &mut Formatter<impl Write>, todo: &VTodo<S>)
 }
 
 /// Format a `VJournal` component.
-fn write_vjournal<S: StringStorage>(
+pub(crate) fn write_vjournal<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     journal: &VJournal<S>,
 ) -> io::Result<()> {
@@ -327,6 +399,15 @@ fn write_vjournal<S: StringStorage>(
         for categories in &journal.categories {
             write_prop_categories(f, categories)?;
         }
+        for comment in &journal.comments {
+            write_prop_comment(f, comment)?;
+        }
+        for attach in &journal.attachments {
+            write_prop_attach(f, attach)?;
+        }
+        for contact in &journal.contacts {
+            write_prop_contact(f, contact)?;
+        }
         if let Some(rrule) = &journal.rrule {
             write_prop_rrule(f, rrule)?;
         }
@@ -432,6 +513,157 @@ fn write_vfreebusy<S: StringStorage>(
     })
 }
 
+/// Format a `VAvailability` component.
+fn write_vavailability<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    availability: &VAvailability<S>,
+) -> io::Result<()> {
+    with_block(f, KW_VAVAILABILITY, |f| {
+        // Required properties
+        write_prop_uid(f, &availability.uid)?;
+        write_prop_dtstamp(f, &availability.dt_stamp)?;
+
+        // Optional properties
+        if let Some(dt_start) = &availability.dt_start {
+            write_prop_dtstart(f, dt_start)?;
+        }
+        if let Some(dt_end) = &availability.dt_end {
+            write_prop_dtend(f, dt_end)?;
+        }
+        if let Some(duration) = &availability.duration {
+            write_prop_duration(f, duration)?;
+        }
+        if let Some(busy_type) = &availability.busy_type {
+            write_prop_busytype(f, busy_type)?;
+        }
+        if let Some(priority) = &availability.priority {
+            write_prop_priority(f, priority)?;
+        }
+        if let Some(summary) = &availability.summary {
+            write_prop_summary(f, summary)?;
+        }
+        if let Some(description) = &availability.description {
+            write_prop_description(f, description)?;
+        }
+        if let Some(location) = &availability.location {
+            write_prop_location(f, location)?;
+        }
+        if let Some(organizer) = &availability.organizer {
+            write_prop_organizer(f, organizer)?;
+        }
+        if let Some(url) = &availability.url {
+            write_prop_url(f, url)?;
+        }
+        if let Some(classification) = &availability.classification {
+            write_prop_class(f, classification)?;
+        }
+        if let Some(created) = &availability.created {
+            write_prop_created(f, created)?;
+        }
+        if let Some(last_modified) = &availability.last_modified {
+            write_prop_last_modified(f, last_modified)?;
+        }
+        if let Some(sequence) = &availability.sequence {
+            write_prop_sequence(f, sequence)?;
+        }
+        for categories in &availability.categories {
+            write_prop_categories(f, categories)?;
+        }
+        for comment in &availability.comments {
+            write_prop_comment(f, comment)?;
+        }
+        for contact in &availability.contacts {
+            write_prop_contact(f, contact)?;
+        }
+
+        // X-properties
+        for prop in &availability.x_properties {
+            write_prop_xname(f, prop)?;
+        }
+
+        // Unrecognized properties
+        for prop in &availability.retained_properties {
+            write_property(f, prop)?;
+        }
+
+        // Available time
+        for available in &availability.available {
+            write_available(f, available)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Format an `Available` sub-component.
+fn write_available<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    available: &Available<S>,
+) -> io::Result<()> {
+    with_block(f, KW_AVAILABLE, |f| {
+        // Required properties
+        write_prop_uid(f, &available.uid)?;
+        write_prop_dtstamp(f, &available.dt_stamp)?;
+        write_prop_dtstart(f, &available.dt_start)?;
+
+        // Optional properties
+        if let Some(dt_end) = &available.dt_end {
+            write_prop_dtend(f, dt_end)?;
+        }
+        if let Some(duration) = &available.duration {
+            write_prop_duration(f, duration)?;
+        }
+        if let Some(recurrence_id) = &available.recurrence_id {
+            write_prop_recurrence_id(f, recurrence_id)?;
+        }
+        if let Some(summary) = &available.summary {
+            write_prop_summary(f, summary)?;
+        }
+        if let Some(description) = &available.description {
+            write_prop_description(f, description)?;
+        }
+        if let Some(location) = &available.location {
+            write_prop_location(f, location)?;
+        }
+        if let Some(created) = &available.created {
+            write_prop_created(f, created)?;
+        }
+        if let Some(last_modified) = &available.last_modified {
+            write_prop_last_modified(f, last_modified)?;
+        }
+        for categories in &available.categories {
+            write_prop_categories(f, categories)?;
+        }
+        for comment in &available.comments {
+            write_prop_comment(f, comment)?;
+        }
+        for contact in &available.contacts {
+            write_prop_contact(f, contact)?;
+        }
+        if let Some(rrule) = &available.rrule {
+            write_prop_rrule(f, rrule)?;
+        }
+        for rdate in &available.rdates {
+            write_prop_rdate(f, rdate)?;
+        }
+        for exdate in &available.ex_dates {
+            write_prop_ex_date(f, exdate)?;
+        }
+
+        // X-properties
+        for prop in &available.x_properties {
+            write_prop_xname(f, prop)?;
+        }
+
+        // Unrecognized properties
+        for prop in &available.retained_properties {
+            write_property(f, prop)?;
+        }
+
+        Ok(())
+    })
+}
+
 /// Format a `VTimeZone` component.
 fn write_vtimezone<S: StringStorage>(
     f: &mut Formatter<impl Write>,
@@ -7,17 +7,19 @@
 //! This module provides functions to format all iCalendar parameter types
 //! as defined in RFC 5545 Section 3.2.
 
+use std::borrow::Cow;
 use std::io::{self, Write};
 
 use crate::fmt::Formatter;
 use crate::keyword::{
-    KW_ALTREP, KW_CN, KW_CUTYPE, KW_DELEGATED_FROM, KW_DELEGATED_TO, KW_DIR, KW_ENCODING,
-    KW_FBTYPE, KW_FMTTYPE, KW_LANGUAGE, KW_MEMBER, KW_PARTSTAT, KW_RANGE, KW_RELATED, KW_RELTYPE,
-    KW_ROLE, KW_RSVP, KW_RSVP_FALSE, KW_RSVP_TRUE, KW_SENT_BY, KW_TZID, KW_VALUE,
+    KW_ALTREP, KW_CN, KW_CUTYPE, KW_DELEGATED_FROM, KW_DELEGATED_TO, KW_DIR, KW_DISPLAY, KW_EMAIL,
+    KW_ENCODING, KW_FBTYPE, KW_FEATURE, KW_FMTTYPE, KW_LABEL, KW_LANGUAGE, KW_MEMBER, KW_PARTSTAT,
+    KW_RANGE, KW_RELATED, KW_RELTYPE, KW_ROLE, KW_RSVP, KW_RSVP_FALSE, KW_RSVP_TRUE, KW_SENT_BY,
+    KW_TZID, KW_VALUE,
 };
 use crate::parameter::{
-    AlarmTriggerRelationship, CalendarUserType, Encoding, FreeBusyType, Parameter,
-    ParticipationRole, ParticipationStatus, RelationshipType,
+    AlarmTriggerRelationship, CalendarUserType, DisplayType, Encoding, FeatureType, FreeBusyType,
+    Parameter, ParticipationRole, ParticipationStatus, RelationshipType,
 };
 use crate::string_storage::StringStorage;
 use crate::syntax::RawParameter;
@@ -75,6 +77,10 @@ fn write_parameter<S: StringStorage>(
         Parameter::RsvpExpectation { value, .. } => write_param_rsvp(f, *value),
         Parameter::TimeZoneIdentifier { value, .. } => write_param_tzid(f, value),
         Parameter::ValueType { value, .. } => write_param_value(f, value),
+        Parameter::DisplayType { values, .. } => write_param_display(f, values),
+        Parameter::Email { value, .. } => write_param_email(f, value),
+        Parameter::FeatureType { values, .. } => write_param_feature(f, values),
+        Parameter::Label { value, .. } => write_param_label(f, value),
         Parameter::XName(raw) => write_param_xname(f, raw),
         Parameter::Unrecognized(raw) => write_param_unrecognized(f, raw),
     }
@@ -92,7 +98,8 @@ pub fn write_param_altrep<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_ALTREP, &value)?;
     write!(f, ";{KW_ALTREP}={quoted}")
 }
 
@@ -101,7 +108,8 @@ pub fn write_param_cn<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_CN, &value)?;
     write!(f, ";{KW_CN}={quoted}")
 }
 
@@ -119,7 +127,7 @@ pub fn write_param_delegated_from<S: StringStorage>(
     values: &[S],
 ) -> io::Result<()> {
     write!(f, ";{KW_DELEGATED_FROM}=")?;
-    format_quoted_list(f, values)
+    format_quoted_list(f, KW_DELEGATED_FROM, values)
 }
 
 /// Write DELEGATED-TO parameter (multi-value, quoted)
@@ -128,7 +136,7 @@ pub fn write_param_delegated_to<S: StringStorage>(
     values: &[S],
 ) -> io::Result<()> {
     write!(f, ";{KW_DELEGATED_TO}=")?;
-    format_quoted_list(f, values)
+    format_quoted_list(f, KW_DELEGATED_TO, values)
 }
 
 /// Write a DIR parameter
@@ -136,7 +144,8 @@ pub fn write_param_dir<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_DIR, &value)?;
     write!(f, ";{KW_DIR}={quoted}")
 }
 
@@ -150,7 +159,8 @@ pub fn write_param_fmttype<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_FMTTYPE, &value)?;
     write!(f, ";{KW_FMTTYPE}={quoted}")
 }
 
@@ -167,7 +177,8 @@ pub fn write_param_language<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_LANGUAGE, &value)?;
     write!(f, ";{KW_LANGUAGE}={quoted}")
 }
 
@@ -177,7 +188,7 @@ pub fn write_param_member<S: StringStorage>(
     values: &[S],
 ) -> io::Result<()> {
     write!(f, ";{KW_MEMBER}=")?;
-    format_quoted_list(f, values)
+    format_quoted_list(f, KW_MEMBER, values)
 }
 
 /// Write a PARTSTAT parameter
@@ -225,7 +236,8 @@ pub fn write_param_sent_by<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_SENT_BY, &value)?;
     write!(f, ";{KW_SENT_BY}={quoted}")
 }
 
@@ -240,7 +252,8 @@ pub fn write_param_tzid<S: StringStorage>(
     f: &mut Formatter<impl Write>,
     value: &S,
 ) -> io::Result<()> {
-    let quoted = quote_if_needed(value.to_string());
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_TZID, &value)?;
     write!(f, ";{KW_TZID}={quoted}")
 }
 
@@ -252,6 +265,44 @@ pub fn write_param_value<S: StringStorage>(
     write!(f, ";{KW_VALUE}={value}")
 }
 
+/// Write a DISPLAY parameter (multi-value)
+pub fn write_param_display<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    values: &[DisplayType<S>],
+) -> io::Result<()> {
+    write!(f, ";{KW_DISPLAY}=")?;
+    format_list(f, values)
+}
+
+/// Write an EMAIL parameter
+pub fn write_param_email<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    value: &S,
+) -> io::Result<()> {
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_EMAIL, &value)?;
+    write!(f, ";{KW_EMAIL}={quoted}")
+}
+
+/// Write a FEATURE parameter (multi-value)
+pub fn write_param_feature<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    values: &[FeatureType<S>],
+) -> io::Result<()> {
+    write!(f, ";{KW_FEATURE}=")?;
+    format_list(f, values)
+}
+
+/// Write a LABEL parameter
+pub fn write_param_label<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    value: &S,
+) -> io::Result<()> {
+    let value = value.to_string();
+    let quoted = quote_if_needed(KW_LABEL, &value)?;
+    write!(f, ";{KW_LABEL}={quoted}")
+}
+
 /// Write an X-NAME parameter
 pub fn write_param_xname<S: StringStorage>(
     f: &mut Formatter<impl Write>,
@@ -273,70 +324,121 @@ fn write_param_syntax<S: StringStorage>(
     raw: &RawParameter<S>,
 ) -> io::Result<()> {
     // Unrecognized: name=value
-    write!(f, ";{}", raw.name)?;
-    if !raw.values.is_empty() {
-        write!(f, "=")?;
-        // Format values as comma-separated list (quoted if needed)
-        for (i, v) in raw.values.iter().enumerate() {
-            if i > 0 {
-                write!(f, ",")?;
-            }
-            let s = v.value.to_string();
-            write!(f, "{}", quote_if_needed(&s))?;
+    let name = raw.name.to_string();
+    write!(f, ";{name}=")?;
+    if raw.values.is_empty() {
+        // A parameter always carries a value, so write an explicit empty one
+        return write!(f, "\"\"");
+    }
+
+    // Format values as comma-separated list (quoted if needed)
+    for (i, v) in raw.values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
         }
+        let s = v.value.to_string();
+        write!(f, "{}", quote_if_needed(&name, &s)?)?;
     }
     Ok(())
 }
 
-/// Quote a string if it contains special characters.
+/// Quote a parameter value if it contains special characters.
 ///
-/// Per RFC 5545, parameter values containing these characters MUST be quoted:
-/// - Control characters
-/// - DQUOTE (")
-/// - Semicolon (;)
-/// - Colon (:)
-/// - Backslash (\)
-/// - Comma (,)
-fn quote_if_needed<S: AsRef<str>>(s: S) -> String {
-    // Check if string needs quoting
-    let needs_quoting = s
-        .as_ref()
-        .chars()
-        .any(|c| c.is_ascii_control() || c == '"' || c == ';' || c == ':' || c == '\\' || c == ',');
-
-    if needs_quoting {
-        let s = s.as_ref().replace('\\', r"\\").replace('"', r#"\""#);
-        format!("\"{s}\"")
+/// Per RFC 5545, a parameter value MUST be quoted if it contains a semicolon
+/// (`;`), colon (`:`) or comma (`,`). Values are also quoted when empty or when
+/// they contain `=`, since neither can be read back unquoted. Otherwise the
+/// value is written as is, so that formatting does not introduce needless
+/// changes.
+///
+/// # Errors
+///
+/// Returns an [`io::ErrorKind::InvalidInput`] error if the value contains a
+/// DQUOTE or a control character other than HTAB, as neither can be represented
+/// in a parameter value, quoted or not.
+fn quote_if_needed<'a>(name: &str, s: &'a str) -> io::Result<Cow<'a, str>> {
+    check_param_value(name, s)?;
+    let needs_quoting = s.is_empty() || s.contains([';', ':', ',', '=']);
+    Ok(if needs_quoting {
+        Cow::Owned(format!("\"{s}\""))
     } else {
-        s.as_ref().to_string()
+        Cow::Borrowed(s)
+    })
+}
+
+/// Check that a value can be written as a parameter value.
+///
+/// See [`quote_if_needed`] for the characters that are rejected.
+fn check_param_value(name: &str, s: &str) -> io::Result<()> {
+    match s
+        .chars()
+        .find(|&c| c == '"' || (c.is_control() && c != '\t'))
+    {
+        Some(c) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid character {c:?} in {name} parameter value: {s:?}"),
+        )),
+        None => Ok(()),
     }
 }
 
 /// Format a quoted list for multi-value parameters (MEMBER, DELEGATED-TO, DELEGATED-FROM)
+///
+/// The values of these parameters are cal-addresses, which RFC 5545 requires to
+/// always be quoted.
 fn format_quoted_list<S: StringStorage>(
     f: &mut Formatter<impl Write>,
+    name: &str,
     values: &[S],
 ) -> io::Result<()> {
     for (i, value) in values.iter().enumerate() {
         if i > 0 {
             write!(f, ",")?;
         }
+        let value = value.to_string();
+        check_param_value(name, &value)?;
         write!(f, "\"{value}\"")?;
     }
     Ok(())
 }
 
+/// Format an unquoted list for multi-value parameters of keywords (DISPLAY, FEATURE)
+fn format_list<T: std::fmt::Display>(
+    f: &mut Formatter<impl Write>,
+    values: &[T],
+) -> io::Result<()> {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{value}")?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_quote_if_needed() {
-        assert_eq!(quote_if_needed("simple"), "simple");
-        assert_eq!(quote_if_needed("with;semicolon"), "\"with;semicolon\"");
-        assert_eq!(quote_if_needed("with:colon"), "\"with:colon\"");
-        assert_eq!(quote_if_needed("with,comma"), "\"with,comma\"");
-        assert_eq!(quote_if_needed("with\\backslash"), r#""with\\backslash""#);
-        assert_eq!(quote_if_needed("with\"quote"), r#""with\"quote""#);
+        let quote = |s| quote_if_needed("CN", s).unwrap().into_owned();
+        assert_eq!(quote("simple"), "simple");
+        assert_eq!(quote("with space"), "with space");
+        assert_eq!(quote("with;semicolon"), "\"with;semicolon\"");
+        assert_eq!(quote("with:colon"), "\"with:colon\"");
+        assert_eq!(quote("with,comma"), "\"with,comma\"");
+        assert_eq!(quote("with=equals"), "\"with=equals\"");
+        assert_eq!(quote(r"with\backslash"), r"with\backslash");
+        assert_eq!(quote("with\ttab"), "with\ttab");
+        assert_eq!(quote(""), "\"\"");
+    }
+
+    #[test]
+    fn test_quote_if_needed_rejects_unrepresentable() {
+        for s in ["with\"quote", "with\nnewline", "with\u{7f}delete"] {
+            let err = quote_if_needed("CN", s).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains("CN"));
+        }
     }
 }
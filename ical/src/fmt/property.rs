@@ -10,41 +10,44 @@
 use std::fmt::Display;
 use std::io::{self, Write};
 
-use crate::StatusValue;
 use crate::fmt::Formatter;
 use crate::fmt::parameter::{
     write_param_altrep, write_param_cn, write_param_cutype, write_param_delegated_from,
-    write_param_delegated_to, write_param_dir, write_param_encoding, write_param_fbtype,
-    write_param_fmttype, write_param_language, write_param_member, write_param_partstat,
-    write_param_related, write_param_reltype, write_param_role, write_param_rsvp,
-    write_param_sent_by, write_param_tzid, write_parameters, write_syntax_parameters,
+    write_param_delegated_to, write_param_dir, write_param_display, write_param_encoding,
+    write_param_fbtype, write_param_feature, write_param_fmttype, write_param_label,
+    write_param_language, write_param_member, write_param_partstat, write_param_related,
+    write_param_reltype, write_param_role, write_param_rsvp, write_param_sent_by, write_param_tzid,
+    write_param_value, write_parameters, write_syntax_parameters,
 };
 use crate::fmt::value::{
     format_value_text, write_date, write_duration, write_recurrence_rule, write_utc_offset,
     write_value,
 };
 use crate::keyword::{
-    KW_ACTION, KW_ATTACH, KW_ATTENDEE, KW_CALSCALE, KW_CATEGORIES, KW_CLASS, KW_COMMENT,
-    KW_COMPLETED, KW_CONTACT, KW_CREATED, KW_DESCRIPTION, KW_DTEND, KW_DTSTAMP, KW_DTSTART, KW_DUE,
-    KW_DURATION, KW_EXDATE, KW_FREEBUSY, KW_GEO, KW_LAST_MODIFIED, KW_LOCATION, KW_METHOD,
-    KW_ORGANIZER, KW_PERCENT_COMPLETE, KW_PRIORITY, KW_PRODID, KW_RDATE, KW_RECURRENCE_ID,
-    KW_RELATED_TO, KW_REPEAT, KW_REQUEST_STATUS, KW_RESOURCES, KW_RRULE, KW_SEQUENCE, KW_STATUS,
+    KW_ACTION, KW_ATTACH, KW_ATTENDEE, KW_BUSYTYPE, KW_CALSCALE, KW_CATEGORIES, KW_CLASS, KW_COLOR,
+    KW_COMMENT, KW_COMPLETED, KW_CONFERENCE, KW_CONTACT, KW_CREATED, KW_DESCRIPTION, KW_DTEND,
+    KW_DTSTAMP, KW_DTSTART, KW_DUE, KW_DURATION, KW_EXDATE, KW_FREEBUSY, KW_GEO, KW_IMAGE,
+    KW_LAST_MODIFIED, KW_LOCATION, KW_METHOD, KW_NAME, KW_ORGANIZER, KW_PERCENT_COMPLETE,
+    KW_PRIORITY, KW_PRODID, KW_RDATE, KW_RECURRENCE_ID, KW_REFRESH_INTERVAL, KW_RELATED_TO,
+    KW_REPEAT, KW_REQUEST_STATUS, KW_RESOURCES, KW_RRULE, KW_SEQUENCE, KW_SOURCE, KW_STATUS,
     KW_SUMMARY, KW_TRANSP, KW_TRIGGER, KW_TZID, KW_TZNAME, KW_TZOFFSETFROM, KW_TZOFFSETTO,
     KW_TZURL, KW_UID, KW_URL, KW_VERSION,
 };
 use crate::parameter::{FreeBusyType, Parameter};
 use crate::property::{
-    Action, Attachment, AttachmentValue, Attendee, CalendarScale, Categories, Classification,
-    Comment, Completed, Contact, Created, DateTime, DateTimeProperty, DateTimeUtc, Description,
-    DtEnd, DtStamp, DtStart, Due, Duration, ExDate, FreeBusy, Geo, LastModified, Location, Method,
-    Organizer, PercentComplete, Period, Priority, ProductId, Property, RDate, RDateValue, RRule,
-    RecurrenceId, RelatedTo, Repeat, RequestStatus, Resources, Sequence, Status, Summary, Time,
+    Action, Attachment, AttachmentValue, Attendee, BusyType, CalendarScale, Categories,
+    Classification, Color, Comment, Completed, Conference, Contact, Created, DateTime,
+    DateTimeProperty, DateTimeUtc, Description, DtEnd, DtStamp, DtStart, Due, Duration, ExDate,
+    FreeBusy, Geo, Image, LastModified, Location, Method, Name, Organizer, PercentComplete, Period,
+    Priority, ProductId, Property, RDate, RDateValue, RRule, RecurrenceId, RefreshInterval,
+    RelatedTo, Repeat, RequestStatus, Resources, Sequence, Source, Status, Summary, Time,
     TimeTransparency, Trigger, TriggerValue, TzId, TzName, TzOffsetFrom, TzOffsetTo, TzUrl, Uid,
     UnrecognizedProperty, UriProperty, Url, Version, XNameProperty,
 };
 use crate::string_storage::StringStorage;
 use crate::syntax::RawParameter;
 use crate::value::ValueText;
+use crate::{StatusValue, ValueType};
 
 /// Format a single property.
 ///
@@ -126,6 +129,17 @@ pub fn write_property<S: StringStorage>(
         // Miscellaneous properties
         Property::RequestStatus(prop) => write_prop_request_status(f, prop),
 
+        // RFC 7986 calendar properties
+        Property::Name(prop) => write_prop_name(f, prop),
+        Property::RefreshInterval(prop) => write_prop_refresh_interval(f, prop),
+        Property::Source(prop) => write_prop_source(f, prop),
+        Property::Color(prop) => write_prop_color(f, prop),
+        Property::Image(prop) => write_prop_image(f, prop),
+        Property::Conference(prop) => write_prop_conference(f, prop),
+
+        // RFC 7953 availability properties
+        Property::BusyType(prop) => write_prop_busytype(f, prop),
+
         // XName and Unrecognized properties - use their value directly
         Property::XName(prop) => write_prop_xname(f, prop),
         Property::Unrecognized(prop) => write_prop_unrecognized(f, prop),
@@ -646,6 +660,21 @@ pub fn write_prop_transp<S: StringStorage>(
     f.writeln()
 }
 
+/// Write a `BusyType` property.
+pub fn write_prop_busytype<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &BusyType<S>,
+) -> io::Result<()> {
+    write_escaped_text(
+        f,
+        KW_BUSYTYPE,
+        &prop.value,
+        &prop.x_parameters,
+        &prop.retained_parameters,
+    )?;
+    f.writeln()
+}
+
 /// Write a `Priority` property.
 pub fn write_prop_priority<S: StringStorage>(
     f: &mut Formatter<impl Write>,
@@ -920,6 +949,141 @@ pub fn write_prop_unrecognized<S: StringStorage>(
     f.writeln()
 }
 
+/// Write a `Name` property.
+pub fn write_prop_name<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &Name<S>,
+) -> io::Result<()> {
+    write_text_with_params(
+        f,
+        KW_NAME,
+        &prop.content,
+        prop.language.as_ref(),
+        prop.altrep.as_ref(),
+        &prop.x_parameters,
+        &prop.retained_parameters,
+    )?;
+    f.writeln()
+}
+
+/// Write a `RefreshInterval` property.
+///
+/// RFC 7986 requires `VALUE=DURATION`, so it is written unless already retained.
+pub fn write_prop_refresh_interval<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &RefreshInterval<S>,
+) -> io::Result<()> {
+    write!(f, "{KW_REFRESH_INTERVAL}")?;
+    let has_value_type = prop
+        .retained_parameters
+        .iter()
+        .any(|p| matches!(p, Parameter::ValueType { .. }));
+    if !has_value_type {
+        write_param_value(f, &ValueType::<S>::Duration)?;
+    }
+    write_syntax_parameters(f, &prop.x_parameters)?;
+    write_parameters(f, &prop.retained_parameters)?;
+    write!(f, ":")?;
+    write_duration(f, &prop.value)?;
+    f.writeln()
+}
+
+/// Write a `Source` property.
+pub fn write_prop_source<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &Source<S>,
+) -> io::Result<()> {
+    write_uri(f, KW_SOURCE, prop)?;
+    f.writeln()
+}
+
+/// Write a `Color` property.
+pub fn write_prop_color<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &Color<S>,
+) -> io::Result<()> {
+    write_text_with_language(
+        f,
+        KW_COLOR,
+        &prop.content,
+        None,
+        &prop.x_parameters,
+        &prop.retained_parameters,
+    )?;
+    f.writeln()
+}
+
+/// Write an `Image` property.
+///
+/// The value type has no default, so `VALUE` is written unless retained.
+pub fn write_prop_image<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &Image<S>,
+) -> io::Result<()> {
+    write!(f, "{KW_IMAGE}")?;
+    let has_value_type = prop
+        .retained_parameters
+        .iter()
+        .any(|p| matches!(p, Parameter::ValueType { .. }));
+    if !has_value_type {
+        let value_type = match prop.value {
+            AttachmentValue::Uri(_) => ValueType::<S>::Uri,
+            AttachmentValue::Binary(_) => ValueType::Binary,
+        };
+        write_param_value(f, &value_type)?;
+    }
+    if !prop.display.is_empty() {
+        write_param_display(f, &prop.display)?;
+    }
+    if let Some(v) = &prop.fmt_type {
+        write_param_fmttype(f, v)?;
+    }
+    if let Some(v) = prop.encoding {
+        write_param_encoding(f, v)?;
+    }
+    if let Some(v) = &prop.altrep {
+        write_param_altrep(f, v)?;
+    }
+    write_syntax_parameters(f, &prop.x_parameters)?;
+    write_parameters(f, &prop.retained_parameters)?;
+    write!(f, ":")?;
+    match &prop.value {
+        AttachmentValue::Uri(uri) => write!(f, "{uri}")?,
+        AttachmentValue::Binary(data) => write!(f, "{data}")?,
+    }
+    f.writeln()
+}
+
+/// Write a `Conference` property.
+///
+/// The value type has no default, so `VALUE=URI` is written unless retained.
+pub fn write_prop_conference<S: StringStorage>(
+    f: &mut Formatter<impl Write>,
+    prop: &Conference<S>,
+) -> io::Result<()> {
+    write!(f, "{KW_CONFERENCE}")?;
+    let has_value_type = prop
+        .retained_parameters
+        .iter()
+        .any(|p| matches!(p, Parameter::ValueType { .. }));
+    if !has_value_type {
+        write_param_value(f, &ValueType::<S>::Uri)?;
+    }
+    if !prop.features.is_empty() {
+        write_param_feature(f, &prop.features)?;
+    }
+    if let Some(v) = &prop.label {
+        write_param_label(f, v)?;
+    }
+    if let Some(v) = &prop.language {
+        write_param_language(f, v)?;
+    }
+    write_syntax_parameters(f, &prop.x_parameters)?;
+    write_parameters(f, &prop.retained_parameters)?;
+    write!(f, ":{}", prop.uri)?;
+    f.writeln()
+}
+
 // ============================================================================
 // Helper functions for common patterns
 // ============================================================================
@@ -1043,6 +1207,20 @@ fn write_datetime<S: StringStorage>(
 
     // Write: NAME;params:value
     write!(f, "{name}")?;
+
+    // Write TZID parameter if present
+    if let (Some(tz), DateTime::Zoned { .. }) = (&datetime.tz_id, &datetime.value) {
+        write_param_tzid(f, tz)?;
+    }
+
+    // A date value needs VALUE=DATE, which parsed properties keep in the retained parameters
+    let has_value_type = retained_params
+        .iter()
+        .any(|p| matches!(p, Parameter::ValueType { .. }));
+    if datetime.is_date_only() && !has_value_type {
+        write_param_value(f, &ValueType::<S>::Date)?;
+    }
+
     write_syntax_parameters(f, x_params)?;
     write_parameters(f, retained_params)?;
 
@@ -1116,27 +1294,17 @@ fn write_period<S: StringStorage>(
 ) -> io::Result<()> {
     match period {
         Period::ExplicitUtc { start, end } => {
-            write_date(f, start.date())?;
-            write!(f, "T")?;
-            write_time(f, &start.time().unwrap(), true)?;
+            write_period_datetime(f, start, true)?;
             write!(f, "/")?;
-            write_date(f, end.date())?;
-            write!(f, "T")?;
-            write_time(f, &end.time().unwrap(), true)?;
+            write_period_datetime(f, end, true)?;
         }
         Period::ExplicitFloating { start, end } | Period::ExplicitZoned { start, end, .. } => {
-            write_date(f, start.date())?;
-            write!(f, "T")?;
-            write_time(f, &start.time().unwrap(), false)?;
+            write_period_datetime(f, start, false)?;
             write!(f, "/")?;
-            write_date(f, end.date())?;
-            write!(f, "T")?;
-            write_time(f, &end.time().unwrap(), false)?;
+            write_period_datetime(f, end, false)?;
         }
         Period::DurationUtc { start, duration } => {
-            write_date(f, start.date())?;
-            write!(f, "T")?;
-            write_time(f, &start.time().unwrap(), true)?;
+            write_period_datetime(f, start, true)?;
             write!(f, "/")?;
             write_duration(f, duration)?;
         }
@@ -1144,12 +1312,26 @@ fn write_period<S: StringStorage>(
         | Period::DurationZoned {
             start, duration, ..
         } => {
-            write_date(f, start.date())?;
-            write!(f, "T")?;
-            write_time(f, &start.time().unwrap(), false)?;
+            write_period_datetime(f, start, false)?;
             write!(f, "/")?;
             write_duration(f, duration)?;
         }
     }
     Ok(())
 }
+
+/// Write one side of a Period, omitting the time for a date-only value.
+fn write_period_datetime(
+    f: &mut Formatter<impl Write>,
+    datetime: &DateTime,
+    utc: bool,
+) -> io::Result<()> {
+    write_date(f, datetime.date())?;
+    match datetime.time() {
+        Some(time) => {
+            write!(f, "T")?;
+            write_time(f, &time, utc)
+        }
+        None => Ok(()),
+    }
+}
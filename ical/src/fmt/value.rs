@@ -410,6 +410,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut f = Formatter::new(&mut buffer, FormatOptions::default());
         write_date(&mut f, date).unwrap();
+        f.flush().unwrap();
         assert_eq!(String::from_utf8(buffer).unwrap(), "19970714");
     }
 
@@ -437,6 +438,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut f = Formatter::new(&mut buffer, FormatOptions::default());
         write_utc_offset(&mut f, offset).unwrap();
+        f.flush().unwrap();
         assert_eq!(String::from_utf8(buffer).unwrap(), "-0500");
 
         let offset = ValueUtcOffset {
@@ -448,6 +450,7 @@ mod tests {
         let mut buffer = Vec::new();
         let mut f = Formatter::new(&mut buffer, FormatOptions::default());
         write_utc_offset(&mut f, offset).unwrap();
+        f.flush().unwrap();
         assert_eq!(String::from_utf8(buffer).unwrap(), "+0100");
     }
 }
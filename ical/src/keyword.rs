@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-//! Keywords defined in iCalendar RFC 5545.
+//! Keywords defined in iCalendar RFC 5545, RFC 7986 and RFC 7953.
 
 #![allow(missing_docs)]
 
@@ -76,6 +76,27 @@ pub const KW_ROLE_NON_PARTICIPANT: &str = "NON-PARTICIPANT";
 pub const KW_RSVP_TRUE: &str = "TRUE";
 pub const KW_RSVP_FALSE: &str = "FALSE";
 
+// RFC 7986 6.  Property Parameters
+pub const KW_DISPLAY: &str = "DISPLAY";
+pub const KW_EMAIL: &str = "EMAIL";
+pub const KW_FEATURE: &str = "FEATURE";
+pub const KW_LABEL: &str = "LABEL";
+
+// RFC 7986 6.1.  Display
+pub const KW_DISPLAY_BADGE: &str = "BADGE";
+pub const KW_DISPLAY_GRAPHIC: &str = "GRAPHIC";
+pub const KW_DISPLAY_FULLSIZE: &str = "FULLSIZE";
+pub const KW_DISPLAY_THUMBNAIL: &str = "THUMBNAIL";
+
+// RFC 7986 6.3.  Feature
+pub const KW_FEATURE_AUDIO: &str = "AUDIO";
+pub const KW_FEATURE_CHAT: &str = "CHAT";
+pub const KW_FEATURE_FEED: &str = "FEED";
+pub const KW_FEATURE_MODERATOR: &str = "MODERATOR";
+pub const KW_FEATURE_PHONE: &str = "PHONE";
+pub const KW_FEATURE_SCREEN: &str = "SCREEN";
+pub const KW_FEATURE_VIDEO: &str = "VIDEO";
+
 // 3.3.  Property Value Data Types
 pub const KW_BINARY: &str = "BINARY";
 pub const KW_BOOLEAN: &str = "BOOLEAN";
@@ -252,3 +273,26 @@ pub const KW_SEQUENCE: &str = "SEQUENCE";
 
 // 3.8.8.  Miscellaneous Component Properties
 pub const KW_REQUEST_STATUS: &str = "REQUEST-STATUS";
+
+// RFC 7986 5.  Properties
+pub const KW_NAME: &str = "NAME";
+pub const KW_REFRESH_INTERVAL: &str = "REFRESH-INTERVAL";
+pub const KW_SOURCE: &str = "SOURCE";
+pub const KW_COLOR: &str = "COLOR";
+pub const KW_IMAGE: &str = "IMAGE";
+pub const KW_CONFERENCE: &str = "CONFERENCE";
+
+// RFC 7953 3.  Calendar Availability
+pub const KW_VAVAILABILITY: &str = "VAVAILABILITY";
+pub const KW_AVAILABLE: &str = "AVAILABLE";
+pub const KW_BUSYTYPE: &str = "BUSYTYPE";
+
+// RFC 7953 3.2.  Busy Time Type
+pub const KW_BUSYTYPE_BUSY: &str = "BUSY";
+pub const KW_BUSYTYPE_BUSY_UNAVAILABLE: &str = "BUSY-UNAVAILABLE";
+pub const KW_BUSYTYPE_BUSY_TENTATIVE: &str = "BUSY-TENTATIVE";
+
+// Calendar properties of common vendor extensions, used where RFC 7986 ones are missing
+pub const KW_X_WR_CALNAME: &str = "X-WR-CALNAME";
+pub const KW_X_WR_CALDESC: &str = "X-WR-CALDESC";
+pub const KW_X_WR_TIMEZONE: &str = "X-WR-TIMEZONE";
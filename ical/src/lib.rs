@@ -16,9 +16,11 @@
     unused_qualifications,
     clippy::dbg_macro,
     clippy::indexing_slicing,
-    clippy::pedantic
+    clippy::pedantic,
+    clippy::unwrap_used
 )]
 
+pub mod diagnostic;
 pub mod fmt;
 
 // TODO: Remove deprecated `formatter` module alias in version 0.15.0 (3 versions after 0.12.0)
@@ -42,28 +44,37 @@ pub mod syntax;
 pub mod typed;
 pub mod value;
 
+pub use crate::diagnostic::{Diagnostic, FixIt, RelatedSpan, Severity};
 pub use crate::parameter::{
-    AlarmTriggerRelationship, CalendarUserType, Encoding, FreeBusyType, Parameter, ParameterKind,
-    ParticipationRole, ParticipationStatus, RecurrenceIdRange, RelationshipType, ValueType,
+    AlarmTriggerRelationship, CalendarUserType, DisplayType, Encoding, FeatureType, FreeBusyType,
+    Parameter, ParameterKind, ParticipationRole, ParticipationStatus, RecurrenceIdRange,
+    RelationshipType, ValueType,
+};
+pub use crate::parser::{
+    BrokenComponent, ParseError, PartialParse, parse, parse_partial, parse_with_diagnostics,
+    parse_with_diagnostics_and_options, parse_with_options,
 };
-pub use crate::parser::{ParseError, parse, parse_with_options};
 pub use crate::property::{
-    Action, ActionValue, Attachment, AttachmentValue, Attendee, CalendarScale, CalendarScaleValue,
-    Categories, Classification, ClassificationValue, Comment, Completed, Contact, Created, Date,
-    DateTime, DateTimeProperty, DateTimeUtc, Description, DtEnd, DtStamp, DtStart, Due, Duration,
-    ExDate, FreeBusy, Geo, LastModified, Location, Method, MethodValue, Organizer, PercentComplete,
-    Period, Priority, ProductId, Property, PropertyKind, RDate, RDateValue, RecurrenceId,
-    RelatedTo, Repeat, RequestStatus, Resources, Sequence, Status, StatusValue, Summary, Text,
-    TextOnly, TextWithLanguage, Time, TimeTransparency, TimeTransparencyValue, Trigger,
+    Action, ActionValue, Attachment, AttachmentValue, Attendee, BusyType, BusyTypeValue,
+    CalendarScale, CalendarScaleValue, Categories, Classification, ClassificationValue, Color,
+    Comment, Completed, Conference, Contact, Created, Date, DateTime, DateTimeProperty,
+    DateTimeUtc, Description, DtEnd, DtStamp, DtStart, Due, Duration, ExDate, FreeBusy, Geo, Image,
+    LastModified, Location, Method, MethodValue, Name, Organizer, PercentComplete, Period,
+    Priority, ProductId, Property, PropertyKind, RDate, RDateValue, RecurrenceId, RefreshInterval,
+    RelatedTo, Repeat, RequestStatus, Resources, Sequence, Source, Status, StatusValue, Summary,
+    Text, TextOnly, TextWithLanguage, Time, TimeTransparency, TimeTransparencyValue, Trigger,
     TriggerValue, TzId, TzName, TzOffsetFrom, TzOffsetTo, TzUrl, Uid, UnrecognizedProperty,
-    UriProperty, Url, Version, VersionValue, XNameProperty,
+    UriProperty, Url, UtcOffsetProperty, ValueForm, Version, VersionValue, XNameProperty,
 };
+#[cfg(feature = "jiff")]
+pub use crate::semantic::AlarmTriggerError;
 pub use crate::semantic::{
-    CalendarComponent, EventStatus, EventStatusValue, ICalendar, JournalStatus, TimeZoneObservance,
-    TodoStatus, TodoStatusValue, VAlarm, VEvent, VFreeBusy, VJournal, VTimeZone, VTodo,
+    Available, CalendarComponent, ComponentIdentity, EventStatus, EventStatusValue, ICalendar,
+    JournalStatus, TimeZoneObservance, TodoStatus, TodoStatusValue, VAlarm, VAvailability, VEvent,
+    VFreeBusy, VJournal, VTimeZone, VTodo,
 };
 pub use crate::string_storage::{Segments, StringStorage};
-pub use crate::syntax::ParseOptions;
+pub use crate::syntax::{Compat, ParseLimits, ParseOptions, Repair, RepairKind};
 pub use crate::value::{
     RecurrenceFrequency, Value, ValueDate, ValueDateTime, ValueDuration, ValuePeriod,
     ValueRecurrenceRule, ValueText, ValueTime, ValueUtcOffset, WeekDay, WeekDayNum,
@@ -11,11 +11,21 @@
 //!
 //! - [`rrule`] - `RRule` expansion and computation utilities
 //! - [`conflict`] - Event conflict detection utilities
+//! - [`diff`] - Differences between two versions of a calendar
+//! - [`availability`] - Effective availability of `VAVAILABILITY` components
 
+pub mod availability;
 pub mod conflict;
+pub mod diff;
+pub mod export;
 pub mod rrule;
 
+#[cfg(feature = "jiff")]
+pub use availability::{AvailabilityKind, AvailabilityPeriod, effective_availability};
 #[cfg(feature = "jiff")]
 pub use conflict::ConflictExt;
+pub use diff::{ComponentChange, diff};
+#[cfg(feature = "jiff")]
+pub use export::{standalone_calendar, vtimezone};
 #[cfg(feature = "jiff")]
 pub use rrule::{DateRange, EventOccurrence, RRuleExt, VEventExt};
@@ -0,0 +1,428 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Effective availability of `VAVAILABILITY` components (RFC 7953 section 4).
+//!
+//! Each availability leaves the time it covers busy as given by its `BUSYTYPE`, apart from the
+//! time its `AVAILABLE` blocks leave free. Where availabilities overlap, the one of the highest
+//! `PRIORITY` decides, 1 being the highest and the undefined 0 the lowest. Availabilities of the
+//! same priority are combined: time free in either of them is free, and other time is as busy as
+//! the busiest of them.
+
+#![cfg(feature = "jiff")]
+
+use jiff::civil::{DateTime, Time};
+
+use crate::ops::rrule::{DateRange, RRuleError, RRuleExt, value_duration_to_span};
+use crate::property::{BusyTypeValue, DateTimeProperty, RDateValue};
+use crate::semantic::{Available, VAvailability};
+
+/// What the time of a period of effective availability is, from the least to the most busy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AvailabilityKind {
+    /// The time is free
+    Free,
+    /// The time is tentatively busy
+    BusyTentative,
+    /// The time is busy
+    Busy,
+    /// The time is unavailable
+    BusyUnavailable,
+}
+
+impl From<BusyTypeValue> for AvailabilityKind {
+    fn from(value: BusyTypeValue) -> Self {
+        match value {
+            BusyTypeValue::Busy => Self::Busy,
+            BusyTypeValue::BusyUnavailable => Self::BusyUnavailable,
+            BusyTypeValue::BusyTentative => Self::BusyTentative,
+        }
+    }
+}
+
+/// A period of effective availability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvailabilityPeriod {
+    /// Start of the period (inclusive)
+    pub start: DateTime,
+    /// End of the period (exclusive)
+    pub end: DateTime,
+    /// What the time of the period is
+    pub kind: AvailabilityKind,
+}
+
+/// Evaluates the effective availability between `start` and `end`.
+///
+/// Returns the periods in order, adjacent periods of the same kind merged. The time no
+/// availability covers is left out, as nothing is known about it. Times are compared as civil
+/// times, like the other operations of this module.
+///
+/// # Errors
+///
+/// Returns an error if the recurrence rule of an `AVAILABLE` block cannot be expanded.
+pub fn effective_availability<'a>(
+    availabilities: impl IntoIterator<Item = &'a VAvailability<String>>,
+    start: DateTime,
+    end: DateTime,
+) -> Result<Vec<AvailabilityPeriod>, RRuleError> {
+    let mut layers = Vec::new();
+    for availability in availabilities {
+        if let Some(layer) = Layer::of(availability, start, end)? {
+            layers.push(layer);
+        }
+    }
+
+    let mut points = vec![start, end];
+    for layer in &layers {
+        points.extend([layer.start, layer.end]);
+        points.extend(layer.free.iter().flat_map(|&(s, e)| [s, e]));
+    }
+    points.sort_unstable();
+    points.dedup();
+
+    let mut periods: Vec<AvailabilityPeriod> = Vec::new();
+    for pair in points.windows(2) {
+        let &[a, b] = pair else {
+            continue;
+        };
+        if a < start || b > end {
+            continue;
+        }
+
+        let covering: Vec<_> = layers
+            .iter()
+            .filter(|layer| layer.start <= a && b <= layer.end)
+            .collect();
+        let Some(rank) = covering.iter().map(|layer| layer.rank).min() else {
+            continue;
+        };
+        let kind = covering
+            .iter()
+            .filter(|layer| layer.rank == rank)
+            .map(|layer| layer.kind_within(a, b))
+            .min_by_key(|kind| (*kind != AvailabilityKind::Free, std::cmp::Reverse(*kind)))
+            .unwrap_or(AvailabilityKind::Free);
+
+        match periods.last_mut() {
+            Some(last) if last.end == a && last.kind == kind => last.end = b,
+            _ => periods.push(AvailabilityPeriod {
+                start: a,
+                end: b,
+                kind,
+            }),
+        }
+    }
+    Ok(periods)
+}
+
+/// The time an availability covers within the range, with its free blocks.
+struct Layer {
+    start: DateTime,
+    end: DateTime,
+    rank: u8,
+    busy: AvailabilityKind,
+    free: Vec<(DateTime, DateTime)>,
+}
+
+impl Layer {
+    fn of(
+        availability: &VAvailability<String>,
+        start: DateTime,
+        end: DateTime,
+    ) -> Result<Option<Self>, RRuleError> {
+        let from = availability.dt_start.as_ref().map(|dt| civil(dt));
+        let to = match (&availability.dt_end, &availability.duration) {
+            (Some(dt_end), _) => Some(civil(dt_end)),
+            (None, Some(duration)) => from
+                .zip(value_duration_to_span(&duration.value))
+                .and_then(|(from, length)| from.checked_add(length).ok()),
+            (None, None) => None,
+        };
+        let from = from.map_or(start, |from| from.max(start));
+        let to = to.map_or(end, |to| to.min(end));
+        if from >= to {
+            return Ok(None);
+        }
+
+        let mut free = Vec::new();
+        for available in &availability.available {
+            free.extend(available_blocks(available, availability, from, to)?);
+        }
+        Ok(Some(Self {
+            start: from,
+            end: to,
+            rank: availability.priority_rank(),
+            busy: availability.busy_type_value().into(),
+            free,
+        }))
+    }
+
+    /// What the time between `a` and `b` is, which no boundary of the layer falls within.
+    fn kind_within(&self, a: DateTime, b: DateTime) -> AvailabilityKind {
+        if self.free.iter().any(|&(s, e)| s <= a && b <= e) {
+            AvailabilityKind::Free
+        } else {
+            self.busy
+        }
+    }
+}
+
+/// The blocks of an `AVAILABLE` overlapping the time between `from` and `to`, clipped to it.
+///
+/// The instances replaced by another `AVAILABLE` of the same UID with a `RECURRENCE-ID` are left
+/// out, so the replacement takes their place.
+fn available_blocks(
+    available: &Available<String>,
+    availability: &VAvailability<String>,
+    from: DateTime,
+    to: DateTime,
+) -> Result<Vec<(DateTime, DateTime)>, RRuleError> {
+    let dt_start = civil(&available.dt_start);
+    let length = match (&available.dt_end, &available.duration) {
+        (Some(dt_end), _) => dt_start.until(civil(dt_end)).ok(),
+        (None, Some(duration)) => value_duration_to_span(&duration.value),
+        // A block without an end has no length
+        (None, None) => None,
+    };
+    let Some(length) = length else {
+        return Ok(Vec::new());
+    };
+
+    let mut blocks = Vec::new();
+    if available.recurrence_id.is_some() {
+        blocks.push((dt_start, length));
+    } else {
+        let uid = available.uid.content.as_str();
+        let replaced: Vec<_> = availability
+            .available
+            .iter()
+            .filter(|other| other.uid.content.as_str() == uid)
+            .filter_map(|other| other.recurrence_id.as_ref())
+            .map(|id| civil(id))
+            .collect();
+        let excluded = |start: &DateTime| {
+            replaced.contains(start)
+                || available
+                    .ex_dates
+                    .iter()
+                    .flat_map(|ex| &ex.dates)
+                    .any(|ex| match ex.civil_date_time() {
+                        Some(dt) => dt == *start,
+                        None => ex.date().civil_date() == start.date(),
+                    })
+        };
+
+        // Instances starting before the range may still overlap it
+        let since = from.checked_sub(length).unwrap_or(from);
+        let mut starts = match &available.rrule {
+            Some(rrule) => rrule
+                .value
+                .expand(dt_start, DateRange::new(since.date(), to.date()))?,
+            None => Vec::new(),
+        };
+        // DTSTART is always the first instance, even when the rule does not match it
+        if !starts.contains(&dt_start) {
+            starts.push(dt_start);
+        }
+        for rdate in available.rdates.iter().flat_map(|rdate| &rdate.dates) {
+            match rdate {
+                RDateValue::DateTime(dt) => starts.push(
+                    dt.civil_date_time()
+                        .unwrap_or_else(|| dt.date().civil_date().to_datetime(Time::midnight())),
+                ),
+                RDateValue::Period(period) => {
+                    if let Some(end) = period.end_civil() {
+                        let start = period.start_civil();
+                        blocks.extend(start.until(end).ok().map(|length| (start, length)));
+                    }
+                }
+            }
+        }
+        blocks.extend(
+            starts
+                .into_iter()
+                .filter(|start| !excluded(start))
+                .map(|start| (start, length)),
+        );
+    }
+
+    Ok(blocks
+        .into_iter()
+        .filter_map(|(start, length)| Some((start, start.checked_add(length).ok()?)))
+        .map(|(start, end)| (start.max(from), end.min(to)))
+        .filter(|(start, end)| start < end)
+        .collect())
+}
+
+/// The civil date and time of a property, midnight for a date.
+fn civil(dt: &DateTimeProperty<String>) -> DateTime {
+    dt.civil_date_time()
+        .unwrap_or_else(|| dt.date().civil_date().to_datetime(Time::midnight()))
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+    use crate::parse;
+
+    fn at(day: i8, hour: i8) -> DateTime {
+        date(2026, 3, day).at(hour, 0, 0, 0)
+    }
+
+    fn parse_availabilities(body: &str) -> Vec<VAvailability<String>> {
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\n{body}END:VCALENDAR\r\n"
+        );
+        let calendar = parse(&src).unwrap().into_iter().next().unwrap().to_owned();
+        calendar.availabilities().into_iter().cloned().collect()
+    }
+
+    fn period(start: DateTime, end: DateTime, kind: AvailabilityKind) -> AvailabilityPeriod {
+        AvailabilityPeriod { start, end, kind }
+    }
+
+    /// Weekdays from 9 to 17 from Monday 2026-03-02 on.
+    const WORKING_HOURS: &str = "\
+BEGIN:VAVAILABILITY\r
+UID:working-hours\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260302T000000\r
+BEGIN:AVAILABLE\r
+UID:weekdays\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260302T090000\r
+DTEND:20260302T170000\r
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+";
+
+    #[test]
+    fn leaves_the_time_between_available_blocks_unavailable() {
+        let availabilities = parse_availabilities(WORKING_HOURS);
+        let periods = effective_availability(&availabilities, at(6, 12), at(9, 12)).unwrap();
+        assert_eq!(
+            periods,
+            [
+                period(at(6, 12), at(6, 17), AvailabilityKind::Free),
+                period(at(6, 17), at(9, 9), AvailabilityKind::BusyUnavailable),
+                period(at(9, 9), at(9, 12), AvailabilityKind::Free),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_out_time_no_availability_covers() {
+        let availabilities = parse_availabilities(WORKING_HOURS);
+        let periods = effective_availability(&availabilities, at(1, 0), at(2, 10)).unwrap();
+        assert_eq!(
+            periods,
+            [
+                period(at(2, 0), at(2, 9), AvailabilityKind::BusyUnavailable),
+                period(at(2, 9), at(2, 10), AvailabilityKind::Free),
+            ]
+        );
+    }
+
+    #[test]
+    fn higher_priority_overrides_lower_priority() {
+        let body = format!(
+            "{WORKING_HOURS}\
+BEGIN:VAVAILABILITY\r
+UID:conference\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260304T000000\r
+DTEND:20260305T000000\r
+PRIORITY:1\r
+BUSYTYPE:BUSY\r
+END:VAVAILABILITY\r
+"
+        );
+        let availabilities = parse_availabilities(&body);
+        let periods = effective_availability(&availabilities, at(4, 8), at(5, 10)).unwrap();
+        assert_eq!(
+            periods,
+            [
+                period(at(4, 8), at(5, 0), AvailabilityKind::Busy),
+                period(at(5, 0), at(5, 9), AvailabilityKind::BusyUnavailable),
+                period(at(5, 9), at(5, 10), AvailabilityKind::Free),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaced_instances_take_the_place_of_the_recurrence() {
+        let body = "\
+BEGIN:VAVAILABILITY\r
+UID:working-hours\r
+DTSTAMP:20260301T000000Z\r
+BEGIN:AVAILABLE\r
+UID:weekdays\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260302T090000\r
+DTEND:20260302T170000\r
+RRULE:FREQ=DAILY\r
+EXDATE:20260304T090000\r
+END:AVAILABLE\r
+BEGIN:AVAILABLE\r
+UID:weekdays\r
+DTSTAMP:20260301T000000Z\r
+RECURRENCE-ID:20260303T090000\r
+DTSTART:20260303T130000\r
+DTEND:20260303T190000\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+";
+        let availabilities = parse_availabilities(body);
+        let periods = effective_availability(&availabilities, at(3, 8), at(5, 0)).unwrap();
+        assert_eq!(
+            periods,
+            [
+                period(at(3, 8), at(3, 13), AvailabilityKind::BusyUnavailable),
+                period(at(3, 13), at(3, 19), AvailabilityKind::Free),
+                period(at(3, 19), at(5, 0), AvailabilityKind::BusyUnavailable),
+            ]
+        );
+    }
+
+    #[test]
+    fn combines_availabilities_of_the_same_priority() {
+        let body = "\
+BEGIN:VAVAILABILITY\r
+UID:a\r
+DTSTAMP:20260301T000000Z\r
+BUSYTYPE:BUSY-TENTATIVE\r
+BEGIN:AVAILABLE\r
+UID:a-1\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260302T090000\r
+DURATION:PT3H\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+BEGIN:VAVAILABILITY\r
+UID:b\r
+DTSTAMP:20260301T000000Z\r
+BUSYTYPE:BUSY\r
+BEGIN:AVAILABLE\r
+UID:b-1\r
+DTSTAMP:20260301T000000Z\r
+DTSTART:20260302T110000\r
+DTEND:20260302T140000\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+";
+        let availabilities = parse_availabilities(body);
+        let periods = effective_availability(&availabilities, at(2, 8), at(2, 15)).unwrap();
+        assert_eq!(
+            periods,
+            [
+                period(at(2, 8), at(2, 9), AvailabilityKind::Busy),
+                period(at(2, 9), at(2, 14), AvailabilityKind::Free),
+                period(at(2, 14), at(2, 15), AvailabilityKind::Busy),
+            ]
+        );
+    }
+}
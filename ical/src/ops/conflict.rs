@@ -6,6 +6,8 @@
 
 #![cfg(feature = "jiff")]
 
+use jiff::civil::DateTime;
+
 use crate::ops::rrule::EventOccurrence;
 
 /// Extension trait for conflict detection.
@@ -25,10 +27,10 @@ impl<T: AsRef<[EventOccurrence<String>]>> ConflictExt for T {
         let occurrences = self.as_ref();
 
         // Filter to only events with both start and end times
-        let valid_events: Vec<(usize, &EventOccurrence<String>)> = occurrences
+        let valid_events: Vec<(usize, &EventOccurrence<String>, DateTime)> = occurrences
             .iter()
             .enumerate()
-            .filter(|(_, occ)| occ.end.is_some())
+            .filter_map(|(idx, occ)| Some((idx, occ, occ.end?)))
             .collect();
 
         if valid_events.len() < 2 {
@@ -38,12 +40,8 @@ impl<T: AsRef<[EventOccurrence<String>]>> ConflictExt for T {
         let mut conflicts = Vec::new();
 
         // Compare all pairs using iterators
-        for (i, (idx1, occ1)) in valid_events.iter().enumerate() {
-            for (idx2, occ2) in valid_events.iter().skip(i + 1) {
-                // SAFETY: We filtered for events with end times
-                let end1 = occ1.end.unwrap();
-                let end2 = occ2.end.unwrap();
-
+        for (i, &(idx1, occ1, end1)) in valid_events.iter().enumerate() {
+            for &(idx2, occ2, end2) in valid_events.iter().skip(i + 1) {
                 // Check overlap: occ1.start < occ2.end AND occ2.start < occ1.end
                 if occ1.start < end2 && occ2.start < end1 {
                     let overlap_start = std::cmp::max(occ1.start, occ2.start);
@@ -52,14 +50,14 @@ impl<T: AsRef<[EventOccurrence<String>]>> ConflictExt for T {
                     conflicts.push(Conflict {
                         events: vec![
                             ConflictEvent {
-                                index: *idx1,
+                                index: idx1,
                                 overlap: ConflictRange {
                                     start: overlap_start,
                                     end: overlap_end,
                                 },
                             },
                             ConflictEvent {
-                                index: *idx2,
+                                index: idx2,
                                 overlap: ConflictRange {
                                     start: overlap_start,
                                     end: overlap_end,
@@ -95,9 +93,9 @@ pub struct ConflictEvent {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ConflictRange {
     /// Start of the conflict (inclusive)
-    pub start: jiff::civil::DateTime,
+    pub start: DateTime,
     /// End of the conflict (inclusive)
-    pub end: jiff::civil::DateTime,
+    pub end: DateTime,
 }
 
 #[cfg(test)]
@@ -115,8 +113,8 @@ mod tests {
         start: &str,
         end: Option<&str>,
     ) -> EventOccurrence<String> {
-        let start_dt: jiff::civil::DateTime = start.parse().unwrap();
-        let end_dt: Option<jiff::civil::DateTime> = end.map(|s| s.parse().unwrap());
+        let start_dt: DateTime = start.parse().unwrap();
+        let end_dt: Option<DateTime> = end.map(|s| s.parse().unwrap());
         EventOccurrence {
             event,
             start: start_dt,
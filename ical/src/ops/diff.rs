@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Differences between two versions of a calendar.
+
+use std::collections::BTreeSet;
+
+use crate::fmt::component::write_calendar_component;
+use crate::property::PropertyKind;
+use crate::semantic::identity::Canonical;
+use crate::semantic::{CalendarComponent, ICalendar};
+
+/// A change of a component between two versions of a calendar.
+#[derive(Debug, Clone)]
+pub enum ComponentChange<'a> {
+    /// The component is only in the new calendar
+    Added(&'a CalendarComponent<String>),
+
+    /// The component is only in the old calendar
+    Removed(&'a CalendarComponent<String>),
+
+    /// The component is in both calendars, but not semantically equal
+    Modified {
+        /// The component in the old calendar
+        old: &'a CalendarComponent<String>,
+        /// The component in the new calendar
+        new: &'a CalendarComponent<String>,
+        /// Kinds of the properties added, removed or changed, ordered by name
+        properties: Vec<PropertyKind<String>>,
+        /// Whether sub-components, such as alarms, were added, removed or changed
+        components: bool,
+    },
+}
+
+impl ComponentChange<'_> {
+    /// Kinds of the properties the change touches, ordered by name: all those of an added or
+    /// removed component, and those that differ for a modified one.
+    #[must_use]
+    pub fn properties(&self) -> Vec<PropertyKind<String>> {
+        match self {
+            ComponentChange::Added(component) | ComponentChange::Removed(component) => {
+                let canonical = Canonical::of(|f| write_calendar_component(f, component));
+                let names: BTreeSet<_> = canonical
+                    .properties
+                    .iter()
+                    .map(|l| property_name(l))
+                    .collect();
+                names.into_iter().map(PropertyKind::from).collect()
+            }
+            ComponentChange::Modified { properties, .. } => properties.clone(),
+        }
+    }
+}
+
+/// Lists the events, todos and journal entries added, removed or modified from `old` to `new`.
+///
+/// Components are matched by their UID and RECURRENCE-ID, see
+/// [`ICalendar::components_by_uid`], and compared by semantic equality. Added and modified
+/// components come first, in the order of `new`, followed by the removed ones in the order of
+/// `old`. Other components, such as time zones, are not compared.
+#[must_use]
+pub fn diff<'a>(
+    old: &'a ICalendar<String>,
+    new: &'a ICalendar<String>,
+) -> Vec<ComponentChange<'a>> {
+    let old_components = old.components_by_uid();
+    let new_components = new.components_by_uid();
+
+    let mut changes = Vec::new();
+    for component in &new.components {
+        let Some(identity) = component.identity() else {
+            continue;
+        };
+        let key = (identity.uid(), identity.recurrence_id());
+        if !new_components
+            .get(&key)
+            .is_some_and(|first| std::ptr::eq(*first, component))
+        {
+            continue; // A duplicate of the key, only the first one is compared
+        }
+        match old_components.get(&key) {
+            None => changes.push(ComponentChange::Added(component)),
+            Some(previous) if *previous != component => {
+                changes.push(modified(previous, component));
+            }
+            Some(_) => {}
+        }
+    }
+
+    for component in &old.components {
+        if let Some(identity) = component.identity()
+            && !new_components.contains_key(&(identity.uid(), identity.recurrence_id()))
+            && old_components
+                .get(&(identity.uid(), identity.recurrence_id()))
+                .is_some_and(|first| std::ptr::eq(*first, component))
+        {
+            changes.push(ComponentChange::Removed(component));
+        }
+    }
+    changes
+}
+
+fn modified<'a>(
+    old: &'a CalendarComponent<String>,
+    new: &'a CalendarComponent<String>,
+) -> ComponentChange<'a> {
+    let before = Canonical::of(|f| write_calendar_component(f, old));
+    let after = Canonical::of(|f| write_calendar_component(f, new));
+
+    // The property lines are sorted, so those on one side only are found in a single pass
+    let mut names = BTreeSet::new();
+    let (mut i, mut j) = (0, 0);
+    loop {
+        match (before.properties.get(i), after.properties.get(j)) {
+            (Some(a), Some(b)) if a == b => (i, j) = (i + 1, j + 1),
+            (Some(a), Some(b)) if a < b => {
+                names.insert(property_name(a));
+                i += 1;
+            }
+            (_, Some(b)) => {
+                names.insert(property_name(b));
+                j += 1;
+            }
+            (Some(a), None) => {
+                names.insert(property_name(a));
+                i += 1;
+            }
+            (None, None) => break,
+        }
+    }
+
+    ComponentChange::Modified {
+        old,
+        new,
+        properties: names.into_iter().map(PropertyKind::from).collect(),
+        components: before.components != after.components,
+    }
+}
+
+/// The name of a property line, which ends at its first parameter or its value.
+fn property_name(line: &str) -> &str {
+    line.split([';', ':']).next().unwrap_or(line)
+}
@@ -0,0 +1,351 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Assembly of standalone calendars, for sharing components outside of their store.
+//!
+//! A standalone calendar carries a `VTIMEZONE` for every `TZID` its components refer to, so that
+//! it can be read without the time zone database of the sender. The observances are taken from
+//! the time zone database, as explicit transitions over the span of the component dates.
+
+#![cfg(feature = "jiff")]
+
+use std::collections::{BTreeMap, HashSet};
+
+use jiff::tz::{Offset, TimeZone};
+use jiff::{Timestamp, ToSpan};
+
+use crate::property::{
+    DateTime, DateTimeProperty, DtStart, Method, MethodValue, RDateValue, TextWithLanguage, TzId,
+    TzName, TzOffsetFrom, TzOffsetTo, UtcOffsetProperty,
+};
+use crate::semantic::{CalendarComponent, ICalendar, TimeZoneObservance, VTimeZone};
+use crate::string_storage::StringStorage;
+use crate::value::ValueUtcOffset;
+
+/// Years covered by the time zones of a recurring component without `UNTIL`.
+const OPEN_RECURRENCE_YEARS: i16 = 10;
+
+/// Builds a calendar holding `components` on their own, such as for sending them to someone.
+///
+/// A `VTIMEZONE` is added for each `TZID` the components refer to, unless `components` already
+/// has one for it or the time zone is not in the time zone database. `METHOD` is set to `method`.
+#[must_use]
+pub fn standalone_calendar(
+    components: Vec<CalendarComponent<String>>,
+    method: Option<MethodValue>,
+) -> ICalendar<String> {
+    let defined: HashSet<String> = components
+        .iter()
+        .filter_map(|c| match c {
+            CalendarComponent::VTimeZone(tz) => Some(tz.tz_id.content.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let mut spans: BTreeMap<String, (Timestamp, Timestamp)> = BTreeMap::new();
+    for component in &components {
+        for (tz_id, start, end) in referenced_time_zones(component) {
+            if defined.contains(&tz_id) {
+                continue;
+            }
+            spans
+                .entry(tz_id)
+                .and_modify(|span| *span = (span.0.min(start), span.1.max(end)))
+                .or_insert((start, end));
+        }
+    }
+
+    let mut all = Vec::with_capacity(spans.len() + components.len());
+    all.extend(
+        spans
+            .into_iter()
+            .filter_map(|(tz_id, (start, end))| vtimezone(&tz_id, start, end))
+            .map(CalendarComponent::VTimeZone),
+    );
+    all.extend(components);
+
+    ICalendar {
+        method: method.map(|value| Method {
+            value,
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        }),
+        components: all,
+        ..ICalendar::new()
+    }
+}
+
+/// Builds a `VTIMEZONE` for a time zone of the time zone database, with the observances in effect
+/// from `start` to `end`.
+///
+/// Returns `None` if the time zone is not in the database.
+#[must_use]
+pub fn vtimezone(tz_id: &str, start: Timestamp, end: Timestamp) -> Option<VTimeZone<String>> {
+    let tz = TimeZone::get(tz_id).ok()?;
+    let mut standard = Vec::new();
+    let mut daylight = Vec::new();
+
+    // The observance in effect at the start, followed by the transitions up to the end
+    let first = tz.preceding(start.checked_add(1.nanosecond()).ok()?).next();
+    let transitions = first
+        .into_iter()
+        .chain(tz.following(start).take_while(|t| t.timestamp() <= end));
+
+    let mut any = false;
+    for transition in transitions {
+        any = true;
+        let offset_to = transition.offset();
+        let offset_from = tz
+            .preceding(transition.timestamp())
+            .next()
+            .map_or(offset_to, |t| t.offset());
+        let observance = observance(
+            offset_from.to_datetime(transition.timestamp()),
+            offset_from,
+            offset_to,
+            transition.abbreviation(),
+        );
+        if transition.dst().is_dst() {
+            daylight.push(observance);
+        } else {
+            standard.push(observance);
+        }
+    }
+
+    // Zones with a fixed offset have no transitions at all
+    if !any {
+        let info = tz.to_offset_info(start);
+        let since = jiff::civil::date(1970, 1, 1).at(0, 0, 0, 0);
+        let observance = observance(since, info.offset(), info.offset(), info.abbreviation());
+        standard.push(observance);
+    }
+
+    Some(VTimeZone {
+        tz_id: TzId::new(tz_id.to_string()),
+        last_modified: None,
+        tz_url: None,
+        standard,
+        daylight,
+        x_properties: Vec::new(),
+        retained_properties: Vec::new(),
+    })
+}
+
+fn observance(
+    onset: jiff::civil::DateTime,
+    offset_from: Offset,
+    offset_to: Offset,
+    name: &str,
+) -> TimeZoneObservance<String> {
+    let dt_start = DateTimeProperty::floating(
+        onset.date().into(),
+        onset.time().into(),
+        Vec::new(),
+        Vec::new(),
+        (),
+    );
+    TimeZoneObservance {
+        dt_start: DtStart(dt_start),
+        tz_offset_from: TzOffsetFrom(UtcOffsetProperty::new(utc_offset(offset_from))),
+        tz_offset_to: TzOffsetTo(UtcOffsetProperty::new(utc_offset(offset_to))),
+        tz_names: if name.is_empty() {
+            Vec::new()
+        } else {
+            vec![TzName(TextWithLanguage::new(name.to_string()))]
+        },
+        rrule: None,
+        x_properties: Vec::new(),
+        retained_properties: Vec::new(),
+    }
+}
+
+fn utc_offset(offset: Offset) -> ValueUtcOffset {
+    let seconds = offset.seconds();
+    let abs = seconds.unsigned_abs();
+    // An offset is less than 26 hours, so each part fits
+    #[expect(clippy::cast_possible_truncation)]
+    let part = |n: u32| n as i8;
+    ValueUtcOffset {
+        positive: seconds >= 0,
+        hour: part(abs / 3600),
+        minute: part(abs % 3600 / 60),
+        second: (!abs.is_multiple_of(60)).then(|| part(abs % 60)),
+    }
+}
+
+/// The `TZID`s a component refers to, each with the first and last instant it is used at.
+fn referenced_time_zones<S: StringStorage>(
+    component: &CalendarComponent<S>,
+) -> Vec<(String, Timestamp, Timestamp)> {
+    let mut refs: Vec<(String, jiff::civil::DateTime)> = Vec::new();
+    let mut push = |tz_id: Option<&S>, value: &DateTime| {
+        if let (Some(tz_id), Some(dt)) = (tz_id, value.civil_date_time()) {
+            refs.push((tz_id.to_string(), dt));
+        }
+    };
+
+    let (rrule, rdates, ex_dates) = match component {
+        CalendarComponent::Event(event) => {
+            if let Some(dt_start) = &event.dt_start {
+                push(dt_start.tz_id(), &dt_start.value);
+            }
+            if let Some(dt_end) = &event.dt_end {
+                push(dt_end.tz_id(), &dt_end.value);
+            }
+            (&event.rrule, &event.rdates, &event.ex_dates)
+        }
+        CalendarComponent::Todo(todo) => {
+            if let Some(dt_start) = &todo.dt_start {
+                push(dt_start.tz_id(), &dt_start.value);
+            }
+            if let Some(due) = &todo.due {
+                push(due.tz_id(), &due.value);
+            }
+            (&todo.rrule, &todo.rdates, &todo.ex_dates)
+        }
+        _ => return Vec::new(),
+    };
+    for rdate in rdates {
+        for value in &rdate.dates {
+            if let RDateValue::DateTime(value) = value {
+                push(rdate.tz_id.as_ref(), value);
+            }
+        }
+    }
+    for ex_date in ex_dates {
+        for value in &ex_date.dates {
+            push(ex_date.tz_id.as_ref(), value);
+        }
+    }
+
+    // A recurrence runs until UNTIL, or for years if it has no end date
+    let recurrence_end = rrule.as_ref().map(|rrule| match &rrule.value.until {
+        Some(until) => *until.civil_date_time(),
+        None => refs
+            .iter()
+            .map(|(_, dt)| *dt)
+            .max()
+            .and_then(|dt| dt.checked_add(OPEN_RECURRENCE_YEARS.years()).ok())
+            .unwrap_or(jiff::civil::DateTime::MAX),
+    });
+
+    let mut spans: BTreeMap<String, (Timestamp, Timestamp)> = BTreeMap::new();
+    for (tz_id, dt) in &refs {
+        let Ok(tz) = TimeZone::get(tz_id) else {
+            continue;
+        };
+        let Ok(start) = dt.to_zoned(tz.clone()).map(|z| z.timestamp()) else {
+            continue;
+        };
+        let end = recurrence_end
+            .and_then(|end| end.max(*dt).to_zoned(tz).ok())
+            .map_or(start, |z| z.timestamp());
+        spans
+            .entry(tz_id.clone())
+            .and_modify(|span| *span = (span.0.min(start), span.1.max(end)))
+            .or_insert((start, end));
+    }
+    spans
+        .into_iter()
+        .map(|(tz_id, (start, end))| (tz_id, start, end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    fn offsets(observances: &[TimeZoneObservance<String>]) -> Vec<String> {
+        observances
+            .iter()
+            .map(|o| {
+                format!(
+                    "{} {:?}->{:?}",
+                    o.dt_start.civil_date_time().unwrap(),
+                    (o.tz_offset_from.value.hour, o.tz_offset_from.value.minute),
+                    (o.tz_offset_to.value.hour, o.tz_offset_to.value.minute),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vtimezone_covers_transitions_in_range() {
+        let tz = vtimezone(
+            "America/New_York",
+            ts("2026-01-15T00:00:00Z"),
+            ts("2026-12-31T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(tz.tz_id.content.to_string(), "America/New_York");
+        assert_eq!(
+            offsets(&tz.standard),
+            [
+                "2025-11-02T02:00:00 (4, 0)->(5, 0)",
+                "2026-11-01T02:00:00 (4, 0)->(5, 0)",
+            ]
+        );
+        assert_eq!(
+            offsets(&tz.daylight),
+            ["2026-03-08T02:00:00 (5, 0)->(4, 0)"]
+        );
+        assert!(!tz.standard.first().unwrap().tz_offset_to.value.positive);
+        assert_eq!(
+            tz.daylight
+                .first()
+                .unwrap()
+                .tz_names
+                .first()
+                .unwrap()
+                .content
+                .to_string(),
+            "EDT"
+        );
+    }
+
+    #[test]
+    fn vtimezone_of_fixed_offset_zone() {
+        let tz = vtimezone(
+            "Etc/GMT-8",
+            ts("2026-01-15T00:00:00Z"),
+            ts("2026-12-31T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(
+            offsets(&tz.standard),
+            ["1970-01-01T00:00:00 (8, 0)->(8, 0)"]
+        );
+        assert!(tz.daylight.is_empty());
+
+        assert!(
+            vtimezone(
+                "Nowhere/Special",
+                ts("2026-01-15T00:00:00Z"),
+                Timestamp::MAX
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn formats_utc_offsets() {
+        let offset = utc_offset(Offset::from_seconds(-(5 * 3600 + 30 * 60)).unwrap());
+        assert_eq!(
+            offset,
+            ValueUtcOffset {
+                positive: false,
+                hour: 5,
+                minute: 30,
+                second: None
+            }
+        );
+        let offset = utc_offset(Offset::from_seconds(3600 + 15).unwrap());
+        assert_eq!(offset.second, Some(15));
+    }
+}
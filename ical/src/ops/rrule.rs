@@ -6,6 +6,12 @@
 //!
 //! This module provides extension traits for expanding recurrence rules
 //! and event occurrences according to RFC 5545.
+//!
+//! A rule is expanded one period at a time, a period being the year, month, week, day, hour,
+//! minute or second given by `FREQ`. The candidates of a period are the days of the period that
+//! match every `BYxxx` day part, combined with the times given by `BYHOUR`, `BYMINUTE` and
+//! `BYSECOND`, and then narrowed down by `BYSETPOS`. Parts that are left out default to the
+//! corresponding part of DTSTART, as described in RFC 5545 section 3.3.10.
 
 #![cfg(feature = "jiff")]
 
@@ -13,7 +19,7 @@ use jiff::civil::{Date, DateTime, Time};
 
 use crate::semantic::VEvent;
 use crate::string_storage::StringStorage;
-use crate::value::{RecurrenceFrequency, ValueDateTime, ValueRecurrenceRule, WeekDay};
+use crate::value::{RecurrenceFrequency, ValueDateTime, ValueRecurrenceRule, WeekDay, WeekDayNum};
 
 /// Maximum number of occurrences to generate to prevent infinite loops.
 const MAX_OCCURRENCES: usize = 10_000;
@@ -50,646 +56,358 @@ pub struct EventOccurrence<S: StringStorage> {
 pub trait RRuleExt {
     /// Expands the recurrence rule within a given date range.
     ///
-    /// Returns a list of dates representing the start times of each occurrence.
+    /// Returns a list of dates representing the start times of each occurrence. Only the
+    /// instances generated by the rule are returned, so DTSTART is left out when it does not
+    /// match the rule. `COUNT` counts the instances from DTSTART on, including the ones before
+    /// the range.
+    ///
+    /// `UNTIL` is compared with the civil time of the occurrences. A midnight `UNTIL` that is not
+    /// in UTC is taken as a whole day, as it cannot be told apart from a DATE value.
     ///
     /// # Errors
     ///
-    /// Returns an error if the `RRule` cannot be expanded (e.g., invalid date arithmetic).
+    /// Returns an error if the rule combines parts that RFC 5545 does not allow together, such
+    /// as `BYWEEKNO` with a `FREQ` other than `YEARLY`, or if it generates too many occurrences.
     fn expand(&self, start: DateTime, range: DateRange) -> Result<Vec<DateTime>, RRuleError>;
 }
 
 impl RRuleExt for ValueRecurrenceRule {
     fn expand(&self, start: DateTime, range: DateRange) -> Result<Vec<DateTime>, RRuleError> {
-        let mut occurrences = Vec::new();
-
-        // Get termination conditions
-        let max_count = self.count.map_or(MAX_OCCURRENCES, |c| c as usize);
-        let until_date = self.until.as_ref().map(value_datetime_to_date);
-
-        // Generate occurrences based on frequency
-        match self.freq {
-            RecurrenceFrequency::Yearly => {
-                self.expand_yearly(start, range, max_count, until_date, &mut occurrences)?;
-            }
-            RecurrenceFrequency::Monthly => {
-                self.expand_monthly(start, range, max_count, until_date, &mut occurrences)?;
-            }
-            RecurrenceFrequency::Weekly => {
-                self.expand_weekly(start, range, max_count, until_date, &mut occurrences)?;
-            }
-            RecurrenceFrequency::Daily => {
-                self.expand_daily(start, range, max_count, until_date, &mut occurrences)?;
-            }
-            RecurrenceFrequency::Hourly
-            | RecurrenceFrequency::Minutely
-            | RecurrenceFrequency::Secondly => {
-                // For sub-daily frequencies, use a simpler approach
-                self.expand_sub_daily(start, range, max_count, until_date, &mut occurrences)?;
-            }
-        }
-
-        Ok(occurrences)
-    }
-}
+        self.validate()?;
 
-impl ValueRecurrenceRule {
-    /// Expand YEARLY frequency.
-    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-    fn expand_yearly(
-        &self,
-        start: DateTime,
-        range: DateRange,
-        max_count: usize,
-        until_date: Option<Date>,
-        occurrences: &mut Vec<DateTime>,
-    ) -> Result<(), RRuleError> {
-        let interval = self.interval.unwrap_or(1) as i16;
-        let base_time = start.time();
-        let start_year = start.year();
-        let start_day = start.day();
-
-        let mut year_iter = (start_year..).step_by(interval as usize);
-
-        while occurrences.len() < max_count {
-            let year = year_iter.next().ok_or(RRuleError::TooManyOccurrences)?;
-
-            // Generate candidates for this year
-            let candidates =
-                self.generate_yearly_candidates(year, base_time, start, Some(start_day));
-
-            for dt in candidates {
-                if occurrences.len() >= max_count {
-                    break;
-                }
-
-                // Skip dates before start
-                if dt < start {
-                    continue;
-                }
-
-                // Check UNTIL condition
-                if let Some(until) = &until_date
-                    && dt.date() > *until
-                {
-                    return Ok(());
-                }
+        let until = self.until.as_ref().map(until_bound);
+        let interval = i64::from(self.interval.unwrap_or(1));
+        let first = self.first_period(start);
 
-                // Check if past range end
-                if dt.date() > range.end {
-                    return Ok(());
-                }
-
-                // Add if within range
-                if dt.date() >= range.start {
-                    occurrences.push(dt);
-                }
-            }
-
-            // Stop if we've gone past the range end significantly
-            if year > range.end.year() + 1 {
+        let mut occurrences = Vec::new();
+        let mut generated = 0;
+        let mut index = 0;
+        while let Some(period) = self.nth_period(first, index * interval) {
+            if period.date() > range.end || until.is_some_and(|until| period > until) {
                 break;
             }
 
-            // Safety check
-            if occurrences.len() >= MAX_OCCURRENCES {
-                return Err(RRuleError::TooManyOccurrences);
+            if self.is_sub_daily() && !self.matches_day(period.date(), start) {
+                // Skip the rest of the day instead of trying each hour, minute or second of it
+                index += self.periods_to_next_day(period, interval);
+                continue;
             }
-        }
-
-        Ok(())
-    }
-
-    /// Expand MONTHLY frequency.
-    #[allow(clippy::cast_possible_truncation)]
-    fn expand_monthly(
-        &self,
-        start: DateTime,
-        range: DateRange,
-        max_count: usize,
-        until_date: Option<Date>,
-        occurrences: &mut Vec<DateTime>,
-    ) -> Result<(), RRuleError> {
-        let interval = self.interval.unwrap_or(1) as i16;
-        let base_time = start.time();
-        let start_day = start.day();
-
-        let mut current = start;
-
-        while occurrences.len() < max_count {
-            // Generate candidates for this month
-            // Pass start_day only if no BYMONTHDAY or BYDAY occurrence modifiers
-            let use_start_day =
-                self.by_month_day.is_empty() && !self.by_day.iter().any(|d| d.occurrence.is_some());
-            let day_filter = if use_start_day { Some(start_day) } else { None };
-
-            let candidates = self.generate_month_candidates(
-                current.year(),
-                current.month(),
-                base_time,
-                day_filter,
-            );
-
-            for dt in candidates {
-                if occurrences.len() >= max_count {
-                    break;
-                }
 
-                // Skip dates before start
+            for dt in self.period_candidates(period, start) {
                 if dt < start {
                     continue;
                 }
-
-                // Check UNTIL condition
-                if let Some(until) = &until_date
-                    && dt.date() > *until
-                {
-                    return Ok(());
-                }
-
-                // Check if past range end
-                if dt.date() > range.end {
-                    return Ok(());
+                if until.is_some_and(|until| dt > until) || dt.date() > range.end {
+                    return Ok(occurrences);
                 }
 
-                // Add if within range
                 if dt.date() >= range.start {
+                    if occurrences.len() >= MAX_OCCURRENCES {
+                        return Err(RRuleError::TooManyOccurrences);
+                    }
                     occurrences.push(dt);
                 }
-            }
-
-            // Advance to next month (applying interval)
-            current = advance_months(current, interval)?;
 
-            // Stop if we've gone past the range end
-            if current.date() > range.end {
-                break;
+                generated += 1;
+                if self.count.is_some_and(|count| generated >= count) {
+                    return Ok(occurrences);
+                }
             }
 
-            // Safety check
-            if occurrences.len() >= MAX_OCCURRENCES {
-                return Err(RRuleError::TooManyOccurrences);
-            }
+            index += 1;
         }
 
-        Ok(())
+        Ok(occurrences)
     }
+}
 
-    /// Expand WEEKLY frequency.
-    fn expand_weekly(
-        &self,
-        start: DateTime,
-        range: DateRange,
-        max_count: usize,
-        until_date: Option<Date>,
-        occurrences: &mut Vec<DateTime>,
-    ) -> Result<(), RRuleError> {
-        let interval = self.interval.unwrap_or(1);
-        let base_time = start.time();
-        let wkst = self.wkst.unwrap_or(WeekDay::Monday);
-
-        // Calculate the start of the week containing DTSTART
-        let week_start = get_week_start(start.date(), wkst);
-
-        let mut current_week = week_start;
-
-        while occurrences.len() < max_count {
-            // Generate candidates for this week
-            let week_days = if self.by_day.is_empty() {
-                // Use the day from DTSTART
-                vec![start.date()]
-            } else {
-                self.get_week_day_dates(current_week)
-            };
-
-            for date in week_days {
-                if occurrences.len() >= max_count {
-                    break;
-                }
-
-                let dt = date.to_datetime(base_time);
-
-                // Skip dates before start
-                if dt < start {
-                    continue;
-                }
-
-                // Check UNTIL condition
-                if let Some(until) = &until_date
-                    && dt.date() > *until
-                {
-                    return Ok(());
-                }
-
-                // Check if past range end
-                if dt.date() > range.end {
-                    return Ok(());
-                }
-
-                // Add if within range
-                if dt.date() >= range.start {
-                    occurrences.push(dt);
-                }
-            }
-
-            // Advance to next week
-            current_week = current_week
-                .checked_add(jiff::Span::new().try_days(i64::from(interval) * 7).unwrap())
-                .map_err(|e| RRuleError::DateArithmetic(e.to_string()))?;
+impl ValueRecurrenceRule {
+    /// Rejects the combinations of parts that RFC 5545 does not allow, instead of expanding them
+    /// in a way the author of the rule may not expect.
+    fn validate(&self) -> Result<(), RRuleError> {
+        let not_allowed = |part: &str| {
+            Err(RRuleError::InvalidRule(format!(
+                "{part} is not allowed with FREQ={}",
+                self.freq
+            )))
+        };
 
-            // Stop if we've gone past the range end
-            if current_week > range.end {
-                break;
+        if !self.by_week_no.is_empty() && self.freq != RecurrenceFrequency::Yearly {
+            return not_allowed("BYWEEKNO");
+        }
+        if !self.by_year_day.is_empty()
+            && matches!(
+                self.freq,
+                RecurrenceFrequency::Daily
+                    | RecurrenceFrequency::Weekly
+                    | RecurrenceFrequency::Monthly
+            )
+        {
+            return not_allowed("BYYEARDAY");
+        }
+        if !self.by_month_day.is_empty() && self.freq == RecurrenceFrequency::Weekly {
+            return not_allowed("BYMONTHDAY");
+        }
+        if self.by_day.iter().any(|d| d.occurrence.is_some()) {
+            if !matches!(
+                self.freq,
+                RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly
+            ) {
+                return not_allowed("BYDAY with an ordinal");
             }
-
-            // Safety check
-            if occurrences.len() >= MAX_OCCURRENCES {
-                return Err(RRuleError::TooManyOccurrences);
+            if !self.by_week_no.is_empty() {
+                return Err(RRuleError::InvalidRule(
+                    "BYDAY with an ordinal is not allowed with BYWEEKNO".to_string(),
+                ));
             }
         }
-
         Ok(())
     }
 
-    /// Expand DAILY frequency.
-    fn expand_daily(
-        &self,
-        start: DateTime,
-        range: DateRange,
-        max_count: usize,
-        until_date: Option<Date>,
-        occurrences: &mut Vec<DateTime>,
-    ) -> Result<(), RRuleError> {
-        let interval = self.interval.unwrap_or(1);
-        let base_time = start.time();
-
-        let mut current = start.date();
-
-        while occurrences.len() < max_count {
-            // Check UNTIL condition
-            if let Some(until) = &until_date
-                && current > *until
-            {
-                return Ok(());
-            }
-
-            // Check if past range end
-            if current > range.end {
-                return Ok(());
-            }
-
-            // Check BYDAY filter
-            let matches_byday = self.by_day.is_empty() || self.day_matches_byday(current);
-
-            // Check BYMONTH filter
-            let matches_bymonth = self.by_month.is_empty()
-                || self.by_month.contains(&current.month().cast_unsigned());
-
-            // Check BYMONTHDAY filter
-            let matches_bymonthday = if self.by_month_day.is_empty() {
-                true
-            } else {
-                let days = days_in_month(current.year(), current.month());
-                self.resolve_month_days_for_date(current.day(), days)
-            };
-
-            if matches_byday && matches_bymonth && matches_bymonthday && current >= range.start {
-                occurrences.push(current.to_datetime(base_time));
-            }
-
-            // Advance by interval days
-            current = current
-                .checked_add(jiff::Span::new().try_days(i64::from(interval)).unwrap())
-                .map_err(|e| RRuleError::DateArithmetic(e.to_string()))?;
-
-            // Safety check
-            if occurrences.len() >= MAX_OCCURRENCES {
-                return Err(RRuleError::TooManyOccurrences);
-            }
-        }
-
-        Ok(())
+    const fn is_sub_daily(&self) -> bool {
+        matches!(
+            self.freq,
+            RecurrenceFrequency::Hourly
+                | RecurrenceFrequency::Minutely
+                | RecurrenceFrequency::Secondly
+        )
     }
 
-    /// Expand sub-daily frequencies (HOURLY, MINUTELY, SECONDLY).
-    fn expand_sub_daily(
-        &self,
-        start: DateTime,
-        range: DateRange,
-        max_count: usize,
-        until_date: Option<Date>,
-        occurrences: &mut Vec<DateTime>,
-    ) -> Result<(), RRuleError> {
-        let interval = self.interval.unwrap_or(1);
-        let span = match self.freq {
-            RecurrenceFrequency::Hourly => {
-                jiff::Span::new().try_hours(i64::from(interval)).unwrap()
-            }
-            RecurrenceFrequency::Minutely => {
-                jiff::Span::new().try_minutes(i64::from(interval)).unwrap()
+    /// The start of the period containing DTSTART.
+    fn first_period(&self, start: DateTime) -> DateTime {
+        let date = start.date();
+        let midnight = Time::midnight();
+        match self.freq {
+            RecurrenceFrequency::Yearly => date.first_of_year().to_datetime(midnight),
+            RecurrenceFrequency::Monthly => date.first_of_month().to_datetime(midnight),
+            RecurrenceFrequency::Weekly => {
+                get_week_start(date, self.wkst.unwrap_or(WeekDay::Monday)).to_datetime(midnight)
             }
+            RecurrenceFrequency::Daily => date.to_datetime(midnight),
+            RecurrenceFrequency::Hourly => date.at(start.hour(), 0, 0, 0),
+            RecurrenceFrequency::Minutely => date.at(start.hour(), start.minute(), 0, 0),
             RecurrenceFrequency::Secondly => {
-                jiff::Span::new().try_seconds(i64::from(interval)).unwrap()
-            }
-            _ => unreachable!(),
-        };
-
-        let mut current = start;
-
-        while occurrences.len() < max_count {
-            // Check UNTIL condition
-            if let Some(until) = &until_date
-                && current.date() > *until
-            {
-                return Ok(());
-            }
-
-            // Check if past range end
-            if current.date() > range.end {
-                return Ok(());
-            }
-
-            // Add if within range
-            if current.date() >= range.start {
-                occurrences.push(current);
-            }
-
-            // Advance
-            current = current
-                .checked_add(span)
-                .map_err(|e| RRuleError::DateArithmetic(e.to_string()))?;
-
-            // Safety check
-            if occurrences.len() >= MAX_OCCURRENCES {
-                return Err(RRuleError::TooManyOccurrences);
+                date.at(start.hour(), start.minute(), start.second(), 0)
             }
         }
-
-        Ok(())
     }
 
-    /// Generate candidates for YEARLY frequency.
-    fn generate_yearly_candidates(
-        &self,
-        year: i16,
-        base_time: Time,
-        start: DateTime,
-        start_day: Option<i8>,
-    ) -> Vec<DateTime> {
-        let mut candidates = Vec::new();
-
-        // BYYEARDAY takes precedence if present
-        if !self.by_year_day.is_empty() {
-            return self.generate_by_year_day(year, base_time);
-        }
-
-        // BYWEEKNO takes precedence if present (YEARLY only)
-        if !self.by_week_no.is_empty() {
-            return self.generate_by_week_no(year, base_time);
-        }
-
-        // Determine which months to iterate
-        // For YEARLY without BYMONTH, use only the month from DTSTART
-        let months: Vec<i8> = if self.by_month.is_empty() {
-            vec![start.month()]
-        } else {
-            self.by_month.iter().map(|&m| m.cast_signed()).collect()
+    /// The start of the period `n` periods after `first`, or `None` past the supported dates.
+    fn nth_period(&self, first: DateTime, n: i64) -> Option<DateTime> {
+        let span = jiff::Span::new();
+        let span = match self.freq {
+            RecurrenceFrequency::Yearly => span.try_years(n),
+            RecurrenceFrequency::Monthly => span.try_months(n),
+            RecurrenceFrequency::Weekly => span.try_weeks(n),
+            RecurrenceFrequency::Daily => span.try_days(n),
+            RecurrenceFrequency::Hourly => span.try_hours(n),
+            RecurrenceFrequency::Minutely => span.try_minutes(n),
+            RecurrenceFrequency::Secondly => span.try_seconds(n),
         };
-
-        for month in months {
-            let month_candidates =
-                self.generate_month_candidates(year, month, base_time, start_day);
-            candidates.extend(month_candidates);
-        }
-
-        candidates
+        first.checked_add(span.ok()?).ok()
     }
 
-    /// Generate candidates for a specific month.
-    fn generate_month_candidates(
-        &self,
-        year: i16,
-        month: i8,
-        base_time: Time,
-        start_day: Option<i8>,
-    ) -> Vec<DateTime> {
-        let mut candidates = Vec::new();
-
-        // If BYDAY has occurrence modifiers (like 2MO, -1FR), handle specially
-        if self.by_day.iter().any(|d| d.occurrence.is_some()) {
-            return self.generate_byday_with_occurrence(year, month, base_time);
-        }
-
-        // Determine days in month
-        let days = days_in_month(year, month);
-
-        // Resolve BYMONTHDAY values (handle negative values)
-        // If no BYMONTHDAY and no BYDAY, use the day from DTSTART (if provided)
-        // If BYDAY is specified, generate all matching weekdays
-        let month_days: Vec<i8> = if !self.by_month_day.is_empty() {
-            self.resolve_month_days(year, month)
-        } else if !self.by_day.is_empty() {
-            // BYDAY is specified - generate all days, then filter by weekday
-            (1..=days).collect()
-        } else if let Some(day) = start_day {
-            vec![day.min(days)]
-        } else {
-            (1..=days).collect()
+    /// Number of sub-daily periods to skip to reach the first period of the next day.
+    fn periods_to_next_day(&self, period: DateTime, interval: i64) -> i64 {
+        let unit = match self.freq {
+            RecurrenceFrequency::Hourly => 3600,
+            RecurrenceFrequency::Minutely => 60,
+            _ => 1,
+        };
+        let Ok(next_day) = period.date().tomorrow() else {
+            return 1;
+        };
+        let seconds = period
+            .duration_until(next_day.to_datetime(Time::midnight()))
+            .as_secs();
+        let step = unit * interval;
+        ((seconds + step - 1) / step).max(1)
+    }
+
+    /// The instances of the period starting at `period`, sorted and narrowed down by `BYSETPOS`.
+    fn period_candidates(&self, period: DateTime, start: DateTime) -> Vec<DateTime> {
+        let first_day = period.date();
+        let days = match self.freq {
+            RecurrenceFrequency::Yearly => i64::from(first_day.days_in_year()),
+            RecurrenceFrequency::Monthly => i64::from(first_day.days_in_month()),
+            RecurrenceFrequency::Weekly => 7,
+            _ => 1,
         };
 
-        // Generate candidates for each day
-        for day in month_days {
-            if let Ok(date) = Date::new(year, month, day) {
-                // Apply BYDAY filter if present
-                if !self.by_day.is_empty() && !self.day_matches_byday(date) {
-                    continue;
-                }
-
-                let dt = date.to_datetime(base_time);
-                candidates.push(dt);
-            }
-        }
+        let times = self.period_times(period, start);
+        let candidates = (0..days)
+            .filter_map(|offset| {
+                first_day
+                    .checked_add(jiff::Span::new().try_days(offset).ok()?)
+                    .ok()
+            })
+            .filter(|&date| self.matches_day(date, start))
+            .flat_map(|date| times.iter().map(move |&time| date.to_datetime(time)))
+            .collect();
 
-        // Apply BYSETPOS filter
         self.apply_by_set_pos(candidates)
     }
 
-    /// Generate candidates from BYYEARDAY.
-    fn generate_by_year_day(&self, year: i16, base_time: Time) -> Vec<DateTime> {
-        let mut result = Vec::new();
-        let days_in_year = if is_leap_year(year) { 366 } else { 365 };
-
-        for &yearday in &self.by_year_day {
-            let day_of_year = if yearday > 0 {
-                yearday
+    /// The times of day of the instances of a period, sorted.
+    fn period_times(&self, period: DateTime, start: DateTime) -> Vec<Time> {
+        let or_start = |values: &[u8], value: i8| -> Vec<i8> {
+            if values.is_empty() {
+                vec![value]
             } else {
-                // Negative: count from end of year
-                days_in_year + yearday + 1
-            };
-
-            if day_of_year < 1 || day_of_year > days_in_year {
-                continue;
+                values.iter().map(|&v| v.cast_signed()).collect()
             }
-
-            if let Some(date) = date_from_year_day(year, day_of_year) {
-                let dt = date.to_datetime(base_time);
-                result.push(dt);
+        };
+        let limit = |values: &[u8], value: i8| -> Vec<i8> {
+            if values.is_empty() || values.contains(&value.cast_unsigned()) {
+                vec![value]
+            } else {
+                Vec::new()
             }
-        }
-
-        result.sort();
-        result.dedup();
-        result
-    }
+        };
 
-    /// Generate candidates from BYWEEKNO.
-    fn generate_by_week_no(&self, year: i16, base_time: Time) -> Vec<DateTime> {
-        let wkst = self.wkst.unwrap_or(WeekDay::Monday);
-        let mut result = Vec::new();
+        let (hours, minutes, seconds) = match self.freq {
+            RecurrenceFrequency::Hourly => (
+                limit(&self.by_hour, period.hour()),
+                or_start(&self.by_minute, start.minute()),
+                or_start(&self.by_second, start.second()),
+            ),
+            RecurrenceFrequency::Minutely => (
+                limit(&self.by_hour, period.hour()),
+                limit(&self.by_minute, period.minute()),
+                or_start(&self.by_second, start.second()),
+            ),
+            RecurrenceFrequency::Secondly => (
+                limit(&self.by_hour, period.hour()),
+                limit(&self.by_minute, period.minute()),
+                limit(&self.by_second, period.second()),
+            ),
+            _ => (
+                or_start(&self.by_hour, start.hour()),
+                or_start(&self.by_minute, start.minute()),
+                or_start(&self.by_second, start.second()),
+            ),
+        };
 
-        for &weekno in &self.by_week_no {
-            let target_week = if weekno > 0 {
-                weekno
-            } else {
-                // Negative week number: count from end of year
-                let weeks_in_year = weeks_in_year(year, wkst);
-                weeks_in_year + weekno + 1
-            };
+        let mut times: Vec<_> = hours
+            .iter()
+            .flat_map(|&h| minutes.iter().map(move |&m| (h, m)))
+            .flat_map(|(h, m)| seconds.iter().map(move |&s| (h, m, s)))
+            .filter_map(|(h, m, s)| Time::new(h, m, s, 0).ok())
+            .collect();
+        times.sort();
+        times.dedup();
+        times
+    }
 
-            // Get the first day of the target week
-            if let Some(week_start) = get_date_for_week(year, target_week, wkst) {
-                // Generate days in this week
-                for offset in 0i64..7 {
-                    if let Ok(date) =
-                        week_start.checked_add(jiff::Span::new().try_days(offset).unwrap())
-                    {
-                        // Apply BYDAY filter if present
-                        if !self.by_day.is_empty() && !self.day_matches_byday(date) {
-                            continue;
-                        }
-
-                        let dt = date.to_datetime(base_time);
-                        result.push(dt);
-                    }
-                }
+    /// Check if a date matches the day parts of the rule.
+    ///
+    /// When there is no `BYWEEKNO`, `BYYEARDAY`, `BYMONTHDAY` or `BYDAY`, the day is taken from
+    /// DTSTART: the day of the month for `MONTHLY` and `YEARLY`, which also takes the month when
+    /// there is no `BYMONTH`, and the day of the week for `WEEKLY`.
+    fn matches_day(&self, date: Date, start: DateTime) -> bool {
+        let from_start = self.by_week_no.is_empty()
+            && self.by_year_day.is_empty()
+            && self.by_month_day.is_empty()
+            && self.by_day.is_empty();
+
+        if self.by_month.is_empty() {
+            if from_start
+                && self.freq == RecurrenceFrequency::Yearly
+                && date.month() != start.month()
+            {
+                return false;
             }
+        } else if !self.by_month.contains(&date.month().cast_unsigned()) {
+            return false;
         }
 
-        result.sort();
-        result.dedup();
-        result
-    }
-
-    /// Resolve BYMONTHDAY values, handling negative indices.
-    fn resolve_month_days(&self, year: i16, month: i8) -> Vec<i8> {
-        let days = days_in_month(year, month);
-        let mut result = Vec::new();
-
-        for &day in &self.by_month_day {
-            let resolved = if day > 0 {
-                if day <= days { Some(day) } else { None }
-            } else {
-                // Negative: -1 = last day, -2 = second to last, etc.
-                let from_end = -day;
-                if from_end <= days {
-                    Some(days - from_end + 1)
-                } else {
-                    None
+        if from_start {
+            return match self.freq {
+                RecurrenceFrequency::Yearly | RecurrenceFrequency::Monthly => {
+                    date.day() == start.day()
                 }
+                RecurrenceFrequency::Weekly => date.weekday() == start.weekday(),
+                _ => true,
             };
-            if let Some(d) = resolved {
-                result.push(d);
-            }
         }
 
-        result
-    }
+        if !self.by_week_no.is_empty() && !self.matches_week_no(date) {
+            return false;
+        }
 
-    /// Check if a specific day matches the BYMONTHDAY list.
-    fn resolve_month_days_for_date(&self, day: i8, days_in_month: i8) -> bool {
-        for &md in &self.by_month_day {
-            let resolved = if md > 0 {
-                md
-            } else {
-                // Negative: -1 = last day
-                days_in_month + md + 1
-            };
-            if resolved == day {
-                return true;
+        if !self.by_year_day.is_empty() {
+            let days = date.days_in_year();
+            if !matches_signed(self.by_year_day.iter().copied(), date.day_of_year(), days) {
+                return false;
             }
         }
-        false
-    }
 
-    /// Check if a date matches the BYDAY constraint.
-    fn day_matches_byday(&self, date: Date) -> bool {
-        let weekday = date.weekday();
-        for byday in &self.by_day {
-            // Only check weekday, not occurrence (that's handled separately)
-            if byday.occurrence.is_none() && weekday_to_weekday(byday.day) == weekday {
-                return true;
+        if !self.by_month_day.is_empty() {
+            let days = i16::from(days_in_month(date.year(), date.month()));
+            let values = self.by_month_day.iter().map(|&d| i16::from(d));
+            if !matches_signed(values, i16::from(date.day()), days) {
+                return false;
             }
         }
-        false
+
+        self.by_day.is_empty() || self.by_day.iter().any(|&d| self.matches_weekday(d, date))
     }
 
-    /// Generate candidates for BYDAY with occurrence modifiers.
-    fn generate_byday_with_occurrence(
-        &self,
-        year: i16,
-        month: i8,
-        base_time: Time,
-    ) -> Vec<DateTime> {
-        let mut candidates = Vec::new();
-
-        for byday in &self.by_day {
-            if let Some(occ) = byday.occurrence {
-                let weekday = weekday_to_weekday(byday.day);
-                let dates = get_nth_weekday_of_month(year, month, weekday, occ);
-                for date in dates {
-                    let dt = date.to_datetime(base_time);
-                    candidates.push(dt);
-                }
-            } else {
-                // No occurrence modifier - add all matching weekdays in month
-                let days = days_in_month(year, month);
-                for day in 1..=days {
-                    if let Ok(date) = Date::new(year, month, day)
-                        && date.weekday() == weekday_to_weekday(byday.day)
-                    {
-                        let dt = date.to_datetime(base_time);
-                        candidates.push(dt);
-                    }
-                }
-            }
+    /// Check if a date matches a `BYDAY` value.
+    ///
+    /// An ordinal counts the weekdays of the month for `MONTHLY` and for `YEARLY` with
+    /// `BYMONTH`, and of the year otherwise.
+    fn matches_weekday(&self, day: WeekDayNum, date: Date) -> bool {
+        if weekday_to_weekday(day.day) != date.weekday() {
+            return false;
         }
+        let Some(occurrence) = day.occurrence else {
+            return true;
+        };
 
-        candidates.sort();
-        candidates.dedup();
-        candidates
-    }
+        let in_month = self.freq == RecurrenceFrequency::Monthly || !self.by_month.is_empty();
+        let (position, len) = if in_month {
+            (
+                i16::from(date.day()),
+                i16::from(days_in_month(date.year(), date.month())),
+            )
+        } else {
+            (date.day_of_year(), date.days_in_year())
+        };
 
-    /// Get dates for specific weekdays within a week.
-    fn get_week_day_dates(&self, week_start: Date) -> Vec<Date> {
-        let mut dates = Vec::new();
-
-        for offset in 0i64..7 {
-            if let Ok(date) = week_start.checked_add(jiff::Span::new().try_days(offset).unwrap()) {
-                let weekday = date.weekday();
-
-                if self.by_day.is_empty() {
-                    dates.push(date);
-                } else {
-                    for byday in &self.by_day {
-                        if byday.occurrence.is_none() && weekday_to_weekday(byday.day) == weekday {
-                            dates.push(date);
-                            break;
-                        }
-                    }
-                }
-            }
+        if occurrence > 0 {
+            (position - 1) / 7 + 1 == i16::from(occurrence)
+        } else {
+            (len - position) / 7 + 1 == -i16::from(occurrence)
         }
+    }
+
+    /// Check if a date is in one of the weeks of `BYWEEKNO`.
+    ///
+    /// Week one is the first week with at least four days in the year, starting on `WKST`. The
+    /// days of a year that belong to a week of the previous or the next year are numbered as
+    /// such.
+    fn matches_week_no(&self, date: Date) -> bool {
+        let wkst = self.wkst.unwrap_or(WeekDay::Monday);
+        let first_week = |year: i16| Some(get_week_start(Date::new(year, 1, 4).ok()?, wkst));
+        let week_of = |year: i16| -> Option<(i16, i16)> {
+            let first = first_week(year)?;
+            let next = first_week(year + 1)?;
+            let week = (date - first).get_days() / 7 + 1;
+            let weeks = (next - first).get_days() / 7;
+            Some((i16::try_from(week).ok()?, i16::try_from(weeks).ok()?))
+        };
 
-        dates
+        let year = date.year();
+        let week_year = match (first_week(year), first_week(year + 1)) {
+            (Some(first), _) if date < first => year - 1,
+            (_, Some(next)) if date >= next => year + 1,
+            _ => year,
+        };
+        let Some((week, weeks)) = week_of(week_year) else {
+            return false;
+        };
+        matches_signed(self.by_week_no.iter().map(|&w| i16::from(w)), week, weeks)
     }
 
     /// Apply BYSETPOS filter to candidates.
@@ -734,7 +452,8 @@ impl ValueRecurrenceRule {
 pub trait VEventExt<S: StringStorage> {
     /// Expands an event with its recurrence rule into individual occurrences.
     ///
-    /// If the event has no recurrence rule, returns a single occurrence.
+    /// If the event has no recurrence rule, returns a single occurrence. An event without
+    /// `DTSTART` is unscheduled, so it has no occurrences.
     ///
     /// # Errors
     ///
@@ -745,8 +464,10 @@ pub trait VEventExt<S: StringStorage> {
 impl<S: StringStorage> VEventExt<S> for VEvent<S> {
     fn expand_occurrences(&self, range: DateRange) -> Result<Vec<EventOccurrence<S>>, RRuleError> {
         // Get start datetime
-        let start = self
-            .dt_start
+        let Some(dt_start) = &self.dt_start else {
+            return Ok(Vec::new());
+        };
+        let start = dt_start
             .civil_date_time()
             .ok_or_else(|| RRuleError::InvalidRule("DTSTART must be a date-time".to_string()))?;
 
@@ -777,9 +498,8 @@ impl<S: StringStorage> VEventExt<S> for VEvent<S> {
 impl<S: StringStorage> VEvent<S> {
     /// Calculate the duration of the event.
     fn calculate_duration(&self) -> Option<jiff::Span> {
-        if let Some(ref dt_end) = self.dt_end
-            && let (Some(start), Some(end)) =
-                (self.dt_start.civil_date_time(), dt_end.civil_date_time())
+        if let (Some(dt_start), Some(dt_end)) = (&self.dt_start, &self.dt_end)
+            && let (Some(start), Some(end)) = (dt_start.civil_date_time(), dt_end.civil_date_time())
         {
             return end.since(start).ok();
         }
@@ -792,13 +512,26 @@ impl<S: StringStorage> VEvent<S> {
     }
 }
 
-/// Convert `ValueDateTime` to `Date`.
-fn value_datetime_to_date(dt: &ValueDateTime) -> Date {
-    jiff::civil::date(dt.date.year, dt.date.month, dt.date.day)
+/// The last date-time allowed by `UNTIL`.
+fn until_bound(until: &ValueDateTime) -> DateTime {
+    let time = until.time;
+    if !time.utc && time.hour == 0 && time.minute == 0 && time.second == 0 {
+        until.date.civil_date().to_datetime(Time::MAX)
+    } else {
+        *until.civil_date_time()
+    }
+}
+
+/// Check if a position matches one of `values`, where negative values count from the end of
+/// `len` positions.
+fn matches_signed(values: impl IntoIterator<Item = i16>, position: i16, len: i16) -> bool {
+    values
+        .into_iter()
+        .any(|v| v == position || v == position - len - 1)
 }
 
 /// Convert `ValueDuration` to `jiff::Span`.
-fn value_duration_to_span(duration: &crate::value::ValueDuration) -> Option<jiff::Span> {
+pub(crate) fn value_duration_to_span(duration: &crate::value::ValueDuration) -> Option<jiff::Span> {
     use crate::value::ValueDuration as VDur;
 
     match duration {
@@ -841,35 +574,6 @@ fn weekday_to_weekday(day: WeekDay) -> jiff::civil::Weekday {
     }
 }
 
-/// Advance a datetime by a number of months.
-#[allow(clippy::cast_possible_truncation)]
-fn advance_months(dt: DateTime, months: i16) -> Result<DateTime, RRuleError> {
-    let year = dt.year();
-    let month = dt.month();
-    let day = dt.day();
-    let time = dt.time();
-
-    // Calculate new year and month
-    let total_months = (year * 12) + i16::from(month) - 1 + months;
-    let new_year = total_months / 12;
-    let new_month = (total_months % 12) + 1;
-
-    // Handle day overflow (e.g., Jan 31 + 1 month = Feb 28/29)
-    let max_day = days_in_month(new_year, new_month as i8);
-    let new_day = day.min(max_day);
-
-    DateTime::new(
-        new_year,
-        new_month as i8,
-        new_day,
-        time.hour(),
-        time.minute(),
-        time.second(),
-        i32::from(time.millisecond()),
-    )
-    .map_err(|e| RRuleError::DateArithmetic(e.to_string()))
-}
-
 /// Get the start of the week containing a date.
 fn get_week_start(date: Date, wkst: WeekDay) -> Date {
     let weekday = date.weekday();
@@ -881,97 +585,12 @@ fn get_week_start(date: Date, wkst: WeekDay) -> Date {
 
     let days_since_week_start = (weekday_num - wkst_num + 7) % 7;
 
-    date.checked_sub(jiff::Span::new().try_days(days_since_week_start).unwrap())
+    jiff::Span::new()
+        .try_days(days_since_week_start)
+        .and_then(|span| date.checked_sub(span))
         .unwrap_or(date)
 }
 
-/// Get the number of weeks in a year.
-#[allow(clippy::cast_possible_truncation)]
-fn weeks_in_year(year: i16, wkst: WeekDay) -> i8 {
-    let jan1 = jiff::civil::date(year, 1, 1);
-    let dec31 = jiff::civil::date(year, 12, 31);
-
-    let first_week_start = get_week_start(jan1, wkst);
-    let last_week_start = get_week_start(dec31, wkst);
-
-    let days = (last_week_start - first_week_start).get_days();
-    ((days / 7) + 1) as i8
-}
-
-/// Get the date for a specific week number in a year.
-fn get_date_for_week(year: i16, week: i8, wkst: WeekDay) -> Option<Date> {
-    let jan1 = jiff::civil::date(year, 1, 1);
-    let first_week_start = get_week_start(jan1, wkst);
-
-    first_week_start
-        .checked_add(jiff::Span::new().try_weeks(i64::from(week - 1)).unwrap())
-        .ok()
-}
-
-/// Get the date from a year and day-of-year.
-#[allow(clippy::cast_possible_truncation)]
-fn date_from_year_day(year: i16, day_of_year: i16) -> Option<Date> {
-    let is_leap = is_leap_year(year);
-    let days_in_months = if is_leap {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
-
-    let mut remaining_days = day_of_year;
-    for (month, &days) in days_in_months.iter().enumerate() {
-        if remaining_days <= days as i16 {
-            return Date::new(year, (month + 1) as i8, remaining_days as i8).ok();
-        }
-        remaining_days -= days as i16;
-    }
-
-    None
-}
-
-/// Get the nth occurrence of a weekday in a month.
-#[allow(clippy::cast_sign_loss)]
-fn get_nth_weekday_of_month(
-    year: i16,
-    month: i8,
-    weekday: jiff::civil::Weekday,
-    n: i8,
-) -> Vec<Date> {
-    let days = days_in_month(year, month);
-
-    // Find all occurrences of the weekday in the month
-    let mut occurrences = Vec::new();
-    for day in 1..=days {
-        if let Ok(date) = Date::new(year, month, day)
-            && date.weekday() == weekday
-        {
-            occurrences.push(date);
-        }
-    }
-
-    if occurrences.is_empty() {
-        return Vec::new();
-    }
-
-    // Get the nth occurrence (1-indexed) or -n from end
-    let index = if n > 0 {
-        (n as usize).checked_sub(1)
-    } else {
-        // Negative: count from end
-        let from_end = (-n) as usize;
-        if from_end <= occurrences.len() {
-            Some(occurrences.len() - from_end)
-        } else {
-            None
-        }
-    };
-
-    index
-        .and_then(|i| occurrences.get(i).copied())
-        .map(|date| vec![date])
-        .unwrap_or_default()
-}
-
 /// Get the number of days in a month.
 fn days_in_month(year: i16, month: i8) -> i8 {
     match month {
@@ -1216,6 +835,45 @@ mod tests {
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn rrule_expand_rejects_parts_not_allowed_with_freq() {
+        let start = create_datetime(2024, 1, 1, 10, 0, 0);
+        let range = DateRange::new(create_date(2024, 1, 1), create_date(2024, 12, 31));
+
+        for src in [
+            "FREQ=MONTHLY;BYWEEKNO=20",
+            "FREQ=WEEKLY;BYYEARDAY=100",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=YEARLY;BYWEEKNO=20;BYDAY=1MO",
+        ] {
+            let rrule: ValueRecurrenceRule = src.parse().unwrap();
+            assert!(
+                matches!(rrule.expand(start, range), Err(RRuleError::InvalidRule(_))),
+                "{src} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rrule_expand_skips_days_without_matching_hours() {
+        let mut rrule = create_rrule(RecurrenceFrequency::Hourly);
+        rrule.by_month = vec![3];
+        rrule.by_hour = vec![8];
+        let start = create_datetime(2024, 1, 1, 8, 30, 0);
+        let range = DateRange::new(create_date(2024, 1, 1), create_date(2024, 3, 2));
+
+        let result = rrule.expand(start, range).unwrap();
+
+        assert_eq!(
+            result,
+            [
+                create_datetime(2024, 3, 1, 8, 30, 0),
+                create_datetime(2024, 3, 2, 8, 30, 0),
+            ]
+        );
+    }
+
     #[test]
     fn days_in_month_february_leap_year() {
         assert_eq!(days_in_month(2024, 2), 29);
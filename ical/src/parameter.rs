@@ -14,14 +14,15 @@ mod definition;
 mod kind;
 
 pub use definition::{
-    AlarmTriggerRelationship, CalendarUserType, Encoding, FreeBusyType, ParticipationRole,
-    ParticipationStatus, RecurrenceIdRange, RelationshipType, ValueType,
+    AlarmTriggerRelationship, CalendarUserType, DisplayType, Encoding, FeatureType, FreeBusyType,
+    ParticipationRole, ParticipationStatus, RecurrenceIdRange, RelationshipType, ValueType,
 };
 pub use kind::ParameterKind;
 
 use crate::parameter::definition::{
-    parse_alarm_trigger_relationship, parse_cutype, parse_encoding, parse_fbtype, parse_partstat,
-    parse_range, parse_reltype, parse_role, parse_rsvp, parse_tzid, parse_value_type,
+    parse_alarm_trigger_relationship, parse_cutype, parse_display, parse_encoding, parse_fbtype,
+    parse_feature, parse_partstat, parse_range, parse_reltype, parse_role, parse_rsvp, parse_tzid,
+    parse_value_type,
 };
 use crate::parameter::util::{parse_multiple_quoted, parse_single, parse_single_quoted};
 use crate::string_storage::{Segments, StringStorage};
@@ -251,6 +252,40 @@ pub enum Parameter<S: StringStorage> {
     /// See also: RFC 5545 Section 3.2.20. Value Data Types
     ValueType { value: ValueType<S>, span: S::Span },
 
+    /// This parameter can be specified on the "IMAGE" property to indicate
+    /// the different ways in which the image can be displayed. Applications
+    /// MUST treat x-name and iana-token values they don't recognize the same
+    /// way as they would the BADGE value.
+    ///
+    /// See also: RFC 7986 Section 6.1. Display
+    DisplayType {
+        values: Vec<DisplayType<S>>,
+        span: S::Span,
+    },
+
+    /// This parameter can be specified on the "ATTENDEE" property to give
+    /// the email address of the calendar user when it differs from the
+    /// calendar address.
+    ///
+    /// See also: RFC 7986 Section 6.2. Email
+    Email { value: S, span: S::Span },
+
+    /// This parameter can be specified on the "CONFERENCE" property to
+    /// describe the features of the conference or broadcast system, such as
+    /// audio, video or screen sharing.
+    ///
+    /// See also: RFC 7986 Section 6.3. Feature
+    FeatureType {
+        values: Vec<FeatureType<S>>,
+        span: S::Span,
+    },
+
+    /// This parameter can be specified on the "CONFERENCE" property to
+    /// provide a human-readable label, such as a dial-in code.
+    ///
+    /// See also: RFC 7986 Section 6.4. Label
+    Label { value: S, span: S::Span },
+
     /// Custom experimental x-name parameter.
     ///
     /// Per RFC 5545 Section 3.2: Applications MUST ignore x-param values,
@@ -293,6 +328,10 @@ impl<S: StringStorage> Parameter<S> {
             Parameter::RsvpExpectation { .. } => ParameterKind::RsvpExpectation,
             Parameter::TimeZoneIdentifier { .. } => ParameterKind::TimeZoneIdentifier,
             Parameter::ValueType { .. } => ParameterKind::ValueType,
+            Parameter::DisplayType { .. } => ParameterKind::DisplayType,
+            Parameter::Email { .. } => ParameterKind::Email,
+            Parameter::FeatureType { .. } => ParameterKind::FeatureType,
+            Parameter::Label { .. } => ParameterKind::Label,
             Parameter::XName(raw) => ParameterKind::XName(&raw.name),
             Parameter::Unrecognized(raw) => ParameterKind::Unrecognized(&raw.name),
         }
@@ -321,7 +360,11 @@ impl<S: StringStorage> Parameter<S> {
             | Parameter::SendBy { span, .. }
             | Parameter::RsvpExpectation { span, .. }
             | Parameter::TimeZoneIdentifier { span, .. }
-            | Parameter::ValueType { span, .. } => *span,
+            | Parameter::ValueType { span, .. }
+            | Parameter::DisplayType { span, .. }
+            | Parameter::Email { span, .. }
+            | Parameter::FeatureType { span, .. }
+            | Parameter::Label { span, .. } => *span,
 
             Parameter::XName(raw) | Parameter::Unrecognized(raw) => raw.span,
         }
@@ -331,6 +374,7 @@ impl<S: StringStorage> Parameter<S> {
 impl Parameter<Segments<'_>> {
     /// Convert borrowed type to owned type
     #[must_use]
+    #[expect(clippy::too_many_lines)]
     pub fn to_owned(&self) -> Parameter<String> {
         match self {
             Parameter::AlternateText { value, .. } => Parameter::AlternateText {
@@ -422,6 +466,22 @@ impl Parameter<Segments<'_>> {
                 value: value.to_owned(),
                 span: (),
             },
+            Parameter::DisplayType { values, .. } => Parameter::DisplayType {
+                values: values.iter().map(DisplayType::to_owned).collect(),
+                span: (),
+            },
+            Parameter::Email { value, .. } => Parameter::Email {
+                value: value.to_owned(),
+                span: (),
+            },
+            Parameter::FeatureType { values, .. } => Parameter::FeatureType {
+                values: values.iter().map(FeatureType::to_owned).collect(),
+                span: (),
+            },
+            Parameter::Label { value, .. } => Parameter::Label {
+                value: value.to_owned(),
+                span: (),
+            },
             Parameter::XName(raw) => Parameter::XName(raw.to_owned()),
             Parameter::Unrecognized(raw) => Parameter::Unrecognized(raw.to_owned()),
         }
@@ -499,6 +559,16 @@ impl<'src> TryFrom<RawParameter<Segments<'src>>> for Parameter<Segments<'src>> {
             ParameterKind::RsvpExpectation => parse_rsvp(param),
             ParameterKind::TimeZoneIdentifier => parse_tzid(param),
             ParameterKind::ValueType => parse_value_type(param),
+            ParameterKind::DisplayType => parse_display(param),
+            ParameterKind::Email => parse_single(&mut param, kind).map(|v| Parameter::Email {
+                value: v.value,
+                span: param.span,
+            }),
+            ParameterKind::FeatureType => parse_feature(param),
+            ParameterKind::Label => parse_single(&mut param, kind).map(|v| Parameter::Label {
+                value: v.value,
+                span: param.span,
+            }),
             // Preserve unknown parameter per RFC 5545 Section 3.2
             // TODO: emit warning for x-name / unrecognized iana-token parameter
             ParameterKind::XName(_) => Ok(Parameter::XName(param)),
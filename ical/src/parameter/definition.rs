@@ -6,16 +6,21 @@ use std::fmt;
 
 use crate::keyword::{
     KW_BINARY, KW_BOOLEAN, KW_CAL_ADDRESS, KW_CUTYPE_GROUP, KW_CUTYPE_INDIVIDUAL,
-    KW_CUTYPE_RESOURCE, KW_CUTYPE_ROOM, KW_CUTYPE_UNKNOWN, KW_DATE, KW_DATETIME, KW_DURATION,
-    KW_ENCODING_8BIT, KW_ENCODING_BASE64, KW_FBTYPE_BUSY, KW_FBTYPE_BUSY_TENTATIVE,
-    KW_FBTYPE_BUSY_UNAVAILABLE, KW_FBTYPE_FREE, KW_FLOAT, KW_INTEGER, KW_PARTSTAT_ACCEPTED,
-    KW_PARTSTAT_COMPLETED, KW_PARTSTAT_DECLINED, KW_PARTSTAT_DELEGATED, KW_PARTSTAT_IN_PROCESS,
-    KW_PARTSTAT_NEEDS_ACTION, KW_PARTSTAT_TENTATIVE, KW_PERIOD, KW_RANGE_THISANDFUTURE,
-    KW_RELATED_END, KW_RELATED_START, KW_RELTYPE_CHILD, KW_RELTYPE_PARENT, KW_RELTYPE_SIBLING,
-    KW_ROLE_CHAIR, KW_ROLE_NON_PARTICIPANT, KW_ROLE_OPT_PARTICIPANT, KW_ROLE_REQ_PARTICIPANT,
-    KW_RRULE, KW_RSVP_FALSE, KW_RSVP_TRUE, KW_TEXT, KW_TIME, KW_URI, KW_UTC_OFFSET,
+    KW_CUTYPE_RESOURCE, KW_CUTYPE_ROOM, KW_CUTYPE_UNKNOWN, KW_DATE, KW_DATETIME, KW_DISPLAY_BADGE,
+    KW_DISPLAY_FULLSIZE, KW_DISPLAY_GRAPHIC, KW_DISPLAY_THUMBNAIL, KW_DURATION, KW_ENCODING_8BIT,
+    KW_ENCODING_BASE64, KW_FBTYPE_BUSY, KW_FBTYPE_BUSY_TENTATIVE, KW_FBTYPE_BUSY_UNAVAILABLE,
+    KW_FBTYPE_FREE, KW_FEATURE_AUDIO, KW_FEATURE_CHAT, KW_FEATURE_FEED, KW_FEATURE_MODERATOR,
+    KW_FEATURE_PHONE, KW_FEATURE_SCREEN, KW_FEATURE_VIDEO, KW_FLOAT, KW_INTEGER,
+    KW_PARTSTAT_ACCEPTED, KW_PARTSTAT_COMPLETED, KW_PARTSTAT_DECLINED, KW_PARTSTAT_DELEGATED,
+    KW_PARTSTAT_IN_PROCESS, KW_PARTSTAT_NEEDS_ACTION, KW_PARTSTAT_TENTATIVE, KW_PERIOD,
+    KW_RANGE_THISANDFUTURE, KW_RELATED_END, KW_RELATED_START, KW_RELTYPE_CHILD, KW_RELTYPE_PARENT,
+    KW_RELTYPE_SIBLING, KW_ROLE_CHAIR, KW_ROLE_NON_PARTICIPANT, KW_ROLE_OPT_PARTICIPANT,
+    KW_ROLE_REQ_PARTICIPANT, KW_RRULE, KW_RSVP_FALSE, KW_RSVP_TRUE, KW_TEXT, KW_TIME, KW_URI,
+    KW_UTC_OFFSET,
+};
+use crate::parameter::util::{
+    ParseResult, parse_multiple_not_quoted, parse_single, parse_single_not_quoted,
 };
-use crate::parameter::util::{ParseResult, parse_single, parse_single_not_quoted};
 use crate::parameter::{Parameter, ParameterKind};
 use crate::string_storage::{Segments, StringStorage};
 use crate::syntax::{RawParameter, RawParameterValue};
@@ -202,6 +207,50 @@ define_param_enum_with_unknown! {
     parser = pub fn parse_role;
 }
 
+define_param_enum_with_unknown! {
+    /// This parameter specifies different ways in which an image for a calendar or component can
+    /// be displayed (RFC 7986 Section 6.1).
+    #[derive(Default)]
+    pub enum DisplayType {
+        /// Image inline with the title of the event
+        #[default]
+        Badge     => KW_DISPLAY_BADGE,
+        /// A full image replacement for the event itself
+        Graphic   => KW_DISPLAY_GRAPHIC,
+        /// An image that is used to enhance the event
+        Fullsize  => KW_DISPLAY_FULLSIZE,
+        /// A smaller variant of "FULLSIZE" to be used when space for the image is constrained
+        Thumbnail => KW_DISPLAY_THUMBNAIL,
+    }
+
+    parser = pub fn parse_display;
+    multiple;
+}
+
+define_param_enum_with_unknown! {
+    /// This parameter specifies a feature or features of a conference or broadcast system
+    /// (RFC 7986 Section 6.3).
+    pub enum FeatureType {
+        /// Audio capability
+        Audio     => KW_FEATURE_AUDIO,
+        /// Chat or instant messaging
+        Chat      => KW_FEATURE_CHAT,
+        /// Blog or Atom feed
+        Feed      => KW_FEATURE_FEED,
+        /// Moderator dial-in code
+        Moderator => KW_FEATURE_MODERATOR,
+        /// Phone conference
+        Phone     => KW_FEATURE_PHONE,
+        /// Screen sharing
+        Screen    => KW_FEATURE_SCREEN,
+        /// Video capability
+        Video     => KW_FEATURE_VIDEO,
+    }
+
+    parser = pub fn parse_feature;
+    multiple;
+}
+
 define_param_enum_with_unknown! {
     pub enum ValueType {
         Binary              => KW_BINARY,
@@ -3,9 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::keyword::{
-    KW_ALTREP, KW_CN, KW_CUTYPE, KW_DELEGATED_FROM, KW_DELEGATED_TO, KW_DIR, KW_ENCODING,
-    KW_FBTYPE, KW_FMTTYPE, KW_LANGUAGE, KW_MEMBER, KW_PARTSTAT, KW_RANGE, KW_RELATED, KW_RELTYPE,
-    KW_ROLE, KW_RSVP, KW_SENT_BY, KW_TZID, KW_VALUE,
+    KW_ALTREP, KW_CN, KW_CUTYPE, KW_DELEGATED_FROM, KW_DELEGATED_TO, KW_DIR, KW_DISPLAY, KW_EMAIL,
+    KW_ENCODING, KW_FBTYPE, KW_FEATURE, KW_FMTTYPE, KW_LABEL, KW_LANGUAGE, KW_MEMBER, KW_PARTSTAT,
+    KW_RANGE, KW_RELATED, KW_RELTYPE, KW_ROLE, KW_RSVP, KW_SENT_BY, KW_TZID, KW_VALUE,
 };
 use crate::string_storage::{Segments, StringStorage};
 
@@ -107,5 +107,9 @@ impl_typed_parameter_kind_mapping! {
         RsvpExpectation     => KW_RSVP,
         TimeZoneIdentifier  => KW_TZID,
         ValueType           => KW_VALUE,
+        DisplayType         => KW_DISPLAY,
+        Email               => KW_EMAIL,
+        FeatureType         => KW_FEATURE,
+        Label               => KW_LABEL,
     }
 }
@@ -25,7 +25,7 @@ pub fn parse_single<'src>(
     kind: ParameterKind<Segments<'src>>,
 ) -> Result<RawParameterValue<Segments<'src>>, Vec<TypedError<'src>>> {
     match param.values.len() {
-        1 => Ok(param.values.pop().unwrap()),
+        1 => Ok(param.values.pop().expect("length checked above")),
         _ => Err(vec![TypedError::ParameterMultipleValuesDisallowed {
             parameter: kind,
             span: param.span,
@@ -46,7 +46,7 @@ pub fn parse_single_quoted<'src>(
 ) -> Result<Segments<'src>, Vec<TypedError<'src>>> {
     match param.values.len() {
         1 => {
-            let v = param.values.pop().unwrap(); // SAFETY: length check
+            let v = param.values.pop().expect("length checked above");
             if v.quoted {
                 Ok(v.value)
             } else {
@@ -77,7 +77,7 @@ pub fn parse_single_not_quoted<'src>(
 ) -> Result<Segments<'src>, Vec<TypedError<'src>>> {
     match param.values.len() {
         1 => {
-            let v = param.values.pop().unwrap();
+            let v = param.values.pop().expect("length checked above");
             if v.quoted {
                 Err(vec![TypedError::ParameterValueMustNotBeQuoted {
                     parameter: kind,
@@ -192,12 +192,44 @@ macro_rules! define_param_enum {
     };
 }
 
+/// Parse multiple unquoted values from a parameter.
+///
+/// # Errors
+///
+/// Returns an error if any of the values are quoted.
+pub fn parse_multiple_not_quoted<'src>(
+    param: RawParameter<Segments<'src>>,
+    kind: &ParameterKind<Segments<'src>>,
+) -> Result<Vec<Segments<'src>>, Vec<TypedError<'src>>> {
+    let mut values = Vec::with_capacity(param.values.len());
+    let mut errors = Vec::new();
+    for v in param.values {
+        if v.quoted {
+            errors.push(TypedError::ParameterValueMustNotBeQuoted {
+                parameter: kind.clone(),
+                span: v.value.span(),
+                value: v.value,
+            });
+        } else {
+            values.push(v.value);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors)
+    }
+}
+
 /// Macro to define parameter enums with x-name and unrecognized value support.
 ///
 /// This generates enums with lifetime parameters for zero-copy storage of
-/// extension values per RFC 5545.
+/// extension values per RFC 5545. With `multiple;` the parameter takes a
+/// COMMA-separated list of values instead of a single one.
 macro_rules! define_param_enum_with_unknown {
     (
+        @enum
         $(#[$meta:meta])*
         $vis:vis enum $name:ident {
             $(
@@ -205,8 +237,6 @@ macro_rules! define_param_enum_with_unknown {
                 $variant:ident => $kw:ident
             ),* $(,)?
         }
-
-        parser = $pvis:vis fn $parse_fn:ident;
     ) => {
         $(#[$meta])*
         #[derive(Debug, Clone)]
@@ -264,6 +294,29 @@ macro_rules! define_param_enum_with_unknown {
                 }
             }
         }
+    };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident => $kw:ident
+            ),* $(,)?
+        }
+
+        parser = $pvis:vis fn $parse_fn:ident;
+    ) => {
+        define_param_enum_with_unknown! {
+            @enum
+            $(#[$meta])*
+            $vis enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant => $kw
+            ),*
+            }
+        }
 
         $pvis fn $parse_fn(mut param: crate::syntax::RawParameter<Segments<'_>>) -> ParseResult<'_> {
             parse_single_not_quoted(&mut param, crate::parameter::ParameterKind::$name).map(|value| {
@@ -276,6 +329,40 @@ macro_rules! define_param_enum_with_unknown {
         }
     };
 
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident => $kw:ident
+            ),* $(,)?
+        }
+
+        parser = $pvis:vis fn $parse_fn:ident;
+        multiple;
+    ) => {
+        define_param_enum_with_unknown! {
+            @enum
+            $(#[$meta])*
+            $vis enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant => $kw
+            ),*
+            }
+        }
+
+        $pvis fn $parse_fn(param: crate::syntax::RawParameter<Segments<'_>>) -> ParseResult<'_> {
+            let span = param.span;
+            parse_multiple_not_quoted(param, &crate::parameter::ParameterKind::$name).map(|values| {
+                Parameter::$name {
+                    values: values.into_iter().map($name::from).collect(),
+                    span,
+                }
+            })
+        }
+    };
+
     (
         $(#[$meta:meta])*
         $vis:vis enum $name:ident {
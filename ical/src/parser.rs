@@ -2,10 +2,22 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::semantic::{ICalendar, SemanticError, semantic_analysis, validate_tzids};
-use crate::string_storage::Segments;
-use crate::syntax::{ParseOptions, SyntaxError};
-use crate::typed::{TypedError, typed_analysis};
+use std::collections::BTreeMap;
+
+use crate::diagnostic::{Diagnostic, Severity, span};
+use crate::keyword::KW_UID;
+#[cfg(feature = "jiff")]
+use crate::semantic::check_tz_offsets;
+use crate::semantic::{
+    ICalendar, SemanticError, check_anchors, check_utc_tzids, semantic_analysis_with_duplicates,
+    validate_tzids,
+};
+use crate::string_storage::{Segments, Span};
+use crate::syntax::{
+    ParseOptions, RawComponent, Repair, SyntaxError, syntax_analysis_partial,
+    syntax_analysis_with_repairs,
+};
+use crate::typed::{TypedError, typed_analysis_with_limits};
 
 /// Parse an iCalendar component from source code
 ///
@@ -15,9 +27,17 @@ use crate::typed::{TypedError, typed_analysis};
 /// 3. Typed analysis
 /// 4. Semantic analysis
 ///
+/// The components borrow their strings from `src`. Consumers that only read them can do so with
+/// [`StringStorage::as_str_lossy`](crate::StringStorage::as_str_lossy) and
+/// [`ValueText::as_str_lossy`](crate::ValueText::as_str_lossy), which allocate only for folded
+/// or escaped values, instead of converting the components with `to_owned` first.
+///
 /// ## Errors
 ///
-/// If there are errors in any phase, a vector of error reports will be returned.
+/// If there are errors in any phase, a vector of error reports will be returned. A component of a
+/// calendar that fails semantic analysis is left out instead, as long as another one of the
+/// calendar parses; [`parse_with_diagnostics`] reports it as a warning, and [`parse_partial`]
+/// returns it as broken.
 ///
 /// ## Examples
 ///
@@ -39,6 +59,13 @@ use crate::typed::{TypedError, typed_analysis};
 /// ";
 /// let calendars = parse(ical_src).unwrap();
 /// assert_eq!(calendars[0].prod_id.value.to_string(), "-//Example Corp.//CalDAV Client//EN");
+///
+/// // Read the summary without copying it out of the source
+/// # use aimcal_ical::CalendarComponent;
+/// # use std::borrow::Cow;
+/// let CalendarComponent::Event(event) = &calendars[0].components[0] else { unreachable!() };
+/// let summary = event.summary.as_ref().unwrap().content.as_str_lossy();
+/// assert!(matches!(summary, Cow::Borrowed("Test Event")));
 /// ```
 ///
 /// Parsing invalid iCalendar source will return errors:
@@ -71,8 +98,9 @@ pub fn parse(src: &'_ str) -> Result<Vec<ICalendar<Segments<'_>>>, Vec<ParseErro
 /// 3. Typed analysis
 /// 4. Semantic analysis
 ///
-/// The `options` parameter controls syntax-level behavior such as strict
-/// CRLF line ending enforcement.
+/// The `options` parameter controls behavior such as strict CRLF line ending
+/// enforcement, whether properties occurring more often than allowed are
+/// rejected, and which malformations of other applications are repaired.
 ///
 /// ## Examples
 ///
@@ -89,18 +117,78 @@ pub fn parse_with_options(
     src: &str,
     options: ParseOptions,
 ) -> Result<Vec<ICalendar<Segments<'_>>>, Vec<ParseError<'_>>> {
+    parse_with_warnings(src, options, false).0
+}
+
+type ParseResult<'src> = Result<Vec<ICalendar<Segments<'src>>>, Vec<ParseError<'src>>>;
+
+/// Parses iCalendar source, returning the problems accepted by the options as warnings.
+///
+/// The errors of the components left out of a calendar are warnings as well, or errors with
+/// `strict_components`.
+fn parse_with_warnings(
+    src: &str,
+    options: ParseOptions,
+    strict_components: bool,
+) -> (ParseResult<'_>, Vec<ParseError<'_>>) {
     // Syntax analysis (includes tokenization, scanning, and tree building)
-    let syntax_components = crate::syntax::syntax_analysis_with_options(src, options)
-        .map_err(|errs| errs.into_iter().map(ParseError::Syntax).collect::<Vec<_>>())?;
+    let (syntax_components, repairs) = syntax_analysis_with_repairs(src, options);
+    let mut warnings: Vec<_> = repairs.into_iter().map(ParseError::Repaired).collect();
+    let syntax_components = match syntax_components {
+        Ok(components) => components,
+        Err(errs) => {
+            return (
+                Err(errs.into_iter().map(ParseError::Syntax).collect()),
+                warnings,
+            );
+        }
+    };
 
-    let typed_components = typed_analysis(syntax_components)
+    let result = analyze(syntax_components, options, strict_components, &mut warnings);
+    (result, warnings)
+}
+
+/// Runs the typed and semantic analysis of a component tree, adding the problems accepted by the
+/// options to `warnings`, and the errors of the components left out of a calendar too unless
+/// `strict_components`.
+fn analyze<'src>(
+    syntax_components: Vec<RawComponent<'src>>,
+    options: ParseOptions,
+    strict_components: bool,
+    warnings: &mut Vec<ParseError<'src>>,
+) -> ParseResult<'src> {
+    let typed_components = typed_analysis_with_limits(syntax_components, options.limits)
         .map_err(|errs| errs.into_iter().map(ParseError::Typed).collect::<Vec<_>>())?;
 
-    let mut icalendars = semantic_analysis(typed_components).map_err(|errs| {
-        errs.into_iter()
-            .map(ParseError::Semantic)
-            .collect::<Vec<_>>()
-    })?;
+    let mut semantic_warnings = Vec::new();
+    for component in &typed_components {
+        check_anchors(component, &mut semantic_warnings);
+        check_utc_tzids(component, &mut semantic_warnings);
+    }
+    warnings.extend(semantic_warnings.into_iter().map(ParseError::Semantic));
+
+    let (result, duplicates, dropped) = semantic_analysis_with_duplicates(typed_components);
+    let duplicates = duplicates.into_iter().map(ParseError::Semantic);
+    let dropped = dropped.into_iter().map(ParseError::Semantic);
+    let mut errors = Vec::new();
+    if options.strict_cardinality {
+        errors.extend(duplicates);
+    } else {
+        warnings.extend(duplicates);
+    }
+    if strict_components {
+        errors.extend(dropped);
+    } else {
+        warnings.extend(dropped);
+    }
+    let mut icalendars = match result {
+        Ok(_) if !errors.is_empty() => return Err(errors),
+        Ok(icalendars) => icalendars,
+        Err(errs) => {
+            errors.extend(errs.into_iter().map(ParseError::Semantic));
+            return Err(errors);
+        }
+    };
 
     // Post-semantic: validate TZIDs against VTIMEZONE components and IANA database
     let mut all_tz_errors = Vec::new();
@@ -114,9 +202,275 @@ pub fn parse_with_options(
         return Err(all_tz_errors);
     }
 
+    #[cfg(feature = "jiff")]
+    {
+        let mut mismatches = Vec::new();
+        for calendar in &icalendars {
+            check_tz_offsets(calendar, &mut mismatches);
+        }
+        warnings.extend(mismatches.into_iter().map(ParseError::Semantic));
+    }
+
     Ok(icalendars)
 }
 
+/// Parse iCalendar source and report problems as [`Diagnostic`]s
+///
+/// This is [`parse`] for editors and other tools: instead of error values, it
+/// returns structured diagnostics with stable codes, related spans and fix-its.
+/// The calendars are `None` if any error was found.
+///
+/// ## Examples
+///
+/// ```
+/// # use aimcal_ical::parse_with_diagnostics;
+/// let ical_src = "\
+/// BEGIN:VCALENDAR\r\n\
+/// BEGIN:VEVENT\r\n\
+/// END:VCALENDAR\r\n\
+/// END:VEVENT\r\n\
+/// ";
+/// let (calendars, diagnostics) = parse_with_diagnostics(ical_src);
+/// assert!(calendars.is_none());
+/// assert_eq!(diagnostics[0].code, "mismatched-nesting");
+/// ```
+#[must_use]
+pub fn parse_with_diagnostics(
+    src: &str,
+) -> (Option<Vec<ICalendar<Segments<'_>>>>, Vec<Diagnostic>) {
+    parse_with_diagnostics_and_options(src, ParseOptions::default())
+}
+
+/// Parse iCalendar source with custom options and report problems as [`Diagnostic`]s
+///
+/// This is [`parse_with_diagnostics`] with the behavior of [`parse_with_options`].
+/// The problems accepted by the options, such as duplicated properties when
+/// [`ParseOptions::strict_cardinality`] is `false` and the malformations
+/// repaired by [`ParseOptions::compat`], are reported as warnings, as are the
+/// errors of the components left out of a calendar.
+///
+/// ## Examples
+///
+/// ```
+/// # use aimcal_ical::{ParseOptions, Severity, parse_with_diagnostics_and_options};
+/// let ical_src = "\
+/// BEGIN:VCALENDAR\r\n\
+/// VERSION:2.0\r\n\
+/// PRODID:-//Example Corp.//CalDAV Client//EN\r\n\
+/// BEGIN:VTODO\r\n\
+/// UID:12345\r\n\
+/// DTSTAMP:20250101T000000Z\r\n\
+/// SUMMARY:First\r\n\
+/// SUMMARY:Second\r\n\
+/// END:VTODO\r\n\
+/// END:VCALENDAR\r\n\
+/// ";
+/// let opts = ParseOptions::new().strict_cardinality(false);
+/// let (calendars, diagnostics) = parse_with_diagnostics_and_options(ical_src, opts);
+/// assert!(calendars.is_some());
+/// assert_eq!(diagnostics[0].code, "duplicate-property");
+/// assert_eq!(diagnostics[0].severity, Severity::Warning);
+/// ```
+#[must_use]
+pub fn parse_with_diagnostics_and_options(
+    src: &str,
+    options: ParseOptions,
+) -> (Option<Vec<ICalendar<Segments<'_>>>>, Vec<Diagnostic>) {
+    let (result, warnings) = parse_with_warnings(src, options, false);
+    let warnings = warnings.iter().map(|warning| {
+        let mut diagnostic = Diagnostic::from_parse_error(src, warning);
+        diagnostic.severity = Severity::Warning;
+        diagnostic
+    });
+    match result {
+        Ok(calendars) => (Some(calendars), warnings.collect()),
+        Err(errors) => {
+            let diagnostics = errors
+                .iter()
+                .map(|err| Diagnostic::from_parse_error(src, err))
+                .chain(warnings)
+                .collect();
+            (None, diagnostics)
+        }
+    }
+}
+
+/// Parse iCalendar source, leaving out the components with errors instead of failing
+///
+/// This is [`parse_with_options`] for sources with many components, such as
+/// calendar exports, where one broken event should not fail the others. An error
+/// found in a top-level component of a calendar, such as a `VEVENT` or `VTODO`,
+/// drops that component, which is returned in [`PartialParse::broken`] with its
+/// errors and its source as it is. The problems accepted by the options are
+/// returned as warnings, as by [`parse_with_diagnostics_and_options`].
+///
+/// ## Errors
+///
+/// Returns the errors of [`parse_with_options`] if the errors cannot be
+/// isolated to a component, such as mismatched BEGIN and END lines or errors in
+/// the properties of a calendar itself.
+///
+/// ## Examples
+///
+/// ```
+/// # use aimcal_ical::{ParseOptions, parse_partial};
+/// let ical_src = "\
+/// BEGIN:VCALENDAR\r\n\
+/// VERSION:2.0\r\n\
+/// PRODID:-//Example Corp.//CalDAV Client//EN\r\n\
+/// BEGIN:VEVENT\r\n\
+/// UID:broken\r\n\
+/// DTSTAMP:20250101T000000Z\r\n\
+/// DTSTART:tomorrow\r\n\
+/// END:VEVENT\r\n\
+/// BEGIN:VEVENT\r\n\
+/// UID:valid\r\n\
+/// DTSTAMP:20250101T000000Z\r\n\
+/// DTSTART:20250101T100000Z\r\n\
+/// END:VEVENT\r\n\
+/// END:VCALENDAR\r\n\
+/// ";
+/// let parsed = parse_partial(ical_src, ParseOptions::default()).unwrap();
+/// assert_eq!(parsed.calendars[0].components.len(), 1);
+/// assert_eq!(parsed.broken[0].uid.as_deref(), Some("broken"));
+/// assert!(parsed.broken[0].source.starts_with("BEGIN:VEVENT"));
+/// ```
+pub fn parse_partial(
+    src: &str,
+    options: ParseOptions,
+) -> Result<PartialParse<'_>, Vec<ParseError<'_>>> {
+    let (result, warnings) = parse_with_warnings(src, options, true);
+    let errors = match result {
+        Ok(calendars) => {
+            return Ok(PartialParse {
+                calendars,
+                broken: Vec::new(),
+                warnings,
+            });
+        }
+        Err(errors) => errors,
+    };
+
+    // Without a sound nesting, the errors cannot be told apart by component
+    let partial = syntax_analysis_partial(src, options);
+    if !partial.tree_errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut roots = partial.roots;
+    let mut broken = Vec::new();
+    let mut found: Vec<_> = partial
+        .line_errors
+        .into_iter()
+        .map(ParseError::Syntax)
+        .collect();
+    loop {
+        if found.is_empty() {
+            let mut warnings = partial
+                .repairs
+                .iter()
+                .cloned()
+                .map(ParseError::Repaired)
+                .collect();
+            match analyze(roots.clone(), options, true, &mut warnings) {
+                Ok(calendars) => {
+                    broken.sort_by_key(|component: &BrokenComponent<'_>| component.span.start);
+                    return Ok(PartialParse {
+                        calendars,
+                        broken,
+                        warnings,
+                    });
+                }
+                Err(errs) => found = errs,
+            }
+        }
+        if !isolate(src, &mut roots, std::mem::take(&mut found), &mut broken) {
+            return Err(errors);
+        }
+    }
+}
+
+/// Moves the top-level components of the calendars that `errors` are found in to `broken`.
+///
+/// Returns false if an error is found outside of them, or there is no error to isolate.
+fn isolate<'src>(
+    src: &'src str,
+    roots: &mut [RawComponent<'src>],
+    errors: Vec<ParseError<'src>>,
+    broken: &mut Vec<BrokenComponent<'src>>,
+) -> bool {
+    let mut by_component: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for err in errors {
+        let span = span(&err);
+        let found = roots.iter().enumerate().find_map(|(i, root)| {
+            root.children
+                .iter()
+                .position(|child| child.extent.start <= span.start && span.end <= child.extent.end)
+                .map(|j| (i, j))
+        });
+        let Some(at) = found else {
+            return false;
+        };
+        by_component.entry(at).or_default().push(err);
+    }
+
+    let isolated = !by_component.is_empty();
+    // Remove from the back, so that the positions of the others stay the same
+    for ((i, j), errors) in by_component.into_iter().rev() {
+        if let Some(root) = roots.get_mut(i)
+            && j < root.children.len()
+        {
+            let component = root.children.remove(j);
+            broken.push(BrokenComponent::new(src, &component, errors));
+        }
+    }
+    isolated
+}
+
+/// Calendars parsed by [`parse_partial`], without their broken components
+#[derive(Debug, Clone)]
+pub struct PartialParse<'src> {
+    /// Calendars parsed, without the broken components
+    pub calendars: Vec<ICalendar<Segments<'src>>>,
+    /// Components left out of the calendars for their errors, in source order
+    pub broken: Vec<BrokenComponent<'src>>,
+    /// Problems accepted by the options, such as the malformations repaired by
+    /// [`ParseOptions::compat`]
+    pub warnings: Vec<ParseError<'src>>,
+}
+
+/// A top-level component of a calendar left out for its errors
+#[derive(Debug, Clone)]
+pub struct BrokenComponent<'src> {
+    /// Component name (e.g., "VEVENT", "VTODO")
+    pub name: String,
+    /// UID of the component, if it could be read
+    pub uid: Option<String>,
+    /// Span of the entire component (from BEGIN to END)
+    pub span: Span,
+    /// Source of the component as it is, to be kept or repaired by hand
+    pub source: &'src str,
+    /// Errors found in the component
+    pub errors: Vec<ParseError<'src>>,
+}
+
+impl<'src> BrokenComponent<'src> {
+    fn new(src: &'src str, component: &RawComponent<'src>, errors: Vec<ParseError<'src>>) -> Self {
+        let uid = component
+            .properties
+            .iter()
+            .find(|prop| prop.name.eq_str_ignore_ascii_case(KW_UID))
+            .map(|prop| prop.value.to_owned());
+        Self {
+            name: component.name.to_owned(),
+            uid,
+            span: component.extent,
+            source: src.get(component.extent.into_range()).unwrap_or_default(),
+            errors,
+        }
+    }
+}
+
 /// Errors that can occur during parsing
 // TODO: generic over error type, support different error types
 #[non_exhaustive]
@@ -133,4 +487,8 @@ pub enum ParseError<'src> {
     /// Errors from semantic analysis
     #[error("{0}")]
     Semantic(SemanticError<'src>),
+
+    /// Malformations repaired in compatibility mode, only ever reported as warnings
+    #[error("{0}")]
+    Repaired(Repair),
 }
@@ -10,7 +10,7 @@
 //!
 //! ## Property Organization
 //!
-//! - 3.7. Calendar Properties (calendar.rs)
+//! - 3.7. Calendar Properties (calendar.rs), with the properties of RFC 7986 Section 5
 //! - 3.8.1. Descriptive Component Properties (descriptive.rs)
 //! - 3.8.2. Date and Time Properties (datetime.rs)
 //! - 3.8.3. Time Zone Component Properties (timezone.rs)
@@ -20,6 +20,8 @@
 //! - 3.8.7. Change Management Component Properties (changemgmt.rs)
 //! - 3.8.8. Miscellaneous Properties (miscellaneous.rs)
 //!
+//! The busy time type of RFC 7953 lives with the date and time properties.
+//!
 //! ## Type Safety
 //!
 //! All property types implement kind validation through:
@@ -48,13 +50,15 @@ mod timezone;
 
 pub use alarm::{Action, ActionValue, Repeat, Trigger, TriggerValue};
 pub use calendar::{
-    CalendarScale, CalendarScaleValue, Method, MethodValue, ProductId, Version, VersionValue,
+    CalendarScale, CalendarScaleValue, Color, Conference, Image, Method, MethodValue, Name,
+    ProductId, RefreshInterval, Source, Version, VersionValue,
 };
 pub use changemgmt::{Created, DtStamp, LastModified, Sequence};
 pub use common::{Text, TextOnly, TextWithLanguage, UriProperty};
 pub use datetime::{
-    Completed, Date, DateTime, DateTimeProperty, DateTimeUtc, DtEnd, DtStart, Due, Duration,
-    FreeBusy, Period, Time, TimeTransparency, TimeTransparencyValue,
+    BusyType, BusyTypeValue, Completed, Date, DateTime, DateTimeProperty, DateTimeUtc, DtEnd,
+    DtStart, Due, Duration, FreeBusy, Period, Time, TimeTransparency, TimeTransparencyValue,
+    ValueForm,
 };
 pub use descriptive::{
     Attachment, AttachmentValue, Categories, Classification, ClassificationValue, Comment,
@@ -64,7 +68,7 @@ pub use kind::PropertyKind;
 pub use miscellaneous::RequestStatus;
 pub use recurrence::{ExDate, RDate, RDateValue, RRule};
 pub use relationship::{Attendee, Contact, Organizer, RecurrenceId, RelatedTo, Uid, Url};
-pub use timezone::{TzId, TzName, TzOffsetFrom, TzOffsetTo, TzUrl};
+pub use timezone::{TzId, TzName, TzOffsetFrom, TzOffsetTo, TzUrl, UtcOffsetProperty};
 
 use crate::parameter::Parameter;
 use crate::string_storage::{Segments, StringStorage};
@@ -234,6 +238,28 @@ pub enum Property<S: StringStorage> {
     /// 3.8.8.3 Request Status
     RequestStatus(RequestStatus<S>),
 
+    // RFC 7986 Section 5 - Calendar Properties
+    /// RFC 7986 5.1 Name
+    Name(Name<S>),
+
+    /// RFC 7986 5.7 Refresh Interval
+    RefreshInterval(RefreshInterval<S>),
+
+    /// RFC 7986 5.8 Source
+    Source(Source<S>),
+
+    /// RFC 7986 5.9 Color
+    Color(Color<S>),
+
+    /// RFC 7986 5.10 Image
+    Image(Image<S>),
+
+    /// RFC 7986 5.11 Conference
+    Conference(Conference<S>),
+
+    /// RFC 7953 3.2 Busy Time Type
+    BusyType(BusyType<S>),
+
     /// Custom experimental x-name property (must start with "X-" or "x-").
     ///
     /// Per RFC 5545: All property names and parameter names are case-insensitive.
@@ -321,6 +347,15 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Property<Segments<'src>> {
             // Section 3.8.8 - Miscellaneous Properties
             PropertyKind::RequestStatus => prop.try_into().map(Property::RequestStatus),
 
+            // RFC 7986 Section 5 - Calendar Properties
+            PropertyKind::Name          => prop.try_into().map(Property::Name),
+            PropertyKind::RefreshInterval => prop.try_into().map(Property::RefreshInterval),
+            PropertyKind::Source        => prop.try_into().map(Property::Source),
+            PropertyKind::Color         => prop.try_into().map(Property::Color),
+            PropertyKind::Image         => prop.try_into().map(Property::Image),
+            PropertyKind::Conference    => prop.try_into().map(Property::Conference),
+            PropertyKind::BusyType      => prop.try_into().map(Property::BusyType),
+
             // XName properties (experimental x-name properties)
             PropertyKind::XName(_)      => Ok(Property::XName(prop.into())),
 
@@ -399,6 +434,15 @@ impl<S: StringStorage> Property<S> {
             // Section 3.8.8 - Miscellaneous Properties
             Self::RequestStatus(_) => PropertyKind::RequestStatus,
 
+            // RFC 7986 Section 5 - Calendar Properties
+            Self::Name(_) => PropertyKind::Name,
+            Self::RefreshInterval(_) => PropertyKind::RefreshInterval,
+            Self::Source(_) => PropertyKind::Source,
+            Self::Color(_) => PropertyKind::Color,
+            Self::Image(_) => PropertyKind::Image,
+            Self::Conference(_) => PropertyKind::Conference,
+            Self::BusyType(_) => PropertyKind::BusyType,
+
             // XName and unknown properties
             Self::XName(v) => PropertyKind::XName(&v.name),
             Self::Unrecognized(v) => PropertyKind::Unrecognized(&v.name),
@@ -473,6 +517,15 @@ impl<S: StringStorage> Property<S> {
             // Section 3.8.8 - Miscellaneous Properties
             Self::RequestStatus(v) => v.span(),
 
+            // RFC 7986 Section 5 - Calendar Properties
+            Self::Name(v) => v.span(),
+            Self::RefreshInterval(v) => v.span(),
+            Self::Source(v) => v.span(),
+            Self::Color(v) => v.span(),
+            Self::Image(v) => v.span(),
+            Self::Conference(v) => v.span(),
+            Self::BusyType(v) => v.span(),
+
             // XName and unknown properties
             Self::XName(v) => v.span(),
             Self::Unrecognized(v) => v.span(),
@@ -549,6 +602,15 @@ impl Property<Segments<'_>> {
             // Section 3.8.8 - Miscellaneous Properties
             Property::RequestStatus(v) => Property::RequestStatus(v.to_owned()),
 
+            // RFC 7986 Section 5 - Calendar Properties
+            Property::Name(v) => Property::Name(v.to_owned()),
+            Property::RefreshInterval(v) => Property::RefreshInterval(v.to_owned()),
+            Property::Source(v) => Property::Source(v.to_owned()),
+            Property::Color(v) => Property::Color(v.to_owned()),
+            Property::Image(v) => Property::Image(v.to_owned()),
+            Property::Conference(v) => Property::Conference(v.to_owned()),
+            Property::BusyType(v) => Property::BusyType(v.to_owned()),
+
             // XName and Unknown properties
             Property::XName(v) => Property::XName(v.to_owned()),
             Property::Unrecognized(v) => Property::Unrecognized(v.to_owned()),
@@ -203,7 +203,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Repeat<Segments<'src>> {
             Value::Integer {
                 values: mut ints, ..
             } if ints.len() == 1 => {
-                let i = ints.pop().unwrap();
+                let i = ints.pop().expect("length checked above");
                 if i >= 0 {
                     #[expect(clippy::cast_sign_loss)]
                     Ok(Repeat {
@@ -342,14 +342,16 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Trigger<Segments<'src>> {
 
         match value {
             Value::Duration { values: durs, .. } if durs.len() == 1 => Ok(Trigger {
-                value: TriggerValue::Duration(durs.into_iter().next().unwrap()),
+                value: TriggerValue::Duration(
+                    durs.into_iter().next().expect("length checked above"),
+                ),
                 related: Some(related.unwrap_or(AlarmTriggerRelationship::Start)),
                 x_parameters,
                 retained_parameters,
                 span: prop.span,
             }),
             Value::DateTime { values: dts, .. } if dts.len() == 1 => {
-                let dt = dts.into_iter().next().unwrap();
+                let dt = dts.into_iter().next().expect("length checked above");
                 let datetime = if dt.time.utc {
                     DateTime::Utc {
                         date: dt.date,
@@ -12,6 +12,19 @@
 //! - 3.7.2: `Method` - iTIP method (PUBLISH, REQUEST, etc.)
 //! - 3.7.3: `ProductId` - Product identifier (vendor/product info)
 //! - 3.7.4: `Version` - iCalendar version (2.0)
+//!
+//! It also holds the new calendar properties of RFC 7986 Section 5:
+//!
+//! - 5.1: `Name` - Display name of the calendar
+//! - 5.7: `RefreshInterval` - How often a subscribed calendar should be polled
+//! - 5.8: `Source` - Where the calendar can be refreshed from
+//! - 5.9: `Color` - Color used for displaying the calendar (CSS3 color name)
+//! - 5.10: `Image` - Image of the calendar or a component
+//! - 5.11: `Conference` - Conference system to join a component
+//!
+//! `COLOR`, `IMAGE` and `CONFERENCE` can also be set on events, to-dos and journals.
+//!
+//! RFC 7986 5.2 `DESCRIPTION` reuses the `Description` type of RFC 5545.
 
 use std::convert::TryFrom;
 
@@ -20,13 +33,15 @@ use crate::keyword::{
     KW_METHOD_DECLINECOUNTER, KW_METHOD_PUBLISH, KW_METHOD_REFRESH, KW_METHOD_REPLY,
     KW_METHOD_REQUEST, KW_VERSION_2_0,
 };
-use crate::parameter::Parameter;
-use crate::property::PropertyKind;
-use crate::property::common::take_single_text;
+use crate::parameter::{DisplayType, Encoding, FeatureType, Parameter, ValueType};
+use crate::property::common::{
+    Text, TextOnly, UriProperty, take_single_text, take_single_uri, take_single_value,
+};
+use crate::property::{AttachmentValue, PropertyKind};
 use crate::string_storage::{Segments, StringStorage};
 use crate::syntax::RawParameter;
 use crate::typed::{ParsedProperty, TypedError};
-use crate::value::ValueText;
+use crate::value::{Value, ValueDuration, ValueText};
 
 define_prop_value_enum! {
     /// Calendar scale value (RFC 5545 Section 3.7.1)
@@ -411,3 +426,423 @@ impl<S: StringStorage> Version<S> {
         self.span
     }
 }
+
+simple_property_wrapper!(
+    /// Display name of the calendar (RFC 7986 Section 5.1)
+    ///
+    /// May occur several times, once per `LANGUAGE`.
+    pub Name<S> => Text
+);
+
+/// Refresh interval of a subscribed calendar (RFC 7986 Section 5.7)
+///
+/// This property specifies a suggested minimum interval for polling for
+/// changes of the calendar data from the original source.
+#[derive(Debug, Clone)]
+pub struct RefreshInterval<S: StringStorage> {
+    /// Refresh interval value
+    pub value: ValueDuration,
+    /// X-name parameters (custom experimental parameters)
+    pub x_parameters: Vec<RawParameter<S>>,
+    /// Unrecognized / Non-standard parameters (preserved for round-trip)
+    pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<'src> TryFrom<ParsedProperty<'src>> for RefreshInterval<Segments<'src>> {
+    type Error = Vec<TypedError<'src>>;
+
+    fn try_from(prop: ParsedProperty<'src>) -> Result<Self, Self::Error> {
+        if !matches!(prop.kind, PropertyKind::RefreshInterval) {
+            return Err(vec![TypedError::PropertyUnexpectedKind {
+                expected: PropertyKind::RefreshInterval,
+                found: prop.kind,
+                span: prop.span,
+            }]);
+        }
+
+        let mut x_parameters = Vec::new();
+        let mut retained_parameters = Vec::new();
+
+        for param in prop.parameters {
+            match param {
+                Parameter::XName(raw) => x_parameters.push(raw),
+                p @ Parameter::Unrecognized { .. } => retained_parameters.push(p),
+                p => {
+                    // Preserve other parameters not used by this property for round-trip
+                    retained_parameters.push(p);
+                }
+            }
+        }
+
+        match take_single_value(&PropertyKind::RefreshInterval, prop.value) {
+            Ok(Value::Duration { values, .. }) if values.is_empty() => {
+                Err(vec![TypedError::PropertyMissingValue {
+                    property: prop.kind,
+                    span: prop.span,
+                }])
+            }
+            Ok(Value::Duration { values, .. }) if values.len() != 1 => {
+                Err(vec![TypedError::PropertyInvalidValueCount {
+                    property: prop.kind,
+                    expected: 1,
+                    found: values.len(),
+                    span: prop.span,
+                }])
+            }
+            Ok(Value::Duration { mut values, .. }) => Ok(Self {
+                value: values.pop().expect("single value checked above"),
+                x_parameters,
+                retained_parameters,
+                span: prop.span,
+            }),
+            Ok(v) => {
+                const EXPECTED: &[ValueType<String>] = &[ValueType::Duration];
+                let span = v.span();
+                Err(vec![TypedError::PropertyUnexpectedValue {
+                    property: prop.kind,
+                    expected: EXPECTED,
+                    found: v.kind().into(),
+                    span,
+                }])
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl RefreshInterval<Segments<'_>> {
+    /// Convert borrowed `RefreshInterval` to owned `RefreshInterval`
+    #[must_use]
+    pub fn to_owned(&self) -> RefreshInterval<String> {
+        RefreshInterval {
+            value: self.value,
+            x_parameters: self
+                .x_parameters
+                .iter()
+                .map(RawParameter::to_owned)
+                .collect(),
+            retained_parameters: self
+                .retained_parameters
+                .iter()
+                .map(Parameter::to_owned)
+                .collect(),
+            span: (),
+        }
+    }
+}
+
+impl<S: StringStorage> RefreshInterval<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
+}
+
+simple_property_wrapper!(
+    /// URI property wrapper (RFC 7986 Section 5.8)
+    pub Source<S> => UriProperty
+);
+
+simple_property_wrapper!(
+    /// Color property wrapper (RFC 7986 Section 5.9)
+    ///
+    /// The value is a CSS3 color name, such as `turquoise`.
+    pub Color<S> => TextOnly
+);
+
+/// Image associated with a calendar or component (RFC 7986 Section 5.10)
+///
+/// The image is given by a URI or inline as binary data, and the `DISPLAY`
+/// parameter says how it is meant to be shown.
+#[derive(Debug, Clone)]
+pub struct Image<S: StringStorage> {
+    /// URI or binary data
+    pub value: AttachmentValue<S>,
+    /// Format type (optional)
+    pub fmt_type: Option<S>,
+    /// Encoding (optional)
+    pub encoding: Option<Encoding>,
+    /// Alternate text representation URI (optional)
+    pub altrep: Option<S>,
+    /// Ways the image can be displayed, BADGE when empty
+    pub display: Vec<DisplayType<S>>,
+    /// X-name parameters (custom experimental parameters)
+    pub x_parameters: Vec<RawParameter<S>>,
+    /// Unrecognized / Non-standard parameters (preserved for round-trip)
+    pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<'src> TryFrom<ParsedProperty<'src>> for Image<Segments<'src>> {
+    type Error = Vec<TypedError<'src>>;
+
+    fn try_from(prop: ParsedProperty<'src>) -> Result<Self, Self::Error> {
+        if !matches!(prop.kind, PropertyKind::Image) {
+            return Err(vec![TypedError::PropertyUnexpectedKind {
+                expected: PropertyKind::Image,
+                found: prop.kind,
+                span: prop.span,
+            }]);
+        }
+
+        let mut errors = Vec::new();
+
+        // Collect all optional parameters in a single pass
+        let mut fmt_type = None;
+        let mut encoding = None;
+        let mut altrep = None;
+        let mut display = None;
+        let mut x_parameters = Vec::new();
+        let mut retained_parameters = Vec::new();
+
+        for param in prop.parameters {
+            match param {
+                p @ Parameter::FormatType { .. } if fmt_type.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::FormatType { value, .. } => fmt_type = Some(value),
+
+                p @ Parameter::Encoding { .. } if encoding.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::Encoding { value, .. } => encoding = Some(value),
+
+                p @ Parameter::AlternateText { .. } if altrep.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::AlternateText { value, .. } => altrep = Some(value),
+
+                p @ Parameter::DisplayType { .. } if display.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::DisplayType { values, .. } => display = Some(values),
+
+                Parameter::XName(raw) => x_parameters.push(raw),
+                p @ Parameter::Unrecognized { .. } => retained_parameters.push(p),
+                p => {
+                    // Preserve other parameters not used by this property for round-trip
+                    retained_parameters.push(p);
+                }
+            }
+        }
+
+        // Get value
+        let value = match take_single_value(&PropertyKind::Image, prop.value) {
+            Ok(Value::Binary { value, .. }) => Some(AttachmentValue::Binary(value)),
+            Ok(Value::Uri { value, .. }) => Some(AttachmentValue::Uri(value)),
+            Ok(v) => {
+                const EXPECTED: &[ValueType<String>] = &[ValueType::Uri, ValueType::Binary];
+                errors.push(TypedError::PropertyUnexpectedValue {
+                    property: prop.kind,
+                    expected: EXPECTED,
+                    found: v.kind().into(),
+                    span: v.span(),
+                });
+                None
+            }
+            Err(e) => {
+                errors.extend(e);
+                None
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Image {
+            value: value.expect("checked errors above"),
+            fmt_type,
+            encoding,
+            altrep,
+            display: display.unwrap_or_default(),
+            x_parameters,
+            retained_parameters,
+            span: prop.span,
+        })
+    }
+}
+
+impl Image<Segments<'_>> {
+    /// Convert borrowed `Image` to owned `Image`
+    #[must_use]
+    pub fn to_owned(&self) -> Image<String> {
+        Image {
+            value: self.value.to_owned(),
+            fmt_type: self.fmt_type.as_ref().map(Segments::to_owned),
+            encoding: self.encoding,
+            altrep: self.altrep.as_ref().map(Segments::to_owned),
+            display: self.display.iter().map(DisplayType::to_owned).collect(),
+            x_parameters: self
+                .x_parameters
+                .iter()
+                .map(RawParameter::to_owned)
+                .collect(),
+            retained_parameters: self
+                .retained_parameters
+                .iter()
+                .map(Parameter::to_owned)
+                .collect(),
+            span: (),
+        }
+    }
+}
+
+impl<S: StringStorage> Image<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
+}
+
+/// Conference system to join a calendar component (RFC 7986 Section 5.11)
+///
+/// Such as the URI of a video call or the dial-in number of a phone
+/// conference, with the `FEATURE` parameter telling them apart and `LABEL`
+/// describing them to the user.
+#[derive(Debug, Clone)]
+pub struct Conference<S: StringStorage> {
+    /// URI to join the conference
+    pub uri: S,
+    /// Features of the conference system, such as AUDIO and VIDEO
+    pub features: Vec<FeatureType<S>>,
+    /// Human-readable label, such as a dial-in code (optional)
+    pub label: Option<S>,
+    /// Language of the label (optional)
+    pub language: Option<S>,
+    /// X-name parameters (custom experimental parameters)
+    pub x_parameters: Vec<RawParameter<S>>,
+    /// Unrecognized / Non-standard parameters (preserved for round-trip)
+    pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<'src> TryFrom<ParsedProperty<'src>> for Conference<Segments<'src>> {
+    type Error = Vec<TypedError<'src>>;
+
+    fn try_from(prop: ParsedProperty<'src>) -> Result<Self, Self::Error> {
+        if !matches!(prop.kind, PropertyKind::Conference) {
+            return Err(vec![TypedError::PropertyUnexpectedKind {
+                expected: PropertyKind::Conference,
+                found: prop.kind,
+                span: prop.span,
+            }]);
+        }
+
+        let mut errors = Vec::new();
+
+        // Collect all optional parameters in a single pass
+        let mut features = None;
+        let mut label = None;
+        let mut language = None;
+        let mut x_parameters = Vec::new();
+        let mut retained_parameters = Vec::new();
+
+        for param in prop.parameters {
+            match param {
+                p @ Parameter::FeatureType { .. } if features.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::FeatureType { values, .. } => features = Some(values),
+
+                p @ Parameter::Label { .. } if label.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::Label { value, .. } => label = Some(value),
+
+                p @ Parameter::Language { .. } if language.is_some() => {
+                    errors.push(TypedError::ParameterDuplicated {
+                        span: p.span(),
+                        parameter: p.kind().into(),
+                    });
+                }
+                Parameter::Language { value, .. } => language = Some(value),
+
+                Parameter::XName(raw) => x_parameters.push(raw),
+                p @ Parameter::Unrecognized { .. } => retained_parameters.push(p),
+                p => {
+                    // Preserve other parameters not used by this property for round-trip
+                    retained_parameters.push(p);
+                }
+            }
+        }
+
+        let uri = match take_single_uri(&PropertyKind::Conference, prop.value) {
+            Ok(uri) => Some(uri),
+            Err(e) => {
+                errors.extend(e);
+                None
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Conference {
+            uri: uri.expect("checked errors above"),
+            features: features.unwrap_or_default(),
+            label,
+            language,
+            x_parameters,
+            retained_parameters,
+            span: prop.span,
+        })
+    }
+}
+
+impl Conference<Segments<'_>> {
+    /// Convert borrowed `Conference` to owned `Conference`
+    #[must_use]
+    pub fn to_owned(&self) -> Conference<String> {
+        Conference {
+            uri: self.uri.to_owned(),
+            features: self.features.iter().map(FeatureType::to_owned).collect(),
+            label: self.label.as_ref().map(Segments::to_owned),
+            language: self.language.as_ref().map(Segments::to_owned),
+            x_parameters: self
+                .x_parameters
+                .iter()
+                .map(RawParameter::to_owned)
+                .collect(),
+            retained_parameters: self
+                .retained_parameters
+                .iter()
+                .map(Parameter::to_owned)
+                .collect(),
+            span: (),
+        }
+    }
+}
+
+impl<S: StringStorage> Conference<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
+}
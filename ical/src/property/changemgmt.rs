@@ -98,7 +98,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Sequence<Segments<'src>> {
                     }]);
                 }
 
-                let value = ints.pop().unwrap(); // SAFETY: checked length above
+                let value = ints.pop().expect("checked length above");
                 if value < 0 {
                     return Err(vec![TypedError::PropertyInvalidValue {
                         property: PropertyKind::Sequence,
@@ -78,7 +78,9 @@ pub fn take_single_text<'src>(
     let value = take_single_value(kind, value)?;
 
     match value {
-        Value::Text { mut values, .. } if values.len() == 1 => Ok(values.pop().unwrap()),
+        Value::Text { mut values, .. } if values.len() == 1 => {
+            Ok(values.pop().expect("length checked above"))
+        }
         Value::Text { ref values, .. } => {
             let span = value.span();
             Err(vec![TypedError::PropertyInvalidValueCount {
@@ -647,6 +649,10 @@ macro_rules! define_prop_value_enum {
             )*
         }
 
+        impl $Name {
+            /// All keywords of this value, in declaration order.
+            pub const KEYWORDS: &[&str] = &[$($kw),*];
+        }
 
         impl<'src> TryFrom<crate::value::ValueText<Segments<'src>>> for $Name {
             type Error = crate::value::ValueText<Segments<'src>>;
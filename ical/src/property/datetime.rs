@@ -26,13 +26,18 @@
 //! - 3.8.2.5: `Duration` - Duration of time
 //! - 3.8.2.6: `FreeBusy` - Free/busy time information
 //! - 3.8.2.7: `TimeTransparency` - Time transparency (OPAQUE/TRANSPARENT)
+//! - RFC 7953 3.2: `BusyType` - Busy time type of unavailable time
 //!
 //! All wrapper types validate their property kind during conversion from
 //! `ParsedProperty`, ensuring type safety throughout the parsing pipeline.
 
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
 
-use crate::keyword::{KW_TRANSP_OPAQUE, KW_TRANSP_TRANSPARENT};
+use crate::keyword::{
+    KW_BUSYTYPE_BUSY, KW_BUSYTYPE_BUSY_TENTATIVE, KW_BUSYTYPE_BUSY_UNAVAILABLE, KW_TRANSP_OPAQUE,
+    KW_TRANSP_TRANSPARENT,
+};
 use crate::parameter::{FreeBusyType, Parameter, ValueType};
 use crate::property::PropertyKind;
 use crate::property::common::{take_single_text, take_single_value};
@@ -126,6 +131,17 @@ impl DateTime {
         matches!(self, DateTime::Zoned { .. })
     }
 
+    /// Get the value form of this `DateTime`
+    #[must_use]
+    pub const fn value_form(&self) -> ValueForm {
+        match self {
+            DateTime::Floating { .. } => ValueForm::Floating,
+            DateTime::Zoned { .. } => ValueForm::Zoned,
+            DateTime::Utc { .. } => ValueForm::Utc,
+            DateTime::Date(_) => ValueForm::Date,
+        }
+    }
+
     /// Get the combined date and time as `jiff::civil::DateTime` (when jiff feature is enabled).
     ///
     /// Returns `None` for date-only values.
@@ -144,6 +160,32 @@ impl DateTime {
     }
 }
 
+/// The value form of a date-time property, which decides how it is written.
+///
+/// Patching a property to a new value in the same form keeps it written the same way, such
+/// as `DUE;VALUE=DATE:20250115` staying a date rather than becoming a date-time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueForm {
+    /// Date-only value, written with `VALUE=DATE`
+    Date,
+    /// Date and time without timezone
+    Floating,
+    /// Date and time in UTC, written with a trailing `Z`
+    Utc,
+    /// Date and time with a `TZID` parameter
+    Zoned,
+}
+
+/// Hashes the variant with its date and time, leaving out the cached time zone of `Zoned`,
+/// so that values equal to each other hash alike.
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        self.date().hash(state);
+        self.time().hash(state);
+    }
+}
+
 /// Date and time representation
 #[derive(Debug, Clone)]
 pub struct DateTimeProperty<S: StringStorage> {
@@ -290,6 +332,12 @@ impl<S: StringStorage> DateTimeProperty<S> {
         self.value.is_zoned()
     }
 
+    /// Get the value form of this property, telling whether it used `VALUE=DATE`
+    #[must_use]
+    pub fn value_form(&self) -> ValueForm {
+        self.value.value_form()
+    }
+
     /// Get the combined date and time as `jiff::civil::DateTime` (when jiff feature is enabled).
     ///
     /// Returns `None` for date-only values.
@@ -324,6 +372,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeProperty<Segments<'src>> {
 
         // Get TZID parameter
         let mut tz_id = None;
+        let mut tz_span = span;
         #[cfg(feature = "jiff")]
         let mut tz_jiff = None;
         let mut x_parameters = Vec::new();
@@ -341,9 +390,10 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeProperty<Segments<'src>> {
                     value,
                     #[cfg(feature = "jiff")]
                     tz,
-                    ..
+                    span,
                 } => {
                     tz_id = Some(value);
+                    tz_span = span;
                     #[cfg(feature = "jiff")]
                     {
                         tz_jiff = tz; // Now Option<jiff::tz::TimeZone>
@@ -368,8 +418,16 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeProperty<Segments<'src>> {
         if let Some(tz_id_value) = tz_id {
             match value {
                 Value::DateTime { mut values, .. } if values.len() == 1 => {
-                    let dt = values.pop().unwrap();
+                    let dt = values.pop().expect("length checked above");
                     if dt.time.utc {
+                        // A value marked UTC is taken as UTC, keeping the contradicting TZID to
+                        // write it back as it was, where it is reported as a warning
+                        retained_parameters.push(Parameter::TimeZoneIdentifier {
+                            value: tz_id_value,
+                            #[cfg(feature = "jiff")]
+                            tz: tz_jiff,
+                            span: tz_span,
+                        });
                         Ok(DateTimeProperty::utc(
                             dt.date,
                             dt.time.into(),
@@ -400,7 +458,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeProperty<Segments<'src>> {
         } else {
             match value {
                 Value::Date { mut values, .. } if values.len() == 1 => {
-                    let date = values.pop().unwrap();
+                    let date = values.pop().expect("length checked above");
                     Ok(DateTimeProperty::date_only(
                         date,
                         x_parameters,
@@ -409,7 +467,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeProperty<Segments<'src>> {
                     ))
                 }
                 Value::DateTime { mut values, .. } if values.len() == 1 => {
-                    let dt = values.pop().unwrap();
+                    let dt = values.pop().expect("length checked above");
                     if dt.time.utc {
                         Ok(DateTimeProperty::utc(
                             dt.date,
@@ -465,7 +523,7 @@ impl DateTimeProperty<Segments<'_>> {
 }
 
 /// Time representation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Time {
     /// Hour component (0-23)
     pub hour: i8,
@@ -639,32 +697,32 @@ impl<S: StringStorage> Period<S> {
     /// Get the end as a `DateTime` (when jiff feature is enabled).
     ///
     /// For duration-based periods, calculates the end by adding the duration to the start.
+    /// Returns `None` if the end is out of the range of `jiff::civil::DateTime`, which parsed
+    /// periods never are.
     #[cfg(feature = "jiff")]
     #[must_use]
-    pub fn end(&self) -> DateTime {
+    pub fn end(&self) -> Option<DateTime> {
         match self {
             Period::ExplicitUtc { end, .. }
             | Period::ExplicitFloating { end, .. }
-            | Period::ExplicitZoned { end, .. } => end.clone(),
+            | Period::ExplicitZoned { end, .. } => Some(end.clone()),
             Period::DurationUtc {
                 start, duration, ..
             } => {
-                let start_dt = start.civil_date_time().unwrap_or_default();
-                let end_dt = add_duration(start_dt, duration);
-                DateTime::Utc {
+                let end_dt = add_duration(start.civil_date_time()?, duration)?;
+                Some(DateTime::Utc {
                     date: end_dt.date().into(),
                     time: end_dt.time().into(),
-                }
+                })
             }
             Period::DurationFloating {
                 start, duration, ..
             } => {
-                let start_dt = start.civil_date_time().unwrap_or_default();
-                let end_dt = add_duration(start_dt, duration);
-                DateTime::Floating {
+                let end_dt = add_duration(start.civil_date_time()?, duration)?;
+                Some(DateTime::Floating {
                     date: end_dt.date().into(),
                     time: end_dt.time().into(),
-                }
+                })
             }
             Period::DurationZoned {
                 start,
@@ -673,14 +731,13 @@ impl<S: StringStorage> Period<S> {
                 tz_jiff,
                 ..
             } => {
-                let start_dt = start.civil_date_time().unwrap_or_default();
-                let end_dt = add_duration(start_dt, duration);
-                DateTime::Zoned {
+                let end_dt = add_duration(start.civil_date_time()?, duration)?;
+                Some(DateTime::Zoned {
                     date: end_dt.date().into(),
                     time: end_dt.time().into(),
                     #[cfg(feature = "jiff")]
                     tz_jiff: tz_jiff.clone(),
-                }
+                })
             }
         }
     }
@@ -696,10 +753,11 @@ impl<S: StringStorage> Period<S> {
     /// Get the end date and time as `jiff::civil::DateTime` (when jiff feature is enabled).
     ///
     /// For duration-based periods, calculates the end by adding the duration to the start.
+    /// Returns `None` if the end is out of range, see [`Period::end`].
     #[cfg(feature = "jiff")]
     #[must_use]
-    pub fn end_civil(&self) -> jiff::civil::DateTime {
-        self.start().civil_date_time().unwrap_or_default() // SAFETY: start() never returns Date-only
+    pub fn end_civil(&self) -> Option<jiff::civil::DateTime> {
+        self.end()?.civil_date_time()
     }
 }
 
@@ -710,7 +768,7 @@ impl<'src> TryFrom<Value<Segments<'src>>> for Period<Segments<'src>> {
         let span = value.span();
         match value {
             Value::Period { mut values, .. } if values.len() == 1 => {
-                let value_period = values.pop().unwrap();
+                let value_period = values.pop().expect("length checked above");
                 match value_period {
                     // Both start and end have the same UTC flag (guaranteed by parser)
                     ValuePeriod::Explicit { start, end } if start.time.utc => {
@@ -747,6 +805,15 @@ impl<'src> TryFrom<Value<Segments<'src>>> for Period<Segments<'src>> {
                             }]);
                         }
 
+                        #[cfg(feature = "jiff")]
+                        if add_duration(*start.civil_date_time(), &duration).is_none() {
+                            return Err(vec![TypedError::PropertyInvalidValue {
+                                property: PropertyKind::FreeBusy,
+                                value: "Period end is out of range".to_string(),
+                                span,
+                            }]);
+                        }
+
                         if start.time.utc {
                             Ok(Period::DurationUtc {
                                 start: DateTime::Utc {
@@ -828,9 +895,13 @@ impl Period<Segments<'_>> {
     }
 }
 
+/// Adds a duration to a date and time, returning `None` if either is out of the range of jiff.
 #[cfg(feature = "jiff")]
-fn add_duration(start: jiff::civil::DateTime, duration: &ValueDuration) -> jiff::civil::DateTime {
-    match duration {
+fn add_duration(
+    start: jiff::civil::DateTime,
+    duration: &ValueDuration,
+) -> Option<jiff::civil::DateTime> {
+    let (positive, span) = match duration {
         ValueDuration::DateTime {
             positive,
             day,
@@ -840,28 +911,25 @@ fn add_duration(start: jiff::civil::DateTime, duration: &ValueDuration) -> jiff:
         } => {
             let span = jiff::Span::new()
                 .try_days(i64::from(*day))
-                .unwrap()
+                .ok()?
                 .try_hours(i64::from(*hour))
-                .unwrap()
+                .ok()?
                 .try_minutes(i64::from(*minute))
-                .unwrap()
+                .ok()?
                 .try_seconds(i64::from(*second))
-                .unwrap();
-
-            if *positive {
-                start.checked_add(span).unwrap()
-            } else {
-                start.checked_sub(span).unwrap()
-            }
-        }
-        ValueDuration::Week { positive, week } => {
-            let span = jiff::Span::new().try_weeks(i64::from(*week)).unwrap();
-            if *positive {
-                start.checked_add(span).unwrap()
-            } else {
-                start.checked_sub(span).unwrap()
-            }
+                .ok()?;
+            (*positive, span)
         }
+        ValueDuration::Week { positive, week } => (
+            *positive,
+            jiff::Span::new().try_weeks(i64::from(*week)).ok()?,
+        ),
+    };
+
+    if positive {
+        start.checked_add(span).ok()
+    } else {
+        start.checked_sub(span).ok()
     }
 }
 
@@ -896,6 +964,12 @@ impl<S: StringStorage> DateTimeUtc<S> {
         self.time
     }
 
+    /// Get the value form of this property, which is always UTC
+    #[must_use]
+    pub const fn value_form(&self) -> ValueForm {
+        ValueForm::Utc
+    }
+
     /// Get the combined date and time as `jiff::civil::DateTime` (when jiff feature is enabled).
     #[cfg(feature = "jiff")]
     #[must_use]
@@ -904,13 +978,15 @@ impl<S: StringStorage> DateTimeUtc<S> {
     }
 
     /// Get the combined date and time as `jiff::Zoned` in UTC (when jiff feature is enabled).
+    ///
+    /// Returns `None` for the last hours of year 9999, which are out of the range of
+    /// `jiff::Timestamp`.
     #[cfg(feature = "jiff")]
     #[must_use]
-    #[expect(clippy::missing_panics_doc)]
-    pub fn zoned(&self) -> jiff::Zoned {
+    pub fn zoned(&self) -> Option<jiff::Zoned> {
         self.civil_date_time()
             .to_zoned(jiff::tz::TimeZone::UTC)
-            .expect("UTC timezone should always be valid")
+            .ok()
     }
 
     /// Get the span of this property
@@ -969,7 +1045,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for DateTimeUtc<Segments<'src>> {
 
         Ok(Self {
             date: dt.date(),
-            time: dt.time().unwrap(), // SAFETY: is_utc() guarantees Some(time)
+            time: dt.time().expect("is_utc() guarantees Some(time)"),
             x_parameters: dt.x_parameters,
             retained_parameters: dt.retained_parameters,
             span,
@@ -1071,7 +1147,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Duration<Segments<'src>> {
                 }])
             }
             Ok(Value::Duration { mut values, .. }) => Ok(Self {
-                value: values.pop().unwrap(), // SAFETY: checked above
+                value: values.pop().expect("single value checked above"),
                 x_parameters,
                 retained_parameters,
                 span: prop.span,
@@ -1228,6 +1304,16 @@ impl<'src> TryFrom<ParsedProperty<'src>> for FreeBusy<Segments<'src>> {
                         continue;
                     }
 
+                    #[cfg(feature = "jiff")]
+                    if add_duration(*start.civil_date_time(), &duration).is_none() {
+                        errors.push(TypedError::PropertyInvalidValue {
+                            property: prop.kind.clone(),
+                            value: "Period end is out of range".to_string(),
+                            span: value_span,
+                        });
+                        continue;
+                    }
+
                     if start.time.utc {
                         Period::DurationUtc {
                             start: DateTime::Utc {
@@ -1393,3 +1479,104 @@ impl<S: StringStorage> TimeTransparency<S> {
         self.span
     }
 }
+
+define_prop_value_enum! {
+    /// Busy time type value (RFC 7953 Section 3.2)
+    #[derive(Default)]
+    pub enum BusyTypeValue {
+        /// Time is busy
+        Busy => KW_BUSYTYPE_BUSY,
+        /// Time is unavailable, the default
+        #[default]
+        BusyUnavailable => KW_BUSYTYPE_BUSY_UNAVAILABLE,
+        /// Time is tentatively busy
+        BusyTentative => KW_BUSYTYPE_BUSY_TENTATIVE,
+    }
+}
+
+/// Busy time type of the time a `VAVAILABILITY` leaves unavailable (RFC 7953 Section 3.2)
+#[derive(Debug, Clone, Default)]
+pub struct BusyType<S: StringStorage> {
+    /// Busy time type value
+    pub value: BusyTypeValue,
+    /// X-name parameters (custom experimental parameters)
+    pub x_parameters: Vec<RawParameter<S>>,
+    /// Unrecognized / Non-standard parameters (preserved for round-trip)
+    pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<'src> TryFrom<ParsedProperty<'src>> for BusyType<Segments<'src>> {
+    type Error = Vec<TypedError<'src>>;
+
+    fn try_from(prop: ParsedProperty<'src>) -> Result<Self, Self::Error> {
+        if !matches!(prop.kind, PropertyKind::BusyType) {
+            return Err(vec![TypedError::PropertyUnexpectedKind {
+                expected: PropertyKind::BusyType,
+                found: prop.kind,
+                span: prop.span,
+            }]);
+        }
+
+        let mut x_parameters = Vec::new();
+        let mut retained_parameters = Vec::new();
+
+        for param in prop.parameters {
+            match param {
+                Parameter::XName(raw) => x_parameters.push(raw),
+                p @ Parameter::Unrecognized { .. } => retained_parameters.push(p),
+                p => {
+                    // Preserve other parameters not used by this property for round-trip
+                    retained_parameters.push(p);
+                }
+            }
+        }
+
+        let value_span = prop.value.span();
+        let text = take_single_text(&PropertyKind::BusyType, prop.value)?;
+        let value = text.try_into().map_err(|value| {
+            vec![TypedError::PropertyInvalidValue {
+                property: PropertyKind::BusyType,
+                value: format!("Invalid busy time type: {value}"),
+                span: value_span,
+            }]
+        })?;
+
+        Ok(BusyType {
+            value,
+            x_parameters,
+            retained_parameters,
+            span: prop.span,
+        })
+    }
+}
+
+impl BusyType<Segments<'_>> {
+    /// Convert borrowed `BusyType` to owned `BusyType`
+    #[must_use]
+    pub fn to_owned(&self) -> BusyType<String> {
+        BusyType {
+            value: self.value,
+            x_parameters: self
+                .x_parameters
+                .iter()
+                .map(RawParameter::to_owned)
+                .collect(),
+            retained_parameters: self
+                .retained_parameters
+                .iter()
+                .map(Parameter::to_owned)
+                .collect(),
+            span: (),
+        }
+    }
+}
+
+impl<S: StringStorage> BusyType<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
+}
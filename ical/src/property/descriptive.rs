@@ -135,7 +135,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Attachment<Segments<'src>> {
         }
 
         Ok(Attachment {
-            value: value.unwrap(), // SAFETY: checked errors above
+            value: value.expect("checked errors above"),
             fmt_type,
             encoding,
             x_parameters,
@@ -185,6 +185,47 @@ impl AttachmentValue<Segments<'_>> {
             AttachmentValue::Binary(data) => AttachmentValue::Binary(data.to_owned()),
         }
     }
+
+    /// Get the size in bytes of the binary data once decoded, without decoding it.
+    ///
+    /// Returns `None` for a URI.
+    #[must_use]
+    pub fn decoded_len(&self) -> Option<usize> {
+        match self {
+            AttachmentValue::Uri(_) => None,
+            AttachmentValue::Binary(data) => {
+                let padding = data
+                    .as_slice()
+                    .iter()
+                    .rev()
+                    .flat_map(|(s, _)| s.bytes().rev())
+                    .take_while(|&b| b == b'=')
+                    .count();
+                Some(base64_decoded_len(data.len(), padding))
+            }
+        }
+    }
+}
+
+impl AttachmentValue<String> {
+    /// Get the size in bytes of the binary data once decoded, without decoding it.
+    ///
+    /// Returns `None` for a URI.
+    #[must_use]
+    pub fn decoded_len(&self) -> Option<usize> {
+        match self {
+            AttachmentValue::Uri(_) => None,
+            AttachmentValue::Binary(data) => {
+                let padding = data.bytes().rev().take_while(|&b| b == b'=').count();
+                Some(base64_decoded_len(data.len(), padding))
+            }
+        }
+    }
+}
+
+/// Size of the data encoded by `len` BASE64 characters, `padding` of them being `=`.
+const fn base64_decoded_len(len: usize, padding: usize) -> usize {
+    (len / 4 * 3).saturating_sub(padding)
 }
 
 define_prop_value_enum! {
@@ -584,7 +625,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for PercentComplete<Segments<'src>> {
             Ok(Value::Integer {
                 values: mut ints, ..
             }) if ints.len() == 1 => {
-                let i = ints.pop().unwrap();
+                let i = ints.pop().expect("length checked above");
                 if (0..=100).contains(&i) {
                     #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
                     Ok(Self {
@@ -711,7 +752,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Priority<Segments<'src>> {
             Ok(Value::Integer {
                 values: mut ints, ..
             }) if ints.len() == 1 => {
-                let i = ints.pop().unwrap();
+                let i = ints.pop().expect("length checked above");
                 if (0..=9).contains(&i) {
                     Ok(Self {
                         value: i as u8,
@@ -5,14 +5,16 @@
 //! Property kinds and value types for iCalendar properties.
 //!
 //! This module defines the `PropertyKind` enum that represents all standard
-//! iCalendar properties defined in RFC 5545, along with their allowed value types.
+//! iCalendar properties defined in RFC 5545 and the calendar properties of RFC 7986, along with
+//! their allowed value types.
 
 use crate::keyword::{
-    KW_ACTION, KW_ATTACH, KW_ATTENDEE, KW_CALSCALE, KW_CATEGORIES, KW_CLASS, KW_COMMENT,
-    KW_COMPLETED, KW_CONTACT, KW_CREATED, KW_DESCRIPTION, KW_DTEND, KW_DTSTAMP, KW_DTSTART, KW_DUE,
-    KW_DURATION, KW_EXDATE, KW_FREEBUSY, KW_GEO, KW_LAST_MODIFIED, KW_LOCATION, KW_METHOD,
-    KW_ORGANIZER, KW_PERCENT_COMPLETE, KW_PRIORITY, KW_PRODID, KW_RDATE, KW_RECURRENCE_ID,
-    KW_RELATED_TO, KW_REPEAT, KW_REQUEST_STATUS, KW_RESOURCES, KW_RRULE, KW_SEQUENCE, KW_STATUS,
+    KW_ACTION, KW_ATTACH, KW_ATTENDEE, KW_BUSYTYPE, KW_CALSCALE, KW_CATEGORIES, KW_CLASS, KW_COLOR,
+    KW_COMMENT, KW_COMPLETED, KW_CONFERENCE, KW_CONTACT, KW_CREATED, KW_DESCRIPTION, KW_DTEND,
+    KW_DTSTAMP, KW_DTSTART, KW_DUE, KW_DURATION, KW_EXDATE, KW_FREEBUSY, KW_GEO, KW_IMAGE,
+    KW_LAST_MODIFIED, KW_LOCATION, KW_METHOD, KW_NAME, KW_ORGANIZER, KW_PERCENT_COMPLETE,
+    KW_PRIORITY, KW_PRODID, KW_RDATE, KW_RECURRENCE_ID, KW_REFRESH_INTERVAL, KW_RELATED_TO,
+    KW_REPEAT, KW_REQUEST_STATUS, KW_RESOURCES, KW_RRULE, KW_SEQUENCE, KW_SOURCE, KW_STATUS,
     KW_SUMMARY, KW_TRANSP, KW_TRIGGER, KW_TZID, KW_TZNAME, KW_TZOFFSETFROM, KW_TZOFFSETTO,
     KW_TZURL, KW_UID, KW_URL, KW_VERSION,
 };
@@ -31,7 +33,7 @@ macro_rules! property_kind {
     ) => {
         /// Kind of iCalendar property.
         /// Represents all standard properties defined in RFC 5545.
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
         #[expect(missing_docs)]
         pub enum PropertyKind<S: crate::string_storage::StringStorage> {
             $(
@@ -58,6 +60,28 @@ macro_rules! property_kind {
             }
         }
 
+        impl<S: crate::string_storage::StringStorage> PropertyKind<S> {
+            /// Returns the keyword naming this property kind.
+            /// Returns `None` for unrecognized or x-name properties.
+            #[must_use]
+            pub const fn keyword(&self) -> Option<&'static str> {
+                match self {
+                    $(PropertyKind::$variant => Some($kw),)*
+                    PropertyKind::XName(_) | PropertyKind::Unrecognized(_) => None,
+                }
+            }
+        }
+
+        impl<S: crate::string_storage::StringStorage> From<PropertyKind<&S>> for PropertyKind<S> {
+            fn from(value: PropertyKind<&S>) -> Self {
+                match value {
+                    $(PropertyKind::$variant => Self::$variant,)*
+                    PropertyKind::XName(s) => PropertyKind::XName(s.to_owned()),
+                    PropertyKind::Unrecognized(s) => PropertyKind::Unrecognized(s.to_owned()),
+                }
+            }
+        }
+
         impl<'src> ::core::convert::From<Segments<'src>> for PropertyKind<crate::string_storage::Segments<'src>> {
             fn from(name: crate::string_storage::Segments<'src>) -> Self {
                 $(
@@ -74,6 +98,22 @@ macro_rules! property_kind {
             }
         }
 
+        impl ::core::convert::From<&str> for PropertyKind<String> {
+            fn from(name: &str) -> Self {
+                $(
+                    if name.eq_ignore_ascii_case($kw) {
+                        return PropertyKind::$variant;
+                    }
+                )*
+
+                if name.get(..2).is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-")) {
+                    PropertyKind::XName(name.to_owned())
+                } else {
+                    PropertyKind::Unrecognized(name.to_owned())
+                }
+            }
+        }
+
         impl<S: crate::string_storage::StringStorage> ::core::fmt::Display for PropertyKind<S> {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 match self {
@@ -106,7 +146,8 @@ macro_rules! property_kind {
     };
 }
 
-// Define PropertyKind with all RFC 5545 properties and their value types
+// Define PropertyKind with all RFC 5545 properties, the calendar properties of RFC 7986, and
+// their value types
 property_kind! {
     // 3.7.1.  Calendar Scale
     CalScale    => KW_CALSCALE  => &[ValueType::<String>::Text],
@@ -200,6 +241,20 @@ property_kind! {
     Sequence    => KW_SEQUENCE  => &[ValueType::<String>::Integer],
     // 3.8.8.3.  Request Status
     RequestStatus => KW_REQUEST_STATUS => &[ValueType::<String>::Text],
+    // RFC 7986 5.1.  Name
+    Name        => KW_NAME      => &[ValueType::<String>::Text],
+    // RFC 7986 5.7.  Refresh Interval
+    RefreshInterval => KW_REFRESH_INTERVAL => &[ValueType::<String>::Duration],
+    // RFC 7986 5.8.  Source
+    Source      => KW_SOURCE    => &[ValueType::<String>::Uri],
+    // RFC 7986 5.9.  Color
+    Color       => KW_COLOR     => &[ValueType::<String>::Text],
+    // RFC 7986 5.10.  Image
+    Image       => KW_IMAGE     => &[ValueType::<String>::Uri, ValueType::Binary],
+    // RFC 7986 5.11.  Conference
+    Conference  => KW_CONFERENCE => &[ValueType::<String>::Uri],
+    // RFC 7953 3.2.  Busy Time Type
+    BusyType    => KW_BUSYTYPE  => &[ValueType::<String>::Text],
 }
 
 #[cfg(test)]
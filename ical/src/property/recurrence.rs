@@ -189,6 +189,85 @@ impl<S: StringStorage> ExDate<S> {
     pub const fn span(&self) -> S::Span {
         self.span
     }
+
+    /// Check whether the list contains the given date
+    #[must_use]
+    pub fn contains(&self, date: &DateTime) -> bool {
+        self.dates.iter().any(|d| date_key(d) == date_key(date))
+    }
+
+    /// Insert a date into the list, keeping it sorted.
+    ///
+    /// Returns `false` if the date is already present.
+    pub fn insert(&mut self, date: DateTime) -> bool {
+        let key = date_key(&date);
+        match self.dates.binary_search_by_key(&key, date_key) {
+            Ok(_) => false,
+            Err(i) => {
+                self.dates.insert(i, date);
+                true
+            }
+        }
+    }
+
+    /// Remove a date from the list.
+    ///
+    /// Returns `false` if the date is not present.
+    pub fn remove(&mut self, date: &DateTime) -> bool {
+        let len = self.dates.len();
+        self.dates.retain(|d| date_key(d) != date_key(date));
+        self.dates.len() != len
+    }
+}
+
+impl ExDate<String> {
+    /// Create an `EXDATE` property, adding `VALUE=DATE` for date-only values
+    #[must_use]
+    pub fn new(dates: Vec<DateTime>, tz_id: Option<String>) -> Self {
+        let mut prop = Self {
+            dates: Vec::with_capacity(dates.len()),
+            tz_id,
+            x_parameters: Vec::new(),
+            retained_parameters: date_value_parameter(&dates),
+            span: (),
+        };
+        for date in dates {
+            prop.insert(date);
+        }
+        prop
+    }
+
+    /// Merge properties sharing the same TZID and value type into one.
+    ///
+    /// Dates are kept sorted and unique, parameters of the first property win,
+    /// and properties without any date are dropped.
+    #[must_use]
+    pub fn merge(props: Vec<Self>) -> Vec<Self> {
+        let mut merged: Vec<Self> = Vec::with_capacity(props.len());
+        for mut prop in props {
+            let date_only = prop.dates.first().is_some_and(DateTime::is_date_only);
+            let target = merged.iter_mut().find(|m| {
+                m.tz_id == prop.tz_id
+                    && m.dates
+                        .first()
+                        .is_some_and(|d| d.is_date_only() == date_only)
+            });
+            match target {
+                Some(target) => {
+                    for date in prop.dates {
+                        target.insert(date);
+                    }
+                }
+                None if !prop.dates.is_empty() => {
+                    prop.dates.sort_by_key(date_key);
+                    prop.dates.dedup_by_key(|d| date_key(d));
+                    merged.push(prop);
+                }
+                None => {}
+            }
+        }
+        merged
+    }
 }
 
 /// Recurrence Date-Times (RFC 5545 Section 3.8.5.2)
@@ -344,6 +423,134 @@ impl<S: StringStorage> RDate<S> {
     pub const fn span(&self) -> S::Span {
         self.span
     }
+
+    /// Check whether the list contains the given date
+    #[must_use]
+    pub fn contains(&self, date: &DateTime) -> bool {
+        self.dates.iter().any(|d| match d {
+            RDateValue::DateTime(d) => date_key(d) == date_key(date),
+            RDateValue::Period(_) => false,
+        })
+    }
+
+    /// Insert a date into the list, keeping it sorted by start.
+    ///
+    /// Returns `false` if the date is already present.
+    pub fn insert(&mut self, date: DateTime) -> bool {
+        if self.contains(&date) {
+            return false;
+        }
+        let key = date_key(&date);
+        let i = self.dates.partition_point(|d| rdate_key(d) <= key);
+        self.dates.insert(i, RDateValue::DateTime(date));
+        true
+    }
+
+    /// Remove a date from the list, periods are kept.
+    ///
+    /// Returns `false` if the date is not present.
+    pub fn remove(&mut self, date: &DateTime) -> bool {
+        let len = self.dates.len();
+        self.dates.retain(|d| match d {
+            RDateValue::DateTime(d) => date_key(d) != date_key(date),
+            RDateValue::Period(_) => true,
+        });
+        self.dates.len() != len
+    }
+}
+
+impl RDate<String> {
+    /// Create an `RDATE` property, adding `VALUE=DATE` for date-only values
+    #[must_use]
+    pub fn new(dates: Vec<DateTime>, tz_id: Option<String>) -> Self {
+        let mut prop = Self {
+            dates: Vec::with_capacity(dates.len()),
+            tz_id,
+            x_parameters: Vec::new(),
+            retained_parameters: date_value_parameter(&dates),
+            span: (),
+        };
+        for date in dates {
+            prop.insert(date);
+        }
+        prop
+    }
+
+    /// Merge properties sharing the same TZID and value type into one.
+    ///
+    /// Values are kept sorted by start and unique, parameters of the first
+    /// property win, and properties without any value are dropped.
+    #[must_use]
+    pub fn merge(props: Vec<Self>) -> Vec<Self> {
+        let mut merged: Vec<Self> = Vec::with_capacity(props.len());
+        for mut prop in props {
+            let kind = prop.dates.first().map(rdate_kind);
+            let target = merged
+                .iter_mut()
+                .find(|m| m.tz_id == prop.tz_id && m.dates.first().map(rdate_kind) == kind);
+            match target {
+                Some(target) => {
+                    for value in prop.dates {
+                        match value {
+                            RDateValue::DateTime(date) => {
+                                target.insert(date);
+                            }
+                            RDateValue::Period(_) => {
+                                let key = rdate_key(&value);
+                                let i = target.dates.partition_point(|d| rdate_key(d) <= key);
+                                target.dates.insert(i, value);
+                            }
+                        }
+                    }
+                }
+                None if !prop.dates.is_empty() => {
+                    prop.dates.sort_by_key(rdate_key);
+                    merged.push(prop);
+                }
+                None => {}
+            }
+        }
+        merged
+    }
+}
+
+/// Sort key of a date in a multi-valued property: date, optional time and UTC flag.
+type DateKey = (i16, i8, i8, Option<(i8, i8, i8)>, bool);
+
+/// Sort key of a date in a multi-valued property.
+///
+/// Floating and zoned values compare alike, since the TZID is set on the property.
+fn date_key(date: &DateTime) -> DateKey {
+    let d = date.date();
+    let t = date.time().map(|t| (t.hour, t.minute, t.second));
+    (d.year, d.month, d.day, t, date.is_utc())
+}
+
+fn rdate_key<S: StringStorage>(value: &RDateValue<S>) -> DateKey {
+    match value {
+        RDateValue::DateTime(date) => date_key(date),
+        RDateValue::Period(period) => date_key(&period.start()),
+    }
+}
+
+/// Value type of an `RDATE` value: date, date-time or period.
+fn rdate_kind<S: StringStorage>(value: &RDateValue<S>) -> u8 {
+    match value {
+        RDateValue::DateTime(date) if date.is_date_only() => 0,
+        RDateValue::DateTime(_) => 1,
+        RDateValue::Period(_) => 2,
+    }
+}
+
+/// `VALUE=DATE` if the values are dates, as the default value type is `DATE-TIME`.
+fn date_value_parameter(dates: &[DateTime]) -> Vec<Parameter<String>> {
+    match dates.first() {
+        Some(date) if date.is_date_only() => vec![Parameter::ValueType {
+            value: ValueType::Date,
+            span: (),
+        }],
+        _ => Vec::new(),
+    }
 }
 
 /// Recurrence Rule (RFC 5545 Section 3.8.5.3)
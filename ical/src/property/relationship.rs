@@ -217,7 +217,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Attendee<Segments<'src>> {
         let cutype = cutype.unwrap_or(CalendarUserType::Individual);
 
         Ok(Attendee {
-            cal_address: cal_address.unwrap(), // SAFETY: ensured above
+            cal_address: cal_address.expect("CAL-ADDRESS ensured above"),
             cn,
             role,
             part_stat,
@@ -391,7 +391,7 @@ impl<'src> TryFrom<ParsedProperty<'src>> for Organizer<Segments<'src>> {
         }
 
         Ok(Organizer {
-            cal_address: cal_address.unwrap(), // SAFETY: ensured above
+            cal_address: cal_address.expect("CAL-ADDRESS ensured above"),
             cn,
             dir,
             sent_by,
@@ -112,6 +112,19 @@ impl UtcOffsetProperty<Segments<'_>> {
     }
 }
 
+impl UtcOffsetProperty<String> {
+    /// Create a new `UtcOffsetProperty<String>` with no parameters.
+    #[must_use]
+    pub fn new(value: ValueUtcOffset) -> Self {
+        Self {
+            value,
+            x_parameters: Vec::new(),
+            retained_parameters: Vec::new(),
+            span: (),
+        }
+    }
+}
+
 impl<S: StringStorage> UtcOffsetProperty<S> {
     /// Get the span of this property
     #[must_use]
@@ -4,31 +4,46 @@
 
 //! This module provides strongly-typed structures that represent the semantic
 //! meaning of iCalendar data parsed from the raw syntax. These types follow
-//! the RFC 5545 specification and provide a convenient API for working with
+//! the RFC 5545 specification, with the availability components of RFC 7953, and provide a convenient API for working with
 //! calendar data without dealing with string parsing and validation.
 
+mod anchor;
+mod cardinality;
 mod extensions;
 mod icalendar;
+pub(crate) mod identity;
 mod tz_validator;
 mod valarm;
+mod vavailability;
 mod vevent;
 mod vfreebusy;
 mod vjournal;
 mod vtimezone;
 mod vtodo;
 
+pub use cardinality::{Cardinality, cardinality};
 pub use extensions::{UnrecognizedComponent, XComponent};
 pub use icalendar::{CalendarComponent, ICalendar};
+pub use identity::ComponentIdentity;
 pub use tz_validator::validate_tzids;
+#[cfg(feature = "jiff")]
+pub use valarm::AlarmTriggerError;
 pub use valarm::VAlarm;
+pub use vavailability::{Available, VAvailability};
 pub use vevent::{EventStatus, EventStatusValue, VEvent};
 pub use vfreebusy::VFreeBusy;
 pub use vjournal::{JournalStatus, VJournal};
 pub use vtimezone::{TimeZoneObservance, VTimeZone};
 pub use vtodo::{TodoStatus, TodoStatusValue, VTodo};
 
+pub(crate) use crate::semantic::anchor::check_anchors;
+#[cfg(feature = "jiff")]
+pub(crate) use crate::semantic::tz_validator::check_tz_offsets;
+pub(crate) use crate::semantic::tz_validator::check_utc_tzids;
+
 use crate::keyword::KW_VCALENDAR;
 use crate::property::PropertyKind;
+use crate::semantic::cardinality::check_cardinality;
 use crate::string_storage::{Segments, Span};
 use crate::typed::TypedComponent;
 
@@ -38,33 +53,68 @@ use crate::typed::TypedComponent;
 ///
 /// Returns a vector of errors if:
 /// - No VCALENDAR components are found
-/// - Any components failed to parse
+/// - Any calendars failed to parse, including those none of whose components parse
+///
+/// Components of a calendar that fail to parse while others parse are left out.
 pub fn semantic_analysis(
     typed_components: Vec<TypedComponent<'_>>,
 ) -> Result<Vec<ICalendar<Segments<'_>>>, Vec<SemanticError<'_>>> {
+    let (result, mut duplicates, _) = semantic_analysis_with_duplicates(typed_components);
+    match result {
+        Ok(_) if !duplicates.is_empty() => Err(duplicates),
+        Ok(calendars) => Ok(calendars),
+        Err(errors) => {
+            duplicates.extend(errors);
+            Err(duplicates)
+        }
+    }
+}
+
+type SemanticResult<'src> = Result<Vec<ICalendar<Segments<'src>>>, Vec<SemanticError<'src>>>;
+
+/// Perform semantic analysis on typed components, reporting the properties occurring more often
+/// than their component allows apart from the other errors, so they can be accepted as warnings,
+/// and the errors of the components left out of the calendars last.
+pub(crate) fn semantic_analysis_with_duplicates(
+    typed_components: Vec<TypedComponent<'_>>,
+) -> (
+    SemanticResult<'_>,
+    Vec<SemanticError<'_>>,
+    Vec<SemanticError<'_>>,
+) {
     // Return error only if no calendars
     if typed_components.is_empty() {
-        return Err(vec![SemanticError::ConstraintViolation {
+        let error = SemanticError::ConstraintViolation {
             span: Span { start: 0, end: 0 },
             message: format!("No {KW_VCALENDAR} components found"),
-        }]);
+        };
+        return (Err(vec![error]), Vec::new(), Vec::new());
+    }
+
+    let mut duplicates = Vec::new();
+    for component in &typed_components {
+        check_cardinality(component, &mut duplicates);
     }
 
     let mut calendars = Vec::with_capacity(typed_components.len());
     let mut all_errors = Vec::new();
+    let mut dropped = Vec::new();
 
     for component in typed_components {
-        match ICalendar::try_from(component) {
+        let (result, errors) = ICalendar::from_typed(component);
+        dropped.extend(errors);
+        match result {
             Ok(calendar) => calendars.push(calendar),
             Err(errors) => all_errors.extend(errors),
         }
     }
 
-    if all_errors.is_empty() {
+    let result = if all_errors.is_empty() {
         Ok(calendars)
     } else {
         Err(all_errors)
-    }
+    };
+    (result, duplicates, dropped)
 }
 
 /// Error type for parsing operations
@@ -98,6 +148,8 @@ pub enum SemanticError<'src> {
         property: PropertyKind<Segments<'src>>,
         /// The span of the error
         span: Span,
+        /// The span of the first occurrence of the property
+        first: Span,
     },
 
     /// Missing required property
@@ -129,6 +181,28 @@ pub enum SemanticError<'src> {
         span: Span,
     },
 
+    /// A component without the date its schedule or recurrence is anchored on, such as an
+    /// event without `DTSTART`, only ever reported as a warning
+    #[error("{message}")]
+    Unanchored {
+        /// Message describing how the component is taken
+        message: String,
+        /// The span of the component
+        span: Span,
+    },
+
+    /// A TZID that disagrees with an explicit UTC offset, such as a TZID on a date-time marked
+    /// UTC, only ever reported as a warning
+    #[error("{message}")]
+    TimezoneMismatch {
+        /// Message describing the mismatch and how it is taken
+        message: String,
+        /// The span of the TZID
+        span: Span,
+        /// The span of the property with the explicit UTC offset
+        offset: Span,
+    },
+
     /// Timezone identifier not found in VTIMEZONE components or local database
     /// This variant does not use the lifetime parameter, as it owns all its data
     #[error(
@@ -153,6 +227,8 @@ impl SemanticError<'_> {
             | Self::MissingProperty { span, .. }
             | Self::InvalidValue { span, .. }
             | Self::ConstraintViolation { span, .. }
+            | Self::Unanchored { span, .. }
+            | Self::TimezoneMismatch { span, .. }
             | Self::TimezoneNotFound { span, .. } => *span,
         }
     }
@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Components without the date their schedule or recurrence is anchored on.
+//!
+//! Some clients write recurring `VTODO`s without `DTSTART`, which then recur from their `DUE`,
+//! and even `VEVENT`s without `DTSTART`, which are unscheduled. RFC 5545 allows the latter only
+//! with a `METHOD`, but both are accepted and reported as warnings.
+
+use crate::keyword::{KW_VEVENT, KW_VTODO};
+use crate::property::Property;
+use crate::semantic::SemanticError;
+use crate::string_storage::Segments;
+use crate::typed::TypedComponent;
+
+/// Reports every event without `DTSTART`, and every recurring todo without it, in the component
+/// and its children.
+pub(crate) fn check_anchors<'src>(
+    component: &TypedComponent<'src>,
+    warnings: &mut Vec<SemanticError<'src>>,
+) {
+    let has = |f: fn(&Property<Segments<'src>>) -> bool| component.properties.iter().any(f);
+    let has_start = has(|p| matches!(p, Property::DtStart(_)));
+    let recurs = has(|p| matches!(p, Property::RRule(_) | Property::RDate(_)));
+
+    let message = if has_start {
+        None
+    } else if component.name.eq_str_ignore_ascii_case(KW_VEVENT) {
+        Some(if recurs {
+            "Event without DTSTART is unscheduled, its recurrence is not expanded"
+        } else {
+            "Event without DTSTART is unscheduled"
+        })
+    } else if component.name.eq_str_ignore_ascii_case(KW_VTODO) && recurs {
+        Some(if has(|p| matches!(p, Property::Due(_))) {
+            "Recurring todo without DTSTART recurs from its DUE"
+        } else {
+            "Recurring todo without DTSTART or DUE has nothing to recur from"
+        })
+    } else {
+        None
+    };
+    if let Some(message) = message {
+        warnings.push(SemanticError::Unanchored {
+            message: message.to_string(),
+            span: component.span,
+        });
+    }
+
+    for child in &component.children {
+        check_anchors(child, warnings);
+    }
+}
@@ -0,0 +1,433 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! How many times each property may occur in each component, per RFC 5545 section 3.6, the
+//! calendar properties of RFC 7986 and the availability components of RFC 7953.
+//!
+//! The table is the single source of these constraints: semantic analysis reports duplicates of
+//! properties allowed at most once from it, and the semantic components keep a `Vec` for every
+//! property allowed more than once. Missing required properties are still reported by each
+//! component, which may require more than RFC 5545 does.
+
+use crate::keyword::{
+    KW_ACTION, KW_ATTACH, KW_ATTENDEE, KW_AVAILABLE, KW_BUSYTYPE, KW_CALSCALE, KW_CATEGORIES,
+    KW_CLASS, KW_COLOR, KW_COMMENT, KW_COMPLETED, KW_CONFERENCE, KW_CONTACT, KW_CREATED,
+    KW_DAYLIGHT, KW_DESCRIPTION, KW_DTEND, KW_DTSTAMP, KW_DTSTART, KW_DUE, KW_DURATION, KW_EXDATE,
+    KW_FREEBUSY, KW_GEO, KW_IMAGE, KW_LAST_MODIFIED, KW_LOCATION, KW_METHOD, KW_NAME, KW_ORGANIZER,
+    KW_PERCENT_COMPLETE, KW_PRIORITY, KW_PRODID, KW_RDATE, KW_RECURRENCE_ID, KW_REFRESH_INTERVAL,
+    KW_RELATED_TO, KW_REPEAT, KW_REQUEST_STATUS, KW_RESOURCES, KW_RRULE, KW_SEQUENCE, KW_SOURCE,
+    KW_STANDARD, KW_STATUS, KW_SUMMARY, KW_TRANSP, KW_TRIGGER, KW_TZID, KW_TZNAME, KW_TZOFFSETFROM,
+    KW_TZOFFSETTO, KW_TZURL, KW_UID, KW_URL, KW_VALARM, KW_VAVAILABILITY, KW_VCALENDAR, KW_VERSION,
+    KW_VEVENT, KW_VFREEBUSY, KW_VJOURNAL, KW_VTIMEZONE, KW_VTODO,
+};
+use crate::property::PropertyKind;
+use crate::semantic::SemanticError;
+use crate::string_storage::{Span, StringStorage};
+use crate::typed::TypedComponent;
+
+/// How many times a property may occur in a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    /// The property must occur exactly once.
+    Required,
+    /// The property may occur at most once.
+    Optional,
+    /// The property may occur any number of times.
+    Many,
+}
+
+impl Cardinality {
+    /// Whether the property may occur more than once.
+    #[must_use]
+    pub const fn is_repeatable(self) -> bool {
+        matches!(self, Self::Many)
+    }
+}
+
+use Cardinality::{Many, Optional, Required};
+
+#[rustfmt::skip]
+const VCALENDAR: &[(&str, Cardinality)] = &[
+    (KW_PRODID,           Required),
+    (KW_VERSION,          Required),
+    (KW_CALSCALE,         Optional),
+    (KW_METHOD,           Optional),
+    // RFC 7986 section 4
+    (KW_UID,              Optional),
+    (KW_LAST_MODIFIED,    Optional),
+    (KW_URL,              Optional),
+    (KW_REFRESH_INTERVAL, Optional),
+    (KW_SOURCE,           Optional),
+    (KW_COLOR,            Optional),
+    (KW_NAME,             Many),
+    (KW_DESCRIPTION,      Many),
+    (KW_CATEGORIES,       Many),
+];
+
+#[rustfmt::skip]
+const VEVENT: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,       Required),
+    (KW_UID,           Required),
+    (KW_DTSTART,       Required),
+    (KW_CLASS,         Optional),
+    (KW_CREATED,       Optional),
+    (KW_DESCRIPTION,   Optional),
+    (KW_GEO,           Optional),
+    (KW_LAST_MODIFIED, Optional),
+    (KW_LOCATION,      Optional),
+    (KW_ORGANIZER,     Optional),
+    (KW_PRIORITY,      Optional),
+    (KW_SEQUENCE,      Optional),
+    (KW_STATUS,        Optional),
+    (KW_SUMMARY,       Optional),
+    (KW_TRANSP,        Optional),
+    (KW_URL,           Optional),
+    (KW_RECURRENCE_ID, Optional),
+    (KW_RRULE,         Optional),
+    (KW_DTEND,         Optional),
+    (KW_DURATION,      Optional),
+    (KW_ATTACH,        Many),
+    (KW_ATTENDEE,      Many),
+    (KW_CATEGORIES,    Many),
+    (KW_COMMENT,       Many),
+    (KW_CONTACT,       Many),
+    (KW_EXDATE,        Many),
+    (KW_REQUEST_STATUS, Many),
+    (KW_RELATED_TO,    Many),
+    (KW_RESOURCES,     Many),
+    (KW_RDATE,         Many),
+    // RFC 7986 section 5
+    (KW_COLOR,         Optional),
+    (KW_IMAGE,         Many),
+    (KW_CONFERENCE,    Many),
+];
+
+#[rustfmt::skip]
+const VTODO: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,          Required),
+    (KW_UID,              Required),
+    (KW_CLASS,            Optional),
+    (KW_COMPLETED,        Optional),
+    (KW_CREATED,          Optional),
+    (KW_DESCRIPTION,      Optional),
+    (KW_DTSTART,          Optional),
+    (KW_GEO,              Optional),
+    (KW_LAST_MODIFIED,    Optional),
+    (KW_LOCATION,         Optional),
+    (KW_ORGANIZER,        Optional),
+    (KW_PERCENT_COMPLETE, Optional),
+    (KW_PRIORITY,         Optional),
+    (KW_RECURRENCE_ID,    Optional),
+    (KW_SEQUENCE,         Optional),
+    (KW_STATUS,           Optional),
+    (KW_SUMMARY,          Optional),
+    (KW_URL,              Optional),
+    (KW_RRULE,            Optional),
+    (KW_DUE,              Optional),
+    (KW_DURATION,         Optional),
+    (KW_ATTACH,           Many),
+    (KW_ATTENDEE,         Many),
+    (KW_CATEGORIES,       Many),
+    (KW_COMMENT,          Many),
+    (KW_CONTACT,          Many),
+    (KW_EXDATE,           Many),
+    (KW_REQUEST_STATUS,   Many),
+    (KW_RELATED_TO,       Many),
+    (KW_RESOURCES,        Many),
+    (KW_RDATE,            Many),
+    // RFC 7986 section 5
+    (KW_COLOR,            Optional),
+    (KW_IMAGE,            Many),
+    (KW_CONFERENCE,       Many),
+];
+
+#[rustfmt::skip]
+const VJOURNAL: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,        Required),
+    (KW_UID,            Required),
+    (KW_CLASS,          Optional),
+    (KW_CREATED,        Optional),
+    (KW_DTSTART,        Optional),
+    (KW_LAST_MODIFIED,  Optional),
+    (KW_ORGANIZER,      Optional),
+    (KW_RECURRENCE_ID,  Optional),
+    (KW_SEQUENCE,       Optional),
+    (KW_STATUS,         Optional),
+    (KW_SUMMARY,        Optional),
+    (KW_URL,            Optional),
+    (KW_RRULE,          Optional),
+    (KW_ATTACH,         Many),
+    (KW_ATTENDEE,       Many),
+    (KW_CATEGORIES,     Many),
+    (KW_COMMENT,        Many),
+    (KW_CONTACT,        Many),
+    (KW_DESCRIPTION,    Many),
+    (KW_EXDATE,         Many),
+    (KW_RELATED_TO,     Many),
+    (KW_RDATE,          Many),
+    (KW_REQUEST_STATUS, Many),
+];
+
+#[rustfmt::skip]
+const VFREEBUSY: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,        Required),
+    (KW_UID,            Required),
+    (KW_CONTACT,        Optional),
+    (KW_DTSTART,        Optional),
+    (KW_DTEND,          Optional),
+    // Allowed by RFC 2445, still accepted for compatibility
+    (KW_DURATION,       Optional),
+    (KW_ORGANIZER,      Optional),
+    (KW_URL,            Optional),
+    (KW_ATTENDEE,       Many),
+    (KW_COMMENT,        Many),
+    (KW_FREEBUSY,       Many),
+    (KW_REQUEST_STATUS, Many),
+];
+
+#[rustfmt::skip]
+const VTIMEZONE: &[(&str, Cardinality)] = &[
+    (KW_TZID,          Required),
+    (KW_LAST_MODIFIED, Optional),
+    (KW_TZURL,         Optional),
+];
+
+/// `STANDARD` and `DAYLIGHT` sub-components of `VTIMEZONE`
+#[rustfmt::skip]
+const OBSERVANCE: &[(&str, Cardinality)] = &[
+    (KW_DTSTART,      Required),
+    (KW_TZOFFSETTO,   Required),
+    (KW_TZOFFSETFROM, Required),
+    (KW_RRULE,        Optional),
+    (KW_COMMENT,      Many),
+    (KW_RDATE,        Many),
+    (KW_TZNAME,       Many),
+];
+
+/// The union of the properties of the audio, display and email alarms, `ATTACH` is allowed once
+/// in audio alarms but many times in email alarms.
+#[rustfmt::skip]
+const VALARM: &[(&str, Cardinality)] = &[
+    (KW_ACTION,      Required),
+    (KW_TRIGGER,     Required),
+    (KW_DURATION,    Optional),
+    (KW_REPEAT,      Optional),
+    (KW_DESCRIPTION, Optional),
+    (KW_SUMMARY,     Optional),
+    (KW_ATTACH,      Many),
+    (KW_ATTENDEE,    Many),
+];
+
+/// RFC 7953 section 3.1
+#[rustfmt::skip]
+const VAVAILABILITY: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,       Required),
+    (KW_UID,           Required),
+    (KW_BUSYTYPE,      Optional),
+    (KW_CLASS,         Optional),
+    (KW_CREATED,       Optional),
+    (KW_DESCRIPTION,   Optional),
+    (KW_DTSTART,       Optional),
+    (KW_LAST_MODIFIED, Optional),
+    (KW_LOCATION,      Optional),
+    (KW_ORGANIZER,     Optional),
+    (KW_PRIORITY,      Optional),
+    (KW_SEQUENCE,      Optional),
+    (KW_SUMMARY,       Optional),
+    (KW_URL,           Optional),
+    (KW_DTEND,         Optional),
+    (KW_DURATION,      Optional),
+    (KW_CATEGORIES,    Many),
+    (KW_COMMENT,       Many),
+    (KW_CONTACT,       Many),
+];
+
+/// `AVAILABLE` sub-components of `VAVAILABILITY`, RFC 7953 section 3.1
+#[rustfmt::skip]
+const AVAILABLE: &[(&str, Cardinality)] = &[
+    (KW_DTSTAMP,       Required),
+    (KW_DTSTART,       Required),
+    (KW_UID,           Required),
+    (KW_DTEND,         Optional),
+    (KW_DURATION,      Optional),
+    (KW_CREATED,       Optional),
+    (KW_DESCRIPTION,   Optional),
+    (KW_LAST_MODIFIED, Optional),
+    (KW_LOCATION,      Optional),
+    (KW_RECURRENCE_ID, Optional),
+    (KW_RRULE,         Optional),
+    (KW_SUMMARY,       Optional),
+    (KW_CATEGORIES,    Many),
+    (KW_COMMENT,       Many),
+    (KW_CONTACT,       Many),
+    (KW_EXDATE,        Many),
+    (KW_RDATE,         Many),
+];
+
+#[rustfmt::skip]
+const COMPONENTS: &[(&str, &[(&str, Cardinality)])] = &[
+    (KW_VCALENDAR, VCALENDAR),
+    (KW_VEVENT,    VEVENT),
+    (KW_VTODO,     VTODO),
+    (KW_VJOURNAL,  VJOURNAL),
+    (KW_VFREEBUSY, VFREEBUSY),
+    (KW_VTIMEZONE, VTIMEZONE),
+    (KW_STANDARD,  OBSERVANCE),
+    (KW_DAYLIGHT,  OBSERVANCE),
+    (KW_VALARM,    VALARM),
+    (KW_VAVAILABILITY, VAVAILABILITY),
+    (KW_AVAILABLE, AVAILABLE),
+];
+
+/// Returns how many times the property may occur in the component named `component`.
+///
+/// Returns `None` for unknown components, x-name and unrecognized properties, and properties
+/// RFC 5545 does not define for the component.
+#[must_use]
+pub fn cardinality<S: StringStorage>(
+    component: &str,
+    property: &PropertyKind<S>,
+) -> Option<Cardinality> {
+    let keyword = property.keyword()?;
+    let (_, properties) = COMPONENTS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(component))?;
+    properties
+        .iter()
+        .find(|(name, _)| *name == keyword)
+        .map(|(_, cardinality)| *cardinality)
+}
+
+/// Reports every property occurring more than once in a component that allows it at most once,
+/// in the component and its children, with the span of its first occurrence.
+pub(crate) fn check_cardinality<'src>(
+    component: &TypedComponent<'src>,
+    errors: &mut Vec<SemanticError<'src>>,
+) {
+    let name = component.name.to_string();
+    let mut seen: Vec<(&str, Span)> = Vec::new();
+    for prop in &component.properties {
+        let kind = prop.kind();
+        let Some(keyword) = kind.keyword() else {
+            continue;
+        };
+        if cardinality(&name, &kind).is_none_or(Cardinality::is_repeatable) {
+            continue;
+        }
+
+        let span = prop.span();
+        match seen.iter().find(|(k, _)| *k == keyword) {
+            Some((_, first)) => errors.push(SemanticError::DuplicateProperty {
+                property: kind.into(),
+                span,
+                first: *first,
+            }),
+            None => seen.push((keyword, span)),
+        }
+    }
+
+    for child in &component.children {
+        check_cardinality(child, errors);
+    }
+}
+
+/// Keeps the first occurrence of a property allowed at most once, the others are reported by
+/// [`check_cardinality`].
+pub(crate) fn keep_first<T>(slot: &mut Option<T>, value: T) {
+    if slot.is_none() {
+        *slot = Some(value);
+    }
+}
+
+/// Converts a property before keeping its first occurrence, so duplicates are not converted.
+pub(crate) fn keep_first_with<T, V, E>(
+    slot: &mut Option<T>,
+    value: V,
+    errors: &mut Vec<E>,
+    convert: impl FnOnce(V) -> Result<T, E>,
+) {
+    if slot.is_none() {
+        match convert(value) {
+            Ok(v) => *slot = Some(v),
+            Err(e) => errors.push(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cardinality_follows_rfc_5545() {
+        let kind = |k: PropertyKind<String>| k;
+        assert_eq!(
+            cardinality(KW_VEVENT, &kind(PropertyKind::Uid)),
+            Some(Required)
+        );
+        assert_eq!(
+            cardinality(KW_VEVENT, &kind(PropertyKind::Summary)),
+            Some(Optional)
+        );
+        assert_eq!(
+            cardinality(KW_VEVENT, &kind(PropertyKind::Comment)),
+            Some(Many)
+        );
+        assert_eq!(
+            cardinality(KW_VTODO, &kind(PropertyKind::Description)),
+            Some(Optional)
+        );
+        assert_eq!(
+            cardinality(KW_VJOURNAL, &kind(PropertyKind::Description)),
+            Some(Many)
+        );
+        assert_eq!(
+            cardinality(KW_VCALENDAR, &kind(PropertyKind::Name)),
+            Some(Many)
+        );
+        assert_eq!(
+            cardinality(KW_DAYLIGHT, &kind(PropertyKind::TzName)),
+            Some(Many)
+        );
+        assert_eq!(
+            cardinality(KW_VAVAILABILITY, &kind(PropertyKind::BusyType)),
+            Some(Optional)
+        );
+        assert_eq!(
+            cardinality(KW_AVAILABLE, &kind(PropertyKind::DtStart)),
+            Some(Required)
+        );
+    }
+
+    #[test]
+    fn cardinality_ignores_case_of_component() {
+        assert_eq!(
+            cardinality("vtodo", &PropertyKind::<String>::Due),
+            Some(Optional)
+        );
+    }
+
+    #[test]
+    fn cardinality_is_unknown_outside_the_table() {
+        assert_eq!(cardinality(KW_VEVENT, &PropertyKind::<String>::Due), None);
+        assert_eq!(
+            cardinality("X-COMPONENT", &PropertyKind::<String>::Uid),
+            None
+        );
+        let x_name = PropertyKind::XName("X-FOO".to_string());
+        assert_eq!(cardinality(KW_VEVENT, &x_name), None);
+    }
+
+    #[test]
+    fn every_table_lists_a_property_once() {
+        for (component, properties) in COMPONENTS {
+            for (i, (name, _)) in properties.iter().enumerate() {
+                assert!(
+                    !properties.iter().skip(i + 1).any(|(n, _)| n == name),
+                    "{component}.{name}"
+                );
+            }
+        }
+    }
+}
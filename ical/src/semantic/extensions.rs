@@ -65,13 +65,10 @@ impl<'src> TryFrom<TypedComponent<'src>> for XComponent<Segments<'src>> {
 
         // Parse child components recursively
         let span = comp.span();
-        let children = match parse_component_children(comp.children) {
-            Ok(v) => v,
-            Err(e) => {
-                errors.extend(e);
-                Vec::new()
-            }
-        };
+        let (children, dropped) = parse_component_children(comp.children);
+        if children.is_empty() {
+            errors.extend(dropped);
+        }
         if errors.is_empty() {
             Ok(XComponent {
                 name: comp.name.to_owned(),
@@ -141,13 +138,10 @@ impl<'src> TryFrom<TypedComponent<'src>> for UnrecognizedComponent<Segments<'src
 
         // Parse child components recursively
         let span = comp.span();
-        let children = match parse_component_children(comp.children) {
-            Ok(v) => v,
-            Err(e) => {
-                errors.extend(e);
-                Vec::new()
-            }
-        };
+        let (children, dropped) = parse_component_children(comp.children);
+        if children.is_empty() {
+            errors.extend(dropped);
+        }
         if errors.is_empty() {
             Ok(UnrecognizedComponent {
                 name: comp.name.to_owned(),
@@ -4,22 +4,26 @@
 
 //! iCalendar container types.
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use crate::keyword::{
-    KW_VALARM, KW_VCALENDAR, KW_VEVENT, KW_VFREEBUSY, KW_VJOURNAL, KW_VTIMEZONE, KW_VTODO,
+    KW_VALARM, KW_VAVAILABILITY, KW_VCALENDAR, KW_VEVENT, KW_VFREEBUSY, KW_VJOURNAL, KW_VTIMEZONE,
+    KW_VTODO, KW_X_WR_CALDESC, KW_X_WR_CALNAME, KW_X_WR_TIMEZONE,
 };
 use crate::property::{
-    CalendarScale, Method, ProductId, Property, PropertyKind, Version, VersionValue, XNameProperty,
+    CalendarScale, Color, DateTime, Description, Method, Name, ProductId, Property, PropertyKind,
+    RefreshInterval, Source, Version, VersionValue, XNameProperty,
 };
+use crate::semantic::cardinality::keep_first;
 use crate::semantic::tz_validator::{TzContext, ValidateTzids};
 use crate::semantic::{
-    SemanticError, UnrecognizedComponent, VAlarm, VEvent, VFreeBusy, VJournal, VTimeZone, VTodo,
-    XComponent,
+    SemanticError, UnrecognizedComponent, VAlarm, VAvailability, VEvent, VFreeBusy, VJournal,
+    VTimeZone, VTodo, XComponent,
 };
 use crate::string_storage::{Segments, StringStorage};
 use crate::typed::TypedComponent;
-use crate::value::ValueText;
+use crate::value::{Value, ValueText};
 
 /// Main iCalendar object that contains components and properties
 #[derive(Debug, Clone)]
@@ -32,6 +36,16 @@ pub struct ICalendar<S: StringStorage> {
     pub calscale: Option<CalendarScale<S>>,
     /// Method for the iCalendar object (e.g., PUBLISH, REQUEST)
     pub method: Option<Method<S>>,
+    /// Display names of the calendar, one per language (RFC 7986)
+    pub names: Vec<Name<S>>,
+    /// Descriptions of the calendar, one per language (RFC 7986)
+    pub descriptions: Vec<Description<S>>,
+    /// Color for displaying the calendar (RFC 7986)
+    pub color: Option<Color<S>>,
+    /// Suggested minimum interval for polling the calendar (RFC 7986)
+    pub refresh_interval: Option<RefreshInterval<S>>,
+    /// Where the calendar can be refreshed from (RFC 7986)
+    pub source: Option<Source<S>>,
     /// All calendar components (events, todos, journals, etc.)
     pub components: Vec<CalendarComponent<S>>,
     /// Custom X- properties (preserved for round-trip)
@@ -46,7 +60,7 @@ impl ICalendar<String> {
     pub fn new() -> Self {
         Self {
             prod_id: ProductId {
-                value: ValueText::new("//-yzx9.xyz//aimcal//EN".to_string()),
+                value: ValueText::new("-//yzx9.xyz//aimcal//EN".to_string()),
                 x_parameters: Vec::new(),
                 retained_parameters: Vec::new(),
                 span: (),
@@ -59,6 +73,11 @@ impl ICalendar<String> {
             },
             calscale: None,
             method: None,
+            names: Vec::new(),
+            descriptions: Vec::new(),
+            color: None,
+            refresh_interval: None,
+            source: None,
             components: Vec::new(),
             x_properties: Vec::new(),
             retained_properties: Vec::new(),
@@ -102,6 +121,101 @@ impl ICalendar<String> {
             })
             .collect()
     }
+
+    /// Get all VAVAILABILITY components from this calendar.
+    #[must_use]
+    pub fn availabilities(&self) -> Vec<&VAvailability<String>> {
+        self.components
+            .iter()
+            .filter_map(|comp| {
+                if let CalendarComponent::VAvailability(availability) = comp {
+                    Some(availability)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Events, todos and journal entries keyed by their UID and RECURRENCE-ID.
+    ///
+    /// When several components share a key, the first of them is kept.
+    #[must_use]
+    pub fn components_by_uid(
+        &self,
+    ) -> HashMap<(&str, Option<&DateTime>), &CalendarComponent<String>> {
+        let mut map = HashMap::with_capacity(self.components.len());
+        for component in &self.components {
+            if let Some(identity) = component.identity() {
+                map.entry((identity.uid(), identity.recurrence_id()))
+                    .or_insert(component);
+            }
+        }
+        map
+    }
+
+    /// Display name of the calendar.
+    ///
+    /// Uses the first `NAME` (RFC 7986), falling back to the widespread `X-WR-CALNAME`.
+    #[must_use]
+    pub fn name(&self) -> Option<String> {
+        match self.names.first() {
+            Some(name) => Some(name.content.to_string()),
+            None => self.x_text(KW_X_WR_CALNAME),
+        }
+    }
+
+    /// Description of the calendar.
+    ///
+    /// Uses the first `DESCRIPTION` (RFC 7986), falling back to `X-WR-CALDESC`.
+    #[must_use]
+    pub fn description(&self) -> Option<String> {
+        match self.descriptions.first() {
+            Some(description) => Some(description.content.to_string()),
+            None => self.x_text(KW_X_WR_CALDESC),
+        }
+    }
+
+    /// Time zone the publisher intends for floating times, from `X-WR-TIMEZONE`.
+    #[must_use]
+    pub fn timezone(&self) -> Option<String> {
+        self.x_text(KW_X_WR_TIMEZONE)
+    }
+
+    /// Text value of the first X- property with the given name, case-insensitively.
+    #[must_use]
+    pub fn x_text(&self, name: &str) -> Option<String> {
+        self.x_properties
+            .iter()
+            .filter(|p| p.name.eq_ignore_ascii_case(name))
+            .find_map(|p| match &p.value {
+                Value::Text { values, .. } => values.first().map(ToString::to_string),
+                // Without a VALUE parameter, x-name values are kept as raw strings
+                Value::XName { raw, .. } | Value::Unrecognized { raw, .. } => Some(unescape(raw)),
+                _ => None,
+            })
+            .filter(|text| !text.trim().is_empty())
+    }
+}
+
+/// Resolves the escapes of a raw text value (RFC 5545 Section 3.3.11).
+fn unescape(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => {
+                text.push('\n');
+                chars.next();
+            }
+            ('\\', Some(escaped @ ('\\' | ';' | ','))) => {
+                text.push(escaped);
+                chars.next();
+            }
+            _ => text.push(c),
+        }
+    }
+    text
 }
 
 /// Parse a `TypedComponent` into typed `ICalendar`
@@ -117,6 +231,19 @@ impl<'src> TryFrom<TypedComponent<'src>> for ICalendar<Segments<'src>> {
     type Error = Vec<SemanticError<'src>>;
 
     fn try_from(comp: TypedComponent<'src>) -> Result<Self, Self::Error> {
+        Self::from_typed(comp).0
+    }
+}
+
+impl<'src> ICalendar<Segments<'src>> {
+    /// Parses a `TypedComponent` like [`TryFrom`], returning the errors of the child components
+    /// left out of the calendar as well.
+    pub(crate) fn from_typed(
+        comp: TypedComponent<'src>,
+    ) -> (
+        Result<Self, Vec<SemanticError<'src>>>,
+        Vec<SemanticError<'src>>,
+    ) {
         let mut errors = Vec::new();
 
         let span = comp.span();
@@ -132,34 +259,17 @@ impl<'src> TryFrom<TypedComponent<'src>> for ICalendar<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::ProdId(prod_id) => match props.prod_id {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::ProdId,
-                        span: prod_id.span(),
-                    }),
-                    None => props.prod_id = Some(prod_id),
-                },
-                Property::Version(version) => match props.version {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Version,
-                        span: version.span(),
-                    }),
-                    None => props.version = Some(version),
-                },
-                Property::CalScale(calscale) => match props.calscale {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::CalScale,
-                        span: calscale.span(),
-                    }),
-                    None => props.calscale = Some(calscale),
-                },
-                Property::Method(method) => match props.method {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Method,
-                        span: method.span(),
-                    }),
-                    None => props.method = Some(method),
-                },
+                Property::ProdId(prod_id) => keep_first(&mut props.prod_id, prod_id),
+                Property::Version(version) => keep_first(&mut props.version, version),
+                Property::CalScale(calscale) => keep_first(&mut props.calscale, calscale),
+                Property::Method(method) => keep_first(&mut props.method, method),
+                Property::Name(name) => props.names.push(name),
+                Property::Description(description) => props.descriptions.push(description),
+                Property::Color(color) => keep_first(&mut props.color, color),
+                Property::RefreshInterval(interval) => {
+                    keep_first(&mut props.refresh_interval, interval);
+                }
+                Property::Source(source) => keep_first(&mut props.source, source),
                 // Preserve unknown properties for round-trip
                 Property::XName(prop) => props.x_properties.push(prop),
                 prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
@@ -185,40 +295,41 @@ impl<'src> TryFrom<TypedComponent<'src>> for ICalendar<Segments<'src>> {
             });
         }
 
-        // Parse child components
-        let components = match parse_component_children(comp.children) {
-            Ok(v) => v,
-            Err(e) => {
-                errors.extend(e);
-                Vec::new()
-            }
-        };
+        // Parse child components, failing only if none of them parses
+        let (components, mut dropped) = parse_component_children(comp.children);
+        if components.is_empty() {
+            errors.append(&mut dropped);
+        }
 
-        if errors.is_empty() {
+        let result = if errors.is_empty() {
             Ok(ICalendar {
-                prod_id: props.prod_id.unwrap(), // SAFETY: checked above
-                version: props.version.unwrap(), // SAFETY: checked above
+                prod_id: props.prod_id.expect("required property checked above"),
+                version: props.version.expect("required property checked above"),
                 calscale: props.calscale,
                 method: props.method,
+                names: props.names,
+                descriptions: props.descriptions,
+                color: props.color,
+                refresh_interval: props.refresh_interval,
+                source: props.source,
                 components,
                 x_properties: props.x_properties,
                 retained_properties: props.unrecognized_properties,
             })
         } else {
             Err(errors)
-        }
+        };
+        (result, dropped)
     }
 }
 
 /// Parse component children into `CalendarComponent` enum
 ///
-/// # Errors
-///
-/// Returns a vector of errors if no components could be parsed successfully.
-/// Individual component parsing errors are collected and included in the result.
+/// The components that cannot be parsed are left out, and their errors are returned along with
+/// the others, so that they can be reported, see [`crate::parse_partial`].
 pub(crate) fn parse_component_children(
     children: Vec<TypedComponent<'_>>,
-) -> Result<Vec<CalendarComponent<Segments<'_>>>, Vec<SemanticError<'_>>> {
+) -> (Vec<CalendarComponent<Segments<'_>>>, Vec<SemanticError<'_>>) {
     let mut components = Vec::with_capacity(children.len());
     let mut errors = Vec::new();
 
@@ -264,6 +375,14 @@ pub(crate) fn parse_component_children(
                     None
                 }
             }
+        } else if child.name.eq_str_ignore_ascii_case(KW_VAVAILABILITY) {
+            match child.try_into() {
+                Ok(v) => Some(CalendarComponent::VAvailability(v)),
+                Err(e) => {
+                    errors.extend(e);
+                    None
+                }
+            }
         } else if child.name.eq_str_ignore_ascii_case(KW_VALARM) {
             match child.try_into() {
                 Ok(v) => Some(CalendarComponent::VAlarm(v)),
@@ -295,12 +414,7 @@ pub(crate) fn parse_component_children(
         }
     }
 
-    // Return error only if no components were parsed successfully
-    if components.is_empty() && !errors.is_empty() {
-        return Err(errors);
-    }
-
-    Ok(components)
+    (components, errors)
 }
 
 /// Calendar components that can appear in an iCalendar object
@@ -318,6 +432,8 @@ pub enum CalendarComponent<S: StringStorage> {
     VTimeZone(VTimeZone<S>),
     /// Alarm component
     VAlarm(VAlarm<S>),
+    /// Availability component (RFC 7953)
+    VAvailability(VAvailability<S>),
     /// X-component (custom experimental component starting with "X-")
     XComponent(XComponent<S>),
     /// Unrecognized component (IANA-registered or other non-X- component)
@@ -360,6 +476,12 @@ impl<S: StringStorage> From<VAlarm<S>> for CalendarComponent<S> {
     }
 }
 
+impl<S: StringStorage> From<VAvailability<S>> for CalendarComponent<S> {
+    fn from(value: VAvailability<S>) -> Self {
+        CalendarComponent::VAvailability(value)
+    }
+}
+
 impl<S: StringStorage> From<XComponent<S>> for CalendarComponent<S> {
     fn from(value: XComponent<S>) -> Self {
         CalendarComponent::XComponent(value)
@@ -380,6 +502,11 @@ struct PropertyCollector<S: StringStorage> {
     version:        Option<Version<S>>,
     calscale:       Option<CalendarScale<S>>,
     method:         Option<Method<S>>,
+    names:          Vec<Name<S>>,
+    descriptions:   Vec<Description<S>>,
+    color:          Option<Color<S>>,
+    refresh_interval: Option<RefreshInterval<S>>,
+    source:         Option<Source<S>>,
     x_properties:   Vec<XNameProperty<S>>,
     unrecognized_properties: Vec<Property<S>>,
 }
@@ -393,6 +520,18 @@ impl ICalendar<Segments<'_>> {
             version: self.version.to_owned(),
             calscale: self.calscale.as_ref().map(CalendarScale::to_owned),
             method: self.method.as_ref().map(Method::to_owned),
+            names: self.names.iter().map(Name::to_owned).collect(),
+            descriptions: self
+                .descriptions
+                .iter()
+                .map(Description::to_owned)
+                .collect(),
+            color: self.color.as_ref().map(Color::to_owned),
+            refresh_interval: self
+                .refresh_interval
+                .as_ref()
+                .map(RefreshInterval::to_owned),
+            source: self.source.as_ref().map(Source::to_owned),
             components: self
                 .components
                 .iter()
@@ -423,6 +562,7 @@ impl CalendarComponent<Segments<'_>> {
             Self::VFreeBusy(v) => CalendarComponent::VFreeBusy(v.to_owned()),
             Self::VTimeZone(v) => CalendarComponent::VTimeZone(v.to_owned()),
             Self::VAlarm(v) => CalendarComponent::VAlarm(v.to_owned()),
+            Self::VAvailability(v) => CalendarComponent::VAvailability(v.to_owned()),
             Self::XComponent(v) => CalendarComponent::XComponent(v.to_owned()),
             Self::Unrecognized(v) => CalendarComponent::Unrecognized(v.to_owned()),
         }
@@ -435,6 +575,7 @@ impl ValidateTzids for CalendarComponent<Segments<'_>> {
             CalendarComponent::Event(event) => event.validate_tzids(ctx),
             CalendarComponent::Todo(todo) => todo.validate_tzids(ctx),
             CalendarComponent::VJournal(journal) => journal.validate_tzids(ctx),
+            CalendarComponent::VAvailability(availability) => availability.validate_tzids(ctx),
             // VTimeZone, VFreeBusy, VAlarm don't have TZID parameters to validate
             _ => Ok(()),
         }
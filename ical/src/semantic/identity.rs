@@ -0,0 +1,229 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Identity and semantic equality of owned calendar components.
+//!
+//! Owned components and calendars compare by semantic equality: they are equal when they have the
+//! same properties, with the same parameters and values, and the same sub-components, such as
+//! alarms. Neither spans nor the order of properties and sub-components are compared, while the
+//! order of the values within a property and of its parameters is.
+
+use std::io;
+
+use crate::fmt::component::{
+    write_calendar_component, write_icalendar, write_vevent, write_vjournal, write_vtodo,
+};
+use crate::fmt::{FormatOptions, Formatter};
+use crate::keyword::{KW_BEGIN, KW_END};
+use crate::property::{DateTime, Property};
+use crate::semantic::{CalendarComponent, ICalendar, VEvent, VJournal, VTodo};
+
+/// Identity of a calendar component, shared by events, todos and journal entries.
+///
+/// A component is identified by its UID together with its RECURRENCE-ID, since overrides of the
+/// occurrences of a recurring component share its UID. The SEQUENCE tells revisions apart.
+pub trait ComponentIdentity {
+    /// Unique identifier of the component, see RFC 5545 section 3.8.4.7.
+    fn uid(&self) -> &str;
+
+    /// Occurrence of a recurring component this component overrides, see RFC 5545 section 3.8.4.4.
+    fn recurrence_id(&self) -> Option<&DateTime>;
+
+    /// Revision sequence number, `0` when absent, see RFC 5545 section 3.8.7.4.
+    fn sequence(&self) -> u32;
+}
+
+impl ComponentIdentity for VEvent<String> {
+    fn uid(&self) -> &str {
+        self.uid.content.as_str()
+    }
+
+    fn recurrence_id(&self) -> Option<&DateTime> {
+        recurrence_id(&self.retained_properties)
+    }
+
+    fn sequence(&self) -> u32 {
+        self.sequence.as_ref().map_or(0, |sequence| sequence.value)
+    }
+}
+
+impl ComponentIdentity for VTodo<String> {
+    fn uid(&self) -> &str {
+        self.uid.content.as_str()
+    }
+
+    fn recurrence_id(&self) -> Option<&DateTime> {
+        recurrence_id(&self.retained_properties)
+    }
+
+    fn sequence(&self) -> u32 {
+        self.sequence.as_ref().map_or(0, |sequence| sequence.value)
+    }
+}
+
+impl ComponentIdentity for VJournal<String> {
+    fn uid(&self) -> &str {
+        self.uid.content.as_str()
+    }
+
+    fn recurrence_id(&self) -> Option<&DateTime> {
+        recurrence_id(&self.retained_properties)
+    }
+
+    fn sequence(&self) -> u32 {
+        // VJOURNAL keeps SEQUENCE with the properties it does not interpret
+        self.retained_properties
+            .iter()
+            .find_map(|prop| match prop {
+                Property::Sequence(sequence) => Some(sequence.value),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl CalendarComponent<String> {
+    /// Identity of the component, for events, todos and journal entries.
+    #[must_use]
+    pub fn identity(&self) -> Option<&dyn ComponentIdentity> {
+        match self {
+            CalendarComponent::Event(event) => Some(event),
+            CalendarComponent::Todo(todo) => Some(todo),
+            CalendarComponent::VJournal(journal) => Some(journal),
+            _ => None,
+        }
+    }
+}
+
+/// RECURRENCE-ID is kept with the properties the components do not interpret.
+fn recurrence_id(retained_properties: &[Property<String>]) -> Option<&DateTime> {
+    retained_properties.iter().find_map(|prop| match prop {
+        Property::RecurrenceId(id) => Some(&id.0.value),
+        _ => None,
+    })
+}
+
+macro_rules! semantic_eq {
+    ($($ty:ty => $write:ident),* $(,)?) => {
+        $(
+            /// Semantic equality, ignoring spans and the order of properties and sub-components,
+            /// see [`ComponentIdentity`].
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    Canonical::of(|f| $write(f, self)) == Canonical::of(|f| $write(f, other))
+                }
+            }
+
+            impl Eq for $ty {}
+        )*
+    };
+}
+
+semantic_eq! {
+    ICalendar<String> => write_icalendar,
+    CalendarComponent<String> => write_calendar_component,
+    VEvent<String> => write_vevent,
+    VTodo<String> => write_vtodo,
+    VJournal<String> => write_vjournal,
+}
+
+/// Canonical form of a component, its formatted property lines and its sub-components, both
+/// sorted so that their order does not matter.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Canonical {
+    name: String,
+    /// Unfolded property lines, such as `SUMMARY;LANGUAGE=en:Standup`
+    pub(crate) properties: Vec<String>,
+    pub(crate) components: Vec<Canonical>,
+}
+
+impl Canonical {
+    /// Canonical form of the component written by `write`.
+    pub(crate) fn of(write: impl FnOnce(&mut Formatter<&mut Vec<u8>>) -> io::Result<()>) -> Self {
+        let mut buffer = Vec::new();
+        let mut f = Formatter::new(&mut buffer, FormatOptions::default().folding(None));
+        let written = write(&mut f).and_then(|()| f.into_writer().map(|_| ()));
+
+        let mut stack = vec![Canonical::default()];
+        for line in String::from_utf8_lossy(&buffer).split("\r\n") {
+            if let Some(name) = block(line, KW_BEGIN) {
+                stack.push(Canonical {
+                    name: name.to_owned(),
+                    ..Canonical::default()
+                });
+            } else if block(line, KW_END).is_some() && stack.len() > 1 {
+                close(&mut stack);
+            } else if let Some(current) = stack.last_mut()
+                && !line.is_empty()
+            {
+                current.properties.push(line.to_owned());
+            }
+        }
+
+        // What could not be formatted, such as a parameter value that cannot be written, still
+        // gets the same canonical form every time
+        if let Err(err) = written
+            && let Some(current) = stack.last_mut()
+        {
+            current.properties.push(format!("\0{err}"));
+        }
+        while stack.len() > 1 {
+            close(&mut stack);
+        }
+
+        let mut root = stack.pop().unwrap_or_default();
+        match root.components.pop() {
+            Some(component) if root.components.is_empty() && root.properties.is_empty() => {
+                component
+            }
+            Some(component) => {
+                root.components.push(component);
+                root
+            }
+            None => root,
+        }
+    }
+}
+
+/// The name of the block a `BEGIN` or `END` line opens or closes.
+fn block<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    line.strip_prefix(keyword)?.strip_prefix(':')
+}
+
+/// Sorts the innermost open block and moves it into its parent.
+fn close(stack: &mut Vec<Canonical>) {
+    if let Some(mut done) = stack.pop() {
+        done.properties.sort_unstable();
+        done.components.sort_unstable();
+        if let Some(parent) = stack.last_mut() {
+            parent.components.push(done);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_sorts_properties_and_keeps_blocks_apart() {
+        let canonical = Canonical::of(|f| {
+            use std::io::Write;
+            f.write_all(
+                b"BEGIN:VEVENT\r\nUID:1\r\nSUMMARY:B\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\n\
+                  END:VALARM\r\nDESCRIPTION:A\r\nEND:VEVENT\r\n",
+            )
+        });
+        assert_eq!(canonical.name, "VEVENT");
+        assert_eq!(
+            canonical.properties,
+            ["DESCRIPTION:A", "SUMMARY:B", "UID:1"]
+        );
+        let [alarm] = canonical.components.as_slice() else {
+            panic!("expected one alarm, got {:?}", canonical.components);
+        };
+        assert_eq!(alarm.name, "VALARM");
+        assert_eq!(alarm.properties, ["ACTION:DISPLAY"]);
+    }
+}
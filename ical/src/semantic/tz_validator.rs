@@ -6,13 +6,23 @@
 //!
 //! This module validates TZID parameters after semantic analysis completes,
 //! ensuring they reference either VTIMEZONE components or IANA timezones.
+//!
+//! TZIDs that disagree with an explicit UTC offset are reported as warnings: a
+//! TZID on a date-time marked UTC, which is taken as UTC, and a VTIMEZONE of an
+//! IANA timezone changing to an offset the timezone never has.
 
 use std::collections::HashSet;
 use std::fmt::Write;
 
-use crate::property::{DateTime, DateTimeProperty};
+use crate::keyword::{
+    KW_COMPLETED, KW_CREATED, KW_DTEND, KW_DTSTAMP, KW_DTSTART, KW_DUE, KW_LAST_MODIFIED,
+    KW_RECURRENCE_ID,
+};
+use crate::parameter::Parameter;
+use crate::property::{DateTime, DateTimeProperty, Property};
 use crate::semantic::{CalendarComponent, ICalendar, SemanticError};
 use crate::string_storage::Segments;
+use crate::typed::TypedComponent;
 
 /// Context for TZID validation containing available timezone definitions.
 pub struct TzContext<'a> {
@@ -262,3 +272,99 @@ pub fn validate_tzids(
         Err(errors)
     }
 }
+
+/// Reports every TZID on a date-time marked UTC in the component and its children.
+///
+/// Such a value is taken as UTC, and its TZID kept in the retained parameters.
+pub(crate) fn check_utc_tzids<'src>(
+    component: &TypedComponent<'src>,
+    warnings: &mut Vec<SemanticError<'src>>,
+) {
+    for property in &component.properties {
+        let (name, retained) = match property {
+            Property::DtStart(p) if p.is_utc() => (KW_DTSTART, &p.retained_parameters),
+            Property::DtEnd(p) if p.is_utc() => (KW_DTEND, &p.retained_parameters),
+            Property::Due(p) if p.is_utc() => (KW_DUE, &p.retained_parameters),
+            Property::RecurrenceId(p) if p.is_utc() => (KW_RECURRENCE_ID, &p.retained_parameters),
+            Property::Completed(p) => (KW_COMPLETED, &p.retained_parameters),
+            Property::Created(p) => (KW_CREATED, &p.retained_parameters),
+            Property::DtStamp(p) => (KW_DTSTAMP, &p.retained_parameters),
+            Property::LastModified(p) => (KW_LAST_MODIFIED, &p.retained_parameters),
+            _ => continue,
+        };
+        for param in retained {
+            if let Parameter::TimeZoneIdentifier { value, span, .. } = param {
+                warnings.push(SemanticError::TimezoneMismatch {
+                    message: format!("TZID '{value}' on {name} marked UTC, which is taken as UTC"),
+                    span: *span,
+                    offset: property.span(),
+                });
+            }
+        }
+    }
+
+    for child in &component.children {
+        check_utc_tzids(child, warnings);
+    }
+}
+
+/// Reports the observances of the VTIMEZONEs of IANA timezones that change to an offset the
+/// timezone never has from their onset on.
+///
+/// Date-times with such a TZID are resolved with the IANA timezone, so the VTIMEZONE likely
+/// means other times than they are taken as.
+#[cfg(feature = "jiff")]
+pub(crate) fn check_tz_offsets(
+    cal: &ICalendar<Segments<'_>>,
+    warnings: &mut Vec<SemanticError<'static>>,
+) {
+    for comp in &cal.components {
+        let CalendarComponent::VTimeZone(vtz) = comp else {
+            continue;
+        };
+        let tz_id = vtz.tz_id.content.to_string();
+        let Ok(tz) = jiff::tz::TimeZone::get(&tz_id) else {
+            continue;
+        };
+        for observance in vtz.standard.iter().chain(&vtz.daylight) {
+            let (Some(onset), Some(from), Some(to)) = (
+                observance.dt_start.civil_date_time(),
+                jiff_offset(observance.tz_offset_from.value),
+                jiff_offset(observance.tz_offset_to.value),
+            ) else {
+                continue;
+            };
+            let Ok(since) = from.to_timestamp(onset) else {
+                continue;
+            };
+            // Zones with rules have transitions forever, so only the first ones are looked at
+            let has_offset = tz.to_offset(since) == to
+                || tz
+                    .following(since)
+                    .take(MAX_TRANSITIONS)
+                    .any(|t| t.offset() == to);
+            if !has_offset {
+                warnings.push(SemanticError::TimezoneMismatch {
+                    message: format!(
+                        "Timezone '{tz_id}' never has the offset {to} its VTIMEZONE changes to at {onset}"
+                    ),
+                    span: vtz.tz_id.span(),
+                    offset: observance.tz_offset_to.span(),
+                });
+            }
+        }
+    }
+}
+
+/// Transitions of an IANA timezone looked at for the offsets of a VTIMEZONE.
+#[cfg(feature = "jiff")]
+const MAX_TRANSITIONS: usize = 1000;
+
+#[cfg(feature = "jiff")]
+fn jiff_offset(offset: crate::value::ValueUtcOffset) -> Option<jiff::tz::Offset> {
+    let seconds = i32::from(offset.hour) * 3600
+        + i32::from(offset.minute) * 60
+        + i32::from(offset.second.unwrap_or(0));
+    let seconds = if offset.positive { seconds } else { -seconds };
+    jiff::tz::Offset::from_seconds(seconds).ok()
+}
@@ -4,14 +4,24 @@
 
 //! Alarm component (VALARM) for iCalendar semantic components.
 
+#[cfg(feature = "jiff")]
+use jiff::{SignedDuration, Timestamp};
+
 use crate::keyword::KW_VALARM;
+#[cfg(feature = "jiff")]
+use crate::parameter::AlarmTriggerRelationship;
 use crate::property::{
     Action, ActionValue, Attachment, Attendee, Description, Duration, Property, PropertyKind,
     Repeat, Summary, Trigger, XNameProperty,
 };
+#[cfg(feature = "jiff")]
+use crate::property::{DateTime, TriggerValue};
 use crate::semantic::SemanticError;
+use crate::semantic::cardinality::keep_first;
 use crate::string_storage::{Segments, StringStorage};
 use crate::typed::TypedComponent;
+#[cfg(feature = "jiff")]
+use crate::value::ValueDuration;
 
 /// Alarm component (VALARM)
 #[derive(Debug, Clone)]
@@ -42,7 +52,6 @@ pub struct VAlarm<S: StringStorage> {
 impl<'src> TryFrom<TypedComponent<'src>> for VAlarm<Segments<'src>> {
     type Error = Vec<SemanticError<'src>>;
 
-    #[expect(clippy::too_many_lines)]
     fn try_from(comp: TypedComponent<'src>) -> Result<Self, Self::Error> {
         let mut errors = Vec::new();
 
@@ -59,54 +68,16 @@ impl<'src> TryFrom<TypedComponent<'src>> for VAlarm<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::Action(action) => match props.action {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Action,
-                        span: action.span(),
-                    }),
-                    None => props.action = Some(action),
-                },
-                Property::Trigger(trigger) => match props.trigger {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Trigger,
-                        span: trigger.span(),
-                    }),
-                    None => props.trigger = Some(trigger),
-                },
-                Property::Duration(duration) => match props.duration {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Duration,
-                        span: duration.span(),
-                    }),
-                    None => props.duration = Some(duration),
-                },
-                Property::Repeat(repeat) => match props.repeat {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Repeat,
-                        span: repeat.span(),
-                    }),
-                    None => props.repeat = Some(repeat),
-                },
-                Property::Description(desc) => match props.description {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Description,
-                        span: desc.span(),
-                    }),
-                    None => props.description = Some(desc),
-                },
-                Property::Summary(s) => match props.summary {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Summary,
-                        span: s.span(),
-                    }),
-                    None => props.summary = Some(s),
-                },
+                Property::Action(action) => keep_first(&mut props.action, action),
+                Property::Trigger(trigger) => keep_first(&mut props.trigger, trigger),
+                Property::Duration(duration) => keep_first(&mut props.duration, duration),
+                Property::Repeat(repeat) => keep_first(&mut props.repeat, repeat),
+                Property::Description(desc) => keep_first(&mut props.description, desc),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
                 Property::Attendee(attendee) => props.attendees.push(attendee),
+                // Email alarms may have several attachments, the others are retained for round-trip
                 Property::Attach(attach) => match props.attach {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Attach,
-                        span: attach.span(),
-                    }),
+                    Some(_) => props.unrecognized_properties.push(Property::Attach(attach)),
                     None => props.attach = Some(attach),
                 },
                 // Preserve unknown properties for round-trip
@@ -181,7 +152,7 @@ impl<'src> TryFrom<TypedComponent<'src>> for VAlarm<Segments<'src>> {
         if errors.is_empty() {
             Ok(VAlarm {
                 action: props.action.unwrap_or(default_action),
-                trigger: props.trigger.unwrap(), // SAFETY: checked above
+                trigger: props.trigger.expect("required property checked above"),
                 repeat: props.repeat,
                 duration: props.duration,
                 description: props.description,
@@ -224,6 +195,130 @@ impl VAlarm<Segments<'_>> {
     }
 }
 
+#[cfg(feature = "jiff")]
+impl<S: StringStorage> VAlarm<S> {
+    /// Resolves the instants the alarm goes off, up to `limit` of them.
+    ///
+    /// A relative `TRIGGER` is a duration from the start of the parent component, or from its end
+    /// with `RELATED=END`, falling back to the start if the parent has no end. An absolute
+    /// `TRIGGER` ignores `RELATED`. The alarm then goes off `REPEAT` more times, `DURATION`
+    /// apart. Days in durations are 24 hours, as the instants carry no time zone.
+    ///
+    /// See also: RFC 5545 Section 3.8.6.3. Trigger, Section 3.8.6.2. Repeat Count
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `REPEAT` is set without a `DURATION`, or the `DURATION` is negative
+    /// - The absolute trigger is a date, or a date-time without a known time zone
+    /// - An instant is out of range
+    pub fn resolve_triggers(
+        &self,
+        parent_start: Timestamp,
+        parent_end: Option<Timestamp>,
+        limit: usize,
+    ) -> Result<Vec<Timestamp>, AlarmTriggerError> {
+        let first = match &self.trigger.value {
+            TriggerValue::Duration(duration) => {
+                let anchor = match self.trigger.related {
+                    Some(AlarmTriggerRelationship::End) => parent_end.unwrap_or(parent_start),
+                    _ => parent_start,
+                };
+                add(anchor, signed_duration(duration))?
+            }
+            TriggerValue::DateTime(dt) => absolute(dt)?,
+        };
+
+        let (repeat, interval) = match (&self.repeat, &self.duration) {
+            (Some(repeat), Some(duration)) => (repeat.value, signed_duration(&duration.value)),
+            (Some(repeat), None) if repeat.value > 0 => {
+                return Err(AlarmTriggerError::RepeatWithoutDuration);
+            }
+            _ => (0, SignedDuration::ZERO),
+        };
+        if interval.is_negative() {
+            return Err(AlarmTriggerError::NegativeRepeatDuration);
+        }
+        // Repeating without an interval goes off at the same instant, so it is only counted once
+        let repeat = if interval.is_zero() { 0 } else { repeat };
+
+        let count =
+            usize::try_from(repeat).map_or(limit, |repeat| repeat.saturating_add(1).min(limit));
+        let mut triggers = Vec::with_capacity(count);
+        let mut at = first;
+        for i in 0..count {
+            if i > 0 {
+                at = add(at, interval)?;
+            }
+            triggers.push(at);
+        }
+        Ok(triggers)
+    }
+}
+
+/// Error type for resolving the instants an alarm goes off.
+#[cfg(feature = "jiff")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AlarmTriggerError {
+    /// The alarm repeats without a duration between repetitions
+    #[error("REPEAT requires a DURATION between repetitions")]
+    RepeatWithoutDuration,
+    /// The duration between repetitions is negative
+    #[error("DURATION between repetitions must not be negative")]
+    NegativeRepeatDuration,
+    /// The absolute trigger cannot be placed in time
+    #[error("Absolute trigger must be a UTC date-time, got {0}")]
+    UnresolvableTrigger(String),
+    /// Date arithmetic failed
+    #[error("Date arithmetic error: {0}")]
+    DateArithmetic(String),
+}
+
+#[cfg(feature = "jiff")]
+fn absolute(dt: &DateTime) -> Result<Timestamp, AlarmTriggerError> {
+    let unresolvable = || AlarmTriggerError::UnresolvableTrigger(format!("{dt:?}"));
+    let civil = dt.civil_date_time().ok_or_else(unresolvable)?;
+    let tz = match dt {
+        DateTime::Utc { .. } => jiff::tz::TimeZone::UTC,
+        // Not allowed by RFC 5545, but unambiguous when the time zone is known
+        DateTime::Zoned {
+            tz_jiff: Some(tz), ..
+        } => tz.clone(),
+        _ => return Err(unresolvable()),
+    };
+    civil
+        .to_zoned(tz)
+        .map(|zoned| zoned.timestamp())
+        .map_err(|e| AlarmTriggerError::DateArithmetic(e.to_string()))
+}
+
+#[cfg(feature = "jiff")]
+fn add(at: Timestamp, duration: SignedDuration) -> Result<Timestamp, AlarmTriggerError> {
+    at.checked_add(duration)
+        .map_err(|e| AlarmTriggerError::DateArithmetic(e.to_string()))
+}
+
+#[cfg(feature = "jiff")]
+fn signed_duration(duration: &ValueDuration) -> SignedDuration {
+    let (positive, seconds) = match *duration {
+        ValueDuration::DateTime {
+            positive,
+            day,
+            hour,
+            minute,
+            second,
+        } => (
+            positive,
+            i64::from(day) * 86400
+                + i64::from(hour) * 3600
+                + i64::from(minute) * 60
+                + i64::from(second),
+        ),
+        ValueDuration::Week { positive, week } => (positive, i64::from(week) * 7 * 86400),
+    };
+    SignedDuration::from_secs(if positive { seconds } else { -seconds })
+}
+
 /// Helper struct to collect properties during single-pass iteration
 #[rustfmt::skip]
 #[derive(Debug, Default)]
@@ -239,3 +334,175 @@ struct PropertyCollector<S: StringStorage> {
     x_properties:   Vec<XNameProperty<S>>,
     unrecognized_properties: Vec<Property<S>>,
 }
+
+#[cfg(test)]
+#[cfg(feature = "jiff")]
+mod tests {
+    use jiff::civil::date;
+
+    use super::*;
+    use crate::semantic::CalendarComponent;
+
+    /// Parses the alarm of an event, from the properties of the `VALARM`.
+    fn alarm(props: &str) -> VAlarm<String> {
+        let src = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\nBEGIN:VEVENT\r\n\
+             UID:alarm@example.com\r\nDTSTAMP:19970101T000000Z\r\n\
+             DTSTART:19970317T133000Z\r\nBEGIN:VALARM\r\n{props}END:VALARM\r\n\
+             END:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = crate::parse(&src).unwrap();
+        let calendar = calendars.first().unwrap().to_owned();
+        let Some(CalendarComponent::Event(event)) = calendar.components.into_iter().next() else {
+            panic!("expected an event");
+        };
+        event.alarms.into_iter().next().unwrap()
+    }
+
+    fn utc(year: i16, month: i8, day: i8, hour: i8, minute: i8) -> Timestamp {
+        date(year, month, day)
+            .at(hour, minute, 0, 0)
+            .in_tz("UTC")
+            .unwrap()
+            .timestamp()
+    }
+
+    const DISPLAY: &str = "ACTION:DISPLAY\r\nDESCRIPTION:Reminder\r\n";
+
+    #[test]
+    fn resolves_relative_trigger_before_start() {
+        // RFC 5545 3.8.6.3: 15 minutes prior to the start
+        let alarm = alarm(&format!("{DISPLAY}TRIGGER:-PT15M\r\n"));
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, 10).unwrap();
+        assert_eq!(triggers, [utc(1997, 3, 17, 13, 15)]);
+    }
+
+    #[test]
+    fn resolves_relative_trigger_after_end() {
+        // RFC 5545 3.8.6.3: 5 minutes after the end
+        let alarm = alarm(&format!("{DISPLAY}TRIGGER;RELATED=END:PT5M\r\n"));
+        let start = utc(1997, 3, 17, 13, 30);
+        let end = utc(1997, 3, 17, 14, 30);
+        let triggers = alarm.resolve_triggers(start, Some(end), 10).unwrap();
+        assert_eq!(triggers, [utc(1997, 3, 17, 14, 35)]);
+    }
+
+    #[test]
+    fn falls_back_to_start_without_parent_end() {
+        let alarm = alarm(&format!("{DISPLAY}TRIGGER;RELATED=END:-P1D\r\n"));
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, 10).unwrap();
+        assert_eq!(triggers, [utc(1997, 3, 16, 13, 30)]);
+    }
+
+    #[test]
+    fn resolves_absolute_trigger_ignoring_related() {
+        // RFC 5545 3.8.6.3: an absolute trigger on 1998-01-01 at 05:00 UTC
+        let alarm = alarm(&format!(
+            "{DISPLAY}TRIGGER;VALUE=DATE-TIME:19980101T050000Z\r\n"
+        ));
+        let start = utc(1997, 3, 17, 13, 30);
+        let end = utc(1997, 3, 17, 14, 30);
+        let triggers = alarm.resolve_triggers(start, Some(end), 10).unwrap();
+        assert_eq!(triggers, [utc(1998, 1, 1, 5, 0)]);
+    }
+
+    #[test]
+    fn repeats_absolute_trigger() {
+        // RFC 5545 3.6.6: an audio alarm repeating 4 more times, 15 minutes apart
+        let alarm = alarm(
+            "ACTION:AUDIO\r\nTRIGGER;VALUE=DATE-TIME:19970317T133000Z\r\n\
+             REPEAT:4\r\nDURATION:PT15M\r\n",
+        );
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, 10).unwrap();
+        assert_eq!(
+            triggers,
+            [
+                utc(1997, 3, 17, 13, 30),
+                utc(1997, 3, 17, 13, 45),
+                utc(1997, 3, 17, 14, 0),
+                utc(1997, 3, 17, 14, 15),
+                utc(1997, 3, 17, 14, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeats_relative_trigger_up_to_limit() {
+        // RFC 5545 3.6.6: 30 minutes before, repeating 2 more times, 15 minutes apart
+        let alarm = alarm(&format!(
+            "{DISPLAY}TRIGGER:-PT30M\r\nREPEAT:2\r\nDURATION:PT15M\r\n"
+        ));
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, 10).unwrap();
+        assert_eq!(
+            triggers,
+            [
+                utc(1997, 3, 17, 13, 0),
+                utc(1997, 3, 17, 13, 15),
+                utc(1997, 3, 17, 13, 30),
+            ]
+        );
+
+        let triggers = alarm.resolve_triggers(start, None, 2).unwrap();
+        assert_eq!(triggers.len(), 2);
+        assert!(alarm.resolve_triggers(start, None, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn resolves_week_and_positive_durations() {
+        let alarm = alarm(&format!("{DISPLAY}TRIGGER:P1W\r\n"));
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, 10).unwrap();
+        assert_eq!(triggers, [utc(1997, 3, 24, 13, 30)]);
+    }
+
+    #[test]
+    fn collapses_repeats_without_interval() {
+        let alarm = alarm(&format!(
+            "{DISPLAY}TRIGGER:-PT5M\r\nREPEAT:2147483647\r\nDURATION:PT0S\r\n"
+        ));
+        let start = utc(1997, 3, 17, 13, 30);
+        let triggers = alarm.resolve_triggers(start, None, usize::MAX).unwrap();
+        assert_eq!(triggers, [utc(1997, 3, 17, 13, 25)]);
+    }
+
+    #[test]
+    fn rejects_repeat_without_duration() {
+        let mut alarm = alarm(&format!(
+            "{DISPLAY}TRIGGER:-PT5M\r\nREPEAT:2\r\nDURATION:PT5M\r\n"
+        ));
+        alarm.duration = None;
+        let start = utc(1997, 3, 17, 13, 30);
+        assert_eq!(
+            alarm.resolve_triggers(start, None, 10),
+            Err(AlarmTriggerError::RepeatWithoutDuration)
+        );
+    }
+
+    #[test]
+    fn rejects_negative_repeat_duration() {
+        let alarm = alarm(&format!(
+            "{DISPLAY}TRIGGER:-PT5M\r\nREPEAT:2\r\nDURATION:-PT5M\r\n"
+        ));
+        let start = utc(1997, 3, 17, 13, 30);
+        assert_eq!(
+            alarm.resolve_triggers(start, None, 10),
+            Err(AlarmTriggerError::NegativeRepeatDuration)
+        );
+    }
+
+    #[test]
+    fn rejects_floating_absolute_trigger() {
+        let mut alarm = alarm(&format!("{DISPLAY}TRIGGER:-PT5M\r\n"));
+        alarm.trigger.value = TriggerValue::DateTime(DateTime::Floating {
+            date: date(1998, 1, 1).into(),
+            time: jiff::civil::time(5, 0, 0, 0).into(),
+        });
+        let start = utc(1997, 3, 17, 13, 30);
+        let err = alarm.resolve_triggers(start, None, 10).unwrap_err();
+        assert!(matches!(err, AlarmTriggerError::UnresolvableTrigger(_)));
+    }
+}
@@ -0,0 +1,507 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Availability components (VAVAILABILITY and AVAILABLE, RFC 7953) for iCalendar semantic
+//! components.
+
+use crate::keyword::{KW_AVAILABLE, KW_VAVAILABILITY};
+use crate::property::{
+    BusyType, BusyTypeValue, Categories, Classification, Comment, Contact, Created, Description,
+    DtEnd, DtStamp, DtStart, Duration, ExDate, LastModified, Location, Organizer, Priority,
+    Property, PropertyKind, RDate, RRule, RecurrenceId, Sequence, Summary, Uid, Url, XNameProperty,
+};
+use crate::semantic::SemanticError;
+use crate::semantic::cardinality::keep_first;
+use crate::semantic::tz_validator::{TzContext, ValidateTzids};
+use crate::string_storage::{Segments, StringStorage};
+use crate::typed::TypedComponent;
+
+/// Availability component (VAVAILABILITY)
+///
+/// The time between its start and end is busy as given by its busy time type, apart from the
+/// time its `AVAILABLE` blocks leave free.
+#[derive(Debug, Clone)]
+pub struct VAvailability<S: StringStorage> {
+    /// Unique identifier for the availability
+    pub uid: Uid<S>,
+    /// Date/time the availability was created
+    pub dt_stamp: DtStamp<S>,
+    /// Start of the time the availability covers, unbounded if missing
+    pub dt_start: Option<DtStart<S>>,
+    /// End of the time the availability covers, unbounded if missing
+    pub dt_end: Option<DtEnd<S>>,
+    /// Duration of the time the availability covers (alternative to `dt_end`)
+    pub duration: Option<Duration<S>>,
+    /// Busy time type of the time left unavailable, `BUSY-UNAVAILABLE` if missing
+    pub busy_type: Option<BusyType<S>>,
+    /// Priority over overlapping availabilities (1-9, 1 is highest, 0 is the lowest)
+    pub priority: Option<Priority<S>>,
+    /// Summary/title of the availability
+    pub summary: Option<Summary<S>>,
+    /// Description of the availability
+    pub description: Option<Description<S>>,
+    /// Location of the availability
+    pub location: Option<Location<S>>,
+    /// Organizer of the availability
+    pub organizer: Option<Organizer<S>>,
+    /// URL associated with the availability
+    pub url: Option<Url<S>>,
+    /// Classification
+    pub classification: Option<Classification<S>>,
+    /// Creation date/time
+    pub created: Option<Created<S>>,
+    /// Last modification date/time
+    pub last_modified: Option<LastModified<S>>,
+    /// Sequence number for revisions
+    pub sequence: Option<Sequence<S>>,
+    /// Categories (can appear multiple times)
+    pub categories: Vec<Categories<S>>,
+    /// Comments (can appear multiple times)
+    pub comments: Vec<Comment<S>>,
+    /// Contacts (can appear multiple times)
+    pub contacts: Vec<Contact<S>>,
+    /// Custom X- properties (preserved for round-trip)
+    pub x_properties: Vec<XNameProperty<S>>,
+    /// Unrecognized / Non-standard properties (preserved for round-trip)
+    pub retained_properties: Vec<Property<S>>,
+    /// Blocks of available time
+    pub available: Vec<Available<S>>,
+}
+
+/// Available time component (AVAILABLE), a sub-component of `VAVAILABILITY`
+#[derive(Debug, Clone)]
+pub struct Available<S: StringStorage> {
+    /// Unique identifier for the available time
+    pub uid: Uid<S>,
+    /// Date/time the available time was created
+    pub dt_stamp: DtStamp<S>,
+    /// Start of the available time
+    pub dt_start: DtStart<S>,
+    /// End of the available time
+    pub dt_end: Option<DtEnd<S>>,
+    /// Duration of the available time (alternative to `dt_end`)
+    pub duration: Option<Duration<S>>,
+    /// Summary/title of the available time
+    pub summary: Option<Summary<S>>,
+    /// Description of the available time
+    pub description: Option<Description<S>>,
+    /// Location of the available time
+    pub location: Option<Location<S>>,
+    /// Creation date/time
+    pub created: Option<Created<S>>,
+    /// Last modification date/time
+    pub last_modified: Option<LastModified<S>>,
+    /// The recurrence of another `AVAILABLE` with the same UID this one replaces
+    pub recurrence_id: Option<RecurrenceId<S>>,
+    /// Recurrence rule
+    pub rrule: Option<RRule<S>>,
+    /// Recurrence dates
+    pub rdates: Vec<RDate<S>>,
+    /// Exception dates
+    pub ex_dates: Vec<ExDate<S>>,
+    /// Categories (can appear multiple times)
+    pub categories: Vec<Categories<S>>,
+    /// Comments (can appear multiple times)
+    pub comments: Vec<Comment<S>>,
+    /// Contacts (can appear multiple times)
+    pub contacts: Vec<Contact<S>>,
+    /// Custom X- properties (preserved for round-trip)
+    pub x_properties: Vec<XNameProperty<S>>,
+    /// Unrecognized / Non-standard properties (preserved for round-trip)
+    pub retained_properties: Vec<Property<S>>,
+}
+
+impl<S: StringStorage> VAvailability<S> {
+    /// Busy time type of the time left unavailable, `BUSY-UNAVAILABLE` by default.
+    #[must_use]
+    pub fn busy_type_value(&self) -> BusyTypeValue {
+        self.busy_type
+            .as_ref()
+            .map(|busy_type| busy_type.value)
+            .unwrap_or_default()
+    }
+
+    /// Rank of the priority over overlapping availabilities, lower first, with the undefined
+    /// priority 0 after the lowest priority 9.
+    #[must_use]
+    pub fn priority_rank(&self) -> u8 {
+        match self.priority.as_ref().map_or(0, |priority| priority.value) {
+            0 => 10,
+            p => p,
+        }
+    }
+}
+
+/// Parse a `TypedComponent` into a `VAvailability`
+impl<'src> TryFrom<TypedComponent<'src>> for VAvailability<Segments<'src>> {
+    type Error = Vec<SemanticError<'src>>;
+
+    fn try_from(comp: TypedComponent<'src>) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+
+        let span = comp.span();
+        if !comp.name.eq_str_ignore_ascii_case(KW_VAVAILABILITY) {
+            errors.push(SemanticError::ExpectedComponent {
+                expected: KW_VAVAILABILITY,
+                got: comp.name,
+                span,
+            });
+        }
+
+        // Collect all properties in a single pass
+        let mut props = PropertyCollector::default();
+        for prop in comp.properties {
+            match prop {
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::DtEnd(dt) => keep_first(&mut props.dt_end, dt),
+                Property::Duration(dur) => keep_first(&mut props.duration, dur),
+                Property::BusyType(busy_type) => keep_first(&mut props.busy_type, busy_type),
+                Property::Priority(pri) => keep_first(&mut props.priority, pri),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
+                Property::Description(desc) => keep_first(&mut props.description, desc),
+                Property::Location(loc) => keep_first(&mut props.location, loc),
+                Property::Organizer(org) => keep_first(&mut props.organizer, org),
+                Property::Url(url) => keep_first(&mut props.url, url),
+                Property::Class(class) => keep_first(&mut props.classification, class),
+                Property::Created(dt) => keep_first(&mut props.created, dt),
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::Sequence(seq) => keep_first(&mut props.sequence, seq),
+                Property::Categories(categories) => props.categories.push(categories),
+                Property::Comment(comment) => props.comments.push(comment),
+                Property::Contact(contact) => props.contacts.push(contact),
+                // Preserve unknown properties for round-trip
+                Property::XName(prop) => props.x_properties.push(prop),
+                prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
+                prop => {
+                    // Preserve other properties not used by VAvailability for round-trip
+                    props.unrecognized_properties.push(prop);
+                }
+            }
+        }
+
+        // Check required fields
+        if props.uid.is_none() {
+            errors.push(SemanticError::MissingProperty {
+                property: PropertyKind::Uid,
+                span,
+            });
+        }
+        if props.dt_stamp.is_none() {
+            errors.push(SemanticError::MissingProperty {
+                property: PropertyKind::DtStamp,
+                span,
+            });
+        }
+
+        // Parse sub-components (available time)
+        let available = comp
+            .children
+            .into_iter()
+            .filter_map(|child| {
+                if child.name.eq_str_ignore_ascii_case(KW_AVAILABLE) {
+                    Some(Available::try_from(child))
+                } else {
+                    None
+                }
+            })
+            .filter_map(|result| match result {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    errors.extend(e);
+                    None
+                }
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(VAvailability {
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
+                dt_start: props.dt_start,
+                dt_end: props.dt_end,
+                duration: props.duration,
+                busy_type: props.busy_type,
+                priority: props.priority,
+                summary: props.summary,
+                description: props.description,
+                location: props.location,
+                organizer: props.organizer,
+                url: props.url,
+                classification: props.classification,
+                created: props.created,
+                last_modified: props.last_modified,
+                sequence: props.sequence,
+                categories: props.categories,
+                comments: props.comments,
+                contacts: props.contacts,
+                x_properties: props.x_properties,
+                retained_properties: props.unrecognized_properties,
+                available,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Parse a `TypedComponent` into an `Available`
+impl<'src> TryFrom<TypedComponent<'src>> for Available<Segments<'src>> {
+    type Error = Vec<SemanticError<'src>>;
+
+    fn try_from(comp: TypedComponent<'src>) -> Result<Self, Self::Error> {
+        let mut errors = Vec::new();
+
+        let span = comp.span();
+        if !comp.name.eq_str_ignore_ascii_case(KW_AVAILABLE) {
+            errors.push(SemanticError::ExpectedComponent {
+                expected: KW_AVAILABLE,
+                got: comp.name,
+                span,
+            });
+        }
+
+        // Collect all properties in a single pass
+        let mut props = PropertyCollector::default();
+        for prop in comp.properties {
+            match prop {
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::DtEnd(dt) => keep_first(&mut props.dt_end, dt),
+                Property::Duration(dur) => keep_first(&mut props.duration, dur),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
+                Property::Description(desc) => keep_first(&mut props.description, desc),
+                Property::Location(loc) => keep_first(&mut props.location, loc),
+                Property::Created(dt) => keep_first(&mut props.created, dt),
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::RecurrenceId(id) => keep_first(&mut props.recurrence_id, id),
+                Property::RRule(rrule) => keep_first(&mut props.rrule, rrule),
+                Property::RDate(rdate) => props.rdates.push(rdate),
+                Property::ExDate(exdate) => props.ex_dates.push(exdate),
+                Property::Categories(categories) => props.categories.push(categories),
+                Property::Comment(comment) => props.comments.push(comment),
+                Property::Contact(contact) => props.contacts.push(contact),
+                // Preserve unknown properties for round-trip
+                Property::XName(prop) => props.x_properties.push(prop),
+                prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
+                prop => {
+                    // Preserve other properties not used by Available for round-trip
+                    props.unrecognized_properties.push(prop);
+                }
+            }
+        }
+
+        // Check required fields
+        if props.uid.is_none() {
+            errors.push(SemanticError::MissingProperty {
+                property: PropertyKind::Uid,
+                span,
+            });
+        }
+        if props.dt_stamp.is_none() {
+            errors.push(SemanticError::MissingProperty {
+                property: PropertyKind::DtStamp,
+                span,
+            });
+        }
+        if props.dt_start.is_none() {
+            errors.push(SemanticError::MissingProperty {
+                property: PropertyKind::DtStart,
+                span,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(Available {
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
+                dt_start: props.dt_start.expect("required property checked above"),
+                dt_end: props.dt_end,
+                duration: props.duration,
+                summary: props.summary,
+                description: props.description,
+                location: props.location,
+                created: props.created,
+                last_modified: props.last_modified,
+                recurrence_id: props.recurrence_id,
+                rrule: props.rrule,
+                rdates: props.rdates,
+                ex_dates: props.ex_dates,
+                categories: props.categories,
+                comments: props.comments,
+                contacts: props.contacts,
+                x_properties: props.x_properties,
+                retained_properties: props.unrecognized_properties,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl VAvailability<Segments<'_>> {
+    /// Convert borrowed data to owned data
+    #[must_use]
+    pub fn to_owned(&self) -> VAvailability<String> {
+        VAvailability {
+            uid: self.uid.to_owned(),
+            dt_stamp: self.dt_stamp.to_owned(),
+            dt_start: self.dt_start.as_ref().map(DtStart::to_owned),
+            dt_end: self.dt_end.as_ref().map(DtEnd::to_owned),
+            duration: self.duration.as_ref().map(Duration::to_owned),
+            busy_type: self.busy_type.as_ref().map(BusyType::to_owned),
+            priority: self.priority.as_ref().map(Priority::to_owned),
+            summary: self.summary.as_ref().map(Summary::to_owned),
+            description: self.description.as_ref().map(Description::to_owned),
+            location: self.location.as_ref().map(Location::to_owned),
+            organizer: self.organizer.as_ref().map(Organizer::to_owned),
+            url: self.url.as_ref().map(Url::to_owned),
+            classification: self.classification.as_ref().map(Classification::to_owned),
+            created: self.created.as_ref().map(Created::to_owned),
+            last_modified: self.last_modified.as_ref().map(LastModified::to_owned),
+            sequence: self.sequence.as_ref().map(Sequence::to_owned),
+            categories: self.categories.iter().map(Categories::to_owned).collect(),
+            comments: self.comments.iter().map(Comment::to_owned).collect(),
+            contacts: self.contacts.iter().map(Contact::to_owned).collect(),
+            x_properties: self
+                .x_properties
+                .iter()
+                .map(XNameProperty::to_owned)
+                .collect(),
+            retained_properties: self
+                .retained_properties
+                .iter()
+                .map(Property::to_owned)
+                .collect(),
+            available: self.available.iter().map(Available::to_owned).collect(),
+        }
+    }
+}
+
+impl Available<Segments<'_>> {
+    /// Convert borrowed data to owned data
+    #[must_use]
+    pub fn to_owned(&self) -> Available<String> {
+        Available {
+            uid: self.uid.to_owned(),
+            dt_stamp: self.dt_stamp.to_owned(),
+            dt_start: self.dt_start.to_owned(),
+            dt_end: self.dt_end.as_ref().map(DtEnd::to_owned),
+            duration: self.duration.as_ref().map(Duration::to_owned),
+            summary: self.summary.as_ref().map(Summary::to_owned),
+            description: self.description.as_ref().map(Description::to_owned),
+            location: self.location.as_ref().map(Location::to_owned),
+            created: self.created.as_ref().map(Created::to_owned),
+            last_modified: self.last_modified.as_ref().map(LastModified::to_owned),
+            recurrence_id: self.recurrence_id.as_ref().map(RecurrenceId::to_owned),
+            rrule: self.rrule.as_ref().map(RRule::to_owned),
+            rdates: self.rdates.iter().map(RDate::to_owned).collect(),
+            ex_dates: self.ex_dates.iter().map(ExDate::to_owned).collect(),
+            categories: self.categories.iter().map(Categories::to_owned).collect(),
+            comments: self.comments.iter().map(Comment::to_owned).collect(),
+            contacts: self.contacts.iter().map(Contact::to_owned).collect(),
+            x_properties: self
+                .x_properties
+                .iter()
+                .map(XNameProperty::to_owned)
+                .collect(),
+            retained_properties: self
+                .retained_properties
+                .iter()
+                .map(Property::to_owned)
+                .collect(),
+        }
+    }
+}
+
+/// Helper struct to collect properties during single-pass iteration, shared by `VAVAILABILITY`
+/// and `AVAILABLE`
+#[rustfmt::skip]
+#[derive(Debug, Default)]
+struct PropertyCollector<S: StringStorage> {
+    uid:            Option<Uid<S>>,
+    dt_stamp:       Option<DtStamp<S>>,
+    dt_start:       Option<DtStart<S>>,
+    dt_end:         Option<DtEnd<S>>,
+    duration:       Option<Duration<S>>,
+    busy_type:      Option<BusyType<S>>,
+    priority:       Option<Priority<S>>,
+    summary:        Option<Summary<S>>,
+    description:    Option<Description<S>>,
+    location:       Option<Location<S>>,
+    organizer:      Option<Organizer<S>>,
+    url:            Option<Url<S>>,
+    classification: Option<Classification<S>>,
+    created:        Option<Created<S>>,
+    last_modified:  Option<LastModified<S>>,
+    sequence:       Option<Sequence<S>>,
+    recurrence_id:  Option<RecurrenceId<S>>,
+    rrule:          Option<RRule<S>>,
+    rdates:         Vec<RDate<S>>,
+    ex_dates:       Vec<ExDate<S>>,
+    categories:     Vec<Categories<S>>,
+    comments:       Vec<Comment<S>>,
+    contacts:       Vec<Contact<S>>,
+    x_properties:   Vec<XNameProperty<S>>,
+    unrecognized_properties: Vec<Property<S>>,
+}
+
+impl ValidateTzids for VAvailability<Segments<'_>> {
+    fn validate_tzids(&mut self, ctx: &TzContext<'_>) -> Result<(), Vec<SemanticError<'static>>> {
+        let mut errors = Vec::new();
+
+        if let Some(ref mut dt_start) = self.dt_start
+            && let Err(e) = ctx.validate_dt(dt_start)
+        {
+            errors.push(e);
+        }
+        if let Some(ref mut dt_end) = self.dt_end
+            && let Err(e) = ctx.validate_dt(dt_end)
+        {
+            errors.push(e);
+        }
+
+        for available in &mut self.available {
+            if let Err(e) = available.validate_tzids(ctx) {
+                errors.extend(e);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl ValidateTzids for Available<Segments<'_>> {
+    fn validate_tzids(&mut self, ctx: &TzContext<'_>) -> Result<(), Vec<SemanticError<'static>>> {
+        let mut errors = Vec::new();
+
+        if let Err(e) = ctx.validate_dt(&mut self.dt_start) {
+            errors.push(e);
+        }
+        if let Some(ref mut dt_end) = self.dt_end
+            && let Err(e) = ctx.validate_dt(dt_end)
+        {
+            errors.push(e);
+        }
+        if let Some(ref mut recurrence_id) = self.recurrence_id
+            && let Err(e) = ctx.validate_dt(recurrence_id)
+        {
+            errors.push(e);
+        }
+        errors.extend(ctx.validate_rdates(&mut self.rdates));
+        errors.extend(ctx.validate_exdates(&mut self.ex_dates));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -9,10 +9,12 @@ use std::fmt::{self, Display};
 use crate::keyword::{KW_VALARM, KW_VEVENT};
 use crate::parameter::Parameter;
 use crate::property::{
-    Attendee, Categories, Classification, Description, DtEnd, DtStamp, DtStart, Duration, ExDate,
-    Geo, LastModified, Location, Organizer, Priority, Property, PropertyKind, RDate, RRule,
-    Resources, Sequence, Status, StatusValue, Summary, TimeTransparency, Uid, Url, XNameProperty,
+    Attachment, Attendee, Categories, Classification, Color, Comment, Conference, Contact,
+    Description, DtEnd, DtStamp, DtStart, Duration, ExDate, Geo, Image, LastModified, Location,
+    Organizer, Priority, Property, PropertyKind, RDate, RRule, Resources, Sequence, Status,
+    StatusValue, Summary, TimeTransparency, Uid, Url, XNameProperty,
 };
+use crate::semantic::cardinality::{keep_first, keep_first_with};
 use crate::semantic::tz_validator::{TzContext, ValidateTzids};
 use crate::semantic::{SemanticError, VAlarm};
 use crate::string_storage::{Segments, StringStorage};
@@ -26,8 +28,8 @@ pub struct VEvent<S: StringStorage> {
     pub uid: Uid<S>,
     /// Date/time the event was created
     pub dt_stamp: DtStamp<S>,
-    /// Date/time the event starts
-    pub dt_start: DtStart<S>,
+    /// Date/time the event starts, missing from the unscheduled events of some clients
+    pub dt_start: Option<DtStart<S>>,
     /// Date/time the event ends
     pub dt_end: Option<DtEnd<S>>,
     /// Duration of the event (alternative to `dt_end`)
@@ -58,10 +60,22 @@ pub struct VEvent<S: StringStorage> {
     pub priority: Option<Priority<S>>,
     /// Classification
     pub classification: Option<Classification<S>>,
-    /// Resources
-    pub resources: Option<Resources<S>>,
-    /// Categories
-    pub categories: Option<Categories<S>>,
+    /// Resources (can appear multiple times)
+    pub resources: Vec<Resources<S>>,
+    /// Categories (can appear multiple times)
+    pub categories: Vec<Categories<S>>,
+    /// Comments (can appear multiple times)
+    pub comments: Vec<Comment<S>>,
+    /// Attachments (can appear multiple times)
+    pub attachments: Vec<Attachment<S>>,
+    /// Contacts (can appear multiple times)
+    pub contacts: Vec<Contact<S>>,
+    /// Color used for displaying (RFC 7986)
+    pub color: Option<Color<S>>,
+    /// Images (RFC 7986, can appear multiple times)
+    pub images: Vec<Image<S>>,
+    /// Conferences to join (RFC 7986, can appear multiple times)
+    pub conferences: Vec<Conference<S>>,
     /// Recurrence rule
     pub rrule: Option<RRule<S>>,
     /// Recurrence dates
@@ -97,150 +111,35 @@ impl<'src> TryFrom<TypedComponent<'src>> for VEvent<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::Uid(uid) => match props.uid {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Uid,
-                        span: uid.span(),
-                    }),
-                    None => props.uid = Some(uid),
-                },
-                Property::DtStamp(dt) => match props.dt_stamp {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStamp,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_stamp = Some(dt),
-                },
-                Property::DtStart(dt) => match props.dt_start {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStart,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_start = Some(dt),
-                },
-                Property::DtEnd(dt) => match props.dt_end {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtEnd,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_end = Some(dt),
-                },
-                Property::Duration(dur) => match props.duration {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Duration,
-                        span: dur.span(),
-                    }),
-                    None => props.duration = Some(dur),
-                },
-                Property::Summary(s) => match props.summary {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Summary,
-                        span: s.span(),
-                    }),
-                    None => props.summary = Some(s),
-                },
-                Property::Description(desc) => match props.description {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Description,
-                        span: desc.span(),
-                    }),
-                    None => props.description = Some(desc),
-                },
-                Property::Location(loc) => match props.location {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Location,
-                        span: loc.span(),
-                    }),
-                    None => props.location = Some(loc),
-                },
-                Property::Geo(geo) => match props.geo {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Geo,
-                        span: geo.span(),
-                    }),
-                    None => props.geo = Some(geo),
-                },
-                Property::Url(url) => match props.url {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Url,
-                        span: url.span(),
-                    }),
-                    None => props.url = Some(url),
-                },
-                Property::Organizer(org) => match props.organizer {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Organizer,
-                        span: org.span(),
-                    }),
-                    None => props.organizer = Some(org),
-                },
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::DtEnd(dt) => keep_first(&mut props.dt_end, dt),
+                Property::Duration(dur) => keep_first(&mut props.duration, dur),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
+                Property::Description(desc) => keep_first(&mut props.description, desc),
+                Property::Location(loc) => keep_first(&mut props.location, loc),
+                Property::Geo(geo) => keep_first(&mut props.geo, geo),
+                Property::Url(url) => keep_first(&mut props.url, url),
+                Property::Organizer(org) => keep_first(&mut props.organizer, org),
                 Property::Attendee(attendee) => props.attendees.push(attendee),
-                Property::LastModified(dt) => match props.last_modified {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::LastModified,
-                        span: dt.span(),
-                    }),
-                    None => props.last_modified = Some(dt),
-                },
-                Property::Status(status) => match props.status {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Status,
-                        span: status.span(),
-                    }),
-                    None => match status.clone().try_into() {
-                        Ok(v) => props.status = Some(v),
-                        Err(e) => errors.push(e),
-                    },
-                },
-                Property::Transp(transp) => match props.transparency {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Transp,
-                        span: transp.span(),
-                    }),
-                    None => props.transparency = Some(transp),
-                },
-                Property::Sequence(seq) => match props.sequence {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Sequence,
-                        span: seq.span(),
-                    }),
-                    None => props.sequence = Some(seq),
-                },
-                Property::Priority(pri) => match props.priority {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Priority,
-                        span: pri.span(),
-                    }),
-                    None => props.priority = Some(pri),
-                },
-                Property::Class(class) => match props.classification {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Class,
-                        span: class.span(),
-                    }),
-                    None => props.classification = Some(class),
-                },
-                Property::Resources(resources) => match props.resources {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: resources.span(),
-                        property: PropertyKind::Resources,
-                    }),
-                    None => props.resources = Some(resources),
-                },
-                Property::Categories(categories) => match props.categories {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: categories.span(),
-                        property: PropertyKind::Categories,
-                    }),
-                    None => props.categories = Some(categories),
-                },
-                Property::RRule(rrule) => match props.rrule {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::RRule,
-                        span: rrule.span(),
-                    }),
-                    None => props.rrule = Some(rrule),
-                },
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::Status(status) => {
+                    keep_first_with(&mut props.status, status, &mut errors, TryInto::try_into);
+                }
+                Property::Transp(transp) => keep_first(&mut props.transparency, transp),
+                Property::Sequence(seq) => keep_first(&mut props.sequence, seq),
+                Property::Priority(pri) => keep_first(&mut props.priority, pri),
+                Property::Class(class) => keep_first(&mut props.classification, class),
+                Property::Resources(resources) => props.resources.push(resources),
+                Property::Categories(categories) => props.categories.push(categories),
+                Property::Comment(comment) => props.comments.push(comment),
+                Property::Attach(attach) => props.attachments.push(attach),
+                Property::Contact(contact) => props.contacts.push(contact),
+                Property::Color(color) => keep_first(&mut props.color, color),
+                Property::Image(image) => props.images.push(image),
+                Property::Conference(conference) => props.conferences.push(conference),
+                Property::RRule(rrule) => keep_first(&mut props.rrule, rrule),
                 Property::RDate(rdate) => props.rdates.push(rdate),
                 Property::ExDate(exdate) => props.ex_dates.push(exdate),
                 // Preserve unknown properties for round-trip
@@ -266,12 +165,6 @@ impl<'src> TryFrom<TypedComponent<'src>> for VEvent<Segments<'src>> {
                 span,
             });
         }
-        if props.dt_start.is_none() {
-            errors.push(SemanticError::MissingProperty {
-                property: PropertyKind::DtStart,
-                span,
-            });
-        }
 
         // Parse sub-components (alarms)
         let alarms = comp
@@ -295,9 +188,9 @@ impl<'src> TryFrom<TypedComponent<'src>> for VEvent<Segments<'src>> {
 
         if errors.is_empty() {
             Ok(VEvent {
-                uid: props.uid.unwrap(),           // SAFETY: checked above
-                dt_stamp: props.dt_stamp.unwrap(), // SAFETY: checked above
-                dt_start: props.dt_start.unwrap(), // SAFETY: checked above
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
+                dt_start: props.dt_start,
                 dt_end: props.dt_end,
                 duration: props.duration,
                 summary: props.summary,
@@ -315,6 +208,12 @@ impl<'src> TryFrom<TypedComponent<'src>> for VEvent<Segments<'src>> {
                 classification: props.classification,
                 resources: props.resources,
                 categories: props.categories,
+                comments: props.comments,
+                attachments: props.attachments,
+                contacts: props.contacts,
+                color: props.color,
+                images: props.images,
+                conferences: props.conferences,
                 rrule: props.rrule,
                 rdates: props.rdates,
                 ex_dates: props.ex_dates,
@@ -334,7 +233,7 @@ impl VEvent<Segments<'_>> {
         VEvent {
             uid: self.uid.to_owned(),
             dt_stamp: self.dt_stamp.to_owned(),
-            dt_start: self.dt_start.to_owned(),
+            dt_start: self.dt_start.as_ref().map(DtStart::to_owned),
             dt_end: self.dt_end.as_ref().map(DtEnd::to_owned),
             duration: self.duration.as_ref().map(Duration::to_owned),
             summary: self.summary.as_ref().map(Summary::to_owned),
@@ -350,8 +249,14 @@ impl VEvent<Segments<'_>> {
             sequence: self.sequence.as_ref().map(Sequence::to_owned),
             priority: self.priority.as_ref().map(Priority::to_owned),
             classification: self.classification.as_ref().map(Classification::to_owned),
-            resources: self.resources.as_ref().map(Resources::to_owned),
-            categories: self.categories.as_ref().map(Categories::to_owned),
+            resources: self.resources.iter().map(Resources::to_owned).collect(),
+            categories: self.categories.iter().map(Categories::to_owned).collect(),
+            comments: self.comments.iter().map(Comment::to_owned).collect(),
+            attachments: self.attachments.iter().map(Attachment::to_owned).collect(),
+            contacts: self.contacts.iter().map(Contact::to_owned).collect(),
+            color: self.color.as_ref().map(Color::to_owned),
+            images: self.images.iter().map(Image::to_owned).collect(),
+            conferences: self.conferences.iter().map(Conference::to_owned).collect(),
             rrule: self.rrule.as_ref().map(RRule::to_owned),
             rdates: self.rdates.iter().map(RDate::to_owned).collect(),
             ex_dates: self.ex_dates.iter().map(ExDate::to_owned).collect(),
@@ -419,6 +324,16 @@ pub struct EventStatus<S: StringStorage> {
     pub x_parameters: Vec<RawParameter<S>>,
     /// Unknown IANA parameters (preserved for round-trip)
     pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<S: StringStorage> EventStatus<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
 }
 
 impl<'src> TryFrom<Status<Segments<'src>>> for EventStatus<Segments<'src>> {
@@ -437,6 +352,7 @@ impl<'src> TryFrom<Status<Segments<'src>>> for EventStatus<Segments<'src>> {
             value,
             x_parameters: property.x_parameters,
             retained_parameters: property.retained_parameters,
+            span: property.span,
         })
     }
 }
@@ -456,6 +372,7 @@ impl EventStatus<Segments<'_>> {
                 .iter()
                 .map(Parameter::to_owned)
                 .collect(),
+            span: (),
         }
     }
 }
@@ -468,6 +385,7 @@ impl EventStatus<String> {
             value,
             x_parameters: Vec::new(),
             retained_parameters: Vec::new(),
+            span: (),
         }
     }
 }
@@ -494,8 +412,14 @@ struct PropertyCollector< S: StringStorage> {
     sequence:       Option<Sequence<S>>,
     priority:       Option<Priority<S>>,
     classification: Option<Classification<S>>,
-    resources:      Option<Resources<S>>,
-    categories:     Option<Categories<S>>,
+    resources:      Vec<Resources<S>>,
+    categories:     Vec<Categories<S>>,
+    comments:       Vec<Comment<S>>,
+    attachments:    Vec<Attachment<S>>,
+    contacts:       Vec<Contact<S>>,
+    color:          Option<Color<S>>,
+    images:         Vec<Image<S>>,
+    conferences:    Vec<Conference<S>>,
     rrule:          Option<RRule<S>>,
     rdates:         Vec<RDate<S>>,
     ex_dates:       Vec<ExDate<S>>,
@@ -507,8 +431,10 @@ impl ValidateTzids for VEvent<Segments<'_>> {
     fn validate_tzids(&mut self, ctx: &TzContext<'_>) -> Result<(), Vec<SemanticError<'static>>> {
         let mut errors = Vec::new();
 
-        // Validate DtStart
-        if let Err(e) = ctx.validate_dt(&mut self.dt_start) {
+        // Validate DtStart if present
+        if let Some(ref mut dt_start) = self.dt_start
+            && let Err(e) = ctx.validate_dt(dt_start)
+        {
             errors.push(e);
         }
 
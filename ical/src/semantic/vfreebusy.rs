@@ -11,6 +11,7 @@ use crate::property::{
     Url, XNameProperty,
 };
 use crate::semantic::SemanticError;
+use crate::semantic::cardinality::keep_first;
 use crate::string_storage::{Segments, StringStorage};
 use crate::typed::TypedComponent;
 
@@ -51,7 +52,6 @@ pub struct VFreeBusy<S: StringStorage> {
 impl<'src> TryFrom<TypedComponent<'src>> for VFreeBusy<Segments<'src>> {
     type Error = Vec<SemanticError<'src>>;
 
-    #[expect(clippy::too_many_lines)]
     fn try_from(comp: TypedComponent<'src>) -> Result<Self, Self::Error> {
         let mut errors = Vec::new();
 
@@ -85,62 +85,14 @@ impl<'src> TryFrom<TypedComponent<'src>> for VFreeBusy<Segments<'src>> {
                         }
                     }
                 }
-                Property::Uid(uid) => match props.uid {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Uid,
-                        span: uid.span(),
-                    }),
-                    None => props.uid = Some(uid),
-                },
-                Property::DtStamp(dt) => match props.dt_stamp {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStamp,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_stamp = Some(dt),
-                },
-                Property::DtStart(dt) => match props.dt_start {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStart,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_start = Some(dt),
-                },
-                Property::DtEnd(dt) => match props.dt_end {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtEnd,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_end = Some(dt),
-                },
-                Property::Duration(dur) => match props.duration {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Duration,
-                        span: dur.span(),
-                    }),
-                    None => props.duration = Some(dur),
-                },
-                Property::Organizer(org) => match props.organizer {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Organizer,
-                        span: org.span(),
-                    }),
-                    None => props.organizer = Some(org),
-                },
-                Property::Contact(contact) => match props.contact {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Contact,
-                        span: contact.span(),
-                    }),
-                    None => props.contact = Some(contact),
-                },
-                Property::Url(url) => match props.url {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Url,
-                        span: url.span(),
-                    }),
-                    None => props.url = Some(url),
-                },
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::DtEnd(dt) => keep_first(&mut props.dt_end, dt),
+                Property::Duration(dur) => keep_first(&mut props.duration, dur),
+                Property::Organizer(org) => keep_first(&mut props.organizer, org),
+                Property::Contact(contact) => keep_first(&mut props.contact, contact),
+                Property::Url(url) => keep_first(&mut props.url, url),
                 // Preserve unknown properties for round-trip
                 Property::XName(prop) => props.x_properties.push(prop),
                 prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
@@ -179,12 +131,12 @@ impl<'src> TryFrom<TypedComponent<'src>> for VFreeBusy<Segments<'src>> {
 
         if errors.is_empty() {
             Ok(VFreeBusy {
-                uid: props.uid.unwrap(),           // SAFETY: checked above
-                dt_stamp: props.dt_stamp.unwrap(), // SAFETY: checked above
-                dt_start: props.dt_start.unwrap(), // SAFETY: checked above
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
+                dt_start: props.dt_start.expect("required property checked above"),
                 dt_end: props.dt_end,
                 duration: props.duration,
-                organizer: props.organizer.unwrap(), // SAFETY: checked above
+                organizer: props.organizer.expect("required property checked above"),
                 contact: props.contact,
                 url: props.url,
                 busy: props.busy,
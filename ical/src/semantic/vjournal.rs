@@ -9,11 +9,12 @@ use std::fmt;
 use crate::keyword::KW_VJOURNAL;
 use crate::parameter::Parameter;
 use crate::property::{
-    Attendee, Categories, Classification, Description, DtStamp, DtStart, ExDate, LastModified,
-    Organizer, Property, PropertyKind, RDate, RRule, Status, StatusValue, Summary, Uid, Url,
-    XNameProperty,
+    Attachment, Attendee, Categories, Classification, Comment, Contact, Description, DtStamp,
+    DtStart, ExDate, LastModified, Organizer, Property, PropertyKind, RDate, RRule, Status,
+    StatusValue, Summary, Uid, Url, XNameProperty,
 };
 use crate::semantic::SemanticError;
+use crate::semantic::cardinality::{keep_first, keep_first_with};
 use crate::semantic::tz_validator::{TzContext, ValidateTzids};
 use crate::string_storage::{Segments, StringStorage};
 use crate::syntax::RawParameter;
@@ -42,8 +43,14 @@ pub struct VJournal<S: StringStorage> {
     pub status: Option<JournalStatus<S>>,
     /// Classification
     pub classification: Option<Classification<S>>,
-    /// Categories
+    /// Categories (can appear multiple times)
     pub categories: Vec<Categories<S>>,
+    /// Comments (can appear multiple times)
+    pub comments: Vec<Comment<S>>,
+    /// Attachments (can appear multiple times)
+    pub attachments: Vec<Attachment<S>>,
+    /// Contacts (can appear multiple times)
+    pub contacts: Vec<Contact<S>>,
     /// Recurrence rule
     pub rrule: Option<RRule<S>>,
     /// Recurrence dates (can be `Period`, `Date`, `or DateTime`)
@@ -59,7 +66,6 @@ pub struct VJournal<S: StringStorage> {
 }
 
 /// Parse a `TypedComponent` into a `VJournal`
-#[expect(clippy::too_many_lines)]
 impl<'src> TryFrom<TypedComponent<'src>> for VJournal<Segments<'src>> {
     type Error = Vec<SemanticError<'src>>;
 
@@ -79,85 +85,27 @@ impl<'src> TryFrom<TypedComponent<'src>> for VJournal<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::Uid(uid) => match props.uid {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Uid,
-                        span: uid.span(),
-                    }),
-                    None => props.uid = Some(uid),
-                },
-                Property::DtStamp(dt) => match props.dt_stamp {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStamp,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_stamp = Some(dt),
-                },
-                Property::DtStart(dt) => match props.dt_start {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStart,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_start = Some(dt),
-                },
-                Property::Summary(s) => match props.summary {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Summary,
-                        span: s.span(),
-                    }),
-                    None => props.summary = Some(s),
-                },
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
                 // VJOURNAL allows multiple DESCRIPTION properties
                 Property::Description(desc) => props.descriptions.push(desc),
-                Property::Organizer(org) => match props.organizer {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Organizer,
-                        span: org.span(),
-                    }),
-                    None => props.organizer = Some(org),
-                },
+                Property::Organizer(org) => keep_first(&mut props.organizer, org),
                 Property::Attendee(attendee) => props.attendees.push(attendee),
-                Property::LastModified(dt) => match props.last_modified {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::LastModified,
-                        span: dt.span(),
-                    }),
-                    None => props.last_modified = Some(dt),
-                },
-                Property::Status(status) => match props.status {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Status,
-                        span: status.span(),
-                    }),
-                    None => match status.try_into() {
-                        Ok(v) => props.status = Some(v),
-                        Err(e) => errors.push(e),
-                    },
-                },
-                Property::Class(class) => match props.classification {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Class,
-                        span: class.span(),
-                    }),
-                    None => props.classification = Some(class),
-                },
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::Status(status) => {
+                    keep_first_with(&mut props.status, status, &mut errors, TryInto::try_into);
+                }
+                Property::Class(class) => keep_first(&mut props.classification, class),
                 Property::Categories(categories) => props.categories.push(categories),
-                Property::RRule(rrule) => match props.rrule {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::RRule,
-                        span: rrule.span(),
-                    }),
-                    None => props.rrule = Some(rrule),
-                },
+                Property::Comment(comment) => props.comments.push(comment),
+                Property::Attach(attach) => props.attachments.push(attach),
+                Property::Contact(contact) => props.contacts.push(contact),
+                Property::RRule(rrule) => keep_first(&mut props.rrule, rrule),
                 Property::RDate(rdate) => props.rdates.push(rdate),
                 Property::ExDate(exdate) => props.ex_dates.push(exdate),
-                Property::Url(url) => match props.url {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: url.span(),
-                        property: PropertyKind::Url,
-                    }),
-                    None => props.url = Some(url),
-                },
+                Property::Url(url) => keep_first(&mut props.url, url),
                 // Preserve unknown properties for round-trip
                 Property::XName(prop) => props.x_properties.push(prop),
                 prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
@@ -190,9 +138,9 @@ impl<'src> TryFrom<TypedComponent<'src>> for VJournal<Segments<'src>> {
 
         if errors.is_empty() {
             Ok(VJournal {
-                uid: props.uid.unwrap(),           // SAFETY: checked above
-                dt_stamp: props.dt_stamp.unwrap(), // SAFETY: checked above
-                dt_start: props.dt_start.unwrap(), // SAFETY: checked above
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
+                dt_start: props.dt_start.expect("required property checked above"),
                 summary: props.summary,
                 descriptions: props.descriptions,
                 organizer: props.organizer,
@@ -201,6 +149,9 @@ impl<'src> TryFrom<TypedComponent<'src>> for VJournal<Segments<'src>> {
                 status: props.status,
                 classification: props.classification,
                 categories: props.categories,
+                comments: props.comments,
+                attachments: props.attachments,
+                contacts: props.contacts,
                 rrule: props.rrule,
                 rdates: props.rdates,
                 ex_dates: props.ex_dates,
@@ -233,6 +184,9 @@ impl VJournal<Segments<'_>> {
             status: self.status.as_ref().map(JournalStatus::to_owned),
             classification: self.classification.as_ref().map(Classification::to_owned),
             categories: self.categories.iter().map(Categories::to_owned).collect(),
+            comments: self.comments.iter().map(Comment::to_owned).collect(),
+            attachments: self.attachments.iter().map(Attachment::to_owned).collect(),
+            contacts: self.contacts.iter().map(Contact::to_owned).collect(),
             rrule: self.rrule.as_ref().map(RRule::to_owned),
             rdates: self.rdates.iter().map(RDate::to_owned).collect(),
             ex_dates: self.ex_dates.iter().map(ExDate::to_owned).collect(),
@@ -300,6 +254,16 @@ pub struct JournalStatus<S: StringStorage> {
     pub x_parameters: Vec<RawParameter<S>>,
     /// Unknown IANA parameters (preserved for round-trip)
     pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<S: StringStorage> JournalStatus<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
 }
 
 impl<'src> TryFrom<Status<Segments<'src>>> for JournalStatus<Segments<'src>> {
@@ -318,6 +282,7 @@ impl<'src> TryFrom<Status<Segments<'src>>> for JournalStatus<Segments<'src>> {
             value,
             x_parameters: property.x_parameters,
             retained_parameters: property.retained_parameters,
+            span: property.span,
         })
     }
 }
@@ -337,6 +302,7 @@ impl JournalStatus<Segments<'_>> {
                 .iter()
                 .map(Parameter::to_owned)
                 .collect(),
+            span: (),
         }
     }
 }
@@ -356,6 +322,9 @@ struct PropertyCollector<S: StringStorage> {
     status:         Option<JournalStatus<S>>,
     classification: Option<Classification<S>>,
     categories:     Vec<Categories<S>>,
+    comments:       Vec<Comment<S>>,
+    attachments:    Vec<Attachment<S>>,
+    contacts:       Vec<Contact<S>>,
     rrule:          Option<RRule<S>>,
     rdates:         Vec<RDate<S>>,
     ex_dates:       Vec<ExDate<S>>,
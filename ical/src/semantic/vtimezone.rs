@@ -10,6 +10,7 @@ use crate::property::{
     TzUrl, XNameProperty,
 };
 use crate::semantic::SemanticError;
+use crate::semantic::cardinality::keep_first;
 use crate::string_storage::{Segments, StringStorage};
 use crate::typed::TypedComponent;
 
@@ -52,27 +53,9 @@ impl<'src> TryFrom<TypedComponent<'src>> for VTimeZone<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::TzId(tz_id) => match props.tz_id {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::TzId,
-                        span: tz_id.span(),
-                    }),
-                    None => props.tz_id = Some(tz_id),
-                },
-                Property::LastModified(dt) => match props.last_modified {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::LastModified,
-                        span: dt.span(),
-                    }),
-                    None => props.last_modified = Some(dt),
-                },
-                Property::TzUrl(tz_url) => match props.tz_url {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::TzUrl,
-                        span: tz_url.span(),
-                    }),
-                    None => props.tz_url = Some(tz_url),
-                },
+                Property::TzId(tz_id) => keep_first(&mut props.tz_id, tz_id),
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::TzUrl(tz_url) => keep_first(&mut props.tz_url, tz_url),
                 // Preserve unknown properties for round-trip
                 Property::XName(prop) => props.x_properties.push(prop),
                 prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
@@ -121,7 +104,7 @@ impl<'src> TryFrom<TypedComponent<'src>> for VTimeZone<Segments<'src>> {
 
         if errors.is_empty() {
             Ok(VTimeZone {
-                tz_id: props.tz_id.unwrap(), // SAFETY: checked above
+                tz_id: props.tz_id.expect("required property checked above"),
                 last_modified: props.last_modified,
                 tz_url: props.tz_url,
                 standard,
@@ -197,36 +180,12 @@ impl<'src> TryFrom<TypedComponent<'src>> for TimeZoneObservance<Segments<'src>>
         let mut props = ObservanceCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::DtStart(dt) => match props.dt_start {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: dt.span(),
-                        property: PropertyKind::DtStart,
-                    }),
-                    None => props.dt_start = Some(dt),
-                },
-                Property::TzOffsetFrom(offset) => match props.tz_offset_from {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: offset.span(),
-                        property: PropertyKind::TzOffsetFrom,
-                    }),
-                    None => props.tz_offset_from = Some(offset),
-                },
-                Property::TzOffsetTo(offset) => match props.tz_offset_to {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: offset.span(),
-                        property: PropertyKind::TzOffsetTo,
-                    }),
-                    None => props.tz_offset_to = Some(offset),
-                },
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::TzOffsetFrom(offset) => keep_first(&mut props.tz_offset_from, offset),
+                Property::TzOffsetTo(offset) => keep_first(&mut props.tz_offset_to, offset),
                 // TZNAME can appear multiple times
                 Property::TzName(tz_name) => props.tz_name.push(tz_name),
-                Property::RRule(rrule) => match props.rrule {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        span: rrule.span(),
-                        property: PropertyKind::RRule,
-                    }),
-                    None => props.rrule = Some(rrule),
-                },
+                Property::RRule(rrule) => keep_first(&mut props.rrule, rrule),
                 // Preserve unknown properties for round-trip
                 Property::XName(prop) => props.x_properties.push(prop),
                 prop @ Property::Unrecognized { .. } => props.unrecognized_properties.push(prop),
@@ -263,9 +222,11 @@ impl<'src> TryFrom<TypedComponent<'src>> for TimeZoneObservance<Segments<'src>>
         }
 
         Ok(TimeZoneObservance {
-            dt_start: props.dt_start.unwrap(), // SAFETY: checked above
-            tz_offset_from: props.tz_offset_from.unwrap(), // SAFETY: checked above
-            tz_offset_to: props.tz_offset_to.unwrap(), // SAFETY: checked above
+            dt_start: props.dt_start.expect("required property checked above"),
+            tz_offset_from: props
+                .tz_offset_from
+                .expect("required property checked above"),
+            tz_offset_to: props.tz_offset_to.expect("required property checked above"),
             tz_names: props.tz_name,
             rrule: props.rrule,
             x_properties: props.x_properties,
@@ -9,11 +9,12 @@ use std::fmt::{self, Display};
 use crate::keyword::{KW_VALARM, KW_VTODO};
 use crate::parameter::Parameter;
 use crate::property::{
-    Attendee, Categories, Classification, Completed, Description, DtStamp, DtStart, Due, Duration,
-    ExDate, Geo, LastModified, Location, Organizer, PercentComplete, Priority, Property,
-    PropertyKind, RDate, RRule, Resources, Sequence, Status, StatusValue, Summary, Uid, Url,
-    XNameProperty,
+    Attachment, Attendee, Categories, Classification, Color, Comment, Completed, Conference,
+    Contact, Description, DtStamp, DtStart, Due, Duration, ExDate, Geo, Image, LastModified,
+    Location, Organizer, PercentComplete, Priority, Property, PropertyKind, RDate, RRule,
+    Resources, Sequence, Status, StatusValue, Summary, Uid, Url, XNameProperty,
 };
+use crate::semantic::cardinality::{keep_first, keep_first_with};
 use crate::semantic::tz_validator::{TzContext, ValidateTzids};
 use crate::semantic::{SemanticError, VAlarm};
 use crate::string_storage::{Segments, StringStorage};
@@ -61,10 +62,22 @@ pub struct VTodo<S: StringStorage> {
     pub percent_complete: Option<PercentComplete<S>>,
     /// Classification
     pub classification: Option<Classification<S>>,
-    /// Resources
-    pub resources: Option<Resources<S>>,
-    /// Categories
-    pub categories: Option<Categories<S>>,
+    /// Resources (can appear multiple times)
+    pub resources: Vec<Resources<S>>,
+    /// Categories (can appear multiple times)
+    pub categories: Vec<Categories<S>>,
+    /// Comments (can appear multiple times)
+    pub comments: Vec<Comment<S>>,
+    /// Attachments (can appear multiple times)
+    pub attachments: Vec<Attachment<S>>,
+    /// Contacts (can appear multiple times)
+    pub contacts: Vec<Contact<S>>,
+    /// Color used for displaying (RFC 7986)
+    pub color: Option<Color<S>>,
+    /// Images (RFC 7986, can appear multiple times)
+    pub images: Vec<Image<S>>,
+    /// Conferences to join (RFC 7986, can appear multiple times)
+    pub conferences: Vec<Conference<S>>,
     /// Recurrence rule
     pub rrule: Option<RRule<S>>,
     /// Recurrence dates
@@ -100,157 +113,36 @@ impl<'src> TryFrom<TypedComponent<'src>> for VTodo<Segments<'src>> {
         let mut props = PropertyCollector::default();
         for prop in comp.properties {
             match prop {
-                Property::Uid(uid) => match props.uid {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Uid,
-                        span: uid.span(),
-                    }),
-                    None => props.uid = Some(uid),
-                },
-                Property::DtStamp(dt) => match props.dt_stamp {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStamp,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_stamp = Some(dt),
-                },
-                Property::DtStart(dt) => match props.dt_start {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::DtStart,
-                        span: dt.span(),
-                    }),
-                    None => props.dt_start = Some(dt),
-                },
-                Property::Due(dt) => match props.due {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Due,
-                        span: dt.span(),
-                    }),
-                    None => props.due = Some(dt),
-                },
-                Property::Completed(dt) => match props.completed {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Completed,
-                        span: dt.span(),
-                    }),
-                    None => props.completed = Some(dt),
-                },
-                Property::Duration(dur) => match props.duration {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Duration,
-                        span: dur.span(),
-                    }),
-                    None => props.duration = Some(dur),
-                },
-                Property::Summary(s) => match props.summary {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Summary,
-                        span: s.span(),
-                    }),
-                    None => props.summary = Some(s),
-                },
-                Property::Description(desc) => match props.description {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Description,
-                        span: desc.span(),
-                    }),
-                    None => props.description = Some(desc),
-                },
-                Property::Location(loc) => match props.location {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Location,
-                        span: loc.span(),
-                    }),
-                    None => props.location = Some(loc),
-                },
-                Property::Geo(geo) => match props.geo {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Geo,
-                        span: geo.span(),
-                    }),
-                    None => props.geo = Some(geo),
-                },
-                Property::Url(url) => match props.url {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Url,
-                        span: url.span(),
-                    }),
-                    None => props.url = Some(url),
-                },
-                Property::Organizer(org) => match props.organizer {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Organizer,
-                        span: org.span(),
-                    }),
-                    None => props.organizer = Some(org),
-                },
+                Property::Uid(uid) => keep_first(&mut props.uid, uid),
+                Property::DtStamp(dt) => keep_first(&mut props.dt_stamp, dt),
+                Property::DtStart(dt) => keep_first(&mut props.dt_start, dt),
+                Property::Due(dt) => keep_first(&mut props.due, dt),
+                Property::Completed(dt) => keep_first(&mut props.completed, dt),
+                Property::Duration(dur) => keep_first(&mut props.duration, dur),
+                Property::Summary(s) => keep_first(&mut props.summary, s),
+                Property::Description(desc) => keep_first(&mut props.description, desc),
+                Property::Location(loc) => keep_first(&mut props.location, loc),
+                Property::Geo(geo) => keep_first(&mut props.geo, geo),
+                Property::Url(url) => keep_first(&mut props.url, url),
+                Property::Organizer(org) => keep_first(&mut props.organizer, org),
                 Property::Attendee(attendee) => props.attendees.push(attendee),
-                Property::LastModified(dt) => match props.last_modified {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::LastModified,
-                        span: dt.span(),
-                    }),
-                    None => props.last_modified = Some(dt),
-                },
-                Property::Status(status) => match props.status {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Status,
-                        span: status.span(),
-                    }),
-                    None => match status.clone().try_into() {
-                        Ok(v) => props.status = Some(v),
-                        Err(e) => errors.push(e),
-                    },
-                },
-                Property::Sequence(seq) => match props.sequence {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Sequence,
-                        span: seq.span(),
-                    }),
-                    None => props.sequence = Some(seq),
-                },
-                Property::Priority(pri) => match props.priority {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Priority,
-                        span: pri.span(),
-                    }),
-                    None => props.priority = Some(pri),
-                },
-                Property::PercentComplete(pct) => match props.percent_complete {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::PercentComplete,
-                        span: pct.span(),
-                    }),
-                    None => props.percent_complete = Some(pct),
-                },
-                Property::Class(class) => match props.classification {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Class,
-                        span: class.span(),
-                    }),
-                    None => props.classification = Some(class),
-                },
-                Property::Resources(resources) => match props.resources {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Resources,
-                        span: resources.span(),
-                    }),
-                    None => props.resources = Some(resources),
-                },
-                Property::Categories(categories) => match props.categories {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::Categories,
-                        span: categories.span(),
-                    }),
-                    None => props.categories = Some(categories),
-                },
-                Property::RRule(rrule) => match props.rrule {
-                    Some(_) => errors.push(SemanticError::DuplicateProperty {
-                        property: PropertyKind::RRule,
-                        span: rrule.span(),
-                    }),
-                    None => props.rrule = Some(rrule),
-                },
+                Property::LastModified(dt) => keep_first(&mut props.last_modified, dt),
+                Property::Status(status) => {
+                    keep_first_with(&mut props.status, status, &mut errors, TryInto::try_into);
+                }
+                Property::Sequence(seq) => keep_first(&mut props.sequence, seq),
+                Property::Priority(pri) => keep_first(&mut props.priority, pri),
+                Property::PercentComplete(pct) => keep_first(&mut props.percent_complete, pct),
+                Property::Class(class) => keep_first(&mut props.classification, class),
+                Property::Resources(resources) => props.resources.push(resources),
+                Property::Categories(categories) => props.categories.push(categories),
+                Property::Comment(comment) => props.comments.push(comment),
+                Property::Attach(attach) => props.attachments.push(attach),
+                Property::Contact(contact) => props.contacts.push(contact),
+                Property::Color(color) => keep_first(&mut props.color, color),
+                Property::Image(image) => props.images.push(image),
+                Property::Conference(conference) => props.conferences.push(conference),
+                Property::RRule(rrule) => keep_first(&mut props.rrule, rrule),
                 Property::RDate(rdate) => props.rdates.push(rdate),
                 Property::ExDate(exdate) => props.ex_dates.push(exdate),
                 // Preserve unknown properties for round-trip
@@ -299,8 +191,8 @@ impl<'src> TryFrom<TypedComponent<'src>> for VTodo<Segments<'src>> {
 
         if errors.is_empty() {
             Ok(VTodo {
-                uid: props.uid.unwrap(),           // SAFETY: checked above
-                dt_stamp: props.dt_stamp.unwrap(), // SAFETY: checked above
+                uid: props.uid.expect("required property checked above"),
+                dt_stamp: props.dt_stamp.expect("required property checked above"),
                 dt_start: props.dt_start,
                 due: props.due,
                 completed: props.completed,
@@ -320,6 +212,12 @@ impl<'src> TryFrom<TypedComponent<'src>> for VTodo<Segments<'src>> {
                 classification: props.classification,
                 resources: props.resources,
                 categories: props.categories,
+                comments: props.comments,
+                attachments: props.attachments,
+                contacts: props.contacts,
+                color: props.color,
+                images: props.images,
+                conferences: props.conferences,
                 rrule: props.rrule,
                 rdates: props.rdates,
                 ex_dates: props.ex_dates,
@@ -359,8 +257,14 @@ impl VTodo<Segments<'_>> {
                 .as_ref()
                 .map(PercentComplete::to_owned),
             classification: self.classification.as_ref().map(Classification::to_owned),
-            resources: self.resources.as_ref().map(Resources::to_owned),
-            categories: self.categories.as_ref().map(Categories::to_owned),
+            resources: self.resources.iter().map(Resources::to_owned).collect(),
+            categories: self.categories.iter().map(Categories::to_owned).collect(),
+            comments: self.comments.iter().map(Comment::to_owned).collect(),
+            attachments: self.attachments.iter().map(Attachment::to_owned).collect(),
+            contacts: self.contacts.iter().map(Contact::to_owned).collect(),
+            color: self.color.as_ref().map(Color::to_owned),
+            images: self.images.iter().map(Image::to_owned).collect(),
+            conferences: self.conferences.iter().map(Conference::to_owned).collect(),
             rrule: self.rrule.as_ref().map(RRule::to_owned),
             rdates: self.rdates.iter().map(RDate::to_owned).collect(),
             ex_dates: self.ex_dates.iter().map(ExDate::to_owned).collect(),
@@ -431,6 +335,16 @@ pub struct TodoStatus<S: StringStorage> {
     pub x_parameters: Vec<RawParameter<S>>,
     /// Unknown IANA parameters (preserved for round-trip)
     pub retained_parameters: Vec<Parameter<S>>,
+    /// Span of the property in the source
+    pub span: S::Span,
+}
+
+impl<S: StringStorage> TodoStatus<S> {
+    /// Get the span of this property
+    #[must_use]
+    pub const fn span(&self) -> S::Span {
+        self.span
+    }
 }
 
 impl<'src> TryFrom<Status<Segments<'src>>> for TodoStatus<Segments<'src>> {
@@ -449,6 +363,7 @@ impl<'src> TryFrom<Status<Segments<'src>>> for TodoStatus<Segments<'src>> {
             value,
             x_parameters: property.x_parameters,
             retained_parameters: property.retained_parameters,
+            span: property.span,
         })
     }
 }
@@ -468,6 +383,7 @@ impl TodoStatus<Segments<'_>> {
                 .iter()
                 .map(Parameter::to_owned)
                 .collect(),
+            span: (),
         }
     }
 }
@@ -480,6 +396,7 @@ impl TodoStatus<String> {
             value,
             x_parameters: Vec::new(),
             retained_parameters: Vec::new(),
+            span: (),
         }
     }
 }
@@ -507,8 +424,14 @@ struct PropertyCollector<S: StringStorage> {
     priority:       Option<Priority<S>>,
     percent_complete: Option<PercentComplete<S>>,
     classification: Option<Classification<S>>,
-    resources:      Option<Resources<S>>,
-    categories:     Option<Categories<S>>,
+    resources:      Vec<Resources<S>>,
+    categories:     Vec<Categories<S>>,
+    comments:       Vec<Comment<S>>,
+    attachments:    Vec<Attachment<S>>,
+    contacts:       Vec<Contact<S>>,
+    color:          Option<Color<S>>,
+    images:         Vec<Image<S>>,
+    conferences:    Vec<Conference<S>>,
     rrule:          Option<RRule<S>>,
     rdates:         Vec<RDate<S>>,
     ex_dates:       Vec<ExDate<S>>,
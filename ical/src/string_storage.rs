@@ -9,6 +9,7 @@ use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 use std::iter::Peekable;
 use std::ops::Range;
+use std::slice;
 use std::str::CharIndices;
 
 use chumsky::span::SimpleSpan;
@@ -29,19 +30,35 @@ pub trait StringStorage: Clone + Display {
     /// source positions. For owned data (`String`), this is `()` since span
     /// information is not preserved.
     type Span: Copy + Debug + PartialEq + Eq + Hash;
+
+    /// View the string, borrowed when it is stored in one piece.
+    ///
+    /// This lets read-only consumers work on the components of [`parse`](crate::parse) without
+    /// converting them to owned ones first: a value of a content line that was not folded is a
+    /// single slice of the source and is borrowed, only a folded one is joined into a new string.
+    fn as_str_lossy(&self) -> Cow<'_, str>;
 }
 
 // Implement StringStorage for references to types that implement it
 impl<T: StringStorage> StringStorage for &T {
     type Span = T::Span;
+
+    fn as_str_lossy(&self) -> Cow<'_, str> {
+        (**self).as_str_lossy()
+    }
 }
 
 impl StringStorage for String {
     type Span = (); // No span information for owned strings
+
+    fn as_str_lossy(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
 }
 
 /// A span representing a range in the source code
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Span {
     /// Start position of the span
     pub start: usize,
@@ -98,18 +115,110 @@ impl Display for Span {
 pub type Segment<'src> = (&'src str, Span);
 
 /// A collection of spanned text segments (multi-segment value with positions)
-#[derive(Default, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct Segments<'src> {
-    pub(crate) segments: Vec<Segment<'src>>,
+    repr: SegmentsRepr<'src>,
     len: usize,
 }
 
+/// Storage for [`Segments`].
+///
+/// Unfolded content lines produce a single contiguous slice of the source, so
+/// that case is kept inline and never touches the heap.
+#[derive(Clone, Debug)]
+enum SegmentsRepr<'src> {
+    Single(Segment<'src>),
+    Multi(Vec<Segment<'src>>),
+}
+
+impl Default for Segments<'_> {
+    fn default() -> Self {
+        Self {
+            repr: SegmentsRepr::Multi(Vec::new()),
+            len: 0,
+        }
+    }
+}
+
 impl<'src> Segments<'src> {
     /// Create a new `Segments` from a vector of segments
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn new(segments: Vec<Segment<'src>>) -> Self {
-        let len = segments.iter().map(|(s, _)| s.len()).sum();
-        Self { segments, len }
+        let mut this = Self::default();
+        for (s, span) in segments {
+            this.push_segment(s, span);
+        }
+        this
+    }
+
+    /// Create a new `Segments` from a single contiguous segment
+    #[must_use]
+    pub(crate) const fn single(s: &'src str, span: Span) -> Self {
+        Self {
+            repr: SegmentsRepr::Single((s, span)),
+            len: s.len(),
+        }
+    }
+
+    /// Append the slice of `src` covered by `span`.
+    ///
+    /// If `span` directly follows the last segment, the last segment is
+    /// extended instead of starting a new one, so only folded lines end up
+    /// with more than one segment.
+    pub(crate) fn push(&mut self, src: &'src str, span: Span) {
+        if let Some((s, last)) = self.as_slice().last()
+            && last.end == span.start
+            && src
+                .get(last.start..)
+                .is_some_and(|rest| rest.as_ptr() == s.as_ptr())
+        {
+            let merged = Span::new(last.start, span.end);
+            let s = src
+                .get(merged.into_range())
+                .expect("spans come from tokenizing src");
+            self.len += span.end - span.start;
+            match &mut self.repr {
+                SegmentsRepr::Single(seg) => *seg = (s, merged),
+                SegmentsRepr::Multi(segments) => {
+                    *segments.last_mut().expect("last segment exists") = (s, merged);
+                }
+            }
+        } else {
+            let s = src
+                .get(span.into_range())
+                .expect("spans come from tokenizing src");
+            self.push_segment(s, span);
+        }
+    }
+
+    /// Append `s` in place of the source covered by `span`, such as a repaired value.
+    ///
+    /// Unlike [`Segments::push`], the segment is never merged with its neighbours.
+    pub(crate) fn push_replacement(&mut self, s: &'static str, span: Span) {
+        self.push_segment(s, span);
+    }
+
+    fn push_segment(&mut self, s: &'src str, span: Span) {
+        self.len += s.len();
+        match &mut self.repr {
+            SegmentsRepr::Multi(segments) if segments.is_empty() => {
+                self.repr = SegmentsRepr::Single((s, span));
+            }
+            SegmentsRepr::Multi(segments) => segments.push((s, span)),
+            SegmentsRepr::Single(first) => {
+                self.repr = SegmentsRepr::Multi(vec![*first, (s, span)]);
+            }
+        }
+    }
+
+    /// View the segments as a slice
+    #[must_use]
+    pub(crate) fn as_slice(&self) -> &[Segment<'src>] {
+        match &self.repr {
+            SegmentsRepr::Single(seg) => slice::from_ref(seg),
+            SegmentsRepr::Multi(segments) => segments,
+        }
     }
 
     /// Get the total length in bytes of all segments
@@ -127,7 +236,8 @@ impl<'src> Segments<'src> {
     /// Get the full span from first to last segment
     #[must_use]
     pub fn span(&self) -> Span {
-        match (self.segments.first(), self.segments.last()) {
+        let segments = self.as_slice();
+        match (segments.first(), segments.last()) {
             (Some((_, first_span)), Some((_, last_span))) => Span {
                 start: first_span.start,
                 end: last_span.end,
@@ -137,22 +247,11 @@ impl<'src> Segments<'src> {
     }
 
     /// Resolve segments into a single string (borrowed if single segment, owned otherwise)
-    ///
-    /// # Panics
-    ///
-    /// Panics if there are no segments. This should never happen in practice
-    /// as `Segments` is always created with at least one segment.
     #[must_use]
     pub fn resolve(&self) -> Cow<'src, str> {
-        if self.segments.len() == 1 {
-            let s = self.segments.first().unwrap().0; // SAFETY: due to len() == 1
-            Cow::Borrowed(s)
-        } else {
-            let mut s = String::with_capacity(self.len);
-            for (seg, _) in &self.segments {
-                s.push_str(seg);
-            }
-            Cow::Owned(s)
+        match &self.repr {
+            SegmentsRepr::Single((s, _)) => Cow::Borrowed(*s),
+            SegmentsRepr::Multi(_) => Cow::Owned(self.to_owned()),
         }
     }
 
@@ -162,11 +261,16 @@ impl<'src> Segments<'src> {
     /// `Display` trait's `to_string()` method, as it uses the known capacity.
     #[must_use]
     pub fn to_owned(&self) -> String {
-        let mut s = String::with_capacity(self.len);
-        for (seg, _) in &self.segments {
-            s.push_str(seg);
+        match &self.repr {
+            SegmentsRepr::Single((s, _)) => (*s).to_owned(),
+            SegmentsRepr::Multi(segments) => {
+                let mut s = String::with_capacity(self.len);
+                for (seg, _) in segments {
+                    s.push_str(seg);
+                }
+                s
+            }
         }
-        s
     }
 
     /// Check if segments start with the given prefix, ignoring ASCII case
@@ -179,7 +283,7 @@ impl<'src> Segments<'src> {
         }
 
         let mut remaining = prefix;
-        for (seg, _) in &self.segments {
+        for (seg, _) in self.as_slice() {
             if remaining.is_empty() {
                 return true;
             } else if seg.len() >= remaining.len() {
@@ -202,7 +306,7 @@ impl<'src> Segments<'src> {
             return false;
         }
 
-        for (seg, _) in &self.segments {
+        for (seg, _) in self.as_slice() {
             let Some((head, tail)) = other.split_at_checked(seg.len()) else {
                 return false;
             };
@@ -217,7 +321,7 @@ impl<'src> Segments<'src> {
 
     pub(crate) fn into_spanned_chars(self) -> SegmentedSpannedChars<'src> {
         SegmentedSpannedChars {
-            segments: self.segments,
+            segments: self,
             seg_idx: 0,
             chars: None,
         }
@@ -226,7 +330,7 @@ impl<'src> Segments<'src> {
 
 impl Display for Segments<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for (seg, _) in &self.segments {
+        for (seg, _) in self.as_slice() {
             Display::fmt(seg, f)?;
         }
         Ok(())
@@ -235,35 +339,37 @@ impl Display for Segments<'_> {
 
 impl StringStorage for Segments<'_> {
     type Span = Span;
+
+    fn as_str_lossy(&self) -> Cow<'_, str> {
+        self.resolve()
+    }
 }
 
 /// Iterator over characters in spanned segments
 #[derive(Debug, Clone)]
 pub struct SegmentedSpannedChars<'src> {
-    segments: Vec<Segment<'src>>,
+    segments: Segments<'src>,
     seg_idx: usize,
-    chars: Option<(Span, Peekable<CharIndices<'src>>)>,
+    chars: Option<Peekable<CharIndices<'src>>>,
 }
 
 impl Iterator for SegmentedSpannedChars<'_> {
     type Item = (char, Span);
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.seg_idx < self.segments.len() {
-            if let Some((ref span, ref mut chars)) = self.chars {
-                if let Some((start, c)) = chars.next() {
-                    let char_span = match chars.peek() {
-                        Some((end, _)) => Span::new(span.start + start, span.start + end),
-                        None => Span::new(span.start + start, span.end),
-                    };
-                    return Some((c, char_span));
-                }
-                self.seg_idx += 1;
-                self.chars = None;
-            } else {
-                let (s, span) = self.segments.get(self.seg_idx).unwrap(); // SAFETY: due to while condition
-                self.chars = Some((*span, s.char_indices().peekable()));
+        while let Some(&(s, span)) = self.segments.as_slice().get(self.seg_idx) {
+            let chars = self
+                .chars
+                .get_or_insert_with(|| s.char_indices().peekable());
+            if let Some((start, c)) = chars.next() {
+                let char_span = match chars.peek() {
+                    Some((end, _)) => Span::new(span.start + start, span.start + end),
+                    None => Span::new(span.start + start, span.end),
+                };
+                return Some((c, char_span));
             }
+            self.seg_idx += 1;
+            self.chars = None;
         }
 
         None
@@ -301,4 +407,37 @@ mod tests {
         assert!(segments.starts_with_str_ignore_ascii_case("x-"));
         assert!(segments.starts_with_str_ignore_ascii_case("X-C"));
     }
+
+    #[test]
+    fn spanned_segments_push_merges_adjacent_spans() {
+        let src = "DESCRIPTION:Hello\r\n World";
+
+        let mut segments = Segments::default();
+        segments.push(src, Span::new(12, 17));
+        assert_eq!(segments.as_slice().len(), 1);
+        assert_eq!(segments.resolve(), "Hello");
+
+        // Folded continuation is not adjacent and starts a new segment
+        segments.push(src, Span::new(20, 21));
+        segments.push(src, Span::new(21, 25));
+        assert_eq!(segments.as_slice().len(), 2);
+        assert_eq!(segments.len(), 10);
+        assert_eq!(segments.to_owned(), "HelloWorld");
+        assert_eq!(segments.span(), Span::new(12, 25));
+    }
+
+    #[test]
+    fn spanned_segments_as_str_lossy_borrows_single_segment() {
+        let src = "DESCRIPTION:Hello\r\n World";
+
+        let mut segments = Segments::default();
+        segments.push(src, Span::new(12, 17));
+        assert!(matches!(segments.as_str_lossy(), Cow::Borrowed("Hello")));
+
+        segments.push(src, Span::new(20, 25));
+        assert!(matches!(segments.as_str_lossy(), Cow::Owned(s) if s == "HelloWorld"));
+
+        let owned = "Hello".to_string();
+        assert!(matches!(owned.as_str_lossy(), Cow::Borrowed("Hello")));
+    }
 }
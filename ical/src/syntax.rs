@@ -29,15 +29,17 @@
 //! assert_eq!(components[0].name.resolve().as_ref(), "VCALENDAR");
 //! ```
 
+mod compat;
 mod lexer;
 mod scanner;
 mod tree_builder;
 
+pub use compat::{Compat, Repair, RepairKind};
 pub use lexer::{SpannedToken, Token, tokenize};
 pub use scanner::{ContentLine, ContentLineError, ScanResult, scan_content_lines};
 pub use tree_builder::{
     RawComponent, RawParameter, RawParameterValue, RawProperty, TreeBuildError, TreeBuilderResult,
-    build_tree,
+    build_tree, build_tree_with_limits,
 };
 
 use std::fmt;
@@ -64,6 +66,23 @@ pub struct ParseOptions {
     /// RFC 5545 specifies CRLF (`\r\n`) as the line ending, but many real-world
     /// iCalendar files use bare LF (`\n`). Default is `false` for compatibility.
     pub strict_line_endings: bool,
+
+    /// Bounds on the size of the parsed data, see [`ParseLimits`].
+    pub limits: ParseLimits,
+
+    /// When `true`, a property occurring more than once in a component that allows it at most
+    /// once is reported as an error.
+    ///
+    /// When `false`, the first occurrence is kept and the others are reported as warnings by
+    /// [`crate::parse_with_diagnostics_and_options`]. Default is `true`, see
+    /// [`crate::semantic::cardinality`] for the allowed occurrences.
+    pub strict_cardinality: bool,
+
+    /// Repairs of common vendor malformations to apply, see [`Compat`].
+    ///
+    /// Each repair is reported as a warning by [`crate::parse_with_diagnostics_and_options`].
+    /// Default is [`Compat::off`].
+    pub compat: Compat,
 }
 
 impl Default for ParseOptions {
@@ -78,6 +97,9 @@ impl ParseOptions {
     pub const fn new() -> Self {
         Self {
             strict_line_endings: false, // Default to lenient line ending handling
+            limits: ParseLimits::new(),
+            strict_cardinality: true,
+            compat: Compat::off(),
         }
     }
 
@@ -86,6 +108,9 @@ impl ParseOptions {
     pub const fn strict() -> Self {
         Self {
             strict_line_endings: true,
+            limits: ParseLimits::new(),
+            strict_cardinality: true,
+            compat: Compat::off(),
         }
     }
 
@@ -98,6 +123,117 @@ impl ParseOptions {
         self.strict_line_endings = strict;
         self
     }
+
+    /// Set whether to reject properties occurring more often than their component allows.
+    ///
+    /// When `false`, the first occurrence is kept and the others are reported as warnings.
+    /// Default is `true`.
+    #[must_use]
+    pub const fn strict_cardinality(mut self, strict: bool) -> Self {
+        self.strict_cardinality = strict;
+        self
+    }
+
+    /// Set the repairs of common vendor malformations to apply.
+    #[must_use]
+    pub const fn compat(mut self, compat: Compat) -> Self {
+        self.compat = compat;
+        self
+    }
+
+    /// Set the limits on the size of the parsed data.
+    #[must_use]
+    pub const fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+/// Limits on the size of parsed data, protecting against malicious input.
+///
+/// Exceeding a limit is reported as a syntax error, and the parser stops building
+/// the component tree instead of allocating without bound. Only an inline binary
+/// value too large is reported by the typed analysis, as it is measured decoded.
+/// Limiting the total size of the input is left to the caller.
+///
+/// The defaults are far above what real calendars use. Use [`ParseLimits::unlimited`]
+/// for trusted input.
+///
+/// # Example
+///
+/// ```rust
+/// use aimcal_ical::syntax::{ParseLimits, ParseOptions, syntax_analysis_with_options};
+///
+/// let src = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example//EN\r\nEND:VCALENDAR\r\n";
+/// let limits = ParseLimits {
+///     max_properties: 1,
+///     ..ParseLimits::default()
+/// };
+/// let result = syntax_analysis_with_options(src, ParseOptions::new().limits(limits));
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum number of properties in a single component.
+    pub max_properties: usize,
+
+    /// Maximum number of components in a calendar, counting nested ones and the
+    /// calendar itself.
+    pub max_components: usize,
+
+    /// Maximum nesting depth of components, where a calendar has depth 1.
+    pub max_depth: usize,
+
+    /// Maximum number of parameters of a single property.
+    pub max_parameters: usize,
+
+    /// Maximum length in bytes of an unfolded content line.
+    pub max_line_length: usize,
+
+    /// Maximum number of comma-separated values of a property or parameter.
+    pub max_values: usize,
+
+    /// Maximum size in decoded bytes of an inline BINARY value, such as an attachment.
+    ///
+    /// Unlimited by default, since such values are already bounded by
+    /// [`ParseLimits::max_line_length`].
+    pub max_binary_size: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseLimits {
+    /// Create the default limits.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_properties: 10_000,
+            max_components: 100_000,
+            max_depth: 16,
+            max_parameters: 100,
+            max_line_length: 16 * 1024 * 1024, // inline attachments can be several MB
+            max_values: 10_000,
+            max_binary_size: usize::MAX,
+        }
+    }
+
+    /// Create limits that never trigger, for trusted input.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_properties: usize::MAX,
+            max_components: usize::MAX,
+            max_depth: usize::MAX,
+            max_parameters: usize::MAX,
+            max_line_length: usize::MAX,
+            max_values: usize::MAX,
+            max_binary_size: usize::MAX,
+        }
+    }
 }
 
 /// Parse raw iCalendar components from source text
@@ -150,10 +286,70 @@ pub fn syntax_analysis(src: &str) -> Result<Vec<RawComponent<'_>>, Vec<SyntaxErr
 /// # Errors
 ///
 /// Returns a vector of [`SyntaxError`] if tokenization, scanning, or tree building fails.
-pub fn syntax_analysis_with_options<'src>(
-    src: &'src str,
+pub fn syntax_analysis_with_options(
+    src: &str,
     options: ParseOptions,
-) -> Result<Vec<RawComponent<'src>>, Vec<SyntaxError<'src>>> {
+) -> Result<Vec<RawComponent<'_>>, Vec<SyntaxError<'_>>> {
+    syntax_analysis_with_repairs(src, options).0
+}
+
+type SyntaxResult<'src> = Result<Vec<RawComponent<'src>>, Vec<SyntaxError<'src>>>;
+
+/// Parse raw iCalendar components from source text, returning the repairs of
+/// [`ParseOptions::compat`] applied along the way.
+pub(crate) fn syntax_analysis_with_repairs(
+    src: &str,
+    options: ParseOptions,
+) -> (SyntaxResult<'_>, Vec<Repair>) {
+    let PartialSyntax {
+        mut roots,
+        line_errors: mut errors,
+        tree_errors,
+        mut repairs,
+    } = scan_and_build(src, options);
+    errors.extend(tree_errors);
+
+    if !errors.is_empty() {
+        return (Err(errors), repairs);
+    }
+
+    compat::repair_components(src, &mut roots, options.compat, &mut repairs);
+    (Ok(roots), repairs)
+}
+
+/// The component tree built from the content lines that could be scanned, with the errors of
+/// the lines that could not and of the tree kept apart.
+#[derive(Debug)]
+pub(crate) struct PartialSyntax<'src> {
+    /// Components built from the lines without errors
+    pub roots: Vec<RawComponent<'src>>,
+    /// Errors of content lines, which were left out of the tree
+    pub line_errors: Vec<SyntaxError<'src>>,
+    /// Errors of the tree itself, such as mismatched BEGIN and END lines
+    pub tree_errors: Vec<SyntaxError<'src>>,
+    /// Repairs of [`ParseOptions::compat`] applied along the way
+    pub repairs: Vec<Repair>,
+}
+
+/// Parse raw iCalendar components from source text, keeping the components built despite the
+/// errors of some content lines.
+///
+/// The repairs of [`ParseOptions::compat`] are only applied to the components if the tree has
+/// no errors, as they rely on its nesting.
+pub(crate) fn syntax_analysis_partial(src: &str, options: ParseOptions) -> PartialSyntax<'_> {
+    let mut partial = scan_and_build(src, options);
+    if partial.tree_errors.is_empty() {
+        compat::repair_components(
+            src,
+            &mut partial.roots,
+            options.compat,
+            &mut partial.repairs,
+        );
+    }
+    partial
+}
+
+fn scan_and_build(src: &str, options: ParseOptions) -> PartialSyntax<'_> {
     // Tokenize
     let tokens = tokenize(src);
 
@@ -161,26 +357,26 @@ pub fn syntax_analysis_with_options<'src>(
     let scan_result = scan_content_lines(src, tokens, options);
 
     // Collect scanning errors
-    let mut errors: Vec<SyntaxError<'src>> = Vec::new();
-    for line in &scan_result.lines {
-        if let Some(ref error) = line.error {
-            errors.push(error.clone().into());
-        }
-    }
+    let line_errors = scan_result
+        .lines
+        .iter()
+        .filter_map(|line| line.error.clone())
+        .map(SyntaxError::from)
+        .collect();
 
     // Phase 2: Build component tree from content lines
-    let tree_result = build_tree(&scan_result.lines);
-
-    // Collect tree builder errors
-    for err in tree_result.errors {
-        errors.push(err.into());
+    let tree_result = build_tree_with_limits(&scan_result.lines, options.limits);
+
+    PartialSyntax {
+        roots: tree_result.roots,
+        line_errors,
+        tree_errors: tree_result
+            .errors
+            .into_iter()
+            .map(SyntaxError::from)
+            .collect(),
+        repairs: scan_result.repairs,
     }
-
-    if !errors.is_empty() {
-        return Err(errors);
-    }
-
-    Ok(tree_result.roots)
 }
 
 /// Unified error type for syntax analysis
@@ -0,0 +1,608 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Repairs of common malformations in iCalendar files written by other applications.
+//!
+//! Strict parsing rejects a file with any of these. In compatibility mode, see
+//! [`ParseOptions::compat`](super::ParseOptions::compat), each enabled repair
+//! fixes the problem in place and records a [`Repair`] saying what was fixed
+//! and where.
+//!
+//! The line level repairs are applied by the scanner, the others to the
+//! component tree before typed analysis. Repaired values replace the source
+//! text of their spans, so the components still borrow from the source.
+
+use std::fmt;
+
+use crate::keyword::{
+    KW_CN, KW_COMPLETED, KW_CREATED, KW_DTEND, KW_DTSTAMP, KW_DTSTART, KW_DUE, KW_EXDATE,
+    KW_LAST_MODIFIED, KW_RDATE, KW_RECURRENCE_ID, KW_TZID, KW_VCALENDAR,
+};
+use crate::string_storage::{Segments, Span};
+use crate::syntax::{RawComponent, RawParameter, RawParameterValue, RawProperty};
+
+/// Repairs applied when parsing in compatibility mode.
+///
+/// Every repair is off by default, see [`Compat::all`] to turn them all on.
+///
+/// # Example
+///
+/// ```rust
+/// use aimcal_ical::syntax::{Compat, ParseOptions};
+///
+/// // Everything but reading hour 24 as midnight of the next day
+/// let opts = ParseOptions::new().compat(Compat::all().hour_24(false));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[expect(clippy::struct_excessive_bools)]
+pub struct Compat {
+    /// Replace the Windows time zone names written by Outlook, such as
+    /// `(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna` or
+    /// `W. Europe Standard Time`, with their IANA names.
+    pub windows_tzid: bool,
+
+    /// Join the values of `CN` and `TZID` parameters split at unquoted commas,
+    /// such as `CN=Doe, Jane`.
+    pub unquoted_commas: bool,
+
+    /// Read hour 24 of a date-time, such as `20250101T240000`, as midnight of
+    /// the next day.
+    pub hour_24: bool,
+
+    /// Skip blank lines, such as those left before an `END` line.
+    pub blank_lines: bool,
+
+    /// Skip whitespace between a property name and the `:` or `;` after it.
+    pub space_before_colon: bool,
+
+    /// Merge a `VCALENDAR` wrapped in another one into the outer calendar.
+    pub nested_calendars: bool,
+}
+
+impl Compat {
+    /// Turn every repair off, the strict parsing of RFC 5545.
+    #[must_use]
+    pub const fn off() -> Self {
+        Self {
+            windows_tzid: false,
+            unquoted_commas: false,
+            hour_24: false,
+            blank_lines: false,
+            space_before_colon: false,
+            nested_calendars: false,
+        }
+    }
+
+    /// Turn every repair on.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            windows_tzid: true,
+            unquoted_commas: true,
+            hour_24: true,
+            blank_lines: true,
+            space_before_colon: true,
+            nested_calendars: true,
+        }
+    }
+
+    /// Whether any repair is on.
+    #[must_use]
+    pub const fn is_on(self) -> bool {
+        self.windows_tzid
+            || self.unquoted_commas
+            || self.hour_24
+            || self.blank_lines
+            || self.space_before_colon
+            || self.nested_calendars
+    }
+
+    /// Set whether to replace Windows time zone names with IANA ones.
+    #[must_use]
+    pub const fn windows_tzid(mut self, on: bool) -> Self {
+        self.windows_tzid = on;
+        self
+    }
+
+    /// Set whether to join `CN` and `TZID` parameter values split at unquoted commas.
+    #[must_use]
+    pub const fn unquoted_commas(mut self, on: bool) -> Self {
+        self.unquoted_commas = on;
+        self
+    }
+
+    /// Set whether to read hour 24 as midnight of the next day.
+    #[must_use]
+    pub const fn hour_24(mut self, on: bool) -> Self {
+        self.hour_24 = on;
+        self
+    }
+
+    /// Set whether to skip blank lines.
+    #[must_use]
+    pub const fn blank_lines(mut self, on: bool) -> Self {
+        self.blank_lines = on;
+        self
+    }
+
+    /// Set whether to skip whitespace before the `:` or `;` after a property name.
+    #[must_use]
+    pub const fn space_before_colon(mut self, on: bool) -> Self {
+        self.space_before_colon = on;
+        self
+    }
+
+    /// Set whether to merge nested `VCALENDAR` wrappers into the outer calendar.
+    #[must_use]
+    pub const fn nested_calendars(mut self, on: bool) -> Self {
+        self.nested_calendars = on;
+        self
+    }
+}
+
+/// A malformation fixed in compatibility mode.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct Repair {
+    /// Which repair was applied
+    pub kind: RepairKind,
+    /// Span of the repaired source
+    pub span: Span,
+    /// What was fixed
+    pub message: String,
+}
+
+/// The kinds of [`Repair`], one for each switch of [`Compat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairKind {
+    /// See [`Compat::windows_tzid`].
+    WindowsTzid,
+    /// See [`Compat::unquoted_commas`].
+    UnquotedComma,
+    /// See [`Compat::hour_24`].
+    Hour24,
+    /// See [`Compat::blank_lines`].
+    BlankLine,
+    /// See [`Compat::space_before_colon`].
+    SpaceBeforeColon,
+    /// See [`Compat::nested_calendars`].
+    NestedCalendar,
+}
+
+impl RepairKind {
+    /// Stable kebab-case identifier of the repair, the code of its diagnostic.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            RepairKind::WindowsTzid => "repaired-windows-tzid",
+            RepairKind::UnquotedComma => "repaired-unquoted-comma",
+            RepairKind::Hour24 => "repaired-hour-24",
+            RepairKind::BlankLine => "repaired-blank-line",
+            RepairKind::SpaceBeforeColon => "repaired-space-before-colon",
+            RepairKind::NestedCalendar => "repaired-nested-calendar",
+        }
+    }
+}
+
+impl fmt::Display for RepairKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Repair {
+    pub(crate) fn new(kind: RepairKind, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parameters whose values are free text, which Outlook and others leave unquoted even if they
+/// contain commas.
+const TEXT_PARAMETERS: &[&str] = &[KW_CN, KW_TZID];
+
+/// Properties whose values are date-times, or lists of them.
+const DATE_TIME_PROPERTIES: &[&str] = &[
+    KW_DTSTART,
+    KW_DTEND,
+    KW_DUE,
+    KW_RECURRENCE_ID,
+    KW_EXDATE,
+    KW_RDATE,
+    KW_DTSTAMP,
+    KW_CREATED,
+    KW_LAST_MODIFIED,
+    KW_COMPLETED,
+];
+
+/// Windows time zones by the label of their display name, without the UTC offset in front, and
+/// by their ID, with the IANA zone CLDR maps them to. The ID is left empty where it is a valid
+/// IANA name as well.
+#[rustfmt::skip]
+const WINDOWS_ZONES: &[(&str, &str, &str)] = &[
+    ("Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna", "W. Europe Standard Time", "Europe/Berlin"),
+    ("Belgrade, Bratislava, Budapest, Ljubljana, Prague", "Central Europe Standard Time", "Europe/Budapest"),
+    ("Brussels, Copenhagen, Madrid, Paris", "Romance Standard Time", "Europe/Paris"),
+    ("Sarajevo, Skopje, Warsaw, Zagreb", "Central European Standard Time", "Europe/Warsaw"),
+    ("Dublin, Edinburgh, Lisbon, London", "GMT Standard Time", "Europe/London"),
+    ("Athens, Bucharest", "GTB Standard Time", "Europe/Bucharest"),
+    ("Helsinki, Kyiv, Riga, Sofia, Tallinn, Vilnius", "FLE Standard Time", "Europe/Helsinki"),
+    ("Moscow, St. Petersburg", "Russian Standard Time", "Europe/Moscow"),
+    ("Istanbul", "Turkey Standard Time", "Europe/Istanbul"),
+    ("Jerusalem", "Israel Standard Time", "Asia/Jerusalem"),
+    ("Cairo", "Egypt Standard Time", "Africa/Cairo"),
+    ("Johannesburg", "South Africa Standard Time", "Africa/Johannesburg"),
+    ("Abu Dhabi, Muscat", "Arabian Standard Time", "Asia/Dubai"),
+    ("Kuwait, Riyadh", "Arab Standard Time", "Asia/Riyadh"),
+    ("Chennai, Kolkata, Mumbai, New Delhi", "India Standard Time", "Asia/Kolkata"),
+    ("Bangkok, Hanoi, Jakarta", "SE Asia Standard Time", "Asia/Bangkok"),
+    ("Beijing, Chongqing, Hong Kong, Urumqi", "China Standard Time", "Asia/Shanghai"),
+    ("Kuala Lumpur, Singapore", "Singapore Standard Time", "Asia/Singapore"),
+    ("Taipei", "Taipei Standard Time", "Asia/Taipei"),
+    ("Osaka, Sapporo, Tokyo", "Tokyo Standard Time", "Asia/Tokyo"),
+    ("Seoul", "Korea Standard Time", "Asia/Seoul"),
+    ("Brisbane", "E. Australia Standard Time", "Australia/Brisbane"),
+    ("Canberra, Melbourne, Sydney", "AUS Eastern Standard Time", "Australia/Sydney"),
+    ("Auckland, Wellington", "New Zealand Standard Time", "Pacific/Auckland"),
+    ("Hawaii", "Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaska", "Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Time (US & Canada)", "Pacific Standard Time", "America/Los_Angeles"),
+    ("Arizona", "US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Time (US & Canada)", "Mountain Standard Time", "America/Denver"),
+    ("Central Time (US & Canada)", "Central Standard Time", "America/Chicago"),
+    ("Guadalajara, Mexico City, Monterrey", "Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Eastern Time (US & Canada)", "Eastern Standard Time", "America/New_York"),
+    ("Atlantic Time (Canada)", "Atlantic Standard Time", "America/Halifax"),
+    ("Brasilia", "E. South America Standard Time", "America/Sao_Paulo"),
+    ("City of Buenos Aires", "Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("Coordinated Universal Time", "", "Etc/UTC"),
+];
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// Apply the repairs of `compat` to components built from `src`, recording each in `repairs`.
+pub(crate) fn repair_components<'src>(
+    src: &'src str,
+    components: &mut [RawComponent<'src>],
+    compat: Compat,
+    repairs: &mut Vec<Repair>,
+) {
+    for component in components {
+        if compat.nested_calendars && component.name.eq_str_ignore_ascii_case(KW_VCALENDAR) {
+            merge_nested_calendars(component, repairs);
+        }
+        repair_component(src, component, compat, repairs);
+    }
+}
+
+fn repair_component<'src>(
+    src: &'src str,
+    component: &mut RawComponent<'src>,
+    compat: Compat,
+    repairs: &mut Vec<Repair>,
+) {
+    for prop in &mut component.properties {
+        repair_property(src, prop, compat, repairs);
+    }
+    for child in &mut component.children {
+        repair_component(src, child, compat, repairs);
+    }
+}
+
+fn repair_property<'src>(
+    src: &'src str,
+    prop: &mut RawProperty<'src>,
+    compat: Compat,
+    repairs: &mut Vec<Repair>,
+) {
+    for param in &mut prop.parameters {
+        if compat.unquoted_commas {
+            join_unquoted_commas(src, param, repairs);
+        }
+        if compat.windows_tzid
+            && param.name.eq_str_ignore_ascii_case(KW_TZID)
+            && let [value] = param.values.as_mut_slice()
+        {
+            replace_windows_tzid(&mut value.value, repairs);
+        }
+    }
+
+    // The TZID of a VTIMEZONE, which the parameters refer to
+    if compat.windows_tzid && prop.name.eq_str_ignore_ascii_case(KW_TZID) {
+        replace_windows_tzid(&mut prop.value, repairs);
+    }
+
+    if compat.hour_24
+        && DATE_TIME_PROPERTIES
+            .iter()
+            .any(|name| prop.name.eq_str_ignore_ascii_case(name))
+    {
+        repair_hour_24(src, prop, repairs);
+    }
+}
+
+/// Join the values of a free text parameter split at unquoted commas.
+fn join_unquoted_commas<'src>(
+    src: &'src str,
+    param: &mut RawParameter<Segments<'src>>,
+    repairs: &mut Vec<Repair>,
+) {
+    if param.values.len() < 2
+        || param.values.iter().any(|v| v.quoted)
+        || !TEXT_PARAMETERS
+            .iter()
+            .any(|name| param.name.eq_str_ignore_ascii_case(name))
+    {
+        return;
+    }
+
+    let mut joined = Segments::default();
+    let mut end = None;
+    for value in &param.values {
+        let span = value.value.span();
+        if let Some(end) = end {
+            joined.push(src, Span::new(end, span.start)); // the comma
+        }
+        for (_, span) in value.value.as_slice() {
+            joined.push(src, *span);
+        }
+        end = Some(span.end);
+    }
+
+    repairs.push(Repair::new(
+        RepairKind::UnquotedComma,
+        joined.span(),
+        format!(
+            "quoted the value of parameter {} containing commas: \"{joined}\"",
+            param.name
+        ),
+    ));
+    param.values = vec![RawParameterValue {
+        value: joined,
+        quoted: true,
+    }];
+}
+
+/// Replace a Windows time zone name with its IANA name.
+fn replace_windows_tzid(value: &mut Segments<'_>, repairs: &mut Vec<Repair>) {
+    let tzid = value.resolve();
+    let Some(iana) = windows_zone(&tzid) else {
+        return;
+    };
+
+    let span = value.span();
+    repairs.push(Repair::new(
+        RepairKind::WindowsTzid,
+        span,
+        format!("replaced Windows time zone \"{tzid}\" with {iana}"),
+    ));
+    // Values are lexed by offsets into their segments, so the span must be as long as the name
+    *value = Segments::single(iana, Span::new(span.start, span.start + iana.len()));
+}
+
+/// The IANA zone of a Windows time zone display name or ID.
+fn windows_zone(tzid: &str) -> Option<&'static str> {
+    let tzid = tzid.trim();
+    // Display names start with the standard offset, `(UTC+01:00) ` or `(GMT+01:00) ` in older
+    // versions of Windows
+    let label = match tzid.strip_prefix('(') {
+        Some(rest) if rest.starts_with("UTC") || rest.starts_with("GMT") => rest
+            .split_once(')')
+            .map_or(tzid, |(_, label)| label.trim_start()),
+        _ => tzid,
+    };
+    WINDOWS_ZONES
+        .iter()
+        .find(|(name, id, _)| {
+            name.eq_ignore_ascii_case(label)
+                || (label == tzid && !id.is_empty() && id.eq_ignore_ascii_case(tzid))
+        })
+        .map(|(_, _, iana)| *iana)
+}
+
+/// Replace date-times at hour 24 in a property value with midnight of the next day.
+///
+/// Only values on a single line are repaired, folding would split the digits to replace.
+fn repair_hour_24<'src>(src: &'src str, prop: &mut RawProperty<'src>, repairs: &mut Vec<Repair>) {
+    let &[(text, span)] = prop.value.as_slice() else {
+        return;
+    };
+
+    let mut value = Segments::default();
+    let mut offset = span.start;
+    let mut repaired = false;
+    for (i, item) in text.split(',').enumerate() {
+        if i > 0 {
+            value.push(src, Span::new(offset, offset + 1)); // the comma
+            offset += 1;
+        }
+        let item_span = Span::new(offset, offset + item.len());
+        offset = item_span.end;
+
+        let Some(next_day) = next_day_of_hour_24(item) else {
+            value.push(src, item_span);
+            continue;
+        };
+
+        // The digits of the date and the hour are replaced, the rest is kept from the source
+        for (j, digit) in next_day.iter().enumerate() {
+            let at = item_span.start + j;
+            let digit = DIGITS.get(usize::from(*digit)).expect("decimal digit");
+            value.push_replacement(digit, Span::new(at, at + 1));
+        }
+        let hour = item_span.start + 9;
+        value.push(src, Span::new(hour - 1, hour)); // the `T`
+        value.push_replacement("00", Span::new(hour, hour + 2));
+        value.push(src, Span::new(hour + 2, item_span.end));
+
+        repairs.push(Repair::new(
+            RepairKind::Hour24,
+            item_span,
+            format!(
+                "read {item} at hour 24 of {} as midnight of the next day",
+                prop.name
+            ),
+        ));
+        repaired = true;
+    }
+
+    if repaired {
+        prop.value = value;
+    }
+}
+
+/// The digits of the date after that of a `YYYYMMDDT240000` date-time, or `None` if it is not
+/// at hour 24 or not a valid date.
+fn next_day_of_hour_24(item: &str) -> Option<[u8; 8]> {
+    let (date, time) = item.split_once(['T', 't'])?;
+    let time = time.strip_suffix(['Z', 'z']).unwrap_or(time);
+    if time != "240000" || date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let number = |range: std::ops::Range<usize>| date.get(range)?.parse::<u32>().ok();
+    let (mut year, mut month, mut day) = (number(0..4)?, number(4..6)?, number(6..8)?);
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    day += 1;
+    if day > days_in_month(year, month) {
+        day = 1;
+        month += 1;
+        if month > 12 {
+            month = 1;
+            year += 1;
+        }
+    }
+    if year > 9999 {
+        return None;
+    }
+
+    let text = format!("{year:04}{month:02}{day:02}");
+    let mut digits = [0; 8];
+    for (digit, b) in digits.iter_mut().zip(text.bytes()) {
+        *digit = b - b'0';
+    }
+    Some(digits)
+}
+
+const fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Merge the `VCALENDAR`s wrapped in a calendar into it.
+///
+/// The components of the inner calendars are moved into the outer one, their properties only if
+/// the outer calendar does not have one of the same name, such as `VERSION` or `PRODID`.
+fn merge_nested_calendars(calendar: &mut RawComponent<'_>, repairs: &mut Vec<Repair>) {
+    let mut children = Vec::with_capacity(calendar.children.len());
+    for child in std::mem::take(&mut calendar.children) {
+        if !child.name.eq_str_ignore_ascii_case(KW_VCALENDAR) {
+            children.push(child);
+            continue;
+        }
+
+        let mut inner = child;
+        merge_nested_calendars(&mut inner, repairs);
+        repairs.push(Repair::new(
+            RepairKind::NestedCalendar,
+            inner.span,
+            "merged a VCALENDAR nested in another one into it",
+        ));
+        for prop in inner.properties {
+            let name = prop.name.resolve();
+            if !calendar
+                .properties
+                .iter()
+                .any(|p| p.name.eq_str_ignore_ascii_case(&name))
+            {
+                calendar.properties.push(prop);
+            }
+        }
+        children.extend(inner.children);
+    }
+    calendar.children = children;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_windows_zones_by_label_and_id() {
+        let cases = [
+            (
+                "(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna",
+                Some("Europe/Berlin"),
+            ),
+            (
+                "(GMT-05:00) Eastern Time (US & Canada)",
+                Some("America/New_York"),
+            ),
+            ("(UTC) Coordinated Universal Time", Some("Etc/UTC")),
+            ("Pacific Standard Time", Some("America/Los_Angeles")),
+            ("tokyo standard time", Some("Asia/Tokyo")),
+            ("Europe/Berlin", None),
+            ("(UTC+01:00) Somewhere Else", None),
+            ("UTC", None),
+        ];
+        for (tzid, expected) in cases {
+            assert_eq!(windows_zone(tzid), expected, "{tzid}");
+        }
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn windows_zones_map_to_iana_zones() {
+        for (_, _, iana) in WINDOWS_ZONES {
+            assert!(jiff::tz::TimeZone::get(iana).is_ok(), "{iana}");
+        }
+    }
+
+    #[test]
+    fn rolls_hour_24_over_to_the_next_day() {
+        let digits = |s: &str| {
+            let mut out = [0; 8];
+            for (d, b) in out.iter_mut().zip(s.bytes()) {
+                *d = b - b'0';
+            }
+            out
+        };
+        assert_eq!(
+            next_day_of_hour_24("20250101T240000"),
+            Some(digits("20250102"))
+        );
+        assert_eq!(
+            next_day_of_hour_24("20250131T240000Z"),
+            Some(digits("20250201"))
+        );
+        assert_eq!(
+            next_day_of_hour_24("20241231T240000"),
+            Some(digits("20250101"))
+        );
+        assert_eq!(
+            next_day_of_hour_24("20240228T240000"),
+            Some(digits("20240229"))
+        );
+        assert_eq!(
+            next_day_of_hour_24("21000228T240000"),
+            Some(digits("21000301"))
+        );
+        assert_eq!(next_day_of_hour_24("20250101T230000"), None);
+        assert_eq!(next_day_of_hour_24("20250101T243000"), None);
+        assert_eq!(next_day_of_hour_24("20250230T240000"), None);
+        assert_eq!(next_day_of_hour_24("20250101"), None);
+    }
+}
@@ -40,6 +40,7 @@ use std::iter::Peekable;
 
 use crate::string_storage::{Segments, Span};
 use crate::syntax::ParseOptions;
+use crate::syntax::compat::{Repair, RepairKind};
 use crate::syntax::lexer::{SpannedToken, Token};
 
 /// A scanned iCalendar content line.
@@ -145,6 +146,47 @@ pub enum ContentLineError {
         /// Span of the bare LF character
         span: Span,
     },
+
+    /// Content line longer than [`ParseLimits::max_line_length`](super::ParseLimits::max_line_length) once unfolded.
+    LineTooLong {
+        /// Span of the content line up to where the limit was exceeded
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
+
+    /// Property with more than [`ParseLimits::max_parameters`](super::ParseLimits::max_parameters) parameters.
+    TooManyParameters {
+        /// Span of the first parameter over the limit
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
+
+    /// Property or parameter with more than [`ParseLimits::max_values`](super::ParseLimits::max_values) values.
+    TooManyValues {
+        /// Span of the first value over the limit
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
+}
+
+impl ContentLineError {
+    /// Get the span of the error.
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        match self {
+            ContentLineError::MissingColon { expected_at, .. } => *expected_at,
+            ContentLineError::EmptyLine { span }
+            | ContentLineError::InvalidParameter { span, .. }
+            | ContentLineError::MalformedLine { span, .. }
+            | ContentLineError::BareLineEnding { span }
+            | ContentLineError::LineTooLong { span, .. }
+            | ContentLineError::TooManyParameters { span, .. }
+            | ContentLineError::TooManyValues { span, .. } => *span,
+        }
+    }
 }
 
 impl fmt::Display for ContentLineError {
@@ -165,6 +207,15 @@ impl fmt::Display for ContentLineError {
             ContentLineError::BareLineEnding { .. } => {
                 write!(f, "bare LF line ending; iCalendar requires CRLF (\\r\\n)")
             }
+            ContentLineError::LineTooLong { limit, .. } => {
+                write!(f, "content line longer than {limit} bytes")
+            }
+            ContentLineError::TooManyParameters { limit, .. } => {
+                write!(f, "property with more than {limit} parameters")
+            }
+            ContentLineError::TooManyValues { limit, .. } => {
+                write!(f, "more than {limit} values in a list")
+            }
         }
     }
 }
@@ -201,6 +252,9 @@ pub struct ScanResult<'src> {
 
     /// Whether any errors were encountered
     pub has_errors: bool,
+
+    /// Malformations repaired by [`ParseOptions::compat`], such as skipped blank lines
+    pub repairs: Vec<Repair>,
 }
 
 /// Scan a token stream into content lines.
@@ -240,10 +294,21 @@ pub fn scan_content_lines<'src>(
     let mut token_iter = tokens.into_iter().peekable();
     let mut lines = Vec::new();
     let mut has_errors = false;
+    let mut repairs = Vec::new();
 
     while token_iter.peek().is_some() {
-        match scan_one_content_line(src, &mut token_iter, options) {
+        match scan_one_content_line(src, &mut token_iter, options, &mut repairs) {
             Some(line) => {
+                if let Some(ContentLineError::EmptyLine { span }) = line.error
+                    && options.compat.blank_lines
+                {
+                    repairs.push(Repair::new(
+                        RepairKind::BlankLine,
+                        span,
+                        "skipped a blank line",
+                    ));
+                    continue;
+                }
                 if line.error.is_some() {
                     has_errors = true;
                 }
@@ -253,7 +318,11 @@ pub fn scan_content_lines<'src>(
         }
     }
 
-    ScanResult { lines, has_errors }
+    ScanResult {
+        lines,
+        has_errors,
+        repairs,
+    }
 }
 
 /// Scan a single content line from tokens.
@@ -263,6 +332,7 @@ fn scan_one_content_line<'src>(
     src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
     options: ParseOptions,
+    repairs: &mut Vec<Repair>,
 ) -> Option<ContentLine<'src>> {
     // Peek at first token to determine if we have content
     let first_token = *tokens.peek()?;
@@ -303,27 +373,51 @@ fn scan_one_content_line<'src>(
     }
 
     // Parse: name [;param]* : value \r\n
-    let result = parse_content_line_structure(src, tokens, line_start, options);
+    let result = parse_content_line_structure(src, tokens, line_start, options, repairs);
 
     Some(result)
 }
 
 /// Parse the structure of a content line.
+#[expect(clippy::too_many_lines)]
 fn parse_content_line_structure<'src>(
     src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
     line_start: Span,
     options: ParseOptions,
+    repairs: &mut Vec<Repair>,
 ) -> ContentLine<'src> {
     // Parse name (sequence of Word tokens)
-    let (name, _name_span) = parse_property_name(tokens);
+    let (name, name_span) = parse_property_name(src, tokens);
+    if options.compat.space_before_colon {
+        skip_space_after_name(tokens, &name, name_span, repairs);
+    }
 
     // Parse parameters (semicolon-separated)
+    let limits = options.limits;
     let mut parameters = Vec::new();
+    let mut length = LineLength {
+        used: name.len() + 1, // name and `;` or `:`
+        limit: limits.max_line_length,
+    };
     while let Some(&SpannedToken(Token::Semicolon, semi_span)) = tokens.peek() {
         tokens.next(); // consume semicolon
 
-        match parse_parameter(src, tokens) {
+        let result = if parameters.len() >= limits.max_parameters {
+            Err(ContentLineError::TooManyParameters {
+                span: semi_span,
+                limit: limits.max_parameters,
+            })
+        } else {
+            parse_parameter(src, tokens, &mut length, limits.max_values).map_err(|err| match err {
+                ParameterError::Kind(kind) => ContentLineError::InvalidParameter {
+                    span: semi_span,
+                    kind,
+                },
+                ParameterError::Limit(err) => err,
+            })
+        };
+        match result {
             Ok(param) => parameters.push(param),
             Err(err) => {
                 // Create error line but consume remaining tokens until newline to avoid infinite loop
@@ -334,10 +428,7 @@ fn parse_content_line_structure<'src>(
                     parameters,
                     value: Segments::default(),
                     span: Span::new(line_start.start, line_end),
-                    error: Some(ContentLineError::InvalidParameter {
-                        span: semi_span,
-                        kind: err,
-                    }),
+                    error: Some(err),
                 };
             }
         }
@@ -379,18 +470,15 @@ fn parse_content_line_structure<'src>(
     };
 
     // Parse value (everything until newline)
-    let (value, value_end, invalid_span) = parse_value(tokens);
+    let (value, value_end, value_error) = parse_value(src, tokens, length, limits.max_values);
 
-    if let Some(span) = invalid_span {
+    if let Some(error) = value_error {
         return ContentLine {
             name,
             parameters,
             value,
-            span: Span::new(line_start.start, span.end),
-            error: Some(ContentLineError::MalformedLine {
-                span,
-                message: invalid_token_message(src, span),
-            }),
+            span: Span::new(line_start.start, error.span().end),
+            error: Some(error),
         };
     }
 
@@ -440,27 +528,99 @@ fn parse_content_line_structure<'src>(
 ///
 /// Returns [`Segments`] and [`Span`] of the property name.
 fn parse_property_name<'src>(
+    src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
 ) -> (Segments<'src>, Span) {
-    let mut segments = Vec::new();
+    let mut segments = Segments::default();
     let mut start = None;
     let mut end = None;
 
     // Collect consecutive Word tokens (property names can be hyphenated like "PERCENT-COMPLETE")
-    while let Some(&SpannedToken(Token::Word(text), span)) = tokens.peek() {
+    while let Some(&SpannedToken(Token::Word(_), span)) = tokens.peek() {
         if start.is_none() {
             start = Some(span.start);
         }
         end = Some(span.end);
-        segments.push((text, span));
+        segments.push(src, span);
         tokens.next(); // consume the token
     }
 
-    if segments.is_empty() {
-        (Segments::default(), Span::new(0, 0))
-    } else {
-        let name_span = Span::new(start.unwrap(), end.unwrap());
-        (Segments::new(segments), name_span)
+    match (start, end) {
+        (Some(start), Some(end)) => (segments, Span::new(start, end)),
+        _ => (segments, Span::new(0, 0)),
+    }
+}
+
+/// Skip the whitespace some writers put between a property name and the `:` or `;` after it.
+fn skip_space_after_name<'src>(
+    tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
+    name: &Segments<'src>,
+    name_span: Span,
+    repairs: &mut Vec<Repair>,
+) {
+    if name.is_empty() {
+        return;
+    }
+    if let Some(&SpannedToken(Token::Symbol(s), span)) = tokens.peek()
+        && span.start == name_span.end
+        && s.bytes().all(|b| b == b' ' || b == b'\t')
+    {
+        tokens.next();
+        if matches!(
+            tokens.peek(),
+            Some(SpannedToken(Token::Colon | Token::Semicolon, _))
+        ) {
+            repairs.push(Repair::new(
+                RepairKind::SpaceBeforeColon,
+                span,
+                format!("skipped whitespace after property name {name}"),
+            ));
+        }
+    }
+}
+
+/// Error of [`parse_parameter`].
+enum ParameterError {
+    /// Invalid parameter syntax
+    Kind(ParameterErrorKind),
+    /// A limit was exceeded
+    Limit(ContentLineError),
+}
+
+impl From<ParameterErrorKind> for ParameterError {
+    fn from(kind: ParameterErrorKind) -> Self {
+        Self::Kind(kind)
+    }
+}
+
+impl From<ContentLineError> for ParameterError {
+    fn from(err: ContentLineError) -> Self {
+        Self::Limit(err)
+    }
+}
+
+/// Unfolded length of the content line scanned so far.
+#[derive(Debug, Clone, Copy)]
+struct LineLength {
+    used: usize,
+    limit: usize,
+}
+
+impl LineLength {
+    const fn add(&mut self, len: usize) {
+        self.used = self.used.saturating_add(len);
+    }
+
+    /// Check that `segments` still fit into the line.
+    const fn check(self, segments: &Segments<'_>, span: Span) -> Result<(), ContentLineError> {
+        if self.used.saturating_add(segments.len()) > self.limit {
+            Err(ContentLineError::LineTooLong {
+                span,
+                limit: self.limit,
+            })
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -468,43 +628,54 @@ fn parse_property_name<'src>(
 ///
 /// Format: `name=value` or `name=value1,value2`
 fn parse_parameter<'src>(
-    _src: &'src str,
+    src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
-) -> Result<ScannedParameter<'src>, ParameterErrorKind> {
+    length: &mut LineLength,
+    max_values: usize,
+) -> Result<ScannedParameter<'src>, ParameterError> {
     let param_start = match tokens.peek() {
         Some(&SpannedToken(_, span)) => span.start,
-        None => return Err(ParameterErrorKind::EmptyName),
+        None => return Err(ParameterErrorKind::EmptyName.into()),
     };
 
     // Parse parameter name
-    let mut name_segments = Vec::new();
+    let mut name = Segments::default();
     let mut name_end = None;
 
-    while let Some(&SpannedToken(Token::Word(text), span)) = tokens.peek() {
+    while let Some(&SpannedToken(Token::Word(_), span)) = tokens.peek() {
         name_end = Some(span.end);
-        name_segments.push((text, span));
+        name.push(src, span);
         tokens.next(); // consume the token
+        length.check(&name, Span::new(param_start, span.end))?;
     }
 
-    if name_segments.is_empty() {
-        return Err(ParameterErrorKind::EmptyName);
+    if name.is_empty() {
+        return Err(ParameterErrorKind::EmptyName.into());
     }
-
-    let name = Segments::new(name_segments);
+    length.add(name.len() + 1); // name and `=`
 
     // Expect equals sign
     match tokens.next() {
         Some(SpannedToken(Token::Equal, _)) => {}
-        Some(_) | None => return Err(ParameterErrorKind::MissingEquals),
+        Some(_) | None => return Err(ParameterErrorKind::MissingEquals.into()),
     }
 
     // Parse parameter values (comma-separated)
     let mut values = Vec::new();
     loop {
-        match parse_parameter_value(tokens) {
-            Ok(Some(value)) => values.push(value),
-            Ok(None) => break,
-            Err(err) => return Err(err),
+        match parse_parameter_value(src, tokens, *length)? {
+            Some(value) if values.len() >= max_values => {
+                return Err(ContentLineError::TooManyValues {
+                    span: value.span,
+                    limit: max_values,
+                }
+                .into());
+            }
+            Some(value) => {
+                length.add(value.value.len() + 1); // value and `,` or `:`
+                values.push(value);
+            }
+            None => break,
         }
 
         // Check for comma separator
@@ -517,7 +688,7 @@ fn parse_parameter<'src>(
     }
 
     if values.is_empty() {
-        return Err(ParameterErrorKind::MissingValue);
+        return Err(ParameterErrorKind::MissingValue.into());
     }
 
     let param_end = values
@@ -537,8 +708,10 @@ fn parse_parameter<'src>(
 ///
 /// Returns `Ok(None)` if there's no value to parse.
 fn parse_parameter_value<'src>(
+    src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
-) -> Result<Option<ScannedParameterValue<'src>>, ParameterErrorKind> {
+    length: LineLength,
+) -> Result<Option<ScannedParameterValue<'src>>, ParameterError> {
     let start = match tokens.peek() {
         Some(&SpannedToken(_, span)) => span.start,
         None => return Ok(None),
@@ -554,7 +727,7 @@ fn parse_parameter_value<'src>(
     };
 
     // Collect value tokens
-    let mut segments = Vec::new();
+    let mut segments = Segments::default();
 
     if quoted {
         // Collect until closing quote
@@ -563,18 +736,18 @@ fn parse_parameter_value<'src>(
                 // End of quoted string
                 Some(SpannedToken(Token::DQuote, span)) => {
                     return Ok(Some(ScannedParameterValue {
-                        value: Segments::new(segments),
+                        value: segments,
                         quoted: true,
                         span: Span::new(start, span.end),
                     }));
                 }
                 Some(token) => {
-                    let text = token_to_text(token.0);
-                    if !text.is_empty() {
-                        segments.push((text, token.1));
+                    if !token_to_text(token.0).is_empty() {
+                        segments.push(src, token.1);
+                        length.check(&segments, Span::new(start, token.1.end))?;
                     }
                 }
-                None => return Err(ParameterErrorKind::UnterminatedQuote),
+                None => return Err(ParameterErrorKind::UnterminatedQuote.into()),
             }
         }
     } else {
@@ -589,10 +762,10 @@ fn parse_parameter_value<'src>(
                     break;
                 }
                 _ => {
-                    let token = tokens.next().unwrap();
-                    let text = token_to_text(token.0);
-                    if !text.is_empty() {
-                        segments.push((text, token.1));
+                    let token = tokens.next().expect("peeked above");
+                    if !token_to_text(token.0).is_empty() {
+                        segments.push(src, token.1);
+                        length.check(&segments, Span::new(start, token.1.end))?;
                     }
                 }
             }
@@ -602,9 +775,9 @@ fn parse_parameter_value<'src>(
             return Ok(None);
         }
 
-        let end = segments.last().map_or(start, |(_, s)| s.end);
+        let end = segments.span().end;
         Ok(Some(ScannedParameterValue {
-            value: Segments::new(segments),
+            value: segments,
             quoted: false,
             span: Span::new(start, end),
         }))
@@ -613,12 +786,20 @@ fn parse_parameter_value<'src>(
 
 /// Parse value content (everything until newline).
 ///
-/// Returns ([`Segments`], `usize`) - value segments and end position.
+/// Values exceeding the [`ParseLimits`](super::ParseLimits) are skipped up to the end of the line.
+///
+/// Returns ([`Segments`], `usize`, error) - value segments, end position and
+/// the error that stopped the value, if any.
 fn parse_value<'src>(
+    src: &'src str,
     tokens: &mut Peekable<impl Iterator<Item = SpannedToken<'src>>>,
-) -> (Segments<'src>, usize, Option<Span>) {
-    let mut segments = Vec::new();
+    length: LineLength,
+    max_values: usize,
+) -> (Segments<'src>, usize, Option<ContentLineError>) {
+    let mut segments = Segments::default();
     let mut end = 0;
+    let mut values = 1;
+    let mut escaped = false;
 
     // Collect all tokens until newline
     while let Some(token) = tokens.peek() {
@@ -626,31 +807,50 @@ fn parse_value<'src>(
             break;
         }
         if matches!(token.0, Token::Error) {
+            let span = token.1;
+            let message = invalid_token_message(src, span);
             return (
-                if segments.is_empty() {
-                    Segments::default()
-                } else {
-                    Segments::new(segments)
-                },
+                segments,
                 end,
-                Some(token.1),
+                Some(ContentLineError::MalformedLine { span, message }),
             );
         }
 
-        let token = tokens.next().unwrap();
+        let token = tokens.next().expect("peeked above");
         end = token.1.end;
 
-        let text = token_to_text(token.0);
-        if !text.is_empty() {
-            segments.push((text, token.1));
+        // Count the separators of multi-value lists, skipping escaped commas in text
+        if token.0 == Token::Comma && !escaped {
+            values += 1;
+        }
+        escaped = match token.0 {
+            Token::Symbol(s) => s.bytes().rev().take_while(|&b| b == b'\\').count() % 2 == 1,
+            _ => false,
+        };
+
+        if !token_to_text(token.0).is_empty() {
+            segments.push(src, token.1);
+        }
+
+        let result = if values > max_values {
+            Err(ContentLineError::TooManyValues {
+                span: token.1,
+                limit: max_values,
+            })
+        } else {
+            length.check(&segments, Span::new(segments.span().start, end))
+        };
+        if let Err(err) = result {
+            consume_until_newline(tokens);
+            return (segments, end, Some(err));
         }
     }
 
     if segments.is_empty() {
-        return (Segments::default(), 0, None);
+        return (segments, 0, None);
     }
 
-    (Segments::new(segments), end, None)
+    (segments, end, None)
 }
 
 /// Get the current end position from token iterator.
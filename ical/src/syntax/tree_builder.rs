@@ -23,6 +23,7 @@
 use crate::StringStorage;
 use crate::keyword::{KW_BEGIN, KW_END};
 use crate::string_storage::{Segments, Span};
+use crate::syntax::ParseLimits;
 use crate::syntax::scanner::ContentLine;
 
 /// A parsed iCalendar component (e.g., VCALENDAR, VEVENT, VTODO)
@@ -34,8 +35,10 @@ pub struct RawComponent<'src> {
     pub properties: Vec<RawProperty<'src>>,
     /// Nested child components
     pub children: Vec<RawComponent<'src>>,
-    /// Span of the entire component (from BEGIN to END)
+    /// Span of the BEGIN line of the component
     pub span: Span,
+    /// Span of the entire component (from BEGIN to END)
+    pub extent: Span,
 }
 
 /// A parsed iCalendar property (name, optional parameters, and value)
@@ -135,9 +138,23 @@ impl RawParameterValue<Segments<'_>> {
 /// ```
 #[must_use]
 pub fn build_tree<'src>(lines: &[ContentLine<'src>]) -> TreeBuilderResult<'src> {
+    build_tree_with_limits(lines, ParseLimits::default())
+}
+
+/// Build a component tree from scanned content lines, enforcing `limits`.
+///
+/// See [`build_tree`]. Building stops at the first exceeded limit, returning the
+/// components completed so far.
+#[must_use]
+#[expect(clippy::too_many_lines)]
+pub fn build_tree_with_limits<'src>(
+    lines: &[ContentLine<'src>],
+    limits: ParseLimits,
+) -> TreeBuilderResult<'src> {
     let mut stack: Vec<RawComponent<'src>> = Vec::new();
     let mut roots: Vec<RawComponent<'src>> = Vec::new();
     let mut errors: Vec<TreeBuildError<'src>> = Vec::new();
+    let mut components = 0; // in the current root component
 
     for line in lines {
         // Skip lines with errors - they don't contribute to the tree structure
@@ -147,6 +164,25 @@ pub fn build_tree<'src>(lines: &[ContentLine<'src>]) -> TreeBuilderResult<'src>
 
         // Manually check if this is BEGIN or END
         if line.name.eq_str_ignore_ascii_case(KW_BEGIN) {
+            if stack.is_empty() {
+                components = 0;
+            }
+            components += 1;
+            if components > limits.max_components {
+                errors.push(TreeBuildError::TooManyComponents {
+                    span: line.span,
+                    limit: limits.max_components,
+                });
+                return TreeBuilderResult { roots, errors };
+            }
+            if stack.len() >= limits.max_depth {
+                errors.push(TreeBuildError::NestingTooDeep {
+                    span: line.span,
+                    limit: limits.max_depth,
+                });
+                return TreeBuilderResult { roots, errors };
+            }
+
             // BEGIN lines should not have parameters
             if !line.parameters.is_empty() {
                 errors.push(TreeBuildError::BeginEndWithParameters {
@@ -165,6 +201,7 @@ pub fn build_tree<'src>(lines: &[ContentLine<'src>]) -> TreeBuilderResult<'src>
                 properties: Vec::new(),
                 children: Vec::new(),
                 span: line.span,
+                extent: line.span,
             });
         } else if line.name.eq_str_ignore_ascii_case(KW_END) {
             // END lines should not have parameters
@@ -178,7 +215,9 @@ pub fn build_tree<'src>(lines: &[ContentLine<'src>]) -> TreeBuilderResult<'src>
 
             let end_name = line.value.clone();
 
-            if let Some(component) = stack.pop() {
+            if let Some(mut component) = stack.pop() {
+                component.extent.end = line.span.end;
+
                 // Check if BEGIN/END names match using Segments comparison
                 if !component.name.eq_str_ignore_ascii_case(&end_name.resolve()) {
                     errors.push(TreeBuildError::MismatchedNesting {
@@ -202,6 +241,15 @@ pub fn build_tree<'src>(lines: &[ContentLine<'src>]) -> TreeBuilderResult<'src>
                 });
             }
         } else if let Some(current) = stack.last_mut() {
+            if current.properties.len() >= limits.max_properties {
+                errors.push(TreeBuildError::TooManyProperties {
+                    component: current.name.clone(),
+                    span: line.span,
+                    limit: limits.max_properties,
+                });
+                return TreeBuilderResult { roots, errors };
+            }
+
             // Regular property - add to current component
             // Build RawParameter from ScannedParameter
             let parameters: Vec<RawParameter<Segments<'src>>> = line
@@ -283,6 +331,35 @@ pub enum TreeBuildError<'src> {
         /// Span of the line
         span: Span,
     },
+
+    /// Component with more than [`ParseLimits::max_properties`] properties
+    #[error("{component} with more than {limit} properties")]
+    TooManyProperties {
+        /// The component name
+        component: Segments<'src>,
+        /// Span of the first property over the limit
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
+
+    /// Calendar with more than [`ParseLimits::max_components`] components
+    #[error("calendar with more than {limit} components")]
+    TooManyComponents {
+        /// Span of the first BEGIN line over the limit
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
+
+    /// Components nested deeper than [`ParseLimits::max_depth`]
+    #[error("components nested deeper than {limit} levels")]
+    NestingTooDeep {
+        /// Span of the first BEGIN line over the limit
+        span: Span,
+        /// The exceeded limit
+        limit: usize,
+    },
 }
 
 /// Result of building a tree.
@@ -16,7 +16,7 @@ use thiserror::Error;
 use crate::parameter::{Parameter, ParameterKind, ValueType};
 use crate::property::{Property, PropertyKind};
 use crate::string_storage::{Segments, Span};
-use crate::syntax::{RawComponent, RawParameter, RawProperty};
+use crate::syntax::{ParseLimits, RawComponent, RawParameter, RawProperty};
 use crate::value::{Value, parse_value};
 
 /// Perform typed analysis on raw components, returning typed components or errors.
@@ -25,11 +25,24 @@ use crate::value::{Value, parse_value};
 /// If there are typing errors, a vector of errors will be returned.
 pub fn typed_analysis(
     components: Vec<RawComponent<'_>>,
+) -> Result<Vec<TypedComponent<'_>>, Vec<TypedError<'_>>> {
+    typed_analysis_with_limits(components, ParseLimits::default())
+}
+
+/// Perform typed analysis on raw components, enforcing the limits checked on values.
+///
+/// See [`typed_analysis`], and [`ParseLimits::max_binary_size`] for the limit checked.
+///
+/// ## Errors
+/// If there are typing errors, a vector of errors will be returned.
+pub fn typed_analysis_with_limits(
+    components: Vec<RawComponent<'_>>,
+    limits: ParseLimits,
 ) -> Result<Vec<TypedComponent<'_>>, Vec<TypedError<'_>>> {
     let mut typed_components = Vec::with_capacity(components.len());
     let mut errors = Vec::new();
     for comp in components {
-        match typed_component(comp) {
+        match typed_component(comp, limits) {
             Ok(typed_comp) => typed_components.push(typed_comp),
             Err(errs) => errors.extend(errs),
         }
@@ -42,12 +55,15 @@ pub fn typed_analysis(
     }
 }
 
-fn typed_component(comp: RawComponent<'_>) -> Result<TypedComponent<'_>, Vec<TypedError<'_>>> {
+fn typed_component(
+    comp: RawComponent<'_>,
+    limits: ParseLimits,
+) -> Result<TypedComponent<'_>, Vec<TypedError<'_>>> {
     let mut existing_props = HashSet::with_capacity(comp.properties.len());
     let mut properties = Vec::with_capacity(comp.properties.len());
     let mut errors = Vec::new();
     for prop in comp.properties {
-        match parsed_property(&mut existing_props, prop) {
+        match parsed_property(&mut existing_props, prop, limits) {
             // Convert ParsedProperty to Property
             Ok(prop) => match Property::try_from(prop) {
                 Ok(property) => properties.push(property),
@@ -59,7 +75,7 @@ fn typed_component(comp: RawComponent<'_>) -> Result<TypedComponent<'_>, Vec<Typ
 
     let mut children = Vec::with_capacity(comp.children.len());
     for comp in comp.children {
-        match typed_component(comp) {
+        match typed_component(comp, limits) {
             Ok(child) => children.push(child),
             Err(errs) => errors.extend(errs),
         }
@@ -80,6 +96,7 @@ fn typed_component(comp: RawComponent<'_>) -> Result<TypedComponent<'_>, Vec<Typ
 fn parsed_property<'src>(
     _existing: &mut HashSet<&str>,
     prop: RawProperty<'src>,
+    limits: ParseLimits,
 ) -> Result<ParsedProperty<'src>, Vec<TypedError<'src>>> {
     // Determine property kind from name (infallible - always returns a kind)
     let kind = PropertyKind::from(prop.name.clone());
@@ -87,15 +104,20 @@ fn parsed_property<'src>(
     let parameters = parameters(prop.parameters)?;
     let value_types = value_types(&kind, &parameters)?;
 
-    // PERF: cache parser
-    let value = parse_value(&value_types, &prop.value).map_err(|errs| {
-        errs.into_iter()
-            .map(|err| TypedError::ValueSyntax {
-                value: prop.value.clone(),
-                err,
-            })
-            .collect::<Vec<_>>()
-    })?;
+    let value = if let [ValueType::Binary] = value_types.as_slice() {
+        // Checked by hand, to point at the offending character and bound the size
+        binary_value(prop.value, limits).map_err(|err| vec![*err])?
+    } else {
+        // PERF: cache parser
+        parse_value(&value_types, &prop.value).map_err(|errs| {
+            errs.into_iter()
+                .map(|err| TypedError::ValueSyntax {
+                    value: prop.value.clone(),
+                    err,
+                })
+                .collect::<Vec<_>>()
+        })?
+    };
 
     Ok(ParsedProperty {
         kind,
@@ -106,6 +128,50 @@ fn parsed_property<'src>(
     })
 }
 
+/// Checks that a BINARY value is well-formed BASE64 (RFC 4648) within the size limit.
+fn binary_value(
+    value: Segments<'_>,
+    limits: ParseLimits,
+) -> Result<Value<Segments<'_>>, Box<TypedError<'_>>> {
+    let invalid = |reason, span| Box::new(TypedError::BinaryInvalid { reason, span });
+    let span = value.span();
+    let mut chars: usize = 0; // b-chars, without the padding
+    let mut padding = 0;
+    let mut group_start = span.start;
+    for (c, char_span) in value.clone().into_spanned_chars() {
+        if chars.is_multiple_of(4) && padding == 0 {
+            group_start = char_span.start;
+        }
+        match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '+' | '/' if padding == 0 => chars += 1,
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '+' | '/' => {
+                return Err(invalid("data after padding", char_span));
+            }
+            '=' if padding == 0 && chars % 4 < 2 => {
+                return Err(invalid("misplaced padding", char_span));
+            }
+            '=' if padding == 0 || (padding == 1 && chars % 4 == 2) => padding += 1,
+            '=' => return Err(invalid("too much padding", char_span)),
+            _ => return Err(invalid("not a BASE64 character", char_span)),
+        }
+    }
+    if !(chars + padding).is_multiple_of(4) {
+        let span = Span::new(group_start, span.end);
+        return Err(invalid("length is not a multiple of 4", span));
+    }
+
+    // Each group of 4 characters is 3 bytes, and a last group of 2 or 3 is 1 or 2 bytes
+    let size = chars / 4 * 3 + (chars % 4).saturating_sub(1);
+    if size > limits.max_binary_size {
+        return Err(Box::new(TypedError::BinaryTooLarge {
+            size,
+            limit: limits.max_binary_size,
+            span,
+        }));
+    }
+    Ok(Value::Binary { value, span })
+}
+
 /// A typed iCalendar component with validated properties and nested child components.
 #[derive(Debug, Clone)]
 pub struct TypedComponent<'src> {
@@ -219,6 +285,26 @@ pub enum TypedError<'src> {
         err: Rich<'src, char>,
     },
 
+    /// BINARY value is not valid BASE64.
+    #[error("Invalid BASE64 in binary value: {reason}")]
+    BinaryInvalid {
+        /// Why the value is invalid
+        reason: &'static str,
+        /// The span of the first invalid character, or of the incomplete last group
+        span: Span,
+    },
+
+    /// Inline BINARY value is larger than [`ParseLimits::max_binary_size`].
+    #[error("Inline binary value of {size} bytes exceeds the limit of {limit} bytes")]
+    BinaryTooLarge {
+        /// The size of the value in decoded bytes
+        size: usize,
+        /// The limit exceeded
+        limit: usize,
+        /// The span of the value
+        span: Span,
+    },
+
     /// Property kind does not match the expected type.
     #[error("Expected property kind '{expected}', found '{found}'")]
     PropertyUnexpectedKind {
@@ -288,6 +374,8 @@ impl TypedError<'_> {
             | TypedError::ParameterValueMustNotBeQuoted { span, .. }
             | TypedError::ParameterValueInvalid { span, .. }
             | TypedError::ValueTypeDisallowed { span, .. }
+            | TypedError::BinaryInvalid { span, .. }
+            | TypedError::BinaryTooLarge { span, .. }
             | TypedError::PropertyUnexpectedKind { span, .. }
             | TypedError::PropertyInvalidValueCount { span, .. }
             | TypedError::PropertyInvalidValue { span, .. }
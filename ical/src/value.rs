@@ -26,7 +26,7 @@ use chumsky::input::Stream;
 use chumsky::prelude::*;
 
 use crate::parameter::ValueType;
-use crate::string_storage::{Segments, Span, StringStorage};
+use crate::string_storage::{Segments, StringStorage};
 use crate::value::datetime::{value_utc_offset, values_date, values_date_time, values_time};
 use crate::value::duration::values_duration;
 use crate::value::miscellaneous::{value_binary, value_boolean};
@@ -578,24 +578,12 @@ fn parse_value_single_type<'src>(
 }
 
 fn make_input(segs: Segments<'_>) -> impl Input<'_, Token = char, Span = SimpleSpan> {
-    let eoi = match (segs.segments.first(), segs.segments.last()) {
-        (Some(first), Some(last)) => Span {
-            start: first.1.start,
-            end: last.1.end,
-        },
-        _ => Span { start: 0, end: 0 },
-    };
+    let eoi = segs.span();
     Stream::from_iter(segs.into_spanned_chars()).map(eoi.into(), |(t, s)| (t, s.into()))
 }
 
 fn make_uppercase_input(segs: Segments<'_>) -> impl Input<'_, Token = char, Span = SimpleSpan> {
-    let eoi = match (segs.segments.first(), segs.segments.last()) {
-        (Some(first), Some(last)) => Span {
-            start: first.1.start,
-            end: last.1.end,
-        },
-        _ => Span { start: 0, end: 0 },
-    };
+    let eoi = segs.span();
     Stream::from_iter(segs.into_spanned_chars())
         .map(eoi.into(), |(t, s)| (t.to_ascii_uppercase(), s.into()))
 }
@@ -14,7 +14,7 @@ use crate::value::miscellaneous::{
 };
 
 /// Date value in the iCalendar format.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ValueDate {
     /// Year component.
     pub year: i16,
@@ -91,7 +91,7 @@ impl From<jiff::civil::Date> for ValueDate {
 #[cfg(feature = "jiff")]
 impl From<ValueDate> for jiff::civil::Date {
     fn from(value: ValueDate) -> Self {
-        jiff::civil::date(value.year, value.month, value.day)
+        value.jiff
     }
 }
 
@@ -154,7 +154,7 @@ where
 }
 
 /// Date-Time value defined in the RFC 5545 Section 3.3.5.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ValueDateTime {
     /// Date component.
     pub date: ValueDate,
@@ -216,7 +216,7 @@ where
 }
 
 /// Time value defined in the RFC 5545 Section 3.3.12.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ValueTime {
     /// Hour component, 0-23.
     pub hour: i8,
@@ -4,6 +4,9 @@
 
 //! Duration value type parser as defined in RFC 5545 Section 3.3.6.
 
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
 use chumsky::extra::ParserExtra;
 use chumsky::input::Input;
 use chumsky::label::LabelError;
@@ -11,6 +14,7 @@ use chumsky::prelude::*;
 use chumsky::span::SimpleSpan;
 
 use crate::value::miscellaneous::ValueExpected;
+use crate::value::numeric::value_u32;
 
 /// Duration Value defined in RFC 5545 Section 3.3.6
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +42,60 @@ pub enum ValueDuration {
     },
 }
 
+impl FromStr for ValueDuration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        value_duration::<'_, _, extra::Err<Rich<'_, char>>>()
+            .parse(s)
+            .into_result()
+            .map_err(|errs| {
+                let reasons: Vec<_> = errs.iter().map(ToString::to_string).collect();
+                format!("invalid duration {s:?}: {}", reasons.join("; "))
+            })
+    }
+}
+
+/// Formats the duration as in RFC 5545, leaving out the components that are zero.
+impl Display for ValueDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Week { positive, week } => {
+                let sign = if positive { "" } else { "-" };
+                write!(f, "{sign}P{week}W")
+            }
+            Self::DateTime {
+                positive,
+                day,
+                hour,
+                minute,
+                second,
+            } => {
+                let sign = if positive { "" } else { "-" };
+                write!(f, "{sign}P")?;
+                if day > 0 {
+                    write!(f, "{day}D")?;
+                }
+                if hour > 0 || minute > 0 || second > 0 {
+                    write!(f, "T")?;
+                    if hour > 0 {
+                        write!(f, "{hour}H")?;
+                    }
+                    if minute > 0 {
+                        write!(f, "{minute}M")?;
+                    }
+                    if second > 0 {
+                        write!(f, "{second}S")?;
+                    }
+                } else if day == 0 {
+                    write!(f, "T0S")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Format Definition:  This value type is defined by the following notation:
 ///
 /// ```txt
@@ -58,24 +116,7 @@ where
     E::Error: LabelError<'src, I, ValueExpected>,
 {
     // case-sensitive
-    let int = select! { c @ '0'..='9' => c }
-        .repeated()
-        .at_least(1)
-        .at_most(10) // u32 max is 10 digits: 4_294_967_295
-        .collect::<String>()
-        .try_map_with(|str, e| match lexical::parse_partial::<u32, _>(&str) {
-            Ok((v, n)) if n == str.len() => Ok(v),
-            Ok((_, n)) => Err(E::Error::expected_found(
-                [ValueExpected::U32],
-                Some(str.chars().nth(n).unwrap().into()), // SAFETY: since n < len
-                e.span(),
-            )),
-            Err(_) => Err(E::Error::expected_found(
-                [ValueExpected::U32],
-                Some(str.chars().next().unwrap().into()), // SAFETY: since at least 1 digit
-                e.span(),
-            )),
-        });
+    let int = value_u32();
 
     let week = int.then_ignore(just('W'));
 
@@ -199,4 +240,25 @@ mod tests {
             assert!(parse(src).is_err(), "Parse {src} should fail");
         }
     }
+
+    #[test]
+    fn round_trips_duration_strings() {
+        for src in [
+            "-PT15M",
+            "-PT9H",
+            "P1DT12H",
+            "P2W",
+            "-P1D",
+            "PT1H30M5S",
+            "PT0S",
+        ] {
+            let duration: ValueDuration = src.parse().unwrap();
+            assert_eq!(duration.to_string(), src);
+        }
+        assert_eq!(
+            "+PT0H15M".parse::<ValueDuration>().unwrap().to_string(),
+            "PT15M"
+        );
+        assert!("15 minutes".parse::<ValueDuration>().is_err());
+    }
 }
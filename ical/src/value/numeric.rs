@@ -9,7 +9,7 @@ use chumsky::extra::ParserExtra;
 use chumsky::label::LabelError;
 use chumsky::prelude::*;
 
-use crate::value::miscellaneous::ValueExpected;
+use crate::value::miscellaneous::{ValueExpected, u8_0_9};
 
 /// Format Definition:  This value type is defined by the following notation:
 ///
@@ -22,49 +22,32 @@ where
     E: ParserExtra<'src, I>,
     E::Error: LabelError<'src, I, ValueExpected>,
 {
-    let sign = sign().or_not();
-    let integer_part = select! { c @ '0'..='9' => c }
+    let integer_part = u8_0_9()
         .repeated()
         .at_least(1)
-        .collect::<String>();
+        .fold(Decimal::default(), Decimal::push_digit);
 
-    let fractional_part = just('.').ignore_then(integer_part);
+    let number = integer_part
+        .clone()
+        .then_ignore(just('.'))
+        .map(Decimal::push_point)
+        .foldl(u8_0_9().repeated().at_least(1), Decimal::push_digit)
+        .or(integer_part);
 
-    sign.then(integer_part)
-        .then(fractional_part.or_not())
-        .try_map_with(|((sign, int_part), frac_part), e| {
-            let capacity = sign.map_or(0, |_| 1)
-                + int_part.len()
-                + frac_part.as_ref().map_or(0, |f| 1 + f.len());
-
-            let mut s = String::with_capacity(capacity);
-            if let Some(sign) = sign {
-                s.push(sign);
-            }
-            s.push_str(&int_part);
-            if let Some(frac) = frac_part {
-                s.push('.');
-                s.push_str(&frac);
+    sign()
+        .or_not()
+        .then(number)
+        .try_map_with(|(sign, decimal), e| {
+            let f = decimal.to_f64();
+            if f.is_finite() {
+                Ok(if sign == Some('-') { -f } else { f })
+            } else {
+                Err(E::Error::expected_found(
+                    [ValueExpected::F64],
+                    None,
+                    e.span(),
+                ))
             }
-
-            let n = match lexical::parse_partial::<f64, _>(&s) {
-                Ok((f, n)) => {
-                    if n < s.len() {
-                        n
-                    } else if f.is_infinite() || f.is_nan() {
-                        0
-                    } else {
-                        return Ok(f);
-                    }
-                }
-                Err(_) => 0,
-            };
-
-            Err(E::Error::expected_found(
-                [ValueExpected::F64],
-                Some(s.chars().nth(n).unwrap().into()), // SAFETY: since n < len
-                e.span(),
-            ))
         })
 }
 
@@ -129,33 +112,12 @@ where
 {
     sign()
         .or_not()
-        .then(
-            select! { c @ '0'..='9' => c }
-                .repeated()
-                .at_least(1)
-                .collect::<String>(),
-        )
-        .try_map_with(|(sign, digits), e| {
-            let capacity = sign.map_or(0, |_| 1) + digits.len();
-            let mut int_str = String::with_capacity(capacity);
-            if let Some(s) = sign {
-                int_str.push(s);
-            }
-            int_str.push_str(&digits);
-
-            match lexical::parse_partial::<i32, _>(&int_str) {
-                Ok((v, n)) if n == int_str.len() => Ok(v),
-                Ok((_, n)) => Err(E::Error::expected_found(
-                    [ValueExpected::I32],
-                    Some(int_str.chars().nth(n).unwrap().into()), // SAFETY: since n < len
-                    e.span(),
-                )),
-                Err(_) => Err(E::Error::expected_found(
-                    [ValueExpected::I32],
-                    Some(int_str.chars().next().unwrap().into()), // SAFETY: since at least 1 digit
-                    e.span(),
-                )),
-            }
+        .then(digits_u32())
+        .try_map_with(|(sign, magnitude), e| {
+            magnitude
+                .map(i64::from)
+                .and_then(|v| i32::try_from(if sign == Some('-') { -v } else { v }).ok())
+                .ok_or_else(|| E::Error::expected_found([ValueExpected::I32], None, e.span()))
         })
 }
 
@@ -172,6 +134,35 @@ where
     value_integer().separated_by(just(',')).collect()
 }
 
+/// Unsigned 32-bit integer parser (1 or more digits).
+///
+/// Digits are folded into the result as they are read, so no intermediate
+/// buffer is allocated.
+pub(crate) fn value_u32<'src, I, E>() -> impl Parser<'src, I, u32, E> + Copy
+where
+    I: Input<'src, Token = char, Span = SimpleSpan>,
+    E: ParserExtra<'src, I>,
+    E::Error: LabelError<'src, I, ValueExpected>,
+{
+    digits_u32().try_map_with(|v, e| {
+        v.ok_or_else(|| E::Error::expected_found([ValueExpected::U32], None, e.span()))
+    })
+}
+
+/// One or more digits folded into a `u32`, `None` on overflow.
+fn digits_u32<'src, I, E>() -> impl Parser<'src, I, Option<u32>, E> + Copy
+where
+    I: Input<'src, Token = char, Span = SimpleSpan>,
+    E: ParserExtra<'src, I>,
+{
+    u8_0_9()
+        .repeated()
+        .at_least(1)
+        .fold(Some(0), |acc: Option<u32>, d| {
+            acc?.checked_mul(10)?.checked_add(u32::from(d))
+        })
+}
+
 const fn sign<'src, I, E>() -> impl Parser<'src, I, char, E> + Copy
 where
     I: Input<'src, Token = char, Span = SimpleSpan>,
@@ -180,6 +171,30 @@ where
     select! { c @ ('+' | '-') => c }
 }
 
+/// Decimal digits and point of an unsigned float, kept as text.
+///
+/// Every digit is kept so that `str::parse` rounds the value correctly, which
+/// scaling a mantissa by a power of ten does not.
+#[derive(Debug, Default, Clone)]
+struct Decimal(String);
+
+impl Decimal {
+    fn push_digit(mut self, d: u8) -> Self {
+        self.0.push(char::from(b'0' + d));
+        self
+    }
+
+    fn push_point(mut self) -> Self {
+        self.0.push('.');
+        self
+    }
+
+    fn to_f64(&self) -> f64 {
+        // The grammar leaves nothing `str::parse` rejects, so this never falls back
+        self.0.parse().unwrap_or(f64::NAN)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chumsky::input::Stream;
@@ -246,6 +261,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_float_correctly_rounded() {
+        fn parse(src: &str) -> f64 {
+            let stream = Stream::from_iter(src.chars());
+            value_float::<'_, _, extra::Err<Rich<'_, char>>>()
+                .parse(stream)
+                .into_result()
+                .unwrap()
+        }
+
+        let zeros = |n| "0".repeat(n);
+        #[expect(clippy::unreadable_literal)]
+        let cases = [
+            // more than 15 significant digits
+            ("0.30000000000000004".to_owned(), 0.30000000000000004),
+            (
+                "3.141592653589793238462643383279".to_owned(),
+                std::f64::consts::PI,
+            ),
+            (
+                "123456789012345678901234567890".to_owned(),
+                1.2345678901234568e29,
+            ),
+            // halfway between two doubles, broken by a digit past the 20th
+            (
+                "9007199254740993.00000000000000000001".to_owned(),
+                9007199254740994.0,
+            ),
+            ("9007199254740993".to_owned(), 9007199254740992.0),
+            // exponents beyond 22
+            (format!("0.{}1", zeros(23)), 1e-24),
+            (format!("1{}", zeros(30)), 1e30),
+            (
+                format!("898846567431158{}", zeros(293)),
+                8.98846567431158e307,
+            ),
+            // subnormals
+            (
+                format!("0.{}22250738585072", zeros(307)),
+                2.2250738585072e-308,
+            ),
+            (format!("0.{}5", zeros(323)), 5e-324),
+            (format!("0.{}2", zeros(323)), 0.0),
+        ];
+        for (src, expected) in cases {
+            assert_eq!(parse(&src).to_bits(), expected.to_bits(), "Parse {src}");
+        }
+    }
+
     #[test]
     fn parses_integer() {
         fn parse(src: &str) -> Result<i32, Vec<Rich<'_, char>>> {
@@ -5,6 +5,7 @@
 //! Recurrence rule type definitions for iCalendar.
 
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use chumsky::extra::ParserExtra;
 use chumsky::input::Input;
@@ -12,6 +13,8 @@ use chumsky::label::LabelError;
 use chumsky::prelude::*;
 use chumsky::span::SimpleSpan;
 
+use crate::fmt::value::write_recurrence_rule;
+use crate::fmt::{FormatOptions, Formatter};
 use crate::keyword::{
     KW_DAY_FR, KW_DAY_MO, KW_DAY_SA, KW_DAY_SU, KW_DAY_TH, KW_DAY_TU, KW_DAY_WE, KW_RRULE_BYDAY,
     KW_RRULE_BYHOUR, KW_RRULE_BYMINUTE, KW_RRULE_BYMONTH, KW_RRULE_BYMONTHDAY, KW_RRULE_BYSECOND,
@@ -25,9 +28,10 @@ use crate::value::miscellaneous::{
     ValueExpected, i8_0_1, i8_0_3, i8_0_9, i8_1_2, i8_1_4, i8_1_9, i16_0_5, i16_0_6, i16_0_9,
     i16_1_2, i16_1_9, u8_0_1, u8_0_3, u8_0_5, u8_0_9, u8_1_9,
 };
+use crate::value::numeric::value_u32;
 
 /// Recurrence rule
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValueRecurrenceRule {
     /// Frequency of recurrence
     pub freq: RecurrenceFrequency,
@@ -59,8 +63,67 @@ pub struct ValueRecurrenceRule {
     pub wkst: Option<WeekDay>,
 }
 
+impl ValueRecurrenceRule {
+    /// Brings the rule into its canonical form, so that rules with the same meaning compare equal.
+    ///
+    /// The `BY*` lists are sorted and deduplicated, and `INTERVAL=1` and `WKST=MO` are dropped as
+    /// they are the defaults.
+    pub fn normalize(&mut self) {
+        fn sort_dedup<T: Ord>(values: &mut Vec<T>) {
+            values.sort_unstable();
+            values.dedup();
+        }
+
+        sort_dedup(&mut self.by_second);
+        sort_dedup(&mut self.by_minute);
+        sort_dedup(&mut self.by_hour);
+        sort_dedup(&mut self.by_month_day);
+        sort_dedup(&mut self.by_year_day);
+        sort_dedup(&mut self.by_week_no);
+        sort_dedup(&mut self.by_month);
+        sort_dedup(&mut self.by_day);
+        sort_dedup(&mut self.by_set_pos);
+        if self.interval == Some(1) {
+            self.interval = None;
+        }
+        if self.wkst == Some(WeekDay::Monday) {
+            self.wkst = None;
+        }
+    }
+}
+
+/// Parses a recurrence rule such as `FREQ=WEEKLY;BYDAY=MO,WE`.
+///
+/// Keywords are matched case-insensitively, as RFC 5545 defines them.
+impl FromStr for ValueRecurrenceRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let src = s.to_ascii_uppercase();
+        value_rrule::<'_, _, extra::Err<Rich<'_, char>>>()
+            .parse(src.as_str())
+            .into_result()
+            .map_err(|errs| {
+                let reasons: Vec<_> = errs.iter().map(ToString::to_string).collect();
+                format!("invalid recurrence rule {s:?}: {}", reasons.join("; "))
+            })
+    }
+}
+
+/// Formats the rule as it is written in an `RRULE` property.
+impl Display for ValueRecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        let mut w = Formatter::new(&mut buffer, FormatOptions::default().folding(None));
+        write_recurrence_rule(&mut w, self)
+            .and_then(|()| w.into_writer().map(|_| ()))
+            .map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
+
 /// Recurrence frequency
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[expect(missing_docs)]
 pub enum RecurrenceFrequency {
     Secondly,
@@ -87,7 +150,7 @@ impl Display for RecurrenceFrequency {
 }
 
 /// Day of week with optional occurrence
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WeekDayNum {
     /// Day of the week
     pub day: WeekDay,
@@ -96,7 +159,7 @@ pub struct WeekDayNum {
 }
 
 /// Day of the week
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[expect(missing_docs)]
 pub enum WeekDay {
     Sunday,
@@ -122,6 +185,15 @@ impl Display for WeekDay {
     }
 }
 
+impl Display for WeekDayNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.occurrence {
+            Some(occurrence) => write!(f, "{occurrence}{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
 /// Format Definition:  This value type is defined by the following notation:
 ///
 /// ```txt
@@ -844,31 +916,25 @@ where
     E: ParserExtra<'src, I>,
     E::Error: LabelError<'src, I, ValueExpected>,
 {
-    select! { c @ '0'..='9' => c }
-        .repeated()
-        .at_least(1)
-        .at_most(10) // u32 max is 10 digits
-        .collect::<String>()
-        .try_map_with(|str, e| {
-            lexical::parse_partial::<u32, _>(&str)
-                .map_err(|_| E::Error::expected_found([ValueExpected::U32], None, e.span()))
-                .and_then(|(v, _)| match v {
-                    0 => Err(E::Error::expected_found(
-                        [ValueExpected::PositiveU32],
-                        None,
-                        e.span(),
-                    )),
-                    v => Ok(v),
-                })
-        })
+    value_u32().try_map_with(|v, e| match v {
+        0 => Err(E::Error::expected_found(
+            [ValueExpected::PositiveU32],
+            None,
+            e.span(),
+        )),
+        v => Ok(v),
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
     use chumsky::extra;
     use chumsky::input::Stream;
 
     use super::*;
+    use crate::value::datetime::ValueDate;
 
     fn parse(src: &'_ str) -> Result<ValueRecurrenceRule, Vec<Rich<'_, char>>> {
         let stream = Stream::from_iter(src.chars());
@@ -1094,4 +1160,155 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn from_str_parses_whole_rule() {
+        let rule: ValueRecurrenceRule = "freq=weekly;byday=mo,-1fr;count=3".parse().unwrap();
+        assert_eq!(rule.freq, RecurrenceFrequency::Weekly);
+        assert_eq!(rule.count, Some(3));
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;COUNT=3;BYDAY=MO,-1FR");
+
+        assert!("FREQ=DAILY;".parse::<ValueRecurrenceRule>().is_err());
+        assert!(
+            "FREQ=DAILY trailing"
+                .parse::<ValueRecurrenceRule>()
+                .is_err()
+        );
+        let err = "INTERVAL=2".parse::<ValueRecurrenceRule>().unwrap_err();
+        assert!(err.contains("FREQ"), "{err}");
+    }
+
+    #[test]
+    fn display_orders_parts_as_written() {
+        let rule: ValueRecurrenceRule =
+            "WKST=MO;BYMONTH=1;BYDAY=SU;INTERVAL=1;UNTIL=19971224T000000Z;FREQ=YEARLY"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=YEARLY;UNTIL=19971224T000000Z;INTERVAL=1;BYMONTH=1;BYDAY=SU;WKST=MO"
+        );
+
+        let rule: ValueRecurrenceRule = "FREQ=WEEKLY;WKST=SU;INTERVAL=2".parse().unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;WKST=SU");
+    }
+
+    #[test]
+    fn normalize_sorts_and_dedupes_lists() {
+        let mut rule: ValueRecurrenceRule =
+            "FREQ=MONTHLY;BYMONTHDAY=15,-1,1,15;BYDAY=FR,1MO,MO,FR;INTERVAL=1;WKST=MO"
+                .parse()
+                .unwrap();
+        rule.normalize();
+        assert_eq!(rule.by_month_day, [-1, 1, 15]);
+        assert_eq!(rule.interval, None);
+        assert_eq!(rule.wkst, None);
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=-1,1,15;BYDAY=MO,1MO,FR"
+        );
+    }
+
+    /// Up to `len - 1` non-zero values in `lo..=hi`.
+    fn list<T>(next: &mut impl FnMut(u64) -> u64, len: u64, lo: i64, hi: i64) -> Vec<T>
+    where
+        T: TryFrom<i64, Error: fmt::Debug>,
+    {
+        let span = u64::try_from(hi - lo + 1).unwrap();
+        (0..next(len))
+            .map(|_| match lo + i64::try_from(next(span)).unwrap() {
+                0 => T::try_from(1).unwrap(),
+                v => T::try_from(v).unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn display_round_trips_generated_rules() {
+        // A small xorshift generator keeps the cases reproducible
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = |bound: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % bound
+        };
+
+        let freqs = [
+            RecurrenceFrequency::Secondly,
+            RecurrenceFrequency::Minutely,
+            RecurrenceFrequency::Hourly,
+            RecurrenceFrequency::Daily,
+            RecurrenceFrequency::Weekly,
+            RecurrenceFrequency::Monthly,
+            RecurrenceFrequency::Yearly,
+        ];
+        let days = [
+            WeekDay::Sunday,
+            WeekDay::Monday,
+            WeekDay::Tuesday,
+            WeekDay::Wednesday,
+            WeekDay::Thursday,
+            WeekDay::Friday,
+            WeekDay::Saturday,
+        ];
+
+        for _ in 0..500 {
+            let by_second: Vec<u8> = list(&mut next, 4, 1, 60);
+            let by_minute: Vec<u8> = list(&mut next, 4, 1, 59);
+            let by_hour: Vec<u8> = list(&mut next, 4, 1, 23);
+            let by_month_day: Vec<i8> = list(&mut next, 4, -31, 31);
+            let by_year_day: Vec<i16> = list(&mut next, 3, -366, 366);
+            let by_week_no: Vec<i8> = list(&mut next, 3, -53, 53);
+            let by_month: Vec<u8> = list(&mut next, 4, 1, 12);
+            let by_set_pos: Vec<i16> = list(&mut next, 3, -366, 366);
+            let by_day = (0..next(4))
+                .map(|_| WeekDayNum {
+                    day: *days.get(usize::try_from(next(7)).unwrap()).unwrap(),
+                    occurrence: match next(3) {
+                        0 => None,
+                        1 => Some(i8::try_from(next(53) + 1).unwrap()),
+                        _ => Some(-i8::try_from(next(53) + 1).unwrap()),
+                    },
+                })
+                .collect();
+            let until = (next(3) == 0).then(|| {
+                let date = ValueDate::new(1990 + i16::try_from(next(50)).unwrap(), 2, 28).unwrap();
+                let time = ValueTime::new(12, 30, 0, next(2) == 0).unwrap();
+                ValueDateTime::new(date, time)
+            });
+            let count =
+                (until.is_none() && next(2) == 0).then(|| u32::try_from(next(100) + 1).unwrap());
+
+            let mut rule = ValueRecurrenceRule {
+                freq: *freqs.get(usize::try_from(next(7)).unwrap()).unwrap(),
+                until,
+                count,
+                interval: (next(2) == 0).then(|| u32::try_from(next(3) + 1).unwrap()),
+                by_second,
+                by_minute,
+                by_hour,
+                by_month_day,
+                by_year_day,
+                by_week_no,
+                by_month,
+                by_day,
+                by_set_pos,
+                wkst: (next(2) == 0).then(|| *days.get(usize::try_from(next(7)).unwrap()).unwrap()),
+            };
+
+            let text = rule.to_string();
+            let mut buffer = Vec::new();
+            let mut f = Formatter::new(&mut buffer, FormatOptions::default().folding(None));
+            write_recurrence_rule(&mut f, &rule).unwrap();
+            f.flush().unwrap();
+            assert_eq!(String::from_utf8(buffer).unwrap(), text);
+
+            let mut parsed: ValueRecurrenceRule = text.parse().unwrap();
+            parsed.normalize();
+            rule.normalize();
+            assert_eq!(parsed, rule, "round trip of {text}");
+            assert_eq!(parsed.to_string(), rule.to_string());
+        }
+    }
 }
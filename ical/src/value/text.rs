@@ -80,19 +80,11 @@ impl<'a> ValueText<Segments<'a>> {
     }
 
     /// Convert borrowed type to owned type
+    ///
+    /// The owned text is resolved into a single string, so that it can be borrowed as a `&str`.
     #[must_use]
     pub fn to_owned(&self) -> ValueText<String> {
-        ValueText {
-            tokens: self
-                .tokens
-                .iter()
-                .map(|(token, _)| match token {
-                    ValueTextToken::Str(s) => ValueTextToken::Str(s.to_owned()),
-                    ValueTextToken::Escape(c) => ValueTextToken::Escape(*c),
-                })
-                .map(|token| (token, ()))
-                .collect(),
-        }
+        ValueText::new(self.resolve().into_owned())
     }
 
     /// Get the full span from the first to the last token.
@@ -129,6 +121,20 @@ impl<'a> ValueText<Segments<'a>> {
     }
 }
 
+impl<S: StringStorage> ValueText<S> {
+    /// View the text value, with escapes processed.
+    ///
+    /// The text is borrowed when it is a single piece without escapes, see
+    /// [`StringStorage::as_str_lossy`], and joined into a new string otherwise.
+    #[must_use]
+    pub fn as_str_lossy(&self) -> Cow<'_, str> {
+        match self.tokens.as_slice() {
+            [(ValueTextToken::Str(part), _)] => part.as_str_lossy(),
+            _ => Cow::Owned(self.to_string()),
+        }
+    }
+}
+
 impl<S: StringStorage> fmt::Display for ValueText<S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for (token, _) in &self.tokens {
@@ -152,6 +158,15 @@ impl ValueText<String> {
             tokens: vec![(ValueTextToken::Str(value), ())],
         }
     }
+
+    /// Borrow the text value, with escapes processed.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self.tokens.as_slice() {
+            [(ValueTextToken::Str(value), ())] => value,
+            _ => "",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -257,7 +272,11 @@ impl Iterator for ValueTextSpannedChars<'_> {
                     self.current_segments = Some(segments.into_spanned_chars());
                 }
                 ValueTextToken::Escape(escape_char) => {
-                    let c = escape_char.as_ref().chars().next().unwrap();
+                    let c = escape_char
+                        .as_ref()
+                        .chars()
+                        .next()
+                        .expect("escapes are one character");
                     self.current_escape = Some((c, span));
                 }
             }
@@ -324,7 +343,7 @@ struct SpanCollector(Vec<SimpleSpan>);
 impl SpanCollector {
     fn build<'src>(self, src: &Segments<'src>) -> Vec<(Segments<'src>, SimpleSpan)> {
         // assume src segments are non-overlapping and sorted
-        let mut iter = src.segments.iter();
+        let mut iter = src.as_slice().iter();
         let Some(mut item) = iter.next() else {
             return Vec::new(); // no segments
         };
@@ -342,19 +361,19 @@ impl SpanCollector {
                 } else if span.end > item.1.end {
                     // need next segment
                     let i = span.start.saturating_sub(item.1.start);
-                    let s = item.0.get(i..).unwrap(); // SAFETY: since in range
+                    let s = item.0.get(i..).expect("span start within segment");
                     match iter.next() {
                         Some(a) => item = a,
                         None => flag = false, // no more segments
                     }
-                    vec.push((Segments::new(vec![(s, span.into())]), span));
+                    vec.push((Segments::single(s, span.into()), span));
                 } else {
                     // within this segment
                     flag = false;
                     let i = span.start.saturating_sub(item.1.start);
                     let j = span.end.saturating_sub(item.1.start);
-                    let s = item.0.get(i..j).unwrap(); // SAFETY: since i,j are in range
-                    vec.push((Segments::new(vec![(s, span.into())]), span));
+                    let s = item.0.get(i..j).expect("span within segment");
+                    vec.push((Segments::single(s, span.into()), span));
                 }
             }
         }
@@ -392,10 +411,7 @@ mod tests {
     use super::*;
 
     fn make_input(segs: Segments<'_>) -> impl Input<'_, Token = char, Span = SimpleSpan> {
-        let eoi = match (segs.segments.first(), segs.segments.last()) {
-            (Some(first), Some(last)) => SimpleSpan::new((), first.1.start..last.1.end),
-            _ => SimpleSpan::new((), 0..0),
-        };
+        let eoi = SimpleSpan::from(segs.span());
         Stream::from_iter(segs.into_spanned_chars()).map(eoi, |(t, s)| {
             // Convert our custom Span to SimpleSpan
             let simple = SimpleSpan::new((), s.start..s.end);
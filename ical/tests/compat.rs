@@ -0,0 +1,446 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for the repairs of compatibility mode
+//!
+//! Every sample is an anonymized file from the wild that strict parsing rejects, repaired by one
+//! switch of [`Compat`] alone.
+
+use aimcal_ical::{
+    CalendarComponent, Compat, Diagnostic, ICalendar, ParseOptions, Severity, parse,
+    parse_with_diagnostics_and_options, parse_with_options,
+};
+
+/// Outlook invitation without its VTIMEZONE, referring to the zone by its display name.
+const OUTLOOK_DISPLAY_NAME: &str = "\
+BEGIN:VCALENDAR\r
+PRODID:-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN\r
+VERSION:2.0\r
+METHOD:REQUEST\r
+BEGIN:VEVENT\r
+CLASS:PUBLIC\r
+DTSTART;TZID=\"(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna\":20250310T140000\r
+DTEND;TZID=\"(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna\":20250310T150000\r
+DTSTAMP:20250301T120000Z\r
+SEQUENCE:0\r
+SUMMARY;LANGUAGE=en-us:Quarterly planning\r
+UID:040000008200E00074C5B7101A82E0080000000050A5C3C1E08ADB01000000000000000010000000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+/// Exchange invitation with the zone names unquoted, and attendees named `Last, First`.
+const OUTLOOK_UNQUOTED: &str = "\
+BEGIN:VCALENDAR\r
+PRODID:-//Microsoft Corporation//Outlook 16.0 MIMEDIR//EN\r
+VERSION:2.0\r
+METHOD:REQUEST\r
+BEGIN:VTIMEZONE\r
+TZID:Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna\r
+BEGIN:STANDARD\r
+DTSTART:16011028T030000\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10\r
+TZOFFSETFROM:+0200\r
+TZOFFSETTO:+0100\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:16010325T020000\r
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3\r
+TZOFFSETFROM:+0100\r
+TZOFFSETTO:+0200\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+ORGANIZER;CN=Doe, Jane:mailto:jane.doe@example.com\r
+ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE;CN=Roe, Richard:mailto:richard.roe@example.com\r
+DTSTART;TZID=Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna:20250415T093000\r
+DTEND;TZID=Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna:20250415T100000\r
+DTSTAMP:20250410T180000Z\r
+SUMMARY:1:1\r
+UID:040000008200E00074C5B7101A82E00800000000B0E3C4B1A0B1DB01000000000000000010000000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+/// A fixture list ending its matches at midnight as hour 24.
+const HOUR_24: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example League//Fixtures 2.1//EN\r
+BEGIN:VEVENT\r
+UID:fixture-1187@example.org\r
+DTSTAMP:20250601T080000Z\r
+DTSTART:20250614T220000\r
+DTEND:20250614T240000\r
+SUMMARY:Home - Away\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:fixture-1188@example.org\r
+DTSTAMP:20250601T080000Z\r
+DTSTART:20250630T230000Z\r
+DTEND:20250630T240000Z\r
+EXDATE:20250615T240000,20250616T120000\r
+SUMMARY:Away - Home\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+/// An export leaving blank lines before the END lines.
+const BLANK_LINES: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Scheduler//EN\r
+BEGIN:VEVENT\r
+UID:7d1c5e0a-0b5e-4c55-9f0e-3f6e1e0c2a41\r
+DTSTAMP:20250301T101500Z\r
+DTSTART:20250305T120000Z\r
+SUMMARY:Team lunch\r
+\r
+END:VEVENT\r
+\r
+END:VCALENDAR\r
+";
+
+/// A hand-rolled feed putting spaces between property names and the colon.
+const SPACE_BEFORE_COLON: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example School//Timetable//EN\r
+BEGIN:VEVENT\r
+UID :lesson-42@example.edu\r
+DTSTAMP:20250301T000000Z\r
+DTSTART :20250401T090000Z\r
+SUMMARY ;LANGUAGE=en:Maths\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+/// A feed wrapping the calendar of an other feed in its own.
+const NESTED_CALENDARS: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Aggregator//EN\r
+X-WR-CALNAME:Holidays\r
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Holidays//EN\r
+CALSCALE:GREGORIAN\r
+BEGIN:VEVENT\r
+UID:holiday-2025-05-01@example.net\r
+DTSTAMP:20250101T000000Z\r
+DTSTART;VALUE=DATE:20250501\r
+SUMMARY:Labour Day\r
+END:VEVENT\r
+END:VCALENDAR\r
+END:VCALENDAR\r
+";
+
+fn parse_compat(
+    src: &str,
+    compat: Compat,
+) -> (
+    Option<Vec<ICalendar<aimcal_ical::Segments<'_>>>>,
+    Vec<Diagnostic>,
+) {
+    parse_with_diagnostics_and_options(src, ParseOptions::new().compat(compat))
+}
+
+/// Checks that strict parsing rejects `src`, that the repair alone accepts it and that no other
+/// repair does, returning the calendars and the repair warnings.
+fn assert_repaired_by(
+    src: &str,
+    compat: Compat,
+) -> (Vec<ICalendar<aimcal_ical::Segments<'_>>>, Vec<Diagnostic>) {
+    assert!(parse(src).is_err(), "strict parsing accepts the sample");
+    let others = Compat {
+        windows_tzid: !compat.windows_tzid,
+        unquoted_commas: !compat.unquoted_commas,
+        hour_24: !compat.hour_24,
+        blank_lines: !compat.blank_lines,
+        space_before_colon: !compat.space_before_colon,
+        nested_calendars: !compat.nested_calendars,
+    };
+    assert!(
+        parse_with_options(src, ParseOptions::new().compat(others)).is_err(),
+        "other repairs accept the sample"
+    );
+
+    let (calendars, diagnostics) = parse_compat(src, compat);
+    let calendars = calendars.unwrap_or_else(|| panic!("not repaired: {diagnostics:#?}"));
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    (calendars, diagnostics)
+}
+
+fn text_at<'a>(src: &'a str, diagnostic: &Diagnostic) -> &'a str {
+    src.get(diagnostic.span.into_range()).unwrap()
+}
+
+fn first_event<'a, 'src>(
+    calendars: &'a [ICalendar<aimcal_ical::Segments<'src>>],
+) -> &'a aimcal_ical::VEvent<aimcal_ical::Segments<'src>> {
+    calendars
+        .iter()
+        .flat_map(|c| &c.components)
+        .find_map(|c| match c {
+            CalendarComponent::Event(event) => Some(event),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn compat_is_off_by_default() {
+    assert_eq!(ParseOptions::default().compat, Compat::off());
+    assert_eq!(ParseOptions::strict().compat, Compat::off());
+    assert!(!Compat::default().is_on());
+    assert!(Compat::all().is_on());
+    assert!(Compat::off().blank_lines(true).is_on());
+}
+
+#[test]
+fn compat_replaces_windows_zone_names() {
+    let src = OUTLOOK_DISPLAY_NAME;
+    let (calendars, diagnostics) = assert_repaired_by(src, Compat::off().windows_tzid(true));
+
+    let event = first_event(&calendars);
+    let tz_id = event.dt_start.as_ref().unwrap().tz_id.as_ref().unwrap();
+    assert_eq!(tz_id.to_string(), "Europe/Berlin");
+    assert_eq!(event.dt_start.as_ref().unwrap().value.date().day, 10);
+    assert_eq!(
+        event
+            .dt_end
+            .as_ref()
+            .unwrap()
+            .tz_id
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "Europe/Berlin"
+    );
+
+    assert_eq!(diagnostics.len(), 2);
+    let diagnostic = diagnostics.first().unwrap();
+    assert_eq!(diagnostic.code, "repaired-windows-tzid");
+    assert_eq!(
+        text_at(src, diagnostic),
+        "(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna"
+    );
+    assert!(
+        diagnostic.message.contains("Europe/Berlin"),
+        "{diagnostic:?}"
+    );
+}
+
+#[test]
+fn compat_joins_unquoted_commas_of_names() {
+    let src = OUTLOOK_UNQUOTED;
+    let compat = Compat::off().unquoted_commas(true).windows_tzid(true);
+    assert!(parse(src).is_err());
+    // The zone names split at their commas need both repairs
+    assert!(
+        parse_with_options(
+            src,
+            ParseOptions::new().compat(Compat::off().windows_tzid(true))
+        )
+        .is_err()
+    );
+
+    let (calendars, diagnostics) = parse_compat(src, compat);
+    let calendars = calendars.unwrap_or_else(|| panic!("not repaired: {diagnostics:#?}"));
+    let event = first_event(&calendars);
+    let organizer = event.organizer.as_ref().unwrap();
+    assert_eq!(organizer.cn.as_ref().unwrap().to_string(), "Doe, Jane");
+    let attendee = event.attendees.first().unwrap();
+    assert_eq!(attendee.cn.as_ref().unwrap().to_string(), "Roe, Richard");
+    assert_eq!(
+        event
+            .dt_start
+            .as_ref()
+            .unwrap()
+            .tz_id
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "Europe/Berlin"
+    );
+
+    let codes: Vec<_> = diagnostics.iter().map(|d| d.code).collect();
+    assert_eq!(
+        codes,
+        [
+            "repaired-windows-tzid", // the TZID of the VTIMEZONE
+            "repaired-unquoted-comma",
+            "repaired-unquoted-comma",
+            "repaired-unquoted-comma",
+            "repaired-windows-tzid",
+            "repaired-unquoted-comma",
+            "repaired-windows-tzid",
+        ]
+    );
+    let comma = diagnostics.get(1).unwrap();
+    assert_eq!(text_at(src, comma), "Doe, Jane");
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+}
+
+#[test]
+fn compat_joins_unquoted_commas_of_common_names() {
+    let src = OUTLOOK_UNQUOTED.replace(
+        "Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna",
+        "Europe/Berlin",
+    );
+    let (calendars, diagnostics) = assert_repaired_by(&src, Compat::off().unquoted_commas(true));
+    let event = first_event(&calendars);
+    assert_eq!(
+        event
+            .organizer
+            .as_ref()
+            .unwrap()
+            .cn
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "Doe, Jane"
+    );
+    assert_eq!(diagnostics.len(), 2);
+    assert!(
+        diagnostics
+            .iter()
+            .all(|d| d.code == "repaired-unquoted-comma")
+    );
+    assert_eq!(text_at(&src, diagnostics.get(1).unwrap()), "Roe, Richard");
+}
+
+#[test]
+fn compat_reads_hour_24_as_next_midnight() {
+    let src = HOUR_24;
+    let (calendars, diagnostics) = assert_repaired_by(src, Compat::off().hour_24(true));
+
+    let events: Vec<_> = calendars
+        .first()
+        .unwrap()
+        .components
+        .iter()
+        .filter_map(|c| match c {
+            CalendarComponent::Event(event) => Some(event),
+            _ => None,
+        })
+        .collect();
+    let end = &events.first().unwrap().dt_end.as_ref().unwrap().value;
+    assert_eq!(
+        (end.date().month, end.date().day, end.time().unwrap().hour),
+        (6, 15, 0)
+    );
+    let end = &events.get(1).unwrap().dt_end.as_ref().unwrap().value;
+    assert_eq!(
+        (end.date().month, end.date().day, end.time().unwrap().hour),
+        (7, 1, 0)
+    );
+    assert!(matches!(end, aimcal_ical::DateTime::Utc { .. }));
+    let ex_dates = events.get(1).unwrap().ex_dates.first().unwrap();
+    let ex_dates = format!("{:?}", ex_dates.dates);
+    assert!(ex_dates.contains("day: 16"), "{ex_dates}");
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| text_at(src, d)).collect();
+    assert_eq!(
+        spans,
+        ["20250614T240000", "20250630T240000Z", "20250615T240000"]
+    );
+    assert!(diagnostics.iter().all(|d| d.code == "repaired-hour-24"));
+}
+
+#[test]
+fn compat_skips_blank_lines() {
+    let src = BLANK_LINES;
+    let (calendars, diagnostics) = assert_repaired_by(src, Compat::off().blank_lines(true));
+    assert_eq!(
+        first_event(&calendars)
+            .summary
+            .as_ref()
+            .unwrap()
+            .content
+            .to_string(),
+        "Team lunch"
+    );
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.code == "repaired-blank-line"));
+    assert!(diagnostics.iter().all(|d| text_at(src, d) == "\r\n"));
+}
+
+#[test]
+fn compat_skips_space_before_colon() {
+    let src = SPACE_BEFORE_COLON;
+    let (calendars, diagnostics) = assert_repaired_by(src, Compat::off().space_before_colon(true));
+    let event = first_event(&calendars);
+    assert_eq!(event.uid.content.to_string(), "lesson-42@example.edu");
+    assert_eq!(event.dt_start.as_ref().unwrap().value.date().month, 4);
+    assert_eq!(event.summary.as_ref().unwrap().content.to_string(), "Maths");
+    assert_eq!(diagnostics.len(), 3);
+    assert!(
+        diagnostics
+            .iter()
+            .all(|d| d.code == "repaired-space-before-colon")
+    );
+    assert!(diagnostics.iter().all(|d| text_at(src, d) == " "));
+}
+
+#[test]
+fn compat_merges_nested_calendars() {
+    let src = NESTED_CALENDARS;
+    // Strict parsing keeps the inner calendar as an unrecognized component, hiding its events
+    let calendars = parse(src).unwrap();
+    let components = &calendars.first().unwrap().components;
+    assert!(
+        components
+            .iter()
+            .all(|c| matches!(c, CalendarComponent::Unrecognized(_)))
+    );
+
+    let (calendars, diagnostics) = parse_compat(src, Compat::off().nested_calendars(true));
+    let calendars = calendars.unwrap();
+    assert_eq!(calendars.len(), 1);
+    let calendar = calendars.first().unwrap();
+    assert_eq!(
+        calendar.prod_id.value.to_string(),
+        "-//Example Aggregator//EN"
+    );
+    assert!(calendar.calscale.is_some());
+    assert_eq!(
+        first_event(&calendars)
+            .summary
+            .as_ref()
+            .unwrap()
+            .content
+            .to_string(),
+        "Labour Day"
+    );
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = diagnostics.first().unwrap();
+    assert_eq!(diagnostic.code, "repaired-nested-calendar");
+    assert!(
+        src.get(diagnostic.span.start..)
+            .unwrap()
+            .starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example Holidays")
+    );
+}
+
+#[test]
+fn compat_leaves_valid_sources_alone() {
+    let src = OUTLOOK_DISPLAY_NAME.replace(
+        "(UTC+01:00) Amsterdam, Berlin, Bern, Rome, Stockholm, Vienna",
+        "Europe/Berlin",
+    );
+    let (calendars, diagnostics) = parse_compat(&src, Compat::all());
+    assert!(calendars.is_some());
+    assert!(diagnostics.is_empty(), "{diagnostics:#?}");
+}
+
+#[test]
+fn compat_repairs_round_trip_through_the_formatter() {
+    let src = OUTLOOK_UNQUOTED;
+    let calendars = parse_with_options(src, ParseOptions::new().compat(Compat::all())).unwrap();
+    let formatted = aimcal_ical::fmt::format(calendars.first().unwrap()).unwrap();
+    assert!(formatted.contains("CN=\"Doe, Jane\""), "{formatted}");
+    assert!(formatted.contains("TZID=Europe/Berlin"), "{formatted}");
+    assert!(parse(&formatted).is_ok(), "{formatted}");
+}
@@ -0,0 +1,198 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Identity, semantic equality and diffing of owned components.
+
+use aimcal_ical::ops::{ComponentChange, diff};
+use aimcal_ical::{CalendarComponent, ComponentIdentity, ICalendar, PropertyKind, parse};
+
+fn calendar(components: &str) -> ICalendar<String> {
+    let src = format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//aim//diff//EN\r\n{components}END:VCALENDAR\r\n"
+    );
+    let calendars = parse(&src).unwrap();
+    calendars.first().unwrap().to_owned()
+}
+
+const STANDUP: &str = "\
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250106T090000Z\r
+RRULE:FREQ=DAILY\r
+SUMMARY;LANGUAGE=en:Standup\r
+CATEGORIES:Work\r
+CATEGORIES:Team\r
+END:VEVENT\r
+";
+
+const STANDUP_MOVED: &str = "\
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250108T100000Z\r
+RECURRENCE-ID:20250108T090000Z\r
+SEQUENCE:2\r
+SUMMARY:Standup\r
+END:VEVENT\r
+";
+
+const REPORT: &str = "\
+BEGIN:VTODO\r
+UID:report\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Write the report\r
+END:VTODO\r
+";
+
+#[test]
+fn identity_reads_uid_recurrence_id_and_sequence() {
+    let calendar = calendar(&format!("{STANDUP}{STANDUP_MOVED}{REPORT}"));
+    let identities: Vec<_> = calendar
+        .components
+        .iter()
+        .filter_map(CalendarComponent::identity)
+        .map(|id| (id.uid(), id.recurrence_id().is_some(), id.sequence()))
+        .collect();
+    assert_eq!(
+        identities,
+        [
+            ("standup", false, 0),
+            ("standup", true, 2),
+            ("report", false, 0)
+        ]
+    );
+}
+
+#[test]
+fn components_by_uid_tells_overrides_apart() {
+    let calendar = calendar(&format!("{STANDUP}{STANDUP_MOVED}{REPORT}"));
+    let map = calendar.components_by_uid();
+    assert_eq!(map.len(), 3);
+
+    let moved = calendar
+        .components
+        .get(1)
+        .and_then(|c| c.identity())
+        .unwrap();
+    let key = ("standup", moved.recurrence_id());
+    let Some(CalendarComponent::Event(event)) = map.get(&key) else {
+        panic!("expected the override of the standup");
+    };
+    assert_eq!(event.sequence(), 2);
+    assert!(map.contains_key(&("report", None)));
+}
+
+#[test]
+fn equality_ignores_property_order_but_not_parameters() {
+    let reordered = "\
+BEGIN:VEVENT\r
+CATEGORIES:Team\r
+SUMMARY;LANGUAGE=en:Standup\r
+RRULE:FREQ=DAILY\r
+UID:standup\r
+DTSTART:20250106T090000Z\r
+CATEGORIES:Work\r
+DTSTAMP:20250101T000000Z\r
+END:VEVENT\r
+";
+    assert_eq!(calendar(STANDUP), calendar(reordered));
+
+    let other_language = STANDUP.replace("LANGUAGE=en", "LANGUAGE=de");
+    assert_ne!(calendar(STANDUP), calendar(&other_language));
+    let other_categories = STANDUP.replace("CATEGORIES:Team", "CATEGORIES:Team,Ops");
+    assert_ne!(calendar(STANDUP), calendar(&other_categories));
+}
+
+#[test]
+fn equality_includes_alarms() {
+    let alarm =
+        "BEGIN:VALARM\r\nACTION:DISPLAY\r\nTRIGGER:-PT5M\r\nDESCRIPTION:Soon\r\nEND:VALARM\r\n";
+    let with_alarm = STANDUP.replace("END:VEVENT", &format!("{alarm}END:VEVENT"));
+    let earlier = with_alarm.replace("-PT5M", "-PT15M");
+    assert_eq!(calendar(&with_alarm), calendar(&with_alarm));
+    assert_ne!(calendar(&with_alarm), calendar(&earlier));
+    assert_ne!(calendar(STANDUP), calendar(&with_alarm));
+}
+
+#[test]
+fn diff_lists_added_removed_and_modified_components() {
+    let old = calendar(&format!("{STANDUP}{STANDUP_MOVED}"));
+    let renamed = STANDUP
+        .replace("SUMMARY;LANGUAGE=en:Standup", "SUMMARY:Daily")
+        .replace("CATEGORIES:Team\r\n", "");
+    let new = calendar(&format!("{REPORT}{renamed}"));
+
+    let changes = diff(&old, &new);
+
+    let summary: Vec<_> = changes
+        .iter()
+        .map(|change| match change {
+            ComponentChange::Added(c) => format!("added {}", c.identity().unwrap().uid()),
+            ComponentChange::Removed(c) => {
+                let id = c.identity().unwrap();
+                format!("removed {} {}", id.uid(), id.recurrence_id().is_some())
+            }
+            ComponentChange::Modified {
+                new,
+                properties,
+                components,
+                ..
+            } => {
+                let kinds: Vec<_> = properties.iter().map(ToString::to_string).collect();
+                let uid = new.identity().unwrap().uid();
+                format!("modified {uid} {} {components}", kinds.join(","))
+            }
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "added report",
+            "modified standup CATEGORIES,SUMMARY false",
+            "removed standup true",
+        ]
+    );
+    assert!(diff(&new, &new).is_empty());
+}
+
+#[test]
+fn diff_reports_extension_properties_by_kind() {
+    let old = calendar(REPORT);
+    let new = calendar(&REPORT.replace("END:VTODO", "X-AIM-INBOX:TRUE\r\nEND:VTODO"));
+    let changes = diff(&old, &new);
+    let [ComponentChange::Modified { properties, .. }] = changes.as_slice() else {
+        panic!("expected the todo to be modified");
+    };
+    assert_eq!(
+        properties.as_slice(),
+        [PropertyKind::XName("X-AIM-INBOX".to_string())]
+    );
+}
+
+#[test]
+fn diff_lists_properties_of_each_change() {
+    fn names(changes: &[ComponentChange<'_>]) -> Vec<String> {
+        changes
+            .iter()
+            .map(|change| {
+                let kinds: Vec<_> = change
+                    .properties()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                kinds.join(",")
+            })
+            .collect()
+    }
+
+    let old = calendar(STANDUP);
+    let renamed = STANDUP.replace("SUMMARY;LANGUAGE=en:Standup", "SUMMARY:Daily");
+    let new = calendar(&format!("{REPORT}{renamed}"));
+    assert_eq!(names(&diff(&old, &new)), ["DTSTAMP,SUMMARY,UID", "SUMMARY"]);
+    assert_eq!(
+        names(&diff(&old, &ICalendar::new())),
+        ["CATEGORIES,DTSTAMP,DTSTART,RRULE,SUMMARY,UID"]
+    );
+}
@@ -0,0 +1,141 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extreme date-times and durations
+//!
+//! These tests feed values at the edges of the supported range through parsing, date arithmetic
+//! and recurrence expansion. Whatever the outcome, none of them may panic: values that cannot be
+//! represented surface as errors or `None`.
+
+use aimcal_ical::ops::{ConflictExt, DateRange, VEventExt};
+use aimcal_ical::semantic::{CalendarComponent, VEvent};
+use aimcal_ical::string_storage::Segments;
+use aimcal_ical::{ICalendar, parse};
+use jiff::Timestamp;
+use jiff::civil::date;
+
+const DTSTARTS: &[&str] = &[
+    "DTSTART:00010101T000000Z",
+    "DTSTART:00010101T000000",
+    "DTSTART;VALUE=DATE:00010101",
+    "DTSTART:99991231T235959Z",
+    "DTSTART:99991231T235959",
+    "DTSTART;VALUE=DATE:99991231",
+    "DTSTART;TZID=America/New_York:99991231T235959",
+];
+
+const ENDS: &[&str] = &[
+    "",
+    "DURATION:P999999999W\r\n",
+    "DURATION:-P999999999W\r\n",
+    "DURATION:P99999999DT99999999H\r\n",
+    "DTEND:99991231T235959Z\r\n",
+];
+
+const RRULES: &[&str] = &[
+    "",
+    "RRULE:FREQ=DAILY\r\n",
+    "RRULE:FREQ=WEEKLY;WKST=SU;BYDAY=SU,MO\r\n",
+    "RRULE:FREQ=YEARLY;INTERVAL=9999\r\n",
+    "RRULE:FREQ=SECONDLY;COUNT=3\r\n",
+];
+
+fn calendar(component: &str, props: &str) -> String {
+    format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//aim//extremes//EN\r\n\
+         BEGIN:{component}\r\nUID:extreme@example.com\r\nDTSTAMP:99991231T235959Z\r\n\
+         {props}END:{component}\r\nEND:VCALENDAR\r\n"
+    )
+}
+
+fn first_event<'a>(calendars: &'a [ICalendar<Segments<'a>>]) -> Option<&'a VEvent<Segments<'a>>> {
+    match calendars.first()?.components.first()? {
+        CalendarComponent::Event(event) => Some(event),
+        _ => None,
+    }
+}
+
+#[test]
+fn extremes_never_panic_when_expanded() {
+    for dtstart in DTSTARTS {
+        // Expanding across the whole range takes millions of steps, so stay near the start
+        let range = if dtstart.contains(":0001") {
+            DateRange::new(date(1, 1, 1), date(1, 1, 31))
+        } else {
+            DateRange::new(date(9999, 12, 1), date(9999, 12, 31))
+        };
+        for end in ENDS {
+            for rrule in RRULES {
+                let src = calendar("VEVENT", &format!("{dtstart}\r\n{end}{rrule}"));
+                let Ok(calendars) = parse(&src) else {
+                    continue;
+                };
+                let Some(event) = first_event(&calendars) else {
+                    continue;
+                };
+                let event = event.to_owned();
+                if let Ok(occurrences) = event.expand_occurrences(range) {
+                    let _ = occurrences.detect_conflicts();
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn extremes_never_panic_in_todos() {
+    for dtstart in DTSTARTS {
+        for end in ENDS {
+            let end = end.replace("DTEND", "DUE");
+            let src = calendar(
+                "VTODO",
+                &format!("{dtstart}\r\n{end}COMPLETED:99991231T235959Z\r\n"),
+            );
+            let _ = parse(&src);
+        }
+    }
+}
+
+#[test]
+fn extremes_dtstamp_at_end_of_range_has_no_zoned_value() {
+    let src = calendar("VEVENT", "DTSTART:99991231T235959Z\r\n");
+    let calendars = parse(&src).unwrap();
+    let event = first_event(&calendars).unwrap();
+    assert_eq!(event.dt_stamp.civil_date_time().year(), 9999);
+    assert!(event.dt_stamp.zoned().is_none());
+}
+
+#[test]
+fn extremes_rejects_freebusy_period_out_of_range() {
+    let src = calendar(
+        "VFREEBUSY",
+        "ORGANIZER:mailto:a@example.com\r\nDTSTART:99991231T000000Z\r\n\
+         FREEBUSY:99991231T000000Z/P999999999W\r\n",
+    );
+    assert!(parse(&src).is_err());
+
+    let src = calendar(
+        "VFREEBUSY",
+        "ORGANIZER:mailto:a@example.com\r\nDTSTART:99991231T000000Z\r\n\
+         FREEBUSY:99991231T000000Z/PT1H\r\n",
+    );
+    assert!(parse(&src).is_ok());
+}
+
+#[test]
+fn extremes_alarm_triggers_out_of_range_are_errors() {
+    let src = calendar(
+        "VEVENT",
+        "DTSTART:20250101T000000Z\r\nBEGIN:VALARM\r\nACTION:AUDIO\r\n\
+         TRIGGER:-P999999999W\r\nEND:VALARM\r\n\
+         BEGIN:VALARM\r\nACTION:AUDIO\r\nTRIGGER:PT0S\r\nREPEAT:999999\r\n\
+         DURATION:P999999999W\r\nEND:VALARM\r\n",
+    );
+    let calendars = parse(&src).unwrap();
+    let event = first_event(&calendars).unwrap();
+    let start: Timestamp = "2025-01-01T00:00:00Z".parse().unwrap();
+    for alarm in &event.alarms {
+        assert!(alarm.resolve_triggers(start, None, 10).is_err());
+    }
+}
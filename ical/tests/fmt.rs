@@ -439,3 +439,214 @@ END:VCALENDAR\r\n";
     assert!(formatted.contains("BEGIN:VCALENDAR"));
     assert!(formatted.contains("VERSION:2.0"));
 }
+
+#[test]
+fn format_merged_ex_dates_as_single_sorted_line() {
+    use aimcal_ical::{CalendarComponent, Date, DateTime, ExDate, Time};
+
+    let input = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:test\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART;TZID=Europe/Berlin:20250103T090000\r\n\
+RRULE:FREQ=WEEKLY\r\n\
+EXDATE;TZID=Europe/Berlin:20250117T090000\r\n\
+EXDATE;TZID=Europe/Berlin:20250110T090000,20250117T090000\r\n\
+SUMMARY:Weekly sync\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(input).unwrap();
+    let mut calendar = calendars[0].to_owned();
+    let Some(CalendarComponent::Event(event)) = calendar.components.first_mut() else {
+        panic!("Expected an event");
+    };
+
+    let mut ex_dates = ExDate::merge(std::mem::take(&mut event.ex_dates));
+    assert_eq!(ex_dates.len(), 1);
+    let at = |day| DateTime::Floating {
+        date: Date::new(2025, 1, day).unwrap(),
+        time: Time::new(9, 0, 0).unwrap(),
+    };
+    assert!(ex_dates[0].insert(at(24)));
+    assert!(!ex_dates[0].insert(at(10)));
+    assert!(ex_dates[0].remove(&at(17)));
+    assert!(!ex_dates[0].contains(&at(17)));
+    event.ex_dates = ex_dates;
+
+    let formatted = format(&calendar).unwrap();
+    assert!(
+        formatted.contains("EXDATE;TZID=Europe/Berlin:20250110T090000,20250124T090000\r\n"),
+        "{formatted}"
+    );
+    assert_eq!(formatted.matches("EXDATE").count(), 1);
+    assert!(
+        formatted.contains("DTSTART;TZID=Europe/Berlin:20250103T090000\r\n"),
+        "{formatted}"
+    );
+}
+
+#[test]
+fn format_new_date_only_rdate_with_value_parameter() {
+    use aimcal_ical::{CalendarComponent, Date, DateTime, RDate};
+
+    let input = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:test\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART;VALUE=DATE:20250417\r\n\
+SUMMARY:Holiday\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(input).unwrap();
+    let mut calendar = calendars[0].to_owned();
+    let Some(CalendarComponent::Event(event)) = calendar.components.first_mut() else {
+        panic!("Expected an event");
+    };
+
+    let day = |day| DateTime::Date(Date::new(2025, 4, day).unwrap());
+    let mut rdate = RDate::new(vec![day(20), day(18)], None);
+    assert!(rdate.insert(day(19)));
+    assert!(rdate.remove(&day(20)));
+    event.rdates = RDate::merge(vec![rdate, RDate::new(vec![], None)]);
+
+    let formatted = format(&calendar).unwrap();
+    assert!(
+        formatted.contains("RDATE;VALUE=DATE:20250418,20250419\r\n"),
+        "{formatted}"
+    );
+
+    // The written property parses back to the same dates
+    let calendars = parse(&formatted).unwrap();
+    let Some(CalendarComponent::Event(event)) = calendars[0].components.first() else {
+        panic!("Expected an event");
+    };
+    assert_eq!(event.rdates.len(), 1);
+    assert!(event.rdates[0].contains(&day(18)));
+}
+
+#[test]
+fn format_new_date_time_properties_with_parameters() {
+    use aimcal_ical::{CalendarComponent, Date, DateTimeProperty, DtEnd, DtStart, Time};
+
+    let input = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:test\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250417T090000Z\r\n\
+SUMMARY:Holiday\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(input).unwrap();
+    let mut calendar = calendars[0].to_owned();
+    let Some(CalendarComponent::Event(event)) = calendar.components.first_mut() else {
+        panic!("Expected an event");
+    };
+    let date = Date::new(2025, 4, 17).unwrap();
+    event.dt_start = Some(DtStart::new(DateTimeProperty::zoned(
+        date,
+        Time::new(9, 0, 0).unwrap(),
+        "Europe/Berlin".to_string(),
+        None,
+        Vec::new(),
+        Vec::new(),
+        (),
+    )));
+    event.dt_end = Some(DtEnd::new(DateTimeProperty::date_only(
+        date,
+        Vec::new(),
+        Vec::new(),
+        (),
+    )));
+
+    let formatted = format(&calendar).unwrap();
+    assert!(
+        formatted.contains("DTSTART;TZID=Europe/Berlin:20250417T090000\r\n"),
+        "{formatted}"
+    );
+    assert!(
+        formatted.contains("DTEND;VALUE=DATE:20250417\r\n"),
+        "{formatted}"
+    );
+}
+
+#[test]
+fn write_calendar_streaming_writes_line_by_line() {
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+
+    use aimcal_ical::fmt::Formatter;
+    use aimcal_ical::{CalendarComponent, Uid};
+
+    /// Records each call to `write`.
+    struct Recorder(Rc<RefCell<Vec<Vec<u8>>>>);
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().push(buf.to_vec());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let input = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:template@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T140000Z\r\n\
+SUMMARY:Streamed event\r\n\
+DESCRIPTION:A description long enough to be folded over several lines\\, so that\r\n \
+ folding happens while the components are streamed one at a time.\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+    let mut calendar = parse(input).unwrap().remove(0).to_owned();
+    let Some(CalendarComponent::Event(template)) = calendar.components.pop() else {
+        panic!("Expected an event");
+    };
+
+    let writes = Rc::new(RefCell::new(Vec::new()));
+    let mut formatter = Formatter::new(Recorder(writes.clone()), FormatOptions::default());
+    let written_before = Rc::new(RefCell::new(Vec::new()));
+    let components = (0..1000).map(|i| {
+        // Each component is only produced once the previous ones were written
+        written_before.borrow_mut().push(writes.borrow().len());
+        let mut event = template.clone();
+        event.uid = Uid::new(format!("event-{i}@example.com"));
+        CalendarComponent::Event(event)
+    });
+    formatter
+        .write_calendar_streaming(&calendar, components)
+        .unwrap();
+
+    let written_before = written_before.borrow();
+    assert!(written_before.windows(2).all(|w| w[0] < w[1]));
+
+    let writes = writes.borrow();
+    for line in writes.iter() {
+        assert!(
+            line.ends_with(b"\r\n"),
+            "{:?}",
+            String::from_utf8_lossy(line)
+        );
+        assert!(line.len() <= 77, "{:?}", String::from_utf8_lossy(line));
+    }
+
+    let output = String::from_utf8(writes.concat()).unwrap();
+    assert_eq!(writes.len(), output.matches("\r\n").count());
+    let calendars = parse(&output).unwrap();
+    assert_eq!(calendars[0].components.len(), 1000);
+}
@@ -0,0 +1,150 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for parsing sources with broken components
+
+use aimcal_ical::{
+    CalendarComponent, ICalendar, ParseOptions, Segments, Severity, parse, parse_partial,
+    parse_with_diagnostics,
+};
+
+/// An export with broken events between valid ones: a value that is no date, an empty line and
+/// a missing DTSTAMP.
+const EXPORT: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//Export//EN\r
+BEGIN:VEVENT\r
+UID:first\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250102T100000Z\r
+SUMMARY:First\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:bad-date\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:next tuesday\r
+SUMMARY:Bad date\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:todo\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Todo\r
+END:VTODO\r
+BEGIN:VEVENT\r
+UID:empty-line\r
+DTSTAMP:20250101T000000Z\r
+\r
+DTSTART:20250103T100000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:no-dtstamp\r
+DTSTART:20250104T100000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:last\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250105T100000Z\r
+SUMMARY:Last\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn uids(calendars: &[ICalendar<Segments<'_>>]) -> Vec<String> {
+    calendars
+        .iter()
+        .flat_map(|calendar| &calendar.components)
+        .filter_map(|component| match component {
+            CalendarComponent::Event(event) => Some(event.uid.content.to_string()),
+            CalendarComponent::Todo(todo) => Some(todo.uid.content.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn partial_keeps_the_components_around_broken_ones() {
+    let parsed = parse_partial(EXPORT, ParseOptions::default()).unwrap();
+
+    assert_eq!(uids(&parsed.calendars), ["first", "todo", "last"]);
+    let broken: Vec<_> = parsed
+        .broken
+        .iter()
+        .map(|component| (component.name.as_str(), component.uid.as_deref()))
+        .collect();
+    assert_eq!(
+        broken,
+        [
+            ("VEVENT", Some("bad-date")),
+            ("VEVENT", Some("empty-line")),
+            ("VEVENT", Some("no-dtstamp")),
+        ]
+    );
+    for component in &parsed.broken {
+        assert!(!component.errors.is_empty(), "{component:?}");
+        assert_eq!(&EXPORT[component.span.into_range()], component.source);
+        assert!(component.source.starts_with("BEGIN:VEVENT\r\n"));
+        assert!(component.source.ends_with("END:VEVENT\r\n"));
+    }
+}
+
+#[test]
+fn partial_parses_valid_sources_as_a_whole() {
+    let src = EXPORT.replace("DTSTART:next tuesday", "DTSTART:20250102T120000Z");
+    let src = src.replace(
+        "DTSTAMP:20250101T000000Z\r\n\r\n",
+        "DTSTAMP:20250101T000000Z\r\n",
+    );
+    let src = src.replace(
+        "UID:no-dtstamp\r\n",
+        "UID:no-dtstamp\r\nDTSTAMP:20250101T000000Z\r\n",
+    );
+
+    let parsed = parse_partial(&src, ParseOptions::default()).unwrap();
+    assert!(parsed.broken.is_empty());
+    assert_eq!(uids(&parsed.calendars).len(), 6);
+}
+
+#[test]
+fn parse_leaves_out_invalid_components_and_reports_them_as_warnings() {
+    let src = EXPORT.replace("DTSTART:next tuesday", "DTSTART:20250102T120000Z");
+    let src = src.replace(
+        "DTSTAMP:20250101T000000Z\r\n\r\n",
+        "DTSTAMP:20250101T000000Z\r\n",
+    );
+
+    let calendars = parse(&src).unwrap();
+    assert_eq!(
+        uids(&calendars),
+        ["first", "bad-date", "todo", "empty-line", "last"]
+    );
+
+    let (calendars, diagnostics) = parse_with_diagnostics(&src);
+    assert_eq!(uids(&calendars.unwrap()).len(), 5);
+    let [diagnostic] = diagnostics.as_slice() else {
+        panic!("expected the missing DTSTAMP only, got {diagnostics:?}");
+    };
+    assert_eq!(diagnostic.severity, Severity::Warning);
+    assert!(diagnostic.message.contains("DTSTAMP"), "{diagnostic:?}");
+
+    let parsed = parse_partial(&src, ParseOptions::default()).unwrap();
+    let broken: Vec<_> = parsed.broken.iter().map(|c| c.uid.as_deref()).collect();
+    assert_eq!(broken, [Some("no-dtstamp")]);
+}
+
+#[test]
+fn partial_fails_on_broken_nesting() {
+    let src = EXPORT.replace("END:VTODO\r\n", "END:VEVENT\r\n");
+    let errors = parse_partial(&src, ParseOptions::default()).unwrap_err();
+    assert!(
+        errors.iter().any(|err| err.to_string().contains("VTODO")),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn partial_fails_on_errors_of_the_calendar_itself() {
+    let src = EXPORT.replace("PRODID:-//Example Corp.//Export//EN\r\n", "");
+    assert!(parse_partial(&src, ParseOptions::default()).is_err());
+}
@@ -9,8 +9,8 @@
 
 use aimcal_ical::fmt::format;
 use aimcal_ical::{
-    CalendarComponent, ICalendar, Segments, VAlarm, VEvent, VFreeBusy, VJournal, VTimeZone, VTodo,
-    parse,
+    AttachmentValue, CalendarComponent, ICalendar, RefreshInterval, Segments, VAlarm, VEvent,
+    VFreeBusy, VJournal, VTimeZone, VTodo, ValueDuration, parse,
 };
 
 #[test]
@@ -533,6 +533,360 @@ END:VCALENDAR\r\n";
     );
 }
 
+#[test]
+fn round_trip_calendar_with_quoted_parameters() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VTIMEZONE\r\n\
+TZID:/example.org/Custom: Zone;1,2\r\n\
+BEGIN:STANDARD\r\n\
+DTSTART:19700101T000000\r\n\
+TZOFFSETFROM:+0100\r\n\
+TZOFFSETTO:+0100\r\n\
+END:STANDARD\r\n\
+END:VTIMEZONE\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART;TZID=\"/example.org/Custom: Zone;1,2\":20250110T140000\r\n\
+SUMMARY;X-EMPTY=\"\";X-PLAIN=plain:Test Event\r\n\
+ATTENDEE;CN=\"Doe, John\";DELEGATED-TO=\"mailto:a@example.com\",\"mailto:b@example.com\":mailto:john@example.com\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars1 = parse(original).unwrap();
+    let calendar1 = &calendars1[0];
+    let formatted = format(&calendar1.to_owned()).unwrap();
+    let calendars2 = parse(&formatted).unwrap();
+    let calendar2 = &calendars2[0];
+
+    assert!(
+        calendars_equal(calendar1, calendar2),
+        "Calendars with quoted parameters should be equal after round-trip"
+    );
+
+    // Values are quoted when required, and only then
+    assert!(formatted.contains("DTSTART;TZID=\"/example.org/Custom: Zone;1,2\":20250110T140000"));
+    assert!(formatted.contains(";CN=\"Doe, John\";"));
+    assert!(formatted.contains(";DELEGATED-TO=\"mailto:a@example.com\",\"mailto:b@example.com\""));
+    assert!(formatted.contains("SUMMARY;X-EMPTY=\"\";X-PLAIN=plain:Test Event"));
+
+    let Some(CalendarComponent::Event(event)) = calendar2.components.get(1) else {
+        panic!("Expected VEVENT");
+    };
+    assert_eq!(
+        event
+            .dt_start
+            .as_ref()
+            .unwrap()
+            .tz_id
+            .as_ref()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("/example.org/Custom: Zone;1,2")
+    );
+    let attendee = &event.attendees[0];
+    assert_eq!(
+        attendee.cn.as_ref().map(ToString::to_string).as_deref(),
+        Some("Doe, John")
+    );
+    let delegated_to: Vec<_> = attendee
+        .delegated_to
+        .iter()
+        .flatten()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        delegated_to,
+        ["mailto:a@example.com", "mailto:b@example.com"]
+    );
+}
+
+#[test]
+fn round_trip_calendar_with_parameter_set_from_code() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T140000Z\r\n\
+ATTENDEE;CN=John:mailto:john@example.com\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(original).unwrap();
+    let mut calendar = calendars[0].to_owned();
+    let Some(CalendarComponent::Event(event)) = calendar.components.first_mut() else {
+        panic!("Expected VEVENT");
+    };
+    event.attendees[0].cn = Some("Doe, John".to_string());
+
+    let formatted = format(&calendar).unwrap();
+    assert!(formatted.contains("ATTENDEE;CN=\"Doe, John\";"));
+
+    let reparsed = parse(&formatted).unwrap();
+    let CalendarComponent::Event(event) = &reparsed[0].components[0] else {
+        panic!("Expected VEVENT");
+    };
+    assert_eq!(
+        event.attendees[0]
+            .cn
+            .as_ref()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("Doe, John")
+    );
+}
+
+#[test]
+fn round_trip_calendar_with_rfc7986_properties() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+NAME:Holidays\r\n\
+NAME;LANGUAGE=de:Feiertage\r\n\
+DESCRIPTION:Public holidays\\, updated weekly\r\n\
+COLOR:turquoise\r\n\
+REFRESH-INTERVAL;VALUE=DURATION:P1W\r\n\
+SOURCE;VALUE=URI:https://example.com/holidays.ics\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars1 = parse(original).unwrap();
+    let calendar_owned = calendars1[0].to_owned();
+    let formatted = format(&calendar_owned).unwrap();
+
+    assert!(formatted.contains("NAME:Holidays\r\n"));
+    assert!(formatted.contains("NAME;LANGUAGE=de:Feiertage\r\n"));
+    assert!(formatted.contains("DESCRIPTION:Public holidays\\, updated weekly\r\n"));
+    assert!(formatted.contains("COLOR:turquoise\r\n"));
+    assert!(formatted.contains("REFRESH-INTERVAL;VALUE=DURATION:P1W\r\n"));
+    assert!(formatted.contains("SOURCE;VALUE=URI:https://example.com/holidays.ics\r\n"));
+
+    let calendars2 = parse(&formatted).unwrap();
+    let calendar2 = calendars2[0].to_owned();
+    assert_eq!(calendar2.names.len(), 2);
+    assert_eq!(calendar2.name().as_deref(), Some("Holidays"));
+    assert_eq!(
+        calendar2.description().as_deref(),
+        Some("Public holidays, updated weekly")
+    );
+    assert_eq!(
+        calendar2.refresh_interval.map(|r| r.value),
+        Some(ValueDuration::Week {
+            positive: true,
+            week: 1
+        })
+    );
+}
+
+#[test]
+fn format_writes_value_type_of_refresh_interval() {
+    let mut calendar = ICalendar::new();
+    calendar.refresh_interval = Some(RefreshInterval {
+        value: ValueDuration::DateTime {
+            positive: true,
+            day: 0,
+            hour: 12,
+            minute: 0,
+            second: 0,
+        },
+        x_parameters: Vec::new(),
+        retained_parameters: Vec::new(),
+        span: (),
+    });
+
+    let formatted = format(&calendar).unwrap();
+    assert!(formatted.contains("REFRESH-INTERVAL;VALUE=DURATION:PT12H\r\n"));
+}
+
+#[test]
+fn round_trip_event_with_rfc7986_properties() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:12345@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T140000Z\r\n\
+COLOR:turquoise\r\n\
+IMAGE;VALUE=URI;DISPLAY=BADGE,THUMBNAIL;FMTTYPE=image/png:https://example.com/i.png\r\n\
+CONFERENCE;VALUE=URI;FEATURE=AUDIO,VIDEO;LABEL=Join:https://chat.example.com/a\r\n\
+CONFERENCE;VALUE=URI;FEATURE=PHONE,MODERATOR;LABEL=Moderator dial-in:tel:+1-412-555-0123\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars1 = parse(original).unwrap();
+    let calendar_owned = calendars1[0].to_owned();
+    let formatted = format(&calendar_owned).unwrap();
+
+    assert!(formatted.contains("COLOR:turquoise\r\n"));
+    // The value type has no default and is written once for each property
+    assert_eq!(formatted.matches("VALUE=URI").count(), 3);
+
+    let calendars2 = parse(&formatted).unwrap();
+    let CalendarComponent::Event(event) = &calendars2[0].components[0] else {
+        panic!("Expected VEVENT");
+    };
+    assert_eq!(
+        event.color.as_ref().map(|c| c.content.to_string()),
+        Some("turquoise".to_string())
+    );
+    assert_eq!(event.images.len(), 1);
+    assert_eq!(event.images[0].display.len(), 2);
+    assert_eq!(event.conferences.len(), 2);
+    assert_eq!(event.conferences[0].features.len(), 2);
+    assert_eq!(
+        event.conferences[1]
+            .label
+            .as_ref()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("Moderator dial-in")
+    );
+}
+
+#[test]
+fn round_trip_event_with_large_binary_attachment() {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    // 102 400 bytes: 136 532 characters of full groups and a last group with padding
+    let mut data: String = (0..136_532)
+        .map(|i| char::from(ALPHABET[i % ALPHABET.len()]))
+        .collect();
+    data.push_str("QQ==");
+
+    // Folded by hand at 75 octets, as a client would
+    let line =
+        format!("ATTACH;FMTTYPE=application/octet-stream;ENCODING=BASE64;VALUE=BINARY:{data}");
+    let (first, mut rest) = line.split_at(75);
+    let mut folded = first.to_string();
+    while !rest.is_empty() {
+        let (chunk, tail) = rest.split_at(rest.len().min(74));
+        folded.push_str("\r\n ");
+        folded.push_str(chunk);
+        rest = tail;
+    }
+    let original = format!(
+        "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:12345@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T140000Z\r\n\
+{folded}\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n"
+    );
+
+    let calendars1 = parse(&original).unwrap();
+    let CalendarComponent::Event(event) = &calendars1[0].components[0] else {
+        panic!("Expected VEVENT");
+    };
+    assert_eq!(event.attachments[0].value.decoded_len(), Some(102_400));
+
+    let formatted = format(&calendars1[0].to_owned()).unwrap();
+    for line in formatted.split("\r\n") {
+        assert!(line.len() <= 75, "line of {} octets", line.len());
+    }
+
+    let calendars2 = parse(&formatted).unwrap();
+    let CalendarComponent::Event(event) = &calendars2[0].components[0] else {
+        panic!("Expected VEVENT");
+    };
+    let AttachmentValue::Binary(value) = &event.attachments[0].value else {
+        panic!("Expected binary attachment");
+    };
+    assert_eq!(value.to_owned(), data);
+    assert_eq!(event.attachments[0].value.decoded_len(), Some(102_400));
+}
+
+#[test]
+fn round_trip_vavailability() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VAVAILABILITY\r\n\
+UID:availability@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250106T000000\r\n\
+BUSYTYPE:BUSY-TENTATIVE\r\n\
+PRIORITY:1\r\n\
+BEGIN:AVAILABLE\r\n\
+UID:weekdays@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250106T090000\r\n\
+DURATION:PT8H\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r\n\
+EXDATE:20250108T090000\r\n\
+END:AVAILABLE\r\n\
+BEGIN:AVAILABLE\r\n\
+UID:weekdays@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+RECURRENCE-ID:20250107T090000\r\n\
+DTSTART:20250107T130000\r\n\
+DTEND:20250107T190000\r\n\
+END:AVAILABLE\r\n\
+END:VAVAILABILITY\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars1 = parse(original).unwrap();
+    let calendar_owned = calendars1[0].to_owned();
+    let formatted = format(&calendar_owned).unwrap();
+
+    assert!(formatted.contains("BUSYTYPE:BUSY-TENTATIVE\r\n"));
+    assert_eq!(formatted.matches("BEGIN:AVAILABLE\r\n").count(), 2);
+
+    let calendars2 = parse(&formatted).unwrap();
+    let CalendarComponent::VAvailability(availability) = &calendars2[0].components[0] else {
+        panic!("Expected VAVAILABILITY");
+    };
+    assert_eq!(availability.priority.as_ref().map(|p| p.value), Some(1));
+    assert_eq!(availability.available.len(), 2);
+    assert!(availability.available[0].rrule.is_some());
+    assert_eq!(availability.available[0].ex_dates.len(), 1);
+    assert!(availability.available[1].recurrence_id.is_some());
+}
+
+#[test]
+fn format_rejects_unrepresentable_parameter_values() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:test@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T140000Z\r\n\
+ATTENDEE;CN=John;DELEGATED-TO=\"mailto:a@example.com\":mailto:john@example.com\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(original).unwrap();
+    for (cn, delegatee) in [
+        ("Say \"hi\"", "mailto:a@example.com"),
+        ("Line\nbreak", "mailto:a@example.com"),
+        ("John", "mailto:\"a\"@example.com"),
+    ] {
+        let mut calendar = calendars[0].to_owned();
+        let Some(CalendarComponent::Event(event)) = calendar.components.first_mut() else {
+            panic!("Expected VEVENT");
+        };
+        event.attendees[0].cn = Some(cn.to_string());
+        event.attendees[0].delegated_to = Some(vec![delegatee.to_string()]);
+
+        let err = format(&calendar).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
+
 /// Helper function to compare two ICalendars structurally for key properties.
 ///
 /// This is a simplified comparison that checks the essential properties
@@ -801,3 +1155,70 @@ fn alarms_equal(a1: &VAlarm<Segments<'_>>, a2: &VAlarm<Segments<'_>>) -> bool {
 
     true
 }
+
+#[test]
+fn round_trip_writes_each_repeated_property() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:event123@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART:20250110T090000Z\r\n\
+CATEGORIES:WORK\r\n\
+CATEGORIES:MEETING\r\n\
+COMMENT:Bring slides\r\n\
+COMMENT:Room changed\r\n\
+ATTACH:https://example.com/agenda.pdf\r\n\
+ATTACH:https://example.com/notes.pdf\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(original).unwrap();
+    let formatted = format(&calendars[0].to_owned()).unwrap();
+
+    for line in [
+        "CATEGORIES:WORK\r\n",
+        "CATEGORIES:MEETING\r\n",
+        "COMMENT:Bring slides\r\n",
+        "COMMENT:Room changed\r\n",
+        "ATTACH:https://example.com/agenda.pdf\r\n",
+        "ATTACH:https://example.com/notes.pdf\r\n",
+    ] {
+        assert_eq!(
+            formatted.matches(line).count(),
+            1,
+            "{line:?} in {formatted}"
+        );
+    }
+    assert!(parse(&formatted).is_ok());
+}
+
+#[test]
+fn round_trip_keeps_tzid_of_utc_date_time() {
+    let original = "\
+BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Cal Client 1.0//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:event123@example.com\r\n\
+DTSTAMP:20250110T120000Z\r\n\
+DTSTART;TZID=Europe/Berlin:20250610T120000Z\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    let calendars = parse(original).unwrap();
+    let CalendarComponent::Event(event) = &calendars[0].components[0] else {
+        panic!("expected an event");
+    };
+    let dt_start = event.dt_start.as_ref().unwrap();
+    assert!(dt_start.is_utc());
+    assert!(dt_start.tz_id.is_none());
+
+    let formatted = format(&calendars[0].to_owned()).unwrap();
+    assert!(
+        formatted.contains("DTSTART;TZID=Europe/Berlin:20250610T120000Z\r\n"),
+        "{formatted}"
+    );
+}
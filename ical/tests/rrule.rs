@@ -0,0 +1,643 @@
+// SPDX-FileCopyrightText: 2025-2026 Zexin Yuan <aim@yzx9.xyz>
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recurrence rule examples of RFC 5545 section 3.8.5.3
+//!
+//! Each example is parsed as part of an event, round-tripped through the formatter and expanded,
+//! and the occurrences are compared with the ones published in the RFC. All examples start in
+//! America/New_York and the occurrences are compared as civil date-times, so the EDT/EST changes
+//! of the RFC listings do not show up here.
+//!
+//! The examples that recur forever are expanded up to the last occurrence listed in the RFC.
+
+use aimcal_ical::ops::{DateRange, RRuleExt, VEventExt};
+use aimcal_ical::parse;
+use aimcal_ical::semantic::CalendarComponent;
+use aimcal_ical::value::ValueRecurrenceRule;
+use jiff::civil::{Date, DateTime, date};
+
+/// Expands the rule of an event starting at `dtstart` up to `end`.
+fn expand(dtstart: &str, rrule: &str, end: Date) -> Vec<String> {
+    let src = format!(
+        "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//aim//RFC 5545 examples//EN\r
+BEGIN:VEVENT\r
+UID:rfc5545-example\r
+DTSTAMP:19970901T000000Z\r
+DTSTART;TZID=America/New_York:{dtstart}\r
+RRULE:{rrule}\r
+END:VEVENT\r
+END:VCALENDAR\r
+"
+    );
+    let calendars = parse(&src).unwrap();
+    let component = calendars.first().and_then(|c| c.components.first());
+    let Some(CalendarComponent::Event(event)) = component else {
+        panic!("expected an event, got {component:?}");
+    };
+
+    // The rule must keep its meaning through formatting, which leaves out defaults like WKST=MO
+    let mut rule = (*event.rrule.as_ref().unwrap().value).clone();
+    let mut reparsed: ValueRecurrenceRule = rule.to_string().parse().unwrap();
+    rule.normalize();
+    reparsed.normalize();
+    assert_eq!(reparsed, rule, "rule changed by a round trip");
+
+    let start = event.dt_start.as_ref().unwrap().civil_date_time().unwrap();
+    event
+        .expand_occurrences(DateRange::new(start.date(), end))
+        .unwrap()
+        .iter()
+        .map(|occurrence| format_datetime(occurrence.start))
+        .collect()
+}
+
+fn format_datetime(dt: DateTime) -> String {
+    dt.strftime("%Y%m%dT%H%M%S").to_string()
+}
+
+/// Expands a rule that ends by itself, with a range far past its end.
+fn assert_bounded(dtstart: &str, rrule: &str, expected: &[String]) {
+    assert_eq!(expand(dtstart, rrule, date(2199, 12, 31)), expected);
+}
+
+/// Expands a rule that recurs forever, up to the last expected occurrence.
+fn assert_starts_with(dtstart: &str, rrule: &str, expected: &[String]) {
+    let last = expected.last().unwrap();
+    let end: DateTime = last.parse().unwrap();
+    assert_eq!(expand(dtstart, rrule, end.date()), expected);
+}
+
+/// The occurrences at 09:00 on the given dates, written as `YYYYMMDD`.
+fn at_nine(dates: &[&str]) -> Vec<String> {
+    dates.iter().map(|d| format!("{d}T090000")).collect()
+}
+
+/// The occurrences at 09:00 every `step` days from `from` to `to`.
+fn every_days(from: Date, to: Date, step: i64) -> Vec<String> {
+    let mut dates = Vec::new();
+    let mut day = from;
+    while day <= to {
+        dates.push(format_datetime(day.at(9, 0, 0, 0)));
+        day = day.checked_add(jiff::Span::new().days(step)).unwrap();
+    }
+    dates
+}
+
+#[test]
+fn daily_for_10_occurrences() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=DAILY;COUNT=10",
+        &every_days(date(1997, 9, 2), date(1997, 9, 11), 1),
+    );
+}
+
+#[test]
+fn daily_until_december_24_1997() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=DAILY;UNTIL=19971224T000000Z",
+        &every_days(date(1997, 9, 2), date(1997, 12, 23), 1),
+    );
+}
+
+#[test]
+fn every_other_day_forever() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=DAILY;INTERVAL=2",
+        &every_days(date(1997, 9, 2), date(1997, 12, 31), 2),
+    );
+}
+
+#[test]
+fn every_10_days_5_occurrences() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=DAILY;INTERVAL=10;COUNT=5",
+        &at_nine(&["19970902", "19970912", "19970922", "19971002", "19971012"]),
+    );
+}
+
+fn every_day_in_january_1998_to_2000() -> Vec<String> {
+    (1998..=2000)
+        .flat_map(|year| every_days(date(year, 1, 1), date(year, 1, 31), 1))
+        .collect()
+}
+
+#[test]
+fn every_day_in_january_for_3_years_yearly() {
+    assert_bounded(
+        "19980101T090000",
+        "FREQ=YEARLY;UNTIL=20000131T140000Z;BYMONTH=1;BYDAY=SU,MO,TU,WE,TH,FR,SA",
+        &every_day_in_january_1998_to_2000(),
+    );
+}
+
+#[test]
+fn every_day_in_january_for_3_years_daily() {
+    assert_bounded(
+        "19980101T090000",
+        "FREQ=DAILY;UNTIL=20000131T140000Z;BYMONTH=1",
+        &every_day_in_january_1998_to_2000(),
+    );
+}
+
+#[test]
+fn weekly_for_10_occurrences() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=WEEKLY;COUNT=10",
+        &every_days(date(1997, 9, 2), date(1997, 11, 4), 7),
+    );
+}
+
+#[test]
+fn weekly_until_december_24_1997() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=WEEKLY;UNTIL=19971224T000000Z",
+        &every_days(date(1997, 9, 2), date(1997, 12, 23), 7),
+    );
+}
+
+#[test]
+fn every_other_week_forever() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=WEEKLY;INTERVAL=2;WKST=SU",
+        &every_days(date(1997, 9, 2), date(1998, 2, 17), 14),
+    );
+}
+
+fn tuesdays_and_thursdays_for_5_weeks() -> Vec<String> {
+    at_nine(&[
+        "19970902", "19970904", "19970909", "19970911", "19970916", "19970918", "19970923",
+        "19970925", "19970930", "19971002",
+    ])
+}
+
+#[test]
+fn weekly_on_tuesday_and_thursday_for_5_weeks_until() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=WEEKLY;UNTIL=19971007T000000Z;WKST=SU;BYDAY=TU,TH",
+        &tuesdays_and_thursdays_for_5_weeks(),
+    );
+}
+
+#[test]
+fn weekly_on_tuesday_and_thursday_for_5_weeks_count() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=WEEKLY;COUNT=10;WKST=SU;BYDAY=TU,TH",
+        &tuesdays_and_thursdays_for_5_weeks(),
+    );
+}
+
+#[test]
+fn every_other_week_on_monday_wednesday_friday_until() {
+    assert_bounded(
+        "19970901T090000",
+        "FREQ=WEEKLY;INTERVAL=2;UNTIL=19971224T000000Z;WKST=SU;BYDAY=MO,WE,FR",
+        &at_nine(&[
+            "19970901", "19970903", "19970905", "19970915", "19970917", "19970919", "19970929",
+            "19971001", "19971003", "19971013", "19971015", "19971017", "19971027", "19971029",
+            "19971031", "19971110", "19971112", "19971114", "19971124", "19971126", "19971128",
+            "19971208", "19971210", "19971212", "19971222",
+        ]),
+    );
+}
+
+#[test]
+fn every_other_week_on_tuesday_and_thursday_for_8_occurrences() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=WEEKLY;INTERVAL=2;COUNT=8;WKST=SU;BYDAY=TU,TH",
+        &at_nine(&[
+            "19970902", "19970904", "19970916", "19970918", "19970930", "19971002", "19971014",
+            "19971016",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_first_friday_for_10_occurrences() {
+    assert_bounded(
+        "19970905T090000",
+        "FREQ=MONTHLY;COUNT=10;BYDAY=1FR",
+        &at_nine(&[
+            "19970905", "19971003", "19971107", "19971205", "19980102", "19980206", "19980306",
+            "19980403", "19980501", "19980605",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_first_friday_until_december_24_1997() {
+    assert_bounded(
+        "19970905T090000",
+        "FREQ=MONTHLY;UNTIL=19971224T000000Z;BYDAY=1FR",
+        &at_nine(&["19970905", "19971003", "19971107", "19971205"]),
+    );
+}
+
+#[test]
+fn every_other_month_on_first_and_last_sunday() {
+    assert_bounded(
+        "19970907T090000",
+        "FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1SU,-1SU",
+        &at_nine(&[
+            "19970907", "19970928", "19971102", "19971130", "19980104", "19980125", "19980301",
+            "19980329", "19980503", "19980531",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_second_to_last_monday_for_6_months() {
+    assert_bounded(
+        "19970922T090000",
+        "FREQ=MONTHLY;COUNT=6;BYDAY=-2MO",
+        &at_nine(&[
+            "19970922", "19971020", "19971117", "19971222", "19980119", "19980216",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_third_to_last_day_forever() {
+    assert_starts_with(
+        "19970928T090000",
+        "FREQ=MONTHLY;BYMONTHDAY=-3",
+        &at_nine(&[
+            "19970928", "19971029", "19971128", "19971229", "19980129", "19980226",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_2nd_and_15th_for_10_occurrences() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=MONTHLY;COUNT=10;BYMONTHDAY=2,15",
+        &at_nine(&[
+            "19970902", "19970915", "19971002", "19971015", "19971102", "19971115", "19971202",
+            "19971215", "19980102", "19980115",
+        ]),
+    );
+}
+
+#[test]
+fn monthly_on_first_and_last_day_for_10_occurrences() {
+    assert_bounded(
+        "19970930T090000",
+        "FREQ=MONTHLY;COUNT=10;BYMONTHDAY=1,-1",
+        &at_nine(&[
+            "19970930", "19971001", "19971031", "19971101", "19971130", "19971201", "19971231",
+            "19980101", "19980131", "19980201",
+        ]),
+    );
+}
+
+#[test]
+fn every_18_months_on_10th_to_15th_for_10_occurrences() {
+    assert_bounded(
+        "19970910T090000",
+        "FREQ=MONTHLY;INTERVAL=18;COUNT=10;BYMONTHDAY=10,11,12,13,14,15",
+        &at_nine(&[
+            "19970910", "19970911", "19970912", "19970913", "19970914", "19970915", "19990310",
+            "19990311", "19990312", "19990313",
+        ]),
+    );
+}
+
+#[test]
+fn every_tuesday_every_other_month() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=MONTHLY;INTERVAL=2;BYDAY=TU",
+        &at_nine(&[
+            "19970902", "19970909", "19970916", "19970923", "19970930", "19971104", "19971111",
+            "19971118", "19971125", "19980106", "19980113", "19980120", "19980127", "19980303",
+            "19980310", "19980317", "19980324", "19980331",
+        ]),
+    );
+}
+
+#[test]
+fn yearly_in_june_and_july_for_10_occurrences() {
+    assert_bounded(
+        "19970610T090000",
+        "FREQ=YEARLY;COUNT=10;BYMONTH=6,7",
+        &at_nine(&[
+            "19970610", "19970710", "19980610", "19980710", "19990610", "19990710", "20000610",
+            "20000710", "20010610", "20010710",
+        ]),
+    );
+}
+
+#[test]
+fn every_other_year_on_january_february_march_for_10_occurrences() {
+    assert_bounded(
+        "19970310T090000",
+        "FREQ=YEARLY;INTERVAL=2;COUNT=10;BYMONTH=1,2,3",
+        &at_nine(&[
+            "19970310", "19990110", "19990210", "19990310", "20010110", "20010210", "20010310",
+            "20030110", "20030210", "20030310",
+        ]),
+    );
+}
+
+#[test]
+fn every_third_year_on_1st_100th_200th_day_for_10_occurrences() {
+    assert_bounded(
+        "19970101T090000",
+        "FREQ=YEARLY;INTERVAL=3;COUNT=10;BYYEARDAY=1,100,200",
+        &at_nine(&[
+            "19970101", "19970410", "19970719", "20000101", "20000409", "20000718", "20030101",
+            "20030410", "20030719", "20060101",
+        ]),
+    );
+}
+
+#[test]
+fn every_20th_monday_of_the_year() {
+    assert_starts_with(
+        "19970519T090000",
+        "FREQ=YEARLY;BYDAY=20MO",
+        &at_nine(&["19970519", "19980518", "19990517"]),
+    );
+}
+
+#[test]
+fn monday_of_week_number_20() {
+    assert_starts_with(
+        "19970512T090000",
+        "FREQ=YEARLY;BYWEEKNO=20;BYDAY=MO",
+        &at_nine(&["19970512", "19980511", "19990517"]),
+    );
+}
+
+#[test]
+fn every_thursday_in_march() {
+    assert_starts_with(
+        "19970313T090000",
+        "FREQ=YEARLY;BYMONTH=3;BYDAY=TH",
+        &at_nine(&[
+            "19970313", "19970320", "19970327", "19980305", "19980312", "19980319", "19980326",
+            "19990304", "19990311", "19990318", "19990325",
+        ]),
+    );
+}
+
+#[test]
+fn every_thursday_in_june_july_august() {
+    assert_starts_with(
+        "19970605T090000",
+        "FREQ=YEARLY;BYDAY=TH;BYMONTH=6,7,8",
+        &at_nine(&[
+            "19970605", "19970612", "19970619", "19970626", "19970703", "19970710", "19970717",
+            "19970724", "19970731", "19970807", "19970814", "19970821", "19970828", "19980604",
+            "19980611", "19980618", "19980625", "19980702", "19980709", "19980716", "19980723",
+            "19980730", "19980806", "19980813", "19980820", "19980827", "19990603", "19990610",
+            "19990617", "19990624", "19990701", "19990708", "19990715", "19990722", "19990729",
+            "19990805", "19990812", "19990819", "19990826",
+        ]),
+    );
+}
+
+/// DTSTART is excluded with EXDATE in the RFC, and is not generated by the rule either.
+#[test]
+fn every_friday_the_13th() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13",
+        &at_nine(&["19980213", "19980313", "19981113", "19990813", "20001013"]),
+    );
+}
+
+#[test]
+fn first_saturday_after_first_sunday_of_the_month() {
+    assert_starts_with(
+        "19970913T090000",
+        "FREQ=MONTHLY;BYDAY=SA;BYMONTHDAY=7,8,9,10,11,12,13",
+        &at_nine(&[
+            "19970913", "19971011", "19971108", "19971213", "19980110", "19980207", "19980307",
+            "19980411", "19980509", "19980613",
+        ]),
+    );
+}
+
+#[test]
+fn us_presidential_election_day() {
+    assert_starts_with(
+        "19961105T090000",
+        "FREQ=YEARLY;INTERVAL=4;BYMONTH=11;BYDAY=TU;BYMONTHDAY=2,3,4,5,6,7,8",
+        &at_nine(&["19961105", "20001107", "20041102"]),
+    );
+}
+
+#[test]
+fn third_tuesday_wednesday_or_thursday_for_3_months() {
+    assert_bounded(
+        "19970904T090000",
+        "FREQ=MONTHLY;COUNT=3;BYDAY=TU,WE,TH;BYSETPOS=3",
+        &at_nine(&["19970904", "19971007", "19971106"]),
+    );
+}
+
+#[test]
+fn second_to_last_weekday_of_the_month() {
+    assert_starts_with(
+        "19970929T090000",
+        "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-2",
+        &at_nine(&[
+            "19970929", "19971030", "19971127", "19971230", "19980129", "19980226", "19980330",
+        ]),
+    );
+}
+
+/// The last work day of the month, given as an example in RFC 5545 section 3.3.10.
+#[test]
+fn last_weekday_of_the_month() {
+    assert_starts_with(
+        "19970930T090000",
+        "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+        &at_nine(&[
+            "19970930", "19971031", "19971128", "19971231", "19980130", "19980227", "19980331",
+        ]),
+    );
+}
+
+#[test]
+fn every_3_hours_from_9_to_5_on_a_day() {
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=HOURLY;INTERVAL=3;UNTIL=19970902T170000Z",
+        &[
+            "19970902T090000".to_string(),
+            "19970902T120000".to_string(),
+            "19970902T150000".to_string(),
+        ],
+    );
+}
+
+#[test]
+fn every_15_minutes_for_6_occurrences() {
+    let expected: Vec<_> = ["090000", "091500", "093000", "094500", "100000", "101500"]
+        .iter()
+        .map(|t| format!("19970902T{t}"))
+        .collect();
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=MINUTELY;INTERVAL=15;COUNT=6",
+        &expected,
+    );
+}
+
+#[test]
+fn every_hour_and_a_half_for_4_occurrences() {
+    let expected: Vec<_> = ["090000", "103000", "120000", "133000"]
+        .iter()
+        .map(|t| format!("19970902T{t}"))
+        .collect();
+    assert_bounded(
+        "19970902T090000",
+        "FREQ=MINUTELY;INTERVAL=90;COUNT=4",
+        &expected,
+    );
+}
+
+/// 9:00, 9:20, 9:40, ... 16:40 on September 2 and 3, 1997.
+fn every_20_minutes_from_9_to_16_40() -> Vec<String> {
+    ["19970902", "19970903"]
+        .iter()
+        .flat_map(|day| {
+            (9..=16).flat_map(move |hour| {
+                [0, 20, 40].map(|minute| format!("{day}T{hour:02}{minute:02}00"))
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn every_20_minutes_from_9_to_16_40_daily() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=DAILY;BYHOUR=9,10,11,12,13,14,15,16;BYMINUTE=0,20,40",
+        &every_20_minutes_from_9_to_16_40(),
+    );
+}
+
+#[test]
+fn every_20_minutes_from_9_to_16_40_minutely() {
+    assert_starts_with(
+        "19970902T090000",
+        "FREQ=MINUTELY;INTERVAL=20;BYHOUR=9,10,11,12,13,14,15,16",
+        &every_20_minutes_from_9_to_16_40(),
+    );
+}
+
+#[test]
+fn week_start_monday_changes_the_weeks() {
+    assert_bounded(
+        "19970805T090000",
+        "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO",
+        &at_nine(&["19970805", "19970810", "19970819", "19970824"]),
+    );
+}
+
+#[test]
+fn week_start_sunday_changes_the_weeks() {
+    assert_bounded(
+        "19970805T090000",
+        "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+        &at_nine(&["19970805", "19970817", "19970819", "19970831"]),
+    );
+}
+
+#[test]
+fn invalid_dates_are_skipped() {
+    assert_bounded(
+        "20070115T090000",
+        "FREQ=MONTHLY;BYMONTHDAY=15,30;COUNT=5",
+        &at_nine(&["20070115", "20070130", "20070215", "20070315", "20070330"]),
+    );
+}
+
+#[test]
+fn yearly_on_february_29_skips_common_years() {
+    assert_bounded(
+        "20960229T090000",
+        "FREQ=YEARLY;COUNT=3",
+        &at_nine(&["20960229", "21040229", "21080229"]),
+    );
+}
+
+#[test]
+fn yearly_on_bymonthday_29_of_february_skips_common_years() {
+    assert_bounded(
+        "19960229T090000",
+        "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;COUNT=3",
+        &at_nine(&["19960229", "20000229", "20040229"]),
+    );
+}
+
+#[test]
+fn monthly_on_29th_skips_february_of_common_years() {
+    assert_bounded(
+        "20230129T090000",
+        "FREQ=MONTHLY;BYMONTHDAY=29;COUNT=4",
+        &at_nine(&["20230129", "20230329", "20230429", "20230529"]),
+    );
+}
+
+#[test]
+fn monthly_on_29th_includes_february_of_leap_years() {
+    assert_bounded(
+        "20240129T090000",
+        "FREQ=MONTHLY;BYMONTHDAY=29;COUNT=3",
+        &at_nine(&["20240129", "20240229", "20240329"]),
+    );
+}
+
+#[test]
+fn count_includes_occurrences_before_the_range() {
+    let rule: ValueRecurrenceRule = "FREQ=DAILY;COUNT=10".parse().unwrap();
+    let start = date(1997, 9, 2).at(9, 0, 0, 0);
+    let range = DateRange::new(date(1997, 9, 10), date(1997, 12, 31));
+    let occurrences: Vec<_> = rule
+        .expand(start, range)
+        .unwrap()
+        .into_iter()
+        .map(format_datetime)
+        .collect();
+    assert_eq!(occurrences, at_nine(&["19970910", "19970911"]));
+}
+
+#[test]
+fn event_without_dtstart_has_no_occurrences() {
+    let src = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//aim//RFC 5545 examples//EN\r
+BEGIN:VEVENT\r
+UID:unscheduled\r
+DTSTAMP:19970901T000000Z\r
+RRULE:FREQ=DAILY;COUNT=10\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let calendars = parse(src).unwrap();
+    let component = calendars.first().and_then(|c| c.components.first());
+    let Some(CalendarComponent::Event(event)) = component else {
+        panic!("expected an event, got {component:?}");
+    };
+    let range = DateRange::new(date(1997, 9, 1), date(1997, 12, 31));
+    assert!(event.expand_occurrences(range).unwrap().is_empty());
+}
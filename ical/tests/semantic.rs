@@ -7,15 +7,17 @@
 //! These tests validate the semantic analyzer's behavior on realistic iCalendar content
 //! and edge cases.
 
-use aimcal_ical::parse;
 use aimcal_ical::semantic::{CalendarComponent, SemanticError, semantic_analysis};
 use aimcal_ical::string_storage::Segments;
 use aimcal_ical::syntax::syntax_analysis;
 use aimcal_ical::typed::typed_analysis;
 use aimcal_ical::value::ValueDuration;
 use aimcal_ical::{
-    CalendarScale, CalendarScaleValue, ICalendar, Method, MethodValue, Period, Version,
-    VersionValue,
+    BusyTypeValue, CalendarScale, CalendarScaleValue, ICalendar, Method, MethodValue, Period,
+    Version, VersionValue,
+};
+use aimcal_ical::{
+    ParseOptions, Severity, parse, parse_with_diagnostics_and_options, parse_with_options,
 };
 
 /// Test helper to parse iCalendar source through semantic phase
@@ -278,7 +280,7 @@ END:VCALENDAR\r
 
     match &calendar.components[0] {
         CalendarComponent::Event(event) => {
-            assert!(event.dt_start.date().day > 0);
+            assert!(event.dt_start.as_ref().unwrap().date().day > 0);
             assert!(event.dt_end.is_some());
         }
         _ => panic!("Expected Event component"),
@@ -305,7 +307,7 @@ END:VCALENDAR\r
 
     match &calendar.components[0] {
         CalendarComponent::Event(event) => {
-            assert!(event.dt_start.date().day > 0);
+            assert!(event.dt_start.as_ref().unwrap().date().day > 0);
             assert!(event.duration.is_some());
         }
         _ => panic!("Expected Event component"),
@@ -1095,6 +1097,227 @@ END:VCALENDAR\r
     }
 }
 
+#[test]
+fn semantic_parses_rfc7986_calendar_properties() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+NAME:Team\r
+DESCRIPTION:Shared team calendar\r
+COLOR:coral\r
+REFRESH-INTERVAL;VALUE=DURATION:PT4H\r
+SOURCE;VALUE=URI:https://example.com/team.ics\r
+END:VCALENDAR\r
+";
+
+    let calendars = parse_semantic(src).unwrap();
+    let calendar = calendars[0].to_owned();
+    assert_eq!(calendar.name().as_deref(), Some("Team"));
+    assert_eq!(
+        calendar.description().as_deref(),
+        Some("Shared team calendar")
+    );
+    assert_eq!(
+        calendar.color.as_ref().map(|c| c.content.to_string()),
+        Some("coral".to_string())
+    );
+    assert_eq!(
+        calendar.refresh_interval.as_ref().map(|r| r.value),
+        Some(ValueDuration::DateTime {
+            positive: true,
+            day: 0,
+            hour: 4,
+            minute: 0,
+            second: 0,
+        })
+    );
+    assert_eq!(
+        calendar.source.as_ref().map(|s| s.uri.as_str()),
+        Some("https://example.com/team.ics")
+    );
+    assert!(calendar.retained_properties.is_empty());
+}
+
+#[test]
+fn semantic_falls_back_to_x_wr_calendar_properties() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Google Inc//Google Calendar 70.9054//EN\r
+X-WR-CALNAME:Holidays in Germany\r
+X-WR-CALDESC:Public holidays\\, Germany\r
+X-WR-TIMEZONE:Europe/Berlin\r
+END:VCALENDAR\r
+";
+
+    let calendars = parse_semantic(src).unwrap();
+    let calendar = calendars[0].to_owned();
+    assert_eq!(calendar.name().as_deref(), Some("Holidays in Germany"));
+    assert_eq!(
+        calendar.description().as_deref(),
+        Some("Public holidays, Germany")
+    );
+    assert_eq!(calendar.timezone().as_deref(), Some("Europe/Berlin"));
+}
+
+#[test]
+fn semantic_rejects_duplicate_color() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+COLOR:red\r
+COLOR:blue\r
+END:VCALENDAR\r
+";
+
+    let errors = parse_semantic(src).unwrap_err();
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, SemanticError::DuplicateProperty { .. })),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn semantic_parses_rfc7986_event_properties() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+BEGIN:VTODO\r
+UID:todo@example.com\r
+DTSTAMP:20250110T120000Z\r
+COLOR:coral\r
+IMAGE;VALUE=URI;DISPLAY=BADGE,THUMBNAIL;FMTTYPE=image/png:https://example.com/i.png\r
+CONFERENCE;VALUE=URI;FEATURE=PHONE,MODERATOR;LABEL=Dial-in;LANGUAGE=en:tel:+1-412-555-0123\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+    let calendars = parse_semantic(src).unwrap();
+    let CalendarComponent::Todo(todo) = &calendars[0].components[0] else {
+        panic!("Expected VTODO");
+    };
+    let todo = todo.to_owned();
+    assert_eq!(
+        todo.color.as_ref().map(|c| c.content.to_string()),
+        Some("coral".to_string())
+    );
+
+    let image = &todo.images[0];
+    assert_eq!(
+        image
+            .display
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["BADGE", "THUMBNAIL"]
+    );
+    assert_eq!(image.fmt_type.as_deref(), Some("image/png"));
+
+    let conference = &todo.conferences[0];
+    assert_eq!(conference.uri, "tel:+1-412-555-0123");
+    assert_eq!(
+        conference
+            .features
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["PHONE", "MODERATOR"]
+    );
+    assert_eq!(conference.label.as_deref(), Some("Dial-in"));
+    assert_eq!(conference.language.as_deref(), Some("en"));
+    assert!(todo.retained_properties.is_empty());
+}
+
+#[test]
+fn semantic_parses_vavailability_with_available_blocks() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+BEGIN:VAVAILABILITY\r
+UID:availability@example.com\r
+DTSTAMP:20250110T120000Z\r
+DTSTART;TZID=America/New_York:20250106T000000\r
+BUSYTYPE:BUSY\r
+PRIORITY:2\r
+SUMMARY:Working hours\r
+BEGIN:AVAILABLE\r
+UID:weekdays@example.com\r
+DTSTAMP:20250110T120000Z\r
+DTSTART;TZID=America/New_York:20250106T090000\r
+DTEND;TZID=America/New_York:20250106T170000\r
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+END:VCALENDAR\r
+";
+
+    let calendars = parse_semantic(src).unwrap();
+    let CalendarComponent::VAvailability(availability) = &calendars[0].components[0] else {
+        panic!("Expected VAVAILABILITY");
+    };
+    assert_eq!(availability.busy_type_value(), BusyTypeValue::Busy);
+    assert_eq!(availability.priority_rank(), 2);
+    assert!(availability.dt_end.is_none());
+    assert_eq!(availability.available.len(), 1);
+
+    let available = &availability.available[0];
+    assert!(available.rrule.is_some());
+    assert!(available.dt_end.is_some());
+    assert!(available.retained_properties.is_empty());
+}
+
+#[test]
+fn semantic_defaults_busytype_and_priority_of_vavailability() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+BEGIN:VAVAILABILITY\r
+UID:availability@example.com\r
+DTSTAMP:20250110T120000Z\r
+END:VAVAILABILITY\r
+END:VCALENDAR\r
+";
+
+    let calendars = parse_semantic(src).unwrap();
+    let CalendarComponent::VAvailability(availability) = &calendars[0].components[0] else {
+        panic!("Expected VAVAILABILITY");
+    };
+    assert_eq!(
+        availability.busy_type_value(),
+        BusyTypeValue::BusyUnavailable
+    );
+    // The undefined priority comes after the lowest one
+    assert_eq!(availability.priority_rank(), 10);
+}
+
+#[test]
+fn semantic_requires_dtstart_of_available() {
+    let src = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Test//Test//EN\r
+BEGIN:VAVAILABILITY\r
+UID:availability@example.com\r
+DTSTAMP:20250110T120000Z\r
+BEGIN:AVAILABLE\r
+UID:block@example.com\r
+DTSTAMP:20250110T120000Z\r
+DTEND:20250106T170000\r
+END:AVAILABLE\r
+END:VAVAILABILITY\r
+END:VCALENDAR\r
+";
+
+    let errors = parse_semantic(src).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::MissingProperty {
+            property: aimcal_ical::PropertyKind::DtStart,
+            ..
+        }
+    )));
+}
+
 #[test]
 fn semantic_parses_custom_x_component() {
     let src = "BEGIN:VCALENDAR\r
@@ -1291,7 +1514,14 @@ END:VCALENDAR\r
     match &calendar.components[1] {
         CalendarComponent::Event(event) => {
             assert_eq!(
-                event.dt_start.tz_id.as_ref().unwrap().to_string(),
+                event
+                    .dt_start
+                    .as_ref()
+                    .unwrap()
+                    .tz_id
+                    .as_ref()
+                    .unwrap()
+                    .to_string(),
                 "Custom-Timezone"
             );
         }
@@ -1339,7 +1569,14 @@ END:VCALENDAR\r
     match &calendar.components[0] {
         CalendarComponent::Event(event) => {
             assert_eq!(
-                event.dt_start.tz_id.as_ref().unwrap().to_string(),
+                event
+                    .dt_start
+                    .as_ref()
+                    .unwrap()
+                    .tz_id
+                    .as_ref()
+                    .unwrap()
+                    .to_string(),
                 "America/New_York"
             );
         }
@@ -1416,7 +1653,14 @@ END:VCALENDAR\r
     match &calendar.components[1] {
         CalendarComponent::Event(event) => {
             assert_eq!(
-                event.dt_start.tz_id.as_ref().unwrap().to_string(),
+                event
+                    .dt_start
+                    .as_ref()
+                    .unwrap()
+                    .tz_id
+                    .as_ref()
+                    .unwrap()
+                    .to_string(),
                 "Custom-Timezone"
             );
         }
@@ -1460,7 +1704,14 @@ END:VCALENDAR\r
     match &calendar.components[1] {
         CalendarComponent::Event(event) => {
             assert_eq!(
-                event.dt_start.tz_id.as_ref().unwrap().to_string(),
+                event
+                    .dt_start
+                    .as_ref()
+                    .unwrap()
+                    .tz_id
+                    .as_ref()
+                    .unwrap()
+                    .to_string(),
                 "Custom-Timezone"
             );
         }
@@ -1470,7 +1721,14 @@ END:VCALENDAR\r
     match &calendar.components[2] {
         CalendarComponent::Event(event) => {
             assert_eq!(
-                event.dt_start.tz_id.as_ref().unwrap().to_string(),
+                event
+                    .dt_start
+                    .as_ref()
+                    .unwrap()
+                    .tz_id
+                    .as_ref()
+                    .unwrap()
+                    .to_string(),
                 "America/Los_Angeles"
             );
         }
@@ -1509,3 +1767,213 @@ END:VCALENDAR\r
     let result = parse(src);
     assert!(result.is_err());
 }
+
+const TODO_WITH_REPEATED_PROPERTIES: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//CalDAV Client//EN\r
+BEGIN:VTODO\r
+UID:todo-1\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:Pack\r
+CATEGORIES:TRAVEL\r
+CATEGORIES:HOME\r
+RESOURCES:CAR\r
+RESOURCES:SUITCASE\r
+COMMENT:First comment\r
+COMMENT:Second comment\r
+ATTACH:https://example.com/list.pdf\r
+ATTACH:https://example.com/map.pdf\r
+CONTACT:Jim Dolittle\r
+CONTACT:Jane Doe\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+#[test]
+fn semantic_collects_repeatable_properties() {
+    let calendars = parse_semantic(TODO_WITH_REPEATED_PROPERTIES).unwrap();
+    let CalendarComponent::Todo(todo) = &calendars[0].components[0] else {
+        panic!("Expected VTODO");
+    };
+
+    let categories: Vec<_> = todo
+        .categories
+        .iter()
+        .flat_map(|c| c.values.iter().map(ToString::to_string))
+        .collect();
+    assert_eq!(categories, ["TRAVEL", "HOME"]);
+    assert_eq!(todo.resources.len(), 2);
+    let comments: Vec<_> = todo
+        .comments
+        .iter()
+        .map(|c| c.content.to_string())
+        .collect();
+    assert_eq!(comments, ["First comment", "Second comment"]);
+    assert_eq!(todo.attachments.len(), 2);
+    assert_eq!(todo.contacts.len(), 2);
+    assert!(todo.retained_properties.is_empty());
+}
+
+#[test]
+fn semantic_collects_repeated_journal_descriptions() {
+    let src = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//CalDAV Client//EN\r
+BEGIN:VJOURNAL\r
+UID:journal-1\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250101T000000Z\r
+DESCRIPTION:Morning\r
+DESCRIPTION:Evening\r
+COMMENT:Reviewed\r
+END:VJOURNAL\r
+END:VCALENDAR\r
+";
+    let calendars = parse_semantic(src).unwrap();
+    let CalendarComponent::VJournal(journal) = &calendars[0].components[0] else {
+        panic!("Expected VJOURNAL");
+    };
+    assert_eq!(journal.descriptions.len(), 2);
+    assert_eq!(journal.comments.len(), 1);
+}
+
+#[test]
+fn semantic_rejects_duplicate_singleton_with_both_spans() {
+    let src = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//CalDAV Client//EN\r
+BEGIN:VEVENT\r
+UID:event-1\r
+DTSTAMP:20250101T000000Z\r
+DTSTART:20250101T100000Z\r
+DESCRIPTION:First\r
+DESCRIPTION:Second\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let errors = parse_semantic(src).unwrap_err();
+    let [
+        SemanticError::DuplicateProperty {
+            property,
+            span,
+            first,
+        },
+    ] = errors.as_slice()
+    else {
+        panic!("Expected a single duplicate property error: {errors:?}");
+    };
+    assert_eq!(property.to_string(), "DESCRIPTION");
+    assert_eq!(Some(first.start), src.find("DESCRIPTION:First"));
+    assert_eq!(Some(span.start), src.find("DESCRIPTION:Second"));
+}
+
+#[test]
+fn semantic_keeps_first_duplicate_when_lenient() {
+    let src = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//CalDAV Client//EN\r
+BEGIN:VTODO\r
+UID:todo-1\r
+DTSTAMP:20250101T000000Z\r
+SUMMARY:First\r
+SUMMARY:Second\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+    assert!(parse(src).is_err());
+
+    let opts = ParseOptions::new().strict_cardinality(false);
+    let calendars = parse_with_options(src, opts).unwrap();
+    let CalendarComponent::Todo(todo) = &calendars[0].components[0] else {
+        panic!("Expected VTODO");
+    };
+    let summary = todo.summary.as_ref().unwrap();
+    assert_eq!(summary.content.to_string(), "First");
+
+    let (calendars, diagnostics) = parse_with_diagnostics_and_options(src, opts);
+    assert!(calendars.is_some());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "duplicate-property");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].related.len(), 1);
+}
+
+/// A recurring reminder as written by Apple Reminders, anchored on its DUE only.
+const TODO_WITHOUT_DTSTART: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Apple Inc.//iOS 17.2//EN\r
+BEGIN:VTODO\r
+CREATED:20240105T081512Z\r
+DTSTAMP:20240105T081530Z\r
+DUE;VALUE=DATE:20240108\r
+LAST-MODIFIED:20240105T081530Z\r
+RRULE:FREQ=WEEKLY;INTERVAL=1\r
+SEQUENCE:0\r
+STATUS:NEEDS-ACTION\r
+SUMMARY:Water the plants\r
+UID:8C1F4A6E-3D2B-4F7A-9E5C-1B2D3E4F5A6B\r
+X-APPLE-SORT-ORDER:726221730\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+/// An event whose date is still to be decided, as exported by a groupware server.
+const EVENT_WITHOUT_DTSTART: &str = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Open-Xchange//7.10.6-Rev52//EN\r
+BEGIN:VEVENT\r
+DTSTAMP:20240212T101500Z\r
+SUMMARY:Plan the offsite\r
+DESCRIPTION:Date to be decided\r
+RRULE:FREQ=MONTHLY\r
+CLASS:PUBLIC\r
+UID:3f2a9c1e-77b4-4d0e-9a51-6c2f0e8d4b13\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[test]
+fn semantic_warns_of_recurring_todo_without_dtstart() {
+    let calendars = parse(TODO_WITHOUT_DTSTART).unwrap();
+    let CalendarComponent::Todo(todo) = &calendars[0].components[0] else {
+        panic!("Expected VTODO");
+    };
+    assert!(todo.dt_start.is_none());
+    assert!(todo.due.is_some() && todo.rrule.is_some());
+
+    let opts = ParseOptions::new();
+    let (calendars, diagnostics) = parse_with_diagnostics_and_options(TODO_WITHOUT_DTSTART, opts);
+    assert!(calendars.is_some());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "unanchored");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].message.contains("DUE"));
+    assert_eq!(
+        Some(diagnostics[0].span.start),
+        TODO_WITHOUT_DTSTART.find("BEGIN:VTODO")
+    );
+}
+
+#[test]
+fn semantic_accepts_event_without_dtstart_as_unscheduled() {
+    let calendars = parse(EVENT_WITHOUT_DTSTART).unwrap();
+    let CalendarComponent::Event(event) = &calendars[0].components[0] else {
+        panic!("Expected VEVENT");
+    };
+    assert!(event.dt_start.is_none());
+    assert!(event.rrule.is_some());
+
+    let opts = ParseOptions::new();
+    let (calendars, diagnostics) = parse_with_diagnostics_and_options(EVENT_WITHOUT_DTSTART, opts);
+    assert!(calendars.is_some());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "unanchored");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].message.contains("unscheduled"));
+}
@@ -8,8 +8,12 @@
 //! and edge cases.
 
 use aimcal_ical::syntax::{
-    ParseOptions, RawComponent, syntax_analysis, syntax_analysis_with_options,
+    ContentLineError, ParseLimits, ParseOptions, RawComponent, SyntaxError, TreeBuildError,
+    syntax_analysis, syntax_analysis_with_options,
 };
+use aimcal_ical::{ParseError, parse, parse_with_options};
+
+const FIXTURE: &str = include_str!("../benches/fixtures/calendar.ics");
 
 /// Test helper to parse and get the first component
 fn parse_first_component(src: &str) -> RawComponent<'_> {
@@ -743,3 +747,234 @@ END:VCALENDAR\r
     );
     assert!(event.children.iter().any(|c| c.name.to_owned() == "VALARM"));
 }
+
+/// Test helper to parse with `limits` and get the errors
+fn limit_errors(src: &str, limits: ParseLimits) -> Vec<SyntaxError<'_>> {
+    syntax_analysis_with_options(src, ParseOptions::new().limits(limits)).unwrap_err()
+}
+
+#[test]
+fn limits_default_accept_fixture() {
+    assert!(parse(FIXTURE).is_ok());
+
+    // Repeat the events and todos, as in the parse benchmark
+    let (head, rest) = FIXTURE.split_once("END:VTIMEZONE\r\n").unwrap();
+    let body = rest.strip_suffix("END:VCALENDAR\r\n").unwrap();
+    let mut src = format!("{head}END:VTIMEZONE\r\n");
+    for i in 0..1000 {
+        src.push_str(&body.replace("@example.com\r\n", &format!("-{i}@example.com\r\n")));
+    }
+    src.push_str("END:VCALENDAR\r\n");
+    assert!(parse(&src).is_ok());
+
+    let opts = ParseOptions::new().limits(ParseLimits::unlimited());
+    assert!(parse_with_options(&src, opts).is_ok());
+}
+
+#[test]
+fn limits_max_properties_per_component() {
+    let src = "\
+BEGIN:VCALENDAR\r
+X-A:1\r
+X-B:2\r
+X-C:3\r
+END:VCALENDAR\r
+";
+    let limits = ParseLimits {
+        max_properties: 2,
+        ..ParseLimits::default()
+    };
+    let errors = limit_errors(src, limits);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::TreeBuilder(TreeBuildError::TooManyProperties { limit: 2, .. })
+    ));
+    assert_eq!(
+        errors[0].to_string(),
+        "VCALENDAR with more than 2 properties"
+    );
+
+    let limits = ParseLimits {
+        max_properties: 3,
+        ..ParseLimits::default()
+    };
+    assert!(syntax_analysis_with_options(src, ParseOptions::new().limits(limits)).is_ok());
+}
+
+#[test]
+fn limits_max_components_counts_nested() {
+    let src = "\
+BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+BEGIN:VALARM\r
+END:VALARM\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let limits = ParseLimits {
+        max_components: 2,
+        ..ParseLimits::default()
+    };
+    let errors = limit_errors(src, limits);
+    assert_eq!(errors.len(), 1, "no unmatched BEGIN errors: {errors:?}");
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::TreeBuilder(TreeBuildError::TooManyComponents { limit: 2, .. })
+    ));
+
+    // The limit is per calendar
+    let two_calendars = "\
+BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+END:VEVENT\r
+END:VCALENDAR\r
+BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+    let opts = ParseOptions::new().limits(limits);
+    assert_eq!(
+        syntax_analysis_with_options(two_calendars, opts)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn limits_max_depth() {
+    let mut src = String::new();
+    for _ in 0..100 {
+        src.push_str("BEGIN:X-NESTED\r\n");
+    }
+    for _ in 0..100 {
+        src.push_str("END:X-NESTED\r\n");
+    }
+    let errors = limit_errors(&src, ParseLimits::default());
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::TreeBuilder(TreeBuildError::NestingTooDeep { limit: 16, .. })
+    ));
+}
+
+#[test]
+fn limits_max_parameters_per_property() {
+    let src = "\
+BEGIN:VCALENDAR\r
+X-PROP;A=1;B=2;C=3:value\r
+END:VCALENDAR\r
+";
+    let limits = ParseLimits {
+        max_parameters: 2,
+        ..ParseLimits::default()
+    };
+    let errors = limit_errors(src, limits);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::Scanner(ContentLineError::TooManyParameters { limit: 2, .. })
+    ));
+}
+
+#[test]
+fn limits_max_line_length_counts_unfolded_line() {
+    let src = "\
+BEGIN:VCALENDAR\r
+SUMMARY:0123456789\r
+ 0123456789\r
+END:VCALENDAR\r
+";
+    // "SUMMARY:" and 20 digits
+    let limits = ParseLimits {
+        max_line_length: 27,
+        ..ParseLimits::default()
+    };
+    let errors = limit_errors(src, limits);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::Scanner(ContentLineError::LineTooLong { limit: 27, .. })
+    ));
+
+    let limits = ParseLimits {
+        max_line_length: 28,
+        ..ParseLimits::default()
+    };
+    let components = syntax_analysis_with_options(src, ParseOptions::new().limits(limits)).unwrap();
+    assert_eq!(components[0].properties[0].value.len(), 20);
+
+    // Parameters count towards the line
+    let src = "\
+BEGIN:VCALENDAR\r
+X-PROP;X-PARAM=\"0123456789\r
+ 0123456789\":value\r
+END:VCALENDAR\r
+";
+    let errors = limit_errors(src, limits);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::Scanner(ContentLineError::LineTooLong { limit: 28, .. })
+    ));
+}
+
+#[test]
+fn limits_max_values_per_list() {
+    let limits = ParseLimits {
+        max_values: 3,
+        ..ParseLimits::default()
+    };
+
+    let src = "\
+BEGIN:VCALENDAR\r
+CATEGORIES:A,B,C,D\r
+END:VCALENDAR\r
+";
+    let errors = limit_errors(src, limits);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::Scanner(ContentLineError::TooManyValues { limit: 3, .. })
+    ));
+
+    let src = "\
+BEGIN:VCALENDAR\r
+ATTENDEE;DELEGATED-TO=\"mailto:a\",\"mailto:b\",\"mailto:c\",\"mailto:d\":mailto:e\r
+END:VCALENDAR\r
+";
+    let errors = limit_errors(src, limits);
+    assert!(matches!(
+        &errors[0],
+        SyntaxError::Scanner(ContentLineError::TooManyValues { limit: 3, .. })
+    ));
+
+    // Escaped commas in text are not separators
+    let src = "\
+BEGIN:VCALENDAR\r
+DESCRIPTION:a\\, b\\, c\\, d\r
+END:VCALENDAR\r
+";
+    assert!(syntax_analysis_with_options(src, ParseOptions::new().limits(limits)).is_ok());
+}
+
+#[test]
+fn limits_are_reported_by_parse() {
+    let src = "\
+BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example Corp.//CalDAV Client//EN\r
+CATEGORIES:A,B\r
+END:VCALENDAR\r
+";
+    let limits = ParseLimits {
+        max_values: 1,
+        ..ParseLimits::default()
+    };
+    let errors = parse_with_options(src, ParseOptions::new().limits(limits)).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ParseError::Syntax(SyntaxError::Scanner(ContentLineError::TooManyValues { .. }))
+    ));
+    assert_eq!(errors[0].to_string(), "more than 1 values in a list");
+}
@@ -9,7 +9,8 @@
 
 use aimcal_ical::property::Property;
 use aimcal_ical::syntax::syntax_analysis;
-use aimcal_ical::typed::{TypedComponent, TypedError, typed_analysis};
+use aimcal_ical::typed::{TypedComponent, TypedError, typed_analysis, typed_analysis_with_limits};
+use aimcal_ical::{ParseLimits, ValueForm};
 
 /// Test helper to parse iCalendar source through typed phase
 fn parse_typed(src: &str) -> Result<Vec<TypedComponent<'_>>, Vec<TypedError<'_>>> {
@@ -640,6 +641,41 @@ END:VEVENT\r
     ));
 }
 
+#[test]
+fn typed_value_form_of_date_properties() {
+    let src = "\
+BEGIN:VTODO\r
+DTSTART;VALUE=DATE:20250615\r
+DUE:20250616T100000\r
+DTEND:20250616T100000Z\r
+DUE;TZID=America/New_York:20250616T100000\r
+COMPLETED:20250616T120000Z\r
+END:VTODO\r
+";
+    let components = parse_typed(src).unwrap();
+    let forms: Vec<_> = components[0]
+        .properties
+        .iter()
+        .map(|prop| match prop {
+            Property::DtStart(dt) => dt.value_form(),
+            Property::Due(dt) => dt.value_form(),
+            Property::DtEnd(dt) => dt.value_form(),
+            Property::Completed(dt) => dt.value_form(),
+            _ => panic!("unexpected property {}", prop.kind()),
+        })
+        .collect();
+    assert_eq!(
+        forms,
+        [
+            ValueForm::Date,
+            ValueForm::Floating,
+            ValueForm::Utc,
+            ValueForm::Zoned,
+            ValueForm::Utc,
+        ]
+    );
+}
+
 #[test]
 fn typed_value_parameter() {
     let src = "\
@@ -772,6 +808,93 @@ END:VEVENT\r
     assert!(matches!(&components[0].properties[0], Property::Attach(_)));
 }
 
+#[test]
+fn typed_attach_binary_decoded_len() {
+    let src = "\
+BEGIN:VEVENT\r
+ATTACH;ENCODING=BASE64;VALUE=BINARY:VGhpcyBpcyBub3QgYSB2\r
+ YWxpZCBVUkk=\r
+ATTACH:http://example.com/document.pdf\r
+END:VEVENT\r
+";
+    let components = parse_typed(src).unwrap();
+    let [Property::Attach(binary), Property::Attach(uri)] = &components[0].properties[..] else {
+        panic!("Expected two ATTACH properties");
+    };
+    // "This is not a valid URI"
+    assert_eq!(binary.value.decoded_len(), Some(23));
+    assert_eq!(binary.value.to_owned().decoded_len(), Some(23));
+    assert_eq!(uri.value.decoded_len(), None);
+}
+
+#[test]
+fn typed_attach_binary_rejects_invalid_base64() {
+    let cases = [
+        // value, reason, offending range in the value
+        ("VGhp*cyBp", "not a BASE64 character", 4..5),
+        ("VGg=cyBp", "data after padding", 4..5),
+        ("VGhpV===", "misplaced padding", 5..6),
+        ("VGh==", "too much padding", 4..5),
+        ("VGhpcyB", "length is not a multiple of 4", 4..7),
+    ];
+    for (value, reason, range) in cases {
+        let src = format!(
+            "BEGIN:VEVENT\r\nATTACH;ENCODING=BASE64;VALUE=BINARY:{value}\r\nEND:VEVENT\r\n"
+        );
+        let errors = parse_typed(&src).unwrap_err();
+        assert_eq!(errors.len(), 1, "{value}: {errors:?}");
+        let TypedError::BinaryInvalid {
+            reason: found,
+            span,
+        } = &errors[0]
+        else {
+            panic!(
+                "{value}: expected an invalid binary error, found {:?}",
+                errors[0]
+            );
+        };
+        assert_eq!(*found, reason, "{value}");
+
+        let start = src.find(value).unwrap();
+        assert_eq!(
+            span.start..span.end,
+            start + range.start..start + range.end,
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn typed_attach_binary_size_limit() {
+    let src = "\
+BEGIN:VEVENT\r
+ATTACH;ENCODING=BASE64;VALUE=BINARY:VGhpcyBpcyBub3QgYSB2YWxpZCBVUkk=\r
+END:VEVENT\r
+";
+    let parse_limited = |max_binary_size| {
+        let limits = ParseLimits {
+            max_binary_size,
+            ..ParseLimits::default()
+        };
+        typed_analysis_with_limits(syntax_analysis(src).unwrap(), limits)
+    };
+
+    let errors = parse_limited(22).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        TypedError::BinaryTooLarge {
+            size: 23,
+            limit: 22,
+            ..
+        }
+    ));
+    assert_eq!(
+        errors[0].to_string(),
+        "Inline binary value of 23 bytes exceeds the limit of 22 bytes"
+    );
+    assert!(parse_limited(23).is_ok());
+}
+
 #[test]
 fn typed_attach_uri_without_value_parameter() {
     // URI without VALUE parameter should parse as URI (default)